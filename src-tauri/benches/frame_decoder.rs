@@ -0,0 +1,79 @@
+//! network｜拆包吞吐基准：`FrameDecoder` 与旧实现（逐 chunk 拷贝 + `Vec::drain`）对比。
+//!
+//! 运行：`cargo bench --bench frame_decoder`。
+
+use std::hint::black_box;
+
+use carrypigeon_desktop_lib::features::network::data::frame_decoder::{DecodedFrame, FrameDecoder};
+use criterion::{Criterion, Throughput, criterion_group, criterion_main};
+
+/// 模拟大批量历史消息：50k 帧，payload 512..2560 字节。
+const FRAME_COUNT: u32 = 50_000;
+/// 模拟单次 socket 读取的 chunk 大小。
+const CHUNK_BYTES: usize = 16 * 1024;
+
+fn frame(payload: &[u8]) -> Vec<u8> {
+    let mut out = (payload.len() as u16).to_be_bytes().to_vec();
+    out.extend_from_slice(payload);
+    out
+}
+
+fn history_stream() -> Vec<u8> {
+    let mut stream = Vec::new();
+    for i in 0..FRAME_COUNT {
+        stream.extend_from_slice(&frame(&vec![(i % 251) as u8; 512 + (i % 2048) as usize]));
+    }
+    stream
+}
+
+/// 旧实现：每次读取拷贝 chunk，累积到 `Vec` 并逐帧 `drain`。
+fn legacy_decode(chunks: &[&[u8]]) -> usize {
+    let mut acc: Vec<u8> = Vec::new();
+    let mut frames = 0;
+    for chunk in chunks {
+        let chunk = chunk.to_vec();
+        let legacy_event = chunk.clone();
+        black_box(&legacy_event);
+        acc.extend_from_slice(&chunk);
+        while acc.len() >= 2 {
+            let len = u16::from_be_bytes([acc[0], acc[1]]) as usize;
+            if acc.len() < 2 + len {
+                break;
+            }
+            black_box(acc[2..2 + len].to_vec());
+            acc.drain(0..2 + len);
+            frames += 1;
+        }
+    }
+    frames
+}
+
+fn pooled_decode(chunks: &[&[u8]]) -> usize {
+    let mut decoder = FrameDecoder::new();
+    let mut frames = 0;
+    for chunk in chunks {
+        decoder.read_target().extend_from_slice(chunk);
+        while let Some(DecodedFrame::Frame(f)) = decoder.next_frame() {
+            black_box(Vec::from(f));
+            frames += 1;
+        }
+    }
+    frames
+}
+
+fn frame_decoder_throughput(c: &mut Criterion) {
+    let stream = history_stream();
+    let chunks: Vec<&[u8]> = stream.chunks(CHUNK_BYTES).collect();
+    assert_eq!(legacy_decode(&chunks), FRAME_COUNT as usize);
+    assert_eq!(pooled_decode(&chunks), FRAME_COUNT as usize);
+
+    let mut group = c.benchmark_group("frame_decoder");
+    group.throughput(Throughput::Bytes(stream.len() as u64));
+    group.sample_size(20);
+    group.bench_function("legacy", |b| b.iter(|| legacy_decode(black_box(&chunks))));
+    group.bench_function("pooled", |b| b.iter(|| pooled_decode(black_box(&chunks))));
+    group.finish();
+}
+
+criterion_group!(benches, frame_decoder_throughput);
+criterion_main!(benches);
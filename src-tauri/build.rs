@@ -1,3 +1,45 @@
-fn main() {
-    tauri_build::build()
+use std::io::Write;
+use std::path::Path;
+
+fn main() -> std::io::Result<()> {
+    // 构建脚本经标准输出向 cargo 传递指令；写入失败时中止构建，避免静默丢失指令。
+    let mut out = std::io::stdout().lock();
+    // 将构建时的 git 短哈希注入编译期环境变量，供 `get_runtime_info` 使用。
+    // 在非 git 工作区（例如源码包）中构建时留空，由运行时回退为 "unknown"。
+    if let Some(hash) = std::process::Command::new("git")
+        .args(["rev-parse", "--short", "HEAD"])
+        .output()
+        .ok()
+        .filter(|output| output.status.success())
+        .and_then(|output| String::from_utf8(output.stdout).ok())
+    {
+        writeln!(out, "cargo:rustc-env=CARRYPIGEON_GIT_HASH={}", hash.trim())?;
+    }
+    for path in git_rerun_paths(Path::new("../.git")) {
+        writeln!(out, "cargo:rerun-if-changed={path}")?;
+    }
+    out.flush()?;
+    drop(out);
+    tauri_build::build();
+    Ok(())
+}
+
+/// 提交变化时需要重新运行构建脚本的文件：
+/// `HEAD`（切换分支）、其指向的分支 ref 文件（分支上提交）与 `packed-refs`（ref 被打包后）。
+/// 只返回存在的文件：cargo 会把不存在的路径视为每次都已变化。
+fn git_rerun_paths(git_dir: &Path) -> Vec<String> {
+    let head = git_dir.join("HEAD");
+    let mut paths = vec![head.clone()];
+    if let Some(reference) = std::fs::read_to_string(&head)
+        .ok()
+        .and_then(|raw| raw.strip_prefix("ref:").map(|r| r.trim().to_string()))
+    {
+        paths.push(git_dir.join(reference));
+    }
+    paths.push(git_dir.join("packed-refs"));
+    paths
+        .into_iter()
+        .filter(|path| path.is_file())
+        .map(|path| path.display().to_string())
+        .collect()
 }
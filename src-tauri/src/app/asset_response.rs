@@ -0,0 +1,345 @@
+//! app｜`app://` 静态资源响应：条件请求、Range 与热点小文件缓存。
+//!
+//! 说明：
+//! - 按文件元数据生成 `ETag`（长度 + 修改时间）与 `Last-Modified`，命中
+//!   `If-None-Match` / `If-Modified-Since` 时返回 304；
+//! - 支持单段 `Range`（媒体拖动进度），只读取所需区间；`If-Range` 不匹配时返回完整内容；
+//!   多段 Range 按完整内容处理；
+//! - 不超过 [`MAX_CACHED_FILE_BYTES`] 的文件（插件 JS 入口、图标等）缓存在内存中，
+//!   以元数据校验是否过期，总量超过 [`MAX_CACHE_BYTES`] 时淘汰最久未使用的条目。
+//! - 所有文件响应带 `X-Content-Type-Options: nosniff`，WebView 不会把 `application/octet-stream`
+//!   等类型嗅探成脚本或文档。
+//!
+//! 约定：注释中文，日志英文（tracing）。
+
+use std::collections::VecDeque;
+use std::io::{Read, Seek, SeekFrom};
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use std::time::UNIX_EPOCH;
+
+use anyhow::Context;
+use tauri::http::{HeaderMap, Response, header};
+
+/// 可进入内存缓存的单文件大小上限。
+pub const MAX_CACHED_FILE_BYTES: u64 = 256 * 1024;
+/// 内存缓存总量上限。
+pub const MAX_CACHE_BYTES: usize = 8 * 1024 * 1024;
+
+/// HTTP-date 格式（RFC 7231 IMF-fixdate）。
+const HTTP_DATE_FORMAT: &str = "%a, %d %b %Y %H:%M:%S GMT";
+
+/// `Range` 请求头的解析结果。
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RangeRequest {
+    /// 无 Range、格式无法识别或多段 Range：返回完整内容。
+    Full,
+    /// 闭区间 `[start, end]`。
+    Partial(u64, u64),
+    /// 区间超出文件范围：返回 416。
+    Unsatisfiable,
+}
+
+/// 解析 `Range: bytes=...`（仅支持单段）。
+pub fn parse_range(value: &str, len: u64) -> RangeRequest {
+    let Some(spec) = value.trim().strip_prefix("bytes=") else {
+        return RangeRequest::Full;
+    };
+    if spec.contains(',') {
+        return RangeRequest::Full;
+    }
+    let Some((start, end)) = spec.trim().split_once('-') else {
+        return RangeRequest::Full;
+    };
+    let (start, end) = (start.trim(), end.trim());
+    let parsed = match (start.is_empty(), end.is_empty()) {
+        // `bytes=-N`：最后 N 个字节。
+        (true, false) => end
+            .parse::<u64>()
+            .ok()
+            .map(|n| (len.saturating_sub(n), len.saturating_sub(1), n > 0)),
+        // `bytes=N-`：从 N 到结尾。
+        (false, true) => start
+            .parse::<u64>()
+            .ok()
+            .map(|s| (s, len.saturating_sub(1), true)),
+        (false, false) => match (start.parse::<u64>(), end.parse::<u64>()) {
+            (Ok(s), Ok(e)) if s <= e => Some((s, e.min(len.saturating_sub(1)), true)),
+            _ => None,
+        },
+        (true, true) => None,
+    };
+    match parsed {
+        None => RangeRequest::Full,
+        Some((start, end, non_empty)) if non_empty && len > 0 && start < len && start <= end => {
+            RangeRequest::Partial(start, end)
+        }
+        Some(_) => RangeRequest::Unsatisfiable,
+    }
+}
+
+/// `If-None-Match` 是否命中（支持 `*`、列表与弱校验前缀 `W/`）。
+pub fn etag_matches(if_none_match: &str, etag: &str) -> bool {
+    let strip = |tag: &str| tag.trim().trim_start_matches("W/").to_string();
+    let etag = strip(etag);
+    if_none_match
+        .split(',')
+        .any(|candidate| candidate.trim() == "*" || strip(candidate) == etag)
+}
+
+fn http_date(secs: i64) -> Option<String> {
+    chrono::DateTime::from_timestamp(secs, 0).map(|t| t.format(HTTP_DATE_FORMAT).to_string())
+}
+
+fn parse_http_date(value: &str) -> Option<i64> {
+    chrono::NaiveDateTime::parse_from_str(value.trim(), HTTP_DATE_FORMAT)
+        .ok()
+        .map(|t| t.and_utc().timestamp())
+}
+
+/// 文件校验信息。
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Validators {
+    len: u64,
+    modified_nanos: u128,
+    etag: String,
+    modified_secs: i64,
+}
+
+impl Validators {
+    fn from_metadata(metadata: &std::fs::Metadata) -> Self {
+        let modified = metadata
+            .modified()
+            .ok()
+            .and_then(|t| t.duration_since(UNIX_EPOCH).ok())
+            .unwrap_or_default();
+        let len = metadata.len();
+        Self {
+            len,
+            modified_nanos: modified.as_nanos(),
+            etag: format!("\"{len:x}-{:x}\"", modified.as_nanos()),
+            modified_secs: modified.as_secs() as i64,
+        }
+    }
+
+    /// 条件请求是否命中（`If-None-Match` 优先于 `If-Modified-Since`）。
+    fn not_modified(&self, headers: &HeaderMap) -> bool {
+        if let Some(value) = header_str(headers, header::IF_NONE_MATCH) {
+            return etag_matches(value, &self.etag);
+        }
+        header_str(headers, header::IF_MODIFIED_SINCE)
+            .and_then(parse_http_date)
+            .is_some_and(|since| self.modified_secs <= since)
+    }
+
+    /// `If-Range` 是否允许返回部分内容（无该请求头时允许）。
+    fn range_allowed(&self, headers: &HeaderMap) -> bool {
+        match header_str(headers, header::IF_RANGE) {
+            None => true,
+            Some(value) if value.trim().starts_with('"') => value.trim() == self.etag,
+            Some(value) => parse_http_date(value) == Some(self.modified_secs),
+        }
+    }
+}
+
+fn header_str(headers: &HeaderMap, name: header::HeaderName) -> Option<&str> {
+    headers.get(name).and_then(|v| v.to_str().ok())
+}
+
+/// 按路径缓存的小文件内容（最近使用的在队尾）。
+#[derive(Debug)]
+struct AssetCache {
+    entries: VecDeque<(PathBuf, u128, Arc<[u8]>)>,
+    total: usize,
+}
+
+impl AssetCache {
+    const fn new() -> Self {
+        Self {
+            entries: VecDeque::new(),
+            total: 0,
+        }
+    }
+
+    fn get(&mut self, path: &Path, modified_nanos: u128, len: u64) -> Option<Arc<[u8]>> {
+        let index = self.entries.iter().position(|(p, _, _)| p == path)?;
+        let entry = self.entries.remove(index)?;
+        if entry.1 != modified_nanos || entry.2.len() as u64 != len {
+            self.total -= entry.2.len();
+            return None;
+        }
+        let bytes = entry.2.clone();
+        self.entries.push_back(entry);
+        Some(bytes)
+    }
+
+    fn insert(&mut self, path: PathBuf, modified_nanos: u128, bytes: Arc<[u8]>) {
+        if let Some(index) = self.entries.iter().position(|(p, _, _)| *p == path)
+            && let Some(old) = self.entries.remove(index)
+        {
+            self.total -= old.2.len();
+        }
+        self.total += bytes.len();
+        self.entries.push_back((path, modified_nanos, bytes));
+        while self.total > MAX_CACHE_BYTES {
+            let Some(evicted) = self.entries.pop_front() else {
+                break;
+            };
+            self.total -= evicted.2.len();
+        }
+    }
+}
+
+static CACHE: Mutex<AssetCache> = Mutex::new(AssetCache::new());
+
+fn read_full(path: &Path, validators: &Validators) -> anyhow::Result<Arc<[u8]>> {
+    let cacheable = validators.len <= MAX_CACHED_FILE_BYTES;
+    if cacheable {
+        let mut cache = CACHE.lock().unwrap_or_else(|p| p.into_inner());
+        if let Some(bytes) = cache.get(path, validators.modified_nanos, validators.len) {
+            return Ok(bytes);
+        }
+    }
+    let bytes: Arc<[u8]> = std::fs::read(path)
+        .with_context(|| format!("Failed to read asset: {}", path.display()))?
+        .into();
+    if cacheable && bytes.len() as u64 == validators.len {
+        CACHE.lock().unwrap_or_else(|p| p.into_inner()).insert(
+            path.to_path_buf(),
+            validators.modified_nanos,
+            bytes.clone(),
+        );
+    }
+    Ok(bytes)
+}
+
+fn read_range(path: &Path, start: u64, end: u64) -> anyhow::Result<Vec<u8>> {
+    let mut file = std::fs::File::open(path)
+        .with_context(|| format!("Failed to open asset: {}", path.display()))?;
+    file.seek(SeekFrom::Start(start))?;
+    let mut bytes = Vec::with_capacity((end - start + 1) as usize);
+    file.take(end - start + 1).read_to_end(&mut bytes)?;
+    Ok(bytes)
+}
+
+/// 为本地文件构建响应（200/206/304/416）。
+///
+/// # 参数
+/// - `path`: 已通过校验的本地文件路径。
+/// - `content_type`: 响应的 Content-Type。
+/// - `headers`: 请求头（`Range` / `If-None-Match` / `If-Modified-Since` / `If-Range`）。
+pub fn respond_with_file(
+    path: &Path,
+    content_type: &str,
+    headers: &HeaderMap,
+) -> anyhow::Result<Response<Vec<u8>>> {
+    let metadata = std::fs::metadata(path)
+        .with_context(|| format!("Failed to stat asset: {}", path.display()))?;
+    let validators = Validators::from_metadata(&metadata);
+
+    let mut builder = Response::builder()
+        .header(header::ETAG, validators.etag.as_str())
+        .header(header::ACCEPT_RANGES, "bytes")
+        .header(header::X_CONTENT_TYPE_OPTIONS, "nosniff");
+    if let Some(date) = http_date(validators.modified_secs) {
+        builder = builder.header(header::LAST_MODIFIED, date);
+    }
+
+    if validators.not_modified(headers) {
+        return Ok(builder.status(304).body(Vec::new())?);
+    }
+
+    let range = match header_str(headers, header::RANGE) {
+        Some(value) if validators.range_allowed(headers) => parse_range(value, validators.len),
+        _ => RangeRequest::Full,
+    };
+    let builder = builder.header(header::CONTENT_TYPE, content_type);
+    match range {
+        RangeRequest::Full => {
+            let bytes = read_full(path, &validators)?;
+            Ok(builder.status(200).body(bytes.to_vec())?)
+        }
+        RangeRequest::Partial(start, end) => {
+            let bytes = read_range(path, start, end)?;
+            Ok(builder
+                .status(206)
+                .header(
+                    header::CONTENT_RANGE,
+                    format!("bytes {start}-{end}/{}", validators.len),
+                )
+                .body(bytes)?)
+        }
+        RangeRequest::Unsatisfiable => Ok(builder
+            .status(416)
+            .header(header::CONTENT_RANGE, format!("bytes */{}", validators.len))
+            .body(Vec::new())?),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_single_byte_ranges() {
+        assert_eq!(
+            parse_range("bytes=0-99", 1000),
+            RangeRequest::Partial(0, 99)
+        );
+        assert_eq!(
+            parse_range("bytes=900-", 1000),
+            RangeRequest::Partial(900, 999)
+        );
+        assert_eq!(
+            parse_range("bytes=-100", 1000),
+            RangeRequest::Partial(900, 999)
+        );
+        assert_eq!(
+            parse_range("bytes=-5000", 1000),
+            RangeRequest::Partial(0, 999)
+        );
+        assert_eq!(
+            parse_range("bytes=500-5000", 1000),
+            RangeRequest::Partial(500, 999)
+        );
+        assert_eq!(
+            parse_range("bytes=1000-", 1000),
+            RangeRequest::Unsatisfiable
+        );
+        assert_eq!(parse_range("bytes=-0", 1000), RangeRequest::Unsatisfiable);
+        assert_eq!(parse_range("bytes=0-", 0), RangeRequest::Unsatisfiable);
+        assert_eq!(parse_range("bytes=0-1,5-6", 1000), RangeRequest::Full);
+        assert_eq!(parse_range("bytes=9-1", 1000), RangeRequest::Full);
+        assert_eq!(parse_range("items=0-1", 1000), RangeRequest::Full);
+    }
+
+    #[test]
+    fn etags_match_lists_wildcards_and_weak_tags() {
+        assert!(etag_matches("\"a-1\"", "\"a-1\""));
+        assert!(etag_matches("\"x\", W/\"a-1\"", "\"a-1\""));
+        assert!(etag_matches("*", "\"a-1\""));
+        assert!(!etag_matches("\"a-2\"", "\"a-1\""));
+    }
+
+    #[test]
+    fn http_dates_round_trip() {
+        let date = http_date(784111777).unwrap();
+        assert_eq!(date, "Sun, 06 Nov 1994 08:49:37 GMT");
+        assert_eq!(parse_http_date(&date), Some(784111777));
+        assert_eq!(parse_http_date("yesterday"), None);
+    }
+
+    #[test]
+    fn cache_invalidates_on_change_and_evicts_oldest() {
+        let mut cache = AssetCache::new();
+        let chunk: Arc<[u8]> = vec![0u8; MAX_CACHE_BYTES / 2].into();
+        cache.insert(PathBuf::from("a"), 1, chunk.clone());
+        cache.insert(PathBuf::from("b"), 1, chunk.clone());
+        assert!(cache.get(Path::new("a"), 1, chunk.len() as u64).is_some());
+        // "b" 最久未使用，插入 "c" 后被淘汰。
+        cache.insert(PathBuf::from("c"), 1, vec![0u8; 1].into());
+        assert!(cache.get(Path::new("b"), 1, chunk.len() as u64).is_none());
+        assert!(cache.get(Path::new("a"), 2, chunk.len() as u64).is_none());
+        assert_eq!(cache.total, 1);
+    }
+}
@@ -0,0 +1,453 @@
+//! 命令行入口：供自动化与脚本调用的子命令。
+//!
+//! 说明：
+//! - `carrypigeon send --server X --channel Y --message "..."`：用系统凭据存储中保存的会话令牌
+//!   经 HTTP `/api` 发送一条文本消息（401 时按 refresh token 刷新后重试一次）；
+//! - `carrypigeon export-logs [--output FILE]`：把日志目录打包为 zip；
+//! - `carrypigeon doctor [--json]`：打开已有的应用数据库（不执行迁移）并运行完整性自检，
+//!   有问题时以退出码 1 结束；
+//! - 子命令均以无界面方式直接执行底层 usecase，不启动 Tauri、不经过单实例转交，
+//!   因此与正在运行的实例互不干扰；
+//! - 无子命令时按 `--hidden` / `--minimized` 启动界面（见 [`super::launch_mode`]）；
+//!   无法识别的参数不阻止界面启动（系统可能附加平台参数）。
+//!
+//! 约定：注释中文，日志英文（tracing）；命令行输出英文。
+
+use std::fmt;
+use std::io::{Stderr, Stdout, Write};
+use std::path::{Path, PathBuf};
+
+use anyhow::Context;
+use clap::{Parser, Subcommand};
+use zip::write::SimpleFileOptions;
+
+use super::launch_mode::LaunchMode;
+use crate::features::network::data::http_client::ReqwestApiRequestAdapter;
+use crate::features::network::data::session_token_store::KeyringSessionTokenStore;
+use crate::features::network::usecases::api_usecases::ApiJsonRequest;
+use crate::features::network::usecases::session_usecases;
+
+/// 应用标识（与 `tauri.conf.json` 的 `identifier` 一致，用于无界面时定位数据目录）。
+const APP_IDENTIFIER: &str = "com.carrypigeon-desktop.app";
+
+/// 命令行参数。
+#[derive(Debug, Parser)]
+#[command(name = "carrypigeon", version, about = "CarryPigeon Desktop")]
+pub struct Cli {
+    /// 仅托盘启动。
+    #[arg(long, help = "Start hidden in the tray")]
+    pub hidden: bool,
+    /// 最小化启动。
+    #[arg(long, help = "Start with the main window minimized")]
+    pub minimized: bool,
+    #[command(subcommand)]
+    pub command: Option<CliCommand>,
+}
+
+/// 子命令。
+#[derive(Debug, Clone, PartialEq, Eq, Subcommand)]
+pub enum CliCommand {
+    /// 发送一条文本消息。
+    #[command(about = "Send a text message to a channel")]
+    Send {
+        #[arg(long, help = "Server socket, e.g. tls://chat.example.com:443")]
+        server: String,
+        #[arg(long, help = "Channel id")]
+        channel: String,
+        #[arg(long, help = "Message text")]
+        message: String,
+        #[arg(long, help = "TLS policy (strict / insecure / trust_fingerprint)")]
+        tls_policy: Option<String>,
+        #[arg(long, help = "Pinned certificate SHA-256 fingerprint")]
+        tls_fingerprint: Option<String>,
+    },
+    /// 导出日志。
+    #[command(about = "Export application logs as a zip archive")]
+    ExportLogs {
+        #[arg(
+            long,
+            short,
+            help = "Output file (default: ./carrypigeon-logs-<timestamp>.zip)"
+        )]
+        output: Option<PathBuf>,
+    },
+    /// 完整性自检。
+    #[command(about = "Run the data integrity self-check")]
+    Doctor {
+        #[arg(long, help = "Print the report as JSON")]
+        json: bool,
+    },
+}
+
+/// 命令行解析结果。
+#[derive(Debug)]
+pub enum Invocation {
+    /// 启动界面。
+    Gui(LaunchMode),
+    /// 无界面执行子命令。
+    Command(CliCommand),
+    /// 参数错误或 `--help` / `--version`：打印后退出。
+    Exit(clap::Error),
+}
+
+impl Invocation {
+    /// 解析命令行参数（首项为可执行文件路径）。
+    pub fn from_args<I, S>(args: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        let args: Vec<String> = args.into_iter().map(Into::into).collect();
+        match Cli::try_parse_from(&args) {
+            Ok(Cli {
+                command: Some(command),
+                ..
+            }) => Self::Command(command),
+            Ok(_) => Self::Gui(LaunchMode::from_args(args.iter().skip(1))),
+            Err(err) if is_cli_request(&args) => Self::Exit(err),
+            // 无法识别的平台参数：照常启动界面。
+            Err(_) => Self::Gui(LaunchMode::from_args(args.iter().skip(1))),
+        }
+    }
+}
+
+/// 参数中是否明确请求了命令行功能（子命令或帮助/版本）。
+fn is_cli_request(args: &[String]) -> bool {
+    args.iter().skip(1).any(|arg| {
+        matches!(
+            arg.as_str(),
+            "send" | "export-logs" | "doctor" | "help" | "--help" | "-h" | "--version" | "-V"
+        )
+    })
+}
+
+/// Windows release 构建为 GUI 子系统：子命令输出前附加到父进程控制台。
+#[cfg(windows)]
+fn attach_parent_console() {
+    use windows::Win32::System::Console::{ATTACH_PARENT_PROCESS, AttachConsole};
+    // 从资源管理器启动时没有父控制台，附加失败可忽略。
+    let _ = unsafe { AttachConsole(ATTACH_PARENT_PROCESS) };
+}
+
+#[cfg(not(windows))]
+fn attach_parent_console() {}
+
+/// 无界面时初始化应用数据目录（与 Tauri `app_data_dir` 规则一致，并遵循数据目录指针/便携模式）。
+fn init_headless_data_dir() -> anyhow::Result<PathBuf> {
+    let default_dir = dirs::data_dir()
+        .context("Failed to resolve the platform data directory")?
+        .join(APP_IDENTIFIER);
+    let app_data_dir = crate::shared::paths::resolve_data_dir(&default_dir);
+    crate::shared::app_data_dir::init_app_data_dir(app_data_dir.clone())?;
+    Ok(app_data_dir)
+}
+
+/// 命令行输出：每行持锁写入标准输出/标准错误，避免与日志输出交错；
+/// 写入失败（如管道已关闭）时忽略，不影响退出码。
+struct CliOutput {
+    stdout: Stdout,
+    stderr: Stderr,
+}
+
+impl CliOutput {
+    fn new() -> Self {
+        Self {
+            stdout: std::io::stdout(),
+            stderr: std::io::stderr(),
+        }
+    }
+
+    /// 向标准输出写一行。
+    fn line(&self, args: fmt::Arguments<'_>) {
+        let _ = writeln!(self.stdout.lock(), "{args}");
+    }
+
+    /// 向标准错误写一行。
+    fn error(&self, args: fmt::Arguments<'_>) {
+        let _ = writeln!(self.stderr.lock(), "{args}");
+    }
+}
+
+/// 执行子命令并返回进程退出码。
+pub async fn run_command(command: CliCommand) -> i32 {
+    attach_parent_console();
+    let out = CliOutput::new();
+    let result = match init_headless_data_dir() {
+        Ok(app_data_dir) => match command {
+            CliCommand::Send {
+                server,
+                channel,
+                message,
+                tls_policy,
+                tls_fingerprint,
+            } => {
+                send(
+                    &out,
+                    &server,
+                    &channel,
+                    &message,
+                    tls_policy,
+                    tls_fingerprint,
+                )
+                .await
+            }
+            CliCommand::ExportLogs { output } => export_logs(&out, &app_data_dir, output),
+            CliCommand::Doctor { json } => doctor(&out, &app_data_dir, json).await,
+        },
+        Err(e) => Err(e),
+    };
+    match result {
+        Ok(code) => code,
+        Err(e) => {
+            out.error(format_args!("error: {e:#}"));
+            2
+        }
+    }
+}
+
+/// 对路径段做百分号编码（保留 RFC 3986 unreserved 字符）。
+fn encode_path_segment(value: &str) -> String {
+    let mut out = String::with_capacity(value.len());
+    for byte in value.bytes() {
+        if byte.is_ascii_alphanumeric() || matches!(byte, b'-' | b'_' | b'.' | b'~') {
+            out.push(byte as char);
+        } else {
+            out.push_str(&format!("%{byte:02X}"));
+        }
+    }
+    out
+}
+
+/// 构造文本消息的发送请求。
+fn send_request(
+    server: &str,
+    channel: &str,
+    message: &str,
+    tls_policy: Option<String>,
+    tls_fingerprint: Option<String>,
+) -> anyhow::Result<ApiJsonRequest> {
+    let server = server.trim();
+    let channel = channel.trim();
+    if server.is_empty() || channel.is_empty() {
+        anyhow::bail!("--server and --channel must not be empty");
+    }
+    if message.trim().is_empty() {
+        anyhow::bail!("--message must not be empty");
+    }
+    let client_message_id = uuid::Uuid::new_v4().to_string();
+    Ok(ApiJsonRequest {
+        server_socket: server.to_string(),
+        method: "POST".to_string(),
+        path: format!("/api/channels/{}/messages", encode_path_segment(channel)),
+        headers: Some(
+            [("Idempotency-Key".to_string(), client_message_id.clone())]
+                .into_iter()
+                .collect(),
+        ),
+        body: Some(serde_json::json!({
+            "domain": "Core:Text",
+            "domain_version": "1",
+            "data": { "text": message },
+            "client_message_id": client_message_id,
+        })),
+        tls_policy,
+        tls_fingerprint,
+    })
+}
+
+async fn send(
+    out: &CliOutput,
+    server: &str,
+    channel: &str,
+    message: &str,
+    tls_policy: Option<String>,
+    tls_fingerprint: Option<String>,
+) -> anyhow::Result<i32> {
+    let request = send_request(server, channel, message, tls_policy, tls_fingerprint)?;
+    let result = session_usecases::api_request_json_with_session(
+        request,
+        ReqwestApiRequestAdapter::shared().as_ref(),
+        KeyringSessionTokenStore::shared().as_ref(),
+    )
+    .await?;
+    if result.session_expired {
+        anyhow::bail!(
+            "Session expired for {}; sign in again in the app",
+            server.trim()
+        );
+    }
+    let response = result.response;
+    if !response.ok {
+        anyhow::bail!(
+            "Server rejected the message (HTTP {}): {}",
+            response.status,
+            response.error.map(|e| e.to_string()).unwrap_or_default()
+        );
+    }
+    let mid = response
+        .body
+        .as_ref()
+        .and_then(|body| body.get("mid"))
+        .map(|mid| match mid {
+            serde_json::Value::String(mid) => mid.clone(),
+            other => other.to_string(),
+        })
+        .unwrap_or_default();
+    tracing::info!(action = "app_cli_message_sent", server_socket = %server.trim(), mid = %mid);
+    out.line(format_args!("sent {mid}"));
+    Ok(0)
+}
+
+fn export_logs(
+    out: &CliOutput,
+    app_data_dir: &Path,
+    output: Option<PathBuf>,
+) -> anyhow::Result<i32> {
+    let log_dir = app_data_dir.join("logs");
+    let mut files: Vec<PathBuf> = std::fs::read_dir(&log_dir)
+        .with_context(|| format!("No log directory at {}", log_dir.display()))?
+        .filter_map(|entry| entry.ok().map(|e| e.path()))
+        .filter(|path| path.is_file())
+        .collect();
+    files.sort();
+    let output = output.unwrap_or_else(|| {
+        PathBuf::from(format!(
+            "carrypigeon-logs-{}.zip",
+            chrono::Local::now().format("%Y%m%d-%H%M%S")
+        ))
+    });
+    let file = std::fs::File::create(&output)
+        .with_context(|| format!("Failed to create {}", output.display()))?;
+    let mut zip = zip::ZipWriter::new(file);
+    for path in &files {
+        let Some(name) = path.file_name().and_then(|n| n.to_str()) else {
+            continue;
+        };
+        let raw =
+            std::fs::read(path).with_context(|| format!("Failed to read {}", path.display()))?;
+        zip.start_file(name, SimpleFileOptions::default())
+            .with_context(|| format!("Failed to add {name}"))?;
+        zip.write_all(&raw)
+            .with_context(|| format!("Failed to write {name}"))?;
+    }
+    zip.finish().context("Failed to finish log archive")?;
+    out.line(format_args!(
+        "exported {} log file(s) to {}",
+        files.len(),
+        output.display()
+    ));
+    Ok(0)
+}
+
+/// 打开数据目录中已有的应用管理数据库（`system` 与 `server_*`），供自检使用。
+async fn open_existing_databases(app_data_dir: &Path) -> usize {
+    let Ok(entries) = std::fs::read_dir(app_data_dir.join("db")) else {
+        return 0;
+    };
+    let mut opened = 0;
+    for path in entries.filter_map(|entry| entry.ok().map(|e| e.path())) {
+        if path.extension().and_then(|e| e.to_str()) != Some("db") {
+            continue;
+        }
+        let Some(key) = path
+            .file_stem()
+            .and_then(|s| s.to_str())
+            .map(str::to_string)
+        else {
+            continue;
+        };
+        if key != "system" && !key.starts_with("server_") {
+            continue;
+        }
+        match crate::shared::db::connect_named(&key, path.clone()).await {
+            Ok(()) => opened += 1,
+            Err(e) => {
+                tracing::warn!(action = "app_cli_doctor_db_open_failed", key = %key, error = %e)
+            }
+        }
+    }
+    opened
+}
+
+async fn doctor(out: &CliOutput, app_data_dir: &Path, json: bool) -> anyhow::Result<i32> {
+    open_existing_databases(app_data_dir).await;
+    let report = super::self_check::run_self_check()
+        .await
+        .map_err(|e| anyhow::anyhow!("{}", e.message))?;
+    if json {
+        out.line(format_args!("{}", serde_json::to_string_pretty(&report)?));
+    } else {
+        out.line(format_args!("data directory: {}", app_data_dir.display()));
+        out.line(format_args!(
+            "checked {} database(s), {} plugin version(s)",
+            report.databases_checked, report.plugin_versions_checked
+        ));
+        for issue in &report.issues {
+            out.line(format_args!(
+                "[{:?}] {:?} {}: {}",
+                issue.severity, issue.category, issue.target, issue.message
+            ));
+        }
+        out.line(format_args!(
+            "{}",
+            if report.ok { "ok" } else { "problems found" }
+        ));
+    }
+    Ok(if report.ok { 0 } else { 1 })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn subcommands_parse_and_plain_launch_falls_back_to_gui() {
+        let invocation = Invocation::from_args([
+            "carrypigeon",
+            "send",
+            "--server",
+            "tls://a:1",
+            "--channel",
+            "42",
+            "--message",
+            "hi there",
+        ]);
+        assert!(matches!(
+            invocation,
+            Invocation::Command(CliCommand::Send { ref channel, ref message, .. })
+                if channel == "42" && message == "hi there"
+        ));
+        assert!(matches!(
+            Invocation::from_args(["carrypigeon", "doctor", "--json"]),
+            Invocation::Command(CliCommand::Doctor { json: true })
+        ));
+        assert!(matches!(
+            Invocation::from_args(["carrypigeon", "--hidden"]),
+            Invocation::Gui(LaunchMode::Hidden)
+        ));
+        // 未知平台参数不阻止界面启动；子命令参数错误则报错退出。
+        assert!(matches!(
+            Invocation::from_args(["carrypigeon", "-psn_0_12345"]),
+            Invocation::Gui(LaunchMode::Foreground)
+        ));
+        assert!(matches!(
+            Invocation::from_args(["carrypigeon", "send", "--server", "x"]),
+            Invocation::Exit(_)
+        ));
+    }
+
+    #[test]
+    fn send_request_targets_channel_messages_with_idempotency_key() {
+        let request = send_request(" tls://a:1 ", "room 1", "hello", None, None).unwrap();
+        assert_eq!(request.path, "/api/channels/room%201/messages");
+        assert_eq!(request.server_socket, "tls://a:1");
+        let body = request.body.unwrap();
+        assert_eq!(body["domain"], "Core:Text");
+        assert_eq!(body["data"]["text"], "hello");
+        assert_eq!(
+            request.headers.unwrap()["Idempotency-Key"],
+            body["client_message_id"].as_str().unwrap()
+        );
+        assert!(send_request("tls://a:1", "1", "  ", None, None).is_err());
+    }
+}
@@ -0,0 +1,155 @@
+//! 深链接（deep link）：`carrypigeon://channel?server=<socket>&id=<channel_id>`。
+//!
+//! 说明：
+//! - Windows 跳转列表、macOS Dock 菜单等系统入口都以深链接描述目标频道，统一经
+//!   [`open_channel`] 打开，避免各入口各自拼装前端事件；
+//! - 进程启动参数中的深链接（应用未运行时从跳转列表启动）先记为待处理，前端首屏后经
+//!   `take_pending_deep_link` 取走；已运行时由单实例回调转交，直接广播给主窗口；
+//! - OAuth 回调（`carrypigeon://auth/...`）由认证窗口自行拦截，不经本模块。
+//!
+//! 约定：注释中文，日志英文（tracing）。
+
+use std::sync::Mutex;
+
+use anyhow::Context;
+use serde::Serialize;
+use tauri::{AppHandle, Url};
+
+use crate::shared::error::CommandResult;
+use crate::shared::events::EventBusExt;
+
+/// 应用注册的 URL scheme。
+pub const DEEP_LINK_SCHEME: &str = "carrypigeon";
+/// 打开频道时投递给主窗口的事件名。
+pub const DEEP_LINK_OPEN_CHANNEL_EVENT: &str = "deep-link-open-channel";
+
+/// 尚未被前端取走的频道深链接（冷启动时由启动参数带入）。
+static PENDING: Mutex<Option<ChannelLink>> = Mutex::new(None);
+
+/// 深链接指向的频道。
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ChannelLink {
+    pub server_socket: String,
+    pub channel_id: i64,
+}
+
+impl ChannelLink {
+    /// 编码为深链接字符串。
+    pub fn to_url(&self) -> anyhow::Result<String> {
+        let mut url = Url::parse(&format!("{DEEP_LINK_SCHEME}://channel"))
+            .context("Failed to build the deep link base")?;
+        url.query_pairs_mut()
+            .append_pair("server", &self.server_socket)
+            .append_pair("id", &self.channel_id.to_string());
+        Ok(url.to_string())
+    }
+
+    /// 解析频道深链接；scheme / host 不符或参数缺失时返回 `None`。
+    pub fn parse(raw: &str) -> Option<Self> {
+        let url = Url::parse(raw.trim()).ok()?;
+        if url.scheme() != DEEP_LINK_SCHEME || url.host_str() != Some("channel") {
+            return None;
+        }
+        let mut server_socket = None;
+        let mut channel_id = None;
+        for (key, value) in url.query_pairs() {
+            match key.as_ref() {
+                "server" => server_socket = Some(value.trim().to_string()),
+                "id" => channel_id = value.parse::<i64>().ok(),
+                _ => {}
+            }
+        }
+        Some(Self {
+            server_socket: server_socket.filter(|s| !s.is_empty())?,
+            channel_id: channel_id?,
+        })
+    }
+
+    /// 从命令行参数中找出第一个频道深链接。
+    pub fn from_args<I, S>(args: I) -> Option<Self>
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        args.into_iter().find_map(|arg| Self::parse(arg.as_ref()))
+    }
+}
+
+/// 记录启动参数中的深链接（`run` 入口调用一次）。
+pub fn init<I, S>(args: I)
+where
+    I: IntoIterator<Item = S>,
+    S: AsRef<str>,
+{
+    if let Some(link) = ChannelLink::from_args(args) {
+        tracing::info!(
+            action = "app_deep_link_pending",
+            channel_id = link.channel_id
+        );
+        *PENDING.lock().unwrap_or_else(|p| p.into_inner()) = Some(link);
+    }
+}
+
+/// 打开深链接指向的频道：显示主窗口并通知前端切换频道。
+///
+/// # 说明
+/// 同时记为待处理：主窗口尚未加载完成时，前端可在首屏后经 `take_pending_deep_link` 取走。
+pub fn open_channel(app: &AppHandle, link: ChannelLink) {
+    tracing::info!(
+        action = "app_deep_link_open_channel",
+        channel_id = link.channel_id
+    );
+    *PENDING.lock().unwrap_or_else(|p| p.into_inner()) = Some(link.clone());
+    #[cfg(desktop)]
+    super::launch_mode::show_main_window(app);
+    app.emit_to("main", DEEP_LINK_OPEN_CHANNEL_EVENT, link);
+}
+
+/// 取走待处理的频道深链接（只返回一次）。
+#[tauri::command]
+pub async fn take_pending_deep_link() -> CommandResult<Option<ChannelLink>> {
+    Ok(PENDING.lock().unwrap_or_else(|p| p.into_inner()).take())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn channel_link_round_trips_with_escaped_socket() {
+        let link = ChannelLink {
+            server_socket: "chat.example.com:8443/a b?x=1&y".to_string(),
+            channel_id: 42,
+        };
+        let raw = link.to_url().unwrap();
+        assert!(raw.starts_with("carrypigeon://channel?server="));
+        assert_eq!(ChannelLink::parse(&raw), Some(link));
+    }
+
+    #[test]
+    fn rejects_other_links_and_finds_link_in_args() {
+        assert_eq!(
+            ChannelLink::parse("carrypigeon://auth/callback?code=1"),
+            None
+        );
+        assert_eq!(ChannelLink::parse("https://channel?server=a&id=1"), None);
+        assert_eq!(ChannelLink::parse("carrypigeon://channel?server=a"), None);
+        assert_eq!(
+            ChannelLink::parse("carrypigeon://channel?server=&id=1"),
+            None
+        );
+        assert_eq!(
+            ChannelLink::from_args([
+                "carrypigeon.exe",
+                "--minimized",
+                "carrypigeon://channel?server=s&id=7"
+            ]),
+            Some(ChannelLink {
+                server_socket: "s".to_string(),
+                channel_id: 7
+            })
+        );
+        assert_eq!(ChannelLink::from_args(["carrypigeon.exe"]), None);
+    }
+}
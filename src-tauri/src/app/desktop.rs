@@ -0,0 +1,262 @@
+//! 桌面端专属的应用组装：托盘、主窗口位置恢复与窗口事件。
+//!
+//! 说明：
+//! - 移动端（Android / iOS）只有一个全屏 WebView，没有托盘、多窗口与窗口位置的概念，
+//!   Tauri 也不提供对应 API；这些逻辑集中在本模块，`app` 只在 `desktop` 下引入；
+//! - 移动端由系统管理前后台切换，关闭到托盘、启动模式（隐藏/最小化）均不适用。
+//!
+//! 约定：注释中文，日志英文（tracing）。
+
+use anyhow::Context;
+use tauri::{
+    App, Manager, Window, WindowEvent,
+    image::Image,
+    menu::{Menu, MenuItem, PredefinedMenuItem},
+    tray::{MouseButton, MouseButtonState, TrayIconBuilder, TrayIconEvent},
+};
+
+use super::launch_mode;
+use crate::features::tray::di::commands::{TrayUnreadState, start_hover_timer};
+use crate::features::tray::domain::tray_i18n::tray_labels;
+use crate::features::voice_call::di::commands::VoiceCallService;
+use crate::features::windows::di::{lock_window, mini_chat, titlebar};
+use crate::features::windows::domain::context_menu;
+use crate::features::windows::domain::window_labels::{
+    TRAY_NOTIFICATION_POPOVER_LABEL, USER_INFO_POPOVER_LABEL,
+};
+use crate::features::windows::usecases::window_usecases::close_to_tray_enabled;
+use crate::shared::window_bounds;
+
+/// 初始化托盘未读闪烁状态，返回托盘图标（稍后由 [`build_tray`] 使用）。
+///
+/// 须在后台任务（免打扰刷新等）启动前调用：它们会读取 [`TrayUnreadState`]。
+pub(super) fn init_tray_state(app: &App) -> anyhow::Result<Image<'static>> {
+    // 获取默认窗口图标，作为托盘图标使用（确保应用资源中已设置默认图标）
+    let tray_icon = app
+        .default_window_icon()
+        .cloned()
+        .context("Default window icon is missing")?;
+
+    // 初始化托盘未读闪烁状态（to_owned 将 App 借用的图片转为 'static）。
+    let tray_icon = tray_icon.to_owned();
+    app.manage(TrayUnreadState::new(tray_icon.clone()));
+    Ok(tray_icon)
+}
+
+/// 恢复主窗口位置/尺寸：读取上次保存的 bounds 并应用，
+/// 然后按启动模式显示窗口以避免出现默认尺寸闪烁。
+pub(super) fn restore_main_window(app: &App) {
+    if let Some(window) = app.get_webview_window("main") {
+        if let Some(bounds) = window_bounds::load() {
+            if window_bounds::is_within_monitors(app.handle(), &bounds) {
+                let _ = window.set_size(tauri::PhysicalSize::new(bounds.width, bounds.height));
+                let _ = window.set_position(tauri::PhysicalPosition::new(bounds.x, bounds.y));
+                tracing::info!(
+                    action = "windows_bounds_restore_applied",
+                    width = bounds.width,
+                    height = bounds.height,
+                    x = bounds.x,
+                    y = bounds.y
+                );
+            } else {
+                tracing::warn!(
+                    action = "windows_bounds_restore_out_of_range",
+                    width = bounds.width,
+                    height = bounds.height,
+                    x = bounds.x,
+                    y = bounds.y
+                );
+            }
+        } else {
+            tracing::info!(action = "windows_bounds_restore_none");
+        }
+        // 按启动模式显示主窗口：仅托盘启动时保持隐藏，等待再次启动或托盘唤起。
+        let mode = launch_mode::resolve_with_settings();
+        tracing::info!(action = "app_launch_mode_resolved", mode = ?mode);
+        if !mode.starts_hidden() {
+            let _ = window.show();
+        }
+        if mode == launch_mode::LaunchMode::Minimized {
+            let _ = window.minimize();
+        }
+    } else {
+        tracing::warn!(action = "windows_bounds_main_window_missing");
+    }
+}
+
+/// 创建托盘图标与菜单。
+pub(super) fn build_tray(app: &App, tray_icon: Image<'static>) -> tauri::Result<()> {
+    // 定义托盘菜单行为（默认中文，前端启动后根据 locale 同步更新）
+    let labels = tray_labels("zh_cn");
+    let show_i = MenuItem::with_id(app, labels[0].0, labels[0].1.clone(), true, None::<&str>)?;
+    let sep = PredefinedMenuItem::separator(app)?;
+    let quit_i = MenuItem::with_id(app, labels[1].0, labels[1].1.clone(), true, None::<&str>)?;
+    let menu = Menu::with_items(app, &[&show_i, &sep, &quit_i])?;
+
+    // 定义托盘图标行为
+    let _tray = TrayIconBuilder::with_id("main")
+        .icon(tray_icon)
+        .menu(&menu)
+        .show_menu_on_left_click(false)
+        .on_menu_event(|app, event| match event.id.as_ref() {
+            "show_window" => {
+                tracing::info!(action = "app_tray_menu_clicked", item_id = "show_window");
+                if let Some(window) = app.get_webview_window("main") {
+                    let _ = window.unminimize();
+                    let _ = window.show();
+                    let _ = window.set_focus();
+                } else {
+                    tracing::warn!(action = "app_tray_menu_main_window_missing");
+                }
+            }
+            "quit" => {
+                tracing::info!(action = "app_tray_menu_clicked", item_id = "quit");
+                app.exit(0);
+            }
+            // 通用右键菜单的事件由 windows::di::context_menu 处理。
+            id if context_menu::is_context_menu_id(id) => {}
+            _ => {
+                tracing::warn!(action = "app_tray_menu_unhandled", item_id = ?event.id);
+            }
+        })
+        .on_tray_icon_event(|tray, event| match event {
+            TrayIconEvent::Click {
+                button: MouseButton::Left,
+                button_state: MouseButtonState::Up,
+                ..
+            } => {
+                let app = tray.app_handle();
+                if let Some(window) = app.get_webview_window("main") {
+                    if let Err(err) = window.unminimize() {
+                        tracing::warn!(action = "app_tray_click_unminimize_failed", error = %err);
+                    }
+                    if let Err(err) = window.show() {
+                        tracing::warn!(action = "app_tray_click_show_failed", error = %err);
+                    }
+                    if let Err(err) = window.set_focus() {
+                        tracing::warn!(action = "app_tray_click_focus_failed", error = %err);
+                    }
+                } else {
+                    tracing::warn!(action = "app_tray_click_main_window_missing");
+                }
+            }
+            TrayIconEvent::Enter { rect, .. } => {
+                let app = tray.app_handle();
+                let state: tauri::State<'_, TrayUnreadState> = app.state();
+                state
+                    .is_hovering
+                    .store(true, std::sync::atomic::Ordering::SeqCst);
+                let (px, py) = match rect.position {
+                    tauri::Position::Physical(p) => (p.x as f64, p.y as f64),
+                    tauri::Position::Logical(p) => (p.x, p.y),
+                };
+                let (pw, _ph) = match rect.size {
+                    tauri::Size::Physical(s) => (s.width as f64, s.height as f64),
+                    tauri::Size::Logical(s) => (s.width, s.height),
+                };
+                let x = px + pw;
+                let y = py;
+                start_hover_timer(app.clone(), &state, x, y);
+            }
+            TrayIconEvent::Leave { .. } => {
+                let app = tray.app_handle();
+                let state: tauri::State<'_, TrayUnreadState> = app.state();
+                state
+                    .is_hovering
+                    .store(false, std::sync::atomic::Ordering::SeqCst);
+                state
+                    .popover_open
+                    .store(false, std::sync::atomic::Ordering::SeqCst);
+                if let Some(win) = app.get_webview_window(TRAY_NOTIFICATION_POPOVER_LABEL)
+                    && let Err(err) = win.close()
+                {
+                    tracing::warn!(action = "app_tray_leave_close_popover_failed", error = %err);
+                }
+            }
+            _ => {}
+        })
+        .build(app)?;
+    Ok(())
+}
+
+/// 桌面端窗口事件：应用锁、子窗口失焦关闭、标题栏状态、窗口 bounds 持久化与关闭到托盘。
+pub(super) fn on_window_event(window: &Window, event: &WindowEvent) {
+    // 应用锁锁定期间：其它窗口获得焦点即隐藏，锁屏窗口不可关闭。
+    if lock_window::guard_window_event(window, event) {
+        return;
+    }
+    let label = window.label();
+    // 子窗口失焦自动关闭。
+    if (label == USER_INFO_POPOVER_LABEL || label == TRAY_NOTIFICATION_POPOVER_LABEL)
+        && matches!(event, &tauri::WindowEvent::Focused(false))
+    {
+        let _ = window.close();
+    }
+    // 最大化状态变化通知前端标题栏（自绘窗口控制按钮需要切换图标）。
+    if matches!(event, tauri::WindowEvent::Resized(_)) {
+        titlebar::notify_maximized_change(window);
+    }
+    // 主窗口 resize/move 时持久化当前 bounds。
+    if label == "main" {
+        match event {
+            tauri::WindowEvent::Resized(_) | tauri::WindowEvent::Moved(_) => {
+                if let Some(bounds) = window_bounds::from_window(window) {
+                    window_bounds::save_async(bounds);
+                }
+            }
+            _ => {}
+        }
+    }
+    // 迷你聊天窗移动/缩放时记住位置。
+    if label == mini_chat::MINI_CHAT_LABEL
+        && matches!(
+            event,
+            tauri::WindowEvent::Resized(_) | tauri::WindowEvent::Moved(_)
+        )
+    {
+        mini_chat::remember_bounds(window);
+    }
+    // 主窗口关闭时：
+    // 1) 若仍有未拨通（dialing/ringing/connecting）的通话，先静默取消
+    //    （向对端发送挂断/离开信令）再关闭/隐藏窗口，不弹确认框；
+    // 2) 否则若 close_to_tray 为 true，则阻止关闭并隐藏到托盘。
+    if label == "main" {
+        if let tauri::WindowEvent::CloseRequested { api, .. } = event {
+            // 优先处理未拨通的通话：关闭窗口前取消。
+            if let Some(service) = window.app_handle().try_state::<VoiceCallService>() {
+                if service.has_not_connected_call() {
+                    api.prevent_close();
+                    if let Some(bounds) = window_bounds::from_window(window) {
+                        window_bounds::save(bounds);
+                    }
+                    let app_handle = window.app_handle().clone();
+                    let svc = (*service).clone();
+                    let close_to_tray = close_to_tray_enabled(window.app_handle());
+                    tauri::async_runtime::spawn(async move {
+                        svc.cancel_not_connected_calls().await;
+                        if let Some(w) = app_handle.get_webview_window("main") {
+                            if close_to_tray {
+                                let _ = w.hide();
+                                tracing::info!(action = "app_main_window_hide_to_tray");
+                            } else {
+                                let _ = w.close();
+                            }
+                        }
+                    });
+                    // 已处理取消逻辑，跳过下方 close_to_tray 分支。
+                    return;
+                }
+            }
+            // 无未拨通通话时，按 close_to_tray 设置决定是否隐藏到托盘。
+            if close_to_tray_enabled(window.app_handle()) {
+                // 关闭到托盘前最后一次持久化当前 bounds。
+                if let Some(bounds) = window_bounds::from_window(window) {
+                    window_bounds::save(bounds);
+                }
+                api.prevent_close();
+                let _ = window.hide();
+                tracing::info!(action = "app_main_window_hide_to_tray");
+            }
+        }
+    }
+}
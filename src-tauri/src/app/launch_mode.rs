@@ -0,0 +1,192 @@
+//! 启动模式：前台 UI / 最小化 / 仅托盘。
+//!
+//! 说明：
+//! - 命令行参数：`--minimized`（主窗口最小化）、`--hidden`（只驻留托盘）；
+//!   同时出现时取最“安静”的一个；
+//! - 未指定参数时按设置 `start_hidden` / `start_minimized` 决定（便于开机自启静默启动）；
+//! - `--hidden` 启动时主窗口保持隐藏；协议握手与解密在前端完成，
+//!   因此主 webview 仍会加载并照常建立 TCP 连接、按通知设置弹出系统通知；
+//! - 进程为单实例：再次以普通方式启动时，新进程退出并把参数转交给已运行的实例，
+//!   由后者显示主窗口，继续使用已建立的会话；
+//! - 移动端没有命令行参数、托盘与单实例回调，始终为 `Foreground`，前后台切换与后台保活
+//!   由系统管理（定时任务只在进程处于前台时运行）。
+//!
+//! 约定：注释中文，日志英文（tracing）。
+
+use std::sync::RwLock;
+
+#[cfg(desktop)]
+use tauri::{AppHandle, Manager};
+
+use crate::features::settings::data::config_store;
+use crate::shared::error::CommandResult;
+
+/// 仅托盘启动参数。
+pub const HIDDEN_FLAG: &str = "--hidden";
+/// 最小化启动参数。
+pub const MINIMIZED_FLAG: &str = "--minimized";
+
+/// 本次进程的启动模式（命令行指定，启动时按设置补全）。
+static MODE: RwLock<LaunchMode> = RwLock::new(LaunchMode::Foreground);
+
+/// 启动模式。
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LaunchMode {
+    Foreground,
+    Minimized,
+    Hidden,
+}
+
+impl LaunchMode {
+    /// 从命令行参数解析启动模式（不含可执行文件路径亦可）。
+    pub fn from_args<I, S>(args: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        args.into_iter()
+            .filter_map(|arg| match arg.as_ref() {
+                HIDDEN_FLAG => Some(Self::Hidden),
+                MINIMIZED_FLAG => Some(Self::Minimized),
+                _ => None,
+            })
+            .max_by_key(|mode| mode.quietness())
+            .unwrap_or(Self::Foreground)
+    }
+
+    /// 未通过命令行指定时，按设置决定启动模式。
+    pub fn from_settings(start_hidden: bool, start_minimized: bool) -> Self {
+        if start_hidden {
+            Self::Hidden
+        } else if start_minimized {
+            Self::Minimized
+        } else {
+            Self::Foreground
+        }
+    }
+
+    /// 启动时主窗口是否保持隐藏。
+    pub fn starts_hidden(self) -> bool {
+        self == Self::Hidden
+    }
+
+    fn quietness(self) -> u8 {
+        match self {
+            Self::Foreground => 0,
+            Self::Minimized => 1,
+            Self::Hidden => 2,
+        }
+    }
+}
+
+/// 记录命令行指定的启动模式（`run` 入口调用一次）。
+pub fn init(mode: LaunchMode) {
+    *MODE.write().unwrap_or_else(|p| p.into_inner()) = mode;
+}
+
+/// 本次进程的启动模式。
+pub fn current() -> LaunchMode {
+    *MODE.read().unwrap_or_else(|p| p.into_inner())
+}
+
+/// 命令行未指定启动方式时，按设置补全启动模式并返回最终结果（setup 阶段调用）。
+pub fn resolve_with_settings() -> LaunchMode {
+    if current() != LaunchMode::Foreground {
+        return current();
+    }
+    let Some(envelope) = config_store::read_config_sync() else {
+        return LaunchMode::Foreground;
+    };
+    let mode = LaunchMode::from_settings(
+        envelope.backend.start_hidden,
+        envelope.backend.start_minimized,
+    );
+    init(mode);
+    mode
+}
+
+/// 启动时主窗口是否未显示（仅托盘启动）。
+pub fn launched_hidden() -> bool {
+    current().starts_hidden()
+}
+
+/// 显示并聚焦主窗口。
+#[cfg(desktop)]
+pub fn show_main_window(app: &AppHandle) {
+    if let Some(window) = app.get_webview_window("main") {
+        let _ = window.unminimize();
+        let _ = window.show();
+        let _ = window.set_focus();
+    } else {
+        tracing::warn!(action = "app_second_instance_main_window_missing");
+    }
+}
+
+/// 第二个实例启动时的处理（单实例插件回调）。
+///
+/// # 说明
+/// - 新实例带 `--hidden` / `--minimized`（如开机自启）：已有实例在运行，忽略；
+/// - 否则视为用户打开应用：显示主窗口；
+/// - 带频道深链接（跳转列表等）时显示主窗口并打开该频道。
+#[cfg(desktop)]
+pub fn on_second_instance(app: &AppHandle, argv: &[String]) {
+    if let Some(link) = super::deep_link::ChannelLink::from_args(argv) {
+        super::deep_link::open_channel(app, link);
+        return;
+    }
+    if LaunchMode::from_args(argv) != LaunchMode::Foreground {
+        tracing::info!(action = "app_second_instance_quiet_launch_ignored");
+        return;
+    }
+    tracing::info!(action = "app_second_instance_show_main");
+    show_main_window(app);
+}
+
+/// 本次是否以隐藏方式启动（`--hidden` / 设置 `start_hidden`）。
+///
+/// 前端据此跳过启动动画等仅前台需要的流程；主窗口之后被显示仍返回 true。
+#[tauri::command]
+pub async fn was_launched_hidden() -> CommandResult<bool> {
+    Ok(launched_hidden())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn flags_are_detected_anywhere_and_quietest_wins() {
+        assert_eq!(
+            LaunchMode::from_args(["carrypigeon", "--minimized", "--hidden"]),
+            LaunchMode::Hidden
+        );
+        assert_eq!(
+            LaunchMode::from_args(["--minimized"]),
+            LaunchMode::Minimized
+        );
+        assert_eq!(
+            LaunchMode::from_args(["carrypigeon"]),
+            LaunchMode::Foreground
+        );
+        // 只接受完整参数名。
+        assert_eq!(
+            LaunchMode::from_args(["--hidden=1", "--minimizedx"]),
+            LaunchMode::Foreground
+        );
+    }
+
+    #[test]
+    fn settings_prefer_hidden_over_minimized() {
+        assert_eq!(LaunchMode::from_settings(true, true), LaunchMode::Hidden);
+        assert_eq!(
+            LaunchMode::from_settings(false, true),
+            LaunchMode::Minimized
+        );
+        assert_eq!(
+            LaunchMode::from_settings(false, false),
+            LaunchMode::Foreground
+        );
+        assert!(LaunchMode::Hidden.starts_hidden());
+        assert!(!LaunchMode::Minimized.starts_hidden());
+    }
+}
@@ -20,7 +20,7 @@ pub fn read_app_log_lines(limit: u32) -> CommandResult<Vec<String>> {
         Ok(dir) => dir.join("logs").join("app.log"),
         Err(e) => {
             tracing::error!(action = "app_log_read_failed", reason = "app_data_dir_unavailable", error = %e);
-            return Err(format!("[APP_DATA_DIR_UNAVAILABLE] {e}"));
+            return Err(format!("[APP_DATA_DIR_UNAVAILABLE] {e}").into());
         }
     };
 
@@ -32,7 +32,7 @@ pub fn read_app_log_lines(limit: u32) -> CommandResult<Vec<String>> {
         Ok(f) => f,
         Err(e) => {
             tracing::error!(action = "app_log_read_failed", path = ?log_path, error = %e);
-            return Err(format!("[LOG_OPEN_FAILED] {e}"));
+            return Err(format!("[LOG_OPEN_FAILED] {e}").into());
         }
     };
 
@@ -50,7 +50,7 @@ pub fn read_app_log_lines(limit: u32) -> CommandResult<Vec<String>> {
             }
             Err(e) => {
                 tracing::error!(action = "app_log_read_failed", error = %e);
-                return Err(format!("[LOG_READ_FAILED] {e}"));
+                return Err(format!("[LOG_READ_FAILED] {e}").into());
             }
         }
     }
@@ -8,41 +8,69 @@
 //! - 注释统一使用中文，便于团队维护与交接。
 //! - 日志输出统一使用英文，便于跨端检索与与上游/第三方日志对齐。
 
-use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::sync::atomic::AtomicBool;
 
 use anyhow::Context;
-use tauri::{
-    Manager,
-    menu::{Menu, MenuItem, PredefinedMenuItem},
-    tray::{MouseButton, MouseButtonState, TrayIconBuilder, TrayIconEvent},
-};
-use tracing_subscriber::prelude::*;
-
+use tauri::{Manager, webview::PageLoadEvent};
+
+mod asset_response;
+#[cfg(desktop)]
+pub mod cli;
+pub mod deep_link;
+#[cfg(desktop)]
+mod desktop;
+pub mod launch_mode;
 pub mod log_commands;
-
+pub mod onboarding;
+pub mod registry;
+pub mod runtime_info;
+pub mod self_check;
+pub mod startup;
+
+use crate::features::assistant::di::AssistantCommands;
+use crate::features::calls::di::CallsCommands;
+use crate::features::emoji::asset_store;
+use crate::features::emoji::di::EmojiCommands;
+use crate::features::message_render::di::MessageRenderCommands;
+use crate::features::network::di::NetworkCommands;
 use crate::features::network::usecases::tcp_usecases::TcpRegistryService;
 use crate::features::plugins::data::plugin_store;
-use crate::features::settings::data::config_store::{Config, config_file_path};
+use crate::features::plugins::di::PluginsCommands;
+use crate::features::profile::di::ProfileCommands;
+#[cfg(desktop)]
+use crate::features::screenshot::di::ScreenshotCommands;
+use crate::features::settings::data::config_store;
 use crate::features::settings::data::config_store_port_adapter::ConfigStorePortAdapter;
-use crate::features::settings::domain::settings_schema::SettingsImportEnvelopeV1;
-use crate::features::tray::di::commands::{TrayUnreadState, start_hover_timer};
-use crate::features::tray::domain::tray_i18n::tray_labels;
-use crate::features::voice_call::di::commands::VoiceCallService;
+use crate::features::settings::di::SettingsCommands;
+use crate::features::translation::di::TranslationCommands;
+#[cfg(desktop)]
+use crate::features::tray::di::TrayCommands;
+use crate::features::voice_call::di::VoiceCallCommands;
+use crate::features::voice_message::di::VoiceMessageCommands;
+#[cfg(desktop)]
+use crate::features::windows::di::WindowsCommands;
+use crate::shared::SharedCommands;
 use crate::shared::close_to_tray_state::CloseToTrayState;
 use crate::shared::temp_file::TempFileManager;
-use crate::shared::window_bounds::{self, WindowBounds};
+use registry::{CommandRegistration, CommandRegistry, command_set};
 
 /// Wraps the `tracing_appender::WorkerGuard` for managed-state storage
 /// so it is dropped on Tauri shutdown, flushing buffered logs to disk.
 #[allow(dead_code)]
 struct LogFlushGuard(std::sync::Mutex<Option<tracing_appender::non_blocking::WorkerGuard>>);
 
-#[cfg_attr(mobile, tauri::mobile_entry_point)]
 /// 启动 Tauri 应用。
 ///
+/// # 参数
+/// - `mode`：命令行指定的启动模式（见 [`launch_mode`]）；`Foreground` 时按设置补全。
+///
 /// # 返回值
 /// 当 Builder 组装或初始化失败时返回错误。
-pub fn run() -> anyhow::Result<()> {
+pub fn run(mode: launch_mode::LaunchMode) -> anyhow::Result<()> {
+    startup::mark_process_start();
+    launch_mode::init(mode);
+    deep_link::init(std::env::args());
     // 设置 panic hook，在 panic 时记录到 tracing
     let default_hook = std::panic::take_hook();
     std::panic::set_hook(Box::new(move |info| {
@@ -64,410 +92,256 @@ pub fn run() -> anyhow::Result<()> {
         default_hook(info);
     }));
 
-    // Tauri Builder 组装
-    tauri::Builder::default()
+    build_app()
+        .run(tauri::generate_context!())
+        .context("error while running tauri application")?;
+    Ok(())
+}
+
+/// 组合所有 feature 的命令注册。
+///
+/// # 说明
+/// 新增 feature 时在此追加一行；命令本身在 feature 的 `di` 模块内登记。
+/// 依赖桌面端 API 的 feature 追加在 `#[cfg(desktop)]` 段。
+pub fn command_registry() -> CommandRegistry {
+    let registry = CommandRegistry::default()
+        .with::<NetworkCommands>()
+        .with::<SharedCommands>()
+        .with::<AppCommands>()
+        .with::<SettingsCommands>()
+        .with::<PluginsCommands>()
+        .with::<VoiceMessageCommands>()
+        .with::<EmojiCommands>()
+        .with::<VoiceCallCommands>()
+        .with::<CallsCommands>()
+        .with::<TranslationCommands>()
+        .with::<MessageRenderCommands>()
+        .with::<AssistantCommands>()
+        .with::<ProfileCommands>();
+    // 托盘、多窗口与截图只在桌面端提供。
+    #[cfg(desktop)]
+    let registry = registry
+        .with::<TrayCommands>()
+        .with::<WindowsCommands>()
+        .with::<ScreenshotCommands>();
+    registry
+}
+
+/// 应用自身（运行时信息/深链接/启动耗时/日志文件/首次运行引导/完整性自检）命令注册。
+pub struct AppCommands;
+
+impl CommandRegistration for AppCommands {
+    fn register(registry: &mut CommandRegistry) {
+        registry.add(command_set!(crate::app::runtime_info => [get_runtime_info]));
+        registry.add(command_set!(crate::app::launch_mode => [was_launched_hidden]));
+        registry.add(command_set!(crate::app::deep_link => [take_pending_deep_link]));
+        registry.add(command_set!(crate::app::startup => [get_startup_trace]));
+        registry.add(command_set!(crate::app::log_commands => [
+            write_app_log,
+            read_app_log_lines,
+        ]));
+        registry.add(command_set!(crate::app::onboarding => [
+            get_onboarding_state,
+            complete_onboarding_step,
+        ]));
+        registry.add(command_set!(crate::app::self_check => [run_self_check, repair]));
+    }
+}
+
+/// 组装 Tauri `Builder`（scheme、setup、窗口事件、托管状态与命令）。
+///
+/// # 说明
+/// 命令来自 [`command_registry`]，测试直接校验同一份注册表，保证与运行时一致。
+pub fn build_app() -> tauri::Builder<tauri::Wry> {
+    let builder = tauri::Builder::default();
+    // 单实例：再次启动时把参数转交已运行的实例（显示主窗口、打开深链接），须最先注册。
+    // 移动端由系统保证单实例。
+    #[cfg(desktop)]
+    let builder = builder.plugin(tauri_plugin_single_instance::init(|app, argv, _cwd| {
+        launch_mode::on_second_instance(app, &argv);
+    }));
+    builder
         // 注册自定义 scheme 处理器，安全地加载本地插件静态资源（如 JS/CSS），避免直接暴露文件系统路径。
-        .register_uri_scheme_protocol("app", |_, req| handle_app_scheme(req).unwrap_or_else(|e| {
-            tracing::warn!(action = "app_scheme_handler_failed", error = %e);
-            build_http_response(500, None, Vec::new())
-        }))
+        .register_uri_scheme_protocol("app", |_, req| {
+            handle_app_scheme(req).unwrap_or_else(|e| {
+                tracing::warn!(action = "app_scheme_handler_failed", error = %e);
+                build_http_response(500, None, Vec::new())
+            })
+        })
         // 初始化应用（托盘、全局事件等）
         .setup(|app| {
             // 初始化 TCP 注册表服务（用于命令层注入）。
             app.manage(TcpRegistryService::new());
-            // 获取默认窗口图标，作为托盘图标使用（确保应用资源中已设置默认图标）
-            let tray_icon = app
-                .default_window_icon()
-                .cloned()
-                .context("Default window icon is missing")?;
-
-            // 初始化托盘未读闪烁状态（to_owned 将 App 借用的图片转为 'static）。
-            app.manage(TrayUnreadState::new(tray_icon.clone().to_owned()));
+            // 初始化托盘未读闪烁状态（桌面端）；托盘图标在 setup 末尾创建。
+            #[cfg(desktop)]
+            let tray_icon = desktop::init_tray_state(app)?;
 
             // 初始化临时文件管理器
             // 注意：setup() 已运行在 tokio 运行时上下文中，不能在当前线程 block_on。
             // 需要在独立 OS 线程中创建新的 tokio 运行时来执行异步初始化。
-            let app_data_dir = app.path().app_data_dir().context("Failed to get app data dir")?;
-            crate::shared::app_data_dir::init_app_data_dir(app_data_dir.clone())?;
+            let app_data_dir = startup::phase("app_data_dir", || -> anyhow::Result<_> {
+                let default_data_dir = app
+                    .path()
+                    .app_data_dir()
+                    .context("Failed to get app data dir")?;
+                let app_data_dir = crate::shared::paths::resolve_data_dir(&default_data_dir);
+                crate::shared::app_data_dir::init_app_data_dir(app_data_dir.clone())?;
+                Ok(app_data_dir)
+            })?;
 
             // 初始化文件日志
-            let log_dir = app_data_dir.join("logs");
-            std::fs::create_dir_all(&log_dir).ok();
-            let file_appender = tracing_appender::rolling::daily(&log_dir, "app.log");
-            let (non_blocking, guard) = tracing_appender::non_blocking(file_appender);
-            let file_layer = tracing_subscriber::fmt::layer()
-                .with_writer(non_blocking)
-                .with_ansi(false);
-            // 尝试添加 file_layer 到全局 subscriber
-            if let Err(e) = tracing_subscriber::registry()
-                .with(tracing_subscriber::EnvFilter::from_default_env())
-                .with(file_layer)
-                .try_init()
-            {
-                // 如果 subscriber 已经设定，file_layer 会失败 — 用 warn 记录（会写入现有 subscriber）
-                tracing::warn!(action = "app_file_logger_already_set", error = %e);
-            }
+            // 文件日志层已在 main.rs 随全局 subscriber 安装，这里只指定写入目录。
+            let guard = startup::phase("file_logger", || {
+                crate::shared::log::subscriber::attach_file_log(&app_data_dir.join("logs"))
+            });
             // Store the log guard as Tauri managed state so it lives for
             // the app's lifetime and properly flushes buffered logs on drop.
-            app.manage(LogFlushGuard(std::sync::Mutex::new(Some(guard))));
+            app.manage(LogFlushGuard(std::sync::Mutex::new(guard)));
+
+            #[cfg(debug_assertions)]
+            crate::shared::metrics::prometheus::spawn_from_env();
 
             let metadata_db_path = app_data_dir.join("temp_files").join("metadata.db");
-            let temp_file_manager = std::thread::spawn({
-                let app_data_dir = app_data_dir.clone();
-                let metadata_db_path = metadata_db_path.clone();
-                move || {
-                    let rt = tokio::runtime::Runtime::new()
-                        .context("Failed to create tokio runtime")?;
-                    rt.block_on(TempFileManager::new(app_data_dir, metadata_db_path))
-                }
-            })
-            .join()
-            .map_err(|e| anyhow::anyhow!("Failed to join TempFileManager init thread: {e:?}"))
-            .context("TempFileManager init thread panicked")??;
+            let temp_file_manager = startup::phase("temp_file_db", || {
+                std::thread::spawn({
+                    let app_data_dir = app_data_dir.clone();
+                    let metadata_db_path = metadata_db_path.clone();
+                    move || {
+                        let rt = tokio::runtime::Runtime::new()
+                            .context("Failed to create tokio runtime")?;
+                        rt.block_on(TempFileManager::new(app_data_dir, metadata_db_path))
+                    }
+                })
+                .join()
+                .map_err(|e| anyhow::anyhow!("Failed to join TempFileManager init thread: {e:?}"))
+                .context("TempFileManager init thread panicked")
+            })??;
 
             app.manage(temp_file_manager);
 
-            // 恢复主窗口位置/尺寸：读取上次保存的 bounds 并应用，
-            // 然后显示窗口以避免出现默认尺寸闪烁。
-            if let Some(window) = app.get_webview_window("main") {
-                if let Some(bounds) = window_bounds::load() {
-                    if is_bounds_within_monitors(app.handle(), &bounds) {
-                        let _ = window.set_size(tauri::PhysicalSize::new(bounds.width, bounds.height));
-                        let _ = window.set_position(tauri::PhysicalPosition::new(bounds.x, bounds.y));
-                        tracing::info!(
-                            action = "windows_bounds_restore_applied",
-                            width = bounds.width,
-                            height = bounds.height,
-                            x = bounds.x,
-                            y = bounds.y
-                        );
-                    } else {
-                        tracing::warn!(
-                            action = "windows_bounds_restore_out_of_range",
-                            width = bounds.width,
-                            height = bounds.height,
-                            x = bounds.x,
-                            y = bounds.y
-                        );
-                    }
-                } else {
-                    tracing::info!(action = "windows_bounds_restore_none");
-                }
-                let _ = window.show();
-            } else {
-                tracing::warn!(action = "windows_bounds_main_window_missing");
-            }
+            // 恢复主窗口位置/尺寸并按启动模式显示（移动端窗口由系统管理）。
+            #[cfg(desktop)]
+            startup::phase("window_bounds", || desktop::restore_main_window(app));
 
             // 同步读取 close_to_tray 设置，缓存到托管状态供窗口关闭事件使用。
-            // 优先解析信封格式（迁移后），回退到旧版 Config 格式。
-            let config_path = config_file_path();
-            let close_to_tray = std::fs::read_to_string(&config_path)
-                .ok()
-                .and_then(|raw| {
-                    serde_json::from_str::<SettingsImportEnvelopeV1>(&raw)
-                        .map(|e| e.backend.close_to_tray)
-                        .or_else(|_| {
-                            serde_json::from_str::<Config>(&raw).map(|c| c.close_to_tray)
-                        })
-                        .ok()
-                })
-                .unwrap_or(true); // 默认启用关闭到托盘（聊天应用标准行为）。
-            tracing::info!(action = "app_close_to_tray_init", close_to_tray = close_to_tray);
-            app.manage(CloseToTrayState(AtomicBool::new(close_to_tray)));
-            // 初始化 ConfigStorePortAdapter 的 AppHandle 引用，
-            // 使 close_to_tray 缓存同步在 data 层完成，无需 di/commands 感知。
-            ConfigStorePortAdapter::init_app_handle(app.handle());
-
-            // 启动时清理过期临时文件（后台执行，不需要阻塞 setup）
+            // 经 config_store 读取（兼容旧版 Config 格式）。
+            let close_to_tray = startup::phase("config_read", || {
+                let envelope = config_store::read_config_sync();
+                // 默认启用关闭到托盘（聊天应用标准行为）。
+                let close_to_tray = envelope
+                    .as_ref()
+                    .is_none_or(|envelope| envelope.backend.close_to_tray);
+                tracing::info!(
+                    action = "app_close_to_tray_init",
+                    close_to_tray = close_to_tray
+                );
+                // 命令错误格式兼容开关（缺省为结构化错误）。
+                if let Some(envelope) = &envelope {
+                    config_store::apply_runtime_switches(envelope);
+                }
+                close_to_tray
+            });
+            let close_to_tray = Arc::new(AtomicBool::new(close_to_tray));
+            app.manage(CloseToTrayState(close_to_tray.clone()));
+            // 与 ConfigStorePortAdapter 共享 close_to_tray 缓存，
+            // 使缓存同步在 data 层完成，无需 di/commands 感知。
+            ConfigStorePortAdapter::init_close_to_tray_cache(close_to_tray);
+            // 隐私模式：按启动时的开关为已有窗口设置内容保护。
+            crate::shared::privacy::init(app.handle());
+            // 跳转列表 / Dock 菜单：前端打开 system DB 后首次发布。
+            crate::shared::jump_list::init(app.handle());
+            // 任务栏进度：长耗时操作经 TaskbarProgress 登记。
+            crate::shared::taskbar_progress::init(app.handle());
+            // 未读角标：托盘未读命令上报未读数，按平台展示。
+            crate::shared::badges::init(app.handle());
+
+            // 以下为非关键初始化：推迟到首屏渲染后依次执行，不与首屏争抢 IO。
+            // 启动时清理过期临时文件
             let handle = app.handle().clone();
-            tauri::async_runtime::spawn(async move {
+            startup::defer("temp_file_cleanup", move || async move {
                 let state = handle.state::<TempFileManager>();
                 if let Err(e) = state.cleanup(None, 24).await {
                     tracing::warn!(action = "app_temp_file_startup_cleanup_failed", error = %e);
                 }
                 // 重启后默认不续传：清理未完成的下载 .part 与记录
-                if let Err(e) = state.prune_incomplete_downloads().await {
-                    tracing::warn!(action = "app_temp_file_prune_failed", error = %e);
-                }
+                state.prune_incomplete_downloads().await.map(|_| ())
             });
-
-            // 定义托盘菜单行为（默认中文，前端启动后根据 locale 同步更新）
-            let labels = tray_labels("zh_cn");
-            let show_i = MenuItem::with_id(app, labels[0].0, labels[0].1.clone(), true, None::<&str>)?;
-            let sep = PredefinedMenuItem::separator(app)?;
-            let quit_i = MenuItem::with_id(app, labels[1].0, labels[1].1.clone(), true, None::<&str>)?;
-            let menu = Menu::with_items(app, &[&show_i, &sep, &quit_i])?;
-
-            // 定义托盘图标行为
-            let _tray = TrayIconBuilder::with_id("main")
-                .icon(tray_icon)
-                .menu(&menu)
-                .show_menu_on_left_click(false)
-                .on_menu_event(|app, event| match event.id.as_ref() {
-                    "show_window" => {
-                        tracing::info!(action = "app_tray_menu_clicked", item_id = "show_window");
-                        if let Some(window) = app.get_webview_window("main") {
-                            let _ = window.unminimize();
-                            let _ = window.show();
-                            let _ = window.set_focus();
-                        } else {
-                            tracing::warn!(action = "app_tray_menu_main_window_missing");
-                        }
-                    }
-                    "quit" => {
-                        tracing::info!(action = "app_tray_menu_clicked", item_id = "quit");
-                        app.exit(0);
-                    }
-                    _ => {
-                        tracing::warn!(action = "app_tray_menu_unhandled", item_id = ?event.id);
-                    }
-                })
-                .on_tray_icon_event(|tray, event| {
-                    match event {
-                        TrayIconEvent::Click {
-                            button: MouseButton::Left,
-                            button_state: MouseButtonState::Up,
-                            ..
-                        } => {
-                            let app = tray.app_handle();
-                            if let Some(window) = app.get_webview_window("main") {
-                                if let Err(err) = window.unminimize() {
-                                    tracing::warn!(action = "app_tray_click_unminimize_failed", error = %err);
-                                }
-                                if let Err(err) = window.show() {
-                                    tracing::warn!(action = "app_tray_click_show_failed", error = %err);
-                                }
-                                if let Err(err) = window.set_focus() {
-                                    tracing::warn!(action = "app_tray_click_focus_failed", error = %err);
-                                }
-                            } else {
-                                tracing::warn!(action = "app_tray_click_main_window_missing");
-                            }
-                        }
-                        TrayIconEvent::Enter { rect, .. } => {
-                            let app = tray.app_handle();
-                            let state: tauri::State<'_, TrayUnreadState> = app.state();
-                            state.is_hovering.store(true, std::sync::atomic::Ordering::SeqCst);
-                            let (px, py) = match rect.position {
-                                tauri::Position::Physical(p) => (p.x as f64, p.y as f64),
-                                tauri::Position::Logical(p) => (p.x, p.y),
-                            };
-                            let (pw, _ph) = match rect.size {
-                                tauri::Size::Physical(s) => (s.width as f64, s.height as f64),
-                                tauri::Size::Logical(s) => (s.width, s.height),
-                            };
-                            let x = px + pw;
-                            let y = py;
-                            start_hover_timer(app.clone(), &state, x, y);
-                        }
-                        TrayIconEvent::Leave { .. } => {
-                            let app = tray.app_handle();
-                            let state: tauri::State<'_, TrayUnreadState> = app.state();
-                            state.is_hovering.store(false, std::sync::atomic::Ordering::SeqCst);
-                            state.popover_open.store(false, std::sync::atomic::Ordering::SeqCst);
-                            if let Some(win) = app.get_webview_window("tray-notification-popover")
-                                && let Err(err) = win.close() {
-                                    tracing::warn!(action = "app_tray_leave_close_popover_failed", error = %err);
-                                }
-                        }
-                        _ => {}
-                    }
-                })
-                .build(app)?;
+            // 监听 config.json 的外部修改（配置只在启动时读取一次，之后走内存缓存）
+            startup::defer("settings_config_watcher", || async {
+                ConfigStorePortAdapter::watch_config_file()
+            });
+            // 旧插件数据（plugins.json / plugin_cache）一次性迁移
+            startup::defer(
+                "plugins_legacy_import",
+                crate::features::plugins::di::legacy_import::run_legacy_import,
+            );
+            // 预加载已启用插件（校验 + 停用损坏的安装），按服务端发出 plugin-runtime-ready
+            let handle = app.handle().clone();
+            startup::defer("plugins_preload", move || {
+                crate::features::plugins::di::preload::run_preload(handle)
+            });
+            // 恢复开发态插件的目录监听（仅插件开发者模式）
+            let handle = app.handle().clone();
+            startup::defer("plugins_dev_watchers", move || {
+                crate::features::plugins::di::dev_reload::restore_dev_watchers(handle)
+            });
+            // 插件旧版本自动清理（按设置开关，后台周期执行）
+            startup::defer("plugins_version_gc", || async {
+                crate::features::plugins::di::version_gc::spawn_version_gc();
+                Ok(())
+            });
+            // 消息保留策略自动清理（按设置与频道覆盖，后台周期执行）
+            startup::defer("db_retention_prune", || async {
+                crate::shared::db::retention::spawn_retention_job();
+                Ok(())
+            });
+            // 定时备份（每晚一次，按设置轮换；电池供电时跳过）
+            startup::defer("db_scheduled_backup", || async {
+                crate::shared::db::backup::spawn_backup_job();
+                Ok(())
+            });
+            startup::spawn_render_fallback();
+            // 免打扰状态定时刷新（定时规则边界、手动覆盖到期）
+            crate::shared::dnd::spawn_dnd_watcher(app.handle().clone());
+            // 应用锁：空闲超时自动锁定
+            crate::shared::app_lock::spawn_idle_lock_watcher(app.handle().clone());
+
+            // 托盘图标与菜单（移动端没有托盘）。
+            #[cfg(desktop)]
+            desktop::build_tray(app, tray_icon)?;
             Ok(())
         })
         .on_window_event(|window, event| {
-            let label = window.label();
-            // 子窗口失焦自动关闭。
-            if (label == "user-info-popover" || label == "tray-notification-popover")
-                && matches!(event, &tauri::WindowEvent::Focused(false))
-            {
-                let _ = window.close();
+            // 窗口销毁后释放其连接订阅（该窗口不再接收帧）。
+            if matches!(event, tauri::WindowEvent::Destroyed) {
+                crate::features::network::di::connection_channels::ConnectionChannels::global()
+                    .remove_window(window.label());
             }
-            // 主窗口 resize/move 时持久化当前 bounds。
-            if label == "main" {
-                match event {
-                    tauri::WindowEvent::Resized(_) | tauri::WindowEvent::Moved(_) => {
-                        if let Some(bounds) = current_main_bounds(window) {
-                            window_bounds::save_async(bounds);
-                        }
-                    }
-                    _ => {}
-                }
+            #[cfg(desktop)]
+            desktop::on_window_event(window, event);
+        })
+        // 主窗口首个页面加载完成即视为首屏渲染，开始执行延迟初始化。
+        .on_page_load(|webview, payload| {
+            if matches!(payload.event(), PageLoadEvent::Started) {
+                // 页面重新加载后旧的插件会话随之失效。
+                crate::features::plugins::data::plugin_store::release_webview(webview.label());
             }
-            // 主窗口关闭时：
-            // 1) 若仍有未拨通（dialing/ringing/connecting）的通话，先静默取消
-            //    （向对端发送挂断/离开信令）再关闭/隐藏窗口，不弹确认框；
-            // 2) 否则若 close_to_tray 为 true，则阻止关闭并隐藏到托盘。
-            if label == "main" {
-                if let tauri::WindowEvent::CloseRequested { api, .. } = event {
-                    // 优先处理未拨通的通话：关闭窗口前取消。
-                    if let Some(service) = window.app_handle().try_state::<VoiceCallService>() {
-                        if service.has_not_connected_call() {
-                            api.prevent_close();
-                            if let Some(bounds) = current_main_bounds(window) {
-                                window_bounds::save(bounds);
-                            }
-                            let app_handle = window.app_handle().clone();
-                            let svc = (*service).clone();
-                            let close_to_tray = window
-                                .app_handle()
-                                .try_state::<CloseToTrayState>()
-                                .map(|s| s.0.load(Ordering::SeqCst))
-                                .unwrap_or(false);
-                            tauri::async_runtime::spawn(async move {
-                                svc.cancel_not_connected_calls().await;
-                                if let Some(w) = app_handle.get_webview_window("main") {
-                                    if close_to_tray {
-                                        let _ = w.hide();
-                                        tracing::info!(action = "app_main_window_hide_to_tray");
-                                    } else {
-                                        let _ = w.close();
-                                    }
-                                }
-                            });
-                            // 已处理取消逻辑，跳过下方 close_to_tray 分支。
-                            return;
-                        }
-                    }
-                    // 无未拨通通话时，按 close_to_tray 设置决定是否隐藏到托盘。
-                    if let Some(state) = window.app_handle().try_state::<CloseToTrayState>()
-                        && state.0.load(Ordering::SeqCst)
-                    {
-                        // 关闭到托盘前最后一次持久化当前 bounds。
-                        if let Some(bounds) = current_main_bounds(window) {
-                            window_bounds::save(bounds);
-                        }
-                        api.prevent_close();
-                        let _ = window.hide();
-                        tracing::info!(action = "app_main_window_hide_to_tray");
-                    }
-                }
+            if webview.label() == "main" && matches!(payload.event(), PageLoadEvent::Finished) {
+                startup::on_first_render(false);
             }
         })
         .plugin(tauri_plugin_opener::init())
         .plugin(tauri_plugin_notification::init())
         .manage(crate::features::voice_call::di::commands::VoiceCallService::new())
-        .manage(crate::features::voice_message::di::commands::VoiceRecorderState(
-            std::sync::Mutex::new(None),
+        .manage(
+            crate::features::voice_message::di::commands::VoiceRecorderState(
+                std::sync::Mutex::new(None),
+            ),
+        )
+        // 注册对外暴露的命令（按 feature 组合；每次调用分配 correlation id）
+        .invoke_handler(crate::shared::correlation::with_correlation(
+            command_registry().into_handler(),
         ))
-        // 注册对外暴露的事件钩子
-        .invoke_handler(tauri::generate_handler![
-            // tray
-            crate::features::tray::di::commands::set_tray_unread_flashing,
-            crate::features::tray::di::commands::set_tray_locale,
-            // windows
-            crate::features::windows::di::commands::to_chat_window_size,
-            crate::features::windows::di::commands::open_popover_window,
-            crate::features::windows::di::commands::open_info_window,
-            crate::features::windows::di::commands::close_tray_notification_popover,
-            // network
-            crate::features::network::di::commands::send_tcp_service,
-            crate::features::network::di::commands::add_tcp_service,
-            crate::features::network::di::commands::remove_tcp_service,
-            crate::features::network::di::commands::api_request_json,
-            crate::features::network::di::commands::download_file,
-            // link_preview
-            crate::features::network::link_preview::fetch_link_preview,
-            // temp_file
-            crate::shared::temp_file::commands::cleanup_temp_files,
-            crate::shared::temp_file::commands::remove_temp_file,
-            crate::shared::temp_file::commands::save_temp_file,
-            crate::shared::temp_file::commands::open_temp_file,
-            // db
-            crate::shared::db::commands::db_init,
-            crate::shared::db::commands::db_execute,
-            crate::shared::db::commands::db_query,
-            crate::shared::db::commands::db_transaction,
-            crate::shared::db::commands::db_path,
-            crate::shared::db::commands::db_close,
-            crate::shared::db::commands::db_remove,
-            crate::shared::chat_cache::commands::chat_cache_get,
-            crate::shared::chat_cache::commands::chat_cache_load_all,
-            crate::shared::chat_cache::commands::chat_cache_clear_all,
-            crate::shared::chat_cache::commands::chat_cache_put,
-            crate::shared::chat_cache::commands::chat_cache_remove,
-            crate::shared::chat_cache::commands::chat_cache_remove_many,
-            // logs
-            crate::app::log_commands::write_app_log,
-            crate::app::log_commands::read_app_log_lines,
-            crate::shared::log::log_info,
-            crate::shared::log::log_error,
-            crate::shared::log::log_warning,
-            crate::shared::log::log_debug,
-            // settings/config
-            crate::features::settings::di::commands::get_config,
-            crate::features::settings::di::commands::export_settings,
-            crate::features::settings::di::commands::import_settings,
-            crate::features::settings::di::commands::reset_settings,
-            crate::features::settings::di::commands::get_config_bool,
-            crate::features::settings::di::commands::get_config_u32,
-            crate::features::settings::di::commands::get_config_u64,
-            crate::features::settings::di::commands::get_config_string,
-            crate::features::settings::di::commands::get_server_config_string,
-            crate::features::settings::di::commands::get_server_config_u32,
-            crate::features::settings::di::commands::get_server_config_u64,
-            crate::features::settings::di::commands::get_server_config_bool,
-            crate::features::settings::di::commands::update_config_bool,
-            crate::features::settings::di::commands::update_config_u32,
-            crate::features::settings::di::commands::update_config_string,
-            // plugins legacy debug commands
-            crate::features::plugins::di::commands::load_plugin,
-            crate::features::plugins::di::commands::list_plugins,
-            // plugins
-            crate::features::plugins::di::commands::plugins_list_installed,
-            crate::features::plugins::di::commands::plugins_get_installed_state,
-            crate::features::plugins::di::commands::plugins_get_runtime_entry,
-            crate::features::plugins::di::commands::plugins_get_runtime_entry_for_version,
-            crate::features::plugins::di::commands::plugins_install_from_server_catalog,
-            crate::features::plugins::di::commands::plugins_install_from_url,
-            crate::features::plugins::di::commands::plugins_enable,
-            crate::features::plugins::di::commands::plugins_disable,
-            crate::features::plugins::di::commands::plugins_switch_version,
-            crate::features::plugins::di::commands::plugins_uninstall,
-            crate::features::plugins::di::commands::plugins_set_failed,
-            crate::features::plugins::di::commands::plugins_clear_error,
-            crate::features::plugins::di::commands::plugins_storage_get,
-            crate::features::plugins::di::commands::plugins_storage_set,
-            crate::features::plugins::di::commands::plugins_network_fetch,
-            // voice_message
-            crate::features::voice_message::di::commands::start_voice_recording,
-            crate::features::voice_message::di::commands::stop_voice_recording,
-            crate::features::voice_message::di::commands::read_file_base64,
-            crate::features::voice_message::di::commands::read_file_base64_chunk,
-            // emoji
-            crate::features::emoji::di::commands::list_custom_emojis,
-            crate::features::emoji::di::commands::save_emoji,
-            crate::features::emoji::di::commands::delete_emoji,
-            crate::features::emoji::di::commands::copy_emoji,
-            crate::features::emoji::di::commands::write_temp_emoji_file,
-            crate::features::emoji::di::commands::get_emoji_image_path,
-            // screenshot
-            crate::features::screenshot::di::commands::start_screenshot,
-            crate::features::screenshot::di::commands::get_screenshot_data,
-            crate::features::screenshot::di::commands::finish_screenshot,
-            crate::features::screenshot::di::commands::cancel_screenshot,
-            // voice_call
-            crate::features::voice_call::di::commands::connect_signaling,
-            crate::features::voice_call::di::commands::start_direct_call,
-            crate::features::voice_call::di::commands::start_conference,
-            crate::features::voice_call::di::commands::accept_call,
-            crate::features::voice_call::di::commands::reject_call,
-            crate::features::voice_call::di::commands::hangup_call,
-            crate::features::voice_call::di::commands::toggle_mute,
-            crate::features::voice_call::di::commands::toggle_noise_suppression,
-            crate::features::voice_call::di::commands::enumerate_input_devices,
-            crate::features::voice_call::di::commands::enumerate_output_devices,
-            crate::features::voice_call::di::commands::enumerate_audio_devices,
-            crate::features::voice_call::di::commands::select_input_device,
-            crate::features::voice_call::di::commands::select_output_device,
-            crate::features::voice_call::di::commands::join_conference,
-            crate::features::voice_call::di::commands::leave_conference,
-            crate::features::voice_call::di::commands::send_video_signaling,
-        ])
-        .run(tauri::generate_context!())
-        .context("error while running tauri application")?;
-    Ok(())
 }
 
 /// 构建 HTTP 响应（用于自定义 scheme handler）。
@@ -499,57 +373,138 @@ fn build_http_response(
     }
 }
 
+/// 需要固定取值的后缀（文本类型带 charset；wasm/媒体类型保证 WebView 可流式加载与拖动进度）。
+const EXPLICIT_MIME_TYPES: &[(&str, &str)] = &[
+    ("html", "text/html; charset=utf-8"),
+    ("htm", "text/html; charset=utf-8"),
+    ("js", "text/javascript; charset=utf-8"),
+    ("mjs", "text/javascript; charset=utf-8"),
+    ("css", "text/css; charset=utf-8"),
+    ("json", "application/json; charset=utf-8"),
+    ("map", "application/json; charset=utf-8"),
+    ("txt", "text/plain; charset=utf-8"),
+    ("wasm", "application/wasm"),
+    ("svg", "image/svg+xml"),
+    ("png", "image/png"),
+    ("jpg", "image/jpeg"),
+    ("jpeg", "image/jpeg"),
+    ("webp", "image/webp"),
+    ("gif", "image/gif"),
+    ("mp4", "video/mp4"),
+    ("webm", "video/webm"),
+    ("pdf", "application/pdf"),
+    ("woff", "font/woff"),
+    ("woff2", "font/woff2"),
+    ("ttf", "font/ttf"),
+];
+
+/// 默认 MIME 类型。
+const OCTET_STREAM: &str = "application/octet-stream";
+
 /// 根据文件后缀推导 MIME 类型。
 ///
 /// # 参数
 /// - `path`: 文件路径（通常为 URL 的相对路径）。
 ///
 /// # 返回值
-/// MIME 字符串（默认 `application/octet-stream`）。
+/// MIME 字符串：先查 [`EXPLICIT_MIME_TYPES`]，再查 `mime_guess`（只接受被动类型，见 [`is_passive_mime`]），
+/// 都未命中时为 `application/octet-stream`。
 fn mime_by_path(path: &str) -> &'static str {
-    let p = path.to_lowercase();
-    if p.ends_with(".js") || p.ends_with(".mjs") {
-        return "text/javascript; charset=utf-8";
-    }
-    if p.ends_with(".css") {
-        return "text/css; charset=utf-8";
-    }
-    if p.ends_with(".json") {
-        return "application/json; charset=utf-8";
-    }
-    if p.ends_with(".svg") {
-        return "image/svg+xml";
-    }
-    if p.ends_with(".png") {
-        return "image/png";
-    }
-    if p.ends_with(".jpg") || p.ends_with(".jpeg") {
-        return "image/jpeg";
-    }
-    if p.ends_with(".webp") {
-        return "image/webp";
-    }
-    if p.ends_with(".woff") {
-        return "font/woff";
-    }
-    if p.ends_with(".woff2") {
-        return "font/woff2";
-    }
-    if p.ends_with(".ttf") {
-        return "font/ttf";
+    let Some(ext) = std::path::Path::new(path)
+        .extension()
+        .and_then(|e| e.to_str())
+        .map(str::to_ascii_lowercase)
+    else {
+        return OCTET_STREAM;
+    };
+    if let Some((_, mime)) = EXPLICIT_MIME_TYPES.iter().find(|(e, _)| *e == ext) {
+        return mime;
     }
-    "application/octet-stream"
+    mime_guess::from_ext(&ext)
+        .first_raw()
+        .filter(|mime| is_passive_mime(mime))
+        .unwrap_or(OCTET_STREAM)
+}
+
+/// 未显式列出的后缀只接受不能执行脚本的类型（音视频/字体/位图）；
+/// `text/*`、`application/*` 与 `*+xml` 等可能被当作脚本或文档的类型一律按 `application/octet-stream` 返回。
+fn is_passive_mime(mime: &str) -> bool {
+    matches!(
+        mime.split('/').next(),
+        Some("audio" | "font" | "image" | "video")
+    ) && !plugin_store::is_document_mime(mime)
+}
+
+/// 推导本地文件的 MIME 类型：后缀无法识别时按文件头嗅探（`infer`），仍未知时记录一次诊断日志。
+///
+/// # 参数
+/// - `file_path`: 已通过校验的本地文件。
+/// - `rel_path`: 请求中的相对路径（用于后缀判断与日志）。
+fn mime_for_file(file_path: &std::path::Path, rel_path: &str) -> &'static str {
+    let by_path = mime_by_path(rel_path);
+    if by_path != OCTET_STREAM {
+        return by_path;
+    }
+    if let Some(kind) = infer::get_from_path(file_path)
+        .ok()
+        .flatten()
+        .filter(|kind| is_passive_mime(kind.mime_type()))
+    {
+        return kind.mime_type();
+    }
+    static REPORTED: std::sync::Mutex<Vec<String>> = std::sync::Mutex::new(Vec::new());
+    let ext = std::path::Path::new(rel_path)
+        .extension()
+        .map(|e| e.to_string_lossy().to_ascii_lowercase())
+        .unwrap_or_default();
+    let mut reported = REPORTED.lock().unwrap_or_else(|p| p.into_inner());
+    if !reported.contains(&ext) {
+        tracing::debug!(action = "app_scheme_mime_unknown", path = %rel_path, ext = %ext);
+        reported.push(ext);
+    }
+    OCTET_STREAM
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn command_registry_has_no_duplicates() {
+        let registry = command_registry();
+        assert!(
+            registry.duplicates().is_empty(),
+            "{:?}",
+            registry.duplicates()
+        );
+        assert!(!registry.is_empty());
+    }
+
+    #[test]
+    fn command_registry_covers_frontend_commands() {
+        // 前端 TAURI_COMMANDS 中的每个命令都必须在注册表中存在，防止入口漂移。
+        let source = include_str!("../../../src/shared/tauri/commands.ts");
+        let pattern = regex::Regex::new(r#":\s*"([a-z0-9_]+)""#).expect("valid regex");
+        let registry = command_registry();
+        let missing: Vec<&str> = pattern
+            .captures_iter(source)
+            .filter_map(|c| c.get(1).map(|m| m.as_str()))
+            .filter(|name| !registry.contains(name))
+            .collect();
+        assert!(missing.is_empty(), "unregistered commands: {missing:?}");
+    }
+
     #[test]
     fn mime_by_path_js() {
         assert_eq!(mime_by_path("bundle.js"), "text/javascript; charset=utf-8");
     }
 
+    #[test]
+    fn mime_by_path_html() {
+        assert_eq!(mime_by_path("index.html"), "text/html; charset=utf-8");
+        assert_eq!(mime_by_path("legacy.htm"), "text/html; charset=utf-8");
+    }
+
     #[test]
     fn mime_by_path_css() {
         assert_eq!(mime_by_path("style.css"), "text/css; charset=utf-8");
@@ -581,6 +536,21 @@ mod tests {
         assert_eq!(mime_by_path("img.webp"), "image/webp");
     }
 
+    #[test]
+    fn mime_by_path_gif() {
+        assert_eq!(mime_by_path("party.gif"), "image/gif");
+    }
+
+    #[test]
+    fn mime_by_path_media_and_wasm() {
+        assert_eq!(mime_by_path("module.wasm"), "application/wasm");
+        assert_eq!(mime_by_path("clip.mp4"), "video/mp4");
+        assert_eq!(mime_by_path("clip.webm"), "video/webm");
+        assert_eq!(mime_by_path("manual.pdf"), "application/pdf");
+        // 未列出的后缀回退到 mime_guess。
+        assert_eq!(mime_by_path("song.mp3"), "audio/mpeg");
+    }
+
     #[test]
     fn mime_by_path_woff() {
         assert_eq!(mime_by_path("font.woff"), "font/woff");
@@ -601,6 +571,15 @@ mod tests {
         assert_eq!(mime_by_path("IMAGE.PNG"), "image/png");
     }
 
+    #[test]
+    fn mime_by_path_serves_unlisted_active_types_as_octet_stream() {
+        assert_eq!(mime_by_path("page.xhtml"), "application/octet-stream");
+        assert_eq!(mime_by_path("feed.xml"), "application/octet-stream");
+        assert_eq!(mime_by_path("run.sh"), "application/octet-stream");
+        assert_eq!(mime_by_path("notes.md"), "application/octet-stream");
+        assert_eq!(mime_by_path("photo.bmp"), "image/bmp");
+    }
+
     #[test]
     fn mime_by_path_unknown() {
         assert_eq!(mime_by_path("file.xyz"), "application/octet-stream");
@@ -683,8 +662,9 @@ fn percent_decode(input: &str) -> String {
 
 /// 处理 `app://` scheme 请求。
 ///
-/// 当前仅支持插件静态资源：
-/// `app://plugins/<server_id>/<plugin_id>/<version>/<path>`
+/// 支持：
+/// - 插件静态资源：`app://plugins/<server_id>/<plugin_id>/<version>/<path>`
+/// - 服务端自定义表情：`app://emoji/<sha256>.<ext>`
 ///
 /// # 参数
 /// - `req`: Tauri scheme 请求。
@@ -695,18 +675,34 @@ fn handle_app_scheme(
     req: tauri::http::Request<Vec<u8>>,
 ) -> Result<tauri::http::Response<Vec<u8>>, anyhow::Error> {
     let uri = req.uri().to_string();
-    // 只处理插件静态资源请求：`app://plugins/<server_id>/<plugin_id>/<version>/<path>`
-    if !uri.starts_with("app://plugins/") {
+    // 服务端自定义表情：`app://emoji/<sha256>.<ext>`（资源名严格校验，见 `emoji::asset_store`）
+    if let Some(name) = uri.strip_prefix(asset_store::ASSET_URL_PREFIX) {
+        let name = name.split(['?', '#']).next().unwrap_or(name);
+        let Some((file_path, _)) = asset_store::resolve(name) else {
+            return Ok(build_http_response(404, None, Vec::new()));
+        };
+        return asset_response::respond_with_file(&file_path, mime_by_path(name), req.headers());
+    }
+    // 插件静态资源请求：`app://plugins/<server_id>/<plugin_id>/<version>/<path>`
+    let Some(rest) = uri.strip_prefix("app://plugins/") else {
         return Ok(build_http_response(404, None, Vec::new()));
-    }
-    let rest = &uri["app://plugins/".len()..];
-    let path_only = rest
-        .split('?')
-        .next()
-        .unwrap_or(rest)
-        .split('#')
-        .next()
-        .unwrap_or(rest);
+    };
+    let path_only = rest.split(['?', '#']).next().unwrap_or(rest);
+    serve_plugin_asset(path_only, req.headers())
+}
+
+/// 读取插件静态资源（`app://plugins` 与插件本地 HTTP 服务共用）。
+///
+/// # 参数
+/// - `path_only`: `<server_id>/<plugin_id>/<version>/<path>`（不含 query/fragment，可含 `%xx`）。
+/// - `headers`: 请求头（用于 Range 与条件请求，见 `asset_response`）。
+///
+/// # 返回值
+/// HTTP 响应（200/206/304/400/416）；文件不存在或越界时返回 `Err`。
+pub(crate) fn serve_plugin_asset(
+    path_only: &str,
+    headers: &tauri::http::HeaderMap,
+) -> Result<tauri::http::Response<Vec<u8>>, anyhow::Error> {
     let segs: Vec<&str> = path_only.split('/').filter(|s| !s.is_empty()).collect();
     if segs.len() < 4 {
         return Ok(build_http_response(400, None, Vec::new()));
@@ -724,68 +720,20 @@ fn handle_app_scheme(
     let file_path = plugin_store::resolve_app_plugins_canonical_file_path(
         &server_id, &plugin_id, &version, &rel_path,
     )?;
-    let bytes = std::fs::read(&file_path)
-        .with_context(|| format!("Failed to read plugin file: {}", file_path.display()))?;
-
-    Ok(build_http_response(
-        200,
-        Some(mime_by_path(&rel_path)),
-        bytes,
-    ))
-}
-
-/// 读取主窗口当前的物理 bounds。
-///
-/// 当窗口最小化或不可见时 outer_size 可能为 0，跳过保存以避免坏值。
-fn current_main_bounds(window: &tauri::Window) -> Option<WindowBounds> {
-    if !window.is_visible().unwrap_or(false) {
-        return None;
-    }
-    let size = window.outer_size().ok()?;
-    let pos = window.outer_position().ok()?;
-    let scale = window.scale_factor().unwrap_or(1.0);
-    let width = (f64::from(size.width) / scale).round().max(1.0) as u32;
-    let height = (f64::from(size.height) / scale).round().max(1.0) as u32;
-    let x = (f64::from(pos.x) / scale).round() as i32;
-    let y = (f64::from(pos.y) / scale).round() as i32;
-    Some(WindowBounds {
-        width,
-        height,
-        x,
-        y,
-    })
-}
-
-/// 校验 bounds 是否落在任意可用显示器的工作区内（防止越界）。
-///
-/// 允许较小偏差（窗口标题栏等可能略出屏）。
-fn is_bounds_within_monitors<R: tauri::Runtime>(
-    app: &tauri::AppHandle<R>,
-    b: &WindowBounds,
-) -> bool {
-    let Ok(monitors) = app.available_monitors() else {
-        // 拿不到显示器列表时不做校验，默认通过。
-        return true;
-    };
-    if monitors.is_empty() {
-        return true;
-    }
-    for m in monitors {
-        let mpos = m.position();
-        let msize = m.size();
-        let mx0 = mpos.x;
-        let my0 = mpos.y;
-        let mx1 = mpos.x + msize.width as i32;
-        let my1 = mpos.y + msize.height as i32;
-        let wx1 = b.x + b.width as i32;
-        let wy1 = b.y + b.height as i32;
-        // 至少 64x64 像素落在某显示器工作区内。
-        let overlap_w = (wx1.min(mx1) - b.x.max(mx0)).max(0);
-        let overlap_h = (wy1.min(my1) - b.y.max(my0)).max(0);
-        if overlap_w >= 64 && overlap_h >= 64 {
-            return true;
-        }
+    let content_type = mime_for_file(&file_path, &rel_path);
+    let mut response = asset_response::respond_with_file(&file_path, content_type, headers)?;
+    // 可渲染为文档的资源（HTML/XHTML/SVG/XML）附带 CSP（按插件权限放开所属服务器 origin），
+    // 把插件内 XSS 限制在插件资源内；以 `import()` 载入主窗口的模块不受此约束。
+    if plugin_store::is_document_mime(content_type)
+        && let Ok(csp) = tauri::http::HeaderValue::from_str(&plugin_store::plugin_html_csp(
+            &server_id, &plugin_id, &version,
+        ))
+    {
+        response
+            .headers_mut()
+            .insert(tauri::http::header::CONTENT_SECURITY_POLICY, csp);
     }
-    false
+    Ok(response)
 }
+
 pub mod commands;
@@ -0,0 +1,382 @@
+//! 首次运行引导（onboarding）状态机。
+//!
+//! 说明：
+//! - 引导步骤按固定顺序推进，已完成的步骤写入 system DB `onboarding_steps` 表；
+//! - 当前步骤为第一个未完成的步骤；全部完成后 `current_step` 为空；
+//! - 首次查询状态时执行一次准备工作：创建默认目录、初始化 system DB（含迁移）、
+//!   导入旧版 `config.json` / `plugins.json`；准备结果在进程内缓存。
+//!
+//! 约定：注释中文，日志英文（tracing）。
+
+use std::path::Path;
+use std::sync::OnceLock;
+
+use anyhow::Context;
+use sea_orm::ConnectionTrait;
+use serde::{Deserialize, Serialize};
+
+use crate::features::plugins::data::plugin_ports::PluginInstallStorePortAdapter;
+use crate::features::plugins::usecases::plugin_usecases;
+use crate::features::settings::data::config_store;
+use crate::features::settings::data::config_store_port_adapter::ConfigStorePortAdapter;
+use crate::features::settings::usecases::config_usecases;
+use crate::shared::db::commands::RawStatement;
+use crate::shared::error::{CommandResult, command_error, to_command_error};
+
+/// 引导状态所在的 DB key（应用级 system DB）。
+const SYSTEM_DB_KEY: &str = "system";
+
+/// 应用数据目录下需要预先创建的子目录。
+pub(crate) const DEFAULT_DIRECTORIES: &[&str] =
+    &["db", "plugins", "temp_files", "logs", "screenshots"];
+
+/// 旧插件加载器留下的清单文件名。
+const LEGACY_PLUGINS_FILE: &str = "plugins.json";
+
+/// 引导步骤（按推进顺序排列）。
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum OnboardingStep {
+    /// 欢迎页。
+    Welcome,
+    /// 确认数据目录。
+    DataDirectory,
+    /// 添加第一个服务器。
+    AddServer,
+    /// 通知权限与偏好。
+    Notifications,
+}
+
+impl OnboardingStep {
+    /// 全部步骤（推进顺序）。
+    pub const ALL: [Self; 4] = [
+        Self::Welcome,
+        Self::DataDirectory,
+        Self::AddServer,
+        Self::Notifications,
+    ];
+
+    /// 数据库中保存的步骤名。
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Welcome => "welcome",
+            Self::DataDirectory => "data_directory",
+            Self::AddServer => "add_server",
+            Self::Notifications => "notifications",
+        }
+    }
+
+    /// 解析步骤名；未知值返回 `None`。
+    pub fn parse(raw: &str) -> Option<Self> {
+        Self::ALL
+            .into_iter()
+            .find(|step| step.as_str() == raw.trim())
+    }
+}
+
+/// 第一个未完成的步骤；全部完成时返回 `None`。
+pub fn next_step(completed: &[OnboardingStep]) -> Option<OnboardingStep> {
+    OnboardingStep::ALL
+        .into_iter()
+        .find(|step| !completed.contains(step))
+}
+
+/// 完成某一步骤前的校验结果。
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StepTransition {
+    /// 该步骤即为当前步骤，可以完成。
+    Advance,
+    /// 该步骤已完成（重复调用视为成功）。
+    AlreadyCompleted,
+    /// 前面仍有未完成的步骤。
+    OutOfOrder { expected: OnboardingStep },
+}
+
+/// 校验 `step` 能否在 `completed` 的基础上完成。
+pub fn check_transition(completed: &[OnboardingStep], step: OnboardingStep) -> StepTransition {
+    if completed.contains(&step) {
+        return StepTransition::AlreadyCompleted;
+    }
+    match next_step(completed) {
+        Some(expected) if expected != step => StepTransition::OutOfOrder { expected },
+        _ => StepTransition::Advance,
+    }
+}
+
+/// 首次运行准备工作的结果。
+#[derive(Debug, Clone, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct OnboardingSetupReport {
+    /// 本次新建的目录（相对应用数据目录）。
+    pub created_directories: Vec<String>,
+    /// 是否把旧版 `config.json` 迁移成了新配置格式。
+    pub legacy_config_imported: bool,
+    /// 从旧版 `plugins.json` 导入的插件数量。
+    pub legacy_plugins_imported: usize,
+}
+
+/// 引导状态（Rust -> 前端）。
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct OnboardingState {
+    /// 尚未完成任何步骤。
+    pub first_run: bool,
+    /// 当前步骤；全部完成时为空。
+    pub current_step: Option<OnboardingStep>,
+    /// 已完成的步骤（推进顺序）。
+    pub completed_steps: Vec<OnboardingStep>,
+    /// 是否全部完成。
+    pub completed: bool,
+    pub setup: OnboardingSetupReport,
+}
+
+impl OnboardingState {
+    fn new(completed_steps: Vec<OnboardingStep>, setup: OnboardingSetupReport) -> Self {
+        let current_step = next_step(&completed_steps);
+        Self {
+            first_run: completed_steps.is_empty(),
+            current_step,
+            completed: current_step.is_none(),
+            completed_steps,
+            setup,
+        }
+    }
+}
+
+fn now_ms() -> i64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_millis() as i64
+}
+
+/// 创建缺失的默认目录，返回新建的目录名。
+async fn ensure_default_directories(app_data: &Path) -> anyhow::Result<Vec<String>> {
+    let mut created = Vec::new();
+    for name in DEFAULT_DIRECTORIES {
+        let dir = app_data.join(name);
+        if tokio::fs::try_exists(&dir).await.unwrap_or(false) {
+            continue;
+        }
+        tokio::fs::create_dir_all(&dir)
+            .await
+            .with_context(|| format!("Failed to create directory: {}", dir.display()))?;
+        created.push(name.to_string());
+    }
+    Ok(created)
+}
+
+/// 检测到旧版 `config.json` 时通过重新加载触发配置迁移。
+async fn import_legacy_config() -> anyhow::Result<bool> {
+    if !config_store::has_legacy_config().await {
+        return Ok(false);
+    }
+    config_usecases::reload_config(ConfigStorePortAdapter::shared()).await?;
+    Ok(true)
+}
+
+/// 检测到旧版 `plugins.json` 时导入插件（导入后文件会被改名，不会重复执行）。
+async fn import_legacy_plugins(app_data: &Path) -> anyhow::Result<usize> {
+    if !tokio::fs::try_exists(app_data.join(LEGACY_PLUGINS_FILE))
+        .await
+        .unwrap_or(false)
+    {
+        return Ok(0);
+    }
+    plugin_usecases::plugins_import_legacy(PluginInstallStorePortAdapter::shared()).await
+}
+
+async fn run_setup() -> CommandResult<OnboardingSetupReport> {
+    let app_data = crate::shared::app_data_dir::get_app_data_dir()
+        .map_err(|e| to_command_error("APP_DATA_DIR", "error.app_data_dir", e))?;
+    let created_directories = ensure_default_directories(&app_data).await.map_err(|e| {
+        to_command_error(
+            "ONBOARDING_SETUP_FAILED",
+            "error.onboarding_setup_failed",
+            e,
+        )
+    })?;
+
+    crate::shared::db::commands::db_init(crate::shared::db::commands::DbInitRequest {
+        key: SYSTEM_DB_KEY.to_string(),
+        path: None,
+        kind: Some(SYSTEM_DB_KEY.to_string()),
+    })
+    .await?;
+
+    // 旧数据导入失败不阻断引导，下次启动会再次检测。
+    let legacy_config_imported = import_legacy_config().await.unwrap_or_else(|e| {
+        tracing::warn!(action = "app_onboarding_legacy_config_failed", error = %e);
+        false
+    });
+    let legacy_plugins_imported = import_legacy_plugins(&app_data).await.unwrap_or_else(|e| {
+        tracing::warn!(action = "app_onboarding_legacy_plugins_failed", error = %e);
+        0
+    });
+
+    tracing::info!(
+        action = "app_onboarding_setup_completed",
+        created_directories = ?created_directories,
+        legacy_config_imported,
+        legacy_plugins_imported
+    );
+    Ok(OnboardingSetupReport {
+        created_directories,
+        legacy_config_imported,
+        legacy_plugins_imported,
+    })
+}
+
+/// 每个进程只执行一次准备工作；失败时下次调用重试。
+async fn ensure_setup() -> CommandResult<OnboardingSetupReport> {
+    static SETUP: OnceLock<tokio::sync::Mutex<Option<OnboardingSetupReport>>> = OnceLock::new();
+    let mut guard = SETUP
+        .get_or_init(|| tokio::sync::Mutex::new(None))
+        .lock()
+        .await;
+    if let Some(report) = guard.as_ref() {
+        return Ok(report.clone());
+    }
+    let report = run_setup().await?;
+    *guard = Some(report.clone());
+    Ok(report)
+}
+
+async fn load_completed_steps() -> anyhow::Result<Vec<OnboardingStep>> {
+    let db = crate::shared::db::get_db(SYSTEM_DB_KEY).await?;
+    let rows = db
+        .connection
+        .query_all(&RawStatement::new(
+            "SELECT step FROM onboarding_steps",
+            Vec::new(),
+        ))
+        .await
+        .context("ONBOARDING_LOAD_FAILED")?;
+    let stored: Vec<String> = rows
+        .iter()
+        .filter_map(|row| row.try_get("", "step").ok())
+        .collect();
+    Ok(OnboardingStep::ALL
+        .into_iter()
+        .filter(|step| stored.iter().any(|s| s == step.as_str()))
+        .collect())
+}
+
+async fn current_state() -> CommandResult<OnboardingState> {
+    let setup = ensure_setup().await?;
+    let completed = load_completed_steps().await.map_err(|e| {
+        to_command_error("ONBOARDING_LOAD_FAILED", "error.onboarding_load_failed", e)
+    })?;
+    Ok(OnboardingState::new(completed, setup))
+}
+
+/// 获取首次运行引导状态。
+///
+/// # 说明
+/// 首次调用时会创建默认目录、初始化 system DB 并导入旧版配置/插件数据。
+///
+/// # 返回值
+/// - `Ok(OnboardingState)`：当前引导状态与准备结果。
+/// - `Err(CommandError)`：数据目录不可用、DB 初始化或读取失败。
+#[tauri::command]
+pub async fn get_onboarding_state() -> CommandResult<OnboardingState> {
+    current_state().await
+}
+
+/// 完成一个引导步骤。
+///
+/// # 参数
+/// - `step`：步骤名（`welcome` / `data_directory` / `add_server` / `notifications`）。
+///
+/// # 返回值
+/// - `Ok(OnboardingState)`：更新后的引导状态；重复完成已完成的步骤不报错。
+/// - `Err(CommandError)`：步骤未知、前面仍有未完成的步骤或写入失败。
+#[tauri::command]
+pub async fn complete_onboarding_step(step: String) -> CommandResult<OnboardingState> {
+    let step = OnboardingStep::parse(&step)
+        .ok_or_else(|| command_error("ONBOARDING_STEP_UNKNOWN", "error.onboarding_step_unknown"))?;
+    let state = current_state().await?;
+    match check_transition(&state.completed_steps, step) {
+        StepTransition::AlreadyCompleted => return Ok(state),
+        StepTransition::OutOfOrder { expected } => {
+            tracing::warn!(
+                action = "app_onboarding_step_out_of_order",
+                step = step.as_str(),
+                expected = expected.as_str()
+            );
+            return Err(command_error(
+                "ONBOARDING_STEP_OUT_OF_ORDER",
+                "error.onboarding_step_out_of_order",
+            ));
+        }
+        StepTransition::Advance => {}
+    }
+
+    let db = crate::shared::db::get_db(SYSTEM_DB_KEY)
+        .await
+        .map_err(|e| {
+            to_command_error("ONBOARDING_SAVE_FAILED", "error.onboarding_save_failed", e)
+        })?;
+    db.connection
+        .execute(&RawStatement::new(
+            "INSERT OR IGNORE INTO onboarding_steps (step, completed_at) VALUES (?, ?)",
+            vec![step.as_str().into(), now_ms().into()],
+        ))
+        .await
+        .map_err(|e| {
+            to_command_error("ONBOARDING_SAVE_FAILED", "error.onboarding_save_failed", e)
+        })?;
+    tracing::info!(
+        action = "app_onboarding_step_completed",
+        step = step.as_str()
+    );
+
+    let mut completed_steps = state.completed_steps;
+    completed_steps.push(step);
+    Ok(OnboardingState::new(completed_steps, state.setup))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn steps_advance_in_order() {
+        assert_eq!(next_step(&[]), Some(OnboardingStep::Welcome));
+        assert_eq!(
+            check_transition(&[], OnboardingStep::AddServer),
+            StepTransition::OutOfOrder {
+                expected: OnboardingStep::Welcome
+            }
+        );
+        let done = [OnboardingStep::Welcome];
+        assert_eq!(
+            check_transition(&done, OnboardingStep::Welcome),
+            StepTransition::AlreadyCompleted
+        );
+        assert_eq!(
+            check_transition(&done, OnboardingStep::DataDirectory),
+            StepTransition::Advance
+        );
+
+        let state = OnboardingState::new(
+            OnboardingStep::ALL.to_vec(),
+            OnboardingSetupReport::default(),
+        );
+        assert!(state.completed);
+        assert!(!state.first_run);
+        assert_eq!(state.current_step, None);
+    }
+
+    #[test]
+    fn step_names_round_trip() {
+        for step in OnboardingStep::ALL {
+            assert_eq!(OnboardingStep::parse(step.as_str()), Some(step));
+            assert_eq!(
+                serde_json::to_value(step).expect("serialize"),
+                serde_json::Value::String(step.as_str().to_string())
+            );
+        }
+        assert_eq!(OnboardingStep::parse("unknown"), None);
+    }
+}
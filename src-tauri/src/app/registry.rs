@@ -0,0 +1,115 @@
+//! 命令注册表：按 feature 组合 Tauri invoke handler。
+//!
+//! 说明：
+//! - `tauri::Builder::invoke_handler` 只能设置一次，且 `generate_handler!` 生成的闭包会消费
+//!   `Invoke`，无法直接串联；因此每组命令同时记录命令名，由注册表按命令名分发；
+//! - 各 feature 在自己的 `di` 模块实现 [`CommandRegistration`]，`app` 只负责按顺序组合，
+//!   新增命令只需改动所属 feature，避免入口之间的命令列表漂移。
+//!
+//! 约定：注释中文，日志英文（tracing）。
+
+use std::collections::HashMap;
+
+use tauri::Wry;
+use tauri::ipc::Invoke;
+
+type InvokeHandler = Box<dyn Fn(Invoke<Wry>) -> bool + Send + Sync>;
+
+/// 一组由同一个 `generate_handler!` 生成的命令（命令名 + handler）。
+pub struct CommandSet {
+    names: &'static [&'static str],
+    handler: InvokeHandler,
+}
+
+impl CommandSet {
+    /// 创建命令组；通常通过 [`command_set!`] 调用，保证命令名与 handler 一致。
+    pub fn new<F>(names: &'static [&'static str], handler: F) -> Self
+    where
+        F: Fn(Invoke<Wry>) -> bool + Send + Sync + 'static,
+    {
+        Self {
+            names,
+            handler: Box::new(handler),
+        }
+    }
+}
+
+/// 由 feature 实现：向注册表登记自身对外暴露的命令。
+pub trait CommandRegistration {
+    fn register(registry: &mut CommandRegistry);
+}
+
+/// 命令注册表。
+#[derive(Default)]
+pub struct CommandRegistry {
+    sets: Vec<CommandSet>,
+    index: HashMap<&'static str, usize>,
+    duplicates: Vec<&'static str>,
+}
+
+impl CommandRegistry {
+    /// 登记一组命令；重名命令保留先登记的一方并记录到 `duplicates`。
+    pub fn add(&mut self, set: CommandSet) {
+        let slot = self.sets.len();
+        for name in set.names {
+            if self.index.contains_key(name) {
+                tracing::error!(action = "app_command_duplicate_registration", command = %name);
+                self.duplicates.push(name);
+                continue;
+            }
+            self.index.insert(name, slot);
+        }
+        self.sets.push(set);
+    }
+
+    /// 链式登记一个 feature。
+    pub fn with<T: CommandRegistration>(mut self) -> Self {
+        T::register(&mut self);
+        self
+    }
+
+    /// 是否已登记某个命令。
+    pub fn contains(&self, name: &str) -> bool {
+        self.index.contains_key(name)
+    }
+
+    /// 已登记的命令数。
+    pub fn len(&self) -> usize {
+        self.index.len()
+    }
+
+    /// 是否没有登记任何命令。
+    pub fn is_empty(&self) -> bool {
+        self.index.is_empty()
+    }
+
+    /// 重复登记的命令名。
+    pub fn duplicates(&self) -> &[&'static str] {
+        &self.duplicates
+    }
+
+    /// 转换为 `invoke_handler` 可用的分发闭包（未登记的命令返回 `false`，由 Tauri 报告未找到）。
+    pub fn into_handler(self) -> impl Fn(Invoke<Wry>) -> bool + Send + Sync + 'static {
+        let Self { sets, index, .. } = self;
+        move |invoke: Invoke<Wry>| {
+            let Some(&slot) = index.get(invoke.message.command()) else {
+                return false;
+            };
+            (sets[slot].handler)(invoke)
+        }
+    }
+}
+
+/// 以模块路径 + 命令名列表生成 [`CommandSet`]。
+///
+/// 用法：`command_set!(crate::features::tray::di::commands => [set_tray_locale])`。
+macro_rules! command_set {
+    ($($module:ident)::+ => [$($command:ident),* $(,)?]) => {{
+        use $($module)::+ as commands_module;
+        $crate::app::registry::CommandSet::new(
+            &[$(stringify!($command)),*],
+            ::tauri::generate_handler![$(commands_module::$command),*],
+        )
+    }};
+}
+pub(crate) use command_set;
@@ -0,0 +1,84 @@
+//! 应用运行时信息（供前端“关于”页与诊断使用）。
+//!
+//! 约定：注释中文，日志英文（tracing）。
+
+use serde::Serialize;
+
+use crate::shared::error::{CommandResult, to_command_error};
+use crate::shared::feature_flags::{FeatureFlags, current_feature_flags};
+
+/// 后端运行时信息（Rust -> 前端）。
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RuntimeInfo {
+    /// 应用版本（Cargo 包版本）。
+    pub app_version: &'static str,
+    /// 构建时的 git 短哈希；非 git 工作区构建时为 `unknown`。
+    pub git_hash: &'static str,
+    /// Tauri 版本。
+    pub tauri_version: &'static str,
+    /// 系统 WebView 版本；无法获取时为 `None`。
+    pub webview_version: Option<String>,
+    /// 操作系统（`windows` / `macos` / `linux` ...）。
+    pub os: &'static str,
+    /// CPU 架构（`x86_64` / `aarch64` ...）。
+    pub arch: &'static str,
+    /// 是否为桌面端构建（移动端不提供托盘、多窗口与截图命令）。
+    pub desktop: bool,
+    /// 是否为 debug 构建。
+    pub debug: bool,
+    /// 是否允许 `mock://` 传输（仅 debug 构建）。
+    pub mock_transport: bool,
+    /// 当前生效的 feature flag 表。
+    pub feature_flags: FeatureFlags,
+    /// 是否运行在便携模式（数据固定写在可执行文件旁）。
+    pub portable: bool,
+    /// 当前数据目录。
+    pub data_dir: String,
+}
+
+/// 客户端身份（版本与平台），`get_runtime_info` 与网络请求头策略共用。
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ClientIdentity {
+    pub app_version: &'static str,
+    pub git_hash: &'static str,
+    pub os: &'static str,
+    pub arch: &'static str,
+}
+
+/// 当前构建的客户端身份。
+pub fn client_identity() -> ClientIdentity {
+    ClientIdentity {
+        app_version: env!("CARGO_PKG_VERSION"),
+        git_hash: option_env!("CARRYPIGEON_GIT_HASH").unwrap_or("unknown"),
+        os: std::env::consts::OS,
+        arch: std::env::consts::ARCH,
+    }
+}
+
+/// 获取后端运行时信息。
+#[tauri::command]
+pub async fn get_runtime_info() -> CommandResult<RuntimeInfo> {
+    let data_dir = crate::shared::app_data_dir::get_app_data_dir()
+        .map_err(|e| to_command_error("APP_DATA_DIR", "error.app_data_dir", e))?;
+    let webview_version = tauri::webview_version()
+        .map_err(|e| {
+            tracing::warn!(action = "app_runtime_info_webview_version_failed", error = %e);
+        })
+        .ok();
+    let identity = client_identity();
+    Ok(RuntimeInfo {
+        app_version: identity.app_version,
+        git_hash: identity.git_hash,
+        tauri_version: tauri::VERSION,
+        webview_version,
+        os: identity.os,
+        arch: identity.arch,
+        desktop: cfg!(desktop),
+        debug: cfg!(debug_assertions),
+        mock_transport: cfg!(debug_assertions),
+        feature_flags: current_feature_flags().await,
+        portable: crate::shared::paths::is_portable_mode(),
+        data_dir: data_dir.to_string_lossy().to_string(),
+    })
+}
@@ -0,0 +1,458 @@
+//! 数据完整性自检与修复。
+//!
+//! 说明：
+//! - `run_self_check` 只读检查：数据目录结构、已注册数据库（`PRAGMA quick_check`）、
+//!   本地插件清单与目录是否一致、`config.json` 能否按当前 schema 解析；
+//! - 每个问题附带建议的修复动作，前端确认后把问题列表交给 `repair` 执行；
+//! - 修复动作的目标都在后端重新校验（目录名白名单、已注册的 DB key、插件路径段），
+//!   不信任前端回传的任意路径。
+//!
+//! 约定：注释中文，日志英文（tracing）。
+
+use std::path::Path;
+
+use anyhow::Context;
+use sea_orm::ConnectionTrait;
+use serde::{Deserialize, Serialize};
+
+use crate::app::onboarding::DEFAULT_DIRECTORIES;
+use crate::features::plugins::data::plugin_store;
+use crate::features::settings::data::config_store::{config_file_path, is_legacy_config};
+use crate::features::settings::data::config_store_port_adapter::ConfigStorePortAdapter;
+use crate::features::settings::domain::settings_schema::parse_settings_import_envelope;
+use crate::features::settings::usecases::config_usecases;
+use crate::shared::db::commands::RawStatement;
+use crate::shared::error::{CommandResult, to_command_error};
+
+/// 单个数据库最多返回的 quick_check 问题条数。
+const MAX_DB_PROBLEMS: usize = 10;
+
+/// 问题所属的检查项。
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SelfCheckCategory {
+    DataDirectory,
+    Database,
+    Plugin,
+    Config,
+}
+
+/// 问题严重程度。
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SelfCheckSeverity {
+    /// 功能可用，但建议修复。
+    Warning,
+    /// 相关功能已无法正常工作。
+    Error,
+}
+
+/// 建议的修复动作。
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(
+    tag = "action",
+    rename_all = "snake_case",
+    rename_all_fields = "camelCase"
+)]
+pub enum RepairAction {
+    /// 创建缺失的默认目录（`name` 必须是默认目录之一）。
+    CreateDirectory { name: String },
+    /// 对数据库执行 `REINDEX`（可修复仅索引损坏的情况）。
+    ReindexDatabase { key: String },
+    /// 禁用插件并标记为 failed（当前版本损坏时）。
+    DisablePlugin {
+        server_id: String,
+        plugin_id: String,
+    },
+    /// 删除损坏的非当前版本。
+    RemovePluginVersion {
+        server_id: String,
+        plugin_id: String,
+        version: String,
+    },
+    /// 把旧版 `config.json` 迁移为当前格式。
+    MigrateConfig,
+    /// 备份损坏的 `config.json` 并重置为默认配置。
+    ResetConfig,
+}
+
+/// 自检发现的问题。
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SelfCheckIssue {
+    pub category: SelfCheckCategory,
+    pub severity: SelfCheckSeverity,
+    /// 问题对象（目录名、DB key、`server_id/plugin_id@version` 或文件名）。
+    pub target: String,
+    /// 问题描述（英文，便于检索）。
+    pub message: String,
+    /// 建议的修复动作；为空表示需要用户手动处理。
+    pub repair: Option<RepairAction>,
+}
+
+/// 自检报告。
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SelfCheckReport {
+    pub checked_at: i64,
+    pub ok: bool,
+    pub databases_checked: usize,
+    pub plugin_versions_checked: usize,
+    pub issues: Vec<SelfCheckIssue>,
+}
+
+/// 单个修复动作的执行结果。
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RepairOutcome {
+    pub target: String,
+    pub action: RepairAction,
+    pub ok: bool,
+    pub error: Option<String>,
+}
+
+fn now_ms() -> i64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_millis() as i64
+}
+
+/// 从 quick_check 输出中提取问题行（唯一一行 `ok` 表示通过）。
+fn quick_check_problems(rows: Vec<String>) -> Vec<String> {
+    rows.into_iter()
+        .filter(|row| !row.trim().eq_ignore_ascii_case("ok"))
+        .take(MAX_DB_PROBLEMS)
+        .collect()
+}
+
+async fn check_directories(app_data: &Path, issues: &mut Vec<SelfCheckIssue>) {
+    for name in DEFAULT_DIRECTORIES {
+        let dir = app_data.join(name);
+        match tokio::fs::metadata(&dir).await {
+            Ok(meta) if meta.is_dir() => {}
+            Ok(_) => issues.push(SelfCheckIssue {
+                category: SelfCheckCategory::DataDirectory,
+                severity: SelfCheckSeverity::Error,
+                target: name.to_string(),
+                message: format!("{} exists but is not a directory", dir.display()),
+                repair: None,
+            }),
+            Err(_) => issues.push(SelfCheckIssue {
+                category: SelfCheckCategory::DataDirectory,
+                severity: SelfCheckSeverity::Warning,
+                target: name.to_string(),
+                message: format!("Missing directory: {}", dir.display()),
+                repair: Some(RepairAction::CreateDirectory {
+                    name: name.to_string(),
+                }),
+            }),
+        }
+    }
+}
+
+async fn quick_check(key: &str) -> anyhow::Result<Vec<String>> {
+    let db = crate::shared::db::get_db(key).await?;
+    let rows = db
+        .connection
+        .query_all(&RawStatement::new("PRAGMA quick_check", Vec::new()))
+        .await
+        .context("PRAGMA quick_check failed")?;
+    Ok(quick_check_problems(
+        rows.iter()
+            .filter_map(|row| row.try_get_by_index::<String>(0).ok())
+            .collect(),
+    ))
+}
+
+async fn check_databases(issues: &mut Vec<SelfCheckIssue>) -> usize {
+    let keys = crate::shared::db::registered_keys().await;
+    for key in &keys {
+        match quick_check(key).await {
+            Ok(problems) if problems.is_empty() => {}
+            Ok(problems) => issues.push(SelfCheckIssue {
+                category: SelfCheckCategory::Database,
+                severity: SelfCheckSeverity::Error,
+                target: key.clone(),
+                message: problems.join("; "),
+                repair: Some(RepairAction::ReindexDatabase { key: key.clone() }),
+            }),
+            Err(e) => issues.push(SelfCheckIssue {
+                category: SelfCheckCategory::Database,
+                severity: SelfCheckSeverity::Error,
+                target: key.clone(),
+                message: e.to_string(),
+                repair: None,
+            }),
+        }
+    }
+    keys.len()
+}
+
+async fn check_plugins(issues: &mut Vec<SelfCheckIssue>) -> usize {
+    let installed = match plugin_store::list_all_installed().await {
+        Ok(installed) => installed,
+        Err(e) => {
+            issues.push(SelfCheckIssue {
+                category: SelfCheckCategory::Plugin,
+                severity: SelfCheckSeverity::Error,
+                target: "plugins".to_string(),
+                message: e.to_string(),
+                repair: None,
+            });
+            return 0;
+        }
+    };
+    let mut checked = 0;
+    // 开发态插件指向本机源目录，由开发者自行维护。
+    for (server_id, state) in installed.into_iter().filter(|(_, s)| !s.dev) {
+        let mut versions = state.installed_versions.clone();
+        if let Some(current) = &state.current_version
+            && !versions.contains(current)
+        {
+            versions.push(current.clone());
+        }
+        for version in versions {
+            checked += 1;
+            let Err(e) =
+                plugin_store::verify_installed_version(&server_id, &state.plugin_id, &version)
+                    .await
+            else {
+                continue;
+            };
+            let is_current = state.current_version.as_deref() == Some(version.as_str());
+            let repair = if is_current {
+                RepairAction::DisablePlugin {
+                    server_id: server_id.clone(),
+                    plugin_id: state.plugin_id.clone(),
+                }
+            } else {
+                RepairAction::RemovePluginVersion {
+                    server_id: server_id.clone(),
+                    plugin_id: state.plugin_id.clone(),
+                    version: version.clone(),
+                }
+            };
+            issues.push(SelfCheckIssue {
+                category: SelfCheckCategory::Plugin,
+                severity: if is_current {
+                    SelfCheckSeverity::Error
+                } else {
+                    SelfCheckSeverity::Warning
+                },
+                target: format!("{server_id}/{}@{version}", state.plugin_id),
+                message: format!("{e:#}"),
+                repair: Some(repair),
+            });
+        }
+    }
+    checked
+}
+
+async fn check_config(issues: &mut Vec<SelfCheckIssue>) {
+    let path = config_file_path();
+    let raw = match tokio::fs::read_to_string(&path).await {
+        Ok(raw) => raw,
+        // 缺失时会在首次读取配置时按默认值创建。
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return,
+        Err(e) => {
+            issues.push(SelfCheckIssue {
+                category: SelfCheckCategory::Config,
+                severity: SelfCheckSeverity::Error,
+                target: "config.json".to_string(),
+                message: format!("Failed to read {}: {e}", path.display()),
+                repair: None,
+            });
+            return;
+        }
+    };
+    let Err(e) = parse_settings_import_envelope(&raw) else {
+        return;
+    };
+    let issue = if is_legacy_config(&raw) {
+        SelfCheckIssue {
+            category: SelfCheckCategory::Config,
+            severity: SelfCheckSeverity::Warning,
+            target: "config.json".to_string(),
+            message: "Config uses the legacy layout".to_string(),
+            repair: Some(RepairAction::MigrateConfig),
+        }
+    } else {
+        SelfCheckIssue {
+            category: SelfCheckCategory::Config,
+            severity: SelfCheckSeverity::Error,
+            target: "config.json".to_string(),
+            message: format!("{e:#}"),
+            repair: Some(RepairAction::ResetConfig),
+        }
+    };
+    issues.push(issue);
+}
+
+/// 执行完整性自检。
+///
+/// # 返回值
+/// - `Ok(SelfCheckReport)`：检查结果；`issues` 为空时 `ok` 为 true。
+/// - `Err(CommandError)`：数据目录不可用。
+#[tauri::command]
+pub async fn run_self_check() -> CommandResult<SelfCheckReport> {
+    let app_data = crate::shared::app_data_dir::get_app_data_dir()
+        .map_err(|e| to_command_error("APP_DATA_DIR", "error.app_data_dir", e))?;
+    let mut issues = Vec::new();
+    check_directories(&app_data, &mut issues).await;
+    let databases_checked = check_databases(&mut issues).await;
+    let plugin_versions_checked = check_plugins(&mut issues).await;
+    check_config(&mut issues).await;
+
+    tracing::info!(
+        action = "app_self_check_completed",
+        databases_checked,
+        plugin_versions_checked,
+        issues = issues.len()
+    );
+    Ok(SelfCheckReport {
+        checked_at: now_ms(),
+        ok: issues.is_empty(),
+        databases_checked,
+        plugin_versions_checked,
+        issues,
+    })
+}
+
+async fn apply_repair(app_data: &Path, action: &RepairAction) -> anyhow::Result<()> {
+    match action {
+        RepairAction::CreateDirectory { name } => {
+            if !DEFAULT_DIRECTORIES.contains(&name.as_str()) {
+                return Err(anyhow::anyhow!("Unknown data directory: {name}"));
+            }
+            tokio::fs::create_dir_all(app_data.join(name)).await?;
+        }
+        RepairAction::ReindexDatabase { key } => {
+            let db = crate::shared::db::get_db(key).await?;
+            db.connection
+                .execute_unprepared("REINDEX")
+                .await
+                .context("REINDEX failed")?;
+            let problems = quick_check(key).await?;
+            if !problems.is_empty() {
+                return Err(anyhow::anyhow!(
+                    "Database still fails quick_check after REINDEX: {}",
+                    problems.join("; ")
+                ));
+            }
+        }
+        RepairAction::DisablePlugin {
+            server_id,
+            plugin_id,
+        } => {
+            plugin_store::mark_broken(server_id, plugin_id, "Disabled by integrity self-check")
+                .await?;
+        }
+        RepairAction::RemovePluginVersion {
+            server_id,
+            plugin_id,
+            version,
+        } => {
+            plugin_store::remove_installed_version(server_id, plugin_id, version).await?;
+        }
+        RepairAction::MigrateConfig => {
+            config_usecases::reload_config(ConfigStorePortAdapter::shared()).await?;
+        }
+        RepairAction::ResetConfig => {
+            let path = config_file_path();
+            let backup = path.with_extension(format!("json.corrupt-{}", now_ms()));
+            tokio::fs::rename(&path, &backup)
+                .await
+                .with_context(|| format!("Failed to back up {}", path.display()))?;
+            tracing::warn!(action = "app_self_check_config_reset", backup = %backup.display());
+            config_usecases::reload_config(ConfigStorePortAdapter::shared()).await?;
+        }
+    }
+    Ok(())
+}
+
+/// 按自检问题执行建议的修复动作。
+///
+/// # 参数
+/// - `issues`：`run_self_check` 返回的问题（没有修复动作的条目会被跳过）。
+///
+/// # 返回值
+/// - `Ok(Vec<RepairOutcome>)`：每个修复动作的结果（单个失败不影响其它动作）。
+/// - `Err(CommandError)`：数据目录不可用。
+#[tauri::command]
+pub async fn repair(issues: Vec<SelfCheckIssue>) -> CommandResult<Vec<RepairOutcome>> {
+    let app_data = crate::shared::app_data_dir::get_app_data_dir()
+        .map_err(|e| to_command_error("APP_DATA_DIR", "error.app_data_dir", e))?;
+    let mut outcomes = Vec::new();
+    for issue in issues {
+        let Some(action) = issue.repair else {
+            continue;
+        };
+        let result = apply_repair(&app_data, &action).await;
+        match &result {
+            Ok(()) => {
+                tracing::info!(action = "app_self_check_repaired", target = %issue.target, repair = ?action)
+            }
+            Err(e) => {
+                tracing::warn!(action = "app_self_check_repair_failed", target = %issue.target, repair = ?action, error = %e)
+            }
+        }
+        outcomes.push(RepairOutcome {
+            target: issue.target,
+            action,
+            ok: result.is_ok(),
+            error: result.err().map(|e| format!("{e:#}")),
+        });
+    }
+    Ok(outcomes)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn quick_check_ok_row_means_no_problems() {
+        assert!(quick_check_problems(vec!["ok".to_string()]).is_empty());
+        let many: Vec<String> = (0..20).map(|i| format!("row {i} missing")).collect();
+        assert_eq!(quick_check_problems(many).len(), MAX_DB_PROBLEMS);
+    }
+
+    #[test]
+    fn repair_actions_serialize_with_action_tag() {
+        let action = RepairAction::RemovePluginVersion {
+            server_id: "srv".to_string(),
+            plugin_id: "demo".to_string(),
+            version: "1.0.0".to_string(),
+        };
+        let json = serde_json::to_value(&action).expect("serialize");
+        assert_eq!(
+            json,
+            serde_json::json!({
+                "action": "remove_plugin_version",
+                "serverId": "srv",
+                "pluginId": "demo",
+                "version": "1.0.0"
+            })
+        );
+        let back: RepairAction = serde_json::from_value(json).expect("deserialize");
+        assert_eq!(back, action);
+        assert_eq!(
+            serde_json::to_value(RepairAction::ResetConfig).expect("serialize"),
+            serde_json::json!({ "action": "reset_config" })
+        );
+    }
+
+    #[tokio::test]
+    async fn create_directory_rejects_names_outside_defaults() {
+        let err = apply_repair(
+            &std::env::temp_dir(),
+            &RepairAction::CreateDirectory {
+                name: "../escape".to_string(),
+            },
+        )
+        .await
+        .expect_err("should reject");
+        assert!(err.to_string().contains("Unknown data directory"));
+    }
+}
@@ -0,0 +1,236 @@
+//! 启动编排与耗时追踪。
+//!
+//! 说明：
+//! - setup 中必须同步完成的阶段（数据目录、日志、临时文件库、窗口 bounds、配置读取）用
+//!   [`phase`] 包一层，只记录耗时；
+//! - 非关键初始化（插件迁移/开发态监听/旧版本清理、临时文件清理等）用 [`defer`] 登记，
+//!   主窗口首个页面加载完成后（[`on_first_render`]）才依次执行，避免与首屏渲染争抢 IO；
+//! - 页面加载事件丢失时由 [`spawn_render_fallback`] 兜底触发；
+//! - 所有耗时以进程启动（[`mark_process_start`]）为零点，通过 `get_startup_trace` 提供给诊断面板。
+//!
+//! 约定：注释中文，日志英文（tracing）。
+
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::{Mutex, OnceLock};
+use std::time::{Duration, Instant};
+
+use serde::Serialize;
+
+use crate::shared::error::CommandResult;
+
+/// 首屏渲染事件丢失时的兜底等待时间。
+const RENDER_FALLBACK: Duration = Duration::from_secs(15);
+
+type DeferredFuture = Pin<Box<dyn Future<Output = anyhow::Result<()>> + Send>>;
+type DeferredTask = Box<dyn FnOnce() -> DeferredFuture + Send>;
+
+/// 单个启动阶段的耗时记录。
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StartupPhase {
+    pub name: String,
+    /// 相对进程启动的开始时间（毫秒）。
+    pub start_ms: u64,
+    pub duration_ms: u64,
+    /// 是否为首屏渲染后执行的延迟阶段。
+    pub deferred: bool,
+    /// 失败原因；成功为空。
+    pub error: Option<String>,
+}
+
+/// 启动耗时追踪快照。
+#[derive(Debug, Clone, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StartupTrace {
+    pub phases: Vec<StartupPhase>,
+    /// 主窗口首个页面加载完成时间（毫秒）；尚未渲染时为空。
+    pub first_render_ms: Option<u64>,
+    /// 首屏是否由兜底计时器触发（页面加载事件丢失）。
+    pub render_fallback: bool,
+    /// 全部延迟阶段完成时间（毫秒）；尚未完成时为空。
+    pub deferred_done_ms: Option<u64>,
+}
+
+#[derive(Default)]
+struct TraceState {
+    trace: StartupTrace,
+    /// 首屏是否已渲染；与 `pending` 在同一把锁下读写，登记与取出不会错过彼此。
+    rendered: bool,
+    pending: Vec<(&'static str, DeferredTask)>,
+}
+
+impl TraceState {
+    fn record(&mut self, phase: StartupPhase) {
+        self.trace.phases.push(phase);
+    }
+
+    fn snapshot(&self) -> StartupTrace {
+        let mut trace = self.trace.clone();
+        trace.phases.sort_by_key(|p| p.start_ms);
+        trace
+    }
+}
+
+fn origin() -> Instant {
+    static ORIGIN: OnceLock<Instant> = OnceLock::new();
+    *ORIGIN.get_or_init(Instant::now)
+}
+
+fn state() -> &'static Mutex<TraceState> {
+    static STATE: OnceLock<Mutex<TraceState>> = OnceLock::new();
+    STATE.get_or_init(|| Mutex::new(TraceState::default()))
+}
+
+fn elapsed_ms(at: Instant) -> u64 {
+    at.saturating_duration_since(origin()).as_millis() as u64
+}
+
+/// 记录进程启动时间（在 `run()` 最开始调用）。
+pub fn mark_process_start() {
+    let _ = origin();
+}
+
+/// 执行并记录一个同步启动阶段。
+pub fn phase<T>(name: &'static str, f: impl FnOnce() -> T) -> T {
+    let started = Instant::now();
+    let value = f();
+    let duration_ms = started.elapsed().as_millis() as u64;
+    tracing::debug!(
+        action = "app_startup_phase_completed",
+        phase = name,
+        duration_ms
+    );
+    state()
+        .lock()
+        .unwrap_or_else(|p| p.into_inner())
+        .record(StartupPhase {
+            name: name.to_string(),
+            start_ms: elapsed_ms(started),
+            duration_ms,
+            deferred: false,
+            error: None,
+        });
+    value
+}
+
+/// 登记一个延迟到首屏渲染后执行的初始化任务；首屏已渲染时立即调度。
+pub fn defer<F, Fut>(name: &'static str, task: F)
+where
+    F: FnOnce() -> Fut + Send + 'static,
+    Fut: Future<Output = anyhow::Result<()>> + Send + 'static,
+{
+    let task: DeferredTask = Box::new(move || Box::pin(task()));
+    let mut st = state().lock().unwrap_or_else(|p| p.into_inner());
+    if st.rendered {
+        tauri::async_runtime::spawn(run_deferred(vec![(name, task)]));
+    } else {
+        st.pending.push((name, task));
+    }
+}
+
+async fn run_deferred(tasks: Vec<(&'static str, DeferredTask)>) {
+    // 依次执行，避免多个后台初始化同时抢占磁盘。
+    for (name, task) in tasks {
+        let started = Instant::now();
+        let result = task().await;
+        let duration_ms = started.elapsed().as_millis() as u64;
+        if let Err(e) = &result {
+            tracing::warn!(action = "app_startup_deferred_failed", phase = name, error = %e);
+        } else {
+            tracing::debug!(
+                action = "app_startup_deferred_completed",
+                phase = name,
+                duration_ms
+            );
+        }
+        state()
+            .lock()
+            .unwrap_or_else(|p| p.into_inner())
+            .record(StartupPhase {
+                name: name.to_string(),
+                start_ms: elapsed_ms(started),
+                duration_ms,
+                deferred: true,
+                error: result.err().map(|e| format!("{e:#}")),
+            });
+    }
+}
+
+/// 首屏渲染完成：记录时间并开始执行延迟任务（多次调用只生效一次）。
+pub fn on_first_render(fallback: bool) {
+    let at = elapsed_ms(Instant::now());
+    let tasks = {
+        let mut st = state().lock().unwrap_or_else(|p| p.into_inner());
+        if st.rendered {
+            return;
+        }
+        st.rendered = true;
+        st.trace.first_render_ms = Some(at);
+        st.trace.render_fallback = fallback;
+        std::mem::take(&mut st.pending)
+    };
+    tracing::info!(
+        action = "app_startup_first_render",
+        elapsed_ms = at,
+        fallback,
+        deferred = tasks.len()
+    );
+    tauri::async_runtime::spawn(async move {
+        run_deferred(tasks).await;
+        let done = elapsed_ms(Instant::now());
+        state()
+            .lock()
+            .unwrap_or_else(|p| p.into_inner())
+            .trace
+            .deferred_done_ms = Some(done);
+        tracing::info!(action = "app_startup_deferred_done", elapsed_ms = done);
+    });
+}
+
+/// 首屏是否已渲染（含兜底触发）。
+pub fn first_render_done() -> bool {
+    state().lock().unwrap_or_else(|p| p.into_inner()).rendered
+}
+
+/// 兜底：超时仍未收到首屏事件时强制开始延迟任务。
+pub fn spawn_render_fallback() {
+    tauri::async_runtime::spawn(async {
+        tokio::time::sleep(RENDER_FALLBACK).await;
+        if !first_render_done() {
+            tracing::warn!(action = "app_startup_render_fallback");
+            on_first_render(true);
+        }
+    });
+}
+
+/// 获取启动耗时追踪（阶段按开始时间排序）。
+#[tauri::command]
+pub async fn get_startup_trace() -> CommandResult<StartupTrace> {
+    Ok(state().lock().unwrap_or_else(|p| p.into_inner()).snapshot())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample(name: &str, start_ms: u64, deferred: bool) -> StartupPhase {
+        StartupPhase {
+            name: name.to_string(),
+            start_ms,
+            duration_ms: 1,
+            deferred,
+            error: None,
+        }
+    }
+
+    #[test]
+    fn snapshot_orders_phases_by_start_time() {
+        let mut st = TraceState::default();
+        st.record(sample("plugins_legacy_import", 900, true));
+        st.record(sample("data_dir", 5, false));
+        st.record(sample("temp_files", 40, false));
+        let names: Vec<_> = st.snapshot().phases.into_iter().map(|p| p.name).collect();
+        assert_eq!(names, ["data_dir", "temp_files", "plugins_legacy_import"]);
+    }
+}
@@ -0,0 +1,208 @@
+//! assistant｜OpenAI 兼容端点的流式 chat 客户端。
+//!
+//! 协议：`POST {endpoint}/chat/completions`，`stream: true`，响应为 SSE：
+//! 每行 `data: {json}`，以 `data: [DONE]` 结束；增量文本位于 `choices[0].delta.content`。
+//!
+//! 约定：注释中文，日志英文（tracing）。
+
+use std::sync::OnceLock;
+use std::time::Duration;
+
+use anyhow::Context;
+use futures_util::StreamExt;
+use serde::{Deserialize, Serialize};
+use tauri::AppHandle;
+
+use crate::shared::events::EventBusExt;
+
+use super::context::ChatMessage;
+use super::{ASSISTANT_STREAM_EVENT, AssistantStreamChunk};
+
+/// 建连超时；生成过程本身可能较长，不设整体超时。
+const CONNECT_TIMEOUT: Duration = Duration::from_secs(10);
+
+static HTTP_CLIENT: OnceLock<reqwest::Client> = OnceLock::new();
+
+fn http_client() -> &'static reqwest::Client {
+    HTTP_CLIENT.get_or_init(|| {
+        reqwest::Client::builder()
+            .connect_timeout(CONNECT_TIMEOUT)
+            .build()
+            .map_err(|e| {
+                tracing::error!(action = "app_assistant_http_client_build_failed", error = %e);
+            })
+            .ok()
+            .unwrap_or_default()
+    })
+}
+
+#[derive(Debug, Serialize)]
+struct ChatRequest<'a> {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    model: Option<&'a str>,
+    messages: &'a [ChatMessage],
+    stream: bool,
+}
+
+#[derive(Debug, Deserialize)]
+struct ChatChunk {
+    #[serde(default)]
+    choices: Vec<ChatChoice>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ChatChoice {
+    #[serde(default)]
+    delta: ChatDelta,
+}
+
+#[derive(Debug, Default, Deserialize)]
+struct ChatDelta {
+    content: Option<String>,
+}
+
+/// 单行 SSE 的解析结果。
+#[derive(Debug, PartialEq, Eq)]
+enum SseLine {
+    Delta(String),
+    Done,
+    Skip,
+}
+
+fn parse_sse_line(line: &str) -> SseLine {
+    let Some(data) = line.trim_end_matches('\r').strip_prefix("data:") else {
+        return SseLine::Skip;
+    };
+    let data = data.trim();
+    if data == "[DONE]" {
+        return SseLine::Done;
+    }
+    match serde_json::from_str::<ChatChunk>(data) {
+        Ok(chunk) => chunk
+            .choices
+            .into_iter()
+            .next()
+            .and_then(|c| c.delta.content)
+            .filter(|s| !s.is_empty())
+            .map(SseLine::Delta)
+            .unwrap_or(SseLine::Skip),
+        Err(_) => SseLine::Skip,
+    }
+}
+
+/// 拼出 chat completions 地址（允许端点带或不带末尾 `/`）。
+pub fn completions_url(endpoint: &str) -> anyhow::Result<reqwest::Url> {
+    let base = endpoint.trim().trim_end_matches('/');
+    let url = reqwest::Url::parse(&format!("{base}/chat/completions"))
+        .context("Invalid assistant endpoint")?;
+    if url.scheme() != "http" && url.scheme() != "https" {
+        return Err(anyhow::anyhow!(
+            "Unsupported assistant endpoint scheme: {}",
+            url.scheme()
+        ));
+    }
+    Ok(url)
+}
+
+fn emit(app: &AppHandle, chunk: AssistantStreamChunk) {
+    app.emit(ASSISTANT_STREAM_EVENT, chunk);
+}
+
+async fn stream_inner(
+    app: &AppHandle,
+    request_id: &str,
+    url: reqwest::Url,
+    model: Option<&str>,
+    messages: &[ChatMessage],
+) -> anyhow::Result<()> {
+    let resp = http_client()
+        .post(url)
+        .json(&ChatRequest {
+            model,
+            messages,
+            stream: true,
+        })
+        .send()
+        .await
+        .context("Assistant request failed")?
+        .error_for_status()
+        .context("Assistant endpoint returned an error")?;
+
+    let mut stream = resp.bytes_stream();
+    let mut buf: Vec<u8> = Vec::new();
+    while let Some(bytes) = stream.next().await {
+        buf.extend_from_slice(&bytes.context("Assistant stream interrupted")?);
+        // 按行切分；不完整的行留到下一块。
+        while let Some(pos) = buf.iter().position(|b| *b == b'\n') {
+            let line: Vec<u8> = buf.drain(..=pos).collect();
+            match parse_sse_line(&String::from_utf8_lossy(&line[..pos])) {
+                SseLine::Delta(delta) => emit(
+                    app,
+                    AssistantStreamChunk {
+                        request_id: request_id.to_string(),
+                        delta,
+                        done: false,
+                        error: None,
+                    },
+                ),
+                SseLine::Done => return Ok(()),
+                SseLine::Skip => {}
+            }
+        }
+    }
+    Ok(())
+}
+
+/// 流式请求补全并逐段推送事件；结束（或失败）时推送 `done = true`。
+pub async fn stream_completion(
+    app: AppHandle,
+    request_id: String,
+    url: reqwest::Url,
+    model: Option<String>,
+    messages: Vec<ChatMessage>,
+) {
+    let result = stream_inner(&app, &request_id, url, model.as_deref(), &messages).await;
+    if let Err(e) = &result {
+        tracing::warn!(action = "app_assistant_stream_failed", request_id = %request_id, error = %e);
+    }
+    emit(
+        &app,
+        AssistantStreamChunk {
+            request_id,
+            delta: String::new(),
+            done: true,
+            error: result.err().map(|e| format!("{e:#}")),
+        },
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sse_lines_are_parsed() {
+        assert_eq!(
+            parse_sse_line(r#"data: {"choices":[{"delta":{"content":"Hel"}}]}"#),
+            SseLine::Delta("Hel".to_string())
+        );
+        assert_eq!(parse_sse_line("data: [DONE]\r"), SseLine::Done);
+        assert_eq!(
+            parse_sse_line(r#"data: {"choices":[{"delta":{"role":"assistant"}}]}"#),
+            SseLine::Skip
+        );
+        assert_eq!(parse_sse_line(": keep-alive"), SseLine::Skip);
+        assert_eq!(parse_sse_line("data: not json"), SseLine::Skip);
+    }
+
+    #[test]
+    fn completions_url_accepts_trailing_slash_and_rejects_other_schemes() {
+        assert_eq!(
+            completions_url("http://localhost:11434/v1/")
+                .map(|u| u.to_string())
+                .ok(),
+            Some("http://localhost:11434/v1/chat/completions".to_string())
+        );
+        assert!(completions_url("file:///etc/passwd").is_err());
+    }
+}
@@ -0,0 +1,235 @@
+//! assistant｜上下文组装：从本地 server DB 读取消息并拼成对话提示。
+//!
+//! 约定：注释中文，日志英文（tracing）。
+
+use anyhow::Context;
+use sea_orm::{ConnectionTrait, QueryResult};
+use serde::Serialize;
+
+use super::AssistantRange;
+
+use crate::shared::db::commands::RawStatement;
+
+/// 摘要默认取最近的消息条数。
+const DEFAULT_SUMMARY_LIMIT: u32 = 200;
+/// 摘要最多取的消息条数。
+const MAX_SUMMARY_LIMIT: u32 = 500;
+/// 草拟回复时取目标消息之前的消息条数。
+const REPLY_CONTEXT_LIMIT: i64 = 30;
+/// 拼入提示的对话记录最大字符数（超出时丢弃最早的消息）。
+const MAX_TRANSCRIPT_CHARS: usize = 24_000;
+
+const SUMMARY_SYSTEM_PROMPT: &str = "You summarize chat conversations. Reply in the language most used in the conversation. Be concise: list the main topics, decisions and open questions.";
+const REPLY_SYSTEM_PROMPT: &str = "You help the user draft a reply in a chat conversation. Reply in the language of the target message. Output only the reply text.";
+
+const CHANNEL_MESSAGES_SQL: &str = r#"
+    SELECT m.id AS id, m.user_id AS user_id, u.name AS name, m.content AS content, m.created_at AS created_at
+    FROM messages m
+    LEFT JOIN users u ON u.id = m.user_id
+    WHERE m.channel_id = ? AND m.created_at >= ? AND m.created_at <= ?
+      AND m.user_id NOT IN (SELECT user_id FROM blocked_users)
+    ORDER BY m.created_at DESC
+    LIMIT ?
+"#;
+
+const TARGET_MESSAGE_SQL: &str = r#"
+    SELECT m.id AS id, m.channel_id AS channel_id, m.user_id AS user_id, u.name AS name,
+           m.content AS content, m.created_at AS created_at
+    FROM messages m
+    LEFT JOIN users u ON u.id = m.user_id
+    WHERE m.id = ?
+"#;
+
+/// 对话记录中的一条消息。
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TranscriptLine {
+    pub author: String,
+    pub content: String,
+    pub created_at: i64,
+}
+
+/// OpenAI 兼容 chat 消息。
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ChatMessage {
+    pub role: &'static str,
+    pub content: String,
+}
+
+fn line_from_row(row: &QueryResult) -> Option<TranscriptLine> {
+    let user_id: i64 = row.try_get("", "user_id").ok()?;
+    let name: Option<String> = row.try_get("", "name").ok().flatten();
+    Some(TranscriptLine {
+        author: name
+            .filter(|n| !n.trim().is_empty())
+            .unwrap_or_else(|| format!("user {user_id}")),
+        content: row.try_get("", "content").ok()?,
+        created_at: row.try_get("", "created_at").ok()?,
+    })
+}
+
+/// 读取频道在时间范围内的最近消息（按时间正序返回；不含被屏蔽用户的消息）。
+pub async fn load_channel_transcript(
+    db_key: &str,
+    channel_id: i64,
+    range: &AssistantRange,
+) -> anyhow::Result<Vec<TranscriptLine>> {
+    let limit = range
+        .limit
+        .unwrap_or(DEFAULT_SUMMARY_LIMIT)
+        .clamp(1, MAX_SUMMARY_LIMIT);
+    let db = crate::shared::db::get_db(db_key).await?;
+    let rows = db
+        .connection
+        .query_all(&RawStatement::new(
+            CHANNEL_MESSAGES_SQL,
+            vec![
+                channel_id.into(),
+                range.from_ms.unwrap_or(i64::MIN).into(),
+                range.to_ms.unwrap_or(i64::MAX).into(),
+                i64::from(limit).into(),
+            ],
+        ))
+        .await
+        .context("ASSISTANT_CONTEXT_LOAD_FAILED")?;
+    let mut lines: Vec<_> = rows.iter().filter_map(line_from_row).collect();
+    lines.reverse();
+    Ok(lines)
+}
+
+/// 读取目标消息及其之前的若干条消息（按时间正序）；目标不存在时返回 `None`。
+pub async fn load_reply_context(
+    db_key: &str,
+    message_id: &str,
+) -> anyhow::Result<Option<(Vec<TranscriptLine>, TranscriptLine)>> {
+    let db = crate::shared::db::get_db(db_key).await?;
+    let Some(row) = db
+        .connection
+        .query_one(&RawStatement::new(
+            TARGET_MESSAGE_SQL,
+            vec![message_id.into()],
+        ))
+        .await
+        .context("ASSISTANT_CONTEXT_LOAD_FAILED")?
+    else {
+        return Ok(None);
+    };
+    let Some(target) = line_from_row(&row) else {
+        return Ok(None);
+    };
+    let channel_id: i64 = row
+        .try_get("", "channel_id")
+        .context("ASSISTANT_CONTEXT_LOAD_FAILED")?;
+
+    let rows = db
+        .connection
+        .query_all(&RawStatement::new(
+            r#"
+            SELECT m.user_id AS user_id, u.name AS name, m.content AS content, m.created_at AS created_at
+            FROM messages m
+            LEFT JOIN users u ON u.id = m.user_id
+            WHERE m.channel_id = ? AND m.created_at <= ? AND m.id <> ?
+              AND m.user_id NOT IN (SELECT user_id FROM blocked_users)
+            ORDER BY m.created_at DESC
+            LIMIT ?
+            "#,
+            vec![
+                channel_id.into(),
+                target.created_at.into(),
+                message_id.into(),
+                REPLY_CONTEXT_LIMIT.into(),
+            ],
+        ))
+        .await
+        .context("ASSISTANT_CONTEXT_LOAD_FAILED")?;
+    let mut lines: Vec<_> = rows.iter().filter_map(line_from_row).collect();
+    lines.reverse();
+    Ok(Some((lines, target)))
+}
+
+/// 将对话记录格式化为纯文本；超出 `max_chars` 时保留最新的部分。
+pub fn format_transcript(lines: &[TranscriptLine], max_chars: usize) -> String {
+    let mut kept: Vec<String> = Vec::new();
+    let mut used = 0;
+    for line in lines.iter().rev() {
+        let text = format!("{}: {}", line.author, line.content.trim());
+        let cost = text.chars().count() + 1;
+        if used + cost > max_chars && !kept.is_empty() {
+            break;
+        }
+        used += cost;
+        kept.push(text);
+    }
+    kept.reverse();
+    kept.join("\n")
+}
+
+/// 频道摘要提示。
+pub fn summary_prompt(lines: &[TranscriptLine]) -> Vec<ChatMessage> {
+    vec![
+        ChatMessage {
+            role: "system",
+            content: SUMMARY_SYSTEM_PROMPT.to_string(),
+        },
+        ChatMessage {
+            role: "user",
+            content: format!(
+                "Summarize this conversation:\n\n{}",
+                format_transcript(lines, MAX_TRANSCRIPT_CHARS)
+            ),
+        },
+    ]
+}
+
+/// 草拟回复提示。
+pub fn reply_prompt(lines: &[TranscriptLine], target: &TranscriptLine) -> Vec<ChatMessage> {
+    vec![
+        ChatMessage {
+            role: "system",
+            content: REPLY_SYSTEM_PROMPT.to_string(),
+        },
+        ChatMessage {
+            role: "user",
+            content: format!(
+                "Conversation so far:\n\n{}\n\nDraft a reply to this message from {}:\n{}",
+                format_transcript(lines, MAX_TRANSCRIPT_CHARS),
+                target.author,
+                target.content.trim()
+            ),
+        },
+    ]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn line(author: &str, content: &str, created_at: i64) -> TranscriptLine {
+        TranscriptLine {
+            author: author.to_string(),
+            content: content.to_string(),
+            created_at,
+        }
+    }
+
+    #[test]
+    fn transcript_keeps_newest_lines_within_budget() {
+        let lines = [
+            line("alice", "first message", 1),
+            line("bob", "second", 2),
+            line("carol", "third", 3),
+        ];
+        assert_eq!(
+            format_transcript(&lines, 1000),
+            "alice: first message\nbob: second\ncarol: third"
+        );
+        assert_eq!(format_transcript(&lines, 25), "bob: second\ncarol: third");
+    }
+
+    #[test]
+    fn reply_prompt_names_the_target_author() {
+        let prompt = reply_prompt(&[line("alice", "hi", 1)], &line("bob", "lunch?", 2));
+        assert_eq!(prompt[0].role, "system");
+        assert!(prompt[1].content.contains("alice: hi"));
+        assert!(prompt[1].content.ends_with("from bob:\nlunch?"));
+    }
+}
@@ -0,0 +1,204 @@
+//! assistant｜DI/命令入口：commands。
+//!
+//! 约定：注释中文，日志英文（tracing）。
+
+use tauri::AppHandle;
+
+use crate::features::assistant::{AssistantRange, AssistantStatus};
+use crate::shared::error::CommandResult;
+#[cfg(not(feature = "assistant"))]
+use crate::shared::error::command_error;
+
+/// 查询助手是否可用（前端据此决定是否展示入口）。
+#[tauri::command]
+pub async fn get_assistant_status() -> CommandResult<AssistantStatus> {
+    let enabled =
+        crate::features::settings::get_config_value::<bool>(String::from("assistant_enabled"))
+            .await;
+    let endpoint =
+        crate::features::settings::get_config_value::<String>(String::from("assistant_endpoint"))
+            .await;
+    Ok(AssistantStatus {
+        built: cfg!(feature = "assistant"),
+        enabled,
+        endpoint_configured: !endpoint.trim().is_empty(),
+    })
+}
+
+#[cfg(feature = "assistant")]
+mod enabled {
+    use tauri::AppHandle;
+
+    use crate::features::assistant::client;
+    use crate::features::assistant::context::ChatMessage;
+    use crate::shared::error::{CommandResult, command_error, to_command_error};
+
+    /// 校验开关与端点；返回 `(url, model)`。
+    pub(super) async fn checked_endpoint() -> CommandResult<(reqwest::Url, Option<String>)> {
+        if !crate::features::settings::get_config_value::<bool>(String::from("assistant_enabled"))
+            .await
+        {
+            return Err(command_error(
+                "ASSISTANT_DISABLED",
+                "error.assistant_disabled",
+            ));
+        }
+        let endpoint = crate::features::settings::get_config_value::<String>(String::from(
+            "assistant_endpoint",
+        ))
+        .await;
+        if endpoint.trim().is_empty() {
+            return Err(command_error(
+                "ASSISTANT_ENDPOINT_MISSING",
+                "error.assistant_endpoint_missing",
+            ));
+        }
+        let url = client::completions_url(&endpoint).map_err(|e| {
+            to_command_error(
+                "ASSISTANT_ENDPOINT_INVALID",
+                "error.assistant_endpoint_invalid",
+                e,
+            )
+        })?;
+        let model =
+            crate::features::settings::get_config_value::<String>(String::from("assistant_model"))
+                .await;
+        let model = Some(model.trim().to_string()).filter(|m| !m.is_empty());
+        Ok((url, model))
+    }
+
+    /// 校验通过后在后台开始流式生成，立即返回请求 id。
+    pub(super) async fn start(
+        app: AppHandle,
+        url: reqwest::Url,
+        model: Option<String>,
+        messages: Vec<ChatMessage>,
+    ) -> String {
+        let request_id = uuid::Uuid::new_v4().to_string();
+        tracing::info!(action = "app_assistant_request_started", request_id = %request_id);
+        tauri::async_runtime::spawn(client::stream_completion(
+            app,
+            request_id.clone(),
+            url,
+            model,
+            messages,
+        ));
+        request_id
+    }
+}
+
+/// 摘要频道内的一段对话；结果通过 `assistant-stream` 事件流式推送。
+///
+/// # 参数
+/// - `db_key`：服务端数据库 key（需已通过 `db_init` 初始化）。
+/// - `channel_id`：频道 id。
+/// - `range`：时间范围与条数上限（可选）。
+///
+/// # 返回值
+/// - `Ok(String)`：请求 id，用于匹配流式事件。
+/// - `Err(String)`：未编译、未开启、端点未配置或读取本地消息失败。
+#[cfg(feature = "assistant")]
+#[tauri::command]
+pub async fn assistant_summarize_channel(
+    app: AppHandle,
+    db_key: String,
+    channel_id: i64,
+    range: Option<AssistantRange>,
+) -> CommandResult<String> {
+    use crate::features::assistant::context;
+    use crate::shared::error::{command_error, to_command_error};
+
+    let (url, model) = enabled::checked_endpoint().await?;
+    let lines = context::load_channel_transcript(&db_key, channel_id, &range.unwrap_or_default())
+        .await
+        .map_err(|e| {
+            to_command_error(
+                "ASSISTANT_CONTEXT_LOAD_FAILED",
+                "error.assistant_context_load_failed",
+                e,
+            )
+        })?;
+    if lines.is_empty() {
+        return Err(command_error(
+            "ASSISTANT_NO_MESSAGES",
+            "error.assistant_no_messages",
+        ));
+    }
+    Ok(enabled::start(app, url, model, context::summary_prompt(&lines)).await)
+}
+
+/// 摘要频道内的一段对话（当前构建未包含助手功能）。
+#[cfg(not(feature = "assistant"))]
+#[tauri::command]
+pub async fn assistant_summarize_channel(
+    app: AppHandle,
+    db_key: String,
+    channel_id: i64,
+    range: Option<AssistantRange>,
+) -> CommandResult<String> {
+    let _ = (app, db_key, channel_id, range);
+    Err(command_error(
+        "ASSISTANT_NOT_BUILT",
+        "error.assistant_not_built",
+    ))
+}
+
+/// 基于目标消息及其前文草拟回复；结果通过 `assistant-stream` 事件流式推送。
+///
+/// # 参数
+/// - `db_key`：服务端数据库 key。
+/// - `message_id`：要回复的消息 id。
+///
+/// # 返回值
+/// - `Ok(String)`：请求 id。
+/// - `Err(String)`：未编译、未开启、端点未配置或消息不存在。
+#[cfg(feature = "assistant")]
+#[tauri::command]
+pub async fn assistant_draft_reply(
+    app: AppHandle,
+    db_key: String,
+    message_id: String,
+) -> CommandResult<String> {
+    use crate::features::assistant::context;
+    use crate::shared::error::{command_error, to_command_error};
+
+    let message_id = message_id.trim().to_string();
+    if message_id.is_empty() {
+        return Err(command_error(
+            "ASSISTANT_MESSAGE_ID_REQUIRED",
+            "error.assistant_message_id_required",
+        ));
+    }
+    let (url, model) = enabled::checked_endpoint().await?;
+    let (lines, target) = context::load_reply_context(&db_key, &message_id)
+        .await
+        .map_err(|e| {
+            to_command_error(
+                "ASSISTANT_CONTEXT_LOAD_FAILED",
+                "error.assistant_context_load_failed",
+                e,
+            )
+        })?
+        .ok_or_else(|| {
+            command_error(
+                "ASSISTANT_MESSAGE_NOT_FOUND",
+                "error.assistant_message_not_found",
+            )
+        })?;
+    Ok(enabled::start(app, url, model, context::reply_prompt(&lines, &target)).await)
+}
+
+/// 草拟回复（当前构建未包含助手功能）。
+#[cfg(not(feature = "assistant"))]
+#[tauri::command]
+pub async fn assistant_draft_reply(
+    app: AppHandle,
+    db_key: String,
+    message_id: String,
+) -> CommandResult<String> {
+    let _ = (app, db_key, message_id);
+    Err(command_error(
+        "ASSISTANT_NOT_BUILT",
+        "error.assistant_not_built",
+    ))
+}
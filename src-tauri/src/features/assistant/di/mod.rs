@@ -0,0 +1,21 @@
+//! 模块入口：di。
+//!
+//! 说明：该文件负责导出子模块与组织依赖关系。
+//!
+//! 约定：注释中文，日志英文（tracing）。
+pub mod commands;
+
+use crate::app::registry::{CommandRegistration, CommandRegistry, command_set};
+
+/// 助手命令注册（未编译 `assistant` feature 时同样注册，返回 `ASSISTANT_NOT_BUILT`）。
+pub struct AssistantCommands;
+
+impl CommandRegistration for AssistantCommands {
+    fn register(registry: &mut CommandRegistry) {
+        registry.add(command_set!(crate::features::assistant::di::commands => [
+            get_assistant_status,
+            assistant_summarize_channel,
+            assistant_draft_reply,
+        ]));
+    }
+}
@@ -0,0 +1,57 @@
+//! 模块入口：assistant（本地 AI 助手桥接，可选）。
+//!
+//! 说明：
+//! - 编译开关：cargo feature `assistant`；未开启时命令仍注册，但直接返回 `ASSISTANT_NOT_BUILT`，
+//!   前端可据 `get_assistant_status` 隐藏入口；
+//! - 隐私：只有设置 `assistant_enabled` 显式开启后才会读取本地消息并发送到配置的端点；
+//! - 上下文来自本地 server DB（`messages` + `users`），不向 CarryPigeon 服务端请求；
+//! - 回复以流式 token 通过 `assistant-stream` 事件推送，按 `requestId` 区分请求。
+//!
+//! 约定：注释中文，日志英文（tracing）。
+#[cfg(feature = "assistant")]
+pub mod client;
+#[cfg(feature = "assistant")]
+pub mod context;
+pub mod di;
+
+use serde::{Deserialize, Serialize};
+
+/// 流式输出事件名。
+pub const ASSISTANT_STREAM_EVENT: &str = "assistant-stream";
+
+/// 频道摘要的时间范围。
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct AssistantRange {
+    /// 起始时间（毫秒，含）；为空表示不限。
+    pub from_ms: Option<i64>,
+    /// 结束时间（毫秒，含）；为空表示不限。
+    pub to_ms: Option<i64>,
+    /// 最多取最近多少条消息（缺省 200，最大 500）。
+    pub limit: Option<u32>,
+}
+
+/// 流式输出事件载荷。
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AssistantStreamChunk {
+    pub request_id: String,
+    /// 本次新增的文本片段。
+    pub delta: String,
+    /// 是否为最后一个事件。
+    pub done: bool,
+    /// 失败原因（仅 `done = true` 时可能出现）。
+    pub error: Option<String>,
+}
+
+/// 助手可用状态。
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AssistantStatus {
+    /// 当前构建是否包含助手功能。
+    pub built: bool,
+    /// 用户是否已允许发送数据。
+    pub enabled: bool,
+    /// 是否已配置端点。
+    pub endpoint_configured: bool,
+}
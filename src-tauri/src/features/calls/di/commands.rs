@@ -0,0 +1,163 @@
+//! calls｜Tauri 命令：呼叫信令处理与历史查询。
+//!
+//! 约定：注释中文，日志英文（tracing）。
+
+use std::sync::OnceLock;
+
+use serde::Serialize;
+use serde_json::Value;
+use tauri::AppHandle;
+
+use crate::features::calls::domain::{
+    CallDirection, CallRecord, CallRegistry, call_signal_kind, parse_call_signal,
+};
+use crate::features::calls::history_store;
+use crate::features::voice_call::domain::model::SignalingMessage;
+use crate::shared::error::{CommandResult, command_error, to_command_error};
+use crate::shared::events::EventBusExt;
+
+/// 呼叫事件名。
+pub const CALL_EVENT: &str = "call-event";
+
+/// 历史查询默认条数。
+const DEFAULT_HISTORY_LIMIT: u32 = 50;
+/// 历史查询最大条数。
+const MAX_HISTORY_LIMIT: u32 = 500;
+
+/// `call-event` 事件载荷。
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CallEventPayload {
+    /// 信令类型：`invite` / `accept` / `reject` / `ice_candidate` / `hangup`。
+    pub kind: &'static str,
+    /// 信令处理后的呼叫状态。
+    pub call: CallRecord,
+    /// 仅 `ice_candidate` 携带：ICE 候选（`candidate` / `sdpMid` / `sdpMLineIndex`）。
+    pub candidate: Option<Value>,
+    pub at: i64,
+}
+
+fn registry() -> &'static CallRegistry {
+    static REGISTRY: OnceLock<CallRegistry> = OnceLock::new();
+    REGISTRY.get_or_init(CallRegistry::default)
+}
+
+fn now_ms() -> i64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_millis() as i64
+}
+
+#[tauri::command]
+/// 处理一条呼叫信令：推进对应呼叫的状态机、写入历史并广播 `call-event`。
+///
+/// # 参数
+/// - `route`：协议路由名（如 `call.invite`）。
+/// - `payload`：路由 payload（需包含 `call_id`；邀请需 `peer_id`，ICE 需 `candidate`）。
+/// - `direction`：`inbound`（对端推送，默认）或 `outbound`（本端发出）。
+///
+/// # 返回值
+/// 信令处理后的呼叫状态。
+pub async fn handle_call_signal(
+    app: AppHandle,
+    server_socket: String,
+    route: String,
+    payload: Value,
+    direction: Option<String>,
+) -> CommandResult<CallRecord> {
+    let direction = match direction.as_deref() {
+        None => CallDirection::Inbound,
+        Some(raw) => CallDirection::parse(raw.trim())
+            .ok_or_else(|| command_error("CALL_SIGNAL_INVALID", "error.call_signal_invalid"))?,
+    };
+    let signal = parse_call_signal(&route, &payload)
+        .ok_or_else(|| command_error("CALL_SIGNAL_INVALID", "error.call_signal_invalid"))?;
+    let server_socket = server_socket.trim();
+    let at = now_ms();
+
+    let transition = registry()
+        .apply(server_socket, direction, &signal, at)
+        .map_err(|e| {
+            to_command_error(
+                "CALL_TRANSITION_INVALID",
+                "error.call_transition_invalid",
+                e,
+            )
+        })?;
+
+    if transition.state_changed {
+        // 历史写入失败不影响信令流转。
+        if let Err(e) = history_store::upsert_call(&transition.record).await {
+            tracing::warn!(
+                action = "app_call_history_save_failed",
+                call_id = %transition.record.call_id,
+                error = %e
+            );
+        }
+        tracing::info!(
+            action = "app_call_state_changed",
+            server_socket = %server_socket,
+            call_id = %transition.record.call_id,
+            state = transition.record.state.as_str()
+        );
+    }
+
+    let candidate = match &signal {
+        SignalingMessage::IceCandidate {
+            candidate,
+            sdp_mid,
+            sdp_mline_index,
+            ..
+        } => Some(serde_json::json!({
+            "candidate": candidate,
+            "sdpMid": sdp_mid,
+            "sdpMLineIndex": sdp_mline_index,
+        })),
+        _ => None,
+    };
+    app.emit(
+        CALL_EVENT,
+        CallEventPayload {
+            kind: call_signal_kind(&signal).unwrap_or_default(),
+            call: transition.record.clone(),
+            candidate,
+            at,
+        },
+    );
+    Ok(transition.record)
+}
+
+#[tauri::command]
+/// 列出当前活跃（未结束）的呼叫。
+pub fn list_active_calls() -> CommandResult<Vec<CallRecord>> {
+    Ok(registry().active())
+}
+
+#[tauri::command]
+/// 读取呼叫历史（邀请时间降序）。
+///
+/// # 参数
+/// - `server_socket`：仅返回该服务器的记录；缺省返回全部。
+/// - `limit`：条数上限（默认 50，最大 500）。
+pub async fn list_call_history(
+    server_socket: Option<String>,
+    limit: Option<u32>,
+) -> CommandResult<Vec<CallRecord>> {
+    let limit = limit
+        .unwrap_or(DEFAULT_HISTORY_LIMIT)
+        .clamp(1, MAX_HISTORY_LIMIT);
+    let server_socket = server_socket
+        .as_deref()
+        .map(str::trim)
+        .filter(|s| !s.is_empty());
+    history_store::load_history(server_socket, limit)
+        .await
+        .map_err(|e| {
+            to_command_error(
+                "CALL_HISTORY_LOAD_FAILED",
+                "error.call_history_load_failed",
+                e,
+            )
+        })
+}
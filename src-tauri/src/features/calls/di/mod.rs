@@ -0,0 +1,16 @@
+pub mod commands;
+
+use crate::app::registry::{CommandRegistration, CommandRegistry, command_set};
+
+/// 呼叫信令命令注册。
+pub struct CallsCommands;
+
+impl CommandRegistration for CallsCommands {
+    fn register(registry: &mut CommandRegistry) {
+        registry.add(command_set!(crate::features::calls::di::commands => [
+            handle_call_signal,
+            list_active_calls,
+            list_call_history,
+        ]));
+    }
+}
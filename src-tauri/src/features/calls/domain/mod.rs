@@ -0,0 +1,422 @@
+//! calls｜领域层：呼叫信令与单通呼叫状态机。
+//!
+//! 信令与状态复用 voice_call 领域模型（[`SignalingMessage`] / [`CallState`]），
+//! 这里只负责协议路由解析与按 call id 的状态流转。
+//!
+//! 状态流转（按 call id）：
+//! - `invite` → `ringing`；
+//! - `ringing` + `accept` → `active`；`ringing` + `reject` → `rejected`；
+//! - `ringing` + `hangup` → `missed`；`active` + `hangup` → `ended`；
+//! - `ice_candidate` 只在 `ringing`/`active` 中透传，不改变状态。
+//!
+//! `rejected`/`missed`/`ended` 为终态：终态呼叫从活跃表移除，只保留在历史记录中。
+//!
+//! 约定：注释中文，日志英文（tracing）。
+
+use std::collections::HashMap;
+use std::sync::Mutex;
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::features::voice_call::domain::model::{CallMedia, CallState, SignalingMessage};
+
+/// 呼叫方向（相对本端）。
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CallDirection {
+    /// 对端发起、本端接听。
+    Inbound,
+    /// 本端发起。
+    Outbound,
+}
+
+impl CallDirection {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Inbound => "inbound",
+            Self::Outbound => "outbound",
+        }
+    }
+
+    pub fn parse(raw: &str) -> Option<Self> {
+        match raw {
+            "inbound" => Some(Self::Inbound),
+            "outbound" => Some(Self::Outbound),
+            _ => None,
+        }
+    }
+}
+
+/// 信令 payload（兼容 snake_case 与 camelCase 字段名）。
+#[derive(Debug, Deserialize)]
+struct SignalPayload {
+    #[serde(alias = "callId")]
+    call_id: String,
+    #[serde(default, alias = "peerId")]
+    peer_id: Option<String>,
+    #[serde(default)]
+    media: Option<String>,
+    #[serde(default)]
+    reason: Option<String>,
+    #[serde(default, alias = "sdpOffer")]
+    sdp_offer: Option<String>,
+    #[serde(default, alias = "sdpAnswer")]
+    sdp_answer: Option<String>,
+    #[serde(default)]
+    candidate: Option<Value>,
+}
+
+/// ICE 候选（WebRTC `RTCIceCandidateInit` 形状）。
+#[derive(Debug, Deserialize)]
+struct IceCandidateBody {
+    candidate: String,
+    #[serde(default, alias = "sdpMid")]
+    sdp_mid: Option<String>,
+    #[serde(default, alias = "sdpMLineIndex")]
+    sdp_mline_index: Option<u16>,
+}
+
+/// 从协议路由名与 payload 解析呼叫信令（`call.invite` / `call.accept` / `call.reject` /
+/// `call.ice_candidate` / `call.hangup`），映射到 voice_call 的 [`SignalingMessage`]。
+///
+/// # 返回值
+/// - `None`：不是呼叫路由，或缺少必需字段（call id、邀请的对端、ICE 候选）。
+pub fn parse_call_signal(route: &str, payload: &Value) -> Option<SignalingMessage> {
+    let body = SignalPayload::deserialize(payload).ok()?;
+    let session_id = body.call_id.trim().to_string();
+    if session_id.is_empty() {
+        return None;
+    }
+    let reason = body
+        .reason
+        .map(|r| r.trim().to_string())
+        .filter(|r| !r.is_empty());
+    match route.trim() {
+        "call.invite" => {
+            let target_uid = body.peer_id?.trim().to_string();
+            if target_uid.is_empty() {
+                return None;
+            }
+            let media = match body.media.as_deref() {
+                None => CallMedia::default(),
+                Some(raw) => CallMedia::parse(raw)?,
+            };
+            Some(SignalingMessage::CallInvite {
+                session_id,
+                target_uid,
+                sdp_offer: body.sdp_offer.unwrap_or_default(),
+                ice_candidates: Vec::new(),
+                media,
+            })
+        }
+        "call.accept" => Some(SignalingMessage::CallAccept {
+            session_id,
+            sdp_answer: body.sdp_answer.unwrap_or_default(),
+            ice_candidates: Vec::new(),
+        }),
+        "call.reject" => Some(SignalingMessage::CallReject { session_id, reason }),
+        "call.ice_candidate" => {
+            let candidate = match body.candidate? {
+                Value::String(candidate) => IceCandidateBody {
+                    candidate,
+                    sdp_mid: None,
+                    sdp_mline_index: None,
+                },
+                other => IceCandidateBody::deserialize(other).ok()?,
+            };
+            Some(SignalingMessage::IceCandidate {
+                session_id,
+                candidate: candidate.candidate,
+                sdp_mid: candidate.sdp_mid,
+                sdp_mline_index: candidate.sdp_mline_index,
+            })
+        }
+        "call.hangup" => Some(SignalingMessage::CallHangup { session_id, reason }),
+        _ => None,
+    }
+}
+
+/// 信令类型（`call-event` 的 `kind` 字段）；非 1:1 呼叫信令返回 `None`。
+pub fn call_signal_kind(signal: &SignalingMessage) -> Option<&'static str> {
+    match signal {
+        SignalingMessage::CallInvite { .. } => Some("invite"),
+        SignalingMessage::CallAccept { .. } => Some("accept"),
+        SignalingMessage::CallReject { .. } => Some("reject"),
+        SignalingMessage::IceCandidate { .. } => Some("ice_candidate"),
+        SignalingMessage::CallHangup { .. } => Some("hangup"),
+        _ => None,
+    }
+}
+
+/// 单通呼叫记录（活跃表与历史表共用）。
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CallRecord {
+    pub server_socket: String,
+    pub call_id: String,
+    pub peer_id: String,
+    pub direction: CallDirection,
+    pub media: CallMedia,
+    pub state: CallState,
+    pub invited_at: i64,
+    pub answered_at: Option<i64>,
+    pub ended_at: Option<i64>,
+    pub end_reason: Option<String>,
+}
+
+/// 信令在当前状态下不合法。
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CallTransitionError {
+    /// 非 `invite` 信令指向不存在（或已结束）的呼叫。
+    UnknownCall(String),
+    /// 同一 call id 重复邀请。
+    DuplicateInvite(String),
+    /// 当前状态不接受该信令。
+    InvalidState { call_id: String, state: CallState },
+}
+
+impl std::fmt::Display for CallTransitionError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::UnknownCall(call_id) => write!(f, "Unknown call {call_id}"),
+            Self::DuplicateInvite(call_id) => write!(f, "Duplicate invite for call {call_id}"),
+            Self::InvalidState { call_id, state } => {
+                write!(
+                    f,
+                    "Call {call_id} cannot accept signal in state {}",
+                    state.as_str()
+                )
+            }
+        }
+    }
+}
+
+impl std::error::Error for CallTransitionError {}
+
+/// 一次信令处理的结果。
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CallTransition {
+    pub record: CallRecord,
+    /// 状态是否变化（ICE 候选不改变状态，无需写历史）。
+    pub state_changed: bool,
+}
+
+/// 活跃呼叫表：按 `(server_socket, call_id)` 维护状态机。
+#[derive(Debug, Default)]
+pub struct CallRegistry {
+    calls: Mutex<HashMap<(String, String), CallRecord>>,
+}
+
+impl CallRegistry {
+    /// 将信令应用到对应呼叫的状态机。
+    ///
+    /// # 参数
+    /// - `direction`：仅对 `invite` 生效（对端发来的邀请为 inbound，本端发出为 outbound）。
+    /// - `now_ms`：事件时间（毫秒）。
+    pub fn apply(
+        &self,
+        server_socket: &str,
+        direction: CallDirection,
+        signal: &SignalingMessage,
+        now_ms: i64,
+    ) -> Result<CallTransition, CallTransitionError> {
+        let mut calls = self.calls.lock().unwrap_or_else(|e| e.into_inner());
+        let call_id = signal.session_id();
+        let key = (server_socket.to_string(), call_id.to_string());
+
+        if let SignalingMessage::CallInvite {
+            target_uid, media, ..
+        } = signal
+        {
+            if calls.contains_key(&key) {
+                return Err(CallTransitionError::DuplicateInvite(call_id.to_string()));
+            }
+            let record = CallRecord {
+                server_socket: server_socket.to_string(),
+                call_id: call_id.to_string(),
+                peer_id: target_uid.clone(),
+                direction,
+                media: *media,
+                state: CallState::Ringing,
+                invited_at: now_ms,
+                answered_at: None,
+                ended_at: None,
+                end_reason: None,
+            };
+            calls.insert(key, record.clone());
+            return Ok(CallTransition {
+                record,
+                state_changed: true,
+            });
+        }
+
+        let Some(current) = calls.get_mut(&key) else {
+            return Err(CallTransitionError::UnknownCall(call_id.to_string()));
+        };
+        let invalid = |state| CallTransitionError::InvalidState {
+            call_id: call_id.to_string(),
+            state,
+        };
+        let next = match (signal, current.state) {
+            (SignalingMessage::IceCandidate { .. }, _) => None,
+            (SignalingMessage::CallAccept { .. }, CallState::Ringing) => Some(CallState::Active),
+            (SignalingMessage::CallReject { .. }, CallState::Ringing) => Some(CallState::Rejected),
+            (SignalingMessage::CallHangup { .. }, CallState::Ringing) => Some(CallState::Missed),
+            (SignalingMessage::CallHangup { .. }, CallState::Active) => Some(CallState::Ended),
+            (_, state) => return Err(invalid(state)),
+        };
+        let Some(next) = next else {
+            return Ok(CallTransition {
+                record: current.clone(),
+                state_changed: false,
+            });
+        };
+
+        current.state = next;
+        match signal {
+            SignalingMessage::CallAccept { .. } => current.answered_at = Some(now_ms),
+            SignalingMessage::CallReject { reason, .. }
+            | SignalingMessage::CallHangup { reason, .. } => {
+                current.ended_at = Some(now_ms);
+                current.end_reason = reason.clone();
+            }
+            _ => {}
+        }
+        let record = current.clone();
+        if next.is_terminal() {
+            calls.remove(&key);
+        }
+        Ok(CallTransition {
+            record,
+            state_changed: true,
+        })
+    }
+
+    /// 当前活跃（未到终态）的呼叫。
+    pub fn active(&self) -> Vec<CallRecord> {
+        let calls = self.calls.lock().unwrap_or_else(|e| e.into_inner());
+        let mut records: Vec<CallRecord> = calls.values().cloned().collect();
+        records.sort_by_key(|r| r.invited_at);
+        records
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn invite(call_id: &str) -> SignalingMessage {
+        parse_call_signal(
+            "call.invite",
+            &json!({ "call_id": call_id, "peer_id": "42", "media": "video" }),
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn parses_routes_and_rejects_incomplete_payloads() {
+        assert!(matches!(
+            invite("c1"),
+            SignalingMessage::CallInvite { session_id, target_uid, media: CallMedia::Video, .. }
+                if session_id == "c1" && target_uid == "42"
+        ));
+        assert!(matches!(
+            parse_call_signal(
+                "call.reject",
+                &json!({ "callId": "c1", "reason": " busy " })
+            ),
+            Some(SignalingMessage::CallReject { session_id, reason: Some(reason) })
+                if session_id == "c1" && reason == "busy"
+        ));
+        assert!(matches!(
+            parse_call_signal(
+                "call.ice_candidate",
+                &json!({ "call_id": "c1", "candidate": { "candidate": "candidate:1", "sdpMLineIndex": 0 } })
+            ),
+            Some(SignalingMessage::IceCandidate { candidate, sdp_mline_index: Some(0), .. })
+                if candidate == "candidate:1"
+        ));
+        assert!(parse_call_signal("call.invite", &json!({ "call_id": "c1" })).is_none());
+        assert!(parse_call_signal("call.ice_candidate", &json!({ "call_id": "c1" })).is_none());
+        assert!(parse_call_signal("call.accept", &json!({ "call_id": " " })).is_none());
+        assert!(parse_call_signal("message.created", &json!({ "call_id": "c1" })).is_none());
+    }
+
+    #[test]
+    fn accepted_call_ends_on_hangup_and_leaves_registry() {
+        let registry = CallRegistry::default();
+        let s = "tls://a:1";
+        let t = registry
+            .apply(s, CallDirection::Inbound, &invite("c1"), 10)
+            .unwrap();
+        assert_eq!(t.record.state, CallState::Ringing);
+
+        let ice = parse_call_signal(
+            "call.ice_candidate",
+            &json!({ "call_id": "c1", "candidate": { "candidate": "candidate:1" } }),
+        )
+        .unwrap();
+        let t = registry.apply(s, CallDirection::Inbound, &ice, 11).unwrap();
+        assert!(!t.state_changed);
+
+        let accept = parse_call_signal("call.accept", &json!({ "call_id": "c1" })).unwrap();
+        let t = registry
+            .apply(s, CallDirection::Inbound, &accept, 20)
+            .unwrap();
+        assert_eq!(
+            (t.record.state, t.record.answered_at),
+            (CallState::Active, Some(20))
+        );
+
+        let hangup = parse_call_signal("call.hangup", &json!({ "call_id": "c1" })).unwrap();
+        let t = registry
+            .apply(s, CallDirection::Inbound, &hangup, 30)
+            .unwrap();
+        assert_eq!(
+            (t.record.state, t.record.ended_at),
+            (CallState::Ended, Some(30))
+        );
+        assert!(registry.active().is_empty());
+        assert_eq!(
+            registry.apply(s, CallDirection::Inbound, &ice, 31),
+            Err(CallTransitionError::UnknownCall("c1".to_string()))
+        );
+    }
+
+    #[test]
+    fn invalid_transitions_are_rejected_per_call() {
+        let registry = CallRegistry::default();
+        registry
+            .apply("a", CallDirection::Outbound, &invite("c1"), 1)
+            .unwrap();
+        // 同一 call id 在不同服务器上互不影响。
+        registry
+            .apply("b", CallDirection::Inbound, &invite("c1"), 1)
+            .unwrap();
+        assert_eq!(
+            registry.apply("a", CallDirection::Outbound, &invite("c1"), 2),
+            Err(CallTransitionError::DuplicateInvite("c1".to_string()))
+        );
+
+        let accept = parse_call_signal("call.accept", &json!({ "call_id": "c1" })).unwrap();
+        registry
+            .apply("a", CallDirection::Outbound, &accept, 3)
+            .unwrap();
+        assert_eq!(
+            registry.apply("a", CallDirection::Outbound, &accept, 4),
+            Err(CallTransitionError::InvalidState {
+                call_id: "c1".to_string(),
+                state: CallState::Active,
+            })
+        );
+
+        let hangup = parse_call_signal("call.hangup", &json!({ "call_id": "c1" })).unwrap();
+        let t = registry
+            .apply("b", CallDirection::Inbound, &hangup, 5)
+            .unwrap();
+        assert_eq!(t.record.state, CallState::Missed);
+        assert_eq!(registry.active().len(), 1);
+    }
+}
@@ -0,0 +1,85 @@
+//! calls｜呼叫历史持久化（system DB `calls` 表）。
+
+use anyhow::{Context, Result};
+use sea_orm::{ConnectionTrait, QueryResult};
+
+use super::domain::{CallDirection, CallRecord};
+use crate::features::voice_call::domain::model::{CallMedia, CallState};
+
+use crate::shared::db::commands::RawStatement;
+
+/// 呼叫历史的 DB key（应用级 system DB）。
+const SYSTEM_DB_KEY: &str = "system";
+
+/// 写入（或更新）一条呼叫记录；同一 `(server_socket, call_id)` 只保留最新状态。
+pub async fn upsert_call(record: &CallRecord) -> Result<()> {
+    let db = crate::shared::db::get_db(SYSTEM_DB_KEY).await?;
+    let stmt = RawStatement::new(
+        "INSERT INTO calls (server_socket, call_id, peer_id, direction, media, state,
+                            invited_at, answered_at, ended_at, end_reason)
+         VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
+         ON CONFLICT(server_socket, call_id) DO UPDATE SET
+           state = excluded.state,
+           answered_at = excluded.answered_at,
+           ended_at = excluded.ended_at,
+           end_reason = excluded.end_reason",
+        vec![
+            record.server_socket.clone().into(),
+            record.call_id.clone().into(),
+            record.peer_id.clone().into(),
+            record.direction.as_str().into(),
+            record.media.as_str().into(),
+            record.state.as_str().into(),
+            record.invited_at.into(),
+            record.answered_at.into(),
+            record.ended_at.into(),
+            record.end_reason.clone().into(),
+        ],
+    );
+    db.connection
+        .execute(&stmt)
+        .await
+        .context("CALL_HISTORY_SAVE_FAILED")?;
+    Ok(())
+}
+
+/// 读取呼叫历史（邀请时间降序）；`server_socket` 为空时返回全部服务器的记录。
+pub async fn load_history(server_socket: Option<&str>, limit: u32) -> Result<Vec<CallRecord>> {
+    let db = crate::shared::db::get_db(SYSTEM_DB_KEY).await?;
+    let stmt = RawStatement::new(
+        "SELECT server_socket, call_id, peer_id, direction, media, state,
+                invited_at, answered_at, ended_at, end_reason
+         FROM calls
+         WHERE (? IS NULL OR server_socket = ?)
+         ORDER BY invited_at DESC LIMIT ?",
+        vec![
+            server_socket.map(str::to_string).into(),
+            server_socket.map(str::to_string).into(),
+            i64::from(limit).into(),
+        ],
+    );
+    let rows = db
+        .connection
+        .query_all(&stmt)
+        .await
+        .context("CALL_HISTORY_LOAD_FAILED")?;
+    Ok(rows.iter().filter_map(row_to_record).collect())
+}
+
+fn row_to_record(row: &QueryResult) -> Option<CallRecord> {
+    let direction: String = row.try_get("", "direction").ok()?;
+    let media: String = row.try_get("", "media").ok()?;
+    let state: String = row.try_get("", "state").ok()?;
+    Some(CallRecord {
+        server_socket: row.try_get("", "server_socket").ok()?,
+        call_id: row.try_get("", "call_id").ok()?,
+        peer_id: row.try_get("", "peer_id").ok()?,
+        direction: CallDirection::parse(&direction)?,
+        media: CallMedia::parse(&media)?,
+        state: CallState::parse(&state)?,
+        invited_at: row.try_get("", "invited_at").ok()?,
+        answered_at: row.try_get("", "answered_at").ok()?,
+        ended_at: row.try_get("", "ended_at").ok()?,
+        end_reason: row.try_get("", "end_reason").ok()?,
+    })
+}
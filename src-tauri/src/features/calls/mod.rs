@@ -0,0 +1,15 @@
+//! calls｜呼叫信令模块（音视频通话的信令层）。
+//!
+//! 说明：
+//! - 协议推送在前端解密后由事件路由器按路由名（`call.invite` / `call.accept` / `call.reject` /
+//!   `call.ice_candidate` / `call.hangup`）转交 `handle_call_signal`；本端发出的信令同样回送，
+//!   以便两个方向共用一套状态机；
+//! - 每个 call id 维护一份状态机（见 `domain`），状态变化写入 system DB `calls` 表；
+//! - 每条被接受的信令都会广播结构化 `call-event`，供前端或后续 WebRTC 层消费；
+//!   媒体协商（SDP）仍由 `voice_call` 负责。
+//!
+//! 约定：注释中文，日志英文（tracing）。
+
+pub mod di;
+pub mod domain;
+pub mod history_store;
@@ -0,0 +1,106 @@
+//! emoji｜服务端表情图片的内容寻址存储。
+//!
+//! 说明：
+//! - 图片存放在 {app_data_dir}/custom-emoji/server-assets/ 下，文件名为 `<sha256>.<ext>`，
+//!   不同服务器的相同图片只保存一份；写入先落临时文件再改名，已存在时跳过；
+//! - 通过 `app://emoji/<sha256>.<ext>` 提供给 WebView（见 `app::handle_app_scheme`）；
+//! - 不再被任何服务器引用的文件由 [`remove_unreferenced`] 清理。
+//!
+//! 约定：注释中文，日志英文（tracing）。
+
+use std::collections::HashSet;
+use std::fs;
+use std::path::PathBuf;
+
+use anyhow::{Context, Result};
+
+use crate::features::emoji::server_catalog::ImageKind;
+use crate::shared::net::tls_fingerprint::sha256_hex;
+
+/// `app://` 下的资源前缀。
+pub const ASSET_URL_PREFIX: &str = "app://emoji/";
+
+/// 资源目录。
+pub fn assets_dir() -> Result<PathBuf> {
+    Ok(crate::shared::app_data_dir::get_app_data_dir()
+        .context("EMOJI_DIR_UNAVAILABLE")?
+        .join("custom-emoji")
+        .join("server-assets"))
+}
+
+/// 校验资源名（`<64 位小写 hex>.<受支持的后缀>`）。
+pub fn parse_asset_name(name: &str) -> Option<ImageKind> {
+    let (hash, ext) = name.split_once('.')?;
+    let valid_hash = hash.len() == 64
+        && hash
+            .bytes()
+            .all(|b| b.is_ascii_digit() || (b'a'..=b'f').contains(&b));
+    if !valid_hash {
+        return None;
+    }
+    ImageKind::from_extension(ext)
+}
+
+/// 资源对应的 `app://` 地址。
+pub fn asset_url(name: &str) -> String {
+    format!("{ASSET_URL_PREFIX}{name}")
+}
+
+/// 写入一张图片，返回资源名；不是受支持的图片格式时报错。
+pub fn store(bytes: &[u8]) -> Result<String> {
+    let kind = ImageKind::sniff(bytes).context("EMOJI_ASSET_UNSUPPORTED")?;
+    let name = format!("{}.{}", sha256_hex(bytes), kind.extension());
+    let dir = assets_dir()?;
+    let path = dir.join(&name);
+    if path.exists() {
+        return Ok(name);
+    }
+    fs::create_dir_all(&dir).context("create assets dir")?;
+    let tmp = dir.join(format!("{name}.tmp"));
+    fs::write(&tmp, bytes).context("write asset")?;
+    fs::rename(&tmp, &path).context("rename asset")?;
+    Ok(name)
+}
+
+/// 解析 `app://emoji/` 之后的路径为本地文件；资源名非法或文件不存在时为 `None`。
+pub fn resolve(name: &str) -> Option<(PathBuf, ImageKind)> {
+    let kind = parse_asset_name(name)?;
+    let path = assets_dir().ok()?.join(name);
+    path.is_file().then_some((path, kind))
+}
+
+/// 删除未被引用的资源文件，返回删除数量。
+pub fn remove_unreferenced(referenced: &HashSet<String>) -> usize {
+    let Ok(entries) = assets_dir().and_then(|dir| Ok(fs::read_dir(dir)?)) else {
+        return 0;
+    };
+    let mut removed = 0;
+    for entry in entries.flatten() {
+        let name = entry.file_name().to_string_lossy().to_string();
+        let stale_tmp = name.ends_with(".tmp");
+        let managed = stale_tmp || parse_asset_name(&name).is_some();
+        if managed && !referenced.contains(&name) && fs::remove_file(entry.path()).is_ok() {
+            removed += 1;
+        }
+    }
+    removed
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn asset_names_are_strictly_validated() {
+        let hash = "a".repeat(64);
+        assert_eq!(
+            parse_asset_name(&format!("{hash}.png")),
+            Some(ImageKind::Png)
+        );
+        assert_eq!(parse_asset_name(&format!("{hash}.svg")), None);
+        assert_eq!(parse_asset_name(&format!("{}.png", "A".repeat(64))), None);
+        assert_eq!(parse_asset_name(&format!("{hash}/../x.png")), None);
+        assert_eq!(parse_asset_name("abc.png"), None);
+        assert_eq!(asset_url("x.png"), "app://emoji/x.png");
+    }
+}
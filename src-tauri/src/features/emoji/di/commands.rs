@@ -2,16 +2,36 @@
 
 use tauri::AppHandle;
 
-use crate::features::emoji::domain::types::EmojiEntry;
-use crate::features::emoji::repository;
-use crate::shared::error::CommandResult;
+use crate::features::emoji::domain::types::{
+    EmojiEntry, EmojiSearchResult, FrequentEmoji, ServerEmoji,
+};
+use crate::features::emoji::server_sync::{self, ServerEmojiSyncOptions, ServerEmojiSyncResult};
+use crate::features::emoji::{repository, server_store, shortcode_index, usage_store};
+use crate::shared::error::{CommandResult, command_error, to_command_error};
+use crate::shared::events::EventBusExt;
+
+/// 服务器自定义表情变化的事件名。
+pub const SERVER_EMOJIS_CHANGED_EVENT: &str = "server-emojis-changed";
+/// 服务器表情列表默认返回条数（表情面板一次展示全部）。
+const DEFAULT_SERVER_EMOJI_LIMIT: u32 = 1000;
+/// 服务器表情列表最大返回条数。
+const MAX_SERVER_EMOJI_LIMIT: u32 = 5000;
+
+/// `server-emojis-changed` 事件载荷。
+#[derive(Debug, Clone, serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ServerEmojisChangedPayload {
+    pub server_socket: String,
+}
+
+/// 搜索/常用列表默认返回条数。
+const DEFAULT_EMOJI_LIMIT: u32 = 24;
+/// 搜索/常用列表最大返回条数。
+const MAX_EMOJI_LIMIT: u32 = 200;
 
 #[tauri::command]
-pub async fn list_custom_emojis(
-    app_handle: AppHandle,
-    uid: String,
-) -> CommandResult<Vec<EmojiEntry>> {
-    let index = repository::load_index(&app_handle);
+pub async fn list_custom_emojis(uid: String) -> CommandResult<Vec<EmojiEntry>> {
+    let index = repository::load_index();
     let uid_trimmed = uid.trim();
     let items: Vec<EmojiEntry> = index
         .items
@@ -23,54 +43,36 @@ pub async fn list_custom_emojis(
 
 #[tauri::command]
 pub async fn save_emoji(
-    app_handle: AppHandle,
     source_path: String,
     name: String,
     tags: Vec<String>,
     uid: String,
 ) -> CommandResult<EmojiEntry> {
     let id = uuid::Uuid::new_v4().to_string();
-    let entry = repository::add_emoji(
-        &app_handle,
-        &id,
-        &name,
-        std::path::Path::new(&source_path),
-        &tags,
-        &uid,
-    )
-    .map_err(|e| e.to_string())?;
+    let entry = repository::add_emoji(&id, &name, std::path::Path::new(&source_path), &tags, &uid)
+        .map_err(|e| e.to_string())?;
     tracing::info!(action = "app_emoji_saved", id = %id, name = %name, uid = %uid);
     Ok(entry)
 }
 
 #[tauri::command]
-pub async fn delete_emoji(app_handle: AppHandle, id: String, uid: String) -> CommandResult<()> {
-    repository::delete_emoji(&app_handle, &id, &uid).map_err(|e| e.to_string())?;
+pub async fn delete_emoji(id: String, uid: String) -> CommandResult<()> {
+    repository::delete_emoji(&id, &uid).map_err(|e| e.to_string())?;
     tracing::info!(action = "app_emoji_deleted", id = %id, uid = %uid);
     Ok(())
 }
 
 #[tauri::command]
-pub async fn copy_emoji(
-    app_handle: AppHandle,
-    source_id: String,
-    uid: String,
-    name: String,
-) -> CommandResult<EmojiEntry> {
-    let entry =
-        repository::copy_emoji(&app_handle, &source_id, &uid, &name).map_err(|e| e.to_string())?;
+pub async fn copy_emoji(source_id: String, uid: String, name: String) -> CommandResult<EmojiEntry> {
+    let entry = repository::copy_emoji(&source_id, &uid, &name).map_err(|e| e.to_string())?;
     tracing::info!(action = "app_emoji_copied", source = %source_id, new_id = %entry.id, uid = %uid);
     Ok(entry)
 }
 
 #[tauri::command]
-pub async fn write_temp_emoji_file(
-    app_handle: AppHandle,
-    name: String,
-    data: Vec<u8>,
-) -> CommandResult<String> {
+pub async fn write_temp_emoji_file(name: String, data: Vec<u8>) -> CommandResult<String> {
     use std::io::Write;
-    let tmp_dir = repository::emoji_dir(&app_handle)
+    let tmp_dir = repository::emoji_dir()
         .map_err(|e| e.to_string())?
         .join("_upload_tmp");
     std::fs::create_dir_all(&tmp_dir).map_err(|e| e.to_string())?;
@@ -81,15 +83,152 @@ pub async fn write_temp_emoji_file(
 }
 
 #[tauri::command]
-pub async fn get_emoji_image_path(app_handle: AppHandle, id: String) -> CommandResult<String> {
-    let index = repository::load_index(&app_handle);
+pub async fn get_emoji_image_path(id: String) -> CommandResult<String> {
+    let index = repository::load_index();
     let entry = index
         .items
         .iter()
         .find(|e| e.id == id)
         .ok_or("emoji not found")?;
-    let full_path = repository::emoji_dir(&app_handle)
+    let full_path = repository::emoji_dir()
         .map_err(|e| e.to_string())?
         .join(&entry.file_path);
     Ok(full_path.to_string_lossy().to_string())
 }
+
+/// 读取肤色偏好（settings `emoji_skin_tone`，超出范围按默认处理）。
+pub(crate) async fn preferred_skin_tone() -> u32 {
+    let tone =
+        crate::features::settings::get_config_value::<u32>(String::from("emoji_skin_tone")).await;
+    if tone > shortcode_index::MAX_SKIN_TONE {
+        0
+    } else {
+        tone
+    }
+}
+
+fn now_ms() -> i64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_millis() as i64
+}
+
+/// 按 shortcode / 描述 / 标签搜索标准 emoji（结果已应用肤色偏好）。
+#[tauri::command]
+pub async fn search_emoji(
+    query: String,
+    limit: Option<u32>,
+) -> CommandResult<Vec<EmojiSearchResult>> {
+    let limit = limit
+        .unwrap_or(DEFAULT_EMOJI_LIMIT)
+        .clamp(1, MAX_EMOJI_LIMIT) as usize;
+    let skin_tone = preferred_skin_tone().await;
+    Ok(shortcode_index::search(&query, limit, skin_tone))
+}
+
+/// 记录一次 emoji 使用（用于常用列表排序）。
+#[tauri::command]
+pub async fn record_emoji_usage(emoji: String) -> CommandResult<()> {
+    // 只记录索引内的标准 emoji；肤色变体统一归并到基础 emoji。
+    let Some(entry) = shortcode_index::lookup(emoji.trim(), 0) else {
+        return Err(command_error("EMOJI_UNKNOWN", "error.emoji_unknown"));
+    };
+    usage_store::record_usage(&entry.base_emoji, now_ms())
+        .await
+        .map_err(|e| {
+            to_command_error(
+                "EMOJI_USAGE_RECORD_FAILED",
+                "error.emoji_usage_record_failed",
+                e,
+            )
+        })
+}
+
+/// 获取常用 emoji（按使用次数降序，结果已应用肤色偏好）。
+#[tauri::command]
+pub async fn get_frequently_used_emoji(limit: Option<u32>) -> CommandResult<Vec<FrequentEmoji>> {
+    let limit = limit
+        .unwrap_or(DEFAULT_EMOJI_LIMIT)
+        .clamp(1, MAX_EMOJI_LIMIT);
+    let skin_tone = preferred_skin_tone().await;
+    let usage = usage_store::load_frequent(limit).await.map_err(|e| {
+        to_command_error(
+            "EMOJI_USAGE_LOAD_FAILED",
+            "error.emoji_usage_load_failed",
+            e,
+        )
+    })?;
+    Ok(usage
+        .into_iter()
+        .filter_map(|row| {
+            let entry = shortcode_index::lookup(&row.emoji, skin_tone)?;
+            Some(FrequentEmoji {
+                emoji: entry.emoji,
+                base_emoji: entry.base_emoji,
+                shortcodes: entry.shortcodes,
+                use_count: row.use_count,
+                last_used_at: row.last_used_at,
+            })
+        })
+        .collect())
+}
+
+/// 同步服务器自定义表情目录（ETag 未变化时不重新下载）。
+///
+/// # 参数
+/// - `options`：连接参数（`serverSocket` / `headers` / `tlsPolicy` / `tlsFingerprint`）。
+///
+/// # 返回值
+/// 同步结果；本地索引发生变化时广播 `server-emojis-changed`。
+#[tauri::command]
+pub async fn sync_server_emojis(
+    app: AppHandle,
+    options: ServerEmojiSyncOptions,
+) -> CommandResult<ServerEmojiSyncResult> {
+    let server_socket = options.server_socket.trim().to_string();
+    let result = server_sync::sync_server_emojis(options)
+        .await
+        .map_err(|e| {
+            to_command_error(
+                "EMOJI_SERVER_SYNC_FAILED",
+                "error.emoji_server_sync_failed",
+                e,
+            )
+        })?;
+    if result.changed {
+        app.emit(
+            SERVER_EMOJIS_CHANGED_EVENT,
+            ServerEmojisChangedPayload { server_socket },
+        );
+    }
+    Ok(result)
+}
+
+/// 列出已同步的服务器自定义表情；`query` 非空时按 shortcode 搜索（前缀匹配优先）。
+///
+/// # 返回值
+/// 表情列表，`url` 为 `app://emoji/...` 地址，可直接用作图片地址。
+#[tauri::command]
+pub async fn list_server_emojis(
+    server_socket: String,
+    query: Option<String>,
+    limit: Option<u32>,
+) -> CommandResult<Vec<ServerEmoji>> {
+    let limit = limit
+        .unwrap_or(DEFAULT_SERVER_EMOJI_LIMIT)
+        .clamp(1, MAX_SERVER_EMOJI_LIMIT);
+    server_store::list(
+        server_socket.trim(),
+        query.as_deref().unwrap_or_default(),
+        limit,
+    )
+    .await
+    .map_err(|e| {
+        to_command_error(
+            "EMOJI_SERVER_LIST_FAILED",
+            "error.emoji_server_list_failed",
+            e,
+        )
+    })
+}
@@ -1 +1,24 @@
 pub mod commands;
+
+use crate::app::registry::{CommandRegistration, CommandRegistry, command_set};
+
+/// 表情命令注册。
+pub struct EmojiCommands;
+
+impl CommandRegistration for EmojiCommands {
+    fn register(registry: &mut CommandRegistry) {
+        registry.add(command_set!(crate::features::emoji::di::commands => [
+            list_custom_emojis,
+            save_emoji,
+            delete_emoji,
+            copy_emoji,
+            write_temp_emoji_file,
+            get_emoji_image_path,
+            search_emoji,
+            record_emoji_usage,
+            get_frequently_used_emoji,
+            sync_server_emojis,
+            list_server_emojis,
+        ]));
+    }
+}
@@ -25,3 +25,36 @@ impl Default for EmojiIndex {
         }
     }
 }
+
+/// 标准 emoji 搜索结果（来自内置 shortcode 索引）。
+#[derive(Debug, Clone, Serialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct EmojiSearchResult {
+    /// emoji 字符（已按肤色偏好替换）。
+    pub emoji: String,
+    /// 基础 emoji 字符（不含肤色修饰）。
+    pub base_emoji: String,
+    pub shortcodes: Vec<String>,
+    pub annotation: String,
+}
+
+/// 常用 emoji 条目（按使用次数与最近使用时间排序）。
+#[derive(Debug, Clone, Serialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct FrequentEmoji {
+    pub emoji: String,
+    pub base_emoji: String,
+    pub shortcodes: Vec<String>,
+    pub use_count: i64,
+    pub last_used_at: i64,
+}
+
+/// 服务端自定义表情（已同步到本地，`url` 为 `app://emoji/...` 地址）。
+#[derive(Debug, Clone, Serialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct ServerEmoji {
+    pub shortcode: String,
+    pub url: String,
+    pub animated: bool,
+    pub category: Option<String>,
+}
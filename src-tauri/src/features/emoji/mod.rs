@@ -2,7 +2,15 @@
 //!
 //! 提供本地自定义表情的增删查功能。
 //! 表情数据存储在 {app_data_dir}/custom-emoji/ 目录下。
+//! 标准 emoji 的 shortcode 搜索与常用统计也在此模块（见 `shortcode_index` / `usage_store`）。
+//! 服务端自定义表情的同步、索引与本地资源见 `server_sync` / `server_store` / `asset_store`。
 
+pub mod asset_store;
 pub mod di;
 pub mod domain;
 pub mod repository;
+pub mod server_catalog;
+pub mod server_store;
+pub mod server_sync;
+pub mod shortcode_index;
+pub mod usage_store;
@@ -7,30 +7,27 @@ use std::io::BufReader;
 use std::path::{Path, PathBuf};
 
 use anyhow::{Context, Result};
-use tauri::Manager;
 use uuid::Uuid;
 
 use crate::features::emoji::domain::types::{EmojiEntry, EmojiIndex};
 
-pub fn emoji_dir(app_handle: &tauri::AppHandle) -> Result<PathBuf> {
-    let dir = app_handle
-        .path()
-        .app_data_dir()
+pub fn emoji_dir() -> Result<PathBuf> {
+    let dir = crate::shared::app_data_dir::get_app_data_dir()
         .context("EMOJI_DIR_UNAVAILABLE")?
         .join("custom-emoji");
     Ok(dir)
 }
 
-fn index_path(app_handle: &tauri::AppHandle) -> Result<PathBuf> {
-    Ok(emoji_dir(app_handle)?.join("index.json"))
+fn index_path() -> Result<PathBuf> {
+    Ok(emoji_dir()?.join("index.json"))
 }
 
-fn images_dir(app_handle: &tauri::AppHandle) -> Result<PathBuf> {
-    Ok(emoji_dir(app_handle)?.join("images"))
+fn images_dir() -> Result<PathBuf> {
+    Ok(emoji_dir()?.join("images"))
 }
 
-pub fn load_index(app_handle: &tauri::AppHandle) -> EmojiIndex {
-    let Ok(path) = index_path(app_handle) else {
+pub fn load_index() -> EmojiIndex {
+    let Ok(path) = index_path() else {
         return EmojiIndex::default();
     };
     if !path.exists() {
@@ -42,11 +39,11 @@ pub fn load_index(app_handle: &tauri::AppHandle) -> EmojiIndex {
         .unwrap_or_default()
 }
 
-pub fn save_index(app_handle: &tauri::AppHandle, index: &EmojiIndex) -> Result<()> {
-    let dir = emoji_dir(app_handle)?;
+pub fn save_index(index: &EmojiIndex) -> Result<()> {
+    let dir = emoji_dir()?;
     fs::create_dir_all(&dir).context("create dir")?;
     let json = serde_json::to_string_pretty(index).context("serialize")?;
-    fs::write(index_path(app_handle)?, json).context("write")
+    fs::write(index_path()?, json).context("write")
 }
 
 /// 检测图像是否为动画格式（GIF 多帧 / APNG / WebP 动画）。
@@ -118,14 +115,13 @@ fn is_animated_image(source_path: &Path) -> bool {
 }
 
 pub fn add_emoji(
-    app_handle: &tauri::AppHandle,
     id: &str,
     name: &str,
     source_path: &Path,
     tags: &[String],
     owner_uid: &str,
 ) -> Result<EmojiEntry> {
-    let imgs = images_dir(app_handle)?;
+    let imgs = images_dir()?;
     fs::create_dir_all(&imgs).context("create images dir")?;
 
     let ext = source_path
@@ -159,15 +155,15 @@ pub fn add_emoji(
         is_animated: animated,
     };
 
-    let mut index = load_index(app_handle);
+    let mut index = load_index();
     index.items.push(entry.clone());
-    save_index(app_handle, &index)?;
+    save_index(&index)?;
 
     Ok(entry)
 }
 
-pub fn delete_emoji(app_handle: &tauri::AppHandle, id: &str, owner_uid: &str) -> Result<()> {
-    let mut index = load_index(app_handle);
+pub fn delete_emoji(id: &str, owner_uid: &str) -> Result<()> {
+    let mut index = load_index();
     let idx = index
         .items
         .iter()
@@ -176,7 +172,7 @@ pub fn delete_emoji(app_handle: &tauri::AppHandle, id: &str, owner_uid: &str) ->
     let entry = index.items.remove(idx);
 
     // Remove image file
-    let Ok(img_path) = emoji_dir(app_handle) else {
+    let Ok(img_path) = emoji_dir() else {
         return Err(anyhow::anyhow!("EMOJI_DIR_UNAVAILABLE"));
     };
     let img_path = img_path.join(&entry.file_path);
@@ -184,24 +180,19 @@ pub fn delete_emoji(app_handle: &tauri::AppHandle, id: &str, owner_uid: &str) ->
         let _ = fs::remove_file(img_path);
     }
 
-    save_index(app_handle, &index)
+    save_index(&index)
 }
 
 /// 从他人消息中一键保存表情到当前用户的表情列表。
-pub fn copy_emoji(
-    app_handle: &tauri::AppHandle,
-    source_id: &str,
-    owner_uid: &str,
-    new_name: &str,
-) -> Result<EmojiEntry> {
-    let index = load_index(app_handle);
+pub fn copy_emoji(source_id: &str, owner_uid: &str, new_name: &str) -> Result<EmojiEntry> {
+    let index = load_index();
     let source = index
         .items
         .iter()
         .find(|e| e.id == source_id)
         .context("source emoji not found")?;
 
-    let src_path = emoji_dir(app_handle)?.join(&source.file_path);
+    let src_path = emoji_dir()?.join(&source.file_path);
     if !src_path.exists() {
         anyhow::bail!("source emoji file not found on disk");
     }
@@ -211,9 +202,9 @@ pub fn copy_emoji(
         .extension()
         .and_then(|e| e.to_str())
         .unwrap_or("png");
-    let dest = images_dir(app_handle)?.join(format!("{}.{}", new_id, ext));
+    let dest = images_dir()?.join(format!("{}.{}", new_id, ext));
 
-    let imgs = images_dir(app_handle)?;
+    let imgs = images_dir()?;
     fs::create_dir_all(&imgs).context("create images dir")?;
     fs::copy(&src_path, &dest).context("copy emoji file")?;
 
@@ -230,9 +221,9 @@ pub fn copy_emoji(
         is_animated: source.is_animated,
     };
 
-    let mut index = load_index(app_handle);
+    let mut index = load_index();
     index.items.push(entry.clone());
-    save_index(app_handle, &index)?;
+    save_index(&index)?;
 
     Ok(entry)
 }
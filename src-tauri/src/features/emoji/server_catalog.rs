@@ -0,0 +1,277 @@
+//! emoji｜服务端自定义表情目录：解析、图片类型识别与同步差异计算。
+//!
+//! 说明：
+//! - 目录由 `GET /api/emojis` 返回，可为数组或 `{ "emojis": [...] }`；每项至少包含
+//!   `shortcode`（或 `name`）与 `url`，可选 `animated` / `category`；
+//! - shortcode 统一为小写、去掉首尾冒号，只允许 `[a-z0-9_+-]`，同名以目录中先出现的为准；
+//! - 图片只接受 PNG / GIF / WebP / JPEG（按文件头识别，不信任 URL 后缀与响应头），
+//!   避免把 SVG 等可执行内容写入本地资源。
+//!
+//! 约定：注释中文，日志英文（tracing）。
+
+use std::collections::{HashMap, HashSet};
+
+use serde::Serialize;
+use serde_json::Value;
+
+/// 单个 shortcode 的最大长度。
+pub const MAX_SHORTCODE_LEN: usize = 64;
+
+/// 目录中的一个表情。
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CatalogEmoji {
+    pub shortcode: String,
+    /// 图片地址（绝对地址或 `/` 开头的相对路径）。
+    pub url: String,
+    pub animated: bool,
+    pub category: Option<String>,
+}
+
+/// 已识别的图片类型。
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ImageKind {
+    Png,
+    Gif,
+    Webp,
+    Jpeg,
+}
+
+impl ImageKind {
+    /// 按文件头识别图片类型。
+    pub fn sniff(bytes: &[u8]) -> Option<Self> {
+        if bytes.starts_with(b"\x89PNG\r\n\x1a\n") {
+            Some(Self::Png)
+        } else if bytes.starts_with(b"GIF87a") || bytes.starts_with(b"GIF89a") {
+            Some(Self::Gif)
+        } else if bytes.len() >= 12 && &bytes[0..4] == b"RIFF" && &bytes[8..12] == b"WEBP" {
+            Some(Self::Webp)
+        } else if bytes.starts_with(&[0xFF, 0xD8, 0xFF]) {
+            Some(Self::Jpeg)
+        } else {
+            None
+        }
+    }
+
+    /// 资源文件后缀。
+    pub fn extension(self) -> &'static str {
+        match self {
+            Self::Png => "png",
+            Self::Gif => "gif",
+            Self::Webp => "webp",
+            Self::Jpeg => "jpg",
+        }
+    }
+
+    pub fn from_extension(ext: &str) -> Option<Self> {
+        match ext {
+            "png" => Some(Self::Png),
+            "gif" => Some(Self::Gif),
+            "webp" => Some(Self::Webp),
+            "jpg" => Some(Self::Jpeg),
+            _ => None,
+        }
+    }
+}
+
+/// 规范化 shortcode；不合法时返回 `None`。
+pub fn normalize_shortcode(raw: &str) -> Option<String> {
+    let code = raw.trim().trim_matches(':').to_ascii_lowercase();
+    let valid = !code.is_empty()
+        && code.len() <= MAX_SHORTCODE_LEN
+        && code
+            .bytes()
+            .all(|b| b.is_ascii_alphanumeric() || matches!(b, b'_' | b'+' | b'-'));
+    valid.then_some(code)
+}
+
+fn parse_item(item: &Value) -> Option<CatalogEmoji> {
+    let shortcode = item
+        .get("shortcode")
+        .or_else(|| item.get("name"))
+        .and_then(Value::as_str)
+        .and_then(normalize_shortcode)?;
+    let url = item.get("url").and_then(Value::as_str)?.trim().to_string();
+    if url.is_empty() {
+        return None;
+    }
+    Some(CatalogEmoji {
+        shortcode,
+        url,
+        animated: item
+            .get("animated")
+            .and_then(Value::as_bool)
+            .unwrap_or(false),
+        category: item
+            .get("category")
+            .and_then(Value::as_str)
+            .map(str::trim)
+            .filter(|c| !c.is_empty())
+            .map(str::to_string),
+    })
+}
+
+/// 解析目录响应体；无法识别的条目与重复 shortcode 被忽略。
+pub fn parse_catalog(body: &Value) -> Vec<CatalogEmoji> {
+    let items = match body {
+        Value::Array(items) => items.as_slice(),
+        Value::Object(map) => match map.get("emojis") {
+            Some(Value::Array(items)) => items.as_slice(),
+            _ => &[],
+        },
+        _ => &[],
+    };
+    let mut seen = HashSet::new();
+    items
+        .iter()
+        .filter_map(parse_item)
+        .filter(|emoji| seen.insert(emoji.shortcode.clone()))
+        .collect()
+}
+
+/// 将目录中的图片地址解析为完整 URL；只允许与服务器同源的地址（避免同步任务请求任意主机）。
+///
+/// `origin` 形如 `https://host:port`（不含结尾 `/`）。
+pub fn resolve_image_url(origin: &str, url: &str) -> Option<String> {
+    let full = if url.starts_with('/') && !url.starts_with("//") {
+        format!("{origin}{url}")
+    } else {
+        url.to_string()
+    };
+    let path = full.strip_prefix(origin)?;
+    let valid = path.starts_with('/')
+        && !path.split(['/', '?', '#']).any(|segment| segment == "..")
+        && !path.contains('\\');
+    valid.then_some(full)
+}
+
+/// 本地已同步的一条记录（用于差异计算）。
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SyncedEmoji {
+    pub shortcode: String,
+    pub source_url: String,
+    pub asset: String,
+}
+
+/// 一次同步需要执行的变更。
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct CatalogDiff {
+    /// 新增或图片地址变化、需要下载的表情。
+    pub download: Vec<CatalogEmoji>,
+    /// 图片未变、只需更新元数据的表情（附带已有资源名）。
+    pub keep: Vec<(CatalogEmoji, String)>,
+    /// 目录中已不存在的 shortcode。
+    pub remove: Vec<String>,
+}
+
+/// 比较目录与本地记录：图片地址不变的表情不重复下载。
+pub fn diff_catalog(catalog: Vec<CatalogEmoji>, local: &[SyncedEmoji]) -> CatalogDiff {
+    let existing: HashMap<&str, &SyncedEmoji> =
+        local.iter().map(|e| (e.shortcode.as_str(), e)).collect();
+    let wanted: HashSet<String> = catalog.iter().map(|e| e.shortcode.clone()).collect();
+    let mut diff = CatalogDiff::default();
+    for emoji in catalog {
+        match existing.get(emoji.shortcode.as_str()) {
+            Some(synced) if synced.source_url == emoji.url => {
+                let asset = synced.asset.clone();
+                diff.keep.push((emoji, asset));
+            }
+            _ => diff.download.push(emoji),
+        }
+    }
+    diff.remove = local
+        .iter()
+        .filter(|e| !wanted.contains(&e.shortcode))
+        .map(|e| e.shortcode.clone())
+        .collect();
+    diff
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn catalog_entries_are_normalized_and_deduplicated() {
+        let body = serde_json::json!({ "emojis": [
+            { "shortcode": ":Party_Parrot:", "url": "/api/emojis/1.gif", "animated": true, "category": "fun" },
+            { "name": "party_parrot", "url": "/api/emojis/2.png" },
+            { "shortcode": "bad code", "url": "/x.png" },
+            { "shortcode": "no_url" },
+            { "name": "ok", "url": "https://cdn.example.com/ok.png", "category": " " }
+        ]});
+        let catalog = parse_catalog(&body);
+        assert_eq!(catalog.len(), 2);
+        assert_eq!(catalog[0].shortcode, "party_parrot");
+        assert!(catalog[0].animated);
+        assert_eq!(catalog[0].category.as_deref(), Some("fun"));
+        assert_eq!(catalog[1].category, None);
+        assert_eq!(
+            parse_catalog(&serde_json::json!([{ "name": "a", "url": "/a" }])).len(),
+            1
+        );
+        assert!(parse_catalog(&serde_json::json!("nope")).is_empty());
+    }
+
+    #[test]
+    fn images_are_identified_by_header() {
+        assert_eq!(
+            ImageKind::sniff(b"\x89PNG\r\n\x1a\n...."),
+            Some(ImageKind::Png)
+        );
+        assert_eq!(ImageKind::sniff(b"GIF89a...."), Some(ImageKind::Gif));
+        assert_eq!(
+            ImageKind::sniff(b"RIFF\0\0\0\0WEBPVP8X"),
+            Some(ImageKind::Webp)
+        );
+        assert_eq!(
+            ImageKind::sniff(&[0xFF, 0xD8, 0xFF, 0xE0]),
+            Some(ImageKind::Jpeg)
+        );
+        assert_eq!(ImageKind::sniff(b"<svg xmlns=\"\"></svg>"), None);
+        for kind in [
+            ImageKind::Png,
+            ImageKind::Gif,
+            ImageKind::Webp,
+            ImageKind::Jpeg,
+        ] {
+            assert_eq!(ImageKind::from_extension(kind.extension()), Some(kind));
+        }
+    }
+
+    #[test]
+    fn image_urls_must_share_the_server_origin() {
+        let origin = "https://chat.example.com:8443";
+        assert_eq!(
+            resolve_image_url(origin, "/api/emojis/a.png").as_deref(),
+            Some("https://chat.example.com:8443/api/emojis/a.png")
+        );
+        assert!(resolve_image_url(origin, "https://chat.example.com:8443/e/a.png").is_some());
+        assert!(resolve_image_url(origin, "https://chat.example.com:8443.evil.io/a.png").is_none());
+        assert!(resolve_image_url(origin, "https://cdn.example.com/a.png").is_none());
+        assert!(resolve_image_url(origin, "//evil.io/a.png").is_none());
+        assert!(resolve_image_url(origin, "/api/../../etc/passwd").is_none());
+    }
+
+    #[test]
+    fn diff_only_downloads_changed_images() {
+        let emoji = |code: &str, url: &str| CatalogEmoji {
+            shortcode: code.to_string(),
+            url: url.to_string(),
+            animated: false,
+            category: None,
+        };
+        let synced = |code: &str, url: &str| SyncedEmoji {
+            shortcode: code.to_string(),
+            source_url: url.to_string(),
+            asset: format!("{code}.png"),
+        };
+        let diff = diff_catalog(
+            vec![emoji("a", "/a1"), emoji("b", "/b2"), emoji("c", "/c")],
+            &[synced("a", "/a1"), synced("b", "/b1"), synced("d", "/d")],
+        );
+        assert_eq!(diff.keep, vec![(emoji("a", "/a1"), "a.png".to_string())]);
+        assert_eq!(diff.download, vec![emoji("b", "/b2"), emoji("c", "/c")]);
+        assert_eq!(diff.remove, vec!["d".to_string()]);
+    }
+}
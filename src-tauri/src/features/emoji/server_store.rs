@@ -0,0 +1,180 @@
+//! emoji｜服务端自定义表情索引（system DB `server_emojis` / `server_emoji_catalogs` 表）。
+//!
+//! 说明：
+//! - `server_emojis` 按 `(server_socket, shortcode)` 记录图片来源地址与本地资源名；
+//! - `server_emoji_catalogs` 记录每个服务器上次同步的目录 ETag，用于条件请求；
+//! - 一次同步的增删改与新 ETag 在同一事务内写入，失败时整体回滚，下次重新同步。
+//!
+//! 约定：注释中文，日志英文（tracing）。
+
+use std::collections::HashSet;
+
+use anyhow::{Context, Result};
+use sea_orm::{ConnectionTrait, QueryResult, TransactionTrait};
+
+use crate::features::emoji::asset_store;
+use crate::features::emoji::domain::types::ServerEmoji;
+use crate::features::emoji::server_catalog::{CatalogEmoji, SyncedEmoji};
+use crate::shared::db::commands::RawStatement;
+
+/// 表情索引的 DB key（应用级 system DB）。
+const SYSTEM_DB_KEY: &str = "system";
+
+/// 读取某服务器已同步的表情（用于差异计算）。
+pub async fn load_synced(server_socket: &str) -> Result<Vec<SyncedEmoji>> {
+    let db = crate::shared::db::get_db(SYSTEM_DB_KEY).await?;
+    let rows = db
+        .connection
+        .query_all(&RawStatement::new(
+            "SELECT shortcode, source_url, asset FROM server_emojis WHERE server_socket = ?",
+            vec![server_socket.into()],
+        ))
+        .await
+        .context("SERVER_EMOJI_LOAD_FAILED")?;
+    Ok(rows
+        .iter()
+        .filter_map(|row| {
+            Some(SyncedEmoji {
+                shortcode: row.try_get("", "shortcode").ok()?,
+                source_url: row.try_get("", "source_url").ok()?,
+                asset: row.try_get("", "asset").ok()?,
+            })
+        })
+        .collect())
+}
+
+/// 读取某服务器上次同步的目录 ETag。
+pub async fn load_etag(server_socket: &str) -> Result<Option<String>> {
+    let db = crate::shared::db::get_db(SYSTEM_DB_KEY).await?;
+    let row = db
+        .connection
+        .query_one(&RawStatement::new(
+            "SELECT etag FROM server_emoji_catalogs WHERE server_socket = ?",
+            vec![server_socket.into()],
+        ))
+        .await
+        .context("SERVER_EMOJI_LOAD_FAILED")?;
+    Ok(row.and_then(|row| row.try_get::<Option<String>>("", "etag").ok().flatten()))
+}
+
+/// 写入一次同步结果（同一事务）。
+///
+/// # 参数
+/// - `upserts`：目录中的表情及其本地资源名。
+/// - `removed`：目录中已不存在的 shortcode。
+/// - `etag`：本次目录的 ETag（服务端未返回时为 `None`，下次将完整拉取）。
+pub async fn apply_sync(
+    server_socket: &str,
+    upserts: &[(CatalogEmoji, String)],
+    removed: &[String],
+    etag: Option<&str>,
+    now_ms: i64,
+) -> Result<()> {
+    let db = crate::shared::db::get_db(SYSTEM_DB_KEY).await?;
+    let txn = db.connection.begin().await?;
+    for shortcode in removed {
+        txn.execute(&RawStatement::new(
+            "DELETE FROM server_emojis WHERE server_socket = ? AND shortcode = ?",
+            vec![server_socket.into(), shortcode.clone().into()],
+        ))
+        .await?;
+    }
+    for (emoji, asset) in upserts {
+        txn.execute(&RawStatement::new(
+            "INSERT INTO server_emojis
+                 (server_socket, shortcode, source_url, asset, animated, category, updated_at)
+             VALUES (?, ?, ?, ?, ?, ?, ?)
+             ON CONFLICT(server_socket, shortcode) DO UPDATE SET
+               source_url = excluded.source_url,
+               asset = excluded.asset,
+               animated = excluded.animated,
+               category = excluded.category,
+               updated_at = excluded.updated_at",
+            vec![
+                server_socket.into(),
+                emoji.shortcode.clone().into(),
+                emoji.url.clone().into(),
+                asset.clone().into(),
+                emoji.animated.into(),
+                emoji.category.clone().into(),
+                now_ms.into(),
+            ],
+        ))
+        .await?;
+    }
+    txn.execute(&RawStatement::new(
+        "INSERT INTO server_emoji_catalogs (server_socket, etag, synced_at) VALUES (?, ?, ?)
+         ON CONFLICT(server_socket) DO UPDATE SET
+           etag = excluded.etag,
+           synced_at = excluded.synced_at",
+        vec![
+            server_socket.into(),
+            etag.map(str::to_string).into(),
+            now_ms.into(),
+        ],
+    ))
+    .await?;
+    txn.commit().await.context("SERVER_EMOJI_SAVE_FAILED")?;
+    Ok(())
+}
+
+/// 所有服务器仍在引用的资源名。
+pub async fn referenced_assets() -> Result<HashSet<String>> {
+    let db = crate::shared::db::get_db(SYSTEM_DB_KEY).await?;
+    let rows = db
+        .connection
+        .query_all(&RawStatement::new(
+            "SELECT DISTINCT asset FROM server_emojis",
+            Vec::new(),
+        ))
+        .await
+        .context("SERVER_EMOJI_LOAD_FAILED")?;
+    Ok(rows
+        .iter()
+        .filter_map(|row| row.try_get("", "asset").ok())
+        .collect())
+}
+
+fn row_to_emoji(row: &QueryResult) -> Option<ServerEmoji> {
+    let asset: String = row.try_get("", "asset").ok()?;
+    Some(ServerEmoji {
+        shortcode: row.try_get("", "shortcode").ok()?,
+        url: asset_store::asset_url(&asset),
+        animated: row.try_get("", "animated").ok()?,
+        category: row.try_get("", "category").ok()?,
+    })
+}
+
+/// 列出某服务器的自定义表情（按分类、shortcode 排序）；`query` 非空时按 shortcode 子串过滤。
+pub async fn list(server_socket: &str, query: &str, limit: u32) -> Result<Vec<ServerEmoji>> {
+    let db = crate::shared::db::get_db(SYSTEM_DB_KEY).await?;
+    let pattern = format!(
+        "%{}%",
+        query
+            .trim()
+            .trim_matches(':')
+            .to_ascii_lowercase()
+            .replace('\\', "\\\\")
+            .replace('%', "\\%")
+            .replace('_', "\\_")
+    );
+    let rows = db
+        .connection
+        .query_all(&RawStatement::new(
+            "SELECT shortcode, asset, animated, category FROM server_emojis
+             WHERE server_socket = ? AND shortcode LIKE ? ESCAPE '\\'
+             ORDER BY
+               CASE WHEN shortcode LIKE ? ESCAPE '\\' THEN 0 ELSE 1 END,
+               category IS NULL, category, shortcode
+             LIMIT ?",
+            vec![
+                server_socket.into(),
+                pattern.clone().into(),
+                pattern[1..].to_string().into(),
+                i64::from(limit).into(),
+            ],
+        ))
+        .await
+        .context("SERVER_EMOJI_LOAD_FAILED")?;
+    Ok(rows.iter().filter_map(row_to_emoji).collect())
+}
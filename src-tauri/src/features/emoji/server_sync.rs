@@ -0,0 +1,218 @@
+//! emoji｜服务端自定义表情同步任务。
+//!
+//! 流程：
+//! 1. 带上次的 ETag 条件请求 `GET /api/emojis`，`304` 时直接结束；
+//! 2. 与本地索引比较，只下载新增或图片地址变化的表情（同源地址，大小受限，按文件头校验格式）；
+//! 3. 图片写入内容寻址存储，索引与新 ETag 在同一事务内落库；
+//! 4. 清理不再被任何服务器引用的图片文件。
+//!
+//! 单个表情下载失败只跳过该表情（保留旧记录），此时不保存 ETag，下次同步会重新拉取完整目录。
+//!
+//! 约定：注释中文，日志英文（tracing）。
+
+use std::collections::BTreeMap;
+use std::sync::OnceLock;
+use std::time::Duration;
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+
+use crate::features::emoji::server_catalog::{self, CatalogEmoji};
+use crate::features::emoji::{asset_store, server_store};
+use crate::features::network::data::http_client::{
+    ReqwestApiRequestAdapter, build_reqwest_client, verify_https_fingerprint,
+};
+use crate::features::network::domain::ports::api_request_port::ApiHttpTlsPolicy;
+use crate::features::network::usecases::api_usecases::{self, ApiJsonRequest, api_tls_policy};
+use crate::shared::net::origin::to_http_origin;
+
+/// 目录接口路径。
+const CATALOG_PATH: &str = "/api/emojis";
+/// 单张表情图片的最大字节数。
+const MAX_EMOJI_IMAGE_BYTES: usize = 512 * 1024;
+/// 单张图片的下载超时。
+const IMAGE_DOWNLOAD_TIMEOUT: Duration = Duration::from_secs(15);
+
+/// 同步参数（与 `api_request_json` 的连接参数一致）。
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ServerEmojiSyncOptions {
+    pub server_socket: String,
+    /// 请求头（通常包含鉴权 token）。
+    pub headers: Option<BTreeMap<String, String>>,
+    pub tls_policy: Option<String>,
+    pub tls_fingerprint: Option<String>,
+}
+
+/// 同步结果。
+#[derive(Debug, Clone, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ServerEmojiSyncResult {
+    /// 本地索引是否发生变化。
+    pub changed: bool,
+    /// 同步后该服务器的表情数量（目录未变化时为 `None`）。
+    pub total: Option<usize>,
+    pub downloaded: usize,
+    pub removed: usize,
+    /// 下载失败而跳过的表情数量。
+    pub failed: usize,
+    pub etag: Option<String>,
+}
+
+/// 串行化所有服务器的同步：清理未引用图片时，不能有其他同步已写入图片但尚未落库。
+fn sync_lock() -> &'static tokio::sync::Mutex<()> {
+    static LOCK: OnceLock<tokio::sync::Mutex<()>> = OnceLock::new();
+    LOCK.get_or_init(|| tokio::sync::Mutex::new(()))
+}
+
+fn now_ms() -> i64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_millis() as i64
+}
+
+/// 下载一张图片并写入资源存储，返回资源名。
+async fn download_image(
+    client: &reqwest::Client,
+    headers: &BTreeMap<String, String>,
+    url: &str,
+) -> Result<String> {
+    let mut req = client.get(url).timeout(IMAGE_DOWNLOAD_TIMEOUT);
+    for (k, v) in headers {
+        if k.trim().is_empty() {
+            continue;
+        }
+        req = req.header(k, v);
+    }
+    let res = req.send().await.context("Failed to send request")?;
+    if !res.status().is_success() {
+        anyhow::bail!("Image request returned {}", res.status());
+    }
+    if res.content_length().unwrap_or(0) > MAX_EMOJI_IMAGE_BYTES as u64 {
+        anyhow::bail!("Emoji image is too large");
+    }
+    let bytes = res.bytes().await.context("Failed to read image body")?;
+    if bytes.len() > MAX_EMOJI_IMAGE_BYTES {
+        anyhow::bail!("Emoji image is too large");
+    }
+    let bytes = bytes.to_vec();
+    tauri::async_runtime::spawn_blocking(move || asset_store::store(&bytes)).await?
+}
+
+/// 同步某服务器的自定义表情目录。
+pub async fn sync_server_emojis(options: ServerEmojiSyncOptions) -> Result<ServerEmojiSyncResult> {
+    let _guard = sync_lock().lock().await;
+    let server_socket = options.server_socket.trim().to_string();
+    let headers = options.headers.clone().unwrap_or_default();
+    let previous_etag = server_store::load_etag(&server_socket).await?;
+
+    let mut request_headers = headers.clone();
+    if let Some(etag) = &previous_etag {
+        request_headers.insert("If-None-Match".to_string(), etag.clone());
+    }
+    let api_request_port = ReqwestApiRequestAdapter::shared();
+    let response = api_usecases::api_request_json(
+        ApiJsonRequest {
+            server_socket: server_socket.clone(),
+            method: "GET".to_string(),
+            path: CATALOG_PATH.to_string(),
+            headers: Some(request_headers),
+            body: None,
+            tls_policy: options.tls_policy.clone(),
+            tls_fingerprint: options.tls_fingerprint.clone(),
+        },
+        api_request_port.as_ref(),
+    )
+    .await?;
+    if response.status == 304 {
+        return Ok(ServerEmojiSyncResult {
+            etag: previous_etag,
+            ..Default::default()
+        });
+    }
+    if !response.ok {
+        anyhow::bail!("GET {CATALOG_PATH} returned {}", response.status);
+    }
+
+    let catalog = server_catalog::parse_catalog(&response.body.unwrap_or_default());
+    let total = catalog.len();
+    let local = server_store::load_synced(&server_socket).await?;
+    let diff = server_catalog::diff_catalog(catalog, &local);
+
+    let origin = to_http_origin(&server_socket)?;
+    let policy = api_tls_policy(options.tls_policy.as_deref());
+    if !diff.download.is_empty() && policy == ApiHttpTlsPolicy::TrustFingerprint {
+        verify_https_fingerprint(&origin, options.tls_fingerprint.as_deref().unwrap_or("")).await?;
+    }
+    let client = build_reqwest_client(policy)?;
+
+    let mut upserts = diff.keep;
+    let mut downloaded = 0;
+    let mut failed = 0;
+    for emoji in diff.download {
+        match fetch_emoji(&client, &headers, &origin, &emoji).await {
+            Ok(asset) => {
+                downloaded += 1;
+                upserts.push((emoji, asset));
+            }
+            Err(e) => {
+                failed += 1;
+                tracing::warn!(
+                    action = "app_emoji_server_asset_download_failed",
+                    server_socket = %server_socket,
+                    shortcode = %emoji.shortcode,
+                    error = %e
+                );
+            }
+        }
+    }
+
+    // 有下载失败时不记录 ETag，确保下次同步重新拉取完整目录补齐。
+    let etag = if failed == 0 { response.etag } else { None };
+    server_store::apply_sync(
+        &server_socket,
+        &upserts,
+        &diff.remove,
+        etag.as_deref(),
+        now_ms(),
+    )
+    .await?;
+
+    let referenced = server_store::referenced_assets().await?;
+    let cleaned =
+        tauri::async_runtime::spawn_blocking(move || asset_store::remove_unreferenced(&referenced))
+            .await
+            .unwrap_or(0);
+
+    // 目录内容变化（ETag 不同）时，即使图片均未变化也可能更新了分类等元数据。
+    let changed = downloaded > 0 || !diff.remove.is_empty() || etag != previous_etag;
+    tracing::info!(
+        action = "app_emoji_server_catalog_synced",
+        server_socket = %server_socket,
+        total,
+        downloaded,
+        removed = diff.remove.len(),
+        failed,
+        cleaned
+    );
+    Ok(ServerEmojiSyncResult {
+        changed,
+        total: Some(total),
+        downloaded,
+        removed: diff.remove.len(),
+        failed,
+        etag,
+    })
+}
+
+async fn fetch_emoji(
+    client: &reqwest::Client,
+    headers: &BTreeMap<String, String>,
+    origin: &str,
+    emoji: &CatalogEmoji,
+) -> Result<String> {
+    let url = server_catalog::resolve_image_url(origin, &emoji.url)
+        .context("Emoji image URL is not on the server origin")?;
+    download_image(client, headers, &url).await
+}
@@ -0,0 +1,276 @@
+//! emoji｜内置标准 emoji shortcode 索引。
+//!
+//! 复用前端 emoji picker 的数据源（`public/emoji-data.json`，emojibase compact 格式），
+//! 编译期嵌入并在首次使用时解析，搜索在 Rust 侧完成，避免把整份数据交给 WebView 过滤。
+
+use std::collections::HashMap;
+use std::sync::OnceLock;
+
+use serde::Deserialize;
+
+use crate::features::emoji::domain::types::EmojiSearchResult;
+
+const EMOJI_DATA_JSON: &str = include_str!("../../../../public/emoji-data.json");
+
+/// 肤色偏好取值上限（0 = 默认黄色，1..=5 对应 Fitzpatrick 1-2..6）。
+pub const MAX_SKIN_TONE: u32 = 5;
+
+#[derive(Debug, Deserialize)]
+struct RawSkin {
+    emoji: String,
+    #[serde(default)]
+    tone: serde_json::Value,
+}
+
+#[derive(Debug, Deserialize)]
+struct RawEmoji {
+    emoji: String,
+    #[serde(default)]
+    annotation: String,
+    #[serde(default)]
+    shortcodes: Vec<String>,
+    #[serde(default)]
+    tags: Vec<String>,
+    #[serde(default)]
+    emoticon: Option<String>,
+    #[serde(default)]
+    skins: Vec<RawSkin>,
+}
+
+/// 索引内单条 emoji（预先转小写以便匹配）。
+struct IndexedEmoji {
+    emoji: String,
+    annotation: String,
+    annotation_lower: String,
+    shortcodes: Vec<String>,
+    tags: Vec<String>,
+    emoticon: Option<String>,
+    /// 单一肤色变体：tone(1..=5) -> emoji。
+    skins: HashMap<u32, String>,
+}
+
+impl IndexedEmoji {
+    fn with_tone(&self, skin_tone: u32) -> String {
+        self.skins
+            .get(&skin_tone)
+            .cloned()
+            .unwrap_or_else(|| self.emoji.clone())
+    }
+
+    fn to_result(&self, skin_tone: u32) -> EmojiSearchResult {
+        EmojiSearchResult {
+            emoji: self.with_tone(skin_tone),
+            base_emoji: self.emoji.clone(),
+            shortcodes: self.shortcodes.clone(),
+            annotation: self.annotation.clone(),
+        }
+    }
+
+    /// 匹配得分：shortcode > 描述词首 > 标签 > 子串；不匹配返回 0。
+    fn score(&self, query: &str) -> u32 {
+        let mut best = 0;
+        for (i, code) in self.shortcodes.iter().enumerate() {
+            // 首个 shortcode 为主名称，命中时略微优先。
+            let primary = if i == 0 { 5 } else { 0 };
+            if code == query {
+                return 900 + primary;
+            }
+            if code.starts_with(query) {
+                best = best.max(700 + primary);
+            } else if code.split('_').any(|part| part.starts_with(query)) {
+                best = best.max(500 + primary);
+            }
+        }
+        if self
+            .annotation_lower
+            .split(' ')
+            .any(|word| word.starts_with(query))
+        {
+            best = best.max(400);
+        }
+        if self.tags.iter().any(|tag| tag.starts_with(query)) {
+            best = best.max(300);
+        }
+        if best == 0
+            && (self.annotation_lower.contains(query)
+                || self.shortcodes.iter().any(|code| code.contains(query)))
+        {
+            best = 100;
+        }
+        best
+    }
+}
+
+struct ShortcodeIndex {
+    items: Vec<IndexedEmoji>,
+    by_emoji: HashMap<String, usize>,
+    by_shortcode: HashMap<String, usize>,
+}
+
+fn parse_index(raw: &str) -> ShortcodeIndex {
+    let parsed: Vec<RawEmoji> = serde_json::from_str(raw).unwrap_or_else(|error| {
+        tracing::error!(action = "app_emoji_index_parse_failed", error = %error);
+        Vec::new()
+    });
+    let items: Vec<IndexedEmoji> = parsed
+        .into_iter()
+        .map(|raw| IndexedEmoji {
+            annotation_lower: raw.annotation.to_lowercase(),
+            annotation: raw.annotation,
+            shortcodes: raw.shortcodes,
+            tags: raw.tags,
+            emoticon: raw.emoticon,
+            skins: raw
+                .skins
+                .into_iter()
+                .filter_map(|skin| Some((skin.tone.as_u64()? as u32, skin.emoji)))
+                .collect(),
+            emoji: raw.emoji,
+        })
+        .collect();
+    let mut by_emoji = HashMap::with_capacity(items.len() * 2);
+    let mut by_shortcode = HashMap::with_capacity(items.len() * 2);
+    for (idx, item) in items.iter().enumerate() {
+        by_emoji.insert(item.emoji.clone(), idx);
+        for skin in item.skins.values() {
+            by_emoji.insert(skin.clone(), idx);
+        }
+        for code in &item.shortcodes {
+            // 重名 shortcode 以数据源中先出现的为准。
+            by_shortcode.entry(code.clone()).or_insert(idx);
+        }
+    }
+    ShortcodeIndex {
+        items,
+        by_emoji,
+        by_shortcode,
+    }
+}
+
+fn index() -> &'static ShortcodeIndex {
+    static INDEX: OnceLock<ShortcodeIndex> = OnceLock::new();
+    INDEX.get_or_init(|| parse_index(EMOJI_DATA_JSON))
+}
+
+/// 规范化查询：去除首尾空白与 `:` 包裹，转小写。
+fn normalize_query(query: &str) -> String {
+    query.trim().trim_matches(':').to_lowercase()
+}
+
+fn search_in(
+    index: &ShortcodeIndex,
+    query: &str,
+    limit: usize,
+    skin_tone: u32,
+) -> Vec<EmojiSearchResult> {
+    let raw = query.trim();
+    let query = normalize_query(raw);
+    if query.is_empty() {
+        return index
+            .items
+            .iter()
+            .take(limit)
+            .map(|item| item.to_result(skin_tone))
+            .collect();
+    }
+    let mut scored: Vec<(u32, usize)> = index
+        .items
+        .iter()
+        .enumerate()
+        .filter_map(|(idx, item)| {
+            // 颜文字（如 `:D`）大小写敏感，单独按原始输入匹配。
+            let score = if item.emoticon.as_deref() == Some(raw) {
+                1000
+            } else {
+                item.score(&query)
+            };
+            (score > 0).then_some((score, idx))
+        })
+        .collect();
+    // 得分相同按数据源顺序（即 picker 中的展示顺序）。
+    scored.sort_by(|a, b| b.0.cmp(&a.0).then(a.1.cmp(&b.1)));
+    scored
+        .into_iter()
+        .take(limit)
+        .map(|(_, idx)| index.items[idx].to_result(skin_tone))
+        .collect()
+}
+
+/// 按 shortcode / 描述 / 标签搜索标准 emoji。
+///
+/// # 参数
+/// - `query`：查询文本（可带 `:` 前缀，如 `:smi`）。
+/// - `limit`：返回条数上限。
+/// - `skin_tone`：肤色偏好（0 表示默认）。
+pub fn search(query: &str, limit: usize, skin_tone: u32) -> Vec<EmojiSearchResult> {
+    search_in(index(), query, limit, skin_tone)
+}
+
+/// 查找 emoji（含肤色变体）对应的索引条目，返回已按肤色偏好替换的结果。
+pub fn lookup(emoji: &str, skin_tone: u32) -> Option<EmojiSearchResult> {
+    let index = index();
+    let idx = *index.by_emoji.get(emoji)?;
+    Some(index.items[idx].to_result(skin_tone))
+}
+
+/// 按完整 shortcode（不含 `:`，大小写不敏感）查找 emoji，返回已按肤色偏好替换的字符。
+pub fn by_shortcode(code: &str, skin_tone: u32) -> Option<String> {
+    let index = index();
+    let idx = *index.by_shortcode.get(&code.to_lowercase())?;
+    Some(index.items[idx].with_tone(skin_tone))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const FIXTURE: &str = r#"[
+        {"shortcodes":["grinning","grinning_face"],"annotation":"grinning face","tags":["happy","smile"],"emoji":"😀"},
+        {"shortcodes":["grinning_face_with_closed_eyes","smile"],"annotation":"grinning face with smiling eyes","tags":["laugh"],"emoji":"😄","emoticon":":D"},
+        {"shortcodes":["wave","waving_hand"],"annotation":"waving hand","tags":["hello"],"emoji":"👋",
+         "skins":[{"emoji":"👋🏻","tone":1},{"emoji":"👋🏿","tone":5},{"emoji":"x","tone":[1,2]}]}
+    ]"#;
+
+    #[test]
+    fn shortcode_exact_match_ranks_first() {
+        let index = parse_index(FIXTURE);
+        let results = search_in(&index, ":smile:", 10, 0);
+        assert_eq!(results[0].emoji, "😄");
+        assert_eq!(results.len(), 2);
+    }
+
+    #[test]
+    fn prefix_and_tag_matches_are_found() {
+        let index = parse_index(FIXTURE);
+        let results = search_in(&index, "gri", 10, 0);
+        assert_eq!(results[0].emoji, "😀");
+        let results = search_in(&index, "hello", 10, 0);
+        assert_eq!(results[0].base_emoji, "👋");
+        assert!(search_in(&index, "zzz", 10, 0).is_empty());
+    }
+
+    #[test]
+    fn emoticon_matches_case_sensitively() {
+        let index = parse_index(FIXTURE);
+        assert_eq!(search_in(&index, ":D", 10, 0)[0].emoji, "😄");
+    }
+
+    #[test]
+    fn skin_tone_preference_is_applied() {
+        let index = parse_index(FIXTURE);
+        let results = search_in(&index, "wave", 1, 5);
+        assert_eq!(results[0].emoji, "👋🏿");
+        assert_eq!(results[0].base_emoji, "👋");
+        let results = search_in(&index, "grinning", 1, 5);
+        assert_eq!(results[0].emoji, "😀");
+    }
+
+    #[test]
+    fn embedded_data_parses() {
+        assert!(index().items.len() > 1000);
+        assert!(lookup("👋🏻", 0).is_some());
+        assert_eq!(by_shortcode("grinning", 0).as_deref(), Some("😀"));
+        assert_eq!(by_shortcode("Smiley", 0).as_deref(), Some("😃"));
+        assert_eq!(by_shortcode("not_an_emoji", 0), None);
+    }
+}
@@ -0,0 +1,59 @@
+//! emoji｜常用 emoji 使用频次持久化（system DB `emoji_usage` 表）。
+
+use anyhow::{Context, Result};
+use sea_orm::ConnectionTrait;
+
+use crate::shared::db::commands::RawStatement;
+
+/// 使用频次记录的 DB key（应用级 system DB）。
+const SYSTEM_DB_KEY: &str = "system";
+
+/// 单条使用频次记录。
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EmojiUsage {
+    pub emoji: String,
+    pub use_count: i64,
+    pub last_used_at: i64,
+}
+
+/// 记录一次 emoji 使用（次数 +1，并刷新最近使用时间）。
+pub async fn record_usage(emoji: &str, now_ms: i64) -> Result<()> {
+    let db = crate::shared::db::get_db(SYSTEM_DB_KEY).await?;
+    let stmt = RawStatement::new(
+        "INSERT INTO emoji_usage (emoji, use_count, last_used_at) VALUES (?, 1, ?)
+         ON CONFLICT(emoji) DO UPDATE SET
+           use_count = use_count + 1,
+           last_used_at = excluded.last_used_at",
+        vec![emoji.into(), now_ms.into()],
+    );
+    db.connection
+        .execute(&stmt)
+        .await
+        .context("EMOJI_USAGE_RECORD_FAILED")?;
+    Ok(())
+}
+
+/// 读取最常用的 emoji（次数降序，最近使用时间次之）。
+pub async fn load_frequent(limit: u32) -> Result<Vec<EmojiUsage>> {
+    let db = crate::shared::db::get_db(SYSTEM_DB_KEY).await?;
+    let stmt = RawStatement::new(
+        "SELECT emoji, use_count, last_used_at FROM emoji_usage
+         ORDER BY use_count DESC, last_used_at DESC LIMIT ?",
+        vec![i64::from(limit).into()],
+    );
+    let rows = db
+        .connection
+        .query_all(&stmt)
+        .await
+        .context("EMOJI_USAGE_LOAD_FAILED")?;
+    Ok(rows
+        .iter()
+        .filter_map(|row| {
+            Some(EmojiUsage {
+                emoji: row.try_get("", "emoji").ok()?,
+                use_count: row.try_get("", "use_count").ok()?,
+                last_used_at: row.try_get("", "last_used_at").ok()?,
+            })
+        })
+        .collect())
+}
@@ -0,0 +1,101 @@
+//! message_render｜渲染结果缓存（按内容 + 选项哈希）。
+//!
+//! 说明：同一条消息在滚动、切换频道时会被反复渲染；缓存命中直接返回，超出容量按写入顺序淘汰。
+//!
+//! 约定：注释中文，日志英文（tracing）。
+
+use std::collections::{HashMap, VecDeque};
+use std::sync::{Mutex, OnceLock};
+
+use sha2::{Digest, Sha256};
+
+use super::renderer::{RenderOptions, RenderedContent};
+
+/// 缓存容量（条）。
+const CACHE_CAPACITY: usize = 2048;
+
+#[derive(Default)]
+struct RenderCache {
+    entries: HashMap<[u8; 32], RenderedContent>,
+    order: VecDeque<[u8; 32]>,
+}
+
+impl RenderCache {
+    fn get(&self, key: &[u8; 32]) -> Option<RenderedContent> {
+        self.entries.get(key).cloned()
+    }
+
+    fn insert(&mut self, key: [u8; 32], value: RenderedContent, capacity: usize) {
+        if self.entries.insert(key, value).is_some() {
+            return;
+        }
+        self.order.push_back(key);
+        while self.order.len() > capacity {
+            if let Some(oldest) = self.order.pop_front() {
+                self.entries.remove(&oldest);
+            }
+        }
+    }
+}
+
+fn cache() -> &'static Mutex<RenderCache> {
+    static CACHE: OnceLock<Mutex<RenderCache>> = OnceLock::new();
+    CACHE.get_or_init(|| Mutex::new(RenderCache::default()))
+}
+
+/// 计算缓存键：内容、选项与肤色共同决定渲染结果。
+pub fn cache_key(content: &str, options: &RenderOptions, skin_tone: u32) -> [u8; 32] {
+    let mut hasher = Sha256::new();
+    hasher.update(serde_json::to_vec(options).unwrap_or_default());
+    hasher.update(skin_tone.to_le_bytes());
+    hasher.update([0u8]);
+    hasher.update(content.as_bytes());
+    hasher.finalize().into()
+}
+
+/// 读取缓存。
+pub fn get(key: &[u8; 32]) -> Option<RenderedContent> {
+    cache().lock().unwrap_or_else(|p| p.into_inner()).get(key)
+}
+
+/// 写入缓存。
+pub fn put(key: [u8; 32], value: RenderedContent) {
+    cache()
+        .lock()
+        .unwrap_or_else(|p| p.into_inner())
+        .insert(key, value, CACHE_CAPACITY);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rendered(html: &str) -> RenderedContent {
+        RenderedContent {
+            html: html.to_string(),
+            mentioned_user_ids: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn oldest_entries_are_evicted_first() {
+        let mut cache = RenderCache::default();
+        for i in 0..3u8 {
+            cache.insert([i; 32], rendered(&i.to_string()), 2);
+        }
+        assert!(cache.get(&[0; 32]).is_none());
+        assert_eq!(cache.get(&[2; 32]), Some(rendered("2")));
+    }
+
+    #[test]
+    fn key_depends_on_options() {
+        let plain = RenderOptions::default();
+        let narrowed = RenderOptions {
+            allowed_tags: Some(vec!["p".to_string()]),
+            ..RenderOptions::default()
+        };
+        assert_ne!(cache_key("hi", &plain, 0), cache_key("hi", &narrowed, 0));
+        assert_ne!(cache_key("hi", &plain, 0), cache_key("hi", &plain, 1));
+        assert_eq!(cache_key("hi", &plain, 0), cache_key("hi", &plain, 0));
+    }
+}
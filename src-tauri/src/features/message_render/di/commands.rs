@@ -0,0 +1,57 @@
+//! message_render｜Tauri 命令
+
+use crate::features::emoji::di::commands::preferred_skin_tone;
+use crate::features::emoji::shortcode_index::MAX_SKIN_TONE;
+use crate::shared::error::{CommandResult, command_error, to_command_error};
+
+use crate::features::message_render::render_cached;
+use crate::features::message_render::renderer::{RenderOptions, RenderedContent};
+
+/// 单次批量渲染的最大条数。
+const MAX_BATCH: usize = 500;
+
+async fn resolve_skin_tone(options: &RenderOptions) -> u32 {
+    match options.skin_tone {
+        Some(tone) if tone <= MAX_SKIN_TONE => tone,
+        _ => preferred_skin_tone().await,
+    }
+}
+
+/// 将一条消息内容渲染为净化后的 HTML。
+///
+/// # 参数
+/// - `content`：消息原文（Markdown，可含 `<@uid>` 提及与 `:shortcode:`）。
+/// - `options`：渲染选项（可选）。
+#[tauri::command]
+pub async fn render_message_content(
+    content: String,
+    options: Option<RenderOptions>,
+) -> CommandResult<RenderedContent> {
+    let options = options.unwrap_or_default();
+    let skin_tone = resolve_skin_tone(&options).await;
+    Ok(render_cached(&content, &options, skin_tone))
+}
+
+/// 批量渲染消息内容（顺序与输入一致，共用同一组选项）。
+#[tauri::command]
+pub async fn render_message_contents(
+    contents: Vec<String>,
+    options: Option<RenderOptions>,
+) -> CommandResult<Vec<RenderedContent>> {
+    if contents.len() > MAX_BATCH {
+        return Err(command_error(
+            "MESSAGE_RENDER_BATCH_TOO_LARGE",
+            "error.message_render_batch_too_large",
+        ));
+    }
+    let options = options.unwrap_or_default();
+    let skin_tone = resolve_skin_tone(&options).await;
+    tauri::async_runtime::spawn_blocking(move || {
+        contents
+            .iter()
+            .map(|content| render_cached(content, &options, skin_tone))
+            .collect()
+    })
+    .await
+    .map_err(|e| to_command_error("MESSAGE_RENDER_FAILED", "error.message_render_failed", e))
+}
@@ -0,0 +1,22 @@
+//! 模块入口：message_render（消息内容渲染）。
+//!
+//! 说明：Markdown 渲染与 HTML 净化放在 Rust 侧完成，WebView 只插入净化后的结果，
+//! 既减少热路径上的 JS 解析开销，也收敛 XSS 面。
+//!
+//! 约定：注释中文，日志英文（tracing）。
+pub mod cache;
+pub mod di;
+pub mod renderer;
+
+use renderer::{RenderOptions, RenderedContent};
+
+/// 渲染消息内容（先查缓存）。
+pub fn render_cached(content: &str, options: &RenderOptions, skin_tone: u32) -> RenderedContent {
+    let key = cache::cache_key(content, options, skin_tone);
+    if let Some(hit) = cache::get(&key) {
+        return hit;
+    }
+    let rendered = renderer::render(content, options, skin_tone);
+    cache::put(key, rendered.clone());
+    rendered
+}
@@ -0,0 +1,259 @@
+//! message_render｜Markdown → 净化后的 HTML。
+//!
+//! 流程：pulldown-cmark 解析 → 用户输入的原始 HTML 一律按文本处理 → 合并相邻文本并展开
+//! 提及（`<@uid>`）与 emoji（`:shortcode:`）令牌 → 生成 HTML → ammonia 按白名单净化。
+//! 代码块/行内代码中的令牌保持原样。
+//!
+//! 约定：注释中文，日志英文（tracing）。
+
+use std::collections::{BTreeMap, HashSet};
+use std::sync::OnceLock;
+
+use pulldown_cmark::{CowStr, Event, Options, Parser, TextMergeStream};
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+
+/// 默认允许的标签；调用方只能在此范围内收窄，不能放宽。
+pub const SAFE_TAGS: &[&str] = &[
+    "a",
+    "blockquote",
+    "br",
+    "code",
+    "del",
+    "em",
+    "h1",
+    "h2",
+    "h3",
+    "h4",
+    "h5",
+    "h6",
+    "hr",
+    "li",
+    "ol",
+    "p",
+    "pre",
+    "span",
+    "strong",
+    "table",
+    "tbody",
+    "td",
+    "th",
+    "thead",
+    "tr",
+    "ul",
+];
+
+/// 提及节点的 class。
+const MENTION_CLASS: &str = "cp-mention";
+
+/// 渲染选项。
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase", default)]
+pub struct RenderOptions {
+    /// 允许的标签（与 [`SAFE_TAGS`] 取交集）；为空时使用全部默认标签。
+    pub allowed_tags: Option<Vec<String>>,
+    /// 提及展示名：user id → 显示名；缺失时显示 `@uid`。
+    pub mentions: BTreeMap<String, String>,
+    /// 是否展开 `:shortcode:`（默认展开）。
+    pub disable_emoji: bool,
+    /// 肤色偏好（缺省时使用设置 `emoji_skin_tone`）。
+    pub skin_tone: Option<u32>,
+}
+
+/// 渲染结果。
+#[derive(Debug, Clone, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct RenderedContent {
+    pub html: String,
+    /// 内容中出现的被提及用户 id（去重，按出现顺序）。
+    pub mentioned_user_ids: Vec<String>,
+}
+
+fn token_regex() -> Option<&'static Regex> {
+    static RE: OnceLock<Option<Regex>> = OnceLock::new();
+    RE.get_or_init(|| Regex::new(r"<@(\d+)>|:([A-Za-z0-9_+\-]+):").ok())
+        .as_ref()
+}
+
+fn escape_html(input: &str) -> String {
+    let mut out = String::with_capacity(input.len());
+    for ch in input.chars() {
+        match ch {
+            '&' => out.push_str("&amp;"),
+            '<' => out.push_str("&lt;"),
+            '>' => out.push_str("&gt;"),
+            '"' => out.push_str("&quot;"),
+            '\'' => out.push_str("&#39;"),
+            _ => out.push(ch),
+        }
+    }
+    out
+}
+
+/// 展开一段纯文本中的令牌，返回替换后的事件序列。
+fn expand_tokens<'a>(
+    text: &str,
+    options: &RenderOptions,
+    skin_tone: u32,
+    mentioned: &mut Vec<String>,
+) -> Vec<Event<'a>> {
+    let Some(re) = token_regex() else {
+        return vec![Event::Text(CowStr::from(text.to_string()))];
+    };
+    let mut events = Vec::new();
+    let mut plain = String::new();
+    let mut last = 0;
+    for caps in re.captures_iter(text) {
+        let Some(whole) = caps.get(0) else {
+            continue;
+        };
+        let replacement = if let Some(uid) = caps.get(1) {
+            let uid = uid.as_str();
+            if !mentioned.iter().any(|m| m == uid) {
+                mentioned.push(uid.to_string());
+            }
+            let name = options.mentions.get(uid).map(String::as_str).unwrap_or(uid);
+            Some(Event::InlineHtml(CowStr::from(format!(
+                r#"<span class="{MENTION_CLASS}" data-user-id="{uid}">@{}</span>"#,
+                escape_html(name)
+            ))))
+        } else if let Some(code) = caps.get(2).filter(|_| !options.disable_emoji) {
+            crate::features::emoji::shortcode_index::by_shortcode(code.as_str(), skin_tone)
+                .map(|emoji| Event::Text(CowStr::from(emoji)))
+        } else {
+            None
+        };
+        let Some(replacement) = replacement else {
+            continue;
+        };
+        plain.push_str(&text[last..whole.start()]);
+        if let Event::Text(emoji) = &replacement {
+            plain.push_str(emoji);
+        } else {
+            if !plain.is_empty() {
+                events.push(Event::Text(CowStr::from(std::mem::take(&mut plain))));
+            }
+            events.push(replacement);
+        }
+        last = whole.end();
+    }
+    plain.push_str(&text[last..]);
+    if !plain.is_empty() {
+        events.push(Event::Text(CowStr::from(plain)));
+    }
+    events
+}
+
+fn allowed_tags(options: &RenderOptions) -> HashSet<&'static str> {
+    match &options.allowed_tags {
+        Some(tags) if !tags.is_empty() => SAFE_TAGS
+            .iter()
+            .copied()
+            .filter(|safe| tags.iter().any(|t| t.eq_ignore_ascii_case(safe)))
+            .collect(),
+        _ => SAFE_TAGS.iter().copied().collect(),
+    }
+}
+
+/// 渲染消息内容（纯函数，不读缓存）。
+pub fn render(content: &str, options: &RenderOptions, skin_tone: u32) -> RenderedContent {
+    let mut md_options = Options::empty();
+    md_options.insert(Options::ENABLE_STRIKETHROUGH);
+    md_options.insert(Options::ENABLE_TABLES);
+
+    // 用户输入的原始 HTML 按文本显示，不交给浏览器解析。
+    let parser = Parser::new_ext(content, md_options).map(|event| match event {
+        Event::Html(raw) | Event::InlineHtml(raw) => Event::Text(raw),
+        other => other,
+    });
+
+    let mut mentioned = Vec::new();
+    let mut events = Vec::new();
+    for event in TextMergeStream::new(parser) {
+        match event {
+            Event::Text(text) => {
+                events.extend(expand_tokens(&text, options, skin_tone, &mut mentioned));
+            }
+            other => events.push(other),
+        }
+    }
+
+    let mut raw_html = String::with_capacity(content.len() * 2);
+    pulldown_cmark::html::push_html(&mut raw_html, events.into_iter());
+
+    let html = ammonia::Builder::default()
+        .tags(allowed_tags(options))
+        .add_allowed_classes("span", &[MENTION_CLASS])
+        .add_tag_attributes("span", &["data-user-id"])
+        .url_schemes(["http", "https", "mailto"].into_iter().collect())
+        .link_rel(Some("noopener noreferrer"))
+        .clean(&raw_html)
+        .to_string();
+
+    RenderedContent {
+        html,
+        mentioned_user_ids: mentioned,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn render_default(content: &str) -> RenderedContent {
+        render(content, &RenderOptions::default(), 0)
+    }
+
+    #[test]
+    fn markdown_is_rendered() {
+        let out = render_default("**bold** _em_ ~~del~~\n\n- a\n- b");
+        assert!(out.html.contains("<strong>bold</strong>"));
+        assert!(out.html.contains("<em>em</em>"));
+        assert!(out.html.contains("<del>del</del>"));
+        assert!(out.html.contains("<li>a</li>"));
+    }
+
+    #[test]
+    fn raw_html_and_scripts_never_reach_output() {
+        let out = render_default("<script>alert(1)</script> <img src=x onerror=alert(1)>");
+        assert!(!out.html.contains("<script"));
+        assert!(!out.html.contains("<img"));
+        assert!(out.html.contains("&lt;script&gt;"));
+
+        let out = render_default("[x](javascript:alert(1))");
+        assert!(!out.html.contains("javascript:"));
+    }
+
+    #[test]
+    fn mentions_and_emoji_are_expanded_outside_code() {
+        let mut options = RenderOptions::default();
+        options
+            .mentions
+            .insert("42".to_string(), "<Alice>".to_string());
+        let out = render("hi <@42> :grinning: `:grinning: <@7>`", &options, 0);
+        assert!(
+            out.html
+                .contains(r#"<span class="cp-mention" data-user-id="42">@&lt;Alice&gt;</span>"#)
+        );
+        assert!(out.html.contains("😀"));
+        assert!(out.html.contains("<code>:grinning: &lt;@7&gt;</code>"));
+        assert_eq!(out.mentioned_user_ids, vec!["42".to_string()]);
+    }
+
+    #[test]
+    fn unknown_shortcodes_are_left_alone() {
+        let out = render_default("ratio 1:2:3 and :not_an_emoji:");
+        assert!(out.html.contains("1:2:3"));
+        assert!(out.html.contains(":not_an_emoji:"));
+    }
+
+    #[test]
+    fn allowed_tags_can_only_narrow_the_default_set() {
+        let options = RenderOptions {
+            allowed_tags: Some(vec!["p".to_string(), "script".to_string()]),
+            ..RenderOptions::default()
+        };
+        let out = render("**bold**", &options, 0);
+        assert_eq!(out.html.trim(), "<p>bold</p>");
+    }
+}
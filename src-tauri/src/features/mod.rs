@@ -3,12 +3,22 @@
 //! 说明：该文件负责导出子模块与组织依赖关系。
 //!
 //! 约定：注释中文，日志英文（tracing）。
+//!
+//! 托盘、多窗口与屏幕截图依赖桌面端 API，仅在 `desktop` 下编译（移动端不注册对应命令）。
+pub mod assistant;
+pub mod calls;
 pub mod emoji;
+pub mod message_render;
 pub mod network;
 pub mod plugins;
+pub mod profile;
+#[cfg(desktop)]
 pub mod screenshot;
 pub mod settings;
+pub mod translation;
+#[cfg(desktop)]
 pub mod tray;
 pub mod voice_call;
 pub mod voice_message;
+#[cfg(desktop)]
 pub mod windows;
@@ -0,0 +1,165 @@
+//! network｜数据层：服务端能力持久化（system DB `server_capabilities` 表）。
+//!
+//! 说明：
+//! - 读循环收到 `/handshake` 推送后经 [`record_in_background`] 记录协商结果：先写内存缓存，
+//!   再在后台写入 system DB，重启后未重新握手前仍可按上次结果开关特性；
+//! - 其他子系统（增量同步、表情回应、输入提示）经 [`supports`] 判断特性是否可用；
+//!   从未握手过的服务端按旧服务端处理（全部视为支持）。
+//!
+//! 约定：注释中文，日志英文（tracing）。
+
+use std::collections::HashMap;
+use std::sync::{Mutex, OnceLock};
+
+use anyhow::{Context, Result};
+use sea_orm::{ConnectionTrait, QueryResult};
+use serde::Serialize;
+
+use crate::features::network::domain::capabilities::{ProtocolFeature, ServerCapabilities};
+use crate::shared::db::commands::RawStatement;
+
+/// 能力记录所在的 DB key（应用级 system DB）。
+const SYSTEM_DB_KEY: &str = "system";
+
+/// 已记录的服务端能力（`get_server_capabilities` 返回值）。
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StoredCapabilities {
+    pub server_socket: String,
+    #[serde(flatten)]
+    pub capabilities: ServerCapabilities,
+    /// 各特性是否可用。
+    pub features: std::collections::BTreeMap<ProtocolFeature, bool>,
+    /// 协商时间（毫秒时间戳）。
+    pub negotiated_at: i64,
+}
+
+impl StoredCapabilities {
+    fn new(server_socket: &str, capabilities: ServerCapabilities, negotiated_at: i64) -> Self {
+        Self {
+            server_socket: server_socket.to_string(),
+            features: capabilities.features(),
+            capabilities,
+            negotiated_at,
+        }
+    }
+}
+
+fn cache() -> &'static Mutex<HashMap<String, StoredCapabilities>> {
+    static CACHE: OnceLock<Mutex<HashMap<String, StoredCapabilities>>> = OnceLock::new();
+    CACHE.get_or_init(|| Mutex::new(HashMap::new()))
+}
+
+fn now_ms() -> i64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_millis() as i64
+}
+
+/// 记录一次握手协商结果（立即更新缓存，后台写库）。
+pub fn record_in_background(server_socket: &str, capabilities: ServerCapabilities) {
+    let stored = StoredCapabilities::new(server_socket, capabilities, now_ms());
+    cache()
+        .lock()
+        .unwrap_or_else(|p| p.into_inner())
+        .insert(server_socket.to_string(), stored.clone());
+    tauri::async_runtime::spawn(async move {
+        if let Err(e) = save(&stored).await {
+            tracing::warn!(
+                action = "network_capabilities_save_failed",
+                server_socket = %stored.server_socket,
+                error = %e
+            );
+        }
+    });
+}
+
+async fn save(stored: &StoredCapabilities) -> Result<()> {
+    let db = crate::shared::db::get_db(SYSTEM_DB_KEY).await?;
+    let routes = serde_json::to_string(&stored.capabilities.routes)?;
+    let stmt = RawStatement::new(
+        "INSERT INTO server_capabilities
+             (server_socket, protocol_version, routes, advertised, negotiated_at)
+         VALUES (?, ?, ?, ?, ?)
+         ON CONFLICT(server_socket) DO UPDATE SET
+           protocol_version = excluded.protocol_version,
+           routes = excluded.routes,
+           advertised = excluded.advertised,
+           negotiated_at = excluded.negotiated_at",
+        vec![
+            stored.server_socket.clone().into(),
+            i64::from(stored.capabilities.protocol_version).into(),
+            routes.into(),
+            stored.capabilities.advertised.into(),
+            stored.negotiated_at.into(),
+        ],
+    );
+    db.connection
+        .execute(&stmt)
+        .await
+        .context("SERVER_CAPABILITIES_SAVE_FAILED")?;
+    Ok(())
+}
+
+fn row_to_stored(server_socket: &str, row: &QueryResult) -> Option<StoredCapabilities> {
+    let protocol_version: i64 = row.try_get("", "protocol_version").ok()?;
+    let routes: String = row.try_get("", "routes").ok()?;
+    let advertised: bool = row.try_get("", "advertised").ok()?;
+    let negotiated_at: i64 = row.try_get("", "negotiated_at").ok()?;
+    let capabilities = ServerCapabilities {
+        protocol_version: u32::try_from(protocol_version).ok()?,
+        routes: serde_json::from_str(&routes).ok()?,
+        advertised,
+    };
+    Some(StoredCapabilities::new(
+        server_socket,
+        capabilities,
+        negotiated_at,
+    ))
+}
+
+/// 读取某服务端最近一次协商的能力（缓存优先，其次 system DB）；从未握手过时为 `None`。
+pub async fn load(server_socket: &str) -> Result<Option<StoredCapabilities>> {
+    if let Some(stored) = cache()
+        .lock()
+        .unwrap_or_else(|p| p.into_inner())
+        .get(server_socket)
+    {
+        return Ok(Some(stored.clone()));
+    }
+    let db = crate::shared::db::get_db(SYSTEM_DB_KEY).await?;
+    let row = db
+        .connection
+        .query_one(&RawStatement::new(
+            "SELECT protocol_version, routes, advertised, negotiated_at
+             FROM server_capabilities WHERE server_socket = ?",
+            vec![server_socket.to_string().into()],
+        ))
+        .await
+        .context("SERVER_CAPABILITIES_LOAD_FAILED")?;
+    let stored = row.and_then(|row| row_to_stored(server_socket, &row));
+    if let Some(stored) = &stored {
+        cache()
+            .lock()
+            .unwrap_or_else(|p| p.into_inner())
+            .entry(server_socket.to_string())
+            .or_insert_with(|| stored.clone());
+    }
+    Ok(stored)
+}
+
+/// 某服务端是否支持某特性；未知（从未握手或读取失败）时视为支持。
+pub async fn supports(server_socket: &str, feature: ProtocolFeature) -> bool {
+    match load(server_socket.trim()).await {
+        Ok(stored) => stored.is_none_or(|s| s.capabilities.supports(feature)),
+        Err(e) => {
+            tracing::debug!(
+                action = "network_capabilities_unavailable",
+                server_socket = %server_socket,
+                error = %e
+            );
+            true
+        }
+    }
+}
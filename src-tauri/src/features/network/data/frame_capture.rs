@@ -0,0 +1,443 @@
+//! network｜数据层：调试抓包（frame capture）。
+//!
+//! 说明：
+//! - 开发者模式（设置项 `frame_capture`）下按 server 记录收发帧到环形缓冲，可选追加到
+//!   `<data>/debug/*.ndjson`；
+//! - 读写路径只做 `ACTIVE` 原子判断并把 payload 副本投递到有界队列（队列满时丢弃并计数），
+//!   解析、脱敏、环形缓冲与落盘都在专用写入线程完成，不在 async 读循环中加锁或做文件 I/O；
+//! - `stop` / `export` 先等待队列排空，保证已记录的帧可见；
+//! - 明文 JSON 中的敏感字段（`key` / `*token*` / `*password*` / `*secret*`）写入前脱敏。
+//!
+//! 约定：注释中文，日志英文（tracing）。
+
+use std::collections::{HashMap, VecDeque};
+use std::fs::File;
+use std::io::{BufWriter, Write};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc::{self, Receiver, SyncSender, TrySendError};
+use std::sync::{Mutex, OnceLock};
+use std::time::Duration;
+
+use serde_json::Value;
+
+use crate::features::network::data::frame_compression::split_frames;
+use crate::features::network::domain::types::{CapturedFrame, FrameCaptureStatus, FrameDirection};
+
+/// 每个 server 的环形缓冲容量（帧数）。
+pub const RING_CAPACITY: usize = 2000;
+/// 非 JSON 帧记录的前缀长度（bytes）。
+const HEX_PREFIX_BYTES: usize = 32;
+/// 换钥包前置的 nonce + AAD 长度（明文握手时为全零）。
+const HANDSHAKE_PREFIX_BYTES: usize = 32;
+const REDACTED: &str = "<redacted>";
+/// 写入队列容量（条）；读写路径不等待，满时丢弃。
+const QUEUE_CAPACITY: usize = 4096;
+/// `stop` / `export` 等待队列排空的上限。
+const DRAIN_TIMEOUT: Duration = Duration::from_secs(5);
+
+static ACTIVE: AtomicBool = AtomicBool::new(false);
+
+/// 投递给写入线程的消息。
+enum CaptureMsg {
+    /// 入站为单帧 payload；出站为一次完整写入（由写入线程拆帧）。
+    Frame {
+        server_socket: String,
+        direction: FrameDirection,
+        ts_ms: i64,
+        data: Vec<u8>,
+    },
+    /// 排空屏障：处理完之前的消息后回执。
+    Drain(SyncSender<()>),
+}
+
+#[derive(Default)]
+struct CaptureState {
+    filter: Option<String>,
+    seq: u64,
+    buffers: HashMap<String, VecDeque<CapturedFrame>>,
+    file: Option<(PathBuf, BufWriter<File>)>,
+}
+
+impl CaptureState {
+    fn buffered(&self) -> usize {
+        self.buffers.values().map(VecDeque::len).sum()
+    }
+
+    fn status(&self) -> FrameCaptureStatus {
+        FrameCaptureStatus {
+            active: ACTIVE.load(Ordering::Acquire),
+            server_socket: self.filter.clone(),
+            buffered: self.buffered(),
+            file_path: self
+                .file
+                .as_ref()
+                .map(|(path, _)| path.display().to_string()),
+        }
+    }
+
+    fn push(&mut self, server_socket: &str, direction: FrameDirection, ts_ms: i64, payload: &[u8]) {
+        if self
+            .filter
+            .as_deref()
+            .is_some_and(|only| only != server_socket)
+        {
+            return;
+        }
+        self.seq += 1;
+        let frame = capture_frame(self.seq, ts_ms, server_socket, direction, payload);
+        if let Some((path, writer)) = self.file.as_mut()
+            && let Err(e) = write_ndjson_line(writer, &frame)
+        {
+            tracing::warn!(action = "network_frame_capture_write_failed", path = %path.display(), error = %e);
+            self.file = None;
+        }
+        let ring = self.buffers.entry(server_socket.to_string()).or_default();
+        if ring.len() >= RING_CAPACITY {
+            ring.pop_front();
+        }
+        ring.push_back(frame);
+    }
+}
+
+fn state() -> &'static Mutex<CaptureState> {
+    static STATE: OnceLock<Mutex<CaptureState>> = OnceLock::new();
+    STATE.get_or_init(|| Mutex::new(CaptureState::default()))
+}
+
+/// 写入队列发送端（首次使用时启动专用写入线程）。
+fn queue() -> Option<&'static SyncSender<CaptureMsg>> {
+    static QUEUE: OnceLock<Option<SyncSender<CaptureMsg>>> = OnceLock::new();
+    QUEUE
+        .get_or_init(|| {
+            let (tx, rx) = mpsc::sync_channel(QUEUE_CAPACITY);
+            match std::thread::Builder::new()
+                .name("frame-capture".to_string())
+                .spawn(move || run_writer(rx))
+            {
+                Ok(_) => Some(tx),
+                Err(e) => {
+                    tracing::warn!(action = "network_frame_capture_writer_spawn_failed", error = %e);
+                    None
+                }
+            }
+        })
+        .as_ref()
+}
+
+/// 写入线程：逐条处理队列消息（解析/脱敏/环形缓冲/落盘）。
+fn run_writer(rx: Receiver<CaptureMsg>) {
+    for msg in rx {
+        match msg {
+            CaptureMsg::Frame {
+                server_socket,
+                direction,
+                ts_ms,
+                data,
+            } => {
+                let Ok(mut guard) = state().lock() else {
+                    continue;
+                };
+                match direction {
+                    FrameDirection::Outbound => match split_frames(&data) {
+                        Some(frames) => {
+                            for payload in frames {
+                                guard.push(&server_socket, direction, ts_ms, payload);
+                            }
+                        }
+                        None => guard.push(&server_socket, direction, ts_ms, &data),
+                    },
+                    FrameDirection::Inbound => guard.push(&server_socket, direction, ts_ms, &data),
+                }
+            }
+            CaptureMsg::Drain(ack) => {
+                let _ = ack.send(());
+            }
+        }
+    }
+}
+
+/// 非阻塞投递一条帧记录；队列已满时丢弃（抓包为尽力而为）。
+fn enqueue(server_socket: &str, direction: FrameDirection, data: &[u8]) {
+    let Some(tx) = queue() else {
+        return;
+    };
+    let msg = CaptureMsg::Frame {
+        server_socket: server_socket.to_string(),
+        direction,
+        ts_ms: now_ms(),
+        data: data.to_vec(),
+    };
+    if let Err(TrySendError::Full(_)) = tx.try_send(msg) {
+        tracing::debug!(action = "network_frame_capture_dropped", server_socket = %server_socket);
+    }
+}
+
+/// 等待写入线程处理完已投递的记录（阻塞调用，需在非 async 上下文中使用）。
+fn drain() {
+    let Some(tx) = queue() else {
+        return;
+    };
+    let (ack, done) = mpsc::sync_channel(1);
+    if tx.send(CaptureMsg::Drain(ack)).is_ok() {
+        let _ = done.recv_timeout(DRAIN_TIMEOUT);
+    }
+}
+
+fn now_ms() -> i64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_millis() as i64
+}
+
+fn write_ndjson_line(writer: &mut impl Write, frame: &CapturedFrame) -> anyhow::Result<()> {
+    serde_json::to_writer(&mut *writer, frame)?;
+    writer.write_all(b"\n")?;
+    Ok(())
+}
+
+/// 字段名是否视为敏感（大小写不敏感）。
+fn is_secret_field(name: &str) -> bool {
+    let lower = name.to_ascii_lowercase();
+    lower == "key"
+        || lower.ends_with("_key")
+        || lower.contains("token")
+        || lower.contains("password")
+        || lower.contains("secret")
+}
+
+/// 递归脱敏 JSON 中的敏感字段。
+fn redact(value: &mut Value) {
+    match value {
+        Value::Object(map) => {
+            for (name, field) in map.iter_mut() {
+                if is_secret_field(name) && !field.is_null() {
+                    *field = Value::String(REDACTED.to_string());
+                } else {
+                    redact(field);
+                }
+            }
+        }
+        Value::Array(items) => items.iter_mut().for_each(redact),
+        _ => {}
+    }
+}
+
+/// 识别明文 JSON 帧：握手推送直接以 `{` 开头；换钥包前置 32 字节全零 nonce/AAD。
+fn plaintext_json(payload: &[u8]) -> Option<Value> {
+    let body = match payload.first() {
+        Some(b'{') => payload,
+        _ if payload.len() > HANDSHAKE_PREFIX_BYTES
+            && payload[..HANDSHAKE_PREFIX_BYTES].iter().all(|b| *b == 0) =>
+        {
+            &payload[HANDSHAKE_PREFIX_BYTES..]
+        }
+        _ => return None,
+    };
+    serde_json::from_slice(body).ok()
+}
+
+fn capture_frame(
+    seq: u64,
+    ts_ms: i64,
+    server_socket: &str,
+    direction: FrameDirection,
+    payload: &[u8],
+) -> CapturedFrame {
+    let json = plaintext_json(payload).map(|mut value| {
+        redact(&mut value);
+        value
+    });
+    let hex_prefix = json.is_none().then(|| {
+        payload
+            .iter()
+            .take(HEX_PREFIX_BYTES)
+            .map(|b| format!("{b:02x}"))
+            .collect()
+    });
+    CapturedFrame {
+        seq,
+        ts_ms,
+        server_socket: server_socket.to_string(),
+        direction,
+        len: payload.len(),
+        json,
+        hex_prefix,
+    }
+}
+
+/// 记录一个入站帧（已拆包的 payload；只投递队列，不阻塞）。
+pub fn record_inbound(server_socket: &str, payload: &[u8]) {
+    if !ACTIVE.load(Ordering::Acquire) {
+        return;
+    }
+    enqueue(server_socket, FrameDirection::Inbound, payload);
+}
+
+/// 记录一次出站写入：完整帧逐帧记录，其余情况按单条原始 bytes 记录（只投递队列，不阻塞）。
+pub fn record_outbound(server_socket: &str, data: &[u8]) {
+    if !ACTIVE.load(Ordering::Acquire) {
+        return;
+    }
+    enqueue(server_socket, FrameDirection::Outbound, data);
+}
+
+/// 开始抓包（清空旧缓冲；阻塞调用）。
+///
+/// # 参数
+/// - `server_socket`：仅抓取该 server（为空表示全部）。
+/// - `debug_dir`：需要落盘时传入 NDJSON 目录。
+pub fn start(
+    server_socket: Option<String>,
+    debug_dir: Option<&Path>,
+) -> anyhow::Result<FrameCaptureStatus> {
+    let file = match debug_dir {
+        Some(dir) => {
+            std::fs::create_dir_all(dir)?;
+            let path = dir.join(format!("frames-{}.ndjson", now_ms()));
+            let file = File::create(&path)
+                .map_err(|e| anyhow::anyhow!("Failed to create capture file: {}", e))?;
+            Some((path, BufWriter::new(file)))
+        }
+        None => None,
+    };
+    drain();
+    let mut guard = state()
+        .lock()
+        .map_err(|_| anyhow::anyhow!("Frame capture state poisoned"))?;
+    *guard = CaptureState {
+        filter: server_socket,
+        file,
+        ..CaptureState::default()
+    };
+    ACTIVE.store(true, Ordering::Release);
+    Ok(guard.status())
+}
+
+/// 停止抓包（等待队列排空，保留环形缓冲以便导出，并刷新落盘文件；阻塞调用）。
+pub fn stop() -> anyhow::Result<FrameCaptureStatus> {
+    ACTIVE.store(false, Ordering::Release);
+    drain();
+    let mut guard = state()
+        .lock()
+        .map_err(|_| anyhow::anyhow!("Frame capture state poisoned"))?;
+    let status = guard.status();
+    if let Some((path, mut writer)) = guard.file.take() {
+        writer
+            .flush()
+            .map_err(|e| anyhow::anyhow!("Failed to flush {}: {}", path.display(), e))?;
+    }
+    Ok(status)
+}
+
+/// 导出环形缓冲中的帧为 NDJSON 文件（按序号排序；阻塞调用）。
+///
+/// # 返回值
+/// 返回 `(文件路径, 帧数)`。
+pub fn export(server_socket: Option<&str>, debug_dir: &Path) -> anyhow::Result<(PathBuf, usize)> {
+    drain();
+    let mut frames: Vec<CapturedFrame> = {
+        let guard = state()
+            .lock()
+            .map_err(|_| anyhow::anyhow!("Frame capture state poisoned"))?;
+        guard
+            .buffers
+            .iter()
+            .filter(|(socket, _)| server_socket.is_none_or(|only| only == socket.as_str()))
+            .flat_map(|(_, ring)| ring.iter().cloned())
+            .collect()
+    };
+    frames.sort_by_key(|frame| frame.seq);
+
+    std::fs::create_dir_all(debug_dir)?;
+    let path = debug_dir.join(format!("frames-export-{}.ndjson", now_ms()));
+    let mut writer = BufWriter::new(
+        File::create(&path).map_err(|e| anyhow::anyhow!("Failed to create export file: {}", e))?,
+    );
+    for frame in &frames {
+        write_ndjson_line(&mut writer, frame)?;
+    }
+    writer.flush()?;
+    Ok((path, frames.len()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn secret_fields_are_redacted_recursively() {
+        let mut value = serde_json::json!({
+            "id": 1,
+            "key": "aes",
+            "data": { "access_token": "t", "items": [{ "Password": "p", "name": "n" }] },
+            "session_key": null
+        });
+        redact(&mut value);
+        assert_eq!(value["key"], REDACTED);
+        assert_eq!(value["data"]["access_token"], REDACTED);
+        assert_eq!(value["data"]["items"][0]["Password"], REDACTED);
+        assert_eq!(value["data"]["items"][0]["name"], "n");
+        assert_eq!(value["id"], 1);
+        assert!(value["session_key"].is_null());
+    }
+
+    #[test]
+    fn key_pack_and_ciphertext_are_captured_safely() {
+        let mut key_pack = vec![0u8; HANDSHAKE_PREFIX_BYTES];
+        key_pack.extend_from_slice(br#"{"id":1,"session_id":0,"key":"c2VjcmV0"}"#);
+        let frame = capture_frame(1, 0, "s", FrameDirection::Outbound, &key_pack);
+        let json = frame.json.expect("key pack is plaintext json");
+        assert_eq!(json["key"], REDACTED);
+        assert!(frame.hex_prefix.is_none());
+
+        let cipher = vec![0xAB; 100];
+        let frame = capture_frame(2, 0, "s", FrameDirection::Inbound, &cipher);
+        assert!(frame.json.is_none());
+        assert_eq!(frame.len, 100);
+        assert_eq!(
+            frame.hex_prefix.map(|hex| hex.len()),
+            Some(HEX_PREFIX_BYTES * 2)
+        );
+    }
+
+    #[test]
+    fn ring_buffer_keeps_latest_frames_per_server() {
+        let mut state = CaptureState {
+            filter: Some("a".to_string()),
+            ..CaptureState::default()
+        };
+        for i in 0..(RING_CAPACITY + 5) {
+            state.push("a", FrameDirection::Inbound, 0, &(i as u32).to_be_bytes());
+        }
+        state.push("b", FrameDirection::Inbound, 0, b"ignored");
+        assert_eq!(state.buffered(), RING_CAPACITY);
+        assert_eq!(state.buffers["a"].front().map(|f| f.seq), Some(6));
+        assert!(!state.buffers.contains_key("b"));
+    }
+
+    #[test]
+    fn recorded_frames_are_processed_by_the_writer_thread() {
+        start(Some("capture-test".to_string()), None).expect("start");
+        record_inbound("capture-test", br#"{"id":-1,"token":"t"}"#);
+        let mut framed = 3u16.to_be_bytes().to_vec();
+        framed.extend_from_slice(b"abc");
+        framed.extend_from_slice(&2u16.to_be_bytes());
+        framed.extend_from_slice(b"de");
+        record_outbound("capture-test", &framed);
+        let status = stop().expect("stop");
+        assert_eq!(status.buffered, 3);
+
+        let guard = state().lock().expect("state");
+        let ring = &guard.buffers["capture-test"];
+        assert_eq!(
+            ring[0].json.as_ref().map(|v| v["token"].clone()),
+            Some(REDACTED.into())
+        );
+        assert_eq!(
+            ring.iter().map(|f| f.len).collect::<Vec<_>>(),
+            vec![21, 3, 2]
+        );
+    }
+}
@@ -0,0 +1,201 @@
+//! network｜数据层：大 payload zstd 压缩（加密前）。
+//!
+//! 说明：
+//! - 设置项 `frame_compression` 开启时，前端在换钥包中声明 `compression: ["zstd"]`；
+//!   服务端在 `/handshake` 推送中返回 `compression: "zstd"` 表示本连接启用；
+//! - 压缩作用于明文：前端在 AES-GCM 加密前经 `tcp_encode_payload` 压缩，解密后经
+//!   `tcp_decode_payload` 解压；传输层只观察握手，不改写帧（密文不可压缩）；
+//! - 启用后明文首字节为 flags 头（[`PAYLOAD_FLAG_ZSTD`] 表示其后为 zstd 数据），
+//!   flags 位于密文内，由 GCM tag 一并认证，不可被中间人篡改。
+//!
+//! 约定：注释中文，日志英文（tracing）。
+
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use serde_json::Value;
+
+use crate::features::network::domain::codec::handshake_push_data;
+use crate::shared::metrics;
+
+/// 压缩阈值：payload 不小于该长度才尝试压缩。
+pub const COMPRESSION_THRESHOLD_BYTES: usize = 1024;
+/// 明文 flags 头：其后的数据为 zstd 压缩结果。
+pub const PAYLOAD_FLAG_ZSTD: u8 = 0x01;
+/// 单帧解压后的长度上限（防止解压炸弹）。
+const DECOMPRESSED_MAX_BYTES: usize = 1024 * 1024;
+const ZSTD_LEVEL: i32 = 3;
+
+/// 从明文握手推送中判断服务端是否选定 zstd。
+///
+/// # 返回值
+/// - `Some(true/false)`：payload 为握手推送；未携带 `compression` 的旧服务端视为未启用。
+/// - `None`：不是握手推送。
+pub fn zstd_from_handshake(payload: &[u8]) -> Option<bool> {
+    let data = handshake_push_data(payload)?;
+    Some(
+        data.get("compression")
+            .and_then(Value::as_str)
+            .is_some_and(|raw| raw.trim().eq_ignore_ascii_case("zstd")),
+    )
+}
+
+/// 单连接的压缩协商状态（读循环写入，编解码命令读取）。
+#[derive(Debug, Default)]
+pub struct NegotiatedCompression {
+    /// 本地设置是否允许压缩（连接建立时确定）。
+    allowed: bool,
+    active: AtomicBool,
+}
+
+impl NegotiatedCompression {
+    /// 创建协商状态；`allowed=false` 时即使服务端选定 zstd 也不启用。
+    pub fn new(allowed: bool) -> Self {
+        Self {
+            allowed,
+            active: AtomicBool::new(false),
+        }
+    }
+
+    /// 本连接是否已启用压缩。
+    pub fn is_active(&self) -> bool {
+        self.active.load(Ordering::Acquire)
+    }
+
+    /// 若 payload 为握手推送，则更新协商结果并返回是否启用。
+    pub fn observe_frame(&self, payload: &[u8]) -> Option<bool> {
+        let active = self.allowed && zstd_from_handshake(payload)?;
+        self.active.store(active, Ordering::Release);
+        Some(active)
+    }
+}
+
+/// 为待加密明文加上 flags 头；超过阈值且压缩后更小时写入 zstd 数据。
+pub fn encode_plaintext(plaintext: Vec<u8>) -> Vec<u8> {
+    let Some(compressed) = compress_payload(&plaintext) else {
+        let mut out = Vec::with_capacity(plaintext.len() + 1);
+        out.push(0);
+        out.extend_from_slice(&plaintext);
+        return out;
+    };
+    metrics::incr_counter(metrics::FRAMES_COMPRESSED, 1);
+    metrics::incr_counter(
+        metrics::FRAME_COMPRESSION_SAVED_BYTES,
+        (plaintext.len() - compressed.len()) as u64,
+    );
+    let mut out = Vec::with_capacity(compressed.len() + 1);
+    out.push(PAYLOAD_FLAG_ZSTD);
+    out.extend_from_slice(&compressed);
+    out
+}
+
+/// 去掉解密后明文的 flags 头，按需解压。
+///
+/// # 返回值
+/// - `Err`：缺少 flags 头、含未知 flag 或解压失败（已通过 GCM 认证，视为协议错误）。
+pub fn decode_plaintext(plaintext: &[u8]) -> anyhow::Result<Vec<u8>> {
+    let (&flags, body) = plaintext
+        .split_first()
+        .ok_or_else(|| anyhow::anyhow!("Missing payload flags header"))?;
+    match flags {
+        0 => Ok(body.to_vec()),
+        PAYLOAD_FLAG_ZSTD => {
+            let plain = decompress_payload(body)?;
+            metrics::incr_counter(metrics::FRAMES_DECOMPRESSED, 1);
+            Ok(plain)
+        }
+        other => Err(anyhow::anyhow!("Unknown payload flags: {:#04x}", other)),
+    }
+}
+
+/// 将数据按 2 字节大端长度前缀拆分为 payload；存在半帧时返回 `None`。
+pub(crate) fn split_frames(data: &[u8]) -> Option<Vec<&[u8]>> {
+    let mut frames = Vec::new();
+    let mut rest = data;
+    while !rest.is_empty() {
+        if rest.len() < 2 {
+            return None;
+        }
+        let len = u16::from_be_bytes([rest[0], rest[1]]) as usize;
+        let payload = rest.get(2..2 + len)?;
+        frames.push(payload);
+        rest = &rest[2 + len..];
+    }
+    Some(frames)
+}
+
+/// 压缩单帧 payload；低于阈值或压缩后不更小时返回 `None`。
+pub fn compress_payload(payload: &[u8]) -> Option<Vec<u8>> {
+    if payload.len() < COMPRESSION_THRESHOLD_BYTES {
+        return None;
+    }
+    let compressed = zstd::bulk::compress(payload, ZSTD_LEVEL).ok()?;
+    (compressed.len() < payload.len()).then_some(compressed)
+}
+
+/// 解压单帧 payload（长度上限 1 MiB）。
+pub fn decompress_payload(payload: &[u8]) -> anyhow::Result<Vec<u8>> {
+    zstd::bulk::decompress(payload, DECOMPRESSED_MAX_BYTES)
+        .map_err(|e| anyhow::anyhow!("Failed to decompress zstd frame: {}", e))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const HANDSHAKE_ZSTD: &[u8] =
+        br#"{"id":-1,"code":0,"data":{"route":"handshake","data":{"compression":"zstd"}}}"#;
+
+    #[test]
+    fn handshake_selects_zstd_only_when_allowed() {
+        assert_eq!(zstd_from_handshake(HANDSHAKE_ZSTD), Some(true));
+        assert_eq!(
+            zstd_from_handshake(br#"{"id":-1,"code":0,"data":{"route":"handshake","data":{}}}"#),
+            Some(false)
+        );
+        assert_eq!(zstd_from_handshake(b"\x00\x01"), None);
+
+        let disabled = NegotiatedCompression::new(false);
+        assert_eq!(disabled.observe_frame(HANDSHAKE_ZSTD), Some(false));
+        assert!(!disabled.is_active());
+        let enabled = NegotiatedCompression::new(true);
+        assert_eq!(enabled.observe_frame(HANDSHAKE_ZSTD), Some(true));
+        assert!(enabled.is_active());
+    }
+
+    #[test]
+    fn small_or_incompressible_payloads_are_kept() {
+        assert_eq!(compress_payload(&[b'a'; 16]), None);
+        let mut state = 0x9E37_79B9_7F4A_7C15u64;
+        let noise: Vec<u8> = (0..4096)
+            .map(|_| {
+                // xorshift64：近似随机字节，模拟不可压缩的数据。
+                state ^= state << 13;
+                state ^= state >> 7;
+                state ^= state << 17;
+                (state >> 56) as u8
+            })
+            .collect();
+        assert_eq!(compress_payload(&noise), None);
+        assert_eq!(encode_plaintext(noise.clone())[0], 0);
+    }
+
+    #[test]
+    fn plaintext_round_trips_through_flags_header() {
+        let big = vec![b'x'; 8192];
+        let sealed = encode_plaintext(big.clone());
+        assert_eq!(sealed[0], PAYLOAD_FLAG_ZSTD);
+        assert!(sealed.len() < big.len());
+        assert_eq!(decode_plaintext(&sealed).expect("decode"), big);
+
+        let small = encode_plaintext(b"{}".to_vec());
+        assert_eq!(small, b"\x00{}");
+        assert_eq!(decode_plaintext(&small).expect("decode"), b"{}");
+    }
+
+    #[test]
+    fn malformed_flags_are_rejected() {
+        assert!(decode_plaintext(b"").is_err());
+        assert!(decode_plaintext(b"\x80{}").is_err());
+        assert!(decode_plaintext(b"\x01not zstd").is_err());
+    }
+}
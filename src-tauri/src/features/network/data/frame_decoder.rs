@@ -0,0 +1,151 @@
+//! network｜数据层：frame_decoder（Netty length-prefix 拆包，基于 `BytesMut`）。
+//!
+//! 说明：
+//! - 读循环直接 `read_buf` 到解码器内部缓冲，不再为每次读取分配/拷贝 chunk；
+//! - 拆出的帧通过 `split_to` 共享底层内存，不做逐帧 memmove；
+//! - 缓冲在消费后复用，空间不足时按 [`READ_RESERVE_BYTES`] 追加预留；
+//! - 帧声明长度超过当前上限（见 `frame_limit` 协商）时返回 [`DecodedFrame::Oversized`]，
+//!   调用方应关闭连接：此时无法可靠定位下一帧边界。
+//!
+//! 约定：注释中文，日志英文（tracing）。
+
+use bytes::{Buf, BytesMut};
+
+/// 单帧最大长度（2 字节无符号长度前缀）。
+pub const FRAME_MAX_BYTES: usize = u16::MAX as usize;
+/// 每次读取前保证的最小可写空间。
+pub const READ_RESERVE_BYTES: usize = 16 * 1024;
+
+const LENGTH_PREFIX_BYTES: usize = 2;
+
+/// 拆包结果。
+#[derive(Debug, PartialEq, Eq)]
+pub enum DecodedFrame {
+    /// 完整帧载荷（不含长度前缀）。
+    Frame(BytesMut),
+    /// 帧声明长度超过上限（协议错误，携带声明长度）。
+    Oversized(usize),
+}
+
+/// 长度前缀帧解码器。
+pub struct FrameDecoder {
+    buf: BytesMut,
+    max_frame: usize,
+}
+
+impl Default for FrameDecoder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl FrameDecoder {
+    /// 创建解码器；单帧上限默认为 [`FRAME_MAX_BYTES`]。
+    pub fn new() -> Self {
+        Self {
+            buf: BytesMut::with_capacity(READ_RESERVE_BYTES),
+            max_frame: FRAME_MAX_BYTES,
+        }
+    }
+
+    /// 当前单帧上限。
+    pub fn max_frame(&self) -> usize {
+        self.max_frame
+    }
+
+    /// 更新单帧上限（握手协商后调用）。
+    pub fn set_max_frame(&mut self, max_frame: usize) {
+        self.max_frame = max_frame.min(FRAME_MAX_BYTES);
+    }
+
+    /// 读取目标缓冲（调用方以 `read_buf` 追加数据）；保证至少 [`READ_RESERVE_BYTES`] 可写空间。
+    pub fn read_target(&mut self) -> &mut BytesMut {
+        self.buf.reserve(READ_RESERVE_BYTES);
+        &mut self.buf
+    }
+
+    /// 最近一次读入的 `n` 字节（用于 legacy 原始字节事件）。
+    pub fn tail(&self, n: usize) -> &[u8] {
+        &self.buf[self.buf.len().saturating_sub(n)..]
+    }
+
+    /// 当前未消费的字节数。
+    pub fn buffered(&self) -> usize {
+        self.buf.len()
+    }
+
+    /// 取出下一个完整帧；空帧直接跳过，数据不足时返回 `None`。
+    pub fn next_frame(&mut self) -> Option<DecodedFrame> {
+        loop {
+            if self.buf.len() < LENGTH_PREFIX_BYTES {
+                return None;
+            }
+            let len = u16::from_be_bytes([self.buf[0], self.buf[1]]) as usize;
+            if len == 0 {
+                self.buf.advance(LENGTH_PREFIX_BYTES);
+                continue;
+            }
+            if len > self.max_frame {
+                return Some(DecodedFrame::Oversized(len));
+            }
+            if self.buf.len() < LENGTH_PREFIX_BYTES + len {
+                return None;
+            }
+            self.buf.advance(LENGTH_PREFIX_BYTES);
+            return Some(DecodedFrame::Frame(self.buf.split_to(len)));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn frame(payload: &[u8]) -> Vec<u8> {
+        let mut out = (payload.len() as u16).to_be_bytes().to_vec();
+        out.extend_from_slice(payload);
+        out
+    }
+
+    fn drain(decoder: &mut FrameDecoder) -> Vec<Vec<u8>> {
+        let mut out = Vec::new();
+        while let Some(DecodedFrame::Frame(f)) = decoder.next_frame() {
+            out.push(f.to_vec());
+        }
+        out
+    }
+
+    #[test]
+    fn decodes_split_and_empty_frames() {
+        let mut decoder = FrameDecoder::new();
+        let mut stream = frame(b"hello");
+        stream.extend_from_slice(&[0, 0]);
+        stream.extend_from_slice(&frame(b"world"));
+
+        decoder.read_target().extend_from_slice(&stream[..4]);
+        assert!(drain(&mut decoder).is_empty());
+        decoder.read_target().extend_from_slice(&stream[4..]);
+        assert_eq!(decoder.tail(3), b"rld");
+        assert_eq!(
+            drain(&mut decoder),
+            vec![b"hello".to_vec(), b"world".to_vec()]
+        );
+        assert_eq!(decoder.buffered(), 0);
+    }
+
+    #[test]
+    fn reports_frames_over_the_negotiated_limit() {
+        let mut decoder = FrameDecoder::new();
+        decoder.set_max_frame(4);
+        decoder.read_target().extend_from_slice(&frame(b"ok"));
+        decoder.read_target().extend_from_slice(&[0, 20, 1, 2]);
+        assert_eq!(
+            decoder.next_frame(),
+            Some(DecodedFrame::Frame(BytesMut::from(&b"ok"[..])))
+        );
+        // 声明长度超限时立即报告，无需等待整帧到达。
+        assert_eq!(decoder.next_frame(), Some(DecodedFrame::Oversized(20)));
+        decoder.set_max_frame(usize::MAX);
+        assert_eq!(decoder.max_frame(), FRAME_MAX_BYTES);
+    }
+}
@@ -0,0 +1,130 @@
+//! network｜数据层：单帧长度上限协商。
+//!
+//! 说明：
+//! - 前端在换钥包中声明 `max_frame_bytes`（本端可接收的单帧上限）；服务端在 `/handshake`
+//!   推送中返回 `max_frame_bytes` 表示本连接约定的上限，客户端取其与协议上限的较小值；
+//!   未携带该字段的旧服务端沿用协议上限 [`FRAME_MAX_BYTES`]；
+//! - 入站帧声明长度超过上限视为协议错误：读循环上报 `error` 状态并关闭连接（由上层重连），
+//!   不再丢弃缓冲后继续读取，避免帧边界错位；
+//! - 出站帧超过上限时拒绝发送；超过上限的业务数据按分块传输约定改走 HTTP 旁路下载
+//!   （推送 `large_payload` 描述，前端见 `largePayload.ts`）。
+//! - 协议口径见 `docs/api/15-tcp-frame-protocol-v1.md`。
+//!
+//! 约定：注释中文，日志英文（tracing）。
+
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use serde_json::Value;
+
+use crate::features::network::data::frame_compression::split_frames;
+use crate::features::network::data::frame_decoder::FRAME_MAX_BYTES;
+use crate::features::network::domain::codec::handshake_push_data;
+
+/// 可协商的最小帧上限（防止服务端返回过小值导致握手后无法通信）。
+pub const FRAME_MIN_BYTES: usize = 1024;
+
+/// 从明文握手推送中读取服务端约定的单帧上限。
+///
+/// # 返回值
+/// - `Some(limit)`：payload 为握手推送；结果限制在 [`FRAME_MIN_BYTES`]..=[`FRAME_MAX_BYTES`]，
+///   未携带 `max_frame_bytes` 时为 [`FRAME_MAX_BYTES`]。
+/// - `None`：不是握手推送。
+pub fn max_frame_from_handshake(payload: &[u8]) -> Option<usize> {
+    let data = handshake_push_data(payload)?;
+    let limit = data
+        .get("max_frame_bytes")
+        .and_then(Value::as_u64)
+        .map(|raw| (raw as usize).clamp(FRAME_MIN_BYTES, FRAME_MAX_BYTES))
+        .unwrap_or(FRAME_MAX_BYTES);
+    Some(limit)
+}
+
+/// 单连接的帧上限协商状态（读循环写入，发送路径读取）。
+#[derive(Debug)]
+pub struct NegotiatedFrameLimit {
+    limit: AtomicUsize,
+}
+
+impl Default for NegotiatedFrameLimit {
+    fn default() -> Self {
+        Self {
+            limit: AtomicUsize::new(FRAME_MAX_BYTES),
+        }
+    }
+}
+
+impl NegotiatedFrameLimit {
+    /// 当前生效的单帧上限。
+    pub fn get(&self) -> usize {
+        self.limit.load(Ordering::Acquire)
+    }
+
+    /// 若 payload 为握手推送，则更新协商结果并返回新上限。
+    pub fn observe_frame(&self, payload: &[u8]) -> Option<usize> {
+        let limit = max_frame_from_handshake(payload)?;
+        self.limit.store(limit, Ordering::Release);
+        Some(limit)
+    }
+
+    /// 校验出站 bytes 中每个完整帧的 payload 长度不超过上限（原始 bytes、半帧不校验）。
+    pub fn check_outbound(&self, data: &[u8]) -> anyhow::Result<()> {
+        let limit = self.get();
+        let Some(frames) = split_frames(data) else {
+            return Ok(());
+        };
+        match frames.iter().find(|payload| payload.len() > limit) {
+            Some(payload) => Err(anyhow::anyhow!(
+                "Frame payload of {} bytes exceeds negotiated max frame size {}",
+                payload.len(),
+                limit
+            )),
+            None => Ok(()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::features::network::test_support::encode_frame;
+
+    #[test]
+    fn reads_and_clamps_limit_from_handshake_push() {
+        let push = |data: &str| {
+            format!(r#"{{"id":-1,"code":0,"data":{{"route":"handshake","data":{data}}}}}"#)
+        };
+        assert_eq!(
+            max_frame_from_handshake(push(r#"{"max_frame_bytes":4096}"#).as_bytes()),
+            Some(4096)
+        );
+        assert_eq!(
+            max_frame_from_handshake(push(r#"{"max_frame_bytes":16}"#).as_bytes()),
+            Some(FRAME_MIN_BYTES)
+        );
+        assert_eq!(
+            max_frame_from_handshake(push(r#"{"max_frame_bytes":10485760}"#).as_bytes()),
+            Some(FRAME_MAX_BYTES)
+        );
+        assert_eq!(
+            max_frame_from_handshake(push("{}").as_bytes()),
+            Some(FRAME_MAX_BYTES)
+        );
+        assert_eq!(max_frame_from_handshake(br#"{"id":1,"code":0}"#), None);
+    }
+
+    #[test]
+    fn rejects_outbound_frames_over_negotiated_limit() {
+        let limit = NegotiatedFrameLimit::default();
+        let handshake =
+            br#"{"id":-1,"code":0,"data":{"route":"handshake","data":{"max_frame_bytes":2048}}}"#;
+        assert_eq!(limit.observe_frame(handshake), Some(2048));
+
+        let mut data = encode_frame(&[1; 16]);
+        data.extend_from_slice(&encode_frame(&vec![2; 2048]));
+        assert!(limit.check_outbound(&data).is_ok());
+        data.extend_from_slice(&encode_frame(&vec![3; 2049]));
+        assert!(limit.check_outbound(&data).is_err());
+        // 原始 bytes / 半帧不做校验。
+        assert!(limit.check_outbound(&[0xFF, 0xFF, 1]).is_ok());
+    }
+}
@@ -0,0 +1,139 @@
+//! network｜数据层：header_policy（客户端请求头策略）。
+//!
+//! 说明：
+//! - 访问 CarryPigeon 服务端的 reqwest client（API、插件商店、文件下载）统一通过
+//!   [`client_builder`] 创建，默认附带 `User-Agent`、`X-Client-Version` 与
+//!   `X-Client-Protocol-Versions`；
+//! - 取值来自 `app::runtime_info::client_identity`（与 `get_runtime_info` 一致），
+//!   测试时可通过 [`set_override`] 覆盖；
+//! - 进程内缓存的 client（如下载 client）在首次创建时读取策略，覆盖只影响之后创建的 client。
+//!
+//! 约定：注释中文，日志英文（tracing）。
+
+use std::sync::{Arc, RwLock};
+
+use reqwest::header::{HeaderMap, HeaderName, HeaderValue, USER_AGENT};
+
+use crate::app::runtime_info::{ClientIdentity, client_identity};
+use crate::shared::net::doh::DohResolver;
+
+/// 客户端版本请求头。
+pub const CLIENT_VERSION_HEADER: &str = "x-client-version";
+/// 客户端可接受的协议版本请求头（逗号分隔）。
+pub const CLIENT_PROTOCOL_VERSIONS_HEADER: &str = "x-client-protocol-versions";
+/// 客户端支持的服务端协议版本。
+pub const SUPPORTED_PROTOCOL_VERSIONS: &str = "1";
+
+/// 请求头策略取值。
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HeaderPolicy {
+    pub user_agent: String,
+    pub client_version: String,
+    pub protocol_versions: String,
+}
+
+impl HeaderPolicy {
+    fn from_identity(identity: ClientIdentity) -> Self {
+        Self {
+            user_agent: format!(
+                "CarryPigeon-Desktop/{} ({}; {})",
+                identity.app_version, identity.os, identity.arch
+            ),
+            client_version: format!("{}+{}", identity.app_version, identity.git_hash),
+            protocol_versions: SUPPORTED_PROTOCOL_VERSIONS.to_string(),
+        }
+    }
+
+    /// 转为请求头；非法取值跳过并记录日志。
+    pub fn to_headers(&self) -> HeaderMap {
+        let mut headers = HeaderMap::new();
+        let entries = [
+            (USER_AGENT, &self.user_agent),
+            (
+                HeaderName::from_static(CLIENT_VERSION_HEADER),
+                &self.client_version,
+            ),
+            (
+                HeaderName::from_static(CLIENT_PROTOCOL_VERSIONS_HEADER),
+                &self.protocol_versions,
+            ),
+        ];
+        for (name, value) in entries {
+            match HeaderValue::from_str(value) {
+                Ok(value) => {
+                    headers.insert(name, value);
+                }
+                Err(_) => {
+                    tracing::warn!(action = "network_header_policy_invalid_value", header = %name);
+                }
+            }
+        }
+        headers
+    }
+}
+
+static OVERRIDE: RwLock<Option<HeaderPolicy>> = RwLock::new(None);
+
+/// 当前生效的请求头策略。
+pub fn current() -> HeaderPolicy {
+    OVERRIDE
+        .read()
+        .unwrap_or_else(|p| p.into_inner())
+        .clone()
+        .unwrap_or_else(|| HeaderPolicy::from_identity(client_identity()))
+}
+
+/// 覆盖请求头策略（测试用；`None` 恢复默认）。
+pub fn set_override(policy: Option<HeaderPolicy>) {
+    *OVERRIDE.write().unwrap_or_else(|p| p.into_inner()) = policy;
+}
+
+/// 创建带默认请求头的 reqwest client builder（主机名解析经 [`DohResolver`]，开启 DoH 时生效）。
+pub fn client_builder() -> reqwest::ClientBuilder {
+    reqwest::Client::builder()
+        .default_headers(current().to_headers())
+        .dns_resolver(Arc::new(DohResolver))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn default_policy_is_derived_from_client_identity() {
+        let policy = HeaderPolicy::from_identity(ClientIdentity {
+            app_version: "1.2.3",
+            git_hash: "abc1234",
+            os: "linux",
+            arch: "x86_64",
+        });
+        assert_eq!(
+            policy.user_agent,
+            "CarryPigeon-Desktop/1.2.3 (linux; x86_64)"
+        );
+        assert_eq!(policy.client_version, "1.2.3+abc1234");
+
+        let headers = policy.to_headers();
+        assert_eq!(
+            headers[USER_AGENT],
+            "CarryPigeon-Desktop/1.2.3 (linux; x86_64)"
+        );
+        assert_eq!(headers[CLIENT_VERSION_HEADER], "1.2.3+abc1234");
+        assert_eq!(
+            headers[CLIENT_PROTOCOL_VERSIONS_HEADER],
+            SUPPORTED_PROTOCOL_VERSIONS
+        );
+    }
+
+    #[test]
+    fn invalid_header_values_are_skipped() {
+        let policy = HeaderPolicy {
+            user_agent: "bad\nvalue".to_string(),
+            client_version: "9.9.9".to_string(),
+            protocol_versions: "1".to_string(),
+        };
+        let headers = policy.to_headers();
+        assert!(headers.get(USER_AGENT).is_none());
+        assert_eq!(headers[CLIENT_VERSION_HEADER], "9.9.9");
+    }
+}
@@ -4,10 +4,10 @@
 //! - 注释统一使用中文，便于团队维护与交接。
 //! - 日志输出统一使用英文，便于跨端检索与与上游/第三方日志对齐。
 
+use crate::features::network::data::header_policy;
 use anyhow::Context;
-use reqwest::Client;
 use sha2::{Digest, Sha256};
-use std::path::Path;
+use std::path::{Path, PathBuf};
 use tokio::io::AsyncWriteExt;
 use tracing::{debug, info};
 
@@ -62,10 +62,10 @@ pub async fn download_avatar_impl(
 
     // 创建reqwest客户端
     let client = match config.timeout {
-        Some(timeout) => Client::builder()
+        Some(timeout) => header_policy::client_builder()
             .timeout(std::time::Duration::from_secs(timeout))
             .build()?,
-        None => Client::new(),
+        None => header_policy::client_builder().build()?,
     };
 
     // 发送HEAD请求获取文件大小（可选）
@@ -147,22 +147,42 @@ pub async fn download_avatar_impl(
     Ok(())
 }
 
+/// avatar_id 仅允许字母数字、连字符和下划线，防止路径穿越。
+fn is_valid_avatar_id(avatar_id: &str) -> bool {
+    !avatar_id.is_empty()
+        && avatar_id
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+}
+
+fn avatar_dir() -> anyhow::Result<PathBuf> {
+    Ok(crate::shared::app_data_dir::get_app_data_dir()
+        .map_err(|e| anyhow::anyhow!("{e}"))?
+        .join("avatars"))
+}
+
+/// 已下载头像的本地路径；avatar_id 非法或尚未下载时返回 `None`。
+pub async fn cached_avatar_path(avatar_id: &str) -> Option<PathBuf> {
+    if !is_valid_avatar_id(avatar_id) {
+        return None;
+    }
+    let path = avatar_dir().ok()?.join(format!("{}.jpg", avatar_id));
+    tokio::fs::try_exists(&path)
+        .await
+        .unwrap_or(false)
+        .then_some(path)
+}
+
 /// 下载头像的包装函数。
 ///
 /// avatar_id 仅允许字母数字、连字符和下划线，防止路径穿越。
 pub async fn download_avatar(avatar_id: &str, url: &str) -> anyhow::Result<()> {
     // 校验 avatar_id，只允许安全字符
-    if avatar_id.is_empty()
-        || !avatar_id
-            .chars()
-            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
-    {
+    if !is_valid_avatar_id(avatar_id) {
         anyhow::bail!("Invalid avatar_id: only alphanumeric, hyphen, and underscore are allowed");
     }
 
-    let avatar_dir = crate::shared::app_data_dir::get_app_data_dir()
-        .map_err(|e| anyhow::anyhow!("{e}"))?
-        .join("avatars");
+    let avatar_dir = avatar_dir()?;
     tokio::fs::create_dir_all(&avatar_dir)
         .await
         .context("Failed to create avatar directory")?;
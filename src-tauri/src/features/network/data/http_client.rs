@@ -5,11 +5,12 @@
 use std::{sync::Arc, time::Duration};
 
 use anyhow::Context;
-use tokio::net::TcpStream;
 
+use crate::features::network::data::header_policy;
 use crate::features::network::domain::ports::api_request_port::{
     ApiHttpRequest, ApiHttpRequestFuture, ApiHttpResponse, ApiHttpTlsPolicy, ApiRequestPort,
 };
+use crate::shared::net::happy_eyeballs;
 use crate::shared::net::tls_fingerprint::verify_der_sha256_fingerprint;
 
 const API_REQUEST_TIMEOUT: Duration = Duration::from_secs(30);
@@ -32,7 +33,7 @@ impl ApiRequestPort for ReqwestApiRequestAdapter {
     }
 }
 
-fn extract_host_port_from_url(url: &str) -> anyhow::Result<(String, u16)> {
+pub(crate) fn extract_host_port_from_url(url: &str) -> anyhow::Result<(String, u16)> {
     let u = reqwest::Url::parse(url).context("Invalid request URL")?;
     let host = u.host_str().unwrap_or_default().to_string();
     if host.trim().is_empty() {
@@ -44,10 +45,13 @@ fn extract_host_port_from_url(url: &str) -> anyhow::Result<(String, u16)> {
     Ok((host, port))
 }
 
-async fn verify_https_fingerprint(url: &str, expected_sha256: &str) -> anyhow::Result<()> {
+pub(crate) async fn verify_https_fingerprint(
+    url: &str,
+    expected_sha256: &str,
+) -> anyhow::Result<()> {
     let (host, port) = extract_host_port_from_url(url)?;
     let addr = format!("{}:{}", host, port);
-    let stream = TcpStream::connect(addr.clone())
+    let stream = happy_eyeballs::connect(&addr)
         .await
         .with_context(|| format!("Failed to connect for TLS fingerprint check: {}", addr))?;
 
@@ -76,8 +80,8 @@ async fn verify_https_fingerprint(url: &str, expected_sha256: &str) -> anyhow::R
     verify_der_sha256_fingerprint(expected_sha256, &der)
 }
 
-fn build_reqwest_client(policy: ApiHttpTlsPolicy) -> anyhow::Result<reqwest::Client> {
-    let mut builder = reqwest::Client::builder().timeout(API_REQUEST_TIMEOUT);
+pub(crate) fn build_reqwest_client(policy: ApiHttpTlsPolicy) -> anyhow::Result<reqwest::Client> {
+    let mut builder = header_policy::client_builder().timeout(API_REQUEST_TIMEOUT);
     if policy != ApiHttpTlsPolicy::Strict {
         builder = builder
             .danger_accept_invalid_certs(true)
@@ -86,6 +90,22 @@ fn build_reqwest_client(policy: ApiHttpTlsPolicy) -> anyhow::Result<reqwest::Cli
     Ok(builder.build()?)
 }
 
+/// 按 TLS 策略构建请求 client；`TrustFingerprint` 策略下先校验目标证书指纹。
+///
+/// # 说明
+/// 指纹策略下 client 本身接受任意证书，信任完全来自这里的指纹校验，
+/// 因此所有对服务端的 HTTP 请求都必须经由该函数获取 client。
+pub(crate) async fn pinned_client(
+    url: &str,
+    policy: ApiHttpTlsPolicy,
+    tls_fingerprint: Option<&str>,
+) -> anyhow::Result<reqwest::Client> {
+    if policy == ApiHttpTlsPolicy::TrustFingerprint {
+        verify_https_fingerprint(url, tls_fingerprint.unwrap_or("")).await?;
+    }
+    build_reqwest_client(policy)
+}
+
 /// 执行 JSON HTTP 请求（含 TLS 策略处理）。
 async fn execute_json_request_impl(args: ApiHttpRequest) -> anyhow::Result<ApiHttpResponse> {
     let ApiHttpRequest {
@@ -97,12 +117,7 @@ async fn execute_json_request_impl(args: ApiHttpRequest) -> anyhow::Result<ApiHt
         tls_fingerprint,
     } = args;
 
-    if tls_policy == ApiHttpTlsPolicy::TrustFingerprint {
-        let fp = tls_fingerprint.as_deref().unwrap_or("");
-        verify_https_fingerprint(&url, fp).await?;
-    }
-
-    let client = build_reqwest_client(tls_policy)?;
+    let client = pinned_client(&url, tls_policy, tls_fingerprint.as_deref()).await?;
     let mut req = client.request(method.parse()?, url);
 
     for (k, v) in headers {
@@ -119,12 +134,18 @@ async fn execute_json_request_impl(args: ApiHttpRequest) -> anyhow::Result<ApiHt
     let res = req.send().await.context("Failed to send request")?;
     let status = res.status().as_u16();
     let ok = res.status().is_success();
+    let etag = res
+        .headers()
+        .get(reqwest::header::ETAG)
+        .and_then(|v| v.to_str().ok())
+        .map(str::to_string);
 
     if status == 204 {
         return Ok(ApiHttpResponse {
             ok,
             status,
             body: None,
+            etag,
         });
     }
 
@@ -141,6 +162,7 @@ async fn execute_json_request_impl(args: ApiHttpRequest) -> anyhow::Result<ApiHt
             ok,
             status,
             body: None,
+            etag,
         });
     }
 
@@ -150,5 +172,6 @@ async fn execute_json_request_impl(args: ApiHttpRequest) -> anyhow::Result<ApiHt
         ok,
         status,
         body: Some(json),
+        etag,
     })
 }
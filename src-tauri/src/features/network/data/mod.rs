@@ -3,6 +3,15 @@
 //! 说明：该文件负责导出子模块与组织依赖关系。
 //!
 //! 约定：注释中文，日志英文（tracing）。
+pub mod capabilities_store;
+pub mod frame_capture;
+pub mod frame_compression;
+pub mod frame_decoder;
+pub mod frame_limit;
+pub mod header_policy;
 pub mod http;
 pub mod http_client;
+pub mod oauth_loopback;
+pub mod server_probe;
+pub mod session_token_store;
 pub mod tcp_real;
@@ -0,0 +1,183 @@
+//! network｜数据层：oauth_loopback（OAuth 本地回环回调监听）。
+//!
+//! 说明：
+//! - 在 `127.0.0.1` 的随机端口监听，回调地址为 `http://127.0.0.1:<port>/callback`；
+//! - 只处理 `GET /callback`，其它请求（如 favicon）返回 404 并继续等待；
+//! - `state` 与本次授权不一致的回调（本机其他进程探测、旧授权页重放）返回 400 并继续等待，
+//!   直到收到匹配的回调或整个流程超时；
+//! - 收到匹配回调后向浏览器返回提示页，并把 query 参数交给用例层校验。
+//!
+//! 约定：注释中文，日志英文（tracing）。
+
+use std::collections::BTreeMap;
+use std::time::Duration;
+
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::{TcpListener, TcpStream};
+
+/// 回调 query 参数。
+pub type CallbackParams = BTreeMap<String, String>;
+
+const CALLBACK_PATH: &str = "/callback";
+/// 单个请求头的读取上限。
+const MAX_REQUEST_BYTES: usize = 8 * 1024;
+/// 单个连接的读取超时。
+const READ_TIMEOUT: Duration = Duration::from_secs(5);
+
+const STALE_PAGE: &str = "<!doctype html><html><head><meta charset=\"utf-8\"><title>CarryPigeon</title></head>\
+<body><p>该登录链接已失效，请回到 CarryPigeon 重新发起登录。</p>\
+<p>This sign-in link is no longer valid. Please start again from CarryPigeon.</p></body></html>";
+
+const DONE_PAGE: &str = "<!doctype html><html><head><meta charset=\"utf-8\"><title>CarryPigeon</title></head>\
+<body><p>登录已完成，可以关闭此页面并返回 CarryPigeon。</p>\
+<p>Sign-in complete. You can close this page and return to CarryPigeon.</p></body></html>";
+
+/// 回环回调监听器。
+pub struct OAuthLoopbackListener {
+    listener: TcpListener,
+    port: u16,
+}
+
+impl OAuthLoopbackListener {
+    /// 绑定随机端口。
+    pub async fn bind() -> anyhow::Result<Self> {
+        let listener = TcpListener::bind(("127.0.0.1", 0)).await?;
+        let port = listener.local_addr()?.port();
+        Ok(Self { listener, port })
+    }
+
+    /// 回调地址（作为 `redirect_uri`）。
+    pub fn redirect_uri(&self) -> String {
+        format!("http://127.0.0.1:{}{}", self.port, CALLBACK_PATH)
+    }
+
+    /// 等待 `state` 匹配的回调并返回其 query 参数；不匹配的回调被忽略。
+    ///
+    /// # 返回值
+    /// - `Err(anyhow::Error)`：超时或监听失败。
+    pub async fn wait_for_callback(
+        self,
+        expected_state: &str,
+        timeout: Duration,
+    ) -> anyhow::Result<CallbackParams> {
+        tokio::time::timeout(timeout, async {
+            loop {
+                let (stream, _) = self.listener.accept().await?;
+                match handle_connection(stream, expected_state).await {
+                    Ok(Some(params)) => return Ok(params),
+                    Ok(None) => continue,
+                    Err(err) => {
+                        tracing::debug!(action = "network_oauth_loopback_request_ignored", error = %err);
+                    }
+                }
+            }
+        })
+        .await
+        .map_err(|_| anyhow::anyhow!("Timed out waiting for OAuth callback"))?
+    }
+}
+
+/// 读取请求行；是 `state` 匹配的回调请求时返回其参数。
+async fn handle_connection(
+    mut stream: TcpStream,
+    expected_state: &str,
+) -> anyhow::Result<Option<CallbackParams>> {
+    let mut buf = Vec::with_capacity(1024);
+    let mut chunk = [0u8; 1024];
+    while !buf.windows(4).any(|w| w == b"\r\n\r\n") {
+        let n = tokio::time::timeout(READ_TIMEOUT, stream.read(&mut chunk)).await??;
+        if n == 0 {
+            break;
+        }
+        buf.extend_from_slice(&chunk[..n]);
+        if buf.len() > MAX_REQUEST_BYTES {
+            return Err(anyhow::anyhow!("Request header too large"));
+        }
+    }
+    let head = String::from_utf8_lossy(&buf);
+    let target = head
+        .lines()
+        .next()
+        .and_then(|line| line.strip_prefix("GET "))
+        .and_then(|rest| rest.split(' ').next())
+        .unwrap_or_default();
+    let url = reqwest::Url::parse(&format!("http://127.0.0.1{target}"))?;
+    if url.path() != CALLBACK_PATH {
+        stream
+            .write_all(b"HTTP/1.1 404 Not Found\r\nContent-Length: 0\r\nConnection: close\r\n\r\n")
+            .await?;
+        return Ok(None);
+    }
+    let params: CallbackParams = url.query_pairs().into_owned().collect();
+    let matched = params.get("state").map(String::as_str) == Some(expected_state);
+    let (status, page) = if matched {
+        ("200 OK", DONE_PAGE)
+    } else {
+        tracing::debug!(action = "network_oauth_loopback_state_mismatch");
+        ("400 Bad Request", STALE_PAGE)
+    };
+    let response = format!(
+        "HTTP/1.1 {}\r\nContent-Type: text/html; charset=utf-8\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+        status,
+        page.len(),
+        page
+    );
+    stream.write_all(response.as_bytes()).await?;
+    let _ = stream.shutdown().await;
+    Ok(matched.then_some(params))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    async fn send(port: u16, request_line: &str) -> String {
+        let mut stream = TcpStream::connect(("127.0.0.1", port)).await.unwrap();
+        stream
+            .write_all(format!("{request_line}\r\nHost: 127.0.0.1\r\n\r\n").as_bytes())
+            .await
+            .unwrap();
+        let mut response = String::new();
+        stream.read_to_string(&mut response).await.unwrap();
+        response
+    }
+
+    #[tokio::test]
+    async fn returns_callback_params_and_skips_other_paths() {
+        let listener = OAuthLoopbackListener::bind().await.unwrap();
+        let port = listener.port;
+        assert_eq!(
+            listener.redirect_uri(),
+            format!("http://127.0.0.1:{port}/callback")
+        );
+        let waiter = tokio::spawn(async move {
+            listener
+                .wait_for_callback("s 1", Duration::from_secs(5))
+                .await
+        });
+
+        let favicon = send(port, "GET /favicon.ico HTTP/1.1").await;
+        assert!(favicon.starts_with("HTTP/1.1 404"));
+        // state 不匹配的回调不会结束等待。
+        let forged = send(port, "GET /callback?code=evil&state=other HTTP/1.1").await;
+        assert!(forged.starts_with("HTTP/1.1 400"));
+        assert!(!waiter.is_finished());
+        let done = send(port, "GET /callback?code=abc&state=s%201 HTTP/1.1").await;
+        assert!(done.starts_with("HTTP/1.1 200"));
+
+        let params = waiter.await.unwrap().unwrap();
+        assert_eq!(params["code"], "abc");
+        assert_eq!(params["state"], "s 1");
+    }
+
+    #[tokio::test]
+    async fn times_out_without_callback() {
+        let listener = OAuthLoopbackListener::bind().await.unwrap();
+        assert!(
+            listener
+                .wait_for_callback("s", Duration::from_millis(20))
+                .await
+                .is_err()
+        );
+    }
+}
@@ -0,0 +1,222 @@
+//! network｜数据层：server_probe（服务器连通性与延迟探测）。
+//!
+//! 说明：
+//! - 依次测量 TCP 建连、TLS 握手、`GET /api/server` 往返，并用响应 `Date` 头估算时钟偏差；
+//! - HTTP 阶段与 `/api/*` 请求共用 [`pinned_client`]，指纹策略下同样校验证书指纹；
+//! - 任一阶段失败即停止后续阶段，失败原因写入对应阶段；
+//! - 每个 server_socket 只缓存最近一次结果，供服务器列表展示。
+//!
+//! 约定：注释中文，日志英文（tracing）。
+
+use std::collections::HashMap;
+use std::future::Future;
+use std::sync::{Mutex, OnceLock};
+use std::time::{Duration, Instant};
+
+use tokio::io::AsyncWriteExt;
+
+use crate::features::network::data::http_client::{extract_host_port_from_url, pinned_client};
+use crate::features::network::data::tcp_real::{tls_handshake, verify_tls_fingerprint_sha256};
+use crate::features::network::domain::ports::api_request_port::ApiHttpTlsPolicy;
+use crate::features::network::domain::types::{ProbeStage, ServerHealthReport};
+use crate::shared::net::happy_eyeballs;
+use crate::shared::net::origin::to_http_origin;
+
+/// 单个阶段的超时时间。
+const PROBE_STAGE_TIMEOUT: Duration = Duration::from_secs(10);
+
+fn now_ms() -> i64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_millis() as i64
+}
+
+fn probe_cache() -> &'static Mutex<HashMap<String, ServerHealthReport>> {
+    static CACHE: OnceLock<Mutex<HashMap<String, ServerHealthReport>>> = OnceLock::new();
+    CACHE.get_or_init(|| Mutex::new(HashMap::new()))
+}
+
+/// 执行一个带超时的阶段，返回阶段结果与产出值。
+async fn run_stage<T>(fut: impl Future<Output = anyhow::Result<T>>) -> (ProbeStage, Option<T>) {
+    let started = Instant::now();
+    let result = match tokio::time::timeout(PROBE_STAGE_TIMEOUT, fut).await {
+        Ok(result) => result,
+        Err(_) => Err(anyhow::anyhow!(
+            "Timed out after {}s",
+            PROBE_STAGE_TIMEOUT.as_secs()
+        )),
+    };
+    match result {
+        Ok(value) => (
+            ProbeStage {
+                duration_ms: Some(started.elapsed().as_millis() as u64),
+                error: None,
+            },
+            Some(value),
+        ),
+        Err(e) => (
+            ProbeStage {
+                duration_ms: None,
+                error: Some(format!("{e:#}")),
+            },
+            None,
+        ),
+    }
+}
+
+/// 根据 `Date` 头估算服务器时钟偏差（以请求发出与响应到达的中点作为服务器生成响应的本地时刻）。
+///
+/// # 参数
+/// - `sent_at_ms`/`received_at_ms`：紧贴 `send` 前后记录的本地时间，不含建 client、指纹校验等耗时。
+///
+/// # 返回值
+/// 服务器时间减本地时间（毫秒）；`Date` 头无法解析时返回 `None`。
+pub fn clock_skew_ms(date_header: &str, sent_at_ms: i64, received_at_ms: i64) -> Option<i64> {
+    let server = chrono::DateTime::parse_from_rfc2822(date_header.trim()).ok()?;
+    let local_mid = sent_at_ms.saturating_add(received_at_ms.saturating_sub(sent_at_ms).max(0) / 2);
+    Some(server.timestamp_millis() - local_mid)
+}
+
+/// 探测服务器健康状况并缓存结果。
+///
+/// # 参数
+/// - `server_socket`：服务器 socket（与 `/api/*` 请求使用相同的 origin 映射）。
+/// - `tls_policy`/`tls_fingerprint`：与 HTTP API 请求一致的 TLS 策略。
+///
+/// # 返回值
+/// - `Ok(ServerHealthReport)`：各阶段结果（阶段失败不视为错误）。
+/// - `Err(anyhow::Error)`：socket 无法解析为地址。
+pub async fn probe(
+    server_socket: &str,
+    tls_policy: ApiHttpTlsPolicy,
+    tls_fingerprint: Option<&str>,
+) -> anyhow::Result<ServerHealthReport> {
+    let origin = to_http_origin(server_socket)?;
+    let (host, port) = extract_host_port_from_url(&origin)?;
+    let addr = format!("{host}:{port}");
+    let mut report = ServerHealthReport {
+        server_socket: server_socket.to_string(),
+        probed_at: now_ms(),
+        tcp_connect: ProbeStage::default(),
+        tls_handshake: ProbeStage::default(),
+        http_round_trip: ProbeStage::default(),
+        http_status: None,
+        clock_skew_ms: None,
+        healthy: false,
+    };
+
+    let (stage, stream) = run_stage(async { happy_eyeballs::connect(&addr).await }).await;
+    report.tcp_connect = stage;
+    let Some(stream) = stream else {
+        return Ok(remember(report));
+    };
+
+    if origin.starts_with("https://") {
+        let insecure = tls_policy != ApiHttpTlsPolicy::Strict;
+        let (stage, tls) = run_stage(async {
+            let tls = tls_handshake(&addr, stream, insecure).await?;
+            if tls_policy == ApiHttpTlsPolicy::TrustFingerprint {
+                verify_tls_fingerprint_sha256(&tls, tls_fingerprint.unwrap_or(""))?;
+            }
+            Ok(tls)
+        })
+        .await;
+        report.tls_handshake = stage;
+        let Some(mut tls) = tls else {
+            return Ok(remember(report));
+        };
+        let _ = tls.shutdown().await;
+    } else {
+        drop(stream);
+    }
+
+    let url = format!("{origin}/api/server");
+    let (mut stage, response) = run_stage(async {
+        let client = pinned_client(&url, tls_policy, tls_fingerprint).await?;
+        let sent_at = now_ms();
+        let response = client.get(&url).send().await?;
+        Ok((response, sent_at, now_ms()))
+    })
+    .await;
+    if let Some((response, sent_at, received_at)) = response {
+        // 往返只计请求本身，不含建 client 与指纹校验。
+        stage.duration_ms = Some(received_at.saturating_sub(sent_at).max(0) as u64);
+        report.http_status = Some(response.status().as_u16());
+        report.clock_skew_ms = response
+            .headers()
+            .get(reqwest::header::DATE)
+            .and_then(|v| v.to_str().ok())
+            .and_then(|date| clock_skew_ms(date, sent_at, received_at));
+        report.healthy = response.status().is_success();
+    }
+    report.http_round_trip = stage;
+    Ok(remember(report))
+}
+
+fn remember(report: ServerHealthReport) -> ServerHealthReport {
+    tracing::info!(
+        action = "network_server_probe_completed",
+        server_socket = %report.server_socket,
+        healthy = report.healthy,
+        tcp_ms = ?report.tcp_connect.duration_ms,
+        tls_ms = ?report.tls_handshake.duration_ms,
+        http_ms = ?report.http_round_trip.duration_ms
+    );
+    probe_cache()
+        .lock()
+        .unwrap_or_else(|p| p.into_inner())
+        .insert(report.server_socket.clone(), report.clone());
+    report
+}
+
+/// 读取缓存的最近一次探测结果（按 server_socket 排序）。
+pub fn cached_reports() -> Vec<ServerHealthReport> {
+    let mut reports: Vec<ServerHealthReport> = probe_cache()
+        .lock()
+        .unwrap_or_else(|p| p.into_inner())
+        .values()
+        .cloned()
+        .collect();
+    reports.sort_by(|a, b| a.server_socket.cmp(&b.server_socket));
+    reports
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::features::network::test_support::FrameTestServerBuilder;
+
+    #[test]
+    fn skew_uses_round_trip_midpoint() {
+        // 2024-01-01T00:00:10Z
+        let date = "Mon, 01 Jan 2024 00:00:10 GMT";
+        let sent_at = 1_704_067_200_000;
+        assert_eq!(clock_skew_ms(date, sent_at, sent_at + 400), Some(9_800));
+        assert_eq!(clock_skew_ms("yesterday", sent_at, sent_at + 400), None);
+        // 时钟回拨导致 received < sent 时按零往返处理。
+        assert_eq!(clock_skew_ms(date, sent_at, sent_at - 50), Some(10_000));
+    }
+
+    #[tokio::test]
+    async fn unreachable_http_stage_is_reported_without_error() {
+        // 测试服务端只说 frame 协议，HTTP 阶段应失败但 TCP 阶段成功。
+        let server = FrameTestServerBuilder::new()
+            .close_after_greeting()
+            .spawn()
+            .await
+            .expect("server should bind");
+        let report = probe(&server.tcp_socket(), ApiHttpTlsPolicy::Strict, None)
+            .await
+            .expect("probe should return a report");
+        assert!(report.tcp_connect.duration_ms.is_some());
+        assert_eq!(report.tls_handshake, ProbeStage::default());
+        assert!(report.http_round_trip.error.is_some());
+        assert!(!report.healthy);
+        assert!(
+            cached_reports()
+                .iter()
+                .any(|r| r.server_socket == server.tcp_socket())
+        );
+    }
+}
@@ -0,0 +1,115 @@
+//! network｜数据层：session_token_store（会话令牌管理）。
+//!
+//! 说明：
+//! - 令牌以 JSON 保存在系统凭据存储（账户 `session-tokens:<server_socket>`），内存中缓存；
+//! - 每个服务器一把刷新锁，由用例层在刷新时持有。
+//!
+//! 约定：注释中文，日志英文（tracing）。
+
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex, OnceLock};
+
+use crate::features::network::domain::ports::session_token_port::{
+    SessionTokenFuture, SessionTokenPort, SessionTokens,
+};
+use crate::shared::secrets;
+
+pub(crate) const ACCOUNT_PREFIX: &str = "session-tokens:";
+
+fn account_for(server_socket: &str) -> String {
+    format!("{ACCOUNT_PREFIX}{}", server_socket.trim())
+}
+
+/// 基于系统凭据存储的会话令牌适配器（进程内单例）。
+#[derive(Debug, Default)]
+pub struct KeyringSessionTokenStore {
+    /// 令牌缓存：值为 `None` 表示已确认凭据存储中没有该服务器的令牌。
+    cache: Mutex<HashMap<String, Option<SessionTokens>>>,
+    refresh_locks: Mutex<HashMap<String, Arc<tokio::sync::Mutex<()>>>>,
+}
+
+static SHARED: OnceLock<Arc<KeyringSessionTokenStore>> = OnceLock::new();
+
+impl KeyringSessionTokenStore {
+    /// 获取共享实例（缓存与刷新锁需在所有调用方之间共享）。
+    pub fn shared() -> Arc<Self> {
+        Arc::clone(SHARED.get_or_init(|| Arc::new(Self::default())))
+    }
+
+    fn cached(&self, key: &str) -> Option<Option<SessionTokens>> {
+        self.cache
+            .lock()
+            .unwrap_or_else(|p| p.into_inner())
+            .get(key)
+            .cloned()
+    }
+
+    fn put_cache(&self, key: String, tokens: Option<SessionTokens>) {
+        self.cache
+            .lock()
+            .unwrap_or_else(|p| p.into_inner())
+            .insert(key, tokens);
+    }
+}
+
+impl SessionTokenPort for KeyringSessionTokenStore {
+    fn load_tokens<'a>(
+        &'a self,
+        server_socket: &'a str,
+    ) -> SessionTokenFuture<'a, Option<SessionTokens>> {
+        Box::pin(async move {
+            let key = server_socket.trim().to_string();
+            if let Some(cached) = self.cached(&key) {
+                return Ok(cached);
+            }
+            let account = account_for(&key);
+            let raw = tokio::task::spawn_blocking(move || secrets::get(&account)).await??;
+            let tokens = match raw {
+                None => None,
+                Some(raw) => match serde_json::from_str::<SessionTokens>(&raw) {
+                    Ok(tokens) => Some(tokens),
+                    Err(err) => {
+                        tracing::warn!(
+                            action = "network_session_tokens_corrupted",
+                            server_socket = %key,
+                            error = %err
+                        );
+                        None
+                    }
+                },
+            };
+            self.put_cache(key, tokens.clone());
+            Ok(tokens)
+        })
+    }
+
+    fn save_tokens<'a>(
+        &'a self,
+        server_socket: &'a str,
+        tokens: SessionTokens,
+    ) -> SessionTokenFuture<'a, ()> {
+        Box::pin(async move {
+            let key = server_socket.trim().to_string();
+            let account = account_for(&key);
+            let raw = serde_json::to_string(&tokens)?;
+            tokio::task::spawn_blocking(move || secrets::set(&account, &raw)).await??;
+            self.put_cache(key, Some(tokens));
+            Ok(())
+        })
+    }
+
+    fn clear_tokens<'a>(&'a self, server_socket: &'a str) -> SessionTokenFuture<'a, ()> {
+        Box::pin(async move {
+            let key = server_socket.trim().to_string();
+            let account = account_for(&key);
+            tokio::task::spawn_blocking(move || secrets::delete(&account)).await??;
+            self.put_cache(key, None);
+            Ok(())
+        })
+    }
+
+    fn refresh_lock(&self, server_socket: &str) -> Arc<tokio::sync::Mutex<()>> {
+        let mut locks = self.refresh_locks.lock().unwrap_or_else(|p| p.into_inner());
+        Arc::clone(locks.entry(server_socket.trim().to_string()).or_default())
+    }
+}
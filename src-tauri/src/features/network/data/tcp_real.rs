@@ -9,15 +9,20 @@ use tokio::net::TcpStream;
 use tokio::task::JoinHandle;
 use tokio_native_tls::TlsStream;
 
+use crate::features::network::data::capabilities_store;
+use crate::features::network::data::frame_capture;
+use crate::features::network::data::frame_compression::NegotiatedCompression;
+use crate::features::network::data::frame_decoder::{DecodedFrame, FrameDecoder};
+use crate::features::network::data::frame_limit::NegotiatedFrameLimit;
+use crate::features::network::domain::capabilities::capabilities_from_handshake;
+use crate::features::network::domain::codec::{NegotiatedCodec, PayloadCodec};
 use crate::features::network::domain::ports::tcp_event_sink::TcpEventSink;
 use crate::features::network::domain::types::{TcpMessageEvent, TcpStateEvent};
+use crate::shared::net::happy_eyeballs;
 use crate::shared::net::tls_fingerprint::{
     normalize_sha256_fingerprint, verify_der_sha256_fingerprint,
 };
 
-const TCP_FRAME_MAX_BYTES: usize = u16::MAX as usize;
-const TCP_ACCUMULATOR_MAX_BYTES: usize = 1024 * 1024;
-
 enum Transport {
     Plain,
     Tls {
@@ -67,41 +72,66 @@ fn emit_tcp_frame_payload(
     server_socket: &str,
     payload: Vec<u8>,
 ) {
+    crate::shared::metrics::incr_counter(crate::shared::metrics::FRAMES_RECEIVED, 1);
+    frame_capture::record_inbound(server_socket, &payload);
     event_sink.emit_frame(TcpMessageEvent {
         server_socket: server_socket.to_string(),
         payload,
     });
 }
 
+/// 拆出缓冲中的完整帧并逐帧投递。
+///
+/// # 返回值
+/// - `Err(len)`：遇到声明长度超过上限的帧，调用方需关闭连接。
 fn emit_deframed_payloads(
     event_sink: &Arc<dyn TcpEventSink>,
     server_socket: &str,
-    acc: &mut Vec<u8>,
-) {
-    loop {
-        if acc.len() < 2 {
-            break;
+    codec: &NegotiatedCodec,
+    compression: &NegotiatedCompression,
+    frame_limit: &NegotiatedFrameLimit,
+    decoder: &mut FrameDecoder,
+) -> Result<(), usize> {
+    while let Some(decoded) = decoder.next_frame() {
+        let payload = match decoded {
+            DecodedFrame::Frame(payload) => payload,
+            DecodedFrame::Oversized(len) => return Err(len),
+        };
+        if let Some(selected) = codec.observe_frame(&payload) {
+            tracing::info!(
+                action = "network_tcp_codec_negotiated",
+                server_socket = %server_socket,
+                codec = selected.as_str()
+            );
         }
-
-        let len = u16::from_be_bytes([acc[0], acc[1]]) as usize;
-        if len == 0 {
-            // Consume header; ignore empty payload.
-            acc.drain(0..2);
-            continue;
+        if let Some(true) = compression.observe_frame(&payload) {
+            tracing::info!(
+                action = "network_tcp_compression_negotiated",
+                server_socket = %server_socket,
+                compression = "zstd"
+            );
         }
-        if len > TCP_FRAME_MAX_BYTES {
-            tracing::warn!(action = "network_tcp_frame_invalid_length", len);
-            acc.clear();
-            break;
+        if let Some(limit) = frame_limit.observe_frame(&payload) {
+            decoder.set_max_frame(limit);
+            tracing::info!(
+                action = "network_tcp_frame_limit_negotiated",
+                server_socket = %server_socket,
+                max_frame_bytes = limit
+            );
         }
-        if acc.len() < 2 + len {
-            break;
+        if let Some(capabilities) = capabilities_from_handshake(&payload) {
+            tracing::info!(
+                action = "network_tcp_capabilities_negotiated",
+                server_socket = %server_socket,
+                protocol_version = capabilities.protocol_version,
+                advertised = capabilities.advertised,
+                routes = capabilities.routes.len()
+            );
+            capabilities_store::record_in_background(server_socket, capabilities);
         }
-
-        let payload = acc[2..2 + len].to_vec();
-        acc.drain(0..2 + len);
-        emit_tcp_frame_payload(event_sink, server_socket, payload);
+        emit_tcp_frame_payload(event_sink, server_socket, Vec::from(payload));
     }
+    Ok(())
 }
 
 /// 基于 tokio 的真实 TCP service（支持纯 TCP 与 TLS）。
@@ -113,6 +143,14 @@ pub struct TcpServiceReal {
     reader: Option<TcpReader>,
     writer: TcpWriter,
     read_task: Option<JoinHandle<()>>,
+    codec: Arc<NegotiatedCodec>,
+    compression: Arc<NegotiatedCompression>,
+    /// 握手协商的单帧上限（读循环写入，发送路径读取）。
+    frame_limit: Arc<NegotiatedFrameLimit>,
+    /// `start` 时记录的逻辑 server_socket（用于调试抓包归因）。
+    server_socket: Option<String>,
+    /// 是否额外发出原始字节 `tcp-message` 事件（兼容旧前端，默认关闭）。
+    legacy_message_events: bool,
 }
 
 impl TcpServiceReal {
@@ -121,7 +159,7 @@ impl TcpServiceReal {
         let (transport, addr) = parse_transport(&socket);
         let addr = addr.to_string();
 
-        let stream = TcpStream::connect(addr.clone())
+        let stream = happy_eyeballs::connect(&addr)
             .await
             .map_err(|e| anyhow::anyhow!("Failed to connect TCP stream: {}", e))?;
 
@@ -134,17 +172,7 @@ impl TcpServiceReal {
                 insecure,
                 fingerprint_sha256,
             } => {
-                let host = extract_host(&addr)?;
-                let mut builder = native_tls::TlsConnector::builder();
-                if insecure {
-                    builder.danger_accept_invalid_certs(true);
-                    builder.danger_accept_invalid_hostnames(true);
-                }
-                let connector = tokio_native_tls::TlsConnector::from(builder.build()?);
-                let tls = connector
-                    .connect(&host, stream)
-                    .await
-                    .map_err(|e| anyhow::anyhow!("TLS handshake failed: {}", e))?;
+                let tls = tls_handshake(&addr, stream, insecure).await?;
 
                 if let Some(expected) = fingerprint_sha256.as_deref() {
                     verify_tls_fingerprint_sha256(&tls, expected)?;
@@ -159,9 +187,28 @@ impl TcpServiceReal {
             reader: Some(reader),
             writer,
             read_task: None,
+            codec: Arc::new(NegotiatedCodec::default()),
+            compression: Arc::new(NegotiatedCompression::default()),
+            frame_limit: Arc::new(NegotiatedFrameLimit::default()),
+            server_socket: None,
+            legacy_message_events: false,
         })
     }
 
+    /// 允许本连接在服务端选定时启用明文 zstd 压缩（需在 `start` 前调用）。
+    ///
+    /// 传输层只记录协商结果，压缩由加密前的编码命令完成。
+    pub fn with_frame_compression(mut self, allowed: bool) -> Self {
+        self.compression = Arc::new(NegotiatedCompression::new(allowed));
+        self
+    }
+
+    /// 额外发出原始字节 `tcp-message` 事件（兼容开关，需在 `start` 前调用）。
+    pub fn with_legacy_message_events(mut self, enabled: bool) -> Self {
+        self.legacy_message_events = enabled;
+        self
+    }
+
     /// 启动读取循环：将收到的数据通过 Tauri event 广播给前端。
     ///
     /// # 返回值
@@ -184,17 +231,21 @@ impl TcpServiceReal {
 
         emit_tcp_state(&event_sink, &server_socket, session_id, "connected", None);
 
+        self.server_socket = Some(server_socket.clone());
+        let codec = Arc::clone(&self.codec);
+        let compression = Arc::clone(&self.compression);
+        let frame_limit = Arc::clone(&self.frame_limit);
+        let legacy_message_events = self.legacy_message_events;
         let task = tokio::spawn(async move {
             // Netty frame：2 字节无符号短整型长度前缀（大端），后跟 `length` 字节载荷。
             //
-            // 注意：为向后兼容仍会发出原始 `tcp-message` 事件；
-            // 推荐使用 `tcp-frame` 事件，它会发出已拆包后的 payload。
-            let mut acc: Vec<u8> = Vec::new();
-            let mut buffer = vec![0; 4096];
+            // 数据直接读入解码器缓冲并原地拆帧；原始 `tcp-message` 事件仅在兼容开关开启时发出。
+            // 超过协商上限的帧无法可靠定位后续边界，直接关闭连接交由上层重连。
+            let mut decoder = FrameDecoder::new();
             loop {
                 let read_result = match &mut reader {
-                    TcpReader::Plain(r) => r.read(&mut buffer).await,
-                    TcpReader::Tls(r) => r.read(&mut buffer).await,
+                    TcpReader::Plain(r) => r.read_buf(decoder.read_target()).await,
+                    TcpReader::Tls(r) => r.read_buf(decoder.read_target()).await,
                 };
 
                 match read_result {
@@ -209,22 +260,40 @@ impl TcpServiceReal {
                         return;
                     }
                     Ok(n) => {
-                        let chunk = buffer[..n].to_vec();
-
-                        // Legacy: emit raw TCP chunk.
-                        emit_legacy_tcp_chunk(&event_sink, &server_socket, chunk.clone());
-
-                        // New: deframe and emit payload frames.
-                        acc.extend_from_slice(&chunk);
-                        if acc.len() > TCP_ACCUMULATOR_MAX_BYTES {
+                        if legacy_message_events {
+                            emit_legacy_tcp_chunk(
+                                &event_sink,
+                                &server_socket,
+                                decoder.tail(n).to_vec(),
+                            );
+                        }
+                        if let Err(len) = emit_deframed_payloads(
+                            &event_sink,
+                            &server_socket,
+                            &codec,
+                            &compression,
+                            &frame_limit,
+                            &mut decoder,
+                        ) {
+                            let max = decoder.max_frame();
                             tracing::warn!(
-                                action = "network_tcp_frame_accumulator_overflow",
-                                len = acc.len()
+                                action = "network_tcp_frame_oversized",
+                                server_socket = %server_socket,
+                                len,
+                                max_frame_bytes = max
+                            );
+                            emit_tcp_state(
+                                &event_sink,
+                                &server_socket,
+                                session_id,
+                                "error",
+                                Some(format!(
+                                    "Frame length {} exceeds negotiated max frame size {}",
+                                    len, max
+                                )),
                             );
-                            acc.clear();
-                            continue;
+                            break;
                         }
-                        emit_deframed_payloads(&event_sink, &server_socket, &mut acc);
                     }
                     Err(e) => {
                         emit_tcp_state(
@@ -254,8 +323,13 @@ impl TcpServiceReal {
     /// - `Err(anyhow::Error)`：发送失败原因。
     ///
     /// # 说明
-    /// 写入目标取决于连接类型：明文 TCP 或 TLS。
+    /// - 写入目标取决于连接类型：明文 TCP 或 TLS；
+    /// - 帧 payload 超过协商上限时拒绝发送（大数据应走 HTTP 旁路）。
     pub async fn send(&mut self, data: Vec<u8>) -> anyhow::Result<()> {
+        if let Some(server_socket) = self.server_socket.as_deref() {
+            frame_capture::record_outbound(server_socket, &data);
+        }
+        self.frame_limit.check_outbound(&data)?;
         let result = match &mut self.writer {
             TcpWriter::Plain(w) => w.write_all(&data).await,
             TcpWriter::Tls(w) => w.write_all(&data).await,
@@ -284,6 +358,21 @@ impl TcpServiceReal {
             .map(|task| !task.is_finished())
             .unwrap_or(false)
     }
+
+    /// 握手协商的单帧上限（收到 `/handshake` 推送前为协议上限）。
+    pub fn max_frame_bytes(&self) -> usize {
+        self.frame_limit.get()
+    }
+
+    /// 握手协商的 payload 编码（收到 `/handshake` 推送前为 JSON）。
+    pub fn codec(&self) -> PayloadCodec {
+        self.codec.get()
+    }
+
+    /// 本连接是否已协商明文 zstd 压缩。
+    pub fn compression_active(&self) -> bool {
+        self.compression.is_active()
+    }
 }
 
 fn parse_transport(raw: &str) -> (Transport, &str) {
@@ -332,25 +421,66 @@ fn parse_transport(raw: &str) -> (Transport, &str) {
     (Transport::Plain, raw)
 }
 
-fn verify_tls_fingerprint_sha256(
-    tls: &tokio_native_tls::TlsStream<TcpStream>,
-    expected_sha256: &str,
-) -> anyhow::Result<()> {
+pub(crate) async fn tls_handshake(
+    addr: &str,
+    stream: TcpStream,
+    insecure: bool,
+) -> anyhow::Result<TlsStream<TcpStream>> {
+    let host = extract_host(addr)?;
+    let mut builder = native_tls::TlsConnector::builder();
+    if insecure {
+        builder.danger_accept_invalid_certs(true);
+        builder.danger_accept_invalid_hostnames(true);
+    }
+    let connector = tokio_native_tls::TlsConnector::from(builder.build()?);
+    connector
+        .connect(&host, stream)
+        .await
+        .map_err(|e| anyhow::anyhow!("TLS handshake failed: {}", e))
+}
+
+fn peer_certificate_der(tls: &TlsStream<TcpStream>) -> anyhow::Result<Vec<u8>> {
     let peer = tls
         .get_ref()
         .peer_certificate()
         .map_err(|e| anyhow::anyhow!("Failed to read peer certificate: {}", e))?;
     let Some(cert) = peer else {
-        return Err(anyhow::anyhow!(
-            "TLS fingerprint check failed: missing peer certificate"
-        ));
+        return Err(anyhow::anyhow!("Missing peer certificate"));
     };
-    let der = cert
-        .to_der()
-        .map_err(|e| anyhow::anyhow!("Failed to export peer certificate DER: {}", e))?;
+    cert.to_der()
+        .map_err(|e| anyhow::anyhow!("Failed to export peer certificate DER: {}", e))
+}
+
+pub(crate) fn verify_tls_fingerprint_sha256(
+    tls: &TlsStream<TcpStream>,
+    expected_sha256: &str,
+) -> anyhow::Result<()> {
+    let der = peer_certificate_der(tls)
+        .map_err(|e| anyhow::anyhow!("TLS fingerprint check failed: {}", e))?;
     verify_der_sha256_fingerprint(expected_sha256, &der)
 }
 
+/// 探测 TLS 服务端证书（返回叶子证书 DER）。
+///
+/// # 说明
+/// - 与 [`TcpServiceReal::connect`] 使用同一套握手代码，但总是接受任意证书，
+///   以便在用户决定是否信任（固定指纹）之前展示证书详情；
+/// - 握手完成后立即断开，不发送任何业务数据；
+/// - 非 TLS 地址（`tcp://` 或无前缀）返回错误。
+pub async fn probe_peer_certificate(socket: &str) -> anyhow::Result<Vec<u8>> {
+    let (transport, addr) = parse_transport(socket);
+    if matches!(transport, Transport::Plain) {
+        return Err(anyhow::anyhow!("Socket does not use TLS: {}", socket));
+    }
+    let stream = happy_eyeballs::connect(addr)
+        .await
+        .map_err(|e| anyhow::anyhow!("Failed to connect TCP stream: {}", e))?;
+    let mut tls = tls_handshake(addr, stream, true).await?;
+    let der = peer_certificate_der(&tls);
+    let _ = tls.shutdown().await;
+    der
+}
+
 fn extract_host(addr: &str) -> anyhow::Result<String> {
     // Supports:
     // - host:port
@@ -374,3 +504,251 @@ fn extract_host(addr: &str) -> anyhow::Result<String> {
     }
     Ok(trimmed.to_string())
 }
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::*;
+    use crate::features::network::data::frame_decoder::FRAME_MAX_BYTES;
+    use crate::features::network::test_support::{
+        FrameTestServerBuilder, RecordingEventSink, TEST_CERT_SHA256, encode_frame,
+    };
+
+    const WAIT: Duration = Duration::from_secs(3);
+
+    async fn connect_and_start(socket: String) -> (TcpServiceReal, Arc<RecordingEventSink>) {
+        let mut service = TcpServiceReal::connect(socket)
+            .await
+            .expect("connect should succeed");
+        let sink = Arc::new(RecordingEventSink::default());
+        assert!(service.start(sink.clone(), "socket://test".to_string(), 1));
+        (service, sink)
+    }
+
+    #[tokio::test]
+    async fn tcp_real_round_trips_canned_response() {
+        let server = FrameTestServerBuilder::new()
+            .respond(b"ping", &[b"pong", b"pong-2"])
+            .spawn()
+            .await
+            .expect("server should bind");
+        let (mut service, sink) = connect_and_start(server.tcp_socket()).await;
+
+        service
+            .send(encode_frame(b"ping"))
+            .await
+            .expect("send should succeed");
+
+        assert!(sink.wait_until(WAIT, |s| s.frames().len() == 2).await);
+        assert_eq!(sink.frames(), vec![b"pong".to_vec(), b"pong-2".to_vec()]);
+        assert_eq!(server.received(), vec![b"ping".to_vec()]);
+        assert_eq!(sink.states(), vec!["connected".to_string()]);
+        service.close().await.expect("close should succeed");
+    }
+
+    #[tokio::test]
+    async fn tcp_real_reassembles_partial_frames() {
+        let server = FrameTestServerBuilder::new()
+            .greeting_chunk(&[0x00])
+            .greeting_chunk(&[0x05, b'h', b'e'])
+            .greeting_chunk(&[b'l', b'l', b'o', 0x00, 0x00, 0x00])
+            .greeting_chunk(&[0x05, b'w', b'o', b'r', b'l', b'd'])
+            .spawn()
+            .await
+            .expect("server should bind");
+        let mut service = TcpServiceReal::connect(server.tcp_socket())
+            .await
+            .expect("connect should succeed")
+            .with_legacy_message_events(true);
+        let sink = Arc::new(RecordingEventSink::default());
+        assert!(service.start(sink.clone(), "socket://test".to_string(), 1));
+
+        assert!(sink.wait_until(WAIT, |s| s.frames().len() == 2).await);
+        // 空帧被丢弃，其余帧按原顺序投递；legacy 事件保留全部原始字节。
+        assert_eq!(sink.frames(), vec![b"hello".to_vec(), b"world".to_vec()]);
+        assert_eq!(sink.raw_bytes_len(), 16);
+    }
+
+    #[tokio::test]
+    async fn tcp_real_delivers_max_size_frame_across_reads() {
+        let payload = vec![0x5A; FRAME_MAX_BYTES];
+        let server = FrameTestServerBuilder::new()
+            .greeting_chunk(&encode_frame(&payload))
+            .spawn()
+            .await
+            .expect("server should bind");
+        let (_service, sink) = connect_and_start(server.tcp_socket()).await;
+
+        assert!(sink.wait_until(WAIT, |s| !s.frames().is_empty()).await);
+        let frames = sink.frames();
+        assert_eq!(frames.len(), 1);
+        assert_eq!(frames[0].len(), FRAME_MAX_BYTES);
+        // 未开启兼容开关时不发出原始字节事件。
+        assert_eq!(sink.raw_bytes_len(), 0);
+    }
+
+    #[tokio::test]
+    async fn tcp_real_records_codec_from_handshake_push() {
+        let handshake = br#"{"id":-1,"code":0,"data":{"route":"handshake","data":{"session_id":"7","codec":"msgpack"}}}"#;
+        let server = FrameTestServerBuilder::new()
+            .greeting_chunk(&encode_frame(handshake))
+            .spawn()
+            .await
+            .expect("server should bind");
+        let (service, sink) = connect_and_start(server.tcp_socket()).await;
+
+        assert_eq!(service.codec(), PayloadCodec::Json);
+        assert!(sink.wait_until(WAIT, |s| !s.frames().is_empty()).await);
+        assert_eq!(service.codec(), PayloadCodec::MsgPack);
+        // 握手帧本身仍原样投递给前端。
+        assert_eq!(sink.frames(), vec![handshake.to_vec()]);
+    }
+
+    #[tokio::test]
+    async fn tcp_real_records_zstd_negotiation_without_rewriting_frames() {
+        let handshake =
+            br#"{"id":-1,"code":0,"data":{"route":"handshake","data":{"compression":"zstd"}}}"#;
+        let opaque = vec![b'z'; 4096];
+        let server = FrameTestServerBuilder::new()
+            .greeting_chunk(&encode_frame(handshake))
+            .greeting_chunk(&encode_frame(&opaque))
+            .spawn()
+            .await
+            .expect("server should bind");
+        let mut service = TcpServiceReal::connect(server.tcp_socket())
+            .await
+            .expect("connect should succeed")
+            .with_frame_compression(true);
+        let sink = Arc::new(RecordingEventSink::default());
+        assert!(!service.compression_active());
+        assert!(service.start(sink.clone(), "socket://test".to_string(), 1));
+
+        assert!(sink.wait_until(WAIT, |s| s.frames().len() == 2).await);
+        assert!(service.compression_active());
+        // 压缩发生在加密前，传输层按原样投递密文帧。
+        assert_eq!(sink.frames(), vec![handshake.to_vec(), opaque]);
+    }
+
+    #[tokio::test]
+    async fn tcp_real_closes_on_frame_over_negotiated_limit() {
+        let handshake =
+            br#"{"id":-1,"code":0,"data":{"route":"handshake","data":{"max_frame_bytes":2048}}}"#;
+        let server = FrameTestServerBuilder::new()
+            .greeting_chunk(&encode_frame(handshake))
+            .greeting_chunk(&encode_frame(&vec![7; 4096]))
+            .greeting_chunk(&encode_frame(b"after"))
+            .spawn()
+            .await
+            .expect("server should bind");
+        let (mut service, sink) = connect_and_start(server.tcp_socket()).await;
+
+        assert!(
+            sink.wait_until(WAIT, |s| s.states().contains(&"error".to_string()))
+                .await
+        );
+        assert!(sink.wait_until(WAIT, |_| !service.is_listening()).await);
+        // 超限帧及其后的数据均不投递，避免错位解析。
+        assert_eq!(sink.frames(), vec![handshake.to_vec()]);
+        assert_eq!(service.max_frame_bytes(), 2048);
+        assert!(service.send(encode_frame(&vec![1; 4096])).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn tcp_real_drops_truncated_frame_on_disconnect() {
+        let server = FrameTestServerBuilder::new()
+            .greeting_chunk(&[0x00, 0x0A, 1, 2, 3])
+            .close_after_greeting()
+            .spawn()
+            .await
+            .expect("server should bind");
+        let (service, sink) = connect_and_start(server.tcp_socket()).await;
+
+        assert!(
+            sink.wait_until(WAIT, |s| s.states().contains(&"disconnected".to_string()))
+                .await
+        );
+        assert!(sink.frames().is_empty());
+        assert!(sink.wait_until(WAIT, |_| !service.is_listening()).await);
+    }
+
+    #[tokio::test]
+    async fn tcp_real_rejects_tls_fingerprint_mismatch() {
+        let server = FrameTestServerBuilder::new()
+            .tls()
+            .spawn()
+            .await
+            .expect("server should bind");
+        let err = TcpServiceReal::connect(server.tls_fp_socket(&"0".repeat(64)))
+            .await
+            .err()
+            .expect("mismatched fingerprint should fail");
+        assert!(err.to_string().contains("TLS fingerprint mismatch"));
+    }
+
+    #[tokio::test]
+    async fn tcp_real_accepts_matching_tls_fingerprint() {
+        let server = FrameTestServerBuilder::new()
+            .tls()
+            .respond(b"hello", &[b"world"])
+            .spawn()
+            .await
+            .expect("server should bind");
+        let (mut service, sink) =
+            connect_and_start(server.tls_fp_socket(&TEST_CERT_SHA256.to_uppercase())).await;
+
+        service
+            .send(encode_frame(b"hello"))
+            .await
+            .expect("send should succeed");
+        assert!(sink.wait_until(WAIT, |s| !s.frames().is_empty()).await);
+        assert_eq!(sink.frames(), vec![b"world".to_vec()]);
+    }
+
+    #[tokio::test]
+    async fn probe_returns_certificate_without_pinning() {
+        let server = FrameTestServerBuilder::new()
+            .tls()
+            .spawn()
+            .await
+            .expect("server should bind");
+        let socket = server.tls_fp_socket("").replacen("tls-fp://@", "tls://", 1);
+        let der = probe_peer_certificate(&socket)
+            .await
+            .expect("probe should succeed");
+        assert!(verify_der_sha256_fingerprint(TEST_CERT_SHA256, &der).is_ok());
+
+        let info = crate::shared::net::tls_certificate::describe_certificate_der(&der)
+            .expect("test cert should parse");
+        assert_eq!(info.subject, "CN=localhost");
+        assert!(info.self_signed);
+        assert_eq!(
+            info.subject_alt_names,
+            vec!["DNS:localhost", "IP:127.0.0.1"]
+        );
+        assert_eq!(info.sha256_fingerprint, TEST_CERT_SHA256);
+    }
+
+    #[tokio::test]
+    async fn probe_rejects_plain_tcp_socket() {
+        let err = probe_peer_certificate("tcp://127.0.0.1:1")
+            .await
+            .expect_err("plain socket should fail");
+        assert!(err.to_string().contains("does not use TLS"));
+    }
+
+    #[tokio::test]
+    async fn tcp_real_rejects_tls_fp_socket_without_fingerprint() {
+        let server = FrameTestServerBuilder::new()
+            .tls()
+            .spawn()
+            .await
+            .expect("server should bind");
+        let socket = server.tls_fp_socket("").replacen("@", "", 1);
+        let err = TcpServiceReal::connect(socket)
+            .await
+            .err()
+            .expect("missing fingerprint should fail");
+        assert!(err.to_string().contains("Invalid TLS fingerprint"));
+    }
+}
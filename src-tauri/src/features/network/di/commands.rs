@@ -2,20 +2,47 @@
 //!
 //! 约定：注释中文，日志英文（tracing）。
 
-use std::sync::OnceLock;
-use std::time::{Duration, Instant};
-use tauri::{AppHandle, Emitter, State};
+use std::sync::{Arc, OnceLock};
+use std::time::Duration;
+use tauri::{AppHandle, State};
 
+use crate::features::network::data::capabilities_store::{self, StoredCapabilities};
+use crate::features::network::data::frame_capture;
+use crate::features::network::data::frame_compression;
+use crate::features::network::data::header_policy;
 use crate::features::network::data::http_client::ReqwestApiRequestAdapter;
+use crate::features::network::data::oauth_loopback::OAuthLoopbackListener;
+use crate::features::network::data::server_probe;
+use crate::features::network::data::session_token_store::KeyringSessionTokenStore;
+use crate::features::network::data::tcp_real;
 use crate::features::network::di::event_sink::TauriTcpEventSink;
-use crate::features::network::di::models::{ApiRequestJsonArgs, ApiRequestJsonResult};
+use crate::features::network::di::models::{
+    ApiRequestJsonArgs, ApiRequestJsonResult, OAuthLoginResult, SessionExpiredPayload,
+    SessionRefreshedPayload,
+};
 use crate::features::network::di::tcp_backend_factory::DefaultTcpBackendFactory;
+use crate::features::network::domain::ports::session_token_port::{
+    SessionTokenPort, SessionTokens,
+};
+use crate::features::network::domain::types::{
+    FrameCaptureStatus, ServerHealthReport, TcpConnectionStatus,
+};
 use crate::features::network::usecases::api_usecases::{self, ApiJsonRequest};
+use crate::features::network::usecases::oauth_usecases::{self, OAuthError, OAuthTarget};
+use crate::features::network::usecases::session_usecases::{self, SessionRefreshOutcome};
 use crate::features::network::usecases::tcp_usecases::TcpRegistryService;
-use crate::shared::error::{CommandResult, to_command_error};
+use crate::shared::error::{CommandError, CommandResult, command_error, to_command_error};
+use crate::shared::events::{EventBus, EventBusExt};
+use crate::shared::net::tls_certificate::{CertificateInfo, describe_certificate_der};
 use crate::shared::temp_file::{DownloadResult, TempFileManager};
+use tauri_plugin_opener::OpenerExt;
 use tokio::io::AsyncWriteExt;
 
+/// Rust 侧刷新会话令牌后广播的事件名（载荷含新令牌，供前端同步本地会话）。
+pub const SESSION_REFRESHED_EVENT: &str = "session-refreshed";
+/// 会话刷新被拒绝（需重新登录）时广播的事件名。
+pub const SESSION_EXPIRED_EVENT: &str = "session-expired";
+
 #[tauri::command]
 /// 注册并启动一个 TCP service（real 或 mock）。
 ///
@@ -59,6 +86,7 @@ pub async fn remove_tcp_service(
     app: AppHandle,
     server_socket: String,
 ) -> CommandResult<()> {
+    let replay_key = server_socket.trim().to_string();
     tcp_registry
         .remove_tcp_service(server_socket, TauriTcpEventSink::shared(app))
         .await
@@ -68,6 +96,111 @@ pub async fn remove_tcp_service(
                 "error.network_tcp_remove_failed",
                 e,
             )
+        })?;
+    crate::features::network::di::frame_replay::global().clear(&replay_key);
+    crate::features::network::di::typing::clear_server(&replay_key);
+    crate::features::network::di::presence::clear_server(&replay_key);
+    Ok(())
+}
+
+/// 证书探测的总超时（连接 + 握手）。
+const CERTIFICATE_PROBE_TIMEOUT: Duration = Duration::from_secs(10);
+
+#[tauri::command]
+/// 探测服务端 TLS 证书并返回详情（供信任对话框在固定指纹前展示）。
+///
+/// # 参数
+/// - `server_socket`：TLS 地址（`tls://`、`tls-insecure://` 或 `tls-fp://<fp>@...`）。
+///
+/// # 返回值
+/// - `Ok(CertificateInfo)`：subject/issuer/有效期/SAN/SHA-256 指纹。
+/// - `Err(String)`：非 TLS 地址、连接/握手失败、超时或证书无法解析。
+pub async fn get_server_certificate_info(server_socket: String) -> CommandResult<CertificateInfo> {
+    let map_err = |e: anyhow::Error| {
+        to_command_error(
+            "NETWORK_TLS_PROBE_FAILED",
+            "error.network_tls_probe_failed",
+            e,
+        )
+    };
+    let der = tokio::time::timeout(
+        CERTIFICATE_PROBE_TIMEOUT,
+        tcp_real::probe_peer_certificate(server_socket.trim()),
+    )
+    .await
+    .map_err(|e| map_err(e.into()))?
+    .map_err(map_err)?;
+    let info = describe_certificate_der(&der).map_err(|e| {
+        to_command_error(
+            "NETWORK_TLS_CERTIFICATE_INVALID",
+            "error.network_tls_certificate_invalid",
+            e,
+        )
+    })?;
+    tracing::info!(
+        action = "network_tls_certificate_probed",
+        server_socket = %server_socket,
+        fingerprint = %info.sha256_fingerprint
+    );
+    Ok(info)
+}
+
+#[tauri::command]
+/// 探测服务器连通性与延迟（TCP 建连、TLS 握手、`/api/server` 往返、时钟偏差）。
+///
+/// # 参数
+/// - `server_socket`：服务器 socket。
+/// - `tls_policy`/`tls_fingerprint`：TLS 策略（与 `api_request_json` 一致，可选）。
+///
+/// # 返回值
+/// - `Ok(ServerHealthReport)`：各阶段结果；阶段失败体现在报告中而非返回错误。
+/// - `Err(String)`：socket 无法解析。
+pub async fn probe_server(
+    server_socket: String,
+    tls_policy: Option<String>,
+    tls_fingerprint: Option<String>,
+) -> CommandResult<ServerHealthReport> {
+    server_probe::probe(
+        server_socket.trim(),
+        api_usecases::api_tls_policy(tls_policy.as_deref()),
+        tls_fingerprint.as_deref(),
+    )
+    .await
+    .map_err(|e| {
+        to_command_error(
+            "NETWORK_SERVER_PROBE_FAILED",
+            "error.network_server_probe_failed",
+            e,
+        )
+    })
+}
+
+#[tauri::command]
+/// 读取每个服务器最近一次的探测结果（供服务器列表展示）。
+pub async fn get_server_probes() -> CommandResult<Vec<ServerHealthReport>> {
+    Ok(server_probe::cached_reports())
+}
+
+#[tauri::command]
+/// 读取服务端在握手中声明的协议版本与路由能力（供前端按特性开关界面）。
+///
+/// # 参数
+/// - `server_socket`：服务器 socket。
+///
+/// # 返回值
+/// - `Ok(Some(StoredCapabilities))`：最近一次协商结果（含各特性是否可用）。
+/// - `Ok(None)`：尚未与该服务器完成握手；此时应按旧服务端处理（全部特性可用）。
+pub async fn get_server_capabilities(
+    server_socket: String,
+) -> CommandResult<Option<StoredCapabilities>> {
+    capabilities_store::load(server_socket.trim())
+        .await
+        .map_err(|e| {
+            to_command_error(
+                "NETWORK_SERVER_CAPABILITIES_FAILED",
+                "error.network_server_capabilities_failed",
+                e,
+            )
         })
 }
 
@@ -98,13 +231,236 @@ pub async fn send_tcp_service(
         })
 }
 
+#[tauri::command]
+/// 查询指定 server_socket 的 TCP 连接状态（含握手协商的 payload 编码，便于调试）。
+///
+/// # 参数
+/// - `server_socket`：逻辑 server_socket。
+///
+/// # 返回值
+/// - `Ok(TcpConnectionStatus)`：连接状态快照。
+/// - `Err(CommandError)`：未注册该 server_socket。
+pub async fn tcp_connection_status(
+    tcp_registry: State<'_, TcpRegistryService>,
+    server_socket: String,
+) -> CommandResult<TcpConnectionStatus> {
+    tcp_registry
+        .connection_status(server_socket)
+        .await
+        .map_err(|e| {
+            to_command_error(
+                "NETWORK_TCP_STATUS_FAILED",
+                "error.network_tcp_status_failed",
+                e,
+            )
+        })
+}
+
+#[tauri::command]
+/// 按连接协商的编码，将业务 JSON 文本编码为 payload bytes（加密前调用）。
+///
+/// # 参数
+/// - `server_socket`：逻辑 server_socket。
+/// - `json`：业务 JSON 文本。
+///
+/// # 返回值
+/// - `Ok(Vec<u8>)`：编码后的 bytes（JSON 编码下即 UTF-8 文本；已协商压缩时带 flags 头）。
+/// - `Err(CommandError)`：未注册或编码失败。
+pub async fn tcp_encode_payload(
+    tcp_registry: State<'_, TcpRegistryService>,
+    server_socket: String,
+    json: String,
+) -> CommandResult<Vec<u8>> {
+    let (codec, compressed) = tcp_registry
+        .negotiated_payload(server_socket)
+        .await
+        .map_err(|e| {
+            to_command_error(
+                "NETWORK_TCP_STATUS_FAILED",
+                "error.network_tcp_status_failed",
+                e,
+            )
+        })?;
+    let payload = codec.encode_json_text(&json).map_err(|e| {
+        to_command_error(
+            "NETWORK_CODEC_ENCODE_FAILED",
+            "error.network_codec_encode_failed",
+            e,
+        )
+    })?;
+    Ok(if compressed {
+        frame_compression::encode_plaintext(payload)
+    } else {
+        payload
+    })
+}
+
+#[tauri::command]
+/// 按连接协商的编码，将解密后的 payload bytes 解码为业务 JSON 文本。
+///
+/// # 参数
+/// - `server_socket`：逻辑 server_socket。
+/// - `payload`：解密后的 bytes。
+///
+/// # 返回值
+/// - `Ok(String)`：JSON 文本（已协商压缩时先去掉 flags 头并按需解压）。
+/// - `Err(CommandError)`：未注册或解码失败。
+pub async fn tcp_decode_payload(
+    tcp_registry: State<'_, TcpRegistryService>,
+    server_socket: String,
+    payload: Vec<u8>,
+) -> CommandResult<String> {
+    let (codec, compressed) = tcp_registry
+        .negotiated_payload(server_socket)
+        .await
+        .map_err(|e| {
+            to_command_error(
+                "NETWORK_TCP_STATUS_FAILED",
+                "error.network_tcp_status_failed",
+                e,
+            )
+        })?;
+    let payload = if compressed {
+        frame_compression::decode_plaintext(&payload).map_err(|e| {
+            to_command_error(
+                "NETWORK_CODEC_DECODE_FAILED",
+                "error.network_codec_decode_failed",
+                e,
+            )
+        })?
+    } else {
+        payload
+    };
+    codec.decode_to_json_text(&payload).map_err(|e| {
+        to_command_error(
+            "NETWORK_CODEC_DECODE_FAILED",
+            "error.network_codec_decode_failed",
+            e,
+        )
+    })
+}
+
+/// 抓包落盘/导出目录：`<app_data>/debug`。
+fn frame_capture_dir() -> CommandResult<std::path::PathBuf> {
+    crate::shared::app_data_dir::get_app_data_dir()
+        .map(|dir| dir.join("debug"))
+        .map_err(|e| {
+            to_command_error(
+                "NETWORK_FRAME_CAPTURE_FAILED",
+                "error.network_frame_capture_failed",
+                e,
+            )
+        })
+}
+
+#[tauri::command]
+/// 开始调试抓包（需开启开发者设置 `frame_capture`）。
+///
+/// # 参数
+/// - `server_socket`：仅抓取该 server（为空表示全部）。
+/// - `persist`：是否追加写入 `<data>/debug/frames-*.ndjson`（由抓包写入线程落盘）。
+///
+/// # 返回值
+/// - `Ok(FrameCaptureStatus)`：抓包状态。
+/// - `Err(CommandError)`：设置未开启或创建文件失败。
+pub async fn start_frame_capture(
+    server_socket: Option<String>,
+    persist: Option<bool>,
+) -> CommandResult<FrameCaptureStatus> {
+    if !crate::features::settings::get_config_value::<bool>(String::from("frame_capture")).await {
+        return Err(command_error(
+            "NETWORK_FRAME_CAPTURE_DISABLED",
+            "error.network_frame_capture_disabled",
+        ));
+    }
+    let dir = match persist.unwrap_or(false) {
+        true => Some(frame_capture_dir()?),
+        false => None,
+    };
+    let status =
+        tokio::task::spawn_blocking(move || frame_capture::start(server_socket, dir.as_deref()))
+            .await
+            .map_err(anyhow::Error::from)
+            .and_then(|result| result)
+            .map_err(|e| {
+                to_command_error(
+                    "NETWORK_FRAME_CAPTURE_FAILED",
+                    "error.network_frame_capture_failed",
+                    e,
+                )
+            })?;
+    tracing::info!(
+        action = "network_frame_capture_started",
+        server_socket = ?status.server_socket,
+        file = ?status.file_path
+    );
+    Ok(status)
+}
+
+#[tauri::command]
+/// 停止调试抓包（环形缓冲保留，可继续导出）。
+///
+/// # 返回值
+/// - `Ok(FrameCaptureStatus)`：停止后的状态。
+/// - `Err(CommandError)`：刷新落盘文件失败。
+pub async fn stop_frame_capture() -> CommandResult<FrameCaptureStatus> {
+    let status = tokio::task::spawn_blocking(frame_capture::stop)
+        .await
+        .map_err(anyhow::Error::from)
+        .and_then(|result| result)
+        .map_err(|e| {
+            to_command_error(
+                "NETWORK_FRAME_CAPTURE_FAILED",
+                "error.network_frame_capture_failed",
+                e,
+            )
+        })?;
+    tracing::info!(
+        action = "network_frame_capture_stopped",
+        buffered = status.buffered
+    );
+    Ok(status)
+}
+
+#[tauri::command]
+/// 将环形缓冲中的帧导出为 NDJSON 文件（已脱敏）。
+///
+/// # 参数
+/// - `server_socket`：仅导出该 server（为空表示全部）。
+///
+/// # 返回值
+/// - `Ok(String)`：导出文件路径。
+/// - `Err(CommandError)`：写文件失败。
+pub async fn export_frame_capture(server_socket: Option<String>) -> CommandResult<String> {
+    let dir = frame_capture_dir()?;
+    let (path, count) =
+        tokio::task::spawn_blocking(move || frame_capture::export(server_socket.as_deref(), &dir))
+            .await
+            .map_err(anyhow::Error::from)
+            .and_then(|result| result)
+            .map_err(|e| {
+                to_command_error(
+                    "NETWORK_FRAME_CAPTURE_FAILED",
+                    "error.network_frame_capture_failed",
+                    e,
+                )
+            })?;
+    tracing::info!(action = "network_frame_capture_exported", path = %path.display(), frames = count);
+    Ok(path.display().to_string())
+}
+
 /// 使用 Rust `reqwest` 执行 `/api/*` JSON 请求（支持 TLS 策略）。
 ///
 /// # 说明
 /// - WebView 的 `fetch/WebSocket` 无法绕过自签证书校验；
-/// - 桌面端可通过 Rust sidecar 按 TLS 策略（insecure/指纹）完成请求。
+/// - 桌面端可通过 Rust sidecar 按 TLS 策略（insecure/指纹）完成请求；
+/// - 401 时使用已保存的 refresh token 透明刷新并重试一次：刷新成功广播 `session-refreshed`，
+///   刷新被拒绝广播 `session-expired`。
 #[tauri::command]
-pub async fn api_request_json(args: ApiRequestJsonArgs) -> CommandResult<ApiRequestJsonResult> {
+pub async fn api_request_json(
+    app: AppHandle,
+    args: ApiRequestJsonArgs,
+) -> CommandResult<ApiRequestJsonResult> {
     let usecase_args = ApiJsonRequest {
         server_socket: args.server_socket,
         method: args.method,
@@ -114,22 +470,237 @@ pub async fn api_request_json(args: ApiRequestJsonArgs) -> CommandResult<ApiRequ
         tls_policy: args.tls_policy,
         tls_fingerprint: args.tls_fingerprint,
     };
+    let server_socket = usecase_args.server_socket.trim().to_string();
     let api_request_port = ReqwestApiRequestAdapter::shared();
-    api_usecases::api_request_json(usecase_args, api_request_port.as_ref())
+    let token_port = KeyringSessionTokenStore::shared();
+    let result = session_usecases::api_request_json_with_session(
+        usecase_args,
+        api_request_port.as_ref(),
+        token_port.as_ref(),
+    )
+    .await
+    .map_err(|e| {
+        to_command_error(
+            "NETWORK_API_REQUEST_FAILED",
+            "error.network_api_request_failed",
+            e,
+        )
+    })?;
+    if let Some(tokens) = result.refreshed {
+        emit_session_refreshed(&app, &server_socket, tokens);
+    }
+    if result.session_expired {
+        emit_session_expired(&app, &server_socket);
+    }
+    let response = result.response;
+    Ok(ApiRequestJsonResult {
+        ok: response.ok,
+        status: response.status,
+        body: response.body,
+        error: response.error,
+    })
+}
+
+fn emit_session_refreshed(events: &dyn EventBus, server_socket: &str, tokens: SessionTokens) {
+    events.emit(
+        SESSION_REFRESHED_EVENT,
+        SessionRefreshedPayload {
+            server_socket: server_socket.to_string(),
+            tokens,
+        },
+    );
+}
+
+fn emit_session_expired(events: &dyn EventBus, server_socket: &str) {
+    events.emit(
+        SESSION_EXPIRED_EVENT,
+        SessionExpiredPayload {
+            server_socket: server_socket.to_string(),
+        },
+    );
+}
+
+/// 保存或清除指定服务器的会话令牌（登录、登出、前端刷新后调用）。
+///
+/// # 参数
+/// - `tokens`：会话令牌；为 `None` 时清除。
+#[tauri::command]
+pub async fn set_session_tokens(
+    server_socket: String,
+    tokens: Option<SessionTokens>,
+) -> CommandResult<()> {
+    let socket = server_socket.trim();
+    if socket.is_empty() {
+        return Err(command_error(
+            "NETWORK_SESSION_TOKENS_FAILED",
+            "error.network_session_tokens_failed",
+        ));
+    }
+    let token_port = KeyringSessionTokenStore::shared();
+    let result = match tokens {
+        Some(tokens) => token_port.save_tokens(socket, tokens).await,
+        None => token_port.clear_tokens(socket).await,
+    };
+    result.map_err(|e| {
+        to_command_error(
+            "NETWORK_SESSION_TOKENS_FAILED",
+            "error.network_session_tokens_failed",
+            e,
+        )
+    })
+}
+
+/// 刷新指定服务器的会话令牌（与 `api_request_json` 的 401 自动刷新共用串行锁）。
+///
+/// # 参数
+/// - `tokens`：前端当前持有的令牌（Rust 侧尚未保存时以此为准）。
+///
+/// # 返回值
+/// - `Ok(Some(tokens))`：刷新后的令牌（可能由并发请求刷新）；
+/// - `Ok(None)`：暂时无法刷新，或刷新被拒绝（此时同时广播 `session-expired`）。
+#[tauri::command]
+pub async fn refresh_session_tokens(
+    app: AppHandle,
+    server_socket: String,
+    tokens: SessionTokens,
+    tls_policy: Option<String>,
+    tls_fingerprint: Option<String>,
+) -> CommandResult<Option<SessionTokens>> {
+    let socket = server_socket.trim().to_string();
+    let token_port = KeyringSessionTokenStore::shared();
+    let to_err = |e: anyhow::Error| {
+        to_command_error(
+            "NETWORK_SESSION_TOKENS_FAILED",
+            "error.network_session_tokens_failed",
+            e,
+        )
+    };
+    if token_port
+        .load_tokens(&socket)
+        .await
+        .map_err(to_err)?
+        .is_none()
+    {
+        token_port
+            .save_tokens(&socket, tokens.clone())
+            .await
+            .map_err(to_err)?;
+    }
+    let outcome = session_usecases::refresh_session(
+        &socket,
+        &tokens.access_token,
+        tls_policy,
+        tls_fingerprint,
+        ReqwestApiRequestAdapter::shared().as_ref(),
+        token_port.as_ref(),
+    )
+    .await
+    .map_err(to_err)?;
+    match outcome {
+        SessionRefreshOutcome::Refreshed(tokens) => Ok(Some(tokens)),
+        SessionRefreshOutcome::Expired => {
+            emit_session_expired(&app, &socket);
+            Ok(None)
+        }
+        SessionRefreshOutcome::Unavailable => Ok(None),
+    }
+}
+
+/// OAuth 登录等待浏览器回调的最长时间。
+const OAUTH_CALLBACK_TIMEOUT: Duration = Duration::from_secs(5 * 60);
+
+fn oauth_command_error(err: anyhow::Error) -> CommandError {
+    let (code, key) = match err.downcast_ref::<OAuthError>() {
+        Some(OAuthError::Unsupported) => ("OAUTH_UNSUPPORTED", "error.oauth_unsupported"),
+        Some(OAuthError::StateMismatch) => ("OAUTH_STATE_MISMATCH", "error.oauth_state_mismatch"),
+        Some(OAuthError::Denied(_)) => ("OAUTH_DENIED", "error.oauth_denied"),
+        Some(OAuthError::ExchangeRejected(_)) => {
+            ("OAUTH_EXCHANGE_REJECTED", "error.oauth_exchange_rejected")
+        }
+        None => ("OAUTH_FAILED", "error.oauth_failed"),
+    };
+    to_command_error(code, key, err)
+}
+
+/// 使用 OAuth2/OIDC（PKCE + 本地回环回调）登录指定服务器。
+///
+/// # 参数
+/// - `device_id`：刷新/登录时上报的设备 ID（可选）。
+/// - `tls_policy` / `tls_fingerprint`：服务器 TLS 策略（与 `api_request_json` 一致）。
+///
+/// # 返回值
+/// - `Ok(OAuthLoginResult)`：登录成功；令牌已写入系统凭据存储，并随结果返回由前端写入本地会话。
+/// - `Err(CommandError)`：服务器不支持、用户拒绝、回调超时或换取令牌失败。
+///
+/// # 说明
+/// 授权页面在系统浏览器中打开（opener 插件），回调由 `127.0.0.1` 随机端口接收；
+/// `state` 不匹配的回调被忽略，继续等待直到匹配回调到达或超时。协议见
+/// `docs/api/11-http-endpoints-v1.md`（`GET /api/server` 的 `oauth` 字段与 `grant_type=authorization_code`）。
+#[tauri::command]
+pub async fn login_oauth(
+    app: AppHandle,
+    server_socket: String,
+    device_id: Option<String>,
+    tls_policy: Option<String>,
+    tls_fingerprint: Option<String>,
+) -> CommandResult<OAuthLoginResult> {
+    let target = OAuthTarget {
+        server_socket: server_socket.trim().to_string(),
+        tls_policy,
+        tls_fingerprint,
+    };
+    let api_request_port = ReqwestApiRequestAdapter::shared();
+    let config = oauth_usecases::discover_oauth_config(&target, api_request_port.as_ref())
+        .await
+        .map_err(oauth_command_error)?;
+    let pkce = oauth_usecases::generate_pkce().map_err(oauth_command_error)?;
+    let state = oauth_usecases::generate_state().map_err(oauth_command_error)?;
+    let listener = OAuthLoopbackListener::bind()
+        .await
+        .map_err(oauth_command_error)?;
+    let redirect_uri = listener.redirect_uri();
+    let authorize_url =
+        oauth_usecases::build_authorization_url(&config, &redirect_uri, &pkce, &state)
+            .map_err(oauth_command_error)?;
+
+    app.opener()
+        .open_url(&authorize_url, None::<&str>)
+        .map_err(|e| oauth_command_error(anyhow::anyhow!(e.to_string())))?;
+    tracing::info!(action = "network_oauth_browser_opened", server_socket = %target.server_socket);
+
+    let params = listener
+        .wait_for_callback(&state, OAUTH_CALLBACK_TIMEOUT)
         .await
-        .map(|result| ApiRequestJsonResult {
-            ok: result.ok,
-            status: result.status,
-            body: result.body,
-            error: result.error,
-        })
         .map_err(|e| {
             to_command_error(
-                "NETWORK_API_REQUEST_FAILED",
-                "error.network_api_request_failed",
+                "OAUTH_CALLBACK_TIMED_OUT",
+                "error.oauth_callback_timed_out",
                 e,
             )
-        })
+        })?;
+    let code = oauth_usecases::authorization_code(&params, &state)
+        .map_err(|e| oauth_command_error(e.into()))?;
+    let result = oauth_usecases::exchange_code(
+        &target,
+        &code,
+        &pkce,
+        &redirect_uri,
+        device_id,
+        api_request_port.as_ref(),
+    )
+    .await
+    .map_err(oauth_command_error)?;
+
+    KeyringSessionTokenStore::shared()
+        .save_tokens(&target.server_socket, result.tokens.clone())
+        .await
+        .map_err(oauth_command_error)?;
+    tracing::info!(action = "network_oauth_login_succeeded", server_socket = %target.server_socket);
+    Ok(OAuthLoginResult {
+        uid: result.uid,
+        is_new_user: result.is_new_user,
+        tokens: result.tokens,
+    })
 }
 
 /// 使用 Rust `reqwest` 下载文件，通过 Tauri event 推送下载进度。
@@ -142,7 +713,7 @@ static HTTP_CLIENT: OnceLock<reqwest::Client> = OnceLock::new();
 
 fn http_client() -> &'static reqwest::Client {
     HTTP_CLIENT.get_or_init(|| {
-        reqwest::Client::builder()
+        header_policy::client_builder()
             .timeout(std::time::Duration::from_secs(120))
             .build()
             .map_err(|e| {
@@ -300,10 +871,10 @@ pub async fn download_file(
     }
     let mut downloaded: u64 = if resumed { resume_from } else { 0 };
     let mut stream = response.bytes_stream();
-    let mut last_emit = Instant::now();
-    let mut last_emitted_downloaded = downloaded;
+    // 进度事件按任务合并：同一任务每 100ms 最多投递一次，结束时补发最终进度。
     const PROGRESS_EMIT_INTERVAL: Duration = Duration::from_millis(100);
-    const PROGRESS_EMIT_BYTES: u64 = 64 * 1024;
+    let progress =
+        (Arc::new(app) as Arc<dyn EventBus>).channel("download:progress", PROGRESS_EMIT_INTERVAL);
 
     let stream_result: Result<(), _> = async {
         while let Some(chunk) = stream.next().await {
@@ -320,25 +891,19 @@ pub async fn download_file(
                 tracing::warn!(action = "network_temp_file_update_progress_failed", task_id = %task_id, error = %e);
             }
 
-            let should_emit = downloaded == total
-                || downloaded.saturating_sub(last_emitted_downloaded) >= PROGRESS_EMIT_BYTES
-                || last_emit.elapsed() >= PROGRESS_EMIT_INTERVAL;
-            if should_emit {
-                last_emit = Instant::now();
-                last_emitted_downloaded = downloaded;
-                let _ = app.emit(
-                    "download:progress",
-                    serde_json::json!({
-                        "taskId": task_id,
-                        "downloaded": downloaded,
-                        "total": total,
-                    }),
-                );
-            }
+            progress.send(
+                &task_id,
+                serde_json::json!({
+                    "taskId": task_id,
+                    "downloaded": downloaded,
+                    "total": total,
+                }),
+            );
         }
         Ok::<_, String>(())
     }
     .await;
+    progress.flush();
 
     if let Err(e) = stream_result {
         let _ = temp_files.mark_failed(&task_id).await;
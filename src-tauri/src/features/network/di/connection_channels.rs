@@ -0,0 +1,405 @@
+//! network｜DI：按连接订阅的 Tauri Channel（替代全局广播帧事件）。
+//!
+//! 说明：
+//! - 全局 `emit` 会把每一帧复制给所有窗口；窗口通过 `subscribe_connection(server_socket)`
+//!   领取该连接的 `Channel` 后，帧（`tcp-frame` / `tcp-frame-batch` / `tcp-message`）只经
+//!   Channel 投递给订阅窗口；
+//! - 未订阅的窗口仍通过全局事件接收（回退路径）；全局事件会按窗口 label 过滤掉已订阅窗口，
+//!   因此前端的帧事件监听需注册在当前窗口上（见前端 `listenTcpFrame`）；
+//! - 状态/洪泛等小事件仍走全局事件；
+//! - 订阅在 `unsubscribe_connection`、窗口销毁或投递失败时移除；
+//! - 帧事件携带 server 内自增序号（见 `frame_replay`），每个订阅记录最后投递的序号，
+//!   序号跳跃即判定为缺帧，由分发器触发受影响窗口的定向补齐；
+//!   `connection_subscription_status` 暴露最后投递的序号与累计缺帧数。
+//!
+//! 约定：注释中文，日志英文（tracing）。
+
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex, OnceLock};
+
+use serde::Serialize;
+use tauri::ipc::Channel;
+
+use crate::shared::error::CommandResult;
+
+/// 经连接 Channel 投递的事件。
+#[derive(Debug, Clone, Serialize)]
+#[serde(tag = "kind", rename_all = "camelCase")]
+pub enum ConnectionChannelEvent {
+    /// 拆包后的单帧（对应 `tcp-frame`）。
+    Frame { seq: u64, payload: Vec<u8> },
+    /// 洪泛期间的批量帧（对应 `tcp-frame-batch`），序号从 `first_seq` 起连续。
+    #[serde(rename_all = "camelCase")]
+    FrameBatch {
+        first_seq: u64,
+        payloads: Vec<Vec<u8>>,
+    },
+    /// 原始字节（legacy，对应 `tcp-message`；不带序号）。
+    Message { payload: Vec<u8> },
+}
+
+impl ConnectionChannelEvent {
+    /// 事件覆盖的序号区间（首、尾）；legacy 原始字节与空批次为 `None`。
+    fn seq_range(&self) -> Option<(u64, u64)> {
+        match self {
+            Self::Frame { seq, .. } => Some((*seq, *seq)),
+            Self::FrameBatch {
+                first_seq,
+                payloads,
+            } if !payloads.is_empty() => Some((*first_seq, first_seq + payloads.len() as u64 - 1)),
+            Self::FrameBatch { .. } | Self::Message { .. } => None,
+        }
+    }
+}
+
+/// 某订阅检测到的缺帧：`expected_seq..received_seq` 之间（不含 `received_seq`）的帧未投递。
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FrameGap {
+    pub server_socket: String,
+    pub subscription_id: u64,
+    pub window_label: String,
+    pub expected_seq: u64,
+    pub received_seq: u64,
+}
+
+impl FrameGap {
+    /// 缺失的帧数。
+    pub fn missing(&self) -> u64 {
+        self.received_seq - self.expected_seq
+    }
+}
+
+/// 一次投递的结果。
+#[derive(Debug, Default)]
+pub struct Delivery {
+    /// 成功投递的窗口 label。
+    pub delivered: Vec<String>,
+    /// 本次投递发现的缺帧。
+    pub gaps: Vec<FrameGap>,
+}
+
+/// 订阅状态快照（`connection_subscription_status` 返回值）。
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SubscriptionStatus {
+    pub server_socket: String,
+    pub window_label: String,
+    /// 最后投递的帧序号（尚未投递过为 0）。
+    pub last_seq: u64,
+    /// 累计检测到的缺帧数。
+    pub missed_frames: u64,
+}
+
+/// 投递函数；返回 `false` 表示通道已失效。
+type ChannelSender = Arc<dyn Fn(ConnectionChannelEvent) -> bool + Send + Sync>;
+
+#[derive(Clone)]
+struct Subscription {
+    id: u64,
+    window_label: String,
+    sender: ChannelSender,
+    /// 最后投递的帧序号（0 = 尚未投递）。
+    last_seq: Arc<AtomicU64>,
+    missed_frames: Arc<AtomicU64>,
+}
+
+/// 连接订阅表（key 为 server_socket）。
+#[derive(Default)]
+pub struct ConnectionChannels {
+    next_id: AtomicU64,
+    subscriptions: Mutex<HashMap<String, Vec<Subscription>>>,
+}
+
+impl ConnectionChannels {
+    /// 进程级共享实例。
+    pub fn global() -> &'static Self {
+        static CHANNELS: OnceLock<ConnectionChannels> = OnceLock::new();
+        CHANNELS.get_or_init(Self::default)
+    }
+
+    /// 订阅连接；同一窗口重复订阅同一连接时替换旧通道。
+    fn subscribe(&self, server_socket: &str, window_label: &str, sender: ChannelSender) -> u64 {
+        let id = self.next_id.fetch_add(1, Ordering::Relaxed) + 1;
+        let mut guard = self.subscriptions.lock().unwrap_or_else(|p| p.into_inner());
+        let subs = guard.entry(server_socket.to_string()).or_default();
+        subs.retain(|s| s.window_label != window_label);
+        subs.push(Subscription {
+            id,
+            window_label: window_label.to_string(),
+            sender,
+            last_seq: Arc::new(AtomicU64::new(0)),
+            missed_frames: Arc::new(AtomicU64::new(0)),
+        });
+        id
+    }
+
+    /// 按订阅 id 取消订阅。
+    fn unsubscribe(&self, id: u64) -> bool {
+        let mut guard = self.subscriptions.lock().unwrap_or_else(|p| p.into_inner());
+        let mut removed = false;
+        guard.retain(|_, subs| {
+            let before = subs.len();
+            subs.retain(|s| s.id != id);
+            removed |= subs.len() != before;
+            !subs.is_empty()
+        });
+        removed
+    }
+
+    /// 移除某窗口的全部订阅（窗口销毁时调用）。
+    pub fn remove_window(&self, window_label: &str) {
+        let mut guard = self.subscriptions.lock().unwrap_or_else(|p| p.into_inner());
+        guard.retain(|_, subs| {
+            subs.retain(|s| s.window_label != window_label);
+            !subs.is_empty()
+        });
+    }
+
+    /// 是否有窗口订阅了该连接。
+    pub fn has_subscribers(&self, server_socket: &str) -> bool {
+        self.subscriptions
+            .lock()
+            .unwrap_or_else(|p| p.into_inner())
+            .contains_key(server_socket)
+    }
+
+    /// 订阅状态；订阅不存在时为 `None`。
+    fn status(&self, id: u64) -> Option<SubscriptionStatus> {
+        let guard = self.subscriptions.lock().unwrap_or_else(|p| p.into_inner());
+        guard.iter().find_map(|(server_socket, subs)| {
+            subs.iter()
+                .find(|s| s.id == id)
+                .map(|s| SubscriptionStatus {
+                    server_socket: server_socket.clone(),
+                    window_label: s.window_label.clone(),
+                    last_seq: s.last_seq.load(Ordering::Relaxed),
+                    missed_frames: s.missed_frames.load(Ordering::Relaxed),
+                })
+        })
+    }
+
+    /// 向订阅窗口投递事件，返回成功投递的窗口 label 与发现的缺帧；投递失败的订阅被移除。
+    ///
+    /// # 说明
+    /// 序号早于已投递序号的帧（并发投递导致的乱序）照常投递，不回退记录的序号。
+    pub fn deliver(&self, server_socket: &str, event: ConnectionChannelEvent) -> Delivery {
+        let targets: Vec<Subscription> = {
+            let guard = self.subscriptions.lock().unwrap_or_else(|p| p.into_inner());
+            guard.get(server_socket).cloned().unwrap_or_default()
+        };
+
+        let seq_range = event.seq_range();
+        let mut delivery = Delivery {
+            delivered: Vec::with_capacity(targets.len()),
+            gaps: Vec::new(),
+        };
+        let mut event = Some(event);
+        let last = targets.len().saturating_sub(1);
+        for (index, target) in targets.into_iter().enumerate() {
+            // 最后一个订阅者直接移交所有权，避免多一次拷贝。
+            let payload = if index == last {
+                event.take()
+            } else {
+                event.clone()
+            };
+            let Some(payload) = payload else {
+                break;
+            };
+            if (target.sender)(payload) {
+                if let Some((first, end)) = seq_range {
+                    let previous = target.last_seq.fetch_max(end, Ordering::Relaxed);
+                    if previous != 0 && first > previous + 1 {
+                        let gap = FrameGap {
+                            server_socket: server_socket.to_string(),
+                            subscription_id: target.id,
+                            window_label: target.window_label.clone(),
+                            expected_seq: previous + 1,
+                            received_seq: first,
+                        };
+                        target
+                            .missed_frames
+                            .fetch_add(gap.missing(), Ordering::Relaxed);
+                        delivery.gaps.push(gap);
+                    }
+                }
+                delivery.delivered.push(target.window_label);
+            } else {
+                tracing::debug!(
+                    action = "network_connection_channel_dropped",
+                    server_socket = %server_socket,
+                    window = %target.window_label
+                );
+                self.unsubscribe(target.id);
+            }
+        }
+        delivery
+    }
+}
+
+/// 订阅某连接的帧事件（调用窗口之后不再收到该连接的全局帧事件）。
+///
+/// # 返回值
+/// - `Ok(u64)`：订阅 id，用于 `unsubscribe_connection`。
+#[tauri::command]
+pub fn subscribe_connection(
+    window: tauri::WebviewWindow,
+    server_socket: String,
+    on_event: Channel<ConnectionChannelEvent>,
+) -> CommandResult<u64> {
+    let label = window.label().to_string();
+    let sender: ChannelSender = Arc::new(move |event| on_event.send(event).is_ok());
+    let id = ConnectionChannels::global().subscribe(&server_socket, &label, sender);
+    tracing::debug!(
+        action = "network_connection_channel_subscribed",
+        server_socket = %server_socket,
+        window = %label,
+        subscription_id = id
+    );
+    Ok(id)
+}
+
+/// 取消连接订阅（该窗口恢复接收全局帧事件）。
+///
+/// # 返回值
+/// - `Ok(bool)`：订阅是否存在。
+#[tauri::command]
+pub fn unsubscribe_connection(subscription_id: u64) -> CommandResult<bool> {
+    Ok(ConnectionChannels::global().unsubscribe(subscription_id))
+}
+
+/// 查询订阅状态（最后投递的帧序号与累计缺帧数）。
+///
+/// # 返回值
+/// - `Ok(Some(SubscriptionStatus))`：订阅存在；
+/// - `Ok(None)`：订阅已取消或已因投递失败移除。
+#[tauri::command]
+pub fn connection_subscription_status(
+    subscription_id: u64,
+) -> CommandResult<Option<SubscriptionStatus>> {
+    Ok(ConnectionChannels::global().status(subscription_id))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn recording_sender(alive: bool) -> (ChannelSender, Arc<Mutex<Vec<ConnectionChannelEvent>>>) {
+        let seen = Arc::new(Mutex::new(Vec::new()));
+        let sink = seen.clone();
+        let sender: ChannelSender = Arc::new(move |event| {
+            sink.lock().unwrap().push(event);
+            alive
+        });
+        (sender, seen)
+    }
+
+    #[test]
+    fn delivers_only_to_subscribers_of_the_connection() {
+        let channels = ConnectionChannels::default();
+        let (main, main_seen) = recording_sender(true);
+        let (mini, mini_seen) = recording_sender(true);
+        channels.subscribe("tls://a:1", "main", main);
+        channels.subscribe("tls://b:1", "mini-chat", mini);
+
+        let delivered = channels.deliver(
+            "tls://a:1",
+            ConnectionChannelEvent::Frame {
+                seq: 1,
+                payload: vec![1, 2],
+            },
+        );
+        assert_eq!(delivered.delivered, vec!["main".to_string()]);
+        assert_eq!(main_seen.lock().unwrap().len(), 1);
+        assert!(mini_seen.lock().unwrap().is_empty());
+        assert!(
+            channels
+                .deliver(
+                    "tls://c:1",
+                    ConnectionChannelEvent::Message { payload: vec![] }
+                )
+                .delivered
+                .is_empty()
+        );
+    }
+
+    #[test]
+    fn resubscribe_replaces_and_unsubscribe_restores_fallback() {
+        let channels = ConnectionChannels::default();
+        let (first, first_seen) = recording_sender(true);
+        let (second, second_seen) = recording_sender(true);
+        channels.subscribe("tls://a:1", "main", first);
+        let id = channels.subscribe("tls://a:1", "main", second);
+
+        channels.deliver(
+            "tls://a:1",
+            ConnectionChannelEvent::Frame {
+                seq: 1,
+                payload: vec![7],
+            },
+        );
+        assert!(first_seen.lock().unwrap().is_empty());
+        assert_eq!(second_seen.lock().unwrap().len(), 1);
+
+        assert!(channels.unsubscribe(id));
+        assert!(!channels.has_subscribers("tls://a:1"));
+        assert!(!channels.unsubscribe(id));
+    }
+
+    #[test]
+    fn failed_sends_drop_the_subscription() {
+        let channels = ConnectionChannels::default();
+        let (dead, _) = recording_sender(false);
+        channels.subscribe("tls://a:1", "closed-window", dead);
+        let delivered = channels.deliver(
+            "tls://a:1",
+            ConnectionChannelEvent::FrameBatch {
+                first_seq: 1,
+                payloads: vec![vec![1]],
+            },
+        );
+        assert!(delivered.delivered.is_empty());
+        assert!(!channels.has_subscribers("tls://a:1"));
+
+        let (alive, _) = recording_sender(true);
+        channels.subscribe("tls://a:1", "main", alive);
+        channels.remove_window("main");
+        assert!(!channels.has_subscribers("tls://a:1"));
+    }
+
+    #[test]
+    fn sequence_jumps_are_reported_per_subscription() {
+        let channels = ConnectionChannels::default();
+        let (main, _) = recording_sender(true);
+        let id = channels.subscribe("tls://a:1", "main", main);
+        let frame = |seq| ConnectionChannelEvent::Frame {
+            seq,
+            payload: vec![],
+        };
+
+        assert!(channels.deliver("tls://a:1", frame(5)).gaps.is_empty());
+        assert!(
+            channels
+                .deliver(
+                    "tls://a:1",
+                    ConnectionChannelEvent::FrameBatch {
+                        first_seq: 6,
+                        payloads: vec![vec![], vec![]],
+                    }
+                )
+                .gaps
+                .is_empty()
+        );
+        let gaps = channels.deliver("tls://a:1", frame(10)).gaps;
+        assert_eq!(gaps.len(), 1);
+        assert_eq!((gaps[0].expected_seq, gaps[0].received_seq), (8, 10));
+        assert_eq!(gaps[0].missing(), 2);
+
+        // 乱序到达的旧帧照常投递，不回退序号也不算缺帧。
+        assert!(channels.deliver("tls://a:1", frame(9)).gaps.is_empty());
+        let status = channels.status(id).unwrap();
+        assert_eq!((status.last_seq, status.missed_frames), (10, 2));
+        assert!(channels.status(id + 1).is_none());
+    }
+}
@@ -1,15 +1,28 @@
-//! network｜DI：tcp 事件分发器（Tauri 实现）。
+//! network｜DI：tcp 事件分发器（经共享事件总线投递）。
 //!
 //! 约定：注释中文，日志英文（tracing）。
 
 use std::collections::HashMap;
-use std::sync::{Arc, Mutex};
+use std::sync::atomic::{AtomicU32, Ordering};
+use std::sync::{Arc, Mutex, Weak};
 use std::time::{Duration, Instant};
 
-use tauri::{AppHandle, Emitter};
+use serde::Serialize;
+use tauri::AppHandle;
 
+use crate::features::network::di::connection_channels::{
+    ConnectionChannelEvent, ConnectionChannels, FrameGap,
+};
+use crate::features::network::di::frame_replay;
+use crate::features::network::domain::flood_guard::{
+    FloodGuard, FloodIncident, FloodVerdict, effective_limit,
+};
+use crate::features::network::domain::frame_replay::effective_capacity;
 use crate::features::network::domain::ports::tcp_event_sink::TcpEventSink;
-use crate::features::network::domain::types::{TcpMessageEvent, TcpStateEvent};
+use crate::features::network::domain::types::{
+    TcpFloodEvent, TcpFrameBatchEvent, TcpMessageEvent, TcpStateEvent,
+};
+use crate::shared::events::{EventBus, EventBusExt};
 
 /// 同状态 TCP 生命周期事件的去重窗口。
 ///
@@ -18,20 +31,57 @@ use crate::features::network::domain::types::{TcpMessageEvent, TcpStateEvent};
 /// - 完全重复的状态事件在 200ms 内只投递一次，避免重连抖动时前端闪烁。
 const TCP_STATE_DEDUP_INTERVAL: Duration = Duration::from_millis(200);
 
-/// 基于 Tauri 事件总线的 TCP 事件分发器。
+/// 洪泛期间缓冲帧的批量投递间隔。
+///
+/// 前端按批处理帧，落库也随之按批进行，而不是每帧一次写入。
+const FLOOD_FLUSH_INTERVAL: Duration = Duration::from_millis(500);
+
+/// 单批最多缓冲的帧数；达到后立即投递，避免缓冲无限增长。
+const FLOOD_MAX_BATCH_FRAMES: usize = 1000;
+
+/// 连接空闲超过该时长后移除其洪泛检测状态（断开或不再活跃的 server_socket 不会一直占用）。
+const FLOOD_STATE_IDLE_TTL: Duration = Duration::from_secs(60);
+
+/// 订阅窗口缺帧时投递给该窗口的事件名。
+pub const FRAME_GAP_EVENT: &str = "connection-frame-gap";
+
+/// 单个连接的洪泛检测状态与缓冲帧。
+struct FloodState {
+    guard: FloodGuard,
+    buffer: Vec<Vec<u8>>,
+}
+
+/// 基于共享事件总线的 TCP 事件分发器。
 pub struct TauriTcpEventSink {
-    app: AppHandle,
+    events: Arc<dyn EventBus>,
     /// 每个 server_socket 最近一次发出的状态事件及其时间戳。
     last_state: Mutex<HashMap<String, (TcpStateEvent, Instant)>>,
+    /// 每个 server_socket 的入站洪泛检测状态。
+    flood: Mutex<HashMap<String, FloodState>>,
+    /// 每秒帧数上限（由后台任务按设置刷新）。
+    flood_limit: AtomicU32,
 }
 
 impl TauriTcpEventSink {
     /// 创建共享事件分发器实例。
+    ///
+    /// 同时启动后台任务：刷新洪泛上限、批量投递洪泛期间缓冲的帧、检测洪泛结束；
+    /// 分发器被释放后任务自动退出。
     pub fn shared(app: AppHandle) -> Arc<dyn TcpEventSink> {
-        Arc::new(Self {
-            app,
+        Self::with_event_bus(Arc::new(app))
+    }
+
+    /// 基于任意事件总线创建分发器（测试与无界面执行时使用）。
+    pub fn with_event_bus(events: Arc<dyn EventBus>) -> Arc<dyn TcpEventSink> {
+        let sink = Arc::new(Self {
+            events,
             last_state: Mutex::new(HashMap::new()),
-        })
+            flood: Mutex::new(HashMap::new()),
+            flood_limit: AtomicU32::new(effective_limit(0)),
+        });
+        let weak = Arc::downgrade(&sink);
+        tauri::async_runtime::spawn(run_flood_ticker(weak));
+        sink
     }
 
     /// 判断当前状态事件是否需要投递。
@@ -53,6 +103,166 @@ impl TauriTcpEventSink {
             guard.insert(event.server_socket.clone(), (event, now));
         }
     }
+
+    /// 投递某连接的帧类事件：订阅窗口经 Channel 接收，其余窗口经全局事件接收。
+    fn emit_connection_event<S: Serialize + Clone>(
+        &self,
+        event_name: &str,
+        server_socket: &str,
+        event: S,
+        to_channel: impl FnOnce(&S) -> ConnectionChannelEvent,
+    ) {
+        let channels = ConnectionChannels::global();
+        if !channels.has_subscribers(server_socket) {
+            self.events.emit(event_name, event);
+            return;
+        }
+        let delivery = channels.deliver(server_socket, to_channel(&event));
+        for gap in delivery.gaps {
+            self.handle_gap(gap);
+        }
+        if delivery.delivered.is_empty() {
+            self.events.emit(event_name, event);
+            return;
+        }
+        self.events
+            .emit_except(&delivery.delivered, event_name, event);
+    }
+
+    /// 处理订阅窗口的缺帧：通知该窗口，并为该连接的聚焦频道安排一次定向补齐。
+    ///
+    /// 缺失的帧不补投：帧须按序解密，补投只会打乱后续帧的解密状态。
+    /// 没有聚焦频道（`resyncChannelId` 为 null）时由收到事件的窗口自行补拉当前频道。
+    fn handle_gap(&self, gap: FrameGap) {
+        tracing::warn!(
+            action = "network_frame_gap_detected",
+            server_socket = %gap.server_socket,
+            window = %gap.window_label,
+            expected_seq = gap.expected_seq,
+            received_seq = gap.received_seq,
+            missing = gap.missing()
+        );
+        let resync_channel =
+            crate::features::network::di::prefetch::request_resync(&gap.server_socket);
+        self.events.emit_to(
+            &gap.window_label,
+            FRAME_GAP_EVENT,
+            serde_json::json!({
+                "serverSocket": gap.server_socket,
+                "subscriptionId": gap.subscription_id,
+                "expectedSeq": gap.expected_seq,
+                "receivedSeq": gap.received_seq,
+                "resyncChannelId": resync_channel,
+            }),
+        );
+    }
+
+    fn emit_flood(&self, event: TcpFloodEvent) {
+        self.events.emit("flood-detected", event);
+    }
+
+    fn emit_frame_batch(&self, server_socket: &str, payloads: Vec<Vec<u8>>) {
+        if payloads.is_empty() {
+            return;
+        }
+        // 持锁连续分配序号，保证批内序号连续。
+        let first_seq = {
+            let mut replay = frame_replay::global();
+            let seqs: Vec<u64> = payloads
+                .iter()
+                .map(|payload| replay.push(server_socket, payload.clone()))
+                .collect();
+            seqs[0]
+        };
+        let event = TcpFrameBatchEvent {
+            server_socket: server_socket.to_string(),
+            payloads,
+        };
+        self.emit_connection_event("tcp-frame-batch", server_socket, event, |e| {
+            ConnectionChannelEvent::FrameBatch {
+                first_seq,
+                payloads: e.payloads.clone(),
+            }
+        });
+    }
+
+    /// 投递所有缓冲帧，结束已平息的洪泛（写入审计日志），并移除空闲连接的检测状态。
+    fn flush_floods(&self, now: Instant) {
+        let limit = self.flood_limit.load(Ordering::Relaxed);
+        let mut ended = Vec::new();
+        {
+            // 持锁投递缓冲帧：洪泛结束后的下一帧必须排在这批帧之后。
+            let mut guard = self.flood.lock().unwrap_or_else(|e| e.into_inner());
+            for (server_socket, state) in guard.iter_mut() {
+                state.guard.set_limit(limit);
+                self.emit_frame_batch(server_socket, std::mem::take(&mut state.buffer));
+                if let Some(incident) = state.guard.poll_end(now) {
+                    ended.push((server_socket.clone(), incident));
+                }
+            }
+            guard.retain(|_, state| !state.guard.is_idle(now, FLOOD_STATE_IDLE_TTL));
+        }
+        for (server_socket, incident) in ended {
+            self.finish_incident(&server_socket, limit, incident, now);
+        }
+    }
+
+    fn finish_incident(
+        &self,
+        server_socket: &str,
+        limit: u32,
+        incident: FloodIncident,
+        now: Instant,
+    ) {
+        let duration_ms = now.duration_since(incident.started_at).as_millis() as u64;
+        tracing::warn!(
+            action = "network_tcp_inbound_flood_ended",
+            server_socket,
+            frames = incident.frames,
+            peak_rate = incident.peak_rate,
+            duration_ms
+        );
+        crate::shared::audit_log::record_in_background(
+            crate::shared::audit_log::KIND_INBOUND_FLOOD,
+            server_socket.to_string(),
+            serde_json::json!({
+                "limit": limit,
+                "frames": incident.frames,
+                "peakRate": incident.peak_rate,
+                "durationMs": duration_ms,
+            }),
+        );
+        self.emit_flood(TcpFloodEvent {
+            server_socket: server_socket.to_string(),
+            active: false,
+            limit,
+            peak_rate: incident.peak_rate,
+            frames: incident.frames,
+            duration_ms,
+        });
+    }
+}
+
+/// 洪泛后台任务：按间隔刷新上限（含重放缓冲容量）并批量投递缓冲帧。
+async fn run_flood_ticker(sink: Weak<TauriTcpEventSink>) {
+    loop {
+        tokio::time::sleep(FLOOD_FLUSH_INTERVAL).await;
+        let Some(sink) = sink.upgrade() else {
+            break;
+        };
+        let configured = crate::features::settings::get_config_value::<u32>(String::from(
+            "inbound_flood_max_per_sec",
+        ))
+        .await;
+        sink.flood_limit
+            .store(effective_limit(configured), Ordering::Relaxed);
+        let replay_frames = crate::features::settings::get_config_value::<u32>(String::from(
+            "frame_replay_buffer_frames",
+        ))
+        .await;
+        frame_replay::global().set_capacity(effective_capacity(replay_frames));
+        sink.flush_floods(Instant::now());
+    }
 }
 
 impl TcpEventSink for TauriTcpEventSink {
@@ -62,20 +272,76 @@ impl TcpEventSink for TauriTcpEventSink {
             return;
         }
         self.record_state(event.clone(), now);
-        if let Err(e) = self.app.emit("tcp-state", event) {
-            tracing::warn!(action = "network_tcp_emit_state_failed", error = ?e);
-        }
+        self.events.emit("tcp-state", event);
     }
 
     fn emit_message(&self, event: TcpMessageEvent) {
-        if let Err(e) = self.app.emit("tcp-message", event) {
-            tracing::warn!(action = "network_tcp_emit_message_failed", error = ?e);
-        }
+        let server_socket = event.server_socket.clone();
+        self.emit_connection_event("tcp-message", &server_socket, event, |e| {
+            ConnectionChannelEvent::Message {
+                payload: e.payload.clone(),
+            }
+        });
     }
 
     fn emit_frame(&self, event: TcpMessageEvent) {
-        if let Err(e) = self.app.emit("tcp-frame", event) {
-            tracing::warn!(action = "network_tcp_emit_frame_failed", error = ?e);
+        let now = Instant::now();
+        let limit = self.flood_limit.load(Ordering::Relaxed);
+        let TcpMessageEvent {
+            server_socket,
+            payload,
+        } = event;
+        let mut payload = Some(payload);
+        let verdict = {
+            let mut guard = self.flood.lock().unwrap_or_else(|e| e.into_inner());
+            let state = guard
+                .entry(server_socket.clone())
+                .or_insert_with(|| FloodState {
+                    guard: FloodGuard::new(limit, now),
+                    buffer: Vec::new(),
+                });
+            let verdict = state.guard.observe(now);
+            if verdict != FloodVerdict::Pass {
+                state.buffer.extend(payload.take());
+                if state.buffer.len() >= FLOOD_MAX_BATCH_FRAMES {
+                    self.emit_frame_batch(&server_socket, std::mem::take(&mut state.buffer));
+                }
+            }
+            verdict
+        };
+
+        match verdict {
+            FloodVerdict::Pass => {
+                let payload = payload.unwrap_or_default();
+                let seq = frame_replay::global().push(&server_socket, payload.clone());
+                let event = TcpMessageEvent {
+                    server_socket: server_socket.clone(),
+                    payload,
+                };
+                self.emit_connection_event("tcp-frame", &server_socket, event, |e| {
+                    ConnectionChannelEvent::Frame {
+                        seq,
+                        payload: e.payload.clone(),
+                    }
+                });
+            }
+            FloodVerdict::Started { rate } => {
+                tracing::warn!(
+                    action = "network_tcp_inbound_flood_detected",
+                    server_socket = %server_socket,
+                    rate,
+                    limit
+                );
+                self.emit_flood(TcpFloodEvent {
+                    server_socket: server_socket.clone(),
+                    active: true,
+                    limit,
+                    peak_rate: rate,
+                    frames: 0,
+                    duration_ms: 0,
+                });
+            }
+            FloodVerdict::Throttled => {}
         }
     }
 }
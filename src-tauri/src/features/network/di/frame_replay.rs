@@ -0,0 +1,44 @@
+//! network｜DI：最近入站帧重放（新打开窗口补齐订阅前错过的帧）。
+//!
+//! 说明：
+//! - 分发器（`TauriTcpEventSink`）投递每一帧（含洪泛期间的批量帧）前记录到进程级缓冲；
+//! - 新窗口应先 `subscribe_connection`，再调用 `replay_recent_frames` 补齐，两者之间到达的帧
+//!   可能重复投递，前端按消息 id 去重；
+//! - 缓冲容量由设置项 `frame_replay_buffer_frames` 控制（0 = 默认值），随洪泛上限一并刷新。
+//!
+//! 约定：注释中文，日志英文（tracing）。
+
+use std::sync::{Mutex, MutexGuard, OnceLock};
+
+use crate::features::network::domain::frame_replay::{FrameReplay, FrameReplayBuffer};
+use crate::shared::error::CommandResult;
+
+/// 进程级重放缓冲。
+pub fn global() -> MutexGuard<'static, FrameReplayBuffer> {
+    static BUFFER: OnceLock<Mutex<FrameReplayBuffer>> = OnceLock::new();
+    BUFFER
+        .get_or_init(|| Mutex::new(FrameReplayBuffer::default()))
+        .lock()
+        .unwrap_or_else(|p| p.into_inner())
+}
+
+/// 取回某连接最近的入站帧。
+///
+/// # 参数
+/// - `server_socket`：连接的 server_socket。
+/// - `since_seq`：只返回序号大于该值的帧；`0` 表示缓冲中的全部帧。
+///
+/// # 返回值
+/// - `Ok(FrameReplay)`：帧列表、最新序号，以及请求范围是否完整（不完整时应从数据库补齐）。
+#[tauri::command]
+pub fn replay_recent_frames(server_socket: String, since_seq: u64) -> CommandResult<FrameReplay> {
+    let replay = global().since(server_socket.trim(), since_seq);
+    tracing::debug!(
+        action = "network_frame_replay_served",
+        server_socket = %server_socket,
+        since_seq,
+        frames = replay.frames.len(),
+        complete = replay.complete
+    );
+    Ok(replay)
+}
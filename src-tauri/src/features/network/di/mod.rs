@@ -5,6 +5,72 @@
 //! 约定：注释中文，日志英文（tracing）。
 
 pub mod commands;
+pub mod connection_channels;
 pub mod event_sink;
+pub mod frame_replay;
 pub mod models;
+pub mod prefetch;
+pub mod presence;
 pub mod tcp_backend_factory;
+pub mod typing;
+
+use crate::app::registry::{CommandRegistration, CommandRegistry, command_set};
+
+/// 网络（TCP/HTTP/链接预览）命令注册。
+pub struct NetworkCommands;
+
+impl CommandRegistration for NetworkCommands {
+    fn register(registry: &mut CommandRegistry) {
+        registry.add(command_set!(crate::features::network::di::commands => [
+            send_tcp_service,
+            tcp_connection_status,
+            tcp_encode_payload,
+            tcp_decode_payload,
+            start_frame_capture,
+            stop_frame_capture,
+            export_frame_capture,
+            add_tcp_service,
+            remove_tcp_service,
+            get_server_certificate_info,
+            probe_server,
+            get_server_probes,
+            get_server_capabilities,
+            api_request_json,
+            set_session_tokens,
+            refresh_session_tokens,
+            login_oauth,
+            download_file,
+        ]));
+        registry.add(
+            command_set!(crate::features::network::di::connection_channels => [
+                subscribe_connection,
+                unsubscribe_connection,
+                connection_subscription_status,
+            ]),
+        );
+        registry.add(command_set!(crate::features::network::di::frame_replay => [
+            replay_recent_frames,
+        ]));
+        registry.add(command_set!(crate::features::network::di::prefetch => [
+            set_active_channel,
+            enqueue_channel_prefetch,
+            complete_channel_prefetch,
+            clear_channel_prefetch,
+        ]));
+        registry.add(command_set!(crate::features::network::di::presence => [
+            set_presence_watch,
+            resubscribe_presence,
+            handle_presence_update,
+            get_presence,
+        ]));
+        registry.add(command_set!(crate::features::network::di::typing => [
+            notify_typing,
+            reset_typing,
+            handle_typing_event,
+            list_typing_users,
+        ]));
+        registry.add(command_set!(crate::features::network::link_preview => [
+            fetch_link_preview,
+        ]));
+    }
+}
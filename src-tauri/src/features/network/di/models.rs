@@ -2,6 +2,8 @@
 //!
 //! 约定：注释中文，日志英文（tracing）。
 
+use crate::features::network::domain::ports::session_token_port::SessionTokens;
+
 /// `/api/*` JSON 请求参数（前端 -> Rust 命令边界）。
 #[derive(Debug, Clone, serde::Deserialize)]
 #[serde(rename_all = "camelCase")]
@@ -35,3 +37,28 @@ pub struct ApiRequestJsonResult {
     /// 错误响应体（JSON）。
     pub error: Option<serde_json::Value>,
 }
+
+/// `session-refreshed` 事件载荷（Rust 侧刷新后同步给前端）。
+#[derive(Debug, Clone, serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SessionRefreshedPayload {
+    pub server_socket: String,
+    pub tokens: SessionTokens,
+}
+
+/// `session-expired` 事件载荷。
+#[derive(Debug, Clone, serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SessionExpiredPayload {
+    pub server_socket: String,
+}
+
+/// `login_oauth` 结果（令牌同时已写入系统凭据存储）。
+#[derive(Debug, Clone, serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct OAuthLoginResult {
+    pub uid: Option<String>,
+    pub is_new_user: bool,
+    /// 换取到的令牌，供前端写入本地 session（与邮箱验证码登录一致）。
+    pub tokens: SessionTokens,
+}
@@ -0,0 +1,204 @@
+//! network｜DI：频道预取调度命令与后台任务。
+//!
+//! 说明：
+//! - 调度状态见 [`PrefetchScheduler`]；[`PrefetchRuntime`] 经事件总线把票据通过
+//!   `channel-prefetch-ready` 事件发给前端；这里持有其全局实例，并定时发放（空闲后的冷频道、
+//!   超时释放的名额）；
+//! - 每次命令调用后立即尝试发放，聚焦频道不需要等待下一次定时。
+//!
+//! 约定：注释中文，日志英文（tracing）。
+
+use std::sync::{Arc, Mutex, OnceLock};
+use std::time::{Duration, Instant};
+
+use tauri::AppHandle;
+
+use crate::features::network::domain::prefetch_scheduler::{PrefetchPriority, PrefetchScheduler};
+use crate::shared::error::CommandResult;
+use crate::shared::events::{EventBus, EventBusExt};
+
+/// 票据发放事件名。
+pub const PREFETCH_READY_EVENT: &str = "channel-prefetch-ready";
+
+/// 后台发放间隔（同时刷新并发上限配置）。
+const PREFETCH_TICK_INTERVAL: Duration = Duration::from_secs(1);
+
+/// 预取运行时：持有调度器并经事件总线发放票据（不依赖 Tauri，可直接用于测试）。
+pub struct PrefetchRuntime {
+    events: Arc<dyn EventBus>,
+    scheduler: Mutex<PrefetchScheduler>,
+}
+
+impl PrefetchRuntime {
+    pub fn new(events: Arc<dyn EventBus>) -> Self {
+        Self {
+            events,
+            scheduler: Mutex::new(PrefetchScheduler::new(0)),
+        }
+    }
+
+    fn with_scheduler<R>(&self, f: impl FnOnce(&mut PrefetchScheduler) -> R) -> R {
+        let mut scheduler = self.scheduler.lock().unwrap_or_else(|p| p.into_inner());
+        f(&mut scheduler)
+    }
+
+    /// 发放当前可发放的票据。
+    fn pump(&self) {
+        let tickets = self.with_scheduler(|s| s.dispatch(Instant::now()));
+        for ticket in tickets {
+            tracing::debug!(
+                action = "network_prefetch_ticket_issued",
+                server_socket = %ticket.server_socket,
+                channel_id = %ticket.channel_id,
+                priority = ?ticket.priority,
+                ticket = ticket.ticket
+            );
+            self.events.emit(PREFETCH_READY_EVENT, ticket);
+        }
+    }
+
+    /// 设置某 server 的聚焦频道（`None` 表示离开频道视图）并立即发放。
+    pub fn set_active(&self, server_socket: &str, channel_id: Option<&str>) {
+        self.with_scheduler(|s| s.set_active(server_socket, channel_id, Instant::now()));
+        self.pump();
+    }
+
+    /// 将频道加入预取队列并立即发放。
+    pub fn enqueue(&self, server_socket: &str, channel_ids: &[String], priority: PrefetchPriority) {
+        self.with_scheduler(|s| s.enqueue(server_socket, channel_ids, priority));
+        self.pump();
+    }
+
+    /// 归还票据并发放空出的名额。
+    ///
+    /// # 返回值
+    /// 票据是否仍在途（已超时释放或重复归还时为 `false`）。
+    pub fn complete(&self, ticket: u64) -> bool {
+        let released = self.with_scheduler(|s| s.complete(ticket));
+        self.pump();
+        released
+    }
+
+    /// 为聚焦频道重新发放票据；该 server 没有聚焦频道时返回 `None`。
+    pub fn resync(&self, server_socket: &str) -> Option<String> {
+        let channel_id = self.with_scheduler(|s| {
+            let channel_id = s.active_channel(server_socket)?;
+            s.enqueue(
+                server_socket,
+                std::slice::from_ref(&channel_id),
+                PrefetchPriority::Warm,
+            );
+            Some(channel_id)
+        })?;
+        self.pump();
+        Some(channel_id)
+    }
+
+    /// 刷新并发上限并发放（后台任务按间隔调用）。
+    pub fn tick(&self, configured_max_concurrent: u32) {
+        self.with_scheduler(|s| s.set_max_concurrent(configured_max_concurrent));
+        self.pump();
+    }
+
+    /// 清空某个 server 的预取队列。
+    pub fn clear_server(&self, server_socket: &str) {
+        self.with_scheduler(|s| s.clear_server(server_socket));
+    }
+}
+
+static RUNTIME: OnceLock<PrefetchRuntime> = OnceLock::new();
+
+fn runtime(app: &AppHandle) -> &'static PrefetchRuntime {
+    RUNTIME.get_or_init(|| {
+        tauri::async_runtime::spawn(run_prefetch_ticker());
+        PrefetchRuntime::new(Arc::new(app.clone()))
+    })
+}
+
+/// 预取后台任务：刷新并发上限并按间隔发放票据。
+async fn run_prefetch_ticker() {
+    loop {
+        tokio::time::sleep(PREFETCH_TICK_INTERVAL).await;
+        let configured = crate::features::settings::get_config_value::<u32>(String::from(
+            "prefetch_max_concurrent_per_server",
+        ))
+        .await;
+        if let Some(runtime) = RUNTIME.get() {
+            runtime.tick(configured);
+        }
+    }
+}
+
+/// 为某 server 的聚焦频道重新发放票据（帧流缺帧后定向补齐）。
+///
+/// # 返回值
+/// 安排补齐的频道 id；调度器尚未启动或该 server 没有聚焦频道时为 `None`。
+pub fn request_resync(server_socket: &str) -> Option<String> {
+    let channel_id = RUNTIME.get()?.resync(server_socket)?;
+    tracing::info!(
+        action = "network_prefetch_resync_requested",
+        server_socket = %server_socket,
+        channel_id = %channel_id
+    );
+    Some(channel_id)
+}
+
+#[tauri::command]
+/// 上报前端当前聚焦的频道。
+///
+/// # 参数
+/// - `server_socket`：服务器 socket。
+/// - `channel_id`：聚焦的频道 id；为空表示离开该 server 的频道视图。
+///
+/// # 说明
+/// 聚焦频道会立即以最高优先级发放票据（`channel-prefetch-ready`）。
+pub async fn set_active_channel(
+    app: AppHandle,
+    server_socket: String,
+    channel_id: Option<String>,
+) -> CommandResult<()> {
+    let channel_id = channel_id
+        .as_deref()
+        .map(str::trim)
+        .filter(|c| !c.is_empty());
+    runtime(&app).set_active(server_socket.trim(), channel_id);
+    Ok(())
+}
+
+#[tauri::command]
+/// 将频道加入预取队列。
+///
+/// # 参数
+/// - `server_socket`：服务器 socket。
+/// - `channel_ids`：频道 id 列表。
+/// - `priority`：`warm`（如有未读）或 `cold`（默认）；聚焦优先级只由 `set_active_channel` 决定。
+pub async fn enqueue_channel_prefetch(
+    app: AppHandle,
+    server_socket: String,
+    channel_ids: Vec<String>,
+    priority: Option<PrefetchPriority>,
+) -> CommandResult<()> {
+    runtime(&app).enqueue(
+        server_socket.trim(),
+        &channel_ids,
+        priority.unwrap_or(PrefetchPriority::Cold),
+    );
+    Ok(())
+}
+
+#[tauri::command]
+/// 归还预取票据（历史请求完成或失败后调用）。
+///
+/// # 返回值
+/// - `Ok(true)`：票据已归还；
+/// - `Ok(false)`：票据已超时释放或重复归还。
+pub async fn complete_channel_prefetch(app: AppHandle, ticket: u64) -> CommandResult<bool> {
+    Ok(runtime(&app).complete(ticket))
+}
+
+#[tauri::command]
+/// 清空某个 server 的预取队列（断开或切换 server 时调用）。
+pub async fn clear_channel_prefetch(app: AppHandle, server_socket: String) -> CommandResult<()> {
+    runtime(&app).clear_server(server_socket.trim());
+    Ok(())
+}
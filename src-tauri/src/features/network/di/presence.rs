@@ -0,0 +1,204 @@
+//! network｜DI：在线状态订阅命令与后台合批任务。
+//!
+//! 说明：
+//! - 去重、合批与缓存规则见 [`PresenceManager`]；[`PresenceRuntime`] 经事件总线投递，
+//!   这里持有其全局实例；
+//! - 后台任务按间隔取出待发批次，经 `presence-subscription-batch` 事件交给主窗口加密发送
+//!   （载荷自带路由名，前端原样作为一帧 `{ route, data: { uids } }` 下发）；只发给主窗口，
+//!   避免多个窗口重复发帧；
+//! - 服务端推送经 `handle_presence_update` 写入缓存，状态变化时合并为一次 `presence-changed` 广播。
+//!
+//! 约定：注释中文，日志英文（tracing）。
+
+use std::sync::{Arc, Mutex, OnceLock};
+use std::time::{Duration, Instant};
+
+use serde::Serialize;
+use tauri::AppHandle;
+
+use crate::features::network::domain::presence::{PresenceEntry, PresenceManager};
+use crate::shared::error::CommandResult;
+use crate::shared::events::{EventBus, EventBusExt};
+
+/// 待发订阅/退订批次的事件名。
+pub const PRESENCE_BATCH_EVENT: &str = "presence-subscription-batch";
+/// 在线状态变化的事件名。
+pub const PRESENCE_CHANGED_EVENT: &str = "presence-changed";
+
+/// 合批间隔：同一间隔内的订阅变化合并为最少的帧。
+const PRESENCE_TICK_INTERVAL: Duration = Duration::from_millis(300);
+
+/// `presence-changed` 事件载荷。
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PresenceChangedPayload {
+    pub server_socket: String,
+    pub presences: Vec<PresenceEntry>,
+}
+
+/// 在线状态运行时：持有订阅与缓存并经事件总线投递批次与变化（不依赖 Tauri，可直接用于测试）。
+pub struct PresenceRuntime {
+    events: Arc<dyn EventBus>,
+    manager: Mutex<PresenceManager>,
+}
+
+impl PresenceRuntime {
+    pub fn new(events: Arc<dyn EventBus>) -> Self {
+        Self {
+            events,
+            manager: Mutex::new(PresenceManager::default()),
+        }
+    }
+
+    fn with_manager<R>(&self, f: impl FnOnce(&mut PresenceManager) -> R) -> R {
+        let mut manager = self.manager.lock().unwrap_or_else(|p| p.into_inner());
+        f(&mut manager)
+    }
+
+    /// 发出所有待发批次。
+    fn flush(&self) {
+        let batches = self.with_manager(|m| m.drain_batches());
+        for batch in batches {
+            tracing::debug!(
+                action = "network_presence_batch_ready",
+                server_socket = %batch.server_socket,
+                route = batch.route,
+                users = batch.user_ids.len()
+            );
+            self.events.emit_to("main", PRESENCE_BATCH_EVENT, batch);
+        }
+    }
+
+    /// 替换某视图需要在线状态的用户（变化在下一次 `tick` 时合批下发）。
+    pub fn set_watch(&self, server_socket: &str, watcher_id: &str, user_ids: &[String]) {
+        self.with_manager(|m| m.set_watch(server_socket, watcher_id, user_ids));
+    }
+
+    /// 重新订阅某 server 当前需要的全部用户。
+    pub fn resubscribe(&self, server_socket: &str) {
+        self.with_manager(|m| m.resubscribe(server_socket));
+    }
+
+    /// 写入服务端推送的在线状态；与缓存相比发生变化的条目合并为一次 `presence-changed` 广播。
+    pub fn observe(&self, server_socket: &str, presences: Vec<PresenceEntry>) {
+        let now = Instant::now();
+        let changed: Vec<PresenceEntry> = self.with_manager(|m| {
+            presences
+                .into_iter()
+                .filter(|entry| !entry.user_id.trim().is_empty())
+                .filter_map(|entry| m.observe(server_socket, entry, now))
+                .collect()
+        });
+        if changed.is_empty() {
+            return;
+        }
+        tracing::debug!(
+            action = "network_presence_changed",
+            server_socket = %server_socket,
+            users = changed.len()
+        );
+        self.events.emit(
+            PRESENCE_CHANGED_EVENT,
+            PresenceChangedPayload {
+                server_socket: server_socket.to_string(),
+                presences: changed,
+            },
+        );
+    }
+
+    /// 读取缓存中的在线状态（不触发订阅）。
+    pub fn get(&self, server_socket: &str, user_ids: &[String]) -> Vec<PresenceEntry> {
+        self.with_manager(|m| m.get(server_socket, user_ids, Instant::now()))
+    }
+
+    /// 淘汰过期缓存并下发待发批次（后台任务按间隔调用）。
+    pub fn tick(&self) {
+        self.with_manager(|m| m.expire(Instant::now()));
+        self.flush();
+    }
+
+    /// 清空某 server 的订阅与缓存。
+    pub fn clear_server(&self, server_socket: &str) {
+        self.with_manager(|m| m.clear_server(server_socket));
+    }
+}
+
+static RUNTIME: OnceLock<PresenceRuntime> = OnceLock::new();
+
+fn runtime(app: &AppHandle) -> &'static PresenceRuntime {
+    RUNTIME.get_or_init(|| {
+        tauri::async_runtime::spawn(run_presence_ticker());
+        PresenceRuntime::new(Arc::new(app.clone()))
+    })
+}
+
+/// 后台任务：合批下发订阅变化并淘汰过期缓存。
+async fn run_presence_ticker() {
+    loop {
+        tokio::time::sleep(PRESENCE_TICK_INTERVAL).await;
+        if let Some(runtime) = RUNTIME.get() {
+            runtime.tick();
+        }
+    }
+}
+
+/// 清空某 server 的订阅与缓存（连接移除时调用）。
+pub fn clear_server(server_socket: &str) {
+    if let Some(runtime) = RUNTIME.get() {
+        runtime.clear_server(server_socket);
+    }
+}
+
+#[tauri::command]
+/// 上报某视图当前可见、需要在线状态的用户（替换该视图上一次的集合）。
+///
+/// # 参数
+/// - `server_socket`：服务器 socket。
+/// - `watcher_id`：视图标识（如 `members:<cid>`），同一视图重复调用时覆盖。
+/// - `user_ids`：可见用户；空列表表示该视图不再需要在线状态。
+///
+/// # 说明
+/// 订阅变化不会立即发帧，而是在下一个合批周期经 `presence-subscription-batch` 下发。
+pub async fn set_presence_watch(
+    app: AppHandle,
+    server_socket: String,
+    watcher_id: String,
+    user_ids: Vec<String>,
+) -> CommandResult<()> {
+    runtime(&app).set_watch(server_socket.trim(), watcher_id.trim(), &user_ids);
+    Ok(())
+}
+
+#[tauri::command]
+/// 重新订阅某 server 当前需要的全部用户（连接重建后调用）。
+pub async fn resubscribe_presence(app: AppHandle, server_socket: String) -> CommandResult<()> {
+    runtime(&app).resubscribe(server_socket.trim());
+    Ok(())
+}
+
+#[tauri::command]
+/// 处理服务端推送的在线状态（前端解密后转交，可一次多条）。
+///
+/// # 说明
+/// 与缓存相比发生变化的条目合并为一次 `presence-changed` 广播。
+pub async fn handle_presence_update(
+    app: AppHandle,
+    server_socket: String,
+    presences: Vec<PresenceEntry>,
+) -> CommandResult<()> {
+    runtime(&app).observe(server_socket.trim(), presences);
+    Ok(())
+}
+
+#[tauri::command]
+/// 读取缓存中的在线状态（不触发订阅）。
+///
+/// # 返回值
+/// 已知的条目；未知或已过期的用户不出现在结果中，调用方按“未知”展示。
+pub async fn get_presence(
+    app: AppHandle,
+    server_socket: String,
+    user_ids: Vec<String>,
+) -> CommandResult<Vec<PresenceEntry>> {
+    Ok(runtime(&app).get(server_socket.trim(), &user_ids))
+}
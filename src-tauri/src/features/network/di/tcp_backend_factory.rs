@@ -5,12 +5,17 @@
 use std::sync::Arc;
 
 use crate::features::network::data::tcp_real::TcpServiceReal;
+use crate::features::network::domain::codec::PayloadCodec;
 use crate::features::network::domain::ports::tcp_backend_factory_port::{
     TcpBackendFactoryFuture, TcpBackendFactoryPort,
 };
 use crate::features::network::domain::ports::tcp_backend_port::{TcpBackendFuture, TcpBackendPort};
 use crate::features::network::domain::ports::tcp_event_sink::TcpEventSink;
 #[cfg(debug_assertions)]
+use crate::features::network::mock::demo_backend::DemoTcpService;
+#[cfg(debug_assertions)]
+use crate::features::network::mock::demo_fixture::{load_demo_fixture, parse_fixture_socket};
+#[cfg(debug_assertions)]
 use crate::features::network::mock::tcp_mock::{MockTcpMode, MockTcpService};
 
 struct RealTcpBackend {
@@ -44,6 +49,14 @@ impl TcpBackendPort for RealTcpBackend {
     fn is_listening(&self) -> bool {
         self.inner.is_listening()
     }
+
+    fn codec(&self) -> PayloadCodec {
+        self.inner.codec()
+    }
+
+    fn compression_active(&self) -> bool {
+        self.inner.compression_active()
+    }
 }
 
 #[cfg(debug_assertions)]
@@ -85,6 +98,36 @@ impl TcpBackendPort for MockTcpBackend {
     }
 }
 
+#[cfg(debug_assertions)]
+struct DemoTcpBackend {
+    inner: DemoTcpService,
+}
+
+#[cfg(debug_assertions)]
+impl TcpBackendPort for DemoTcpBackend {
+    fn start(
+        &mut self,
+        event_sink: Arc<dyn TcpEventSink>,
+        server_socket: String,
+        session_id: u64,
+    ) -> bool {
+        self.inner.start(event_sink, server_socket, session_id);
+        true
+    }
+
+    fn send<'a>(&'a mut self, data: Vec<u8>) -> TcpBackendFuture<'a, ()> {
+        Box::pin(async move { self.inner.send(data).await })
+    }
+
+    fn close<'a>(&'a mut self) -> TcpBackendFuture<'a, ()> {
+        Box::pin(async move { self.inner.close().await })
+    }
+
+    fn is_listening(&self) -> bool {
+        true
+    }
+}
+
 #[cfg(debug_assertions)]
 fn parse_mock_mode(socket: &str) -> MockTcpMode {
     if socket.starts_with("mock://handshake") {
@@ -114,6 +157,14 @@ impl TcpBackendFactoryPort for DefaultTcpBackendFactory {
             if socket.starts_with("mock://") {
                 #[cfg(debug_assertions)]
                 {
+                    if let Some(name) = parse_fixture_socket(&socket) {
+                        let fixture = load_demo_fixture(name).await?;
+                        tracing::info!(action = "network_mock_demo_fixture_loaded", fixture = %name, channels = fixture.channels.len(), "Demo fixture loaded");
+                        let backend: Box<dyn TcpBackendPort> = Box::new(DemoTcpBackend {
+                            inner: DemoTcpService::new(fixture),
+                        });
+                        return Ok(backend);
+                    }
                     let backend: Box<dyn TcpBackendPort> =
                         Box::new(MockTcpBackend::new(parse_mock_mode(&socket)));
                     return Ok(backend);
@@ -129,6 +180,18 @@ impl TcpBackendFactoryPort for DefaultTcpBackendFactory {
 
             match TcpServiceReal::connect(socket.clone()).await {
                 Ok(real) => {
+                    let compression = crate::features::settings::get_config_value::<bool>(
+                        String::from("frame_compression"),
+                    )
+                    .await;
+                    let legacy_message_events =
+                        crate::features::settings::get_config_value::<bool>(String::from(
+                            "tcp_legacy_message_events",
+                        ))
+                        .await;
+                    let real = real
+                        .with_frame_compression(compression)
+                        .with_legacy_message_events(legacy_message_events);
                     let backend: Box<dyn TcpBackendPort> = Box::new(RealTcpBackend::new(real));
                     Ok(backend)
                 }
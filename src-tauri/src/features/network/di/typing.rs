@@ -0,0 +1,205 @@
+//! network｜DI：正在输入提示命令与后台过期任务。
+//!
+//! 说明：
+//! - 节流与过期规则见 [`TypingTracker`]；[`TypingRuntime`] 经事件总线广播某频道合并后的
+//!   输入者列表（`typing-state-changed`），这里持有其全局实例；
+//! - 后台任务按间隔移除过期输入者，同时刷新节流间隔设置（`typing_throttle_secs`）；
+//! - 服务端未声明 typing 能力时（见 `capabilities_store`）不放行出站 typing 帧。
+//!
+//! 约定：注释中文，日志英文（tracing）。
+
+use std::sync::{Arc, Mutex, OnceLock};
+use std::time::{Duration, Instant};
+
+use tauri::AppHandle;
+
+use crate::features::network::data::capabilities_store;
+use crate::features::network::domain::capabilities::ProtocolFeature;
+use crate::features::network::usecases::typing_usecases::{
+    TypingState, TypingTracker, effective_throttle,
+};
+use crate::shared::error::CommandResult;
+use crate::shared::events::{EventBus, EventBusExt};
+
+/// 输入者集合变化时广播的事件名。
+pub const TYPING_STATE_CHANGED_EVENT: &str = "typing-state-changed";
+
+/// 后台过期检查间隔。
+const TYPING_TICK_INTERVAL: Duration = Duration::from_secs(1);
+
+/// 输入状态运行时：持有输入者集合并经事件总线广播变化（不依赖 Tauri，可直接用于测试）。
+pub struct TypingRuntime {
+    events: Arc<dyn EventBus>,
+    tracker: Mutex<TypingTracker>,
+}
+
+impl TypingRuntime {
+    pub fn new(events: Arc<dyn EventBus>) -> Self {
+        Self {
+            events,
+            tracker: Mutex::new(TypingTracker::default()),
+        }
+    }
+
+    fn with_tracker<R>(&self, f: impl FnOnce(&mut TypingTracker) -> R) -> R {
+        let mut tracker = self.tracker.lock().unwrap_or_else(|p| p.into_inner());
+        f(&mut tracker)
+    }
+
+    fn broadcast(&self, states: Vec<TypingState>) {
+        for state in states {
+            tracing::debug!(
+                action = "network_typing_state_changed",
+                server_socket = %state.server_socket,
+                channel_id = %state.channel_id,
+                typing = state.user_ids.len()
+            );
+            self.events.emit(TYPING_STATE_CHANGED_EVENT, state);
+        }
+    }
+
+    /// 本地用户正在输入：是否应立即发送一帧 typing。
+    pub fn should_send(&self, server_socket: &str, channel_id: &str) -> bool {
+        self.with_tracker(|t| t.should_send(server_socket, channel_id, Instant::now()))
+    }
+
+    /// 本地用户已发出消息：清除节流记录。
+    pub fn reset_outbound(&self, server_socket: &str, channel_id: &str) {
+        self.with_tracker(|t| t.reset_outbound(server_socket, channel_id));
+    }
+
+    /// 记录一条入站 typing 推送；频道输入者集合变化时广播。
+    pub fn observe(&self, server_socket: &str, channel_id: &str, user_id: &str, typing: bool) {
+        let changed = self.with_tracker(|t| {
+            t.observe(server_socket, channel_id, user_id, typing, Instant::now())
+        });
+        self.broadcast(changed.into_iter().collect());
+    }
+
+    /// 某频道当前的输入者。
+    pub fn users(&self, server_socket: &str, channel_id: &str) -> Vec<String> {
+        self.with_tracker(|t| t.users(server_socket, channel_id))
+    }
+
+    /// 刷新节流间隔设置并移除过期输入者（后台任务按间隔调用）。
+    pub fn tick(&self, configured_throttle_secs: u32) {
+        let expired = self.with_tracker(|t| {
+            t.set_throttle(effective_throttle(configured_throttle_secs));
+            t.expire(Instant::now())
+        });
+        self.broadcast(expired);
+    }
+
+    /// 清空某 server 的输入状态，并广播被清空的频道。
+    pub fn clear_server(&self, server_socket: &str) {
+        let cleared = self.with_tracker(|t| t.clear_server(server_socket));
+        self.broadcast(cleared);
+    }
+}
+
+static RUNTIME: OnceLock<TypingRuntime> = OnceLock::new();
+
+fn runtime(app: &AppHandle) -> &'static TypingRuntime {
+    RUNTIME.get_or_init(|| {
+        tauri::async_runtime::spawn(run_typing_ticker());
+        TypingRuntime::new(Arc::new(app.clone()))
+    })
+}
+
+/// 后台任务：刷新节流间隔并移除过期输入者。
+async fn run_typing_ticker() {
+    loop {
+        tokio::time::sleep(TYPING_TICK_INTERVAL).await;
+        let configured = crate::features::settings::get_config_value::<u32>(String::from(
+            "typing_throttle_secs",
+        ))
+        .await;
+        if let Some(runtime) = RUNTIME.get() {
+            runtime.tick(configured);
+        }
+    }
+}
+
+/// 清空某 server 的输入状态（连接移除时调用），并广播被清空的频道。
+pub fn clear_server(server_socket: &str) {
+    if let Some(runtime) = RUNTIME.get() {
+        runtime.clear_server(server_socket);
+    }
+}
+
+#[tauri::command]
+/// 上报本地用户正在某频道输入（可在每次按键时调用）。
+///
+/// # 参数
+/// - `server_socket`：服务器 socket。
+/// - `channel_id`：频道 id。
+///
+/// # 返回值
+/// - `Ok(true)`：应立即发送一帧 typing（由前端加密发送）；
+/// - `Ok(false)`：仍在节流间隔内，或服务端不支持 typing，本次不发送。
+pub async fn notify_typing(
+    app: AppHandle,
+    server_socket: String,
+    channel_id: String,
+) -> CommandResult<bool> {
+    let server_socket = server_socket.trim();
+    let channel_id = channel_id.trim();
+    if channel_id.is_empty()
+        || !capabilities_store::supports(server_socket, ProtocolFeature::Typing).await
+    {
+        return Ok(false);
+    }
+    Ok(runtime(&app).should_send(server_socket, channel_id))
+}
+
+#[tauri::command]
+/// 本地用户已在某频道发出消息：清除节流记录，下次输入立即放行。
+pub async fn reset_typing(
+    app: AppHandle,
+    server_socket: String,
+    channel_id: String,
+) -> CommandResult<()> {
+    runtime(&app).reset_outbound(server_socket.trim(), channel_id.trim());
+    Ok(())
+}
+
+#[tauri::command]
+/// 处理一条入站 typing 推送（前端解密后转交）。
+///
+/// # 参数
+/// - `server_socket`：服务器 socket。
+/// - `channel_id`：频道 id。
+/// - `user_id`：正在输入的用户 id。
+/// - `typing`：`false` 表示对端停止输入；缺省为 `true`。
+///
+/// # 说明
+/// 频道输入者集合变化时广播 `typing-state-changed`；重复推送只延长有效期。
+pub async fn handle_typing_event(
+    app: AppHandle,
+    server_socket: String,
+    channel_id: String,
+    user_id: String,
+    typing: Option<bool>,
+) -> CommandResult<()> {
+    let (channel_id, user_id) = (channel_id.trim(), user_id.trim());
+    if channel_id.is_empty() || user_id.is_empty() {
+        return Ok(());
+    }
+    runtime(&app).observe(
+        server_socket.trim(),
+        channel_id,
+        user_id,
+        typing.unwrap_or(true),
+    );
+    Ok(())
+}
+
+#[tauri::command]
+/// 读取某频道当前的输入者（新打开的窗口初始化用）。
+pub async fn list_typing_users(
+    app: AppHandle,
+    server_socket: String,
+    channel_id: String,
+) -> CommandResult<Vec<String>> {
+    Ok(runtime(&app).users(server_socket.trim(), channel_id.trim()))
+}
@@ -0,0 +1,189 @@
+//! network｜领域：协议版本与服务端能力协商。
+//!
+//! 说明：
+//! - 服务端在 `/handshake` 推送中声明 `protocol_version` 与 `routes`（路由 -> 版本）；
+//!   `routes` 也可为字符串数组，元素形如 `"channels/typing"` 或 `"channels/typing@2"`；
+//! - 未声明任何能力的旧服务端记为协议版本 [`LEGACY_PROTOCOL_VERSION`]，`advertised = false`：
+//!   此时所有特性按“支持”处理，保持与协商前一致的行为；
+//! - 声明了能力的服务端只放行所需路由版本不低于 [`ProtocolFeature::min_version`] 的特性。
+//!
+//! 约定：注释中文，日志英文（tracing）。
+
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::features::network::domain::codec::handshake_push_data;
+
+/// 未声明协议版本的服务端视为的版本。
+pub const LEGACY_PROTOCOL_VERSION: u32 = 1;
+
+/// 可按服务端能力开关的特性。
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum ProtocolFeature {
+    /// 频道增量同步（`GET /api/channels/{cid}/changes`）。
+    DeltaSync,
+    /// 消息表情回应。
+    Reactions,
+    /// 正在输入提示。
+    Typing,
+}
+
+impl ProtocolFeature {
+    pub const ALL: [Self; 3] = [Self::DeltaSync, Self::Reactions, Self::Typing];
+
+    /// 特性依赖的路由名（与握手 `routes` 中的键一致）。
+    pub fn route(self) -> &'static str {
+        match self {
+            Self::DeltaSync => "channels/changes",
+            Self::Reactions => "messages/reactions",
+            Self::Typing => "channels/typing",
+        }
+    }
+
+    /// 特性要求的最低路由版本。
+    pub fn min_version(self) -> u32 {
+        1
+    }
+}
+
+/// 服务端声明的协议能力。
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ServerCapabilities {
+    pub protocol_version: u32,
+    /// 路由 -> 支持的最高版本。
+    pub routes: BTreeMap<String, u32>,
+    /// 服务端是否在握手中声明了能力（旧服务端为 `false`）。
+    pub advertised: bool,
+}
+
+impl Default for ServerCapabilities {
+    fn default() -> Self {
+        Self {
+            protocol_version: LEGACY_PROTOCOL_VERSION,
+            routes: BTreeMap::new(),
+            advertised: false,
+        }
+    }
+}
+
+/// 规范化路由名：去掉首尾空白与前导 `/`，转小写。
+fn normalize_route(raw: &str) -> Option<String> {
+    let route = raw.trim().trim_start_matches('/').to_ascii_lowercase();
+    (!route.is_empty()).then_some(route)
+}
+
+/// 解析 `routes` 字段（对象或字符串数组）；无法识别的条目忽略。
+fn parse_routes(value: &Value) -> BTreeMap<String, u32> {
+    let version = |v: &Value| v.as_u64().and_then(|v| u32::try_from(v).ok());
+    match value {
+        Value::Object(map) => map
+            .iter()
+            .filter_map(|(route, v)| Some((normalize_route(route)?, version(v)?)))
+            .collect(),
+        Value::Array(items) => items
+            .iter()
+            .filter_map(Value::as_str)
+            .filter_map(|item| {
+                let (route, version) = match item.rsplit_once('@') {
+                    Some((route, version)) => (route, version.trim().parse().ok()?),
+                    None => (item, 1),
+                };
+                Some((normalize_route(route)?, version))
+            })
+            .collect(),
+        _ => BTreeMap::new(),
+    }
+}
+
+impl ServerCapabilities {
+    /// 从握手推送的内层 `data` 中读取能力。
+    pub fn from_handshake_data(data: &Value) -> Self {
+        let protocol_version = data
+            .get("protocol_version")
+            .and_then(Value::as_u64)
+            .and_then(|v| u32::try_from(v).ok())
+            .filter(|v| *v > 0);
+        let routes = data.get("routes").map(parse_routes);
+        Self {
+            advertised: protocol_version.is_some() || routes.is_some(),
+            protocol_version: protocol_version.unwrap_or(LEGACY_PROTOCOL_VERSION),
+            routes: routes.unwrap_or_default(),
+        }
+    }
+
+    /// 某路由支持的最高版本。
+    pub fn route_version(&self, route: &str) -> Option<u32> {
+        self.routes.get(&normalize_route(route)?).copied()
+    }
+
+    /// 服务端是否支持某特性（旧服务端一律视为支持）。
+    pub fn supports(&self, feature: ProtocolFeature) -> bool {
+        !self.advertised
+            || self
+                .route_version(feature.route())
+                .is_some_and(|version| version >= feature.min_version())
+    }
+
+    /// 所有特性的支持情况（供前端按特性开关界面）。
+    pub fn features(&self) -> BTreeMap<ProtocolFeature, bool> {
+        ProtocolFeature::ALL
+            .into_iter()
+            .map(|feature| (feature, self.supports(feature)))
+            .collect()
+    }
+}
+
+/// 从明文握手推送中读取服务端能力；非握手推送返回 `None`。
+pub fn capabilities_from_handshake(payload: &[u8]) -> Option<ServerCapabilities> {
+    handshake_push_data(payload).map(|data| ServerCapabilities::from_handshake_data(&data))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn handshake(data: &str) -> Vec<u8> {
+        format!(r#"{{"id":-1,"code":0,"data":{{"route":"handshake","data":{data}}}}}"#).into_bytes()
+    }
+
+    #[test]
+    fn legacy_servers_support_every_feature() {
+        let caps = capabilities_from_handshake(&handshake(r#"{"session_id":"1"}"#)).unwrap();
+        assert_eq!(caps, ServerCapabilities::default());
+        assert!(ProtocolFeature::ALL.iter().all(|f| caps.supports(*f)));
+        assert!(capabilities_from_handshake(b"\x00\x01encrypted").is_none());
+    }
+
+    #[test]
+    fn advertised_routes_gate_features() {
+        let caps = capabilities_from_handshake(&handshake(
+            r#"{"protocol_version":3,"routes":{"/Channels/Changes":2,"channels/typing":0,"bad":"x"}}"#,
+        ))
+        .unwrap();
+        assert!(caps.advertised);
+        assert_eq!(caps.protocol_version, 3);
+        assert_eq!(caps.route_version("channels/changes"), Some(2));
+        assert_eq!(caps.routes.len(), 2);
+        let features = caps.features();
+        assert!(features[&ProtocolFeature::DeltaSync]);
+        assert!(!features[&ProtocolFeature::Typing]);
+        assert!(!features[&ProtocolFeature::Reactions]);
+    }
+
+    #[test]
+    fn routes_may_be_listed_as_strings() {
+        let caps = ServerCapabilities::from_handshake_data(&serde_json::json!({
+            "routes": ["messages/reactions", "channels/typing@2", "broken@x", ""]
+        }));
+        assert!(caps.advertised);
+        assert_eq!(caps.protocol_version, LEGACY_PROTOCOL_VERSION);
+        assert_eq!(caps.route_version("messages/reactions"), Some(1));
+        assert_eq!(caps.route_version("channels/typing"), Some(2));
+        assert_eq!(caps.routes.len(), 2);
+        assert!(!caps.supports(ProtocolFeature::DeltaSync));
+    }
+}
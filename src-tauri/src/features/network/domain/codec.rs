@@ -0,0 +1,203 @@
+//! network｜领域层：业务 payload 编解码（JSON / MessagePack）。
+//!
+//! 说明：
+//! - 握手阶段由客户端在换钥包中声明 `codecs`，服务端在 `/handshake` 推送中返回选定的 `codec`；
+//! - 握手推送本身始终为明文 JSON，Rust 读循环据此记录本连接协商结果；
+//! - 业务帧在前端加密前/解密后经由本模块在 JSON 文本与选定编码之间转换。
+//!
+//! 约定：注释中文，日志英文（tracing）。
+
+use std::sync::atomic::{AtomicU8, Ordering};
+
+use serde_json::Value;
+
+/// 客户端支持的编码（按偏好排序，写入握手声明）。
+pub const SUPPORTED_CODECS: &[PayloadCodec] = &[PayloadCodec::MsgPack, PayloadCodec::Json];
+
+/// 业务 payload 编码。
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum PayloadCodec {
+    /// UTF-8 JSON 文本（默认，未协商时使用）。
+    #[default]
+    Json,
+    /// MessagePack 二进制。
+    MsgPack,
+}
+
+impl PayloadCodec {
+    /// 协议中的编码名。
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Json => "json",
+            Self::MsgPack => "msgpack",
+        }
+    }
+
+    /// 解析协议中的编码名（大小写不敏感）。
+    pub fn parse(raw: &str) -> Option<Self> {
+        match raw.trim().to_ascii_lowercase().as_str() {
+            "json" => Some(Self::Json),
+            "msgpack" | "messagepack" => Some(Self::MsgPack),
+            _ => None,
+        }
+    }
+
+    /// 将 JSON 文本编码为本编码的 bytes。
+    pub fn encode_json_text(self, json_text: &str) -> anyhow::Result<Vec<u8>> {
+        match self {
+            Self::Json => Ok(json_text.as_bytes().to_vec()),
+            Self::MsgPack => {
+                let value: Value = serde_json::from_str(json_text)
+                    .map_err(|e| anyhow::anyhow!("Invalid JSON payload: {}", e))?;
+                rmp_serde::to_vec_named(&value)
+                    .map_err(|e| anyhow::anyhow!("Failed to encode msgpack payload: {}", e))
+            }
+        }
+    }
+
+    /// 将本编码的 bytes 解码为 JSON 文本。
+    pub fn decode_to_json_text(self, payload: &[u8]) -> anyhow::Result<String> {
+        match self {
+            Self::Json => String::from_utf8(payload.to_vec())
+                .map_err(|e| anyhow::anyhow!("Invalid UTF-8 JSON payload: {}", e)),
+            Self::MsgPack => {
+                let value: Value = rmp_serde::from_slice(payload)
+                    .map_err(|e| anyhow::anyhow!("Failed to decode msgpack payload: {}", e))?;
+                serde_json::to_string(&value)
+                    .map_err(|e| anyhow::anyhow!("Failed to serialize decoded payload: {}", e))
+            }
+        }
+    }
+
+    fn to_tag(self) -> u8 {
+        match self {
+            Self::Json => 0,
+            Self::MsgPack => 1,
+        }
+    }
+
+    fn from_tag(tag: u8) -> Self {
+        match tag {
+            1 => Self::MsgPack,
+            _ => Self::Json,
+        }
+    }
+}
+
+/// 解析明文 `/handshake` 推送（`id=-1, code=0, route=handshake`），返回其内层 `data`。
+///
+/// 非握手推送（含加密业务帧）返回 `None`；内层缺失时返回空对象。
+pub fn handshake_push_data(payload: &[u8]) -> Option<Value> {
+    // 握手推送以 `{` 开头；加密业务帧以随机 nonce 开头，快速跳过避免无谓解析。
+    if payload.first() != Some(&b'{') {
+        return None;
+    }
+    let mut value: Value = serde_json::from_slice(payload).ok()?;
+    if value.get("id").and_then(Value::as_i64) != Some(-1)
+        || value.get("code").and_then(Value::as_i64) != Some(0)
+    {
+        return None;
+    }
+    let data = value.get_mut("data")?;
+    if data.get("route").and_then(Value::as_str).map(str::trim) != Some("handshake") {
+        return None;
+    }
+    Some(
+        data.get_mut("data")
+            .map(Value::take)
+            .unwrap_or_else(|| Value::Object(Default::default())),
+    )
+}
+
+/// 从明文握手推送中提取服务端选定的编码。
+///
+/// # 返回值
+/// - `Some(codec)`：payload 为 `/handshake` 推送（`id=-1, code=0, route=handshake`）。
+///   未携带 `codec` 的旧服务端视为 JSON；不支持的编码同样回退 JSON。
+/// - `None`：不是握手推送。
+pub fn codec_from_handshake(payload: &[u8]) -> Option<PayloadCodec> {
+    let selected = handshake_push_data(payload)?
+        .get("codec")
+        .and_then(Value::as_str)
+        .and_then(PayloadCodec::parse)
+        .filter(|codec| SUPPORTED_CODECS.contains(codec));
+    Some(selected.unwrap_or_default())
+}
+
+/// 单连接的协商结果（读循环写入，命令读取）。
+#[derive(Debug, Default)]
+pub struct NegotiatedCodec(AtomicU8);
+
+impl NegotiatedCodec {
+    /// 当前编码（未协商时为 JSON）。
+    pub fn get(&self) -> PayloadCodec {
+        PayloadCodec::from_tag(self.0.load(Ordering::Acquire))
+    }
+
+    /// 记录协商结果。
+    pub fn set(&self, codec: PayloadCodec) {
+        self.0.store(codec.to_tag(), Ordering::Release);
+    }
+
+    /// 若 payload 为握手推送，则更新协商结果并返回。
+    pub fn observe_frame(&self, payload: &[u8]) -> Option<PayloadCodec> {
+        let codec = codec_from_handshake(payload)?;
+        self.set(codec);
+        Some(codec)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn handshake_selects_server_codec() {
+        let payload = br#"{"id":-1,"code":0,"data":{"route":"handshake","data":{"session_id":"1","codec":"msgpack"}}}"#;
+        assert_eq!(codec_from_handshake(payload), Some(PayloadCodec::MsgPack));
+    }
+
+    #[test]
+    fn legacy_handshake_defaults_to_json() {
+        let payload =
+            br#"{"id":-1,"code":0,"data":{"route":"handshake","data":{"session_id":"1"}}}"#;
+        assert_eq!(codec_from_handshake(payload), Some(PayloadCodec::Json));
+        let unknown = br#"{"id":-1,"code":0,"data":{"route":"handshake","data":{"codec":"cbor"}}}"#;
+        assert_eq!(codec_from_handshake(unknown), Some(PayloadCodec::Json));
+    }
+
+    #[test]
+    fn non_handshake_frames_are_ignored() {
+        assert_eq!(
+            codec_from_handshake(br#"{"id":3,"code":200,"data":{}}"#),
+            None
+        );
+        assert_eq!(codec_from_handshake(&[0x12, 0x34, 0x00]), None);
+    }
+
+    #[test]
+    fn msgpack_round_trips_json_text() {
+        let text =
+            r#"{"route":"chat/send","data":{"cid":42,"content":"hi","big":18446744073709551615}}"#;
+        let encoded = PayloadCodec::MsgPack
+            .encode_json_text(text)
+            .expect("encode");
+        assert_ne!(encoded, text.as_bytes());
+        let decoded = PayloadCodec::MsgPack
+            .decode_to_json_text(&encoded)
+            .expect("decode");
+        let a: Value = serde_json::from_str(text).expect("parse original");
+        let b: Value = serde_json::from_str(&decoded).expect("parse decoded");
+        assert_eq!(a, b);
+    }
+
+    #[test]
+    fn negotiated_codec_tracks_handshake() {
+        let negotiated = NegotiatedCodec::default();
+        assert_eq!(negotiated.get(), PayloadCodec::Json);
+        negotiated.observe_frame(
+            br#"{"id":-1,"code":0,"data":{"route":"handshake","data":{"codec":"msgpack"}}}"#,
+        );
+        assert_eq!(negotiated.get(), PayloadCodec::MsgPack);
+    }
+}
@@ -0,0 +1,225 @@
+//! network｜领域：入站洪泛检测。
+//!
+//! 说明：
+//! - 按连接统计固定 1 秒窗口内的入站帧数，超过上限即进入洪泛状态；
+//! - 洪泛期间的帧不再逐条投递，由分发器缓冲后批量投递；
+//! - 出现一个完整的“未超限”窗口后，洪泛结束并返回本次事件的统计。
+//!
+//! 约定：注释中文，日志英文（tracing）。
+
+use std::time::{Duration, Instant};
+
+/// 未配置 `inbound_flood_max_per_sec` 时的默认每秒帧数上限。
+pub const DEFAULT_MAX_FRAMES_PER_SEC: u32 = 200;
+
+/// 统计窗口长度。
+const WINDOW: Duration = Duration::from_secs(1);
+
+/// 一次洪泛事件的统计。
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FloodIncident {
+    pub started_at: Instant,
+    /// 洪泛期间收到的帧数（含触发洪泛的那一帧）。
+    pub frames: u64,
+    /// 洪泛期间单个窗口内的最大帧数。
+    pub peak_rate: u32,
+}
+
+/// 单帧的判定结果。
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FloodVerdict {
+    /// 正常投递。
+    Pass,
+    /// 本帧触发洪泛（`rate` 为当前窗口帧数）；本帧及后续帧进入缓冲。
+    Started { rate: u32 },
+    /// 洪泛进行中，本帧进入缓冲。
+    Throttled,
+}
+
+/// 单个连接的洪泛检测器。
+#[derive(Debug)]
+pub struct FloodGuard {
+    limit: u32,
+    window_start: Instant,
+    window_count: u32,
+    incident: Option<FloodIncident>,
+    /// 最近一帧的时间（用于判断连接是否空闲）。
+    last_frame_at: Instant,
+    /// 洪泛期间是否已出现完整的未超限窗口（由 [`FloodGuard::poll_end`] 消费）。
+    calm_window_seen: bool,
+}
+
+/// 设置值为 0 时使用默认上限。
+pub fn effective_limit(configured: u32) -> u32 {
+    if configured == 0 {
+        DEFAULT_MAX_FRAMES_PER_SEC
+    } else {
+        configured
+    }
+}
+
+impl FloodGuard {
+    pub fn new(limit: u32, now: Instant) -> Self {
+        Self {
+            limit: effective_limit(limit),
+            window_start: now,
+            window_count: 0,
+            incident: None,
+            last_frame_at: now,
+            calm_window_seen: false,
+        }
+    }
+
+    pub fn set_limit(&mut self, limit: u32) {
+        self.limit = effective_limit(limit);
+    }
+
+    pub fn is_flooding(&self) -> bool {
+        self.incident.is_some()
+    }
+
+    /// 不在洪泛中且至少 `idle` 时长未收到帧；此时丢弃检测器不会丢失任何状态。
+    pub fn is_idle(&self, now: Instant, idle: Duration) -> bool {
+        self.incident.is_none() && now.saturating_duration_since(self.last_frame_at) >= idle
+    }
+
+    /// 推进窗口；跨过窗口边界时检查上一个窗口是否未超限。
+    fn roll(&mut self, now: Instant) {
+        let elapsed = now.saturating_duration_since(self.window_start);
+        if elapsed < WINDOW {
+            return;
+        }
+        // 跨过多个窗口时，中间的空窗口同样视为未超限。
+        let last_count = if elapsed >= WINDOW * 2 {
+            0
+        } else {
+            self.window_count
+        };
+        if self.incident.is_some() && last_count <= self.limit {
+            self.calm_window_seen = true;
+        }
+        self.window_start = if elapsed >= WINDOW * 2 {
+            now
+        } else {
+            self.window_start + WINDOW
+        };
+        self.window_count = 0;
+    }
+
+    /// 记录一帧并给出判定。
+    pub fn observe(&mut self, now: Instant) -> FloodVerdict {
+        self.roll(now);
+        self.last_frame_at = now;
+        self.window_count = self.window_count.saturating_add(1);
+        if let Some(incident) = &mut self.incident {
+            incident.frames += 1;
+            incident.peak_rate = incident.peak_rate.max(self.window_count);
+            return FloodVerdict::Throttled;
+        }
+        if self.window_count > self.limit {
+            self.incident = Some(FloodIncident {
+                started_at: now,
+                frames: 1,
+                peak_rate: self.window_count,
+            });
+            self.calm_window_seen = false;
+            return FloodVerdict::Started {
+                rate: self.window_count,
+            };
+        }
+        FloodVerdict::Pass
+    }
+
+    /// 检查洪泛是否已平息；平息时结束本次事件并返回其统计。
+    pub fn poll_end(&mut self, now: Instant) -> Option<FloodIncident> {
+        self.roll(now);
+        if !self.calm_window_seen {
+            return None;
+        }
+        self.calm_window_seen = false;
+        self.incident.take()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn burst(guard: &mut FloodGuard, now: Instant, n: u32) -> Vec<FloodVerdict> {
+        (0..n).map(|_| guard.observe(now)).collect()
+    }
+
+    #[test]
+    fn frames_under_limit_pass() {
+        let t0 = Instant::now();
+        let mut guard = FloodGuard::new(3, t0);
+        assert!(
+            burst(&mut guard, t0, 3)
+                .iter()
+                .all(|v| *v == FloodVerdict::Pass)
+        );
+        // 新窗口重新计数。
+        assert_eq!(
+            guard.observe(t0 + Duration::from_millis(1000)),
+            FloodVerdict::Pass
+        );
+        assert!(!guard.is_flooding());
+    }
+
+    #[test]
+    fn exceeding_limit_starts_incident_once() {
+        let t0 = Instant::now();
+        let mut guard = FloodGuard::new(3, t0);
+        let verdicts = burst(&mut guard, t0, 6);
+        assert_eq!(verdicts[3], FloodVerdict::Started { rate: 4 });
+        assert_eq!(verdicts[4], FloodVerdict::Throttled);
+        assert_eq!(verdicts[5], FloodVerdict::Throttled);
+        assert!(guard.is_flooding());
+    }
+
+    #[test]
+    fn incident_ends_after_a_calm_window() {
+        let t0 = Instant::now();
+        let mut guard = FloodGuard::new(3, t0);
+        burst(&mut guard, t0, 10);
+        // 同一窗口内不会结束。
+        assert_eq!(guard.poll_end(t0 + Duration::from_millis(500)), None);
+        // 第二个窗口仍然超限。
+        burst(&mut guard, t0 + Duration::from_millis(1200), 5);
+        assert_eq!(guard.poll_end(t0 + Duration::from_millis(1500)), None);
+        // 第三个窗口未超限，进入第四个窗口时结束。
+        burst(&mut guard, t0 + Duration::from_millis(2100), 2);
+        let incident = guard
+            .poll_end(t0 + Duration::from_millis(3000))
+            .expect("incident should end");
+        assert_eq!(incident.frames, 14);
+        assert_eq!(incident.peak_rate, 10);
+        assert!(!guard.is_flooding());
+    }
+
+    #[test]
+    fn idle_gap_ends_incident() {
+        let t0 = Instant::now();
+        let mut guard = FloodGuard::new(3, t0);
+        burst(&mut guard, t0, 5);
+        assert!(guard.poll_end(t0 + Duration::from_secs(5)).is_some());
+    }
+
+    #[test]
+    fn guard_is_idle_only_outside_incidents() {
+        let t0 = Instant::now();
+        let idle = Duration::from_secs(60);
+        let mut guard = FloodGuard::new(3, t0);
+        burst(&mut guard, t0, 5);
+        assert!(!guard.is_idle(t0 + idle, idle));
+        assert!(guard.poll_end(t0 + Duration::from_secs(5)).is_some());
+        assert!(!guard.is_idle(t0 + Duration::from_secs(5), idle));
+        assert!(guard.is_idle(t0 + idle, idle));
+    }
+
+    #[test]
+    fn zero_limit_uses_default() {
+        assert_eq!(effective_limit(0), DEFAULT_MAX_FRAMES_PER_SEC);
+        assert_eq!(effective_limit(50), 50);
+    }
+}
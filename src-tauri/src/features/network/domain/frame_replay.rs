@@ -0,0 +1,207 @@
+//! network｜领域：最近入站帧的重放缓冲。
+//!
+//! 说明：
+//! - 按 server 保存最近 N 帧（拆包后的 payload），每帧带 server 内自增序号（从 1 开始）；
+//! - 会话中途打开的窗口先订阅连接，再按序号取回订阅前错过的帧，无需读库；
+//! - 缓冲已淘汰所请求序号之后的帧时，结果标记为不完整，调用方应回退到数据库补齐；
+//! - 连接移除时清空该 server 的帧，但序号继续递增，旧序号不会与新帧混淆。
+//!
+//! 约定：注释中文，日志英文（tracing）。
+
+use std::collections::{HashMap, VecDeque};
+
+use serde::Serialize;
+
+/// 未配置 `frame_replay_buffer_frames` 时每个 server 保留的帧数。
+pub const DEFAULT_REPLAY_FRAMES: usize = 200;
+
+/// 设置值为 0 时使用默认容量。
+pub fn effective_capacity(configured: u32) -> usize {
+    if configured == 0 {
+        DEFAULT_REPLAY_FRAMES
+    } else {
+        configured as usize
+    }
+}
+
+/// 重放的一帧。
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ReplayedFrame {
+    pub seq: u64,
+    pub payload: Vec<u8>,
+}
+
+/// 一次重放的结果。
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FrameReplay {
+    /// 序号大于请求值的帧（按序号升序）。
+    pub frames: Vec<ReplayedFrame>,
+    /// 该 server 最近一帧的序号（尚无帧时为 0）。
+    pub latest_seq: u64,
+    /// 请求序号之后的帧是否全部仍在缓冲中；为 `false` 时需回退到数据库补齐。
+    pub complete: bool,
+}
+
+#[derive(Debug)]
+struct ServerRing {
+    next_seq: u64,
+    frames: VecDeque<ReplayedFrame>,
+}
+
+impl Default for ServerRing {
+    fn default() -> Self {
+        Self {
+            next_seq: 1,
+            frames: VecDeque::new(),
+        }
+    }
+}
+
+impl ServerRing {
+    /// 缓冲中最早一帧的序号（缓冲为空时为下一帧的序号）。
+    fn first_available(&self) -> u64 {
+        self.frames.front().map_or(self.next_seq, |frame| frame.seq)
+    }
+}
+
+/// 所有 server 的重放缓冲。
+#[derive(Debug)]
+pub struct FrameReplayBuffer {
+    capacity: usize,
+    servers: HashMap<String, ServerRing>,
+}
+
+impl Default for FrameReplayBuffer {
+    fn default() -> Self {
+        Self::new(DEFAULT_REPLAY_FRAMES)
+    }
+}
+
+impl FrameReplayBuffer {
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            capacity,
+            servers: HashMap::new(),
+        }
+    }
+
+    /// 调整每个 server 的容量；缩小时淘汰最旧的帧。
+    pub fn set_capacity(&mut self, capacity: usize) {
+        if capacity == self.capacity {
+            return;
+        }
+        self.capacity = capacity;
+        for ring in self.servers.values_mut() {
+            while ring.frames.len() > capacity {
+                ring.frames.pop_front();
+            }
+        }
+    }
+
+    /// 记录一帧，返回分配的序号。
+    pub fn push(&mut self, server_socket: &str, payload: Vec<u8>) -> u64 {
+        let ring = self.servers.entry(server_socket.to_string()).or_default();
+        let seq = ring.next_seq;
+        ring.next_seq += 1;
+        if self.capacity == 0 {
+            return seq;
+        }
+        if ring.frames.len() >= self.capacity {
+            ring.frames.pop_front();
+        }
+        ring.frames.push_back(ReplayedFrame { seq, payload });
+        seq
+    }
+
+    /// 取出序号大于 `since_seq` 的帧（`since_seq = 0` 表示缓冲中的全部帧）。
+    pub fn since(&self, server_socket: &str, since_seq: u64) -> FrameReplay {
+        let Some(ring) = self.servers.get(server_socket) else {
+            return FrameReplay {
+                frames: Vec::new(),
+                latest_seq: 0,
+                complete: since_seq == 0,
+            };
+        };
+        FrameReplay {
+            frames: ring
+                .frames
+                .iter()
+                .filter(|frame| frame.seq > since_seq)
+                .cloned()
+                .collect(),
+            latest_seq: ring.next_seq - 1,
+            complete: since_seq.saturating_add(1) >= ring.first_available(),
+        }
+    }
+
+    /// 清空某 server 的缓冲帧（序号不重置）。
+    pub fn clear(&mut self, server_socket: &str) {
+        if let Some(ring) = self.servers.get_mut(server_socket) {
+            ring.frames.clear();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn seqs(replay: &FrameReplay) -> Vec<u64> {
+        replay.frames.iter().map(|frame| frame.seq).collect()
+    }
+
+    #[test]
+    fn replays_frames_after_sequence_per_server() {
+        let mut buffer = FrameReplayBuffer::new(10);
+        for i in 0..3u8 {
+            buffer.push("a", vec![i]);
+        }
+        assert_eq!(buffer.push("b", vec![9]), 1);
+
+        let replay = buffer.since("a", 1);
+        assert_eq!(seqs(&replay), vec![2, 3]);
+        assert_eq!(replay.frames[0].payload, vec![1]);
+        assert_eq!(replay.latest_seq, 3);
+        assert!(replay.complete);
+
+        let replay = buffer.since("a", 3);
+        assert!(replay.frames.is_empty() && replay.complete);
+        assert_eq!(seqs(&buffer.since("a", 0)), vec![1, 2, 3]);
+
+        let unknown = buffer.since("c", 0);
+        assert_eq!((unknown.latest_seq, unknown.complete), (0, true));
+    }
+
+    #[test]
+    fn evicted_frames_mark_replay_incomplete() {
+        let mut buffer = FrameReplayBuffer::new(3);
+        for i in 0..5u8 {
+            buffer.push("a", vec![i]);
+        }
+        let replay = buffer.since("a", 0);
+        assert_eq!(seqs(&replay), vec![3, 4, 5]);
+        assert!(!replay.complete);
+        assert!(buffer.since("a", 2).complete);
+        assert!(!buffer.since("a", 1).complete);
+
+        buffer.set_capacity(1);
+        assert_eq!(seqs(&buffer.since("a", 0)), vec![5]);
+        assert!(!buffer.since("a", 3).complete);
+    }
+
+    #[test]
+    fn clear_keeps_sequence_numbers_increasing() {
+        let mut buffer = FrameReplayBuffer::new(10);
+        buffer.push("a", vec![1]);
+        buffer.push("a", vec![2]);
+        buffer.clear("a");
+        let replay = buffer.since("a", 1);
+        assert!(replay.frames.is_empty());
+        assert_eq!(replay.latest_seq, 2);
+        assert!(!replay.complete);
+        assert!(buffer.since("a", 2).complete);
+        assert_eq!(buffer.push("a", vec![3]), 3);
+    }
+}
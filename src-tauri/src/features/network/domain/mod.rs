@@ -4,5 +4,11 @@
 //!
 //! 约定：注释中文，日志英文（tracing）。
 
+pub mod capabilities;
+pub mod codec;
+pub mod flood_guard;
+pub mod frame_replay;
 pub mod ports;
+pub mod prefetch_scheduler;
+pub mod presence;
 pub mod types;
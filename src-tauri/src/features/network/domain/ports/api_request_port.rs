@@ -31,6 +31,8 @@ pub struct ApiHttpResponse {
     pub ok: bool,
     pub status: u16,
     pub body: Option<serde_json::Value>,
+    /// 响应头 `ETag`（可选，用于条件请求）。
+    pub etag: Option<String>,
 }
 
 /// API 请求端口 Future 类型。
@@ -5,6 +5,7 @@
 //! 约定：注释中文，日志英文（tracing）。
 
 pub mod api_request_port;
+pub mod session_token_port;
 pub mod tcp_backend_factory_port;
 pub mod tcp_backend_port;
 pub mod tcp_event_sink;
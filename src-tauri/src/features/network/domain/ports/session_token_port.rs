@@ -0,0 +1,48 @@
+//! network｜领域端口：session_token_port。
+//!
+//! 约定：注释中文，日志英文（tracing）。
+
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::Arc;
+
+use serde::{Deserialize, Serialize};
+
+/// 某个服务器的登录会话令牌。
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SessionTokens {
+    pub access_token: String,
+    pub refresh_token: String,
+    /// access token 过期时间（Unix 毫秒，可选）。
+    #[serde(default)]
+    pub expires_at_ms: Option<i64>,
+    /// 刷新时上报的设备 ID（可选）。
+    #[serde(default)]
+    pub device_id: Option<String>,
+}
+
+/// 会话令牌端口 Future 类型。
+pub type SessionTokenFuture<'a, T> = Pin<Box<dyn Future<Output = anyhow::Result<T>> + Send + 'a>>;
+
+/// 会话令牌端口（由数据层适配器实现，按 server socket 隔离）。
+pub trait SessionTokenPort: Send + Sync {
+    /// 读取令牌（`None` 表示未登录或已清除）。
+    fn load_tokens<'a>(
+        &'a self,
+        server_socket: &'a str,
+    ) -> SessionTokenFuture<'a, Option<SessionTokens>>;
+
+    /// 保存令牌（覆盖已有值）。
+    fn save_tokens<'a>(
+        &'a self,
+        server_socket: &'a str,
+        tokens: SessionTokens,
+    ) -> SessionTokenFuture<'a, ()>;
+
+    /// 清除令牌。
+    fn clear_tokens<'a>(&'a self, server_socket: &'a str) -> SessionTokenFuture<'a, ()>;
+
+    /// 该服务器的刷新锁（同一服务器的刷新需串行，避免 refresh token 轮换冲突）。
+    fn refresh_lock(&self, server_socket: &str) -> Arc<tokio::sync::Mutex<()>>;
+}
@@ -6,6 +6,7 @@ use std::future::Future;
 use std::pin::Pin;
 use std::sync::Arc;
 
+use crate::features::network::domain::codec::PayloadCodec;
 use crate::features::network::domain::ports::tcp_event_sink::TcpEventSink;
 
 /// TCP backend 端口 Future 类型。
@@ -29,4 +30,14 @@ pub trait TcpBackendPort: Send + Sync {
 
     /// 是否已在监听中。
     fn is_listening(&self) -> bool;
+
+    /// 当前连接协商的 payload 编码（未协商或不支持协商时为 JSON）。
+    fn codec(&self) -> PayloadCodec {
+        PayloadCodec::Json
+    }
+
+    /// 当前连接是否协商了明文 zstd 压缩（未协商或不支持协商时为 false）。
+    fn compression_active(&self) -> bool {
+        false
+    }
 }
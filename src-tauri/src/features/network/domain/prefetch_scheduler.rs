@@ -0,0 +1,375 @@
+//! network｜领域层：prefetch_scheduler（频道历史预取调度）。
+//!
+//! 说明：
+//! - 历史请求由前端发出，本调度器只决定“何时轮到哪个频道”：按 server 发放预取票据，
+//!   前端拿到票据后请求历史，完成后归还票据；
+//! - 优先级：当前聚焦频道 > 温频道（如有未读）> 冷频道；同级按入队顺序；
+//! - 每个 server 同时在途的后台票据数受限；聚焦频道另有一个预留名额，不会排在后台预取之后；
+//! - 冷频道只在空闲时发放：最近一次聚焦变化已过去 [`IDLE_AFTER`]，且在途请求都是冷频道；
+//! - 票据超过 [`TICKET_TIMEOUT`] 未归还视为丢失（如前端刷新），自动释放名额。
+//!
+//! 约定：注释中文，日志英文（tracing）。
+
+use std::collections::HashMap;
+use std::time::{Duration, Instant};
+
+use serde::{Deserialize, Serialize};
+
+/// 未配置 `prefetch_max_concurrent_per_server` 时每个 server 的并发上限。
+pub const DEFAULT_MAX_CONCURRENT: u32 = 2;
+
+/// 聚焦变化后多久视为空闲（开始发放冷频道）。
+pub const IDLE_AFTER: Duration = Duration::from_secs(5);
+
+/// 票据未归还的超时时间。
+pub const TICKET_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// 预取优先级（按从低到高排列）。
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PrefetchPriority {
+    Cold,
+    Warm,
+    Focused,
+}
+
+/// 发放给前端的预取票据（`channel-prefetch-ready` 事件载荷）。
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct PrefetchTicket {
+    pub ticket: u64,
+    pub server_socket: String,
+    pub channel_id: String,
+    pub priority: PrefetchPriority,
+}
+
+/// 将配置值换算为实际并发上限（0 = 默认值）。
+pub fn effective_max_concurrent(configured: u32) -> u32 {
+    if configured == 0 {
+        DEFAULT_MAX_CONCURRENT
+    } else {
+        configured
+    }
+}
+
+#[derive(Debug)]
+struct Pending {
+    channel_id: String,
+    priority: PrefetchPriority,
+    seq: u64,
+}
+
+#[derive(Debug)]
+struct InFlight {
+    priority: PrefetchPriority,
+    issued_at: Instant,
+}
+
+#[derive(Debug, Default)]
+struct ServerQueue {
+    pending: Vec<Pending>,
+    in_flight: HashMap<u64, InFlight>,
+    active_channel: Option<String>,
+    last_focus_change: Option<Instant>,
+}
+
+impl ServerQueue {
+    fn is_in_flight(&self, channel_id: &str, tickets: &HashMap<u64, (String, String)>) -> bool {
+        self.in_flight
+            .keys()
+            .any(|t| tickets.get(t).is_some_and(|(_, c)| c == channel_id))
+    }
+
+    fn is_idle(&self, now: Instant) -> bool {
+        let calm = self
+            .last_focus_change
+            .is_none_or(|at| now.saturating_duration_since(at) >= IDLE_AFTER);
+        calm && self
+            .in_flight
+            .values()
+            .all(|f| f.priority == PrefetchPriority::Cold)
+    }
+
+    /// 占用普通名额的在途票据数（聚焦频道占用的是预留名额）。
+    fn background_in_flight(&self) -> u32 {
+        self.in_flight
+            .values()
+            .filter(|f| f.priority != PrefetchPriority::Focused)
+            .count() as u32
+    }
+
+    /// 当前可发放的最高优先级条目下标。
+    fn best_pending(&self) -> Option<usize> {
+        self.pending
+            .iter()
+            .enumerate()
+            .max_by(|(_, a), (_, b)| a.priority.cmp(&b.priority).then(b.seq.cmp(&a.seq)))
+            .map(|(i, _)| i)
+    }
+}
+
+/// 频道预取调度器（纯状态机，时间由调用方传入）。
+#[derive(Debug)]
+pub struct PrefetchScheduler {
+    servers: HashMap<String, ServerQueue>,
+    /// 在途票据：ticket -> (server_socket, channel_id)。
+    tickets: HashMap<u64, (String, String)>,
+    max_concurrent: u32,
+    next_seq: u64,
+    next_ticket: u64,
+}
+
+impl PrefetchScheduler {
+    /// 创建调度器。
+    pub fn new(max_concurrent: u32) -> Self {
+        Self {
+            servers: HashMap::new(),
+            tickets: HashMap::new(),
+            max_concurrent: effective_max_concurrent(max_concurrent),
+            next_seq: 0,
+            next_ticket: 1,
+        }
+    }
+
+    /// 更新每个 server 的并发上限（0 = 默认值）。
+    pub fn set_max_concurrent(&mut self, configured: u32) {
+        self.max_concurrent = effective_max_concurrent(configured);
+    }
+
+    fn upsert(&mut self, server_socket: &str, channel_id: &str, priority: PrefetchPriority) {
+        let seq = self.next_seq;
+        let queue = self.servers.entry(server_socket.to_string()).or_default();
+        if queue.is_in_flight(channel_id, &self.tickets) {
+            return;
+        }
+        match queue
+            .pending
+            .iter_mut()
+            .find(|p| p.channel_id == channel_id)
+        {
+            Some(existing) => existing.priority = existing.priority.max(priority),
+            None => {
+                queue.pending.push(Pending {
+                    channel_id: channel_id.to_string(),
+                    priority,
+                    seq,
+                });
+                self.next_seq += 1;
+            }
+        }
+    }
+
+    /// 记录前端聚焦的频道（`None` 表示离开该 server 的频道视图）。
+    ///
+    /// 聚焦频道立即以最高优先级入队；此前聚焦但尚未发放的频道降为温频道。
+    pub fn set_active(&mut self, server_socket: &str, channel_id: Option<&str>, now: Instant) {
+        let queue = self.servers.entry(server_socket.to_string()).or_default();
+        queue.active_channel = channel_id.map(str::to_string);
+        queue.last_focus_change = Some(now);
+        for pending in queue.pending.iter_mut() {
+            if pending.priority == PrefetchPriority::Focused
+                && Some(pending.channel_id.as_str()) != channel_id
+            {
+                pending.priority = PrefetchPriority::Warm;
+            }
+        }
+        if let Some(channel_id) = channel_id {
+            self.upsert(server_socket, channel_id, PrefetchPriority::Focused);
+        }
+    }
+
+    /// 批量入队；已入队的频道只会提升优先级，已在途的频道忽略。
+    pub fn enqueue(
+        &mut self,
+        server_socket: &str,
+        channel_ids: &[String],
+        priority: PrefetchPriority,
+    ) {
+        let active = self
+            .servers
+            .get(server_socket)
+            .and_then(|q| q.active_channel.clone());
+        for channel_id in channel_ids {
+            let channel_id = channel_id.trim();
+            if channel_id.is_empty() {
+                continue;
+            }
+            // 聚焦优先级只由 `set_active` 决定。
+            let priority = if active.as_deref() == Some(channel_id) {
+                PrefetchPriority::Focused
+            } else {
+                priority.min(PrefetchPriority::Warm)
+            };
+            self.upsert(server_socket, channel_id, priority);
+        }
+    }
+
+    /// 归还票据。
+    ///
+    /// # 返回值
+    /// 票据是否仍在途（超时释放或重复归还时为 false）。
+    pub fn complete(&mut self, ticket: u64) -> bool {
+        let Some((server_socket, _)) = self.tickets.remove(&ticket) else {
+            return false;
+        };
+        if let Some(queue) = self.servers.get_mut(&server_socket) {
+            queue.in_flight.remove(&ticket);
+        }
+        true
+    }
+
+    /// 清空某个 server 的队列与在途票据（断开或切换 server 时调用）。
+    pub fn clear_server(&mut self, server_socket: &str) {
+        if self.servers.remove(server_socket).is_some() {
+            self.tickets.retain(|_, (s, _)| s != server_socket);
+        }
+    }
+
+    /// 某个 server 当前聚焦的频道。
+    pub fn active_channel(&self, server_socket: &str) -> Option<String> {
+        self.servers
+            .get(server_socket)
+            .and_then(|q| q.active_channel.clone())
+    }
+
+    /// 某个 server 尚未发放的条目数。
+    pub fn pending_len(&self, server_socket: &str) -> usize {
+        self.servers
+            .get(server_socket)
+            .map(|q| q.pending.len())
+            .unwrap_or(0)
+    }
+
+    /// 释放超时票据并发放所有当前可发放的票据。
+    pub fn dispatch(&mut self, now: Instant) -> Vec<PrefetchTicket> {
+        let mut issued = Vec::new();
+        let mut expired = Vec::new();
+        for (server_socket, queue) in self.servers.iter_mut() {
+            queue.in_flight.retain(|ticket, f| {
+                let alive = now.saturating_duration_since(f.issued_at) < TICKET_TIMEOUT;
+                if !alive {
+                    expired.push(*ticket);
+                }
+                alive
+            });
+
+            while let Some(index) = queue.best_pending() {
+                let priority = queue.pending[index].priority;
+                let busy = if priority == PrefetchPriority::Focused {
+                    // 聚焦频道可以占用预留名额。
+                    queue.in_flight.len() as u32 >= self.max_concurrent + 1
+                } else {
+                    queue.background_in_flight() >= self.max_concurrent
+                };
+                if busy {
+                    break;
+                }
+                if priority == PrefetchPriority::Cold && !queue.is_idle(now) {
+                    break;
+                }
+                let pending = queue.pending.remove(index);
+                let ticket = self.next_ticket;
+                self.next_ticket += 1;
+                queue.in_flight.insert(
+                    ticket,
+                    InFlight {
+                        priority,
+                        issued_at: now,
+                    },
+                );
+                self.tickets
+                    .insert(ticket, (server_socket.clone(), pending.channel_id.clone()));
+                issued.push(PrefetchTicket {
+                    ticket,
+                    server_socket: server_socket.clone(),
+                    channel_id: pending.channel_id,
+                    priority,
+                });
+            }
+        }
+        for ticket in expired {
+            self.tickets.remove(&ticket);
+        }
+        self.servers.retain(|_, q| {
+            !q.pending.is_empty() || !q.in_flight.is_empty() || q.active_channel.is_some()
+        });
+        issued
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const S: &str = "tls://chat.example.com:443";
+
+    fn ids(list: &[&str]) -> Vec<String> {
+        list.iter().map(|s| s.to_string()).collect()
+    }
+
+    fn channels(tickets: &[PrefetchTicket]) -> Vec<&str> {
+        tickets.iter().map(|t| t.channel_id.as_str()).collect()
+    }
+
+    #[test]
+    fn focused_channel_jumps_ahead_and_gets_reserved_slot() {
+        let mut s = PrefetchScheduler::new(2);
+        let t0 = Instant::now();
+        s.enqueue(S, &ids(&["a", "b", "c"]), PrefetchPriority::Warm);
+        assert_eq!(channels(&s.dispatch(t0)), vec!["a", "b"]);
+
+        s.set_active(S, Some("z"), t0);
+        let issued = s.dispatch(t0);
+        assert_eq!(channels(&issued), vec!["z"]);
+        assert_eq!(issued[0].priority, PrefetchPriority::Focused);
+        // 两个温频道与聚焦频道都在途，`c` 需要等待。
+        assert!(s.dispatch(t0).is_empty());
+
+        assert!(s.complete(1));
+        assert_eq!(channels(&s.dispatch(t0)), vec!["c"]);
+    }
+
+    #[test]
+    fn cold_channels_wait_for_idle() {
+        let mut s = PrefetchScheduler::new(2);
+        let t0 = Instant::now();
+        s.set_active(S, Some("a"), t0);
+        s.enqueue(S, &ids(&["x", "y"]), PrefetchPriority::Cold);
+        let focused = s.dispatch(t0);
+        assert_eq!(channels(&focused), vec!["a"]);
+        assert!(
+            s.dispatch(t0 + IDLE_AFTER).is_empty(),
+            "focused fetch in flight"
+        );
+
+        s.complete(focused[0].ticket);
+        assert!(s.dispatch(t0 + Duration::from_secs(1)).is_empty());
+        assert_eq!(channels(&s.dispatch(t0 + IDLE_AFTER)), vec!["x", "y"]);
+    }
+
+    #[test]
+    fn enqueue_only_raises_priority_and_skips_in_flight() {
+        let mut s = PrefetchScheduler::new(1);
+        let t0 = Instant::now();
+        s.enqueue(S, &ids(&["a"]), PrefetchPriority::Warm);
+        assert_eq!(channels(&s.dispatch(t0)), vec!["a"]);
+        s.enqueue(S, &ids(&["a", "b", "c"]), PrefetchPriority::Cold);
+        s.enqueue(S, &ids(&["c"]), PrefetchPriority::Warm);
+        // 调用方不能直接入队聚焦优先级。
+        s.enqueue(S, &ids(&["b"]), PrefetchPriority::Focused);
+        assert_eq!(s.pending_len(S), 2);
+
+        s.complete(1);
+        assert_eq!(channels(&s.dispatch(t0)), vec!["b"]);
+    }
+
+    #[test]
+    fn lost_tickets_expire() {
+        let mut s = PrefetchScheduler::new(1);
+        let t0 = Instant::now();
+        s.enqueue(S, &ids(&["a", "b"]), PrefetchPriority::Warm);
+        let first = s.dispatch(t0);
+        assert!(s.dispatch(t0 + Duration::from_secs(1)).is_empty());
+        assert_eq!(channels(&s.dispatch(t0 + TICKET_TIMEOUT)), vec!["b"]);
+        assert!(!s.complete(first[0].ticket));
+    }
+}
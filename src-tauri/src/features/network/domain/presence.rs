@@ -0,0 +1,289 @@
+//! network｜领域：在线状态订阅管理。
+//!
+//! 说明：
+//! - 前端各视图（成员栏、私聊列表等）按 `watcher_id` 上报“当前可见的用户”，
+//!   同一 server 下按用户引用计数去重：首个观察者出现时订阅，最后一个离开时退订；
+//! - 订阅/退订先进入待发队列，由后台按间隔合批下发（每批最多 [`MAX_BATCH_USERS`] 个用户）；
+//!   同一周期内先订阅再退订（或反之）的用户互相抵消，不产生任何帧；
+//! - 服务端推送的在线状态缓存在内存中：仍在订阅中的用户始终有效（服务端会推送变化），
+//!   已退订的用户超过 [`PRESENCE_TTL`] 后淘汰。
+//!
+//! 约定：注释中文，日志英文（tracing）。
+
+use std::collections::{BTreeSet, HashMap};
+use std::time::{Duration, Instant};
+
+use serde::{Deserialize, Serialize};
+
+/// 订阅路由。
+pub const SUBSCRIBE_ROUTE: &str = "/presence/subscribe";
+/// 退订路由。
+pub const UNSUBSCRIBE_ROUTE: &str = "/presence/unsubscribe";
+
+/// 单个订阅/退订帧携带的最大用户数。
+pub const MAX_BATCH_USERS: usize = 200;
+
+/// 未订阅用户的在线状态缓存有效期。
+pub const PRESENCE_TTL: Duration = Duration::from_secs(300);
+
+/// 一个用户的在线状态（`presence-changed` 事件与 `get_presence` 返回值）。
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PresenceEntry {
+    pub user_id: String,
+    /// 服务端定义的状态（如 `online` / `idle` / `offline`）。
+    pub status: String,
+    /// 最近在线时间（毫秒时间戳，可选）。
+    #[serde(default)]
+    pub last_seen: Option<i64>,
+}
+
+/// 待前端加密下发的一帧订阅/退订（`presence-subscription-batch` 事件载荷）。
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PresenceBatch {
+    pub server_socket: String,
+    /// [`SUBSCRIBE_ROUTE`] 或 [`UNSUBSCRIBE_ROUTE`]。
+    pub route: &'static str,
+    pub user_ids: Vec<String>,
+}
+
+#[derive(Debug, Default)]
+struct ServerPresence {
+    /// 观察者 -> 其可见的用户。
+    watchers: HashMap<String, BTreeSet<String>>,
+    /// 用户 -> 观察者数量。
+    refs: HashMap<String, u32>,
+    pending_subscribe: BTreeSet<String>,
+    pending_unsubscribe: BTreeSet<String>,
+    /// 用户 -> (在线状态, 最近更新时间)。
+    cache: HashMap<String, (PresenceEntry, Instant)>,
+}
+
+impl ServerPresence {
+    fn retain(&mut self, user_id: &str) {
+        let count = self.refs.entry(user_id.to_string()).or_default();
+        *count += 1;
+        if *count == 1 && !self.pending_unsubscribe.remove(user_id) {
+            self.pending_subscribe.insert(user_id.to_string());
+        }
+    }
+
+    fn release(&mut self, user_id: &str) {
+        let Some(count) = self.refs.get_mut(user_id) else {
+            return;
+        };
+        *count -= 1;
+        if *count > 0 {
+            return;
+        }
+        self.refs.remove(user_id);
+        if !self.pending_subscribe.remove(user_id) {
+            self.pending_unsubscribe.insert(user_id.to_string());
+        }
+    }
+}
+
+/// 所有 server 的在线状态订阅与缓存。
+#[derive(Debug, Default)]
+pub struct PresenceManager {
+    servers: HashMap<String, ServerPresence>,
+}
+
+fn batches(
+    server_socket: &str,
+    route: &'static str,
+    users: BTreeSet<String>,
+) -> Vec<PresenceBatch> {
+    let users: Vec<String> = users.into_iter().collect();
+    users
+        .chunks(MAX_BATCH_USERS)
+        .map(|chunk| PresenceBatch {
+            server_socket: server_socket.to_string(),
+            route,
+            user_ids: chunk.to_vec(),
+        })
+        .collect()
+}
+
+impl PresenceManager {
+    /// 替换某观察者当前可见的用户集合（空集合表示该观察者离开）。
+    pub fn set_watch(&mut self, server_socket: &str, watcher_id: &str, user_ids: &[String]) {
+        let next: BTreeSet<String> = user_ids
+            .iter()
+            .map(|id| id.trim())
+            .filter(|id| !id.is_empty())
+            .map(str::to_string)
+            .collect();
+        let server = self.servers.entry(server_socket.to_string()).or_default();
+        let previous = if next.is_empty() {
+            server.watchers.remove(watcher_id).unwrap_or_default()
+        } else {
+            server
+                .watchers
+                .insert(watcher_id.to_string(), next.clone())
+                .unwrap_or_default()
+        };
+        for user_id in next.difference(&previous) {
+            server.retain(user_id);
+        }
+        for user_id in previous.difference(&next) {
+            server.release(user_id);
+        }
+    }
+
+    /// 取出所有待发的订阅/退订批次（先退订后订阅）。
+    pub fn drain_batches(&mut self) -> Vec<PresenceBatch> {
+        let mut out = Vec::new();
+        for (server_socket, server) in &mut self.servers {
+            let unsubscribe = std::mem::take(&mut server.pending_unsubscribe);
+            out.extend(batches(server_socket, UNSUBSCRIBE_ROUTE, unsubscribe));
+            let subscribe = std::mem::take(&mut server.pending_subscribe);
+            out.extend(batches(server_socket, SUBSCRIBE_ROUTE, subscribe));
+        }
+        out
+    }
+
+    /// 将某 server 当前订阅的全部用户重新排入订阅队列（重连后调用）。
+    pub fn resubscribe(&mut self, server_socket: &str) {
+        if let Some(server) = self.servers.get_mut(server_socket) {
+            server.pending_unsubscribe.clear();
+            server.pending_subscribe = server.refs.keys().cloned().collect();
+        }
+    }
+
+    /// 记录服务端推送的在线状态；与缓存不同（或首次出现）时返回新状态。
+    pub fn observe(
+        &mut self,
+        server_socket: &str,
+        entry: PresenceEntry,
+        now: Instant,
+    ) -> Option<PresenceEntry> {
+        let server = self.servers.entry(server_socket.to_string()).or_default();
+        let previous = server
+            .cache
+            .insert(entry.user_id.clone(), (entry.clone(), now));
+        (previous.map(|(previous, _)| previous).as_ref() != Some(&entry)).then_some(entry)
+    }
+
+    /// 读取缓存中的在线状态；未知或已过期的用户不出现在结果中。
+    pub fn get(
+        &self,
+        server_socket: &str,
+        user_ids: &[String],
+        now: Instant,
+    ) -> Vec<PresenceEntry> {
+        let Some(server) = self.servers.get(server_socket) else {
+            return Vec::new();
+        };
+        user_ids
+            .iter()
+            .filter_map(|user_id| {
+                let (entry, updated_at) = server.cache.get(user_id.trim())?;
+                let fresh = server.refs.contains_key(user_id.trim())
+                    || now.saturating_duration_since(*updated_at) < PRESENCE_TTL;
+                fresh.then(|| entry.clone())
+            })
+            .collect()
+    }
+
+    /// 淘汰已退订且超过有效期的缓存。
+    pub fn expire(&mut self, now: Instant) {
+        for server in self.servers.values_mut() {
+            let refs = &server.refs;
+            server.cache.retain(|user_id, (_, updated_at)| {
+                refs.contains_key(user_id)
+                    || now.saturating_duration_since(*updated_at) < PRESENCE_TTL
+            });
+        }
+        self.servers.retain(|_, server| {
+            !(server.watchers.is_empty()
+                && server.cache.is_empty()
+                && server.pending_unsubscribe.is_empty())
+        });
+    }
+
+    /// 清空某 server 的订阅与缓存（连接移除时调用；连接已断开，无需退订）。
+    pub fn clear_server(&mut self, server_socket: &str) {
+        self.servers.remove(server_socket);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ids(raw: &[&str]) -> Vec<String> {
+        raw.iter().map(|id| id.to_string()).collect()
+    }
+
+    #[test]
+    fn watchers_are_deduplicated_and_batched() {
+        let mut manager = PresenceManager::default();
+        manager.set_watch("s", "members", &ids(&["1", "2"]));
+        manager.set_watch("s", "dm", &ids(&["2", "3"]));
+        let batches = manager.drain_batches();
+        assert_eq!(batches.len(), 1);
+        assert_eq!(batches[0].route, SUBSCRIBE_ROUTE);
+        assert_eq!(batches[0].user_ids, ids(&["1", "2", "3"]));
+        assert!(manager.drain_batches().is_empty());
+
+        // "2" 仍被 dm 观察，只有 "1" 被退订。
+        manager.set_watch("s", "members", &[]);
+        let batches = manager.drain_batches();
+        assert_eq!(batches.len(), 1);
+        assert_eq!(batches[0].route, UNSUBSCRIBE_ROUTE);
+        assert_eq!(batches[0].user_ids, ids(&["1"]));
+    }
+
+    #[test]
+    fn churn_within_a_tick_cancels_out() {
+        let mut manager = PresenceManager::default();
+        manager.set_watch("s", "a", &ids(&["1"]));
+        manager.set_watch("s", "a", &[]);
+        assert!(manager.drain_batches().is_empty());
+
+        manager.set_watch("s", "a", &ids(&["1"]));
+        manager.drain_batches();
+        manager.set_watch("s", "a", &[]);
+        manager.set_watch("s", "b", &ids(&["1"]));
+        assert!(manager.drain_batches().is_empty());
+
+        let many: Vec<String> = (0..=MAX_BATCH_USERS).map(|i| format!("u{i}")).collect();
+        manager.set_watch("s", "c", &many);
+        let sizes: Vec<usize> = manager
+            .drain_batches()
+            .iter()
+            .map(|b| b.user_ids.len())
+            .collect();
+        assert_eq!(sizes, vec![MAX_BATCH_USERS, 1]);
+    }
+
+    #[test]
+    fn cache_reports_changes_and_expires_unwatched_users() {
+        let mut manager = PresenceManager::default();
+        let t0 = Instant::now();
+        let online = |id: &str| PresenceEntry {
+            user_id: id.to_string(),
+            status: "online".to_string(),
+            last_seen: None,
+        };
+        manager.set_watch("s", "a", &ids(&["1"]));
+        assert!(manager.observe("s", online("1"), t0).is_some());
+        assert!(manager.observe("s", online("1"), t0).is_none());
+        assert!(manager.observe("s", online("2"), t0).is_some());
+
+        let later = t0 + PRESENCE_TTL;
+        assert_eq!(manager.get("s", &ids(&["1", "2", "3"]), t0).len(), 2);
+        assert_eq!(
+            manager.get("s", &ids(&["1", "2"]), later),
+            vec![online("1")]
+        );
+        manager.expire(later);
+        manager.set_watch("s", "a", &[]);
+        assert_eq!(manager.get("s", &ids(&["1", "2"]), t0), vec![online("1")]);
+
+        manager.clear_server("s");
+        assert!(manager.get("s", &ids(&["1"]), t0).is_empty());
+    }
+}
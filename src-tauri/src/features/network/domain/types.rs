@@ -17,6 +17,39 @@ pub struct TcpMessageEvent {
     pub payload: Vec<u8>,
 }
 
+/// 洪泛期间批量投递的帧事件载荷（`tcp-frame-batch`）。
+///
+/// # 说明
+/// - `payloads` 按接收顺序排列，前端应逐条按 `tcp-frame` 的方式处理。
+#[derive(Clone, Debug, Serialize)]
+pub struct TcpFrameBatchEvent {
+    /// 服务器 socket 地址。
+    pub server_socket: String,
+    /// 拆包后的帧载荷。
+    pub payloads: Vec<Vec<u8>>,
+}
+
+/// 入站洪泛事件载荷（`flood-detected`）。
+///
+/// # 说明
+/// - 每次洪泛只在开始（`active = true`）与结束（`active = false`）时各发送一次；
+/// - `frames` / `duration_ms` 仅在结束事件中有意义。
+#[derive(Clone, Debug, Serialize)]
+pub struct TcpFloodEvent {
+    /// 服务器 socket 地址。
+    pub server_socket: String,
+    /// 洪泛是否仍在进行。
+    pub active: bool,
+    /// 每秒帧数上限。
+    pub limit: u32,
+    /// 洪泛期间单秒最大帧数。
+    pub peak_rate: u32,
+    /// 洪泛期间收到的帧数。
+    pub frames: u64,
+    /// 洪泛持续时间（毫秒）。
+    pub duration_ms: u64,
+}
+
 /// 前端事件总线的 TCP 连接生命周期事件载荷。
 ///
 /// # 说明
@@ -33,3 +66,92 @@ pub struct TcpStateEvent {
     /// 错误摘要（仅在 error 状态下可选）。
     pub error: Option<String>,
 }
+
+/// TCP 连接状态快照（用于调试与诊断）。
+#[derive(Clone, Debug, Serialize)]
+pub struct TcpConnectionStatus {
+    /// 服务器 socket 地址。
+    pub server_socket: String,
+    /// 当前 TCP 会话代际 id。
+    pub session_id: u64,
+    /// 读循环是否仍在运行。
+    pub listening: bool,
+    /// 握手协商的 payload 编码（`json` / `msgpack`）。
+    pub codec: String,
+}
+
+/// 抓包记录的帧方向。
+#[derive(Clone, Copy, Debug, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum FrameDirection {
+    /// 服务端 -> 客户端（已拆包的 payload）。
+    Inbound,
+    /// 客户端 -> 服务端（含 length-prefix 的原始写入）。
+    Outbound,
+}
+
+/// 调试抓包中的单帧记录。
+///
+/// # 说明
+/// - 明文 JSON 帧记录脱敏后的 `json`；
+/// - 其余帧（密文/二进制）仅记录长度与前缀十六进制，避免导出完整载荷。
+#[derive(Clone, Debug, Serialize)]
+pub struct CapturedFrame {
+    /// 抓包内自增序号。
+    pub seq: u64,
+    /// 记录时间（毫秒时间戳）。
+    pub ts_ms: i64,
+    /// 服务器 socket 地址。
+    pub server_socket: String,
+    /// 帧方向。
+    pub direction: FrameDirection,
+    /// 载荷长度（bytes）。
+    pub len: usize,
+    /// 脱敏后的明文 JSON（非 JSON 帧为空）。
+    pub json: Option<serde_json::Value>,
+    /// 非 JSON 帧的前缀十六进制。
+    pub hex_prefix: Option<String>,
+}
+
+/// 调试抓包状态快照。
+#[derive(Clone, Debug, Serialize)]
+pub struct FrameCaptureStatus {
+    /// 是否正在抓包。
+    pub active: bool,
+    /// 仅抓取该 server_socket（为空表示全部）。
+    pub server_socket: Option<String>,
+    /// 环形缓冲中的帧数（所有 server 合计）。
+    pub buffered: usize,
+    /// NDJSON 落盘文件路径（未落盘为空）。
+    pub file_path: Option<String>,
+}
+
+/// 单个探测阶段的结果。
+#[derive(Clone, Debug, Default, Serialize, PartialEq, Eq)]
+pub struct ProbeStage {
+    /// 耗时（毫秒）；阶段失败或未执行时为空。
+    pub duration_ms: Option<u64>,
+    /// 失败原因；成功或未执行时为空。
+    pub error: Option<String>,
+}
+
+/// 服务器健康探测结果（`probe_server`）。
+#[derive(Clone, Debug, Serialize)]
+pub struct ServerHealthReport {
+    /// 服务器 socket 地址。
+    pub server_socket: String,
+    /// 探测开始时间（毫秒时间戳）。
+    pub probed_at: i64,
+    /// TCP 建连耗时。
+    pub tcp_connect: ProbeStage,
+    /// TLS 握手耗时（明文连接时两项均为空）。
+    pub tls_handshake: ProbeStage,
+    /// `GET /api/server` 往返耗时。
+    pub http_round_trip: ProbeStage,
+    /// `/api/server` 的 HTTP 状态码。
+    pub http_status: Option<u16>,
+    /// 服务器时钟减本地时钟（毫秒，正数表示服务器偏快；`Date` 头精度为 1 秒）。
+    pub clock_skew_ms: Option<i64>,
+    /// 所有已执行阶段均成功。
+    pub healthy: bool,
+}
@@ -0,0 +1,361 @@
+//! network｜Mock：demo_backend。
+//!
+//! 约定：注释中文，日志英文（tracing）。
+
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use serde_json::{Value, json};
+
+use crate::features::network::domain::ports::tcp_event_sink::TcpEventSink;
+use crate::features::network::domain::types::TcpStateEvent;
+use crate::features::network::mock::demo_fixture::{DemoFixture, DemoMessage};
+use crate::features::network::mock::tcp_mock::emit_mock_payload;
+
+/// 成功回包的 code（与前端 `BaseAPI` 约定一致）。
+const CODE_OK: i64 = 200;
+/// 未知路由回包的 code。
+const CODE_NOT_FOUND: i64 = 404;
+/// 历史查询默认条数。
+const DEFAULT_HISTORY_LIMIT: usize = 50;
+/// 本地用户在 fixture 中的 uid（取第一个用户）。
+const FALLBACK_SELF_UID: &str = "1";
+
+/// Demo 后端的可变状态（fixture + 周期脚本游标）。
+#[derive(Debug)]
+pub struct DemoState {
+    fixture: DemoFixture,
+    script_cursor: usize,
+    next_mid: u64,
+}
+
+impl DemoState {
+    /// 基于 fixture 创建状态。
+    pub fn new(fixture: DemoFixture) -> Self {
+        let next_mid = fixture
+            .messages
+            .iter()
+            .filter_map(|m| m.mid.parse::<u64>().ok())
+            .max()
+            .unwrap_or(0)
+            + 1;
+        Self {
+            fixture,
+            script_cursor: 0,
+            next_mid,
+        }
+    }
+
+    fn self_uid(&self) -> String {
+        self.fixture
+            .users
+            .first()
+            .map(|u| u.uid.clone())
+            .unwrap_or_else(|| FALLBACK_SELF_UID.to_string())
+    }
+
+    fn append_message(&mut self, cid: String, uid: String, content: String) -> DemoMessage {
+        let message = DemoMessage {
+            mid: self.next_mid.to_string(),
+            cid,
+            uid,
+            content,
+            send_time: now_ms(),
+        };
+        self.next_mid += 1;
+        self.fixture.messages.push(message.clone());
+        message
+    }
+
+    /// 取出下一条脚本消息并写入历史（脚本为空时返回 None）。
+    pub fn next_scripted_message(&mut self) -> Option<DemoMessage> {
+        if self.fixture.script.is_empty() {
+            return None;
+        }
+        let line = self.fixture.script[self.script_cursor % self.fixture.script.len()].clone();
+        self.script_cursor += 1;
+        Some(self.append_message(line.cid, line.uid, line.content))
+    }
+
+    /// 处理一条请求，返回需要投递给前端的 JSON 帧（按顺序）。
+    ///
+    /// # 说明
+    /// - 带 `id` 的请求会得到一条同 id 的回包；
+    /// - `/chat/send` 额外产生一条 `message.created` 推送（id = -1）。
+    pub fn handle_request(&mut self, request: &Value) -> Vec<Value> {
+        let id = request.get("id").cloned().unwrap_or(Value::Null);
+        let route = request
+            .get("route")
+            .and_then(Value::as_str)
+            .unwrap_or_default()
+            .trim_start_matches('/');
+        let data = request.get("data").cloned().unwrap_or(Value::Null);
+
+        let mut frames = Vec::new();
+        let (code, body) = match route {
+            "handshake" => (CODE_OK, Value::Null),
+            "channels" | "channel/list" => (CODE_OK, json!({ "channels": self.fixture.channels })),
+            "users" | "user/list" => (CODE_OK, json!({ "users": self.fixture.users })),
+            "messages/history" | "chat/history" => {
+                let cid = data.get("cid").and_then(Value::as_str).unwrap_or_default();
+                let before = data.get("before").and_then(Value::as_i64);
+                let limit = data
+                    .get("limit")
+                    .and_then(Value::as_u64)
+                    .map(|v| v as usize)
+                    .unwrap_or(DEFAULT_HISTORY_LIMIT);
+                let messages = self.fixture.history(cid, before, limit);
+                (CODE_OK, json!({ "messages": messages }))
+            }
+            "chat/send" => {
+                let cid = data
+                    .get("cid")
+                    .and_then(Value::as_str)
+                    .unwrap_or_default()
+                    .to_string();
+                let content = data
+                    .get("content")
+                    .or_else(|| data.get("text"))
+                    .and_then(Value::as_str)
+                    .unwrap_or_default()
+                    .to_string();
+                let uid = self.self_uid();
+                let message = self.append_message(cid, uid, content);
+                frames.push(push_frame("message.created", json!(message)));
+                (CODE_OK, json!({ "mid": message.mid }))
+            }
+            _ => (CODE_NOT_FOUND, Value::Null),
+        };
+
+        if !id.is_null() {
+            frames.insert(0, json!({ "id": id, "code": code, "data": body }));
+        }
+        frames
+    }
+}
+
+fn push_frame(route: &str, data: Value) -> Value {
+    json!({ "id": -1, "code": 0, "data": { "route": route, "data": data } })
+}
+
+fn now_ms() -> i64 {
+    let millis = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_millis();
+    millis as i64
+}
+
+/// 去掉 Netty 2B length-prefix（若存在）。
+///
+/// 前端发送的是已封帧的 bytes；这里容忍未封帧的 payload 以便调试。
+fn deframe_request(data: &[u8]) -> &[u8] {
+    if data.len() >= 2 {
+        let len = u16::from_be_bytes([data[0], data[1]]) as usize;
+        if len == data.len() - 2 {
+            return &data[2..];
+        }
+    }
+    data
+}
+
+fn emit_json(event_sink: &dyn TcpEventSink, server_socket: &str, frame: &Value) {
+    emit_mock_payload(event_sink, server_socket, frame.to_string().as_bytes());
+}
+
+/// 基于 fixture 的离线 demo 后端（`mock://fixture/<name>`）。
+///
+/// # 说明
+/// - `start` 发送握手首包，并按 fixture 间隔周期性推送脚本消息；
+/// - `send` 解析明文 JSON 请求并回放频道/用户/历史消息；
+/// - 加密后的请求无法解析，会被忽略（demo 仅用于 UI 开发与截图）。
+pub struct DemoTcpService {
+    state: Arc<Mutex<DemoState>>,
+    target: Option<(Arc<dyn TcpEventSink>, String)>,
+    ticker: Option<tokio::task::JoinHandle<()>>,
+}
+
+impl DemoTcpService {
+    /// 创建一个 demo 服务实例。
+    pub fn new(fixture: DemoFixture) -> Self {
+        Self {
+            state: Arc::new(Mutex::new(DemoState::new(fixture))),
+            target: None,
+            ticker: None,
+        }
+    }
+
+    /// 启动 demo 服务（幂等）。
+    pub fn start(
+        &mut self,
+        event_sink: Arc<dyn TcpEventSink>,
+        server_socket: String,
+        session_id: u64,
+    ) {
+        if self.target.is_some() {
+            return;
+        }
+        self.target = Some((event_sink.clone(), server_socket.clone()));
+
+        event_sink.emit_state(TcpStateEvent {
+            server_socket: server_socket.clone(),
+            session_id,
+            state: "connected".to_string(),
+            error: None,
+        });
+
+        let interval_ms = self
+            .state
+            .lock()
+            .map(|s| s.fixture.interval_ms)
+            .unwrap_or(0);
+        let state = self.state.clone();
+        self.ticker = Some(tokio::spawn(async move {
+            emit_json(
+                event_sink.as_ref(),
+                &server_socket,
+                &push_frame("handshake", json!({ "session_id": session_id.to_string() })),
+            );
+            if interval_ms == 0 {
+                return;
+            }
+            let mut interval = tokio::time::interval(Duration::from_millis(interval_ms));
+            // interval 的首个 tick 立即完成，跳过以避免连接后立刻推送。
+            interval.tick().await;
+            loop {
+                interval.tick().await;
+                let message = match state.lock() {
+                    Ok(mut guard) => guard.next_scripted_message(),
+                    Err(_) => break,
+                };
+                let Some(message) = message else { break };
+                emit_json(
+                    event_sink.as_ref(),
+                    &server_socket,
+                    &push_frame("message.created", json!(message)),
+                );
+            }
+        }));
+    }
+
+    /// 处理前端发送的请求 bytes。
+    pub async fn send(&mut self, data: Vec<u8>) -> anyhow::Result<()> {
+        let Some((event_sink, server_socket)) = self.target.clone() else {
+            return Err(anyhow::anyhow!("Demo TCP: service not started"));
+        };
+        let request: Value = match serde_json::from_slice(deframe_request(&data)) {
+            Ok(v) => v,
+            Err(_) => {
+                tracing::debug!(
+                    action = "network_mock_demo_request_ignored",
+                    len = data.len(),
+                    "Ignoring non-JSON demo request"
+                );
+                return Ok(());
+            }
+        };
+        let frames = self
+            .state
+            .lock()
+            .map_err(|_| anyhow::anyhow!("Demo TCP: state lock poisoned"))?
+            .handle_request(&request);
+        for frame in &frames {
+            emit_json(event_sink.as_ref(), &server_socket, frame);
+        }
+        Ok(())
+    }
+
+    /// 停止周期推送任务。
+    pub async fn close(&mut self) -> anyhow::Result<()> {
+        if let Some(task) = self.ticker.take() {
+            task.abort();
+        }
+        Ok(())
+    }
+}
+
+impl Drop for DemoTcpService {
+    fn drop(&mut self) {
+        if let Some(task) = self.ticker.take() {
+            task.abort();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn fixture() -> DemoFixture {
+        DemoFixture::from_json_str(
+            r#"{
+              "channels": [{ "cid": "c1", "name": "general" }],
+              "users": [{ "uid": "u1", "nickname": "Me" }],
+              "messages": [
+                { "mid": "7", "cid": "c1", "uid": "u1", "content": "hi", "send_time": 10 }
+              ],
+              "script": [{ "cid": "c1", "uid": "u1", "content": "tick" }]
+            }"#,
+        )
+        .expect("fixture")
+    }
+
+    #[test]
+    fn handle_request_replies_with_same_id() {
+        let mut state = DemoState::new(fixture());
+        let frames = state.handle_request(&json!({ "id": 42, "route": "/channels" }));
+        assert_eq!(frames.len(), 1);
+        assert_eq!(frames[0]["id"], 42);
+        assert_eq!(frames[0]["code"], CODE_OK);
+        assert_eq!(frames[0]["data"]["channels"][0]["cid"], "c1");
+    }
+
+    #[test]
+    fn handle_send_appends_message_and_pushes_event() {
+        let mut state = DemoState::new(fixture());
+        let frames = state.handle_request(
+            &json!({ "id": 1, "route": "/chat/send", "data": { "cid": "c1", "content": "yo" } }),
+        );
+        assert_eq!(frames.len(), 2);
+        assert_eq!(frames[0]["data"]["mid"], "8");
+        assert_eq!(frames[1]["data"]["route"], "message.created");
+
+        let history = state.handle_request(
+            &json!({ "id": 2, "route": "/messages/history", "data": { "cid": "c1" } }),
+        );
+        assert_eq!(
+            history[0]["data"]["messages"].as_array().map(Vec::len),
+            Some(2)
+        );
+    }
+
+    #[test]
+    fn scripted_messages_cycle() {
+        let mut state = DemoState::new(fixture());
+        let first = state.next_scripted_message().expect("first");
+        let second = state.next_scripted_message().expect("second");
+        assert_eq!(first.content, "tick");
+        assert_ne!(first.mid, second.mid);
+    }
+
+    #[test]
+    fn unknown_route_returns_not_found() {
+        let mut state = DemoState::new(fixture());
+        let frames = state.handle_request(&json!({ "id": 3, "route": "/nope" }));
+        assert_eq!(frames[0]["code"], CODE_NOT_FOUND);
+        assert!(
+            state
+                .handle_request(&json!({ "route": "/nope" }))
+                .is_empty()
+        );
+    }
+
+    #[test]
+    fn deframe_request_strips_length_prefix() {
+        let payload = br#"{"route":"/users"}"#;
+        let mut framed = (payload.len() as u16).to_be_bytes().to_vec();
+        framed.extend_from_slice(payload);
+        assert_eq!(deframe_request(&framed), payload);
+        assert_eq!(deframe_request(payload), payload);
+    }
+}
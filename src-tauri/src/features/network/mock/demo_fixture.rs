@@ -0,0 +1,208 @@
+//! network｜Mock：demo_fixture。
+//!
+//! 约定：注释中文，日志英文（tracing）。
+
+use anyhow::Context;
+use serde::{Deserialize, Serialize};
+
+/// 自定义 fixture 所在目录（位于应用数据目录下）。
+const FIXTURE_DIR: &str = "mock-fixtures";
+
+/// 内置 fixture 名称。
+pub const DEFAULT_FIXTURE_NAME: &str = "default";
+
+/// 周期消息的默认间隔（毫秒）。
+const DEFAULT_INTERVAL_MS: u64 = 8_000;
+
+/// 历史查询单页上限。
+pub const HISTORY_PAGE_MAX: usize = 100;
+
+const BUILTIN_DEFAULT_FIXTURE: &str = r#"{
+  "channels": [
+    { "cid": "1001", "name": "general", "brief": "Company-wide announcements", "owner_uid": "1" },
+    { "cid": "1002", "name": "design", "brief": "UI reviews and screenshots", "owner_uid": "2" },
+    { "cid": "1003", "name": "random", "brief": "Off-topic chatter", "owner_uid": "3" }
+  ],
+  "users": [
+    { "uid": "1", "nickname": "Demo User", "avatar": "" },
+    { "uid": "2", "nickname": "Alice", "avatar": "" },
+    { "uid": "3", "nickname": "Bob", "avatar": "" }
+  ],
+  "messages": [
+    { "mid": "1", "cid": "1001", "uid": "2", "content": "Welcome to the CarryPigeon demo server!", "send_time": 1700000000000 },
+    { "mid": "2", "cid": "1001", "uid": "3", "content": "Everything here runs offline.", "send_time": 1700000060000 },
+    { "mid": "3", "cid": "1002", "uid": "2", "content": "New mockups are up for review.", "send_time": 1700000120000 },
+    { "mid": "4", "cid": "1003", "uid": "3", "content": "Anyone up for lunch?", "send_time": 1700000180000 }
+  ],
+  "script": [
+    { "cid": "1001", "uid": "2", "content": "Ping from the demo backend." },
+    { "cid": "1002", "uid": "3", "content": "Looks great on my screen." },
+    { "cid": "1003", "uid": "2", "content": "Coffee break in five." }
+  ]
+}"#;
+
+/// Demo 频道。
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DemoChannel {
+    pub cid: String,
+    pub name: String,
+    #[serde(default)]
+    pub brief: String,
+    #[serde(default)]
+    pub owner_uid: String,
+}
+
+/// Demo 用户。
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DemoUser {
+    pub uid: String,
+    pub nickname: String,
+    #[serde(default)]
+    pub avatar: String,
+}
+
+/// Demo 消息（与服务端消息字段保持一致的最小子集）。
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DemoMessage {
+    pub mid: String,
+    pub cid: String,
+    pub uid: String,
+    pub content: String,
+    pub send_time: i64,
+}
+
+/// 周期消息脚本条目（按顺序循环投递）。
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DemoScriptLine {
+    pub cid: String,
+    pub uid: String,
+    pub content: String,
+}
+
+/// Demo 后端 fixture（频道/用户/历史消息 + 周期消息脚本）。
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DemoFixture {
+    pub channels: Vec<DemoChannel>,
+    pub users: Vec<DemoUser>,
+    #[serde(default)]
+    pub messages: Vec<DemoMessage>,
+    #[serde(default)]
+    pub script: Vec<DemoScriptLine>,
+    /// 周期消息间隔（毫秒）；为 0 时不生成周期消息。
+    #[serde(default = "default_interval_ms")]
+    pub interval_ms: u64,
+}
+
+fn default_interval_ms() -> u64 {
+    DEFAULT_INTERVAL_MS
+}
+
+impl DemoFixture {
+    /// 从 JSON 字符串解析 fixture。
+    pub fn from_json_str(raw: &str) -> anyhow::Result<Self> {
+        serde_json::from_str(raw).context("Failed to parse demo fixture JSON")
+    }
+
+    /// 查询频道历史消息。
+    ///
+    /// # 参数
+    /// - `cid`：频道 id。
+    /// - `before`：仅返回 `send_time` 早于该值的消息（可选）。
+    /// - `limit`：最多返回条数（上限 `HISTORY_PAGE_MAX`）。
+    ///
+    /// # 返回值
+    /// 返回按 `send_time` 升序排列的最近一页消息。
+    pub fn history(&self, cid: &str, before: Option<i64>, limit: usize) -> Vec<DemoMessage> {
+        let mut matched: Vec<&DemoMessage> = self
+            .messages
+            .iter()
+            .filter(|m| m.cid == cid && before.is_none_or(|b| m.send_time < b))
+            .collect();
+        matched.sort_by_key(|m| m.send_time);
+        let limit = limit.clamp(1, HISTORY_PAGE_MAX);
+        let skip = matched.len().saturating_sub(limit);
+        matched.into_iter().skip(skip).cloned().collect()
+    }
+}
+
+/// 从 `mock://fixture/<name>` 中提取 fixture 名称。
+///
+/// # 返回值
+/// - `Some(name)`：socket 指向 fixture（缺省名称时返回 `default`）。
+/// - `None`：不是 fixture socket。
+pub fn parse_fixture_socket(socket: &str) -> Option<&str> {
+    let rest = socket.strip_prefix("mock://fixture")?;
+    let name = rest.strip_prefix('/').unwrap_or(rest).trim_end_matches('/');
+    if !rest.is_empty() && !rest.starts_with('/') {
+        return None;
+    }
+    Some(if name.is_empty() {
+        DEFAULT_FIXTURE_NAME
+    } else {
+        name
+    })
+}
+
+/// 加载 fixture：`default` 使用内置数据，其余从 `{app_data}/mock-fixtures/<name>.json` 读取。
+pub async fn load_demo_fixture(name: &str) -> anyhow::Result<DemoFixture> {
+    if name == DEFAULT_FIXTURE_NAME {
+        return DemoFixture::from_json_str(BUILTIN_DEFAULT_FIXTURE);
+    }
+    if !name
+        .chars()
+        .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+    {
+        return Err(anyhow::anyhow!("Invalid demo fixture name: {}", name));
+    }
+    let path = crate::shared::app_data_dir::get_app_data_dir()?
+        .join(FIXTURE_DIR)
+        .join(format!("{name}.json"));
+    let raw = tokio::fs::read_to_string(&path)
+        .await
+        .with_context(|| format!("Failed to read demo fixture: {}", path.display()))?;
+    DemoFixture::from_json_str(&raw)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn builtin_fixture_parses() {
+        let fixture = DemoFixture::from_json_str(BUILTIN_DEFAULT_FIXTURE).expect("fixture");
+        assert_eq!(fixture.channels.len(), 3);
+        assert!(!fixture.script.is_empty());
+        assert_eq!(fixture.interval_ms, DEFAULT_INTERVAL_MS);
+    }
+
+    #[test]
+    fn history_pages_backwards_from_before() {
+        let mut fixture = DemoFixture::from_json_str(BUILTIN_DEFAULT_FIXTURE).expect("fixture");
+        fixture.messages.push(DemoMessage {
+            mid: "99".to_string(),
+            cid: "1001".to_string(),
+            uid: "1".to_string(),
+            content: "latest".to_string(),
+            send_time: 1_700_000_900_000,
+        });
+
+        let latest = fixture.history("1001", None, 2);
+        assert_eq!(
+            latest.iter().map(|m| m.mid.as_str()).collect::<Vec<_>>(),
+            vec!["2", "99"]
+        );
+
+        let older = fixture.history("1001", Some(1_700_000_060_000), 10);
+        assert_eq!(older.len(), 1);
+        assert_eq!(older[0].mid, "1");
+    }
+
+    #[test]
+    fn parse_fixture_socket_extracts_name() {
+        assert_eq!(parse_fixture_socket("mock://fixture/team"), Some("team"));
+        assert_eq!(parse_fixture_socket("mock://fixture"), Some("default"));
+        assert_eq!(parse_fixture_socket("mock://fixture/"), Some("default"));
+        assert_eq!(parse_fixture_socket("mock://fixtures"), None);
+        assert_eq!(parse_fixture_socket("mock://handshake"), None);
+    }
+}
@@ -4,4 +4,6 @@
 //!
 //! 约定：注释中文，日志英文（tracing）。
 
+pub mod demo_backend;
+pub mod demo_fixture;
 pub mod tcp_mock;
@@ -85,24 +85,7 @@ impl MockTcpService {
         tokio::spawn(async move {
             let json =
                 r#"{"id":-1,"code":0,"data":{"route":"handshake","data":{"session_id":"1"}}}"#;
-            let payload = json.as_bytes();
-            let len = payload.len().min(u16::MAX as usize) as u16;
-            let mut framed = Vec::with_capacity(2 + len as usize);
-            framed.push((len >> 8) as u8);
-            framed.push((len & 0xFF) as u8);
-            framed.extend_from_slice(&payload[..len as usize]);
-
-            // 旧事件（带 length-prefix 封帧后的 raw bytes）。
-            event_sink.emit_message(TcpMessageEvent {
-                server_socket: server_socket.clone(),
-                payload: framed,
-            });
-
-            // 新事件（仅 payload，去掉 length-prefix；由 Rust 侧 deframe 后统一投递）。
-            event_sink.emit_frame(TcpMessageEvent {
-                server_socket,
-                payload: payload[..len as usize].to_vec(),
-            });
+            emit_mock_payload(event_sink.as_ref(), &server_socket, json.as_bytes());
         });
     }
 
@@ -127,3 +110,29 @@ impl MockTcpService {
         Ok(())
     }
 }
+
+/// 以真实连接相同的形态投递一段 mock payload。
+///
+/// # 说明
+/// 旧事件（`emit_message`）携带 Netty 2B length-prefix 封帧后的 raw bytes；
+/// 新事件（`emit_frame`）仅携带 payload（与 Rust 侧 deframe 后的投递一致）。
+pub(crate) fn emit_mock_payload(
+    event_sink: &dyn TcpEventSink,
+    server_socket: &str,
+    payload: &[u8],
+) {
+    let len = payload.len().min(u16::MAX as usize) as u16;
+    let mut framed = Vec::with_capacity(2 + len as usize);
+    framed.push((len >> 8) as u8);
+    framed.push((len & 0xFF) as u8);
+    framed.extend_from_slice(&payload[..len as usize]);
+
+    event_sink.emit_message(TcpMessageEvent {
+        server_socket: server_socket.to_string(),
+        payload: framed,
+    });
+    event_sink.emit_frame(TcpMessageEvent {
+        server_socket: server_socket.to_string(),
+        payload: payload[..len as usize].to_vec(),
+    });
+}
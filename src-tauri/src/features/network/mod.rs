@@ -9,6 +9,8 @@ pub mod domain;
 pub mod link_preview;
 #[cfg(debug_assertions)]
 pub mod mock;
+#[cfg(test)]
+pub mod test_support;
 pub mod usecases;
 
 pub use di::commands::*;
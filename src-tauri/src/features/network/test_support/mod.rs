@@ -0,0 +1,258 @@
+//! network｜测试支撑：本地 frame 协议服务端与事件记录桩。
+//!
+//! 说明：仅在 `cfg(test)` 下编译，用于 `TcpServiceReal` 等组件的契约测试。
+//!
+//! 约定：注释中文，日志英文（tracing）。
+
+use std::collections::HashMap;
+use std::net::SocketAddr;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use tokio::io::{AsyncRead, AsyncReadExt, AsyncWrite, AsyncWriteExt};
+use tokio::net::TcpListener;
+use tokio::task::JoinHandle;
+
+use crate::features::network::domain::ports::tcp_event_sink::TcpEventSink;
+use crate::features::network::domain::types::{TcpMessageEvent, TcpStateEvent};
+
+/// 测试用自签名证书（CN=localhost，SAN 含 127.0.0.1）。
+const TEST_CERT_PEM: &[u8] = include_bytes!("fixtures/test_cert.pem");
+/// 测试用证书私钥（PKCS#8，仅用于本地测试）。
+const TEST_KEY_PEM: &[u8] = include_bytes!("fixtures/test_key.pem");
+/// 测试证书 DER 的 SHA-256 指纹。
+pub const TEST_CERT_SHA256: &str =
+    "acb7707261a2e07b503283abc52125799685b74b4cd379720ddd3274ad302e02";
+
+/// 分段写入之间的间隔（确保客户端观察到多次 read）。
+const CHUNK_WRITE_GAP: Duration = Duration::from_millis(20);
+
+/// 按 Netty 2B length-prefix 封帧。
+pub fn encode_frame(payload: &[u8]) -> Vec<u8> {
+    let len = payload.len().min(u16::MAX as usize) as u16;
+    let mut framed = Vec::with_capacity(2 + len as usize);
+    framed.extend_from_slice(&len.to_be_bytes());
+    framed.extend_from_slice(&payload[..len as usize]);
+    framed
+}
+
+/// 本地 frame 协议服务端构建器。
+#[derive(Default)]
+pub struct FrameTestServerBuilder {
+    routes: HashMap<Vec<u8>, Vec<Vec<u8>>>,
+    greeting_chunks: Vec<Vec<u8>>,
+    close_after_greeting: bool,
+    tls: bool,
+}
+
+impl FrameTestServerBuilder {
+    /// 创建空构建器（明文 TCP、无预置回包）。
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 注册一条“请求 payload → 回包 payload 列表”的映射。
+    pub fn respond(mut self, request: &[u8], responses: &[&[u8]]) -> Self {
+        self.routes.insert(
+            request.to_vec(),
+            responses.iter().map(|r| r.to_vec()).collect(),
+        );
+        self
+    }
+
+    /// 连接建立后按顺序写入的原始 bytes（每段单独 flush，用于构造半包）。
+    pub fn greeting_chunk(mut self, chunk: &[u8]) -> Self {
+        self.greeting_chunks.push(chunk.to_vec());
+        self
+    }
+
+    /// 写完 greeting 后立即关闭连接。
+    pub fn close_after_greeting(mut self) -> Self {
+        self.close_after_greeting = true;
+        self
+    }
+
+    /// 使用测试证书以 TLS 提供服务。
+    pub fn tls(mut self) -> Self {
+        self.tls = true;
+        self
+    }
+
+    /// 绑定 `127.0.0.1:0` 并在后台接受一个连接。
+    pub async fn spawn(self) -> anyhow::Result<FrameTestServer> {
+        let listener = TcpListener::bind("127.0.0.1:0").await?;
+        let addr = listener.local_addr()?;
+        let received = Arc::new(Mutex::new(Vec::new()));
+        let acceptor = if self.tls {
+            let identity = native_tls::Identity::from_pkcs8(TEST_CERT_PEM, TEST_KEY_PEM)?;
+            Some(tokio_native_tls::TlsAcceptor::from(
+                native_tls::TlsAcceptor::new(identity)?,
+            ))
+        } else {
+            None
+        };
+
+        let recorded = Arc::clone(&received);
+        let task = tokio::spawn(async move {
+            let Ok((stream, _)) = listener.accept().await else {
+                return;
+            };
+            match acceptor {
+                Some(acceptor) => {
+                    // 指纹不匹配时客户端会主动断开，握手失败属于预期路径。
+                    if let Ok(tls) = acceptor.accept(stream).await {
+                        serve(tls, self, recorded).await;
+                    }
+                }
+                None => serve(stream, self, recorded).await,
+            }
+        });
+
+        Ok(FrameTestServer {
+            addr,
+            received,
+            task,
+        })
+    }
+}
+
+async fn serve<S>(mut stream: S, spec: FrameTestServerBuilder, received: Arc<Mutex<Vec<Vec<u8>>>>)
+where
+    S: AsyncRead + AsyncWrite + Unpin,
+{
+    for chunk in &spec.greeting_chunks {
+        if stream.write_all(chunk).await.is_err() || stream.flush().await.is_err() {
+            return;
+        }
+        tokio::time::sleep(CHUNK_WRITE_GAP).await;
+    }
+    if spec.close_after_greeting {
+        let _ = stream.shutdown().await;
+        return;
+    }
+
+    let mut acc = Vec::new();
+    let mut buffer = vec![0; 4096];
+    loop {
+        let n = match stream.read(&mut buffer).await {
+            Ok(0) | Err(_) => return,
+            Ok(n) => n,
+        };
+        acc.extend_from_slice(&buffer[..n]);
+        while acc.len() >= 2 {
+            let len = u16::from_be_bytes([acc[0], acc[1]]) as usize;
+            if acc.len() < 2 + len {
+                break;
+            }
+            let request: Vec<u8> = acc.drain(0..2 + len).skip(2).collect();
+            if let Ok(mut guard) = received.lock() {
+                guard.push(request.clone());
+            }
+            for response in spec.routes.get(&request).into_iter().flatten() {
+                if stream.write_all(&encode_frame(response)).await.is_err() {
+                    return;
+                }
+            }
+            let _ = stream.flush().await;
+        }
+    }
+}
+
+/// 正在运行的本地 frame 协议服务端。
+pub struct FrameTestServer {
+    addr: SocketAddr,
+    received: Arc<Mutex<Vec<Vec<u8>>>>,
+    task: JoinHandle<()>,
+}
+
+impl FrameTestServer {
+    /// 明文 TCP socket（`tcp://127.0.0.1:<port>`）。
+    pub fn tcp_socket(&self) -> String {
+        format!("tcp://{}", self.addr)
+    }
+
+    /// 带指纹校验的 TLS socket（`tls-fp://<fp>@127.0.0.1:<port>`）。
+    pub fn tls_fp_socket(&self, fingerprint: &str) -> String {
+        format!("tls-fp://{}@{}", fingerprint, self.addr)
+    }
+
+    /// 服务端已收到的请求 payload（已拆包）。
+    pub fn received(&self) -> Vec<Vec<u8>> {
+        self.received
+            .lock()
+            .map(|guard| guard.clone())
+            .unwrap_or_default()
+    }
+}
+
+impl Drop for FrameTestServer {
+    fn drop(&mut self) {
+        self.task.abort();
+    }
+}
+
+/// 记录所有事件的 `TcpEventSink` 测试桩。
+#[derive(Default)]
+pub struct RecordingEventSink {
+    states: Mutex<Vec<TcpStateEvent>>,
+    messages: Mutex<Vec<TcpMessageEvent>>,
+    frames: Mutex<Vec<TcpMessageEvent>>,
+}
+
+impl RecordingEventSink {
+    /// 已投递的状态序列（仅 state 字段）。
+    pub fn states(&self) -> Vec<String> {
+        self.states
+            .lock()
+            .map(|guard| guard.iter().map(|e| e.state.clone()).collect())
+            .unwrap_or_default()
+    }
+
+    /// 已投递的拆包后 payload。
+    pub fn frames(&self) -> Vec<Vec<u8>> {
+        self.frames
+            .lock()
+            .map(|guard| guard.iter().map(|e| e.payload.clone()).collect())
+            .unwrap_or_default()
+    }
+
+    /// legacy 原始 chunk 拼接后的总字节数。
+    pub fn raw_bytes_len(&self) -> usize {
+        self.messages
+            .lock()
+            .map(|guard| guard.iter().map(|e| e.payload.len()).sum())
+            .unwrap_or_default()
+    }
+
+    /// 轮询直到条件满足或超时。
+    pub async fn wait_until(&self, timeout: Duration, pred: impl Fn(&Self) -> bool) -> bool {
+        let deadline = tokio::time::Instant::now() + timeout;
+        while tokio::time::Instant::now() < deadline {
+            if pred(self) {
+                return true;
+            }
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+        pred(self)
+    }
+}
+
+impl TcpEventSink for RecordingEventSink {
+    fn emit_state(&self, event: TcpStateEvent) {
+        if let Ok(mut guard) = self.states.lock() {
+            guard.push(event);
+        }
+    }
+
+    fn emit_message(&self, event: TcpMessageEvent) {
+        if let Ok(mut guard) = self.messages.lock() {
+            guard.push(event);
+        }
+    }
+
+    fn emit_frame(&self, event: TcpMessageEvent) {
+        if let Ok(mut guard) = self.frames.lock() {
+            guard.push(event);
+        }
+    }
+}
@@ -41,6 +41,8 @@ pub struct ApiJsonResponse {
     pub body: Option<serde_json::Value>,
     /// 错误响应体（JSON）。
     pub error: Option<serde_json::Value>,
+    /// 响应头 `ETag`（可选；配合 `If-None-Match` 请求头，未变化时状态码为 304）。
+    pub etag: Option<String>,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -66,6 +68,11 @@ fn map_tls_policy(policy: TlsPolicy) -> ApiHttpTlsPolicy {
     }
 }
 
+/// 解析前端传入的 TLS 策略字符串（未知值按 `strict` 处理）。
+pub fn api_tls_policy(raw: Option<&str>) -> ApiHttpTlsPolicy {
+    map_tls_policy(parse_tls_policy(raw))
+}
+
 fn normalize_server_socket(raw: &str) -> anyhow::Result<String> {
     let socket = raw.trim().to_string();
     if socket.is_empty() {
@@ -100,6 +107,7 @@ fn to_api_json_response(response: ApiHttpResponse) -> ApiJsonResponse {
             status: response.status,
             body: response.body,
             error: None,
+            etag: response.etag,
         };
     }
     ApiJsonResponse {
@@ -107,6 +115,7 @@ fn to_api_json_response(response: ApiHttpResponse) -> ApiJsonResponse {
         status: response.status,
         body: None,
         error: response.body,
+        etag: response.etag,
     }
 }
 
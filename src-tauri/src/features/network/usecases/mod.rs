@@ -5,4 +5,7 @@
 //! 约定：注释中文，日志英文（tracing）。
 
 pub mod api_usecases;
+pub mod oauth_usecases;
+pub mod session_usecases;
 pub mod tcp_usecases;
+pub mod typing_usecases;
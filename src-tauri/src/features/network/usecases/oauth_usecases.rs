@@ -0,0 +1,325 @@
+//! network｜用例层：oauth_usecases（OAuth2/OIDC 登录，PKCE + 本地回环回调）。
+//!
+//! 说明：
+//! - 授权端点由 `GET /api/server` 的 `oauth` 字段下发（`authorization_endpoint` 必填，
+//!   相对路径按服务器同源解析；`client_id` / `scope` 可选）；
+//! - 授权码通过 `POST /api/auth/tokens`（`grant_type=authorization_code`）换取令牌，
+//!   与邮箱验证码登录共用同一端点与响应格式；
+//! - 请求均经 `ApiRequestPort`（遵循服务器 TLS 策略）。
+//!
+//! 约定：注释中文，日志英文（tracing）。
+
+use std::collections::BTreeMap;
+
+use base64::Engine;
+use base64::engine::general_purpose::URL_SAFE_NO_PAD;
+use sha2::{Digest, Sha256};
+
+use crate::features::network::domain::ports::api_request_port::ApiRequestPort;
+use crate::features::network::domain::ports::session_token_port::SessionTokens;
+use crate::features::network::usecases::api_usecases::{self, ApiJsonRequest};
+use crate::shared::net::origin::to_http_origin;
+
+const DEFAULT_CLIENT_ID: &str = "carrypigeon-desktop";
+const DEFAULT_SCOPE: &str = "openid";
+
+/// 服务器下发的 OAuth 配置。
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OAuthConfig {
+    pub authorization_endpoint: String,
+    pub client_id: String,
+    pub scope: String,
+}
+
+/// PKCE 参数。
+#[derive(Debug, Clone)]
+pub struct PkcePair {
+    pub verifier: String,
+    pub challenge: String,
+}
+
+/// 授权码换取的登录结果。
+#[derive(Debug, Clone)]
+pub struct OAuthTokens {
+    pub tokens: SessionTokens,
+    pub uid: Option<String>,
+    pub is_new_user: bool,
+}
+
+/// OAuth 请求目标（服务器与 TLS 参数）。
+#[derive(Debug, Clone)]
+pub struct OAuthTarget {
+    pub server_socket: String,
+    pub tls_policy: Option<String>,
+    pub tls_fingerprint: Option<String>,
+}
+
+/// OAuth 登录失败原因（用于映射错误码）。
+#[derive(Debug)]
+pub enum OAuthError {
+    Unsupported,
+    StateMismatch,
+    Denied(String),
+    ExchangeRejected(u16),
+}
+
+impl std::fmt::Display for OAuthError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Unsupported => write!(f, "Server does not support OAuth login"),
+            Self::StateMismatch => write!(f, "OAuth state mismatch"),
+            Self::Denied(reason) => write!(f, "Authorization denied: {reason}"),
+            Self::ExchangeRejected(status) => {
+                write!(f, "Token exchange failed with status {status}")
+            }
+        }
+    }
+}
+
+impl std::error::Error for OAuthError {}
+
+fn now_ms() -> i64 {
+    let millis = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_millis();
+    millis as i64
+}
+
+/// 由 verifier 计算 S256 challenge。
+fn pkce_challenge(verifier: &str) -> String {
+    URL_SAFE_NO_PAD.encode(Sha256::digest(verifier.as_bytes()))
+}
+
+/// 生成 PKCE 参数（32 字节随机 verifier）。
+pub fn generate_pkce() -> anyhow::Result<PkcePair> {
+    let mut bytes = [0u8; 32];
+    getrandom::fill(&mut bytes).map_err(|_| anyhow::anyhow!("Failed to generate PKCE verifier"))?;
+    let verifier = URL_SAFE_NO_PAD.encode(bytes);
+    let challenge = pkce_challenge(&verifier);
+    Ok(PkcePair {
+        verifier,
+        challenge,
+    })
+}
+
+/// 生成随机 `state`。
+pub fn generate_state() -> anyhow::Result<String> {
+    let mut bytes = [0u8; 16];
+    getrandom::fill(&mut bytes).map_err(|_| anyhow::anyhow!("Failed to generate OAuth state"))?;
+    Ok(hex::encode(bytes))
+}
+
+fn parse_oauth_config(origin: &str, server_info: &serde_json::Value) -> Option<OAuthConfig> {
+    let oauth = server_info.get("oauth")?;
+    let field = |name: &str| {
+        oauth
+            .get(name)
+            .and_then(|v| v.as_str())
+            .map(str::trim)
+            .filter(|v| !v.is_empty())
+    };
+    let endpoint = field("authorization_endpoint")?;
+    let authorization_endpoint =
+        if endpoint.starts_with("https://") || endpoint.starts_with("http://") {
+            endpoint.to_string()
+        } else {
+            format!("{}/{}", origin, endpoint.trim_start_matches('/'))
+        };
+    Some(OAuthConfig {
+        authorization_endpoint,
+        client_id: field("client_id").unwrap_or(DEFAULT_CLIENT_ID).to_string(),
+        scope: field("scope").unwrap_or(DEFAULT_SCOPE).to_string(),
+    })
+}
+
+/// 从 `GET /api/server` 读取 OAuth 配置。
+pub async fn discover_oauth_config(
+    target: &OAuthTarget,
+    api_request_port: &dyn ApiRequestPort,
+) -> anyhow::Result<OAuthConfig> {
+    let response = api_usecases::api_request_json(
+        ApiJsonRequest {
+            server_socket: target.server_socket.clone(),
+            method: "GET".to_string(),
+            path: "/api/server".to_string(),
+            headers: None,
+            body: None,
+            tls_policy: target.tls_policy.clone(),
+            tls_fingerprint: target.tls_fingerprint.clone(),
+        },
+        api_request_port,
+    )
+    .await?;
+    let origin = to_http_origin(&target.server_socket)?;
+    response
+        .body
+        .as_ref()
+        .filter(|_| response.ok)
+        .and_then(|body| parse_oauth_config(&origin, body))
+        .ok_or_else(|| OAuthError::Unsupported.into())
+}
+
+/// 构造授权请求地址。
+pub fn build_authorization_url(
+    config: &OAuthConfig,
+    redirect_uri: &str,
+    pkce: &PkcePair,
+    state: &str,
+) -> anyhow::Result<String> {
+    let mut url = reqwest::Url::parse(&config.authorization_endpoint)?;
+    url.query_pairs_mut()
+        .append_pair("response_type", "code")
+        .append_pair("client_id", &config.client_id)
+        .append_pair("redirect_uri", redirect_uri)
+        .append_pair("scope", &config.scope)
+        .append_pair("state", state)
+        .append_pair("code_challenge", &pkce.challenge)
+        .append_pair("code_challenge_method", "S256");
+    Ok(url.to_string())
+}
+
+/// 校验回调参数并取出授权码。
+pub fn authorization_code(
+    params: &BTreeMap<String, String>,
+    expected_state: &str,
+) -> Result<String, OAuthError> {
+    if params.get("state").map(String::as_str) != Some(expected_state) {
+        return Err(OAuthError::StateMismatch);
+    }
+    if let Some(error) = params.get("error") {
+        return Err(OAuthError::Denied(error.clone()));
+    }
+    params
+        .get("code")
+        .filter(|c| !c.is_empty())
+        .cloned()
+        .ok_or_else(|| OAuthError::Denied("missing_code".to_string()))
+}
+
+/// 用授权码换取令牌。
+pub async fn exchange_code(
+    target: &OAuthTarget,
+    code: &str,
+    pkce: &PkcePair,
+    redirect_uri: &str,
+    device_id: Option<String>,
+    api_request_port: &dyn ApiRequestPort,
+) -> anyhow::Result<OAuthTokens> {
+    let mut body = serde_json::json!({
+        "grant_type": "authorization_code",
+        "code": code,
+        "code_verifier": pkce.verifier,
+        "redirect_uri": redirect_uri,
+    });
+    if let Some(device_id) = device_id.as_deref() {
+        body["client"] = serde_json::json!({ "device_id": device_id });
+    }
+    let response = api_usecases::api_request_json(
+        ApiJsonRequest {
+            server_socket: target.server_socket.clone(),
+            method: "POST".to_string(),
+            path: "/api/auth/tokens".to_string(),
+            headers: None,
+            body: Some(body),
+            tls_policy: target.tls_policy.clone(),
+            tls_fingerprint: target.tls_fingerprint.clone(),
+        },
+        api_request_port,
+    )
+    .await?;
+    if !response.ok {
+        return Err(OAuthError::ExchangeRejected(response.status).into());
+    }
+    let body = response
+        .body
+        .ok_or_else(|| anyhow::anyhow!("Token response is empty"))?;
+    let field = |name: &str| {
+        body.get(name)
+            .and_then(|v| v.as_str())
+            .map(str::trim)
+            .filter(|v| !v.is_empty())
+            .map(str::to_string)
+    };
+    let (Some(access_token), Some(refresh_token)) = (field("access_token"), field("refresh_token"))
+    else {
+        return Err(anyhow::anyhow!("Token response is missing token fields"));
+    };
+    let expires_at_ms = body
+        .get("expires_in")
+        .and_then(|v| v.as_i64())
+        .map(|secs| now_ms() + secs.max(0) * 1000);
+    Ok(OAuthTokens {
+        tokens: SessionTokens {
+            access_token,
+            refresh_token,
+            expires_at_ms,
+            device_id,
+        },
+        uid: field("uid"),
+        is_new_user: body
+            .get("is_new_user")
+            .and_then(|v| v.as_bool())
+            .unwrap_or(false),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn pkce_challenge_matches_rfc7636_vector() {
+        assert_eq!(
+            pkce_challenge("dBjftJeZ4CVP-mB92K27uhbUJU1p1r_wW1gFWFOEjXk"),
+            "E9Melhoa2OwvFrEMTJguCHaoeK1t8URWbuGJSstw-cM"
+        );
+        let pair = generate_pkce().unwrap();
+        assert_eq!(pair.verifier.len(), 43);
+        assert_eq!(pair.challenge, pkce_challenge(&pair.verifier));
+    }
+
+    #[test]
+    fn oauth_config_resolves_relative_endpoint_and_defaults() {
+        let info = serde_json::json!({ "oauth": { "authorization_endpoint": "/oauth/authorize" } });
+        let config = parse_oauth_config("https://example.com", &info).unwrap();
+        assert_eq!(
+            config.authorization_endpoint,
+            "https://example.com/oauth/authorize"
+        );
+        assert_eq!(config.client_id, DEFAULT_CLIENT_ID);
+        assert!(parse_oauth_config("https://example.com", &serde_json::json!({})).is_none());
+
+        let pkce = PkcePair {
+            verifier: "v".to_string(),
+            challenge: "c".to_string(),
+        };
+        let url =
+            build_authorization_url(&config, "http://127.0.0.1:5000/callback", &pkce, "s").unwrap();
+        assert!(url.starts_with("https://example.com/oauth/authorize?response_type=code"));
+        assert!(url.contains("redirect_uri=http%3A%2F%2F127.0.0.1%3A5000%2Fcallback"));
+        assert!(url.contains("code_challenge=c&code_challenge_method=S256"));
+    }
+
+    #[test]
+    fn callback_requires_matching_state_and_code() {
+        let params = |pairs: &[(&str, &str)]| -> BTreeMap<String, String> {
+            pairs
+                .iter()
+                .map(|(k, v)| (k.to_string(), v.to_string()))
+                .collect()
+        };
+        assert_eq!(
+            authorization_code(&params(&[("state", "s"), ("code", "abc")]), "s").unwrap(),
+            "abc"
+        );
+        assert!(matches!(
+            authorization_code(&params(&[("state", "x"), ("code", "abc")]), "s"),
+            Err(OAuthError::StateMismatch)
+        ));
+        assert!(matches!(
+            authorization_code(&params(&[("state", "s"), ("error", "access_denied")]), "s"),
+            Err(OAuthError::Denied(reason)) if reason == "access_denied"
+        ));
+    }
+}
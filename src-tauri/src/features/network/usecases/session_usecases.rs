@@ -0,0 +1,429 @@
+//! network｜用例层：session_usecases（会话令牌自动刷新）。
+//!
+//! 说明：
+//! - `/api/*` 请求未带 `Authorization` 时自动附加已保存的 access token；
+//! - 响应 401 时用 refresh token 调用 `POST /api/auth/refresh`，成功后以新令牌重试一次；
+//! - 同一服务器的刷新持有刷新锁串行执行：等锁期间令牌已被其它请求刷新时直接复用新令牌；
+//! - 刷新被服务端拒绝（400/401/403）时清除令牌并报告会话过期；网络错误或 5xx 不视为过期。
+//!
+//! 约定：注释中文，日志英文（tracing）。
+
+use std::collections::BTreeMap;
+
+use crate::features::network::domain::ports::api_request_port::ApiRequestPort;
+use crate::features::network::domain::ports::session_token_port::{
+    SessionTokenPort, SessionTokens,
+};
+use crate::features::network::usecases::api_usecases::{self, ApiJsonRequest, ApiJsonResponse};
+
+const REFRESH_PATH: &str = "/api/auth/refresh";
+
+/// 带会话管理的请求结果。
+#[derive(Debug, Clone)]
+pub struct SessionApiJsonResponse {
+    /// 最终响应（刷新成功时为重试后的响应）。
+    pub response: ApiJsonResponse,
+    /// 本次请求过程中刷新得到的新令牌。
+    pub refreshed: Option<SessionTokens>,
+    /// 刷新被拒绝、会话已失效。
+    pub session_expired: bool,
+}
+
+/// 刷新结果。
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SessionRefreshOutcome {
+    /// 已得到可用的新令牌（可能由并发请求刷新）。
+    Refreshed(SessionTokens),
+    /// 刷新被拒绝，令牌已清除。
+    Expired,
+    /// 无法刷新（未保存令牌、网络错误或服务端暂不可用），保留现有令牌。
+    Unavailable,
+}
+
+fn now_ms() -> i64 {
+    let millis = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_millis();
+    millis as i64
+}
+
+fn bearer_token(headers: Option<&BTreeMap<String, String>>) -> Option<String> {
+    let (_, value) = headers?
+        .iter()
+        .find(|(name, _)| name.eq_ignore_ascii_case("authorization"))?;
+    let token = value.trim().strip_prefix("Bearer ")?.trim();
+    (!token.is_empty()).then(|| token.to_string())
+}
+
+fn with_bearer(
+    headers: Option<BTreeMap<String, String>>,
+    token: &str,
+) -> Option<BTreeMap<String, String>> {
+    let mut headers = headers.unwrap_or_default();
+    headers.retain(|name, _| !name.eq_ignore_ascii_case("authorization"));
+    headers.insert("Authorization".to_string(), format!("Bearer {token}"));
+    Some(headers)
+}
+
+fn parse_refresh_body(
+    body: Option<&serde_json::Value>,
+    device_id: Option<String>,
+) -> Option<SessionTokens> {
+    let body = body?;
+    let field = |name: &str| {
+        body.get(name)
+            .and_then(|v| v.as_str())
+            .map(str::trim)
+            .filter(|v| !v.is_empty())
+            .map(str::to_string)
+    };
+    let expires_in = body.get("expires_in").and_then(|v| v.as_i64());
+    Some(SessionTokens {
+        access_token: field("access_token")?,
+        refresh_token: field("refresh_token")?,
+        expires_at_ms: expires_in.map(|secs| now_ms() + secs.max(0) * 1000),
+        device_id,
+    })
+}
+
+/// 刷新指定服务器的会话令牌（同一服务器串行）。
+///
+/// # 参数
+/// - `stale_access_token`：调用方认为已失效的 access token；
+///   持锁后若已保存的令牌与之不同，说明已被并发刷新，直接返回当前令牌。
+///
+/// # 返回值
+/// - `Err(anyhow::Error)`：读写凭据存储失败。
+pub async fn refresh_session(
+    server_socket: &str,
+    stale_access_token: &str,
+    tls_policy: Option<String>,
+    tls_fingerprint: Option<String>,
+    api_request_port: &dyn ApiRequestPort,
+    token_port: &dyn SessionTokenPort,
+) -> anyhow::Result<SessionRefreshOutcome> {
+    let socket = server_socket.trim();
+    let lock = token_port.refresh_lock(socket);
+    let _guard = lock.lock().await;
+
+    let Some(current) = token_port.load_tokens(socket).await? else {
+        return Ok(SessionRefreshOutcome::Unavailable);
+    };
+    if current.access_token != stale_access_token {
+        return Ok(SessionRefreshOutcome::Refreshed(current));
+    }
+
+    let mut body = serde_json::json!({ "refresh_token": current.refresh_token });
+    if let Some(device_id) = current.device_id.as_deref() {
+        body["client"] = serde_json::json!({ "device_id": device_id });
+    }
+    let response = match api_usecases::api_request_json(
+        ApiJsonRequest {
+            server_socket: socket.to_string(),
+            method: "POST".to_string(),
+            path: REFRESH_PATH.to_string(),
+            headers: None,
+            body: Some(body),
+            tls_policy,
+            tls_fingerprint,
+        },
+        api_request_port,
+    )
+    .await
+    {
+        Ok(response) => response,
+        Err(err) => {
+            tracing::warn!(action = "network_session_refresh_failed", server_socket = %socket, error = %err);
+            return Ok(SessionRefreshOutcome::Unavailable);
+        }
+    };
+
+    if !response.ok {
+        if matches!(response.status, 400 | 401 | 403) {
+            token_port.clear_tokens(socket).await?;
+            tracing::warn!(action = "network_session_expired", server_socket = %socket, status = response.status);
+            return Ok(SessionRefreshOutcome::Expired);
+        }
+        tracing::warn!(action = "network_session_refresh_failed", server_socket = %socket, status = response.status);
+        return Ok(SessionRefreshOutcome::Unavailable);
+    }
+    let Some(next) = parse_refresh_body(response.body.as_ref(), current.device_id.clone()) else {
+        tracing::warn!(action = "network_session_refresh_invalid_response", server_socket = %socket);
+        return Ok(SessionRefreshOutcome::Unavailable);
+    };
+    token_port.save_tokens(socket, next.clone()).await?;
+    tracing::info!(action = "network_session_refreshed", server_socket = %socket);
+    Ok(SessionRefreshOutcome::Refreshed(next))
+}
+
+/// 执行 `/api/*` JSON 请求，并在 401 时透明刷新会话后重试一次。
+///
+/// # 说明
+/// - `/api/auth/*` 请求原样转发（避免刷新请求自身递归刷新）；
+/// - 读写凭据存储失败只记录日志，返回原始响应。
+pub async fn api_request_json_with_session(
+    mut args: ApiJsonRequest,
+    api_request_port: &dyn ApiRequestPort,
+    token_port: &dyn SessionTokenPort,
+) -> anyhow::Result<SessionApiJsonResponse> {
+    let socket = args.server_socket.trim().to_string();
+    let plain = |response| SessionApiJsonResponse {
+        response,
+        refreshed: None,
+        session_expired: false,
+    };
+    if args.path.trim().starts_with("/api/auth/") {
+        return api_usecases::api_request_json(args, api_request_port)
+            .await
+            .map(plain);
+    }
+
+    let stored = token_port.load_tokens(&socket).await.unwrap_or_else(|err| {
+        tracing::warn!(action = "network_session_tokens_load_failed", server_socket = %socket, error = %err);
+        None
+    });
+    let used_token = match (bearer_token(args.headers.as_ref()), stored.as_ref()) {
+        (Some(token), _) => Some(token),
+        (None, Some(tokens)) => {
+            args.headers = with_bearer(args.headers.take(), &tokens.access_token);
+            Some(tokens.access_token.clone())
+        }
+        (None, None) => None,
+    };
+
+    let response = api_usecases::api_request_json(args.clone(), api_request_port).await?;
+    let (401, Some(used_token), Some(_)) = (response.status, used_token, stored) else {
+        return Ok(plain(response));
+    };
+
+    let outcome = refresh_session(
+        &socket,
+        &used_token,
+        args.tls_policy.clone(),
+        args.tls_fingerprint.clone(),
+        api_request_port,
+        token_port,
+    )
+    .await
+    .unwrap_or_else(|err| {
+        tracing::warn!(action = "network_session_refresh_failed", server_socket = %socket, error = %err);
+        SessionRefreshOutcome::Unavailable
+    });
+    match outcome {
+        SessionRefreshOutcome::Refreshed(tokens) => {
+            args.headers = with_bearer(args.headers.take(), &tokens.access_token);
+            let response = api_usecases::api_request_json(args, api_request_port).await?;
+            Ok(SessionApiJsonResponse {
+                response,
+                refreshed: Some(tokens),
+                session_expired: false,
+            })
+        }
+        SessionRefreshOutcome::Expired => Ok(SessionApiJsonResponse {
+            response,
+            refreshed: None,
+            session_expired: true,
+        }),
+        SessionRefreshOutcome::Unavailable => Ok(plain(response)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::features::network::domain::ports::api_request_port::{
+        ApiHttpRequest, ApiHttpRequestFuture, ApiHttpResponse,
+    };
+    use crate::features::network::domain::ports::session_token_port::SessionTokenFuture;
+    use std::collections::HashMap;
+    use std::sync::{Arc, Mutex};
+
+    /// 只接受 `Bearer fresh` 的测试服务端；刷新接口按 `refresh_ok` 返回。
+    struct TestServer {
+        refresh_ok: bool,
+        requests: Mutex<Vec<ApiHttpRequest>>,
+    }
+
+    impl TestServer {
+        fn new(refresh_ok: bool) -> Self {
+            Self {
+                refresh_ok,
+                requests: Mutex::new(Vec::new()),
+            }
+        }
+
+        fn refresh_calls(&self) -> usize {
+            self.requests
+                .lock()
+                .unwrap()
+                .iter()
+                .filter(|r| r.url.ends_with(REFRESH_PATH))
+                .count()
+        }
+    }
+
+    impl ApiRequestPort for TestServer {
+        fn execute_json_request<'a>(&'a self, request: ApiHttpRequest) -> ApiHttpRequestFuture<'a> {
+            Box::pin(async move {
+                let response = if request.url.ends_with(REFRESH_PATH) {
+                    if self.refresh_ok {
+                        ApiHttpResponse {
+                            ok: true,
+                            status: 200,
+                            body: Some(serde_json::json!({
+                                "access_token": "fresh",
+                                "refresh_token": "rt-2",
+                                "expires_in": 900,
+                            })),
+                            etag: None,
+                        }
+                    } else {
+                        ApiHttpResponse {
+                            ok: false,
+                            status: 401,
+                            body: None,
+                            etag: None,
+                        }
+                    }
+                } else if request.headers.get("Authorization").map(String::as_str)
+                    == Some("Bearer fresh")
+                {
+                    ApiHttpResponse {
+                        ok: true,
+                        status: 200,
+                        body: Some(serde_json::json!({ "hello": "world" })),
+                        etag: None,
+                    }
+                } else {
+                    ApiHttpResponse {
+                        ok: false,
+                        status: 401,
+                        body: None,
+                        etag: None,
+                    }
+                };
+                self.requests.lock().unwrap().push(request);
+                Ok(response)
+            })
+        }
+    }
+
+    #[derive(Default)]
+    struct MemoryTokens {
+        tokens: Mutex<HashMap<String, SessionTokens>>,
+        lock: Arc<tokio::sync::Mutex<()>>,
+    }
+
+    impl SessionTokenPort for MemoryTokens {
+        fn load_tokens<'a>(
+            &'a self,
+            server_socket: &'a str,
+        ) -> SessionTokenFuture<'a, Option<SessionTokens>> {
+            Box::pin(async move { Ok(self.tokens.lock().unwrap().get(server_socket).cloned()) })
+        }
+
+        fn save_tokens<'a>(
+            &'a self,
+            server_socket: &'a str,
+            tokens: SessionTokens,
+        ) -> SessionTokenFuture<'a, ()> {
+            Box::pin(async move {
+                self.tokens
+                    .lock()
+                    .unwrap()
+                    .insert(server_socket.to_string(), tokens);
+                Ok(())
+            })
+        }
+
+        fn clear_tokens<'a>(&'a self, server_socket: &'a str) -> SessionTokenFuture<'a, ()> {
+            Box::pin(async move {
+                self.tokens.lock().unwrap().remove(server_socket);
+                Ok(())
+            })
+        }
+
+        fn refresh_lock(&self, _server_socket: &str) -> Arc<tokio::sync::Mutex<()>> {
+            Arc::clone(&self.lock)
+        }
+    }
+
+    const SOCKET: &str = "ws://example.com:8080";
+
+    fn tokens(access: &str) -> SessionTokens {
+        SessionTokens {
+            access_token: access.to_string(),
+            refresh_token: "rt-1".to_string(),
+            expires_at_ms: None,
+            device_id: Some("device-1".to_string()),
+        }
+    }
+
+    fn get_me() -> ApiJsonRequest {
+        ApiJsonRequest {
+            server_socket: SOCKET.to_string(),
+            method: "GET".to_string(),
+            path: "/api/users/me".to_string(),
+            headers: None,
+            body: None,
+            tls_policy: None,
+            tls_fingerprint: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn refreshes_on_401_and_retries_with_new_token() {
+        let server = TestServer::new(true);
+        let store = MemoryTokens::default();
+        store.save_tokens(SOCKET, tokens("stale")).await.unwrap();
+
+        let result = api_request_json_with_session(get_me(), &server, &store)
+            .await
+            .unwrap();
+        assert!(result.response.ok);
+        assert!(!result.session_expired);
+        let saved = store.load_tokens(SOCKET).await.unwrap().unwrap();
+        assert_eq!(saved.access_token, "fresh");
+        assert_eq!(saved.refresh_token, "rt-2");
+        assert_eq!(saved.device_id.as_deref(), Some("device-1"));
+        assert!(saved.expires_at_ms.is_some());
+        assert_eq!(result.refreshed, Some(saved));
+
+        let requests = server.requests.lock().unwrap();
+        assert_eq!(requests.len(), 3);
+        assert_eq!(requests[0].headers["Authorization"], "Bearer stale");
+        assert_eq!(
+            requests[1].body.as_ref().unwrap()["client"]["device_id"],
+            "device-1"
+        );
+    }
+
+    #[tokio::test]
+    async fn rejected_refresh_clears_tokens_and_reports_expiry() {
+        let server = TestServer::new(false);
+        let store = MemoryTokens::default();
+        store.save_tokens(SOCKET, tokens("stale")).await.unwrap();
+
+        let result = api_request_json_with_session(get_me(), &server, &store)
+            .await
+            .unwrap();
+        assert_eq!(result.response.status, 401);
+        assert!(result.session_expired);
+        assert!(store.load_tokens(SOCKET).await.unwrap().is_none());
+    }
+
+    #[tokio::test]
+    async fn concurrent_refresh_reuses_already_rotated_token() {
+        let server = TestServer::new(true);
+        let store = MemoryTokens::default();
+        // 另一请求已完成刷新：保存的令牌已不是调用方手上的旧令牌。
+        store.save_tokens(SOCKET, tokens("fresh")).await.unwrap();
+
+        let outcome = refresh_session(SOCKET, "stale", None, None, &server, &store)
+            .await
+            .unwrap();
+        assert_eq!(outcome, SessionRefreshOutcome::Refreshed(tokens("fresh")));
+        assert_eq!(server.refresh_calls(), 0);
+    }
+}
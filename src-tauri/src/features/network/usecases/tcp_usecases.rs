@@ -9,10 +9,11 @@ use std::sync::atomic::{AtomicU64, Ordering};
 
 use tokio::sync::{Mutex, RwLock};
 
+use crate::features::network::domain::codec::PayloadCodec;
 use crate::features::network::domain::ports::tcp_backend_factory_port::TcpBackendFactoryPort;
 use crate::features::network::domain::ports::tcp_backend_port::TcpBackendPort;
 use crate::features::network::domain::ports::tcp_event_sink::TcpEventSink;
-use crate::features::network::domain::types::TcpStateEvent;
+use crate::features::network::domain::types::{TcpConnectionStatus, TcpStateEvent};
 use crate::shared::error::command_error;
 
 type SharedTcpBackend = Arc<Mutex<Box<dyn TcpBackendPort>>>;
@@ -194,6 +195,45 @@ impl TcpRegistryService {
         backend.send(data).await
     }
 
+    /// 查询指定 server_socket 的连接状态（会话代际、监听状态、协商编码）。
+    pub async fn connection_status(
+        &self,
+        server_socket: String,
+    ) -> anyhow::Result<TcpConnectionStatus> {
+        let server_socket = normalize_server_socket(server_socket)?;
+        let (backend, session_id) = {
+            let lock = self.registry.read().await;
+            lock.map
+                .get(&server_socket)
+                .map(|entry| (Arc::clone(&entry.backend), entry.session_id))
+        }
+        .ok_or_else(|| registered_backend_not_found(&server_socket))?;
+        let backend = backend.lock().await;
+        Ok(TcpConnectionStatus {
+            server_socket,
+            session_id,
+            listening: backend.is_listening(),
+            codec: backend.codec().as_str().to_string(),
+        })
+    }
+
+    /// 指定 server_socket 当前协商的 payload 编码与是否启用明文压缩。
+    pub async fn negotiated_payload(
+        &self,
+        server_socket: String,
+    ) -> anyhow::Result<(PayloadCodec, bool)> {
+        let server_socket = normalize_server_socket(server_socket)?;
+        let backend = {
+            let lock = self.registry.read().await;
+            lock.map
+                .get(&server_socket)
+                .map(|entry| Arc::clone(&entry.backend))
+        }
+        .ok_or_else(|| registered_backend_not_found(&server_socket))?;
+        let backend = backend.lock().await;
+        Ok((backend.codec(), backend.compression_active()))
+    }
+
     /// 移除并关闭指定 server_socket 的 TCP backend。
     pub async fn remove_tcp_service(
         &self,
@@ -361,6 +401,37 @@ mod tests {
         println!("PASS tcp_registered_server_workspace_operations_succeed");
     }
 
+    #[tokio::test]
+    async fn tcp_connection_status_reports_session_and_codec() {
+        let service = TcpRegistryService::new();
+        let factory = Arc::new(TestBackendFactory {
+            state: Arc::new(StdMutex::new(TestBackendState::default())),
+        });
+        service
+            .add_tcp_service(
+                factory,
+                Arc::new(TestEventSink::default()),
+                "socket://server-a".to_string(),
+                "tcp://127.0.0.1:9000".to_string(),
+            )
+            .await
+            .expect("registered service should add");
+
+        let status = service
+            .connection_status(" socket://server-a ".to_string())
+            .await
+            .expect("status should be available");
+        assert_eq!(status.server_socket, "socket://server-a");
+        assert!(status.listening);
+        assert_eq!(status.codec, "json");
+        assert!(
+            service
+                .connection_status("socket://missing".to_string())
+                .await
+                .is_err()
+        );
+    }
+
     #[tokio::test]
     async fn tcp_rejects_unregistered_workspace_socket() {
         let prev_locale = rust_i18n::locale();
@@ -0,0 +1,241 @@
+//! network｜用例：正在输入提示（出站节流 + 入站过期）。
+//!
+//! 说明：
+//! - 出站：前端每次按键都可上报，同一 `(server, channel)` 在节流间隔内只放行一次，
+//!   放行后由前端加密并发送 typing 帧（Rust 不持有会话密钥）；
+//! - 入站：每条对端 typing 推送刷新该用户的过期时间，超过 [`TYPING_TTL`] 未刷新、
+//!   或收到 `typing = false` 时移除；
+//! - 入站集合发生变化时产出该频道完整的输入者列表（[`TypingState`]），供合并广播。
+//!
+//! 约定：注释中文，日志英文（tracing）。
+
+use std::collections::{BTreeSet, HashMap};
+use std::time::{Duration, Instant};
+
+use serde::Serialize;
+
+/// 未配置 `typing_throttle_secs` 时同一频道两次发出 typing 帧的最短间隔。
+pub const DEFAULT_TYPING_THROTTLE: Duration = Duration::from_secs(5);
+
+/// 入站 typing 状态的有效期（略长于默认节流间隔，容忍一次推送延迟）。
+pub const TYPING_TTL: Duration = Duration::from_secs(8);
+
+/// 将配置值换算为实际节流间隔（0 = 默认值）。
+pub fn effective_throttle(configured_secs: u32) -> Duration {
+    if configured_secs == 0 {
+        DEFAULT_TYPING_THROTTLE
+    } else {
+        Duration::from_secs(u64::from(configured_secs))
+    }
+}
+
+/// 某频道当前的输入者（`typing-state-changed` 事件载荷）。
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TypingState {
+    pub server_socket: String,
+    pub channel_id: String,
+    /// 正在输入的用户 id（升序）；为空表示无人输入。
+    pub user_ids: Vec<String>,
+}
+
+type ChannelKey = (String, String);
+
+/// 正在输入状态跟踪器。
+#[derive(Debug)]
+pub struct TypingTracker {
+    throttle: Duration,
+    /// 每个频道最近一次放行出站 typing 帧的时间。
+    outbound: HashMap<ChannelKey, Instant>,
+    /// 每个频道的入站输入者 -> 过期时间。
+    inbound: HashMap<ChannelKey, HashMap<String, Instant>>,
+}
+
+impl Default for TypingTracker {
+    fn default() -> Self {
+        Self::new(DEFAULT_TYPING_THROTTLE)
+    }
+}
+
+fn key(server_socket: &str, channel_id: &str) -> ChannelKey {
+    (server_socket.to_string(), channel_id.to_string())
+}
+
+impl TypingTracker {
+    pub fn new(throttle: Duration) -> Self {
+        Self {
+            throttle,
+            outbound: HashMap::new(),
+            inbound: HashMap::new(),
+        }
+    }
+
+    pub fn set_throttle(&mut self, throttle: Duration) {
+        self.throttle = throttle;
+    }
+
+    /// 本地用户在某频道输入；返回是否应发出 typing 帧（放行时记录发送时间）。
+    pub fn should_send(&mut self, server_socket: &str, channel_id: &str, now: Instant) -> bool {
+        let channel_key = key(server_socket, channel_id);
+        let throttled = self
+            .outbound
+            .get(&channel_key)
+            .is_some_and(|sent_at| now.saturating_duration_since(*sent_at) < self.throttle);
+        if !throttled {
+            self.outbound.insert(channel_key, now);
+        }
+        !throttled
+    }
+
+    /// 本地用户已发出消息：清除节流记录，下一次输入立即放行。
+    pub fn reset_outbound(&mut self, server_socket: &str, channel_id: &str) {
+        self.outbound.remove(&key(server_socket, channel_id));
+    }
+
+    /// 记录一条入站 typing 推送；频道输入者集合变化时返回新状态。
+    pub fn observe(
+        &mut self,
+        server_socket: &str,
+        channel_id: &str,
+        user_id: &str,
+        typing: bool,
+        now: Instant,
+    ) -> Option<TypingState> {
+        let channel_key = key(server_socket, channel_id);
+        let changed = if typing {
+            self.inbound
+                .entry(channel_key.clone())
+                .or_default()
+                .insert(user_id.to_string(), now + TYPING_TTL)
+                .is_none()
+        } else {
+            self.inbound
+                .get_mut(&channel_key)
+                .is_some_and(|users| users.remove(user_id).is_some())
+        };
+        changed.then(|| self.state_of(channel_key))
+    }
+
+    /// 当前某频道的输入者。
+    pub fn users(&self, server_socket: &str, channel_id: &str) -> Vec<String> {
+        self.inbound
+            .get(&key(server_socket, channel_id))
+            .map(|users| {
+                users
+                    .keys()
+                    .cloned()
+                    .collect::<BTreeSet<_>>()
+                    .into_iter()
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+
+    /// 移除过期的输入者，返回集合发生变化的频道状态；同时淘汰已过节流期的出站记录。
+    pub fn expire(&mut self, now: Instant) -> Vec<TypingState> {
+        let throttle = self.throttle;
+        self.outbound
+            .retain(|_, sent_at| now.saturating_duration_since(*sent_at) < throttle);
+
+        let mut changed = Vec::new();
+        for (channel_key, users) in &mut self.inbound {
+            let before = users.len();
+            users.retain(|_, expires_at| *expires_at > now);
+            if users.len() != before {
+                changed.push(channel_key.clone());
+            }
+        }
+        let states = changed
+            .into_iter()
+            .map(|channel_key| self.state_of(channel_key))
+            .collect();
+        self.inbound.retain(|_, users| !users.is_empty());
+        states
+    }
+
+    /// 清空某 server 的全部状态（连接移除时调用），返回被清空的频道状态。
+    pub fn clear_server(&mut self, server_socket: &str) -> Vec<TypingState> {
+        self.outbound
+            .retain(|(server, _), _| server != server_socket);
+        let cleared: Vec<ChannelKey> = self
+            .inbound
+            .keys()
+            .filter(|(server, _)| server == server_socket)
+            .cloned()
+            .collect();
+        cleared
+            .into_iter()
+            .map(|channel_key| {
+                self.inbound.remove(&channel_key);
+                self.state_of(channel_key)
+            })
+            .collect()
+    }
+
+    fn state_of(&self, (server_socket, channel_id): ChannelKey) -> TypingState {
+        TypingState {
+            user_ids: self.users(&server_socket, &channel_id),
+            server_socket,
+            channel_id,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn outbound_frames_are_throttled_per_channel() {
+        let mut tracker = TypingTracker::new(Duration::from_secs(5));
+        let t0 = Instant::now();
+        assert!(tracker.should_send("s", "1", t0));
+        assert!(!tracker.should_send("s", "1", t0 + Duration::from_secs(4)));
+        assert!(tracker.should_send("s", "2", t0 + Duration::from_secs(4)));
+        assert!(tracker.should_send("s", "1", t0 + Duration::from_secs(5)));
+
+        tracker.reset_outbound("s", "1");
+        assert!(tracker.should_send("s", "1", t0 + Duration::from_secs(6)));
+    }
+
+    #[test]
+    fn inbound_typing_changes_are_consolidated_and_expire() {
+        let mut tracker = TypingTracker::default();
+        let t0 = Instant::now();
+        let state = tracker.observe("s", "1", "bob", true, t0).unwrap();
+        assert_eq!(state.user_ids, vec!["bob"]);
+        let state = tracker.observe("s", "1", "alice", true, t0).unwrap();
+        assert_eq!(state.user_ids, vec!["alice", "bob"]);
+        // 刷新已有输入者不产生事件，但会延后过期。
+        assert!(
+            tracker
+                .observe("s", "1", "bob", true, t0 + Duration::from_secs(4))
+                .is_none()
+        );
+
+        let expired = tracker.expire(t0 + TYPING_TTL);
+        assert_eq!(expired.len(), 1);
+        assert_eq!(expired[0].user_ids, vec!["bob"]);
+        assert!(tracker.expire(t0 + TYPING_TTL).is_empty());
+
+        let state = tracker.observe("s", "1", "bob", false, t0).unwrap();
+        assert!(state.user_ids.is_empty());
+        assert!(tracker.observe("s", "1", "bob", false, t0).is_none());
+    }
+
+    #[test]
+    fn clearing_a_server_reports_emptied_channels() {
+        let mut tracker = TypingTracker::default();
+        let t0 = Instant::now();
+        tracker.observe("s", "1", "bob", true, t0);
+        tracker.observe("t", "1", "bob", true, t0);
+        assert!(tracker.should_send("s", "1", t0));
+
+        let cleared = tracker.clear_server("s");
+        assert_eq!(cleared.len(), 1);
+        assert_eq!(cleared[0].channel_id, "1");
+        assert!(cleared[0].user_ids.is_empty());
+        assert_eq!(tracker.users("t", "1"), vec!["bob"]);
+        assert!(tracker.should_send("s", "1", t0));
+    }
+}
@@ -3,7 +3,5 @@
 //! 说明：该文件负责导出子模块与组织依赖关系。
 //!
 //! 约定：注释中文，日志英文（tracing）。
-pub mod plugin_manager;
-pub mod plugin_manifest;
 pub mod plugin_ports;
 pub mod plugin_store;
@@ -1,45 +1,21 @@
 //! plugins｜数据适配器：plugin_ports。
 
+use std::path::PathBuf;
+
 use crate::features::plugins::domain::ports::plugin_install_store_port::{
     PluginInstallStoreFuture, PluginInstallStorePort,
 };
-use crate::features::plugins::domain::ports::plugin_loader_port::{
-    PluginLoaderFuture, PluginLoaderPort,
-};
 use crate::features::plugins::domain::types::{
-    InstalledPluginState, PluginFetchResponse, PluginInstallFromUrlRequest, PluginLoadResult,
-    PluginManifest, PluginNetworkFetchRequest, PluginRuntimeEntry,
+    InstalledPluginState, PluginAsset, PluginAutoDisabled, PluginBusMessage, PluginCatalogPage,
+    PluginCatalogQuery, PluginCompatibility, PluginDomainProviderChanged, PluginDomainResolution,
+    PluginFetchResponse, PluginInstallFromUrlRequest, PluginLocaleCatalog,
+    PluginNetworkFetchRequest, PluginPackageValidation, PluginPruneResult,
+    PluginRequirementsReport, PluginRuntimeEntry, PluginRuntimeReady, PluginSettingsSchema,
+    PluginSettingsValues,
 };
 
-use super::plugin_manager::{list_installed_manifests, plugin_manager};
 use super::plugin_store;
 
-#[derive(Debug, Default, Clone, Copy)]
-pub struct PluginLoaderPortAdapter;
-
-impl PluginLoaderPortAdapter {
-    pub fn shared() -> &'static Self {
-        static ADAPTER: PluginLoaderPortAdapter = PluginLoaderPortAdapter;
-        &ADAPTER
-    }
-}
-
-impl PluginLoaderPort for PluginLoaderPortAdapter {
-    fn load_plugin<'a>(
-        &'a self,
-        manifest: PluginManifest,
-    ) -> PluginLoaderFuture<'a, PluginLoadResult> {
-        Box::pin(async move {
-            let manager = plugin_manager()?;
-            manager.load_plugin(manifest).await
-        })
-    }
-
-    fn list_plugins<'a>(&'a self) -> PluginLoaderFuture<'a, Vec<PluginManifest>> {
-        Box::pin(async move { list_installed_manifests().await })
-    }
-}
-
 #[derive(Debug, Default, Clone, Copy)]
 pub struct PluginInstallStorePortAdapter;
 
@@ -76,14 +52,21 @@ impl PluginInstallStorePort for PluginInstallStorePortAdapter {
 
     fn get_runtime_entry<'a>(
         &'a self,
+        webview: &'a str,
         server_socket: &'a str,
         plugin_id: &'a str,
         tls_policy: Option<&'a str>,
         tls_fingerprint: Option<&'a str>,
     ) -> PluginInstallStoreFuture<'a, PluginRuntimeEntry> {
         Box::pin(async move {
-            plugin_store::get_runtime_entry(server_socket, plugin_id, tls_policy, tls_fingerprint)
-                .await
+            plugin_store::get_runtime_entry(
+                webview,
+                server_socket,
+                plugin_id,
+                tls_policy,
+                tls_fingerprint,
+            )
+            .await
         })
     }
 
@@ -107,11 +90,225 @@ impl PluginInstallStorePort for PluginInstallStorePortAdapter {
         })
     }
 
+    fn browse_catalog<'a>(
+        &'a self,
+        request: PluginCatalogQuery<'a>,
+    ) -> PluginInstallStoreFuture<'a, PluginCatalogPage> {
+        Box::pin(async move { plugin_store::browse_catalog(request).await })
+    }
+
+    fn get_assets<'a>(
+        &'a self,
+        server_socket: &'a str,
+        plugin_id: &'a str,
+        version: Option<&'a str>,
+        tls_policy: Option<&'a str>,
+        tls_fingerprint: Option<&'a str>,
+    ) -> PluginInstallStoreFuture<'a, Vec<PluginAsset>> {
+        Box::pin(async move {
+            plugin_store::get_assets(
+                server_socket,
+                plugin_id,
+                version,
+                tls_policy,
+                tls_fingerprint,
+            )
+            .await
+        })
+    }
+
+    fn get_locale<'a>(
+        &'a self,
+        server_socket: &'a str,
+        plugin_id: &'a str,
+        lang: &'a str,
+        tls_policy: Option<&'a str>,
+        tls_fingerprint: Option<&'a str>,
+    ) -> PluginInstallStoreFuture<'a, PluginLocaleCatalog> {
+        Box::pin(async move {
+            plugin_store::get_locale(server_socket, plugin_id, lang, tls_policy, tls_fingerprint)
+                .await
+        })
+    }
+
+    fn get_settings_schema<'a>(
+        &'a self,
+        server_socket: &'a str,
+        plugin_id: &'a str,
+        tls_policy: Option<&'a str>,
+        tls_fingerprint: Option<&'a str>,
+    ) -> PluginInstallStoreFuture<'a, PluginSettingsSchema> {
+        Box::pin(async move {
+            plugin_store::get_settings_schema(server_socket, plugin_id, tls_policy, tls_fingerprint)
+                .await
+        })
+    }
+
+    fn get_settings<'a>(
+        &'a self,
+        server_socket: &'a str,
+        plugin_id: &'a str,
+        tls_policy: Option<&'a str>,
+        tls_fingerprint: Option<&'a str>,
+    ) -> PluginInstallStoreFuture<'a, PluginSettingsValues> {
+        Box::pin(async move {
+            plugin_store::get_settings(server_socket, plugin_id, tls_policy, tls_fingerprint).await
+        })
+    }
+
+    fn update_settings<'a>(
+        &'a self,
+        server_socket: &'a str,
+        plugin_id: &'a str,
+        patch: &'a serde_json::Map<String, serde_json::Value>,
+        tls_policy: Option<&'a str>,
+        tls_fingerprint: Option<&'a str>,
+    ) -> PluginInstallStoreFuture<'a, (PluginSettingsValues, Vec<String>)> {
+        Box::pin(async move {
+            plugin_store::update_settings(
+                server_socket,
+                plugin_id,
+                patch,
+                tls_policy,
+                tls_fingerprint,
+            )
+            .await
+        })
+    }
+
+    fn bus_open<'a>(
+        &'a self,
+        webview: &'a str,
+        server_socket: &'a str,
+        plugin_id: &'a str,
+        tls_policy: Option<&'a str>,
+        tls_fingerprint: Option<&'a str>,
+    ) -> PluginInstallStoreFuture<'a, String> {
+        Box::pin(async move {
+            plugin_store::bus_open(
+                webview,
+                server_socket,
+                plugin_id,
+                tls_policy,
+                tls_fingerprint,
+            )
+            .await
+        })
+    }
+
+    fn bus_publish<'a>(
+        &'a self,
+        webview: &'a str,
+        server_socket: &'a str,
+        bus_token: &'a str,
+        topic: &'a str,
+        payload: serde_json::Value,
+    ) -> PluginInstallStoreFuture<'a, PluginBusMessage> {
+        Box::pin(async move {
+            plugin_store::bus_publish(webview, server_socket, bus_token, topic, payload).await
+        })
+    }
+
+    fn bus_subscribe<'a>(
+        &'a self,
+        webview: &'a str,
+        bus_token: &'a str,
+        topic: &'a str,
+    ) -> PluginInstallStoreFuture<'a, ()> {
+        Box::pin(async move { plugin_store::bus_subscribe(webview, bus_token, topic).await })
+    }
+
+    fn bus_unsubscribe<'a>(
+        &'a self,
+        webview: &'a str,
+        bus_token: &'a str,
+        topic: Option<&'a str>,
+    ) -> PluginInstallStoreFuture<'a, ()> {
+        Box::pin(async move { plugin_store::bus_unsubscribe(webview, bus_token, topic) })
+    }
+
+    fn report_crash<'a>(
+        &'a self,
+        webview: &'a str,
+        server_socket: &'a str,
+        plugin_id: &'a str,
+        message: &'a str,
+        tls_policy: Option<&'a str>,
+        tls_fingerprint: Option<&'a str>,
+    ) -> PluginInstallStoreFuture<'a, Option<PluginAutoDisabled>> {
+        Box::pin(async move {
+            plugin_store::report_crash(
+                webview,
+                server_socket,
+                plugin_id,
+                message,
+                tls_policy,
+                tls_fingerprint,
+            )
+            .await
+        })
+    }
+
+    fn check_compatibility<'a>(
+        &'a self,
+        server_socket: &'a str,
+        plugin_id: &'a str,
+        version: Option<&'a str>,
+        tls_policy: Option<&'a str>,
+        tls_fingerprint: Option<&'a str>,
+    ) -> PluginInstallStoreFuture<'a, PluginCompatibility> {
+        Box::pin(async move {
+            plugin_store::check_compatibility(
+                server_socket,
+                plugin_id,
+                version,
+                tls_policy,
+                tls_fingerprint,
+            )
+            .await
+        })
+    }
+
+    fn check_requirements<'a>(
+        &'a self,
+        server_socket: &'a str,
+        tls_policy: Option<&'a str>,
+        tls_fingerprint: Option<&'a str>,
+    ) -> PluginInstallStoreFuture<'a, PluginRequirementsReport> {
+        Box::pin(async move {
+            plugin_store::check_requirements(server_socket, tls_policy, tls_fingerprint).await
+        })
+    }
+
+    fn resolve_domain<'a>(
+        &'a self,
+        server_socket: &'a str,
+        domain: &'a str,
+        tls_policy: Option<&'a str>,
+        tls_fingerprint: Option<&'a str>,
+    ) -> PluginInstallStoreFuture<'a, PluginDomainResolution> {
+        Box::pin(async move {
+            plugin_store::resolve_domain(server_socket, domain, tls_policy, tls_fingerprint).await
+        })
+    }
+
+    fn refresh_domains<'a>(
+        &'a self,
+        server_socket: &'a str,
+        tls_policy: Option<&'a str>,
+        tls_fingerprint: Option<&'a str>,
+    ) -> PluginInstallStoreFuture<'a, Vec<PluginDomainProviderChanged>> {
+        Box::pin(async move {
+            plugin_store::refresh_domain_registry(server_socket, tls_policy, tls_fingerprint).await
+        })
+    }
+
     fn install_from_server_catalog<'a>(
         &'a self,
         server_socket: &'a str,
         plugin_id: &'a str,
         version: Option<&'a str>,
+        confirm_untrusted: bool,
         tls_policy: Option<&'a str>,
         tls_fingerprint: Option<&'a str>,
     ) -> PluginInstallStoreFuture<'a, InstalledPluginState> {
@@ -120,6 +317,7 @@ impl PluginInstallStorePort for PluginInstallStorePortAdapter {
                 server_socket,
                 plugin_id,
                 version,
+                confirm_untrusted,
                 tls_policy,
                 tls_fingerprint,
             )
@@ -131,18 +329,7 @@ impl PluginInstallStorePort for PluginInstallStorePortAdapter {
         &'a self,
         request: PluginInstallFromUrlRequest<'a>,
     ) -> PluginInstallStoreFuture<'a, InstalledPluginState> {
-        Box::pin(async move {
-            plugin_store::install_from_url(
-                request.server_socket,
-                request.plugin_id,
-                request.version,
-                request.url,
-                request.sha256,
-                request.tls_policy,
-                request.tls_fingerprint,
-            )
-            .await
-        })
+        Box::pin(async move { plugin_store::install_from_url(request).await })
     }
 
     fn enable<'a>(
@@ -206,6 +393,7 @@ impl PluginInstallStorePort for PluginInstallStorePortAdapter {
         server_socket: &'a str,
         plugin_id: &'a str,
         message: &'a str,
+        auto_rollback_after: u32,
         tls_policy: Option<&'a str>,
         tls_fingerprint: Option<&'a str>,
     ) -> PluginInstallStoreFuture<'a, InstalledPluginState> {
@@ -214,6 +402,39 @@ impl PluginInstallStorePort for PluginInstallStorePortAdapter {
                 server_socket,
                 plugin_id,
                 message,
+                auto_rollback_after,
+                tls_policy,
+                tls_fingerprint,
+            )
+            .await
+        })
+    }
+
+    fn rollback<'a>(
+        &'a self,
+        server_socket: &'a str,
+        plugin_id: &'a str,
+        tls_policy: Option<&'a str>,
+        tls_fingerprint: Option<&'a str>,
+    ) -> PluginInstallStoreFuture<'a, InstalledPluginState> {
+        Box::pin(async move {
+            plugin_store::rollback(server_socket, plugin_id, tls_policy, tls_fingerprint).await
+        })
+    }
+
+    fn prune_versions<'a>(
+        &'a self,
+        server_socket: &'a str,
+        plugin_id: &'a str,
+        keep_latest_n: u32,
+        tls_policy: Option<&'a str>,
+        tls_fingerprint: Option<&'a str>,
+    ) -> PluginInstallStoreFuture<'a, PluginPruneResult> {
+        Box::pin(async move {
+            plugin_store::prune_versions(
+                server_socket,
+                plugin_id,
+                keep_latest_n,
                 tls_policy,
                 tls_fingerprint,
             )
@@ -221,6 +442,51 @@ impl PluginInstallStorePort for PluginInstallStorePortAdapter {
         })
     }
 
+    fn prune_all_versions<'a>(
+        &'a self,
+        keep_latest_n: u32,
+    ) -> PluginInstallStoreFuture<'a, Vec<PluginPruneResult>> {
+        Box::pin(async move { plugin_store::prune_all_versions(keep_latest_n).await })
+    }
+
+    fn dev_link<'a>(
+        &'a self,
+        server_id: &'a str,
+        plugin_id: &'a str,
+        path: &'a str,
+    ) -> PluginInstallStoreFuture<'a, (InstalledPluginState, PathBuf)> {
+        Box::pin(async move { plugin_store::dev_link(server_id, plugin_id, path).await })
+    }
+
+    fn dev_unlink<'a>(
+        &'a self,
+        server_id: &'a str,
+        plugin_id: &'a str,
+    ) -> PluginInstallStoreFuture<'a, Option<InstalledPluginState>> {
+        Box::pin(async move { plugin_store::dev_unlink(server_id, plugin_id).await })
+    }
+
+    fn list_dev_links<'a>(
+        &'a self,
+    ) -> PluginInstallStoreFuture<'a, Vec<(String, String, PathBuf)>> {
+        Box::pin(async move { plugin_store::list_dev_links().await })
+    }
+
+    fn import_legacy<'a>(&'a self) -> PluginInstallStoreFuture<'a, usize> {
+        Box::pin(async move { plugin_store::import_legacy_plugins().await })
+    }
+
+    fn preload_enabled<'a>(&'a self) -> PluginInstallStoreFuture<'a, Vec<PluginRuntimeReady>> {
+        Box::pin(async move { plugin_store::preload_enabled().await })
+    }
+
+    fn validate_package<'a>(
+        &'a self,
+        path: &'a str,
+    ) -> PluginInstallStoreFuture<'a, PluginPackageValidation> {
+        Box::pin(async move { plugin_store::validate_package(path).await })
+    }
+
     fn clear_error<'a>(
         &'a self,
         server_socket: &'a str,
@@ -233,6 +499,26 @@ impl PluginInstallStorePort for PluginInstallStorePortAdapter {
         })
     }
 
+    fn mark_loaded<'a>(
+        &'a self,
+        server_socket: &'a str,
+        plugin_id: &'a str,
+        version: &'a str,
+        tls_policy: Option<&'a str>,
+        tls_fingerprint: Option<&'a str>,
+    ) -> PluginInstallStoreFuture<'a, ()> {
+        Box::pin(async move {
+            plugin_store::mark_loaded(
+                server_socket,
+                plugin_id,
+                version,
+                tls_policy,
+                tls_fingerprint,
+            )
+            .await
+        })
+    }
+
     fn storage_get<'a>(
         &'a self,
         server_socket: &'a str,
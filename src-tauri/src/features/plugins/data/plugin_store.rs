@@ -15,33 +15,56 @@
 use std::path::PathBuf;
 
 pub use crate::features::plugins::domain::types::{
-    InstalledPluginState, PluginFetchResponse, PluginProvidesDomain, PluginRuntimeEntry,
+    InstalledPluginState, PluginAsset, PluginAutoDisabled, PluginBusMessage, PluginCatalogPage,
+    PluginCatalogQuery, PluginCompatibility, PluginDomainProviderChanged, PluginDomainResolution,
+    PluginFetchResponse, PluginInstallFromUrlRequest, PluginLocaleCatalog, PluginPackageValidation,
+    PluginProvidesDomain, PluginPruneResult, PluginRequirementsReport, PluginRuntimeEntry,
+    PluginRuntimeReady, PluginSettingsSchema, PluginSettingsValues, PluginTrustLevel,
 };
 use anyhow::Context;
 use serde::{Deserialize, Serialize};
 
 mod api;
+mod assets;
+mod bus;
+mod catalog;
+mod compat;
+mod crash_guard;
+mod csp;
+mod dev_link;
+mod domains;
 mod download;
 mod hash;
 mod json_io;
+mod legacy_import;
+mod locales;
+mod manifest_schema;
 mod net_fetch;
 mod origin;
+mod package_check;
 mod paths;
+mod preload;
+mod prune;
+mod requirements;
+mod settings;
 mod state;
 mod storage;
 mod tls;
+mod trust;
 mod unpack;
 
 use api::{
-    fetch_plugin_catalog, fetch_server_id, fetch_server_id_with_client, get_cached_server_id,
+    fetch_plugin_catalog, fetch_plugin_catalog_cached, fetch_server_id,
+    fetch_server_id_with_client, get_cached_server_id,
 };
+pub use csp::{is_document_mime, plugin_html_csp};
 use download::download_plugin_zip_bytes;
 use hash::{eq_hash_hex, sha256_hex};
 use origin::to_http_origin;
 use paths::{base_plugins_dir, manifest_file_path, plugin_root_dir, plugin_version_dir};
 use state::{
-    PluginCurrent, PluginStateFile, build_installed_state, read_current, write_current,
-    write_state_file,
+    PluginCurrent, PluginStateFile, build_installed_state, read_current, read_history,
+    write_current, write_history, write_state_file,
 };
 use tls::build_server_client;
 use unpack::unpack_plugin_zip;
@@ -74,6 +97,9 @@ pub struct PluginManifestV1 {
     pub permissions: Vec<String>,
     /// 插件提供的 domain 列表。
     pub provides_domains: Vec<PluginProvidesDomain>,
+    /// 插件设置 schema（可选；按字段宽松解析，见 `settings` 子模块）。
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub settings: Option<serde_json::Value>,
 }
 
 // current.json/state.json 的结构体与读写逻辑已下沉到 `state` 子模块。
@@ -123,6 +149,39 @@ pub async fn list_installed(
     Ok(out)
 }
 
+/// 列出本地所有服务端下的已安装插件（不访问网络）。
+///
+/// # 返回值
+/// - `Ok(Vec<(server_id, InstalledPluginState)>)`：按目录扫描得到的安装状态。
+/// - `Err(anyhow::Error)`：插件根目录读取失败。
+pub async fn list_all_installed() -> anyhow::Result<Vec<(String, InstalledPluginState)>> {
+    let base = base_plugins_dir()?;
+    let mut out = Vec::new();
+    let mut servers = match tokio::fs::read_dir(&base).await {
+        Ok(rd) => rd,
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => return Ok(out),
+        Err(err) => return Err(err.into()),
+    };
+    while let Some(server) = servers.next_entry().await? {
+        if !server.file_type().await?.is_dir() {
+            continue;
+        }
+        let server_id = server.file_name().to_string_lossy().to_string();
+        let mut plugins = tokio::fs::read_dir(server.path()).await?;
+        while let Some(plugin) = plugins.next_entry().await? {
+            if !plugin.file_type().await?.is_dir() {
+                continue;
+            }
+            let plugin_id = plugin.file_name().to_string_lossy().to_string();
+            out.push((
+                server_id.clone(),
+                build_installed_state(&server_id, &plugin_id).await?,
+            ));
+        }
+    }
+    Ok(out)
+}
+
 /// 获取某个插件的本地安装状态。
 ///
 /// # 参数
@@ -152,6 +211,7 @@ pub async fn get_installed(
 /// 获取插件“当前版本”的运行时入口信息。
 ///
 /// # 参数
+/// - `webview`：请求入口的 webview label（据此登记一次加载崩溃上报资格，见 [`report_crash`]）。
 /// - `server_socket`：服务端 socket。
 /// - `plugin_id`：插件 id。
 /// - `tls_policy`/`tls_fingerprint`：TLS 相关参数（可选）。
@@ -163,6 +223,7 @@ pub async fn get_installed(
 /// # 说明
 /// 当前版本来自 `current.json`；若插件未安装，会返回错误（而非 `None`）。
 pub async fn get_runtime_entry(
+    webview: &str,
     server_socket: &str,
     plugin_id: &str,
     tls_policy: Option<&str>,
@@ -173,7 +234,11 @@ pub async fn get_runtime_entry(
     let current = read_current(&server_id, plugin_id)
         .await?
         .ok_or_else(|| anyhow::anyhow!("Plugin is not installed: {}", plugin_id))?;
-    get_runtime_entry_for_version_inner(&origin, &server_id, plugin_id, &current.version).await
+    let entry =
+        get_runtime_entry_for_version_inner(&origin, &server_id, plugin_id, &current.version)
+            .await?;
+    crash_guard::grant_report(webview, &server_id, plugin_id);
+    Ok(entry)
 }
 
 /// 获取插件“指定版本”的运行时入口信息。
@@ -214,10 +279,13 @@ async fn get_runtime_entry_for_version_inner(
         .await
         .with_context(|| format!("Failed to read manifest: {}", manifest_path.display()))?;
     let manifest: PluginManifestV1 = serde_json::from_str(&raw).context("Invalid plugin.json")?;
+    compat::ensure_compatible(plugin_id, &manifest.min_host_version)?;
     let entry = manifest.entry.trim().to_string();
     if entry.is_empty() {
         return Err(anyhow::anyhow!("Manifest entry is empty"));
     }
+    let version_dir = plugin_version_dir(server_id, plugin_id, version)?;
+    let available_locales = locales::available_locales(version_dir).await;
     Ok(PluginRuntimeEntry {
         server_id: server_id.to_string(),
         plugin_id: plugin_id.to_string(),
@@ -239,15 +307,441 @@ async fn get_runtime_entry_for_version_inner(
             })
             .filter(|d| !d.domain.is_empty())
             .collect(),
+        locales: available_locales,
     })
 }
 
+/// 获取插件当前版本在指定语言下的语言资源（带回退）。
+///
+/// # 参数
+/// - `server_socket`：服务端 socket。
+/// - `plugin_id`：插件 id。
+/// - `lang`：期望语言（如宿主的 `zh_cn`，或 BCP 47 的 `zh-CN`）。
+/// - `tls_policy`/`tls_fingerprint`：TLS 相关参数（可选）。
+///
+/// # 返回值
+/// - `Ok(PluginLocaleCatalog)`：合并后的消息；插件未携带语言文件时 `messages` 为空。
+/// - `Err(anyhow::Error)`：插件未安装或获取 server_id 失败。
+pub async fn get_locale(
+    server_socket: &str,
+    plugin_id: &str,
+    lang: &str,
+    tls_policy: Option<&str>,
+    tls_fingerprint: Option<&str>,
+) -> anyhow::Result<PluginLocaleCatalog> {
+    let origin = to_http_origin(server_socket)?;
+    let server_id = fetch_server_id(&origin, tls_policy, tls_fingerprint).await?;
+    let current = read_current(&server_id, plugin_id)
+        .await?
+        .ok_or_else(|| anyhow::anyhow!("Plugin is not installed: {}", plugin_id))?;
+    locales::get_locale(&server_id, plugin_id, &current.version, lang).await
+}
+
+/// 获取插件当前版本声明的设置 schema（未声明时 `fields` 为空）。
+///
+/// # 返回值
+/// - `Ok(PluginSettingsSchema)`：设置项声明。
+/// - `Err(anyhow::Error)`：插件未安装或清单不可读。
+pub async fn get_settings_schema(
+    server_socket: &str,
+    plugin_id: &str,
+    tls_policy: Option<&str>,
+    tls_fingerprint: Option<&str>,
+) -> anyhow::Result<PluginSettingsSchema> {
+    let origin = to_http_origin(server_socket)?;
+    let server_id = fetch_server_id(&origin, tls_policy, tls_fingerprint).await?;
+    settings::get_schema(&server_id, plugin_id).await
+}
+
+/// 获取插件设置的生效值（默认值 + 用户修改）。
+///
+/// # 返回值
+/// - `Ok(PluginSettingsValues)`：schema 中每个 key 的生效值。
+/// - `Err(anyhow::Error)`：插件未安装或存储读取失败。
+pub async fn get_settings(
+    server_socket: &str,
+    plugin_id: &str,
+    tls_policy: Option<&str>,
+    tls_fingerprint: Option<&str>,
+) -> anyhow::Result<PluginSettingsValues> {
+    let origin = to_http_origin(server_socket)?;
+    let server_id = fetch_server_id(&origin, tls_policy, tls_fingerprint).await?;
+    settings::get_values(&server_id, plugin_id).await
+}
+
+/// 按 schema 校验并写入设置补丁（`null` 表示恢复默认）。
+///
+/// # 返回值
+/// - `Ok((PluginSettingsValues, Vec<String>))`：更新后的生效值与发生变化的 key。
+/// - `Err(anyhow::Error)`：校验失败（`PluginSettingsInvalid`，整批拒绝）或写入失败。
+pub async fn update_settings(
+    server_socket: &str,
+    plugin_id: &str,
+    patch: &serde_json::Map<String, serde_json::Value>,
+    tls_policy: Option<&str>,
+    tls_fingerprint: Option<&str>,
+) -> anyhow::Result<(PluginSettingsValues, Vec<String>)> {
+    let origin = to_http_origin(server_socket)?;
+    let server_id = fetch_server_id(&origin, tls_policy, tls_fingerprint).await?;
+    settings::update(&server_id, plugin_id, patch).await
+}
+
+/// 为宿主桥打开插件总线会话（插件加载后、首次发布/订阅前调用）。
+///
+/// # 参数
+/// - `webview`：发起调用的 webview label（会话只在该 webview 内有效）。
+/// - `server_socket`：服务端 socket。
+/// - `plugin_id`：宿主桥加载的插件 id。
+/// - `tls_policy`/`tls_fingerprint`：TLS 相关参数（可选）。
+///
+/// # 返回值
+/// - `Ok(String)`：会话 token，后续总线调用据此确定身份。
+/// - `Err(anyhow::Error)`：插件无权使用总线或该插件已有会话（`PluginBusDenied`）。
+pub async fn bus_open(
+    webview: &str,
+    server_socket: &str,
+    plugin_id: &str,
+    tls_policy: Option<&str>,
+    tls_fingerprint: Option<&str>,
+) -> anyhow::Result<String> {
+    let origin = to_http_origin(server_socket)?;
+    let server_id = fetch_server_id(&origin, tls_policy, tls_fingerprint).await?;
+    bus::open_session(webview, &server_id, plugin_id).await
+}
+
+/// 向插件总线发布消息（需 `bus` 权限，topic 须位于插件提供的 domain 下）。
+///
+/// # 参数
+/// - `webview`：发起调用的 webview label。
+/// - `server_socket`：服务端 socket（写入消息，供前端按 server 过滤）。
+/// - `bus_token`：[`bus_open`] 返回的会话 token（发布者身份由其确定）。
+/// - `topic`：消息 topic（如 `emoji/packs`）。
+/// - `payload`：消息载荷（JSON）。
+///
+/// # 返回值
+/// - `Ok(PluginBusMessage)`：含收件人的消息，由 DI 层转发给前端。
+/// - `Err(anyhow::Error)`：会话无效、权限/topic 不合法（`PluginBusDenied`）或读取插件状态失败。
+pub async fn bus_publish(
+    webview: &str,
+    server_socket: &str,
+    bus_token: &str,
+    topic: &str,
+    payload: serde_json::Value,
+) -> anyhow::Result<PluginBusMessage> {
+    let (server_id, plugin_id) = bus::session_identity(webview, bus_token)?;
+    bus::publish(server_socket, &server_id, &plugin_id, topic, payload).await
+}
+
+/// 登记插件总线订阅（`<topic>` 精确匹配，`<prefix>/*` 匹配子 topic）。
+///
+/// # 返回值
+/// - `Ok(())`：已登记。
+/// - `Err(anyhow::Error)`：会话无效、topic 不合法、订阅数超限或插件无权使用总线。
+pub async fn bus_subscribe(webview: &str, bus_token: &str, topic: &str) -> anyhow::Result<()> {
+    let (server_id, plugin_id) = bus::session_identity(webview, bus_token)?;
+    bus::subscribe(&server_id, &plugin_id, topic).await
+}
+
+/// 取消插件总线订阅；`topic` 为 `None` 时清空该插件的全部订阅并关闭会话。
+pub fn bus_unsubscribe(webview: &str, bus_token: &str, topic: Option<&str>) -> anyhow::Result<()> {
+    match topic {
+        Some(topic) => {
+            let (server_id, plugin_id) = bus::session_identity(webview, bus_token)?;
+            bus::unsubscribe(&server_id, &plugin_id, Some(topic));
+        }
+        None => bus::close_session(webview, bus_token),
+    }
+    Ok(())
+}
+
+/// 作废某个 webview 持有的插件会话：总线会话与待上报的加载崩溃资格（页面开始加载时调用）。
+pub fn release_webview(webview: &str) {
+    bus::close_webview_sessions(webview);
+    crash_guard::revoke_reports(webview);
+}
+
+/// 启动预加载：校验所有服务端下已启用的插件，停用损坏的安装（不访问网络）。
+///
+/// # 返回值
+/// - `Ok(Vec<PluginRuntimeReady>)`：按服务端分组的就绪与失败插件。
+/// - `Err(anyhow::Error)`：插件根目录扫描失败。
+pub async fn preload_enabled() -> anyhow::Result<Vec<PluginRuntimeReady>> {
+    preload::preload_enabled().await
+}
+
+/// 浏览服务端插件目录（搜索 + 分页）。
+///
+/// # 参数
+/// - `request`：目录浏览请求（server_socket、搜索词、页码、TLS 参数）。
+///
+/// # 返回值
+/// - `Ok(PluginCatalogPage)`：当前页条目与总数。
+/// - `Err(anyhow::Error)`：获取 server_id 或目录失败。
+///
+/// # 说明
+/// - 目录按 origin 缓存 60 秒，翻页/输入搜索词不会重复请求；
+/// - 图标中的包内相对路径改写为 `app://plugins/...`（安装后由本地文件提供）。
+pub async fn browse_catalog(request: PluginCatalogQuery<'_>) -> anyhow::Result<PluginCatalogPage> {
+    let origin = to_http_origin(request.server_socket)?;
+    let client = build_server_client(&origin, request.tls_policy, request.tls_fingerprint).await?;
+    let server_id = fetch_server_id_with_client(&origin, &client).await?;
+    let catalog = fetch_plugin_catalog_cached(&origin, &client).await?;
+    let mut page = catalog::browse(
+        &server_id,
+        &origin,
+        &catalog,
+        request.query,
+        request.page,
+        request.page_size,
+    );
+
+    // 已安装版本优先使用本地预取的资源；缺失时补齐预取（未安装的版本不创建目录）。
+    let base = reqwest::Url::parse(&origin).context("Invalid server origin")?;
+    for entry in page.items.iter_mut() {
+        let local = match assets::read_assets(&server_id, &entry.plugin_id, &entry.version).await {
+            Ok(Some(list)) => list,
+            _ => {
+                let version_dir = plugin_version_dir(&server_id, &entry.plugin_id, &entry.version)?;
+                let installed = tokio::fs::metadata(&version_dir)
+                    .await
+                    .is_ok_and(|meta| meta.is_dir());
+                let declared = catalog
+                    .plugins
+                    .iter()
+                    .find(|p| p.plugin_id.trim() == entry.plugin_id)
+                    .map(assets::declared_assets)
+                    .unwrap_or_default();
+                if !installed || declared.is_empty() {
+                    continue;
+                }
+                match assets::prefetch_assets(
+                    &base,
+                    &client,
+                    &server_id,
+                    &entry.plugin_id,
+                    &entry.version,
+                    &declared,
+                )
+                .await
+                {
+                    Ok(list) => list,
+                    Err(e) => {
+                        tracing::warn!(action = "plugins_asset_prefetch_failed", plugin_id = %entry.plugin_id, error = %e);
+                        continue;
+                    }
+                }
+            }
+        };
+        if let Some(url) = assets::app_url_of(&local, assets::AssetKind::Icon) {
+            entry.icon_url = Some(url);
+        }
+        if let Some(url) = assets::app_url_of(&local, assets::AssetKind::Banner) {
+            entry.banner_url = Some(url);
+        }
+    }
+    Ok(page)
+}
+
+/// 列出插件某个版本已预取的展示资源（icon/banner）。
+///
+/// # 参数
+/// - `server_socket`：服务端 socket。
+/// - `plugin_id`：插件 id。
+/// - `version`：版本（可选；为空时使用 `current.json` 中的当前版本）。
+/// - `tls_policy`/`tls_fingerprint`：TLS 相关参数（可选）。
+///
+/// # 返回值
+/// - `Ok(Vec<PluginAsset>)`：资源列表（未预取过时为空）。
+/// - `Err(anyhow::Error)`：插件未安装或读取失败。
+pub async fn get_assets(
+    server_socket: &str,
+    plugin_id: &str,
+    version: Option<&str>,
+    tls_policy: Option<&str>,
+    tls_fingerprint: Option<&str>,
+) -> anyhow::Result<Vec<PluginAsset>> {
+    let origin = to_http_origin(server_socket)?;
+    let server_id = fetch_server_id(&origin, tls_policy, tls_fingerprint).await?;
+    let version = match version.map(str::trim).filter(|v| !v.is_empty()) {
+        Some(v) => v.to_string(),
+        None => read_current(&server_id, plugin_id)
+            .await?
+            .map(|current| current.version)
+            .ok_or_else(|| anyhow::anyhow!("Plugin not installed: {}", plugin_id))?,
+    };
+    Ok(assets::read_assets(&server_id, plugin_id, &version)
+        .await?
+        .unwrap_or_default())
+}
+
+/// 重新计算某个服务端的 domain 注册表。
+///
+/// # 参数
+/// - `server_socket`：服务端 socket。
+/// - `tls_policy`/`tls_fingerprint`：TLS 相关参数（可选）。
+///
+/// # 返回值
+/// - `Ok(Vec<PluginDomainProviderChanged>)`：提供方发生变化的 domain（首次构建时为全部 domain）。
+/// - `Err(anyhow::Error)`：获取 server_id 或读取本地状态失败。
+pub async fn refresh_domain_registry(
+    server_socket: &str,
+    tls_policy: Option<&str>,
+    tls_fingerprint: Option<&str>,
+) -> anyhow::Result<Vec<PluginDomainProviderChanged>> {
+    let origin = to_http_origin(server_socket)?;
+    let server_id = fetch_server_id(&origin, tls_policy, tls_fingerprint).await?;
+    domains::refresh(server_socket, &server_id).await
+}
+
+/// 解析某个 domain 当前由哪个启用插件提供。
+///
+/// # 参数
+/// - `server_socket`：服务端 socket。
+/// - `domain`：domain 名称（精确匹配）。
+/// - `tls_policy`/`tls_fingerprint`：TLS 相关参数（可选）。
+///
+/// # 返回值
+/// - `Ok(PluginDomainResolution)`：解析结果（无提供方时 `provider` 为空）。
+/// - `Err(anyhow::Error)`：domain 为空或读取失败。
+pub async fn resolve_domain(
+    server_socket: &str,
+    domain: &str,
+    tls_policy: Option<&str>,
+    tls_fingerprint: Option<&str>,
+) -> anyhow::Result<PluginDomainResolution> {
+    let origin = to_http_origin(server_socket)?;
+    let server_id = fetch_server_id(&origin, tls_policy, tls_fingerprint).await?;
+    domains::resolve(server_socket, &server_id, domain).await
+}
+
+/// 安装后校验宿主版本；不兼容时删除刚解压的版本目录（若它不是当前版本）并返回错误。
+async fn reject_incompatible_version(
+    server_id: &str,
+    plugin_id: &str,
+    version: &str,
+    manifest: &PluginManifestV1,
+) -> anyhow::Result<()> {
+    let Err(e) = compat::ensure_compatible(plugin_id, &manifest.min_host_version) else {
+        return Ok(());
+    };
+    let is_current = read_current(server_id, plugin_id)
+        .await?
+        .is_some_and(|current| current.version == version);
+    if !is_current {
+        let version_dir = plugin_version_dir(server_id, plugin_id, version)?;
+        if let Err(err) = tokio::fs::remove_dir_all(&version_dir).await {
+            tracing::warn!(action = "plugins_incompatible_cleanup_failed", plugin_id = %plugin_id, version = %version, error = %err);
+        }
+    }
+    Err(e)
+}
+
+/// 检查插件某个版本与当前宿主版本是否兼容（安装前提示用）。
+///
+/// # 参数
+/// - `server_socket`：服务端 socket。
+/// - `plugin_id`：插件 id。
+/// - `version`：版本（可选；为空时取目录中的版本）。
+/// - `tls_policy`/`tls_fingerprint`：TLS 相关参数（可选）。
+///
+/// # 返回值
+/// - `Ok(PluginCompatibility)`：兼容性结果。
+/// - `Err(anyhow::Error)`：插件不在目录中、版本不匹配或请求失败。
+///
+/// # 说明
+/// 该版本已安装时以本地 `plugin.json` 为准；否则使用目录声明（与浏览共用 60 秒缓存）。
+pub async fn check_compatibility(
+    server_socket: &str,
+    plugin_id: &str,
+    version: Option<&str>,
+    tls_policy: Option<&str>,
+    tls_fingerprint: Option<&str>,
+) -> anyhow::Result<PluginCompatibility> {
+    let origin = to_http_origin(server_socket)?;
+    let client = build_server_client(&origin, tls_policy, tls_fingerprint).await?;
+    let server_id = fetch_server_id_with_client(&origin, &client).await?;
+    let version = version.map(str::trim).filter(|v| !v.is_empty());
+
+    let local = match version {
+        Some(v) => {
+            let manifest_path = manifest_file_path(&server_id, plugin_id, v)?;
+            match tokio::fs::read_to_string(&manifest_path).await {
+                Ok(raw) => Some(
+                    serde_json::from_str::<PluginManifestV1>(&raw)
+                        .context("Invalid plugin.json")?,
+                ),
+                Err(_) => None,
+            }
+        }
+        None => None,
+    };
+    let (version, min_host_version, source) = match local {
+        Some(manifest) => (
+            manifest.version.trim().to_string(),
+            manifest.min_host_version.trim().to_string(),
+            "installed",
+        ),
+        None => {
+            let catalog = fetch_plugin_catalog_cached(&origin, &client).await?;
+            let target = catalog
+                .plugins
+                .iter()
+                .find(|p| p.plugin_id.trim() == plugin_id)
+                .ok_or_else(|| anyhow::anyhow!("Plugin not found in catalog: {}", plugin_id))?;
+            let catalog_version = target.version.trim();
+            if version.is_some_and(|v| v != catalog_version) {
+                return Err(anyhow::anyhow!(
+                    "Version not available in catalog for {}: {}",
+                    plugin_id,
+                    version.unwrap_or_default()
+                ));
+            }
+            (
+                catalog_version.to_string(),
+                target
+                    .min_host_version
+                    .as_deref()
+                    .unwrap_or_default()
+                    .trim()
+                    .to_string(),
+                "catalog",
+            )
+        }
+    };
+    Ok(PluginCompatibility {
+        plugin_id: plugin_id.to_string(),
+        compatible: compat::is_compatible(&min_host_version),
+        min_host_version: (!min_host_version.is_empty()).then_some(min_host_version),
+        host_version: compat::host_version().to_string(),
+        version,
+        source: source.to_string(),
+    })
+}
+
+/// 检查服务端 `/api/server` 声明的必装插件（缺失/过旧/未启用）。
+///
+/// # 参数
+/// - `server_socket`：服务端 socket。
+/// - `tls_policy`/`tls_fingerprint`：TLS 相关参数（可选）。
+///
+/// # 返回值
+/// - `Ok(PluginRequirementsReport)`：按声明顺序的检查结果（未声明必装插件时为空且 `satisfied`）。
+/// - `Err(anyhow::Error)`：请求 `/api/server` 或目录失败。
+pub async fn check_requirements(
+    server_socket: &str,
+    tls_policy: Option<&str>,
+    tls_fingerprint: Option<&str>,
+) -> anyhow::Result<PluginRequirementsReport> {
+    requirements::check_requirements(server_socket, tls_policy, tls_fingerprint).await
+}
+
 /// 从服务端插件目录（catalog）安装插件。
 ///
 /// # 参数
 /// - `server_socket`：服务端 socket。
 /// - `plugin_id`：插件 id。
 /// - `expected_version`：期望版本（可选；若提供且不匹配则报错）。
+/// - `confirm_untrusted`：用户是否已确认安装未经验证的插件。
 /// - `tls_policy`/`tls_fingerprint`：TLS 相关参数（可选）。
 ///
 /// # 返回值
@@ -255,13 +749,17 @@ async fn get_runtime_entry_for_version_inner(
 /// - `Err(anyhow::Error)`：安装失败原因（下载/校验/解压/写入状态等）。
 ///
 /// # 说明
+/// - 目录条目签名校验后为 `unverified` 且未确认时，下载前返回 `PluginUntrustedSource`；
+///   安装成功后信任级别按版本记入 `trust.json`；
 /// - 会根据 catalog 的 download url + sha256 下载 zip 并做完整性校验；
 /// - 解压后会校验 `plugin.json` 的 `plugin_id/version/entry` 等关键字段；
+/// - 宿主版本低于 `min_host_version` 时拒绝安装（目录声明在下载前校验，清单在解压后校验）；
 /// - 首次安装会初始化 `current.json`（默认 disabled），并将 `state.json` 重置为 ok。
 pub async fn install_from_server_catalog(
     server_socket: &str,
     plugin_id: &str,
     expected_version: Option<&str>,
+    confirm_untrusted: bool,
     tls_policy: Option<&str>,
     tls_fingerprint: Option<&str>,
 ) -> anyhow::Result<InstalledPluginState> {
@@ -292,6 +790,13 @@ pub async fn install_from_server_catalog(
         }
     }
 
+    // 目录已声明最低宿主版本时，下载前即拒绝，避免无谓的下载/解压。
+    if let Some(min) = target.min_host_version.as_deref() {
+        compat::ensure_compatible(plugin_id, min)?;
+    }
+    let trust_level = trust::catalog_trust_level(target);
+    trust::ensure_confirmed(plugin_id, trust_level, confirm_untrusted)?;
+
     let dl = target
         .download
         .as_ref()
@@ -337,7 +842,7 @@ pub async fn install_from_server_catalog(
     let raw = tokio::fs::read_to_string(&manifest_path)
         .await
         .with_context(|| format!("Missing plugin.json at {}", manifest_path.display()))?;
-    let manifest: PluginManifestV1 = serde_json::from_str(&raw).context("Invalid plugin.json")?;
+    let manifest = manifest_schema::parse_manifest(&raw)?;
     let mid = manifest.plugin_id.trim();
     if mid != plugin_id {
         return Err(anyhow::anyhow!(
@@ -354,8 +859,21 @@ pub async fn install_from_server_catalog(
             mv
         ));
     }
-    if manifest.entry.trim().is_empty() {
-        return Err(anyhow::anyhow!("Manifest entry is empty"));
+    reject_incompatible_version(&server_id, plugin_id, &version, &manifest).await?;
+    trust::write_trust_level(&server_id, plugin_id, &version, trust_level).await?;
+
+    // 展示资源预取失败不影响安装结果。
+    if let Err(e) = assets::prefetch_assets(
+        &base,
+        &client,
+        &server_id,
+        plugin_id,
+        &version,
+        &assets::declared_assets(target),
+    )
+    .await
+    {
+        tracing::warn!(action = "plugins_asset_prefetch_failed", plugin_id = %plugin_id, error = %e);
     }
 
     // 首次安装初始化 current.json；若已存在则保留原选择。
@@ -389,12 +907,9 @@ pub async fn install_from_server_catalog(
 /// 从指定 URL 安装插件（自定义来源）。
 ///
 /// # 参数
-/// - `server_socket`：服务端 socket。
-/// - `plugin_id`：插件 id（不能为空）。
-/// - `version`：插件版本（不能为空）。
-/// - `download_url`：插件 zip 下载地址（不能为空）。
-/// - `sha256_expected`：期望 sha256（不能为空）。
-/// - `tls_policy`/`tls_fingerprint`：TLS 相关参数（可选）。
+/// - `request`：安装请求。
+///   - `plugin_id` / `version` / `url` / `sha256`：不能为空；
+///   - `confirm_untrusted`：自定义来源总是 `unverified`，未确认时下载前返回 `PluginUntrustedSource`。
 ///
 /// # 返回值
 /// - `Ok(InstalledPluginState)`：安装后的插件状态。
@@ -403,14 +918,18 @@ pub async fn install_from_server_catalog(
 /// # 说明
 /// 流程与 `install_from_server_catalog` 类似，但安装源由调用方显式指定。
 pub async fn install_from_url(
-    server_socket: &str,
-    plugin_id: &str,
-    version: &str,
-    download_url: &str,
-    sha256_expected: &str,
-    tls_policy: Option<&str>,
-    tls_fingerprint: Option<&str>,
+    request: PluginInstallFromUrlRequest<'_>,
 ) -> anyhow::Result<InstalledPluginState> {
+    let PluginInstallFromUrlRequest {
+        server_socket,
+        plugin_id,
+        version,
+        url: download_url,
+        sha256: sha256_expected,
+        tls_policy,
+        tls_fingerprint,
+        confirm_untrusted,
+    } = request;
     let origin = to_http_origin(server_socket)?;
     let server_client = build_server_client(&origin, tls_policy, tls_fingerprint).await?;
     let server_id = if let Some(cached) = get_cached_server_id(&origin).await {
@@ -435,6 +954,7 @@ pub async fn install_from_url(
     if sha.is_empty() {
         return Err(anyhow::anyhow!("Missing sha256"));
     }
+    trust::ensure_confirmed(id, PluginTrustLevel::Unverified, confirm_untrusted)?;
 
     let base = reqwest::Url::parse(&origin).context("Invalid server origin")?;
     let download_parsed = reqwest::Url::parse(url).context("Invalid download url")?;
@@ -462,7 +982,7 @@ pub async fn install_from_url(
     let raw = tokio::fs::read_to_string(&manifest_path)
         .await
         .with_context(|| format!("Missing plugin.json at {}", manifest_path.display()))?;
-    let manifest: PluginManifestV1 = serde_json::from_str(&raw).context("Invalid plugin.json")?;
+    let manifest = manifest_schema::parse_manifest(&raw)?;
     let mid = manifest.plugin_id.trim();
     if mid != id {
         return Err(anyhow::anyhow!(
@@ -479,9 +999,8 @@ pub async fn install_from_url(
             mv
         ));
     }
-    if manifest.entry.trim().is_empty() {
-        return Err(anyhow::anyhow!("Manifest entry is empty"));
-    }
+    reject_incompatible_version(&server_id, id, v, &manifest).await?;
+    trust::write_trust_level(&server_id, id, v, PluginTrustLevel::Unverified).await?;
 
     let current = read_current(&server_id, id).await?;
     if current.is_none() {
@@ -521,8 +1040,9 @@ pub async fn install_from_url(
 /// - `Err(anyhow::Error)`：启用失败原因。
 ///
 /// # 说明
-/// - 启用前会校验 `plugin.json` 与入口文件是否存在；
-/// - 若入口缺失，会将状态写为 failed 并返回错误，避免 UI “显示可用但无法加载”。
+/// - 启用前会校验 `plugin.json` 与入口文件是否存在，以及 `min_host_version` 是否满足；
+/// - 若入口缺失，会将状态写为 failed 并返回错误，避免 UI “显示可用但无法加载”；
+/// - 不会清零连续失败次数，known-good 版本由 [`mark_loaded`] 在确认加载成功后记录。
 pub async fn enable(
     server_socket: &str,
     plugin_id: &str,
@@ -541,6 +1061,18 @@ pub async fn enable(
         .await
         .with_context(|| format!("Missing plugin.json: {}", manifest_path.display()))?;
     let manifest: PluginManifestV1 = serde_json::from_str(&raw).context("Invalid plugin.json")?;
+    if let Err(e) = compat::ensure_compatible(plugin_id, &manifest.min_host_version) {
+        write_state_file(
+            &server_id,
+            plugin_id,
+            &PluginStateFile {
+                status: "failed".to_string(),
+                last_error: e.to_string(),
+            },
+        )
+        .await?;
+        return Err(e);
+    }
     let entry_rel = manifest.entry.trim();
     let entry_path = plugin_version_dir(&server_id, plugin_id, &current.version)?.join(entry_rel);
     if tokio::fs::metadata(&entry_path).await.is_err() {
@@ -571,25 +1103,65 @@ pub async fn enable(
     build_installed_state(&server_id, plugin_id).await
 }
 
+/// 记录插件运行时加载成功，把该版本记为 known-good。
+///
+/// # 参数
+/// - `server_socket`：服务端 socket。
+/// - `plugin_id`：插件 id。
+/// - `version`：前端实际加载的版本。
+/// - `tls_policy`/`tls_fingerprint`：TLS 相关参数（可选）。
+///
+/// # 返回值
+/// - `Ok(())`：已记录（或版本已切换、插件已停用，无需记录）。
+/// - `Err(anyhow::Error)`：插件未安装或写入失败。
+///
+/// # 说明
+/// - 只有确认加载成功才清零连续失败次数与崩溃记录；[`enable`] 本身不会清零，
+///   避免反复启用坏插件绕过自动回滚/自动停用；
+/// - 加载期间版本被切换或插件被停用时忽略本次上报。
+pub async fn mark_loaded(
+    server_socket: &str,
+    plugin_id: &str,
+    version: &str,
+    tls_policy: Option<&str>,
+    tls_fingerprint: Option<&str>,
+) -> anyhow::Result<()> {
+    let origin = to_http_origin(server_socket)?;
+    let server_id = fetch_server_id(&origin, tls_policy, tls_fingerprint).await?;
+    let current = read_current(&server_id, plugin_id)
+        .await?
+        .ok_or_else(|| anyhow::anyhow!("Plugin is not installed: {}", plugin_id))?;
+    if !current.enabled || current.version != version.trim() {
+        tracing::debug!(action = "plugins_mark_loaded_stale", plugin_id = %plugin_id, version = %version, current = %current.version);
+        return Ok(());
+    }
+    let mut history = read_history(&server_id, plugin_id).await?;
+    history.mark_good(&current.version);
+    write_history(&server_id, plugin_id, &history).await
+}
+
 /// 将插件标记为失败，并写入错误信息。
 ///
 /// # 参数
 /// - `server_socket`：服务端 socket。
 /// - `plugin_id`：插件 id。
 /// - `message`：错误信息（会做 trim）。
+/// - `auto_rollback_after`：连续失败达到该次数时自动回滚到 known-good 版本（0 表示关闭）。
 /// - `tls_policy`/`tls_fingerprint`：TLS 相关参数（可选）。
 ///
 /// # 返回值
-/// - `Ok(InstalledPluginState)`：更新后的插件状态。
+/// - `Ok(InstalledPluginState)`：更新后的插件状态（自动回滚时为回滚后的状态）。
 /// - `Err(anyhow::Error)`：更新失败原因。
 ///
 /// # 说明
 /// - 该操作会强制将 `current.enabled` 置为 false；
-/// - `state.json` 会被写为 `failed` 并更新 `last_error`。
+/// - `state.json` 会被写为 `failed` 并更新 `last_error`；
+/// - 自动回滚失败只记录日志，插件保持 failed 状态。
 pub async fn set_failed(
     server_socket: &str,
     plugin_id: &str,
     message: &str,
+    auto_rollback_after: u32,
     tls_policy: Option<&str>,
     tls_fingerprint: Option<&str>,
 ) -> anyhow::Result<InstalledPluginState> {
@@ -609,9 +1181,232 @@ pub async fn set_failed(
         },
     )
     .await?;
+
+    let mut history = read_history(&server_id, plugin_id).await?;
+    history.consecutive_failures = history.consecutive_failures.saturating_add(1);
+    write_history(&server_id, plugin_id, &history).await?;
+    if auto_rollback_after > 0
+        && history.consecutive_failures >= auto_rollback_after
+        && history.rollback_target(&current.version).is_some()
+    {
+        match rollback_inner(&server_id, plugin_id).await {
+            Ok(state) => {
+                tracing::info!(action = "plugins_auto_rollback_succeeded", plugin_id = %plugin_id, failures = history.consecutive_failures, version = ?state.current_version);
+                return Ok(state);
+            }
+            Err(e) => {
+                tracing::warn!(action = "plugins_auto_rollback_failed", plugin_id = %plugin_id, error = %e);
+            }
+        }
+    }
     build_installed_state(&server_id, plugin_id).await
 }
 
+/// 记录一次插件运行时加载崩溃（前端加载器捕获的 import/activate 失败）。
+///
+/// # 参数
+/// - `webview`：上报方 webview label。
+/// - `server_socket`：服务端 socket。
+/// - `plugin_id`：插件 id。
+/// - `message`：崩溃信息。
+/// - `tls_policy`/`tls_fingerprint`：TLS 相关参数（可选）。
+///
+/// # 返回值
+/// - `Ok(Some(PluginAutoDisabled))`：统计窗口内崩溃次数达到阈值，插件已被自动停用。
+/// - `Ok(None)`：已记录，插件保持原状态。
+/// - `Err(anyhow::Error)`：插件未安装、该 webview 没有待上报的加载或读写状态文件失败。
+///
+/// # 说明
+/// - 与 [`set_failed`] 不同，单次崩溃不会停用插件；只有短时间内反复崩溃才会停用；
+/// - 上报资格由 [`get_runtime_entry`] 按 webview 登记，每次取入口只能上报一次，
+///   其它窗口或未加载该插件的调用方无法借此停用插件。
+pub async fn report_crash(
+    webview: &str,
+    server_socket: &str,
+    plugin_id: &str,
+    message: &str,
+    tls_policy: Option<&str>,
+    tls_fingerprint: Option<&str>,
+) -> anyhow::Result<Option<PluginAutoDisabled>> {
+    let origin = to_http_origin(server_socket)?;
+    let server_id = fetch_server_id(&origin, tls_policy, tls_fingerprint).await?;
+    if read_current(&server_id, plugin_id).await?.is_none() {
+        return Err(anyhow::anyhow!("Plugin is not installed: {}", plugin_id));
+    }
+    if !crash_guard::take_report(webview, &server_id, plugin_id) {
+        return Err(anyhow::anyhow!(
+            "No runtime load of this plugin is pending in the calling window: {}",
+            plugin_id
+        ));
+    }
+    crash_guard::record_crash(&server_id, plugin_id, message).await
+}
+
+/// 回滚到最近一个 known-good 版本（`history.json`）。
+///
+/// # 参数
+/// - `server_socket`：服务端 socket。
+/// - `plugin_id`：插件 id。
+/// - `tls_policy`/`tls_fingerprint`：TLS 相关参数（可选）。
+///
+/// # 返回值
+/// - `Ok(InstalledPluginState)`：回滚后的插件状态（已启用，错误已清除）。
+/// - `Err(anyhow::Error)`：没有可回滚的版本或目标版本校验失败。
+pub async fn rollback(
+    server_socket: &str,
+    plugin_id: &str,
+    tls_policy: Option<&str>,
+    tls_fingerprint: Option<&str>,
+) -> anyhow::Result<InstalledPluginState> {
+    let origin = to_http_origin(server_socket)?;
+    let server_id = fetch_server_id(&origin, tls_policy, tls_fingerprint).await?;
+    rollback_inner(&server_id, plugin_id).await
+}
+
+/// 回滚实现：逐个尝试 known-good 版本，校验失败的版本从历史中移除。
+async fn rollback_inner(server_id: &str, plugin_id: &str) -> anyhow::Result<InstalledPluginState> {
+    let current = read_current(server_id, plugin_id)
+        .await?
+        .ok_or_else(|| anyhow::anyhow!("Plugin is not installed: {}", plugin_id))?;
+    let mut history = read_history(server_id, plugin_id).await?;
+    let target = loop {
+        let Some(candidate) = history
+            .rollback_target(&current.version)
+            .map(str::to_string)
+        else {
+            write_history(server_id, plugin_id, &history).await?;
+            return Err(anyhow::anyhow!(
+                "No known-good version to roll back to: {}",
+                plugin_id
+            ));
+        };
+        match check_version_loadable(server_id, plugin_id, &candidate).await {
+            Ok(()) => break candidate,
+            Err(e) => {
+                tracing::warn!(action = "plugins_rollback_candidate_skipped", plugin_id = %plugin_id, version = %candidate, error = %e);
+                history.forget(&candidate);
+            }
+        }
+    };
+
+    write_current(
+        server_id,
+        plugin_id,
+        &PluginCurrent {
+            version: target.clone(),
+            enabled: true,
+        },
+    )
+    .await?;
+    write_state_file(
+        server_id,
+        plugin_id,
+        &PluginStateFile {
+            status: "ok".to_string(),
+            last_error: "".to_string(),
+        },
+    )
+    .await?;
+    history.mark_good(&target);
+    write_history(server_id, plugin_id, &history).await?;
+    tracing::info!(action = "plugins_rollback_completed", plugin_id = %plugin_id, from = %current.version, to = %target);
+    build_installed_state(server_id, plugin_id).await
+}
+
+/// 校验某个已安装版本可被加载（清单可解析、宿主版本兼容、入口文件存在）。
+async fn check_version_loadable(
+    server_id: &str,
+    plugin_id: &str,
+    version: &str,
+) -> anyhow::Result<()> {
+    let manifest_path = manifest_file_path(server_id, plugin_id, version)?;
+    let raw = tokio::fs::read_to_string(&manifest_path)
+        .await
+        .with_context(|| format!("Missing plugin.json: {}", manifest_path.display()))?;
+    let manifest: PluginManifestV1 = serde_json::from_str(&raw).context("Invalid plugin.json")?;
+    compat::ensure_compatible(plugin_id, &manifest.min_host_version)?;
+    let entry_rel = manifest.entry.trim();
+    if entry_rel.is_empty() {
+        return Err(anyhow::anyhow!("Manifest entry is empty"));
+    }
+    let entry_path = plugin_version_dir(server_id, plugin_id, version)?.join(entry_rel);
+    tokio::fs::metadata(&entry_path)
+        .await
+        .with_context(|| format!("Missing plugin entry: {}", entry_rel))?;
+    Ok(())
+}
+
+/// 校验本地已安装版本的完整性（不访问网络）。
+///
+/// # 说明
+/// 在 [`check_version_loadable`] 的基础上，额外要求清单中的 `plugin_id`/`version`
+/// 与所在目录一致，避免目录被手工改名或覆盖后加载到错误的包。
+pub async fn verify_installed_version(
+    server_id: &str,
+    plugin_id: &str,
+    version: &str,
+) -> anyhow::Result<()> {
+    check_version_loadable(server_id, plugin_id, version).await?;
+    let raw = tokio::fs::read_to_string(manifest_file_path(server_id, plugin_id, version)?).await?;
+    let manifest: PluginManifestV1 = serde_json::from_str(&raw).context("Invalid plugin.json")?;
+    if manifest.plugin_id != plugin_id {
+        return Err(anyhow::anyhow!(
+            "Manifest plugin_id mismatch: {} != {}",
+            manifest.plugin_id,
+            plugin_id
+        ));
+    }
+    if manifest.version != version {
+        return Err(anyhow::anyhow!(
+            "Manifest version mismatch: {} != {}",
+            manifest.version,
+            version
+        ));
+    }
+    Ok(())
+}
+
+/// 将插件标记为损坏：禁用并写入 failed 状态（按 server_id 操作，不访问网络）。
+pub async fn mark_broken(server_id: &str, plugin_id: &str, message: &str) -> anyhow::Result<()> {
+    if let Some(mut current) = read_current(server_id, plugin_id).await? {
+        current.enabled = false;
+        write_current(server_id, plugin_id, &current).await?;
+    }
+    write_state_file(
+        server_id,
+        plugin_id,
+        &PluginStateFile {
+            status: "failed".to_string(),
+            last_error: message.trim().to_string(),
+        },
+    )
+    .await
+}
+
+/// 删除某个非当前的已安装版本，并从 known-good 历史中移除。
+pub async fn remove_installed_version(
+    server_id: &str,
+    plugin_id: &str,
+    version: &str,
+) -> anyhow::Result<()> {
+    let current = read_current(server_id, plugin_id).await?;
+    if current.is_some_and(|c| c.version == version) {
+        return Err(anyhow::anyhow!(
+            "Refusing to remove current plugin version: {}",
+            version
+        ));
+    }
+    match tokio::fs::remove_dir_all(plugin_version_dir(server_id, plugin_id, version)?).await {
+        Ok(_) => {}
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => {}
+        Err(err) => return Err(err.into()),
+    }
+    let mut history = read_history(server_id, plugin_id).await?;
+    history.forget(version);
+    write_history(server_id, plugin_id, &history).await?;
+    trust::forget_trust_level(server_id, plugin_id, version).await
+}
+
 /// 清除插件错误信息（将状态恢复为 ok，清空 last_error）。
 ///
 /// # 参数
@@ -624,7 +1419,7 @@ pub async fn set_failed(
 /// - `Err(anyhow::Error)`：更新失败原因。
 ///
 /// # 说明
-/// 该操作不会修改 `current.enabled`。
+/// 该操作不会修改 `current.enabled`，但会将 `history.json` 中的连续失败次数与崩溃记录清零。
 pub async fn clear_error(
     server_socket: &str,
     plugin_id: &str,
@@ -642,6 +1437,12 @@ pub async fn clear_error(
         },
     )
     .await?;
+    let mut history = read_history(&server_id, plugin_id).await?;
+    if history.consecutive_failures > 0 || !history.recent_crashes.is_empty() {
+        history.consecutive_failures = 0;
+        history.recent_crashes.clear();
+        write_history(&server_id, plugin_id, &history).await?;
+    }
     build_installed_state(&server_id, plugin_id).await
 }
 
@@ -744,6 +1545,100 @@ pub async fn uninstall(
     }
 }
 
+/// 清理某个插件的旧版本。
+///
+/// # 参数
+/// - `server_socket`：服务端 socket。
+/// - `plugin_id`：插件 id。
+/// - `keep_latest_n`：除当前/上一个版本外额外保留的最新版本数。
+/// - `tls_policy`/`tls_fingerprint`：TLS 相关参数（可选）。
+///
+/// # 返回值
+/// - `Ok(PluginPruneResult)`：删除的版本与释放的空间。
+/// - `Err(anyhow::Error)`：读取本地状态失败。
+pub async fn prune_versions(
+    server_socket: &str,
+    plugin_id: &str,
+    keep_latest_n: u32,
+    tls_policy: Option<&str>,
+    tls_fingerprint: Option<&str>,
+) -> anyhow::Result<PluginPruneResult> {
+    let origin = to_http_origin(server_socket)?;
+    let server_id = fetch_server_id(&origin, tls_policy, tls_fingerprint).await?;
+    prune::prune_plugin(&server_id, plugin_id, keep_latest_n).await
+}
+
+/// 清理本地所有插件的旧版本（自动清理策略使用）。
+///
+/// # 返回值
+/// - `Ok(Vec<PluginPruneResult>)`：有版本被删除的插件列表。
+/// - `Err(anyhow::Error)`：插件根目录读取失败。
+pub async fn prune_all_versions(keep_latest_n: u32) -> anyhow::Result<Vec<PluginPruneResult>> {
+    prune::prune_all(keep_latest_n).await
+}
+
+/// 将本地目录链接为插件的开发态版本（`dev`），并切换为当前版本。
+///
+/// # 参数
+/// - `server_id`：服务端 id（与 `app://plugins/{server_id}/...` 一致）。
+/// - `plugin_id`：插件 id（需与目录内 `plugin.json` 一致）。
+/// - `path`：本地插件源目录。
+///
+/// # 返回值
+/// - `Ok((InstalledPluginState, PathBuf))`：链接后的安装状态与源目录 canonical 路径。
+/// - `Err(anyhow::Error)`：目录不存在、清单非法或链接创建失败。
+pub async fn dev_link(
+    server_id: &str,
+    plugin_id: &str,
+    path: &str,
+) -> anyhow::Result<(InstalledPluginState, PathBuf)> {
+    let source = dev_link::link(server_id, plugin_id, std::path::Path::new(path)).await?;
+    let state = build_installed_state(server_id, plugin_id).await?;
+    tracing::info!(action = "plugins_dev_linked", plugin_id = %plugin_id, path = %source.display());
+    Ok((state, source))
+}
+
+/// 取消插件的开发态链接（只删除链接，不触及源目录）。
+///
+/// # 返回值
+/// - `Ok(Option<InstalledPluginState>)`：回落后的安装状态；没有正式版本时为 `None`。
+/// - `Err(anyhow::Error)`：删除链接或写入状态失败。
+pub async fn dev_unlink(
+    server_id: &str,
+    plugin_id: &str,
+) -> anyhow::Result<Option<InstalledPluginState>> {
+    dev_link::unlink(server_id, plugin_id).await?;
+    tracing::info!(action = "plugins_dev_unlinked", plugin_id = %plugin_id);
+    if !tokio::fs::try_exists(plugin_root_dir(server_id, plugin_id)?).await? {
+        return Ok(None);
+    }
+    Ok(Some(build_installed_state(server_id, plugin_id).await?))
+}
+
+/// 列出本地所有开发态链接：`(server_id, plugin_id, 源目录)`（用于启动时恢复目录监听）。
+pub async fn list_dev_links() -> anyhow::Result<Vec<(String, String, PathBuf)>> {
+    dev_link::list_links().await
+}
+
+/// 发布前自检插件包（zip 文件或已构建的插件目录），一次性返回全部问题。
+///
+/// # 返回值
+/// - `Ok(PluginPackageValidation)`：自检结果（`valid = false` 时 `issues` 非空）。
+/// - `Err(anyhow::Error)`：路径不存在或不是有效的 zip 文件。
+pub async fn validate_package(path: &str) -> anyhow::Result<PluginPackageValidation> {
+    package_check::validate_package(PathBuf::from(path.trim())).await
+}
+
+/// 将旧 wasm 加载器遗留的 `plugins.json`/`plugin_cache` 一次性导入插件存储。
+///
+/// # 返回值
+/// - `Ok(usize)`：导入的插件数（已迁移或无旧数据时为 0）。
+/// - `Err(anyhow::Error)`：旧清单读取/解析失败。
+pub async fn import_legacy_plugins() -> anyhow::Result<usize> {
+    let app_data_dir = crate::shared::app_data_dir::get_app_data_dir()?;
+    legacy_import::import(&app_data_dir).await
+}
+
 /// 解析 `app://plugins/...` 自定义 scheme 对应的本地文件路径。
 ///
 /// 说明：
@@ -7,11 +7,14 @@
 use anyhow::Context;
 use serde::Deserialize;
 use std::collections::HashMap;
-use std::sync::OnceLock;
+use std::sync::{Arc, OnceLock};
+use std::time::{Duration, Instant};
 use tokio::sync::RwLock;
 
+use super::legacy_import::adopt_for_origin;
 use super::paths::base_plugins_dir;
 use super::tls::build_server_client;
+use crate::features::plugins::domain::types::{PluginProvidesDomain, PluginTrustLevel};
 use crate::shared::net::headers::API_ACCEPT_V1;
 
 #[derive(Debug, Clone, Deserialize)]
@@ -20,6 +23,26 @@ struct ApiServerInfo {
     server_id: String,
 }
 
+/// `/api/server` 中的必装插件声明（单独解析，避免格式问题影响 server_id 获取）。
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(rename_all = "snake_case")]
+struct ApiServerRequirements {
+    #[serde(default)]
+    required_plugins: Vec<ApiRequiredPlugin>,
+}
+
+/// 必装插件：兼容纯 id 与带最低版本的对象两种写法。
+#[derive(Debug, Clone, Deserialize)]
+#[serde(untagged)]
+pub(super) enum ApiRequiredPlugin {
+    Id(String),
+    Spec {
+        plugin_id: String,
+        #[serde(default)]
+        min_version: Option<String>,
+    },
+}
+
 #[derive(Debug, Clone, Deserialize)]
 #[serde(rename_all = "snake_case")]
 pub(super) struct ApiPluginCatalog {
@@ -32,6 +55,38 @@ pub(super) struct ApiCatalogItem {
     pub(super) plugin_id: String,
     pub(super) version: String,
     pub(super) download: Option<ApiDownload>,
+    // 以下为目录浏览用的展示字段，旧服务端可能缺省。
+    #[serde(default)]
+    pub(super) name: Option<String>,
+    #[serde(default)]
+    pub(super) description: Option<String>,
+    #[serde(default)]
+    pub(super) author: Option<String>,
+    #[serde(default)]
+    pub(super) icon: Option<String>,
+    #[serde(default)]
+    pub(super) banner: Option<String>,
+    #[serde(default)]
+    pub(super) min_host_version: Option<String>,
+    #[serde(default)]
+    pub(super) required: bool,
+    #[serde(default)]
+    pub(super) permissions: Vec<String>,
+    #[serde(default)]
+    pub(super) provides_domains: Vec<PluginProvidesDomain>,
+    // 信任级别声明与签名（见 `trust`），旧服务端可能缺省。
+    #[serde(default)]
+    pub(super) trust_level: Option<PluginTrustLevel>,
+    #[serde(default)]
+    pub(super) signature: Option<ApiPluginSignature>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub(super) struct ApiPluginSignature {
+    pub(super) key_id: String,
+    /// base64 编码的 Ed25519 签名。
+    pub(super) signature: String,
 }
 
 #[derive(Debug, Clone, Deserialize)]
@@ -41,6 +96,16 @@ pub(super) struct ApiDownload {
     pub(super) sha256: String,
 }
 
+/// 目录浏览缓存有效期（翻页/搜索期间复用同一份目录）。
+const CATALOG_CACHE_TTL: Duration = Duration::from_secs(60);
+
+type CatalogCache = HashMap<String, (Instant, Arc<ApiPluginCatalog>)>;
+static CATALOG_CACHE: OnceLock<RwLock<CatalogCache>> = OnceLock::new();
+
+fn catalog_cache() -> &'static RwLock<CatalogCache> {
+    CATALOG_CACHE.get_or_init(|| RwLock::new(HashMap::new()))
+}
+
 type ServerIdCache = HashMap<String, String>;
 static SERVER_ID_CACHE: OnceLock<RwLock<ServerIdCache>> = OnceLock::new();
 
@@ -74,6 +139,24 @@ async fn persist_server_id_cache_file(cache: &ServerIdCache) {
     }
 }
 
+/// 反查已缓存的 server_id 对应的 origin（同步读取，供 scheme handler 使用）。
+pub(super) fn origins_for_server_id(server_id: &str) -> Vec<String> {
+    let mut cache: ServerIdCache = std::fs::read_to_string(server_id_cache_file())
+        .ok()
+        .and_then(|raw| serde_json::from_str(&raw).ok())
+        .unwrap_or_default();
+    if let Ok(memory) = server_id_cache().try_read() {
+        cache.extend(memory.iter().map(|(k, v)| (k.clone(), v.clone())));
+    }
+    let mut origins: Vec<String> = cache
+        .into_iter()
+        .filter(|(_, id)| id == server_id)
+        .map(|(origin, _)| origin)
+        .collect();
+    origins.sort();
+    origins
+}
+
 pub(super) async fn get_cached_server_id(origin: &str) -> Option<String> {
     let key = origin.trim().to_string();
     if key.is_empty() {
@@ -125,14 +208,23 @@ async fn fetch_server_id_network(origin: &str, client: &reqwest::Client) -> anyh
     Ok(id)
 }
 
+/// server_id 解析完成后，把来源为该 origin 的旧插件移入其分区（best-effort）。
+async fn adopt_legacy(origin: &str, server_id: String) -> String {
+    if let Err(e) = adopt_for_origin(origin, &server_id).await {
+        tracing::warn!(action = "plugins_legacy_adopt_failed", origin = %origin, error = %e);
+    }
+    server_id
+}
+
 pub(super) async fn fetch_server_id_with_client(
     origin: &str,
     client: &reqwest::Client,
 ) -> anyhow::Result<String> {
-    if let Some(cached) = get_cached_server_id(origin).await {
-        return Ok(cached);
-    }
-    fetch_server_id_network(origin, client).await
+    let id = match get_cached_server_id(origin).await {
+        Some(cached) => cached,
+        None => fetch_server_id_network(origin, client).await?,
+    };
+    Ok(adopt_legacy(origin, id).await)
 }
 
 pub(super) async fn fetch_server_id(
@@ -140,11 +232,35 @@ pub(super) async fn fetch_server_id(
     tls_policy: Option<&str>,
     tls_fingerprint: Option<&str>,
 ) -> anyhow::Result<String> {
-    if let Some(cached) = get_cached_server_id(origin).await {
-        return Ok(cached);
-    }
-    let client = build_server_client(origin, tls_policy, tls_fingerprint).await?;
-    fetch_server_id_network(origin, &client).await
+    let id = match get_cached_server_id(origin).await {
+        Some(cached) => cached,
+        None => {
+            let client = build_server_client(origin, tls_policy, tls_fingerprint).await?;
+            fetch_server_id_network(origin, &client).await?
+        }
+    };
+    Ok(adopt_legacy(origin, id).await)
+}
+
+/// 拉取 `/api/server` 声明的必装插件（不缓存：服务端可能随时调整）。
+pub(super) async fn fetch_required_plugins(
+    origin: &str,
+    client: &reqwest::Client,
+) -> anyhow::Result<Vec<ApiRequiredPlugin>> {
+    let url = format!("{}/api/server", origin);
+    let res = client
+        .get(url)
+        .header("Accept", API_ACCEPT_V1)
+        .send()
+        .await
+        .context("Failed to request /api/server")?
+        .error_for_status()
+        .context("GET /api/server returned an error status")?;
+    let info: ApiServerRequirements = res
+        .json()
+        .await
+        .context("Failed to parse /api/server JSON")?;
+    Ok(info.required_plugins)
 }
 
 pub(super) async fn fetch_plugin_catalog(
@@ -164,3 +280,22 @@ pub(super) async fn fetch_plugin_catalog(
         .await
         .context("Failed to parse /api/plugins/catalog JSON")
 }
+
+/// 带短时缓存的目录读取（仅用于浏览；安装流程始终实时拉取以拿到最新下载指针）。
+pub(super) async fn fetch_plugin_catalog_cached(
+    origin: &str,
+    client: &reqwest::Client,
+) -> anyhow::Result<Arc<ApiPluginCatalog>> {
+    let key = origin.trim().to_string();
+    if let Some((fetched_at, catalog)) = catalog_cache().read().await.get(&key)
+        && fetched_at.elapsed() < CATALOG_CACHE_TTL
+    {
+        return Ok(catalog.clone());
+    }
+    let catalog = Arc::new(fetch_plugin_catalog(origin, client).await?);
+    catalog_cache()
+        .write()
+        .await
+        .insert(key, (Instant::now(), catalog.clone()));
+    Ok(catalog)
+}
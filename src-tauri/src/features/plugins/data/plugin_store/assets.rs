@@ -0,0 +1,245 @@
+//! plugin_store｜展示资源（icon/banner）预取。
+//!
+//! 说明：
+//! - 目录中声明的远程图标/横幅在安装（以及浏览已安装版本）时下载到版本目录 `_assets/` 下，
+//!   并以 `app://plugins/...` 对外暴露，避免 UI 直接请求远程地址（泄露访问 + 离线失效）；
+//! - 仅下载与服务端同源的资源（沿用插件包的同源策略），跨域地址直接忽略；
+//! - 资源清单写入 `_assets/index.json`，供 `plugins_get_assets` 与目录浏览读取。
+
+use anyhow::Context;
+
+use super::api::ApiCatalogItem;
+use super::download::is_same_origin;
+use super::json_io::{read_json_file, write_json_file};
+use super::paths::{app_plugins_url, plugin_version_dir, resolve_app_plugins_path};
+use crate::features::plugins::domain::types::PluginAsset;
+
+/// 版本目录下存放预取资源的子目录。
+const ASSETS_DIR: &str = "_assets";
+const ASSETS_INDEX: &str = "index.json";
+/// 单个资源大小上限。
+const ASSET_MAX_BYTES: usize = 2 * 1024 * 1024;
+
+/// 资源类型。
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(super) enum AssetKind {
+    Icon,
+    Banner,
+}
+
+impl AssetKind {
+    fn as_str(self) -> &'static str {
+        match self {
+            Self::Icon => "icon",
+            Self::Banner => "banner",
+        }
+    }
+}
+
+/// 目录条目声明的资源来源。
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum AssetSource {
+    /// 需要下载的地址（绝对 http(s) 或以 `/` 开头的服务端路径）。
+    Remote(String),
+    /// 插件包内相对路径（安装后即存在于版本目录）。
+    Package(String),
+}
+
+fn classify_source(raw: &str) -> Option<AssetSource> {
+    let raw = raw.trim();
+    if raw.is_empty() {
+        return None;
+    }
+    if raw.starts_with("https://") || raw.starts_with("http://") || raw.starts_with('/') {
+        return Some(AssetSource::Remote(raw.to_string()));
+    }
+    if raw.contains(':') {
+        // data:/javascript:/其它 scheme 一律不接受。
+        return None;
+    }
+    Some(AssetSource::Package(raw.to_string()))
+}
+
+/// 目录条目声明的全部资源。
+pub(super) fn declared_assets(item: &ApiCatalogItem) -> Vec<(AssetKind, String)> {
+    [
+        (AssetKind::Icon, item.icon.as_deref()),
+        (AssetKind::Banner, item.banner.as_deref()),
+    ]
+    .into_iter()
+    .filter_map(|(kind, raw)| {
+        let raw = raw?.trim();
+        (!raw.is_empty()).then(|| (kind, raw.to_string()))
+    })
+    .collect()
+}
+
+/// 根据 Content-Type 选择扩展名；非图片返回 `None`。
+fn ext_for_content_type(content_type: &str) -> Option<&'static str> {
+    let mime = content_type
+        .split(';')
+        .next()
+        .unwrap_or_default()
+        .trim()
+        .to_ascii_lowercase();
+    match mime.as_str() {
+        "image/png" => Some("png"),
+        "image/jpeg" | "image/jpg" => Some("jpg"),
+        "image/gif" => Some("gif"),
+        "image/webp" => Some("webp"),
+        "image/svg+xml" => Some("svg"),
+        "image/x-icon" | "image/vnd.microsoft.icon" => Some("ico"),
+        _ => None,
+    }
+}
+
+async fn download_asset(
+    origin: &reqwest::Url,
+    client: &reqwest::Client,
+    url: &reqwest::Url,
+) -> anyhow::Result<(Vec<u8>, &'static str)> {
+    if !is_same_origin(url, origin) {
+        return Err(anyhow::anyhow!(
+            "Cross-origin plugin asset rejected: {}",
+            url
+        ));
+    }
+    let res = client
+        .get(url.clone())
+        .send()
+        .await
+        .context("Failed to request plugin asset")?
+        .error_for_status()
+        .context("Plugin asset request returned an error status")?;
+    if res
+        .content_length()
+        .is_some_and(|len| len > ASSET_MAX_BYTES as u64)
+    {
+        return Err(anyhow::anyhow!("Plugin asset too large: {}", url));
+    }
+    let ext = res
+        .headers()
+        .get(reqwest::header::CONTENT_TYPE)
+        .and_then(|v| v.to_str().ok())
+        .and_then(ext_for_content_type)
+        .ok_or_else(|| anyhow::anyhow!("Plugin asset is not an image: {}", url))?;
+    let bytes = res
+        .bytes()
+        .await
+        .context("Failed to read plugin asset bytes")?;
+    if bytes.len() > ASSET_MAX_BYTES {
+        return Err(anyhow::anyhow!("Plugin asset too large: {}", url));
+    }
+    Ok((bytes.to_vec(), ext))
+}
+
+/// 预取目录声明的资源到版本目录，并写入资源清单。
+///
+/// # 说明
+/// - 单个资源失败只记录日志并跳过，不影响安装结果；
+/// - 包内相对路径不下载，仅在文件存在时登记。
+pub(super) async fn prefetch_assets(
+    origin: &reqwest::Url,
+    client: &reqwest::Client,
+    server_id: &str,
+    plugin_id: &str,
+    version: &str,
+    declared: &[(AssetKind, String)],
+) -> anyhow::Result<Vec<PluginAsset>> {
+    let version_dir = plugin_version_dir(server_id, plugin_id, version)?;
+    let mut assets = Vec::new();
+    for (kind, raw) in declared {
+        let rel_path = match classify_source(raw) {
+            Some(AssetSource::Remote(src)) => {
+                let fetched = match origin.join(&src) {
+                    Ok(url) => download_asset(origin, client, &url).await,
+                    Err(e) => Err(anyhow::anyhow!("Invalid plugin asset url: {}", e)),
+                };
+                let (bytes, ext) = match fetched {
+                    Ok(v) => v,
+                    Err(e) => {
+                        tracing::warn!(action = "plugins_asset_prefetch_skipped", plugin_id = %plugin_id, kind = kind.as_str(), error = %e);
+                        continue;
+                    }
+                };
+                let rel = format!("{ASSETS_DIR}/{}.{ext}", kind.as_str());
+                let path = version_dir.join(&rel);
+                if let Some(parent) = path.parent() {
+                    tokio::fs::create_dir_all(parent).await?;
+                }
+                tokio::fs::write(&path, &bytes)
+                    .await
+                    .with_context(|| format!("Failed to write plugin asset: {}", path.display()))?;
+                rel
+            }
+            Some(AssetSource::Package(rel)) => rel,
+            None => continue,
+        };
+        let path = resolve_app_plugins_path(server_id, plugin_id, version, &rel_path)?;
+        let Ok(meta) = tokio::fs::metadata(&path).await else {
+            continue;
+        };
+        assets.push(PluginAsset {
+            kind: kind.as_str().to_string(),
+            source_url: raw.clone(),
+            app_url: app_plugins_url(server_id, plugin_id, version, &rel_path)?,
+            rel_path,
+            size: meta.len(),
+        });
+    }
+    write_json_file(&version_dir.join(ASSETS_DIR).join(ASSETS_INDEX), &assets).await?;
+    Ok(assets)
+}
+
+/// 读取版本目录的资源清单（未预取过时返回 `None`）。
+pub(super) async fn read_assets(
+    server_id: &str,
+    plugin_id: &str,
+    version: &str,
+) -> anyhow::Result<Option<Vec<PluginAsset>>> {
+    let path = plugin_version_dir(server_id, plugin_id, version)?
+        .join(ASSETS_DIR)
+        .join(ASSETS_INDEX);
+    read_json_file::<Vec<PluginAsset>>(&path).await
+}
+
+/// 取某类资源的本地地址。
+pub(super) fn app_url_of(assets: &[PluginAsset], kind: AssetKind) -> Option<String> {
+    assets
+        .iter()
+        .find(|a| a.kind == kind.as_str())
+        .map(|a| a.app_url.clone())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sources_are_classified() {
+        assert_eq!(
+            classify_source("https://srv.example/icon.png"),
+            Some(AssetSource::Remote("https://srv.example/icon.png".into()))
+        );
+        assert_eq!(
+            classify_source("/static/icon.png"),
+            Some(AssetSource::Remote("/static/icon.png".into()))
+        );
+        assert_eq!(
+            classify_source("assets/icon.png"),
+            Some(AssetSource::Package("assets/icon.png".into()))
+        );
+        assert_eq!(classify_source("data:image/png;base64,AAAA"), None);
+        assert_eq!(classify_source("  "), None);
+    }
+
+    #[test]
+    fn only_images_are_accepted() {
+        assert_eq!(
+            ext_for_content_type("image/PNG; charset=binary"),
+            Some("png")
+        );
+        assert_eq!(ext_for_content_type("image/svg+xml"), Some("svg"));
+        assert_eq!(ext_for_content_type("text/html"), None);
+    }
+}
@@ -0,0 +1,391 @@
+//! plugin_store｜插件间消息总线（`plugins_bus_publish` / `plugins_bus_subscribe`）。
+//!
+//! 说明：
+//! - 发布与订阅都要求插件处于启用、状态正常，且清单声明了 `bus` 权限；
+//! - 发布的 topic 必须位于插件 `provides_domains` 派生的前缀下（`<domain>` 或 `<domain>/...`），
+//!   插件不能冒充其它 domain 发消息；订阅不限 topic（`<topic>` 精确匹配，`<prefix>/*` 匹配子 topic）；
+//! - 发布者/订阅者身份不由调用方声明：宿主桥（前端 host API）在插件加载时以 [`open_session`] 换取会话 token，
+//!   之后的发布/订阅只携带 token，由本模块解析出 server_id + plugin_id；会话绑定打开它的 webview，
+//!   同一 webview 内同一插件只能有一个会话（不能顶替已加载插件的身份），webview 重新加载页面时全部作废；
+//! - 订阅只登记在内存中（按 server_id + plugin_id），运行时重新加载插件时需重新登记；
+//!   收件人在发布时按当前启用状态过滤，禁用/卸载后的残留订阅不会再收到消息；
+//! - 只投递给前端运行时：DI 层把消息转为 `plugins-bus-message` 事件，由各插件的 host API 按收件人过滤；
+//!   插件后端（`backend.wasm`）不接入总线。
+
+use std::collections::{BTreeSet, HashMap};
+use std::sync::Mutex;
+
+use serde_json::Value;
+
+use super::PluginManifestV1;
+use super::domains::enabled_manifests;
+use crate::features::plugins::domain::types::{PluginBusDenied, PluginBusMessage};
+
+/// 总线权限 key。
+const BUS_PERMISSION: &str = "bus";
+/// topic 最大长度（字节）。
+const MAX_TOPIC_LEN: usize = 128;
+/// 单条消息载荷上限（序列化后）。
+const MAX_PAYLOAD_BYTES: usize = 64 * 1024;
+/// 单个插件的订阅数上限。
+const MAX_SUBSCRIPTIONS_PER_PLUGIN: usize = 64;
+
+/// （server_id, plugin_id）。
+type PluginKey = (String, String);
+
+/// 总线会话：打开它的 webview 与解析出的插件身份。
+struct BusSession {
+    webview: String,
+    plugin: PluginKey,
+}
+
+static SUBSCRIPTIONS: Mutex<Option<HashMap<PluginKey, BTreeSet<String>>>> = Mutex::new(None);
+/// token -> 会话。
+static SESSIONS: Mutex<Option<HashMap<String, BusSession>>> = Mutex::new(None);
+
+fn key(server_id: &str, plugin_id: &str) -> PluginKey {
+    (server_id.to_string(), plugin_id.to_string())
+}
+
+/// topic：`/` 分隔的非空段，段内只允许字母数字与 `.` `_` `-` `:`。
+fn is_valid_topic(topic: &str) -> bool {
+    !topic.is_empty()
+        && topic.len() <= MAX_TOPIC_LEN
+        && topic.split('/').all(|seg| {
+            !seg.is_empty()
+                && seg
+                    .chars()
+                    .all(|c| c.is_ascii_alphanumeric() || matches!(c, '.' | '_' | '-' | ':'))
+        })
+}
+
+/// 订阅模式：精确 topic，或以 `/*` 结尾的前缀。
+fn is_valid_pattern(pattern: &str) -> bool {
+    is_valid_topic(pattern.strip_suffix("/*").unwrap_or(pattern))
+}
+
+/// `topic` 等于 `prefix` 或位于其下一级及更深处。
+fn is_under(topic: &str, prefix: &str) -> bool {
+    topic == prefix
+        || topic
+            .strip_prefix(prefix)
+            .is_some_and(|rest| rest.starts_with('/'))
+}
+
+fn pattern_matches(pattern: &str, topic: &str) -> bool {
+    match pattern.strip_suffix("/*") {
+        Some(prefix) => topic != prefix && is_under(topic, prefix),
+        None => pattern == topic,
+    }
+}
+
+fn has_bus_permission(manifest: &PluginManifestV1) -> bool {
+    manifest
+        .permissions
+        .iter()
+        .any(|p| p.trim() == BUS_PERMISSION)
+}
+
+/// 插件可发布的 topic 前缀（即其提供的 domain）。
+fn publish_prefixes(manifest: &PluginManifestV1) -> Vec<&str> {
+    manifest
+        .provides_domains
+        .iter()
+        .map(|d| d.domain.trim())
+        .filter(|d| !d.is_empty())
+        .collect()
+}
+
+fn denied(plugin_id: &str, reason: impl Into<String>) -> anyhow::Error {
+    PluginBusDenied {
+        plugin_id: plugin_id.to_string(),
+        reason: reason.into(),
+    }
+    .into()
+}
+
+/// 读取可使用总线的插件清单（启用且声明 `bus` 权限）；`plugin_id` 不在其中时拒绝。
+async fn bus_manifests(server_id: &str, plugin_id: &str) -> anyhow::Result<Vec<PluginManifestV1>> {
+    let manifests: Vec<PluginManifestV1> = enabled_manifests(server_id)
+        .await?
+        .into_iter()
+        .filter(has_bus_permission)
+        .collect();
+    if !manifests.iter().any(|m| m.plugin_id.trim() == plugin_id) {
+        return Err(denied(
+            plugin_id,
+            "plugin is not enabled or does not declare the `bus` permission",
+        ));
+    }
+    Ok(manifests)
+}
+
+/// 生成会话 token（两个 UUIDv4 拼接，64 位 hex）。
+fn new_session_token() -> String {
+    format!(
+        "{}{}",
+        uuid::Uuid::new_v4().simple(),
+        uuid::Uuid::new_v4().simple()
+    )
+}
+
+/// 为 `webview` 中加载的插件打开总线会话（插件需启用且声明 `bus` 权限）。
+///
+/// # 返回值
+/// - `Ok(token)`：后续发布/订阅携带的会话 token。
+/// - `Err(anyhow::Error)`：插件无权使用总线，或该 webview 中已有该插件的会话（`PluginBusDenied`）。
+pub(super) async fn open_session(
+    webview: &str,
+    server_id: &str,
+    plugin_id: &str,
+) -> anyhow::Result<String> {
+    bus_manifests(server_id, plugin_id).await?;
+    let plugin = key(server_id, plugin_id);
+    let mut guard = SESSIONS.lock().unwrap_or_else(|p| p.into_inner());
+    let sessions = guard.get_or_insert_with(HashMap::new);
+    if sessions
+        .values()
+        .any(|s| s.webview == webview && s.plugin == plugin)
+    {
+        return Err(denied(
+            plugin_id,
+            "a bus session is already open for this plugin",
+        ));
+    }
+    let token = new_session_token();
+    sessions.insert(
+        token.clone(),
+        BusSession {
+            webview: webview.to_string(),
+            plugin,
+        },
+    );
+    Ok(token)
+}
+
+/// 解析会话 token 对应的（server_id, plugin_id）；token 未知或不属于 `webview` 时拒绝。
+pub(super) fn session_identity(webview: &str, token: &str) -> anyhow::Result<PluginKey> {
+    let guard = SESSIONS.lock().unwrap_or_else(|p| p.into_inner());
+    guard
+        .as_ref()
+        .and_then(|sessions| sessions.get(token.trim()))
+        .filter(|s| s.webview == webview)
+        .map(|s| s.plugin.clone())
+        .ok_or_else(|| denied("", "unknown bus session"))
+}
+
+/// 关闭会话并清空该插件的全部订阅（插件运行时卸载时调用）。
+pub(super) fn close_session(webview: &str, token: &str) {
+    let removed = {
+        let mut guard = SESSIONS.lock().unwrap_or_else(|p| p.into_inner());
+        let Some(sessions) = guard.as_mut() else {
+            return;
+        };
+        match sessions.get(token.trim()) {
+            Some(s) if s.webview == webview => sessions.remove(token.trim()),
+            _ => None,
+        }
+    };
+    if let Some(session) = removed {
+        unsubscribe(&session.plugin.0, &session.plugin.1, None);
+    }
+}
+
+/// 作废 `webview` 打开的全部会话及其订阅（页面重新加载时调用）。
+pub(super) fn close_webview_sessions(webview: &str) {
+    let removed: Vec<BusSession> = {
+        let mut guard = SESSIONS.lock().unwrap_or_else(|p| p.into_inner());
+        let Some(sessions) = guard.as_mut() else {
+            return;
+        };
+        let tokens: Vec<String> = sessions
+            .iter()
+            .filter(|(_, s)| s.webview == webview)
+            .map(|(token, _)| token.clone())
+            .collect();
+        tokens
+            .iter()
+            .filter_map(|token| sessions.remove(token))
+            .collect()
+    };
+    for session in removed {
+        unsubscribe(&session.plugin.0, &session.plugin.1, None);
+    }
+}
+
+/// 登记订阅（重复登记同一模式视为成功）。
+pub(super) async fn subscribe(
+    server_id: &str,
+    plugin_id: &str,
+    pattern: &str,
+) -> anyhow::Result<()> {
+    let pattern = pattern.trim();
+    if !is_valid_pattern(pattern) {
+        return Err(anyhow::anyhow!("Invalid bus topic: {}", pattern));
+    }
+    bus_manifests(server_id, plugin_id).await?;
+    let mut guard = SUBSCRIPTIONS.lock().unwrap_or_else(|p| p.into_inner());
+    let patterns = guard
+        .get_or_insert_with(HashMap::new)
+        .entry(key(server_id, plugin_id))
+        .or_default();
+    if !patterns.contains(pattern) && patterns.len() >= MAX_SUBSCRIPTIONS_PER_PLUGIN {
+        return Err(anyhow::anyhow!(
+            "Too many bus subscriptions (max {})",
+            MAX_SUBSCRIPTIONS_PER_PLUGIN
+        ));
+    }
+    patterns.insert(pattern.to_string());
+    Ok(())
+}
+
+/// 取消订阅；`pattern` 为 `None` 时清空该插件的全部订阅（运行时卸载插件时调用）。
+pub(super) fn unsubscribe(server_id: &str, plugin_id: &str, pattern: Option<&str>) {
+    let mut guard = SUBSCRIPTIONS.lock().unwrap_or_else(|p| p.into_inner());
+    let Some(subscriptions) = guard.as_mut() else {
+        return;
+    };
+    let key = key(server_id, plugin_id);
+    match pattern.map(str::trim) {
+        Some(pattern) => {
+            if let Some(patterns) = subscriptions.get_mut(&key) {
+                patterns.remove(pattern);
+                if patterns.is_empty() {
+                    subscriptions.remove(&key);
+                }
+            }
+        }
+        None => {
+            subscriptions.remove(&key);
+        }
+    }
+}
+
+/// 计算收件人：可使用总线、订阅命中且不是发布者本身的插件（按 id 排序）。
+fn recipients(
+    server_id: &str,
+    from: &str,
+    topic: &str,
+    manifests: &[PluginManifestV1],
+) -> Vec<String> {
+    let guard = SUBSCRIPTIONS.lock().unwrap_or_else(|p| p.into_inner());
+    let Some(subscriptions) = guard.as_ref() else {
+        return Vec::new();
+    };
+    let mut out: Vec<String> = manifests
+        .iter()
+        .map(|m| m.plugin_id.trim())
+        .filter(|id| *id != from)
+        .filter(|id| {
+            subscriptions
+                .get(&key(server_id, id))
+                .is_some_and(|patterns| patterns.iter().any(|p| pattern_matches(p, topic)))
+        })
+        .map(str::to_string)
+        .collect();
+    out.sort();
+    out.dedup();
+    out
+}
+
+/// 发布消息：校验权限与 topic 前缀并计算收件人。
+///
+/// 返回的消息由调用方转为前端事件（收件人为空时无需发出）。
+pub(super) async fn publish(
+    server_socket: &str,
+    server_id: &str,
+    plugin_id: &str,
+    topic: &str,
+    payload: Value,
+) -> anyhow::Result<PluginBusMessage> {
+    let topic = topic.trim();
+    if !is_valid_topic(topic) {
+        return Err(anyhow::anyhow!("Invalid bus topic: {}", topic));
+    }
+    let size = serde_json::to_vec(&payload)?.len();
+    if size > MAX_PAYLOAD_BYTES {
+        return Err(anyhow::anyhow!(
+            "Bus payload is too large ({} bytes, max {})",
+            size,
+            MAX_PAYLOAD_BYTES
+        ));
+    }
+    let manifests = bus_manifests(server_id, plugin_id).await?;
+    let prefixes = manifests
+        .iter()
+        .find(|m| m.plugin_id.trim() == plugin_id)
+        .map(publish_prefixes)
+        .unwrap_or_default();
+    if !prefixes.iter().any(|prefix| is_under(topic, prefix)) {
+        return Err(denied(
+            plugin_id,
+            format!(
+                "topic `{topic}` is outside the plugin's domains ({})",
+                prefixes.join(", ")
+            ),
+        ));
+    }
+
+    let message = PluginBusMessage {
+        server_socket: server_socket.to_string(),
+        topic: topic.to_string(),
+        from: plugin_id.to_string(),
+        recipients: recipients(server_id, plugin_id, topic, &manifests),
+        payload,
+    };
+    tracing::debug!(action = "plugins_bus_published", plugin_id = %plugin_id, topic = %topic, recipients = message.recipients.len());
+    Ok(message)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn topics_and_patterns_match_by_segment() {
+        assert!(is_valid_topic("emoji/packs"));
+        assert!(is_valid_topic("core:text/updated"));
+        assert!(!is_valid_topic("emoji//packs"));
+        assert!(!is_valid_topic("emoji/pack s"));
+        assert!(is_valid_pattern("emoji/*"));
+        assert!(!is_valid_pattern("*"));
+
+        assert!(pattern_matches("emoji/packs", "emoji/packs"));
+        assert!(pattern_matches("emoji/*", "emoji/packs/added"));
+        assert!(!pattern_matches("emoji/*", "emoji"));
+        assert!(!pattern_matches("emoji/*", "emojis/packs"));
+    }
+
+    #[test]
+    fn sessions_are_bound_to_their_webview() {
+        let token = new_session_token();
+        SESSIONS
+            .lock()
+            .unwrap_or_else(|p| p.into_inner())
+            .get_or_insert_with(HashMap::new)
+            .insert(
+                token.clone(),
+                BusSession {
+                    webview: "test-bus-main".to_string(),
+                    plugin: key("s1", "composer"),
+                },
+            );
+        assert_eq!(
+            session_identity("test-bus-main", &token).expect("identity"),
+            key("s1", "composer")
+        );
+        assert!(session_identity("test-bus-other", &token).is_err());
+        assert!(session_identity("test-bus-main", "forged").is_err());
+
+        close_webview_sessions("test-bus-main");
+        assert!(session_identity("test-bus-main", &token).is_err());
+    }
+
+    #[test]
+    fn publish_prefix_must_be_a_provided_domain() {
+        assert!(is_under("emoji", "emoji"));
+        assert!(is_under("emoji/packs", "emoji"));
+        assert!(!is_under("emojis/packs", "emoji"));
+        assert!(!is_under("mc/emoji", "emoji"));
+    }
+}
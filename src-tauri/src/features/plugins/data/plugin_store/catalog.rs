@@ -0,0 +1,299 @@
+//! plugin_store｜目录浏览（校验 + 搜索 + 排序 + 分页）。
+//!
+//! 说明：
+//! - 该模块只处理已拉取的目录数据（纯函数），网络请求与缓存见 `api`；
+//! - 不合法的目录条目（id/版本/下载指针缺失或非法）会被跳过并计数，避免 UI 展示无法安装的条目。
+
+use super::api::{ApiCatalogItem, ApiPluginCatalog};
+use super::download::is_same_origin;
+use super::paths::app_plugins_url;
+use super::trust;
+use crate::features::plugins::domain::types::{PluginCatalogEntry, PluginCatalogPage};
+
+/// 单页条数上限。
+pub(super) const MAX_PAGE_SIZE: u32 = 100;
+
+/// 校验目录条目是否可安装。
+fn is_valid_item(item: &ApiCatalogItem) -> bool {
+    let id = item.plugin_id.trim();
+    let id_ok = !id.is_empty()
+        && id
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.'))
+        && id != "."
+        && id != "..";
+    let version = item.version.trim();
+    let version_ok = !version.is_empty() && !version.contains(['/', '\\', ':']);
+    let download_ok = item.download.as_ref().is_some_and(|dl| {
+        let sha = dl.sha256.trim();
+        !dl.url.trim().is_empty() && sha.len() == 64 && sha.chars().all(|c| c.is_ascii_hexdigit())
+    });
+    id_ok && version_ok && download_ok
+}
+
+/// 展示资源地址改写：
+/// - 包内相对路径 -> `app://plugins/...`；
+/// - 同源绝对地址 / 以 `/` 开头的服务端路径 -> 同源绝对地址；
+/// - 跨域地址与其它 scheme 丢弃（避免 UI 向第三方发起请求）。
+fn rewrite_asset(
+    server_id: &str,
+    origin: &str,
+    item: &ApiCatalogItem,
+    raw: Option<&str>,
+) -> Option<String> {
+    let raw = raw?.trim();
+    if raw.is_empty() {
+        return None;
+    }
+    if raw.starts_with("https://") || raw.starts_with("http://") || raw.starts_with('/') {
+        let base = reqwest::Url::parse(origin).ok()?;
+        let url = base.join(raw).ok()?;
+        return is_same_origin(&url, &base).then(|| url.to_string());
+    }
+    if raw.contains(':') {
+        return None;
+    }
+    app_plugins_url(server_id, item.plugin_id.trim(), item.version.trim(), raw).ok()
+}
+
+/// 搜索得分：id/名称完全匹配 > 前缀 > 子串 > 描述/作者/domain 子串；不匹配返回 0。
+fn match_score(item: &ApiCatalogItem, query: &str) -> u32 {
+    let id = item.plugin_id.to_lowercase();
+    let name = item.name.as_deref().unwrap_or_default().to_lowercase();
+    if id == query || name == query {
+        return 400;
+    }
+    if id.starts_with(query) || name.starts_with(query) {
+        return 300;
+    }
+    if id.contains(query) || name.contains(query) {
+        return 200;
+    }
+    let in_text = |text: Option<&str>| text.is_some_and(|t| t.to_lowercase().contains(query));
+    if in_text(item.description.as_deref())
+        || in_text(item.author.as_deref())
+        || item
+            .provides_domains
+            .iter()
+            .any(|d| d.domain.to_lowercase().contains(query))
+    {
+        return 100;
+    }
+    0
+}
+
+fn to_entry(server_id: &str, origin: &str, item: &ApiCatalogItem) -> PluginCatalogEntry {
+    let plugin_id = item.plugin_id.trim().to_string();
+    PluginCatalogEntry {
+        name: item
+            .name
+            .as_deref()
+            .map(str::trim)
+            .filter(|n| !n.is_empty())
+            .unwrap_or(&plugin_id)
+            .to_string(),
+        icon_url: rewrite_asset(server_id, origin, item, item.icon.as_deref()),
+        banner_url: rewrite_asset(server_id, origin, item, item.banner.as_deref()),
+        plugin_id,
+        version: item.version.trim().to_string(),
+        description: item.description.clone(),
+        author: item.author.clone(),
+        min_host_version: item
+            .min_host_version
+            .as_deref()
+            .map(str::trim)
+            .filter(|v| !v.is_empty())
+            .map(str::to_string),
+        required: item.required,
+        permissions: item.permissions.clone(),
+        provides_domains: item
+            .provides_domains
+            .iter()
+            .filter(|d| !d.domain.is_empty())
+            .cloned()
+            .collect(),
+        trust_level: trust::catalog_trust_level(item),
+    }
+}
+
+/// 对目录做校验、搜索、排序并取指定页。
+///
+/// # 说明
+/// - 无搜索词时：必需插件优先，其次按名称排序；
+/// - 有搜索词时：按匹配得分降序，同分再按上述规则；
+/// - `page` 从 1 开始，`page_size` 会被限制在 `1..=MAX_PAGE_SIZE`。
+pub(super) fn browse(
+    server_id: &str,
+    origin: &str,
+    catalog: &ApiPluginCatalog,
+    query: Option<&str>,
+    page: u32,
+    page_size: u32,
+) -> PluginCatalogPage {
+    let query = query.map(|q| q.trim().to_lowercase()).unwrap_or_default();
+    let page = page.max(1);
+    let page_size = page_size.clamp(1, MAX_PAGE_SIZE);
+
+    let valid: Vec<&ApiCatalogItem> = catalog
+        .plugins
+        .iter()
+        .filter(|item| is_valid_item(item))
+        .collect();
+    let skipped = catalog.plugins.len() - valid.len();
+
+    let mut matched: Vec<(u32, PluginCatalogEntry)> = valid
+        .into_iter()
+        .filter_map(|item| {
+            let score = if query.is_empty() {
+                1
+            } else {
+                match_score(item, &query)
+            };
+            (score > 0).then(|| (score, to_entry(server_id, origin, item)))
+        })
+        .collect();
+    matched.sort_by(|(sa, a), (sb, b)| {
+        sb.cmp(sa)
+            .then(b.required.cmp(&a.required))
+            .then_with(|| a.name.to_lowercase().cmp(&b.name.to_lowercase()))
+            .then_with(|| a.plugin_id.cmp(&b.plugin_id))
+    });
+
+    let total = matched.len();
+    let start = (page as usize - 1).saturating_mul(page_size as usize);
+    let items = matched
+        .into_iter()
+        .skip(start)
+        .take(page_size as usize)
+        .map(|(_, entry)| entry)
+        .collect();
+    PluginCatalogPage {
+        items,
+        total,
+        page,
+        page_size,
+        skipped,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SHA: &str = "e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855";
+
+    fn catalog() -> ApiPluginCatalog {
+        let raw = serde_json::json!({
+            "plugins": [
+                { "plugin_id": "math-formula", "name": "Math Formula", "version": "1.2.0",
+                  "description": "Render LaTeX", "icon": "assets/icon.png", "min_host_version": "0.1.0",
+                  "provides_domains": [{ "domain": "Math:Formula", "domain_version": "1.0.0" }],
+                  "download": { "url": "/plugins/math.zip", "sha256": SHA } },
+                { "plugin_id": "mc-bind", "name": "MC Bind", "version": "0.3.0", "required": true,
+                  "icon": "https://cdn.example.com/mc.png", "banner": "/static/mc-banner.png",
+                  "download": { "url": "/plugins/mc.zip", "sha256": SHA } },
+                { "plugin_id": "aardvark", "version": "1.0.0", "icon": "javascript:alert(1)",
+                  "download": { "url": "/plugins/a.zip", "sha256": SHA } },
+                { "plugin_id": "../evil", "version": "1.0.0",
+                  "download": { "url": "/plugins/e.zip", "sha256": SHA } },
+                { "plugin_id": "no-download", "version": "1.0.0" }
+            ]
+        });
+        serde_json::from_value(raw).expect("catalog fixture")
+    }
+
+    #[test]
+    fn invalid_entries_are_skipped_and_required_sorts_first() {
+        let page = browse("srv", "https://srv.example", &catalog(), None, 1, 20);
+        assert_eq!(page.skipped, 2);
+        assert_eq!(page.total, 3);
+        let ids: Vec<&str> = page.items.iter().map(|e| e.plugin_id.as_str()).collect();
+        assert_eq!(ids, vec!["mc-bind", "aardvark", "math-formula"]);
+        assert_eq!(page.items[1].name, "aardvark");
+    }
+
+    #[test]
+    fn icons_are_rewritten_or_dropped() {
+        let page = browse(
+            "srv",
+            "https://srv.example",
+            &catalog(),
+            Some("math"),
+            1,
+            20,
+        );
+        assert_eq!(
+            page.items[0].icon_url.as_deref(),
+            Some("app://plugins/srv/math-formula/1.2.0/assets/icon.png")
+        );
+        assert_eq!(page.items[0].min_host_version.as_deref(), Some("0.1.0"));
+        let page = browse(
+            "srv",
+            "https://srv.example",
+            &catalog(),
+            Some("aardvark"),
+            1,
+            20,
+        );
+        assert_eq!(page.items[0].icon_url, None);
+        let page = browse(
+            "srv",
+            "https://srv.example",
+            &catalog(),
+            Some("mc-bind"),
+            1,
+            20,
+        );
+        assert_eq!(page.items[0].icon_url, None, "cross-origin icon is dropped");
+        assert_eq!(
+            page.items[0].banner_url.as_deref(),
+            Some("https://srv.example/static/mc-banner.png")
+        );
+    }
+
+    #[test]
+    fn search_matches_description_and_domains() {
+        assert_eq!(
+            browse(
+                "srv",
+                "https://srv.example",
+                &catalog(),
+                Some("latex"),
+                1,
+                20
+            )
+            .total,
+            1
+        );
+        assert_eq!(
+            browse(
+                "srv",
+                "https://srv.example",
+                &catalog(),
+                Some("math:"),
+                1,
+                20
+            )
+            .total,
+            1
+        );
+        assert_eq!(
+            browse("srv", "https://srv.example", &catalog(), Some("zzz"), 1, 20).total,
+            0
+        );
+    }
+
+    #[test]
+    fn pagination_clamps_inputs() {
+        let page = browse("srv", "https://srv.example", &catalog(), None, 2, 2);
+        assert_eq!(page.items.len(), 1);
+        assert_eq!(page.items[0].plugin_id, "math-formula");
+        let page = browse("srv", "https://srv.example", &catalog(), None, 0, 0);
+        assert_eq!((page.page, page.page_size, page.items.len()), (1, 1, 1));
+        assert!(
+            browse("srv", "https://srv.example", &catalog(), None, 9, 20)
+                .items
+                .is_empty()
+        );
+    }
+}
@@ -0,0 +1,99 @@
+//! plugin_store｜宿主版本兼容性（`min_host_version`）。
+//!
+//! 说明：
+//! - 按 semver 比较插件声明的最低宿主版本与当前应用版本（`CARGO_PKG_VERSION`）；
+//! - 兼容常见的不规范写法：前缀 `v`、缺省的 minor/patch（`1` / `1.2`）；
+//! - 未声明视为兼容；声明无法解析视为不兼容（宁可拒绝也不加载可能崩溃的插件）。
+
+use crate::features::plugins::domain::types::PluginIncompatibleHost;
+
+/// 当前宿主版本。
+pub(super) fn host_version() -> &'static str {
+    env!("CARGO_PKG_VERSION")
+}
+
+/// 宽松解析版本号：去掉 `v` 前缀并补齐缺省的 minor/patch。
+pub(super) fn parse_lenient(raw: &str) -> Option<semver::Version> {
+    let raw = raw.trim();
+    let raw = raw
+        .strip_prefix('v')
+        .or_else(|| raw.strip_prefix('V'))
+        .unwrap_or(raw);
+    if let Ok(v) = semver::Version::parse(raw) {
+        return Some(v);
+    }
+    let split = raw.find(['-', '+']).unwrap_or(raw.len());
+    let (core, suffix) = raw.split_at(split);
+    let padded = match core.split('.').count() {
+        1 => format!("{core}.0.0{suffix}"),
+        2 => format!("{core}.0{suffix}"),
+        _ => return None,
+    };
+    semver::Version::parse(&padded).ok()
+}
+
+/// 判断 `host` 是否满足 `min_host_version`（空声明视为满足）。
+fn satisfies(host: &str, min_host_version: &str) -> bool {
+    if min_host_version.trim().is_empty() {
+        return true;
+    }
+    match (parse_lenient(host), parse_lenient(min_host_version)) {
+        (Some(host), Some(min)) => host >= min,
+        _ => false,
+    }
+}
+
+/// 当前宿主是否满足插件声明的最低版本。
+pub(super) fn is_compatible(min_host_version: &str) -> bool {
+    satisfies(host_version(), min_host_version)
+}
+
+/// 校验兼容性，不满足时返回 [`PluginIncompatibleHost`]。
+pub(super) fn ensure_compatible(plugin_id: &str, min_host_version: &str) -> anyhow::Result<()> {
+    if is_compatible(min_host_version) {
+        return Ok(());
+    }
+    Err(PluginIncompatibleHost {
+        plugin_id: plugin_id.to_string(),
+        min_host_version: min_host_version.trim().to_string(),
+        host_version: host_version().to_string(),
+    }
+    .into())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn lenient_versions_are_normalized() {
+        assert_eq!(parse_lenient("v1.2"), semver::Version::parse("1.2.0").ok());
+        assert_eq!(parse_lenient("2"), semver::Version::parse("2.0.0").ok());
+        assert_eq!(
+            parse_lenient("1.0-beta.1"),
+            semver::Version::parse("1.0.0-beta.1").ok()
+        );
+        assert_eq!(parse_lenient("latest"), None);
+        assert_eq!(parse_lenient("1.2.3.4"), None);
+    }
+
+    #[test]
+    fn host_must_be_at_least_min_version() {
+        assert!(satisfies("0.3.1", ""));
+        assert!(satisfies("0.3.1", "0.3.1"));
+        assert!(satisfies("0.3.1", "v0.3"));
+        assert!(!satisfies("0.3.1", "0.4.0"));
+        assert!(!satisfies("1.0.0-rc.1", "1.0.0"));
+        assert!(!satisfies("0.3.1", "not-a-version"));
+    }
+
+    #[test]
+    fn incompatible_error_can_be_downcast() {
+        let err = ensure_compatible("demo", "999.0.0").expect_err("must be incompatible");
+        let typed = err
+            .downcast_ref::<PluginIncompatibleHost>()
+            .expect("typed error");
+        assert_eq!(typed.host_version, host_version());
+        assert!(ensure_compatible("demo", "0.0.1").is_ok());
+    }
+}
@@ -0,0 +1,141 @@
+//! plugin_store｜插件崩溃统计与自动停用。
+//!
+//! 说明：
+//! - 每次崩溃的时间记入 `history.json`（跨重启保留），统计窗口内达到阈值时把插件置为
+//!   disabled + failed，并写入说明原因的 `last_error`，避免坏插件在每次启动时反复崩溃；
+//! - 确认加载成功或用户清除错误后记录清零（见 `mark_loaded` / `clear_error`）；仅重新启用不会清零；
+//! - 前端上报的加载崩溃须凭上报资格：webview 取运行时入口时登记一次，上报时消耗，页面重新加载时作废。
+
+use std::collections::BTreeSet;
+use std::sync::Mutex;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use super::state::{
+    PluginStateFile, read_current, read_history, write_current, write_history, write_state_file,
+};
+use crate::features::plugins::domain::types::PluginAutoDisabled;
+
+/// 统计窗口内达到该崩溃次数时自动停用。
+const CRASH_DISABLE_THRESHOLD: usize = 3;
+/// 崩溃统计窗口（毫秒）。
+const CRASH_WINDOW_MS: u64 = 10 * 60 * 1000;
+
+/// 待上报的加载：`(webview, server_id, plugin_id)`。
+static REPORT_TICKETS: Mutex<BTreeSet<(String, String, String)>> = Mutex::new(BTreeSet::new());
+
+fn ticket(webview: &str, server_id: &str, plugin_id: &str) -> (String, String, String) {
+    (
+        webview.to_string(),
+        server_id.to_string(),
+        plugin_id.to_string(),
+    )
+}
+
+/// 登记一次上报资格（同一 webview 对同一插件最多持有一个，重复取入口不会累加）。
+pub(super) fn grant_report(webview: &str, server_id: &str, plugin_id: &str) {
+    let mut tickets = REPORT_TICKETS.lock().unwrap_or_else(|p| p.into_inner());
+    tickets.insert(ticket(webview, server_id, plugin_id));
+}
+
+/// 消耗上报资格；没有资格时返回 `false`。
+pub(super) fn take_report(webview: &str, server_id: &str, plugin_id: &str) -> bool {
+    let mut tickets = REPORT_TICKETS.lock().unwrap_or_else(|p| p.into_inner());
+    tickets.remove(&ticket(webview, server_id, plugin_id))
+}
+
+/// 作废某个 webview 的全部上报资格。
+pub(super) fn revoke_reports(webview: &str) {
+    let mut tickets = REPORT_TICKETS.lock().unwrap_or_else(|p| p.into_inner());
+    tickets.retain(|(owner, _, _)| owner != webview);
+}
+
+fn now_ms() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_millis() as u64)
+        .unwrap_or(0)
+}
+
+/// 记入一次崩溃：丢弃窗口外（及时钟回拨产生的未来）记录，返回窗口内的崩溃次数。
+fn push_crash(recent: &mut Vec<u64>, now: u64) -> usize {
+    recent.retain(|&t| t <= now && now - t < CRASH_WINDOW_MS);
+    recent.push(now);
+    recent.len()
+}
+
+/// 记录一次运行时崩溃；窗口内次数达到阈值且插件仍启用时自动停用，并返回停用信息。
+pub(super) async fn record_crash(
+    server_id: &str,
+    plugin_id: &str,
+    message: &str,
+) -> anyhow::Result<Option<PluginAutoDisabled>> {
+    let message = message.trim();
+    let mut history = read_history(server_id, plugin_id).await?;
+    let crashes = push_crash(&mut history.recent_crashes, now_ms());
+    tracing::warn!(action = "plugins_runtime_crashed", plugin_id = %plugin_id, crashes, error = %message);
+
+    let current = read_current(server_id, plugin_id).await?;
+    let Some(mut current) = current.filter(|c| c.enabled && crashes >= CRASH_DISABLE_THRESHOLD)
+    else {
+        write_history(server_id, plugin_id, &history).await?;
+        return Ok(None);
+    };
+
+    history.recent_crashes.clear();
+    write_history(server_id, plugin_id, &history).await?;
+    let last_error = format!(
+        "Automatically disabled after {} crashes within {} minutes. Last error: {}",
+        crashes,
+        CRASH_WINDOW_MS / 60_000,
+        message
+    );
+    current.enabled = false;
+    write_current(server_id, plugin_id, &current).await?;
+    write_state_file(
+        server_id,
+        plugin_id,
+        &PluginStateFile {
+            status: "failed".to_string(),
+            last_error: last_error.clone(),
+        },
+    )
+    .await?;
+    tracing::error!(action = "plugins_auto_disabled", plugin_id = %plugin_id, version = %current.version, crashes);
+    Ok(Some(PluginAutoDisabled {
+        server_id: server_id.to_string(),
+        plugin_id: plugin_id.to_string(),
+        version: current.version,
+        crashes: crashes as u32,
+        last_error,
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn crashes_outside_the_window_are_forgotten() {
+        let mut recent = vec![1_000, 2_000];
+        assert_eq!(push_crash(&mut recent, 3_000), 3);
+        let later = 2_000 + CRASH_WINDOW_MS;
+        assert_eq!(push_crash(&mut recent, later), 2);
+        assert_eq!(recent, vec![3_000, later]);
+        // 时钟回拨：未来的记录不计入。
+        assert_eq!(push_crash(&mut recent, 2_500), 1);
+    }
+
+    #[test]
+    fn crash_reports_need_a_ticket_from_the_same_webview() {
+        grant_report("main", "srv-ticket", "renderer");
+        grant_report("main", "srv-ticket", "renderer");
+        assert!(!take_report("popover", "srv-ticket", "renderer"));
+        assert!(take_report("main", "srv-ticket", "renderer"));
+        // 重复取入口不会累加资格。
+        assert!(!take_report("main", "srv-ticket", "renderer"));
+
+        grant_report("main", "srv-ticket", "math");
+        revoke_reports("main");
+        assert!(!take_report("main", "srv-ticket", "math"));
+    }
+}
@@ -0,0 +1,129 @@
+//! plugin_store｜插件文档资源的 Content Security Policy。
+//!
+//! 说明：
+//! - `app://plugins/...`（及插件本地 HTTP 服务）返回可被渲染为文档的类型（HTML / XHTML / SVG / XML，
+//!   见 [`is_document_mime`]）时附带 CSP 响应头，把插件内的 XSS 限制在插件自身资源内：
+//!   脚本/样式/图片只允许同源（`'self'`）加载；
+//! - 声明了 `network` 权限的插件，额外允许连接/加载所属服务器的 origin（含对应的 `ws(s)`），
+//!   与 `plugins_network_fetch` 的同源限制一致；
+//! - 以响应头而不是 `<meta>` 注入，保持响应体与文件一致（ETag / Range 不受影响）；
+//! - 只约束以独立文档打开的插件资源（iframe / 新窗口）。前端加载器以 `import()` 载入主窗口的插件模块
+//!   运行在主窗口的 CSP 与权限下，不受本 CSP 隔离。
+
+use super::paths::manifest_file_path;
+use super::{PluginManifestV1, api};
+
+/// 允许访问服务器 origin 的权限 key。
+const NETWORK_PERMISSION: &str = "network";
+
+/// 由权限与服务器 origin 构建 CSP。
+///
+/// # 参数
+/// - `permissions`：插件清单声明的权限。
+/// - `server_origins`：插件所属服务器的 HTTP origin（`http(s)://host:port`）。
+pub fn build_plugin_csp(permissions: &[String], server_origins: &[String]) -> String {
+    let network = permissions
+        .iter()
+        .any(|p| p.trim().eq_ignore_ascii_case(NETWORK_PERMISSION));
+    let mut remote: Vec<String> = Vec::new();
+    let mut sockets: Vec<String> = Vec::new();
+    if network {
+        for origin in server_origins {
+            let origin = origin.trim().trim_end_matches('/');
+            if let Some(rest) = origin.strip_prefix("https://") {
+                sockets.push(format!("wss://{rest}"));
+            } else if let Some(rest) = origin.strip_prefix("http://") {
+                sockets.push(format!("ws://{rest}"));
+            } else {
+                continue;
+            }
+            remote.push(origin.to_string());
+        }
+    }
+    let with = |base: &str, extra: &[String]| {
+        std::iter::once(base.to_string())
+            .chain(extra.iter().cloned())
+            .collect::<Vec<_>>()
+            .join(" ")
+    };
+    let connect: Vec<String> = remote.iter().chain(sockets.iter()).cloned().collect();
+    [
+        "default-src 'none'".to_string(),
+        "script-src 'self' 'wasm-unsafe-eval'".to_string(),
+        "style-src 'self' 'unsafe-inline'".to_string(),
+        with("img-src 'self' data: blob:", &remote),
+        with("media-src 'self' blob:", &remote),
+        "font-src 'self' data:".to_string(),
+        with("connect-src 'self'", &connect),
+        "worker-src 'self' blob:".to_string(),
+        "object-src 'none'".to_string(),
+        "base-uri 'none'".to_string(),
+        "form-action 'none'".to_string(),
+    ]
+    .join("; ")
+}
+
+/// `content_type` 是否可被 WebView 当作文档渲染并执行脚本（忽略参数与大小写）。
+pub fn is_document_mime(content_type: &str) -> bool {
+    let essence = content_type
+        .split(';')
+        .next()
+        .unwrap_or_default()
+        .trim()
+        .to_ascii_lowercase();
+    matches!(
+        essence.as_str(),
+        "text/html" | "text/xml" | "application/xml"
+    ) || essence.ends_with("+xml")
+}
+
+/// 某插件版本文档资源的 CSP（清单不可读时按“无权限”处理）。
+pub fn plugin_html_csp(server_id: &str, plugin_id: &str, version: &str) -> String {
+    let permissions = manifest_file_path(server_id, plugin_id, version)
+        .ok()
+        .and_then(|path| std::fs::read_to_string(path).ok())
+        .and_then(|raw| serde_json::from_str::<PluginManifestV1>(&raw).ok())
+        .map(|manifest| manifest.permissions)
+        .unwrap_or_default();
+    build_plugin_csp(&permissions, &api::origins_for_server_id(server_id))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn network_permission_opens_only_the_server_origin() {
+        let origins = vec!["https://chat.example.com:8443".to_string()];
+        let closed = build_plugin_csp(&[], &origins);
+        assert!(closed.contains("connect-src 'self';"));
+        assert!(!closed.contains("chat.example.com"));
+
+        let open = build_plugin_csp(&["network".to_string()], &origins);
+        assert!(open.contains(
+            "connect-src 'self' https://chat.example.com:8443 wss://chat.example.com:8443;"
+        ));
+        assert!(open.contains("img-src 'self' data: blob: https://chat.example.com:8443;"));
+        assert!(open.starts_with("default-src 'none'; script-src 'self' 'wasm-unsafe-eval';"));
+    }
+
+    #[test]
+    fn every_document_capable_type_gets_the_csp() {
+        for mime in [
+            "text/html; charset=utf-8",
+            "application/xhtml+xml",
+            "image/svg+xml",
+            "Text/XML",
+            "application/xml; charset=utf-8",
+        ] {
+            assert!(is_document_mime(mime), "{mime}");
+        }
+        for mime in [
+            "text/javascript; charset=utf-8",
+            "image/png",
+            "application/wasm",
+        ] {
+            assert!(!is_document_mime(mime), "{mime}");
+        }
+    }
+}
@@ -0,0 +1,255 @@
+//! plugin_store｜开发态插件链接（本地目录 + 热重载）。
+//!
+//! 说明：
+//! - 开发态插件以固定版本号 `dev` 挂载：`{base}/{server_id}/{plugin_id}/dev` 是指向源目录的
+//!   符号链接，因此 `app://plugins/...`、清单读取、启用校验等流程无需区分开发态；
+//! - 链接信息写入插件根目录的 `dev.json`，用于 UI 标记与启动时恢复目录监听；
+//! - 卸载/清理只删除链接本身（`remove_dir_all` 不跟随符号链接），不会触及源目录。
+
+use std::path::{Path, PathBuf};
+
+use anyhow::Context;
+use serde::{Deserialize, Serialize};
+
+use super::compat;
+use super::json_io::{read_json_file, write_json_file};
+use super::manifest_schema;
+use super::paths::{base_plugins_dir, dev_link_file_path, plugin_root_dir, plugin_version_dir};
+use super::state::{PluginCurrent, list_installed_versions, read_current, write_current};
+
+/// 开发态插件的固定版本号。
+pub(super) const DEV_VERSION: &str = "dev";
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub(super) struct DevLinkFile {
+    /// 源目录（canonical 绝对路径）。
+    pub path: String,
+    pub linked_at_ms: i64,
+}
+
+pub(super) async fn read_dev_link(
+    server_id: &str,
+    plugin_id: &str,
+) -> anyhow::Result<Option<DevLinkFile>> {
+    read_json_file::<DevLinkFile>(&dev_link_file_path(server_id, plugin_id)?).await
+}
+
+#[cfg(unix)]
+async fn create_dir_link(target: &Path, link: &Path) -> std::io::Result<()> {
+    tokio::fs::symlink(target, link).await
+}
+
+#[cfg(windows)]
+async fn create_dir_link(target: &Path, link: &Path) -> std::io::Result<()> {
+    // Windows 创建目录符号链接需要开启“开发者模式”或管理员权限。
+    tokio::fs::symlink_dir(target, link).await
+}
+
+#[cfg(unix)]
+async fn remove_dir_link(link: &Path) -> std::io::Result<()> {
+    tokio::fs::remove_file(link).await
+}
+
+#[cfg(windows)]
+async fn remove_dir_link(link: &Path) -> std::io::Result<()> {
+    tokio::fs::remove_dir(link).await
+}
+
+/// 校验源目录是一个可加载的插件目录，返回 canonical 路径。
+async fn validate_source(plugin_id: &str, source: &Path) -> anyhow::Result<PathBuf> {
+    let source = tokio::fs::canonicalize(source)
+        .await
+        .with_context(|| format!("Dev plugin directory not found: {}", source.display()))?;
+    if !tokio::fs::metadata(&source).await?.is_dir() {
+        return Err(anyhow::anyhow!(
+            "Dev plugin path is not a directory: {}",
+            source.display()
+        ));
+    }
+    let manifest_path = source.join("plugin.json");
+    let raw = tokio::fs::read_to_string(&manifest_path)
+        .await
+        .with_context(|| format!("Missing plugin.json at {}", manifest_path.display()))?;
+    let manifest = manifest_schema::parse_manifest(&raw)?;
+    if manifest.plugin_id.trim() != plugin_id {
+        return Err(anyhow::anyhow!(
+            "plugin_id mismatch in manifest: expected {}, got {}",
+            plugin_id,
+            manifest.plugin_id.trim()
+        ));
+    }
+    compat::ensure_compatible(plugin_id, &manifest.min_host_version)?;
+    Ok(source)
+}
+
+/// 删除已存在的 `dev` 链接；若该位置是普通目录则拒绝覆盖。
+async fn remove_existing_link(link: &Path) -> anyhow::Result<()> {
+    match tokio::fs::symlink_metadata(link).await {
+        Ok(meta) if meta.file_type().is_symlink() => remove_dir_link(link)
+            .await
+            .with_context(|| format!("Failed to remove dev link: {}", link.display())),
+        Ok(_) => Err(anyhow::anyhow!(
+            "Dev version path is occupied by a regular directory: {}",
+            link.display()
+        )),
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => Ok(()),
+        Err(err) => Err(err.into()),
+    }
+}
+
+/// 将本地目录链接为插件的 `dev` 版本，并切换为当前版本（保留原启用状态）。
+///
+/// # 返回值
+/// 源目录的 canonical 路径。
+pub(super) async fn link(
+    server_id: &str,
+    plugin_id: &str,
+    source: &Path,
+) -> anyhow::Result<PathBuf> {
+    let source = validate_source(plugin_id, source).await?;
+    let root = plugin_root_dir(server_id, plugin_id)?;
+    tokio::fs::create_dir_all(&root)
+        .await
+        .with_context(|| format!("Failed to create dir: {}", root.display()))?;
+    let link = plugin_version_dir(server_id, plugin_id, DEV_VERSION)?;
+    remove_existing_link(&link).await?;
+    create_dir_link(&source, &link)
+        .await
+        .with_context(|| format!("Failed to create dev link: {}", link.display()))?;
+
+    write_json_file(
+        &dev_link_file_path(server_id, plugin_id)?,
+        &DevLinkFile {
+            path: source.display().to_string(),
+            linked_at_ms: std::time::SystemTime::now()
+                .duration_since(std::time::UNIX_EPOCH)
+                .map(|d| d.as_millis() as i64)
+                .unwrap_or(0),
+        },
+    )
+    .await?;
+    let enabled = read_current(server_id, plugin_id)
+        .await?
+        .is_some_and(|current| current.enabled);
+    write_current(
+        server_id,
+        plugin_id,
+        &PluginCurrent {
+            version: DEV_VERSION.to_string(),
+            enabled,
+        },
+    )
+    .await?;
+    Ok(source)
+}
+
+/// 取消开发态链接：当前版本回落到最近安装的正式版本；没有正式版本时删除插件目录。
+pub(super) async fn unlink(server_id: &str, plugin_id: &str) -> anyhow::Result<()> {
+    let link = plugin_version_dir(server_id, plugin_id, DEV_VERSION)?;
+    remove_existing_link(&link).await?;
+    let dev_file = dev_link_file_path(server_id, plugin_id)?;
+    match tokio::fs::remove_file(&dev_file).await {
+        Ok(()) => {}
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => {}
+        Err(err) => return Err(err.into()),
+    }
+
+    let Some(mut current) = read_current(server_id, plugin_id).await? else {
+        return Ok(());
+    };
+    if current.version != DEV_VERSION {
+        return Ok(());
+    }
+    match list_installed_versions(server_id, plugin_id).await?.pop() {
+        Some(latest) => {
+            current.version = latest;
+            write_current(server_id, plugin_id, &current).await
+        }
+        None => {
+            let root = plugin_root_dir(server_id, plugin_id)?;
+            tokio::fs::remove_dir_all(&root)
+                .await
+                .with_context(|| format!("Failed to remove plugin dir: {}", root.display()))
+        }
+    }
+}
+
+/// 列出本地所有开发态链接：`(server_id, plugin_id, 源目录)`。
+pub(super) async fn list_links() -> anyhow::Result<Vec<(String, String, PathBuf)>> {
+    let base = base_plugins_dir()?;
+    let mut out = Vec::new();
+    let mut servers = match tokio::fs::read_dir(&base).await {
+        Ok(rd) => rd,
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => return Ok(out),
+        Err(err) => return Err(err.into()),
+    };
+    while let Some(server) = servers.next_entry().await? {
+        if !server.file_type().await?.is_dir() {
+            continue;
+        }
+        let server_id = server.file_name().to_string_lossy().to_string();
+        let mut plugins = tokio::fs::read_dir(server.path()).await?;
+        while let Some(plugin) = plugins.next_entry().await? {
+            if !plugin.file_type().await?.is_dir() {
+                continue;
+            }
+            let plugin_id = plugin.file_name().to_string_lossy().to_string();
+            if let Ok(Some(link)) = read_dev_link(&server_id, &plugin_id).await {
+                out.push((server_id.clone(), plugin_id, PathBuf::from(link.path)));
+            }
+        }
+    }
+    Ok(out)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn temp_dir(prefix: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!(
+            "carrypigeon-{}-{}-{}",
+            prefix,
+            std::process::id(),
+            std::time::SystemTime::now()
+                .duration_since(std::time::UNIX_EPOCH)
+                .map(|d| d.as_nanos())
+                .unwrap_or(0)
+        ));
+        std::fs::create_dir_all(&dir).expect("create temp dir");
+        dir
+    }
+
+    #[tokio::test]
+    async fn source_manifest_must_match_plugin_id() {
+        let dir = temp_dir("dev-link-src");
+        std::fs::write(
+            dir.join("plugin.json"),
+            br#"{"plugin_id":"demo","name":"Demo","version":"0.0.1","min_host_version":"","description":null,"author":null,"license":null,"entry":"index.js","permissions":[],"provides_domains":[]}"#,
+        )
+        .expect("write manifest");
+        assert!(validate_source("demo", &dir).await.is_ok());
+        assert!(validate_source("other", &dir).await.is_err());
+        assert!(validate_source("demo", &dir.join("missing")).await.is_err());
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn regular_directory_is_not_replaced() {
+        let dir = temp_dir("dev-link-occupied");
+        let occupied = dir.join("dev");
+        std::fs::create_dir_all(&occupied).expect("create dir");
+        assert!(remove_existing_link(&occupied).await.is_err());
+
+        let target = dir.join("target");
+        std::fs::create_dir_all(&target).expect("create target");
+        let link = dir.join("linked");
+        create_dir_link(&target, &link).await.expect("create link");
+        remove_existing_link(&link).await.expect("remove link");
+        assert!(target.exists(), "link removal must not touch the source");
+        assert!(!link.exists());
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+}
@@ -0,0 +1,264 @@
+//! plugin_store｜domain 注册表（按 server 记录启用插件提供的 domain）。
+//!
+//! 说明：
+//! - 注册表由本地状态推导：仅统计 `enabled` 且 `state.json` 为 ok 的插件当前版本的
+//!   `provides_domains`；
+//! - 多个插件提供同一 domain 时视为冲突：domain 版本高者优先，同版本按 plugin_id 字典序，
+//!   保证结果确定；
+//! - 每次刷新与上一份快照比较，返回提供方变化列表（由 DI 层转为事件）。
+
+use std::collections::{BTreeMap, HashMap};
+use std::sync::OnceLock;
+
+use anyhow::Context;
+use tokio::sync::RwLock;
+
+use super::PluginManifestV1;
+use super::compat::parse_lenient;
+use super::paths::{base_plugins_dir, manifest_file_path};
+use super::state::{read_current, read_state_file};
+use crate::features::plugins::domain::types::{
+    PluginDomainProvider, PluginDomainProviderChanged, PluginDomainResolution,
+};
+
+/// 单个 server 的 domain 表（domain -> 解析结果）。
+type DomainTable = BTreeMap<String, PluginDomainResolution>;
+
+static REGISTRY: OnceLock<RwLock<HashMap<String, DomainTable>>> = OnceLock::new();
+
+fn registry() -> &'static RwLock<HashMap<String, DomainTable>> {
+    REGISTRY.get_or_init(|| RwLock::new(HashMap::new()))
+}
+
+/// 提供方优先级：domain 版本降序，其次 plugin_id 升序。
+fn provider_order(a: &PluginDomainProvider, b: &PluginDomainProvider) -> std::cmp::Ordering {
+    let version_cmp = match (
+        parse_lenient(&a.domain_version),
+        parse_lenient(&b.domain_version),
+    ) {
+        (Some(va), Some(vb)) => vb.cmp(&va),
+        (Some(_), None) => std::cmp::Ordering::Less,
+        (None, Some(_)) => std::cmp::Ordering::Greater,
+        (None, None) => b.domain_version.cmp(&a.domain_version),
+    };
+    version_cmp.then_with(|| a.plugin_id.cmp(&b.plugin_id))
+}
+
+/// 由启用插件的清单构建 domain 表。
+fn build_table(manifests: &[PluginManifestV1]) -> DomainTable {
+    let mut grouped: BTreeMap<String, Vec<PluginDomainProvider>> = BTreeMap::new();
+    for manifest in manifests {
+        for provided in &manifest.provides_domains {
+            let domain = provided.domain.trim();
+            if domain.is_empty() {
+                continue;
+            }
+            let providers = grouped.entry(domain.to_string()).or_default();
+            let plugin_id = manifest.plugin_id.trim();
+            // 同一插件重复声明同一 domain 时只取第一条。
+            if providers.iter().any(|p| p.plugin_id == plugin_id) {
+                continue;
+            }
+            providers.push(PluginDomainProvider {
+                plugin_id: plugin_id.to_string(),
+                plugin_version: manifest.version.trim().to_string(),
+                domain_version: provided.domain_version.trim().to_string(),
+            });
+        }
+    }
+    grouped
+        .into_iter()
+        .map(|(domain, mut candidates)| {
+            candidates.sort_by(provider_order);
+            let resolution = PluginDomainResolution {
+                domain: domain.clone(),
+                provider: candidates.first().cloned(),
+                conflict: candidates.len() > 1,
+                candidates,
+            };
+            (domain, resolution)
+        })
+        .collect()
+}
+
+/// 比较新旧 domain 表，返回提供方（或冲突状态）发生变化的 domain。
+fn diff_tables(
+    server_socket: &str,
+    old: &DomainTable,
+    new: &DomainTable,
+) -> Vec<PluginDomainProviderChanged> {
+    let mut domains: Vec<&String> = old.keys().chain(new.keys()).collect();
+    domains.sort();
+    domains.dedup();
+    domains
+        .into_iter()
+        .filter_map(|domain| {
+            let previous = old.get(domain);
+            let current = new.get(domain);
+            let previous_provider = previous.and_then(|r| r.provider.clone());
+            let current_provider = current.and_then(|r| r.provider.clone());
+            let previous_conflict = previous.is_some_and(|r| r.conflict);
+            let conflict = current.is_some_and(|r| r.conflict);
+            (previous_provider != current_provider || previous_conflict != conflict).then(|| {
+                PluginDomainProviderChanged {
+                    server_socket: server_socket.to_string(),
+                    domain: domain.clone(),
+                    previous: previous_provider,
+                    current: current_provider,
+                    conflict,
+                }
+            })
+        })
+        .collect()
+}
+
+/// 读取某个 server 下所有“启用且状态正常”插件的当前版本清单。
+pub(super) async fn enabled_manifests(server_id: &str) -> anyhow::Result<Vec<PluginManifestV1>> {
+    let base = base_plugins_dir()?.join(server_id);
+    let mut rd = match tokio::fs::read_dir(&base).await {
+        Ok(rd) => rd,
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => return Ok(vec![]),
+        Err(err) => return Err(err.into()),
+    };
+    let mut out = Vec::new();
+    while let Some(ent) = rd.next_entry().await? {
+        if !ent.file_type().await?.is_dir() {
+            continue;
+        }
+        let plugin_id = ent.file_name().to_string_lossy().to_string();
+        let Some(current) = read_current(server_id, &plugin_id).await? else {
+            continue;
+        };
+        if !current.enabled || read_state_file(server_id, &plugin_id).await?.status != "ok" {
+            continue;
+        }
+        let manifest_path = manifest_file_path(server_id, &plugin_id, &current.version)?;
+        let manifest = match tokio::fs::read_to_string(&manifest_path).await {
+            Ok(raw) => serde_json::from_str::<PluginManifestV1>(&raw)
+                .with_context(|| format!("Invalid plugin.json: {}", manifest_path.display())),
+            Err(e) => Err(e.into()),
+        };
+        match manifest {
+            Ok(manifest) => out.push(manifest),
+            Err(e) => {
+                tracing::warn!(action = "plugins_domain_manifest_skipped", plugin_id = %plugin_id, error = %e);
+            }
+        }
+    }
+    Ok(out)
+}
+
+/// 重新构建某个 server 的 domain 表，并返回提供方变化列表。
+pub(super) async fn refresh(
+    server_socket: &str,
+    server_id: &str,
+) -> anyhow::Result<Vec<PluginDomainProviderChanged>> {
+    let table = build_table(&enabled_manifests(server_id).await?);
+    for resolution in table.values().filter(|r| r.conflict) {
+        tracing::warn!(
+            action = "plugins_domain_conflict",
+            domain = %resolution.domain,
+            providers = ?resolution.candidates.iter().map(|p| p.plugin_id.as_str()).collect::<Vec<_>>()
+        );
+    }
+    let mut guard = registry().write().await;
+    let old = guard.remove(server_socket).unwrap_or_default();
+    let changes = diff_tables(server_socket, &old, &table);
+    guard.insert(server_socket.to_string(), table);
+    Ok(changes)
+}
+
+/// 解析 domain 的提供方（该 server 尚未建表时先刷新一次）。
+pub(super) async fn resolve(
+    server_socket: &str,
+    server_id: &str,
+    domain: &str,
+) -> anyhow::Result<PluginDomainResolution> {
+    let domain = domain.trim();
+    if domain.is_empty() {
+        return Err(anyhow::anyhow!("Missing domain"));
+    }
+    let loaded = registry().read().await.contains_key(server_socket);
+    if !loaded {
+        refresh(server_socket, server_id).await?;
+    }
+    let guard = registry().read().await;
+    Ok(guard
+        .get(server_socket)
+        .and_then(|table| table.get(domain))
+        .cloned()
+        .unwrap_or_else(|| PluginDomainResolution {
+            domain: domain.to_string(),
+            provider: None,
+            candidates: vec![],
+            conflict: false,
+        }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::features::plugins::domain::types::PluginProvidesDomain;
+
+    fn manifest(plugin_id: &str, domains: &[(&str, &str)]) -> PluginManifestV1 {
+        PluginManifestV1 {
+            plugin_id: plugin_id.to_string(),
+            name: plugin_id.to_string(),
+            version: "1.0.0".to_string(),
+            min_host_version: String::new(),
+            description: None,
+            author: None,
+            license: None,
+            entry: "index.js".to_string(),
+            permissions: vec![],
+            provides_domains: domains
+                .iter()
+                .map(|(domain, version)| PluginProvidesDomain {
+                    domain: domain.to_string(),
+                    domain_version: version.to_string(),
+                })
+                .collect(),
+            settings: None,
+        }
+    }
+
+    #[test]
+    fn conflicts_prefer_higher_domain_version_then_plugin_id() {
+        let table = build_table(&[
+            manifest("zeta", &[("Math:Formula", "1.0.0")]),
+            manifest("alpha", &[("Math:Formula", "1.0.0"), ("Poll:Vote", "2")]),
+            manifest("beta", &[("Poll:Vote", "2.1.0")]),
+        ]);
+        let math = &table["Math:Formula"];
+        assert!(math.conflict);
+        assert_eq!(
+            math.provider.as_ref().map(|p| p.plugin_id.as_str()),
+            Some("alpha")
+        );
+        let poll = &table["Poll:Vote"];
+        assert_eq!(
+            poll.provider.as_ref().map(|p| p.plugin_id.as_str()),
+            Some("beta")
+        );
+        assert_eq!(poll.candidates.len(), 2);
+    }
+
+    #[test]
+    fn diff_reports_added_removed_and_conflict_changes() {
+        let old = build_table(&[manifest("a", &[("X:One", "1.0.0"), ("X:Two", "1.0.0")])]);
+        let new = build_table(&[
+            manifest("a", &[("X:One", "1.0.0")]),
+            manifest("b", &[("X:One", "0.9.0"), ("X:Three", "1.0.0")]),
+        ]);
+        let changes = diff_tables("s", &old, &new);
+        let domains: Vec<&str> = changes.iter().map(|c| c.domain.as_str()).collect();
+        assert_eq!(domains, vec!["X:One", "X:Three", "X:Two"]);
+        assert!(
+            changes[0].conflict,
+            "provider unchanged but conflict appeared"
+        );
+        assert_eq!(changes[0].previous, changes[0].current);
+        assert!(changes[2].current.is_none());
+        assert!(diff_tables("s", &new, &new).is_empty());
+    }
+}
@@ -0,0 +1,319 @@
+//! plugin_store｜旧插件数据一次性迁移（`plugins.json` + `plugin_cache`）。
+//!
+//! 说明：
+//! - 早期 wasm 调试加载器把清单写在 `{app_data}/plugins.json`，资源缓存在
+//!   `{app_data}/plugin_cache/{name}/`；该加载器已移除，这里把残留数据导入新的插件存储；
+//! - 旧数据没有 server 归属，先导入到 [`LEGACY_SERVER_ID`] 分区，默认不启用；旧清单的下载 `url`
+//!   记录为来源 origin，首次解析到同 origin 服务端的 server_id 时把插件整体移入该服务端分区
+//!   （见 [`adopt_for_origin`]）；没有 `url` 的条目无法归属，留在 legacy 分区；
+//! - 导入完成后把 `plugins.json` 改名为 `plugins.json.migrated` 并删除 `plugin_cache`，
+//!   因此迁移只会执行一次；单个条目失败只记录日志，不阻断其它条目。
+
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use anyhow::Context;
+use serde::Deserialize;
+
+use super::PluginManifestV1;
+use super::json_io::{read_json_file, write_json_file};
+use super::origin::to_http_origin;
+use super::paths::{base_plugins_dir, manifest_file_path, plugin_root_dir, plugin_version_dir};
+use super::state::{PluginCurrent, PluginStateFile, write_current, write_state_file};
+
+/// 旧插件导入后所在的 server 分区。
+pub(super) const LEGACY_SERVER_ID: &str = "legacy";
+
+/// 待归属插件的来源索引（`{base}/legacy/origins.json`：plugin_id -> origin）。
+const ORIGINS_FILE: &str = "origins.json";
+
+/// 来源索引内容：plugin_id -> origin。
+type OriginIndex = BTreeMap<String, String>;
+
+/// 已确认没有待归属的旧插件（进程内缓存，避免每次解析 server_id 都读索引）。
+static NOTHING_PENDING: AtomicBool = AtomicBool::new(false);
+
+/// 串行化归属迁移，避免并发解析同一 origin 时重复移动目录。
+static ADOPT_LOCK: tokio::sync::Mutex<()> = tokio::sync::Mutex::const_new(());
+
+/// 旧加载器缓存的文件（`frontend.html` 可能缺失）。
+const LEGACY_FILES: &[&str] = &[
+    "frontend.js",
+    "frontend.wasm",
+    "backend.wasm",
+    "frontend.html",
+];
+
+/// `plugins.json` 中的旧清单条目（仅解析迁移需要的字段）。
+#[derive(Debug, Clone, Deserialize)]
+struct LegacyPluginManifest {
+    name: String,
+    version: String,
+    #[serde(default)]
+    description: Option<String>,
+    #[serde(default)]
+    author: Option<String>,
+    #[serde(default)]
+    license: Option<String>,
+    /// 旧加载器的下载地址，用于推断来源服务端。
+    #[serde(default)]
+    url: Option<String>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+struct LegacyPluginManifestList {
+    #[serde(default)]
+    plugins: Vec<LegacyPluginManifest>,
+}
+
+fn parse_legacy_list(raw: &str) -> anyhow::Result<Vec<LegacyPluginManifest>> {
+    let raw = raw.trim();
+    if raw.is_empty() {
+        return Ok(vec![]);
+    }
+    let list: LegacyPluginManifestList =
+        serde_json::from_str(raw).context("Failed to parse legacy plugins.json")?;
+    Ok(list.plugins)
+}
+
+/// 旧插件名只有满足新 plugin_id 规则时才导入。
+fn is_valid_plugin_id(name: &str) -> bool {
+    !name.is_empty()
+        && name != "."
+        && name != ".."
+        && name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.'))
+}
+
+fn to_manifest(legacy: &LegacyPluginManifest) -> PluginManifestV1 {
+    PluginManifestV1 {
+        plugin_id: legacy.name.trim().to_string(),
+        name: legacy.name.trim().to_string(),
+        version: legacy.version.trim().to_string(),
+        min_host_version: String::new(),
+        description: legacy.description.clone(),
+        author: legacy.author.clone(),
+        license: legacy.license.clone(),
+        entry: "frontend.js".to_string(),
+        permissions: vec![],
+        provides_domains: vec![],
+        settings: None,
+    }
+}
+
+/// 旧清单 `url` 对应的 HTTP origin；缺失或无法解析时返回 `None`。
+fn source_origin(legacy: &LegacyPluginManifest) -> Option<String> {
+    let url = legacy.url.as_deref()?.trim();
+    if url.is_empty() {
+        return None;
+    }
+    to_http_origin(url).ok()
+}
+
+fn origins_file_path() -> anyhow::Result<PathBuf> {
+    Ok(base_plugins_dir()?
+        .join(LEGACY_SERVER_ID)
+        .join(ORIGINS_FILE))
+}
+
+async fn read_origins() -> anyhow::Result<OriginIndex> {
+    Ok(read_json_file(&origins_file_path()?)
+        .await?
+        .unwrap_or_default())
+}
+
+async fn write_origins(origins: &OriginIndex) -> anyhow::Result<()> {
+    let path = origins_file_path()?;
+    if origins.is_empty() {
+        return match tokio::fs::remove_file(&path).await {
+            Err(err) if err.kind() != std::io::ErrorKind::NotFound => Err(err.into()),
+            _ => Ok(()),
+        };
+    }
+    write_json_file(&path, origins).await
+}
+
+async fn import_one(cache_dir: &Path, legacy: &LegacyPluginManifest) -> anyhow::Result<()> {
+    let plugin_id = legacy.name.trim();
+    if !is_valid_plugin_id(plugin_id) {
+        return Err(anyhow::anyhow!("Invalid legacy plugin name: {}", plugin_id));
+    }
+    let source = cache_dir.join(plugin_id);
+    if !tokio::fs::try_exists(source.join("frontend.js")).await? {
+        return Err(anyhow::anyhow!(
+            "Legacy plugin cache missing: {}",
+            source.display()
+        ));
+    }
+    let manifest = to_manifest(legacy);
+    let dest = plugin_version_dir(LEGACY_SERVER_ID, plugin_id, &manifest.version)?;
+    tokio::fs::create_dir_all(&dest)
+        .await
+        .with_context(|| format!("Failed to create dir: {}", dest.display()))?;
+    for name in LEGACY_FILES {
+        match tokio::fs::copy(source.join(name), dest.join(name)).await {
+            Ok(_) => {}
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => {}
+            Err(err) => return Err(err.into()),
+        }
+    }
+    write_json_file(
+        &manifest_file_path(LEGACY_SERVER_ID, plugin_id, &manifest.version)?,
+        &manifest,
+    )
+    .await?;
+    write_current(
+        LEGACY_SERVER_ID,
+        plugin_id,
+        &PluginCurrent {
+            version: manifest.version.clone(),
+            enabled: false,
+        },
+    )
+    .await?;
+    write_state_file(
+        LEGACY_SERVER_ID,
+        plugin_id,
+        &PluginStateFile {
+            status: "ok".to_string(),
+            last_error: String::new(),
+        },
+    )
+    .await
+}
+
+/// 执行迁移。
+///
+/// # 返回值
+/// - `Ok(n)`：成功导入的插件数；没有旧数据时为 0。
+/// - `Err(anyhow::Error)`：`plugins.json` 无法读取/解析或标记迁移完成失败。
+pub(super) async fn import(app_data_dir: &Path) -> anyhow::Result<usize> {
+    let list_path = app_data_dir.join("plugins.json");
+    let cache_dir = app_data_dir.join("plugin_cache");
+    let raw = match tokio::fs::read_to_string(&list_path).await {
+        Ok(raw) => raw,
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => {
+            remove_cache_dir(&cache_dir).await;
+            return Ok(0);
+        }
+        Err(err) => return Err(err.into()),
+    };
+
+    let mut imported = 0;
+    let mut origins = read_origins().await?;
+    for legacy in parse_legacy_list(&raw)? {
+        match import_one(&cache_dir, &legacy).await {
+            Ok(()) => {
+                imported += 1;
+                if let Some(origin) = source_origin(&legacy) {
+                    origins.insert(legacy.name.trim().to_string(), origin);
+                }
+            }
+            Err(e) => {
+                tracing::warn!(action = "plugins_legacy_import_skipped", plugin = %legacy.name, error = %e);
+            }
+        }
+    }
+
+    write_origins(&origins).await?;
+    NOTHING_PENDING.store(origins.is_empty(), Ordering::Relaxed);
+
+    let migrated: PathBuf = app_data_dir.join("plugins.json.migrated");
+    tokio::fs::rename(&list_path, &migrated)
+        .await
+        .with_context(|| format!("Failed to rename {}", list_path.display()))?;
+    remove_cache_dir(&cache_dir).await;
+    Ok(imported)
+}
+
+/// 把来源为 `origin` 的旧插件移入 `server_id` 分区。
+///
+/// # 返回值
+/// - `Ok(n)`：本次归属的插件数；目标分区已有同名插件时跳过（保留 legacy 副本）并移出索引。
+/// - `Err(anyhow::Error)`：读取/写入索引或移动目录失败。
+pub(super) async fn adopt_for_origin(origin: &str, server_id: &str) -> anyhow::Result<usize> {
+    if NOTHING_PENDING.load(Ordering::Relaxed) || server_id == LEGACY_SERVER_ID {
+        return Ok(0);
+    }
+    let _guard = ADOPT_LOCK.lock().await;
+    let mut origins = read_origins().await?;
+    let matched: Vec<String> = origins
+        .iter()
+        .filter(|(_, o)| o.as_str() == origin.trim())
+        .map(|(plugin_id, _)| plugin_id.clone())
+        .collect();
+    if matched.is_empty() {
+        NOTHING_PENDING.store(origins.is_empty(), Ordering::Relaxed);
+        return Ok(0);
+    }
+
+    let mut adopted = 0;
+    for plugin_id in matched {
+        let from = plugin_root_dir(LEGACY_SERVER_ID, &plugin_id)?;
+        let to = plugin_root_dir(server_id, &plugin_id)?;
+        if tokio::fs::try_exists(&to).await? {
+            tracing::warn!(action = "plugins_legacy_adopt_skipped", plugin_id = %plugin_id, server_id = %server_id);
+        } else if tokio::fs::try_exists(&from).await? {
+            if let Some(parent) = to.parent() {
+                tokio::fs::create_dir_all(parent).await?;
+            }
+            tokio::fs::rename(&from, &to)
+                .await
+                .with_context(|| format!("Failed to move {}", from.display()))?;
+            tracing::info!(action = "plugins_legacy_adopted", plugin_id = %plugin_id, server_id = %server_id);
+            adopted += 1;
+        }
+        origins.remove(&plugin_id);
+    }
+    write_origins(&origins).await?;
+    NOTHING_PENDING.store(origins.is_empty(), Ordering::Relaxed);
+    Ok(adopted)
+}
+
+async fn remove_cache_dir(cache_dir: &Path) {
+    match tokio::fs::remove_dir_all(cache_dir).await {
+        Ok(()) => {}
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => {}
+        Err(e) => {
+            tracing::warn!(action = "plugins_legacy_cache_remove_failed", path = %cache_dir.display(), error = %e);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn legacy_list_parses_old_manifest_shape() {
+        let raw = r#"{"plugins":[{"name":"test-plugin","version":"1.0.0","description":"A test plugin",
+            "author":"tester","license":"MIT","url":"https://example.com/plugin",
+            "frontend_sha256":"","backend_sha256":""}]}"#;
+        let list = parse_legacy_list(raw).expect("parse");
+        assert_eq!(list.len(), 1);
+        let manifest = to_manifest(&list[0]);
+        assert_eq!(manifest.plugin_id, "test-plugin");
+        assert_eq!(
+            source_origin(&list[0]).as_deref(),
+            Some("https://example.com")
+        );
+        assert_eq!(manifest.entry, "frontend.js");
+        assert!(manifest.min_host_version.is_empty());
+    }
+
+    #[test]
+    fn empty_and_invalid_lists() {
+        assert!(parse_legacy_list("  ").expect("empty").is_empty());
+        assert!(parse_legacy_list("{invalid json}").is_err());
+    }
+
+    #[test]
+    fn plugin_names_must_be_path_safe() {
+        assert!(is_valid_plugin_id("mc-bind_2.0"));
+        assert!(!is_valid_plugin_id(".."));
+        assert!(!is_valid_plugin_id("a/b"));
+        assert!(!is_valid_plugin_id("My Plugin"));
+    }
+}
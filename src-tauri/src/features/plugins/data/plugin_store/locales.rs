@@ -0,0 +1,237 @@
+//! plugin_store｜插件多语言资源（`locales/*.json`）。
+//!
+//! 说明：
+//! - 插件包可在版本目录下携带 `locales/<lang>.json`（JSON 对象，可嵌套），文件名即语言标记；
+//! - 语言标记统一规范化为小写、`-` 分隔（`zh_CN` / `zh-CN` 均视为 `zh-cn`），与宿主的 `zh_cn` 等标记可直接比较；
+//! - 回退链：精确匹配 → 主语言（`zh`）→ 同主语言的其它地区 → `en` / `en-us`；
+//!   返回的消息按回退链逐层合并，缺失的 key 落到更通用的语言上；
+//! - 解析结果按（路径、修改时间、大小）缓存，开发态链接修改文件后自动失效。
+
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use std::time::SystemTime;
+
+use anyhow::Context;
+use serde_json::{Map, Value};
+
+use super::paths::plugin_version_dir;
+use crate::features::plugins::domain::types::PluginLocaleCatalog;
+
+/// 语言资源目录（相对插件版本目录）。
+const LOCALES_DIR: &str = "locales";
+/// 单个语言文件的大小上限。
+const MAX_LOCALE_FILE_BYTES: u64 = 1024 * 1024;
+/// 缓存的语言文件数上限（超出时整体清空，卸载的插件版本随之淘汰）。
+const MAX_CACHED_CATALOGS: usize = 256;
+/// 最终回退语言。
+const DEFAULT_LANGS: &[&str] = &["en", "en-us"];
+
+/// 缓存校验键：（修改时间, 文件大小）。
+type CacheKey = (Option<SystemTime>, u64);
+type CachedCatalog = (CacheKey, Arc<Map<String, Value>>);
+
+static CACHE: Mutex<Option<HashMap<PathBuf, CachedCatalog>>> = Mutex::new(None);
+
+/// 规范化语言标记：小写、`_` 转 `-`。
+pub(super) fn normalize_lang(raw: &str) -> String {
+    raw.trim().replace('_', "-").to_ascii_lowercase()
+}
+
+fn primary(lang: &str) -> &str {
+    lang.split('-').next().unwrap_or(lang)
+}
+
+/// 计算回退链（最具体的在前，只包含 `available` 中存在的语言）。
+pub(super) fn fallback_chain(requested: &str, available: &[String]) -> Vec<String> {
+    let requested = normalize_lang(requested);
+    let mut chain: Vec<String> = Vec::new();
+    let mut push = |lang: &str| {
+        if available.iter().any(|a| a == lang) && !chain.iter().any(|c| c == lang) {
+            chain.push(lang.to_string());
+        }
+    };
+    if !requested.is_empty() {
+        push(&requested);
+        push(primary(&requested));
+        for lang in available {
+            if primary(lang) == primary(&requested) {
+                push(lang);
+            }
+        }
+    }
+    for lang in DEFAULT_LANGS {
+        push(lang);
+    }
+    chain
+}
+
+/// 深合并：`overlay` 中的 key 覆盖 `base`，对象递归合并。
+fn merge_into(base: &mut Map<String, Value>, overlay: &Map<String, Value>) {
+    for (key, value) in overlay {
+        match (base.get_mut(key), value) {
+            (Some(Value::Object(existing)), Value::Object(nested)) => merge_into(existing, nested),
+            _ => {
+                base.insert(key.clone(), value.clone());
+            }
+        }
+    }
+}
+
+/// 列出版本目录下的语言文件：`(规范化语言标记, 文件路径)`，按标记排序。
+fn locale_files(version_dir: &Path) -> Vec<(String, PathBuf)> {
+    let Ok(rd) = std::fs::read_dir(version_dir.join(LOCALES_DIR)) else {
+        return Vec::new();
+    };
+    let mut out: Vec<(String, PathBuf)> = rd
+        .filter_map(Result::ok)
+        .map(|ent| ent.path())
+        .filter(|path| {
+            path.is_file()
+                && path
+                    .extension()
+                    .is_some_and(|ext| ext.eq_ignore_ascii_case("json"))
+        })
+        .filter_map(|path| {
+            let lang = normalize_lang(path.file_stem()?.to_str()?);
+            (!lang.is_empty()).then_some((lang, path))
+        })
+        .collect();
+    out.sort();
+    out.dedup_by(|a, b| a.0 == b.0);
+    out
+}
+
+/// 插件版本携带的语言列表（用于运行时入口）。
+pub(super) async fn available_locales(version_dir: PathBuf) -> Vec<String> {
+    tokio::task::spawn_blocking(move || {
+        locale_files(&version_dir)
+            .into_iter()
+            .map(|(lang, _)| lang)
+            .collect()
+    })
+    .await
+    .unwrap_or_default()
+}
+
+/// 读取并解析语言文件（命中缓存时直接返回）；非对象或超限的文件视为不可用。
+fn load_catalog(path: &Path) -> anyhow::Result<Arc<Map<String, Value>>> {
+    let meta = std::fs::metadata(path)?;
+    if meta.len() > MAX_LOCALE_FILE_BYTES {
+        anyhow::bail!("Locale file is too large: {}", path.display());
+    }
+    let key: CacheKey = (meta.modified().ok(), meta.len());
+    if let Some((cached_key, catalog)) = CACHE
+        .lock()
+        .unwrap_or_else(|p| p.into_inner())
+        .as_ref()
+        .and_then(|cache| cache.get(path))
+        && *cached_key == key
+    {
+        return Ok(catalog.clone());
+    }
+
+    let raw = std::fs::read_to_string(path)?;
+    let Value::Object(map) = serde_json::from_str::<Value>(&raw)? else {
+        anyhow::bail!("Locale file must be a JSON object: {}", path.display());
+    };
+    let catalog = Arc::new(map);
+    let mut guard = CACHE.lock().unwrap_or_else(|p| p.into_inner());
+    let cache = guard.get_or_insert_with(HashMap::new);
+    if cache.len() >= MAX_CACHED_CATALOGS {
+        cache.clear();
+    }
+    cache.insert(path.to_path_buf(), (key, catalog.clone()));
+    Ok(catalog)
+}
+
+/// 解析插件某版本在 `lang` 下的语言资源（在 blocking 线程读取文件）。
+pub(super) async fn get_locale(
+    server_id: &str,
+    plugin_id: &str,
+    version: &str,
+    lang: &str,
+) -> anyhow::Result<PluginLocaleCatalog> {
+    let version_dir = plugin_version_dir(server_id, plugin_id, version)?;
+    let (plugin_id, version, requested) = (
+        plugin_id.to_string(),
+        version.to_string(),
+        normalize_lang(lang),
+    );
+    tokio::task::spawn_blocking(move || {
+        let files = locale_files(&version_dir);
+        let available: Vec<String> = files.iter().map(|(lang, _)| lang.clone()).collect();
+        let chain = fallback_chain(&requested, &available);
+
+        let mut messages = Map::new();
+        let mut resolved: Option<String> = None;
+        // 从最通用的语言开始合并，更具体的语言覆盖其上。
+        for lang in chain.iter().rev() {
+            let Some((_, path)) = files.iter().find(|(l, _)| l == lang) else {
+                continue;
+            };
+            match load_catalog(path) {
+                Ok(catalog) => {
+                    merge_into(&mut messages, &catalog);
+                    resolved = Some(lang.clone());
+                }
+                Err(e) => {
+                    tracing::warn!(action = "plugins_locale_invalid", plugin_id = %plugin_id, lang = %lang, error = %e);
+                }
+            }
+        }
+        PluginLocaleCatalog {
+            plugin_id,
+            version,
+            requested,
+            lang: resolved,
+            available,
+            messages,
+        }
+    })
+    .await
+    .context("Locale task failed")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn langs(items: &[&str]) -> Vec<String> {
+        items.iter().map(|s| s.to_string()).collect()
+    }
+
+    #[test]
+    fn fallback_prefers_exact_then_primary_then_english() {
+        let available = langs(&["en", "ja", "zh", "zh-tw"]);
+        assert_eq!(
+            fallback_chain("zh_CN", &available),
+            langs(&["zh", "zh-tw", "en"])
+        );
+        assert_eq!(
+            fallback_chain("zh-TW", &available),
+            langs(&["zh-tw", "zh", "en"])
+        );
+        assert_eq!(fallback_chain("fr", &available), langs(&["en"]));
+        assert_eq!(fallback_chain("", &langs(&["en-us"])), langs(&["en-us"]));
+        assert!(fallback_chain("fr", &langs(&["ja"])).is_empty());
+    }
+
+    #[test]
+    fn specific_catalogs_override_fallback_keys() {
+        let mut base =
+            serde_json::json!({"title": "Hello", "menu": {"open": "Open", "close": "Close"}})
+                .as_object()
+                .cloned()
+                .unwrap();
+        let overlay = serde_json::json!({"menu": {"open": "打开"}})
+            .as_object()
+            .cloned()
+            .unwrap();
+        merge_into(&mut base, &overlay);
+        assert_eq!(
+            Value::Object(base),
+            serde_json::json!({"title": "Hello", "menu": {"open": "打开", "close": "Close"}})
+        );
+    }
+}
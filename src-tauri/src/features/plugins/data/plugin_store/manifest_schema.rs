@@ -0,0 +1,310 @@
+//! plugin_store｜`plugin.json` 清单结构校验。
+//!
+//! 说明：
+//! - 反序列化前先按 JSON 结构逐项检查，一次性报告全部问题，而不是停在 serde 的第一个错误；
+//! - 规则：必填字段、版本号可按 semver 解析（与兼容性判断同样宽松）、权限必须是宿主认识的 key、
+//!   入口必须是包内相对路径、可选的 `settings` 声明合法；
+//! - 校验失败返回 [`PluginManifestInvalid`]，安装/开发链接/发布前自检共用同一套规则。
+
+use anyhow::Context;
+use serde_json::{Map, Value};
+
+use super::PluginManifestV1;
+use super::compat::parse_lenient;
+use crate::features::plugins::domain::types::{PluginManifestInvalid, PluginManifestIssue};
+
+/// 宿主认识的权限 key（与前端 `hostApiFactory` 的注入项一致）。
+const KNOWN_PERMISSIONS: &[&str] = &["network", "invoke", "events", "ui", "storage", "bus"];
+
+pub(super) fn issue(path: &str, code: &str, message: impl Into<String>) -> PluginManifestIssue {
+    PluginManifestIssue {
+        path: path.to_string(),
+        code: code.to_string(),
+        message: message.into(),
+    }
+}
+
+/// 读取必填字符串字段；缺失、类型错误或为空时记录问题并返回 `None`。
+fn required_str<'a>(
+    obj: &'a Map<String, Value>,
+    path: &str,
+    key: &str,
+    allow_empty: bool,
+    issues: &mut Vec<PluginManifestIssue>,
+) -> Option<&'a str> {
+    match obj.get(key) {
+        None | Some(Value::Null) => {
+            issues.push(issue(
+                path,
+                "missing_field",
+                format!("Missing required field `{key}`"),
+            ));
+            None
+        }
+        Some(Value::String(s)) if !allow_empty && s.trim().is_empty() => {
+            issues.push(issue(
+                path,
+                "empty_field",
+                format!("`{key}` must not be empty"),
+            ));
+            None
+        }
+        Some(Value::String(s)) => Some(s.as_str()),
+        Some(_) => {
+            issues.push(issue(
+                path,
+                "invalid_type",
+                format!("`{key}` must be a string"),
+            ));
+            None
+        }
+    }
+}
+
+/// 读取必填数组字段。
+fn required_array<'a>(
+    obj: &'a Map<String, Value>,
+    key: &str,
+    issues: &mut Vec<PluginManifestIssue>,
+) -> Option<&'a Vec<Value>> {
+    match obj.get(key) {
+        None | Some(Value::Null) => {
+            issues.push(issue(
+                key,
+                "missing_field",
+                format!("Missing required field `{key}` (use [] when empty)"),
+            ));
+            None
+        }
+        Some(Value::Array(items)) => Some(items),
+        Some(_) => {
+            issues.push(issue(
+                key,
+                "invalid_type",
+                format!("`{key}` must be an array"),
+            ));
+            None
+        }
+    }
+}
+
+/// 插件 id 会作为本地目录名与 `app://plugins/...` 的路径段。
+fn is_valid_plugin_id(id: &str) -> bool {
+    id == id.trim()
+        && id != "."
+        && id != ".."
+        && !id
+            .chars()
+            .any(|c| matches!(c, '/' | '\\' | ':') || c.is_control())
+}
+
+/// 入口必须是插件包内的相对路径（不允许绝对路径、盘符与 `.`/`..` 段）。
+pub(super) fn is_valid_entry(entry: &str) -> bool {
+    let entry = entry.trim().replace('\\', "/");
+    !entry.is_empty()
+        && !entry.starts_with('/')
+        && !entry.contains(':')
+        && entry
+            .split('/')
+            .all(|seg| !seg.is_empty() && seg != "." && seg != "..")
+}
+
+/// 校验清单 JSON，返回全部问题（空列表表示通过）。
+pub(super) fn validate_manifest(value: &Value) -> Vec<PluginManifestIssue> {
+    let mut issues = Vec::new();
+    let Some(obj) = value.as_object() else {
+        issues.push(issue(
+            "",
+            "invalid_type",
+            "plugin.json must be a JSON object",
+        ));
+        return issues;
+    };
+
+    if let Some(id) = required_str(obj, "plugin_id", "plugin_id", false, &mut issues)
+        && !is_valid_plugin_id(id)
+    {
+        issues.push(issue(
+            "plugin_id",
+            "invalid_plugin_id",
+            "`plugin_id` must not contain '/', '\\', ':', control characters or surrounding spaces",
+        ));
+    }
+    required_str(obj, "name", "name", false, &mut issues);
+    if let Some(version) = required_str(obj, "version", "version", false, &mut issues)
+        && parse_lenient(version).is_none()
+    {
+        issues.push(issue(
+            "version",
+            "invalid_version",
+            format!("`version` is not a semantic version: {version}"),
+        ));
+    }
+    // 空字符串表示不限制宿主版本。
+    if let Some(min) = required_str(
+        obj,
+        "min_host_version",
+        "min_host_version",
+        true,
+        &mut issues,
+    ) && !min.trim().is_empty()
+        && parse_lenient(min).is_none()
+    {
+        issues.push(issue(
+            "min_host_version",
+            "invalid_version",
+            format!("`min_host_version` is not a semantic version: {min}"),
+        ));
+    }
+    for key in ["description", "author", "license"] {
+        if !matches!(
+            obj.get(key),
+            None | Some(Value::Null) | Some(Value::String(_))
+        ) {
+            issues.push(issue(
+                key,
+                "invalid_type",
+                format!("`{key}` must be a string"),
+            ));
+        }
+    }
+    if let Some(entry) = required_str(obj, "entry", "entry", false, &mut issues)
+        && !is_valid_entry(entry)
+    {
+        issues.push(issue(
+            "entry",
+            "invalid_entry",
+            format!("`entry` must be a relative path inside the package: {entry}"),
+        ));
+    }
+
+    if let Some(permissions) = required_array(obj, "permissions", &mut issues) {
+        for (idx, item) in permissions.iter().enumerate() {
+            let path = format!("permissions[{idx}]");
+            match item.as_str() {
+                None => issues.push(issue(&path, "invalid_type", "Permission must be a string")),
+                Some(p) if !KNOWN_PERMISSIONS.contains(&p.trim()) => issues.push(issue(
+                    &path,
+                    "unknown_permission",
+                    format!(
+                        "Unknown permission `{p}` (expected one of: {})",
+                        KNOWN_PERMISSIONS.join(", ")
+                    ),
+                )),
+                Some(_) => {}
+            }
+        }
+    }
+
+    if let Some(domains) = required_array(obj, "provides_domains", &mut issues) {
+        for (idx, item) in domains.iter().enumerate() {
+            let base = format!("provides_domains[{idx}]");
+            let Some(domain) = item.as_object() else {
+                issues.push(issue(
+                    &base,
+                    "invalid_type",
+                    "Domain entry must be an object with `domain` and `domain_version`",
+                ));
+                continue;
+            };
+            for key in ["domain", "domain_version"] {
+                required_str(domain, &format!("{base}.{key}"), key, false, &mut issues);
+            }
+        }
+    }
+
+    if let Some(settings) = obj.get("settings").filter(|v| !v.is_null()) {
+        super::settings::validate_schema(settings, &mut issues);
+    }
+    issues
+}
+
+/// 解析并校验 `plugin.json`；不通过时返回携带全部问题的 [`PluginManifestInvalid`]。
+pub(super) fn parse_manifest(raw: &str) -> anyhow::Result<PluginManifestV1> {
+    let value: Value = serde_json::from_str(raw).map_err(|e| PluginManifestInvalid {
+        issues: vec![issue(
+            "",
+            "invalid_json",
+            format!("plugin.json is not valid JSON: {e}"),
+        )],
+    })?;
+    let issues = validate_manifest(&value);
+    if !issues.is_empty() {
+        return Err(PluginManifestInvalid { issues }.into());
+    }
+    serde_json::from_value(value).context("Invalid plugin.json")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn codes(issues: &[PluginManifestIssue]) -> Vec<(&str, &str)> {
+        issues
+            .iter()
+            .map(|i| (i.path.as_str(), i.code.as_str()))
+            .collect()
+    }
+
+    #[test]
+    fn valid_manifest_parses() {
+        let raw = r#"{
+            "plugin_id": "mc-bridge", "name": "MC Bridge", "version": "v1.2",
+            "min_host_version": "", "entry": "dist/index.html",
+            "permissions": ["network", "storage"],
+            "provides_domains": [{"domain": "mc", "domain_version": "1"}]
+        }"#;
+        let manifest = parse_manifest(raw).expect("valid manifest");
+        assert_eq!(manifest.plugin_id, "mc-bridge");
+        assert_eq!(manifest.provides_domains.len(), 1);
+    }
+
+    #[test]
+    fn all_problems_are_reported_at_once() {
+        let raw = r#"{
+            "plugin_id": "bad/id", "version": "latest", "min_host_version": "1.x",
+            "author": 3, "entry": "../index.html",
+            "permissions": ["network", "camera", 1],
+            "provides_domains": [{"domain": ""}, "mc"]
+        }"#;
+        let err = parse_manifest(raw).expect_err("invalid manifest");
+        let invalid = err
+            .downcast_ref::<PluginManifestInvalid>()
+            .expect("typed error");
+        assert_eq!(
+            codes(&invalid.issues),
+            vec![
+                ("plugin_id", "invalid_plugin_id"),
+                ("name", "missing_field"),
+                ("version", "invalid_version"),
+                ("min_host_version", "invalid_version"),
+                ("author", "invalid_type"),
+                ("entry", "invalid_entry"),
+                ("permissions[1]", "unknown_permission"),
+                ("permissions[2]", "invalid_type"),
+                ("provides_domains[0].domain", "empty_field"),
+                ("provides_domains[0].domain_version", "missing_field"),
+                ("provides_domains[1]", "invalid_type"),
+            ]
+        );
+    }
+
+    #[test]
+    fn syntax_errors_are_structured_too() {
+        let err = parse_manifest("{ not json").expect_err("invalid json");
+        let invalid = err
+            .downcast_ref::<PluginManifestInvalid>()
+            .expect("typed error");
+        assert_eq!(codes(&invalid.issues), vec![("", "invalid_json")]);
+    }
+
+    #[test]
+    fn entry_must_stay_inside_the_package() {
+        assert!(is_valid_entry("index.html"));
+        assert!(is_valid_entry("dist\\index.html"));
+        assert!(!is_valid_entry("/index.html"));
+        assert!(!is_valid_entry("C:/index.html"));
+        assert!(!is_valid_entry("dist/../../index.html"));
+        assert!(!is_valid_entry("dist//index.html"));
+    }
+}
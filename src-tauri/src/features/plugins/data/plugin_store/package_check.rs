@@ -0,0 +1,178 @@
+//! plugin_store｜插件包发布前自检（`plugins_validate_package`）。
+//!
+//! 说明：
+//! - 支持插件 zip 包与已构建的插件目录；只读取，不解压、不写入任何文件；
+//! - 清单规则与安装时一致（见 `manifest_schema`），另外检查入口文件是否存在；
+//! - zip 包额外检查安装时会被拒绝的条目：不安全路径、符号链接、前端源码文件。
+
+use std::collections::HashSet;
+use std::io::Read;
+use std::path::{Path, PathBuf};
+
+use anyhow::Context;
+use serde_json::Value;
+use zip::ZipArchive;
+
+use super::manifest_schema::{is_valid_entry, issue, validate_manifest};
+use super::unpack::{
+    detect_single_root_prefix, is_forbidden_source_file, is_zip_entry_symlink, is_zip_name_safe,
+    normalize_zip_name, strip_root_prefix,
+};
+use crate::features::plugins::domain::types::{PluginManifestIssue, PluginPackageValidation};
+
+const MANIFEST_NAME: &str = "plugin.json";
+
+/// 自检插件包（zip 文件或目录）。
+pub(super) async fn validate_package(path: PathBuf) -> anyhow::Result<PluginPackageValidation> {
+    tokio::task::spawn_blocking(move || {
+        let meta = std::fs::metadata(&path)
+            .with_context(|| format!("Plugin package not found: {}", path.display()))?;
+        if meta.is_dir() {
+            Ok(validate_dir(&path))
+        } else {
+            validate_zip(&path)
+        }
+    })
+    .await
+    .context("Package validation task failed")?
+}
+
+fn validate_dir(dir: &Path) -> PluginPackageValidation {
+    let raw = std::fs::read_to_string(dir.join(MANIFEST_NAME)).ok();
+    check_manifest(
+        raw.as_deref(),
+        |entry| dir.join(entry).is_file(),
+        Vec::new(),
+    )
+}
+
+fn validate_zip(path: &Path) -> anyhow::Result<PluginPackageValidation> {
+    let file = std::fs::File::open(path)
+        .with_context(|| format!("Failed to open plugin package: {}", path.display()))?;
+    let mut archive = ZipArchive::new(file).context("Invalid zip archive")?;
+    let mut issues = Vec::new();
+
+    let mut names: Vec<String> = Vec::new();
+    for i in 0..archive.len() {
+        let entry = archive.by_index(i)?;
+        let name = normalize_zip_name(entry.name());
+        if entry.is_dir() || name.is_empty() {
+            continue;
+        }
+        if !is_zip_name_safe(&name) {
+            issues.push(issue(&name, "unsafe_path", "Zip entry path is not allowed"));
+        } else if is_zip_entry_symlink(&entry) {
+            issues.push(issue(&name, "symlink", "Zip entries must not be symlinks"));
+        }
+        names.push(name);
+    }
+
+    // 与安装时一致：所有内容包在单一根目录下时去掉该前缀。
+    let prefix = detect_single_root_prefix(&names);
+    let mut files: HashSet<String> = HashSet::new();
+    let mut manifest_name: Option<&str> = None;
+    for name in &names {
+        let stripped = match prefix.as_deref() {
+            Some(prefix) => strip_root_prefix(name, prefix),
+            None => name.clone(),
+        };
+        if is_forbidden_source_file(&stripped) {
+            issues.push(issue(
+                &stripped,
+                "forbidden_file",
+                "Source files (.vue/.ts/.scss/...) must not be shipped; package the build output",
+            ));
+        }
+        if stripped == MANIFEST_NAME {
+            manifest_name = Some(name);
+        }
+        files.insert(stripped);
+    }
+
+    let raw = match manifest_name {
+        Some(name) => {
+            let mut raw = String::new();
+            archive
+                .by_name(name)?
+                .read_to_string(&mut raw)
+                .context("Failed to read plugin.json")?;
+            Some(raw)
+        }
+        None => None,
+    };
+    Ok(check_manifest(
+        raw.as_deref(),
+        |entry| files.contains(entry),
+        issues,
+    ))
+}
+
+/// 校验清单并检查入口文件存在；汇总为自检结果。
+fn check_manifest(
+    raw: Option<&str>,
+    has_file: impl Fn(&str) -> bool,
+    mut issues: Vec<PluginManifestIssue>,
+) -> PluginPackageValidation {
+    let mut plugin_id = None;
+    let mut version = None;
+    match raw.map(serde_json::from_str::<Value>) {
+        None => issues.push(issue(
+            MANIFEST_NAME,
+            "missing_manifest",
+            "Package has no plugin.json at its root",
+        )),
+        Some(Err(e)) => issues.push(issue(
+            "",
+            "invalid_json",
+            format!("plugin.json is not valid JSON: {e}"),
+        )),
+        Some(Ok(value)) => {
+            issues.extend(validate_manifest(&value));
+            let field = |key: &str| value.get(key).and_then(Value::as_str).map(str::to_string);
+            plugin_id = field("plugin_id");
+            version = field("version");
+            if let Some(entry) = field("entry")
+                && is_valid_entry(&entry)
+            {
+                let entry = entry.trim().replace('\\', "/");
+                if !has_file(&entry) {
+                    issues.push(issue(
+                        "entry",
+                        "entry_not_found",
+                        format!("Entry file does not exist in the package: {entry}"),
+                    ));
+                }
+            }
+        }
+    }
+    PluginPackageValidation {
+        valid: issues.is_empty(),
+        plugin_id,
+        version,
+        issues,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn missing_entry_file_is_reported() {
+        let raw = r#"{
+            "plugin_id": "demo", "name": "Demo", "version": "1.0.0", "min_host_version": "",
+            "entry": "dist/index.html", "permissions": [], "provides_domains": []
+        }"#;
+        let ok = check_manifest(Some(raw), |e| e == "dist/index.html", Vec::new());
+        assert!(ok.valid);
+        assert_eq!(ok.plugin_id.as_deref(), Some("demo"));
+
+        let missing = check_manifest(Some(raw), |_| false, Vec::new());
+        assert!(!missing.valid);
+        assert_eq!(missing.issues[0].code, "entry_not_found");
+
+        let none = check_manifest(None, |_| true, Vec::new());
+        assert_eq!(none.issues[0].code, "missing_manifest");
+        assert_eq!(none.version, None);
+    }
+}
@@ -83,6 +83,21 @@ pub(super) fn state_file_path(server_id: &str, plugin_id: &str) -> anyhow::Resul
     Ok(plugin_root_dir(server_id, plugin_id)?.join("state.json"))
 }
 
+/// `history.json` 路径：记录可回滚的 known-good 版本与连续失败次数。
+pub(super) fn history_file_path(server_id: &str, plugin_id: &str) -> anyhow::Result<PathBuf> {
+    Ok(plugin_root_dir(server_id, plugin_id)?.join("history.json"))
+}
+
+/// `trust.json` 路径：各已安装版本安装时确定的信任级别。
+pub(super) fn trust_file_path(server_id: &str, plugin_id: &str) -> anyhow::Result<PathBuf> {
+    Ok(plugin_root_dir(server_id, plugin_id)?.join("trust.json"))
+}
+
+/// `dev.json` 路径：开发态链接信息（源目录）。
+pub(super) fn dev_link_file_path(server_id: &str, plugin_id: &str) -> anyhow::Result<PathBuf> {
+    Ok(plugin_root_dir(server_id, plugin_id)?.join("dev.json"))
+}
+
 /// `plugin.json` 路径：插件清单文件（位于版本目录）。
 pub(super) fn manifest_file_path(
     server_id: &str,
@@ -169,6 +184,51 @@ pub(super) fn resolve_app_plugins_canonical_file_path(
     Ok(canonical_file)
 }
 
+fn encode_url_segment(seg: &str) -> String {
+    let mut out = String::with_capacity(seg.len());
+    for b in seg.bytes() {
+        if b.is_ascii_alphanumeric() || matches!(b, b'-' | b'_' | b'.' | b'~') {
+            out.push(b as char);
+        } else {
+            out.push_str(&format!("%{b:02X}"));
+        }
+    }
+    out
+}
+
+/// 构造插件包内资源的 `app://plugins/<server_id>/<plugin_id>/<version>/<path>` 地址。
+///
+/// 说明：
+/// - 与前端 `runtimeGateway` 的拼接规则一致（各段 percent-encode）；
+/// - 相对路径做与 `resolve_app_plugins_path` 相同的穿越校验，不做 IO。
+pub(super) fn app_plugins_url(
+    server_id: &str,
+    plugin_id: &str,
+    version: &str,
+    rel_path: &str,
+) -> anyhow::Result<String> {
+    let rel = rel_path.trim().trim_start_matches('/');
+    if rel.is_empty() || rel.contains('\\') {
+        return Err(anyhow::anyhow!("Invalid relative path"));
+    }
+    let mut rel_segments = Vec::new();
+    for seg in rel.split('/') {
+        if seg.is_empty() || seg == "." || seg == ".." {
+            return Err(anyhow::anyhow!("Invalid relative path segment"));
+        }
+        rel_segments.push(encode_url_segment(seg));
+    }
+    let root = [server_id, plugin_id, version]
+        .iter()
+        .map(|seg| sanitize_segment(seg).map(|seg| encode_url_segment(&seg)))
+        .collect::<anyhow::Result<Vec<_>>>()?;
+    Ok(format!(
+        "app://plugins/{}/{}",
+        root.join("/"),
+        rel_segments.join("/")
+    ))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -307,4 +367,14 @@ mod tests {
 
         cleanup_dir(&app_dir);
     }
+
+    #[test]
+    fn app_plugins_url_encodes_segments() {
+        assert_eq!(
+            app_plugins_url("srv", "my plugin", "1.0.0", "/assets/icon.png").expect("url"),
+            "app://plugins/srv/my%20plugin/1.0.0/assets/icon.png"
+        );
+        assert!(app_plugins_url("srv", "p", "1.0.0", "../secret").is_err());
+        assert!(app_plugins_url("srv", "p", "..", "icon.png").is_err());
+    }
 }
@@ -0,0 +1,161 @@
+//! plugin_store｜启动预加载：扫描已启用插件并校验，提前停用损坏的安装。
+//!
+//! 说明：
+//! - 只扫描本地 `data/plugins`（按 server_id），不访问网络；仅处理启用且状态正常、有当前版本的插件；
+//! - 校验失败的正式安装插件按损坏处理（停用 + failed）；开发态插件只报告失败，由开发者自行修复；
+//! - 插件只有前端运行时（由 domain 注册表在连接服务端后加载），这里不启动任何代码；
+//!   结果经 `plugin-runtime-ready` 交给前端，用于卸载已被停用的运行时并刷新插件状态。
+
+use std::collections::BTreeMap;
+
+use super::{
+    InstalledPluginState, check_version_loadable, get_runtime_entry_for_version_inner,
+    list_all_installed, mark_broken, verify_installed_version,
+};
+use crate::features::plugins::domain::types::{
+    PluginPreloadFailure, PluginRuntimeEntry, PluginRuntimeReady,
+};
+
+/// 预加载候选：`server_id` -> 启用且状态正常的插件状态（按扫描顺序）。
+fn preload_candidates(
+    installed: Vec<(String, InstalledPluginState)>,
+) -> BTreeMap<String, Vec<InstalledPluginState>> {
+    let mut out: BTreeMap<String, Vec<InstalledPluginState>> = BTreeMap::new();
+    for (server_id, state) in installed {
+        if state.enabled && state.status == "ok" && state.current_version.is_some() {
+            out.entry(server_id).or_default().push(state);
+        }
+    }
+    out
+}
+
+/// 校验单个插件；失败时返回失败记录。
+async fn preload_one(
+    server_id: &str,
+    state: &InstalledPluginState,
+    version: &str,
+) -> Result<PluginRuntimeEntry, PluginPreloadFailure> {
+    let plugin_id = state.plugin_id.as_str();
+    let failure = |error: String, disabled: bool| PluginPreloadFailure {
+        plugin_id: plugin_id.to_string(),
+        version: version.to_string(),
+        error,
+        disabled,
+    };
+
+    // 开发态插件的清单随源码变化，只要求可加载。
+    let checked = if state.dev {
+        check_version_loadable(server_id, plugin_id, version).await
+    } else {
+        verify_installed_version(server_id, plugin_id, version).await
+    };
+    let entry = match checked {
+        Ok(()) => get_runtime_entry_for_version_inner("", server_id, plugin_id, version).await,
+        Err(e) => Err(e),
+    };
+    match entry {
+        Ok(entry) => Ok(entry),
+        Err(e) => {
+            let message = e.to_string();
+            let disabled = !state.dev
+                && match mark_broken(server_id, plugin_id, &message).await {
+                    Ok(()) => true,
+                    Err(e) => {
+                        tracing::warn!(action = "plugins_preload_mark_broken_failed", plugin_id = %plugin_id, error = %e);
+                        false
+                    }
+                };
+            Err(failure(message, disabled))
+        }
+    }
+}
+
+/// 预加载所有服务端下已启用的插件，按服务端返回结果（没有启用插件的服务端不出现）。
+pub(super) async fn preload_enabled() -> anyhow::Result<Vec<PluginRuntimeReady>> {
+    let mut out = Vec::new();
+    for (server_id, states) in preload_candidates(list_all_installed().await?) {
+        let mut result = PluginRuntimeReady {
+            server_id: server_id.clone(),
+            ready: Vec::new(),
+            failed: Vec::new(),
+        };
+        for state in &states {
+            let Some(version) = state.current_version.as_deref() else {
+                continue;
+            };
+            match preload_one(&server_id, state, version).await {
+                Ok(entry) => result.ready.push(entry),
+                Err(failure) => {
+                    tracing::warn!(
+                        action = "plugins_preload_failed",
+                        plugin_id = %failure.plugin_id,
+                        version = %failure.version,
+                        disabled = failure.disabled,
+                        error = %failure.error
+                    );
+                    result.failed.push(failure);
+                }
+            }
+        }
+        tracing::info!(
+            action = "plugins_preload_server_completed",
+            server_id = %server_id,
+            ready = result.ready.len(),
+            failed = result.failed.len()
+        );
+        out.push(result);
+    }
+    Ok(out)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn state(
+        plugin_id: &str,
+        enabled: bool,
+        status: &str,
+        current: Option<&str>,
+    ) -> InstalledPluginState {
+        InstalledPluginState {
+            plugin_id: plugin_id.to_string(),
+            installed_versions: current.iter().map(|v| v.to_string()).collect(),
+            current_version: current.map(str::to_string),
+            enabled,
+            status: status.to_string(),
+            last_error: String::new(),
+            dev: false,
+            dev_path: None,
+            trust_level: Default::default(),
+        }
+    }
+
+    #[test]
+    fn only_enabled_healthy_plugins_are_preloaded() {
+        let installed = vec![
+            ("srv-b".to_string(), state("a", true, "ok", Some("1.0.0"))),
+            (
+                "srv-a".to_string(),
+                state("off", false, "ok", Some("1.0.0")),
+            ),
+            (
+                "srv-a".to_string(),
+                state("broken", true, "failed", Some("1.0.0")),
+            ),
+            ("srv-a".to_string(), state("empty", true, "ok", None)),
+            ("srv-a".to_string(), state("b", true, "ok", Some("2.0.0"))),
+        ];
+        let candidates = preload_candidates(installed);
+        let ids: Vec<(&str, Vec<&str>)> = candidates
+            .iter()
+            .map(|(server, states)| {
+                (
+                    server.as_str(),
+                    states.iter().map(|s| s.plugin_id.as_str()).collect(),
+                )
+            })
+            .collect();
+        assert_eq!(ids, vec![("srv-a", vec!["b"]), ("srv-b", vec!["a"])]);
+    }
+}
@@ -0,0 +1,178 @@
+//! plugin_store｜旧版本清理（GC）。
+//!
+//! 说明：
+//! - 当前版本与“上一个版本”（`history.json` 中最近的 known-good，缺失时取最近安装的其它版本）
+//!   永远保留，保证随时可回滚；
+//! - 其余版本按安装时间保留最新的 `keep_latest_n` 个，更早的版本目录整体删除。
+
+use std::path::Path;
+
+use super::dev_link::DEV_VERSION;
+use super::paths::{base_plugins_dir, plugin_version_dir};
+use super::state::{list_installed_versions, read_current, read_history, write_history};
+use crate::features::plugins::domain::types::PluginPruneResult;
+
+/// 选出可删除的版本。
+///
+/// # 参数
+/// - `versions`：已安装版本（按安装时间从旧到新）。
+/// - `protected`：必须保留的版本（当前/上一个）。
+/// - `keep_latest_n`：除受保护版本外额外保留的最新版本数。
+fn select_prunable(versions: &[String], protected: &[&str], keep_latest_n: usize) -> Vec<String> {
+    let mut candidates: Vec<String> = versions
+        .iter()
+        .filter(|v| !protected.contains(&v.as_str()))
+        .cloned()
+        .collect();
+    candidates.truncate(candidates.len().saturating_sub(keep_latest_n));
+    candidates
+}
+
+/// 递归统计目录大小（不跟随符号链接）。
+async fn dir_size(root: &Path) -> u64 {
+    let mut total = 0u64;
+    let mut stack = vec![root.to_path_buf()];
+    while let Some(dir) = stack.pop() {
+        let Ok(mut rd) = tokio::fs::read_dir(&dir).await else {
+            continue;
+        };
+        while let Ok(Some(ent)) = rd.next_entry().await {
+            let Ok(meta) = tokio::fs::symlink_metadata(ent.path()).await else {
+                continue;
+            };
+            if meta.is_dir() {
+                stack.push(ent.path());
+            } else {
+                total = total.saturating_add(meta.len());
+            }
+        }
+    }
+    total
+}
+
+/// 清理单个插件的旧版本。
+pub(super) async fn prune_plugin(
+    server_id: &str,
+    plugin_id: &str,
+    keep_latest_n: u32,
+) -> anyhow::Result<PluginPruneResult> {
+    // 开发态链接不是下载的版本，永远不参与清理。
+    let versions: Vec<String> = list_installed_versions(server_id, plugin_id)
+        .await?
+        .into_iter()
+        .filter(|v| v != DEV_VERSION)
+        .collect();
+    let current = read_current(server_id, plugin_id)
+        .await?
+        .map(|c| c.version)
+        .unwrap_or_default();
+    let mut history = read_history(server_id, plugin_id).await?;
+    let previous = history
+        .rollback_target(&current)
+        .filter(|v| versions.iter().any(|installed| installed == v))
+        .map(str::to_string)
+        .or_else(|| versions.iter().rev().find(|v| **v != current).cloned())
+        .unwrap_or_default();
+
+    let prunable = select_prunable(
+        &versions,
+        &[current.as_str(), previous.as_str()],
+        keep_latest_n as usize,
+    );
+    let mut result = PluginPruneResult {
+        plugin_id: plugin_id.to_string(),
+        removed_versions: vec![],
+        freed_bytes: 0,
+    };
+    for version in prunable {
+        let dir = plugin_version_dir(server_id, plugin_id, &version)?;
+        let size = dir_size(&dir).await;
+        match tokio::fs::remove_dir_all(&dir).await {
+            Ok(()) => {
+                history.forget(&version);
+                result.freed_bytes = result.freed_bytes.saturating_add(size);
+                result.removed_versions.push(version);
+            }
+            Err(e) => {
+                tracing::warn!(action = "plugins_prune_version_failed", plugin_id = %plugin_id, version = %version, error = %e);
+            }
+        }
+    }
+    if !result.removed_versions.is_empty() {
+        write_history(server_id, plugin_id, &history).await?;
+        tracing::info!(action = "plugins_prune_completed", plugin_id = %plugin_id, removed = result.removed_versions.len(), freed_bytes = result.freed_bytes);
+    }
+    Ok(result)
+}
+
+/// 清理本地所有服务端下所有插件的旧版本（自动策略使用，无需网络）。
+pub(super) async fn prune_all(keep_latest_n: u32) -> anyhow::Result<Vec<PluginPruneResult>> {
+    let base = base_plugins_dir()?;
+    let mut results = Vec::new();
+    let mut servers = match tokio::fs::read_dir(&base).await {
+        Ok(rd) => rd,
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => return Ok(results),
+        Err(err) => return Err(err.into()),
+    };
+    while let Some(server) = servers.next_entry().await? {
+        if !server.file_type().await?.is_dir() {
+            continue;
+        }
+        let server_id = server.file_name().to_string_lossy().to_string();
+        let mut plugins = tokio::fs::read_dir(server.path()).await?;
+        while let Some(plugin) = plugins.next_entry().await? {
+            if !plugin.file_type().await?.is_dir() {
+                continue;
+            }
+            let plugin_id = plugin.file_name().to_string_lossy().to_string();
+            match prune_plugin(&server_id, &plugin_id, keep_latest_n).await {
+                Ok(result) if !result.removed_versions.is_empty() => results.push(result),
+                Ok(_) => {}
+                Err(e) => {
+                    tracing::warn!(action = "plugins_prune_plugin_failed", server_id = %server_id, plugin_id = %plugin_id, error = %e);
+                }
+            }
+        }
+    }
+    Ok(results)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn versions(list: &[&str]) -> Vec<String> {
+        list.iter().map(|v| v.to_string()).collect()
+    }
+
+    #[test]
+    fn protected_versions_are_never_pruned() {
+        let installed = versions(&["0.1.0", "0.2.0", "0.3.0", "0.4.0", "0.5.0"]);
+        assert_eq!(
+            select_prunable(&installed, &["0.5.0", "0.1.0"], 1),
+            versions(&["0.2.0", "0.3.0"])
+        );
+        assert_eq!(
+            select_prunable(&installed, &["0.3.0", "0.2.0"], 0),
+            versions(&["0.1.0", "0.4.0", "0.5.0"])
+        );
+        assert!(select_prunable(&installed, &["0.5.0", "0.4.0"], 10).is_empty());
+    }
+
+    #[tokio::test]
+    async fn dir_size_counts_nested_files() {
+        let root = std::env::temp_dir().join(format!(
+            "carrypigeon-prune-size-{}-{}",
+            std::process::id(),
+            std::time::SystemTime::now()
+                .duration_since(std::time::UNIX_EPOCH)
+                .map(|d| d.as_nanos())
+                .unwrap_or(0)
+        ));
+        std::fs::create_dir_all(root.join("assets")).expect("create dirs");
+        std::fs::write(root.join("index.js"), [0u8; 10]).expect("write entry");
+        std::fs::write(root.join("assets/icon.png"), [0u8; 32]).expect("write asset");
+        assert_eq!(dir_size(&root).await, 42);
+        let _ = std::fs::remove_dir_all(&root);
+    }
+}
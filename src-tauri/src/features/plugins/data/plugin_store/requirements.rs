@@ -0,0 +1,211 @@
+//! plugin_store｜服务端必装插件（`/api/server` 的 `required_plugins`）检查。
+//!
+//! 说明：
+//! - `required_plugins` 兼容两种写法：插件 id 字符串，或 `{ plugin_id, min_version }`；
+//! - 未声明 `min_version` 时以目录版本为要求版本（服务端通过目录推送必装插件的更新）；
+//! - 开发态插件只要求已启用，不比较版本；
+//! - 本模块只负责判定，安装编排（下载/切换版本/启用 + 进度事件）在用例层完成。
+
+use super::api::{
+    ApiRequiredPlugin, fetch_plugin_catalog, fetch_required_plugins, fetch_server_id_with_client,
+};
+use super::compat::parse_lenient;
+use super::origin::to_http_origin;
+use super::paths::plugin_root_dir;
+use super::state::build_installed_state;
+use super::tls::build_server_client;
+use crate::features::plugins::domain::types::{
+    InstalledPluginState, PluginRequirement, PluginRequirementStatus, PluginRequirementsReport,
+};
+
+/// 规范化必装声明为 `(plugin_id, min_version)`：去空、去重并保持声明顺序。
+fn normalize(required: Vec<ApiRequiredPlugin>) -> Vec<(String, Option<String>)> {
+    let mut out: Vec<(String, Option<String>)> = Vec::new();
+    for item in required {
+        let (plugin_id, min_version) = match item {
+            ApiRequiredPlugin::Id(plugin_id) => (plugin_id, None),
+            ApiRequiredPlugin::Spec {
+                plugin_id,
+                min_version,
+            } => (plugin_id, min_version),
+        };
+        let plugin_id = plugin_id.trim().to_string();
+        let min_version = min_version
+            .map(|v| v.trim().to_string())
+            .filter(|v| !v.is_empty());
+        if plugin_id.is_empty() || out.iter().any(|(seen, _)| *seen == plugin_id) {
+            continue;
+        }
+        out.push((plugin_id, min_version));
+    }
+    out
+}
+
+/// `current` 是否低于 `required`；任一方无法按 semver 解析时，版本不同即视为过旧。
+fn is_older(current: &str, required: &str) -> bool {
+    match (parse_lenient(current), parse_lenient(required)) {
+        (Some(current), Some(required)) => current < required,
+        _ => current.trim() != required.trim(),
+    }
+}
+
+/// 判定单个必装插件的满足情况。
+fn evaluate(
+    plugin_id: &str,
+    min_version: Option<&str>,
+    installed: Option<&InstalledPluginState>,
+    available: Option<&str>,
+) -> PluginRequirement {
+    let required = min_version.or(available);
+    let current = installed.and_then(|s| s.current_version.as_deref());
+    let installable = available.is_some_and(|a| min_version.is_none_or(|min| !is_older(a, min)));
+    let status = match (installed, current) {
+        (Some(state), Some(current))
+            if state.dev || required.is_none_or(|r| !is_older(current, r)) =>
+        {
+            if state.enabled && state.status == "ok" {
+                PluginRequirementStatus::Satisfied
+            } else {
+                PluginRequirementStatus::Disabled
+            }
+        }
+        _ if !installable => PluginRequirementStatus::Unavailable,
+        (_, Some(_)) => PluginRequirementStatus::Outdated,
+        (_, None) => PluginRequirementStatus::Missing,
+    };
+    PluginRequirement {
+        plugin_id: plugin_id.to_string(),
+        status,
+        required_version: required.map(str::to_string),
+        installed_version: current.map(str::to_string),
+        available_version: available.map(str::to_string),
+        error: None,
+    }
+}
+
+/// 读取本地安装状态；非法 id 或未安装时返回 `None`。
+async fn installed_state(
+    server_id: &str,
+    plugin_id: &str,
+) -> anyhow::Result<Option<InstalledPluginState>> {
+    let Ok(root) = plugin_root_dir(server_id, plugin_id) else {
+        return Ok(None);
+    };
+    if tokio::fs::metadata(&root).await.is_err() {
+        return Ok(None);
+    }
+    Ok(Some(build_installed_state(server_id, plugin_id).await?))
+}
+
+/// 检查服务端声明的必装插件（目录实时拉取，与随后的安装保持一致）。
+pub(super) async fn check_requirements(
+    server_socket: &str,
+    tls_policy: Option<&str>,
+    tls_fingerprint: Option<&str>,
+) -> anyhow::Result<PluginRequirementsReport> {
+    let origin = to_http_origin(server_socket)?;
+    let client = build_server_client(&origin, tls_policy, tls_fingerprint).await?;
+    let server_id = fetch_server_id_with_client(&origin, &client).await?;
+    let required = normalize(fetch_required_plugins(&origin, &client).await?);
+
+    let mut requirements = Vec::with_capacity(required.len());
+    if !required.is_empty() {
+        let catalog = fetch_plugin_catalog(&origin, &client).await?;
+        for (plugin_id, min_version) in &required {
+            let available = catalog
+                .plugins
+                .iter()
+                .find(|p| p.plugin_id.trim() == plugin_id)
+                .map(|p| p.version.trim());
+            let installed = installed_state(&server_id, plugin_id).await?;
+            requirements.push(evaluate(
+                plugin_id,
+                min_version.as_deref(),
+                installed.as_ref(),
+                available,
+            ));
+        }
+    }
+    let satisfied = requirements
+        .iter()
+        .all(|r| r.status == PluginRequirementStatus::Satisfied);
+    Ok(PluginRequirementsReport {
+        server_id,
+        requirements,
+        satisfied,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use PluginRequirementStatus::*;
+
+    fn installed(current: &str, enabled: bool, status: &str) -> InstalledPluginState {
+        InstalledPluginState {
+            plugin_id: "renderer".to_string(),
+            installed_versions: vec![current.to_string()],
+            current_version: Some(current.to_string()),
+            enabled,
+            status: status.to_string(),
+            last_error: String::new(),
+            dev: false,
+            dev_path: None,
+            trust_level: Default::default(),
+        }
+    }
+
+    #[test]
+    fn both_declaration_forms_are_accepted() {
+        let raw = serde_json::json!([
+            " renderer ",
+            { "plugin_id": "math", "min_version": "1.2" },
+            { "plugin_id": "renderer", "min_version": "9.0.0" },
+            "",
+        ]);
+        let required: Vec<ApiRequiredPlugin> = serde_json::from_value(raw).expect("required");
+        assert_eq!(
+            normalize(required),
+            vec![
+                ("renderer".to_string(), None),
+                ("math".to_string(), Some("1.2".to_string())),
+            ]
+        );
+    }
+
+    #[test]
+    fn requirement_status_follows_install_state() {
+        let status = |min: Option<&str>, state: Option<&InstalledPluginState>, available| {
+            evaluate("renderer", min, state, available).status
+        };
+        let ok = installed("1.2.0", true, "ok");
+
+        assert_eq!(status(None, None, Some("1.2.0")), Missing);
+        assert_eq!(status(None, None, None), Unavailable);
+        assert_eq!(status(None, Some(&ok), Some("1.2.0")), Satisfied);
+        // 未声明最低版本时以目录版本为准。
+        assert_eq!(status(None, Some(&ok), Some("1.3.0")), Outdated);
+        assert_eq!(status(Some("1.1"), Some(&ok), Some("1.3.0")), Satisfied);
+        assert_eq!(status(Some("1.3"), Some(&ok), Some("1.3.0")), Outdated);
+        // 目录版本也达不到要求。
+        assert_eq!(status(Some("2.0"), Some(&ok), Some("1.3.0")), Unavailable);
+        // 已从目录下架但本地版本满足要求。
+        assert_eq!(status(None, Some(&ok), None), Satisfied);
+        assert_eq!(
+            status(None, Some(&installed("1.2.0", false, "ok")), Some("1.2.0")),
+            Disabled
+        );
+        assert_eq!(
+            status(
+                None,
+                Some(&installed("1.2.0", true, "failed")),
+                Some("1.2.0")
+            ),
+            Disabled
+        );
+
+        let mut dev = installed("0.1.0", true, "ok");
+        dev.dev = true;
+        assert_eq!(status(Some("1.0"), Some(&dev), Some("1.3.0")), Satisfied);
+    }
+}
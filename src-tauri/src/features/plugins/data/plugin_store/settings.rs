@@ -0,0 +1,450 @@
+//! plugin_store｜插件设置（清单 `settings` schema + KV 存储中的取值）。
+//!
+//! 说明：
+//! - 插件在 `plugin.json` 的 `settings` 中声明设置项（类型、默认值、范围/选项）；
+//!   安装时由 `manifest_schema` 严格校验，运行时按字段宽松解析（单个非法字段被忽略，不影响插件加载）；
+//! - 取值保存在插件 KV 存储（`storage.json`）的保留 key [`SETTINGS_STORAGE_KEY`] 下，只保存用户显式修改过的项；
+//! - 读取时以默认值为底、已存值覆盖；已存值不再满足 schema（插件升级改了类型/范围）时回落到默认值；
+//! - 更新以补丁形式提交：`null` 表示恢复默认；任一项不合法则整批拒绝。
+
+use serde_json::{Map, Value};
+
+use super::PluginManifestV1;
+use super::manifest_schema::issue;
+use super::paths::{manifest_file_path, storage_file_path};
+use super::state::read_current;
+use super::storage::{atomic_write, read_storage_map, storage_file_lock};
+use crate::features::plugins::domain::types::{
+    PluginManifestIssue, PluginSettingField, PluginSettingKind, PluginSettingsInvalid,
+    PluginSettingsSchema, PluginSettingsValues,
+};
+
+/// 设置值在插件 KV 存储中的保留 key（插件自身的 `storage_set` 不能写入）。
+pub(super) const SETTINGS_STORAGE_KEY: &str = "__settings__";
+/// 单个插件最多声明的设置项数量。
+const MAX_SETTING_FIELDS: usize = 64;
+
+fn is_valid_key(key: &str) -> bool {
+    !key.is_empty()
+        && key.len() <= 64
+        && key
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, '_' | '-' | '.'))
+}
+
+/// 校验取值是否满足设置项声明。
+///
+/// # 返回值
+/// - `Err(PluginManifestIssue)`：`path` 为设置项 key，`code` 为 `invalid_setting_value`。
+pub(super) fn check_value(
+    field: &PluginSettingField,
+    value: &Value,
+) -> Result<(), PluginManifestIssue> {
+    let invalid = |message: String| issue(&field.key, "invalid_setting_value", message);
+    let in_range = |n: f64| {
+        if field.min.is_some_and(|min| n < min) || field.max.is_some_and(|max| n > max) {
+            return Err(invalid(format!(
+                "Value must be within [{}, {}]",
+                field
+                    .min
+                    .map(|v| v.to_string())
+                    .unwrap_or_else(|| "-inf".into()),
+                field
+                    .max
+                    .map(|v| v.to_string())
+                    .unwrap_or_else(|| "inf".into())
+            )));
+        }
+        Ok(())
+    };
+    let expected = |what: &str| invalid(format!("Expected {what}"));
+    match field.kind {
+        PluginSettingKind::Boolean => value
+            .is_boolean()
+            .then_some(())
+            .ok_or_else(|| expected("a boolean")),
+        PluginSettingKind::String => {
+            let s = value.as_str().ok_or_else(|| expected("a string"))?;
+            match field.max_length {
+                Some(max) if s.chars().count() > max => {
+                    Err(invalid(format!("Value must be at most {max} characters")))
+                }
+                _ => Ok(()),
+            }
+        }
+        PluginSettingKind::Number => in_range(value.as_f64().ok_or_else(|| expected("a number"))?),
+        PluginSettingKind::Integer => {
+            let n = value
+                .as_i64()
+                .or_else(|| value.as_u64().and_then(|n| i64::try_from(n).ok()))
+                .ok_or_else(|| expected("an integer"))?;
+            in_range(n as f64)
+        }
+        PluginSettingKind::Enum => {
+            let s = value.as_str().ok_or_else(|| expected("a string option"))?;
+            if field.options.iter().any(|o| o == s) {
+                Ok(())
+            } else {
+                Err(invalid(format!(
+                    "Value must be one of: {}",
+                    field.options.join(", ")
+                )))
+            }
+        }
+    }
+}
+
+/// 设置项的默认值：声明的默认值合法时使用，否则取类型零值。
+pub(super) fn default_value(field: &PluginSettingField) -> Value {
+    if let Some(default) = &field.default
+        && check_value(field, default).is_ok()
+    {
+        return default.clone();
+    }
+    match field.kind {
+        PluginSettingKind::Boolean => Value::Bool(false),
+        PluginSettingKind::String => Value::String(String::new()),
+        PluginSettingKind::Number => serde_json::json!(field.min.unwrap_or(0.0)),
+        PluginSettingKind::Integer => Value::from(field.min.map(|v| v.ceil() as i64).unwrap_or(0)),
+        PluginSettingKind::Enum => field
+            .options
+            .first()
+            .cloned()
+            .map(Value::String)
+            .unwrap_or(Value::Null),
+    }
+}
+
+/// 严格校验清单中的 `settings`（供 `manifest_schema` 调用，问题路径形如 `settings[0].key`）。
+pub(super) fn validate_schema(value: &Value, issues: &mut Vec<PluginManifestIssue>) {
+    let Some(items) = value.as_array() else {
+        issues.push(issue(
+            "settings",
+            "invalid_type",
+            "`settings` must be an array",
+        ));
+        return;
+    };
+    if items.len() > MAX_SETTING_FIELDS {
+        issues.push(issue(
+            "settings",
+            "too_many_settings",
+            format!("At most {MAX_SETTING_FIELDS} settings can be declared"),
+        ));
+    }
+    let mut seen: Vec<String> = Vec::new();
+    for (idx, item) in items.iter().enumerate() {
+        let base = format!("settings[{idx}]");
+        let field = match serde_json::from_value::<PluginSettingField>(item.clone()) {
+            Ok(field) => field,
+            Err(e) => {
+                issues.push(issue(
+                    &base,
+                    "invalid_setting",
+                    format!("Invalid setting: {e}"),
+                ));
+                continue;
+            }
+        };
+        if !is_valid_key(&field.key) {
+            issues.push(issue(
+                &format!("{base}.key"),
+                "invalid_setting_key",
+                "Setting key must be 1-64 characters of [A-Za-z0-9_.-]",
+            ));
+        } else if seen.contains(&field.key) {
+            issues.push(issue(
+                &format!("{base}.key"),
+                "duplicate_setting_key",
+                format!("Duplicate setting key `{}`", field.key),
+            ));
+        }
+        seen.push(field.key.clone());
+        if field.kind == PluginSettingKind::Enum && field.options.is_empty() {
+            issues.push(issue(
+                &format!("{base}.options"),
+                "missing_field",
+                "Enum settings must declare at least one option",
+            ));
+        }
+        if let (Some(min), Some(max)) = (field.min, field.max)
+            && min > max
+        {
+            issues.push(issue(
+                &format!("{base}.min"),
+                "invalid_range",
+                "`min` must not be greater than `max`",
+            ));
+        }
+        if let Some(default) = &field.default
+            && let Err(invalid) = check_value(&field, default)
+        {
+            issues.push(issue(
+                &format!("{base}.default"),
+                "invalid_default",
+                invalid.message,
+            ));
+        }
+    }
+}
+
+/// 宽松解析设置项：跳过无法解析或 key 重复/非法的项。
+pub(super) fn parse_fields(value: Option<&Value>) -> Vec<PluginSettingField> {
+    let mut out: Vec<PluginSettingField> = Vec::new();
+    for item in value.and_then(Value::as_array).into_iter().flatten() {
+        let Ok(field) = serde_json::from_value::<PluginSettingField>(item.clone()) else {
+            continue;
+        };
+        if is_valid_key(&field.key)
+            && !out.iter().any(|f| f.key == field.key)
+            && out.len() < MAX_SETTING_FIELDS
+        {
+            out.push(field);
+        }
+    }
+    out
+}
+
+/// 计算生效值：默认值为底，已存且仍合法的值覆盖。
+pub(super) fn effective_values(
+    fields: &[PluginSettingField],
+    stored: &Map<String, Value>,
+) -> Map<String, Value> {
+    fields
+        .iter()
+        .map(|field| {
+            let value = stored
+                .get(&field.key)
+                .filter(|v| check_value(field, v).is_ok())
+                .cloned()
+                .unwrap_or_else(|| default_value(field));
+            (field.key.clone(), value)
+        })
+        .collect()
+}
+
+/// 将补丁应用到已存值；返回新的已存值（已剔除 schema 外的 key）。
+pub(super) fn apply_patch(
+    fields: &[PluginSettingField],
+    stored: &Map<String, Value>,
+    patch: &Map<String, Value>,
+) -> Result<Map<String, Value>, Vec<PluginManifestIssue>> {
+    let mut issues = Vec::new();
+    let mut next: Map<String, Value> = stored
+        .iter()
+        .filter(|(key, _)| fields.iter().any(|f| &f.key == *key))
+        .map(|(key, value)| (key.clone(), value.clone()))
+        .collect();
+    for (key, value) in patch {
+        let Some(field) = fields.iter().find(|f| &f.key == key) else {
+            issues.push(issue(
+                key,
+                "unknown_setting",
+                "Setting is not declared by the plugin",
+            ));
+            continue;
+        };
+        if value.is_null() {
+            next.remove(key);
+            continue;
+        }
+        match check_value(field, value) {
+            Ok(()) => {
+                next.insert(key.clone(), value.clone());
+            }
+            Err(invalid) => issues.push(invalid),
+        }
+    }
+    if issues.is_empty() {
+        Ok(next)
+    } else {
+        Err(issues)
+    }
+}
+
+/// 读取插件当前版本及其设置项声明。
+async fn current_fields(
+    server_id: &str,
+    plugin_id: &str,
+) -> anyhow::Result<(String, Vec<PluginSettingField>)> {
+    let current = read_current(server_id, plugin_id)
+        .await?
+        .ok_or_else(|| anyhow::anyhow!("Plugin is not installed: {}", plugin_id))?;
+    let manifest_path = manifest_file_path(server_id, plugin_id, &current.version)?;
+    let raw = tokio::fs::read_to_string(&manifest_path).await?;
+    let manifest: PluginManifestV1 = serde_json::from_str(&raw)?;
+    Ok((current.version, parse_fields(manifest.settings.as_ref())))
+}
+
+fn stored_settings(map: &Map<String, Value>) -> Map<String, Value> {
+    map.get(SETTINGS_STORAGE_KEY)
+        .and_then(Value::as_object)
+        .cloned()
+        .unwrap_or_default()
+}
+
+/// 插件当前版本的设置 schema。
+pub(super) async fn get_schema(
+    server_id: &str,
+    plugin_id: &str,
+) -> anyhow::Result<PluginSettingsSchema> {
+    let (version, fields) = current_fields(server_id, plugin_id).await?;
+    Ok(PluginSettingsSchema {
+        plugin_id: plugin_id.to_string(),
+        version,
+        fields,
+    })
+}
+
+/// 插件设置的生效值。
+pub(super) async fn get_values(
+    server_id: &str,
+    plugin_id: &str,
+) -> anyhow::Result<PluginSettingsValues> {
+    let (version, fields) = current_fields(server_id, plugin_id).await?;
+    let path = storage_file_path(server_id, plugin_id)?;
+    let map = {
+        let _read_guard = storage_file_lock().read().await;
+        read_storage_map(&path).await?
+    };
+    Ok(PluginSettingsValues {
+        plugin_id: plugin_id.to_string(),
+        version,
+        values: effective_values(&fields, &stored_settings(&map)),
+    })
+}
+
+/// 按 schema 校验并写入设置补丁；返回更新后的生效值与发生变化的 key。
+pub(super) async fn update(
+    server_id: &str,
+    plugin_id: &str,
+    patch: &Map<String, Value>,
+) -> anyhow::Result<(PluginSettingsValues, Vec<String>)> {
+    let (version, fields) = current_fields(server_id, plugin_id).await?;
+    let path = storage_file_path(server_id, plugin_id)?;
+    let _write_guard = storage_file_lock().write().await;
+    let mut map = read_storage_map(&path).await?;
+    let stored = stored_settings(&map);
+    let next = apply_patch(&fields, &stored, patch).map_err(|issues| PluginSettingsInvalid {
+        plugin_id: plugin_id.to_string(),
+        issues,
+    })?;
+
+    let before = effective_values(&fields, &stored);
+    let values = effective_values(&fields, &next);
+    let changed: Vec<String> = values
+        .iter()
+        .filter(|(key, value)| before.get(*key) != Some(*value))
+        .map(|(key, _)| key.clone())
+        .collect();
+    if next != stored {
+        map.insert(SETTINGS_STORAGE_KEY.to_string(), Value::Object(next));
+        atomic_write(&path, &serde_json::to_string_pretty(&map)?).await?;
+    }
+    Ok((
+        PluginSettingsValues {
+            plugin_id: plugin_id.to_string(),
+            version,
+            values,
+        },
+        changed,
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn fields() -> Vec<PluginSettingField> {
+        parse_fields(Some(&json!([
+            {"key": "theme", "type": "enum", "options": ["dark", "light"], "default": "light"},
+            {"key": "volume", "type": "integer", "min": 0, "max": 100, "default": 50},
+            {"key": "nickname", "type": "string", "max_length": 4},
+            {"key": "theme", "type": "boolean"},
+            {"key": "broken", "type": "color"}
+        ])))
+    }
+
+    #[test]
+    fn fields_are_parsed_leniently_and_defaults_fill_gaps() {
+        let fields = fields();
+        assert_eq!(
+            fields.iter().map(|f| f.key.as_str()).collect::<Vec<_>>(),
+            vec!["theme", "volume", "nickname"]
+        );
+        let stored = json!({"volume": 500, "nickname": "abc", "stale": true});
+        let values = effective_values(&fields, stored.as_object().unwrap());
+        assert_eq!(
+            Value::Object(values),
+            json!({"theme": "light", "volume": 50, "nickname": "abc"})
+        );
+    }
+
+    #[test]
+    fn patches_are_validated_as_a_whole() {
+        let fields = fields();
+        let stored = json!({"nickname": "abc", "stale": 1});
+        let stored = stored.as_object().unwrap();
+
+        let ok = apply_patch(
+            &fields,
+            stored,
+            json!({"theme": "dark", "nickname": null})
+                .as_object()
+                .unwrap(),
+        )
+        .expect("valid patch");
+        assert_eq!(Value::Object(ok), json!({"theme": "dark"}));
+
+        let issues = apply_patch(
+            &fields,
+            stored,
+            json!({"theme": "blue", "volume": 1.5, "nickname": "abcde", "missing": 1})
+                .as_object()
+                .unwrap(),
+        )
+        .expect_err("invalid patch");
+        let mut codes: Vec<(String, String)> =
+            issues.into_iter().map(|i| (i.path, i.code)).collect();
+        codes.sort();
+        assert_eq!(
+            codes,
+            vec![
+                ("missing".into(), "unknown_setting".into()),
+                ("nickname".into(), "invalid_setting_value".into()),
+                ("theme".into(), "invalid_setting_value".into()),
+                ("volume".into(), "invalid_setting_value".into()),
+            ]
+        );
+    }
+
+    #[test]
+    fn schema_validation_reports_declaration_errors() {
+        let mut issues = Vec::new();
+        validate_schema(
+            &json!([
+                {"key": "a", "type": "enum"},
+                {"key": "a", "type": "number", "min": 5, "max": 1},
+                {"key": "bad key", "type": "boolean", "default": "yes"},
+                {"type": "string"}
+            ]),
+            &mut issues,
+        );
+        let codes: Vec<(&str, &str)> = issues
+            .iter()
+            .map(|i| (i.path.as_str(), i.code.as_str()))
+            .collect();
+        assert_eq!(
+            codes,
+            vec![
+                ("settings[0].options", "missing_field"),
+                ("settings[1].key", "duplicate_setting_key"),
+                ("settings[1].min", "invalid_range"),
+                ("settings[2].key", "invalid_setting_key"),
+                ("settings[2].default", "invalid_default"),
+                ("settings[3]", "invalid_setting"),
+            ]
+        );
+    }
+}
@@ -3,6 +3,7 @@
 //! 职责：
 //! - 读取/写入 `current.json`（当前版本 + enabled）
 //! - 读取/写入 `state.json`（status + last_error）
+//! - 读取/写入 `history.json`（known-good 版本 + 连续失败次数，用于回滚）
 //! - 枚举已安装版本目录
 //! - 组装 `InstalledPluginState`（供前端展示与运行时决策）
 //!
@@ -15,9 +16,12 @@ use serde::{Deserialize, Serialize};
 
 use super::{
     InstalledPluginState,
+    dev_link::read_dev_link,
     json_io::{read_json_file, write_json_file},
-    paths::{current_file_path, plugin_root_dir, state_file_path},
+    paths::{current_file_path, history_file_path, plugin_root_dir, state_file_path},
+    trust::read_trust_level,
 };
+use crate::features::plugins::domain::types::PluginTrustLevel;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "snake_case")]
@@ -33,7 +37,53 @@ pub(super) struct PluginStateFile {
     pub last_error: String, // 人类可读的错误信息
 }
 
-async fn list_installed_versions(server_id: &str, plugin_id: &str) -> anyhow::Result<Vec<String>> {
+/// known-good 版本最多保留条数。
+const HISTORY_MAX_GOOD_VERSIONS: usize = 10;
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case", default)]
+pub(super) struct PluginHistory {
+    /// 确认加载成功过的版本（旧 -> 新，去重）。
+    pub good_versions: Vec<String>,
+    /// 当前版本连续上报失败的次数（确认加载成功/清除错误/回滚后归零）。
+    pub consecutive_failures: u32,
+    /// 最近崩溃时间（unix 毫秒，只保留统计窗口内的记录，见 `crash_guard`）。
+    pub recent_crashes: Vec<u64>,
+}
+
+impl PluginHistory {
+    /// 记录一次确认成功的加载。
+    pub(super) fn mark_good(&mut self, version: &str) {
+        self.good_versions.retain(|v| v != version);
+        self.good_versions.push(version.to_string());
+        let overflow = self
+            .good_versions
+            .len()
+            .saturating_sub(HISTORY_MAX_GOOD_VERSIONS);
+        self.good_versions.drain(..overflow);
+        self.consecutive_failures = 0;
+        self.recent_crashes.clear();
+    }
+
+    /// 最近一次 known-good 且不同于当前版本的版本。
+    pub(super) fn rollback_target(&self, current_version: &str) -> Option<&str> {
+        self.good_versions
+            .iter()
+            .rev()
+            .find(|v| v.as_str() != current_version)
+            .map(String::as_str)
+    }
+
+    /// 移除已不可用的版本（目录被删除/入口缺失）。
+    pub(super) fn forget(&mut self, version: &str) {
+        self.good_versions.retain(|v| v != version);
+    }
+}
+
+pub(super) async fn list_installed_versions(
+    server_id: &str,
+    plugin_id: &str,
+) -> anyhow::Result<Vec<String>> {
     let root = plugin_root_dir(server_id, plugin_id)?;
     let mut versions: Vec<(SystemTime, String)> = Vec::new();
     let mut rd = match tokio::fs::read_dir(&root).await {
@@ -43,7 +93,12 @@ async fn list_installed_versions(server_id: &str, plugin_id: &str) -> anyhow::Re
     };
     while let Some(ent) = rd.next_entry().await? {
         let ty = ent.file_type().await?;
-        if !ty.is_dir() {
+        // 开发态版本是指向源目录的符号链接，同样视为已安装版本。
+        let is_linked_dir = ty.is_symlink()
+            && tokio::fs::metadata(ent.path())
+                .await
+                .is_ok_and(|meta| meta.is_dir());
+        if !ty.is_dir() && !is_linked_dir {
             continue;
         }
         let name = ent.file_name().to_string_lossy().to_string();
@@ -102,6 +157,25 @@ pub(super) async fn write_state_file(
     write_json_file(&path, st).await
 }
 
+pub(super) async fn read_history(
+    server_id: &str,
+    plugin_id: &str,
+) -> anyhow::Result<PluginHistory> {
+    let path = history_file_path(server_id, plugin_id)?;
+    Ok(read_json_file::<PluginHistory>(&path)
+        .await?
+        .unwrap_or_default())
+}
+
+pub(super) async fn write_history(
+    server_id: &str,
+    plugin_id: &str,
+    history: &PluginHistory,
+) -> anyhow::Result<()> {
+    let path = history_file_path(server_id, plugin_id)?;
+    write_json_file(&path, history).await
+}
+
 pub(super) async fn build_installed_state(
     server_id: &str,
     plugin_id: &str,
@@ -109,6 +183,12 @@ pub(super) async fn build_installed_state(
     let installed_versions = list_installed_versions(server_id, plugin_id).await?;
     let current = read_current(server_id, plugin_id).await?;
     let state = read_state_file(server_id, plugin_id).await?;
+    let dev_link = read_dev_link(server_id, plugin_id).await?;
+    // 开发态插件来自本机源目录，不沿用安装记录。
+    let trust_level = match (&current, &dev_link) {
+        (Some(current), None) => read_trust_level(server_id, plugin_id, &current.version).await?,
+        _ => PluginTrustLevel::Unverified,
+    };
 
     Ok(InstalledPluginState {
         plugin_id: plugin_id.to_string(),
@@ -117,5 +197,43 @@ pub(super) async fn build_installed_state(
         enabled: current.as_ref().map(|c| c.enabled).unwrap_or(false),
         status: state.status,
         last_error: state.last_error,
+        dev: dev_link.is_some(),
+        dev_path: dev_link.map(|link| link.path),
+        trust_level,
     })
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn history_tracks_latest_good_versions() {
+        let mut history = PluginHistory {
+            consecutive_failures: 3,
+            ..PluginHistory::default()
+        };
+        history.mark_good("1.0.0");
+        history.mark_good("1.1.0");
+        history.mark_good("1.0.0");
+        assert_eq!(history.good_versions, vec!["1.1.0", "1.0.0"]);
+        assert_eq!(history.consecutive_failures, 0);
+        assert_eq!(history.rollback_target("2.0.0"), Some("1.0.0"));
+        assert_eq!(history.rollback_target("1.0.0"), Some("1.1.0"));
+        history.forget("1.1.0");
+        assert_eq!(history.rollback_target("1.0.0"), None);
+    }
+
+    #[test]
+    fn history_is_capped() {
+        let mut history = PluginHistory::default();
+        for i in 0..(HISTORY_MAX_GOOD_VERSIONS + 3) {
+            history.mark_good(&format!("1.0.{i}"));
+        }
+        assert_eq!(history.good_versions.len(), HISTORY_MAX_GOOD_VERSIONS);
+        assert_eq!(
+            history.good_versions.first().map(String::as_str),
+            Some("1.0.3")
+        );
+    }
+}
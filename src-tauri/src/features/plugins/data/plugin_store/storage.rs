@@ -10,9 +10,10 @@ use std::sync::OnceLock;
 use tokio::io::AsyncWriteExt;
 use tokio::sync::RwLock;
 
+use super::settings::SETTINGS_STORAGE_KEY;
 use super::{api::fetch_server_id, origin::to_http_origin, paths::storage_file_path};
 
-fn storage_file_lock() -> &'static RwLock<()> {
+pub(super) fn storage_file_lock() -> &'static RwLock<()> {
     static LOCK: OnceLock<RwLock<()>> = OnceLock::new();
     LOCK.get_or_init(|| RwLock::new(()))
 }
@@ -58,7 +59,7 @@ fn replace_file_windows(src: &Path, dst: &Path) -> std::io::Result<()> {
     Err(std::io::Error::last_os_error())
 }
 
-async fn atomic_write(path: &Path, out: &str) -> Result<()> {
+pub(super) async fn atomic_write(path: &Path, out: &str) -> Result<()> {
     if let Some(parent) = path.parent() {
         tokio::fs::create_dir_all(parent)
             .await
@@ -95,6 +96,17 @@ async fn atomic_write(path: &Path, out: &str) -> Result<()> {
     Ok(())
 }
 
+/// 读取整个 storage.json（不存在时为空 map）；调用方负责持有 [`storage_file_lock`]。
+pub(super) async fn read_storage_map(
+    path: &Path,
+) -> Result<serde_json::Map<String, serde_json::Value>> {
+    match tokio::fs::read_to_string(path).await {
+        Ok(v) => serde_json::from_str(&v).context("Invalid storage.json"),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(serde_json::Map::new()),
+        Err(e) => Err(e.into()),
+    }
+}
+
 /// 读取插件 KV 存储中的某个键值。
 ///
 /// # 参数
@@ -121,13 +133,7 @@ pub async fn storage_get(
     let server_id = fetch_server_id(&origin, tls_policy, tls_fingerprint).await?;
     let path = storage_file_path(&server_id, plugin_id)?;
     let _read_guard = storage_file_lock().read().await;
-    let raw = match tokio::fs::read_to_string(&path).await {
-        Ok(v) => v,
-        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(None),
-        Err(e) => return Err(e.into()),
-    };
-    let map: serde_json::Map<String, serde_json::Value> =
-        serde_json::from_str(&raw).context("Invalid storage.json")?;
+    let map = read_storage_map(&path).await?;
     Ok(map.get(key).cloned())
 }
 
@@ -155,16 +161,15 @@ pub async fn storage_set(
     tls_policy: Option<&str>,
     tls_fingerprint: Option<&str>,
 ) -> Result<()> {
+    // 设置值只能经 `plugins_update_settings` 按 schema 校验后写入。
+    if key == SETTINGS_STORAGE_KEY {
+        return Err(anyhow::anyhow!("Storage key is reserved: {}", key));
+    }
     let origin = to_http_origin(server_socket)?;
     let server_id = fetch_server_id(&origin, tls_policy, tls_fingerprint).await?;
     let path = storage_file_path(&server_id, plugin_id)?;
     let _write_guard = storage_file_lock().write().await;
-    let mut map: serde_json::Map<String, serde_json::Value> =
-        match tokio::fs::read_to_string(&path).await {
-            Ok(v) => serde_json::from_str(&v).context("Invalid storage.json")?,
-            Err(e) if e.kind() == std::io::ErrorKind::NotFound => serde_json::Map::new(),
-            Err(e) => return Err(e.into()),
-        };
+    let mut map = read_storage_map(&path).await?;
     map.insert(key.to_string(), value);
     let out = serde_json::to_string_pretty(&map).context("Failed to serialize storage")?;
     atomic_write(&path, &out).await?;
@@ -5,7 +5,9 @@
 //! - 指纹校验用于 `tls-fp://` 场景：允许无效证书/域名，但必须匹配指定证书 SHA-256 指纹。
 
 use anyhow::Context;
-use tokio::net::TcpStream;
+
+use crate::features::network::data::header_policy;
+use crate::shared::net::happy_eyeballs;
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 enum TlsPolicy {
@@ -53,7 +55,7 @@ async fn verify_https_fingerprint(origin: &str, expected_sha256: &str) -> anyhow
 
     let (host, port) = extract_host_port_from_origin(origin)?;
     let addr = format!("{}:{}", host, port);
-    let stream = TcpStream::connect(addr.clone())
+    let stream = happy_eyeballs::connect(&addr)
         .await
         .with_context(|| format!("Failed to connect for TLS fingerprint check: {}", addr))?;
 
@@ -91,7 +93,7 @@ async fn verify_https_fingerprint(origin: &str, expected_sha256: &str) -> anyhow
 }
 
 fn build_reqwest_client(policy: TlsPolicy) -> anyhow::Result<reqwest::Client> {
-    let mut builder = reqwest::Client::builder();
+    let mut builder = header_policy::client_builder();
     if policy != TlsPolicy::Strict {
         builder = builder
             .danger_accept_invalid_certs(true)
@@ -110,7 +112,7 @@ pub(super) async fn build_server_client(
     tls_fingerprint: Option<&str>,
 ) -> anyhow::Result<reqwest::Client> {
     if !origin.trim().starts_with("https://") {
-        return Ok(reqwest::Client::new());
+        return Ok(header_policy::client_builder().build()?);
     }
     let policy = parse_tls_policy(tls_policy);
     if policy == TlsPolicy::TrustFingerprint {
@@ -0,0 +1,249 @@
+//! plugin_store｜插件来源信任级别（目录签名校验 + 安装确认 + `trust.json`）。
+//!
+//! 说明：
+//! - 目录条目可携带 `trust_level` 声明与 `signature { key_id, signature }`；签名为 Ed25519，
+//!   覆盖 [`signing_message`]（plugin_id + version + 包 sha256），只有宿主内置的签名公钥才被认可；
+//! - 最终级别取「签名公钥对应级别」与「目录声明」中较低者，服务端无法自行抬高信任级别；
+//!   无签名、未知公钥或签名无效一律为 `unverified`；
+//! - 签名公钥在发布构建时通过环境变量注入（见 [`TRUSTED_SIGNERS`]），未注入时所有插件均为 `unverified`；
+//! - 安装时确定的级别按版本记入插件根目录的 `trust.json`（不放进版本目录，避免被插件包覆盖）。
+
+use std::collections::BTreeMap;
+
+use base64::Engine;
+use base64::engine::general_purpose::STANDARD as BASE64;
+use ed25519_dalek::{Signature, Verifier, VerifyingKey};
+use serde::{Deserialize, Serialize};
+
+use super::api::ApiCatalogItem;
+use super::json_io::{read_json_file, write_json_file};
+use super::paths::trust_file_path;
+use crate::features::plugins::domain::types::{PluginTrustLevel, PluginUntrustedSource};
+
+/// 宿主认可的目录签名公钥。
+struct TrustedSigner {
+    key_id: &'static str,
+    /// base64 编码的 Ed25519 公钥（32 字节）；未注入时为 `None`。
+    public_key: Option<&'static str>,
+    level: PluginTrustLevel,
+}
+
+/// 内置签名公钥：官方插件与审核通过的第三方插件各一把。
+const TRUSTED_SIGNERS: &[TrustedSigner] = &[
+    TrustedSigner {
+        key_id: "carrypigeon-first-party",
+        public_key: option_env!("CARRYPIGEON_PLUGIN_FIRST_PARTY_KEY"),
+        level: PluginTrustLevel::FirstParty,
+    },
+    TrustedSigner {
+        key_id: "carrypigeon-verified",
+        public_key: option_env!("CARRYPIGEON_PLUGIN_VERIFIED_KEY"),
+        level: PluginTrustLevel::Verified,
+    },
+];
+
+/// 已解码的签名公钥：(key_id, 公钥, 级别)。
+type Signer = (&'static str, VerifyingKey, PluginTrustLevel);
+
+fn decode_key(raw: &str) -> Option<VerifyingKey> {
+    let bytes: [u8; 32] = BASE64.decode(raw.trim()).ok()?.try_into().ok()?;
+    VerifyingKey::from_bytes(&bytes).ok()
+}
+
+fn signers() -> Vec<Signer> {
+    TRUSTED_SIGNERS
+        .iter()
+        .filter_map(|s| Some((s.key_id, decode_key(s.public_key?)?, s.level)))
+        .collect()
+}
+
+/// 签名覆盖的内容。
+fn signing_message(plugin_id: &str, version: &str, sha256: &str) -> String {
+    format!(
+        "carrypigeon-plugin-v1\n{}\n{}\n{}",
+        plugin_id.trim(),
+        version.trim(),
+        sha256.trim().to_ascii_lowercase()
+    )
+}
+
+fn trust_level_with(item: &ApiCatalogItem, signers: &[Signer]) -> PluginTrustLevel {
+    let (Some(sig), Some(download)) = (&item.signature, &item.download) else {
+        return PluginTrustLevel::Unverified;
+    };
+    let Some((_, key, level)) = signers.iter().find(|(id, _, _)| *id == sig.key_id.trim()) else {
+        return PluginTrustLevel::Unverified;
+    };
+    let signature = BASE64
+        .decode(sig.signature.trim())
+        .ok()
+        .and_then(|bytes| Signature::from_slice(&bytes).ok());
+    let message = signing_message(&item.plugin_id, &item.version, &download.sha256);
+    match signature {
+        Some(signature) if key.verify(message.as_bytes(), &signature).is_ok() => item
+            .trust_level
+            .map_or(*level, |claimed| claimed.min(*level)),
+        _ => PluginTrustLevel::Unverified,
+    }
+}
+
+/// 目录条目的信任级别（签名校验失败时为 `unverified`）。
+pub(super) fn catalog_trust_level(item: &ApiCatalogItem) -> PluginTrustLevel {
+    trust_level_with(item, &signers())
+}
+
+/// 未经验证的来源必须显式确认后才能安装。
+pub(super) fn ensure_confirmed(
+    plugin_id: &str,
+    level: PluginTrustLevel,
+    confirm_untrusted: bool,
+) -> anyhow::Result<()> {
+    if level == PluginTrustLevel::Unverified && !confirm_untrusted {
+        return Err(PluginUntrustedSource {
+            plugin_id: plugin_id.to_string(),
+            trust_level: level,
+        }
+        .into());
+    }
+    Ok(())
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case", default)]
+struct PluginTrustFile {
+    /// version -> 安装时确定的信任级别。
+    versions: BTreeMap<String, PluginTrustLevel>,
+}
+
+async fn read_trust_file(server_id: &str, plugin_id: &str) -> anyhow::Result<PluginTrustFile> {
+    Ok(
+        read_json_file::<PluginTrustFile>(&trust_file_path(server_id, plugin_id)?)
+            .await?
+            .unwrap_or_default(),
+    )
+}
+
+/// 读取某个已安装版本的信任级别（未记录时为 `unverified`）。
+pub(super) async fn read_trust_level(
+    server_id: &str,
+    plugin_id: &str,
+    version: &str,
+) -> anyhow::Result<PluginTrustLevel> {
+    Ok(read_trust_file(server_id, plugin_id)
+        .await?
+        .versions
+        .get(version)
+        .copied()
+        .unwrap_or_default())
+}
+
+/// 记录某个版本安装时的信任级别（覆盖同版本的旧记录）。
+pub(super) async fn write_trust_level(
+    server_id: &str,
+    plugin_id: &str,
+    version: &str,
+    level: PluginTrustLevel,
+) -> anyhow::Result<()> {
+    let mut file = read_trust_file(server_id, plugin_id).await?;
+    file.versions.insert(version.to_string(), level);
+    write_json_file(&trust_file_path(server_id, plugin_id)?, &file).await
+}
+
+/// 移除已删除版本的信任记录。
+pub(super) async fn forget_trust_level(
+    server_id: &str,
+    plugin_id: &str,
+    version: &str,
+) -> anyhow::Result<()> {
+    let mut file = read_trust_file(server_id, plugin_id).await?;
+    if file.versions.remove(version).is_some() {
+        write_json_file(&trust_file_path(server_id, plugin_id)?, &file).await?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ed25519_dalek::{Signer as _, SigningKey};
+
+    const SHA: &str = "E3B0C44298FC1C149AFBF4C8996FB92427AE41E4649B934CA495991B7852B855";
+
+    fn item(extra: serde_json::Value) -> ApiCatalogItem {
+        let mut raw = serde_json::json!({
+            "plugin_id": "math-formula",
+            "version": "1.2.0",
+            "download": { "url": "/plugins/math.zip", "sha256": SHA }
+        });
+        raw.as_object_mut()
+            .expect("object")
+            .extend(extra.as_object().cloned().unwrap_or_default());
+        serde_json::from_value(raw).expect("catalog item")
+    }
+
+    fn sign(key: &SigningKey, version: &str) -> String {
+        let message = signing_message("math-formula", version, SHA);
+        BASE64.encode(key.sign(message.as_bytes()).to_bytes())
+    }
+
+    #[test]
+    fn signature_from_a_known_key_sets_the_level() {
+        let first = SigningKey::from_bytes(&[1; 32]);
+        let review = SigningKey::from_bytes(&[2; 32]);
+        let signers = vec![
+            ("first", first.verifying_key(), PluginTrustLevel::FirstParty),
+            ("review", review.verifying_key(), PluginTrustLevel::Verified),
+        ];
+        let signed = |key_id: &str, key: &SigningKey, claim: Option<&str>| {
+            let mut extra = serde_json::json!({
+                "signature": { "key_id": key_id, "signature": sign(key, "1.2.0") }
+            });
+            if let Some(claim) = claim {
+                extra["trust_level"] = claim.into();
+            }
+            trust_level_with(&item(extra), &signers)
+        };
+
+        assert_eq!(signed("first", &first, None), PluginTrustLevel::FirstParty);
+        assert_eq!(signed("review", &review, None), PluginTrustLevel::Verified);
+        // 目录声明只能降低级别，不能抬高。
+        assert_eq!(
+            signed("review", &review, Some("first_party")),
+            PluginTrustLevel::Verified
+        );
+        assert_eq!(
+            signed("first", &first, Some("verified")),
+            PluginTrustLevel::Verified
+        );
+        // 用错误的公钥签名、未知 key_id。
+        assert_eq!(signed("first", &review, None), PluginTrustLevel::Unverified);
+        assert_eq!(signed("other", &first, None), PluginTrustLevel::Unverified);
+    }
+
+    #[test]
+    fn unsigned_or_tampered_entries_are_unverified() {
+        let key = SigningKey::from_bytes(&[1; 32]);
+        let signers = vec![("first", key.verifying_key(), PluginTrustLevel::FirstParty)];
+        let claimed = item(serde_json::json!({ "trust_level": "first_party" }));
+        assert_eq!(
+            trust_level_with(&claimed, &signers),
+            PluginTrustLevel::Unverified
+        );
+        // 签名对应的是另一个版本。
+        let tampered = item(serde_json::json!({
+            "signature": { "key_id": "first", "signature": sign(&key, "1.1.0") }
+        }));
+        assert_eq!(
+            trust_level_with(&tampered, &signers),
+            PluginTrustLevel::Unverified
+        );
+    }
+
+    #[test]
+    fn unverified_installs_need_confirmation() {
+        let err = ensure_confirmed("x", PluginTrustLevel::Unverified, false).expect_err("refused");
+        assert!(err.downcast_ref::<PluginUntrustedSource>().is_some());
+        assert!(ensure_confirmed("x", PluginTrustLevel::Unverified, true).is_ok());
+        assert!(ensure_confirmed("x", PluginTrustLevel::Verified, false).is_ok());
+    }
+}
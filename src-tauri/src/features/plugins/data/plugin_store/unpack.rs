@@ -13,11 +13,11 @@ use std::{
 use anyhow::Context;
 use zip::ZipArchive;
 
-fn normalize_zip_name(raw: &str) -> String {
+pub(super) fn normalize_zip_name(raw: &str) -> String {
     raw.replace('\\', "/").trim_start_matches('/').to_string()
 }
 
-fn is_zip_name_safe(name: &str) -> bool {
+pub(super) fn is_zip_name_safe(name: &str) -> bool {
     if name.is_empty() {
         return false;
     }
@@ -37,7 +37,7 @@ fn is_zip_name_safe(name: &str) -> bool {
     true
 }
 
-fn detect_single_root_prefix(names: &[String]) -> Option<String> {
+pub(super) fn detect_single_root_prefix(names: &[String]) -> Option<String> {
     let mut prefix: Option<&str> = None;
     for n in names {
         let segs: Vec<&str> = n.split('/').collect();
@@ -53,7 +53,7 @@ fn detect_single_root_prefix(names: &[String]) -> Option<String> {
     prefix.map(|s| s.to_string())
 }
 
-fn strip_root_prefix(name: &str, prefix: &str) -> String {
+pub(super) fn strip_root_prefix(name: &str, prefix: &str) -> String {
     if !name.starts_with(prefix) {
         return name.to_string();
     }
@@ -61,7 +61,7 @@ fn strip_root_prefix(name: &str, prefix: &str) -> String {
     trimmed.trim_start_matches('/').to_string()
 }
 
-fn is_forbidden_source_file(path: &str) -> bool {
+pub(super) fn is_forbidden_source_file(path: &str) -> bool {
     let lower = path.to_lowercase();
     if lower.ends_with(".d.ts") {
         return false;
@@ -87,7 +87,7 @@ fn is_windows_reparse_point(_meta: &std::fs::Metadata) -> bool {
     false
 }
 
-fn is_zip_entry_symlink<R: Read>(file: &zip::read::ZipFile<'_, R>) -> bool {
+pub(super) fn is_zip_entry_symlink<R: Read>(file: &zip::read::ZipFile<'_, R>) -> bool {
     file.unix_mode()
         .map(|mode| mode & 0o170000 == 0o120000)
         .unwrap_or(false)
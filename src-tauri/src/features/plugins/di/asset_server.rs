@@ -0,0 +1,307 @@
+//! plugins｜DI：插件静态资源的本地回环 HTTP 服务。
+//!
+//! 说明：
+//! - 面向插件作者的调试接口：部分插件前端无法在自定义 scheme 下工作（Service Worker、
+//!   `WebAssembly.instantiateStreaming` 等），在配置文件开启 `plugin_http_server`（不提供设置界面）后，
+//!   可用外部浏览器访问 `http://127.0.0.1:<port>/<token>/<server_id>/<plugin_id>/<version>/<path>` 排查；
+//! - 桌面端插件加载器始终使用 `app://plugins`（主窗口 CSP 的 `script-src` 不允许回环 HTTP 来源），
+//!   不会自动启动本服务；
+//! - 只绑定 `127.0.0.1` 的随机端口；每次启动生成新的会话 token，作为路径第一段（相对路径的子资源自动带上），
+//!   token 不匹配一律 403；`Host` 必须是回环地址本身，防止 DNS rebinding；
+//! - 资源读取与 `app://plugins` 共用 [`crate::app::serve_plugin_asset`]，两者返回的内容与响应头一致；
+//! - 只支持 `GET` / `HEAD`，每个连接处理一个请求后关闭。
+//!
+//! 约定：注释中文，日志英文（tracing）。
+
+use std::sync::Mutex;
+use std::time::Duration;
+
+use serde::Serialize;
+use tauri::http::{HeaderMap, HeaderName, HeaderValue, header};
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::{TcpListener, TcpStream};
+
+/// 单个请求头的读取上限。
+const MAX_REQUEST_BYTES: usize = 16 * 1024;
+/// 单个连接的读取超时。
+const READ_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// 运行中的服务信息（`plugins_http_server_start` 返回值）。
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PluginHttpServerInfo {
+    pub port: u16,
+    /// 资源根地址（以 `/` 结尾）；在其后拼接 `<server_id>/<plugin_id>/<version>/<path>`。
+    pub base_url: String,
+}
+
+struct RunningServer {
+    info: PluginHttpServerInfo,
+    task: tauri::async_runtime::JoinHandle<()>,
+}
+
+static SERVER: Mutex<Option<RunningServer>> = Mutex::new(None);
+
+/// 生成会话 token（两个 UUIDv4 拼接，64 位 hex）。
+fn new_session_token() -> String {
+    format!(
+        "{}{}",
+        uuid::Uuid::new_v4().simple(),
+        uuid::Uuid::new_v4().simple()
+    )
+}
+
+/// 启动服务（已在运行时直接返回当前信息）。
+pub async fn start() -> anyhow::Result<PluginHttpServerInfo> {
+    if let Some(running) = SERVER.lock().unwrap_or_else(|p| p.into_inner()).as_ref() {
+        return Ok(running.info.clone());
+    }
+    let listener = TcpListener::bind(("127.0.0.1", 0)).await?;
+    let port = listener.local_addr()?.port();
+    let token = new_session_token();
+    let info = PluginHttpServerInfo {
+        port,
+        base_url: format!("http://127.0.0.1:{port}/{token}/"),
+    };
+
+    let mut guard = SERVER.lock().unwrap_or_else(|p| p.into_inner());
+    // 并发启动时以先完成的为准，本次绑定的监听器随之释放。
+    if let Some(running) = guard.as_ref() {
+        return Ok(running.info.clone());
+    }
+    let task = tauri::async_runtime::spawn(accept_loop(listener, port, token));
+    *guard = Some(RunningServer {
+        info: info.clone(),
+        task,
+    });
+    tracing::info!(action = "plugins_http_server_started", port);
+    Ok(info)
+}
+
+/// 停止服务（未运行时无操作）；旧 token 随之失效。
+pub fn stop() {
+    let running = SERVER.lock().unwrap_or_else(|p| p.into_inner()).take();
+    if let Some(running) = running {
+        running.task.abort();
+        tracing::info!(
+            action = "plugins_http_server_stopped",
+            port = running.info.port
+        );
+    }
+}
+
+async fn accept_loop(listener: TcpListener, port: u16, token: String) {
+    loop {
+        let stream = match listener.accept().await {
+            Ok((stream, _)) => stream,
+            Err(e) => {
+                tracing::warn!(action = "plugins_http_server_accept_failed", error = %e);
+                continue;
+            }
+        };
+        let token = token.clone();
+        tauri::async_runtime::spawn(async move {
+            if let Err(e) = handle_connection(stream, port, &token).await {
+                tracing::debug!(action = "plugins_http_server_request_failed", error = %e);
+            }
+        });
+    }
+}
+
+/// 解析后的请求行与请求头。
+#[derive(Debug)]
+struct RequestHead {
+    method: String,
+    target: String,
+    host: Option<String>,
+    /// 转交给资源读取（Range / 条件请求）。
+    headers: HeaderMap,
+}
+
+fn parse_request_head(raw: &[u8]) -> Option<RequestHead> {
+    let head = std::str::from_utf8(raw).ok()?;
+    let mut lines = head.split("\r\n");
+    let mut request_line = lines.next()?.split(' ');
+    let method = request_line.next()?.to_string();
+    let target = request_line.next()?.to_string();
+    if !request_line.next()?.starts_with("HTTP/1.") {
+        return None;
+    }
+    let mut headers = HeaderMap::new();
+    for (name, value) in lines
+        .take_while(|line| !line.is_empty())
+        .filter_map(|line| line.split_once(':'))
+    {
+        let name = HeaderName::from_bytes(name.trim().as_bytes()).ok()?;
+        let value = HeaderValue::from_str(value.trim()).ok()?;
+        headers.append(name, value);
+    }
+    let host = headers
+        .get(header::HOST)
+        .and_then(|v| v.to_str().ok())
+        .map(str::to_ascii_lowercase);
+    Some(RequestHead {
+        method,
+        target,
+        host,
+        headers,
+    })
+}
+
+/// 常量时间比较，避免按响应时间逐字节猜测 token。
+fn token_matches(candidate: &str, token: &str) -> bool {
+    candidate.len() == token.len()
+        && candidate
+            .bytes()
+            .zip(token.bytes())
+            .fold(0u8, |acc, (a, b)| acc | (a ^ b))
+            == 0
+}
+
+/// 校验请求并取出资源路径；失败时返回应答状态码。
+fn authorize<'a>(head: &'a RequestHead, port: u16, token: &str) -> Result<&'a str, u16> {
+    let host_ok = head.host.as_deref().is_some_and(|host| {
+        host == format!("127.0.0.1:{port}") || host == format!("localhost:{port}")
+    });
+    if !host_ok {
+        return Err(403);
+    }
+    if head.method != "GET" && head.method != "HEAD" {
+        return Err(405);
+    }
+    let path = head.target.split(['?', '#']).next().unwrap_or_default();
+    let (candidate, rest) = path
+        .strip_prefix('/')
+        .and_then(|p| p.split_once('/'))
+        .ok_or(403u16)?;
+    if !token_matches(candidate, token) {
+        return Err(403);
+    }
+    Ok(rest)
+}
+
+/// 将响应编码为 HTTP/1.1 报文（`HEAD` 请求不带响应体）。
+fn encode_response(response: &tauri::http::Response<Vec<u8>>, head_only: bool) -> Vec<u8> {
+    let status = response.status();
+    let mut out = format!(
+        "HTTP/1.1 {} {}\r\n",
+        status.as_u16(),
+        status.canonical_reason().unwrap_or("")
+    );
+    for (name, value) in response.headers() {
+        if name == header::CONTENT_LENGTH || name == header::CONNECTION {
+            continue;
+        }
+        if let Ok(value) = value.to_str() {
+            out.push_str(&format!("{name}: {value}\r\n"));
+        }
+    }
+    out.push_str(&format!(
+        "Content-Length: {}\r\nCache-Control: no-cache\r\nConnection: close\r\n\r\n",
+        response.body().len()
+    ));
+    let mut bytes = out.into_bytes();
+    if !head_only {
+        bytes.extend_from_slice(response.body());
+    }
+    bytes
+}
+
+fn status_response(status: u16) -> tauri::http::Response<Vec<u8>> {
+    let mut response = tauri::http::Response::new(Vec::new());
+    *response.status_mut() =
+        tauri::http::StatusCode::from_u16(status).unwrap_or(tauri::http::StatusCode::BAD_REQUEST);
+    response
+}
+
+async fn handle_connection(mut stream: TcpStream, port: u16, token: &str) -> anyhow::Result<()> {
+    let mut buf = Vec::with_capacity(1024);
+    let mut chunk = [0u8; 1024];
+    while !buf.windows(4).any(|w| w == b"\r\n\r\n") {
+        let n = tokio::time::timeout(READ_TIMEOUT, stream.read(&mut chunk)).await??;
+        if n == 0 {
+            break;
+        }
+        buf.extend_from_slice(&chunk[..n]);
+        if buf.len() > MAX_REQUEST_BYTES {
+            anyhow::bail!("Request header too large");
+        }
+    }
+    let (response, head_only) =
+        match parse_request_head(&buf) {
+            None => (status_response(400), false),
+            Some(head) => {
+                let head_only = head.method == "HEAD";
+                let response = match authorize(&head, port, token) {
+                Err(status) => status_response(status),
+                Ok(path) => crate::app::serve_plugin_asset(path, &head.headers).unwrap_or_else(|e| {
+                    tracing::debug!(action = "plugins_http_server_asset_missing", error = %e);
+                    status_response(404)
+                }),
+            };
+                (response, head_only)
+            }
+        };
+    stream
+        .write_all(&encode_response(&response, head_only))
+        .await?;
+    let _ = stream.shutdown().await;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn head(method: &str, target: &str, host: &str) -> RequestHead {
+        let raw = format!("{method} {target} HTTP/1.1\r\nHost: {host}\r\nAccept: */*\r\n\r\n");
+        parse_request_head(raw.as_bytes()).unwrap()
+    }
+
+    #[test]
+    fn requests_require_the_session_token_and_loopback_host() {
+        let token = "t".repeat(64);
+        let target = format!("/{token}/srv/plugin/1.0.0/index.html?x=1");
+        assert_eq!(
+            authorize(&head("GET", &target, "127.0.0.1:4000"), 4000, &token),
+            Ok("srv/plugin/1.0.0/index.html")
+        );
+        assert_eq!(
+            authorize(&head("HEAD", &target, "localhost:4000"), 4000, &token),
+            Ok("srv/plugin/1.0.0/index.html")
+        );
+        let wrong = format!("/{}/srv/plugin/1.0.0/index.html", "u".repeat(64));
+        assert_eq!(
+            authorize(&head("GET", &wrong, "127.0.0.1:4000"), 4000, &token),
+            Err(403)
+        );
+        assert_eq!(
+            authorize(&head("GET", "/srv/plugin", "127.0.0.1:4000"), 4000, &token),
+            Err(403)
+        );
+        assert_eq!(
+            authorize(&head("GET", &target, "evil.example:4000"), 4000, &token),
+            Err(403)
+        );
+        assert_eq!(
+            authorize(&head("POST", &target, "127.0.0.1:4000"), 4000, &token),
+            Err(405)
+        );
+        assert!(parse_request_head(b"garbage\r\n\r\n").is_none());
+    }
+
+    #[test]
+    fn head_responses_omit_the_body() {
+        let mut response = tauri::http::Response::new(b"hello".to_vec());
+        response
+            .headers_mut()
+            .insert("Content-Type", "text/plain".parse().unwrap());
+        let full = String::from_utf8(encode_response(&response, false)).unwrap();
+        assert!(full.starts_with("HTTP/1.1 200 OK\r\n"));
+        assert!(full.contains("content-type: text/plain\r\n"));
+        assert!(full.contains("Content-Length: 5\r\n"));
+        assert!(full.ends_with("\r\n\r\nhello"));
+        let head = String::from_utf8(encode_response(&response, true)).unwrap();
+        assert!(head.ends_with("Connection: close\r\n\r\n"));
+    }
+}
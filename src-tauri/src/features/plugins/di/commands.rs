@@ -1,67 +1,623 @@
 //! plugins｜DI/命令入口：commands。
 //!
 //! 约定：注释中文，日志英文（tracing）。
-use crate::features::plugins::data::plugin_ports::{
-    PluginInstallStorePortAdapter, PluginLoaderPortAdapter,
-};
+use crate::features::plugins::data::plugin_ports::PluginInstallStorePortAdapter;
+use crate::features::plugins::di::asset_server::PluginHttpServerInfo;
 use crate::features::plugins::domain::types::{
-    InstalledPluginState, PluginFetchResponse, PluginInstallFromUrlRequest, PluginLoadResult,
-    PluginManifest, PluginNetworkFetchRequest, PluginRuntimeEntry,
+    InstalledPluginState, PluginAsset, PluginAutoDisabled, PluginBusDenied, PluginCatalogPage,
+    PluginCatalogQuery, PluginCompatibility, PluginDomainResolution, PluginFetchResponse,
+    PluginIncompatibleHost, PluginInstallFromUrlRequest, PluginLocaleCatalog,
+    PluginManifestInvalid, PluginManifestIssue, PluginNetworkFetchRequest, PluginPackageValidation,
+    PluginPruneResult, PluginRequirementsReport, PluginRuntimeEntry, PluginSettingsInvalid,
+    PluginSettingsSchema, PluginSettingsValues, PluginUntrustedSource,
 };
 use crate::features::plugins::usecases::plugin_usecases;
-use crate::shared::error::{CommandResult, to_command_error};
+use crate::shared::error::{CommandError, CommandResult, command_error, to_command_error};
+use crate::shared::taskbar_progress::TaskbarProgress;
 use std::collections::HashMap;
+use tauri::AppHandle;
+
+/// 启用状态变化后刷新 domain 注册表，并经 Tauri 事件总线发出提供方变化事件。
+async fn sync_domain_registry(
+    app: &AppHandle,
+    server_socket: &str,
+    tls_policy: Option<&str>,
+    tls_fingerprint: Option<&str>,
+) {
+    plugin_usecases::plugins_sync_domain_registry(
+        server_socket,
+        tls_policy,
+        tls_fingerprint,
+        PluginInstallStorePortAdapter::shared(),
+        app,
+    )
+    .await;
+}
+
+/// 校验问题列表作为 `details`（JSON）附在错误上，release 构建同样携带。
+fn with_issue_details(
+    code: &'static str,
+    i18n_key: &str,
+    issues: &[PluginManifestIssue],
+    e: &anyhow::Error,
+) -> CommandError {
+    tracing::error!(action = "tauri_command_failed", code, error = %e);
+    let error = command_error(code, i18n_key);
+    match serde_json::to_string(issues) {
+        Ok(details) => error.with_details(details),
+        Err(_) => error,
+    }
+}
+
+/// 宿主版本不兼容 / 总线访问被拒分别映射为独立错误码 `PLUGINS_INCOMPATIBLE_HOST` / `PLUGINS_BUS_DENIED`；
+/// 清单 / 设置校验未通过映射为 `PLUGINS_MANIFEST_INVALID` / `PLUGINS_SETTINGS_INVALID`（`details` 为问题列表 JSON）；
+/// 其余沿用命令自身的错误码。
+fn map_plugin_error(code: &'static str, i18n_key: &str, e: anyhow::Error) -> CommandError {
+    if let Some(invalid) = e.downcast_ref::<PluginManifestInvalid>() {
+        return with_issue_details(
+            "PLUGINS_MANIFEST_INVALID",
+            "error.plugins_manifest_invalid",
+            &invalid.issues,
+            &e,
+        );
+    }
+    if let Some(invalid) = e.downcast_ref::<PluginSettingsInvalid>() {
+        return with_issue_details(
+            "PLUGINS_SETTINGS_INVALID",
+            "error.plugins_settings_invalid",
+            &invalid.issues,
+            &e,
+        );
+    }
+    if e.downcast_ref::<PluginIncompatibleHost>().is_some() {
+        return to_command_error(
+            "PLUGINS_INCOMPATIBLE_HOST",
+            "error.plugins_incompatible_host",
+            e,
+        );
+    }
+    if e.downcast_ref::<PluginBusDenied>().is_some() {
+        return to_command_error("PLUGINS_BUS_DENIED", "error.plugins_bus_denied", e);
+    }
+    if e.downcast_ref::<PluginUntrustedSource>().is_some() {
+        return to_command_error(
+            "PLUGINS_UNTRUSTED_SOURCE",
+            "error.plugins_untrusted_source",
+            e,
+        );
+    }
+    to_command_error(code, i18n_key, e)
+}
 
-/// 加载并实例化一个插件（legacy 调试路径，由 manifest 指定）。
+/// 查询服务端已安装插件列表（含当前版本/启用态/错误等状态）。
 ///
 /// # 参数
-/// - `manifest`：插件清单（包含 id/version/url/sha256 等）。
+/// - `server_socket`：目标服务端 socket。
+/// - `tls_policy`：TLS 策略（可选，传递给网络层）。
+/// - `tls_fingerprint`：TLS 指纹（可选，传递给网络层）。
 ///
 /// # 返回值
-/// - `Ok(PluginLoadResult)`：加载结果（包含运行时入口等信息）。
-/// - `Err(String)`：加载失败原因。
+/// - `Ok(Vec<InstalledPluginState>)`：已安装插件状态列表。
+/// - `Err(String)`：查询失败原因。
+#[tauri::command]
+pub async fn plugins_list_installed(
+    server_socket: String,
+    tls_policy: Option<String>,
+    tls_fingerprint: Option<String>,
+) -> CommandResult<Vec<InstalledPluginState>> {
+    plugin_usecases::plugins_list_installed(
+        &server_socket,
+        tls_policy.as_deref(),
+        tls_fingerprint.as_deref(),
+        PluginInstallStorePortAdapter::shared(),
+    )
+    .await
+    .map_err(|e| {
+        to_command_error(
+            "PLUGINS_LIST_INSTALLED_FAILED",
+            "error.plugins_list_installed_failed",
+            e,
+        )
+    })
+}
+
+/// 查询某个插件在服务端的安装状态。
 ///
-/// # 说明
-/// 该命令主要用于调试/开发态：前端传入 manifest 后触发旧 wasm loader。
-/// 正式插件中心主链路使用 `plugins_install_*` + `plugins_get_runtime_entry*`
-/// 暴露 ESM 静态资源，不再依赖该命令。
+/// # 参数
+/// - `server_socket`：目标服务端 socket。
+/// - `plugin_id`：插件 id。
+/// - `tls_policy`/`tls_fingerprint`：TLS 相关参数（可选）。
+///
+/// # 返回值
+/// - `Ok(Some(InstalledPluginState))`：已安装则返回状态。
+/// - `Ok(None)`：未安装。
+/// - `Err(String)`：查询失败原因。
 #[tauri::command]
-pub async fn load_plugin(manifest: PluginManifest) -> CommandResult<PluginLoadResult> {
-    plugin_usecases::load_plugin(manifest, PluginLoaderPortAdapter::shared())
-        .await
-        .map_err(|e| to_command_error("PLUGINS_LOAD_FAILED", "error.plugins_load_failed", e))
+pub async fn plugins_get_installed_state(
+    server_socket: String,
+    plugin_id: String,
+    tls_policy: Option<String>,
+    tls_fingerprint: Option<String>,
+) -> CommandResult<Option<InstalledPluginState>> {
+    plugin_usecases::plugins_get_installed_state(
+        &server_socket,
+        &plugin_id,
+        tls_policy.as_deref(),
+        tls_fingerprint.as_deref(),
+        PluginInstallStorePortAdapter::shared(),
+    )
+    .await
+    .map_err(|e| {
+        to_command_error(
+            "PLUGINS_GET_INSTALLED_STATE_FAILED",
+            "error.plugins_get_installed_state_failed",
+            e,
+        )
+    })
 }
 
-/// 列出本地已保存的插件清单列表。
+/// 获取插件运行时入口（用于前端动态 import 插件模块）。
+///
+/// # 参数
+/// - `webview`：调用方 webview（加载该插件的宿主窗口，可据此上报一次加载崩溃）。
+/// - `server_socket`：目标服务端 socket。
+/// - `plugin_id`：插件 id。
+/// - `tls_policy`/`tls_fingerprint`：TLS 相关参数（可选）。
 ///
 /// # 返回值
-/// - `Ok(Vec<PluginManifest>)`：清单列表。
-/// - `Err(String)`：读取失败原因。
+/// - `Ok(PluginRuntimeEntry)`：运行时入口信息（URL/版本等）。
+/// - `Err(String)`：获取失败原因。
 #[tauri::command]
-pub async fn list_plugins() -> CommandResult<Vec<PluginManifest>> {
-    plugin_usecases::list_plugins(PluginLoaderPortAdapter::shared())
-        .await
-        .map_err(|e| to_command_error("PLUGINS_LIST_FAILED", "error.plugins_list_failed", e))
+pub async fn plugins_get_runtime_entry(
+    webview: tauri::Webview,
+    server_socket: String,
+    plugin_id: String,
+    tls_policy: Option<String>,
+    tls_fingerprint: Option<String>,
+) -> CommandResult<PluginRuntimeEntry> {
+    plugin_usecases::plugins_get_runtime_entry(
+        webview.label(),
+        &server_socket,
+        &plugin_id,
+        tls_policy.as_deref(),
+        tls_fingerprint.as_deref(),
+        PluginInstallStorePortAdapter::shared(),
+    )
+    .await
+    .map_err(|e| {
+        map_plugin_error(
+            "PLUGINS_GET_RUNTIME_ENTRY_FAILED",
+            "error.plugins_get_runtime_entry_failed",
+            e,
+        )
+    })
+}
+
+/// 获取指定版本的插件运行时入口。
+///
+/// # 参数
+/// - `server_socket`：目标服务端 socket。
+/// - `plugin_id`：插件 id。
+/// - `version`：目标版本。
+/// - `tls_policy`/`tls_fingerprint`：TLS 相关参数（可选）。
+///
+/// # 返回值
+/// - `Ok(PluginRuntimeEntry)`：运行时入口信息。
+/// - `Err(String)`：获取失败原因。
+#[tauri::command]
+pub async fn plugins_get_runtime_entry_for_version(
+    server_socket: String,
+    plugin_id: String,
+    version: String,
+    tls_policy: Option<String>,
+    tls_fingerprint: Option<String>,
+) -> CommandResult<PluginRuntimeEntry> {
+    plugin_usecases::plugins_get_runtime_entry_for_version(
+        &server_socket,
+        &plugin_id,
+        &version,
+        tls_policy.as_deref(),
+        tls_fingerprint.as_deref(),
+        PluginInstallStorePortAdapter::shared(),
+    )
+    .await
+    .map_err(|e| {
+        map_plugin_error(
+            "PLUGINS_GET_RUNTIME_ENTRY_FOR_VERSION_FAILED",
+            "error.plugins_get_runtime_entry_for_version_failed",
+            e,
+        )
+    })
+}
+
+/// 浏览服务端插件目录（搜索 + 分页）。
+///
+/// # 参数
+/// - `server_socket`：目标服务端 socket。
+/// - `query`：搜索词（可选；匹配 id/名称/描述/作者/domain）。
+/// - `page`：页码（可选，从 1 开始，默认 1）。
+/// - `page_size`：每页条数（可选，默认 20，最大 100）。
+/// - `tls_policy`/`tls_fingerprint`：TLS 相关参数（可选）。
+///
+/// # 返回值
+/// - `Ok(PluginCatalogPage)`：当前页条目与总数（图标已改写为 `app://`）。
+/// - `Err(String)`：获取目录失败原因。
+#[tauri::command]
+pub async fn plugins_browse_catalog(
+    server_socket: String,
+    query: Option<String>,
+    page: Option<u32>,
+    page_size: Option<u32>,
+    tls_policy: Option<String>,
+    tls_fingerprint: Option<String>,
+) -> CommandResult<PluginCatalogPage> {
+    plugin_usecases::plugins_browse_catalog(
+        PluginCatalogQuery {
+            server_socket: &server_socket,
+            query: query.as_deref(),
+            page: page.unwrap_or(1),
+            page_size: page_size.unwrap_or(20),
+            tls_policy: tls_policy.as_deref(),
+            tls_fingerprint: tls_fingerprint.as_deref(),
+            confirm_untrusted: confirm_untrusted.unwrap_or(false),
+        },
+        PluginInstallStorePortAdapter::shared(),
+    )
+    .await
+    .map_err(|e| {
+        to_command_error(
+            "PLUGINS_BROWSE_CATALOG_FAILED",
+            "error.plugins_browse_catalog_failed",
+            e,
+        )
+    })
+}
+
+/// 列出插件已预取到本地的展示资源（icon/banner）。
+///
+/// # 参数
+/// - `server_socket`：目标服务端 socket。
+/// - `plugin_id`：插件 id。
+/// - `version`：版本（可选；为空时取当前版本）。
+/// - `tls_policy`/`tls_fingerprint`：TLS 相关参数（可选）。
+///
+/// # 返回值
+/// - `Ok(Vec<PluginAsset>)`：资源列表（含 `app://plugins/...` 地址）。
+/// - `Err(String)`：插件未安装或读取失败。
+#[tauri::command]
+pub async fn plugins_get_assets(
+    server_socket: String,
+    plugin_id: String,
+    version: Option<String>,
+    tls_policy: Option<String>,
+    tls_fingerprint: Option<String>,
+) -> CommandResult<Vec<PluginAsset>> {
+    plugin_usecases::plugins_get_assets(
+        &server_socket,
+        &plugin_id,
+        version.as_deref(),
+        tls_policy.as_deref(),
+        tls_fingerprint.as_deref(),
+        PluginInstallStorePortAdapter::shared(),
+    )
+    .await
+    .map_err(|e| {
+        to_command_error(
+            "PLUGINS_GET_ASSETS_FAILED",
+            "error.plugins_get_assets_failed",
+            e,
+        )
+    })
+}
+
+/// 获取插件当前版本的语言资源（`locales/*.json`，按回退链合并）。
+///
+/// # 参数
+/// - `server_socket`：目标服务端 socket。
+/// - `plugin_id`：插件 id。
+/// - `lang`：期望语言（通常为宿主当前语言，如 `zh_cn`）。
+/// - `tls_policy`/`tls_fingerprint`：TLS 相关参数（可选）。
+///
+/// # 返回值
+/// - `Ok(PluginLocaleCatalog)`：命中的语言与合并后的消息（插件未携带语言文件时为空）。
+/// - `Err(String)`：插件未安装或获取失败。
+#[tauri::command]
+pub async fn plugins_get_locale(
+    server_socket: String,
+    plugin_id: String,
+    lang: String,
+    tls_policy: Option<String>,
+    tls_fingerprint: Option<String>,
+) -> CommandResult<PluginLocaleCatalog> {
+    plugin_usecases::plugins_get_locale(
+        &server_socket,
+        &plugin_id,
+        &lang,
+        tls_policy.as_deref(),
+        tls_fingerprint.as_deref(),
+        PluginInstallStorePortAdapter::shared(),
+    )
+    .await
+    .map_err(|e| {
+        to_command_error(
+            "PLUGINS_GET_LOCALE_FAILED",
+            "error.plugins_get_locale_failed",
+            e,
+        )
+    })
+}
+
+/// 获取插件当前版本声明的设置 schema（供设置界面渲染表单）。
+///
+/// # 参数
+/// - `server_socket`：目标服务端 socket。
+/// - `plugin_id`：插件 id。
+/// - `tls_policy`/`tls_fingerprint`：TLS 相关参数（可选）。
+///
+/// # 返回值
+/// - `Ok(PluginSettingsSchema)`：设置项声明（插件未声明设置时为空列表）。
+/// - `Err(String)`：插件未安装或清单不可读。
+#[tauri::command]
+pub async fn plugins_get_settings_schema(
+    server_socket: String,
+    plugin_id: String,
+    tls_policy: Option<String>,
+    tls_fingerprint: Option<String>,
+) -> CommandResult<PluginSettingsSchema> {
+    plugin_usecases::plugins_get_settings_schema(
+        &server_socket,
+        &plugin_id,
+        tls_policy.as_deref(),
+        tls_fingerprint.as_deref(),
+        PluginInstallStorePortAdapter::shared(),
+    )
+    .await
+    .map_err(|e| {
+        to_command_error(
+            "PLUGINS_GET_SETTINGS_SCHEMA_FAILED",
+            "error.plugins_get_settings_schema_failed",
+            e,
+        )
+    })
+}
+
+/// 获取插件设置的生效值（默认值 + 用户修改）。
+///
+/// # 参数
+/// - `server_socket`：目标服务端 socket。
+/// - `plugin_id`：插件 id。
+/// - `tls_policy`/`tls_fingerprint`：TLS 相关参数（可选）。
+///
+/// # 返回值
+/// - `Ok(PluginSettingsValues)`：schema 中每个 key 的生效值。
+/// - `Err(String)`：插件未安装或读取失败。
+#[tauri::command]
+pub async fn plugins_get_settings(
+    server_socket: String,
+    plugin_id: String,
+    tls_policy: Option<String>,
+    tls_fingerprint: Option<String>,
+) -> CommandResult<PluginSettingsValues> {
+    plugin_usecases::plugins_get_settings(
+        &server_socket,
+        &plugin_id,
+        tls_policy.as_deref(),
+        tls_fingerprint.as_deref(),
+        PluginInstallStorePortAdapter::shared(),
+    )
+    .await
+    .map_err(|e| {
+        to_command_error(
+            "PLUGINS_GET_SETTINGS_FAILED",
+            "error.plugins_get_settings_failed",
+            e,
+        )
+    })
+}
+
+/// 按 schema 校验并更新插件设置；有变化时发出 `plugins-settings-changed`。
+///
+/// # 参数
+/// - `server_socket`：目标服务端 socket。
+/// - `plugin_id`：插件 id。
+/// - `patch`：要修改的 key → 值（`null` 表示恢复默认）。
+/// - `tls_policy`/`tls_fingerprint`：TLS 相关参数（可选）。
+///
+/// # 返回值
+/// - `Ok(PluginSettingsValues)`：更新后的生效值。
+/// - `Err(String)`：校验失败（`PLUGINS_SETTINGS_INVALID`，`details` 为问题列表）或写入失败。
+#[tauri::command]
+pub async fn plugins_update_settings(
+    app: AppHandle,
+    server_socket: String,
+    plugin_id: String,
+    patch: serde_json::Map<String, serde_json::Value>,
+    tls_policy: Option<String>,
+    tls_fingerprint: Option<String>,
+) -> CommandResult<PluginSettingsValues> {
+    plugin_usecases::plugins_update_settings(
+        &server_socket,
+        &plugin_id,
+        &patch,
+        tls_policy.as_deref(),
+        tls_fingerprint.as_deref(),
+        PluginInstallStorePortAdapter::shared(),
+        &app,
+    )
+    .await
+    .map_err(|e| {
+        map_plugin_error(
+            "PLUGINS_UPDATE_SETTINGS_FAILED",
+            "error.plugins_update_settings_failed",
+            e,
+        )
+    })
+}
+
+/// 为宿主桥接打开插件总线会话（需 `bus` 权限）。
+///
+/// # 参数
+/// - `webview`：调用方 webview（会话绑定到该 webview，页面重新加载时失效）。
+/// - `server_socket`：目标服务端 socket。
+/// - `plugin_id`：会话所属插件 id。
+/// - `tls_policy`/`tls_fingerprint`：TLS 相关参数（可选）。
+///
+/// # 返回值
+/// - `Ok(String)`：会话令牌；后续发布/订阅以令牌确定插件身份。
+/// - `Err(String)`：无权使用总线或该插件已有会话（`PLUGINS_BUS_DENIED`）。
+#[tauri::command]
+pub async fn plugins_bus_open(
+    webview: tauri::Webview,
+    server_socket: String,
+    plugin_id: String,
+    tls_policy: Option<String>,
+    tls_fingerprint: Option<String>,
+) -> CommandResult<String> {
+    plugin_usecases::plugins_bus_open(
+        webview.label(),
+        &server_socket,
+        &plugin_id,
+        tls_policy.as_deref(),
+        tls_fingerprint.as_deref(),
+        PluginInstallStorePortAdapter::shared(),
+    )
+    .await
+    .map_err(|e| {
+        map_plugin_error(
+            "PLUGINS_BUS_OPEN_FAILED",
+            "error.plugins_bus_open_failed",
+            e,
+        )
+    })
+}
+
+/// 向插件总线发布消息（topic 须位于插件提供的 domain 下）。
+///
+/// # 参数
+/// - `webview`：调用方 webview。
+/// - `server_socket`：目标服务端 socket。
+/// - `bus_token`：`plugins_bus_open` 返回的会话令牌。
+/// - `topic`：消息 topic。
+/// - `payload`：消息载荷（JSON，序列化后不超过 64KB）。
+///
+/// # 返回值
+/// - `Ok(Vec<String>)`：收到消息的插件 id（有收件人时发出 `plugins-bus-message`）。
+/// - `Err(String)`：会话无效或无权发布（`PLUGINS_BUS_DENIED`）、参数不合法。
+#[tauri::command]
+pub async fn plugins_bus_publish(
+    app: AppHandle,
+    webview: tauri::Webview,
+    server_socket: String,
+    bus_token: String,
+    topic: String,
+    payload: serde_json::Value,
+) -> CommandResult<Vec<String>> {
+    plugin_usecases::plugins_bus_publish(
+        webview.label(),
+        &server_socket,
+        &bus_token,
+        &topic,
+        payload,
+        PluginInstallStorePortAdapter::shared(),
+        &app,
+    )
+    .await
+    .map_err(|e| {
+        map_plugin_error(
+            "PLUGINS_BUS_PUBLISH_FAILED",
+            "error.plugins_bus_publish_failed",
+            e,
+        )
+    })
+}
+
+/// 登记插件总线订阅（`<topic>` 精确匹配，`<prefix>/*` 匹配子 topic）。
+///
+/// # 参数
+/// - `webview`：调用方 webview。
+/// - `bus_token`：`plugins_bus_open` 返回的会话令牌。
+/// - `topic`：订阅模式。
+///
+/// # 返回值
+/// - `Ok(())`：已登记。
+/// - `Err(String)`：会话无效或无权订阅（`PLUGINS_BUS_DENIED`）、参数不合法。
+#[tauri::command]
+pub async fn plugins_bus_subscribe(
+    webview: tauri::Webview,
+    bus_token: String,
+    topic: String,
+) -> CommandResult<()> {
+    plugin_usecases::plugins_bus_subscribe(
+        webview.label(),
+        &bus_token,
+        &topic,
+        PluginInstallStorePortAdapter::shared(),
+    )
+    .await
+    .map_err(|e| {
+        map_plugin_error(
+            "PLUGINS_BUS_SUBSCRIBE_FAILED",
+            "error.plugins_bus_subscribe_failed",
+            e,
+        )
+    })
+}
+
+/// 取消插件总线订阅。
+///
+/// # 参数
+/// - `webview`：调用方 webview。
+/// - `bus_token`：`plugins_bus_open` 返回的会话令牌。
+/// - `topic`：要取消的订阅模式；为空时关闭会话并清空全部订阅（插件卸载时调用）。
+///
+/// # 返回值
+/// - `Ok(())`：已取消。
+/// - `Err(String)`：会话无效（`PLUGINS_BUS_DENIED`）。
+#[tauri::command]
+pub async fn plugins_bus_unsubscribe(
+    webview: tauri::Webview,
+    bus_token: String,
+    topic: Option<String>,
+) -> CommandResult<()> {
+    plugin_usecases::plugins_bus_unsubscribe(
+        webview.label(),
+        &bus_token,
+        topic.as_deref(),
+        PluginInstallStorePortAdapter::shared(),
+    )
+    .await
+    .map_err(|e| {
+        map_plugin_error(
+            "PLUGINS_BUS_UNSUBSCRIBE_FAILED",
+            "error.plugins_bus_unsubscribe_failed",
+            e,
+        )
+    })
 }
 
-/// 查询服务端已安装插件列表（含当前版本/启用态/错误等状态）。
+/// 检查插件与当前宿主版本的兼容性（安装前提示）。
 ///
 /// # 参数
 /// - `server_socket`：目标服务端 socket。
-/// - `tls_policy`：TLS 策略（可选，传递给网络层）。
-/// - `tls_fingerprint`：TLS 指纹（可选，传递给网络层）。
+/// - `plugin_id`：插件 id。
+/// - `version`：版本（可选；为空时取目录中的版本）。
+/// - `tls_policy`/`tls_fingerprint`：TLS 相关参数（可选）。
 ///
 /// # 返回值
-/// - `Ok(Vec<InstalledPluginState>)`：已安装插件状态列表。
-/// - `Err(String)`：查询失败原因。
+/// - `Ok(PluginCompatibility)`：兼容性结果（含声明的最低版本与当前宿主版本）。
+/// - `Err(String)`：插件不在目录中或请求失败。
 #[tauri::command]
-pub async fn plugins_list_installed(
+pub async fn plugins_check_compatibility(
     server_socket: String,
+    plugin_id: String,
+    version: Option<String>,
     tls_policy: Option<String>,
     tls_fingerprint: Option<String>,
-) -> CommandResult<Vec<InstalledPluginState>> {
-    plugin_usecases::plugins_list_installed(
+) -> CommandResult<PluginCompatibility> {
+    plugin_usecases::plugins_check_compatibility(
         &server_socket,
+        &plugin_id,
+        version.as_deref(),
         tls_policy.as_deref(),
         tls_fingerprint.as_deref(),
         PluginInstallStorePortAdapter::shared(),
@@ -69,34 +625,30 @@ pub async fn plugins_list_installed(
     .await
     .map_err(|e| {
         to_command_error(
-            "PLUGINS_LIST_INSTALLED_FAILED",
-            "error.plugins_list_installed_failed",
+            "PLUGINS_CHECK_COMPATIBILITY_FAILED",
+            "error.plugins_check_compatibility_failed",
             e,
         )
     })
 }
 
-/// 查询某个插件在服务端的安装状态。
+/// 检查服务端 `/api/server` 声明的必装插件（缺失/过旧/未启用）。
 ///
 /// # 参数
 /// - `server_socket`：目标服务端 socket。
-/// - `plugin_id`：插件 id。
 /// - `tls_policy`/`tls_fingerprint`：TLS 相关参数（可选）。
 ///
 /// # 返回值
-/// - `Ok(Some(InstalledPluginState))`：已安装则返回状态。
-/// - `Ok(None)`：未安装。
-/// - `Err(String)`：查询失败原因。
+/// - `Ok(PluginRequirementsReport)`：按声明顺序的检查结果。
+/// - `Err(String)`：请求 `/api/server` 或插件目录失败。
 #[tauri::command]
-pub async fn plugins_get_installed_state(
+pub async fn plugins_check_requirements(
     server_socket: String,
-    plugin_id: String,
     tls_policy: Option<String>,
     tls_fingerprint: Option<String>,
-) -> CommandResult<Option<InstalledPluginState>> {
-    plugin_usecases::plugins_get_installed_state(
+) -> CommandResult<PluginRequirementsReport> {
+    plugin_usecases::plugins_check_requirements(
         &server_socket,
-        &plugin_id,
         tls_policy.as_deref(),
         tls_fingerprint.as_deref(),
         PluginInstallStorePortAdapter::shared(),
@@ -104,70 +656,71 @@ pub async fn plugins_get_installed_state(
     .await
     .map_err(|e| {
         to_command_error(
-            "PLUGINS_GET_INSTALLED_STATE_FAILED",
-            "error.plugins_get_installed_state_failed",
+            "PLUGINS_CHECK_REQUIREMENTS_FAILED",
+            "error.plugins_check_requirements_failed",
             e,
         )
     })
 }
 
-/// 获取插件运行时入口（用于前端动态 import 插件模块）。
+/// 一次性安装并启用所有未满足的必装插件（进度见 `plugins-requirements-progress` 事件）。
 ///
 /// # 参数
 /// - `server_socket`：目标服务端 socket。
-/// - `plugin_id`：插件 id。
+/// - `confirm_untrusted`：用户已确认安装未经验证的插件（缺省为 `false`，此时这类插件记为失败）。
 /// - `tls_policy`/`tls_fingerprint`：TLS 相关参数（可选）。
 ///
 /// # 返回值
-/// - `Ok(PluginRuntimeEntry)`：运行时入口信息（URL/版本等）。
-/// - `Err(String)`：获取失败原因。
+/// - `Ok(PluginRequirementsReport)`：安装后重新检查的结果（失败条目带 `error`）。
+/// - `Err(String)`：检查必装插件失败。
 #[tauri::command]
-pub async fn plugins_get_runtime_entry(
+pub async fn plugins_install_requirements(
+    app: AppHandle,
     server_socket: String,
-    plugin_id: String,
+    confirm_untrusted: Option<bool>,
     tls_policy: Option<String>,
     tls_fingerprint: Option<String>,
-) -> CommandResult<PluginRuntimeEntry> {
-    plugin_usecases::plugins_get_runtime_entry(
+) -> CommandResult<PluginRequirementsReport> {
+    let taskbar = TaskbarProgress::start("plugin_install");
+    plugin_usecases::plugins_install_requirements(
         &server_socket,
-        &plugin_id,
+        confirm_untrusted.unwrap_or(false),
         tls_policy.as_deref(),
         tls_fingerprint.as_deref(),
         PluginInstallStorePortAdapter::shared(),
+        &app,
     )
     .await
+    .inspect(|_| taskbar.finish())
     .map_err(|e| {
         to_command_error(
-            "PLUGINS_GET_RUNTIME_ENTRY_FAILED",
-            "error.plugins_get_runtime_entry_failed",
+            "PLUGINS_INSTALL_REQUIREMENTS_FAILED",
+            "error.plugins_install_requirements_failed",
             e,
         )
     })
 }
 
-/// 获取指定版本的插件运行时入口。
+/// 解析 domain 当前由哪个启用插件提供。
 ///
 /// # 参数
 /// - `server_socket`：目标服务端 socket。
-/// - `plugin_id`：插件 id。
-/// - `version`：目标版本。
+/// - `domain`：domain 名称（精确匹配）。
 /// - `tls_policy`/`tls_fingerprint`：TLS 相关参数（可选）。
 ///
 /// # 返回值
-/// - `Ok(PluginRuntimeEntry)`：运行时入口信息。
-/// - `Err(String)`：获取失败原因。
+/// - `Ok(PluginDomainResolution)`：提供方、候选列表与冲突标记（无提供方时 `provider` 为空）。
+/// - `Err(String)`：解析失败原因。
 #[tauri::command]
-pub async fn plugins_get_runtime_entry_for_version(
+pub async fn plugins_resolve_domain(
     server_socket: String,
-    plugin_id: String,
-    version: String,
+    domain: String,
     tls_policy: Option<String>,
     tls_fingerprint: Option<String>,
-) -> CommandResult<PluginRuntimeEntry> {
-    plugin_usecases::plugins_get_runtime_entry_for_version(
+) -> CommandResult<PluginDomainResolution> {
+    plugin_usecases::plugins_resolve_domain(
         &server_socket,
-        &plugin_id,
-        &version,
+        &domain,
         tls_policy.as_deref(),
         tls_fingerprint.as_deref(),
         PluginInstallStorePortAdapter::shared(),
@@ -175,8 +728,8 @@ pub async fn plugins_get_runtime_entry_for_version(
     .await
     .map_err(|e| {
         to_command_error(
-            "PLUGINS_GET_RUNTIME_ENTRY_FOR_VERSION_FAILED",
-            "error.plugins_get_runtime_entry_for_version_failed",
+            "PLUGINS_RESOLVE_DOMAIN_FAILED",
+            "error.plugins_resolve_domain_failed",
             e,
         )
     })
@@ -188,6 +741,7 @@ pub async fn plugins_get_runtime_entry_for_version(
 /// - `server_socket`：目标服务端 socket。
 /// - `plugin_id`：插件 id。
 /// - `version`：目标版本（可选；为空时由服务端/目录决定默认版本）。
+/// - `confirm_untrusted`：用户已确认安装未经验证的插件（缺省为 `false`）。
 /// - `tls_policy`/`tls_fingerprint`：TLS 相关参数（可选）。
 ///
 /// # 返回值
@@ -198,20 +752,24 @@ pub async fn plugins_install_from_server_catalog(
     server_socket: String,
     plugin_id: String,
     version: Option<String>,
+    confirm_untrusted: Option<bool>,
     tls_policy: Option<String>,
     tls_fingerprint: Option<String>,
 ) -> CommandResult<InstalledPluginState> {
+    let taskbar = TaskbarProgress::start("plugin_install");
     plugin_usecases::plugins_install_from_server_catalog(
         &server_socket,
         &plugin_id,
         version.as_deref(),
+        confirm_untrusted.unwrap_or(false),
         tls_policy.as_deref(),
         tls_fingerprint.as_deref(),
         PluginInstallStorePortAdapter::shared(),
     )
     .await
+    .inspect(|_| taskbar.finish())
     .map_err(|e| {
-        to_command_error(
+        map_plugin_error(
             "PLUGINS_INSTALL_FROM_SERVER_CATALOG_FAILED",
             "error.plugins_install_from_server_catalog_failed",
             e,
@@ -227,6 +785,7 @@ pub async fn plugins_install_from_server_catalog(
 /// - `version`：要安装的版本。
 /// - `url`：插件包下载地址。
 /// - `sha256`：插件包 sha256（用于完整性校验）。
+/// - `confirm_untrusted`：自定义来源总是未经验证，必须为 `true` 才会安装。
 /// - `tls_policy`/`tls_fingerprint`：TLS 相关参数（可选）。
 ///
 /// # 返回值
@@ -239,9 +798,11 @@ pub async fn plugins_install_from_url(
     version: String,
     url: String,
     sha256: String,
+    confirm_untrusted: Option<bool>,
     tls_policy: Option<String>,
     tls_fingerprint: Option<String>,
 ) -> CommandResult<InstalledPluginState> {
+    let taskbar = TaskbarProgress::start("plugin_install");
     plugin_usecases::plugins_install_from_url(
         PluginInstallFromUrlRequest {
             server_socket: &server_socket,
@@ -255,8 +816,9 @@ pub async fn plugins_install_from_url(
         PluginInstallStorePortAdapter::shared(),
     )
     .await
+    .inspect(|_| taskbar.finish())
     .map_err(|e| {
-        to_command_error(
+        map_plugin_error(
             "PLUGINS_INSTALL_FROM_URL_FAILED",
             "error.plugins_install_from_url_failed",
             e,
@@ -276,12 +838,13 @@ pub async fn plugins_install_from_url(
 /// - `Err(String)`：启用失败原因。
 #[tauri::command]
 pub async fn plugins_enable(
+    app: AppHandle,
     server_socket: String,
     plugin_id: String,
     tls_policy: Option<String>,
     tls_fingerprint: Option<String>,
 ) -> CommandResult<InstalledPluginState> {
-    plugin_usecases::plugins_enable(
+    let result = plugin_usecases::plugins_enable(
         &server_socket,
         &plugin_id,
         tls_policy.as_deref(),
@@ -289,7 +852,15 @@ pub async fn plugins_enable(
         PluginInstallStorePortAdapter::shared(),
     )
     .await
-    .map_err(|e| to_command_error("PLUGINS_ENABLE_FAILED", "error.plugins_enable_failed", e))
+    .map_err(|e| map_plugin_error("PLUGINS_ENABLE_FAILED", "error.plugins_enable_failed", e))?;
+    sync_domain_registry(
+        &app,
+        &server_socket,
+        tls_policy.as_deref(),
+        tls_fingerprint.as_deref(),
+    )
+    .await;
+    Ok(result)
 }
 
 /// 禁用已安装插件。
@@ -304,12 +875,13 @@ pub async fn plugins_enable(
 /// - `Err(String)`：禁用失败原因。
 #[tauri::command]
 pub async fn plugins_disable(
+    app: AppHandle,
     server_socket: String,
     plugin_id: String,
     tls_policy: Option<String>,
     tls_fingerprint: Option<String>,
 ) -> CommandResult<InstalledPluginState> {
-    plugin_usecases::plugins_disable(
+    let result = plugin_usecases::plugins_disable(
         &server_socket,
         &plugin_id,
         tls_policy.as_deref(),
@@ -317,7 +889,15 @@ pub async fn plugins_disable(
         PluginInstallStorePortAdapter::shared(),
     )
     .await
-    .map_err(|e| to_command_error("PLUGINS_DISABLE_FAILED", "error.plugins_disable_failed", e))
+    .map_err(|e| to_command_error("PLUGINS_DISABLE_FAILED", "error.plugins_disable_failed", e))?;
+    sync_domain_registry(
+        &app,
+        &server_socket,
+        tls_policy.as_deref(),
+        tls_fingerprint.as_deref(),
+    )
+    .await;
+    Ok(result)
 }
 
 /// 切换已安装插件的当前版本。
@@ -333,13 +913,14 @@ pub async fn plugins_disable(
 /// - `Err(String)`：切换失败原因。
 #[tauri::command]
 pub async fn plugins_switch_version(
+    app: AppHandle,
     server_socket: String,
     plugin_id: String,
     version: String,
     tls_policy: Option<String>,
     tls_fingerprint: Option<String>,
 ) -> CommandResult<InstalledPluginState> {
-    plugin_usecases::plugins_switch_version(
+    let result = plugin_usecases::plugins_switch_version(
         &server_socket,
         &plugin_id,
         &version,
@@ -354,7 +935,15 @@ pub async fn plugins_switch_version(
             "error.plugins_switch_version_failed",
             e,
         )
-    })
+    })?;
+    sync_domain_registry(
+        &app,
+        &server_socket,
+        tls_policy.as_deref(),
+        tls_fingerprint.as_deref(),
+    )
+    .await;
+    Ok(result)
 }
 
 /// 卸载插件（移除服务端安装记录与本地缓存）。
@@ -369,6 +958,7 @@ pub async fn plugins_switch_version(
 /// - `Err(String)`：卸载失败原因。
 #[tauri::command]
 pub async fn plugins_uninstall(
+    app: AppHandle,
     server_socket: String,
     plugin_id: String,
     tls_policy: Option<String>,
@@ -388,7 +978,15 @@ pub async fn plugins_uninstall(
             "error.plugins_uninstall_failed",
             e,
         )
-    })
+    })?;
+    sync_domain_registry(
+        &app,
+        &server_socket,
+        tls_policy.as_deref(),
+        tls_fingerprint.as_deref(),
+    )
+    .await;
+    Ok(())
 }
 
 /// 将插件状态标记为失败（写入 last_error 等字段）。
@@ -404,16 +1002,22 @@ pub async fn plugins_uninstall(
 /// - `Err(String)`：更新失败原因。
 #[tauri::command]
 pub async fn plugins_set_failed(
+    app: AppHandle,
     server_socket: String,
     plugin_id: String,
     message: String,
     tls_policy: Option<String>,
     tls_fingerprint: Option<String>,
 ) -> CommandResult<InstalledPluginState> {
-    plugin_usecases::plugins_set_failed(
+    let auto_rollback_after = crate::features::settings::get_config_value::<u32>(String::from(
+        "plugin_auto_rollback_failures",
+    ))
+    .await;
+    let result = plugin_usecases::plugins_set_failed(
         &server_socket,
         &plugin_id,
         &message,
+        auto_rollback_after,
         tls_policy.as_deref(),
         tls_fingerprint.as_deref(),
         PluginInstallStorePortAdapter::shared(),
@@ -425,6 +1029,316 @@ pub async fn plugins_set_failed(
             "error.plugins_set_failed_state_failed",
             e,
         )
+    })?;
+    sync_domain_registry(
+        &app,
+        &server_socket,
+        tls_policy.as_deref(),
+        tls_fingerprint.as_deref(),
+    )
+    .await;
+    Ok(result)
+}
+
+/// 记录一次插件运行时加载崩溃，短时间内反复崩溃时自动停用插件。
+///
+/// # 参数
+/// - `webview`：调用方 webview；只有经 `plugins_get_runtime_entry` 为该插件取过入口的 webview
+///   才能上报，且每次取入口只能上报一次。
+/// - `server_socket`：目标服务端 socket。
+/// - `plugin_id`：插件 id。
+/// - `message`：崩溃信息。
+/// - `tls_policy`/`tls_fingerprint`：TLS 相关参数（可选）。
+///
+/// # 返回值
+/// - `Ok(Some(PluginAutoDisabled))`：插件已被自动停用（同时发出 `plugin-auto-disabled`）。
+/// - `Ok(None)`：已记录，插件保持原状态。
+/// - `Err(String)`：插件未安装、该 webview 没有待上报的加载或状态写入失败。
+#[tauri::command]
+pub async fn plugins_report_crash(
+    app: AppHandle,
+    webview: tauri::Webview,
+    server_socket: String,
+    plugin_id: String,
+    message: String,
+    tls_policy: Option<String>,
+    tls_fingerprint: Option<String>,
+) -> CommandResult<Option<PluginAutoDisabled>> {
+    plugin_usecases::plugins_report_crash(
+        webview.label(),
+        &server_socket,
+        &plugin_id,
+        &message,
+        tls_policy.as_deref(),
+        tls_fingerprint.as_deref(),
+        PluginInstallStorePortAdapter::shared(),
+        &app,
+    )
+    .await
+    .map_err(|e| {
+        to_command_error(
+            "PLUGINS_REPORT_CRASH_FAILED",
+            "error.plugins_report_crash_failed",
+            e,
+        )
+    })
+}
+
+/// 回滚插件到最近一个 known-good 版本（`history.json`）。
+///
+/// # 参数
+/// - `server_socket`：目标服务端 socket。
+/// - `plugin_id`：插件 id。
+/// - `tls_policy`/`tls_fingerprint`：TLS 相关参数（可选）。
+///
+/// # 返回值
+/// - `Ok(InstalledPluginState)`：回滚后的状态（已启用，错误已清除）。
+/// - `Err(String)`：没有可回滚的版本或校验失败。
+#[tauri::command]
+pub async fn plugins_rollback(
+    app: AppHandle,
+    server_socket: String,
+    plugin_id: String,
+    tls_policy: Option<String>,
+    tls_fingerprint: Option<String>,
+) -> CommandResult<InstalledPluginState> {
+    let result = plugin_usecases::plugins_rollback(
+        &server_socket,
+        &plugin_id,
+        tls_policy.as_deref(),
+        tls_fingerprint.as_deref(),
+        PluginInstallStorePortAdapter::shared(),
+    )
+    .await
+    .map_err(|e| {
+        map_plugin_error(
+            "PLUGINS_ROLLBACK_FAILED",
+            "error.plugins_rollback_failed",
+            e,
+        )
+    })?;
+    sync_domain_registry(
+        &app,
+        &server_socket,
+        tls_policy.as_deref(),
+        tls_fingerprint.as_deref(),
+    )
+    .await;
+    Ok(result)
+}
+
+/// 清理插件旧版本（当前版本与上一个版本始终保留）。
+///
+/// # 参数
+/// - `server_socket`：目标服务端 socket。
+/// - `plugin_id`：插件 id。
+/// - `keep_latest_n`：除当前/上一个版本外额外保留的最新版本数。
+/// - `tls_policy`/`tls_fingerprint`：TLS 相关参数（可选）。
+///
+/// # 返回值
+/// - `Ok(PluginPruneResult)`：删除的版本与释放的空间（bytes）。
+/// - `Err(String)`：清理失败原因。
+#[tauri::command]
+pub async fn plugins_prune_versions(
+    server_socket: String,
+    plugin_id: String,
+    keep_latest_n: u32,
+    tls_policy: Option<String>,
+    tls_fingerprint: Option<String>,
+) -> CommandResult<PluginPruneResult> {
+    plugin_usecases::plugins_prune_versions(
+        &server_socket,
+        &plugin_id,
+        keep_latest_n,
+        tls_policy.as_deref(),
+        tls_fingerprint.as_deref(),
+        PluginInstallStorePortAdapter::shared(),
+    )
+    .await
+    .map_err(|e| {
+        to_command_error(
+            "PLUGINS_PRUNE_VERSIONS_FAILED",
+            "error.plugins_prune_versions_failed",
+            e,
+        )
+    })
+}
+
+/// 插件开发者模式未开启时拒绝开发态命令。
+async fn ensure_dev_mode() -> CommandResult<()> {
+    if !crate::features::settings::get_config_value::<bool>(String::from("plugin_dev_mode")).await {
+        return Err(command_error(
+            "PLUGINS_DEV_MODE_DISABLED",
+            "error.plugins_dev_mode_disabled",
+        ));
+    }
+    Ok(())
+}
+
+/// 将本地目录链接为开发态插件，并监听目录变化（发出 `plugin-dev-reload` 事件）。
+///
+/// # 参数
+/// - `server_id`：服务端 id（插件挂载在 `app://plugins/{server_id}/{plugin_id}/dev/...`）。
+/// - `plugin_id`：插件 id（需与目录内 `plugin.json` 一致）。
+/// - `path`：本地插件源目录。
+///
+/// # 返回值
+/// - `Ok(InstalledPluginState)`：链接后的状态（`dev = true`，当前版本为 `dev`）。
+/// - `Err(String)`：开发者模式未开启、目录/清单非法或链接失败。
+#[tauri::command]
+pub async fn plugins_dev_link(
+    app: AppHandle,
+    server_id: String,
+    plugin_id: String,
+    path: String,
+) -> CommandResult<InstalledPluginState> {
+    ensure_dev_mode().await?;
+    let (state, source) = plugin_usecases::plugins_dev_link(
+        &server_id,
+        &plugin_id,
+        &path,
+        PluginInstallStorePortAdapter::shared(),
+    )
+    .await
+    .map_err(|e| {
+        map_plugin_error(
+            "PLUGINS_DEV_LINK_FAILED",
+            "error.plugins_dev_link_failed",
+            e,
+        )
+    })?;
+    super::dev_reload::watch(&app, &server_id, &plugin_id, &source).map_err(|e| {
+        to_command_error(
+            "PLUGINS_DEV_LINK_FAILED",
+            "error.plugins_dev_link_failed",
+            e,
+        )
+    })?;
+    Ok(state)
+}
+
+/// 取消开发态插件链接并停止目录监听（源目录不会被删除）。
+///
+/// # 参数
+/// - `server_id`：服务端 id。
+/// - `plugin_id`：插件 id。
+///
+/// # 返回值
+/// - `Ok(Some(InstalledPluginState))`：回落到最近安装的正式版本后的状态。
+/// - `Ok(None)`：没有正式版本，插件已从本地移除。
+/// - `Err(String)`：删除链接失败。
+#[tauri::command]
+pub async fn plugins_dev_unlink(
+    server_id: String,
+    plugin_id: String,
+) -> CommandResult<Option<InstalledPluginState>> {
+    super::dev_reload::unwatch(&server_id, &plugin_id);
+    plugin_usecases::plugins_dev_unlink(
+        &server_id,
+        &plugin_id,
+        PluginInstallStorePortAdapter::shared(),
+    )
+    .await
+    .map_err(|e| {
+        to_command_error(
+            "PLUGINS_DEV_UNLINK_FAILED",
+            "error.plugins_dev_unlink_failed",
+            e,
+        )
+    })
+}
+
+/// 发布前自检插件包：按安装时的规则校验 `plugin.json` 并检查入口文件与包内容，一次性返回全部问题。
+///
+/// # 参数
+/// - `path`：插件 zip 文件或已构建的插件目录。
+///
+/// # 返回值
+/// - `Ok(PluginPackageValidation)`：自检结果（问题列表为空即可发布）。
+/// - `Err(String)`：路径不存在或不是有效的 zip 文件。
+#[tauri::command]
+pub async fn plugins_validate_package(path: String) -> CommandResult<PluginPackageValidation> {
+    plugin_usecases::plugins_validate_package(&path, PluginInstallStorePortAdapter::shared())
+        .await
+        .map_err(|e| {
+            to_command_error(
+                "PLUGINS_VALIDATE_PACKAGE_FAILED",
+                "error.plugins_validate_package_failed",
+                e,
+            )
+        })
+}
+
+/// 启动插件本地 HTTP 服务（已运行时返回当前地址）。
+///
+/// # 返回值
+/// - `Ok(PluginHttpServerInfo)`：端口与资源根地址（含本次会话 token）。
+/// - `Err(CommandError)`：设置 `plugin_http_server` 未开启（`PLUGINS_HTTP_SERVER_DISABLED`，同时停止已运行的服务）
+///   或监听失败（`PLUGINS_HTTP_SERVER_START_FAILED`）。
+///
+/// # 说明
+/// 仅供插件作者调试使用（见 `asset_server` 模块说明），前端加载器不会调用。
+#[tauri::command]
+pub async fn plugins_http_server_start() -> CommandResult<PluginHttpServerInfo> {
+    if !crate::features::settings::get_config_value::<bool>(String::from("plugin_http_server"))
+        .await
+    {
+        super::asset_server::stop();
+        return Err(command_error(
+            "PLUGINS_HTTP_SERVER_DISABLED",
+            "error.plugins_http_server_disabled",
+        ));
+    }
+    super::asset_server::start().await.map_err(|e| {
+        to_command_error(
+            "PLUGINS_HTTP_SERVER_START_FAILED",
+            "error.plugins_http_server_start_failed",
+            e,
+        )
+    })
+}
+
+/// 停止插件本地 HTTP 服务（关闭设置或不再需要时调用）。
+#[tauri::command]
+pub async fn plugins_http_server_stop() -> CommandResult<()> {
+    super::asset_server::stop();
+    Ok(())
+}
+
+/// 上报插件运行时加载成功，把当前版本记为 known-good。
+///
+/// # 参数
+/// - `server_socket`：目标服务端 socket。
+/// - `plugin_id`：插件 id。
+/// - `version`：实际加载的版本（与当前版本不一致时忽略）。
+/// - `tls_policy`/`tls_fingerprint`：TLS 相关参数（可选）。
+///
+/// # 返回值
+/// - `Ok(())`：已记录。
+/// - `Err(String)`：插件未安装或写入失败。
+#[tauri::command]
+pub async fn plugins_mark_loaded(
+    server_socket: String,
+    plugin_id: String,
+    version: String,
+    tls_policy: Option<String>,
+    tls_fingerprint: Option<String>,
+) -> CommandResult<()> {
+    plugin_usecases::plugins_mark_loaded(
+        &server_socket,
+        &plugin_id,
+        &version,
+        tls_policy.as_deref(),
+        tls_fingerprint.as_deref(),
+        PluginInstallStorePortAdapter::shared(),
+    )
+    .await
+    .map_err(|e| {
+        to_command_error(
+            "PLUGINS_MARK_LOADED_FAILED",
+            "error.plugins_mark_loaded_failed",
+            e,
+        )
     })
 }
 
@@ -440,12 +1354,13 @@ pub async fn plugins_set_failed(
 /// - `Err(String)`：更新失败原因。
 #[tauri::command]
 pub async fn plugins_clear_error(
+    app: AppHandle,
     server_socket: String,
     plugin_id: String,
     tls_policy: Option<String>,
     tls_fingerprint: Option<String>,
 ) -> CommandResult<InstalledPluginState> {
-    plugin_usecases::plugins_clear_error(
+    let result = plugin_usecases::plugins_clear_error(
         &server_socket,
         &plugin_id,
         tls_policy.as_deref(),
@@ -459,7 +1374,15 @@ pub async fn plugins_clear_error(
             "error.plugins_clear_error_failed",
             e,
         )
-    })
+    })?;
+    sync_domain_registry(
+        &app,
+        &server_socket,
+        tls_policy.as_deref(),
+        tls_fingerprint.as_deref(),
+    )
+    .await;
+    Ok(result)
 }
 
 /// 读取插件私有存储（KV）。
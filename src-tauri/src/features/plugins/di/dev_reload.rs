@@ -0,0 +1,140 @@
+//! plugins｜DI：开发态插件目录监听与热重载事件。
+//!
+//! 说明：
+//! - 每个开发态链接对应一个文件系统监听器（监听源目录本身，而不是插件目录下的符号链接）；
+//! - 变化先做 300ms 防抖，再合并为一条 `plugin-dev-reload` 事件发给前端，由前端重新加载插件；
+//! - 取消链接时移除监听器：监听器释放后通道关闭，防抖任务随之退出。
+//!
+//! 约定：注释中文，日志英文（tracing）。
+
+use std::collections::{BTreeSet, HashMap};
+use std::path::{Path, PathBuf};
+use std::sync::{Mutex, OnceLock};
+use std::time::Duration;
+
+use notify::{EventKind, RecommendedWatcher, RecursiveMode, Watcher};
+use tauri::AppHandle;
+use tokio::sync::mpsc;
+
+use crate::features::plugins::data::plugin_ports::PluginInstallStorePortAdapter;
+use crate::features::plugins::domain::types::PluginDevReload;
+use crate::features::plugins::usecases::plugin_usecases;
+use crate::shared::events::EventBusExt;
+
+/// 开发态插件热重载事件名。
+pub const PLUGIN_DEV_RELOAD_EVENT: &str = "plugin-dev-reload";
+
+/// 防抖窗口：编辑器保存/构建工具输出通常会连续触发多次写入。
+const DEBOUNCE: Duration = Duration::from_millis(300);
+
+/// `(server_id, plugin_id)` -> 监听器。
+static WATCHERS: OnceLock<Mutex<HashMap<(String, String), RecommendedWatcher>>> = OnceLock::new();
+
+fn watchers() -> &'static Mutex<HashMap<(String, String), RecommendedWatcher>> {
+    WATCHERS.get_or_init(|| Mutex::new(HashMap::new()))
+}
+
+/// 开始监听开发态插件源目录（已存在的监听器会被替换）。
+pub fn watch(
+    app: &AppHandle,
+    server_id: &str,
+    plugin_id: &str,
+    source: &Path,
+) -> anyhow::Result<()> {
+    let (tx, rx) = mpsc::unbounded_channel::<PathBuf>();
+    let mut watcher =
+        notify::recommended_watcher(move |res: notify::Result<notify::Event>| match res {
+            Ok(event) if !matches!(event.kind, EventKind::Access(_)) => {
+                for path in event.paths {
+                    let _ = tx.send(path);
+                }
+            }
+            Ok(_) => {}
+            Err(e) => {
+                tracing::warn!(action = "plugins_dev_watch_error", error = %e);
+            }
+        })?;
+    watcher.watch(source, RecursiveMode::Recursive)?;
+
+    tauri::async_runtime::spawn(forward_changes(
+        app.clone(),
+        server_id.to_string(),
+        plugin_id.to_string(),
+        source.to_path_buf(),
+        rx,
+    ));
+    watchers()
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+        .insert((server_id.to_string(), plugin_id.to_string()), watcher);
+    tracing::info!(action = "plugins_dev_watch_started", plugin_id = %plugin_id, path = %source.display());
+    Ok(())
+}
+
+/// 停止监听（未监听时为 no-op）。
+pub fn unwatch(server_id: &str, plugin_id: &str) {
+    let removed = watchers()
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+        .remove(&(server_id.to_string(), plugin_id.to_string()));
+    if removed.is_some() {
+        tracing::info!(action = "plugins_dev_watch_stopped", plugin_id = %plugin_id);
+    }
+}
+
+/// 合并防抖窗口内的变化并发出事件；发送端全部释放后退出。
+async fn forward_changes(
+    app: AppHandle,
+    server_id: String,
+    plugin_id: String,
+    source: PathBuf,
+    mut rx: mpsc::UnboundedReceiver<PathBuf>,
+) {
+    while let Some(first) = rx.recv().await {
+        let mut changed = BTreeSet::from([first]);
+        let deadline = tokio::time::sleep(DEBOUNCE);
+        tokio::pin!(deadline);
+        loop {
+            tokio::select! {
+                _ = &mut deadline => break,
+                next = rx.recv() => match next {
+                    Some(path) => {
+                        changed.insert(path);
+                    }
+                    None => break,
+                },
+            }
+        }
+        let paths: Vec<String> = changed
+            .iter()
+            .map(|p| {
+                p.strip_prefix(&source)
+                    .unwrap_or(p)
+                    .to_string_lossy()
+                    .replace('\\', "/")
+            })
+            .collect();
+        tracing::debug!(action = "plugins_dev_reload", plugin_id = %plugin_id, changed = paths.len());
+        let payload = PluginDevReload {
+            server_id: server_id.clone(),
+            plugin_id: plugin_id.clone(),
+            paths,
+        };
+        app.emit(PLUGIN_DEV_RELOAD_EVENT, payload);
+    }
+}
+
+/// 启动时为已有的开发态链接恢复监听（仅在开启插件开发者模式时）。
+pub async fn restore_dev_watchers(app: AppHandle) -> anyhow::Result<()> {
+    if !crate::features::settings::get_config_value::<bool>(String::from("plugin_dev_mode")).await {
+        return Ok(());
+    }
+    let links =
+        plugin_usecases::plugins_list_dev_links(PluginInstallStorePortAdapter::shared()).await?;
+    for (server_id, plugin_id, source) in links {
+        if let Err(e) = watch(&app, &server_id, &plugin_id, &source) {
+            tracing::warn!(action = "plugins_dev_watch_restore_failed", plugin_id = %plugin_id, error = %e);
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,16 @@
+//! plugins｜DI：旧插件数据迁移的启动入口。
+//!
+//! 约定：注释中文，日志英文（tracing）。
+
+use crate::features::plugins::data::plugin_ports::PluginInstallStorePortAdapter;
+use crate::features::plugins::usecases::plugin_usecases;
+
+/// 执行一次旧插件数据导入（由启动编排器在首屏渲染后调度；失败下次启动重试）。
+pub async fn run_legacy_import() -> anyhow::Result<()> {
+    let imported =
+        plugin_usecases::plugins_import_legacy(PluginInstallStorePortAdapter::shared()).await?;
+    if imported > 0 {
+        tracing::info!(action = "plugins_legacy_import_completed", imported);
+    }
+    Ok(())
+}
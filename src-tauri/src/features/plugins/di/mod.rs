@@ -3,4 +3,59 @@
 //! 说明：该文件负责导出子模块与组织依赖关系。
 //!
 //! 约定：注释中文，日志英文（tracing）。
+pub mod asset_server;
 pub mod commands;
+pub mod dev_reload;
+pub mod legacy_import;
+pub mod preload;
+pub mod version_gc;
+
+use crate::app::registry::{CommandRegistration, CommandRegistry, command_set};
+
+/// 插件命令注册。
+pub struct PluginsCommands;
+
+impl CommandRegistration for PluginsCommands {
+    fn register(registry: &mut CommandRegistry) {
+        registry.add(command_set!(crate::features::plugins::di::commands => [
+            plugins_list_installed,
+            plugins_get_installed_state,
+            plugins_get_runtime_entry,
+            plugins_get_runtime_entry_for_version,
+            plugins_browse_catalog,
+            plugins_get_assets,
+            plugins_get_locale,
+            plugins_get_settings_schema,
+            plugins_get_settings,
+            plugins_update_settings,
+            plugins_bus_open,
+            plugins_bus_publish,
+            plugins_bus_subscribe,
+            plugins_bus_unsubscribe,
+            plugins_check_compatibility,
+            plugins_check_requirements,
+            plugins_install_requirements,
+            plugins_resolve_domain,
+            plugins_install_from_server_catalog,
+            plugins_install_from_url,
+            plugins_enable,
+            plugins_disable,
+            plugins_switch_version,
+            plugins_uninstall,
+            plugins_set_failed,
+            plugins_report_crash,
+            plugins_clear_error,
+            plugins_mark_loaded,
+            plugins_rollback,
+            plugins_prune_versions,
+            plugins_dev_link,
+            plugins_dev_unlink,
+            plugins_validate_package,
+            plugins_http_server_start,
+            plugins_http_server_stop,
+            plugins_storage_get,
+            plugins_storage_set,
+            plugins_network_fetch,
+        ]));
+    }
+}
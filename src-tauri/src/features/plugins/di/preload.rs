@@ -0,0 +1,28 @@
+//! plugins｜DI：启动时预加载已启用插件。
+//!
+//! 说明：
+//! - 由启动编排器在首屏渲染后调度（排在旧数据迁移之后），在连接服务端前提前停用损坏的安装；
+//! - 每个有启用插件的服务端发出一条 `plugin-runtime-ready` 事件，失败项随事件一并上报。
+//!
+//! 约定：注释中文，日志英文（tracing）。
+
+use tauri::AppHandle;
+
+use crate::features::plugins::data::plugin_ports::PluginInstallStorePortAdapter;
+use crate::features::plugins::usecases::plugin_usecases;
+
+/// 执行一次启动预加载。
+pub async fn run_preload(app: AppHandle) -> anyhow::Result<()> {
+    let results =
+        plugin_usecases::plugins_preload_enabled(PluginInstallStorePortAdapter::shared(), &app)
+            .await?;
+    let ready: usize = results.iter().map(|r| r.ready.len()).sum();
+    let failed: usize = results.iter().map(|r| r.failed.len()).sum();
+    tracing::info!(
+        action = "plugins_preload_completed",
+        servers = results.len(),
+        ready,
+        failed
+    );
+    Ok(())
+}
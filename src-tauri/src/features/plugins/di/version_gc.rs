@@ -0,0 +1,55 @@
+//! plugins｜DI：旧版本自动清理调度。
+//!
+//! 说明：
+//! - 启动后延迟一段时间首次执行，之后每 24 小时执行一次；
+//! - 每次执行前读取设置 `plugin_auto_prune` / `plugin_auto_prune_keep`，修改设置无需重启。
+//!
+//! 约定：注释中文，日志英文（tracing）。
+
+use std::time::Duration;
+
+use crate::features::plugins::data::plugin_ports::PluginInstallStorePortAdapter;
+use crate::features::plugins::usecases::plugin_usecases;
+use crate::features::settings::get_config_value;
+
+/// 启动后首次清理前的延迟（避开启动高峰）。
+const STARTUP_DELAY: Duration = Duration::from_secs(10 * 60);
+/// 清理周期。
+const PRUNE_INTERVAL: Duration = Duration::from_secs(24 * 60 * 60);
+
+/// 启动后台清理任务。
+pub fn spawn_version_gc() {
+    tauri::async_runtime::spawn(async move {
+        tokio::time::sleep(STARTUP_DELAY).await;
+        let mut interval = tokio::time::interval(PRUNE_INTERVAL);
+        loop {
+            interval.tick().await;
+            run_once().await;
+        }
+    });
+}
+
+async fn run_once() {
+    if !get_config_value::<bool>(String::from("plugin_auto_prune")).await {
+        tracing::debug!(action = "plugins_auto_prune_skipped");
+        return;
+    }
+    let keep = get_config_value::<u32>(String::from("plugin_auto_prune_keep")).await;
+    match plugin_usecases::plugins_prune_all_versions(keep, PluginInstallStorePortAdapter::shared())
+        .await
+    {
+        Ok(results) => {
+            let freed: u64 = results.iter().map(|r| r.freed_bytes).sum();
+            let removed: usize = results.iter().map(|r| r.removed_versions.len()).sum();
+            tracing::info!(
+                action = "plugins_auto_prune_completed",
+                plugins = results.len(),
+                removed,
+                freed_bytes = freed
+            );
+        }
+        Err(e) => {
+            tracing::warn!(action = "plugins_auto_prune_failed", error = %e);
+        }
+    }
+}
@@ -1,4 +1,3 @@
 //! 模块入口：plugins/domain/ports。
 
 pub mod plugin_install_store_port;
-pub mod plugin_loader_port;
@@ -1,11 +1,16 @@
 //! plugins｜领域端口：plugin_install_store_port。
 
 use std::future::Future;
+use std::path::PathBuf;
 use std::pin::Pin;
 
 use crate::features::plugins::domain::types::{
-    InstalledPluginState, PluginFetchResponse, PluginInstallFromUrlRequest,
-    PluginNetworkFetchRequest, PluginRuntimeEntry,
+    InstalledPluginState, PluginAsset, PluginAutoDisabled, PluginBusMessage, PluginCatalogPage,
+    PluginCatalogQuery, PluginCompatibility, PluginDomainProviderChanged, PluginDomainResolution,
+    PluginFetchResponse, PluginInstallFromUrlRequest, PluginLocaleCatalog,
+    PluginNetworkFetchRequest, PluginPackageValidation, PluginPruneResult,
+    PluginRequirementsReport, PluginRuntimeEntry, PluginRuntimeReady, PluginSettingsSchema,
+    PluginSettingsValues,
 };
 
 pub type PluginInstallStoreFuture<'a, T> =
@@ -29,6 +34,7 @@ pub trait PluginInstallStorePort: Send + Sync {
 
     fn get_runtime_entry<'a>(
         &'a self,
+        webview: &'a str,
         server_socket: &'a str,
         plugin_id: &'a str,
         tls_policy: Option<&'a str>,
@@ -44,11 +50,133 @@ pub trait PluginInstallStorePort: Send + Sync {
         tls_fingerprint: Option<&'a str>,
     ) -> PluginInstallStoreFuture<'a, PluginRuntimeEntry>;
 
+    fn browse_catalog<'a>(
+        &'a self,
+        request: PluginCatalogQuery<'a>,
+    ) -> PluginInstallStoreFuture<'a, PluginCatalogPage>;
+
+    fn get_assets<'a>(
+        &'a self,
+        server_socket: &'a str,
+        plugin_id: &'a str,
+        version: Option<&'a str>,
+        tls_policy: Option<&'a str>,
+        tls_fingerprint: Option<&'a str>,
+    ) -> PluginInstallStoreFuture<'a, Vec<PluginAsset>>;
+
+    fn get_locale<'a>(
+        &'a self,
+        server_socket: &'a str,
+        plugin_id: &'a str,
+        lang: &'a str,
+        tls_policy: Option<&'a str>,
+        tls_fingerprint: Option<&'a str>,
+    ) -> PluginInstallStoreFuture<'a, PluginLocaleCatalog>;
+
+    fn get_settings_schema<'a>(
+        &'a self,
+        server_socket: &'a str,
+        plugin_id: &'a str,
+        tls_policy: Option<&'a str>,
+        tls_fingerprint: Option<&'a str>,
+    ) -> PluginInstallStoreFuture<'a, PluginSettingsSchema>;
+
+    fn get_settings<'a>(
+        &'a self,
+        server_socket: &'a str,
+        plugin_id: &'a str,
+        tls_policy: Option<&'a str>,
+        tls_fingerprint: Option<&'a str>,
+    ) -> PluginInstallStoreFuture<'a, PluginSettingsValues>;
+
+    fn update_settings<'a>(
+        &'a self,
+        server_socket: &'a str,
+        plugin_id: &'a str,
+        patch: &'a serde_json::Map<String, serde_json::Value>,
+        tls_policy: Option<&'a str>,
+        tls_fingerprint: Option<&'a str>,
+    ) -> PluginInstallStoreFuture<'a, (PluginSettingsValues, Vec<String>)>;
+
+    fn bus_open<'a>(
+        &'a self,
+        webview: &'a str,
+        server_socket: &'a str,
+        plugin_id: &'a str,
+        tls_policy: Option<&'a str>,
+        tls_fingerprint: Option<&'a str>,
+    ) -> PluginInstallStoreFuture<'a, String>;
+
+    fn bus_publish<'a>(
+        &'a self,
+        webview: &'a str,
+        server_socket: &'a str,
+        bus_token: &'a str,
+        topic: &'a str,
+        payload: serde_json::Value,
+    ) -> PluginInstallStoreFuture<'a, PluginBusMessage>;
+
+    fn bus_subscribe<'a>(
+        &'a self,
+        webview: &'a str,
+        bus_token: &'a str,
+        topic: &'a str,
+    ) -> PluginInstallStoreFuture<'a, ()>;
+
+    fn bus_unsubscribe<'a>(
+        &'a self,
+        webview: &'a str,
+        bus_token: &'a str,
+        topic: Option<&'a str>,
+    ) -> PluginInstallStoreFuture<'a, ()>;
+
+    fn report_crash<'a>(
+        &'a self,
+        webview: &'a str,
+        server_socket: &'a str,
+        plugin_id: &'a str,
+        message: &'a str,
+        tls_policy: Option<&'a str>,
+        tls_fingerprint: Option<&'a str>,
+    ) -> PluginInstallStoreFuture<'a, Option<PluginAutoDisabled>>;
+
+    fn check_compatibility<'a>(
+        &'a self,
+        server_socket: &'a str,
+        plugin_id: &'a str,
+        version: Option<&'a str>,
+        tls_policy: Option<&'a str>,
+        tls_fingerprint: Option<&'a str>,
+    ) -> PluginInstallStoreFuture<'a, PluginCompatibility>;
+
+    fn check_requirements<'a>(
+        &'a self,
+        server_socket: &'a str,
+        tls_policy: Option<&'a str>,
+        tls_fingerprint: Option<&'a str>,
+    ) -> PluginInstallStoreFuture<'a, PluginRequirementsReport>;
+
+    fn resolve_domain<'a>(
+        &'a self,
+        server_socket: &'a str,
+        domain: &'a str,
+        tls_policy: Option<&'a str>,
+        tls_fingerprint: Option<&'a str>,
+    ) -> PluginInstallStoreFuture<'a, PluginDomainResolution>;
+
+    fn refresh_domains<'a>(
+        &'a self,
+        server_socket: &'a str,
+        tls_policy: Option<&'a str>,
+        tls_fingerprint: Option<&'a str>,
+    ) -> PluginInstallStoreFuture<'a, Vec<PluginDomainProviderChanged>>;
+
     fn install_from_server_catalog<'a>(
         &'a self,
         server_socket: &'a str,
         plugin_id: &'a str,
         version: Option<&'a str>,
+        confirm_untrusted: bool,
         tls_policy: Option<&'a str>,
         tls_fingerprint: Option<&'a str>,
     ) -> PluginInstallStoreFuture<'a, InstalledPluginState>;
@@ -96,10 +224,58 @@ pub trait PluginInstallStorePort: Send + Sync {
         server_socket: &'a str,
         plugin_id: &'a str,
         message: &'a str,
+        auto_rollback_after: u32,
+        tls_policy: Option<&'a str>,
+        tls_fingerprint: Option<&'a str>,
+    ) -> PluginInstallStoreFuture<'a, InstalledPluginState>;
+
+    fn rollback<'a>(
+        &'a self,
+        server_socket: &'a str,
+        plugin_id: &'a str,
         tls_policy: Option<&'a str>,
         tls_fingerprint: Option<&'a str>,
     ) -> PluginInstallStoreFuture<'a, InstalledPluginState>;
 
+    fn prune_versions<'a>(
+        &'a self,
+        server_socket: &'a str,
+        plugin_id: &'a str,
+        keep_latest_n: u32,
+        tls_policy: Option<&'a str>,
+        tls_fingerprint: Option<&'a str>,
+    ) -> PluginInstallStoreFuture<'a, PluginPruneResult>;
+
+    fn prune_all_versions<'a>(
+        &'a self,
+        keep_latest_n: u32,
+    ) -> PluginInstallStoreFuture<'a, Vec<PluginPruneResult>>;
+
+    fn dev_link<'a>(
+        &'a self,
+        server_id: &'a str,
+        plugin_id: &'a str,
+        path: &'a str,
+    ) -> PluginInstallStoreFuture<'a, (InstalledPluginState, PathBuf)>;
+
+    fn dev_unlink<'a>(
+        &'a self,
+        server_id: &'a str,
+        plugin_id: &'a str,
+    ) -> PluginInstallStoreFuture<'a, Option<InstalledPluginState>>;
+
+    fn list_dev_links<'a>(&'a self)
+    -> PluginInstallStoreFuture<'a, Vec<(String, String, PathBuf)>>;
+
+    fn import_legacy<'a>(&'a self) -> PluginInstallStoreFuture<'a, usize>;
+
+    fn preload_enabled<'a>(&'a self) -> PluginInstallStoreFuture<'a, Vec<PluginRuntimeReady>>;
+
+    fn validate_package<'a>(
+        &'a self,
+        path: &'a str,
+    ) -> PluginInstallStoreFuture<'a, PluginPackageValidation>;
+
     fn clear_error<'a>(
         &'a self,
         server_socket: &'a str,
@@ -108,6 +284,15 @@ pub trait PluginInstallStorePort: Send + Sync {
         tls_fingerprint: Option<&'a str>,
     ) -> PluginInstallStoreFuture<'a, InstalledPluginState>;
 
+    fn mark_loaded<'a>(
+        &'a self,
+        server_socket: &'a str,
+        plugin_id: &'a str,
+        version: &'a str,
+        tls_policy: Option<&'a str>,
+        tls_fingerprint: Option<&'a str>,
+    ) -> PluginInstallStoreFuture<'a, ()>;
+
     fn storage_get<'a>(
         &'a self,
         server_socket: &'a str,
@@ -3,26 +3,6 @@
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct PluginManifest {
-    pub name: String,
-    pub version: String,
-    pub description: Option<String>,
-    pub author: Option<String>,
-    pub license: Option<String>,
-    pub url: String,
-    pub frontend_sha256: String,
-    pub backend_sha256: String,
-}
-
-#[derive(Debug, Clone, Serialize, Deserialize)]
-#[serde(rename_all = "camelCase")]
-pub struct PluginLoadResult {
-    pub frontend_wasm: String,
-    pub frontend_js: String,
-    pub frontend_html: String,
-}
-
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "snake_case")]
 pub struct PluginProvidesDomain {
@@ -39,6 +19,31 @@ pub struct InstalledPluginState {
     pub enabled: bool,
     pub status: String,
     pub last_error: String,
+    /// 是否为开发态链接插件（`plugins_dev_link`）。
+    #[serde(default)]
+    pub dev: bool,
+    /// 开发态插件的本地源目录。
+    #[serde(default)]
+    pub dev_path: Option<String>,
+    /// 当前版本安装时确定的信任级别（开发态与自定义来源均为 `unverified`）。
+    #[serde(default)]
+    pub trust_level: PluginTrustLevel,
+}
+
+/// 插件来源信任级别（由目录条目签名决定，见 `plugin_store::trust`）。
+///
+/// # 说明
+/// 变体按信任程度从低到高排列，可直接比较大小。
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PluginTrustLevel {
+    /// 无有效签名（含自定义 URL 安装）；安装需显式确认。
+    #[default]
+    Unverified,
+    /// 经审核的第三方插件。
+    Verified,
+    /// 官方插件。
+    FirstParty,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -51,6 +56,25 @@ pub struct PluginRuntimeEntry {
     pub min_host_version: String,
     pub permissions: Vec<String>,
     pub provides_domains: Vec<PluginProvidesDomain>,
+    /// 插件包携带的语言（`locales/*.json`，规范化为小写 `-` 分隔）。
+    #[serde(default)]
+    pub locales: Vec<String>,
+}
+
+/// 插件语言资源（`plugins_get_locale`）。
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PluginLocaleCatalog {
+    pub plugin_id: String,
+    pub version: String,
+    /// 请求的语言（已规范化）。
+    pub requested: String,
+    /// 实际命中的最具体语言（插件未携带任何可用语言时为 `None`）。
+    pub lang: Option<String>,
+    /// 插件携带的全部语言。
+    pub available: Vec<String>,
+    /// 按回退链合并后的消息（嵌套对象）。
+    pub messages: serde_json::Map<String, serde_json::Value>,
 }
 
 #[derive(Debug, Clone, Serialize)]
@@ -71,6 +95,8 @@ pub struct PluginInstallFromUrlRequest<'a> {
     pub sha256: &'a str,
     pub tls_policy: Option<&'a str>,
     pub tls_fingerprint: Option<&'a str>,
+    /// 自定义来源总是 `unverified`，必须为 `true` 才会安装。
+    pub confirm_untrusted: bool,
 }
 
 #[derive(Debug, Clone)]
@@ -83,3 +109,453 @@ pub struct PluginNetworkFetchRequest<'a> {
     pub tls_policy: Option<&'a str>,
     pub tls_fingerprint: Option<&'a str>,
 }
+
+/// 插件目录浏览请求（分页 + 搜索）。
+#[derive(Debug, Clone)]
+pub struct PluginCatalogQuery<'a> {
+    pub server_socket: &'a str,
+    /// 搜索词（匹配 plugin_id / 名称 / 描述 / 作者 / domain，大小写不敏感）。
+    pub query: Option<&'a str>,
+    /// 页码（从 1 开始）。
+    pub page: u32,
+    pub page_size: u32,
+    pub tls_policy: Option<&'a str>,
+    pub tls_fingerprint: Option<&'a str>,
+}
+
+/// 插件目录中的单条展示信息。
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PluginCatalogEntry {
+    pub plugin_id: String,
+    pub name: String,
+    pub version: String,
+    pub description: Option<String>,
+    pub author: Option<String>,
+    /// 图标地址：包内相对路径或已预取的资源为 `app://plugins/...`，其余为服务端同源绝对地址。
+    pub icon_url: Option<String>,
+    /// 横幅地址（规则同 `icon_url`）。
+    pub banner_url: Option<String>,
+    /// 兼容的宿主最低版本（目录未声明时为空）。
+    pub min_host_version: Option<String>,
+    pub required: bool,
+    pub permissions: Vec<String>,
+    pub provides_domains: Vec<PluginProvidesDomain>,
+    pub trust_level: PluginTrustLevel,
+}
+
+/// 插件目录分页结果。
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PluginCatalogPage {
+    pub items: Vec<PluginCatalogEntry>,
+    /// 过滤后的总条数。
+    pub total: usize,
+    pub page: u32,
+    pub page_size: u32,
+    /// 因校验失败被跳过的目录条目数。
+    pub skipped: usize,
+}
+
+/// 预取到插件版本目录的展示资源（图标/横幅）。
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PluginAsset {
+    /// 资源类型：`icon` / `banner`。
+    pub kind: String,
+    /// 目录中声明的原始地址（包内相对路径时与 `rel_path` 相同）。
+    pub source_url: String,
+    /// 相对版本目录的路径。
+    pub rel_path: String,
+    /// 本地访问地址（`app://plugins/...`）。
+    pub app_url: String,
+    /// 文件大小（bytes）。
+    pub size: u64,
+}
+
+/// 插件兼容性检查结果（供 UI 安装前提示）。
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PluginCompatibility {
+    pub plugin_id: String,
+    pub version: String,
+    /// 插件声明的宿主最低版本（未声明时为空）。
+    pub min_host_version: Option<String>,
+    /// 当前宿主版本。
+    pub host_version: String,
+    pub compatible: bool,
+    /// 判定依据来源：`installed`（本地 plugin.json）/ `catalog`（服务端目录）。
+    pub source: String,
+}
+
+/// 插件要求的宿主版本高于当前版本（或声明无法解析）。
+///
+/// # 说明
+/// 命令层通过 `downcast_ref` 识别该错误并映射为 `PLUGINS_INCOMPATIBLE_HOST`，
+/// 前端据此提示“需要升级客户端”，而非笼统的安装/启用失败。
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PluginIncompatibleHost {
+    pub plugin_id: String,
+    pub min_host_version: String,
+    pub host_version: String,
+}
+
+impl std::fmt::Display for PluginIncompatibleHost {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "Plugin {} requires host >= {}, current host is {}",
+            self.plugin_id, self.min_host_version, self.host_version
+        )
+    }
+}
+
+impl std::error::Error for PluginIncompatibleHost {}
+
+/// 安装未经验证的插件但调用方未确认（`confirm_untrusted` 不为 `true`）。
+///
+/// # 说明
+/// 在下载前返回；命令层映射为 `PLUGINS_UNTRUSTED_SOURCE`，前端向用户确认后带上标记重试。
+#[derive(Debug, Clone)]
+pub struct PluginUntrustedSource {
+    pub plugin_id: String,
+    pub trust_level: PluginTrustLevel,
+}
+
+impl std::fmt::Display for PluginUntrustedSource {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "Plugin {} is from an unverified source; installing it requires confirmation",
+            self.plugin_id
+        )
+    }
+}
+
+impl std::error::Error for PluginUntrustedSource {}
+
+/// `plugin.json` / 插件包 / 插件设置的单个校验问题。
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PluginManifestIssue {
+    /// 问题位置：清单字段的 JSON 路径（如 `permissions[1]`）、包内文件路径或设置项 key。
+    pub path: String,
+    /// 稳定的问题代码（如 `missing_field` / `invalid_version` / `unknown_permission`）。
+    pub code: String,
+    /// 面向插件作者的说明（英文）。
+    pub message: String,
+}
+
+/// 插件清单校验未通过（携带全部问题）。
+///
+/// # 说明
+/// 命令层通过 `downcast_ref` 识别该错误并映射为 `PLUGINS_MANIFEST_INVALID`，
+/// 问题列表以 JSON 写入 `details`，便于前端逐条展示。
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PluginManifestInvalid {
+    pub issues: Vec<PluginManifestIssue>,
+}
+
+impl std::fmt::Display for PluginManifestInvalid {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Invalid plugin.json ({} issue(s))", self.issues.len())?;
+        for issue in &self.issues {
+            write!(f, "; {}: {}", issue.path, issue.message)?;
+        }
+        Ok(())
+    }
+}
+
+impl std::error::Error for PluginManifestInvalid {}
+
+/// 插件设置项类型。
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum PluginSettingKind {
+    Boolean,
+    String,
+    Number,
+    Integer,
+    Enum,
+}
+
+/// 插件设置项声明（`plugin.json` 的 `settings[]`；清单中为 `snake_case`，返回前端为 `camelCase`）。
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all(serialize = "camelCase", deserialize = "snake_case"))]
+pub struct PluginSettingField {
+    /// 设置 key（插件内唯一）。
+    pub key: String,
+    #[serde(rename = "type")]
+    pub kind: PluginSettingKind,
+    /// 展示名（可为插件语言资源中的 key）。
+    pub label: Option<String>,
+    pub description: Option<String>,
+    /// 默认值（未声明时按类型取零值：`false` / `""` / `min` 或 `0` / 首个选项）。
+    pub default: Option<serde_json::Value>,
+    /// 数值下限（`number` / `integer`）。
+    pub min: Option<f64>,
+    /// 数值上限（`number` / `integer`）。
+    pub max: Option<f64>,
+    /// 最大字符数（`string`）。
+    pub max_length: Option<usize>,
+    /// 可选值（`enum`）。
+    #[serde(default)]
+    pub options: Vec<String>,
+}
+
+/// 插件设置 schema（`plugins_get_settings_schema`，取自当前版本清单）。
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PluginSettingsSchema {
+    pub plugin_id: String,
+    pub version: String,
+    pub fields: Vec<PluginSettingField>,
+}
+
+/// 插件设置的生效值（已存值覆盖默认值，只含 schema 中声明的 key）。
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PluginSettingsValues {
+    pub plugin_id: String,
+    pub version: String,
+    pub values: serde_json::Map<String, serde_json::Value>,
+}
+
+/// 插件设置变化事件载荷（`plugins-settings-changed`）。
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PluginSettingsChanged {
+    pub server_socket: String,
+    pub plugin_id: String,
+    /// 生效值发生变化的 key。
+    pub changed: Vec<String>,
+    /// 变化后的全部生效值。
+    pub values: serde_json::Map<String, serde_json::Value>,
+}
+
+/// 设置补丁未通过 schema 校验（整批拒绝，携带全部问题）。
+///
+/// # 说明
+/// 命令层通过 `downcast_ref` 识别该错误并映射为 `PLUGINS_SETTINGS_INVALID`。
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PluginSettingsInvalid {
+    pub plugin_id: String,
+    pub issues: Vec<PluginManifestIssue>,
+}
+
+impl std::fmt::Display for PluginSettingsInvalid {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Invalid settings for plugin {}", self.plugin_id)?;
+        for issue in &self.issues {
+            write!(f, "; {}: {}", issue.path, issue.message)?;
+        }
+        Ok(())
+    }
+}
+
+impl std::error::Error for PluginSettingsInvalid {}
+
+/// 插件因短时间内反复崩溃被自动停用（`plugin-auto-disabled` 事件载荷）。
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PluginAutoDisabled {
+    pub server_id: String,
+    pub plugin_id: String,
+    pub version: String,
+    /// 统计窗口内的崩溃次数。
+    pub crashes: u32,
+    /// 写入 `state.json` 的错误说明。
+    pub last_error: String,
+}
+
+/// 启动预加载中未能就绪的插件。
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PluginPreloadFailure {
+    pub plugin_id: String,
+    pub version: String,
+    pub error: String,
+    /// 插件是否因此被停用（正式安装的插件校验失败时停用，开发态插件只报告）。
+    pub disabled: bool,
+}
+
+/// 某个服务端下已启用插件的启动预加载结果（`plugin-runtime-ready` 事件载荷）。
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PluginRuntimeReady {
+    pub server_id: String,
+    /// 校验通过的插件运行时入口。
+    pub ready: Vec<PluginRuntimeEntry>,
+    pub failed: Vec<PluginPreloadFailure>,
+}
+
+/// 服务端必装插件（`/api/server` 的 `required_plugins`）的满足情况。
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PluginRequirementStatus {
+    /// 已安装、已启用且版本满足要求。
+    Satisfied,
+    /// 未安装。
+    Missing,
+    /// 当前版本低于要求的版本。
+    Outdated,
+    /// 版本满足，但未启用或处于失败态。
+    Disabled,
+    /// 目录中没有该插件，或目录版本也无法满足要求。
+    Unavailable,
+}
+
+/// 单个必装插件的检查结果。
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PluginRequirement {
+    pub plugin_id: String,
+    pub status: PluginRequirementStatus,
+    /// 要求的最低版本（服务端未声明时取目录版本）。
+    pub required_version: Option<String>,
+    /// 本地当前版本。
+    pub installed_version: Option<String>,
+    /// 目录中可安装的版本。
+    pub available_version: Option<String>,
+    /// 最近一次安装/启用失败的原因（仅 `plugins_install_requirements` 返回）。
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+/// 服务端必装插件检查报告。
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PluginRequirementsReport {
+    pub server_id: String,
+    /// 按服务端声明顺序排列。
+    pub requirements: Vec<PluginRequirement>,
+    /// 所有必装插件均为 `satisfied`。
+    pub satisfied: bool,
+}
+
+/// 必装插件安装进度阶段。
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PluginRequirementStage {
+    Installing,
+    Enabling,
+    Done,
+    Failed,
+}
+
+/// 必装插件安装进度（`plugins-requirements-progress` 事件载荷）。
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PluginRequirementProgress {
+    pub server_socket: String,
+    pub plugin_id: String,
+    /// 当前处理的插件序号（从 1 开始）与待处理总数。
+    pub index: u32,
+    pub total: u32,
+    pub stage: PluginRequirementStage,
+    pub error: Option<String>,
+}
+
+/// 插件总线消息（`plugins-bus-message` 事件载荷，也是后端宿主函数的入参）。
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PluginBusMessage {
+    pub server_socket: String,
+    pub topic: String,
+    /// 发布者插件 id。
+    pub from: String,
+    /// 订阅了该 topic 的插件 id（不含发布者；运行时只把消息交给这些插件）。
+    pub recipients: Vec<String>,
+    pub payload: serde_json::Value,
+}
+
+/// 插件总线访问被拒绝（未声明 `bus` 权限、插件未启用或 topic 越界）。
+///
+/// # 说明
+/// 命令层通过 `downcast_ref` 识别该错误并映射为 `PLUGINS_BUS_DENIED`。
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PluginBusDenied {
+    pub plugin_id: String,
+    pub reason: String,
+}
+
+impl std::fmt::Display for PluginBusDenied {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "Plugin bus access denied for {}: {}",
+            self.plugin_id, self.reason
+        )
+    }
+}
+
+impl std::error::Error for PluginBusDenied {}
+
+/// 插件包发布前自检结果（`plugins_validate_package`）。
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PluginPackageValidation {
+    /// 没有任何问题。
+    pub valid: bool,
+    /// 清单中的插件 id（清单不可读时为 `None`）。
+    pub plugin_id: Option<String>,
+    /// 清单中的插件版本（清单不可读时为 `None`）。
+    pub version: Option<String>,
+    pub issues: Vec<PluginManifestIssue>,
+}
+
+/// 某个 domain 的提供方。
+#[derive(Debug, Clone, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct PluginDomainProvider {
+    pub plugin_id: String,
+    /// 插件当前版本。
+    pub plugin_version: String,
+    /// 插件声明的 domain 版本。
+    pub domain_version: String,
+}
+
+/// domain 解析结果。
+#[derive(Debug, Clone, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct PluginDomainResolution {
+    pub domain: String,
+    /// 生效的提供方（无启用插件提供时为空）。
+    pub provider: Option<PluginDomainProvider>,
+    /// 所有声明该 domain 的启用插件（按优先级排序，首个即 `provider`）。
+    pub candidates: Vec<PluginDomainProvider>,
+    /// 是否存在多个插件同时提供该 domain。
+    pub conflict: bool,
+}
+
+/// domain 提供方变化事件载荷（`plugins-domain-provider-changed`）。
+#[derive(Debug, Clone, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct PluginDomainProviderChanged {
+    pub server_socket: String,
+    pub domain: String,
+    pub previous: Option<PluginDomainProvider>,
+    pub current: Option<PluginDomainProvider>,
+    pub conflict: bool,
+}
+
+/// 开发态插件文件变化事件载荷（`plugin-dev-reload`）。
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PluginDevReload {
+    pub server_id: String,
+    pub plugin_id: String,
+    /// 防抖窗口内变化的文件（相对源目录）。
+    pub paths: Vec<String>,
+}
+
+/// 旧版本清理结果。
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PluginPruneResult {
+    pub plugin_id: String,
+    /// 被删除的版本。
+    pub removed_versions: Vec<String>,
+    /// 释放的磁盘空间（bytes）。
+    pub freed_bytes: u64,
+}
@@ -2,38 +2,37 @@
 //!
 //! 约定：注释中文，日志英文（tracing）。
 
+use std::collections::HashMap;
+use std::path::PathBuf;
+
 use crate::features::plugins::domain::ports::plugin_install_store_port::PluginInstallStorePort;
-use crate::features::plugins::domain::ports::plugin_loader_port::PluginLoaderPort;
 use crate::features::plugins::domain::types::{
-    InstalledPluginState, PluginFetchResponse, PluginInstallFromUrlRequest, PluginLoadResult,
-    PluginManifest, PluginNetworkFetchRequest, PluginRuntimeEntry,
+    InstalledPluginState, PluginAsset, PluginAutoDisabled, PluginCatalogPage, PluginCatalogQuery,
+    PluginCompatibility, PluginDomainProviderChanged, PluginDomainResolution, PluginFetchResponse,
+    PluginInstallFromUrlRequest, PluginLocaleCatalog, PluginNetworkFetchRequest,
+    PluginPackageValidation, PluginPruneResult, PluginRequirement, PluginRequirementProgress,
+    PluginRequirementStage, PluginRequirementStatus, PluginRequirementsReport, PluginRuntimeEntry,
+    PluginRuntimeReady, PluginSettingsChanged, PluginSettingsSchema, PluginSettingsValues,
 };
+use crate::shared::events::{EventBus, EventBusExt};
 
-/// 加载并返回插件前端运行所需资源（wasm/js/html）。
-///
-/// # 参数
-/// - `manifest`：插件清单。
-///
-/// # 返回值
-/// - `Ok(PluginLoadResult)`：加载成功。
-/// - `Err(anyhow::Error)`：加载失败原因。
-pub async fn load_plugin(
-    manifest: PluginManifest,
-    plugin_loader_port: &dyn PluginLoaderPort,
-) -> anyhow::Result<PluginLoadResult> {
-    plugin_loader_port.load_plugin(manifest).await
-}
+/// domain 提供方变化事件名。
+pub const PLUGINS_DOMAIN_PROVIDER_CHANGED_EVENT: &str = "plugins-domain-provider-changed";
 
-/// 列出本地已保存的插件清单列表。
-///
-/// # 返回值
-/// - `Ok(Vec<PluginManifest>)`：清单列表。
-/// - `Err(anyhow::Error)`：读取失败原因。
-pub async fn list_plugins(
-    plugin_loader_port: &dyn PluginLoaderPort,
-) -> anyhow::Result<Vec<PluginManifest>> {
-    plugin_loader_port.list_plugins().await
-}
+/// 插件设置变化事件名。
+pub const PLUGINS_SETTINGS_CHANGED_EVENT: &str = "plugins-settings-changed";
+
+/// 插件总线消息事件名（前端运行时按 `recipients` 分发给订阅插件）。
+pub const PLUGINS_BUS_MESSAGE_EVENT: &str = "plugins-bus-message";
+
+/// 插件因反复崩溃被自动停用事件名。
+pub const PLUGIN_AUTO_DISABLED_EVENT: &str = "plugin-auto-disabled";
+
+/// 启动预加载完成事件名（每个有启用插件的服务端一条，含失败项）。
+pub const PLUGIN_RUNTIME_READY_EVENT: &str = "plugin-runtime-ready";
+
+/// 必装插件安装进度事件名。
+pub const PLUGINS_REQUIREMENTS_PROGRESS_EVENT: &str = "plugins-requirements-progress";
 
 /// 查询服务端已安装插件列表（含当前版本/启用态/错误等状态）。
 pub async fn plugins_list_installed(
@@ -60,8 +59,9 @@ pub async fn plugins_get_installed_state(
         .await
 }
 
-/// 获取插件“当前版本”的运行时入口信息。
+/// 获取插件“当前版本”的运行时入口信息（同时允许该 webview 上报一次加载崩溃）。
 pub async fn plugins_get_runtime_entry(
+    webview: &str,
     server_socket: &str,
     plugin_id: &str,
     tls_policy: Option<&str>,
@@ -69,7 +69,13 @@ pub async fn plugins_get_runtime_entry(
     plugin_store_port: &dyn PluginInstallStorePort,
 ) -> anyhow::Result<PluginRuntimeEntry> {
     plugin_store_port
-        .get_runtime_entry(server_socket, plugin_id, tls_policy, tls_fingerprint)
+        .get_runtime_entry(
+            webview,
+            server_socket,
+            plugin_id,
+            tls_policy,
+            tls_fingerprint,
+        )
         .await
 }
 
@@ -93,11 +99,447 @@ pub async fn plugins_get_runtime_entry_for_version(
         .await
 }
 
+/// 浏览服务端插件目录（搜索 + 分页）。
+pub async fn plugins_browse_catalog(
+    request: PluginCatalogQuery<'_>,
+    plugin_store_port: &dyn PluginInstallStorePort,
+) -> anyhow::Result<PluginCatalogPage> {
+    plugin_store_port.browse_catalog(request).await
+}
+
+/// 列出插件已预取的展示资源。
+pub async fn plugins_get_assets(
+    server_socket: &str,
+    plugin_id: &str,
+    version: Option<&str>,
+    tls_policy: Option<&str>,
+    tls_fingerprint: Option<&str>,
+    plugin_store_port: &dyn PluginInstallStorePort,
+) -> anyhow::Result<Vec<PluginAsset>> {
+    plugin_store_port
+        .get_assets(
+            server_socket,
+            plugin_id,
+            version,
+            tls_policy,
+            tls_fingerprint,
+        )
+        .await
+}
+
+/// 获取插件当前版本的语言资源（带回退）。
+pub async fn plugins_get_locale(
+    server_socket: &str,
+    plugin_id: &str,
+    lang: &str,
+    tls_policy: Option<&str>,
+    tls_fingerprint: Option<&str>,
+    plugin_store_port: &dyn PluginInstallStorePort,
+) -> anyhow::Result<PluginLocaleCatalog> {
+    plugin_store_port
+        .get_locale(server_socket, plugin_id, lang, tls_policy, tls_fingerprint)
+        .await
+}
+
+/// 获取插件设置 schema。
+pub async fn plugins_get_settings_schema(
+    server_socket: &str,
+    plugin_id: &str,
+    tls_policy: Option<&str>,
+    tls_fingerprint: Option<&str>,
+    plugin_store_port: &dyn PluginInstallStorePort,
+) -> anyhow::Result<PluginSettingsSchema> {
+    plugin_store_port
+        .get_settings_schema(server_socket, plugin_id, tls_policy, tls_fingerprint)
+        .await
+}
+
+/// 获取插件设置的生效值。
+pub async fn plugins_get_settings(
+    server_socket: &str,
+    plugin_id: &str,
+    tls_policy: Option<&str>,
+    tls_fingerprint: Option<&str>,
+    plugin_store_port: &dyn PluginInstallStorePort,
+) -> anyhow::Result<PluginSettingsValues> {
+    plugin_store_port
+        .get_settings(server_socket, plugin_id, tls_policy, tls_fingerprint)
+        .await
+}
+
+/// 更新插件设置；生效值有变化时发出 [`PLUGINS_SETTINGS_CHANGED_EVENT`]。
+pub async fn plugins_update_settings(
+    server_socket: &str,
+    plugin_id: &str,
+    patch: &serde_json::Map<String, serde_json::Value>,
+    tls_policy: Option<&str>,
+    tls_fingerprint: Option<&str>,
+    plugin_store_port: &dyn PluginInstallStorePort,
+    events: &dyn EventBus,
+) -> anyhow::Result<PluginSettingsValues> {
+    let (values, changed) = plugin_store_port
+        .update_settings(server_socket, plugin_id, patch, tls_policy, tls_fingerprint)
+        .await?;
+    if !changed.is_empty() {
+        events.emit(
+            PLUGINS_SETTINGS_CHANGED_EVENT,
+            PluginSettingsChanged {
+                server_socket: server_socket.to_string(),
+                plugin_id: plugin_id.to_string(),
+                changed,
+                values: values.values.clone(),
+            },
+        );
+    }
+    Ok(values)
+}
+
+/// 为宿主桥接打开一个插件总线会话，返回会话令牌。
+///
+/// 发布者/订阅者身份由令牌在后端解析，调用方不再自报插件 id。
+pub async fn plugins_bus_open(
+    webview: &str,
+    server_socket: &str,
+    plugin_id: &str,
+    tls_policy: Option<&str>,
+    tls_fingerprint: Option<&str>,
+    plugin_store_port: &dyn PluginInstallStorePort,
+) -> anyhow::Result<String> {
+    plugin_store_port
+        .bus_open(
+            webview,
+            server_socket,
+            plugin_id,
+            tls_policy,
+            tls_fingerprint,
+        )
+        .await
+}
+
+/// 向插件总线发布消息；有收件人时发出 [`PLUGINS_BUS_MESSAGE_EVENT`]，返回收件插件 id。
+pub async fn plugins_bus_publish(
+    webview: &str,
+    server_socket: &str,
+    bus_token: &str,
+    topic: &str,
+    payload: serde_json::Value,
+    plugin_store_port: &dyn PluginInstallStorePort,
+    events: &dyn EventBus,
+) -> anyhow::Result<Vec<String>> {
+    let message = plugin_store_port
+        .bus_publish(webview, server_socket, bus_token, topic, payload)
+        .await?;
+    let recipients = message.recipients.clone();
+    if !recipients.is_empty() {
+        events.emit(PLUGINS_BUS_MESSAGE_EVENT, message);
+    }
+    Ok(recipients)
+}
+
+/// 登记插件总线订阅。
+pub async fn plugins_bus_subscribe(
+    webview: &str,
+    bus_token: &str,
+    topic: &str,
+    plugin_store_port: &dyn PluginInstallStorePort,
+) -> anyhow::Result<()> {
+    plugin_store_port
+        .bus_subscribe(webview, bus_token, topic)
+        .await
+}
+
+/// 取消插件总线订阅（`topic` 为空时关闭会话并清空该插件的全部订阅）。
+pub async fn plugins_bus_unsubscribe(
+    webview: &str,
+    bus_token: &str,
+    topic: Option<&str>,
+    plugin_store_port: &dyn PluginInstallStorePort,
+) -> anyhow::Result<()> {
+    plugin_store_port
+        .bus_unsubscribe(webview, bus_token, topic)
+        .await
+}
+
+/// 记录一次插件崩溃；达到阈值被自动停用时发出 [`PLUGIN_AUTO_DISABLED_EVENT`] 并刷新 domain 注册表。
+pub async fn plugins_report_crash(
+    webview: &str,
+    server_socket: &str,
+    plugin_id: &str,
+    message: &str,
+    tls_policy: Option<&str>,
+    tls_fingerprint: Option<&str>,
+    plugin_store_port: &dyn PluginInstallStorePort,
+    events: &dyn EventBus,
+) -> anyhow::Result<Option<PluginAutoDisabled>> {
+    let disabled = plugin_store_port
+        .report_crash(
+            webview,
+            server_socket,
+            plugin_id,
+            message,
+            tls_policy,
+            tls_fingerprint,
+        )
+        .await?;
+    notify_auto_disabled(
+        server_socket,
+        disabled.iter().cloned().collect(),
+        tls_policy,
+        tls_fingerprint,
+        plugin_store_port,
+        events,
+    )
+    .await;
+    Ok(disabled)
+}
+
+/// 发出自动停用事件；停用改变了启用集合，随后刷新 domain 注册表。
+async fn notify_auto_disabled(
+    server_socket: &str,
+    disabled: Vec<PluginAutoDisabled>,
+    tls_policy: Option<&str>,
+    tls_fingerprint: Option<&str>,
+    plugin_store_port: &dyn PluginInstallStorePort,
+    events: &dyn EventBus,
+) {
+    if disabled.is_empty() {
+        return;
+    }
+    for info in disabled {
+        events.emit(PLUGIN_AUTO_DISABLED_EVENT, info);
+    }
+    plugins_sync_domain_registry(
+        server_socket,
+        tls_policy,
+        tls_fingerprint,
+        plugin_store_port,
+        events,
+    )
+    .await;
+}
+
+/// 检查插件与当前宿主版本的兼容性。
+pub async fn plugins_check_compatibility(
+    server_socket: &str,
+    plugin_id: &str,
+    version: Option<&str>,
+    tls_policy: Option<&str>,
+    tls_fingerprint: Option<&str>,
+    plugin_store_port: &dyn PluginInstallStorePort,
+) -> anyhow::Result<PluginCompatibility> {
+    plugin_store_port
+        .check_compatibility(
+            server_socket,
+            plugin_id,
+            version,
+            tls_policy,
+            tls_fingerprint,
+        )
+        .await
+}
+
+/// 检查服务端声明的必装插件。
+pub async fn plugins_check_requirements(
+    server_socket: &str,
+    tls_policy: Option<&str>,
+    tls_fingerprint: Option<&str>,
+    plugin_store_port: &dyn PluginInstallStorePort,
+) -> anyhow::Result<PluginRequirementsReport> {
+    plugin_store_port
+        .check_requirements(server_socket, tls_policy, tls_fingerprint)
+        .await
+}
+
+/// 让单个必装插件达到 `satisfied`：缺失/过旧时安装目录版本并切换为当前版本，然后启用。
+async fn install_requirement(
+    server_socket: &str,
+    requirement: &PluginRequirement,
+    confirm_untrusted: bool,
+    tls_policy: Option<&str>,
+    tls_fingerprint: Option<&str>,
+    plugin_store_port: &dyn PluginInstallStorePort,
+    on_stage: &dyn Fn(PluginRequirementStage),
+) -> anyhow::Result<()> {
+    let plugin_id = requirement.plugin_id.as_str();
+    match requirement.status {
+        PluginRequirementStatus::Satisfied => return Ok(()),
+        PluginRequirementStatus::Unavailable => {
+            return Err(anyhow::anyhow!(
+                "Required plugin is not available in catalog: {}",
+                plugin_id
+            ));
+        }
+        PluginRequirementStatus::Missing | PluginRequirementStatus::Outdated => {
+            let version = requirement.available_version.as_deref();
+            on_stage(PluginRequirementStage::Installing);
+            plugin_store_port
+                .install_from_server_catalog(
+                    server_socket,
+                    plugin_id,
+                    version,
+                    confirm_untrusted,
+                    tls_policy,
+                    tls_fingerprint,
+                )
+                .await?;
+            if let Some(version) = version {
+                plugin_store_port
+                    .switch_version(
+                        server_socket,
+                        plugin_id,
+                        version,
+                        tls_policy,
+                        tls_fingerprint,
+                    )
+                    .await?;
+            }
+        }
+        PluginRequirementStatus::Disabled => {}
+    }
+    on_stage(PluginRequirementStage::Enabling);
+    plugin_store_port
+        .enable(server_socket, plugin_id, tls_policy, tls_fingerprint)
+        .await?;
+    Ok(())
+}
+
+/// 一次性安装并启用所有未满足的必装插件，逐个发出 [`PLUGINS_REQUIREMENTS_PROGRESS_EVENT`]。
+///
+/// 单个插件失败不会中断其余插件；返回安装后重新检查的报告，失败原因写入对应条目的 `error`。
+pub async fn plugins_install_requirements(
+    server_socket: &str,
+    confirm_untrusted: bool,
+    tls_policy: Option<&str>,
+    tls_fingerprint: Option<&str>,
+    plugin_store_port: &dyn PluginInstallStorePort,
+    events: &dyn EventBus,
+) -> anyhow::Result<PluginRequirementsReport> {
+    let report = plugin_store_port
+        .check_requirements(server_socket, tls_policy, tls_fingerprint)
+        .await?;
+    let pending: Vec<&PluginRequirement> = report
+        .requirements
+        .iter()
+        .filter(|r| r.status != PluginRequirementStatus::Satisfied)
+        .collect();
+    if pending.is_empty() {
+        return Ok(report);
+    }
+
+    let total = pending.len() as u32;
+    let mut errors: HashMap<String, String> = HashMap::new();
+    for (index, requirement) in pending.into_iter().enumerate() {
+        let emit = |stage: PluginRequirementStage, error: Option<String>| {
+            events.emit(
+                PLUGINS_REQUIREMENTS_PROGRESS_EVENT,
+                PluginRequirementProgress {
+                    server_socket: server_socket.to_string(),
+                    plugin_id: requirement.plugin_id.clone(),
+                    index: index as u32 + 1,
+                    total,
+                    stage,
+                    error,
+                },
+            );
+        };
+        let result = install_requirement(
+            server_socket,
+            requirement,
+            confirm_untrusted,
+            tls_policy,
+            tls_fingerprint,
+            plugin_store_port,
+            &|stage| emit(stage, None),
+        )
+        .await;
+        match result {
+            Ok(()) => emit(PluginRequirementStage::Done, None),
+            Err(e) => {
+                let message = e.to_string();
+                tracing::warn!(action = "plugins_requirement_install_failed", plugin_id = %requirement.plugin_id, error = %message);
+                emit(PluginRequirementStage::Failed, Some(message.clone()));
+                errors.insert(requirement.plugin_id.clone(), message);
+            }
+        }
+    }
+
+    plugins_sync_domain_registry(
+        server_socket,
+        tls_policy,
+        tls_fingerprint,
+        plugin_store_port,
+        events,
+    )
+    .await;
+    let mut report = plugin_store_port
+        .check_requirements(server_socket, tls_policy, tls_fingerprint)
+        .await?;
+    for requirement in &mut report.requirements {
+        requirement.error = errors.remove(&requirement.plugin_id);
+    }
+    Ok(report)
+}
+
+/// 解析 domain 的提供方插件。
+pub async fn plugins_resolve_domain(
+    server_socket: &str,
+    domain: &str,
+    tls_policy: Option<&str>,
+    tls_fingerprint: Option<&str>,
+    plugin_store_port: &dyn PluginInstallStorePort,
+) -> anyhow::Result<PluginDomainResolution> {
+    plugin_store_port
+        .resolve_domain(server_socket, domain, tls_policy, tls_fingerprint)
+        .await
+}
+
+/// 刷新 domain 注册表，返回提供方变化列表。
+pub async fn plugins_refresh_domains(
+    server_socket: &str,
+    tls_policy: Option<&str>,
+    tls_fingerprint: Option<&str>,
+    plugin_store_port: &dyn PluginInstallStorePort,
+) -> anyhow::Result<Vec<PluginDomainProviderChanged>> {
+    plugin_store_port
+        .refresh_domains(server_socket, tls_policy, tls_fingerprint)
+        .await
+}
+
+/// 启用状态变化后刷新 domain 注册表，并逐条发出提供方变化事件。
+///
+/// 刷新失败只记录日志：注册表是派生数据，不应让已成功的启用/禁用操作返回错误。
+pub async fn plugins_sync_domain_registry(
+    server_socket: &str,
+    tls_policy: Option<&str>,
+    tls_fingerprint: Option<&str>,
+    plugin_store_port: &dyn PluginInstallStorePort,
+    events: &dyn EventBus,
+) {
+    let changes = match plugins_refresh_domains(
+        server_socket,
+        tls_policy,
+        tls_fingerprint,
+        plugin_store_port,
+    )
+    .await
+    {
+        Ok(changes) => changes,
+        Err(e) => {
+            tracing::warn!(action = "plugins_domain_registry_refresh_failed", server_socket = %server_socket, error = %e);
+            return;
+        }
+    };
+    for change in changes {
+        events.emit(PLUGINS_DOMAIN_PROVIDER_CHANGED_EVENT, change);
+    }
+}
+
 /// 从服务端目录安装插件。
 pub async fn plugins_install_from_server_catalog(
     server_socket: &str,
     plugin_id: &str,
     version: Option<&str>,
+    confirm_untrusted: bool,
     tls_policy: Option<&str>,
     tls_fingerprint: Option<&str>,
     plugin_store_port: &dyn PluginInstallStorePort,
@@ -107,6 +549,7 @@ pub async fn plugins_install_from_server_catalog(
             server_socket,
             plugin_id,
             version,
+            confirm_untrusted,
             tls_policy,
             tls_fingerprint,
         )
@@ -185,6 +628,7 @@ pub async fn plugins_set_failed(
     server_socket: &str,
     plugin_id: &str,
     message: &str,
+    auto_rollback_after: u32,
     tls_policy: Option<&str>,
     tls_fingerprint: Option<&str>,
     plugin_store_port: &dyn PluginInstallStorePort,
@@ -194,12 +638,107 @@ pub async fn plugins_set_failed(
             server_socket,
             plugin_id,
             message,
+            auto_rollback_after,
+            tls_policy,
+            tls_fingerprint,
+        )
+        .await
+}
+
+/// 回滚到最近一个 known-good 版本。
+pub async fn plugins_rollback(
+    server_socket: &str,
+    plugin_id: &str,
+    tls_policy: Option<&str>,
+    tls_fingerprint: Option<&str>,
+    plugin_store_port: &dyn PluginInstallStorePort,
+) -> anyhow::Result<InstalledPluginState> {
+    plugin_store_port
+        .rollback(server_socket, plugin_id, tls_policy, tls_fingerprint)
+        .await
+}
+
+/// 清理插件旧版本。
+pub async fn plugins_prune_versions(
+    server_socket: &str,
+    plugin_id: &str,
+    keep_latest_n: u32,
+    tls_policy: Option<&str>,
+    tls_fingerprint: Option<&str>,
+    plugin_store_port: &dyn PluginInstallStorePort,
+) -> anyhow::Result<PluginPruneResult> {
+    plugin_store_port
+        .prune_versions(
+            server_socket,
+            plugin_id,
+            keep_latest_n,
             tls_policy,
             tls_fingerprint,
         )
         .await
 }
 
+/// 清理本地所有插件的旧版本。
+pub async fn plugins_prune_all_versions(
+    keep_latest_n: u32,
+    plugin_store_port: &dyn PluginInstallStorePort,
+) -> anyhow::Result<Vec<PluginPruneResult>> {
+    plugin_store_port.prune_all_versions(keep_latest_n).await
+}
+
+/// 链接本地目录为开发态插件。
+pub async fn plugins_dev_link(
+    server_id: &str,
+    plugin_id: &str,
+    path: &str,
+    plugin_store_port: &dyn PluginInstallStorePort,
+) -> anyhow::Result<(InstalledPluginState, PathBuf)> {
+    plugin_store_port.dev_link(server_id, plugin_id, path).await
+}
+
+/// 取消开发态插件链接。
+pub async fn plugins_dev_unlink(
+    server_id: &str,
+    plugin_id: &str,
+    plugin_store_port: &dyn PluginInstallStorePort,
+) -> anyhow::Result<Option<InstalledPluginState>> {
+    plugin_store_port.dev_unlink(server_id, plugin_id).await
+}
+
+/// 列出本地所有开发态插件链接。
+pub async fn plugins_list_dev_links(
+    plugin_store_port: &dyn PluginInstallStorePort,
+) -> anyhow::Result<Vec<(String, String, PathBuf)>> {
+    plugin_store_port.list_dev_links().await
+}
+
+/// 导入旧加载器遗留的插件数据（一次性迁移）。
+pub async fn plugins_import_legacy(
+    plugin_store_port: &dyn PluginInstallStorePort,
+) -> anyhow::Result<usize> {
+    plugin_store_port.import_legacy().await
+}
+
+/// 启动预加载已启用插件，并按服务端发出 [`PLUGIN_RUNTIME_READY_EVENT`]。
+pub async fn plugins_preload_enabled(
+    plugin_store_port: &dyn PluginInstallStorePort,
+    events: &dyn EventBus,
+) -> anyhow::Result<Vec<PluginRuntimeReady>> {
+    let results = plugin_store_port.preload_enabled().await?;
+    for result in &results {
+        events.emit(PLUGIN_RUNTIME_READY_EVENT, result.clone());
+    }
+    Ok(results)
+}
+
+/// 发布前自检插件包（zip 文件或插件目录）。
+pub async fn plugins_validate_package(
+    path: &str,
+    plugin_store_port: &dyn PluginInstallStorePort,
+) -> anyhow::Result<PluginPackageValidation> {
+    plugin_store_port.validate_package(path).await
+}
+
 /// 清除插件失败态。
 pub async fn plugins_clear_error(
     server_socket: &str,
@@ -213,6 +752,26 @@ pub async fn plugins_clear_error(
         .await
 }
 
+/// 记录插件运行时加载成功（known-good）。
+pub async fn plugins_mark_loaded(
+    server_socket: &str,
+    plugin_id: &str,
+    version: &str,
+    tls_policy: Option<&str>,
+    tls_fingerprint: Option<&str>,
+    plugin_store_port: &dyn PluginInstallStorePort,
+) -> anyhow::Result<()> {
+    plugin_store_port
+        .mark_loaded(
+            server_socket,
+            plugin_id,
+            version,
+            tls_policy,
+            tls_fingerprint,
+        )
+        .await
+}
+
 /// 读取插件 KV 存储。
 pub async fn plugins_storage_get(
     server_socket: &str,
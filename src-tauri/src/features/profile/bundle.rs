@@ -0,0 +1,273 @@
+//! profile｜档案 zip 的读写（同步 IO，调用方应放在 blocking 线程中执行）。
+//!
+//! 约定：注释中文，日志英文（tracing）。
+
+use std::io::{Read, Write};
+use std::path::Path;
+
+use anyhow::Context;
+use serde::Serialize;
+use serde::de::DeserializeOwned;
+use zip::ZipArchive;
+use zip::write::SimpleFileOptions;
+
+use super::{PROFILE_FORMAT, PROFILE_VERSION, ProfileBundle, ProfileManifest};
+
+const MANIFEST_ENTRY: &str = "profile.json";
+const SECTION_SETTINGS: &str = "settings";
+const SECTION_SERVERS: &str = "servers";
+const SECTION_CERTIFICATES: &str = "certificates";
+const SECTION_PLUGINS: &str = "plugins";
+const SECTION_MUTE_RULES: &str = "mute_rules";
+
+/// 单个条目解压后的最大字节数（档案只含 JSON，正常远小于该值）。
+const MAX_ENTRY_BYTES: u64 = 16 * 1024 * 1024;
+
+fn section_entry(section: &str) -> String {
+    format!("{section}.json")
+}
+
+fn write_json<W: Write + std::io::Seek, T: Serialize>(
+    zip: &mut zip::ZipWriter<W>,
+    name: &str,
+    value: &T,
+) -> anyhow::Result<()> {
+    zip.start_file(name, SimpleFileOptions::default())
+        .with_context(|| format!("Failed to start profile entry: {name}"))?;
+    let json = serde_json::to_vec_pretty(value)?;
+    zip.write_all(&json)
+        .with_context(|| format!("Failed to write profile entry: {name}"))?;
+    Ok(())
+}
+
+fn read_json<R: Read + std::io::Seek, T: DeserializeOwned>(
+    zip: &mut ZipArchive<R>,
+    name: &str,
+) -> anyhow::Result<Option<T>> {
+    let entry = match zip.by_name(name) {
+        Ok(entry) => entry,
+        Err(zip::result::ZipError::FileNotFound) => return Ok(None),
+        Err(e) => return Err(e).with_context(|| format!("Failed to open profile entry: {name}")),
+    };
+    let mut buf = Vec::new();
+    entry
+        .take(MAX_ENTRY_BYTES + 1)
+        .read_to_end(&mut buf)
+        .with_context(|| format!("Failed to read profile entry: {name}"))?;
+    if buf.len() as u64 > MAX_ENTRY_BYTES {
+        return Err(anyhow::anyhow!("Profile entry too large: {name}"));
+    }
+    let value =
+        serde_json::from_slice(&buf).with_context(|| format!("Invalid profile entry: {name}"))?;
+    Ok(Some(value))
+}
+
+fn put_section<W: Write + std::io::Seek, T: Serialize>(
+    zip: &mut zip::ZipWriter<W>,
+    sections: &mut Vec<String>,
+    name: &str,
+    value: Option<&T>,
+) -> anyhow::Result<()> {
+    if let Some(value) = value {
+        write_json(zip, &section_entry(name), value)?;
+        sections.push(name.to_string());
+    }
+    Ok(())
+}
+
+/// 写出档案；`manifest.sections` 按实际写入的分区重新生成。
+///
+/// 先写入同目录临时文件再重命名，避免失败时留下半个档案。
+pub fn write_bundle(dest: &Path, bundle: &ProfileBundle) -> anyhow::Result<ProfileManifest> {
+    let tmp = dest.with_extension("tmp");
+    let file = std::fs::File::create(&tmp)
+        .with_context(|| format!("Failed to create profile file: {}", tmp.display()))?;
+    let mut zip = zip::ZipWriter::new(file);
+
+    let mut sections = Vec::new();
+    put_section(
+        &mut zip,
+        &mut sections,
+        SECTION_SETTINGS,
+        bundle.settings.as_ref(),
+    )?;
+    put_section(
+        &mut zip,
+        &mut sections,
+        SECTION_SERVERS,
+        bundle.servers.as_ref(),
+    )?;
+    put_section(
+        &mut zip,
+        &mut sections,
+        SECTION_CERTIFICATES,
+        bundle.certificates.as_ref(),
+    )?;
+    put_section(
+        &mut zip,
+        &mut sections,
+        SECTION_PLUGINS,
+        bundle.plugins.as_ref(),
+    )?;
+    put_section(
+        &mut zip,
+        &mut sections,
+        SECTION_MUTE_RULES,
+        bundle.mute_rules.as_ref(),
+    )?;
+
+    let manifest = ProfileManifest {
+        sections,
+        ..bundle.manifest.clone()
+    };
+    write_json(&mut zip, MANIFEST_ENTRY, &manifest)?;
+    zip.finish().context("Failed to finish profile archive")?;
+
+    std::fs::rename(&tmp, dest)
+        .with_context(|| format!("Failed to move profile into place: {}", dest.display()))?;
+    Ok(manifest)
+}
+
+/// 读取档案并校验格式与版本；未在 `sections` 中声明的分区忽略。
+pub fn read_bundle(path: &Path) -> anyhow::Result<ProfileBundle> {
+    let file = std::fs::File::open(path)
+        .with_context(|| format!("Failed to open profile file: {}", path.display()))?;
+    let mut zip = ZipArchive::new(file).context("Profile is not a valid archive")?;
+
+    let manifest: ProfileManifest =
+        read_json(&mut zip, MANIFEST_ENTRY)?.context("Profile manifest is missing")?;
+    if manifest.format != PROFILE_FORMAT {
+        return Err(anyhow::anyhow!(
+            "Unsupported profile format: {}",
+            manifest.format
+        ));
+    }
+    if manifest.version == 0 || manifest.version > PROFILE_VERSION {
+        return Err(anyhow::anyhow!(
+            "Unsupported profile version: {}",
+            manifest.version
+        ));
+    }
+
+    let has = |name: &str| manifest.sections.iter().any(|s| s == name);
+    let settings = if has(SECTION_SETTINGS) {
+        read_json(&mut zip, &section_entry(SECTION_SETTINGS))?
+    } else {
+        None
+    };
+    let servers = if has(SECTION_SERVERS) {
+        read_json(&mut zip, &section_entry(SECTION_SERVERS))?
+    } else {
+        None
+    };
+    let certificates = if has(SECTION_CERTIFICATES) {
+        read_json(&mut zip, &section_entry(SECTION_CERTIFICATES))?
+    } else {
+        None
+    };
+    let plugins = if has(SECTION_PLUGINS) {
+        read_json(&mut zip, &section_entry(SECTION_PLUGINS))?
+    } else {
+        None
+    };
+    let mute_rules = if has(SECTION_MUTE_RULES) {
+        read_json(&mut zip, &section_entry(SECTION_MUTE_RULES))?
+    } else {
+        None
+    };
+
+    Ok(ProfileBundle {
+        manifest,
+        settings,
+        servers,
+        certificates,
+        plugins,
+        mute_rules,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::features::profile::{PinnedCertificate, ProfilePlugin};
+    use crate::shared::mute_rules::NewMuteRule;
+
+    fn temp_path(name: &str) -> std::path::PathBuf {
+        let nanos = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .expect("time")
+            .as_nanos();
+        std::env::temp_dir().join(format!("carrypigeon-profile-{name}-{nanos}.zip"))
+    }
+
+    fn manifest() -> ProfileManifest {
+        ProfileManifest {
+            format: PROFILE_FORMAT.to_string(),
+            version: PROFILE_VERSION,
+            created_at: 1,
+            app_version: "0.0.0".to_string(),
+            sections: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn bundle_round_trips_included_sections_only() {
+        let path = temp_path("roundtrip");
+        let bundle = ProfileBundle {
+            manifest: manifest(),
+            settings: None,
+            servers: None,
+            certificates: Some(vec![PinnedCertificate {
+                server_socket: "tls://chat.example.com:443".to_string(),
+                tls_policy: Some("strict".to_string()),
+                tls_fingerprint: "ab".repeat(32),
+            }]),
+            plugins: Some(vec![ProfilePlugin {
+                server_id: "srv".to_string(),
+                plugin_id: "demo".to_string(),
+                current_version: Some("1.0.0".to_string()),
+                enabled: true,
+            }]),
+            mute_rules: Some(vec![NewMuteRule {
+                pattern: "spoiler".to_string(),
+                is_regex: false,
+                case_sensitive: false,
+            }]),
+        };
+
+        let written = write_bundle(&path, &bundle).expect("write");
+        assert_eq!(
+            written.sections,
+            vec!["certificates", "plugins", "mute_rules"]
+        );
+
+        let read = read_bundle(&path).expect("read");
+        assert_eq!(read.manifest, written);
+        assert!(read.settings.is_none());
+        assert!(read.servers.is_none());
+        assert_eq!(read.certificates, bundle.certificates);
+        assert_eq!(read.plugins, bundle.plugins);
+        assert_eq!(read.mute_rules, bundle.mute_rules);
+        let _ = std::fs::remove_file(&path);
+    }
+
+    #[test]
+    fn newer_profile_versions_are_rejected() {
+        let path = temp_path("version");
+        let bundle = ProfileBundle {
+            manifest: ProfileManifest {
+                version: PROFILE_VERSION + 1,
+                ..manifest()
+            },
+            settings: None,
+            servers: None,
+            certificates: None,
+            plugins: None,
+            mute_rules: None,
+        };
+        write_bundle(&path, &bundle).expect("write");
+        let err = read_bundle(&path).expect_err("should reject");
+        assert!(err.to_string().contains("Unsupported profile version"));
+        let _ = std::fs::remove_file(&path);
+    }
+}
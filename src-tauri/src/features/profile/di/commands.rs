@@ -0,0 +1,223 @@
+//! profile｜DI/命令入口：commands。
+//!
+//! 约定：注释中文，日志英文（tracing）。
+
+use std::path::PathBuf;
+
+use crate::features::plugins::data::plugin_store;
+use crate::features::profile::{
+    PROFILE_FORMAT, PROFILE_VERSION, ProfileBundle, ProfileClientState, ProfileImportReport,
+    ProfileInclude, ProfileManifest, ProfilePlugin, ProfileServers, bundle, sanitize_envelope,
+};
+use crate::features::settings::data::config_store_port_adapter::ConfigStorePortAdapter;
+use crate::features::settings::domain::settings_schema::{
+    SettingsImportEnvelopeV1, parse_settings_import_envelope,
+};
+use crate::features::settings::usecases::config_usecases;
+use crate::shared::error::{CommandResult, command_error, to_command_error};
+use crate::shared::mute_rules;
+
+fn now_ms() -> i64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_millis() as i64
+}
+
+fn checked_path(raw: &str) -> CommandResult<PathBuf> {
+    let trimmed = raw.trim();
+    if trimmed.is_empty() {
+        return Err(command_error(
+            "PROFILE_PATH_REQUIRED",
+            "error.profile_path_required",
+        ));
+    }
+    Ok(PathBuf::from(trimmed))
+}
+
+async fn current_envelope() -> anyhow::Result<SettingsImportEnvelopeV1> {
+    let raw = config_usecases::export_settings(ConfigStorePortAdapter::shared()).await?;
+    parse_settings_import_envelope(&raw)
+}
+
+/// 按 `include` 收集各分区内容。
+async fn collect_bundle(
+    include: ProfileInclude,
+    client: ProfileClientState,
+) -> anyhow::Result<ProfileBundle> {
+    let envelope = if include.settings || include.servers {
+        Some(current_envelope().await?)
+    } else {
+        None
+    };
+    let servers = match (&envelope, include.servers) {
+        (Some(envelope), true) => Some(ProfileServers {
+            server_list: envelope.backend.server_list.clone(),
+            server_racks: client.server_racks,
+        }),
+        _ => None,
+    };
+    let settings = envelope.filter(|_| include.settings).map(sanitize_envelope);
+
+    let plugins = if include.plugins {
+        let installed = plugin_store::list_all_installed().await?;
+        Some(
+            installed
+                .into_iter()
+                // 开发态插件指向本机源目录，迁移到其它机器没有意义。
+                .filter(|(_, state)| !state.dev)
+                .map(|(server_id, state)| ProfilePlugin {
+                    server_id,
+                    plugin_id: state.plugin_id,
+                    current_version: state.current_version,
+                    enabled: state.enabled,
+                })
+                .collect(),
+        )
+    } else {
+        None
+    };
+
+    let mute_rules = if include.mute_rules {
+        Some(
+            mute_rules::list()
+                .await?
+                .into_iter()
+                .map(|rule| mute_rules::NewMuteRule {
+                    pattern: rule.pattern,
+                    is_regex: rule.is_regex,
+                    case_sensitive: rule.case_sensitive,
+                })
+                .collect(),
+        )
+    } else {
+        None
+    };
+
+    Ok(ProfileBundle {
+        manifest: ProfileManifest {
+            format: PROFILE_FORMAT.to_string(),
+            version: PROFILE_VERSION,
+            created_at: now_ms(),
+            app_version: env!("CARGO_PKG_VERSION").to_string(),
+            sections: Vec::new(),
+        },
+        settings,
+        servers,
+        certificates: include.certificates.then_some(client.pinned_certificates),
+        plugins,
+        mute_rules,
+    })
+}
+
+/// 导出配置档案（zip）。
+///
+/// # 参数
+/// - `dest_path`：目标文件路径（已存在时覆盖）。
+/// - `include`：包含的分区（缺省时全部包含）。
+/// - `client`：前端持有的服务器机架与固定证书。
+///
+/// # 返回值
+/// - `Ok(ProfileManifest)`：写入的档案清单（含实际分区列表）。
+/// - `Err(CommandError)`：路径为空、读取本地状态或写文件失败。
+#[tauri::command]
+pub async fn export_profile(
+    dest_path: String,
+    include: Option<ProfileInclude>,
+    client: Option<ProfileClientState>,
+) -> CommandResult<ProfileManifest> {
+    let dest = checked_path(&dest_path)?;
+    let map_err = |e: anyhow::Error| {
+        to_command_error("PROFILE_EXPORT_FAILED", "error.profile_export_failed", e)
+    };
+    let profile = collect_bundle(include.unwrap_or_default(), client.unwrap_or_default())
+        .await
+        .map_err(map_err)?;
+    let manifest =
+        tauri::async_runtime::spawn_blocking(move || bundle::write_bundle(&dest, &profile))
+            .await
+            .map_err(|e| map_err(e.into()))?
+            .map_err(map_err)?;
+    tracing::info!(action = "settings_profile_exported", sections = ?manifest.sections);
+    Ok(manifest)
+}
+
+/// 导入配置档案。
+///
+/// # 说明
+/// - 设置与服务器列表写回配置文件；档案只含其一时另一部分保持本机现值；
+/// - 静音规则按（模式, 正则, 大小写）去重后追加；
+/// - 服务器机架、固定证书与插件列表通过返回值交给前端处理。
+///
+/// # 参数
+/// - `path`：档案文件路径。
+///
+/// # 返回值
+/// - `Ok(ProfileImportReport)`：各分区的导入结果。
+/// - `Err(CommandError)`：档案无效、版本不支持或写入失败。
+#[tauri::command]
+pub async fn import_profile(path: String) -> CommandResult<ProfileImportReport> {
+    let path = checked_path(&path)?;
+    let map_err = |e: anyhow::Error| {
+        to_command_error("PROFILE_IMPORT_FAILED", "error.profile_import_failed", e)
+    };
+    let profile = tauri::async_runtime::spawn_blocking(move || bundle::read_bundle(&path))
+        .await
+        .map_err(|e| map_err(e.into()))?
+        .map_err(|e| to_command_error("PROFILE_INVALID", "error.profile_invalid", e))?;
+
+    let mut report = ProfileImportReport {
+        sections: profile.manifest.sections.clone(),
+        ..ProfileImportReport::default()
+    };
+
+    let server_list = profile.servers.as_ref().map(|s| s.server_list.clone());
+    let has_settings = profile.settings.is_some();
+    if has_settings || server_list.is_some() {
+        let current = current_envelope().await.map_err(map_err)?;
+        let mut envelope = profile.settings.unwrap_or_else(|| current.clone());
+        envelope.backend.server_list = match &server_list {
+            Some(list) => list.clone(),
+            None => current.backend.server_list,
+        };
+        let raw = serde_json::to_string(&envelope).map_err(|e| map_err(e.into()))?;
+        config_usecases::import_settings(raw, ConfigStorePortAdapter::shared())
+            .await
+            .map_err(map_err)?;
+        report.settings_applied = has_settings;
+        report.servers_applied = server_list.map(|l| l.len()).unwrap_or(0);
+    }
+
+    if let Some(rules) = profile.mute_rules {
+        let existing = mute_rules::list().await.map_err(map_err)?;
+        for rule in rules {
+            let duplicate = existing.iter().any(|r| {
+                r.pattern == rule.pattern.trim()
+                    && r.is_regex == rule.is_regex
+                    && r.case_sensitive == rule.case_sensitive
+            });
+            if duplicate {
+                continue;
+            }
+            match mute_rules::add(&rule).await {
+                Ok(_) => report.mute_rules_added += 1,
+                Err(e) => {
+                    tracing::warn!(action = "settings_profile_mute_rule_skipped", error = %e);
+                }
+            }
+        }
+        if report.mute_rules_added > 0 {
+            mute_rules::schedule_full_reevaluation();
+        }
+    }
+
+    report.server_racks = profile.servers.and_then(|s| s.server_racks);
+    report.pinned_certificates = profile.certificates.unwrap_or_default();
+    report.plugins = profile.plugins.unwrap_or_default();
+    tracing::info!(
+        action = "settings_profile_imported",
+        sections = ?report.sections,
+        mute_rules_added = report.mute_rules_added
+    );
+    Ok(report)
+}
@@ -85,7 +85,7 @@ pub fn capture_all_screens(save_dir: &Path) -> Result<Vec<ScreenCapture>, String
 }
 
 /// base64 编码（手动实现，避免引入 base64 crate 导致版本冲突）。
-fn use_base64_encode(data: &[u8]) -> String {
+pub(super) fn use_base64_encode(data: &[u8]) -> String {
     const CHARS: &[u8] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
     let mut result = String::with_capacity(data.len() * 4 / 3 + 4);
     let mut i = 0;
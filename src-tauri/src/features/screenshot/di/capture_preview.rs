@@ -0,0 +1,255 @@
+//! screenshot｜屏幕共享本地预览（授权 + 低帧率预览帧推送）。
+//!
+//! 说明：
+//! - 这是通话屏幕共享接入前的占位实现：只把缩放后的画面通过 `tauri::ipc::Channel`
+//!   推给发起窗口做本地预览，不编码、不发送到对端；
+//! - 每个采集源首次开始采集前需要用户授权：后端发出 `capture-permission-requested`，
+//!   前端弹出确认框后调用 `respond_capture_permission` 回复；超时或拒绝即失败；
+//! - 授权只在本次运行内有效（不落盘），重启后重新询问。
+//!
+//! 约定：注释中文，日志英文（tracing）。
+
+use std::collections::{HashMap, HashSet};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex, OnceLock};
+use std::time::Duration;
+
+use serde::Serialize;
+use tauri::AppHandle;
+use tauri::ipc::Channel;
+use tokio::sync::oneshot;
+
+use crate::shared::events::EventBusExt;
+
+use super::capture_sources::{capture_source, encode_preview, source_name};
+
+/// 授权请求事件名。
+pub const CAPTURE_PERMISSION_REQUESTED_EVENT: &str = "capture-permission-requested";
+
+/// 等待用户授权的最长时间。
+const PERMISSION_TIMEOUT: Duration = Duration::from_secs(60);
+
+/// 预览帧间隔（约 2 fps）。
+const PREVIEW_FRAME_INTERVAL: Duration = Duration::from_millis(500);
+
+/// 预览帧最大边长。
+const PREVIEW_MAX_EDGE: u32 = 640;
+
+/// 授权请求事件载荷。
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CapturePermissionRequest {
+    pub request_id: String,
+    pub source_id: String,
+    pub source_name: String,
+}
+
+/// 推送给预览通道的帧。
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CapturePreviewFrame {
+    pub capture_id: String,
+    pub seq: u64,
+    /// PNG data URL。
+    pub data_url: String,
+}
+
+/// 开始采集的失败原因（命令层据此映射错误码）。
+#[derive(Debug)]
+pub enum CaptureStartError {
+    SourceNotFound,
+    PermissionDenied,
+    Internal(String),
+}
+
+impl std::fmt::Display for CaptureStartError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::SourceNotFound => write!(f, "capture source not found"),
+            Self::PermissionDenied => write!(f, "capture permission denied"),
+            Self::Internal(msg) => write!(f, "{msg}"),
+        }
+    }
+}
+
+fn pending_requests() -> &'static Mutex<HashMap<String, oneshot::Sender<bool>>> {
+    static PENDING: OnceLock<Mutex<HashMap<String, oneshot::Sender<bool>>>> = OnceLock::new();
+    PENDING.get_or_init(|| Mutex::new(HashMap::new()))
+}
+
+fn granted_sources() -> &'static Mutex<HashSet<String>> {
+    static GRANTED: OnceLock<Mutex<HashSet<String>>> = OnceLock::new();
+    GRANTED.get_or_init(|| Mutex::new(HashSet::new()))
+}
+
+/// 运行中的预览会话（capture_id -> 停止标记）。
+fn sessions() -> &'static Mutex<HashMap<String, Arc<AtomicBool>>> {
+    static SESSIONS: OnceLock<Mutex<HashMap<String, Arc<AtomicBool>>>> = OnceLock::new();
+    SESSIONS.get_or_init(|| Mutex::new(HashMap::new()))
+}
+
+fn is_granted(source_id: &str) -> bool {
+    granted_sources()
+        .lock()
+        .unwrap_or_else(|p| p.into_inner())
+        .contains(source_id)
+}
+
+/// 向前端请求授权并等待回复。
+async fn request_permission(
+    app: &AppHandle,
+    source_id: &str,
+    name: String,
+) -> Result<bool, CaptureStartError> {
+    let request_id = uuid::Uuid::new_v4().to_string();
+    let (tx, rx) = oneshot::channel();
+    pending_requests()
+        .lock()
+        .unwrap_or_else(|p| p.into_inner())
+        .insert(request_id.clone(), tx);
+
+    let payload = CapturePermissionRequest {
+        request_id: request_id.clone(),
+        source_id: source_id.to_string(),
+        source_name: name,
+    };
+    // 投递失败时前端收不到请求，按超时拒绝处理。
+    app.emit(CAPTURE_PERMISSION_REQUESTED_EVENT, payload);
+
+    let granted = matches!(
+        tokio::time::timeout(PERMISSION_TIMEOUT, rx).await,
+        Ok(Ok(true))
+    );
+    pending_requests()
+        .lock()
+        .unwrap_or_else(|p| p.into_inner())
+        .remove(&request_id);
+    Ok(granted)
+}
+
+/// 前端回复授权请求；请求不存在（已超时/已回复）时返回 `false`。
+pub fn respond_permission(request_id: &str, granted: bool) -> bool {
+    let sender = pending_requests()
+        .lock()
+        .unwrap_or_else(|p| p.into_inner())
+        .remove(request_id);
+    match sender {
+        Some(tx) => tx.send(granted).is_ok(),
+        None => false,
+    }
+}
+
+/// 开始采集：必要时先请求授权，然后启动预览帧推送。
+///
+/// # 返回值
+/// - `Ok(capture_id)`：用于 `stop`。
+pub async fn start(
+    app: AppHandle,
+    source_id: String,
+    on_frame: Channel<CapturePreviewFrame>,
+) -> Result<String, CaptureStartError> {
+    let sid = source_id.clone();
+    let name = tauri::async_runtime::spawn_blocking(move || source_name(&sid))
+        .await
+        .map_err(|e| CaptureStartError::Internal(e.to_string()))?
+        .ok_or(CaptureStartError::SourceNotFound)?;
+
+    if !is_granted(&source_id) {
+        if !request_permission(&app, &source_id, name).await? {
+            tracing::info!(action = "app_capture_permission_denied", source_id = %source_id);
+            return Err(CaptureStartError::PermissionDenied);
+        }
+        granted_sources()
+            .lock()
+            .unwrap_or_else(|p| p.into_inner())
+            .insert(source_id.clone());
+    }
+
+    let capture_id = uuid::Uuid::new_v4().to_string();
+    let stop = Arc::new(AtomicBool::new(false));
+    sessions()
+        .lock()
+        .unwrap_or_else(|p| p.into_inner())
+        .insert(capture_id.clone(), stop.clone());
+    tracing::info!(action = "app_capture_preview_started", capture_id = %capture_id, source_id = %source_id);
+
+    let id = capture_id.clone();
+    tauri::async_runtime::spawn(async move {
+        let mut seq = 0u64;
+        while !stop.load(Ordering::SeqCst) {
+            let sid = source_id.clone();
+            let frame = tauri::async_runtime::spawn_blocking(move || {
+                capture_source(&sid).and_then(|img| encode_preview(&img, PREVIEW_MAX_EDGE))
+            })
+            .await;
+            match frame {
+                Ok(Ok(data_url)) => {
+                    let sent = on_frame.send(CapturePreviewFrame {
+                        capture_id: id.clone(),
+                        seq,
+                        data_url,
+                    });
+                    // 通道关闭（预览窗口已关闭/刷新）即结束会话。
+                    if sent.is_err() {
+                        break;
+                    }
+                    seq += 1;
+                }
+                Ok(Err(e)) => {
+                    // 源消失（窗口关闭/显示器拔出）时结束会话。
+                    tracing::warn!(action = "app_capture_preview_frame_failed", capture_id = %id, error = %e);
+                    break;
+                }
+                Err(e) => {
+                    tracing::warn!(action = "app_capture_preview_frame_failed", capture_id = %id, error = %e);
+                    break;
+                }
+            }
+            tokio::time::sleep(PREVIEW_FRAME_INTERVAL).await;
+        }
+        sessions()
+            .lock()
+            .unwrap_or_else(|p| p.into_inner())
+            .remove(&id);
+        tracing::info!(action = "app_capture_preview_stopped", capture_id = %id, frames = seq);
+    });
+
+    Ok(capture_id)
+}
+
+/// 停止预览；会话不存在时返回 `false`。
+pub fn stop(capture_id: &str) -> bool {
+    match sessions()
+        .lock()
+        .unwrap_or_else(|p| p.into_inner())
+        .remove(capture_id)
+    {
+        Some(flag) => {
+            flag.store(true, Ordering::SeqCst);
+            true
+        }
+        None => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn permission_response_reaches_waiter_once() {
+        let (tx, rx) = oneshot::channel();
+        pending_requests()
+            .lock()
+            .unwrap_or_else(|p| p.into_inner())
+            .insert("req-1".to_string(), tx);
+        assert!(respond_permission("req-1", true));
+        assert!(!respond_permission("req-1", true));
+        assert_eq!(rx.await.ok(), Some(true));
+    }
+
+    #[test]
+    fn stopping_unknown_session_is_noop() {
+        assert!(!stop("missing"));
+    }
+}
@@ -0,0 +1,240 @@
+//! screenshot｜屏幕共享采集源枚举（显示器 + 窗口，附缩略图）。
+//!
+//! 说明：
+//! - 采集源 id 形如 `screen:{monitor_id}` / `window:{window_id}`，由后端生成、前端原样回传；
+//! - 缩略图与预览帧统一缩放后编码为 PNG data URL，避免把整屏原图经 IPC 传给前端；
+//! - 底层采集依赖 xcap（Windows: DXGI/GDI，macOS: CoreGraphics，Linux: X11/PipeWire）。
+//!
+//! 约定：注释中文，日志英文（tracing）。
+
+use anyhow::Context;
+use image::RgbaImage;
+use serde::Serialize;
+use xcap::{Monitor, Window};
+
+use super::capture::use_base64_encode;
+
+/// 列表缩略图的最大边长（逻辑像素）。
+pub const THUMBNAIL_MAX_EDGE: u32 = 320;
+
+/// 采集源类型。
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CaptureSourceKind {
+    Screen,
+    Window,
+}
+
+/// 可供屏幕共享的采集源。
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CaptureSource {
+    /// 采集源 id（`screen:{id}` / `window:{id}`）。
+    pub id: String,
+    pub kind: CaptureSourceKind,
+    /// 显示器名称或窗口标题。
+    pub name: String,
+    /// 窗口所属应用名（显示器为空）。
+    pub app_name: Option<String>,
+    /// 原始尺寸（物理像素）。
+    pub width: u32,
+    pub height: u32,
+    /// 缩略图（PNG data URL）；单个源截取失败时为空，不影响其它源。
+    pub thumbnail: Option<String>,
+}
+
+/// 解析采集源 id。
+pub fn parse_source_id(source_id: &str) -> Option<(CaptureSourceKind, u32)> {
+    let (kind, raw_id) = source_id.split_once(':')?;
+    let kind = match kind {
+        "screen" => CaptureSourceKind::Screen,
+        "window" => CaptureSourceKind::Window,
+        _ => return None,
+    };
+    Some((kind, raw_id.parse().ok()?))
+}
+
+fn source_id(kind: CaptureSourceKind, id: u32) -> String {
+    match kind {
+        CaptureSourceKind::Screen => format!("screen:{id}"),
+        CaptureSourceKind::Window => format!("window:{id}"),
+    }
+}
+
+/// 等比缩放后的尺寸：最长边不超过 `max_edge`，不放大。
+fn scaled_size(width: u32, height: u32, max_edge: u32) -> (u32, u32) {
+    let longest = width.max(height).max(1);
+    if longest <= max_edge {
+        return (width, height);
+    }
+    let ratio = f64::from(max_edge) / f64::from(longest);
+    (
+        ((f64::from(width) * ratio).round() as u32).max(1),
+        ((f64::from(height) * ratio).round() as u32).max(1),
+    )
+}
+
+/// 按最大边长等比缩放并编码为 PNG data URL。
+pub fn encode_preview(image: &RgbaImage, max_edge: u32) -> anyhow::Result<String> {
+    let (width, height) = image.dimensions();
+    let (w, h) = scaled_size(width, height, max_edge);
+    let scaled = if (w, h) == (width, height) {
+        image.clone()
+    } else {
+        image::imageops::thumbnail(image, w, h)
+    };
+    let mut png_bytes = Vec::new();
+    scaled
+        .write_to(
+            &mut std::io::Cursor::new(&mut png_bytes),
+            image::ImageFormat::Png,
+        )
+        .context("encode_preview png encode failed")?;
+    Ok(format!(
+        "data:image/png;base64,{}",
+        use_base64_encode(&png_bytes)
+    ))
+}
+
+fn thumbnail_of(image: xcap::XCapResult<RgbaImage>, source_id: &str) -> Option<String> {
+    match image
+        .map_err(anyhow::Error::from)
+        .and_then(|img| encode_preview(&img, THUMBNAIL_MAX_EDGE))
+    {
+        Ok(url) => Some(url),
+        Err(e) => {
+            tracing::debug!(action = "app_capture_thumbnail_failed", source_id = %source_id, error = %e);
+            None
+        }
+    }
+}
+
+/// 枚举所有显示器与可见窗口（阻塞调用，命令层应放到 `spawn_blocking`）。
+pub fn list_sources() -> anyhow::Result<Vec<CaptureSource>> {
+    let mut sources = Vec::new();
+
+    let monitors = Monitor::all().context("list_sources monitor list failed")?;
+    for monitor in &monitors {
+        let Ok(id) = monitor.id() else {
+            continue;
+        };
+        let sid = source_id(CaptureSourceKind::Screen, id);
+        let thumbnail = thumbnail_of(monitor.capture_image(), &sid);
+        sources.push(CaptureSource {
+            id: sid,
+            kind: CaptureSourceKind::Screen,
+            name: monitor.name().unwrap_or_else(|_| format!("Screen {id}")),
+            app_name: None,
+            width: monitor.width().unwrap_or(0),
+            height: monitor.height().unwrap_or(0),
+            thumbnail,
+        });
+    }
+
+    // 窗口枚举失败（如 Wayland 下无权限）时只返回显示器。
+    match Window::all() {
+        Ok(windows) => {
+            for window in &windows {
+                if window.is_minimized().unwrap_or(false) {
+                    continue;
+                }
+                let title = window.title().unwrap_or_default();
+                if title.trim().is_empty() {
+                    continue;
+                }
+                let Ok(id) = window.id() else {
+                    continue;
+                };
+                let sid = source_id(CaptureSourceKind::Window, id);
+                let thumbnail = thumbnail_of(window.capture_image(), &sid);
+                sources.push(CaptureSource {
+                    id: sid,
+                    kind: CaptureSourceKind::Window,
+                    name: title,
+                    app_name: window.app_name().ok().filter(|s| !s.is_empty()),
+                    width: window.width().unwrap_or(0),
+                    height: window.height().unwrap_or(0),
+                    thumbnail,
+                });
+            }
+        }
+        Err(e) => {
+            tracing::warn!(action = "app_capture_window_list_failed", error = ?e);
+        }
+    }
+
+    Ok(sources)
+}
+
+/// 采集源的显示名称（用于授权提示）；源不存在时返回 `None`。
+pub fn source_name(source_id: &str) -> Option<String> {
+    let (kind, id) = parse_source_id(source_id)?;
+    match kind {
+        CaptureSourceKind::Screen => Monitor::all()
+            .ok()?
+            .into_iter()
+            .find(|m| m.id().ok() == Some(id))
+            .map(|m| m.name().unwrap_or_else(|_| format!("Screen {id}"))),
+        CaptureSourceKind::Window => Window::all()
+            .ok()?
+            .into_iter()
+            .find(|w| w.id().ok() == Some(id))
+            .map(|w| w.title().unwrap_or_default()),
+    }
+}
+
+/// 截取单个采集源的当前画面。
+pub fn capture_source(source_id: &str) -> anyhow::Result<RgbaImage> {
+    let (kind, id) = parse_source_id(source_id)
+        .ok_or_else(|| anyhow::anyhow!("invalid source id: {source_id}"))?;
+    match kind {
+        CaptureSourceKind::Screen => {
+            let monitors = Monitor::all().context("capture_source monitor list failed")?;
+            let monitor = monitors
+                .into_iter()
+                .find(|m| m.id().ok() == Some(id))
+                .ok_or_else(|| anyhow::anyhow!("capture source not found: {source_id}"))?;
+            monitor
+                .capture_image()
+                .context("capture_source capture_image failed")
+        }
+        CaptureSourceKind::Window => {
+            let windows = Window::all().context("capture_source window list failed")?;
+            let window = windows
+                .into_iter()
+                .find(|w| w.id().ok() == Some(id))
+                .ok_or_else(|| anyhow::anyhow!("capture source not found: {source_id}"))?;
+            window
+                .capture_image()
+                .context("capture_source capture_image failed")
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn source_ids_roundtrip() {
+        let id = source_id(CaptureSourceKind::Window, 42);
+        assert_eq!(id, "window:42");
+        assert_eq!(parse_source_id(&id), Some((CaptureSourceKind::Window, 42)));
+        assert_eq!(
+            parse_source_id("screen:1"),
+            Some((CaptureSourceKind::Screen, 1))
+        );
+        assert_eq!(parse_source_id("tab:1"), None);
+        assert_eq!(parse_source_id("screen:abc"), None);
+        assert_eq!(parse_source_id("screen"), None);
+    }
+
+    #[test]
+    fn preview_is_downscaled_to_max_edge() {
+        assert_eq!(scaled_size(1920, 1080, 320), (320, 180));
+        assert_eq!(scaled_size(600, 1200, 320), (160, 320));
+        assert_eq!(scaled_size(200, 100, 320), (200, 100));
+        let url = encode_preview(&RgbaImage::new(64, 32), THUMBNAIL_MAX_EDGE).expect("encode");
+        assert!(url.starts_with("data:image/png;base64,"));
+    }
+}
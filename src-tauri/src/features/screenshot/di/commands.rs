@@ -3,10 +3,15 @@
 //! 约定：注释中文，日志英文（tracing）。
 use std::sync::Mutex;
 
-use tauri::{AppHandle, Emitter, Manager, WebviewUrl, WebviewWindowBuilder};
+use tauri::ipc::Channel;
+use tauri::{AppHandle, Manager, WebviewUrl, WebviewWindowBuilder};
 
 use super::capture::{ScreenCapture, capture_all_screens};
-use crate::shared::error::{CommandResult, command_error};
+use super::capture_preview::{self, CapturePreviewFrame, CaptureStartError};
+use super::capture_sources::{self, CaptureSource};
+use crate::features::windows::domain::window_labels::SCREENSHOT_OVERLAY_LABEL;
+use crate::shared::error::{CommandResult, command_error, to_command_error};
+use crate::shared::events::EventBusExt;
 
 /// 截图数据缓存状态：从 `start_screenshot` 暂存，供遮罩窗口 `get_screenshot_data` 取用。
 pub struct ScreenshotCaptureState(pub Mutex<Option<Vec<ScreenCapture>>>);
@@ -37,7 +42,7 @@ pub async fn start_screenshot(app: AppHandle, hide_window: Option<bool>) -> Comm
 
     // 2. 先打开遮罩窗口（让用户立即看到 loading 界面）
     let url = WebviewUrl::App("index.html?window=screenshot-overlay".into());
-    let window = WebviewWindowBuilder::new(&app, "screenshot-overlay", url)
+    let window = WebviewWindowBuilder::new(&app, SCREENSHOT_OVERLAY_LABEL, url)
         .decorations(false)
         .resizable(false)
         .fullscreen(true)
@@ -50,9 +55,7 @@ pub async fn start_screenshot(app: AppHandle, hide_window: Option<bool>) -> Comm
     let _ = window.set_focus();
 
     // 3. 截取所有显示器
-    let app_data = app
-        .path()
-        .app_data_dir()
+    let app_data = crate::shared::app_data_dir::get_app_data_dir()
         .map_err(|e| command_error("SCREENSHOT_APP_DATA_FAIL", &e.to_string()))?;
     let screenshot_dir = app_data.join("temp-screenshots");
 
@@ -80,8 +83,8 @@ pub async fn start_screenshot(app: AppHandle, hide_window: Option<bool>) -> Comm
     }
 
     // 5. 通知遮罩窗口数据已就绪
-    if let Some(overlay) = app.get_webview_window("screenshot-overlay") {
-        let _ = overlay.emit("screenshot-data-ready", ());
+    if app.get_webview_window(SCREENSHOT_OVERLAY_LABEL).is_some() {
+        app.emit("screenshot-data-ready", ());
     }
 
     tracing::info!(action = "app_screenshot_overlay_opened");
@@ -109,9 +112,7 @@ pub async fn finish_screenshot(app: AppHandle, data: Vec<u8>) -> CommandResult<S
     tracing::info!(action = "app_screenshot_finish", size = data.len());
 
     // 1. 保存到临时目录
-    let app_data = app
-        .path()
-        .app_data_dir()
+    let app_data = crate::shared::app_data_dir::get_app_data_dir()
         .map_err(|e| command_error("SCREENSHOT_APP_DATA_FAIL", &e.to_string()))?;
     let screenshot_dir = app_data.join("screenshots");
     std::fs::create_dir_all(&screenshot_dir)
@@ -125,7 +126,7 @@ pub async fn finish_screenshot(app: AppHandle, data: Vec<u8>) -> CommandResult<S
     let path_str = file_path.to_string_lossy().to_string();
 
     // 2. 关闭遮罩窗口
-    if let Some(overlay) = app.get_webview_window("screenshot-overlay") {
+    if let Some(overlay) = app.get_webview_window(SCREENSHOT_OVERLAY_LABEL) {
         let _ = overlay.close();
     }
 
@@ -138,8 +139,7 @@ pub async fn finish_screenshot(app: AppHandle, data: Vec<u8>) -> CommandResult<S
     }
 
     // 4. 向主窗口发送完成事件
-    app.emit("screenshot-completed", &path_str)
-        .map_err(|e| command_error("SCREENSHOT_EVENT_FAIL", &e.to_string()))?;
+    app.emit("screenshot-completed", &path_str);
 
     tracing::info!(action = "app_screenshot_saved", path = %path_str);
 
@@ -154,7 +154,7 @@ pub async fn finish_screenshot(app: AppHandle, data: Vec<u8>) -> CommandResult<S
 pub async fn cancel_screenshot(app: AppHandle) -> CommandResult<()> {
     tracing::info!(action = "app_screenshot_cancel");
 
-    if let Some(overlay) = app.get_webview_window("screenshot-overlay") {
+    if let Some(overlay) = app.get_webview_window(SCREENSHOT_OVERLAY_LABEL) {
         let _ = overlay.close();
     }
 
@@ -166,12 +166,79 @@ pub async fn cancel_screenshot(app: AppHandle) -> CommandResult<()> {
     }
 
     // 通知主窗口截图已取消
-    let _ = app.emit("screenshot-cancelled", ());
+    app.emit("screenshot-cancelled", ());
 
     // 清理临时截图文件（best-effort）
-    if let Ok(app_data) = app.path().app_data_dir() {
+    if let Ok(app_data) = crate::shared::app_data_dir::get_app_data_dir() {
         let _ = std::fs::remove_dir_all(app_data.join("temp-screenshots"));
     }
 
     Ok(())
 }
+
+/// 枚举可供屏幕共享的显示器与窗口（附缩略图）。
+#[tauri::command]
+pub async fn list_capture_sources() -> CommandResult<Vec<CaptureSource>> {
+    tauri::async_runtime::spawn_blocking(capture_sources::list_sources)
+        .await
+        .map_err(|e| {
+            to_command_error(
+                "CAPTURE_SOURCES_LIST_FAILED",
+                "error.capture_sources_list_failed",
+                e,
+            )
+        })?
+        .map_err(|e| {
+            tracing::warn!(action = "app_capture_sources_list_failed", error = %e);
+            to_command_error(
+                "CAPTURE_SOURCES_LIST_FAILED",
+                "error.capture_sources_list_failed",
+                e,
+            )
+        })
+}
+
+/// 开始采集指定源并向 `on_frame` 推送本地预览帧（首次采集某个源需用户授权）。
+///
+/// # 返回值
+/// - `Ok(String)`：capture_id，用于 `stop_capture`。
+/// - `Err`：源不存在、用户拒绝/超时未授权或内部错误。
+#[tauri::command]
+pub async fn start_capture(
+    app: AppHandle,
+    source_id: String,
+    on_frame: Channel<CapturePreviewFrame>,
+) -> CommandResult<String> {
+    capture_preview::start(app, source_id, on_frame)
+        .await
+        .map_err(|err| match err {
+            CaptureStartError::SourceNotFound => {
+                command_error("CAPTURE_SOURCE_NOT_FOUND", "error.capture_source_not_found")
+            }
+            CaptureStartError::PermissionDenied => command_error(
+                "CAPTURE_PERMISSION_DENIED",
+                "error.capture_permission_denied",
+            ),
+            CaptureStartError::Internal(e) => {
+                to_command_error("CAPTURE_START_FAILED", "error.capture_start_failed", e)
+            }
+        })
+}
+
+/// 回复采集授权请求（由前端确认框调用）。
+///
+/// # 返回值
+/// - `Ok(bool)`：请求是否仍在等待（已超时则为 `false`）。
+#[tauri::command]
+pub fn respond_capture_permission(request_id: String, granted: bool) -> CommandResult<bool> {
+    Ok(capture_preview::respond_permission(&request_id, granted))
+}
+
+/// 停止本地预览采集。
+///
+/// # 返回值
+/// - `Ok(bool)`：会话是否存在。
+#[tauri::command]
+pub fn stop_capture(capture_id: String) -> CommandResult<bool> {
+    Ok(capture_preview::stop(&capture_id))
+}
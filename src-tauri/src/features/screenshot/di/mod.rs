@@ -2,4 +2,26 @@
 //!
 //! 约定：注释中文，日志英文（tracing）。
 pub mod capture;
+pub mod capture_preview;
+pub mod capture_sources;
 pub mod commands;
+
+use crate::app::registry::{CommandRegistration, CommandRegistry, command_set};
+
+/// 截图命令注册。
+pub struct ScreenshotCommands;
+
+impl CommandRegistration for ScreenshotCommands {
+    fn register(registry: &mut CommandRegistry) {
+        registry.add(command_set!(crate::features::screenshot::di::commands => [
+            start_screenshot,
+            get_screenshot_data,
+            finish_screenshot,
+            cancel_screenshot,
+            list_capture_sources,
+            start_capture,
+            respond_capture_permission,
+            stop_capture,
+        ]));
+    }
+}
@@ -8,12 +8,12 @@ use std::path::{Path, PathBuf};
 use std::sync::OnceLock;
 use std::time::{Duration, Instant};
 use tokio::io::AsyncWriteExt;
-use tokio::sync::Mutex as TokioMutex;
+use tokio::sync::RwLock as TokioRwLock;
 
 use crate::features::settings::domain::settings_schema::{
-    SETTINGS_SCHEMA_VERSION, SettingsBackendStateV1, SettingsImportEnvelopeV1,
-    SettingsLocalCacheStateV1, SettingsLocale, SettingsServerConfigV1, SettingsTheme,
-    parse_settings_import_envelope,
+    SETTINGS_SCHEMA_VERSION, SettingsBackendStateV1, SettingsBackupRemoteV1, SettingsDndOverrideV1,
+    SettingsDndRuleV1, SettingsImportEnvelopeV1, SettingsLocalCacheStateV1, SettingsLocale,
+    SettingsServerConfigV1, SettingsTheme, parse_settings_import_envelope,
 };
 
 /// 获取配置文件路径。
@@ -27,13 +27,9 @@ pub(crate) fn config_file_path() -> PathBuf {
 
 /// 配置信封内存缓存的 TTL。
 ///
-/// 说明：
-/// - 生产环境 500ms，避免高频读取配置时反复访问磁盘；
-/// - 测试环境设为 0，保证每次读取都回退到磁盘，避免跨测试缓存导致断言不稳定。
-#[cfg(not(test))]
-const CONFIG_CACHE_TTL: Duration = Duration::from_millis(500);
-#[cfg(test)]
-const CONFIG_CACHE_TTL: Duration = Duration::from_millis(0);
+/// 说明：不过期——启动后只读一次磁盘，外部修改由文件监听或 `reload_config` 刷新；
+/// 测试通过 `cached_envelope_at` 显式传入 TTL。
+const CONFIG_CACHE_TTL: Duration = Duration::MAX;
 
 /// 高频配置写入的批量 flush 延迟。
 ///
@@ -59,10 +55,10 @@ struct CachedConfig {
     flush_handle: Option<tokio::task::JoinHandle<()>>,
 }
 
-static CONFIG_CACHE: OnceLock<TokioMutex<Option<CachedConfig>>> = OnceLock::new();
+static CONFIG_CACHE: OnceLock<TokioRwLock<Option<CachedConfig>>> = OnceLock::new();
 
-fn config_cache() -> &'static TokioMutex<Option<CachedConfig>> {
-    CONFIG_CACHE.get_or_init(|| TokioMutex::new(None))
+fn config_cache() -> &'static TokioRwLock<Option<CachedConfig>> {
+    CONFIG_CACHE.get_or_init(|| TokioRwLock::new(None))
 }
 
 fn config_temp_path(path: &Path) -> PathBuf {
@@ -148,6 +144,46 @@ fn legacy_config_to_backend_state(config: &Config) -> SettingsBackendStateV1 {
         desktop_notifications: config.desktop_notifications,
         global_dnd: config.global_dnd,
         server_port: None,
+        translation_endpoint: None,
+        feature_flags: Default::default(),
+        legacy_string_errors: false,
+        emoji_skin_tone: 0,
+        frame_compression: false,
+        tcp_legacy_message_events: false,
+        start_minimized: false,
+        start_hidden: false,
+        frame_capture: false,
+        plugin_auto_rollback_failures: 0,
+        plugin_auto_prune: false,
+        plugin_auto_prune_keep: 0,
+        plugin_dev_mode: false,
+        plugin_http_server: false,
+        mini_chat_auto_hide_fullscreen: false,
+        dnd_schedule: Vec::new(),
+        dnd_override: None,
+        notification_history_max_age_days: 0,
+        notification_history_max_count: 0,
+        assistant_enabled: false,
+        assistant_endpoint: None,
+        assistant_model: None,
+        inbound_flood_max_per_sec: 0,
+        frame_replay_buffer_frames: 0,
+        prefetch_max_concurrent_per_server: 0,
+        typing_throttle_secs: 0,
+        message_retention_days: 0,
+        message_retention_max_per_channel: 0,
+        db_slow_query_threshold_ms: 0,
+        db_sql_policy: None,
+        scheduled_backup: false,
+        backup_keep_daily: 0,
+        backup_keep_weekly: 0,
+        backup_remote: None,
+        app_lock_idle_minutes: 0,
+        app_lock_biometric: false,
+        privacy_mode: false,
+        dns_over_https: false,
+        dns_over_https_provider: None,
+        hide_unread_badge: false,
         server_list: config
             .server_list
             .iter()
@@ -198,11 +234,10 @@ fn default_config_json() -> String {
     })
 }
 
-async fn ensure_config_file_exists() -> String {
-    let config_file = config_file_path();
+async fn ensure_config_file_exists(config_file: &Path) -> String {
     let default_json = default_config_json();
 
-    match tokio::fs::write(&config_file, &default_json).await {
+    match tokio::fs::write(config_file, &default_json).await {
         Ok(_) => {
             tracing::info!(
                 action = "settings_config_file_initialized",
@@ -240,12 +275,80 @@ fn format_envelope_json(envelope: &SettingsImportEnvelopeV1) -> anyhow::Result<S
 fn envelope_value_for_key(envelope: &SettingsImportEnvelopeV1, key: &str) -> Option<Value> {
     match key {
         "auto_login" => Some(Value::Bool(envelope.backend.auto_login)),
-        "auto_launch" => Some(Value::Bool(envelope.backend.auto_launch)),
+        // 便携模式下开机自启永远视为关闭，避免在宿主机写入启动项。
+        "auto_launch" => Some(Value::Bool(
+            envelope.backend.auto_launch && !crate::shared::paths::is_portable_mode(),
+        )),
         "close_to_tray" => Some(Value::Bool(envelope.backend.close_to_tray)),
         "check_for_updates" => Some(Value::Bool(envelope.backend.check_for_updates)),
         "email_notifications" => Some(Value::Bool(envelope.backend.email_notifications)),
         "desktop_notifications" => Some(Value::Bool(envelope.backend.desktop_notifications)),
         "global_dnd" => Some(Value::Bool(envelope.backend.global_dnd)),
+        "legacy_string_errors" => Some(Value::Bool(envelope.backend.legacy_string_errors)),
+        "frame_compression" => Some(Value::Bool(envelope.backend.frame_compression)),
+        "tcp_legacy_message_events" => {
+            Some(Value::Bool(envelope.backend.tcp_legacy_message_events))
+        }
+        "start_minimized" => Some(Value::Bool(envelope.backend.start_minimized)),
+        "start_hidden" => Some(Value::Bool(envelope.backend.start_hidden)),
+        "frame_capture" => Some(Value::Bool(envelope.backend.frame_capture)),
+        "plugin_auto_prune" => Some(Value::Bool(envelope.backend.plugin_auto_prune)),
+        "scheduled_backup" => Some(Value::Bool(envelope.backend.scheduled_backup)),
+        "app_lock_biometric" => Some(Value::Bool(envelope.backend.app_lock_biometric)),
+        "privacy_mode" => Some(Value::Bool(envelope.backend.privacy_mode)),
+        "dns_over_https" => Some(Value::Bool(envelope.backend.dns_over_https)),
+        "hide_unread_badge" => Some(Value::Bool(envelope.backend.hide_unread_badge)),
+        "plugin_dev_mode" => Some(Value::Bool(envelope.backend.plugin_dev_mode)),
+        "plugin_http_server" => Some(Value::Bool(envelope.backend.plugin_http_server)),
+        "assistant_enabled" => Some(Value::Bool(envelope.backend.assistant_enabled)),
+        "mini_chat_auto_hide_fullscreen" => {
+            Some(Value::Bool(envelope.backend.mini_chat_auto_hide_fullscreen))
+        }
+        "emoji_skin_tone" => Some(Value::Number(serde_json::Number::from(
+            envelope.backend.emoji_skin_tone,
+        ))),
+        "plugin_auto_rollback_failures" => Some(Value::Number(serde_json::Number::from(
+            envelope.backend.plugin_auto_rollback_failures,
+        ))),
+        "plugin_auto_prune_keep" => Some(Value::Number(serde_json::Number::from(
+            envelope.backend.plugin_auto_prune_keep,
+        ))),
+        "notification_history_max_age_days" => Some(Value::Number(serde_json::Number::from(
+            envelope.backend.notification_history_max_age_days,
+        ))),
+        "notification_history_max_count" => Some(Value::Number(serde_json::Number::from(
+            envelope.backend.notification_history_max_count,
+        ))),
+        "inbound_flood_max_per_sec" => Some(Value::Number(serde_json::Number::from(
+            envelope.backend.inbound_flood_max_per_sec,
+        ))),
+        "frame_replay_buffer_frames" => Some(Value::Number(serde_json::Number::from(
+            envelope.backend.frame_replay_buffer_frames,
+        ))),
+        "prefetch_max_concurrent_per_server" => Some(Value::Number(serde_json::Number::from(
+            envelope.backend.prefetch_max_concurrent_per_server,
+        ))),
+        "typing_throttle_secs" => Some(Value::Number(serde_json::Number::from(
+            envelope.backend.typing_throttle_secs,
+        ))),
+        "message_retention_days" => Some(Value::Number(serde_json::Number::from(
+            envelope.backend.message_retention_days,
+        ))),
+        "message_retention_max_per_channel" => Some(Value::Number(serde_json::Number::from(
+            envelope.backend.message_retention_max_per_channel,
+        ))),
+        "db_slow_query_threshold_ms" => Some(Value::Number(serde_json::Number::from(
+            envelope.backend.db_slow_query_threshold_ms,
+        ))),
+        "backup_keep_daily" => Some(Value::Number(serde_json::Number::from(
+            envelope.backend.backup_keep_daily,
+        ))),
+        "backup_keep_weekly" => Some(Value::Number(serde_json::Number::from(
+            envelope.backend.backup_keep_weekly,
+        ))),
+        "app_lock_idle_minutes" => Some(Value::Number(serde_json::Number::from(
+            envelope.backend.app_lock_idle_minutes,
+        ))),
         "server_port" => envelope
             .backend
             .server_port
@@ -253,6 +356,23 @@ fn envelope_value_for_key(envelope: &SettingsImportEnvelopeV1, key: &str) -> Opt
         "theme" => Some(Value::String(
             settings_theme_to_string(envelope.local_cache.theme).to_string(),
         )),
+        "translation_endpoint" => envelope
+            .backend
+            .translation_endpoint
+            .clone()
+            .map(Value::String),
+        "assistant_endpoint" => envelope
+            .backend
+            .assistant_endpoint
+            .clone()
+            .map(Value::String),
+        "assistant_model" => envelope.backend.assistant_model.clone().map(Value::String),
+        "db_sql_policy" => envelope.backend.db_sql_policy.clone().map(Value::String),
+        "dns_over_https_provider" => envelope
+            .backend
+            .dns_over_https_provider
+            .clone()
+            .map(Value::String),
         _ => None,
     }
 }
@@ -266,6 +386,22 @@ fn update_envelope_bool(envelope: &mut SettingsImportEnvelopeV1, key: &str, valu
         "email_notifications" => envelope.backend.email_notifications = value,
         "desktop_notifications" => envelope.backend.desktop_notifications = value,
         "global_dnd" => envelope.backend.global_dnd = value,
+        "legacy_string_errors" => envelope.backend.legacy_string_errors = value,
+        "frame_compression" => envelope.backend.frame_compression = value,
+        "tcp_legacy_message_events" => envelope.backend.tcp_legacy_message_events = value,
+        "start_minimized" => envelope.backend.start_minimized = value,
+        "start_hidden" => envelope.backend.start_hidden = value,
+        "frame_capture" => envelope.backend.frame_capture = value,
+        "plugin_auto_prune" => envelope.backend.plugin_auto_prune = value,
+        "scheduled_backup" => envelope.backend.scheduled_backup = value,
+        "app_lock_biometric" => envelope.backend.app_lock_biometric = value,
+        "privacy_mode" => envelope.backend.privacy_mode = value,
+        "dns_over_https" => envelope.backend.dns_over_https = value,
+        "hide_unread_badge" => envelope.backend.hide_unread_badge = value,
+        "plugin_dev_mode" => envelope.backend.plugin_dev_mode = value,
+        "plugin_http_server" => envelope.backend.plugin_http_server = value,
+        "assistant_enabled" => envelope.backend.assistant_enabled = value,
+        "mini_chat_auto_hide_fullscreen" => envelope.backend.mini_chat_auto_hide_fullscreen = value,
         _ => return false,
     }
     true
@@ -280,6 +416,40 @@ fn update_envelope_string(envelope: &mut SettingsImportEnvelopeV1, key: &str, va
             }
             false
         }
+        "translation_endpoint" => {
+            let trimmed = value.trim();
+            envelope.backend.translation_endpoint =
+                (!trimmed.is_empty()).then(|| trimmed.to_string());
+            true
+        }
+        "assistant_endpoint" => {
+            let trimmed = value.trim();
+            envelope.backend.assistant_endpoint =
+                (!trimmed.is_empty()).then(|| trimmed.to_string());
+            true
+        }
+        "assistant_model" => {
+            let trimmed = value.trim();
+            envelope.backend.assistant_model = (!trimmed.is_empty()).then(|| trimmed.to_string());
+            true
+        }
+        "db_sql_policy" => {
+            if crate::shared::db::sql_policy::SqlPolicyMode::parse(value).is_none() {
+                return false;
+            }
+            let trimmed = value.trim();
+            envelope.backend.db_sql_policy = (!trimmed.is_empty()).then(|| trimmed.to_string());
+            true
+        }
+        "dns_over_https_provider" => {
+            let trimmed = value.trim();
+            if !trimmed.is_empty() && !crate::shared::net::doh::is_valid_provider(trimmed) {
+                return false;
+            }
+            envelope.backend.dns_over_https_provider =
+                (!trimmed.is_empty()).then(|| trimmed.to_string());
+            true
+        }
         _ => false,
     }
 }
@@ -290,29 +460,110 @@ fn update_envelope_u32(envelope: &mut SettingsImportEnvelopeV1, key: &str, value
             envelope.backend.server_port = Some(value as u16);
             true
         }
+        "emoji_skin_tone" => {
+            if value > crate::features::emoji::shortcode_index::MAX_SKIN_TONE {
+                return false;
+            }
+            envelope.backend.emoji_skin_tone = value;
+            true
+        }
+        "plugin_auto_rollback_failures" => {
+            envelope.backend.plugin_auto_rollback_failures = value;
+            true
+        }
+        "plugin_auto_prune_keep" => {
+            envelope.backend.plugin_auto_prune_keep = value;
+            true
+        }
+        "notification_history_max_age_days" => {
+            envelope.backend.notification_history_max_age_days = value;
+            true
+        }
+        "notification_history_max_count" => {
+            envelope.backend.notification_history_max_count = value;
+            true
+        }
+        "inbound_flood_max_per_sec" => {
+            envelope.backend.inbound_flood_max_per_sec = value;
+            true
+        }
+        "frame_replay_buffer_frames" => {
+            envelope.backend.frame_replay_buffer_frames = value;
+            true
+        }
+        "prefetch_max_concurrent_per_server" => {
+            envelope.backend.prefetch_max_concurrent_per_server = value;
+            true
+        }
+        "typing_throttle_secs" => {
+            envelope.backend.typing_throttle_secs = value;
+            true
+        }
+        "message_retention_days" => {
+            envelope.backend.message_retention_days = value;
+            true
+        }
+        "message_retention_max_per_channel" => {
+            envelope.backend.message_retention_max_per_channel = value;
+            true
+        }
+        "db_slow_query_threshold_ms" => {
+            envelope.backend.db_slow_query_threshold_ms = value;
+            true
+        }
+        "backup_keep_daily" => {
+            envelope.backend.backup_keep_daily = value;
+            true
+        }
+        "backup_keep_weekly" => {
+            envelope.backend.backup_keep_weekly = value;
+            true
+        }
+        "app_lock_idle_minutes" => {
+            envelope.backend.app_lock_idle_minutes = value;
+            true
+        }
         _ => false,
     }
 }
 
+/// 将进程级运行时开关与 envelope 对齐（写入即生效，无需重启）。
+pub fn apply_runtime_switches(envelope: &SettingsImportEnvelopeV1) {
+    crate::shared::error::set_legacy_string_errors(envelope.backend.legacy_string_errors);
+    crate::shared::privacy::set_enabled(envelope.backend.privacy_mode);
+    crate::shared::badges::set_hidden(envelope.backend.hide_unread_badge);
+    crate::shared::net::doh::configure(
+        envelope.backend.dns_over_https,
+        envelope.backend.dns_over_https_provider.as_deref(),
+    );
+}
+
 /// 将 envelope 立即写入磁盘，并同步为内存缓存中的“干净”状态。
 ///
 /// 说明：
 /// - 用于 import_settings / reset_settings 等需要立即落盘的场景；
 /// - 写入成功后会取消任何待执行的批量 flush，避免重复写盘。
 async fn persist_envelope(envelope: &SettingsImportEnvelopeV1) -> anyhow::Result<()> {
-    let config_file = config_file_path();
+    persist_envelope_at(&config_file_path(), envelope).await
+}
+
+/// [`persist_envelope`] 的显式路径版本。
+async fn persist_envelope_at(
+    config_file: &Path,
+    envelope: &SettingsImportEnvelopeV1,
+) -> anyhow::Result<()> {
+    apply_runtime_switches(envelope);
     let json = format_envelope_json(envelope)?;
-    atomic_write_config(&config_file, &json).await?;
+    atomic_write_config(config_file, &json).await?;
 
-    let current_path = config_file_path();
-    let mut guard = config_cache().lock().await;
+    let mut guard = config_cache().write().await;
     if let Some(cache) = guard.as_mut() {
         if let Some(handle) = cache.flush_handle.take() {
             handle.abort();
         }
     }
     *guard = Some(CachedConfig {
-        path: current_path,
+        path: config_file.to_path_buf(),
         envelope: envelope.clone(),
         loaded_at: Instant::now(),
         dirty: false,
@@ -323,29 +574,31 @@ async fn persist_envelope(envelope: &SettingsImportEnvelopeV1) -> anyhow::Result
 
 /// 获取当前应使用的配置 envelope（优先使用未过期的内存缓存）。
 async fn cached_envelope() -> SettingsImportEnvelopeV1 {
-    let current_path = config_file_path();
-    let guard = config_cache().lock().await;
+    cached_envelope_at(&config_file_path(), CONFIG_CACHE_TTL).await
+}
+
+/// [`cached_envelope`] 的显式路径/TTL 版本。
+async fn cached_envelope_at(config_file: &Path, ttl: Duration) -> SettingsImportEnvelopeV1 {
+    let guard = config_cache().read().await;
 
     if let Some(cache) = guard.as_ref() {
         // 路径一致且（有脏数据或缓存未过期）时直接返回内存副本。
-        if cache.path == current_path
-            && (cache.dirty || cache.loaded_at.elapsed() < CONFIG_CACHE_TTL)
-        {
+        if cache.path == config_file && (cache.dirty || cache.loaded_at.elapsed() < ttl) {
             return cache.envelope.clone();
         }
     }
 
     drop(guard);
-    let envelope = load_envelope_from_disk().await;
-    let mut guard = config_cache().lock().await;
+    let envelope = load_envelope_from_disk(config_file).await;
+    let mut guard = config_cache().write().await;
     // 如果当前缓存有脏数据，说明在加载磁盘期间发生了写入；以内存中的最新值为准。
     if let Some(cache) = guard.as_ref() {
-        if cache.dirty && cache.path == current_path {
+        if cache.dirty && cache.path == config_file {
             return cache.envelope.clone();
         }
     }
     *guard = Some(CachedConfig {
-        path: current_path,
+        path: config_file.to_path_buf(),
         envelope: envelope.clone(),
         loaded_at: Instant::now(),
         dirty: false,
@@ -356,27 +609,37 @@ async fn cached_envelope() -> SettingsImportEnvelopeV1 {
 
 /// 将 envelope 的修改先写入内存缓存，并按 CONFIG_FLUSH_DELAY 批量 flush 到磁盘。
 async fn schedule_persist_envelope(envelope: SettingsImportEnvelopeV1) -> anyhow::Result<()> {
-    let current_path = config_file_path();
-    let mut guard = config_cache().lock().await;
+    schedule_persist_envelope_at(config_file_path(), envelope, CONFIG_FLUSH_DELAY).await
+}
+
+/// [`schedule_persist_envelope`] 的显式路径/延迟版本。
+async fn schedule_persist_envelope_at(
+    config_file: PathBuf,
+    envelope: SettingsImportEnvelopeV1,
+    flush_delay: Duration,
+) -> anyhow::Result<()> {
+    apply_runtime_switches(&envelope);
+    let mut guard = config_cache().write().await;
 
     if let Some(cache) = guard.as_mut() {
-        if cache.path != current_path {
+        if cache.path != config_file {
             *guard = None;
         } else if let Some(handle) = cache.flush_handle.take() {
             handle.abort();
         }
     }
 
-    if CONFIG_FLUSH_DELAY.is_zero() {
+    if flush_delay.is_zero() {
         // 测试环境：立即落盘，保持与现有断言兼容。
         drop(guard);
-        return persist_envelope(&envelope).await;
+        return persist_envelope_at(&config_file, &envelope).await;
     }
 
     let envelope_for_task = envelope.clone();
+    let path_for_task = config_file.clone();
     let flush_handle = Some(tokio::spawn(async move {
-        tokio::time::sleep(CONFIG_FLUSH_DELAY).await;
-        if let Err(e) = flush_pending_config(&envelope_for_task).await {
+        tokio::time::sleep(flush_delay).await;
+        if let Err(e) = flush_pending_config(&path_for_task, &envelope_for_task).await {
             tracing::warn!(
                 action = "settings_config_flush_failed",
                 error = %e
@@ -385,7 +648,7 @@ async fn schedule_persist_envelope(envelope: SettingsImportEnvelopeV1) -> anyhow
     }));
 
     *guard = Some(CachedConfig {
-        path: current_path,
+        path: config_file,
         envelope,
         loaded_at: Instant::now(),
         dirty: true,
@@ -394,29 +657,39 @@ async fn schedule_persist_envelope(envelope: SettingsImportEnvelopeV1) -> anyhow
     Ok(())
 }
 
+/// 丢弃内存中的配置缓存与待执行的 flush（安全擦除前调用，避免稍后把配置写回磁盘）。
+pub(crate) async fn discard_cache() {
+    let mut guard = config_cache().write().await;
+    if let Some(handle) = guard.as_mut().and_then(|cache| cache.flush_handle.take()) {
+        handle.abort();
+    }
+    *guard = None;
+}
+
 /// 执行一次待 flush 检查：仅当缓存仍持有相同的 envelope 时才真正写盘。
-async fn flush_pending_config(expected_envelope: &SettingsImportEnvelopeV1) -> anyhow::Result<()> {
-    let current_path = config_file_path();
-    let guard = config_cache().lock().await;
+async fn flush_pending_config(
+    config_file: &Path,
+    expected_envelope: &SettingsImportEnvelopeV1,
+) -> anyhow::Result<()> {
+    let guard = config_cache().read().await;
     let Some(cache) = guard.as_ref() else {
         return Ok(());
     };
-    if cache.path != current_path || !cache.dirty || cache.envelope != *expected_envelope {
+    if cache.path != config_file || !cache.dirty || cache.envelope != *expected_envelope {
         return Ok(());
     }
     let envelope = cache.envelope.clone();
     drop(guard);
 
-    persist_envelope(&envelope).await
+    persist_envelope_at(config_file, &envelope).await
 }
 
-async fn load_envelope_from_disk() -> SettingsImportEnvelopeV1 {
-    let config_file = config_file_path();
-    let raw = match tokio::fs::read_to_string(&config_file).await {
+async fn load_envelope_from_disk(config_file: &Path) -> SettingsImportEnvelopeV1 {
+    let raw = match tokio::fs::read_to_string(config_file).await {
         Ok(data) if !data.trim().is_empty() => data,
         Ok(_) => {
             tracing::warn!(action = "settings_config_file_empty", path = %config_file.display());
-            let default_json = ensure_config_file_exists().await;
+            let default_json = ensure_config_file_exists(config_file).await;
             return parse_settings_import_envelope(&default_json)
                 .unwrap_or_else(|_| default_settings_envelope());
         }
@@ -426,7 +699,7 @@ async fn load_envelope_from_disk() -> SettingsImportEnvelopeV1 {
                 path = %config_file.display(),
                 error = %error
             );
-            let default_json = ensure_config_file_exists().await;
+            let default_json = ensure_config_file_exists(config_file).await;
             return parse_settings_import_envelope(&default_json)
                 .unwrap_or_else(|_| default_settings_envelope());
         }
@@ -439,7 +712,7 @@ async fn load_envelope_from_disk() -> SettingsImportEnvelopeV1 {
     match serde_json::from_str::<Config>(&raw) {
         Ok(legacy) => {
             let envelope = envelope_from_legacy_config(legacy);
-            if let Err(error) = persist_envelope(&envelope).await {
+            if let Err(error) = persist_envelope_at(config_file, &envelope).await {
                 tracing::warn!(
                     action = "settings_config_migration_persist_failed",
                     path = %config_file.display(),
@@ -454,13 +727,35 @@ async fn load_envelope_from_disk() -> SettingsImportEnvelopeV1 {
                 path = %config_file.display(),
                 error = %error
             );
-            let default_json = ensure_config_file_exists().await;
+            let default_json = ensure_config_file_exists(config_file).await;
             parse_settings_import_envelope(&default_json)
                 .unwrap_or_else(|_| default_settings_envelope())
         }
     }
 }
 
+/// 严格解析配置文件内容：版本化 envelope 优先，回退到旧版 `Config` 格式。
+fn parse_config_strict(raw: &str) -> anyhow::Result<SettingsImportEnvelopeV1> {
+    if raw.trim().is_empty() {
+        return Err(anyhow::anyhow!("config file is empty"));
+    }
+    if let Ok(envelope) = parse_settings_import_envelope(raw) {
+        return Ok(envelope);
+    }
+    let legacy = serde_json::from_str::<Config>(raw)?;
+    Ok(envelope_from_legacy_config(legacy))
+}
+
+/// 严格读取磁盘上的配置（`reload_config` 使用）。
+///
+/// # 说明
+/// 与 [`load_envelope_from_disk`] 不同：文件为空、不可读或无法解析时返回错误，
+/// 不写入默认配置、也不落盘旧格式迁移结果。
+async fn read_envelope_from_disk(config_file: &Path) -> anyhow::Result<SettingsImportEnvelopeV1> {
+    let raw = tokio::fs::read_to_string(config_file).await?;
+    parse_config_strict(&raw)
+}
+
 /// 单个服务器配置条目（用于本地配置文件持久化）。
 #[derive(Default, Debug, Clone, Serialize, Deserialize)]
 pub struct ServerConfig {
@@ -512,6 +807,51 @@ pub async fn get_config() -> String {
     })
 }
 
+/// 同步读取当前配置（启动阶段等无法 `await` 的调用方使用）。
+///
+/// # 说明
+/// - 内存缓存已加载（含尚未落盘的修改）时直接返回缓存；
+/// - 否则按与 [`reload_config`] 相同的规则严格解析磁盘文件（兼容旧版格式），不写入缓存或磁盘；
+/// - 文件不存在、为空或无法解析时返回 `None`，由调用方决定默认值。
+pub fn read_config_sync() -> Option<SettingsImportEnvelopeV1> {
+    read_config_sync_at(&config_file_path())
+}
+
+/// [`read_config_sync`] 的显式路径版本。
+fn read_config_sync_at(config_file: &Path) -> Option<SettingsImportEnvelopeV1> {
+    if let Ok(guard) = config_cache().try_read()
+        && let Some(cache) = guard.as_ref()
+        && cache.path == config_file
+    {
+        return Some(cache.envelope.clone());
+    }
+    let raw = std::fs::read_to_string(config_file).ok()?;
+    parse_config_strict(&raw)
+        .inspect_err(|error| {
+            tracing::warn!(
+                action = "settings_config_sync_read_failed",
+                path = %config_file.display(),
+                error = %error
+            );
+        })
+        .ok()
+}
+
+/// 磁盘上的配置是否仍为旧版 `Config` 格式。
+pub(crate) fn is_legacy_config(raw: &str) -> bool {
+    let raw = raw.trim();
+    !raw.is_empty()
+        && parse_settings_import_envelope(raw).is_err()
+        && serde_json::from_str::<Config>(raw).is_ok()
+}
+
+/// 磁盘上的 `config.json` 是否仍为旧版格式（尚未迁移为版本化 envelope）。
+pub async fn has_legacy_config() -> bool {
+    tokio::fs::read_to_string(config_file_path())
+        .await
+        .is_ok_and(|raw| is_legacy_config(&raw))
+}
+
 /// 导出版本化 settings envelope（storage 层 helper）。
 pub async fn export_settings() -> String {
     get_config().await
@@ -528,6 +868,68 @@ pub async fn reset_settings() -> anyhow::Result<()> {
     persist_envelope(&default_settings_envelope()).await
 }
 
+/// 从磁盘重新加载配置到内存缓存。
+///
+/// # 说明
+/// - 若内存中有尚未落盘的修改，先将其写盘再重新读取，避免丢失本地修改；
+/// - 文件无法读取或解析时（如外部编辑器写到一半）保留内存中的配置并返回错误，
+///   不会用默认配置覆盖磁盘上的文件。
+///
+/// # 返回值
+/// 内存中的配置是否发生变化。
+pub async fn reload_config() -> anyhow::Result<bool> {
+    reload_config_at(&config_file_path()).await
+}
+
+/// [`reload_config`] 的显式路径版本。
+async fn reload_config_at(config_file: &Path) -> anyhow::Result<bool> {
+    let pending = {
+        let guard = config_cache().read().await;
+        guard
+            .as_ref()
+            .filter(|cache| cache.dirty && cache.path == config_file)
+            .map(|cache| cache.envelope.clone())
+    };
+    if let Some(envelope) = pending {
+        persist_envelope_at(config_file, &envelope).await?;
+    }
+
+    let envelope = match read_envelope_from_disk(config_file).await {
+        Ok(envelope) => envelope,
+        Err(error) => {
+            tracing::error!(
+                action = "settings_config_reload_parse_failed",
+                path = %config_file.display(),
+                error = %error
+            );
+            return Err(error);
+        }
+    };
+    let mut guard = config_cache().write().await;
+    if let Some(cache) = guard.as_ref()
+        && cache.dirty
+        && cache.path == config_file
+    {
+        // 重新读取期间又有新的写入：以内存中的最新值为准。
+        return Ok(false);
+    }
+    let changed = guard
+        .as_ref()
+        .is_none_or(|cache| cache.path != config_file || cache.envelope != envelope);
+    if let Some(handle) = guard.as_mut().and_then(|cache| cache.flush_handle.take()) {
+        handle.abort();
+    }
+    apply_runtime_switches(&envelope);
+    *guard = Some(CachedConfig {
+        path: config_file.to_path_buf(),
+        envelope,
+        loaded_at: Instant::now(),
+        dirty: false,
+        flush_handle: None,
+    });
+    Ok(changed)
+}
+
 /// 配置值抽取器：将 JSON 值转换为指定类型，并支持反向写回 JSON。
 ///
 /// # 说明
@@ -631,8 +1033,63 @@ where
     T::default()
 }
 
+/// 读取本地 feature flag 覆盖表（`backend.featureFlags`）。
+pub async fn get_feature_flag_overrides() -> std::collections::BTreeMap<String, bool> {
+    cached_envelope().await.backend.feature_flags
+}
+
+/// 读取免打扰相关配置：`(global_dnd, 定时规则, 手动覆盖)`。
+pub async fn get_dnd_settings() -> (bool, Vec<SettingsDndRuleV1>, Option<SettingsDndOverrideV1>) {
+    let backend = cached_envelope().await.backend;
+    (
+        backend.global_dnd,
+        backend.dnd_schedule,
+        backend.dnd_override,
+    )
+}
+
+/// 替换免打扰定时规则。
+pub async fn update_dnd_schedule(rules: Vec<SettingsDndRuleV1>) -> anyhow::Result<()> {
+    let mut envelope = cached_envelope().await;
+    envelope.backend.dnd_schedule = rules;
+    schedule_persist_envelope(envelope).await
+}
+
+/// 更新免打扰手动覆盖；`global_dnd` 同步为“无期限开启”的状态，保持旧开关与新模式一致。
+pub async fn update_dnd_override(
+    dnd_override: Option<SettingsDndOverrideV1>,
+) -> anyhow::Result<()> {
+    let mut envelope = cached_envelope().await;
+    envelope.backend.global_dnd = matches!(
+        dnd_override,
+        Some(SettingsDndOverrideV1 {
+            active: true,
+            until_ms: None
+        })
+    );
+    envelope.backend.dnd_override = dnd_override;
+    schedule_persist_envelope(envelope).await
+}
+
+/// 读取远程备份目标。
+pub async fn get_backup_remote() -> Option<SettingsBackupRemoteV1> {
+    cached_envelope().await.backend.backup_remote
+}
+
+/// 更新远程备份目标（`None` 表示停用）。
+pub async fn update_backup_remote(remote: Option<SettingsBackupRemoteV1>) -> anyhow::Result<()> {
+    let mut envelope = cached_envelope().await;
+    envelope.backend.backup_remote = remote;
+    schedule_persist_envelope(envelope).await
+}
+
 /// 异步更新配置文件中的指定 bool 值。
 pub async fn update_config_bool(key: String, value: bool) -> anyhow::Result<()> {
+    if key == "auto_launch" && value && crate::shared::paths::is_portable_mode() {
+        return Err(anyhow::anyhow!(
+            "auto_launch is not available in portable mode"
+        ));
+    }
     let mut envelope = cached_envelope().await;
     if !update_envelope_bool(&mut envelope, &key, value) {
         tracing::error!(action = "settings_config_update_unsupported", key = %key);
@@ -847,6 +1304,8 @@ mod tests {
     async fn legacy_config_is_migrated_to_versioned_envelope() {
         let _guard = test_lock().await;
         let _ = crate::shared::app_data_dir::reset_app_data_dir();
+        // 以下用例都落在相对路径 `./config.json` 上（缓存不过期），先丢弃上一个用例留下的缓存。
+        discard_cache().await;
         let prev = std::env::current_dir().expect("cwd");
         let dir = test_temp_dir();
         std::fs::create_dir_all(&dir).expect("temp dir");
@@ -876,6 +1335,7 @@ mod tests {
     async fn import_export_round_trip_persists_envelope() {
         let _guard = test_lock().await;
         let _ = crate::shared::app_data_dir::reset_app_data_dir();
+        discard_cache().await;
         let prev = std::env::current_dir().expect("cwd");
         let dir = test_temp_dir();
         std::fs::create_dir_all(&dir).expect("temp dir");
@@ -900,6 +1360,7 @@ mod tests {
     async fn import_rejects_version_mismatch_and_unknown_fields() {
         let _guard = test_lock().await;
         let _ = crate::shared::app_data_dir::reset_app_data_dir();
+        discard_cache().await;
         let prev = std::env::current_dir().expect("cwd");
         let dir = test_temp_dir();
         std::fs::create_dir_all(&dir).expect("temp dir");
@@ -965,6 +1426,7 @@ mod tests {
     async fn reset_settings_writes_default_envelope() {
         let _guard = test_lock().await;
         let _ = crate::shared::app_data_dir::reset_app_data_dir();
+        discard_cache().await;
         let prev = std::env::current_dir().expect("cwd");
         let dir = test_temp_dir();
         std::fs::create_dir_all(&dir).expect("temp dir");
@@ -992,6 +1454,7 @@ mod tests {
     async fn update_config_bool_and_theme_are_persisted_atomically() {
         let _guard = test_lock().await;
         let _ = crate::shared::app_data_dir::reset_app_data_dir();
+        discard_cache().await;
         let prev = std::env::current_dir().expect("cwd");
         let dir = test_temp_dir();
         std::fs::create_dir_all(&dir).expect("temp dir");
@@ -1019,4 +1482,134 @@ mod tests {
 
         std::env::set_current_dir(prev).expect("restore cwd");
     }
+
+    /// 在独立临时目录下准备 config.json 路径（不切换工作目录）。
+    fn test_config_path() -> PathBuf {
+        let dir = test_temp_dir();
+        std::fs::create_dir_all(&dir).expect("temp dir");
+        dir.join("config.json")
+    }
+
+    #[tokio::test]
+    async fn reload_config_picks_up_external_edits() {
+        let _guard = test_lock().await;
+        let path = test_config_path();
+
+        persist_envelope_at(&path, &default_settings_envelope())
+            .await
+            .expect("reset");
+        assert!(!reload_config_at(&path).await.expect("reload unchanged"));
+
+        std::fs::write(&path, envelope_payload()).expect("external write");
+        assert!(reload_config_at(&path).await.expect("reload changed"));
+        assert!(
+            cached_envelope_at(&path, Duration::MAX)
+                .await
+                .backend
+                .auto_login
+        );
+    }
+
+    #[tokio::test]
+    async fn reload_config_keeps_cache_when_the_file_is_invalid() {
+        let _guard = test_lock().await;
+        let path = test_config_path();
+
+        std::fs::write(&path, envelope_payload()).expect("seed");
+        assert!(reload_config_at(&path).await.is_ok());
+        assert!(
+            cached_envelope_at(&path, Duration::MAX)
+                .await
+                .backend
+                .auto_login
+        );
+
+        std::fs::write(&path, "{ \"schema_version\": 1, ").expect("partial write");
+        assert!(reload_config_at(&path).await.is_err());
+        assert!(
+            cached_envelope_at(&path, Duration::MAX)
+                .await
+                .backend
+                .auto_login
+        );
+        assert_eq!(
+            std::fs::read_to_string(&path).expect("disk"),
+            "{ \"schema_version\": 1, "
+        );
+    }
+
+    #[tokio::test]
+    async fn cache_is_served_until_the_ttl_expires() {
+        let _guard = test_lock().await;
+        let path = test_config_path();
+
+        std::fs::write(&path, envelope_payload()).expect("seed");
+        assert!(
+            cached_envelope_at(&path, Duration::MAX)
+                .await
+                .backend
+                .auto_login
+        );
+
+        let default_json = format_envelope_json(&default_settings_envelope()).expect("json");
+        std::fs::write(&path, default_json).expect("external write");
+        assert!(
+            cached_envelope_at(&path, Duration::MAX)
+                .await
+                .backend
+                .auto_login
+        );
+        assert_eq!(
+            read_config_sync_at(&path).map(|envelope| envelope.backend.auto_login),
+            Some(true)
+        );
+
+        assert!(
+            !cached_envelope_at(&path, Duration::ZERO)
+                .await
+                .backend
+                .auto_login
+        );
+    }
+
+    #[tokio::test]
+    async fn dirty_cache_wins_over_disk_until_flushed() {
+        let _guard = test_lock().await;
+        let path = test_config_path();
+
+        persist_envelope_at(&path, &default_settings_envelope())
+            .await
+            .expect("reset");
+        let mut envelope = default_settings_envelope();
+        envelope.backend.auto_login = true;
+        schedule_persist_envelope_at(path.clone(), envelope, Duration::from_secs(3600))
+            .await
+            .expect("schedule");
+
+        let disk = parse_settings_import_envelope(&std::fs::read_to_string(&path).expect("disk"))
+            .expect("disk envelope");
+        assert!(!disk.backend.auto_login);
+        assert!(
+            cached_envelope_at(&path, Duration::ZERO)
+                .await
+                .backend
+                .auto_login
+        );
+
+        // 重新加载会先落盘未 flush 的修改，而不是用磁盘内容覆盖它。
+        assert!(!reload_config_at(&path).await.expect("reload"));
+        let disk = parse_settings_import_envelope(&std::fs::read_to_string(&path).expect("disk"))
+            .expect("disk envelope");
+        assert!(disk.backend.auto_login);
+        assert_no_temp_files(path.parent().expect("dir"));
+    }
+
+    #[test]
+    fn only_old_config_layout_counts_as_legacy() {
+        assert!(!is_legacy_config(""));
+        assert!(!is_legacy_config("not json"));
+        assert!(is_legacy_config(
+            r#"{"auto_login":true,"close_to_tray":false,"server_list":[]}"#
+        ));
+    }
 }
@@ -1,27 +1,33 @@
 //! settings｜数据适配器：config_store_port_adapter。
 //!
-//! 本适配器负责两件事：
+//! 本适配器负责三件事：
 //! 1. 将 config_store 的 async 函数适配为 ConfigStorePort trait
 //! 2. 同步 close_to_tray 内存缓存（CloseToTrayState），
 //!    确保 di/commands 层仅做参数透传和错误规范化。
+//! 3. 监听 config.json 的外部修改（手动编辑、同步工具覆盖）并刷新配置缓存。
 
-use std::sync::OnceLock;
-use std::sync::atomic::Ordering;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex, OnceLock};
+use std::time::Duration;
 
-use tauri::Manager;
+use notify::{EventKind, RecommendedWatcher, RecursiveMode, Watcher};
 
-use crate::features::settings::data::config_store::{Config, config_file_path};
+use crate::features::settings::data::config_store::config_file_path;
 use crate::features::settings::domain::ports::config_store_port::{
     ConfigStoreFuture, ConfigStorePort,
 };
-use crate::features::settings::domain::settings_schema::SettingsImportEnvelopeV1;
-use crate::shared::close_to_tray_state::CloseToTrayState;
 
 use super::config_store;
 
-/// 缓存 AppHandle 用于在 data 层同步 close_to_tray 内存缓存，
-/// 避免 di/commands 层需要感知缓存同步逻辑。
-static APP_HANDLE: OnceLock<tauri::AppHandle> = OnceLock::new();
+/// close_to_tray 内存缓存（与 Tauri 托管的 `CloseToTrayState` 共享同一原子值），
+/// 在 data 层同步，避免 di/commands 层需要感知缓存同步逻辑；未初始化时（测试、命令行）不同步。
+static CLOSE_TO_TRAY_CACHE: OnceLock<Arc<AtomicBool>> = OnceLock::new();
+
+/// 配置文件监听器（保持存活即持续监听）。
+static CONFIG_WATCHER: OnceLock<Mutex<Option<RecommendedWatcher>>> = OnceLock::new();
+
+/// 外部修改的合并窗口（原子写入会产生多次事件）。
+const CONFIG_WATCH_DEBOUNCE: Duration = Duration::from_millis(200);
 
 #[derive(Debug, Default, Clone, Copy)]
 pub struct ConfigStorePortAdapter;
@@ -32,30 +38,21 @@ impl ConfigStorePortAdapter {
         &ADAPTER
     }
 
-    /// 初始化适配器的 AppHandle 引用（需在 Tauri setup 期间调用一次）。
-    pub fn init_app_handle(app_handle: &tauri::AppHandle) {
-        let _ = APP_HANDLE.set(app_handle.clone());
+    /// 注册 close_to_tray 内存缓存（需在 Tauri setup 期间调用一次）。
+    pub fn init_close_to_tray_cache(cache: Arc<AtomicBool>) {
+        let _ = CLOSE_TO_TRAY_CACHE.set(cache);
     }
 }
 
 impl ConfigStorePortAdapter {
-    /// 从磁盘 config.json 重新读取 close_to_tray 并同步到 Tauri 托管状态。
+    /// 经 config_store 重新读取 close_to_tray 并同步到内存缓存。
     ///
     /// 用于 import_settings / reset_settings 等批量写入后刷新缓存。
-    /// 优先解析信封格式（迁移后），回退到旧版 Config 格式以兼容未迁移文件。
-    pub fn sync_close_to_tray_cache(app_handle: &tauri::AppHandle) {
-        if let Some(state) = app_handle.try_state::<CloseToTrayState>() {
-            let path = config_file_path();
-            let value = std::fs::read_to_string(&path)
-                .ok()
-                .and_then(|raw| {
-                    serde_json::from_str::<SettingsImportEnvelopeV1>(&raw)
-                        .map(|e| e.backend.close_to_tray)
-                        .or_else(|_| serde_json::from_str::<Config>(&raw).map(|c| c.close_to_tray))
-                        .ok()
-                })
-                .unwrap_or(true);
-            state.0.store(value, Ordering::SeqCst);
+    pub fn sync_close_to_tray_cache() {
+        if let Some(cache) = CLOSE_TO_TRAY_CACHE.get() {
+            let value = config_store::read_config_sync()
+                .is_none_or(|envelope| envelope.backend.close_to_tray);
+            cache.store(value, Ordering::SeqCst);
             tracing::info!(action = "settings_close_to_tray_synced", value = value);
         }
     }
@@ -64,14 +61,77 @@ impl ConfigStorePortAdapter {
     ///
     /// 用于 update_config_bool(key="close_to_tray") ——
     /// usecase 已将值写入磁盘，此方法仅同步内存缓存。
-    pub fn notify_close_to_tray_changed(app_handle: &tauri::AppHandle, value: bool) {
-        if let Some(state) = app_handle.try_state::<CloseToTrayState>() {
-            state.0.store(value, Ordering::SeqCst);
+    pub fn notify_close_to_tray_changed(value: bool) {
+        if let Some(cache) = CLOSE_TO_TRAY_CACHE.get() {
+            cache.store(value, Ordering::SeqCst);
             tracing::info!(action = "settings_close_to_tray_synced", value = value);
         }
     }
 }
 
+impl ConfigStorePortAdapter {
+    /// 从磁盘重新加载配置；配置有变化时同步 close_to_tray 内存缓存。
+    async fn reload_and_sync() -> anyhow::Result<bool> {
+        let changed = config_store::reload_config().await?;
+        if changed {
+            Self::sync_close_to_tray_cache();
+        }
+        Ok(changed)
+    }
+
+    /// 开始监听 config.json 的外部修改（重复调用无副作用）。
+    ///
+    /// # 说明
+    /// 监听的是所在目录：原子写入通过 rename 替换文件，直接监听文件会在第一次替换后失效。
+    /// 本进程自身的写入重新加载后内容一致，不会被视为变化。
+    pub fn watch_config_file() -> anyhow::Result<()> {
+        let slot = CONFIG_WATCHER.get_or_init(|| Mutex::new(None));
+        let mut guard = slot.lock().unwrap_or_else(|p| p.into_inner());
+        if guard.is_some() {
+            return Ok(());
+        }
+        let config_path = config_file_path();
+        let dir = config_path
+            .parent()
+            .map(std::path::Path::to_path_buf)
+            .ok_or_else(|| anyhow::anyhow!("config path has no parent directory"))?;
+
+        let (tx, mut rx) = tokio::sync::mpsc::unbounded_channel::<()>();
+        let mut watcher =
+            notify::recommended_watcher(move |res: notify::Result<notify::Event>| match res {
+                Ok(event) if !matches!(event.kind, EventKind::Access(_)) => {
+                    if event
+                        .paths
+                        .iter()
+                        .any(|p| p.file_name() == config_path.file_name())
+                    {
+                        let _ = tx.send(());
+                    }
+                }
+                Ok(_) => {}
+                Err(e) => {
+                    tracing::warn!(action = "settings_config_watch_error", error = %e);
+                }
+            })?;
+        watcher.watch(&dir, RecursiveMode::NonRecursive)?;
+        *guard = Some(watcher);
+
+        tauri::async_runtime::spawn(async move {
+            while rx.recv().await.is_some() {
+                tokio::time::sleep(CONFIG_WATCH_DEBOUNCE).await;
+                while rx.try_recv().is_ok() {}
+                match Self::reload_and_sync().await {
+                    Ok(true) => tracing::info!(action = "settings_config_reloaded_external"),
+                    Ok(false) => {}
+                    Err(e) => tracing::warn!(action = "settings_config_reload_failed", error = %e),
+                }
+            }
+        });
+        tracing::info!(action = "settings_config_watch_started", path = %dir.display());
+        Ok(())
+    }
+}
+
 impl ConfigStorePort for ConfigStorePortAdapter {
     fn get_config<'a>(&'a self) -> ConfigStoreFuture<'a, String> {
         Box::pin(async { Ok(config_store::get_config().await) })
@@ -85,9 +145,7 @@ impl ConfigStorePort for ConfigStorePortAdapter {
         Box::pin(async move {
             config_store::import_settings(raw).await?;
             // 导入后同步 close_to_tray 内存缓存（data 层职责，避免 di/commands 感知缓存逻辑）。
-            if let Some(app_handle) = APP_HANDLE.get() {
-                Self::sync_close_to_tray_cache(app_handle);
-            }
+            Self::sync_close_to_tray_cache();
             Ok(())
         })
     }
@@ -96,9 +154,7 @@ impl ConfigStorePort for ConfigStorePortAdapter {
         Box::pin(async {
             config_store::reset_settings().await?;
             // 重置后同步 close_to_tray 内存缓存。
-            if let Some(app_handle) = APP_HANDLE.get() {
-                Self::sync_close_to_tray_cache(app_handle);
-            }
+            Self::sync_close_to_tray_cache();
             Ok(())
         })
     }
@@ -142,10 +198,8 @@ impl ConfigStorePort for ConfigStorePortAdapter {
         Box::pin(async move {
             config_store::update_config_bool(key.clone(), value).await?;
             // 更新 close_to_tray 时同步内存缓存（data 层职责）。
-            if key == "close_to_tray"
-                && let Some(app_handle) = APP_HANDLE.get()
-            {
-                Self::notify_close_to_tray_changed(app_handle, value);
+            if key == "close_to_tray" {
+                Self::notify_close_to_tray_changed(value);
             }
             Ok(())
         })
@@ -158,4 +212,8 @@ impl ConfigStorePort for ConfigStorePortAdapter {
     fn update_config_string<'a>(&'a self, key: String, value: String) -> ConfigStoreFuture<'a, ()> {
         Box::pin(async move { config_store::update_config_string(key, value).await })
     }
+
+    fn reload_config<'a>(&'a self) -> ConfigStoreFuture<'a, bool> {
+        Box::pin(Self::reload_and_sync())
+    }
 }
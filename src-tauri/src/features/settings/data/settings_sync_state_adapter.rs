@@ -0,0 +1,79 @@
+//! settings｜数据适配器：settings_sync_state_adapter。
+//!
+//! 约定：注释中文，日志英文（tracing）。
+//!
+//! 同步状态单独保存在 `settings-sync.json`，不写入 config.json，
+//! 避免导出/导入 settings 时把设备 id 带到其它机器上。
+
+use std::path::PathBuf;
+
+use anyhow::Context;
+
+use crate::features::settings::domain::ports::settings_sync_state_port::{
+    SettingsSyncState, SettingsSyncStateFuture, SettingsSyncStatePort,
+};
+
+fn sync_state_file_path() -> PathBuf {
+    crate::shared::app_data_dir::get_app_data_dir()
+        .map(|dir| dir.join("settings-sync.json"))
+        .unwrap_or_else(|_| PathBuf::from("./settings-sync.json"))
+}
+
+#[derive(Debug, Default, Clone, Copy)]
+pub struct SettingsSyncStateAdapter;
+
+impl SettingsSyncStateAdapter {
+    pub fn shared() -> &'static Self {
+        static ADAPTER: SettingsSyncStateAdapter = SettingsSyncStateAdapter;
+        &ADAPTER
+    }
+}
+
+async fn load_state_impl() -> anyhow::Result<SettingsSyncState> {
+    let path = sync_state_file_path();
+    let mut state = match tokio::fs::read_to_string(&path).await {
+        Ok(raw) => serde_json::from_str::<SettingsSyncState>(&raw).unwrap_or_else(|error| {
+            tracing::warn!(
+                action = "settings_sync_state_parse_failed",
+                path = %path.display(),
+                error = %error
+            );
+            SettingsSyncState::default()
+        }),
+        Err(error) if error.kind() == std::io::ErrorKind::NotFound => SettingsSyncState::default(),
+        Err(error) => return Err(error.into()),
+    };
+    if state.device_id.trim().is_empty() {
+        state.device_id = uuid::Uuid::new_v4().to_string();
+        save_state_impl(&state).await?;
+    }
+    Ok(state)
+}
+
+async fn save_state_impl(state: &SettingsSyncState) -> anyhow::Result<()> {
+    let path = sync_state_file_path();
+    if let Some(dir) = path.parent() {
+        tokio::fs::create_dir_all(dir)
+            .await
+            .with_context(|| format!("Failed to create dir: {}", dir.display()))?;
+    }
+    let json = serde_json::to_string_pretty(state)?;
+    let tmp = path.with_extension("json.tmp");
+    tokio::fs::write(&tmp, json)
+        .await
+        .with_context(|| format!("Failed to write sync state: {}", tmp.display()))?;
+    tokio::fs::rename(&tmp, &path)
+        .await
+        .with_context(|| format!("Failed to replace sync state: {}", path.display()))?;
+    Ok(())
+}
+
+impl SettingsSyncStatePort for SettingsSyncStateAdapter {
+    fn load_state<'a>(&'a self) -> SettingsSyncStateFuture<'a, SettingsSyncState> {
+        Box::pin(load_state_impl())
+    }
+
+    fn save_state<'a>(&'a self, state: SettingsSyncState) -> SettingsSyncStateFuture<'a, ()> {
+        Box::pin(async move { save_state_impl(&state).await })
+    }
+}
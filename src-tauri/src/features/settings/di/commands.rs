@@ -5,9 +5,15 @@
 //! 本模块仅做参数透传 + 错误规范化。
 //! close_to_tray 的缓存同步已下沉到 ConfigStorePortAdapter（data 层）。
 
+use crate::features::network::data::http_client::ReqwestApiRequestAdapter;
 use crate::features::settings::data::config_store_port_adapter::ConfigStorePortAdapter;
+use crate::features::settings::data::settings_sync_state_adapter::SettingsSyncStateAdapter;
 use crate::features::settings::usecases::config_usecases;
+use crate::features::settings::usecases::settings_sync_usecases::{
+    self, SettingsSyncOutcome, SettingsSyncTarget,
+};
 use crate::shared::error::{CommandResult, to_command_error};
+use crate::shared::feature_flags::{self, SETTINGS_SYNC};
 
 /// 获取应用配置文件的原始 JSON 字符串。
 ///
@@ -72,6 +78,23 @@ pub async fn reset_settings() -> CommandResult<()> {
         })
 }
 
+/// 从磁盘重新加载配置（配置通常只在启动时读取一次，外部修改后可手动刷新）。
+///
+/// # 返回值
+/// 配置是否发生变化。
+#[tauri::command]
+pub async fn reload_config() -> CommandResult<bool> {
+    config_usecases::reload_config(ConfigStorePortAdapter::shared())
+        .await
+        .map_err(|e| {
+            to_command_error(
+                "SETTINGS_RELOAD_CONFIG_FAILED",
+                "error.settings_reload_config_failed",
+                e,
+            )
+        })
+}
+
 /// 读取 bool 类型配置值（顶层字段）。
 ///
 /// # 参数
@@ -304,3 +327,52 @@ pub async fn update_config_string(key: String, value: String) -> CommandResult<(
             )
         })
 }
+
+/// 立即与服务端 KV 同步白名单设置。
+///
+/// # 参数
+/// - `server_socket`：同步目标服务器（可选；缺省取配置中的第一个服务器）。
+/// - `tls_policy`/`tls_fingerprint`：TLS 相关参数（可选）。
+///
+/// # 返回值
+/// 返回本次同步动作（disabled/unchanged/pushed/pulled）；`settings_sync` 未启用时为 disabled。
+#[tauri::command]
+pub async fn sync_settings_now(
+    server_socket: Option<String>,
+    tls_policy: Option<String>,
+    tls_fingerprint: Option<String>,
+) -> CommandResult<SettingsSyncOutcome> {
+    if !feature_flags::is_feature_enabled(SETTINGS_SYNC).await {
+        return Ok(SettingsSyncOutcome::Disabled);
+    }
+    let api_request_port = ReqwestApiRequestAdapter::shared();
+    settings_sync_usecases::sync_settings_now(
+        SettingsSyncTarget {
+            server_socket,
+            tls_policy,
+            tls_fingerprint,
+        },
+        ConfigStorePortAdapter::shared(),
+        api_request_port.as_ref(),
+        SettingsSyncStateAdapter::shared(),
+    )
+    .await
+    .map_err(|e| to_command_error("SETTINGS_SYNC_FAILED", "error.settings_sync_failed", e))
+}
+
+/// 启用/停用设置同步（默认关闭）。
+///
+/// # 参数
+/// - `enabled`：是否启用。
+#[tauri::command]
+pub async fn set_settings_sync_enabled(enabled: bool) -> CommandResult<()> {
+    settings_sync_usecases::set_settings_sync_enabled(enabled, SettingsSyncStateAdapter::shared())
+        .await
+        .map_err(|e| {
+            to_command_error(
+                "SETTINGS_SYNC_TOGGLE_FAILED",
+                "error.settings_sync_toggle_failed",
+                e,
+            )
+        })
+}
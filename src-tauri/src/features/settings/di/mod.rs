@@ -4,3 +4,33 @@
 //!
 //! 约定：注释中文，日志英文（tracing）。
 pub mod commands;
+
+use crate::app::registry::{CommandRegistration, CommandRegistry, command_set};
+
+/// 设置命令注册。
+pub struct SettingsCommands;
+
+impl CommandRegistration for SettingsCommands {
+    fn register(registry: &mut CommandRegistry) {
+        registry.add(command_set!(crate::features::settings::di::commands => [
+            get_config,
+            export_settings,
+            import_settings,
+            reset_settings,
+            reload_config,
+            get_config_bool,
+            get_config_u32,
+            get_config_u64,
+            get_config_string,
+            get_server_config_string,
+            get_server_config_u32,
+            get_server_config_u64,
+            get_server_config_bool,
+            update_config_bool,
+            update_config_u32,
+            update_config_string,
+            sync_settings_now,
+            set_settings_sync_enabled,
+        ]));
+    }
+}
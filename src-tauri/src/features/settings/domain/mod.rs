@@ -7,3 +7,4 @@
 // Keep this free of Tauri/IO dependencies.
 pub mod ports;
 pub mod settings_schema;
+pub mod settings_sync;
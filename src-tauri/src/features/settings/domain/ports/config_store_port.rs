@@ -24,4 +24,6 @@ pub trait ConfigStorePort: Send + Sync {
     fn update_config_bool<'a>(&'a self, key: String, value: bool) -> ConfigStoreFuture<'a, ()>;
     fn update_config_u32<'a>(&'a self, key: String, value: u32) -> ConfigStoreFuture<'a, ()>;
     fn update_config_string<'a>(&'a self, key: String, value: String) -> ConfigStoreFuture<'a, ()>;
+    /// 从磁盘重新加载配置；返回配置是否发生变化。
+    fn reload_config<'a>(&'a self) -> ConfigStoreFuture<'a, bool>;
 }
@@ -3,3 +3,4 @@
 //! 说明：定义 settings 用例层依赖的输出端口。
 
 pub mod config_store_port;
+pub mod settings_sync_state_port;
@@ -0,0 +1,28 @@
+//! settings｜领域端口：settings_sync_state_port。
+
+use std::future::Future;
+use std::pin::Pin;
+
+use serde::{Deserialize, Serialize};
+
+use crate::features::settings::domain::settings_sync::SettingsSyncDocument;
+
+pub type SettingsSyncStateFuture<'a, T> =
+    Pin<Box<dyn Future<Output = anyhow::Result<T>> + Send + 'a>>;
+
+/// 本机同步状态（不参与同步本身）。
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Default)]
+#[serde(rename_all = "camelCase", default)]
+pub struct SettingsSyncState {
+    /// 是否启用同步（默认关闭）。
+    pub enabled: bool,
+    /// 本机设备 id（首次加载时生成）。
+    pub device_id: String,
+    /// 上次同步完成时的文档（用于检测本地修改并递增时钟）。
+    pub last_document: Option<SettingsSyncDocument>,
+}
+
+pub trait SettingsSyncStatePort: Send + Sync {
+    fn load_state<'a>(&'a self) -> SettingsSyncStateFuture<'a, SettingsSyncState>;
+    fn save_state<'a>(&'a self, state: SettingsSyncState) -> SettingsSyncStateFuture<'a, ()>;
+}
@@ -2,6 +2,8 @@
 //!
 //! 约定：注释中文，日志英文（tracing）。
 
+use std::collections::BTreeMap;
+
 use anyhow::Context;
 use serde::{Deserialize, Serialize};
 
@@ -167,6 +169,126 @@ pub struct SettingsBackendStateV1 {
     pub global_dnd: bool,
     pub server_port: Option<u16>,
     pub server_list: Vec<SettingsServerConfigV1>,
+    /// 消息翻译 HTTP 端点（未配置时 `http` provider 不可用）。
+    #[serde(default)]
+    pub translation_endpoint: Option<String>,
+    /// 本地 feature flag 覆盖（优先级高于服务端下发）。
+    #[serde(default)]
+    pub feature_flags: BTreeMap<String, bool>,
+    /// 命令错误是否以旧版 `[CODE] message` 字符串返回（兼容未升级的前端）。
+    #[serde(default)]
+    pub legacy_string_errors: bool,
+    /// 标准 emoji 肤色偏好（0 = 默认，1..=5 对应各肤色变体）。
+    #[serde(default)]
+    pub emoji_skin_tone: u32,
+    /// 是否向服务端声明支持大帧 zstd 压缩（连接建立时读取）。
+    #[serde(default)]
+    pub frame_compression: bool,
+    /// 兼容开关：TCP 读循环额外发出原始字节 `tcp-message` 事件（连接建立时读取）。
+    #[serde(default)]
+    pub tcp_legacy_message_events: bool,
+    /// 启动时最小化主窗口（未通过命令行指定启动方式时生效）。
+    #[serde(default)]
+    pub start_minimized: bool,
+    /// 启动时只驻留托盘、不显示主窗口（优先于 `start_minimized`）。
+    #[serde(default)]
+    pub start_hidden: bool,
+    /// 开发者抓包模式：允许 `start_frame_capture` 记录收发帧。
+    #[serde(default)]
+    pub frame_capture: bool,
+    /// 插件连续失败达到该次数后自动回滚到 known-good 版本（0 = 关闭）。
+    #[serde(default)]
+    pub plugin_auto_rollback_failures: u32,
+    /// 是否定期自动清理插件旧版本。
+    #[serde(default)]
+    pub plugin_auto_prune: bool,
+    /// 自动清理时除当前/上一个版本外额外保留的最新版本数。
+    #[serde(default)]
+    pub plugin_auto_prune_keep: u32,
+    /// 插件开发者模式：允许 `plugins_dev_link` 挂载本地目录并监听热重载。
+    #[serde(default)]
+    pub plugin_dev_mode: bool,
+    /// 允许启动插件资源的本地回环 HTTP 服务（`http://127.0.0.1:<port>/<token>/...`，仅供插件作者调试，无设置界面）。
+    #[serde(default)]
+    pub plugin_http_server: bool,
+    /// 检测到其它应用全屏时自动隐藏迷你聊天窗。
+    #[serde(default)]
+    pub mini_chat_auto_hide_fullscreen: bool,
+    /// 免打扰每周定时规则。
+    #[serde(default)]
+    pub dnd_schedule: Vec<SettingsDndRuleV1>,
+    /// 免打扰手动覆盖（如“静音 1 小时”）；为空表示跟随 `global_dnd` 与定时规则。
+    #[serde(default)]
+    pub dnd_override: Option<SettingsDndOverrideV1>,
+    /// 通知历史保留天数（0 = 使用默认值）。
+    #[serde(default)]
+    pub notification_history_max_age_days: u32,
+    /// 通知历史最多保留条数（0 = 使用默认值）。
+    #[serde(default)]
+    pub notification_history_max_count: u32,
+    /// 是否允许把本地消息发送给 AI 助手端点（需显式开启）。
+    #[serde(default)]
+    pub assistant_enabled: bool,
+    /// AI 助手端点（OpenAI 兼容 API 的 base URL，如 `http://127.0.0.1:11434/v1`）。
+    #[serde(default)]
+    pub assistant_endpoint: Option<String>,
+    /// AI 助手使用的模型名。
+    #[serde(default)]
+    pub assistant_model: Option<String>,
+    /// 单个连接每秒入站帧数上限，超过即判定为洪泛（0 = 使用默认值）。
+    #[serde(default)]
+    pub inbound_flood_max_per_sec: u32,
+    /// 每个连接保留用于新窗口重放的最近入站帧数（0 = 使用默认值）。
+    #[serde(default)]
+    pub frame_replay_buffer_frames: u32,
+    /// 每个 server 同时进行的后台频道预取请求上限（0 = 使用默认值）。
+    #[serde(default)]
+    pub prefetch_max_concurrent_per_server: u32,
+    /// 同一频道两次发出“正在输入”帧的最短间隔（秒，0 = 使用默认值）。
+    #[serde(default)]
+    pub typing_throttle_secs: u32,
+    /// 消息默认保留天数（0 = 不限制；频道可单独覆盖）。
+    #[serde(default)]
+    pub message_retention_days: u32,
+    /// 每个频道默认最多保留的消息条数（0 = 不限制；频道可单独覆盖）。
+    #[serde(default)]
+    pub message_retention_max_per_channel: u32,
+    /// 慢查询日志阈值（毫秒，0 = 使用默认值）。
+    #[serde(default)]
+    pub db_slow_query_threshold_ms: u32,
+    /// WebView SQL 策略（`allow_all` / `restrict_plugins` / `named_only`；为空表示 `allow_all`）。
+    #[serde(default)]
+    pub db_sql_policy: Option<String>,
+    /// 是否每晚自动备份 system DB 与已打开的 server DB。
+    #[serde(default)]
+    pub scheduled_backup: bool,
+    /// 自动备份保留的每日备份数（0 = 使用默认值）。
+    #[serde(default)]
+    pub backup_keep_daily: u32,
+    /// 自动备份保留的每周备份数（0 = 使用默认值）。
+    #[serde(default)]
+    pub backup_keep_weekly: u32,
+    /// 远程备份目标（WebDAV / S3 兼容）；密码或 secret key 保存在系统凭据存储中。
+    #[serde(default)]
+    pub backup_remote: Option<SettingsBackupRemoteV1>,
+    /// 空闲多少分钟后自动锁定应用（0 = 不自动锁定；需已设置应用锁口令）。
+    #[serde(default)]
+    pub app_lock_idle_minutes: u32,
+    /// 是否允许使用系统生物识别（Windows Hello / Touch ID）解锁应用锁。
+    #[serde(default)]
+    pub app_lock_biometric: bool,
+    /// 隐私模式：通知与托盘预览隐藏消息内容，窗口开启内容保护（防截屏/录屏）。
+    #[serde(default)]
+    pub privacy_mode: bool,
+    /// 是否经 DNS-over-HTTPS 解析服务器主机名（失败时回退系统 DNS）。
+    #[serde(default)]
+    pub dns_over_https: bool,
+    /// DoH 提供方（https URL；`None` 使用默认提供方）。
+    #[serde(default)]
+    pub dns_over_https_provider: Option<String>,
+    /// 是否隐藏未读角标（macOS Dock / Windows 任务栏覆盖图标 / Linux 启动器计数）。
+    #[serde(default)]
+    pub hide_unread_badge: bool,
 }
 
 /// 本地缓存设置快照（版本 1）。
@@ -190,6 +312,51 @@ pub struct SettingsServerConfigV1 {
     pub user_avatar: String,
 }
 
+/// 免打扰定时规则（版本 1）。
+///
+/// `end_minute <= start_minute` 表示跨午夜（如 22:00 → 次日 07:00），归属于开始那一天。
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct SettingsDndRuleV1 {
+    /// 生效的星期（0 = 周一 … 6 = 周日）。
+    pub days: Vec<u8>,
+    /// 开始时间（当天第几分钟，0..1440）。
+    pub start_minute: u16,
+    /// 结束时间（当天第几分钟，0..1440）。
+    pub end_minute: u16,
+    #[serde(default = "default_true")]
+    pub enabled: bool,
+}
+
+fn default_true() -> bool {
+    true
+}
+
+/// 免打扰手动覆盖（版本 1）。
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct SettingsDndOverrideV1 {
+    /// 覆盖期间是否免打扰（`false` 可用于在定时时段内临时恢复通知）。
+    pub active: bool,
+    /// 到期时间（毫秒时间戳）；为空表示直到手动取消。
+    pub until_ms: Option<i64>,
+}
+
+/// 远程备份目标（版本 1）。
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct SettingsBackupRemoteV1 {
+    /// 目标类型：`webdav` / `s3`。
+    pub kind: String,
+    /// WebDAV 目录 URL，或 S3 path-style URL（`https://host/bucket[/prefix]`）。
+    pub url: String,
+    /// WebDAV 用户名或 S3 access key id。
+    pub username: String,
+    /// S3 区域（为空时使用 `us-east-1`；WebDAV 忽略）。
+    #[serde(default)]
+    pub region: Option<String>,
+}
+
 /// 版本化 settings 导入/导出信封（版本 1）。
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase", deny_unknown_fields)]
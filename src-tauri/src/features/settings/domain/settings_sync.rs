@@ -0,0 +1,207 @@
+//! settings｜领域契约：settings_sync。
+//!
+//! 约定：注释中文，日志英文（tracing）。
+//!
+//! 多设备设置同步的数据模型与冲突解决规则（纯逻辑，无 IO）。
+
+use std::cmp::Ordering;
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+/// 服务端 KV 中保存同步文档的 key。
+pub const SETTINGS_SYNC_KV_KEY: &str = "settings-sync";
+
+/// 同步字段值类型。
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SettingsSyncValueKind {
+    Bool,
+    String,
+}
+
+/// 允许跨设备同步的配置白名单（`config_store` 的 key）。
+///
+/// # 说明
+/// - 仅包含纯偏好项；账号、服务器列表、端点地址等一律不出本机；
+/// - 远端文档中出现的非白名单 key 在应用时会被忽略。
+pub const SETTINGS_SYNC_WHITELIST: &[(&str, SettingsSyncValueKind)] = &[
+    ("email_notifications", SettingsSyncValueKind::Bool),
+    ("desktop_notifications", SettingsSyncValueKind::Bool),
+    ("global_dnd", SettingsSyncValueKind::Bool),
+    ("check_for_updates", SettingsSyncValueKind::Bool),
+    ("close_to_tray", SettingsSyncValueKind::Bool),
+    ("theme", SettingsSyncValueKind::String),
+];
+
+/// 判断 key 是否允许同步。
+pub fn is_sync_whitelisted(key: &str) -> bool {
+    SETTINGS_SYNC_WHITELIST.iter().any(|(k, _)| *k == key)
+}
+
+/// 向量时钟：device_id -> 该设备的修改计数。
+pub type VectorClock = BTreeMap<String, u64>;
+
+/// 存放在服务端 KV 中的同步文档。
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct SettingsSyncDocument {
+    /// 白名单字段快照。
+    pub values: BTreeMap<String, Value>,
+    /// 向量时钟。
+    pub clock: VectorClock,
+    /// 最后写入的设备 id。
+    pub device_id: String,
+    /// 最后写入时间（毫秒），仅用于并发冲突时的 LWW 裁决。
+    pub updated_at_ms: i64,
+}
+
+/// 两个向量时钟之间的因果关系。
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ClockOrdering {
+    Equal,
+    Before,
+    After,
+    Concurrent,
+}
+
+/// 比较向量时钟 `a` 相对 `b` 的因果关系。
+pub fn compare_clocks(a: &VectorClock, b: &VectorClock) -> ClockOrdering {
+    let mut a_greater = false;
+    let mut b_greater = false;
+    for key in a.keys().chain(b.keys()) {
+        let av = a.get(key).copied().unwrap_or(0);
+        let bv = b.get(key).copied().unwrap_or(0);
+        match av.cmp(&bv) {
+            Ordering::Greater => a_greater = true,
+            Ordering::Less => b_greater = true,
+            Ordering::Equal => {}
+        }
+    }
+    match (a_greater, b_greater) {
+        (false, false) => ClockOrdering::Equal,
+        (true, false) => ClockOrdering::After,
+        (false, true) => ClockOrdering::Before,
+        (true, true) => ClockOrdering::Concurrent,
+    }
+}
+
+/// 合并两个向量时钟（逐项取最大值）。
+pub fn merge_clocks(a: &VectorClock, b: &VectorClock) -> VectorClock {
+    let mut merged = a.clone();
+    for (key, value) in b {
+        let entry = merged.entry(key.clone()).or_insert(0);
+        *entry = (*entry).max(*value);
+    }
+    merged
+}
+
+/// 冲突裁决结果。
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SettingsSyncWinner {
+    /// 两侧一致，无需任何操作。
+    InSync,
+    /// 本地更新，应推送到服务端。
+    Local,
+    /// 远端更新，应应用到本地。
+    Remote,
+}
+
+/// 按向量时钟 + last-writer-wins 裁决本地与远端文档。
+///
+/// # 说明
+/// - 存在因果先后时以更新的一侧为准；
+/// - 并发修改时比较 `updated_at_ms`，相同再按 `device_id` 字典序决胜，保证各设备结论一致。
+pub fn resolve_sync_winner(
+    local: &SettingsSyncDocument,
+    remote: &SettingsSyncDocument,
+) -> SettingsSyncWinner {
+    match compare_clocks(&local.clock, &remote.clock) {
+        ClockOrdering::Equal => SettingsSyncWinner::InSync,
+        ClockOrdering::After => SettingsSyncWinner::Local,
+        ClockOrdering::Before => SettingsSyncWinner::Remote,
+        ClockOrdering::Concurrent => {
+            let local_key = (local.updated_at_ms, local.device_id.as_str());
+            let remote_key = (remote.updated_at_ms, remote.device_id.as_str());
+            if local_key >= remote_key {
+                SettingsSyncWinner::Local
+            } else {
+                SettingsSyncWinner::Remote
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn clock(entries: &[(&str, u64)]) -> VectorClock {
+        entries.iter().map(|(k, v)| (k.to_string(), *v)).collect()
+    }
+
+    fn doc(entries: &[(&str, u64)], device: &str, at: i64) -> SettingsSyncDocument {
+        SettingsSyncDocument {
+            values: BTreeMap::new(),
+            clock: clock(entries),
+            device_id: device.to_string(),
+            updated_at_ms: at,
+        }
+    }
+
+    #[test]
+    fn compare_clocks_detects_causality() {
+        assert_eq!(
+            compare_clocks(&clock(&[("a", 1)]), &clock(&[("a", 1)])),
+            ClockOrdering::Equal
+        );
+        assert_eq!(
+            compare_clocks(&clock(&[("a", 2)]), &clock(&[("a", 1)])),
+            ClockOrdering::After
+        );
+        assert_eq!(
+            compare_clocks(&clock(&[("a", 1)]), &clock(&[("a", 1), ("b", 1)])),
+            ClockOrdering::Before
+        );
+        assert_eq!(
+            compare_clocks(&clock(&[("a", 2)]), &clock(&[("a", 1), ("b", 1)])),
+            ClockOrdering::Concurrent
+        );
+    }
+
+    #[test]
+    fn resolve_sync_winner_uses_lww_for_concurrent_edits() {
+        let local = doc(&[("a", 2)], "a", 200);
+        let remote = doc(&[("a", 1), ("b", 1)], "b", 100);
+        assert_eq!(
+            resolve_sync_winner(&local, &remote),
+            SettingsSyncWinner::Local
+        );
+
+        let remote_newer = doc(&[("a", 1), ("b", 1)], "b", 300);
+        assert_eq!(
+            resolve_sync_winner(&local, &remote_newer),
+            SettingsSyncWinner::Remote
+        );
+
+        let remote_dominates = doc(&[("a", 2), ("b", 1)], "b", 0);
+        assert_eq!(
+            resolve_sync_winner(&local, &remote_dominates),
+            SettingsSyncWinner::Remote
+        );
+    }
+
+    #[test]
+    fn merge_clocks_takes_max_per_device() {
+        let merged = merge_clocks(&clock(&[("a", 3), ("b", 1)]), &clock(&[("b", 4), ("c", 2)]));
+        assert_eq!(merged, clock(&[("a", 3), ("b", 4), ("c", 2)]));
+    }
+
+    #[test]
+    fn whitelist_excludes_secrets_and_server_list() {
+        assert!(is_sync_whitelisted("theme"));
+        assert!(!is_sync_whitelisted("server_list"));
+        assert!(!is_sync_whitelisted("auto_login"));
+        assert!(!is_sync_whitelisted("translation_endpoint"));
+    }
+}
@@ -29,6 +29,14 @@ pub async fn reset_settings(config_store_port: &dyn ConfigStorePort) -> anyhow::
     config_store_port.reset_settings().await
 }
 
+/// 从磁盘重新加载配置（手动刷新内存缓存）。
+///
+/// # 返回值
+/// 配置是否发生变化。
+pub async fn reload_config(config_store_port: &dyn ConfigStorePort) -> anyhow::Result<bool> {
+    config_store_port.reload_config().await
+}
+
 /// 读取 bool 类型配置值（顶层字段）。
 ///
 /// # 参数
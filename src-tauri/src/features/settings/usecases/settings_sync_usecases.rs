@@ -0,0 +1,255 @@
+//! settings｜用例层：settings_sync_usecases。
+//!
+//! 约定：注释中文，日志英文（tracing）。
+//!
+//! 通过服务端 KV（`/api/kv/{key}`，经由 `api_request_json`）在多设备之间同步白名单设置。
+
+use std::collections::BTreeMap;
+
+use serde::Serialize;
+use serde_json::Value;
+
+use crate::features::network::domain::ports::api_request_port::ApiRequestPort;
+use crate::features::network::usecases::api_usecases::{self, ApiJsonRequest};
+use crate::features::settings::domain::ports::config_store_port::ConfigStorePort;
+use crate::features::settings::domain::ports::settings_sync_state_port::SettingsSyncStatePort;
+use crate::features::settings::domain::settings_schema::parse_settings_import_envelope;
+use crate::features::settings::domain::settings_sync::{
+    SETTINGS_SYNC_KV_KEY, SETTINGS_SYNC_WHITELIST, SettingsSyncDocument, SettingsSyncValueKind,
+    SettingsSyncWinner, merge_clocks, resolve_sync_winner,
+};
+
+/// 同步目标服务器（缺省时取配置中的第一个服务器）。
+#[derive(Debug, Clone, Default)]
+pub struct SettingsSyncTarget {
+    pub server_socket: Option<String>,
+    pub tls_policy: Option<String>,
+    pub tls_fingerprint: Option<String>,
+}
+
+/// 一次同步的结果（Rust -> 前端）。
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SettingsSyncOutcome {
+    /// 同步未启用，未发起任何请求。
+    Disabled,
+    /// 两端一致。
+    Unchanged,
+    /// 本地较新，已推送到服务端。
+    Pushed,
+    /// 远端较新，已应用到本地。
+    Pulled,
+}
+
+fn now_ms() -> i64 {
+    let millis = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_millis();
+    millis as i64
+}
+
+fn kv_path() -> String {
+    format!("/api/kv/{SETTINGS_SYNC_KV_KEY}")
+}
+
+async fn collect_local_values(
+    config_store_port: &dyn ConfigStorePort,
+) -> anyhow::Result<BTreeMap<String, Value>> {
+    let mut values = BTreeMap::new();
+    for (key, kind) in SETTINGS_SYNC_WHITELIST {
+        let value = match kind {
+            SettingsSyncValueKind::Bool => {
+                Value::Bool(config_store_port.get_config_bool(key.to_string()).await?)
+            }
+            SettingsSyncValueKind::String => {
+                Value::String(config_store_port.get_config_string(key.to_string()).await?)
+            }
+        };
+        values.insert(key.to_string(), value);
+    }
+    Ok(values)
+}
+
+async fn apply_remote_values(
+    values: &BTreeMap<String, Value>,
+    config_store_port: &dyn ConfigStorePort,
+) -> anyhow::Result<()> {
+    for (key, kind) in SETTINGS_SYNC_WHITELIST {
+        let Some(value) = values.get(*key) else {
+            continue;
+        };
+        match (kind, value) {
+            (SettingsSyncValueKind::Bool, Value::Bool(b)) => {
+                config_store_port
+                    .update_config_bool(key.to_string(), *b)
+                    .await?
+            }
+            (SettingsSyncValueKind::String, Value::String(s)) => {
+                config_store_port
+                    .update_config_string(key.to_string(), s.clone())
+                    .await?
+            }
+            _ => {
+                tracing::warn!(action = "settings_sync_remote_value_type_mismatch", key = %key);
+            }
+        }
+    }
+    Ok(())
+}
+
+async fn resolve_server_socket(
+    target: &SettingsSyncTarget,
+    config_store_port: &dyn ConfigStorePort,
+) -> anyhow::Result<String> {
+    if let Some(socket) = target.server_socket.as_deref().map(str::trim)
+        && !socket.is_empty()
+    {
+        return Ok(socket.to_string());
+    }
+    let raw = config_store_port.get_config().await?;
+    let envelope = parse_settings_import_envelope(&raw)?;
+    envelope
+        .backend
+        .server_list
+        .first()
+        .map(|s| s.server_socket.clone())
+        .ok_or_else(|| anyhow::anyhow!("No server configured for settings sync"))
+}
+
+async fn fetch_remote_document(
+    base: &ApiJsonRequest,
+    api_request_port: &dyn ApiRequestPort,
+) -> anyhow::Result<Option<SettingsSyncDocument>> {
+    let response = api_usecases::api_request_json(
+        ApiJsonRequest {
+            method: "GET".to_string(),
+            path: kv_path(),
+            body: None,
+            ..base.clone()
+        },
+        api_request_port,
+    )
+    .await?;
+    if response.status == 404 {
+        return Ok(None);
+    }
+    if !response.ok {
+        return Err(anyhow::anyhow!(
+            "Settings sync fetch failed with status {}",
+            response.status
+        ));
+    }
+    let Some(value) = response.body.and_then(|b| b.get("value").cloned()) else {
+        return Ok(None);
+    };
+    Ok(Some(serde_json::from_value(value)?))
+}
+
+async fn push_document(
+    base: &ApiJsonRequest,
+    document: &SettingsSyncDocument,
+    api_request_port: &dyn ApiRequestPort,
+) -> anyhow::Result<()> {
+    let response = api_usecases::api_request_json(
+        ApiJsonRequest {
+            method: "PUT".to_string(),
+            path: kv_path(),
+            body: Some(serde_json::json!({ "value": document })),
+            ..base.clone()
+        },
+        api_request_port,
+    )
+    .await?;
+    if !response.ok {
+        return Err(anyhow::anyhow!(
+            "Settings sync push failed with status {}",
+            response.status
+        ));
+    }
+    Ok(())
+}
+
+/// 启用/停用设置同步。
+pub async fn set_settings_sync_enabled(
+    enabled: bool,
+    state_port: &dyn SettingsSyncStatePort,
+) -> anyhow::Result<()> {
+    let mut state = state_port.load_state().await?;
+    state.enabled = enabled;
+    state_port.save_state(state).await
+}
+
+/// 立即执行一次设置同步。
+///
+/// # 参数
+/// - `target`：同步目标服务器与 TLS 参数。
+///
+/// # 返回值
+/// - `Ok(SettingsSyncOutcome)`：本次同步的动作。
+/// - `Err(anyhow::Error)`：读取配置、请求服务端或写回失败原因。
+///
+/// # 说明
+/// - 本地白名单值与上次同步快照不同即视为本地修改，递增本机时钟分量；
+/// - 冲突裁决见 `resolve_sync_winner`；推送/拉取后两端时钟取合并值。
+pub async fn sync_settings_now(
+    target: SettingsSyncTarget,
+    config_store_port: &dyn ConfigStorePort,
+    api_request_port: &dyn ApiRequestPort,
+    state_port: &dyn SettingsSyncStatePort,
+) -> anyhow::Result<SettingsSyncOutcome> {
+    let mut state = state_port.load_state().await?;
+    if !state.enabled {
+        return Ok(SettingsSyncOutcome::Disabled);
+    }
+
+    let values = collect_local_values(config_store_port).await?;
+    let mut local = state.last_document.clone().unwrap_or_default();
+    if state.last_document.is_none() || local.values != values {
+        let counter = local.clock.entry(state.device_id.clone()).or_insert(0);
+        *counter += 1;
+        local.values = values;
+        local.device_id = state.device_id.clone();
+        local.updated_at_ms = now_ms();
+    }
+
+    let base = ApiJsonRequest {
+        server_socket: resolve_server_socket(&target, config_store_port).await?,
+        method: String::new(),
+        path: String::new(),
+        headers: None,
+        body: None,
+        tls_policy: target.tls_policy.clone(),
+        tls_fingerprint: target.tls_fingerprint.clone(),
+    };
+    let remote = fetch_remote_document(&base, api_request_port).await?;
+
+    let outcome = match remote {
+        None => {
+            push_document(&base, &local, api_request_port).await?;
+            SettingsSyncOutcome::Pushed
+        }
+        Some(remote) => match resolve_sync_winner(&local, &remote) {
+            SettingsSyncWinner::InSync => SettingsSyncOutcome::Unchanged,
+            SettingsSyncWinner::Local => {
+                local.clock = merge_clocks(&local.clock, &remote.clock);
+                push_document(&base, &local, api_request_port).await?;
+                SettingsSyncOutcome::Pushed
+            }
+            SettingsSyncWinner::Remote => {
+                apply_remote_values(&remote.values, config_store_port).await?;
+                local = SettingsSyncDocument {
+                    clock: merge_clocks(&local.clock, &remote.clock),
+                    values: collect_local_values(config_store_port).await?,
+                    ..remote
+                };
+                SettingsSyncOutcome::Pulled
+            }
+        },
+    };
+
+    state.last_document = Some(local);
+    state_port.save_state(state).await?;
+    tracing::info!(action = "settings_sync_completed", outcome = ?outcome);
+    Ok(outcome)
+}
@@ -0,0 +1,112 @@
+//! translation｜数据适配器：http_translation_provider。
+//!
+//! 约定：注释中文，日志英文（tracing）。
+
+use std::sync::OnceLock;
+use std::time::Duration;
+
+use anyhow::Context;
+use serde::{Deserialize, Serialize};
+
+use crate::features::settings::get_config_value;
+use crate::features::translation::domain::ports::translation_provider_port::{
+    TranslationProviderFuture, TranslationProviderPort,
+};
+
+/// 翻译端点配置键（`config.json` 中的 `translation_endpoint`）。
+pub const TRANSLATION_ENDPOINT_CONFIG_KEY: &str = "translation_endpoint";
+
+const TRANSLATION_HTTP_TIMEOUT: Duration = Duration::from_secs(15);
+
+static HTTP_CLIENT: OnceLock<reqwest::Client> = OnceLock::new();
+
+fn http_client() -> &'static reqwest::Client {
+    HTTP_CLIENT.get_or_init(|| {
+        reqwest::Client::builder()
+            .timeout(TRANSLATION_HTTP_TIMEOUT)
+            .build()
+            .map_err(|e| {
+                tracing::error!(action = "app_translation_http_client_build_failed", error = %e);
+            })
+            .ok()
+            .unwrap_or_default()
+    })
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct HttpTranslateRequest<'a> {
+    text: &'a str,
+    target_lang: &'a str,
+}
+
+#[derive(Debug, Deserialize)]
+struct HttpTranslateResponse {
+    text: String,
+}
+
+/// 通过可配置 HTTP 端点翻译的 provider。
+///
+/// # 说明
+/// - 端点地址从配置项 `translation_endpoint` 读取，未配置时直接报错；
+/// - 协议：`POST {endpoint}`，请求体 `{"text","targetLang"}`，响应体 `{"text"}`。
+#[derive(Debug, Default, Clone, Copy)]
+pub struct HttpTranslationProvider;
+
+impl HttpTranslationProvider {
+    pub fn shared() -> &'static Self {
+        static PROVIDER: HttpTranslationProvider = HttpTranslationProvider;
+        &PROVIDER
+    }
+}
+
+impl TranslationProviderPort for HttpTranslationProvider {
+    fn id(&self) -> &'static str {
+        "http"
+    }
+
+    fn translate<'a>(
+        &'a self,
+        text: String,
+        target_lang: String,
+    ) -> TranslationProviderFuture<'a, String> {
+        Box::pin(async move {
+            let endpoint =
+                get_config_value::<String>(TRANSLATION_ENDPOINT_CONFIG_KEY.to_string()).await;
+            let endpoint = endpoint.trim();
+            if endpoint.is_empty() {
+                return Err(anyhow::anyhow!("Translation endpoint is not configured"));
+            }
+            let url = reqwest::Url::parse(endpoint).context("Invalid translation endpoint")?;
+            if url.scheme() != "http" && url.scheme() != "https" {
+                return Err(anyhow::anyhow!(
+                    "Unsupported translation endpoint scheme: {}",
+                    url.scheme()
+                ));
+            }
+
+            let resp = http_client()
+                .post(url)
+                .json(&HttpTranslateRequest {
+                    text: &text,
+                    target_lang: &target_lang,
+                })
+                .send()
+                .await
+                .context("Translation request failed")?;
+            let status = resp.status();
+            if !status.is_success() {
+                tracing::warn!(
+                    action = "app_translation_http_provider_bad_status",
+                    status = status.as_u16()
+                );
+                return Err(anyhow::anyhow!("Translation endpoint returned {}", status));
+            }
+            let body: HttpTranslateResponse = resp
+                .json()
+                .await
+                .context("Invalid translation response body")?;
+            Ok(body.text)
+        })
+    }
+}
@@ -0,0 +1,36 @@
+//! translation｜数据适配器：local_translation_provider。
+//!
+//! 约定：注释中文，日志英文（tracing）。
+
+use crate::features::translation::domain::ports::translation_provider_port::{
+    TranslationProviderFuture, TranslationProviderPort,
+};
+
+/// 本地模型 provider 占位实现。
+///
+/// # 说明
+/// - 尚未接入真实的本地推理，仅原样返回带语言标记的文本；
+/// - 便于前端在无网络/无 HTTP 端点时联调内联翻译 UI。
+#[derive(Debug, Default, Clone, Copy)]
+pub struct LocalStubTranslationProvider;
+
+impl LocalStubTranslationProvider {
+    pub fn shared() -> &'static Self {
+        static PROVIDER: LocalStubTranslationProvider = LocalStubTranslationProvider;
+        &PROVIDER
+    }
+}
+
+impl TranslationProviderPort for LocalStubTranslationProvider {
+    fn id(&self) -> &'static str {
+        "local"
+    }
+
+    fn translate<'a>(
+        &'a self,
+        text: String,
+        target_lang: String,
+    ) -> TranslationProviderFuture<'a, String> {
+        Box::pin(async move { Ok(format!("[{target_lang}] {text}")) })
+    }
+}
@@ -0,0 +1,8 @@
+//! 模块入口：data。
+//!
+//! 说明：该文件负责导出子模块与组织依赖关系。
+//!
+//! 约定：注释中文，日志英文（tracing）。
+pub mod http_translation_provider;
+pub mod local_translation_provider;
+pub mod translation_store_adapter;
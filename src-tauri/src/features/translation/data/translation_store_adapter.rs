@@ -0,0 +1,107 @@
+//! translation｜数据适配器：translation_store_adapter。
+//!
+//! 约定：注释中文，日志英文（tracing）。
+//!
+//! 原文来自服务端库的 `messages` 表，译文缓存写入同库的 `translations` 表
+//! （由 `shared::db` 的 server 迁移 v2 创建，v11 起以 message + language + provider 为键）。
+
+use sea_orm::{ConnectionTrait, Value};
+
+use crate::features::translation::domain::ports::translation_store_port::{
+    TranslationStoreFuture, TranslationStorePort,
+};
+use crate::features::translation::domain::types::CachedTranslation;
+use crate::shared::db::commands::RawStatement;
+use crate::shared::db::get_db;
+
+fn now_ms() -> i64 {
+    let millis = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_millis();
+    millis as i64
+}
+
+#[derive(Debug, Default, Clone, Copy)]
+pub struct TranslationStoreAdapter;
+
+impl TranslationStoreAdapter {
+    pub fn shared() -> &'static Self {
+        static ADAPTER: TranslationStoreAdapter = TranslationStoreAdapter;
+        &ADAPTER
+    }
+}
+
+impl TranslationStorePort for TranslationStoreAdapter {
+    fn get_message_content<'a>(
+        &'a self,
+        db_key: String,
+        message_id: String,
+    ) -> TranslationStoreFuture<'a, Option<String>> {
+        Box::pin(async move {
+            let db = get_db(&db_key).await?;
+            let stmt = RawStatement::new(
+                "SELECT content FROM messages WHERE id = ?",
+                vec![Value::String(Some(message_id))],
+            );
+            let row = db.connection.query_one(&stmt).await?;
+            Ok(row.and_then(|r| r.try_get::<String>("", "content").ok()))
+        })
+    }
+
+    fn get_cached<'a>(
+        &'a self,
+        db_key: String,
+        message_id: String,
+        target_lang: String,
+        provider: String,
+    ) -> TranslationStoreFuture<'a, Option<CachedTranslation>> {
+        Box::pin(async move {
+            let db = get_db(&db_key).await?;
+            let stmt = RawStatement::new(
+                "SELECT provider, text FROM translations WHERE message_id = ? AND target_lang = ? AND provider = ?",
+                vec![
+                    Value::String(Some(message_id)),
+                    Value::String(Some(target_lang)),
+                    Value::String(Some(provider)),
+                ],
+            );
+            let Some(row) = db.connection.query_one(&stmt).await? else {
+                return Ok(None);
+            };
+            let provider = row.try_get::<String>("", "provider")?;
+            let text = row.try_get::<String>("", "text")?;
+            Ok(Some(CachedTranslation { provider, text }))
+        })
+    }
+
+    fn put_cached<'a>(
+        &'a self,
+        db_key: String,
+        message_id: String,
+        target_lang: String,
+        translation: CachedTranslation,
+    ) -> TranslationStoreFuture<'a, ()> {
+        Box::pin(async move {
+            let db = get_db(&db_key).await?;
+            let stmt = RawStatement::new(
+                r#"
+                INSERT INTO translations (message_id, target_lang, provider, text, created_at)
+                VALUES (?, ?, ?, ?, ?)
+                ON CONFLICT(message_id, target_lang, provider) DO UPDATE SET
+                    text = excluded.text,
+                    created_at = excluded.created_at
+                "#,
+                vec![
+                    Value::String(Some(message_id)),
+                    Value::String(Some(target_lang)),
+                    Value::String(Some(translation.provider)),
+                    Value::String(Some(translation.text)),
+                    Value::BigInt(Some(now_ms())),
+                ],
+            );
+            db.connection.execute(&stmt).await?;
+            Ok(())
+        })
+    }
+}
@@ -0,0 +1,88 @@
+//! translation｜DI/命令入口：commands。
+//!
+//! 约定：注释中文，日志英文（tracing）。
+
+use crate::features::translation::data::http_translation_provider::HttpTranslationProvider;
+use crate::features::translation::data::local_translation_provider::LocalStubTranslationProvider;
+use crate::features::translation::data::translation_store_adapter::TranslationStoreAdapter;
+use crate::features::translation::domain::ports::translation_provider_port::TranslationProviderPort;
+use crate::features::translation::domain::types::{TranslationRateLimited, TranslationResult};
+use crate::features::translation::usecases::translation_usecases::{
+    self, TranslationRateLimiter, normalize_target_lang,
+};
+use crate::shared::error::{CommandResult, command_error, to_command_error};
+use crate::shared::feature_flags::{self, MESSAGE_TRANSLATION};
+
+/// 按 provider id 解析 provider 实现；缺省使用 HTTP 端点。
+fn resolve_provider(provider: Option<&str>) -> Option<&'static dyn TranslationProviderPort> {
+    match provider.map(str::trim).unwrap_or("http") {
+        "" | "http" => Some(HttpTranslationProvider::shared()),
+        "local" => Some(LocalStubTranslationProvider::shared()),
+        _ => None,
+    }
+}
+
+/// 翻译一条本地缓存的消息，供 UI 内联展示。
+///
+/// # 参数
+/// - `db_key`：服务端数据库 key（需已通过 `db_init` 初始化）。
+/// - `message_id`：消息 id。
+/// - `target_lang`：目标语言（例如 `en`、`zh_CN`）。
+/// - `provider`：provider id（`http` / `local`，缺省 `http`）。
+///
+/// # 返回值
+/// - `Ok(TranslationResult)`：译文；`cached=true` 表示命中同一 provider 在 `translations` 表中的缓存。
+/// - `Err(CommandError)`：`message_translation` 未启用、参数非法、限流或 provider 调用失败。
+#[tauri::command]
+pub async fn translate_message(
+    db_key: String,
+    message_id: String,
+    target_lang: String,
+    provider: Option<String>,
+) -> CommandResult<TranslationResult> {
+    if !feature_flags::is_feature_enabled(MESSAGE_TRANSLATION).await {
+        return Err(command_error(
+            "TRANSLATION_DISABLED",
+            "error.translation_disabled",
+        ));
+    }
+    let message_id = message_id.trim().to_string();
+    if message_id.is_empty() {
+        return Err(command_error(
+            "TRANSLATION_MESSAGE_ID_REQUIRED",
+            "error.translation_message_id_required",
+        ));
+    }
+    let target_lang = normalize_target_lang(&target_lang).ok_or_else(|| {
+        command_error(
+            "TRANSLATION_TARGET_LANG_INVALID",
+            "error.translation_target_lang_invalid",
+        )
+    })?;
+    let provider = resolve_provider(provider.as_deref()).ok_or_else(|| {
+        command_error(
+            "TRANSLATION_PROVIDER_UNKNOWN",
+            "error.translation_provider_unknown",
+        )
+    })?;
+
+    translation_usecases::translate_message(
+        db_key,
+        message_id,
+        target_lang,
+        provider,
+        TranslationRateLimiter::shared(),
+        TranslationStoreAdapter::shared(),
+    )
+    .await
+    .map_err(|e| {
+        if e.downcast_ref::<TranslationRateLimited>().is_some() {
+            return to_command_error(
+                "TRANSLATION_RATE_LIMITED",
+                "error.translation_rate_limited",
+                e,
+            );
+        }
+        to_command_error("TRANSLATION_FAILED", "error.translation_failed", e)
+    })
+}
@@ -0,0 +1,19 @@
+//! 模块入口：di。
+//!
+//! 说明：该文件负责导出子模块与组织依赖关系。
+//!
+//! 约定：注释中文，日志英文（tracing）。
+pub mod commands;
+
+use crate::app::registry::{CommandRegistration, CommandRegistry, command_set};
+
+/// 翻译命令注册。
+pub struct TranslationCommands;
+
+impl CommandRegistration for TranslationCommands {
+    fn register(registry: &mut CommandRegistry) {
+        registry.add(command_set!(crate::features::translation::di::commands => [
+            translate_message,
+        ]));
+    }
+}
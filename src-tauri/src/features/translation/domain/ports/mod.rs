@@ -0,0 +1,6 @@
+//! 模块入口：translation/domain/ports。
+//!
+//! 说明：定义 translation 用例层依赖的输出端口。
+
+pub mod translation_provider_port;
+pub mod translation_store_port;
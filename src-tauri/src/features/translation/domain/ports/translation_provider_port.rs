@@ -0,0 +1,19 @@
+//! translation｜领域端口：translation_provider_port。
+
+use std::future::Future;
+use std::pin::Pin;
+
+pub type TranslationProviderFuture<'a, T> =
+    Pin<Box<dyn Future<Output = anyhow::Result<T>> + Send + 'a>>;
+
+/// 翻译 provider（HTTP 服务、本地模型等由数据层适配器实现）。
+pub trait TranslationProviderPort: Send + Sync {
+    /// provider 稳定 id（写入缓存并回传前端）。
+    fn id(&self) -> &'static str;
+    /// 将 `text` 翻译为 `target_lang`。
+    fn translate<'a>(
+        &'a self,
+        text: String,
+        target_lang: String,
+    ) -> TranslationProviderFuture<'a, String>;
+}
@@ -0,0 +1,35 @@
+//! translation｜领域端口：translation_store_port。
+
+use std::future::Future;
+use std::pin::Pin;
+
+use crate::features::translation::domain::types::CachedTranslation;
+
+pub type TranslationStoreFuture<'a, T> =
+    Pin<Box<dyn Future<Output = anyhow::Result<T>> + Send + 'a>>;
+
+/// 翻译存储端口：读取原文并维护 `translations` 缓存表。
+pub trait TranslationStorePort: Send + Sync {
+    /// 读取消息原文；消息不存在时返回 `None`。
+    fn get_message_content<'a>(
+        &'a self,
+        db_key: String,
+        message_id: String,
+    ) -> TranslationStoreFuture<'a, Option<String>>;
+    /// 读取缓存译文（按 message + language + provider 命中）。
+    fn get_cached<'a>(
+        &'a self,
+        db_key: String,
+        message_id: String,
+        target_lang: String,
+        provider: String,
+    ) -> TranslationStoreFuture<'a, Option<CachedTranslation>>;
+    /// 写入（覆盖）缓存译文（provider 取自 `translation.provider`）。
+    fn put_cached<'a>(
+        &'a self,
+        db_key: String,
+        message_id: String,
+        target_lang: String,
+        translation: CachedTranslation,
+    ) -> TranslationStoreFuture<'a, ()>;
+}
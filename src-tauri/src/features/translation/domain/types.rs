@@ -0,0 +1,52 @@
+//! translation｜领域类型：types。
+//!
+//! 约定：注释中文，日志英文（tracing）。
+
+use serde::Serialize;
+
+/// 单条消息的翻译结果（Rust -> 前端）。
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TranslationResult {
+    /// 消息 id。
+    pub message_id: String,
+    /// 目标语言（归一化后的小写标签，例如 `en`、`zh-cn`）。
+    pub target_lang: String,
+    /// 实际使用的 provider id。
+    pub provider: String,
+    /// 译文。
+    pub text: String,
+    /// 是否命中本地 `translations` 缓存。
+    pub cached: bool,
+}
+
+/// 缓存中的译文记录（端口 <-> 用例）。
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CachedTranslation {
+    /// 生成该译文的 provider id。
+    pub provider: String,
+    /// 译文。
+    pub text: String,
+}
+
+/// provider 请求过于频繁（由用例层限流器产生）。
+///
+/// # 说明
+/// 命令层通过 `downcast_ref` 识别该错误并映射为独立错误码，便于前端展示“稍后再试”。
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TranslationRateLimited {
+    /// 距离下一次可用额度的剩余毫秒数。
+    pub retry_after_ms: u64,
+}
+
+impl std::fmt::Display for TranslationRateLimited {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "Translation rate limited, retry after {}ms",
+            self.retry_after_ms
+        )
+    }
+}
+
+impl std::error::Error for TranslationRateLimited {}
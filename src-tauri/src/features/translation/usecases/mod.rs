@@ -0,0 +1,6 @@
+//! 模块入口：usecases。
+//!
+//! 说明：该文件负责导出子模块与组织依赖关系。
+//!
+//! 约定：注释中文，日志英文（tracing）。
+pub mod translation_usecases;
@@ -0,0 +1,330 @@
+//! translation｜用例层：translation_usecases。
+//!
+//! 约定：注释中文，日志英文（tracing）。
+
+use std::collections::{HashMap, VecDeque};
+use std::sync::{Mutex, OnceLock};
+use std::time::{Duration, Instant};
+
+use crate::features::translation::domain::ports::translation_provider_port::TranslationProviderPort;
+use crate::features::translation::domain::ports::translation_store_port::TranslationStorePort;
+use crate::features::translation::domain::types::{
+    CachedTranslation, TranslationRateLimited, TranslationResult,
+};
+
+/// 每个 provider 在窗口期内允许的最大请求数（缓存命中不计入）。
+const TRANSLATION_RATE_LIMIT_MAX: usize = 30;
+/// 限流滑动窗口长度。
+const TRANSLATION_RATE_LIMIT_WINDOW: Duration = Duration::from_secs(60);
+
+/// provider 维度的滑动窗口限流器。
+#[derive(Debug)]
+pub struct TranslationRateLimiter {
+    max: usize,
+    window: Duration,
+    hits: Mutex<HashMap<String, VecDeque<Instant>>>,
+}
+
+impl TranslationRateLimiter {
+    pub fn new(max: usize, window: Duration) -> Self {
+        Self {
+            max,
+            window,
+            hits: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// 进程级共享限流器（命令层使用）。
+    pub fn shared() -> &'static Self {
+        static LIMITER: OnceLock<TranslationRateLimiter> = OnceLock::new();
+        LIMITER.get_or_init(|| {
+            TranslationRateLimiter::new(TRANSLATION_RATE_LIMIT_MAX, TRANSLATION_RATE_LIMIT_WINDOW)
+        })
+    }
+
+    /// 尝试占用一次额度；超限时返回 `TranslationRateLimited`。
+    pub fn try_acquire(&self, provider: &str) -> Result<(), TranslationRateLimited> {
+        let now = Instant::now();
+        let Ok(mut hits) = self.hits.lock() else {
+            // 锁中毒时放行，限流仅是保护性措施，不应阻断翻译功能。
+            return Ok(());
+        };
+        let queue = hits.entry(provider.to_string()).or_default();
+        while let Some(front) = queue.front() {
+            if now.duration_since(*front) >= self.window {
+                queue.pop_front();
+            } else {
+                break;
+            }
+        }
+        if queue.len() >= self.max {
+            let retry_after = queue
+                .front()
+                .map(|front| self.window.saturating_sub(now.duration_since(*front)))
+                .unwrap_or(self.window);
+            return Err(TranslationRateLimited {
+                retry_after_ms: retry_after.as_millis() as u64,
+            });
+        }
+        queue.push_back(now);
+        Ok(())
+    }
+}
+
+/// 归一化语言标签：去空白、转小写、`_` 统一为 `-`。
+pub fn normalize_target_lang(raw: &str) -> Option<String> {
+    let lang = raw.trim().to_ascii_lowercase().replace('_', "-");
+    if lang.is_empty()
+        || lang.len() > 16
+        || !lang.chars().all(|c| c.is_ascii_alphanumeric() || c == '-')
+    {
+        return None;
+    }
+    Some(lang)
+}
+
+/// 翻译一条消息（优先命中同一 provider 的缓存）。
+///
+/// # 参数
+/// - `db_key`：服务端数据库 key（消息与缓存所在库）。
+/// - `message_id`：消息 id。
+/// - `target_lang`：已归一化的目标语言。
+/// - `provider`：翻译 provider。
+/// - `limiter`：限流器（仅在实际调用 provider 时占用额度）。
+///
+/// # 返回值
+/// - `Ok(TranslationResult)`：译文及是否命中缓存。
+/// - `Err(anyhow::Error)`：消息不存在、限流（`TranslationRateLimited`）或 provider 失败。
+pub async fn translate_message(
+    db_key: String,
+    message_id: String,
+    target_lang: String,
+    provider: &dyn TranslationProviderPort,
+    limiter: &TranslationRateLimiter,
+    store: &dyn TranslationStorePort,
+) -> anyhow::Result<TranslationResult> {
+    if let Some(cached) = store
+        .get_cached(
+            db_key.clone(),
+            message_id.clone(),
+            target_lang.clone(),
+            provider.id().to_string(),
+        )
+        .await?
+    {
+        return Ok(TranslationResult {
+            message_id,
+            target_lang,
+            provider: cached.provider,
+            text: cached.text,
+            cached: true,
+        });
+    }
+
+    let content = store
+        .get_message_content(db_key.clone(), message_id.clone())
+        .await?
+        .ok_or_else(|| anyhow::anyhow!("Message not found: {}", message_id))?;
+
+    limiter.try_acquire(provider.id())?;
+
+    let text = provider.translate(content, target_lang.clone()).await?;
+    let cached = CachedTranslation {
+        provider: provider.id().to_string(),
+        text: text.clone(),
+    };
+    if let Err(e) = store
+        .put_cached(db_key, message_id.clone(), target_lang.clone(), cached)
+        .await
+    {
+        // 缓存写入失败不影响本次结果返回。
+        tracing::warn!(action = "app_translation_cache_write_failed", error = %e);
+    }
+
+    Ok(TranslationResult {
+        message_id,
+        target_lang,
+        provider: provider.id().to_string(),
+        text,
+        cached: false,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::features::translation::domain::ports::translation_provider_port::TranslationProviderFuture;
+    use crate::features::translation::domain::ports::translation_store_port::TranslationStoreFuture;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    #[derive(Default)]
+    struct MemoryStore {
+        cache: Mutex<HashMap<(String, String, String), CachedTranslation>>,
+    }
+
+    impl TranslationStorePort for MemoryStore {
+        fn get_message_content<'a>(
+            &'a self,
+            _db_key: String,
+            message_id: String,
+        ) -> TranslationStoreFuture<'a, Option<String>> {
+            Box::pin(async move { Ok((message_id == "m1").then(|| "hello".to_string())) })
+        }
+
+        fn get_cached<'a>(
+            &'a self,
+            _db_key: String,
+            message_id: String,
+            target_lang: String,
+            provider: String,
+        ) -> TranslationStoreFuture<'a, Option<CachedTranslation>> {
+            Box::pin(async move {
+                let cache = self.cache.lock().expect("cache lock");
+                Ok(cache.get(&(message_id, target_lang, provider)).cloned())
+            })
+        }
+
+        fn put_cached<'a>(
+            &'a self,
+            _db_key: String,
+            message_id: String,
+            target_lang: String,
+            translation: CachedTranslation,
+        ) -> TranslationStoreFuture<'a, ()> {
+            Box::pin(async move {
+                let mut cache = self.cache.lock().expect("cache lock");
+                cache.insert(
+                    (message_id, target_lang, translation.provider.clone()),
+                    translation,
+                );
+                Ok(())
+            })
+        }
+    }
+
+    #[derive(Default)]
+    struct CountingProvider {
+        calls: AtomicUsize,
+        id: Option<&'static str>,
+    }
+
+    impl TranslationProviderPort for CountingProvider {
+        fn id(&self) -> &'static str {
+            self.id.unwrap_or("counting")
+        }
+
+        fn translate<'a>(
+            &'a self,
+            text: String,
+            target_lang: String,
+        ) -> TranslationProviderFuture<'a, String> {
+            self.calls.fetch_add(1, Ordering::SeqCst);
+            Box::pin(async move { Ok(format!("{text}@{target_lang}")) })
+        }
+    }
+
+    #[test]
+    fn normalize_target_lang_accepts_bcp47_like_tags() {
+        assert_eq!(normalize_target_lang(" zh_CN "), Some("zh-cn".to_string()));
+        assert_eq!(normalize_target_lang("en"), Some("en".to_string()));
+        assert_eq!(normalize_target_lang(""), None);
+        assert_eq!(normalize_target_lang("en;drop"), None);
+    }
+
+    #[test]
+    fn rate_limiter_rejects_after_max_within_window() {
+        let limiter = TranslationRateLimiter::new(2, Duration::from_secs(60));
+        assert!(limiter.try_acquire("http").is_ok());
+        assert!(limiter.try_acquire("http").is_ok());
+        let err = limiter.try_acquire("http").expect_err("third call limited");
+        assert!(err.retry_after_ms > 0);
+        // 不同 provider 独立计数。
+        assert!(limiter.try_acquire("local").is_ok());
+    }
+
+    #[tokio::test]
+    async fn translate_message_caches_by_message_and_language() {
+        let store = MemoryStore::default();
+        let provider = CountingProvider::default();
+        let limiter = TranslationRateLimiter::new(10, Duration::from_secs(60));
+
+        let first = translate_message(
+            "k".to_string(),
+            "m1".to_string(),
+            "en".to_string(),
+            &provider,
+            &limiter,
+            &store,
+        )
+        .await
+        .expect("first translation");
+        assert!(!first.cached);
+        assert_eq!(first.text, "hello@en");
+
+        let second = translate_message(
+            "k".to_string(),
+            "m1".to_string(),
+            "en".to_string(),
+            &provider,
+            &limiter,
+            &store,
+        )
+        .await
+        .expect("cached translation");
+        assert!(second.cached);
+        assert_eq!(second.provider, "counting");
+        assert_eq!(provider.calls.load(Ordering::SeqCst), 1);
+    }
+
+    #[tokio::test]
+    async fn translate_message_does_not_reuse_another_providers_cache() {
+        let store = MemoryStore::default();
+        let limiter = TranslationRateLimiter::new(10, Duration::from_secs(60));
+        let first = CountingProvider::default();
+        let second = CountingProvider {
+            id: Some("other"),
+            ..CountingProvider::default()
+        };
+
+        for provider in [&first, &second] {
+            let result = translate_message(
+                "k".to_string(),
+                "m1".to_string(),
+                "en".to_string(),
+                provider,
+                &limiter,
+                &store,
+            )
+            .await
+            .expect("translation");
+            assert!(!result.cached);
+            assert_eq!(result.provider, provider.id());
+        }
+        assert_eq!(second.calls.load(Ordering::SeqCst), 1);
+    }
+
+    #[tokio::test]
+    async fn translate_message_surfaces_rate_limit_error() {
+        let store = MemoryStore::default();
+        let provider = CountingProvider::default();
+        let limiter = TranslationRateLimiter::new(1, Duration::from_secs(60));
+
+        for lang in ["en", "fr"] {
+            let result = translate_message(
+                "k".to_string(),
+                "m1".to_string(),
+                lang.to_string(),
+                &provider,
+                &limiter,
+                &store,
+            )
+            .await;
+            if lang == "fr" {
+                let err = result.expect_err("second provider call limited");
+                assert!(err.downcast_ref::<TranslationRateLimited>().is_some());
+            } else {
+                result.expect("first call allowed");
+            }
+        }
+    }
+}
@@ -9,10 +9,11 @@ use std::{
 };
 
 use tauri::menu::{Menu, MenuItem, PredefinedMenuItem};
-use tauri::{AppHandle, Emitter, Runtime, State, image::Image};
+use tauri::{AppHandle, Manager, Runtime, State, image::Image};
 
 use crate::features::tray::domain::tray_i18n::tray_labels;
 use crate::shared::error::{CommandResult, command_error, to_command_error};
+use crate::shared::events::EventBusExt;
 
 const TRAY_ID: &str = "main";
 const BLINK_INTERVAL_MS: u64 = 600;
@@ -48,13 +49,24 @@ impl TrayUnreadState {
     }
 }
 
-/// 设置托盘未读闪烁状态。
+/// 设置托盘未读闪烁状态，并同步未读角标。
+///
+/// # 参数
+/// - `has_unread`：是否存在未读消息。
+/// - `unread_count`：总未读数（可选）；缺省时有未读则保持当前角标，无未读则清除。
 #[tauri::command]
 pub fn set_tray_unread_flashing<R: Runtime>(
     app: AppHandle<R>,
     state: State<'_, TrayUnreadState>,
     has_unread: bool,
+    unread_count: Option<u32>,
 ) -> CommandResult<()> {
+    match (has_unread, unread_count) {
+        (false, _) => crate::shared::badges::set_count(0),
+        (true, Some(count)) => crate::shared::badges::set_count(count),
+        (true, None) => {}
+    }
+
     let _guard = state.lifecycle_lock.lock().map_err(|err| {
         to_command_error(
             "TRAY_UNREAD_LOCK_FAILED",
@@ -65,15 +77,36 @@ pub fn set_tray_unread_flashing<R: Runtime>(
 
     state.has_unread.store(has_unread, Ordering::SeqCst);
 
-    if has_unread {
+    // 免打扰期间只记录未读状态，不闪烁；退出免打扰时由 `refresh_unread_flashing` 恢复。
+    if has_unread && !crate::shared::dnd::is_active() {
         start_flashing(app, &state);
         return Ok(());
     }
 
+    stop_flashing(&app, &state)
+}
+
+/// 停止闪烁并恢复正常图标。
+fn stop_flashing<R: Runtime>(app: &AppHandle<R>, state: &TrayUnreadState) -> CommandResult<()> {
     state.flashing.store(false, Ordering::SeqCst);
     state.showing_blank.store(false, Ordering::SeqCst);
     state.generation.fetch_add(1, Ordering::SeqCst);
-    set_tray_icon(&app, &state.normal_icon)
+    set_tray_icon(app, &state.normal_icon)
+}
+
+/// 按当前未读与免打扰状态重新决定是否闪烁（免打扰状态变化时调用）。
+pub fn refresh_unread_flashing<R: Runtime>(app: &AppHandle<R>) {
+    let Some(state) = app.try_state::<TrayUnreadState>() else {
+        return;
+    };
+    let Ok(_guard) = state.lifecycle_lock.lock() else {
+        return;
+    };
+    if state.has_unread.load(Ordering::SeqCst) && !crate::shared::dnd::is_active() {
+        start_flashing(app.clone(), &state);
+    } else if let Err(err) = stop_flashing(app, &state) {
+        tracing::warn!(action = "app_tray_unread_restore_icon_failed", error = %err);
+    }
 }
 
 /// 启动幂等托盘闪烁循环。
@@ -207,7 +240,7 @@ pub fn start_hover_timer<R: Runtime>(app: AppHandle<R>, state: &TrayUnreadState,
             popover_open.store(false, Ordering::SeqCst);
             return;
         }
-        let _ = app_handle.emit("tray-hover-settled", serde_json::json!({ "x": x, "y": y }));
+        app_handle.emit("tray-hover-settled", serde_json::json!({ "x": x, "y": y }));
     });
 }
 
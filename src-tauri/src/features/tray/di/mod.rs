@@ -1,3 +1,17 @@
 //! 托盘命令导出。
 
 pub mod commands;
+
+use crate::app::registry::{CommandRegistration, CommandRegistry, command_set};
+
+/// 托盘命令注册。
+pub struct TrayCommands;
+
+impl CommandRegistration for TrayCommands {
+    fn register(registry: &mut CommandRegistry) {
+        registry.add(command_set!(crate::features::tray::di::commands => [
+            set_tray_unread_flashing,
+            set_tray_locale,
+        ]));
+    }
+}
@@ -10,7 +10,8 @@ use super::super::data::webrtc::peer_manager::WebRtcPeerManager;
 use super::super::di::events::{CallStateChangeEvent, IncomingCallEvent};
 use super::super::domain::model::*;
 use crate::shared::error::CommandResult;
-use tauri::{Emitter, State};
+use crate::shared::events::EventBusExt;
+use tauri::State;
 
 #[derive(Clone)]
 pub struct VoiceCallService {
@@ -163,6 +164,7 @@ async fn spawn_call_timeout(
                     let _ = client
                         .send(&SignalingMessage::CallHangup {
                             session_id: session_id.clone(),
+                            reason: None,
                         })
                         .await;
                 }
@@ -179,7 +181,7 @@ async fn spawn_call_timeout(
 
             inner.cleanup_session(&session_id).await;
 
-            let _ = app_handle.emit(
+            app_handle.emit(
                 "voice_call:state_change",
                 CallStateChangeEvent {
                     session_id,
@@ -257,6 +259,7 @@ pub async fn start_direct_call(
                 target_uid: target_user_id,
                 sdp_offer: offer.sdp,
                 ice_candidates: offer.candidates,
+                media: CallMedia::Audio,
             })
             .await
             .map_err(|e| format!("[VOICE_CALL_FAILED] {e}"))?;
@@ -337,7 +340,7 @@ pub async fn start_conference(
         .map_err(|e| format!("[VOICE_CALL_AUDIO_PLAYBACK_FAILED] {}", e))?;
 
     // Emit state change
-    let _ = app_handle.emit(
+    app_handle.emit(
         "voice_call:state_change",
         CallStateChangeEvent {
             session_id: session_id.clone(),
@@ -430,7 +433,7 @@ pub async fn join_conference(
         .await
         .map_err(|e| format!("[VOICE_CALL_AUDIO_PLAYBACK_FAILED] {}", e))?;
 
-    let _ = app_handle.emit(
+    app_handle.emit(
         "voice_call:state_change",
         CallStateChangeEvent {
             session_id: session_id.clone(),
@@ -500,7 +503,7 @@ pub async fn leave_conference(
         );
     }
 
-    let _ = app_handle.emit(
+    app_handle.emit(
         "voice_call:state_change",
         CallStateChangeEvent {
             session_id,
@@ -620,7 +623,7 @@ pub async fn accept_call(
     }
 
     // Emit state change to frontend
-    let _ = app_handle.emit(
+    app_handle.emit(
         "voice_call:state_change",
         CallStateChangeEvent {
             session_id: session_id.clone(),
@@ -724,6 +727,7 @@ pub(crate) async fn end_session(inner: &Arc<VoiceCallInner>, session_id: &str) {
             let _ = client
                 .send(&SignalingMessage::CallHangup {
                     session_id: session_id.to_string(),
+                    reason: None,
                 })
                 .await;
         }
@@ -907,8 +911,8 @@ pub async fn send_video_signaling(
     };
     let sig = inner.signaling.lock().await;
     match sig.as_ref() {
-        Some(client) => client.send(&msg).await.map_err(|e| e.to_string()),
-        None => Err("[VOICE_CALL_FAILED] Signaling not connected".to_string()),
+        Some(client) => client.send(&msg).await.map_err(|e| e.to_string().into()),
+        None => Err("[VOICE_CALL_FAILED] Signaling not connected".into()),
     }
 }
 
@@ -997,6 +1001,7 @@ async fn global_signaling_listener(inner: Arc<VoiceCallInner>, app_handle: tauri
                 target_uid,
                 sdp_offer,
                 ice_candidates,
+                ..
             } => {
                 // Store SDP offer + ICE candidates for when user accepts
                 {
@@ -1032,7 +1037,7 @@ async fn global_signaling_listener(inner: Arc<VoiceCallInner>, app_handle: tauri
                 let mut sessions = inner.sessions.lock().await;
                 sessions.insert(session_id.clone(), session);
 
-                let _ = app_handle.emit(
+                app_handle.emit(
                     "voice_call:incoming",
                     IncomingCallEvent {
                         session_id: session_id.clone(),
@@ -1070,7 +1075,7 @@ async fn global_signaling_listener(inner: Arc<VoiceCallInner>, app_handle: tauri
                     s.state = CallState::Active;
                 }
 
-                let _ = app_handle.emit(
+                app_handle.emit(
                     "voice_call:state_change",
                     CallStateChangeEvent {
                         session_id: session_id.clone(),
@@ -1100,7 +1105,7 @@ async fn global_signaling_listener(inner: Arc<VoiceCallInner>, app_handle: tauri
                     s.state = CallState::Ended;
                     s.ended_at = Some(now_secs());
                 }
-                let _ = app_handle.emit(
+                app_handle.emit(
                     "voice_call:state_change",
                     CallStateChangeEvent {
                         session_id,
@@ -1110,13 +1115,13 @@ async fn global_signaling_listener(inner: Arc<VoiceCallInner>, app_handle: tauri
                 );
             }
 
-            SignalingMessage::CallHangup { session_id } => {
+            SignalingMessage::CallHangup { session_id, .. } => {
                 let mut sessions = inner.sessions.lock().await;
                 if let Some(s) = sessions.get_mut(&session_id) {
                     s.state = CallState::Ended;
                     s.ended_at = Some(now_secs());
                 }
-                let _ = app_handle.emit(
+                app_handle.emit(
                     "voice_call:state_change",
                     CallStateChangeEvent {
                         session_id,
@@ -1229,7 +1234,7 @@ async fn global_signaling_listener(inner: Arc<VoiceCallInner>, app_handle: tauri
                     .unwrap_or_default();
 
                 // Emit participant update to frontend (before moving participants)
-                let _ = app_handle.emit(
+                app_handle.emit(
                     "voice_call:participant_update",
                     serde_json::json!({
                         "sessionId": &session_id,
@@ -1300,7 +1305,7 @@ async fn global_signaling_listener(inner: Arc<VoiceCallInner>, app_handle: tauri
                 }
 
                 let sid_clone = session_id.clone();
-                let _ = app_handle.emit(
+                app_handle.emit(
                     "voice_call:state_change",
                     CallStateChangeEvent {
                         session_id,
@@ -1348,7 +1353,7 @@ async fn global_signaling_listener(inner: Arc<VoiceCallInner>, app_handle: tauri
                 }
 
                 // Emit participant update
-                let _ = app_handle.emit(
+                app_handle.emit(
                     "voice_call:participant_update",
                     serde_json::json!({
                         "sessionId": session_id.clone(),
@@ -1360,7 +1365,7 @@ async fn global_signaling_listener(inner: Arc<VoiceCallInner>, app_handle: tauri
 
                 // End session if empty
                 if remaining.is_empty() {
-                    let _ = app_handle.emit(
+                    app_handle.emit(
                         "voice_call:state_change",
                         CallStateChangeEvent {
                             session_id,
@@ -1423,7 +1428,7 @@ async fn global_signaling_listener(inner: Arc<VoiceCallInner>, app_handle: tauri
                 signal_type,
                 payload,
             } => {
-                let _ = app_handle.emit(
+                app_handle.emit(
                     "voice_call:video_signaling",
                     serde_json::json!({
                         "sessionId": session_id,
@@ -1468,7 +1473,7 @@ async fn global_signaling_listener(inner: Arc<VoiceCallInner>, app_handle: tauri
         for (sid, session) in sessions.iter_mut() {
             session.state = CallState::Ended;
             session.ended_at = Some(now_secs());
-            let _ = app_handle.emit(
+            app_handle.emit(
                 "voice_call:state_change",
                 CallStateChangeEvent {
                     session_id: sid.clone(),
@@ -1580,7 +1585,7 @@ async fn monitor_ice_state(
 
     while let Ok(()) = rx.changed().await {
         let state = rx.borrow().clone();
-        let _ = app_handle.emit(
+        app_handle.emit(
             "voice_call:ice_state",
             serde_json::json!({
                 "sessionId": session_id,
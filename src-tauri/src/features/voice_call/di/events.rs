@@ -1,6 +1,7 @@
 use super::super::domain::model::*;
 use serde::Serialize;
-use tauri::Emitter;
+
+use crate::shared::events::EventBusExt;
 
 #[derive(Debug, Clone, Serialize)]
 #[serde(rename_all = "camelCase")]
@@ -46,9 +47,6 @@ pub struct SpeakerUpdate {
 }
 
 /// Emit an incoming call event to the frontend via Tauri event system.
-pub fn emit_incoming_call(
-    app_handle: &tauri::AppHandle,
-    event: &IncomingCallEvent,
-) -> Result<(), tauri::Error> {
-    app_handle.emit("voice_call:incoming", event)
+pub fn emit_incoming_call(app_handle: &tauri::AppHandle, event: &IncomingCallEvent) {
+    app_handle.emit("voice_call:incoming", event);
 }
@@ -1,2 +1,30 @@
 pub mod commands;
 pub mod events;
+
+use crate::app::registry::{CommandRegistration, CommandRegistry, command_set};
+
+/// 语音通话命令注册。
+pub struct VoiceCallCommands;
+
+impl CommandRegistration for VoiceCallCommands {
+    fn register(registry: &mut CommandRegistry) {
+        registry.add(command_set!(crate::features::voice_call::di::commands => [
+            connect_signaling,
+            start_direct_call,
+            start_conference,
+            accept_call,
+            reject_call,
+            hangup_call,
+            toggle_mute,
+            toggle_noise_suppression,
+            enumerate_input_devices,
+            enumerate_output_devices,
+            enumerate_audio_devices,
+            select_input_device,
+            select_output_device,
+            join_conference,
+            leave_conference,
+            send_video_signaling,
+        ]));
+    }
+}
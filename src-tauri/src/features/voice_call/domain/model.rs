@@ -20,7 +20,7 @@ pub enum CallKind {
     Conference,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
 #[serde(rename_all = "snake_case")]
 pub enum CallState {
     Idle,
@@ -28,9 +28,73 @@ pub enum CallState {
     Ringing,
     Connecting,
     Active,
+    /// Callee declined while ringing.
+    Rejected,
+    /// Caller hung up before the callee answered.
+    Missed,
     Ended,
 }
 
+impl CallState {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Idle => "idle",
+            Self::Dialing => "dialing",
+            Self::Ringing => "ringing",
+            Self::Connecting => "connecting",
+            Self::Active => "active",
+            Self::Rejected => "rejected",
+            Self::Missed => "missed",
+            Self::Ended => "ended",
+        }
+    }
+
+    pub fn parse(raw: &str) -> Option<Self> {
+        match raw {
+            "idle" => Some(Self::Idle),
+            "dialing" => Some(Self::Dialing),
+            "ringing" => Some(Self::Ringing),
+            "connecting" => Some(Self::Connecting),
+            "active" => Some(Self::Active),
+            "rejected" => Some(Self::Rejected),
+            "missed" => Some(Self::Missed),
+            "ended" => Some(Self::Ended),
+            _ => None,
+        }
+    }
+
+    /// Whether the call can no longer change state.
+    pub fn is_terminal(self) -> bool {
+        matches!(self, Self::Rejected | Self::Missed | Self::Ended)
+    }
+}
+
+/// Media carried by a 1:1 call.
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum CallMedia {
+    #[default]
+    Audio,
+    Video,
+}
+
+impl CallMedia {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Audio => "audio",
+            Self::Video => "video",
+        }
+    }
+
+    pub fn parse(raw: &str) -> Option<Self> {
+        match raw {
+            "audio" => Some(Self::Audio),
+            "video" => Some(Self::Video),
+            _ => None,
+        }
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Participant {
     pub user_id: String,
@@ -102,6 +166,8 @@ pub enum SignalingMessage {
         target_uid: String,
         sdp_offer: String,
         ice_candidates: Vec<String>,
+        #[serde(default)]
+        media: CallMedia,
     },
     CallAccept {
         session_id: String,
@@ -114,6 +180,8 @@ pub enum SignalingMessage {
     },
     CallHangup {
         session_id: String,
+        #[serde(default)]
+        reason: Option<String>,
     },
     IceCandidate {
         session_id: String,
@@ -157,6 +225,25 @@ pub enum SignalingMessage {
     },
 }
 
+impl SignalingMessage {
+    /// Session (call) id shared by every variant.
+    pub fn session_id(&self) -> &str {
+        match self {
+            Self::CallInvite { session_id, .. }
+            | Self::CallAccept { session_id, .. }
+            | Self::CallReject { session_id, .. }
+            | Self::CallHangup { session_id, .. }
+            | Self::IceCandidate { session_id, .. }
+            | Self::ConferenceJoin { session_id, .. }
+            | Self::ConferenceJoinAck { session_id, .. }
+            | Self::ConferenceSdpOffer { session_id, .. }
+            | Self::ConferenceSdpAnswer { session_id, .. }
+            | Self::ConferenceLeave { session_id, .. }
+            | Self::VideoSignaling { session_id, .. } => session_id,
+        }
+    }
+}
+
 /// ICE connection state for UI feedback
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 #[serde(rename_all = "snake_case")]
@@ -3,3 +3,21 @@
 //! 约定：注释中文，日志英文（tracing）。
 
 pub mod commands;
+
+use crate::app::registry::{CommandRegistration, CommandRegistry, command_set};
+
+/// 语音消息命令注册。
+pub struct VoiceMessageCommands;
+
+impl CommandRegistration for VoiceMessageCommands {
+    fn register(registry: &mut CommandRegistry) {
+        registry.add(
+            command_set!(crate::features::voice_message::di::commands => [
+                start_voice_recording,
+                stop_voice_recording,
+                read_file_base64,
+                read_file_base64_chunk,
+            ]),
+        );
+    }
+}
@@ -0,0 +1,260 @@
+//! windows｜DI/命令入口：auth_window（服务端网页认证步骤窗口）。
+//!
+//! 说明：
+//! - 部分服务器登录需要网页认证步骤（验证码、SSO 等）：在独立的无痕 WebView 窗口中打开认证页面，
+//!   该窗口加载的是外部页面，不具备 IPC 权限；
+//! - 窗口导航到回调地址（默认 [`DEFAULT_REDIRECT_URL`]）时拦截导航，在 Rust 中解析 query/fragment
+//!   参数后关闭窗口；
+//! - 回调必须带 access/refresh token，直接写入会话令牌存储（之后 `api_request_json` 会自动附加），
+//!   令牌不会返回给主 WebView；
+//! - 只带授权码的回调视为失败（`missing_credentials`）：授权码换取令牌需要 PKCE，
+//!   走 `network::usecases::oauth_usecases` 的环回监听流程，不经过本窗口；
+//! - 打开前附加随机 `state`，回调的 `state` 不匹配时视为失败。
+//!
+//! 约定：注释中文，日志英文（tracing）。
+use std::collections::BTreeMap;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::Duration;
+
+use serde::Serialize;
+use tauri::{AppHandle, Url, WebviewUrl, WebviewWindowBuilder, WindowEvent};
+
+use crate::features::network::data::session_token_store::KeyringSessionTokenStore;
+use crate::features::network::domain::ports::session_token_port::{
+    SessionTokenPort, SessionTokens,
+};
+
+/// 默认回调地址（服务端认证完成后重定向到此地址）。
+pub const DEFAULT_REDIRECT_URL: &str = "carrypigeon://auth/callback";
+
+const AUTH_WINDOW_WIDTH: f64 = 480.0;
+const AUTH_WINDOW_HEIGHT: f64 = 640.0;
+/// 等待用户完成认证的最长时间。
+const AUTH_WINDOW_TIMEOUT: Duration = Duration::from_secs(10 * 60);
+
+static WINDOW_SEQ: AtomicU64 = AtomicU64::new(1);
+
+/// 认证窗口结束状态。
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AuthWindowStatus {
+    Completed,
+    Cancelled,
+    TimedOut,
+    Failed,
+}
+
+/// 认证窗口结果（返回给前端，不含凭据）。
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AuthWindowOutcome {
+    pub status: AuthWindowStatus,
+    /// 会话令牌已写入令牌存储。
+    pub session_stored: bool,
+    /// 服务端回调的 `error` 参数或本地校验失败原因。
+    pub error: Option<String>,
+}
+
+impl AuthWindowOutcome {
+    fn with_status(status: AuthWindowStatus) -> Self {
+        Self {
+            status,
+            session_stored: false,
+            error: None,
+        }
+    }
+
+    fn failed(error: &str) -> Self {
+        Self {
+            error: Some(error.to_string()),
+            ..Self::with_status(AuthWindowStatus::Failed)
+        }
+    }
+}
+
+enum WindowSignal {
+    Callback(Url),
+    Closed,
+}
+
+/// 导航目标是否为回调地址（比较 scheme/host/port/path，忽略 query 与 fragment）。
+fn matches_redirect(candidate: &Url, redirect: &Url) -> bool {
+    candidate.scheme() == redirect.scheme()
+        && candidate.host_str() == redirect.host_str()
+        && candidate.port_or_known_default() == redirect.port_or_known_default()
+        && candidate.path().trim_end_matches('/') == redirect.path().trim_end_matches('/')
+}
+
+/// 合并回调地址 query 与 fragment 中的参数（同名时 fragment 优先）。
+fn callback_params(url: &Url) -> BTreeMap<String, String> {
+    let mut params: BTreeMap<String, String> = url.query_pairs().into_owned().collect();
+    if let Some(fragment) = url.fragment().filter(|f| !f.is_empty())
+        && let Ok(parsed) = Url::parse(&format!("http://fragment.invalid/?{fragment}"))
+    {
+        params.extend(parsed.query_pairs().into_owned());
+    }
+    params
+}
+
+fn random_hex(len: usize) -> anyhow::Result<String> {
+    let mut bytes = vec![0u8; len];
+    getrandom::fill(&mut bytes).map_err(|_| anyhow::anyhow!("Failed to generate random bytes"))?;
+    Ok(hex::encode(bytes))
+}
+
+/// 处理回调参数：校验 state 并保存令牌。
+async fn complete(
+    server_socket: &str,
+    mut params: BTreeMap<String, String>,
+    state: &str,
+) -> anyhow::Result<AuthWindowOutcome> {
+    if params.remove("state").as_deref() != Some(state) {
+        return Ok(AuthWindowOutcome::failed("state_mismatch"));
+    }
+    if let Some(error) = params.get("error") {
+        return Ok(AuthWindowOutcome::failed(error));
+    }
+
+    let access_token = params.get("access_token").filter(|v| !v.is_empty());
+    let refresh_token = params.get("refresh_token").filter(|v| !v.is_empty());
+    let (Some(access_token), Some(refresh_token)) = (access_token, refresh_token) else {
+        return Ok(AuthWindowOutcome::failed("missing_credentials"));
+    };
+    let token_port = KeyringSessionTokenStore::shared();
+    // 沿用已保存会话的设备 ID，保证后续刷新时上报一致。
+    let device_id = token_port
+        .load_tokens(server_socket)
+        .await
+        .ok()
+        .flatten()
+        .and_then(|t| t.device_id);
+    let expires_at_ms = params
+        .get("expires_in")
+        .and_then(|v| v.parse::<i64>().ok())
+        .map(|secs| chrono::Utc::now().timestamp_millis() + secs.max(0) * 1000);
+    token_port
+        .save_tokens(
+            server_socket,
+            SessionTokens {
+                access_token: access_token.clone(),
+                refresh_token: refresh_token.clone(),
+                expires_at_ms,
+                device_id,
+            },
+        )
+        .await?;
+    Ok(AuthWindowOutcome {
+        session_stored: true,
+        ..AuthWindowOutcome::with_status(AuthWindowStatus::Completed)
+    })
+}
+
+/// 打开认证窗口并等待回调、关闭或超时。
+///
+/// # 参数
+/// - `url`：认证页面地址（仅允许 http/https）。
+/// - `redirect_url`：回调地址；缺省为 [`DEFAULT_REDIRECT_URL`]。
+pub async fn open_auth_window_impl(
+    app: AppHandle,
+    server_socket: String,
+    url: String,
+    redirect_url: Option<String>,
+) -> anyhow::Result<AuthWindowOutcome> {
+    let server_socket = server_socket.trim().to_string();
+    if server_socket.is_empty() {
+        return Err(anyhow::anyhow!("Missing server_socket"));
+    }
+    let mut start = Url::parse(url.trim())?;
+    if !matches!(start.scheme(), "http" | "https") {
+        return Err(anyhow::anyhow!("Auth URL must use http or https"));
+    }
+    let redirect = Url::parse(
+        redirect_url
+            .as_deref()
+            .map(str::trim)
+            .filter(|s| !s.is_empty())
+            .unwrap_or(DEFAULT_REDIRECT_URL),
+    )?;
+    let state = random_hex(16)?;
+    start.query_pairs_mut().append_pair("state", &state);
+
+    let (tx, mut rx) = tokio::sync::mpsc::unbounded_channel::<WindowSignal>();
+    let label = format!("auth-{}", WINDOW_SEQ.fetch_add(1, Ordering::Relaxed));
+    let nav_tx = tx.clone();
+    let window = WebviewWindowBuilder::new(&app, &label, WebviewUrl::External(start))
+        .title("CarryPigeon")
+        .inner_size(AUTH_WINDOW_WIDTH, AUTH_WINDOW_HEIGHT)
+        .center()
+        .incognito(true)
+        .content_protected(crate::shared::privacy::is_enabled())
+        .on_navigation(move |target| {
+            if matches_redirect(target, &redirect) {
+                let _ = nav_tx.send(WindowSignal::Callback(target.clone()));
+                return false;
+            }
+            true
+        })
+        .build()
+        .map_err(|e| anyhow::anyhow!(e.to_string()))?;
+    window.on_window_event(move |event| {
+        if matches!(event, WindowEvent::Destroyed) {
+            let _ = tx.send(WindowSignal::Closed);
+        }
+    });
+    let _ = window.set_focus();
+    tracing::info!(action = "windows_auth_window_opened", label = %label, server_socket = %server_socket);
+
+    let signal = tokio::time::timeout(AUTH_WINDOW_TIMEOUT, rx.recv()).await;
+    let _ = window.destroy();
+    let outcome = match signal {
+        Err(_) => AuthWindowOutcome::with_status(AuthWindowStatus::TimedOut),
+        Ok(None | Some(WindowSignal::Closed)) => {
+            AuthWindowOutcome::with_status(AuthWindowStatus::Cancelled)
+        }
+        Ok(Some(WindowSignal::Callback(callback))) => {
+            complete(&server_socket, callback_params(&callback), &state).await?
+        }
+    };
+    tracing::info!(
+        action = "windows_auth_window_finished",
+        label = %label,
+        status = ?outcome.status,
+        session_stored = outcome.session_stored
+    );
+    Ok(outcome)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn redirect_matching_ignores_query_and_fragment() {
+        let redirect = Url::parse(DEFAULT_REDIRECT_URL).unwrap();
+        let hit = Url::parse("carrypigeon://auth/callback/?code=1#x=2").unwrap();
+        assert!(matches_redirect(&hit, &redirect));
+        let miss = Url::parse("carrypigeon://auth/other?code=1").unwrap();
+        assert!(!matches_redirect(&miss, &redirect));
+
+        let redirect = Url::parse("https://example.com/cb").unwrap();
+        assert!(matches_redirect(
+            &Url::parse("https://example.com:443/cb?x=1").unwrap(),
+            &redirect
+        ));
+        assert!(!matches_redirect(
+            &Url::parse("https://evil.example/cb").unwrap(),
+            &redirect
+        ));
+    }
+
+    #[test]
+    fn callback_params_merge_query_and_fragment() {
+        let url =
+            Url::parse("carrypigeon://auth/callback?state=s1&code=abc#access_token=t%20k&state=s2")
+                .unwrap();
+        let params = callback_params(&url);
+        assert_eq!(params["code"], "abc");
+        assert_eq!(params["access_token"], "t k");
+        assert_eq!(params["state"], "s2");
+    }
+}
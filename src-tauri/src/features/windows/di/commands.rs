@@ -1,9 +1,17 @@
 //! windows｜DI/命令入口：commands。
 //!
 //! 约定：注释中文，日志英文（tracing）。
-use tauri::{AppHandle, LogicalSize, Manager};
+use tauri::{AppHandle, LogicalSize, Manager, Window};
 
-use crate::features::windows::di::{info_window, popover_window};
+use crate::features::windows::di::auth_window::{self, AuthWindowOutcome};
+use crate::features::windows::di::mini_chat::{self, MiniChatReadState, MiniChatTarget};
+use crate::features::windows::di::popover_window::UserPopoverRequest;
+use crate::features::windows::di::titlebar::{self, WindowCloseAction};
+use crate::features::windows::di::{context_menu, info_window, popover_window};
+use crate::features::windows::domain::context_menu::ContextMenuItem;
+use crate::features::windows::domain::popover_geometry::{Anchor, DesiredSize, PopoverGeometry};
+use crate::features::windows::domain::window_platform::WindowCapabilities;
+use crate::features::windows::usecases::window_usecases::window_capabilities;
 use crate::shared::error::{CommandResult, command_error, to_command_error};
 
 /// 将主窗口调整为聊天视图的推荐尺寸。
@@ -30,12 +38,78 @@ pub fn to_chat_window_size(app: AppHandle) -> CommandResult<()> {
     })
 }
 
+/// 获取当前窗口系统能力（会话类型、弹窗定位策略等）。
+///
+/// # 说明
+/// 前端据 `popoverPositioning` 决定弹窗坐标：`absolute` 传屏幕坐标（`screenX/screenY`），
+/// `parent_relative`（Wayland）传相对主窗口的坐标（`clientX/clientY`）。
+#[tauri::command]
+pub fn get_window_capabilities() -> CommandResult<WindowCapabilities> {
+    Ok(window_capabilities())
+}
+
+/// 计算弹窗位置与尺寸（供表情选择、回应栏等自绘弹窗复用，与用户信息弹窗同一套规则）。
+///
+/// # 参数
+/// - `anchor`：期望的左上角（逻辑像素；可带来源窗口的 `scaleFactor` 以便混合 DPI 下选择显示器）。
+/// - `desired`：期望尺寸（逻辑像素）。
+///
+/// # 返回值
+/// - `Ok(PopoverGeometry)`：约束后的逻辑/物理矩形与所在显示器缩放比。
+/// - `Err(String)`：读取显示器或主窗口信息失败。
+#[tauri::command]
+pub fn compute_popover_rect(
+    app: AppHandle,
+    anchor: Anchor,
+    desired: DesiredSize,
+) -> CommandResult<PopoverGeometry> {
+    popover_window::compute_popover_rect_impl(&app, anchor, desired).map_err(|err| {
+        to_command_error(
+            "WINDOW_POPOVER_GEOMETRY_FAILED",
+            "error.window_popover_geometry_failed",
+            err,
+        )
+    })
+}
+
+/// 在调用方窗口内弹出原生右键菜单，并返回选中项 id。
+///
+/// # 参数
+/// - `window`：调用方窗口（菜单在该窗口内弹出）。
+/// - `items`：菜单描述（普通项/勾选项/分隔线/嵌套子菜单）。
+/// - `x`/`y`：窗口内坐标（逻辑像素，即 `clientX/clientY`）。
+///
+/// # 返回值
+/// - `Ok(Some(id))`：选中的菜单项 id。
+/// - `Ok(None)`：菜单被取消（Esc、点击外部或被新菜单替换）。
+/// - `Err(String)`：菜单描述无效或弹出失败。
+///
+/// # 说明
+/// 键盘导航、助记键与屏幕边缘翻转由系统原生菜单提供，见 `context_menu`。
+#[tauri::command]
+pub async fn open_context_menu(
+    window: Window,
+    items: Vec<ContextMenuItem>,
+    x: f64,
+    y: f64,
+) -> CommandResult<Option<String>> {
+    context_menu::open_context_menu_impl(window, items, x, y)
+        .await
+        .map_err(|err| {
+            to_command_error(
+                "WINDOW_CONTEXT_MENU_FAILED",
+                "error.window_context_menu_failed",
+                err,
+            )
+        })
+}
+
 /// 打开用户信息弹窗（Popover）窗口。
 ///
 /// # 参数
 /// - `app`：Tauri 应用句柄。
 /// - `query`：用于在新窗口内加载页面/路由的 query 字符串（由前端构造）。
-/// - `x`/`y`：弹窗显示坐标（逻辑像素）。
+/// - `x`/`y`：弹窗显示坐标（逻辑像素；Wayland 下相对主窗口，见 `get_window_capabilities`）。
 /// - `width`/`height`：弹窗尺寸（逻辑像素）。
 ///
 /// # 返回值
@@ -64,6 +138,30 @@ pub async fn open_popover_window(
         })
 }
 
+/// 打开用户信息弹窗，并预先注入本地缓存的用户资料、共同频道与头像路径。
+///
+/// # 参数
+/// - `req`：server DB key、用户 id、头像 id 以及与 `open_popover_window` 相同的位置/尺寸参数。
+///
+/// # 返回值
+/// - `Ok(())`：打开成功。
+/// - `Err(String)`：读取缓存或打开窗口失败。
+#[tauri::command]
+pub async fn open_user_popover_with_data(
+    app: AppHandle,
+    req: UserPopoverRequest,
+) -> CommandResult<()> {
+    popover_window::open_user_popover_with_data_impl(app, req)
+        .await
+        .map_err(|err| {
+            to_command_error(
+                "WINDOW_POPOVER_OPEN_FAILED",
+                "error.window_popover_open_failed",
+                err,
+            )
+        })
+}
+
 /// 打开信息展示窗口（Info window）。
 ///
 /// # 参数
@@ -99,6 +197,37 @@ pub async fn open_info_window(
         })
 }
 
+/// 打开服务端网页认证步骤窗口（验证码、SSO 等），等待认证完成后返回结果。
+///
+/// # 参数
+/// - `server_socket`：需要认证的服务器。
+/// - `url`：认证页面地址（http/https）。
+/// - `redirect_url`：回调地址（可选，缺省为 `carrypigeon://auth/callback`）。
+///
+/// # 返回值
+/// - `Ok(AuthWindowOutcome)`：完成/取消/超时/失败；凭据留在 Rust 侧，不返回给前端。
+/// - `Err(CommandError)`：参数非法或窗口创建失败。
+///
+/// # 说明
+/// 实际窗口创建与回调解析由 `auth_window::open_auth_window_impl` 实现。
+#[tauri::command]
+pub async fn open_auth_window(
+    app: AppHandle,
+    server_socket: String,
+    url: String,
+    redirect_url: Option<String>,
+) -> CommandResult<AuthWindowOutcome> {
+    auth_window::open_auth_window_impl(app, server_socket, url, redirect_url)
+        .await
+        .map_err(|err| {
+            to_command_error(
+                "WINDOW_AUTH_OPEN_FAILED",
+                "error.window_auth_open_failed",
+                err,
+            )
+        })
+}
+
 /// 关闭托盘通知弹窗并聚焦主窗口。
 ///
 /// 点击通知弹窗中的消息时由前端触发。
@@ -112,3 +241,99 @@ pub async fn close_tray_notification_popover(app: AppHandle) -> CommandResult<()
         )
     })
 }
+
+/// 最小化调用方窗口（自绘标题栏）。
+#[tauri::command]
+pub fn window_minimize(window: Window) -> CommandResult<()> {
+    window.minimize().map_err(|err| {
+        to_command_error("WINDOW_CONTROL_FAILED", "error.window_control_failed", err)
+    })
+}
+
+/// 切换调用方窗口的最大化状态。
+///
+/// # 返回值
+/// - `Ok(bool)`：切换后是否处于最大化。
+/// - `Err(String)`：窗口操作失败。
+#[tauri::command]
+pub fn window_toggle_maximize(window: Window) -> CommandResult<bool> {
+    titlebar::toggle_maximize(&window).map_err(|err| {
+        to_command_error("WINDOW_CONTROL_FAILED", "error.window_control_failed", err)
+    })
+}
+
+/// 标题栏关闭按钮：主窗口按“关闭到托盘”设置隐藏或关闭，其它窗口直接关闭。
+///
+/// # 返回值
+/// - `Ok(WindowCloseAction)`：`closed` 或 `hidden_to_tray`。
+/// - `Err(String)`：窗口操作失败。
+#[tauri::command]
+pub fn window_close_or_tray(window: Window) -> CommandResult<WindowCloseAction> {
+    titlebar::close_or_tray(&window).map_err(|err| {
+        to_command_error("WINDOW_CONTROL_FAILED", "error.window_control_failed", err)
+    })
+}
+
+/// 开始拖动调用方窗口（在标题栏 `mousedown` 时调用）。
+#[tauri::command]
+pub fn window_start_drag(window: Window) -> CommandResult<()> {
+    window.start_dragging().map_err(|err| {
+        to_command_error("WINDOW_CONTROL_FAILED", "error.window_control_failed", err)
+    })
+}
+
+/// 打开画中画迷你聊天窗（置顶、无边框，仅展示一个会话）。
+///
+/// # 参数
+/// - `server_socket`：会话所属服务器。
+/// - `channel_id`：会话（频道）id。
+///
+/// # 说明
+/// 迷你窗已打开时切换到新会话，不重建窗口。
+#[tauri::command]
+pub fn open_mini_chat(
+    app: AppHandle,
+    server_socket: String,
+    channel_id: String,
+) -> CommandResult<()> {
+    mini_chat::open(
+        &app,
+        MiniChatTarget {
+            server_socket,
+            channel_id,
+        },
+    )
+    .map_err(|err| {
+        tracing::warn!(action = "windows_mini_chat_open_failed", error = %err);
+        to_command_error(
+            "WINDOW_MINI_CHAT_OPEN_FAILED",
+            "error.window_mini_chat_open_failed",
+            err,
+        )
+    })
+}
+
+/// 上报会话已读位置，并同步给主窗口与迷你窗。
+///
+/// # 参数
+/// - `window`：上报来源窗口（其 label 写入事件 `source`）。
+/// - `last_read_message_id`：已读到的最后一条消息 id。
+#[tauri::command]
+pub fn mini_chat_mark_read(
+    app: AppHandle,
+    window: Window,
+    server_socket: String,
+    channel_id: String,
+    last_read_message_id: String,
+) -> CommandResult<()> {
+    mini_chat::broadcast_read_state(
+        &app,
+        MiniChatReadState {
+            server_socket,
+            channel_id,
+            last_read_message_id,
+            source: window.label().to_string(),
+        },
+    );
+    Ok(())
+}
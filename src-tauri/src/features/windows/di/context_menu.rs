@@ -0,0 +1,176 @@
+//! windows｜DI/命令入口：context_menu（通用右键菜单）。
+//!
+//! 说明：
+//! - 使用系统原生菜单：嵌套子菜单、键盘导航（方向键/回车/Esc/助记键）与屏幕边缘翻转
+//!   均由系统处理，聊天视图与插件得到一致的菜单外观；
+//! - 同一时间只有一个菜单在等待结果：打开新菜单时，上一个菜单以“未选择”结束；
+//! - Windows / macOS 上弹出菜单会阻塞主线程直到关闭，关闭后稍等片刻收取选择事件，
+//!   未收到即视为取消；Linux（GTK）弹出后立即返回，取消只能由下次打开菜单或超时结束。
+//!
+//! 约定：注释中文，日志英文（tracing）。
+
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Mutex, Once};
+use std::time::Duration;
+
+use tauri::menu::{CheckMenuItem, Menu, MenuItem, MenuItemKind, PredefinedMenuItem, Submenu};
+use tauri::{AppHandle, LogicalPosition, Manager, Window, Wry};
+use tokio::sync::oneshot;
+
+use crate::features::windows::domain::context_menu::{
+    ContextMenuItem, parse_scoped_id, scoped_id, validate_menu,
+};
+
+/// 菜单关闭后等待选择事件送达的时长。
+const SELECTION_GRACE: Duration = Duration::from_millis(250);
+/// 无法感知关闭的平台上，等待选择的最长时长。
+const SELECTION_TIMEOUT: Duration = Duration::from_secs(120);
+
+/// 等待结果的菜单。
+struct PendingMenu {
+    token: u64,
+    tx: oneshot::Sender<Option<String>>,
+}
+
+static NEXT_TOKEN: AtomicU64 = AtomicU64::new(1);
+static PENDING: Mutex<Option<PendingMenu>> = Mutex::new(None);
+static LISTENER: Once = Once::new();
+
+/// 结束等待中的菜单（`token` 不匹配时忽略，避免旧事件结束新菜单）。
+fn resolve(token: u64, choice: Option<String>) {
+    let mut pending = PENDING.lock().unwrap_or_else(|p| p.into_inner());
+    if pending.as_ref().is_some_and(|menu| menu.token == token)
+        && let Some(menu) = pending.take()
+    {
+        let _ = menu.tx.send(choice);
+    }
+}
+
+/// 登记新的等待菜单，并以“未选择”结束上一个。
+fn begin(token: u64) -> oneshot::Receiver<Option<String>> {
+    let (tx, rx) = oneshot::channel();
+    let previous = PENDING
+        .lock()
+        .unwrap_or_else(|p| p.into_inner())
+        .replace(PendingMenu { token, tx });
+    if let Some(previous) = previous {
+        let _ = previous.tx.send(None);
+    }
+    rx
+}
+
+/// 注册全局菜单事件监听（只注册一次）。
+fn ensure_listener(app: &AppHandle) {
+    LISTENER.call_once(|| {
+        app.on_menu_event(|_app, event| {
+            if let Some((token, id)) = parse_scoped_id(event.id().as_ref()) {
+                tracing::info!(action = "windows_context_menu_selected", token);
+                resolve(token, Some(id.to_string()));
+            }
+        });
+    });
+}
+
+/// 按描述递归构建原生菜单项。
+fn build_item(
+    app: &AppHandle,
+    token: u64,
+    item: &ContextMenuItem,
+) -> tauri::Result<MenuItemKind<Wry>> {
+    Ok(match item {
+        ContextMenuItem::Item {
+            id,
+            label,
+            enabled,
+            accelerator,
+        } => MenuItemKind::MenuItem(MenuItem::with_id(
+            app,
+            scoped_id(token, id),
+            label,
+            *enabled,
+            accelerator.as_deref(),
+        )?),
+        ContextMenuItem::Check {
+            id,
+            label,
+            enabled,
+            checked,
+            accelerator,
+        } => MenuItemKind::Check(CheckMenuItem::with_id(
+            app,
+            scoped_id(token, id),
+            label,
+            *enabled,
+            *checked,
+            accelerator.as_deref(),
+        )?),
+        ContextMenuItem::Separator => MenuItemKind::Predefined(PredefinedMenuItem::separator(app)?),
+        ContextMenuItem::Submenu {
+            label,
+            enabled,
+            items,
+        } => {
+            let submenu = Submenu::new(app, label, *enabled)?;
+            for child in items {
+                submenu.append(&build_item(app, token, child)?)?;
+            }
+            MenuItemKind::Submenu(submenu)
+        }
+    })
+}
+
+/// 在调用方窗口内弹出菜单并等待选择。
+///
+/// # 参数
+/// - `window`：调用方窗口；`x` / `y` 为窗口内坐标（逻辑像素，即 `clientX/clientY`）。
+/// - `items`：菜单描述（见 [`ContextMenuItem`]）。
+///
+/// # 返回值
+/// 选中项的原始 id；取消时为 `None`。
+pub async fn open_context_menu_impl(
+    window: Window,
+    items: Vec<ContextMenuItem>,
+    x: f64,
+    y: f64,
+) -> anyhow::Result<Option<String>> {
+    validate_menu(&items)?;
+    let app = window.app_handle().clone();
+    ensure_listener(&app);
+
+    let token = NEXT_TOKEN.fetch_add(1, Ordering::Relaxed);
+    let menu = Menu::new(&app)?;
+    for item in &items {
+        menu.append(&build_item(&app, token, item)?)?;
+    }
+    let rx = begin(token);
+
+    // 在主线程上弹出：Windows / macOS 会阻塞到菜单关闭，之后才发出 closed 信号。
+    let (closed_tx, closed_rx) = oneshot::channel::<()>();
+    let popup_window = window.clone();
+    app.run_on_main_thread(move || {
+        if let Err(err) = popup_window.popup_menu_at(&menu, LogicalPosition::new(x, y)) {
+            tracing::warn!(action = "windows_context_menu_popup_failed", error = %err);
+            resolve(token, None);
+        }
+        let _ = closed_tx.send(());
+    })?;
+
+    let blocking_popup = cfg!(any(target_os = "windows", target_os = "macos"));
+    if blocking_popup {
+        let _ = closed_rx.await;
+        tauri::async_runtime::spawn(async move {
+            tokio::time::sleep(SELECTION_GRACE).await;
+            resolve(token, None);
+        });
+    } else {
+        tauri::async_runtime::spawn(async move {
+            tokio::time::sleep(SELECTION_TIMEOUT).await;
+            resolve(token, None);
+        });
+    }
+    let choice = rx.await.unwrap_or(None);
+    if choice.is_none() {
+        tracing::info!(action = "windows_context_menu_dismissed", token);
+    }
+    Ok(choice)
+}
@@ -3,11 +3,16 @@
 //! 约定：注释中文，日志英文（tracing）。
 use tauri::{AppHandle, Manager, WebviewUrl, WebviewWindowBuilder};
 
+use crate::features::windows::domain::window_labels::{
+    INFO_WINDOW_DEFAULT_LABEL, INFO_WINDOW_LABELS,
+};
+
 /// 打开信息窗口（用户资料/频道信息等）。
 ///
 /// 设计目标：
 /// - 独立窗口，允许编辑/长时间停留
 /// - 同 label 只保留一个实例（避免重复打开）
+/// - label 只能取 [`INFO_WINDOW_LABELS`]，避免关闭或冒用其它应用窗口
 pub async fn open_info_window_impl(
     app: AppHandle,
     label: String,
@@ -16,13 +21,16 @@ pub async fn open_info_window_impl(
     width: f64,
     height: f64,
 ) -> anyhow::Result<()> {
-    let safe_label = if label.trim().is_empty() {
-        "info-window".to_string()
-    } else {
-        label
+    let safe_label = match label.trim() {
+        "" => INFO_WINDOW_DEFAULT_LABEL,
+        label => INFO_WINDOW_LABELS
+            .iter()
+            .copied()
+            .find(|known| *known == label)
+            .ok_or_else(|| anyhow::anyhow!("unknown info window label: {label}"))?,
     };
 
-    if let Some(existing) = app.get_webview_window(&safe_label) {
+    if let Some(existing) = app.get_webview_window(safe_label) {
         let _ = existing.close();
     }
 
@@ -37,6 +45,7 @@ pub async fn open_info_window_impl(
         .decorations(true)
         .center()
         .inner_size(width.max(min_width), height.max(min_height))
+        .content_protected(crate::shared::privacy::is_enabled())
         .build()
         .map_err(|e| anyhow::anyhow!(e.to_string()))?;
 
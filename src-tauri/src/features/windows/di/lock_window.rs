@@ -0,0 +1,115 @@
+//! windows｜DI/命令入口：lock_window（应用锁锁屏窗口）。
+//!
+//! 说明：
+//! - 锁定时隐藏其它所有可见窗口并显示锁屏窗口，解锁后只恢复被锁定隐藏的窗口；
+//! - 锁定期间其它窗口获得焦点（托盘“显示窗口”等）会被重新隐藏，见 [`guard_window_event`]。
+//!
+//! 约定：注释中文，日志英文（tracing）。
+use std::sync::Mutex;
+
+use tauri::{AppHandle, Manager, WebviewUrl, WebviewWindowBuilder, Window, WindowEvent};
+
+use crate::shared::app_lock;
+
+pub use crate::features::windows::domain::window_labels::APP_LOCK_LABEL;
+
+const LOCK_WINDOW_WIDTH: f64 = 360.0;
+const LOCK_WINDOW_HEIGHT: f64 = 420.0;
+
+/// 因锁定而隐藏的窗口 label（解锁后恢复）。
+static HIDDEN_BY_LOCK: Mutex<Vec<String>> = Mutex::new(Vec::new());
+
+/// 隐藏其它窗口并显示锁屏窗口（已存在时仅置前）。
+pub fn show(app: &AppHandle) -> anyhow::Result<()> {
+    {
+        let mut hidden = HIDDEN_BY_LOCK.lock().unwrap_or_else(|p| p.into_inner());
+        for (label, window) in app.webview_windows() {
+            if label == APP_LOCK_LABEL || !window.is_visible().unwrap_or(false) {
+                continue;
+            }
+            if let Err(err) = window.hide() {
+                tracing::warn!(action = "windows_app_lock_hide_failed", label = %label, error = %err);
+                continue;
+            }
+            if !hidden.contains(&label) {
+                hidden.push(label);
+            }
+        }
+    }
+
+    if let Some(existing) = app.get_webview_window(APP_LOCK_LABEL) {
+        let _ = existing.show();
+        let _ = existing.set_focus();
+        return Ok(());
+    }
+
+    let url = WebviewUrl::App("index.html?window=app-lock".into());
+    let window = WebviewWindowBuilder::new(app, APP_LOCK_LABEL, url)
+        .title("CarryPigeon")
+        .inner_size(LOCK_WINDOW_WIDTH, LOCK_WINDOW_HEIGHT)
+        .resizable(false)
+        .maximizable(false)
+        .minimizable(false)
+        .decorations(false)
+        .always_on_top(true)
+        .skip_taskbar(false)
+        .center()
+        .content_protected(crate::shared::privacy::is_enabled())
+        .build()
+        .map_err(|e| anyhow::anyhow!(e.to_string()))?;
+    let _ = window.set_focus();
+    tracing::info!(action = "windows_app_lock_window_opened");
+    Ok(())
+}
+
+/// 关闭锁屏窗口并恢复锁定前可见的窗口。
+pub fn close(app: &AppHandle) {
+    if let Some(window) = app.get_webview_window(APP_LOCK_LABEL)
+        && let Err(err) = window.destroy()
+    {
+        tracing::warn!(action = "windows_app_lock_close_failed", error = %err);
+    }
+    let labels = std::mem::take(&mut *HIDDEN_BY_LOCK.lock().unwrap_or_else(|p| p.into_inner()));
+    for label in labels {
+        if let Some(window) = app.get_webview_window(&label) {
+            let _ = window.show();
+        }
+    }
+    if let Some(main) = app.get_webview_window("main") {
+        let _ = main.set_focus();
+    }
+}
+
+/// 锁定期间的窗口事件守卫（在 `on_window_event` 中调用）。
+///
+/// # 返回值
+/// - `true`：事件已被拦截，调用方不应继续处理。
+pub fn guard_window_event(window: &Window, event: &WindowEvent) -> bool {
+    if !app_lock::is_locked() {
+        return false;
+    }
+    let app = window.app_handle();
+    if window.label() == APP_LOCK_LABEL {
+        // 锁定期间锁屏窗口不可关闭（Alt+F4 等）。
+        if let WindowEvent::CloseRequested { api, .. } = event {
+            api.prevent_close();
+            return true;
+        }
+        return false;
+    }
+    if matches!(event, WindowEvent::Focused(true)) {
+        let label = window.label().to_string();
+        let _ = window.hide();
+        {
+            let mut hidden = HIDDEN_BY_LOCK.lock().unwrap_or_else(|p| p.into_inner());
+            if !hidden.contains(&label) {
+                hidden.push(label);
+            }
+        }
+        if let Err(err) = show(app) {
+            tracing::warn!(action = "windows_app_lock_refocus_failed", error = %err);
+        }
+        return true;
+    }
+    false
+}
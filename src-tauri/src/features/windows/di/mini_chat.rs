@@ -0,0 +1,253 @@
+//! windows｜DI：画中画迷你聊天窗（单会话、置顶、无边框）。
+//!
+//! 说明：
+//! - 全局只保留一个迷你窗（label = [`MINI_CHAT_LABEL`]）；已打开时切换会话通过
+//!   `mini-chat-target-changed` 事件通知前端，避免重建窗口导致闪烁与位置跳动；
+//! - 窗口位置独立持久化到 `mini-chat-bounds.json`，打开时若仍落在某个显示器内则恢复；
+//! - 设置 `mini_chat_auto_hide_fullscreen` 开启时，后台轮询其它应用是否处于全屏
+//!   （目前仅 Windows 可检测；其它平台视为从不全屏），全屏期间隐藏、退出后恢复显示；
+//! - 已读状态由前端任一窗口通过 `mini_chat_mark_read` 上报，后端广播
+//!   `mini-chat-read-state` 给所有窗口，使主窗口与迷你窗的未读计数保持一致。
+//!
+//! 约定：注释中文，日志英文（tracing）。
+
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::Duration;
+
+use serde::Serialize;
+use tauri::{AppHandle, Manager, WebviewUrl, WebviewWindowBuilder};
+
+use crate::shared::events::{EventBus, EventBusExt};
+use crate::shared::window_bounds;
+
+pub use crate::features::windows::domain::window_labels::MINI_CHAT_LABEL;
+
+/// 迷你聊天窗 bounds 文件名。
+pub const MINI_CHAT_BOUNDS_FILE: &str = "mini-chat-bounds.json";
+
+/// 迷你窗切换会话事件名（仅发往迷你窗）。
+pub const MINI_CHAT_TARGET_CHANGED_EVENT: &str = "mini-chat-target-changed";
+
+/// 已读状态同步事件名（广播到所有窗口）。
+pub const MINI_CHAT_READ_STATE_EVENT: &str = "mini-chat-read-state";
+
+const DEFAULT_WIDTH: f64 = 360.0;
+const DEFAULT_HEIGHT: f64 = 520.0;
+const MIN_WIDTH: f64 = 280.0;
+const MIN_HEIGHT: f64 = 320.0;
+
+/// 全屏检测轮询间隔。
+const FULLSCREEN_POLL_INTERVAL: Duration = Duration::from_secs(2);
+
+/// 迷你窗展示的会话。
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MiniChatTarget {
+    pub server_socket: String,
+    pub channel_id: String,
+}
+
+/// 已读状态同步载荷。
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MiniChatReadState {
+    pub server_socket: String,
+    pub channel_id: String,
+    /// 已读到的最后一条消息 id。
+    pub last_read_message_id: String,
+    /// 上报来源窗口 label（前端据此忽略自己发出的同步）。
+    pub source: String,
+}
+
+/// 全屏监听任务是否在运行（同一时间只保留一个）。
+static FULLSCREEN_WATCHER_RUNNING: AtomicBool = AtomicBool::new(false);
+
+/// 对 query 参数值做百分号编码（保留 RFC 3986 unreserved 字符）。
+fn encode_query_value(value: &str) -> String {
+    let mut out = String::with_capacity(value.len());
+    for byte in value.bytes() {
+        if byte.is_ascii_alphanumeric() || matches!(byte, b'-' | b'_' | b'.' | b'~') {
+            out.push(byte as char);
+        } else {
+            out.push_str(&format!("%{:02X}", byte));
+        }
+    }
+    out
+}
+
+fn mini_chat_query(target: &MiniChatTarget) -> String {
+    format!(
+        "window=mini-chat&server_socket={}&channel_id={}",
+        encode_query_value(&target.server_socket),
+        encode_query_value(&target.channel_id)
+    )
+}
+
+/// 打开（或切换）迷你聊天窗。
+///
+/// # 说明
+/// - 已存在时：发送切换事件并显示/聚焦，不重建窗口；
+/// - 首次打开时：恢复上次位置（越界则居中），并启动全屏自动隐藏监听。
+pub fn open(app: &AppHandle, target: MiniChatTarget) -> anyhow::Result<()> {
+    if let Some(existing) = app.get_webview_window(MINI_CHAT_LABEL) {
+        notify_target_changed(app, &target);
+        let _ = existing.show();
+        let _ = existing.set_focus();
+        tracing::debug!(action = "windows_mini_chat_target_changed", channel_id = %target.channel_id);
+        return Ok(());
+    }
+
+    let url = WebviewUrl::App(format!("index.html?{}", mini_chat_query(&target)).into());
+    let saved = window_bounds::load_named(MINI_CHAT_BOUNDS_FILE)
+        .filter(|b| window_bounds::is_within_monitors(app, b));
+    let (width, height) = saved
+        .map(|b| (f64::from(b.width), f64::from(b.height)))
+        .unwrap_or((DEFAULT_WIDTH, DEFAULT_HEIGHT));
+
+    let mut builder = WebviewWindowBuilder::new(app, MINI_CHAT_LABEL, url)
+        .title("CarryPigeon")
+        .decorations(false)
+        .always_on_top(true)
+        .skip_taskbar(true)
+        .resizable(true)
+        .min_inner_size(MIN_WIDTH, MIN_HEIGHT)
+        .inner_size(width.max(MIN_WIDTH), height.max(MIN_HEIGHT))
+        .content_protected(crate::shared::privacy::is_enabled());
+    builder = match saved {
+        Some(b) => builder.position(f64::from(b.x), f64::from(b.y)),
+        None => builder.center(),
+    };
+    let window = builder
+        .build()
+        .map_err(|e| anyhow::anyhow!(e.to_string()))?;
+    let _ = window.set_focus();
+    tracing::info!(action = "windows_mini_chat_opened", channel_id = %target.channel_id);
+
+    spawn_fullscreen_watcher(app.clone());
+    Ok(())
+}
+
+/// 迷你窗移动/缩放时持久化 bounds（由 `on_window_event` 调用）。
+pub fn remember_bounds(window: &tauri::Window) {
+    if let Some(bounds) = window_bounds::from_window(window) {
+        window_bounds::save_named_async(MINI_CHAT_BOUNDS_FILE, bounds);
+    }
+}
+
+/// 通知已打开的迷你窗切换会话。
+fn notify_target_changed(events: &dyn EventBus, target: &MiniChatTarget) {
+    events.emit_to(MINI_CHAT_LABEL, MINI_CHAT_TARGET_CHANGED_EVENT, target);
+}
+
+/// 广播已读状态到所有窗口。
+pub fn broadcast_read_state(events: &dyn EventBus, state: MiniChatReadState) {
+    events.emit(MINI_CHAT_READ_STATE_EVENT, state);
+}
+
+/// 启动全屏自动隐藏监听；迷你窗关闭后任务自行退出。
+fn spawn_fullscreen_watcher(app: AppHandle) {
+    if FULLSCREEN_WATCHER_RUNNING.swap(true, Ordering::SeqCst) {
+        return;
+    }
+    tauri::async_runtime::spawn(async move {
+        // 只记录“因全屏而隐藏”的状态，避免把用户手动隐藏的窗口重新显示出来。
+        let mut auto_hidden = false;
+        loop {
+            tokio::time::sleep(FULLSCREEN_POLL_INTERVAL).await;
+            let Some(window) = app.get_webview_window(MINI_CHAT_LABEL) else {
+                break;
+            };
+            let enabled = crate::features::settings::get_config_value::<bool>(String::from(
+                "mini_chat_auto_hide_fullscreen",
+            ))
+            .await;
+            let fullscreen = enabled && other_app_fullscreen();
+            if fullscreen && !auto_hidden && window.is_visible().unwrap_or(false) {
+                let _ = window.hide();
+                auto_hidden = true;
+                tracing::debug!(action = "windows_mini_chat_hidden_for_fullscreen");
+            } else if !fullscreen && auto_hidden && !crate::shared::app_lock::is_locked() {
+                let _ = window.show();
+                auto_hidden = false;
+                tracing::debug!(action = "windows_mini_chat_restored_after_fullscreen");
+            }
+        }
+        FULLSCREEN_WATCHER_RUNNING.store(false, Ordering::SeqCst);
+    });
+}
+
+/// 前台是否有其它应用处于全屏（游戏/演示/视频）。
+#[cfg(windows)]
+fn other_app_fullscreen() -> bool {
+    #[link(name = "shell32")]
+    unsafe extern "system" {
+        fn SHQueryUserNotificationState(state: *mut i32) -> i32;
+    }
+
+    const QUNS_BUSY: i32 = 2;
+    const QUNS_RUNNING_D3D_FULL_SCREEN: i32 = 3;
+    const QUNS_PRESENTATION_MODE: i32 = 4;
+
+    let mut state = 0i32;
+    let hr = unsafe { SHQueryUserNotificationState(&mut state) };
+    hr == 0
+        && matches!(
+            state,
+            QUNS_BUSY | QUNS_RUNNING_D3D_FULL_SCREEN | QUNS_PRESENTATION_MODE
+        )
+}
+
+/// 非 Windows 平台暂无可靠的跨应用全屏检测，视为从不全屏。
+#[cfg(not(windows))]
+fn other_app_fullscreen() -> bool {
+    false
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::shared::events::RecordingEventBus;
+
+    #[test]
+    fn query_values_are_percent_encoded() {
+        let target = MiniChatTarget {
+            server_socket: "tls://chat.example.com:8443".to_string(),
+            channel_id: "42".to_string(),
+        };
+        assert_eq!(
+            mini_chat_query(&target),
+            "window=mini-chat&server_socket=tls%3A%2F%2Fchat.example.com%3A8443&channel_id=42"
+        );
+        assert_eq!(encode_query_value("a b&c"), "a%20b%26c");
+    }
+
+    #[test]
+    fn target_changes_go_to_mini_window_and_read_state_is_broadcast() {
+        let bus = RecordingEventBus::new();
+        let target = MiniChatTarget {
+            server_socket: "tls://a:1".to_string(),
+            channel_id: "42".to_string(),
+        };
+        notify_target_changed(bus.as_ref(), &target);
+        broadcast_read_state(
+            bus.as_ref(),
+            MiniChatReadState {
+                server_socket: "tls://a:1".to_string(),
+                channel_id: "42".to_string(),
+                last_read_message_id: "m9".to_string(),
+                source: "main".to_string(),
+            },
+        );
+        let events = bus.take();
+        assert_eq!(events.len(), 2);
+        assert_eq!(events[0].event, MINI_CHAT_TARGET_CHANGED_EVENT);
+        assert_eq!(events[0].target.as_deref(), Some(MINI_CHAT_LABEL));
+        assert_eq!(
+            events[0].payload,
+            serde_json::json!({ "serverSocket": "tls://a:1", "channelId": "42" })
+        );
+        assert_eq!(events[1].event, MINI_CHAT_READ_STATE_EVENT);
+        assert_eq!(events[1].target, None);
+        assert_eq!(events[1].payload["lastReadMessageId"], "m9");
+    }
+}
@@ -3,6 +3,38 @@
 //! 说明：该文件负责导出子模块与组织依赖关系。
 //!
 //! 约定：注释中文，日志英文（tracing）。
+pub mod auth_window;
 pub mod commands;
+pub mod context_menu;
 pub mod info_window;
+pub mod lock_window;
+pub mod mini_chat;
 pub mod popover_window;
+pub mod titlebar;
+
+use crate::app::registry::{CommandRegistration, CommandRegistry, command_set};
+
+/// 窗口命令注册。
+pub struct WindowsCommands;
+
+impl CommandRegistration for WindowsCommands {
+    fn register(registry: &mut CommandRegistry) {
+        registry.add(command_set!(crate::features::windows::di::commands => [
+            to_chat_window_size,
+            get_window_capabilities,
+            compute_popover_rect,
+            open_context_menu,
+            open_popover_window,
+            open_user_popover_with_data,
+            open_info_window,
+            open_auth_window,
+            close_tray_notification_popover,
+            window_minimize,
+            window_toggle_maximize,
+            window_close_or_tray,
+            window_start_drag,
+            open_mini_chat,
+            mini_chat_mark_read,
+        ]));
+    }
+}
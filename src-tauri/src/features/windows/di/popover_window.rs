@@ -3,20 +3,37 @@
 //! 约定：注释中文，日志英文（tracing）。
 use std::sync::atomic::Ordering;
 
-use tauri::{AppHandle, Manager, WebviewUrl, WebviewWindowBuilder, WindowEvent};
+use serde::{Deserialize, Serialize};
+use tauri::{
+    AppHandle, Manager, PhysicalPosition, PhysicalSize, WebviewUrl, WebviewWindow,
+    WebviewWindowBuilder, WindowEvent,
+};
 
+use crate::features::network::data::http::cached_avatar_path;
 use crate::features::tray::di::commands::TrayUnreadState;
-use crate::features::windows::usecases::window_usecases::keep_one_popover_window;
+use crate::features::windows::domain::popover_geometry::{
+    Anchor, DesiredSize, PhysicalRect, PopoverGeometry, WorkArea, compute_popover_geometry,
+};
+use crate::features::windows::domain::window_labels::{
+    MAIN_LABEL, TRAY_NOTIFICATION_POPOVER_LABEL, USER_INFO_POPOVER_LABEL,
+};
+use crate::features::windows::domain::window_platform::PopoverPositioning;
+use crate::features::windows::usecases::window_usecases::{
+    keep_one_popover_window, window_capabilities,
+};
+use crate::shared::db::user_profile::{UserProfilePrefetch, load_user_profile_prefetch};
 
 /// 打开用户信息 Popover 窗口。
 ///
 /// 设计目标：
 /// - 避免“先闪一下再正常显示”：窗口创建前就确定 position/size。
-/// - 避免在屏幕边缘/任务栏遮挡导致内容显示不全：根据显示器 work area 约束位置/尺寸。
+/// - 避免在屏幕边缘/任务栏遮挡导致内容显示不全：根据显示器 work area 约束位置/尺寸
+///   （见 [`compute_popover_rect_impl`]）。
 ///
 /// 参数说明：
 /// - `query`: 会拼到 `index.html?...` 的查询串，用于前端路由与数据传递。
-/// - `x` / `y`: 期望弹窗出现的位置（通常来自鼠标点击的 `screenX/screenY`）。
+/// - `x` / `y`: 期望弹窗出现的位置（通常来自鼠标点击的 `screenX/screenY`；
+///   Wayland 下为相对主窗口的 `clientX/clientY`，见 [`window_capabilities`]）。
 /// - `width` / `height`: 期望弹窗大小（由前端预估传入）。
 pub async fn open_popover_window_impl(
     app: AppHandle,
@@ -25,102 +42,150 @@ pub async fn open_popover_window_impl(
     y: f64,
     width: f64,
     height: f64,
+) -> anyhow::Result<()> {
+    open_popover(app, query, x, y, width, height, None)
+}
+
+/// 带预取数据打开用户 Popover 的请求。
+#[derive(Debug, Clone, Deserialize)]
+pub struct UserPopoverRequest {
+    /// 用户所在服务器的 server DB key。
+    pub db_key: String,
+    pub user_id: i64,
+    /// 头像 id（可选，用于解析本地已下载的头像文件）。
+    #[serde(default)]
+    pub avatar_id: Option<String>,
+    /// 同 [`open_popover_window_impl`] 的 `query`/`x`/`y`/`width`/`height`。
+    pub query: String,
+    pub x: f64,
+    pub y: f64,
+    pub width: f64,
+    pub height: f64,
+}
+
+/// 打开用户信息 Popover，并把本地缓存的资料预先注入窗口。
+///
+/// 设计目标：去掉 popover 打开后“先 loading 再出内容”的闪烁。
+/// 预取数据在页面脚本执行前以 `window.__CP_POPOVER_DATA__` 注入，前端拿到即渲染，
+/// 缓存缺失的字段再按原流程补拉。
+pub async fn open_user_popover_with_data_impl(
+    app: AppHandle,
+    req: UserPopoverRequest,
+) -> anyhow::Result<()> {
+    let UserPopoverRequest {
+        db_key,
+        user_id,
+        avatar_id,
+        query,
+        x,
+        y,
+        width,
+        height,
+    } = req;
+    let profile = load_user_profile_prefetch(&db_key, user_id).await?;
+    let avatar_path = match avatar_id.as_deref() {
+        Some(id) => cached_avatar_path(id).await,
+        None => None,
+    };
+    let payload = UserPopoverData {
+        db_key,
+        profile,
+        avatar_path: avatar_path.map(|p| p.display().to_string()),
+    };
+    let script = format!(
+        "window.__CP_POPOVER_DATA__ = {};",
+        serde_json::to_string(&payload)?
+    );
+    open_popover(app, query, x, y, width, height, Some(script))
+}
+
+/// 注入到用户 Popover 的预取数据。
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct UserPopoverData {
+    db_key: String,
+    profile: UserProfilePrefetch,
+    /// 本地头像文件路径（前端用 convertFileSrc 加载）。
+    avatar_path: Option<String>,
+}
+
+fn open_popover(
+    app: AppHandle,
+    query: String,
+    x: f64,
+    y: f64,
+    width: f64,
+    height: f64,
+    init_script: Option<String>,
 ) -> anyhow::Result<()> {
     // 同一时间只允许存在一个 popover
     // 直接关闭旧窗口再创建新窗口，避免状态与 URL 不一致
     keep_one_popover_window(&app);
 
-    // 最小窗口尺寸：避免传入 0 或极小值导致不可见/难以交互。
-    let min_width = 160.0;
-    let min_height = 80.0;
-
-    // work area 边界留白：避免紧贴边缘产生“看起来被遮挡”的感觉
-    let margin = 8.0;
-
-    // 先进行基本的尺寸归一化：
-    // - clamp 到最小值
-    // - ceil 以减少亚像素带来的抖动/模糊
-    let mut width = width.max(min_width).ceil();
-    let mut height = height.max(min_height).ceil();
-
-    // 目标位置（会根据 work area 再修正）
-    let mut x = x;
-    let mut y = y;
-
-    // 尝试根据点击点找到对应显示器；找不到则 fallback 到主显示器
-    let monitor = app
-        .monitor_from_point(x, y)
-        .map_err(|e| anyhow::anyhow!(e.to_string()))?
-        .or(app
-            .primary_monitor()
-            .map_err(|e| anyhow::anyhow!(e.to_string()))?);
-
-    if let Some(monitor) = monitor {
-        // work_area 是“可用区域”（一般会排除任务栏/停靠栏）。
-        // work_area 的 position/size 是物理像素，这里转换成逻辑像素与 x/y/width/height 一致。
-        let scale_factor = monitor.scale_factor();
-        let work_area = monitor.work_area();
-
-        let work_x = work_area.position.x as f64 / scale_factor;
-        let work_y = work_area.position.y as f64 / scale_factor;
-        let work_w = work_area.size.width as f64 / scale_factor;
-        let work_h = work_area.size.height as f64 / scale_factor;
-
-        // 如果传入尺寸大于 work area，则收缩到最大可容纳范围。
-        // 注意：这里仍保留 margin，确保不会“贴边”。
-        let max_width = (work_w - margin * 2.0).max(1.0);
-        let max_height = (work_h - margin * 2.0).max(1.0);
-
-        width = width.min(max_width).ceil();
-        height = height.min(max_height).ceil();
-
-        let right = work_x + work_w;
-        let bottom = work_y + work_h;
-
-        // 如果在右/下边缘放不下，就优先翻转到左/上侧。
-        // 这样在鼠标靠近边缘时弹窗仍能完整显示。
-        if x + width > right - margin {
-            x -= width;
-        }
-        if y + height > bottom - margin {
-            y -= height;
-        }
-
-        // 最终 clamp：确保窗口完全落在 work area 范围内。
-        let min_x = work_x + margin;
-        let min_y = work_y + margin;
-        let max_x = right - width - margin;
-        let max_y = bottom - height - margin;
-
-        x = if max_x >= min_x {
-            x.clamp(min_x, max_x)
-        } else {
-            work_x
-        };
-        y = if max_y >= min_y {
-            y.clamp(min_y, max_y)
-        } else {
-            work_y
-        };
+    let capabilities = window_capabilities();
+    let parent = popover_parent(&app);
+    let geometry = compute_popover_rect_impl(
+        &app,
+        Anchor {
+            x,
+            y,
+            scale_factor: None,
+        },
+        DesiredSize { width, height },
+    )?;
+    let mut placement = geometry.logical;
+    if let Some(parent) = &parent {
+        // 合成器若仍接受坐标（如 XWayland 嵌套），按父窗口位置换算为全局坐标。
+        let origin = parent
+            .outer_position()
+            .map(|p| p.to_logical::<f64>(geometry.scale_factor))
+            .unwrap_or(tauri::LogicalPosition::new(0.0, 0.0));
+        placement.x += origin.x;
+        placement.y += origin.y;
     }
 
     // 通过 query 传递给前端路由页面。
     let url = WebviewUrl::App(format!("index.html?{}", query).into());
 
     // 关键点：position/size 在 build 之前设置，避免窗口创建后再调整导致闪烁。
-    let window = WebviewWindowBuilder::new(&app, "user-info-popover", url)
+    let mut builder = WebviewWindowBuilder::new(&app, USER_INFO_POPOVER_LABEL, url)
         .decorations(false)
         .resizable(false)
         .skip_taskbar(true)
         .always_on_top(true)
         .focusable(true)
         .focused(true)
-        .position(x, y)
-        .inner_size(width, height)
+        .position(placement.x, placement.y)
+        .inner_size(placement.width, placement.height)
+        .content_protected(crate::shared::privacy::is_enabled())
         // 兜底：在创建时再做一次“防溢出”检查。
         .prevent_overflow()
+        // 类型提示须在窗口映射前设置：先隐藏创建，设置后再显示。
+        .visible(!capabilities.layer_shell_hints);
+    if let Some(parent) = &parent {
+        builder = builder.parent(parent)?;
+    }
+    if let Some(script) = init_script {
+        builder = builder.initialization_script(script);
+    }
+    let window = builder
         .build()
         .map_err(|e| anyhow::anyhow!(e.to_string()))?;
+    // 混合 DPI：创建时的逻辑坐标按窗口所在显示器换算，若与目标显示器缩放比不同则按物理像素校正。
+    if parent.is_none()
+        && geometry.monitor_index.is_some()
+        && window
+            .scale_factor()
+            .is_ok_and(|scale| (scale - geometry.scale_factor).abs() > f64::EPSILON)
+    {
+        let physical = geometry.physical;
+        let _ = window.set_position(PhysicalPosition::new(physical.x, physical.y));
+        let _ = window.set_size(PhysicalSize::new(physical.width, physical.height));
+    }
+    if capabilities.layer_shell_hints {
+        apply_popup_hint(&window);
+        let _ = window.show();
+    }
 
     // 失焦自动关闭：popover 交互常用模式。
     let window_for_close = window.clone();
@@ -135,6 +200,74 @@ pub async fn open_popover_window_impl(
     Ok(())
 }
 
+/// 弹窗的父窗口：Wayland 下无法使用全局坐标，挂到主窗口下（坐标相对主窗口）。
+fn popover_parent(app: &AppHandle) -> Option<WebviewWindow> {
+    match window_capabilities().popover_positioning {
+        PopoverPositioning::Absolute => None,
+        PopoverPositioning::ParentRelative => app.get_webview_window(MAIN_LABEL),
+    }
+}
+
+/// 计算弹窗位置与尺寸（所有弹窗共用，见 `compute_popover_geometry`）。
+///
+/// # 说明
+/// - 绝对定位：按所有显示器的 work area 约束；
+/// - 相对定位（Wayland）：以主窗口内部区域作为唯一可用区域，结果坐标相对主窗口。
+pub fn compute_popover_rect_impl(
+    app: &AppHandle,
+    anchor: Anchor,
+    desired: DesiredSize,
+) -> anyhow::Result<PopoverGeometry> {
+    let work_areas = match popover_parent(app) {
+        Some(parent) => {
+            let size = parent.inner_size()?;
+            vec![WorkArea {
+                physical: PhysicalRect {
+                    x: 0,
+                    y: 0,
+                    width: size.width,
+                    height: size.height,
+                },
+                scale_factor: parent.scale_factor()?,
+            }]
+        }
+        None => app
+            .available_monitors()?
+            .iter()
+            .map(|monitor| {
+                // work_area 是“可用区域”（一般会排除任务栏/停靠栏），position/size 为物理像素。
+                let work_area = monitor.work_area();
+                WorkArea {
+                    physical: PhysicalRect {
+                        x: work_area.position.x,
+                        y: work_area.position.y,
+                        width: work_area.size.width,
+                        height: work_area.size.height,
+                    },
+                    scale_factor: monitor.scale_factor(),
+                }
+            })
+            .collect(),
+    };
+    Ok(compute_popover_geometry(&work_areas, anchor, desired))
+}
+
+/// 为弹窗设置 popup-menu 类型提示，wlroots 系合成器据此把它当作弹出菜单就近放置。
+#[cfg(target_os = "linux")]
+fn apply_popup_hint(window: &tauri::WebviewWindow) {
+    use gtk::prelude::GtkWindowExt;
+
+    match window.gtk_window() {
+        Ok(gtk_window) => gtk_window.set_type_hint(gtk::gdk::WindowTypeHint::PopupMenu),
+        Err(err) => {
+            tracing::warn!(action = "windows_popover_type_hint_failed", error = %err);
+        }
+    }
+}
+
+#[cfg(not(target_os = "linux"))]
+fn apply_popup_hint(_window: &tauri::WebviewWindow) {}
+
 /// 关闭托盘通知弹窗并聚焦主窗口。
 ///
 /// 点击通知弹窗中的消息时由前端触发，关闭弹窗后将主窗口置于前台。
@@ -142,10 +275,10 @@ pub fn close_notification_popover(app: &AppHandle) -> anyhow::Result<()> {
     if let Some(state) = app.try_state::<TrayUnreadState>() {
         state.popover_open.store(false, Ordering::SeqCst);
     }
-    if let Some(win) = app.get_webview_window("tray-notification-popover") {
+    if let Some(win) = app.get_webview_window(TRAY_NOTIFICATION_POPOVER_LABEL) {
         win.close().map_err(|e| anyhow::anyhow!(e.to_string()))?;
     }
-    if let Some(main) = app.get_webview_window("main") {
+    if let Some(main) = app.get_webview_window(MAIN_LABEL) {
         main.unminimize()
             .map_err(|e| anyhow::anyhow!(e.to_string()))?;
         main.show().map_err(|e| anyhow::anyhow!(e.to_string()))?;
@@ -0,0 +1,90 @@
+//! windows｜DI：自绘标题栏窗口控制（最小化/最大化/关闭/拖动）。
+//!
+//! 说明：
+//! - 关闭按钮在主窗口上走与系统关闭相同的 `CloseRequested` 处理（含未接通通话取消、关闭到托盘）；
+//! - 最大化状态由窗口 `Resized` 事件驱动比较，变化时发出 `window-maximized-changed`，
+//!   覆盖按钮、双击标题栏、系统快捷键（如 Win+↑）等所有触发来源。
+//!
+//! 约定：注释中文，日志英文（tracing）。
+
+use std::collections::HashMap;
+use std::sync::{Mutex, OnceLock};
+
+use serde::Serialize;
+use tauri::{Manager, Window};
+
+use crate::features::windows::usecases::window_usecases::close_to_tray_enabled;
+use crate::shared::events::EventBusExt;
+
+/// 最大化状态变化事件名。
+pub const WINDOW_MAXIMIZED_CHANGED_EVENT: &str = "window-maximized-changed";
+
+/// 最大化状态变化事件载荷。
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WindowMaximizedChanged {
+    pub label: String,
+    pub maximized: bool,
+}
+
+/// 标题栏关闭按钮的实际效果。
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum WindowCloseAction {
+    /// 窗口已关闭（或进入关闭流程）。
+    Closed,
+    /// 主窗口被隐藏到托盘。
+    HiddenToTray,
+}
+
+/// 每个窗口最近一次上报的最大化状态（label -> maximized）。
+fn last_maximized() -> &'static Mutex<HashMap<String, bool>> {
+    static STATE: OnceLock<Mutex<HashMap<String, bool>>> = OnceLock::new();
+    STATE.get_or_init(|| Mutex::new(HashMap::new()))
+}
+
+/// 比较并在变化时发出最大化状态事件；返回当前状态。
+pub fn notify_maximized_change(window: &Window) -> bool {
+    let Ok(maximized) = window.is_maximized() else {
+        return false;
+    };
+    let label = window.label().to_string();
+    let previous = last_maximized()
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+        .insert(label.clone(), maximized);
+    if previous != Some(maximized) {
+        tracing::debug!(action = "windows_maximized_changed", label = %label, maximized);
+        window.app_handle().emit(
+            WINDOW_MAXIMIZED_CHANGED_EVENT,
+            WindowMaximizedChanged { label, maximized },
+        );
+    }
+    maximized
+}
+
+/// 切换最大化，返回切换后的状态。
+pub fn toggle_maximize(window: &Window) -> tauri::Result<bool> {
+    if window.is_maximized()? {
+        window.unmaximize()?;
+    } else {
+        window.maximize()?;
+    }
+    Ok(notify_maximized_change(window))
+}
+
+/// 关闭或隐藏到托盘。
+///
+/// # 说明
+/// - 非主窗口直接关闭；
+/// - 主窗口调用 `close()` 交给 `CloseRequested` 统一处理，这里只根据同一设置返回结果供前端展示。
+pub fn close_or_tray(window: &Window) -> tauri::Result<WindowCloseAction> {
+    let action = if window.label() == "main" && close_to_tray_enabled(window.app_handle()) {
+        WindowCloseAction::HiddenToTray
+    } else {
+        WindowCloseAction::Closed
+    };
+    window.close()?;
+    tracing::info!(action = "windows_titlebar_close", label = %window.label(), result = ?action);
+    Ok(action)
+}
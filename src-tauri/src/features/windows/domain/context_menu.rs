@@ -0,0 +1,203 @@
+//! windows｜领域层：context_menu（通用右键菜单的结构与校验）。
+//!
+//! 说明：
+//! - 菜单由前端（聊天视图、插件）以 JSON 描述，支持普通项、勾选项、分隔线与嵌套子菜单；
+//! - 原生菜单的项 id 在进程内全局共享（托盘菜单也在其中），因此每次打开菜单都分配一个
+//!   token，把调用方的 id 编码为 `ctx:<token>:<id>`，回调时据此区分来源并取回原始 id；
+//! - 校验限制层级与项数，避免插件构造过大的菜单卡住主线程。
+//!
+//! 约定：注释中文，日志英文（tracing）。
+
+use std::collections::HashSet;
+
+use serde::Deserialize;
+
+/// 菜单项 id 前缀（与托盘等其它菜单区分）。
+const SCOPED_ID_PREFIX: &str = "ctx:";
+/// 最大嵌套层级（顶层为 1）。
+pub const MAX_MENU_DEPTH: usize = 4;
+/// 最大菜单项数（含子菜单内的项与分隔线）。
+pub const MAX_MENU_ITEMS: usize = 200;
+/// 菜单项 id 最大长度。
+const MAX_ID_LEN: usize = 128;
+
+/// 菜单项（前端 -> Rust）。
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum ContextMenuItem {
+    /// 普通项；`label` 可用 `&` 标记助记键（如 `&Copy`）。
+    Item {
+        id: String,
+        label: String,
+        #[serde(default = "default_enabled")]
+        enabled: bool,
+        /// 快捷键提示（如 `CmdOrCtrl+C`），仅用于显示。
+        #[serde(default)]
+        accelerator: Option<String>,
+    },
+    /// 勾选项。
+    Check {
+        id: String,
+        label: String,
+        #[serde(default = "default_enabled")]
+        enabled: bool,
+        #[serde(default)]
+        checked: bool,
+        #[serde(default)]
+        accelerator: Option<String>,
+    },
+    /// 分隔线。
+    Separator,
+    /// 子菜单。
+    Submenu {
+        label: String,
+        #[serde(default = "default_enabled")]
+        enabled: bool,
+        items: Vec<ContextMenuItem>,
+    },
+}
+
+fn default_enabled() -> bool {
+    true
+}
+
+/// 校验菜单：非空、层级与项数受限、可选择项的 id 非空且唯一。
+pub fn validate_menu(items: &[ContextMenuItem]) -> anyhow::Result<()> {
+    if items.is_empty() {
+        anyhow::bail!("Context menu has no items");
+    }
+    let mut ids = HashSet::new();
+    let mut count = 0usize;
+    validate_level(items, 1, &mut ids, &mut count)
+}
+
+fn validate_level<'a>(
+    items: &'a [ContextMenuItem],
+    depth: usize,
+    ids: &mut HashSet<&'a str>,
+    count: &mut usize,
+) -> anyhow::Result<()> {
+    if depth > MAX_MENU_DEPTH {
+        anyhow::bail!("Context menu is nested deeper than {MAX_MENU_DEPTH} levels");
+    }
+    for item in items {
+        *count += 1;
+        if *count > MAX_MENU_ITEMS {
+            anyhow::bail!("Context menu has more than {MAX_MENU_ITEMS} items");
+        }
+        match item {
+            ContextMenuItem::Item { id, label, .. } | ContextMenuItem::Check { id, label, .. } => {
+                if id.is_empty() || id.len() > MAX_ID_LEN {
+                    anyhow::bail!("Context menu item id must be 1..={MAX_ID_LEN} bytes");
+                }
+                if label.trim().is_empty() {
+                    anyhow::bail!("Context menu item {id} has an empty label");
+                }
+                if !ids.insert(id.as_str()) {
+                    anyhow::bail!("Duplicate context menu item id: {id}");
+                }
+            }
+            ContextMenuItem::Separator => {}
+            ContextMenuItem::Submenu { label, items, .. } => {
+                if label.trim().is_empty() {
+                    anyhow::bail!("Context submenu has an empty label");
+                }
+                if items.is_empty() {
+                    anyhow::bail!("Context submenu {label} has no items");
+                }
+                validate_level(items, depth + 1, ids, count)?;
+            }
+        }
+    }
+    Ok(())
+}
+
+/// 把调用方 id 编码为本次菜单专属的原生菜单 id。
+pub fn scoped_id(token: u64, id: &str) -> String {
+    format!("{SCOPED_ID_PREFIX}{token}:{id}")
+}
+
+/// 解析原生菜单 id；不是通用右键菜单的 id 时返回 `None`。
+pub fn parse_scoped_id(raw: &str) -> Option<(u64, &str)> {
+    let (token, id) = raw.strip_prefix(SCOPED_ID_PREFIX)?.split_once(':')?;
+    Some((token.parse().ok()?, id))
+}
+
+/// 是否为通用右键菜单的 id（托盘等其它菜单处理器据此忽略）。
+pub fn is_context_menu_id(raw: &str) -> bool {
+    parse_scoped_id(raw).is_some()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn item(id: &str) -> ContextMenuItem {
+        ContextMenuItem::Item {
+            id: id.to_string(),
+            label: id.to_uppercase(),
+            enabled: true,
+            accelerator: None,
+        }
+    }
+
+    fn submenu(items: Vec<ContextMenuItem>) -> ContextMenuItem {
+        ContextMenuItem::Submenu {
+            label: "More".to_string(),
+            enabled: true,
+            items,
+        }
+    }
+
+    #[test]
+    fn deserializes_nested_menu_with_defaults() {
+        let items: Vec<ContextMenuItem> = serde_json::from_value(serde_json::json!([
+            { "kind": "item", "id": "copy", "label": "&Copy", "accelerator": "CmdOrCtrl+C" },
+            { "kind": "separator" },
+            { "kind": "submenu", "label": "Move to", "items": [
+                { "kind": "check", "id": "pin", "label": "Pinned", "checked": true }
+            ]}
+        ]))
+        .expect("valid menu");
+        assert_eq!(items.len(), 3);
+        assert!(matches!(
+            &items[0],
+            ContextMenuItem::Item { enabled: true, .. }
+        ));
+        assert!(matches!(
+            &items[2],
+            ContextMenuItem::Submenu { items, .. }
+                if matches!(&items[0], ContextMenuItem::Check { checked: true, .. })
+        ));
+        validate_menu(&items).expect("menu is valid");
+    }
+
+    #[test]
+    fn rejects_duplicates_empty_and_deep_menus() {
+        assert!(validate_menu(&[]).is_err());
+        // 子菜单中的 id 与顶层重复。
+        assert!(validate_menu(&[item("a"), submenu(vec![item("a")])]).is_err());
+        assert!(validate_menu(&[submenu(vec![])]).is_err());
+        assert!(validate_menu(&[item("")]).is_err());
+
+        let mut nested = item("leaf");
+        for _ in 0..MAX_MENU_DEPTH - 1 {
+            nested = submenu(vec![nested]);
+        }
+        assert!(validate_menu(std::slice::from_ref(&nested)).is_ok());
+        assert!(validate_menu(&[submenu(vec![nested])]).is_err());
+
+        let many: Vec<ContextMenuItem> =
+            (0..=MAX_MENU_ITEMS).map(|i| item(&i.to_string())).collect();
+        assert!(validate_menu(&many).is_err());
+    }
+
+    #[test]
+    fn scoped_ids_round_trip_and_allow_colons_in_ids() {
+        let raw = scoped_id(7, "plugin:action");
+        assert_eq!(parse_scoped_id(&raw), Some((7, "plugin:action")));
+        assert!(is_context_menu_id(&raw));
+        assert!(!is_context_menu_id("show_window"));
+        assert!(!is_context_menu_id("ctx:x:copy"));
+    }
+}
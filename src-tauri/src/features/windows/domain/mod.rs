@@ -4,4 +4,8 @@
 //!
 //! 约定：注释中文，日志英文（tracing）。
 // Domain layer for the windows feature.
-// Keep this free of IO; usually empty for UI-only concerns.
+// Keep this free of IO.
+pub mod context_menu;
+pub mod popover_geometry;
+pub mod window_labels;
+pub mod window_platform;
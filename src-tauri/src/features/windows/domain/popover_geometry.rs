@@ -0,0 +1,345 @@
+//! windows｜领域层：popover_geometry（弹窗位置/尺寸计算）。
+//!
+//! 说明：
+//! - 输入为各显示器的 work area（物理像素 + 缩放比）、锚点（逻辑像素）与期望尺寸，
+//!   输出弹窗的逻辑/物理矩形；不依赖 Tauri，便于测试，也供其它弹窗（表情选择、
+//!   回应栏等）经 `compute_popover_rect` 复用；
+//! - 混合 DPI：各显示器逻辑坐标按自身缩放比换算，相邻显示器的逻辑区域可能重叠或留缝。
+//!   锚点落在多个显示器内时优先选择与来源窗口缩放比一致的显示器；落在缝隙中时选择最近的；
+//! - 弹窗整体约束在所选显示器内（不跨屏），物理矩形按该显示器缩放比换算，
+//!   调用方据此校正实际所在显示器缩放比不同导致的偏差。
+//!
+//! 约定：注释中文，日志英文（tracing）。
+
+use serde::{Deserialize, Serialize};
+
+/// 弹窗最小宽度（逻辑像素）：避免传入 0 或极小值导致不可见/难以交互。
+pub const POPOVER_MIN_WIDTH: f64 = 160.0;
+/// 弹窗最小高度（逻辑像素）。
+pub const POPOVER_MIN_HEIGHT: f64 = 80.0;
+/// work area 边界留白：避免紧贴边缘产生“看起来被遮挡”的感觉。
+pub const POPOVER_MARGIN: f64 = 8.0;
+
+/// 逻辑像素矩形（位置 + 尺寸）。
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Rect {
+    pub x: f64,
+    pub y: f64,
+    pub width: f64,
+    pub height: f64,
+}
+
+impl Rect {
+    fn contains(&self, x: f64, y: f64) -> bool {
+        x >= self.x && x < self.x + self.width && y >= self.y && y < self.y + self.height
+    }
+
+    /// 点到矩形的距离平方（点在矩形内为 0）。
+    fn distance_sq(&self, x: f64, y: f64) -> f64 {
+        let dx = (self.x - x).max(0.0).max(x - (self.x + self.width));
+        let dy = (self.y - y).max(0.0).max(y - (self.y + self.height));
+        dx * dx + dy * dy
+    }
+}
+
+/// 物理像素矩形。
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PhysicalRect {
+    pub x: i32,
+    pub y: i32,
+    pub width: u32,
+    pub height: u32,
+}
+
+/// 单个显示器的可用区域（一般排除任务栏/停靠栏）。
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct WorkArea {
+    /// 物理像素区域。
+    pub physical: PhysicalRect,
+    /// 显示器缩放比（1.0 / 1.25 / 2.0 ...）。
+    pub scale_factor: f64,
+}
+
+impl WorkArea {
+    /// 按自身缩放比换算的逻辑区域。
+    pub fn logical(&self) -> Rect {
+        Rect {
+            x: self.physical.x as f64 / self.scale_factor,
+            y: self.physical.y as f64 / self.scale_factor,
+            width: self.physical.width as f64 / self.scale_factor,
+            height: self.physical.height as f64 / self.scale_factor,
+        }
+    }
+}
+
+/// 弹窗锚点（期望的左上角，逻辑像素）。
+#[derive(Debug, Clone, Copy, PartialEq, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Anchor {
+    pub x: f64,
+    pub y: f64,
+    /// 来源窗口的缩放比（前端 `devicePixelRatio`）；用于在重叠区域中选择显示器。
+    #[serde(default)]
+    pub scale_factor: Option<f64>,
+}
+
+/// 期望的弹窗尺寸（逻辑像素）。
+#[derive(Debug, Clone, Copy, PartialEq, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DesiredSize {
+    pub width: f64,
+    pub height: f64,
+}
+
+/// 计算结果（Rust -> 前端，`compute_popover_rect` 返回）。
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PopoverGeometry {
+    /// 逻辑像素矩形（所选显示器的逻辑坐标系）。
+    pub logical: Rect,
+    /// 物理像素矩形（按所选显示器缩放比换算）。
+    pub physical: PhysicalRect,
+    /// 所选显示器的缩放比；没有显示器信息时为 1.0。
+    pub scale_factor: f64,
+    /// 所选显示器在输入列表中的下标；没有显示器信息时为 `None`。
+    pub monitor_index: Option<usize>,
+}
+
+/// 在边界内放置弹窗：放不下时翻转到锚点左/上侧，最后整体约束在边界内（留 `margin`）。
+///
+/// # 参数
+/// - `anchor_x` / `anchor_y`：期望的左上角（与 `bounds` 同一坐标系）。
+/// - `width` / `height`：期望尺寸（已归一化）。
+/// - `bounds`：可用区域（显示器 work area，或相对定位时的父窗口内部区域）。
+pub fn place_within(
+    anchor_x: f64,
+    anchor_y: f64,
+    width: f64,
+    height: f64,
+    bounds: Rect,
+    margin: f64,
+) -> Rect {
+    // 如果传入尺寸大于可用区域，则收缩到最大可容纳范围（仍保留 margin）。
+    let width = width.min((bounds.width - margin * 2.0).max(1.0)).ceil();
+    let height = height.min((bounds.height - margin * 2.0).max(1.0)).ceil();
+
+    let right = bounds.x + bounds.width;
+    let bottom = bounds.y + bounds.height;
+    let mut x = anchor_x;
+    let mut y = anchor_y;
+
+    // 如果在右/下边缘放不下，就优先翻转到左/上侧。
+    if x + width > right - margin {
+        x -= width;
+    }
+    if y + height > bottom - margin {
+        y -= height;
+    }
+
+    // 最终 clamp：确保窗口完全落在可用区域内。
+    let min_x = bounds.x + margin;
+    let min_y = bounds.y + margin;
+    let max_x = right - width - margin;
+    let max_y = bottom - height - margin;
+    Rect {
+        x: if max_x >= min_x {
+            x.clamp(min_x, max_x)
+        } else {
+            bounds.x
+        },
+        y: if max_y >= min_y {
+            y.clamp(min_y, max_y)
+        } else {
+            bounds.y
+        },
+        width,
+        height,
+    }
+}
+
+/// 选择锚点所在的显示器（见模块说明）。
+fn pick_monitor(work_areas: &[WorkArea], anchor: Anchor) -> Option<usize> {
+    let containing: Vec<usize> = work_areas
+        .iter()
+        .enumerate()
+        .filter(|(_, area)| area.logical().contains(anchor.x, anchor.y))
+        .map(|(index, _)| index)
+        .collect();
+    let same_scale = anchor.scale_factor.and_then(|scale| {
+        containing
+            .iter()
+            .copied()
+            .find(|&index| (work_areas[index].scale_factor - scale).abs() < 0.01)
+    });
+    same_scale.or(containing.first().copied()).or_else(|| {
+        work_areas
+            .iter()
+            .enumerate()
+            .min_by(|(_, a), (_, b)| {
+                let da = a.logical().distance_sq(anchor.x, anchor.y);
+                let db = b.logical().distance_sq(anchor.x, anchor.y);
+                da.total_cmp(&db)
+            })
+            .map(|(index, _)| index)
+    })
+}
+
+/// 计算弹窗的位置与尺寸。
+///
+/// # 参数
+/// - `work_areas`：所有显示器的可用区域；为空时不做约束。
+/// - `anchor`：期望的左上角（逻辑像素，通常来自点击的 `screenX/screenY`）。
+/// - `desired`：期望尺寸（逻辑像素，由前端预估）。
+pub fn compute_popover_geometry(
+    work_areas: &[WorkArea],
+    anchor: Anchor,
+    desired: DesiredSize,
+) -> PopoverGeometry {
+    // 尺寸归一化：clamp 到最小值，ceil 以减少亚像素带来的抖动/模糊。
+    let width = desired.width.max(POPOVER_MIN_WIDTH).ceil();
+    let height = desired.height.max(POPOVER_MIN_HEIGHT).ceil();
+
+    let monitor_index = pick_monitor(work_areas, anchor);
+    let (logical, scale_factor) = match monitor_index {
+        Some(index) => {
+            let area = work_areas[index];
+            let logical = place_within(
+                anchor.x,
+                anchor.y,
+                width,
+                height,
+                area.logical(),
+                POPOVER_MARGIN,
+            );
+            (logical, area.scale_factor)
+        }
+        None => (
+            Rect {
+                x: anchor.x,
+                y: anchor.y,
+                width,
+                height,
+            },
+            1.0,
+        ),
+    };
+    PopoverGeometry {
+        logical,
+        physical: PhysicalRect {
+            x: (logical.x * scale_factor).round() as i32,
+            y: (logical.y * scale_factor).round() as i32,
+            width: (logical.width * scale_factor).round() as u32,
+            height: (logical.height * scale_factor).round() as u32,
+        },
+        scale_factor,
+        monitor_index,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn area(x: i32, y: i32, width: u32, height: u32, scale_factor: f64) -> WorkArea {
+        WorkArea {
+            physical: PhysicalRect {
+                x,
+                y,
+                width,
+                height,
+            },
+            scale_factor,
+        }
+    }
+
+    fn anchor(x: f64, y: f64, scale_factor: Option<f64>) -> Anchor {
+        Anchor { x, y, scale_factor }
+    }
+
+    const DESIRED: DesiredSize = DesiredSize {
+        width: 200.0,
+        height: 100.0,
+    };
+
+    #[test]
+    fn place_within_flips_and_clamps() {
+        let bounds = Rect {
+            x: 0.0,
+            y: 0.0,
+            width: 800.0,
+            height: 600.0,
+        };
+        // 放得下：保持原位置。
+        let r = place_within(100.0, 100.0, 200.0, 100.0, bounds, 8.0);
+        assert_eq!((r.x, r.y), (100.0, 100.0));
+        // 靠近右下角：翻转到左上侧。
+        let r = place_within(700.0, 550.0, 200.0, 100.0, bounds, 8.0);
+        assert_eq!((r.x, r.y), (500.0, 450.0));
+        // 比可用区域还大：收缩并贴齐 margin。
+        let r = place_within(0.0, 0.0, 2000.0, 100.0, bounds, 8.0);
+        assert_eq!((r.x, r.width), (8.0, 784.0));
+    }
+
+    #[test]
+    fn single_monitor_enforces_min_size_and_physical_scale() {
+        let areas = [area(0, 0, 3840, 2160, 2.0)];
+        let geometry = compute_popover_geometry(
+            &areas,
+            anchor(100.0, 100.0, None),
+            DesiredSize {
+                width: 10.0,
+                height: 10.0,
+            },
+        );
+        assert_eq!(geometry.monitor_index, Some(0));
+        assert_eq!(geometry.logical.width, POPOVER_MIN_WIDTH);
+        assert_eq!(geometry.logical.height, POPOVER_MIN_HEIGHT);
+        assert_eq!(
+            geometry.physical,
+            PhysicalRect {
+                x: 200,
+                y: 200,
+                width: 320,
+                height: 160,
+            }
+        );
+    }
+
+    #[test]
+    fn overlapping_mixed_dpi_monitors_prefer_source_scale() {
+        // 左：1920x1080 @1.0（逻辑 0..1920）；右：3840x2160 @2.0，物理起点 1920（逻辑 960..2880）。
+        let areas = [area(0, 0, 1920, 1080, 1.0), area(1920, 0, 3840, 2160, 2.0)];
+        let left = compute_popover_geometry(&areas, anchor(1000.0, 100.0, Some(1.0)), DESIRED);
+        assert_eq!(left.monitor_index, Some(0));
+        let right = compute_popover_geometry(&areas, anchor(1000.0, 100.0, Some(2.0)), DESIRED);
+        assert_eq!(right.monitor_index, Some(1));
+        assert_eq!(right.scale_factor, 2.0);
+        assert_eq!(right.physical.x, 2000);
+        // 未提供缩放比时取第一个命中的显示器。
+        let default = compute_popover_geometry(&areas, anchor(1000.0, 100.0, None), DESIRED);
+        assert_eq!(default.monitor_index, Some(0));
+        // 只有右侧显示器包含锚点。
+        let only_right =
+            compute_popover_geometry(&areas, anchor(2000.0, 100.0, Some(1.0)), DESIRED);
+        assert_eq!(only_right.monitor_index, Some(1));
+    }
+
+    #[test]
+    fn anchor_in_gap_uses_nearest_monitor_and_clamps_into_it() {
+        // 左 @2.0（逻辑 0..960），右 @1.0 物理起点 1920（逻辑 1920..3840）：中间留缝。
+        let areas = [area(0, 0, 1920, 1080, 2.0), area(1920, 0, 1920, 1080, 1.0)];
+        let geometry = compute_popover_geometry(&areas, anchor(1800.0, 100.0, None), DESIRED);
+        assert_eq!(geometry.monitor_index, Some(1));
+        assert_eq!(geometry.logical.x, 1920.0 + POPOVER_MARGIN);
+    }
+
+    #[test]
+    fn no_monitors_leaves_anchor_unconstrained() {
+        let geometry = compute_popover_geometry(&[], anchor(-50.0, 20.0, None), DESIRED);
+        assert_eq!(geometry.monitor_index, None);
+        assert_eq!((geometry.logical.x, geometry.logical.y), (-50.0, 20.0));
+        assert_eq!(geometry.scale_factor, 1.0);
+    }
+}
@@ -0,0 +1,63 @@
+//! windows｜领域层：window_labels（应用自身窗口的 label 清单）。
+//!
+//! 说明：
+//! - 这里集中声明由应用创建、加载应用自身页面的窗口 label，创建窗口与按 label 判定来源
+//!   （如 WebView SQL 策略）都以此为准；
+//! - 服务端认证窗口（`auth-<seq>`）加载外部页面，不属于应用窗口，不在清单内。
+//!
+//! 约定：注释中文，日志英文（tracing）。
+
+/// 主窗口。
+pub const MAIN_LABEL: &str = "main";
+
+/// 截图覆盖层。
+pub const SCREENSHOT_OVERLAY_LABEL: &str = "screenshot-overlay";
+
+/// 画中画迷你聊天窗。
+pub const MINI_CHAT_LABEL: &str = "mini-chat";
+
+/// 应用锁锁屏窗口。
+pub const APP_LOCK_LABEL: &str = "app-lock";
+
+/// 用户/频道信息等 popover（同一时间只保留一个）。
+pub const USER_INFO_POPOVER_LABEL: &str = "user-info-popover";
+
+/// 频道信息 popover（旧 label，仍按 popover 处理）。
+pub const CHANNEL_INFO_POPOVER_LABEL: &str = "channel-info-popover";
+
+/// 通用 popover（旧 label，仍按 popover 处理）。
+pub const POPOVER_LABEL: &str = "popover";
+
+/// 托盘通知 popover。
+pub const TRAY_NOTIFICATION_POPOVER_LABEL: &str = "tray-notification-popover";
+
+/// 频道信息窗口。
+pub const CHANNEL_INFO_LABEL: &str = "channel-info";
+
+/// 用户资料窗口。
+pub const USER_PROFILE_LABEL: &str = "user-profile";
+
+/// 信息窗口未指定 label 时的默认值。
+pub const INFO_WINDOW_DEFAULT_LABEL: &str = "info-window";
+
+/// 全部应用窗口 label。
+pub const APP_WINDOW_LABELS: &[&str] = &[
+    MAIN_LABEL,
+    SCREENSHOT_OVERLAY_LABEL,
+    MINI_CHAT_LABEL,
+    APP_LOCK_LABEL,
+    USER_INFO_POPOVER_LABEL,
+    CHANNEL_INFO_POPOVER_LABEL,
+    POPOVER_LABEL,
+    TRAY_NOTIFICATION_POPOVER_LABEL,
+    CHANNEL_INFO_LABEL,
+    USER_PROFILE_LABEL,
+    INFO_WINDOW_DEFAULT_LABEL,
+];
+
+/// 信息窗口允许使用的 label。
+pub const INFO_WINDOW_LABELS: &[&str] = &[
+    CHANNEL_INFO_LABEL,
+    USER_PROFILE_LABEL,
+    INFO_WINDOW_DEFAULT_LABEL,
+];
@@ -0,0 +1,156 @@
+//! windows｜领域层：window_platform（窗口系统能力与弹窗定位策略）。
+//!
+//! 说明：
+//! - Wayland 不允许客户端设置窗口的全局坐标（`set_position` 被合成器忽略），
+//!   因此弹窗在 Wayland 下改为“相对父窗口”定位：挂到主窗口下（transient），
+//!   坐标按父窗口内的位置计算并约束在父窗口范围内；
+//! - 通过 `GDK_BACKEND=x11` 走 XWayland 时仍按 X11 处理（可用绝对坐标）；
+//! - 基于 wlroots 的合成器（sway / Hyprland 等）通常按窗口类型提示放置弹出窗，
+//!   此时额外设置 popup-menu 类型提示（见 `layer_shell_hints`）；
+//! - 本模块只依赖传入的 OS 名与环境变量读取函数，不做 IO，便于测试。
+//!
+//! 约定：注释中文，日志英文（tracing）。
+
+use serde::Serialize;
+
+/// 桌面会话（窗口系统）类型。
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SessionType {
+    Windows,
+    Macos,
+    X11,
+    Wayland,
+    Unknown,
+}
+
+/// 弹窗定位策略。
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PopoverPositioning {
+    /// 使用屏幕全局坐标，并按显示器 work area 约束。
+    Absolute,
+    /// 挂到父窗口下，坐标相对父窗口并约束在父窗口范围内。
+    ParentRelative,
+}
+
+/// 当前窗口系统能力（Rust -> 前端，`get_window_capabilities` 返回）。
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WindowCapabilities {
+    pub session_type: SessionType,
+    /// 弹窗定位策略；`parent_relative` 时前端应传入相对主窗口的坐标（`clientX/clientY`）。
+    pub popover_positioning: PopoverPositioning,
+    /// 能否设置/读取窗口的全局坐标（Wayland 下为 false）。
+    pub global_position: bool,
+    /// 是否为弹窗设置 popup-menu 类型提示，由合成器就近放置。
+    pub layer_shell_hints: bool,
+}
+
+/// 常见的 wlroots 系合成器（`XDG_CURRENT_DESKTOP` 取值，小写比较）。
+const WLROOTS_DESKTOPS: &[&str] = &["sway", "hyprland", "river", "wayfire", "labwc", "niri"];
+
+/// 根据 OS 与环境变量判断会话类型。
+///
+/// # 参数
+/// - `os`：`std::env::consts::OS`。
+/// - `env`：读取环境变量（测试时注入）。
+pub fn detect_session_type(os: &str, env: impl Fn(&str) -> Option<String>) -> SessionType {
+    match os {
+        "windows" => return SessionType::Windows,
+        "macos" => return SessionType::Macos,
+        "linux" | "freebsd" | "openbsd" | "netbsd" | "dragonfly" => {}
+        _ => return SessionType::Unknown,
+    }
+    let non_empty = |key: &str| env(key).filter(|v| !v.trim().is_empty());
+    // 显式要求 GTK 走 X11 后端时，即使在 Wayland 会话中也经 XWayland 运行。
+    let forced_x11 = non_empty("GDK_BACKEND")
+        .is_some_and(|backend| backend.split(',').next().map(str::trim) == Some("x11"));
+    let wayland = non_empty("WAYLAND_DISPLAY").is_some()
+        || non_empty("XDG_SESSION_TYPE").is_some_and(|t| t.eq_ignore_ascii_case("wayland"));
+    if wayland && !forced_x11 {
+        return SessionType::Wayland;
+    }
+    if non_empty("DISPLAY").is_some()
+        || non_empty("XDG_SESSION_TYPE").is_some_and(|t| t.eq_ignore_ascii_case("x11"))
+    {
+        return SessionType::X11;
+    }
+    SessionType::Unknown
+}
+
+/// 按会话类型推导窗口能力。
+pub fn capabilities_for(
+    session_type: SessionType,
+    env: impl Fn(&str) -> Option<String>,
+) -> WindowCapabilities {
+    let wayland = session_type == SessionType::Wayland;
+    let wlroots = env("XDG_CURRENT_DESKTOP").is_some_and(|desktops| {
+        desktops
+            .split(':')
+            .any(|d| WLROOTS_DESKTOPS.contains(&d.trim().to_ascii_lowercase().as_str()))
+    });
+    WindowCapabilities {
+        session_type,
+        popover_positioning: if wayland {
+            PopoverPositioning::ParentRelative
+        } else {
+            PopoverPositioning::Absolute
+        },
+        global_position: !wayland,
+        layer_shell_hints: wayland && wlroots,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn env_of(pairs: &'static [(&'static str, &'static str)]) -> impl Fn(&str) -> Option<String> {
+        move |key| {
+            pairs
+                .iter()
+                .find(|(k, _)| *k == key)
+                .map(|(_, v)| v.to_string())
+        }
+    }
+
+    #[test]
+    fn detects_wayland_unless_gdk_forces_x11() {
+        let wayland = env_of(&[("WAYLAND_DISPLAY", "wayland-0"), ("DISPLAY", ":0")]);
+        assert_eq!(detect_session_type("linux", wayland), SessionType::Wayland);
+        let xwayland = env_of(&[
+            ("WAYLAND_DISPLAY", "wayland-0"),
+            ("DISPLAY", ":0"),
+            ("GDK_BACKEND", "x11"),
+        ]);
+        assert_eq!(detect_session_type("linux", xwayland), SessionType::X11);
+        let x11 = env_of(&[("XDG_SESSION_TYPE", "x11")]);
+        assert_eq!(detect_session_type("linux", x11), SessionType::X11);
+        assert_eq!(
+            detect_session_type("linux", env_of(&[])),
+            SessionType::Unknown
+        );
+        assert_eq!(
+            detect_session_type("windows", env_of(&[("WAYLAND_DISPLAY", "w")])),
+            SessionType::Windows
+        );
+    }
+
+    #[test]
+    fn wayland_uses_parent_relative_positioning_and_wlroots_hints() {
+        let sway = env_of(&[("XDG_CURRENT_DESKTOP", "sway")]);
+        let caps = capabilities_for(SessionType::Wayland, sway);
+        assert_eq!(caps.popover_positioning, PopoverPositioning::ParentRelative);
+        assert!(!caps.global_position);
+        assert!(caps.layer_shell_hints);
+
+        let gnome = env_of(&[("XDG_CURRENT_DESKTOP", "ubuntu:GNOME")]);
+        assert!(!capabilities_for(SessionType::Wayland, gnome).layer_shell_hints);
+
+        let caps = capabilities_for(SessionType::X11, env_of(&[("XDG_CURRENT_DESKTOP", "sway")]));
+        assert_eq!(caps.popover_positioning, PopoverPositioning::Absolute);
+        assert!(caps.global_position);
+        assert!(!caps.layer_shell_hints);
+    }
+}
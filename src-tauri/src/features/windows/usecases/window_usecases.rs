@@ -1,8 +1,19 @@
 //! windows｜用例层：window_usecases。
 //!
 //! 约定：注释中文，日志英文（tracing）。
+use std::sync::OnceLock;
+use std::sync::atomic::Ordering;
+
 use tauri::{AppHandle, Manager};
 
+use crate::features::windows::domain::window_labels::{
+    CHANNEL_INFO_POPOVER_LABEL, POPOVER_LABEL, USER_INFO_POPOVER_LABEL,
+};
+use crate::features::windows::domain::window_platform::{
+    WindowCapabilities, capabilities_for, detect_session_type,
+};
+use crate::shared::close_to_tray_state::CloseToTrayState;
+
 /// 确保同一时刻只保留一个 Popover 窗口。
 ///
 /// # 参数
@@ -16,10 +27,35 @@ use tauri::{AppHandle, Manager};
 /// - 若存在任意一个，则尝试关闭；关闭失败会被忽略（best-effort）。
 pub fn keep_one_popover_window(app: &AppHandle) {
     if let Some(existing) = app
-        .get_webview_window("user-info-popover")
-        .or(app.get_webview_window("popover"))
-        .or(app.get_webview_window("channel-info-popover"))
+        .get_webview_window(USER_INFO_POPOVER_LABEL)
+        .or(app.get_webview_window(POPOVER_LABEL))
+        .or(app.get_webview_window(CHANNEL_INFO_POPOVER_LABEL))
     {
         let _ = existing.close();
     }
 }
+
+/// 当前是否开启“关闭到托盘”（读取 setup 阶段缓存的 `CloseToTrayState`）。
+///
+/// # 说明
+/// 主窗口 `CloseRequested` 处理与标题栏关闭按钮共用该判断，保证两条路径行为一致。
+pub fn close_to_tray_enabled(app: &AppHandle) -> bool {
+    app.try_state::<CloseToTrayState>()
+        .is_some_and(|state| state.0.load(Ordering::SeqCst))
+}
+
+/// 当前窗口系统能力（进程内检测一次，会话类型在运行期间不会变化）。
+pub fn window_capabilities() -> WindowCapabilities {
+    static CAPABILITIES: OnceLock<WindowCapabilities> = OnceLock::new();
+    *CAPABILITIES.get_or_init(|| {
+        let env = |key: &str| std::env::var(key).ok();
+        let capabilities = capabilities_for(detect_session_type(std::env::consts::OS, env), env);
+        tracing::info!(
+            action = "windows_capabilities_detected",
+            session_type = ?capabilities.session_type,
+            popover_positioning = ?capabilities.popover_positioning,
+            layer_shell_hints = capabilities.layer_shell_hints
+        );
+        capabilities
+    })
+}
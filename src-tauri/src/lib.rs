@@ -8,7 +8,7 @@ pub mod shared;
 // 初始化 rust-i18n，加载 locales/ 目录下的翻译文件
 rust_i18n::i18n!("locales");
 
-/// 运行 Tauri 应用。
+/// 以前台模式运行 Tauri 应用。
 ///
 /// # 返回值
 /// - `Ok(())`：启动成功并进入事件循环。
@@ -17,5 +17,22 @@ rust_i18n::i18n!("locales");
 /// # 说明
 /// 该函数是二进制入口（`main.rs`）调用的统一入口，便于测试与复用。
 pub fn run() -> anyhow::Result<()> {
-    app::run()
+    app::run(app::launch_mode::LaunchMode::Foreground)
+}
+
+/// 按启动模式运行 Tauri 应用（`main.rs` 解析命令行参数后调用）。
+///
+/// # 参数
+/// - `mode`：命令行指定的启动模式（`--hidden` / `--minimized`），见 [`app::launch_mode`]。
+pub fn run_with_mode(mode: app::launch_mode::LaunchMode) -> anyhow::Result<()> {
+    app::run(mode)
+}
+
+/// 移动端（Android / iOS）入口：由系统拉起，没有命令行参数，始终以前台模式运行。
+#[cfg(mobile)]
+#[tauri::mobile_entry_point]
+fn mobile_main() {
+    if let Err(e) = run() {
+        tracing::error!(action = "app_mobile_start_failed", error = %e);
+    }
 }
@@ -4,24 +4,34 @@
 // Prevents additional console window on Windows in release, DO NOT REMOVE!!
 #![cfg_attr(not(debug_assertions), windows_subsystem = "windows")]
 
-use tracing_subscriber::{
-    Registry, filter::EnvFilter, fmt, layer::SubscriberExt, util::SubscriberInitExt,
-};
+#[cfg(desktop)]
+use carrypigeon_desktop_lib::app::cli::{self, Invocation};
+use carrypigeon_desktop_lib::shared::log::subscriber::{FileLogWriter, build_subscriber};
+use tracing_subscriber::{filter::EnvFilter, util::SubscriberInitExt};
 
 #[tokio::main]
 async fn main() -> anyhow::Result<()> {
     let env_filter = EnvFilter::try_from_default_env()
         .unwrap_or_else(|_| EnvFilter::new("info,sqlx::query=warn"));
-    let formatting_layer = fmt::layer().pretty().with_writer(std::io::stderr);
-
-    Registry::default()
-        .with(env_filter)
-        .with(formatting_layer)
-        .init();
+    // 全局 subscriber 只能安装一次：correlation/指标层与文件日志层在此一并挂上，
+    // 文件日志在 setup 解析出数据目录后才开始写入（见 `attach_file_log`）。
+    build_subscriber(env_filter, FileLogWriter).init();
 
+    // 子命令（send / export-logs / doctor）无界面执行；否则按 `--hidden` / `--minimized`
+    // 启动界面（未指定时按设置决定）。
+    #[cfg(desktop)]
+    let mode = match Invocation::from_args(std::env::args()) {
+        Invocation::Gui(mode) => mode,
+        Invocation::Command(command) => std::process::exit(cli::run_command(command).await),
+        Invocation::Exit(err) => err.exit(),
+    };
+    // 移动端没有命令行：始终以前台模式运行。
+    #[cfg(mobile)]
+    let mode = carrypigeon_desktop_lib::app::launch_mode::LaunchMode::Foreground;
     tracing::info!(
         action = "app_lifecycle_started",
+        mode = ?mode,
         "CarryPigeon Desktop started"
     );
-    carrypigeon_desktop_lib::run()
+    carrypigeon_desktop_lib::run_with_mode(mode)
 }
@@ -0,0 +1,87 @@
+//! app_lock｜系统生物识别解锁（Windows Hello / Touch ID）。
+//!
+//! 说明：
+//! - Windows 使用 WinRT `UserConsentVerifier`；macOS 使用 `LAContext`（LocalAuthentication）；
+//! - 其它平台视为不可用；
+//! - 均为阻塞调用（会弹出系统验证对话框），需在 `spawn_blocking` 中使用。
+//!
+//! 约定：注释中文，日志英文（tracing）。
+
+/// 当前设备是否支持并已配置生物识别。
+pub fn available() -> bool {
+    platform::available()
+}
+
+/// 弹出系统生物识别验证。
+///
+/// # 返回值
+/// - `Ok(true)`：验证通过；
+/// - `Ok(false)`：用户取消或验证未通过。
+pub fn verify(reason: &str) -> anyhow::Result<bool> {
+    platform::verify(reason)
+}
+
+#[cfg(target_os = "windows")]
+mod platform {
+    use windows::Security::Credentials::UI::{
+        UserConsentVerificationResult, UserConsentVerifier, UserConsentVerifierAvailability,
+    };
+    use windows::core::HSTRING;
+
+    pub fn available() -> bool {
+        UserConsentVerifier::CheckAvailabilityAsync()
+            .and_then(|op| op.get())
+            .map(|availability| availability == UserConsentVerifierAvailability::Available)
+            .unwrap_or(false)
+    }
+
+    pub fn verify(reason: &str) -> anyhow::Result<bool> {
+        let result =
+            UserConsentVerifier::RequestVerificationAsync(&HSTRING::from(reason))?.get()?;
+        Ok(result == UserConsentVerificationResult::Verified)
+    }
+}
+
+// objc2 绑定在不同版本中对方法安全性的标注不同，统一包在 unsafe 中。
+#[cfg(target_os = "macos")]
+#[allow(unused_unsafe)]
+mod platform {
+    use block2::RcBlock;
+    use objc2::runtime::Bool;
+    use objc2_foundation::{NSError, NSString};
+    use objc2_local_authentication::{LAContext, LAPolicy};
+
+    const POLICY: LAPolicy = LAPolicy::DeviceOwnerAuthenticationWithBiometrics;
+
+    pub fn available() -> bool {
+        let context = unsafe { LAContext::new() };
+        unsafe { context.canEvaluatePolicy_error(POLICY) }.is_ok()
+    }
+
+    pub fn verify(reason: &str) -> anyhow::Result<bool> {
+        let context = unsafe { LAContext::new() };
+        let (tx, rx) = std::sync::mpsc::channel();
+        let reply = RcBlock::new(move |success: Bool, _error: *mut NSError| {
+            let _ = tx.send(success.as_bool());
+        });
+        unsafe {
+            context.evaluatePolicy_localizedReason_reply(
+                POLICY,
+                &NSString::from_str(reason),
+                &reply,
+            );
+        }
+        Ok(rx.recv().unwrap_or(false))
+    }
+}
+
+#[cfg(not(any(target_os = "windows", target_os = "macos")))]
+mod platform {
+    pub fn available() -> bool {
+        false
+    }
+
+    pub fn verify(_reason: &str) -> anyhow::Result<bool> {
+        Ok(false)
+    }
+}
@@ -0,0 +1,202 @@
+//! app_lock｜Tauri 命令
+
+use serde::Serialize;
+use tauri::AppHandle;
+
+use crate::shared::db::channel_keys;
+use crate::shared::error::{CommandError, CommandResult, command_error, to_command_error};
+
+use super::{
+    biometric, check_verifier, is_locked, load_verifier, lock, make_verifier, record_failure,
+    retry_after, store_verifier, unlock,
+};
+
+/// 口令最小长度（字符数）。
+const MIN_PASSPHRASE_CHARS: usize = 4;
+
+/// 应用锁状态。
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AppLockStatus {
+    /// 是否已设置口令（应用锁已启用）。
+    pub configured: bool,
+    pub locked: bool,
+    /// 当前设备是否支持生物识别。
+    pub biometric_available: bool,
+    /// 是否允许生物识别解锁（设置 `app_lock_biometric`）。
+    pub biometric_enabled: bool,
+    /// 空闲自动锁定分钟数（0 = 关闭）。
+    pub idle_minutes: u32,
+}
+
+fn failed(e: anyhow::Error) -> CommandError {
+    to_command_error("APP_LOCK_FAILED", "error.app_lock_failed", e)
+}
+
+async fn blocking<T, F>(f: F) -> CommandResult<T>
+where
+    T: Send + 'static,
+    F: FnOnce() -> anyhow::Result<T> + Send + 'static,
+{
+    tauri::async_runtime::spawn_blocking(f)
+        .await
+        .map_err(|e| failed(e.into()))?
+        .map_err(failed)
+}
+
+fn ensure_not_rate_limited() -> CommandResult<()> {
+    match retry_after() {
+        Some(wait) => Err(
+            command_error("APP_LOCK_RATE_LIMITED", "error.app_lock_rate_limited")
+                .with_details(format!("retryAfterSecs={}", wait.as_secs().max(1))),
+        ),
+        None => Ok(()),
+    }
+}
+
+/// 校验口令；失败时计入退避。
+async fn verify_passphrase(verifier: String, passphrase: String) -> CommandResult<()> {
+    ensure_not_rate_limited()?;
+    let ok = blocking(move || Ok(check_verifier(&verifier, &passphrase))).await?;
+    if !ok {
+        record_failure();
+        return Err(command_error(
+            "APP_LOCK_WRONG_SECRET",
+            "error.app_lock_wrong_secret",
+        ));
+    }
+    Ok(())
+}
+
+async fn biometric_enabled() -> bool {
+    crate::features::settings::get_config_value::<bool>(String::from("app_lock_biometric")).await
+}
+
+/// 获取应用锁状态。
+#[tauri::command]
+pub async fn get_app_lock_status() -> CommandResult<AppLockStatus> {
+    let configured = blocking(load_verifier).await?.is_some();
+    let biometric_available = blocking(|| Ok(biometric::available())).await?;
+    Ok(AppLockStatus {
+        configured,
+        locked: is_locked(),
+        biometric_available,
+        biometric_enabled: biometric_enabled().await,
+        idle_minutes: crate::features::settings::get_config_value::<u32>(String::from(
+            "app_lock_idle_minutes",
+        ))
+        .await,
+    })
+}
+
+/// 设置、修改或清除应用锁口令。
+///
+/// # 参数
+/// - `current`：当前口令（已设置口令时必填）。
+/// - `passphrase`：新口令；为空表示清除口令（关闭应用锁）。
+#[tauri::command]
+pub async fn set_app_lock_passphrase(
+    current: Option<String>,
+    passphrase: Option<String>,
+) -> CommandResult<()> {
+    let current = match blocking(load_verifier).await? {
+        Some(existing) => {
+            let current = current.unwrap_or_default();
+            verify_passphrase(existing, current.clone()).await?;
+            Some(current)
+        }
+        None => None,
+    };
+    let verifier = match passphrase.clone() {
+        Some(passphrase) => {
+            if passphrase.chars().count() < MIN_PASSPHRASE_CHARS {
+                return Err(command_error(
+                    "APP_LOCK_PASSPHRASE_TOO_SHORT",
+                    "error.app_lock_passphrase_too_short",
+                ));
+            }
+            Some(blocking(move || make_verifier(&passphrase)).await?)
+        }
+        None => None,
+    };
+    // 频道密钥库的 KEK 派生自口令：先重新包装，失败时不更新口令。
+    channel_keys::rewrap(current, passphrase)
+        .await
+        .map_err(failed)?;
+    let enabled = verifier.is_some();
+    blocking(move || store_verifier(verifier.as_deref())).await?;
+    tracing::info!(action = "app_lock_passphrase_updated", enabled);
+    Ok(())
+}
+
+/// 立即锁定应用（需已设置口令）。
+#[tauri::command]
+pub async fn lock_app(app: AppHandle) -> CommandResult<()> {
+    if blocking(load_verifier).await?.is_none() {
+        return Err(command_error(
+            "APP_LOCK_NOT_CONFIGURED",
+            "error.app_lock_not_configured",
+        ));
+    }
+    lock(&app).map_err(failed)
+}
+
+/// 解锁应用。
+///
+/// # 参数
+/// - `secret`：应用锁口令；为空表示使用系统生物识别（需开启 `app_lock_biometric`）。
+///
+/// # 说明
+/// 未处于锁定状态时直接返回成功。
+#[tauri::command]
+pub async fn unlock_app(app: AppHandle, secret: Option<String>) -> CommandResult<()> {
+    if !is_locked() {
+        return Ok(());
+    }
+    match secret {
+        Some(secret) => {
+            let Some(verifier) = blocking(load_verifier).await? else {
+                // 口令已被清除（如凭据存储被外部清空），无从校验，直接解锁。
+                unlock(&app);
+                return Ok(());
+            };
+            verify_passphrase(verifier, secret.clone()).await?;
+            if let Err(err) = channel_keys::unlock_with_passphrase(secret).await {
+                tracing::warn!(action = "app_lock_channel_keys_unlock_failed", error = %err);
+            }
+        }
+        None => {
+            let unavailable = || {
+                command_error(
+                    "APP_LOCK_BIOMETRIC_UNAVAILABLE",
+                    "error.app_lock_biometric_unavailable",
+                )
+            };
+            if !biometric_enabled().await {
+                return Err(unavailable());
+            }
+            ensure_not_rate_limited()?;
+            let reason = rust_i18n::t!("app_lock.biometric_reason").to_string();
+            let verified = blocking(move || {
+                if !biometric::available() {
+                    return Ok(None);
+                }
+                biometric::verify(&reason).map(Some)
+            })
+            .await?;
+            match verified {
+                None => return Err(unavailable()),
+                Some(false) => {
+                    record_failure();
+                    return Err(command_error(
+                        "APP_LOCK_WRONG_SECRET",
+                        "error.app_lock_wrong_secret",
+                    ));
+                }
+                Some(true) => {}
+            }
+        }
+    }
+    unlock(&app);
+    Ok(())
+}
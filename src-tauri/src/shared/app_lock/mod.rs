@@ -0,0 +1,307 @@
+//! 模块入口：app_lock（应用锁）。
+//!
+//! 说明：
+//! - 设置口令后即启用应用锁；口令只以 PBKDF2 校验值（`v1$迭代次数$salt$hash`）保存在
+//!   系统凭据存储中，不落盘明文；
+//! - 锁定时隐藏其它窗口并显示锁屏窗口（见 `features::windows::di::lock_window`），
+//!   状态变化时广播 `app-lock-changed`；移动端只有一个窗口，由主 webview 收到该事件后
+//!   自行覆盖锁屏界面；
+//! - 设置 `app_lock_biometric` 后可用系统生物识别（Windows Hello / Touch ID）代替口令解锁；
+//! - 设置 `app_lock_idle_minutes` > 0 时，用户空闲（见 `shared::idle`）超过该时长自动锁定；
+//! - 连续输错口令后按指数退避拒绝尝试，避免被暴力猜测。
+//!
+//! 约定：注释中文，日志英文（tracing）。
+
+pub mod biometric;
+pub mod commands;
+
+use std::sync::Mutex;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::{Duration, Instant};
+
+use keyring_core::Entry;
+use serde::Serialize;
+use tauri::AppHandle;
+
+#[cfg(desktop)]
+use crate::features::windows::di::lock_window;
+use crate::shared::chat_cache::commands::is_missing_secure_storage_error_message;
+use crate::shared::db::backup_crypto::pbkdf2_sha256;
+use crate::shared::events::EventBusExt;
+
+/// 锁定状态变化事件名。
+pub const APP_LOCK_CHANGED_EVENT: &str = "app-lock-changed";
+
+const SERVICE: &str = "carrypigeon-desktop";
+pub(crate) const VERIFIER_ACCOUNT: &str = "app-lock-verifier";
+const VERIFIER_VERSION: &str = "v1";
+/// 口令校验值的 PBKDF2 迭代次数。
+const VERIFIER_ITERATIONS: u32 = 600_000;
+/// 解析校验值时接受的最大迭代次数。
+const MAX_VERIFIER_ITERATIONS: u32 = 10_000_000;
+
+/// 免退避的连续失败次数。
+const FREE_ATTEMPTS: u32 = 3;
+/// 退避上限。
+const MAX_BACKOFF: Duration = Duration::from_secs(300);
+/// 空闲检查间隔。
+const IDLE_CHECK_INTERVAL: Duration = Duration::from_secs(30);
+
+static LOCKED: AtomicBool = AtomicBool::new(false);
+
+/// 口令校验值缓存：外层 `None` 表示尚未从凭据存储读取。
+static VERIFIER_CACHE: Mutex<Option<Option<String>>> = Mutex::new(None);
+
+struct Attempts {
+    failures: u32,
+    retry_at: Option<Instant>,
+}
+
+static ATTEMPTS: Mutex<Attempts> = Mutex::new(Attempts {
+    failures: 0,
+    retry_at: None,
+});
+
+/// `app-lock-changed` 事件载荷。
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AppLockChangedPayload {
+    pub locked: bool,
+}
+
+/// 当前是否处于锁定状态。
+pub fn is_locked() -> bool {
+    LOCKED.load(Ordering::SeqCst)
+}
+
+/// 锁定应用：隐藏其它窗口并显示锁屏窗口（已锁定时仅置前锁屏窗口）。
+pub fn lock(app: &AppHandle) -> anyhow::Result<()> {
+    let was_locked = LOCKED.swap(true, Ordering::SeqCst);
+    #[cfg(desktop)]
+    if let Err(err) = lock_window::show(app) {
+        if !was_locked {
+            LOCKED.store(false, Ordering::SeqCst);
+        }
+        return Err(err);
+    }
+    if !was_locked {
+        tracing::info!(action = "app_lock_locked");
+        app.emit(
+            APP_LOCK_CHANGED_EVENT,
+            AppLockChangedPayload { locked: true },
+        );
+    }
+    Ok(())
+}
+
+/// 解除锁定：关闭锁屏窗口并恢复锁定前可见的窗口。
+pub fn unlock(app: &AppHandle) {
+    if !LOCKED.swap(false, Ordering::SeqCst) {
+        return;
+    }
+    reset_failures();
+    crate::shared::idle::record_activity();
+    #[cfg(desktop)]
+    lock_window::close(app);
+    tracing::info!(action = "app_lock_unlocked");
+    app.emit(
+        APP_LOCK_CHANGED_EVENT,
+        AppLockChangedPayload { locked: false },
+    );
+}
+
+/// 连续失败 `failures` 次后需要等待的时长。
+fn backoff_for(failures: u32) -> Duration {
+    if failures < FREE_ATTEMPTS {
+        return Duration::ZERO;
+    }
+    let exp = (failures - FREE_ATTEMPTS).min(16);
+    Duration::from_secs(1u64 << exp).min(MAX_BACKOFF)
+}
+
+/// 距允许下一次尝试还需等待的时长（`None` 表示可以立即尝试）。
+pub(crate) fn retry_after() -> Option<Duration> {
+    let attempts = ATTEMPTS.lock().unwrap_or_else(|p| p.into_inner());
+    let remaining = attempts.retry_at?.checked_duration_since(Instant::now())?;
+    (!remaining.is_zero()).then_some(remaining)
+}
+
+pub(crate) fn record_failure() {
+    let mut attempts = ATTEMPTS.lock().unwrap_or_else(|p| p.into_inner());
+    attempts.failures = attempts.failures.saturating_add(1);
+    let wait = backoff_for(attempts.failures);
+    attempts.retry_at = (!wait.is_zero()).then(|| Instant::now() + wait);
+    tracing::warn!(
+        action = "app_lock_unlock_failed",
+        failures = attempts.failures,
+        backoff_secs = wait.as_secs()
+    );
+}
+
+fn reset_failures() {
+    let mut attempts = ATTEMPTS.lock().unwrap_or_else(|p| p.into_inner());
+    attempts.failures = 0;
+    attempts.retry_at = None;
+}
+
+/// 常量时间比较，避免通过耗时推断校验值。
+fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+    if a.len() != b.len() {
+        return false;
+    }
+    a.iter().zip(b).fold(0u8, |acc, (x, y)| acc | (x ^ y)) == 0
+}
+
+fn make_verifier_with(passphrase: &str, iterations: u32) -> anyhow::Result<String> {
+    let mut salt = [0u8; 16];
+    getrandom::fill(&mut salt).map_err(|_| anyhow::anyhow!("Failed to generate salt"))?;
+    let hash = pbkdf2_sha256(passphrase.as_bytes(), &salt, iterations);
+    Ok(format!(
+        "{VERIFIER_VERSION}${iterations}${}${}",
+        hex::encode(salt),
+        hex::encode(hash)
+    ))
+}
+
+/// 生成口令校验值（阻塞调用）。
+pub(crate) fn make_verifier(passphrase: &str) -> anyhow::Result<String> {
+    make_verifier_with(passphrase, VERIFIER_ITERATIONS)
+}
+
+/// 校验口令（阻塞调用）；校验值格式非法时视为不匹配。
+pub(crate) fn check_verifier(verifier: &str, passphrase: &str) -> bool {
+    let mut parts = verifier.split('$');
+    let (Some(VERIFIER_VERSION), Some(iterations), Some(salt), Some(hash), None) = (
+        parts.next(),
+        parts.next(),
+        parts.next(),
+        parts.next(),
+        parts.next(),
+    ) else {
+        return false;
+    };
+    let Ok(iterations) = iterations.parse::<u32>() else {
+        return false;
+    };
+    if iterations == 0 || iterations > MAX_VERIFIER_ITERATIONS {
+        return false;
+    }
+    let (Ok(salt), Ok(hash)) = (hex::decode(salt), hex::decode(hash)) else {
+        return false;
+    };
+    constant_time_eq(
+        &pbkdf2_sha256(passphrase.as_bytes(), &salt, iterations),
+        &hash,
+    )
+}
+
+fn verifier_entry() -> anyhow::Result<Option<Entry>> {
+    match Entry::new(SERVICE, VERIFIER_ACCOUNT) {
+        Ok(entry) => Ok(Some(entry)),
+        Err(e) if is_missing_secure_storage_error_message(&e.to_string()) => Ok(None),
+        Err(e) => Err(e.into()),
+    }
+}
+
+/// 读取口令校验值（阻塞调用，首次读取后缓存）。
+///
+/// # 返回值
+/// - `Ok(None)`：未设置口令（应用锁未启用）。
+pub(crate) fn load_verifier() -> anyhow::Result<Option<String>> {
+    let mut cache = VERIFIER_CACHE.lock().unwrap_or_else(|p| p.into_inner());
+    if let Some(cached) = cache.as_ref() {
+        return Ok(cached.clone());
+    }
+    let verifier = match verifier_entry()? {
+        None => None,
+        Some(entry) => match entry.get_password() {
+            Ok(verifier) => Some(verifier),
+            Err(e) if is_missing_secure_storage_error_message(&e.to_string()) => None,
+            Err(e) => return Err(e.into()),
+        },
+    };
+    *cache = Some(verifier.clone());
+    Ok(verifier)
+}
+
+/// 保存或清除口令校验值（阻塞调用）。
+pub(crate) fn store_verifier(verifier: Option<&str>) -> anyhow::Result<()> {
+    let mut cache = VERIFIER_CACHE.lock().unwrap_or_else(|p| p.into_inner());
+    match verifier {
+        Some(verifier) => {
+            let entry = verifier_entry()?.ok_or_else(|| {
+                anyhow::anyhow!("secure storage is unavailable, cannot persist app lock passphrase")
+            })?;
+            entry.set_password(verifier)?;
+        }
+        None => {
+            if let Some(entry) = verifier_entry()? {
+                match entry.delete_credential() {
+                    Ok(()) => {}
+                    Err(e) if is_missing_secure_storage_error_message(&e.to_string()) => {}
+                    Err(e) => return Err(e.into()),
+                }
+            }
+        }
+    }
+    *cache = Some(verifier.map(str::to_string));
+    Ok(())
+}
+
+async fn is_configured() -> bool {
+    matches!(
+        tauri::async_runtime::spawn_blocking(load_verifier).await,
+        Ok(Ok(Some(_)))
+    )
+}
+
+/// 启动空闲自动锁定检查（在 setup 阶段调用一次）。
+pub fn spawn_idle_lock_watcher(app: AppHandle) {
+    tauri::async_runtime::spawn(async move {
+        loop {
+            tokio::time::sleep(IDLE_CHECK_INTERVAL).await;
+            if is_locked() {
+                continue;
+            }
+            let minutes = crate::features::settings::get_config_value::<u32>(String::from(
+                "app_lock_idle_minutes",
+            ))
+            .await;
+            if minutes == 0
+                || crate::shared::idle::idle_for() < Duration::from_secs(u64::from(minutes) * 60)
+                || !is_configured().await
+            {
+                continue;
+            }
+            tracing::info!(action = "app_lock_idle_timeout", idle_minutes = minutes);
+            if let Err(err) = lock(&app) {
+                tracing::warn!(action = "app_lock_idle_lock_failed", error = %err);
+            }
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn verifier_round_trip_and_rejects_malformed() {
+        let verifier = make_verifier_with("open sesame", 2).unwrap();
+        assert!(verifier.starts_with("v1$2$"));
+        assert!(check_verifier(&verifier, "open sesame"));
+        assert!(!check_verifier(&verifier, "open sesame!"));
+        assert!(!check_verifier("v2$2$00$00", "open sesame"));
+        assert!(!check_verifier("v1$0$00$00", "open sesame"));
+        assert!(!check_verifier(&format!("{verifier}$extra"), "open sesame"));
+    }
+
+    #[test]
+    fn backoff_grows_exponentially_and_is_capped() {
+        assert_eq!(backoff_for(0), Duration::ZERO);
+        assert_eq!(backoff_for(FREE_ATTEMPTS - 1), Duration::ZERO);
+        assert_eq!(backoff_for(FREE_ATTEMPTS), Duration::from_secs(1));
+        assert_eq!(backoff_for(FREE_ATTEMPTS + 3), Duration::from_secs(8));
+        assert_eq!(backoff_for(FREE_ATTEMPTS + 40), MAX_BACKOFF);
+    }
+}
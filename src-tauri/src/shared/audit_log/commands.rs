@@ -0,0 +1,29 @@
+//! audit_log｜Tauri 命令
+
+use crate::shared::error::{CommandResult, to_command_error};
+
+use super::AuditLogEntry;
+
+/// 默认单页条数。
+const DEFAULT_PAGE_SIZE: u32 = 50;
+
+/// 读取审计日志（最新在前）。
+///
+/// # 参数
+/// - `kind`：按类型过滤（如 `inbound_flood`）；为空时不过滤。
+/// - `before_id`：分页游标，传上一页最后一条的 id。
+/// - `limit`：单页条数（默认 50，最大 200）。
+#[tauri::command]
+pub async fn get_audit_log(
+    kind: Option<String>,
+    before_id: Option<i64>,
+    limit: Option<u32>,
+) -> CommandResult<Vec<AuditLogEntry>> {
+    super::load(
+        kind.as_deref().filter(|k| !k.is_empty()),
+        before_id,
+        limit.unwrap_or(DEFAULT_PAGE_SIZE),
+    )
+    .await
+    .map_err(|e| to_command_error("AUDIT_LOG_LOAD_FAILED", "error.audit_log_load_failed", e))
+}
@@ -0,0 +1,133 @@
+//! 模块入口：audit_log（安全审计日志）。
+//!
+//! 说明：
+//! - 记录值得事后复查的异常事件（如入站洪泛），写入 system DB `audit_log` 表；
+//! - `detail` 为 JSON 文本，结构由 `kind` 决定，前端按 `kind` 解析展示；
+//! - 写入失败只记日志，不影响触发事件的主流程。
+//!
+//! 约定：注释中文，日志英文（tracing）。
+
+pub mod commands;
+
+use anyhow::{Context, Result};
+use sea_orm::ConnectionTrait;
+use serde::Serialize;
+
+use crate::shared::db::commands::RawStatement;
+
+/// 审计日志所在的 DB key（应用级 system DB）。
+const SYSTEM_DB_KEY: &str = "system";
+
+/// 单页最大条数。
+pub const MAX_PAGE_SIZE: u32 = 200;
+
+/// 审计日志最多保留的条数（超出时删除最旧的记录）。
+const MAX_ENTRIES: i64 = 5000;
+
+/// 入站洪泛事件。
+pub const KIND_INBOUND_FLOOD: &str = "inbound_flood";
+
+/// 审计日志记录。
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AuditLogEntry {
+    pub id: i64,
+    pub kind: String,
+    pub server_socket: Option<String>,
+    pub detail: serde_json::Value,
+    pub created_at: i64,
+}
+
+/// 写入一条审计记录并裁剪到 [`MAX_ENTRIES`] 条。
+///
+/// # 返回值
+/// 新记录的 id。
+pub async fn record(
+    kind: &str,
+    server_socket: Option<&str>,
+    detail: &serde_json::Value,
+    now_ms: i64,
+) -> Result<i64> {
+    let db = crate::shared::db::get_db(SYSTEM_DB_KEY).await?;
+    let result = db
+        .connection
+        .execute(&RawStatement::new(
+            "INSERT INTO audit_log (kind, server_socket, detail, created_at) VALUES (?, ?, ?, ?)",
+            vec![
+                kind.into(),
+                server_socket.map(str::to_string).into(),
+                detail.to_string().into(),
+                now_ms.into(),
+            ],
+        ))
+        .await
+        .context("AUDIT_LOG_RECORD_FAILED")?;
+    let id = result.last_insert_id() as i64;
+
+    db.connection
+        .execute(&RawStatement::new(
+            "DELETE FROM audit_log WHERE id NOT IN
+               (SELECT id FROM audit_log ORDER BY id DESC LIMIT ?)",
+            vec![MAX_ENTRIES.into()],
+        ))
+        .await
+        .context("AUDIT_LOG_RECORD_FAILED")?;
+    Ok(id)
+}
+
+/// 在后台写入一条审计记录（供同步上下文调用）。
+pub fn record_in_background(kind: &'static str, server_socket: String, detail: serde_json::Value) {
+    let now_ms = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_millis() as i64;
+    tauri::async_runtime::spawn(async move {
+        match record(kind, Some(&server_socket), &detail, now_ms).await {
+            Ok(id) => tracing::info!(action = "app_audit_log_recorded", kind, id),
+            Err(e) => tracing::warn!(action = "app_audit_log_record_failed", kind, error = %e),
+        }
+    });
+}
+
+/// 读取审计记录（id 倒序）。
+///
+/// # 参数
+/// - `kind`：只返回该类型；为空时返回全部。
+/// - `before_id`：只返回 id 小于该值的记录（分页游标）。
+/// - `limit`：条数（1..=[`MAX_PAGE_SIZE`]）。
+pub async fn load(
+    kind: Option<&str>,
+    before_id: Option<i64>,
+    limit: u32,
+) -> Result<Vec<AuditLogEntry>> {
+    let limit = limit.clamp(1, MAX_PAGE_SIZE);
+    let db = crate::shared::db::get_db(SYSTEM_DB_KEY).await?;
+    let rows = db
+        .connection
+        .query_all(&RawStatement::new(
+            "SELECT id, kind, server_socket, detail, created_at FROM audit_log
+             WHERE id < ? AND (? IS NULL OR kind = ?)
+             ORDER BY id DESC LIMIT ?",
+            vec![
+                before_id.unwrap_or(i64::MAX).into(),
+                kind.map(str::to_string).into(),
+                kind.map(str::to_string).into(),
+                i64::from(limit).into(),
+            ],
+        ))
+        .await
+        .context("AUDIT_LOG_LOAD_FAILED")?;
+    Ok(rows
+        .iter()
+        .filter_map(|row| {
+            let detail: String = row.try_get("", "detail").ok()?;
+            Some(AuditLogEntry {
+                id: row.try_get("", "id").ok()?,
+                kind: row.try_get("", "kind").ok()?,
+                server_socket: row.try_get("", "server_socket").ok()?,
+                detail: serde_json::from_str(&detail).unwrap_or(serde_json::Value::Null),
+                created_at: row.try_get("", "created_at").ok()?,
+            })
+        })
+        .collect())
+}
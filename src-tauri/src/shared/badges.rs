@@ -0,0 +1,232 @@
+//! shared｜未读角标（macOS Dock 角标 / Windows 任务栏覆盖图标 / Linux Unity 启动器计数）。
+//!
+//! 说明：
+//! - 未读服务（托盘未读命令）经 [`set_count`] 上报总未读数，本模块按平台统一展示：
+//!   macOS 与 Linux 使用窗口的 badge count（Linux 需桌面环境支持 Unity LauncherEntry，
+//!   否则由系统忽略）；Windows 没有数字角标，改为在任务栏按钮上叠加绘制的覆盖图标；
+//! - 超过 [`MAX_DISPLAY_COUNT`] 时显示为 `99+`；
+//! - 设置 `hide_unread_badge` 开启后清除并不再显示角标，随设置写入即时生效
+//!   （见 `config_store::apply_runtime_switches`）；
+//! - 只作用于主窗口；移动端与不支持的平台为空操作，失败只记录日志。
+//!
+//! 约定：注释中文，日志英文（tracing）。
+
+use std::sync::OnceLock;
+use std::sync::atomic::{AtomicBool, AtomicU32, Ordering};
+
+use tauri::AppHandle;
+
+/// 角标显示的最大数字，超过时显示为 `99+`。
+pub const MAX_DISPLAY_COUNT: u32 = 99;
+
+static HIDDEN: AtomicBool = AtomicBool::new(false);
+static COUNT: AtomicU32 = AtomicU32::new(0);
+static APP_HANDLE: OnceLock<AppHandle> = OnceLock::new();
+
+/// 注册 AppHandle（setup 阶段调用一次），并按当前状态同步角标。
+pub fn init(app: &AppHandle) {
+    let _ = APP_HANDLE.set(app.clone());
+    apply(displayed_count());
+}
+
+/// 更新“隐藏未读角标”开关；状态变化时立即清除或恢复角标。
+pub fn set_hidden(hidden: bool) {
+    if HIDDEN.swap(hidden, Ordering::Relaxed) == hidden {
+        return;
+    }
+    tracing::info!(action = "settings_unread_badge_hidden_changed", hidden);
+    apply(displayed_count());
+}
+
+/// 上报总未读数（0 清除角标）；与上次相同时跳过。
+pub fn set_count(count: u32) {
+    if COUNT.swap(count, Ordering::Relaxed) == count {
+        return;
+    }
+    apply(displayed_count());
+}
+
+/// 当前应显示的数字：关闭角标或无未读时为 `None`。
+fn displayed_count() -> Option<u32> {
+    if HIDDEN.load(Ordering::Relaxed) {
+        return None;
+    }
+    Some(COUNT.load(Ordering::Relaxed)).filter(|&count| count > 0)
+}
+
+/// 角标文本：`1`..`99`，超过时为 `99+`。
+pub fn badge_label(count: u32) -> String {
+    if count > MAX_DISPLAY_COUNT {
+        format!("{MAX_DISPLAY_COUNT}+")
+    } else {
+        count.to_string()
+    }
+}
+
+#[cfg(mobile)]
+fn apply(_count: Option<u32>) {}
+
+#[cfg(desktop)]
+fn apply(count: Option<u32>) {
+    use tauri::Manager;
+
+    let Some(window) = APP_HANDLE
+        .get()
+        .and_then(|app| app.get_webview_window("main"))
+    else {
+        return;
+    };
+    if let Err(err) = platform::apply(&window, count) {
+        tracing::debug!(action = "windows_unread_badge_apply_failed", error = %err);
+    }
+}
+
+#[cfg(all(desktop, target_os = "windows"))]
+mod platform {
+    use tauri::WebviewWindow;
+    use tauri::image::Image;
+
+    use super::{badge_label, render_overlay};
+
+    pub fn apply(window: &WebviewWindow, count: Option<u32>) -> tauri::Result<()> {
+        let icon = count.map(|count| {
+            let (rgba, size) = render_overlay(&badge_label(count));
+            Image::new_owned(rgba, size, size)
+        });
+        window.set_overlay_icon(icon)
+    }
+}
+
+#[cfg(all(desktop, not(target_os = "windows")))]
+mod platform {
+    use tauri::WebviewWindow;
+
+    pub fn apply(window: &WebviewWindow, count: Option<u32>) -> tauri::Result<()> {
+        window.set_badge_count(count.map(i64::from))
+    }
+}
+
+/// 覆盖图标边长（像素；系统按 DPI 缩放到 16x16 逻辑尺寸）。
+pub const OVERLAY_SIZE: u32 = 32;
+/// 字形放大倍数（3x5 点阵 -> 6x10 像素）。
+const GLYPH_SCALE: u32 = 2;
+const GLYPH_WIDTH: u32 = 3;
+const GLYPH_HEIGHT: u32 = 5;
+/// 字形间距（像素）。
+const GLYPH_GAP: u32 = 2;
+const BADGE_COLOR: [u8; 4] = [0xE5, 0x39, 0x35, 0xFF];
+const TEXT_COLOR: [u8; 4] = [0xFF, 0xFF, 0xFF, 0xFF];
+
+/// 3x5 点阵字形：每行 3 位，高位在左。
+fn glyph(c: char) -> Option<[u8; 5]> {
+    Some(match c {
+        '0' => [0b111, 0b101, 0b101, 0b101, 0b111],
+        '1' => [0b010, 0b110, 0b010, 0b010, 0b111],
+        '2' => [0b111, 0b001, 0b111, 0b100, 0b111],
+        '3' => [0b111, 0b001, 0b111, 0b001, 0b111],
+        '4' => [0b101, 0b101, 0b111, 0b001, 0b001],
+        '5' => [0b111, 0b100, 0b111, 0b001, 0b111],
+        '6' => [0b111, 0b100, 0b111, 0b101, 0b111],
+        '7' => [0b111, 0b001, 0b001, 0b001, 0b001],
+        '8' => [0b111, 0b101, 0b111, 0b101, 0b111],
+        '9' => [0b111, 0b101, 0b111, 0b001, 0b111],
+        '+' => [0b000, 0b010, 0b111, 0b010, 0b000],
+        _ => return None,
+    })
+}
+
+/// 绘制覆盖图标：红色圆形底，居中白色数字（RGBA，边长 [`OVERLAY_SIZE`]）。
+///
+/// # 返回值
+/// `(rgba, size)`。
+#[cfg_attr(not(target_os = "windows"), allow(dead_code))]
+pub fn render_overlay(label: &str) -> (Vec<u8>, u32) {
+    let size = OVERLAY_SIZE;
+    let mut rgba = vec![0u8; (size * size * 4) as usize];
+    let mut put = |x: u32, y: u32, color: [u8; 4]| {
+        let offset = ((y * size + x) * 4) as usize;
+        rgba[offset..offset + 4].copy_from_slice(&color);
+    };
+
+    let center = size as f32 / 2.0;
+    let radius_sq = center * center;
+    for y in 0..size {
+        for x in 0..size {
+            let dx = x as f32 + 0.5 - center;
+            let dy = y as f32 + 0.5 - center;
+            if dx * dx + dy * dy <= radius_sq {
+                put(x, y, BADGE_COLOR);
+            }
+        }
+    }
+
+    let glyphs: Vec<[u8; 5]> = label.chars().filter_map(glyph).collect();
+    if glyphs.is_empty() {
+        return (rgba, size);
+    }
+    let glyph_px = GLYPH_WIDTH * GLYPH_SCALE;
+    let text_width = glyphs.len() as u32 * (glyph_px + GLYPH_GAP) - GLYPH_GAP;
+    let left = size.saturating_sub(text_width) / 2;
+    let top = (size - GLYPH_HEIGHT * GLYPH_SCALE) / 2;
+    for (index, rows) in glyphs.iter().enumerate() {
+        let origin = left + index as u32 * (glyph_px + GLYPH_GAP);
+        for (row, bits) in rows.iter().enumerate() {
+            for col in 0..GLYPH_WIDTH {
+                if bits & (0b100 >> col) == 0 {
+                    continue;
+                }
+                for sy in 0..GLYPH_SCALE {
+                    for sx in 0..GLYPH_SCALE {
+                        let x = origin + col * GLYPH_SCALE + sx;
+                        let y = top + row as u32 * GLYPH_SCALE + sy;
+                        if x < size {
+                            put(x, y, TEXT_COLOR);
+                        }
+                    }
+                }
+            }
+        }
+    }
+    (rgba, size)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn pixel(rgba: &[u8], size: u32, x: u32, y: u32) -> [u8; 4] {
+        let offset = ((y * size + x) * 4) as usize;
+        rgba[offset..offset + 4].try_into().unwrap()
+    }
+
+    #[test]
+    fn label_caps_large_counts() {
+        assert_eq!(badge_label(1), "1");
+        assert_eq!(badge_label(99), "99");
+        assert_eq!(badge_label(100), "99+");
+        assert_eq!(badge_label(u32::MAX), "99+");
+    }
+
+    #[test]
+    fn overlay_draws_circle_with_centered_text() {
+        let (rgba, size) = render_overlay("1");
+        assert_eq!(size, OVERLAY_SIZE);
+        assert_eq!(rgba.len(), (size * size * 4) as usize);
+        // 四角透明，圆内为底色。
+        assert_eq!(pixel(&rgba, size, 0, 0), [0; 4]);
+        assert_eq!(pixel(&rgba, size, size - 1, size - 1), [0; 4]);
+        assert_eq!(pixel(&rgba, size, 4, size / 2), BADGE_COLOR);
+        // "1" 的竖笔位于中心列。
+        assert_eq!(pixel(&rgba, size, size / 2, size / 2), TEXT_COLOR);
+
+        // 最宽的文本也完整落在图标内，且左右留白对称。
+        let (rgba, size) = render_overlay(&badge_label(1000));
+        let text_columns: Vec<u32> = (0..size)
+            .filter(|&x| (0..size).any(|y| pixel(&rgba, size, x, y) == TEXT_COLOR))
+            .collect();
+        let first = *text_columns.first().unwrap();
+        let last = *text_columns.last().unwrap();
+        assert!(first > 0 && last < size - 1);
+        assert!(first.abs_diff(size - 1 - last) <= 1);
+    }
+}
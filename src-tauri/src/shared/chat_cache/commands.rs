@@ -3,19 +3,17 @@
 use aes_gcm::{Aes256Gcm, Nonce, aead::Aead, aead::KeyInit};
 use anyhow::{Context, Result};
 use keyring_core::Entry;
-use sea_orm::{
-    ConnectionTrait, Database, DatabaseBackend, Statement, StatementBuilder, TransactionTrait,
-    Value,
-};
+use sea_orm::{ConnectionTrait, Database, TransactionTrait, Value};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::path::{Path, PathBuf};
 use std::sync::{Arc, Mutex, OnceLock};
 
+use crate::shared::db::commands::RawStatement;
 use crate::shared::error::{CommandResult, command_error, to_command_error};
 
 const SERVICE: &str = "carrypigeon-desktop";
-const ACCOUNT: &str = "chat-cache-master-key";
+pub(crate) const ACCOUNT: &str = "chat-cache-master-key";
 
 /// 聊天缓存条目数上限。
 /// 生产环境保持 8192；测试环境降低以便验证淘汰逻辑。
@@ -43,24 +41,6 @@ pub struct ChatCacheRemoveManyRequest {
     pub keys: Vec<String>,
 }
 
-#[derive(Debug, Clone)]
-struct RawStatement {
-    sql: String,
-    values: Vec<Value>,
-}
-
-impl RawStatement {
-    fn new(sql: String, values: Vec<Value>) -> Self {
-        Self { sql, values }
-    }
-}
-
-impl StatementBuilder for RawStatement {
-    fn build(&self, db_backend: &DatabaseBackend) -> Statement {
-        Statement::from_sql_and_values(*db_backend, self.sql.clone(), self.values.clone())
-    }
-}
-
 fn now_ms() -> i64 {
     let millis = std::time::SystemTime::now()
         .duration_since(std::time::UNIX_EPOCH)
@@ -199,7 +179,7 @@ fn master_key(create_if_missing: bool) -> Result<Option<[u8; 32]>> {
     }
 }
 
-fn is_missing_secure_storage_error_message(message: &str) -> bool {
+pub(crate) fn is_missing_secure_storage_error_message(message: &str) -> bool {
     message.contains("not found")
         || message.contains("NoEntry")
         || message.contains("No matching entry found in secure storage")
@@ -229,6 +209,18 @@ fn clear_master_key_cache() {
     }
 }
 
+/// 关闭聊天缓存连接并删除凭据存储中的主密钥（供安全擦除使用）。
+pub(crate) async fn shutdown_and_forget_key() -> Result<()> {
+    let conn = chat_cache_db_cell()
+        .lock()
+        .map_err(|_| anyhow::anyhow!("Failed to lock chat cache db"))?
+        .take();
+    if let Some(conn) = conn {
+        (*conn).clone().close().await?;
+    }
+    forget_master_key()
+}
+
 fn forget_master_key() -> Result<()> {
     clear_master_key_cache();
     let entry = match Entry::new(SERVICE, ACCOUNT) {
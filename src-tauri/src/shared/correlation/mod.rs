@@ -0,0 +1,253 @@
+//! shared｜命令关联 id（correlation id）。
+//!
+//! 说明：
+//! - `with_correlation` 包装 `generate_handler!` 生成的 invoke handler，为每次命令调用分配 id，
+//!   并在 `tauri_command` span 内分发；async command 依赖 tauri `tracing` feature 继承该 span；
+//! - `CorrelationLayer` 将 span 字段记录到 registry 扩展中，供 `current_correlation` 在
+//!   错误构建时读取（见 `shared::error::CommandError`）；
+//! - 前端可在参数中附带 `requestId`，会原样记录并回显到错误中，便于串联多步操作。
+//!
+//! 约定：注释中文，日志英文（tracing）。
+
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::Instant;
+
+use tauri::Runtime;
+use tauri::ipc::{Invoke, InvokeBody};
+use tracing::field::{Field, Visit};
+use tracing::span::{Attributes, Id};
+use tracing::{Subscriber, info_span};
+use tracing_subscriber::layer::{Context, Layer};
+use tracing_subscriber::registry::LookupSpan;
+
+/// 前端参数中的请求 id 键名（camelCase 与 snake_case 均接受）。
+const REQUEST_ID_KEYS: &[&str] = &["requestId", "request_id"];
+
+/// 前端请求 id 的最大长度（超出部分截断，避免日志被滥用）。
+const REQUEST_ID_MAX_LEN: usize = 64;
+
+static NEXT_SEQ: AtomicU64 = AtomicU64::new(1);
+
+/// 单次命令调用的关联上下文。
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct CorrelationContext {
+    pub correlation_id: String,
+    pub request_id: Option<String>,
+}
+
+/// 生成新的 correlation id：进程内单调序号 + 随机后缀（跨重启不易碰撞）。
+pub fn new_correlation_id() -> String {
+    let seq = NEXT_SEQ.fetch_add(1, Ordering::Relaxed);
+    let suffix = uuid::Uuid::new_v4().simple().to_string();
+    format!("cmd-{seq}-{}", &suffix[..8])
+}
+
+/// 从 invoke 参数中提取前端提供的 `requestId`。
+pub fn extract_request_id(body: &InvokeBody) -> Option<String> {
+    let InvokeBody::Json(value) = body else {
+        return None;
+    };
+    REQUEST_ID_KEYS
+        .iter()
+        .find_map(|key| value.get(*key).and_then(|v| v.as_str()))
+        .map(str::trim)
+        .filter(|id| !id.is_empty())
+        .map(|id| id.chars().take(REQUEST_ID_MAX_LEN).collect())
+}
+
+/// 包装 invoke handler：为每次命令调用分配 correlation id 并在对应 span 内分发。
+pub fn with_correlation<R, F>(handler: F) -> impl Fn(Invoke<R>) -> bool + Send + Sync + 'static
+where
+    R: Runtime,
+    F: Fn(Invoke<R>) -> bool + Send + Sync + 'static,
+{
+    move |invoke: Invoke<R>| {
+        let command = invoke.message.command().to_string();
+        let correlation_id = new_correlation_id();
+        let request_id = extract_request_id(invoke.message.payload());
+        let span = info_span!(
+            "tauri_command",
+            command = %command,
+            correlation_id = %correlation_id,
+            request_id = request_id.as_deref().unwrap_or(""),
+        );
+        let _entered = span.enter();
+        tracing::debug!(action = "tauri_command_invoked");
+        handler(invoke)
+    }
+}
+
+/// 读取当前 span 链上的关联上下文（不在命令 span 内时返回 None）。
+pub fn current_correlation() -> Option<CorrelationContext> {
+    let span_id = tracing::Span::current().id()?;
+    tracing::dispatcher::get_default(|dispatch| {
+        let registry = dispatch.downcast_ref::<tracing_subscriber::Registry>()?;
+        let span = registry.span(&span_id)?;
+        span.scope()
+            .find_map(|s| s.extensions().get::<CorrelationContext>().cloned())
+    })
+}
+
+/// 命令 span 的起始时间（用于在 span 关闭时记录命令耗时）。
+struct CommandTiming {
+    command: String,
+    started: Instant,
+}
+
+#[derive(Default)]
+struct CorrelationVisitor {
+    command: Option<String>,
+    correlation_id: Option<String>,
+    request_id: Option<String>,
+}
+
+impl Visit for CorrelationVisitor {
+    fn record_str(&mut self, field: &Field, value: &str) {
+        match field.name() {
+            "command" => self.command = Some(value.to_string()),
+            "correlation_id" => self.correlation_id = Some(value.to_string()),
+            "request_id" if !value.is_empty() => self.request_id = Some(value.to_string()),
+            _ => {}
+        }
+    }
+
+    fn record_debug(&mut self, field: &Field, value: &dyn std::fmt::Debug) {
+        // `%value` 形式的字段会以 Display 包装走 record_debug。
+        self.record_str(field, &format!("{value:?}"));
+    }
+}
+
+/// 将命令 span 的 correlation 字段写入 registry 扩展，并在 span 关闭时记录命令耗时。
+pub struct CorrelationLayer;
+
+impl<S> Layer<S> for CorrelationLayer
+where
+    S: Subscriber + for<'a> LookupSpan<'a>,
+{
+    fn on_new_span(&self, attrs: &Attributes<'_>, id: &Id, ctx: Context<'_, S>) {
+        let mut visitor = CorrelationVisitor::default();
+        attrs.record(&mut visitor);
+        let Some(correlation_id) = visitor.correlation_id else {
+            return;
+        };
+        if let Some(span) = ctx.span(id) {
+            let mut extensions = span.extensions_mut();
+            extensions.insert(CorrelationContext {
+                correlation_id,
+                request_id: visitor.request_id,
+            });
+            if let Some(command) = visitor.command {
+                extensions.insert(CommandTiming {
+                    command,
+                    started: Instant::now(),
+                });
+            }
+        }
+    }
+
+    fn on_close(&self, id: Id, ctx: Context<'_, S>) {
+        let Some(span) = ctx.span(&id) else {
+            return;
+        };
+        if let Some(timing) = span.extensions().get::<CommandTiming>() {
+            crate::shared::metrics::observe_duration(
+                crate::shared::metrics::COMMAND_DURATION_MS,
+                &timing.command,
+                timing.started.elapsed(),
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::shared::error::CommandResult;
+    use tracing_subscriber::prelude::*;
+
+    #[test]
+    fn correlation_ids_are_unique() {
+        let a = new_correlation_id();
+        let b = new_correlation_id();
+        assert!(a.starts_with("cmd-"));
+        assert_ne!(a, b);
+    }
+
+    #[test]
+    fn extract_request_id_accepts_both_casings() {
+        let body = InvokeBody::Json(serde_json::json!({ "requestId": " op-1 " }));
+        assert_eq!(extract_request_id(&body), Some("op-1".to_string()));
+        let body = InvokeBody::Json(serde_json::json!({ "request_id": "op-2" }));
+        assert_eq!(extract_request_id(&body), Some("op-2".to_string()));
+        let body = InvokeBody::Json(serde_json::json!({ "key": "x" }));
+        assert_eq!(extract_request_id(&body), None);
+    }
+
+    #[test]
+    fn current_correlation_reads_nearest_command_span() {
+        let subscriber = tracing_subscriber::registry().with(CorrelationLayer);
+        tracing::subscriber::with_default(subscriber, || {
+            assert_eq!(current_correlation(), None);
+            let span = info_span!(
+                "tauri_command",
+                command = "db_init",
+                correlation_id = "cmd-1-abc",
+                request_id = "install-42",
+            );
+            let _entered = span.enter();
+            let child = info_span!("ipc::request::run");
+            let _child = child.enter();
+            assert_eq!(
+                current_correlation(),
+                Some(CorrelationContext {
+                    correlation_id: "cmd-1-abc".to_string(),
+                    request_id: Some("install-42".to_string()),
+                })
+            );
+        });
+    }
+
+    #[tauri::command]
+    fn metrics_probe() -> CommandResult<&'static str> {
+        Ok("ok")
+    }
+
+    #[test]
+    fn invoked_commands_record_their_duration() {
+        use tauri::test::{INVOKE_KEY, get_ipc_response, mock_builder, mock_context, noop_assets};
+
+        let subscriber = crate::shared::log::subscriber::build_subscriber(
+            tracing_subscriber::EnvFilter::new("info"),
+            std::io::sink,
+        );
+        tracing::subscriber::with_default(subscriber, || {
+            let app = mock_builder()
+                .invoke_handler(with_correlation(tauri::generate_handler![metrics_probe]))
+                .build(mock_context(noop_assets()))
+                .expect("mock app");
+            let webview = tauri::WebviewWindowBuilder::new(&app, "main", Default::default())
+                .build()
+                .expect("webview");
+            let response = get_ipc_response(
+                &webview,
+                tauri::webview::InvokeRequest {
+                    cmd: "metrics_probe".into(),
+                    callback: tauri::ipc::CallbackFn(0),
+                    error: tauri::ipc::CallbackFn(1),
+                    url: "http://tauri.localhost".parse().expect("url"),
+                    body: InvokeBody::default(),
+                    headers: Default::default(),
+                    invoke_key: INVOKE_KEY.to_string(),
+                },
+            );
+            assert!(response.is_ok());
+        });
+
+        let snapshot = crate::shared::metrics::snapshot();
+        assert!(snapshot.histograms.iter().any(|h| {
+            h.name == crate::shared::metrics::COMMAND_DURATION_MS
+                && h.label == "metrics_probe"
+                && h.count >= 1
+        }));
+    }
+}
@@ -0,0 +1,604 @@
+//! shared｜数据库：频道归档（冷存储文件）。
+//!
+//! 说明：
+//! - 归档文件是一个 zip 包：`archive.json` 记录格式版本、来源 server 与频道、各条目的
+//!   SHA-256（对解压后的内容计算），消息与翻译各为一个 zstd 压缩的 JSON Lines 条目；
+//! - `archive_channel` 先完整写出并重命名归档文件，再在事务内从 server DB 删除已归档的
+//!   消息及其翻译；发件箱中的临时行（`status != 'sent'`）不归档；
+//! - `restore_archive` 校验格式与摘要后合并回原 server DB（需已打开）：已有的较新版本
+//!   与已删除（墓碑）的消息不会被覆盖。
+//!
+//! 约定：注释中文，日志英文（tracing）。
+
+use std::io::{Read, Write};
+use std::path::{Path, PathBuf};
+
+use anyhow::Context;
+use sea_orm::{ConnectionTrait, TransactionTrait, Value};
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use zip::ZipArchive;
+use zip::write::SimpleFileOptions;
+
+use crate::shared::error::{CommandResult, command_error, to_command_error};
+use crate::shared::net::tls_fingerprint::sha256_hex;
+use crate::shared::taskbar_progress::TaskbarProgress;
+
+use super::commands::{RawStatement, is_server_db_key};
+use super::{get_db, get_read_db};
+
+/// 归档格式标识。
+pub const ARCHIVE_FORMAT: &str = "carrypigeon-channel-archive";
+
+/// 当前归档格式版本。
+pub const ARCHIVE_VERSION: u32 = 1;
+
+const MANIFEST_ENTRY: &str = "archive.json";
+const MESSAGES_ENTRY: &str = "messages.jsonl.zst";
+const TRANSLATIONS_ENTRY: &str = "translations.jsonl.zst";
+
+/// zstd 压缩级别（归档为冷数据，偏向压缩率）。
+const ZSTD_LEVEL: i32 = 9;
+
+/// 单个条目解压后的最大字节数。
+const MAX_ENTRY_BYTES: u64 = 1024 * 1024 * 1024;
+
+/// 归档中的消息行（与 `messages` 表一致，不含本地评估状态）。
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ArchivedMessage {
+    pub id: String,
+    pub user_id: i64,
+    pub content: String,
+    pub created_at: i64,
+    pub updated_at: i64,
+}
+
+/// 归档中的翻译行。
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ArchivedTranslation {
+    pub message_id: String,
+    pub target_lang: String,
+    pub provider: String,
+    pub text: String,
+    pub created_at: i64,
+}
+
+/// 归档清单。
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ArchiveManifest {
+    pub format: String,
+    pub version: u32,
+    pub server_socket: String,
+    pub db_key: String,
+    pub channel_id: i64,
+    pub created_at: i64,
+    pub message_count: u64,
+    pub translation_count: u64,
+    /// 最旧/最新消息时间（空频道为空）。
+    pub oldest_at: Option<i64>,
+    pub newest_at: Option<i64>,
+    pub messages_sha256: String,
+    pub translations_sha256: String,
+}
+
+/// 归档内容。
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ChannelArchive {
+    pub manifest: ArchiveManifest,
+    pub messages: Vec<ArchivedMessage>,
+    pub translations: Vec<ArchivedTranslation>,
+}
+
+/// `archive_channel` 的结果。
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ArchiveReport {
+    pub path: String,
+    pub manifest: ArchiveManifest,
+    /// 归档文件大小（字节）。
+    pub file_bytes: u64,
+}
+
+/// `restore_archive` 的结果。
+#[derive(Debug, Clone, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RestoreReport {
+    pub server_socket: String,
+    pub db_key: String,
+    pub channel_id: i64,
+    pub restored: u64,
+    /// 因本地已有较新版本或已删除而跳过的消息数。
+    pub skipped: u64,
+    pub translations: u64,
+}
+
+fn now_ms() -> i64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_millis() as i64
+}
+
+fn to_jsonl<T: Serialize>(rows: &[T]) -> anyhow::Result<Vec<u8>> {
+    let mut out = Vec::new();
+    for row in rows {
+        serde_json::to_writer(&mut out, row)?;
+        out.push(b'\n');
+    }
+    Ok(out)
+}
+
+fn from_jsonl<T: DeserializeOwned>(bytes: &[u8], name: &str) -> anyhow::Result<Vec<T>> {
+    bytes
+        .split(|b| *b == b'\n')
+        .filter(|line| !line.is_empty())
+        .map(|line| {
+            serde_json::from_slice(line).with_context(|| format!("Invalid archive entry: {name}"))
+        })
+        .collect()
+}
+
+fn write_entry<W: Write + std::io::Seek>(
+    zip: &mut zip::ZipWriter<W>,
+    name: &str,
+    raw: &[u8],
+) -> anyhow::Result<()> {
+    let compressed = zstd::encode_all(raw, ZSTD_LEVEL)
+        .with_context(|| format!("Failed to compress archive entry: {name}"))?;
+    zip.start_file(
+        name,
+        SimpleFileOptions::default().compression_method(zip::CompressionMethod::Stored),
+    )
+    .with_context(|| format!("Failed to start archive entry: {name}"))?;
+    zip.write_all(&compressed)
+        .with_context(|| format!("Failed to write archive entry: {name}"))?;
+    Ok(())
+}
+
+fn read_entry<R: Read + std::io::Seek>(
+    zip: &mut ZipArchive<R>,
+    name: &str,
+) -> anyhow::Result<Vec<u8>> {
+    let mut compressed = Vec::new();
+    zip.by_name(name)
+        .with_context(|| format!("Archive entry is missing: {name}"))?
+        .read_to_end(&mut compressed)
+        .with_context(|| format!("Failed to read archive entry: {name}"))?;
+    let mut raw = Vec::new();
+    zstd::Decoder::new(compressed.as_slice())?
+        .take(MAX_ENTRY_BYTES + 1)
+        .read_to_end(&mut raw)
+        .with_context(|| format!("Failed to decompress archive entry: {name}"))?;
+    if raw.len() as u64 > MAX_ENTRY_BYTES {
+        anyhow::bail!("Archive entry too large: {name}");
+    }
+    Ok(raw)
+}
+
+/// 写出归档；清单中的条数与摘要按实际内容重新生成。
+///
+/// 先写入同目录临时文件再重命名，避免失败时留下半个归档。
+pub fn write_archive(dest: &Path, archive: &ChannelArchive) -> anyhow::Result<ArchiveManifest> {
+    let messages = to_jsonl(&archive.messages)?;
+    let translations = to_jsonl(&archive.translations)?;
+    let manifest = ArchiveManifest {
+        message_count: archive.messages.len() as u64,
+        translation_count: archive.translations.len() as u64,
+        oldest_at: archive.messages.iter().map(|m| m.created_at).min(),
+        newest_at: archive.messages.iter().map(|m| m.created_at).max(),
+        messages_sha256: sha256_hex(&messages),
+        translations_sha256: sha256_hex(&translations),
+        ..archive.manifest.clone()
+    };
+
+    let tmp = dest.with_extension("tmp");
+    let file = std::fs::File::create(&tmp)
+        .with_context(|| format!("Failed to create archive file: {}", tmp.display()))?;
+    let mut zip = zip::ZipWriter::new(file);
+    write_entry(&mut zip, MESSAGES_ENTRY, &messages)?;
+    write_entry(&mut zip, TRANSLATIONS_ENTRY, &translations)?;
+    write_entry(
+        &mut zip,
+        MANIFEST_ENTRY,
+        &serde_json::to_vec_pretty(&manifest)?,
+    )?;
+    zip.finish().context("Failed to finish channel archive")?;
+    std::fs::rename(&tmp, dest)
+        .with_context(|| format!("Failed to move archive into place: {}", dest.display()))?;
+    Ok(manifest)
+}
+
+/// 读取归档并校验格式、版本与摘要。
+pub fn read_archive(path: &Path) -> anyhow::Result<ChannelArchive> {
+    let file = std::fs::File::open(path)
+        .with_context(|| format!("Failed to open archive file: {}", path.display()))?;
+    let mut zip = ZipArchive::new(file).context("Archive is not a valid zip file")?;
+    let manifest: ArchiveManifest = serde_json::from_slice(&read_entry(&mut zip, MANIFEST_ENTRY)?)
+        .context("Invalid archive manifest")?;
+    if manifest.format != ARCHIVE_FORMAT {
+        anyhow::bail!("Unsupported archive format: {}", manifest.format);
+    }
+    if manifest.version == 0 || manifest.version > ARCHIVE_VERSION {
+        anyhow::bail!("Unsupported archive version: {}", manifest.version);
+    }
+    let messages = read_entry(&mut zip, MESSAGES_ENTRY)?;
+    if sha256_hex(&messages) != manifest.messages_sha256 {
+        anyhow::bail!("Archive checksum mismatch: {MESSAGES_ENTRY}");
+    }
+    let translations = read_entry(&mut zip, TRANSLATIONS_ENTRY)?;
+    if sha256_hex(&translations) != manifest.translations_sha256 {
+        anyhow::bail!("Archive checksum mismatch: {TRANSLATIONS_ENTRY}");
+    }
+    let messages: Vec<ArchivedMessage> = from_jsonl(&messages, MESSAGES_ENTRY)?;
+    let translations: Vec<ArchivedTranslation> = from_jsonl(&translations, TRANSLATIONS_ENTRY)?;
+    if messages.len() as u64 != manifest.message_count {
+        anyhow::bail!("Archive message count mismatch");
+    }
+    Ok(ChannelArchive {
+        manifest,
+        messages,
+        translations,
+    })
+}
+
+async fn collect_channel(
+    db_key: &str,
+    server_socket: &str,
+    channel_id: i64,
+) -> anyhow::Result<ChannelArchive> {
+    let db = get_read_db(db_key).await?;
+    let conn = &db.connection;
+    let rows = conn
+        .query_all(&RawStatement::new(
+            r#"
+            SELECT id, user_id, content, created_at, updated_at FROM messages
+            WHERE channel_id = ? AND status = 'sent'
+            ORDER BY created_at ASC, id ASC
+            "#
+            .to_string(),
+            vec![Value::from(channel_id)],
+        ))
+        .await?;
+    let mut messages = Vec::with_capacity(rows.len());
+    for row in rows {
+        messages.push(ArchivedMessage {
+            id: row.try_get("", "id")?,
+            user_id: row.try_get("", "user_id")?,
+            content: row.try_get("", "content")?,
+            created_at: row.try_get("", "created_at")?,
+            updated_at: row.try_get("", "updated_at")?,
+        });
+    }
+    let rows = conn
+        .query_all(&RawStatement::new(
+            r#"
+            SELECT t.message_id, t.target_lang, t.provider, t.text, t.created_at
+            FROM translations t
+            JOIN messages m ON m.id = t.message_id
+            WHERE m.channel_id = ? AND m.status = 'sent'
+            "#
+            .to_string(),
+            vec![Value::from(channel_id)],
+        ))
+        .await?;
+    let mut translations = Vec::with_capacity(rows.len());
+    for row in rows {
+        translations.push(ArchivedTranslation {
+            message_id: row.try_get("", "message_id")?,
+            target_lang: row.try_get("", "target_lang")?,
+            provider: row.try_get("", "provider")?,
+            text: row.try_get("", "text")?,
+            created_at: row.try_get("", "created_at")?,
+        });
+    }
+    Ok(ChannelArchive {
+        manifest: ArchiveManifest {
+            format: ARCHIVE_FORMAT.to_string(),
+            version: ARCHIVE_VERSION,
+            server_socket: server_socket.to_string(),
+            db_key: db_key.to_string(),
+            channel_id,
+            created_at: now_ms(),
+            message_count: 0,
+            translation_count: 0,
+            oldest_at: None,
+            newest_at: None,
+            messages_sha256: String::new(),
+            translations_sha256: String::new(),
+        },
+        messages,
+        translations,
+    })
+}
+
+/// 删除已归档的消息（只删除归档时读取到的行，期间新到的消息保留）。
+async fn remove_archived(db_key: &str, messages: &[ArchivedMessage]) -> anyhow::Result<u64> {
+    let db = get_db(db_key).await?;
+    let txn = db.connection.begin().await?;
+    let mut removed = 0;
+    for message in messages {
+        // 归档后被编辑或已删除的行不受影响；翻译由删除触发器一并清理。
+        removed += txn
+            .execute(&RawStatement::new(
+                "DELETE FROM messages WHERE id = ? AND updated_at = ?".to_string(),
+                vec![
+                    Value::from(message.id.as_str()),
+                    Value::from(message.updated_at),
+                ],
+            ))
+            .await?
+            .rows_affected();
+    }
+    txn.commit().await?;
+    Ok(removed)
+}
+
+fn checked_path(raw: &str) -> CommandResult<PathBuf> {
+    let trimmed = raw.trim();
+    if trimmed.is_empty() {
+        return Err(command_error(
+            "DB_ARCHIVE_PATH_REQUIRED",
+            "error.db_archive_path_required",
+        ));
+    }
+    Ok(PathBuf::from(trimmed))
+}
+
+#[tauri::command]
+/// 将频道消息归档到冷存储文件，并从 server DB 中删除。
+///
+/// # 参数
+/// - `db_key`：server DB key（`server_<sha256>`），必须已打开。
+/// - `server_socket`：服务器 socket（写入清单，供恢复时展示与校验）。
+/// - `channel_id`：频道 id。
+/// - `dest`：归档文件路径（已存在时覆盖）。
+///
+/// # 返回值
+/// - `Ok(ArchiveReport)`：归档清单与文件大小。
+/// - `Err(CommandError)`：写出失败时不会删除任何消息。
+pub async fn archive_channel(
+    db_key: String,
+    server_socket: String,
+    channel_id: i64,
+    dest: String,
+) -> CommandResult<ArchiveReport> {
+    if !is_server_db_key(&db_key) {
+        return Err(command_error("DB_KEY_INVALID", "error.db_key_invalid"));
+    }
+    let dest = checked_path(&dest)?;
+    let map_err =
+        |e: anyhow::Error| to_command_error("DB_ARCHIVE_FAILED", "error.db_archive_failed", e);
+    let taskbar = TaskbarProgress::start("archive_export");
+    let archive = collect_channel(&db_key, server_socket.trim(), channel_id)
+        .await
+        .map_err(map_err)?;
+    let write_dest = dest.clone();
+    let (manifest, archive) = tauri::async_runtime::spawn_blocking(move || {
+        write_archive(&write_dest, &archive).map(|manifest| (manifest, archive))
+    })
+    .await
+    .map_err(|e| map_err(e.into()))?
+    .map_err(map_err)?;
+    let removed = remove_archived(&db_key, &archive.messages)
+        .await
+        .map_err(map_err)?;
+    super::quick_switch::invalidate(&db_key);
+    taskbar.finish();
+    let file_bytes = std::fs::metadata(&dest).map(|m| m.len()).unwrap_or(0);
+    tracing::info!(
+        action = "db_channel_archived",
+        channel_id,
+        messages = manifest.message_count,
+        removed,
+        file_bytes
+    );
+    Ok(ArchiveReport {
+        path: dest.to_string_lossy().to_string(),
+        manifest,
+        file_bytes,
+    })
+}
+
+async fn merge_archive(archive: &ChannelArchive) -> anyhow::Result<RestoreReport> {
+    let manifest = &archive.manifest;
+    let db = get_db(&manifest.db_key)
+        .await
+        .context("Server database for this archive is not open")?;
+    let txn = db.connection.begin().await?;
+    let mut report = RestoreReport {
+        server_socket: manifest.server_socket.clone(),
+        db_key: manifest.db_key.clone(),
+        channel_id: manifest.channel_id,
+        ..RestoreReport::default()
+    };
+    for message in &archive.messages {
+        let affected = txn
+            .execute(&RawStatement::new(
+                r#"
+                INSERT INTO messages (id, channel_id, user_id, content, created_at, updated_at)
+                SELECT ?1, ?2, ?3, ?4, ?5, ?6
+                WHERE NOT EXISTS (SELECT 1 FROM message_tombstones WHERE message_id = ?1)
+                ON CONFLICT(id) DO UPDATE SET
+                    content = excluded.content,
+                    updated_at = excluded.updated_at
+                WHERE excluded.updated_at > messages.updated_at
+                "#
+                .to_string(),
+                vec![
+                    Value::from(message.id.as_str()),
+                    Value::from(manifest.channel_id),
+                    Value::from(message.user_id),
+                    Value::from(message.content.as_str()),
+                    Value::from(message.created_at),
+                    Value::from(message.updated_at),
+                ],
+            ))
+            .await?
+            .rows_affected();
+        if affected > 0 {
+            report.restored += 1;
+        } else {
+            report.skipped += 1;
+        }
+    }
+    for translation in &archive.translations {
+        report.translations += txn
+            .execute(&RawStatement::new(
+                r#"
+                INSERT OR IGNORE INTO translations (message_id, target_lang, provider, text, created_at)
+                SELECT ?1, ?2, ?3, ?4, ?5
+                WHERE EXISTS (SELECT 1 FROM messages WHERE id = ?1)
+                "#
+                .to_string(),
+                vec![
+                    Value::from(translation.message_id.as_str()),
+                    Value::from(translation.target_lang.as_str()),
+                    Value::from(translation.provider.as_str()),
+                    Value::from(translation.text.as_str()),
+                    Value::from(translation.created_at),
+                ],
+            ))
+            .await?
+            .rows_affected();
+    }
+    txn.commit().await?;
+    Ok(report)
+}
+
+#[tauri::command]
+/// 将归档文件合并回原 server DB。
+///
+/// # 参数
+/// - `path`：归档文件路径；对应的 server DB 必须已打开。
+///
+/// # 返回值
+/// - `Ok(RestoreReport)`：恢复与跳过的消息数。
+/// - `Err(CommandError)`：格式/摘要校验失败或写入失败（事务回滚，不会部分恢复）。
+pub async fn restore_archive(path: String) -> CommandResult<RestoreReport> {
+    let path = checked_path(&path)?;
+    let archive = tauri::async_runtime::spawn_blocking(move || read_archive(&path))
+        .await
+        .map_err(|e| to_command_error("DB_RESTORE_FAILED", "error.db_restore_failed", e.into()))?
+        .map_err(|e| to_command_error("DB_ARCHIVE_INVALID", "error.db_archive_invalid", e))?;
+    if !is_server_db_key(&archive.manifest.db_key) {
+        return Err(command_error(
+            "DB_ARCHIVE_INVALID",
+            "error.db_archive_invalid",
+        ));
+    }
+    let report = merge_archive(&archive)
+        .await
+        .map_err(|e| to_command_error("DB_RESTORE_FAILED", "error.db_restore_failed", e))?;
+    super::quick_switch::invalidate(&report.db_key);
+    crate::shared::mute_rules::schedule_evaluation(&report.db_key);
+    tracing::info!(
+        action = "db_channel_archive_restored",
+        channel_id = report.channel_id,
+        restored = report.restored,
+        skipped = report.skipped
+    );
+    Ok(report)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample() -> ChannelArchive {
+        ChannelArchive {
+            manifest: ArchiveManifest {
+                format: ARCHIVE_FORMAT.to_string(),
+                version: ARCHIVE_VERSION,
+                server_socket: "tls://chat.example.com:443".to_string(),
+                db_key: format!("server_{}", "a".repeat(64)),
+                channel_id: 42,
+                created_at: 1,
+                message_count: 0,
+                translation_count: 0,
+                oldest_at: None,
+                newest_at: None,
+                messages_sha256: String::new(),
+                translations_sha256: String::new(),
+            },
+            messages: vec![
+                ArchivedMessage {
+                    id: "1".to_string(),
+                    user_id: 7,
+                    content: "hello\nworld".to_string(),
+                    created_at: 100,
+                    updated_at: 100,
+                },
+                ArchivedMessage {
+                    id: "2".to_string(),
+                    user_id: 8,
+                    content: "second".to_string(),
+                    created_at: 200,
+                    updated_at: 250,
+                },
+            ],
+            translations: vec![ArchivedTranslation {
+                message_id: "1".to_string(),
+                target_lang: "en".to_string(),
+                provider: "test".to_string(),
+                text: "hello".to_string(),
+                created_at: 300,
+            }],
+        }
+    }
+
+    #[test]
+    fn archive_round_trips_with_recomputed_manifest() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("channel.cparchive");
+        let archive = sample();
+        let manifest = write_archive(&path, &archive).unwrap();
+        assert_eq!(manifest.message_count, 2);
+        assert_eq!(manifest.translation_count, 1);
+        assert_eq!(
+            (manifest.oldest_at, manifest.newest_at),
+            (Some(100), Some(200))
+        );
+
+        let restored = read_archive(&path).unwrap();
+        assert_eq!(restored.manifest, manifest);
+        assert_eq!(restored.messages, archive.messages);
+        assert_eq!(restored.translations, archive.translations);
+    }
+
+    #[test]
+    fn tampered_manifest_checksum_is_rejected() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("channel.cparchive");
+        let mut archive = sample();
+        let manifest = write_archive(&path, &archive).unwrap();
+
+        // 用错误的摘要重写清单：内容不变，但校验应失败。
+        archive.messages.pop();
+        let tampered = dir.path().join("tampered.cparchive");
+        let file = std::fs::File::create(&tampered).unwrap();
+        let mut zip = zip::ZipWriter::new(file);
+        write_entry(
+            &mut zip,
+            MESSAGES_ENTRY,
+            &to_jsonl(&archive.messages).unwrap(),
+        )
+        .unwrap();
+        write_entry(
+            &mut zip,
+            TRANSLATIONS_ENTRY,
+            &to_jsonl(&archive.translations).unwrap(),
+        )
+        .unwrap();
+        write_entry(
+            &mut zip,
+            MANIFEST_ENTRY,
+            &serde_json::to_vec(&manifest).unwrap(),
+        )
+        .unwrap();
+        zip.finish().unwrap();
+
+        let err = read_archive(&tampered).unwrap_err();
+        assert!(err.to_string().contains("checksum mismatch"));
+    }
+}
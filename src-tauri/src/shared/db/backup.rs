@@ -0,0 +1,562 @@
+//! shared｜数据库：备份、定时备份与轮换。
+//!
+//! 说明：
+//! - 每次备份是 `backups/<id>/` 目录：system DB 与当时已打开的 server DB 各一个文件
+//!   （`VACUUM INTO` 生成一致性快照），`manifest.json` 记录大小与 SHA-256；
+//! - 写入先在 `<id>.tmp` 目录完成再重命名，未完成的备份不会出现在列表中；
+//! - 定时备份（设置 `scheduled_backup`）每晚执行一次：当天尚无备份且已过 `NIGHTLY_HOUR`
+//!   时执行，使用电池供电时跳过并在下一次检查时重试；
+//! - 轮换保留最近 `backup_keep_daily` 天（每天最新一份）与最近 `backup_keep_weekly` 周
+//!   （每周最新一份），其余删除；
+//! - 恢复时先关闭对应连接再替换文件，原本已打开的 DB 会重新打开并补齐迁移。
+//!
+//! 约定：注释中文，日志英文（tracing）。
+
+use std::collections::HashSet;
+use std::io::Read;
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+use std::time::Duration;
+
+use anyhow::Context;
+use chrono::{Datelike, Local, NaiveDate, TimeZone, Timelike};
+use sea_orm::ConnectionTrait;
+use serde::{Deserialize, Serialize};
+use sha2::Digest;
+
+use crate::features::settings::get_config_value;
+use crate::shared::error::{CommandResult, command_error, to_command_error};
+use crate::shared::taskbar_progress::TaskbarProgress;
+
+use super::commands::{is_server_db_key, managed_db_file, reopen_managed_db};
+use super::{get_db, registered_keys, remove_db};
+
+/// 备份目录名（位于应用数据目录下）。
+const BACKUP_DIR: &str = "backups";
+pub(super) const MANIFEST_FILE: &str = "manifest.json";
+
+/// 默认保留的每日/每周备份数。
+const DEFAULT_KEEP_DAILY: u32 = 7;
+const DEFAULT_KEEP_WEEKLY: u32 = 4;
+
+/// 每晚备份的最早时刻（本地时间，小时）。
+const NIGHTLY_HOUR: u32 = 2;
+
+/// 启动后首次检查前的延迟（避开启动高峰）。
+const STARTUP_DELAY: Duration = Duration::from_secs(15 * 60);
+/// 定时检查间隔。
+const CHECK_INTERVAL: Duration = Duration::from_secs(60 * 60);
+
+/// 备份中的单个 DB 文件。
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BackupDatabase {
+    pub key: String,
+    pub file: String,
+    pub bytes: u64,
+    pub sha256: String,
+}
+
+/// 备份清单。
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BackupManifest {
+    pub id: String,
+    pub created_at: i64,
+    /// 是否由定时任务创建。
+    pub scheduled: bool,
+    pub databases: Vec<BackupDatabase>,
+}
+
+/// `list_backups` 的单项。
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BackupInfo {
+    #[serde(flatten)]
+    pub manifest: BackupManifest,
+    pub total_bytes: u64,
+}
+
+/// `restore_backup` 的结果。
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RestoreBackupReport {
+    pub id: String,
+    /// 已恢复的 DB key。
+    pub restored: Vec<String>,
+    /// 恢复后重新打开的 DB key（前端应重新加载这些 DB 的数据）。
+    pub reopened: Vec<String>,
+}
+
+/// 备份/恢复互斥锁（避免定时备份与手动操作并发）。
+pub(super) fn backup_lock() -> &'static tokio::sync::Mutex<()> {
+    static LOCK: OnceLock<tokio::sync::Mutex<()>> = OnceLock::new();
+    LOCK.get_or_init(|| tokio::sync::Mutex::new(()))
+}
+
+fn now_ms() -> i64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_millis() as i64
+}
+
+pub(super) fn backup_root() -> anyhow::Result<PathBuf> {
+    Ok(crate::shared::app_data_dir::get_app_data_dir()?.join(BACKUP_DIR))
+}
+
+/// 备份 id 形如 `20260101-030000`（本地时间），只允许数字与连字符。
+pub(super) fn is_valid_backup_id(id: &str) -> bool {
+    !id.is_empty() && id.len() <= 32 && id.chars().all(|c| c.is_ascii_digit() || c == '-')
+}
+
+fn local_date(created_at: i64) -> Option<NaiveDate> {
+    Local
+        .timestamp_millis_opt(created_at)
+        .single()
+        .map(|t| t.date_naive())
+}
+
+fn file_sha256(path: &Path) -> anyhow::Result<String> {
+    let mut file = std::fs::File::open(path)
+        .with_context(|| format!("Failed to open backup file: {}", path.display()))?;
+    let mut hasher = sha2::Sha256::new();
+    let mut buf = vec![0u8; 64 * 1024];
+    loop {
+        let n = file.read(&mut buf)?;
+        if n == 0 {
+            break;
+        }
+        hasher.update(&buf[..n]);
+    }
+    Ok(hex::encode(hasher.finalize()))
+}
+
+/// 计算需要删除的备份：保留最近 `keep_daily` 天与最近 `keep_weekly` 周各自最新的一份。
+///
+/// `backups` 为 `(id, 本地日期)`，顺序任意。
+fn expired_backups(
+    backups: &[(String, NaiveDate)],
+    keep_daily: u32,
+    keep_weekly: u32,
+) -> Vec<String> {
+    let mut sorted: Vec<&(String, NaiveDate)> = backups.iter().collect();
+    // id 由本地时间生成，同一天内按 id 排序即按时间排序。
+    sorted.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| b.0.cmp(&a.0)));
+
+    let mut keep = HashSet::new();
+    let mut days = HashSet::new();
+    let mut weeks = HashSet::new();
+    for (id, date) in &sorted {
+        if days.len() < keep_daily as usize && days.insert(*date) {
+            keep.insert(id.as_str());
+        }
+        let week = (date.iso_week().year(), date.iso_week().week());
+        if weeks.len() < keep_weekly as usize && weeks.insert(week) {
+            keep.insert(id.as_str());
+        }
+    }
+    sorted
+        .iter()
+        .filter(|(id, _)| !keep.contains(id.as_str()))
+        .map(|(id, _)| id.clone())
+        .collect()
+}
+
+pub(super) fn read_manifest(dir: &Path) -> anyhow::Result<BackupManifest> {
+    let raw = std::fs::read(dir.join(MANIFEST_FILE))
+        .with_context(|| format!("Failed to read backup manifest: {}", dir.display()))?;
+    serde_json::from_slice(&raw).context("Invalid backup manifest")
+}
+
+/// 列出已完成的备份（最新在前）。
+fn load_backups(root: &Path) -> anyhow::Result<Vec<BackupManifest>> {
+    let entries = match std::fs::read_dir(root) {
+        Ok(entries) => entries,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(e) => return Err(e.into()),
+    };
+    let mut manifests = Vec::new();
+    for entry in entries.flatten() {
+        let name = entry.file_name().to_string_lossy().to_string();
+        if !is_valid_backup_id(&name) || !entry.path().is_dir() {
+            continue;
+        }
+        match read_manifest(&entry.path()) {
+            Ok(manifest) if manifest.id == name => manifests.push(manifest),
+            Ok(_) => {}
+            Err(e) => {
+                tracing::warn!(action = "db_backup_manifest_invalid", id = %name, error = %e);
+            }
+        }
+    }
+    manifests.sort_by(|a, b| b.id.cmp(&a.id));
+    Ok(manifests)
+}
+
+/// 备份 system DB 与所有已打开的 server DB。
+async fn create_backup(scheduled: bool) -> anyhow::Result<BackupManifest> {
+    let _guard = backup_lock().lock().await;
+    let root = backup_root()?;
+    let created_at = now_ms();
+    let id = Local::now().format("%Y%m%d-%H%M%S").to_string();
+    let dir = root.join(&id);
+    if dir.exists() {
+        anyhow::bail!("Backup already exists: {id}");
+    }
+    let tmp = root.join(format!("{id}.tmp"));
+    if tmp.exists() {
+        tokio::fs::remove_dir_all(&tmp).await?;
+    }
+    tokio::fs::create_dir_all(&tmp)
+        .await
+        .with_context(|| format!("Failed to create backup dir: {}", tmp.display()))?;
+
+    match write_backup(&tmp, &dir, id, created_at, scheduled).await {
+        Ok(manifest) => Ok(manifest),
+        Err(e) => {
+            // 未完成的备份目录不会出现在列表中，也不会被轮换清理，需在此删除。
+            if let Err(cleanup) = tokio::fs::remove_dir_all(&tmp).await
+                && cleanup.kind() != std::io::ErrorKind::NotFound
+            {
+                tracing::warn!(
+                    action = "db_backup_tmp_cleanup_failed",
+                    path = %tmp.display(),
+                    error = %cleanup
+                );
+            }
+            Err(e)
+        }
+    }
+}
+
+/// 在 `tmp` 中写入各 DB 快照与清单，完成后重命名为 `dir`。
+async fn write_backup(
+    tmp: &Path,
+    dir: &Path,
+    id: String,
+    created_at: i64,
+    scheduled: bool,
+) -> anyhow::Result<BackupManifest> {
+    let keys: Vec<String> = registered_keys()
+        .await
+        .into_iter()
+        .filter(|key| key == "system" || is_server_db_key(key))
+        .collect();
+    // 任务栏按已完成的 DB 数显示进度（最后一步写清单计为一份）。
+    let taskbar = TaskbarProgress::start("backup");
+    let steps = keys.len() as u64 + 1;
+    let mut files = Vec::with_capacity(keys.len());
+    for key in keys {
+        taskbar.set(files.len() as u64, steps);
+        let file = format!("{key}.db");
+        let dest = tmp.join(&file);
+        let db = get_db(&key).await?;
+        let dest_sql = dest.to_string_lossy().replace('\'', "''");
+        db.connection
+            .execute_unprepared(&format!("VACUUM INTO '{dest_sql}'"))
+            .await
+            .with_context(|| format!("Failed to back up database: {key}"))?;
+        files.push((key, file));
+    }
+    taskbar.set(files.len() as u64, steps);
+
+    let manifest = tauri::async_runtime::spawn_blocking({
+        let tmp = tmp.to_path_buf();
+        move || -> anyhow::Result<BackupManifest> {
+            let mut databases = Vec::with_capacity(files.len());
+            for (key, file) in files {
+                let path = tmp.join(&file);
+                databases.push(BackupDatabase {
+                    bytes: std::fs::metadata(&path)?.len(),
+                    sha256: file_sha256(&path)?,
+                    key,
+                    file,
+                });
+            }
+            let manifest = BackupManifest {
+                id,
+                created_at,
+                scheduled,
+                databases,
+            };
+            std::fs::write(
+                tmp.join(MANIFEST_FILE),
+                serde_json::to_vec_pretty(&manifest)?,
+            )?;
+            Ok(manifest)
+        }
+    })
+    .await??;
+    tokio::fs::rename(tmp, dir)
+        .await
+        .with_context(|| format!("Failed to finalize backup: {}", dir.display()))?;
+    taskbar.finish();
+    tracing::info!(
+        action = "db_backup_created",
+        id = %manifest.id,
+        databases = manifest.databases.len(),
+        scheduled
+    );
+    Ok(manifest)
+}
+
+/// 按设置删除过期备份。
+async fn rotate_backups() -> anyhow::Result<usize> {
+    let keep_daily = match get_config_value::<u32>(String::from("backup_keep_daily")).await {
+        0 => DEFAULT_KEEP_DAILY,
+        n => n,
+    };
+    let keep_weekly = match get_config_value::<u32>(String::from("backup_keep_weekly")).await {
+        0 => DEFAULT_KEEP_WEEKLY,
+        n => n,
+    };
+    let _guard = backup_lock().lock().await;
+    let root = backup_root()?;
+    let manifests = load_backups(&root)?;
+    let dated: Vec<(String, NaiveDate)> = manifests
+        .iter()
+        .filter_map(|m| Some((m.id.clone(), local_date(m.created_at)?)))
+        .collect();
+    let expired = expired_backups(&dated, keep_daily, keep_weekly);
+    for id in &expired {
+        if let Err(e) = tokio::fs::remove_dir_all(root.join(id)).await {
+            tracing::warn!(action = "db_backup_remove_failed", id = %id, error = %e);
+        }
+    }
+    Ok(expired.len())
+}
+
+/// 定时备份的单次检查。
+async fn run_scheduled_once() {
+    if !get_config_value::<bool>(String::from("scheduled_backup")).await {
+        return;
+    }
+    let now = Local::now();
+    if now.hour() < NIGHTLY_HOUR {
+        return;
+    }
+    let today = now.date_naive();
+    let latest = backup_root()
+        .and_then(|root| load_backups(&root))
+        .ok()
+        .and_then(|manifests| manifests.into_iter().find(|m| m.scheduled))
+        .and_then(|m| local_date(m.created_at));
+    if latest == Some(today) {
+        return;
+    }
+    let on_battery = tauri::async_runtime::spawn_blocking(crate::shared::power::on_battery)
+        .await
+        .ok()
+        .flatten();
+    if on_battery == Some(true) {
+        tracing::info!(action = "db_backup_skipped_on_battery");
+        return;
+    }
+    if let Err(e) = create_backup(true).await {
+        tracing::warn!(action = "db_backup_scheduled_failed", error = %e);
+        return;
+    }
+    match rotate_backups().await {
+        Ok(removed) => tracing::info!(action = "db_backup_rotated", removed),
+        Err(e) => tracing::warn!(action = "db_backup_rotate_failed", error = %e),
+    }
+}
+
+/// 启动定时备份任务。
+pub fn spawn_backup_job() {
+    tauri::async_runtime::spawn(async move {
+        tokio::time::sleep(STARTUP_DELAY).await;
+        let mut interval = tokio::time::interval(CHECK_INTERVAL);
+        loop {
+            interval.tick().await;
+            run_scheduled_once().await;
+        }
+    });
+}
+
+#[tauri::command]
+/// 立即备份 system DB 与所有已打开的 server DB。
+///
+/// # 返回值
+/// - `Ok(BackupManifest)`：备份清单。
+/// - `Err(CommandError)`：备份失败（不会留下不完整的备份）。
+pub async fn backup_now() -> CommandResult<BackupManifest> {
+    create_backup(false)
+        .await
+        .map_err(|e| to_command_error("DB_BACKUP_FAILED", "error.db_backup_failed", e))
+}
+
+#[tauri::command]
+/// 列出已有备份（最新在前）。
+pub async fn list_backups() -> CommandResult<Vec<BackupInfo>> {
+    let map_err = |e: anyhow::Error| {
+        to_command_error("DB_BACKUP_LIST_FAILED", "error.db_backup_list_failed", e)
+    };
+    let root = backup_root().map_err(map_err)?;
+    let manifests = tauri::async_runtime::spawn_blocking(move || load_backups(&root))
+        .await
+        .map_err(|e| map_err(e.into()))?
+        .map_err(map_err)?;
+    Ok(manifests
+        .into_iter()
+        .map(|manifest| BackupInfo {
+            total_bytes: manifest.databases.iter().map(|d| d.bytes).sum(),
+            manifest,
+        })
+        .collect())
+}
+
+/// 校验备份文件并返回 `(key, 备份文件, 目标文件)`。
+fn verify_backup(
+    dir: &Path,
+    manifest: &BackupManifest,
+) -> anyhow::Result<Vec<(String, PathBuf, PathBuf)>> {
+    let mut files = Vec::with_capacity(manifest.databases.len());
+    for db in &manifest.databases {
+        let target = managed_db_file(&db.key)
+            .with_context(|| format!("Backup contains an unmanaged database key: {}", db.key))?;
+        if db.file != format!("{}.db", db.key) {
+            anyhow::bail!("Unexpected backup file name: {}", db.file);
+        }
+        let source = dir.join(&db.file);
+        if file_sha256(&source)? != db.sha256 {
+            anyhow::bail!("Backup checksum mismatch: {}", db.file);
+        }
+        files.push((db.key.clone(), source, target));
+    }
+    Ok(files)
+}
+
+/// 用备份文件替换目标 DB 文件（目标连接必须已关闭）。
+fn replace_db_file(source: &Path, target: &Path) -> anyhow::Result<()> {
+    if let Some(dir) = target.parent() {
+        std::fs::create_dir_all(dir)?;
+    }
+    let tmp = target.with_extension("db.restore");
+    std::fs::copy(source, &tmp)
+        .with_context(|| format!("Failed to copy backup file: {}", source.display()))?;
+    for suffix in ["-wal", "-shm"] {
+        let sidecar = PathBuf::from(format!("{}{suffix}", target.display()));
+        if sidecar.exists() {
+            std::fs::remove_file(&sidecar)?;
+        }
+    }
+    std::fs::rename(&tmp, target)
+        .with_context(|| format!("Failed to replace database file: {}", target.display()))?;
+    Ok(())
+}
+
+#[tauri::command]
+/// 从备份恢复 DB（覆盖当前数据）。
+///
+/// # 参数
+/// - `id`：备份 id（见 `list_backups`）。
+///
+/// # 返回值
+/// - `Ok(RestoreBackupReport)`：已恢复与重新打开的 DB key。
+/// - `Err(CommandError)`：备份不存在、校验失败或替换失败。
+///
+/// # 说明
+/// 恢复前会先校验所有文件；替换过程中失败时已替换的文件不会回滚，可再次恢复同一备份。
+pub async fn restore_backup(id: String) -> CommandResult<RestoreBackupReport> {
+    let id = id.trim().to_string();
+    let map_err = |e: anyhow::Error| {
+        to_command_error(
+            "DB_BACKUP_RESTORE_FAILED",
+            "error.db_backup_restore_failed",
+            e,
+        )
+    };
+    let dir = backup_root().map_err(map_err)?.join(&id);
+    if !is_valid_backup_id(&id) || !dir.is_dir() {
+        return Err(command_error(
+            "DB_BACKUP_NOT_FOUND",
+            "error.db_backup_not_found",
+        ));
+    }
+    let _guard = backup_lock().lock().await;
+    let files = tauri::async_runtime::spawn_blocking(move || {
+        let manifest = read_manifest(&dir)?;
+        verify_backup(&dir, &manifest)
+    })
+    .await
+    .map_err(|e| map_err(e.into()))?
+    .map_err(map_err)?;
+
+    let open_keys = registered_keys().await;
+    let mut report = RestoreBackupReport {
+        id: id.clone(),
+        restored: Vec::new(),
+        reopened: Vec::new(),
+    };
+    for (key, source, target) in files {
+        let was_open = open_keys.contains(&key);
+        if was_open {
+            remove_db(&key).await.map_err(map_err)?;
+        }
+        tauri::async_runtime::spawn_blocking(move || replace_db_file(&source, &target))
+            .await
+            .map_err(|e| map_err(e.into()))?
+            .map_err(map_err)?;
+        report.restored.push(key.clone());
+        if was_open {
+            reopen_managed_db(&key).await.map_err(map_err)?;
+            if is_server_db_key(&key) {
+                super::quick_switch::invalidate(&key);
+            }
+            report.reopened.push(key);
+        }
+    }
+    tracing::info!(
+        action = "db_backup_restored",
+        id = %id,
+        restored = report.restored.len()
+    );
+    Ok(report)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn day(y: i32, m: u32, d: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(y, m, d).unwrap()
+    }
+
+    #[test]
+    fn rotation_keeps_newest_per_day_and_week() {
+        // 2026-03-02 是周一。
+        let backups = vec![
+            ("20260316-030000".to_string(), day(2026, 3, 16)),
+            ("20260315-120000".to_string(), day(2026, 3, 15)),
+            ("20260315-030000".to_string(), day(2026, 3, 15)),
+            ("20260314-030000".to_string(), day(2026, 3, 14)),
+            ("20260310-030000".to_string(), day(2026, 3, 10)),
+            ("20260309-030000".to_string(), day(2026, 3, 9)),
+            ("20260303-030000".to_string(), day(2026, 3, 3)),
+            ("20260224-030000".to_string(), day(2026, 2, 24)),
+        ];
+        let mut expired = expired_backups(&backups, 2, 3);
+        expired.sort();
+        // 每日：16 与 15（中午那份）；每周：第 12 周（16）、第 11 周（15 中午）、第 10 周（3）。
+        assert_eq!(
+            expired,
+            vec![
+                "20260224-030000",
+                "20260309-030000",
+                "20260310-030000",
+                "20260314-030000",
+                "20260315-030000",
+            ]
+        );
+    }
+
+    #[test]
+    fn backup_ids_reject_path_segments() {
+        assert!(is_valid_backup_id("20260101-030000"));
+        assert!(!is_valid_backup_id(""));
+        assert!(!is_valid_backup_id("../system"));
+        assert!(!is_valid_backup_id("20260101-030000.tmp"));
+    }
+}
@@ -0,0 +1,300 @@
+//! shared｜数据库：备份包加密（口令派生密钥 + 分块 AES-256-GCM）。
+//!
+//! 格式：
+//! - 头部：`MAGIC`(8) | PBKDF2 迭代次数 u32 BE | salt(16) | nonce 前缀(8)；
+//! - 之后为若干分块：`last` 标记 u8 | 密文长度 u32 BE | 密文（含 16 字节 tag）；
+//! - 第 i 块的 nonce 为 `前缀 || i`（u32 BE），AAD 为 `头部 || last`，
+//!   因此分块被重排、截断或头部被篡改都会导致解密失败。
+//!
+//! 密钥由 PBKDF2-HMAC-SHA256 从用户口令派生；HMAC 同时供 S3 签名使用。
+//!
+//! 约定：注释中文，日志英文（tracing）。
+
+use std::io::{Read, Write};
+
+use aes_gcm::aead::{Aead, KeyInit, Payload};
+use aes_gcm::{Aes256Gcm, Nonce};
+use anyhow::Context;
+use sha2::{Digest, Sha256};
+
+const MAGIC: &[u8; 8] = b"CPBKENC1";
+const HEADER_LEN: usize = 8 + 4 + 16 + 8;
+const TAG_LEN: usize = 16;
+
+/// 默认 PBKDF2 迭代次数。
+const DEFAULT_ITERATIONS: u32 = 600_000;
+/// 解密时接受的最大迭代次数（避免恶意文件导致长时间计算）。
+const MAX_ITERATIONS: u32 = 10_000_000;
+
+/// 明文分块大小。
+#[cfg(not(test))]
+const CHUNK_SIZE: usize = 1024 * 1024;
+#[cfg(test)]
+const CHUNK_SIZE: usize = 64;
+
+/// 口令错误或数据被篡改（调用方据此返回“口令错误”提示）。
+#[derive(Debug)]
+pub struct BackupDecryptError;
+
+impl std::fmt::Display for BackupDecryptError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "backup decryption failed (wrong passphrase or corrupted data)"
+        )
+    }
+}
+
+impl std::error::Error for BackupDecryptError {}
+
+/// HMAC-SHA256。
+pub(super) fn hmac_sha256(key: &[u8], data: &[u8]) -> [u8; 32] {
+    let (inner, outer) = hmac_states(key);
+    hmac_finish(inner, outer, data)
+}
+
+/// 预先计算 HMAC 的内外层状态（PBKDF2 中重复使用）。
+fn hmac_states(key: &[u8]) -> (Sha256, Sha256) {
+    let mut block = [0u8; 64];
+    if key.len() > block.len() {
+        block[..32].copy_from_slice(&Sha256::digest(key));
+    } else {
+        block[..key.len()].copy_from_slice(key);
+    }
+    let mut inner = Sha256::new();
+    let mut outer = Sha256::new();
+    inner.update(block.map(|b| b ^ 0x36));
+    outer.update(block.map(|b| b ^ 0x5c));
+    (inner, outer)
+}
+
+fn hmac_finish(mut inner: Sha256, mut outer: Sha256, data: &[u8]) -> [u8; 32] {
+    inner.update(data);
+    outer.update(inner.finalize());
+    let mut out = [0u8; 32];
+    out.copy_from_slice(&outer.finalize());
+    out
+}
+
+/// PBKDF2-HMAC-SHA256，输出 32 字节。
+pub(crate) fn pbkdf2_sha256(passphrase: &[u8], salt: &[u8], iterations: u32) -> [u8; 32] {
+    let (inner, outer) = hmac_states(passphrase);
+    let mut first = salt.to_vec();
+    first.extend_from_slice(&1u32.to_be_bytes());
+    let mut u = hmac_finish(inner.clone(), outer.clone(), &first);
+    let mut out = u;
+    for _ in 1..iterations {
+        u = hmac_finish(inner.clone(), outer.clone(), &u);
+        for (o, b) in out.iter_mut().zip(u) {
+            *o ^= b;
+        }
+    }
+    out
+}
+
+fn chunk_nonce(prefix: &[u8], index: u32) -> [u8; 12] {
+    let mut nonce = [0u8; 12];
+    nonce[..8].copy_from_slice(prefix);
+    nonce[8..].copy_from_slice(&index.to_be_bytes());
+    nonce
+}
+
+fn chunk_aad(header: &[u8], last: bool) -> Vec<u8> {
+    let mut aad = header.to_vec();
+    aad.push(u8::from(last));
+    aad
+}
+
+/// 尽量读满 `len` 字节；返回实际读到的数据（到达末尾时可能更短）。
+fn read_up_to<R: Read>(reader: &mut R, len: usize) -> std::io::Result<Vec<u8>> {
+    let mut buf = Vec::with_capacity(len);
+    reader.take(len as u64).read_to_end(&mut buf)?;
+    Ok(buf)
+}
+
+/// 用口令加密 `reader` 的全部内容写入 `writer`。
+pub fn encrypt_stream<R: Read, W: Write>(
+    reader: &mut R,
+    writer: &mut W,
+    passphrase: &str,
+) -> anyhow::Result<()> {
+    encrypt_stream_with(reader, writer, passphrase, DEFAULT_ITERATIONS)
+}
+
+fn encrypt_stream_with<R: Read, W: Write>(
+    reader: &mut R,
+    writer: &mut W,
+    passphrase: &str,
+    iterations: u32,
+) -> anyhow::Result<()> {
+    let mut salt = [0u8; 16];
+    let mut prefix = [0u8; 8];
+    getrandom::fill(&mut salt).map_err(|_| anyhow::anyhow!("Failed to generate salt"))?;
+    getrandom::fill(&mut prefix).map_err(|_| anyhow::anyhow!("Failed to generate nonce"))?;
+    let key = pbkdf2_sha256(passphrase.as_bytes(), &salt, iterations);
+    let cipher = Aes256Gcm::new_from_slice(&key).context("Failed to init backup cipher")?;
+
+    let mut header = Vec::with_capacity(HEADER_LEN);
+    header.extend_from_slice(MAGIC);
+    header.extend_from_slice(&iterations.to_be_bytes());
+    header.extend_from_slice(&salt);
+    header.extend_from_slice(&prefix);
+    writer.write_all(&header)?;
+
+    let mut current = read_up_to(reader, CHUNK_SIZE)?;
+    let mut index: u32 = 0;
+    loop {
+        let next = if current.len() == CHUNK_SIZE {
+            read_up_to(reader, CHUNK_SIZE)?
+        } else {
+            Vec::new()
+        };
+        let last = next.is_empty();
+        let aad = chunk_aad(&header, last);
+        let ciphertext = cipher
+            .encrypt(
+                Nonce::from_slice(&chunk_nonce(&prefix, index)),
+                Payload {
+                    msg: &current,
+                    aad: &aad,
+                },
+            )
+            .map_err(|_| anyhow::anyhow!("Failed to encrypt backup chunk"))?;
+        writer.write_all(&[u8::from(last)])?;
+        writer.write_all(&(ciphertext.len() as u32).to_be_bytes())?;
+        writer.write_all(&ciphertext)?;
+        if last {
+            break;
+        }
+        current = next;
+        index = index
+            .checked_add(1)
+            .context("Backup is too large to encrypt")?;
+    }
+    writer.flush()?;
+    Ok(())
+}
+
+/// 用口令解密 `encrypt_stream` 的输出。
+///
+/// 口令错误或数据被篡改时返回 [`BackupDecryptError`]（可通过 `downcast_ref` 识别）。
+pub fn decrypt_stream<R: Read, W: Write>(
+    reader: &mut R,
+    writer: &mut W,
+    passphrase: &str,
+) -> anyhow::Result<()> {
+    let mut header = [0u8; HEADER_LEN];
+    reader
+        .read_exact(&mut header)
+        .context("Encrypted backup is truncated")?;
+    if &header[..8] != MAGIC {
+        anyhow::bail!("Not an encrypted backup");
+    }
+    let iterations = u32::from_be_bytes([header[8], header[9], header[10], header[11]]);
+    if iterations == 0 || iterations > MAX_ITERATIONS {
+        anyhow::bail!("Unsupported key derivation parameters");
+    }
+    let salt = &header[12..28];
+    let prefix = &header[28..36];
+    let key = pbkdf2_sha256(passphrase.as_bytes(), salt, iterations);
+    let cipher = Aes256Gcm::new_from_slice(&key).context("Failed to init backup cipher")?;
+
+    let mut index: u32 = 0;
+    loop {
+        let mut chunk_header = [0u8; 5];
+        reader
+            .read_exact(&mut chunk_header)
+            .context("Encrypted backup is truncated")?;
+        let last = match chunk_header[0] {
+            0 => false,
+            1 => true,
+            _ => return Err(BackupDecryptError.into()),
+        };
+        let len = u32::from_be_bytes([
+            chunk_header[1],
+            chunk_header[2],
+            chunk_header[3],
+            chunk_header[4],
+        ]) as usize;
+        if len > CHUNK_SIZE + TAG_LEN {
+            return Err(BackupDecryptError.into());
+        }
+        let mut ciphertext = vec![0u8; len];
+        reader
+            .read_exact(&mut ciphertext)
+            .context("Encrypted backup is truncated")?;
+        let aad = chunk_aad(&header, last);
+        let plaintext = cipher
+            .decrypt(
+                Nonce::from_slice(&chunk_nonce(prefix, index)),
+                Payload {
+                    msg: &ciphertext,
+                    aad: &aad,
+                },
+            )
+            .map_err(|_| BackupDecryptError)?;
+        writer.write_all(&plaintext)?;
+        if last {
+            break;
+        }
+        index = index.checked_add(1).ok_or(BackupDecryptError)?;
+    }
+    let mut trailing = [0u8; 1];
+    if reader.read(&mut trailing)? != 0 {
+        anyhow::bail!("Unexpected data after encrypted backup");
+    }
+    writer.flush()?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn hmac_and_pbkdf2_match_reference_vectors() {
+        // RFC 4231 test case 2。
+        assert_eq!(
+            hex::encode(hmac_sha256(b"Jefe", b"what do ya want for nothing?")),
+            "5bdcc146bf60754e6a042426089575c75a003f089d2739839dec58b964ec3843"
+        );
+        assert_eq!(
+            hex::encode(pbkdf2_sha256(b"password", b"salt", 1)),
+            "120fb6cffcf8b32c43e7225256c4f837a86548c92ccc35480805987cb70be17b"
+        );
+        assert_eq!(
+            hex::encode(pbkdf2_sha256(b"password", b"salt", 2)),
+            "ae4d0c95af6b46d32d0adff928f06dd02a303f8ef3c251dfd6e2d85a95474c43"
+        );
+    }
+
+    #[test]
+    fn multi_chunk_round_trip_and_wrong_passphrase() {
+        let plain: Vec<u8> = (0..(CHUNK_SIZE * 3 + 7)).map(|i| i as u8).collect();
+        let mut encrypted = Vec::new();
+        encrypt_stream_with(&mut plain.as_slice(), &mut encrypted, "correct horse", 2).unwrap();
+
+        let mut decrypted = Vec::new();
+        decrypt_stream(&mut encrypted.as_slice(), &mut decrypted, "correct horse").unwrap();
+        assert_eq!(decrypted, plain);
+
+        let err = decrypt_stream(&mut encrypted.as_slice(), &mut Vec::new(), "wrong").unwrap_err();
+        assert!(err.downcast_ref::<BackupDecryptError>().is_some());
+    }
+
+    #[test]
+    fn exact_chunk_input_round_trips_and_truncation_is_rejected() {
+        // 恰好整块的输入：通过预读判断最后一块，不额外写空块。
+        let plain = vec![7u8; CHUNK_SIZE * 2];
+        let mut encrypted = Vec::new();
+        encrypt_stream_with(&mut plain.as_slice(), &mut encrypted, "pass phrase", 2).unwrap();
+        let mut decrypted = Vec::new();
+        decrypt_stream(&mut encrypted.as_slice(), &mut decrypted, "pass phrase").unwrap();
+        assert_eq!(decrypted, plain);
+
+        // 去掉最后一块后，前面的块都是非最后块，应判定为截断。
+        let last_chunk = 1 + 4 + CHUNK_SIZE + TAG_LEN;
+        let truncated = &encrypted[..encrypted.len() - last_chunk];
+        assert!(decrypt_stream(&mut &truncated[..], &mut Vec::new(), "pass phrase").is_err());
+    }
+}
@@ -0,0 +1,1081 @@
+//! shared｜数据库：远程备份目标（WebDAV / S3 兼容）。
+//!
+//! 说明：
+//! - 上传：本地备份目录（见 `backup`）打包为 zip，用用户口令加密（见 `backup_crypto`）后
+//!   上传为 `<id>.cpbk`；
+//! - 恢复：下载 → 解密 → 解包为本地 `backups/<id>`（覆盖同 id 的本地副本）→ 按本地备份恢复；
+//! - 口令不落盘，上传与恢复时由用户输入；目标的密码 / secret key 保存在系统凭据存储，
+//!   设置中只保存类型、URL 与用户名；
+//! - S3 使用 path-style URL 与 SigV4 签名，上传使用 `UNSIGNED-PAYLOAD`（依赖 HTTPS 保证完整性，
+//!   备份包本身也带认证加密）；
+//! - 传输进度通过 `backup-transfer-progress` 事件推送，并同步显示在任务栏上。
+//!
+//! 约定：注释中文，日志英文（tracing）。
+
+use std::io::{BufReader, BufWriter, Read};
+use std::path::Path;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, OnceLock};
+use std::time::Duration;
+
+use anyhow::Context;
+use async_trait::async_trait;
+use futures_util::StreamExt;
+use keyring_core::Entry;
+use regex::Regex;
+use reqwest::header::{AUTHORIZATION, CONTENT_LENGTH, CONTENT_TYPE};
+use reqwest::{Method, StatusCode, Url};
+use serde::Serialize;
+use tauri::AppHandle;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use zip::ZipArchive;
+use zip::write::SimpleFileOptions;
+
+use crate::features::settings::data::config_store;
+use crate::features::settings::domain::settings_schema::SettingsBackupRemoteV1;
+use crate::shared::chat_cache::commands::is_missing_secure_storage_error_message;
+use crate::shared::error::{CommandResult, command_error, to_command_error};
+use crate::shared::events::{EventBus, EventBusExt};
+use crate::shared::net::tls_fingerprint::sha256_hex;
+use crate::shared::taskbar_progress::TaskbarProgress;
+
+use super::backup::{
+    MANIFEST_FILE, RestoreBackupReport, backup_lock, backup_root, is_valid_backup_id,
+    read_manifest, restore_backup,
+};
+use super::backup_crypto::{BackupDecryptError, decrypt_stream, encrypt_stream, hmac_sha256};
+
+const SERVICE: &str = "carrypigeon-desktop";
+pub(crate) const SECRET_ACCOUNT: &str = "backup-remote-secret";
+
+/// 远程备份文件后缀。
+const REMOTE_SUFFIX: &str = ".cpbk";
+
+/// 传输进度事件。
+const PROGRESS_EVENT: &str = "backup-transfer-progress";
+/// 两次进度事件之间的最小字节数。
+const PROGRESS_STEP: u64 = 1024 * 1024;
+
+const MIN_PASSPHRASE_CHARS: usize = 8;
+const CONNECT_TIMEOUT: Duration = Duration::from_secs(15);
+const UPLOAD_CHUNK: usize = 256 * 1024;
+/// 解包时单个文件的最大大小。
+const MAX_ENTRY_BYTES: u64 = 8 * 1024 * 1024 * 1024;
+
+const DEFAULT_S3_REGION: &str = "us-east-1";
+const S3_SIGNED_HEADERS: &str = "host;x-amz-content-sha256;x-amz-date";
+const S3_UNSIGNED_PAYLOAD: &str = "UNSIGNED-PAYLOAD";
+
+const PROPFIND_BODY: &str = r#"<?xml version="1.0" encoding="utf-8"?><d:propfind xmlns:d="DAV:"><d:prop><d:getcontentlength/></d:prop></d:propfind>"#;
+
+static HTTP_CLIENT: OnceLock<reqwest::Client> = OnceLock::new();
+
+fn http_client() -> &'static reqwest::Client {
+    // 备份文件可能很大，不设置整体超时，只限制连接时间。
+    HTTP_CLIENT.get_or_init(|| {
+        reqwest::Client::builder()
+            .connect_timeout(CONNECT_TIMEOUT)
+            .build()
+            .map_err(|e| {
+                tracing::error!(action = "db_backup_http_client_build_failed", error = %e);
+            })
+            .ok()
+            .unwrap_or_default()
+    })
+}
+
+/// 远程备份条目。
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RemoteBackupEntry {
+    pub name: String,
+    pub id: String,
+    /// 文件大小（服务器未返回时为空）。
+    pub bytes: Option<u64>,
+}
+
+/// `get_backup_remote` 的结果。
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BackupRemoteStatus {
+    #[serde(flatten)]
+    pub config: SettingsBackupRemoteV1,
+    /// 凭据存储中是否已有密码 / secret key。
+    pub has_secret: bool,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct TransferProgressEvent<'a> {
+    id: &'a str,
+    /// `upload` / `download`。
+    direction: &'a str,
+    transferred: u64,
+    total: Option<u64>,
+}
+
+/// 传输进度上报（按 `PROGRESS_STEP` 节流）。
+#[derive(Clone)]
+struct Progress {
+    events: Arc<dyn EventBus>,
+    id: String,
+    direction: &'static str,
+    total: Option<u64>,
+    last: Arc<AtomicU64>,
+    taskbar: Option<Arc<TaskbarProgress>>,
+}
+
+impl Progress {
+    fn new(
+        events: Arc<dyn EventBus>,
+        id: &str,
+        direction: &'static str,
+        total: Option<u64>,
+    ) -> Self {
+        Self {
+            events,
+            id: id.to_string(),
+            direction,
+            total,
+            last: Arc::new(AtomicU64::new(0)),
+            taskbar: None,
+        }
+    }
+
+    /// 同时在任务栏上显示进度。
+    fn with_taskbar(mut self, operation: &'static str) -> Self {
+        self.taskbar = Some(Arc::new(TaskbarProgress::start(operation)));
+        self
+    }
+
+    /// 传输成功：任务栏进度随后清除，不显示错误。
+    fn finish(&self) {
+        if let Some(taskbar) = &self.taskbar {
+            taskbar.finish();
+        }
+    }
+
+    fn report(&self, transferred: u64) {
+        let last = self.last.load(Ordering::Relaxed);
+        let done = self.total == Some(transferred);
+        if !done && transferred.saturating_sub(last) < PROGRESS_STEP {
+            return;
+        }
+        self.last.store(transferred, Ordering::Relaxed);
+        if let (Some(taskbar), Some(total)) = (&self.taskbar, self.total) {
+            taskbar.set(transferred, total);
+        }
+        let event = TransferProgressEvent {
+            id: &self.id,
+            direction: self.direction,
+            transferred,
+            total: self.total,
+        };
+        self.events.emit(PROGRESS_EVENT, event);
+    }
+}
+
+/// 远程备份目标。
+#[async_trait]
+trait BackupTarget: Send + Sync {
+    async fn upload(&self, name: &str, path: &Path, progress: &Progress) -> anyhow::Result<()>;
+    /// 返回 `(文件名, 大小)`；只包含目标目录下的直接文件。
+    async fn list(&self) -> anyhow::Result<Vec<(String, Option<u64>)>>;
+    async fn download(&self, name: &str, dest: &Path, progress: &Progress) -> anyhow::Result<()>;
+}
+
+/// 以流式请求体读取文件，并上报已发送字节数。
+async fn file_body(path: &Path, progress: Progress) -> anyhow::Result<(reqwest::Body, u64)> {
+    let file = tokio::fs::File::open(path)
+        .await
+        .with_context(|| format!("Failed to open backup package: {}", path.display()))?;
+    let len = file.metadata().await?.len();
+    let stream = futures_util::stream::try_unfold(
+        (file, 0u64, progress),
+        |(mut file, sent, progress)| async move {
+            let mut buf = vec![0u8; UPLOAD_CHUNK];
+            let n = file.read(&mut buf).await?;
+            if n == 0 {
+                return Ok(None);
+            }
+            buf.truncate(n);
+            let sent = sent + n as u64;
+            progress.report(sent);
+            Ok::<_, std::io::Error>(Some((bytes::Bytes::from(buf), (file, sent, progress))))
+        },
+    );
+    Ok((reqwest::Body::wrap_stream(stream), len))
+}
+
+/// 将响应体写入文件，并上报已接收字节数。
+async fn download_to(
+    resp: reqwest::Response,
+    dest: &Path,
+    progress: &Progress,
+) -> anyhow::Result<()> {
+    let mut progress = progress.clone();
+    progress.total = resp.content_length();
+    let mut file = tokio::fs::File::create(dest)
+        .await
+        .with_context(|| format!("Failed to create download file: {}", dest.display()))?;
+    let mut stream = resp.bytes_stream();
+    let mut received = 0u64;
+    while let Some(chunk) = stream.next().await {
+        let chunk = chunk?;
+        file.write_all(&chunk).await?;
+        received += chunk.len() as u64;
+        progress.report(received);
+    }
+    file.flush().await?;
+    Ok(())
+}
+
+fn check_status(resp: reqwest::Response, what: &str) -> anyhow::Result<reqwest::Response> {
+    let status = resp.status();
+    if !status.is_success() {
+        anyhow::bail!("{what} failed with HTTP {status}");
+    }
+    Ok(resp)
+}
+
+/// 确保目录 URL 以 `/` 结尾，便于 `join` 文件名。
+fn directory_url(mut url: Url) -> Url {
+    if !url.path().ends_with('/') {
+        let path = format!("{}/", url.path());
+        url.set_path(&path);
+    }
+    url.set_query(None);
+    url.set_fragment(None);
+    url
+}
+
+struct WebDavTarget {
+    base: Url,
+    username: String,
+    password: String,
+}
+
+impl WebDavTarget {
+    fn request(&self, method: Method, url: Url) -> reqwest::RequestBuilder {
+        http_client()
+            .request(method, url)
+            .basic_auth(&self.username, Some(&self.password))
+    }
+}
+
+#[async_trait]
+impl BackupTarget for WebDavTarget {
+    async fn upload(&self, name: &str, path: &Path, progress: &Progress) -> anyhow::Result<()> {
+        // 目录已存在时服务器返回 405，忽略 MKCOL 的结果，以 PUT 的结果为准。
+        let _ = self
+            .request(Method::from_bytes(b"MKCOL")?, self.base.clone())
+            .send()
+            .await;
+        let (body, len) = file_body(path, progress.clone()).await?;
+        let resp = self
+            .request(Method::PUT, self.base.join(name)?)
+            .header(CONTENT_LENGTH, len)
+            .body(body)
+            .send()
+            .await?;
+        check_status(resp, "WebDAV upload")?;
+        Ok(())
+    }
+
+    async fn list(&self) -> anyhow::Result<Vec<(String, Option<u64>)>> {
+        let resp = self
+            .request(Method::from_bytes(b"PROPFIND")?, self.base.clone())
+            .header("Depth", "1")
+            .header(CONTENT_TYPE, "application/xml; charset=utf-8")
+            .body(PROPFIND_BODY)
+            .send()
+            .await?;
+        if resp.status() == StatusCode::NOT_FOUND {
+            return Ok(Vec::new());
+        }
+        let xml = check_status(resp, "WebDAV list")?.text().await?;
+        Ok(parse_propfind(&xml))
+    }
+
+    async fn download(&self, name: &str, dest: &Path, progress: &Progress) -> anyhow::Result<()> {
+        let resp = self
+            .request(Method::GET, self.base.join(name)?)
+            .send()
+            .await?;
+        download_to(check_status(resp, "WebDAV download")?, dest, progress).await
+    }
+}
+
+struct S3Target {
+    /// `scheme://host/bucket`。
+    bucket_url: Url,
+    /// 对象 key 前缀（为空或以 `/` 结尾）。
+    prefix: String,
+    region: String,
+    access_key: String,
+    secret_key: String,
+}
+
+/// S3 bucket / 前缀只允许 URL 安全字符，保证 URL 路径即为 SigV4 规范路径。
+fn is_s3_path_segment(segment: &str) -> bool {
+    !segment.is_empty()
+        && segment
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.' | '~'))
+}
+
+/// SigV4 的 URI 编码（只保留 unreserved 字符）。
+fn aws_uri_encode(raw: &str) -> String {
+    let mut out = String::with_capacity(raw.len());
+    for b in raw.bytes() {
+        if b.is_ascii_alphanumeric() || matches!(b, b'-' | b'_' | b'.' | b'~') {
+            out.push(b as char);
+        } else {
+            out.push_str(&format!("%{b:02X}"));
+        }
+    }
+    out
+}
+
+/// SigV4 规范请求。
+fn canonical_request(method: &str, url: &Url, amz_date: &str, payload_hash: &str) -> String {
+    let mut query: Vec<(String, String)> = url
+        .query_pairs()
+        .map(|(k, v)| (aws_uri_encode(&k), aws_uri_encode(&v)))
+        .collect();
+    query.sort();
+    let query = query
+        .iter()
+        .map(|(k, v)| format!("{k}={v}"))
+        .collect::<Vec<_>>()
+        .join("&");
+    let host = match url.port() {
+        Some(port) => format!("{}:{port}", url.host_str().unwrap_or_default()),
+        None => url.host_str().unwrap_or_default().to_string(),
+    };
+    format!(
+        "{method}\n{}\n{query}\nhost:{host}\nx-amz-content-sha256:{payload_hash}\nx-amz-date:{amz_date}\n\n{S3_SIGNED_HEADERS}\n{payload_hash}",
+        url.path()
+    )
+}
+
+impl S3Target {
+    fn object_url(&self, name: &str) -> anyhow::Result<Url> {
+        Ok(self
+            .bucket_url
+            .join(&format!("{}/", self.bucket_url.path()))?
+            .join(&format!("{}{}", self.prefix, aws_uri_encode(name)))?)
+    }
+
+    fn signed(&self, method: Method, url: Url, payload_hash: &str) -> reqwest::RequestBuilder {
+        let now = chrono::Utc::now();
+        let amz_date = now.format("%Y%m%dT%H%M%SZ").to_string();
+        let date = now.format("%Y%m%d").to_string();
+        let scope = format!("{date}/{}/s3/aws4_request", self.region);
+        let request = canonical_request(method.as_str(), &url, &amz_date, payload_hash);
+        let string_to_sign = format!(
+            "AWS4-HMAC-SHA256\n{amz_date}\n{scope}\n{}",
+            sha256_hex(request.as_bytes())
+        );
+        let k_date = hmac_sha256(
+            format!("AWS4{}", self.secret_key).as_bytes(),
+            date.as_bytes(),
+        );
+        let k_region = hmac_sha256(&k_date, self.region.as_bytes());
+        let k_service = hmac_sha256(&k_region, b"s3");
+        let k_signing = hmac_sha256(&k_service, b"aws4_request");
+        let signature = hex::encode(hmac_sha256(&k_signing, string_to_sign.as_bytes()));
+        let authorization = format!(
+            "AWS4-HMAC-SHA256 Credential={}/{scope}, SignedHeaders={S3_SIGNED_HEADERS}, Signature={signature}",
+            self.access_key
+        );
+        http_client()
+            .request(method, url)
+            .header("x-amz-date", amz_date)
+            .header("x-amz-content-sha256", payload_hash)
+            .header(AUTHORIZATION, authorization)
+    }
+}
+
+#[async_trait]
+impl BackupTarget for S3Target {
+    async fn upload(&self, name: &str, path: &Path, progress: &Progress) -> anyhow::Result<()> {
+        let (body, len) = file_body(path, progress.clone()).await?;
+        let resp = self
+            .signed(Method::PUT, self.object_url(name)?, S3_UNSIGNED_PAYLOAD)
+            .header(CONTENT_LENGTH, len)
+            .body(body)
+            .send()
+            .await?;
+        check_status(resp, "S3 upload")?;
+        Ok(())
+    }
+
+    async fn list(&self) -> anyhow::Result<Vec<(String, Option<u64>)>> {
+        let empty_hash = sha256_hex(b"");
+        let mut entries = Vec::new();
+        let mut token: Option<String> = None;
+        loop {
+            let mut url = self.bucket_url.clone();
+            {
+                let mut query = url.query_pairs_mut();
+                query.append_pair("list-type", "2");
+                query.append_pair("prefix", &self.prefix);
+                if let Some(token) = &token {
+                    query.append_pair("continuation-token", token);
+                }
+            }
+            let resp = self.signed(Method::GET, url, &empty_hash).send().await?;
+            let xml = check_status(resp, "S3 list")?.text().await?;
+            let (page, next) = parse_list_objects(&xml, &self.prefix);
+            entries.extend(page);
+            match next {
+                Some(next) => token = Some(next),
+                None => break,
+            }
+        }
+        Ok(entries)
+    }
+
+    async fn download(&self, name: &str, dest: &Path, progress: &Progress) -> anyhow::Result<()> {
+        let resp = self
+            .signed(Method::GET, self.object_url(name)?, &sha256_hex(b""))
+            .send()
+            .await?;
+        download_to(check_status(resp, "S3 download")?, dest, progress).await
+    }
+}
+
+struct XmlRules {
+    dav_response: Regex,
+    dav_href: Regex,
+    dav_length: Regex,
+    s3_contents: Regex,
+    s3_key: Regex,
+    s3_size: Regex,
+    s3_token: Regex,
+}
+
+fn xml_rules() -> Option<&'static XmlRules> {
+    static RULES: OnceLock<Option<XmlRules>> = OnceLock::new();
+    RULES
+        .get_or_init(|| {
+            Some(XmlRules {
+                dav_response: Regex::new(r"(?s)<(?:[\w-]+:)?response\b.*?</(?:[\w-]+:)?response>")
+                    .ok()?,
+                dav_href: Regex::new(r"(?s)<(?:[\w-]+:)?href>\s*(.*?)\s*</(?:[\w-]+:)?href>")
+                    .ok()?,
+                dav_length: Regex::new(r"<(?:[\w-]+:)?getcontentlength>\s*(\d+)\s*<").ok()?,
+                s3_contents: Regex::new(r"(?s)<Contents>.*?</Contents>").ok()?,
+                s3_key: Regex::new(r"<Key>(.*?)</Key>").ok()?,
+                s3_size: Regex::new(r"<Size>(\d+)</Size>").ok()?,
+                s3_token: Regex::new(r"<NextContinuationToken>(.*?)</NextContinuationToken>")
+                    .ok()?,
+            })
+        })
+        .as_ref()
+}
+
+/// 解析 PROPFIND（Depth: 1）响应：取每个 href 的最后一段作为文件名。
+fn parse_propfind(xml: &str) -> Vec<(String, Option<u64>)> {
+    let Some(rules) = xml_rules() else {
+        return Vec::new();
+    };
+    rules
+        .dav_response
+        .find_iter(xml)
+        .filter_map(|block| {
+            let block = block.as_str();
+            let href = rules.dav_href.captures(block)?.get(1)?.as_str();
+            let name = href.trim_end_matches('/').rsplit('/').next()?.to_string();
+            if href.ends_with('/') || name.is_empty() {
+                return None;
+            }
+            let bytes = rules
+                .dav_length
+                .captures(block)
+                .and_then(|c| c.get(1)?.as_str().parse().ok());
+            Some((name, bytes))
+        })
+        .collect()
+}
+
+/// 解析 ListObjectsV2 响应：返回前缀下的直接对象与下一页 token。
+fn parse_list_objects(xml: &str, prefix: &str) -> (Vec<(String, Option<u64>)>, Option<String>) {
+    let Some(rules) = xml_rules() else {
+        return (Vec::new(), None);
+    };
+    let entries = rules
+        .s3_contents
+        .find_iter(xml)
+        .filter_map(|block| {
+            let block = block.as_str();
+            let key = rules.s3_key.captures(block)?.get(1)?.as_str();
+            let name = key.strip_prefix(prefix)?;
+            if name.is_empty() || name.contains('/') {
+                return None;
+            }
+            let bytes = rules
+                .s3_size
+                .captures(block)
+                .and_then(|c| c.get(1)?.as_str().parse().ok());
+            Some((name.to_string(), bytes))
+        })
+        .collect();
+    let token = rules
+        .s3_token
+        .captures(xml)
+        .and_then(|c| Some(c.get(1)?.as_str().replace("&amp;", "&")));
+    (entries, token)
+}
+
+/// 校验并规范化远程目标设置。
+fn normalize_remote(config: &SettingsBackupRemoteV1) -> anyhow::Result<SettingsBackupRemoteV1> {
+    let kind = config.kind.trim().to_ascii_lowercase();
+    let url = Url::parse(config.url.trim()).context("Invalid backup remote URL")?;
+    if !matches!(url.scheme(), "http" | "https") || url.host_str().is_none() {
+        anyhow::bail!("Backup remote URL must be http(s)");
+    }
+    if !url.username().is_empty() || url.password().is_some() {
+        anyhow::bail!("Backup remote URL must not contain credentials");
+    }
+    let username = config.username.trim().to_string();
+    if username.is_empty() {
+        anyhow::bail!("Backup remote username is required");
+    }
+    let region = config
+        .region
+        .as_deref()
+        .map(str::trim)
+        .filter(|r| !r.is_empty())
+        .map(str::to_string);
+    match kind.as_str() {
+        "webdav" => {}
+        "s3" => {
+            let segments: Vec<&str> = url.path().split('/').filter(|s| !s.is_empty()).collect();
+            if segments.is_empty() || !segments.iter().all(|s| is_s3_path_segment(s)) {
+                anyhow::bail!("S3 URL must be path-style: https://host/bucket[/prefix]");
+            }
+        }
+        _ => anyhow::bail!("Unsupported backup remote kind: {kind}"),
+    }
+    Ok(SettingsBackupRemoteV1 {
+        kind,
+        url: directory_url(url).to_string(),
+        username,
+        region,
+    })
+}
+
+fn build_target(
+    config: &SettingsBackupRemoteV1,
+    secret: String,
+) -> anyhow::Result<Box<dyn BackupTarget>> {
+    let config = normalize_remote(config)?;
+    let url = Url::parse(&config.url)?;
+    if config.kind == "webdav" {
+        return Ok(Box::new(WebDavTarget {
+            base: url,
+            username: config.username,
+            password: secret,
+        }));
+    }
+    let segments: Vec<&str> = url.path().split('/').filter(|s| !s.is_empty()).collect();
+    let (bucket, prefix) = segments
+        .split_first()
+        .context("S3 URL is missing the bucket")?;
+    let mut bucket_url = url.clone();
+    bucket_url.set_path(&format!("/{bucket}"));
+    Ok(Box::new(S3Target {
+        bucket_url,
+        prefix: prefix.iter().map(|s| format!("{s}/")).collect(),
+        region: config
+            .region
+            .unwrap_or_else(|| DEFAULT_S3_REGION.to_string()),
+        access_key: config.username,
+        secret_key: secret,
+    }))
+}
+
+fn secret_entry() -> anyhow::Result<Option<Entry>> {
+    match Entry::new(SERVICE, SECRET_ACCOUNT) {
+        Ok(entry) => Ok(Some(entry)),
+        Err(e) if is_missing_secure_storage_error_message(&e.to_string()) => Ok(None),
+        Err(e) => Err(e.into()),
+    }
+}
+
+fn read_secret() -> anyhow::Result<Option<String>> {
+    let Some(entry) = secret_entry()? else {
+        return Ok(None);
+    };
+    match entry.get_password() {
+        Ok(secret) => Ok(Some(secret)),
+        Err(e) if is_missing_secure_storage_error_message(&e.to_string()) => Ok(None),
+        Err(e) => Err(e.into()),
+    }
+}
+
+fn write_secret(secret: &str) -> anyhow::Result<()> {
+    let entry = secret_entry()?
+        .context("secure storage is unavailable, cannot persist backup remote credentials")?;
+    entry.set_password(secret)?;
+    Ok(())
+}
+
+pub(crate) fn delete_secret() -> anyhow::Result<()> {
+    let Some(entry) = secret_entry()? else {
+        return Ok(());
+    };
+    match entry.delete_credential() {
+        Ok(()) => Ok(()),
+        Err(e) if is_missing_secure_storage_error_message(&e.to_string()) => Ok(()),
+        Err(e) => Err(e.into()),
+    }
+}
+
+async fn load_target() -> CommandResult<Box<dyn BackupTarget>> {
+    let not_configured = || {
+        command_error(
+            "DB_BACKUP_REMOTE_NOT_CONFIGURED",
+            "error.db_backup_remote_not_configured",
+        )
+    };
+    let config = config_store::get_backup_remote()
+        .await
+        .ok_or_else(not_configured)?;
+    let secret = tauri::async_runtime::spawn_blocking(read_secret)
+        .await
+        .map_err(|e| {
+            to_command_error(
+                "DB_BACKUP_REMOTE_INVALID",
+                "error.db_backup_remote_invalid",
+                e.into(),
+            )
+        })?
+        .map_err(|e| {
+            to_command_error(
+                "DB_BACKUP_REMOTE_INVALID",
+                "error.db_backup_remote_invalid",
+                e,
+            )
+        })?
+        .ok_or_else(not_configured)?;
+    build_target(&config, secret).map_err(|e| {
+        to_command_error(
+            "DB_BACKUP_REMOTE_INVALID",
+            "error.db_backup_remote_invalid",
+            e,
+        )
+    })
+}
+
+fn remote_name(id: &str) -> String {
+    format!("{id}{REMOTE_SUFFIX}")
+}
+
+fn backup_id_from_remote_name(name: &str) -> Option<String> {
+    let id = name.strip_suffix(REMOTE_SUFFIX)?;
+    is_valid_backup_id(id).then(|| id.to_string())
+}
+
+fn check_passphrase(passphrase: &str) -> CommandResult<()> {
+    if passphrase.chars().count() < MIN_PASSPHRASE_CHARS {
+        return Err(command_error(
+            "DB_BACKUP_PASSPHRASE_TOO_SHORT",
+            "error.db_backup_passphrase_too_short",
+        ));
+    }
+    Ok(())
+}
+
+/// 解包时只接受清单与 `<key>.db` 文件（不含目录层级）。
+fn is_package_entry(name: &str) -> bool {
+    name == MANIFEST_FILE
+        || name.strip_suffix(".db").is_some_and(|key| {
+            !key.is_empty() && key.chars().all(|c| c.is_ascii_alphanumeric() || c == '_')
+        })
+}
+
+/// 打包本地备份目录并加密为 `dest`。
+fn pack_and_encrypt(dir: &Path, dest: &Path, passphrase: &str) -> anyhow::Result<()> {
+    let manifest = read_manifest(dir)?;
+    let zip_path = dest.with_extension("zip");
+    let result = (|| -> anyhow::Result<()> {
+        let file = std::fs::File::create(&zip_path)
+            .with_context(|| format!("Failed to create package: {}", zip_path.display()))?;
+        let mut zip = zip::ZipWriter::new(BufWriter::new(file));
+        let options = SimpleFileOptions::default()
+            .compression_method(zip::CompressionMethod::Deflated)
+            .large_file(true);
+        let names = std::iter::once(MANIFEST_FILE.to_string())
+            .chain(manifest.databases.iter().map(|db| db.file.clone()));
+        for name in names {
+            zip.start_file(name.as_str(), options)
+                .with_context(|| format!("Failed to start package entry: {name}"))?;
+            let mut source = std::fs::File::open(dir.join(&name))
+                .with_context(|| format!("Failed to open backup file: {name}"))?;
+            std::io::copy(&mut source, &mut zip)
+                .with_context(|| format!("Failed to write package entry: {name}"))?;
+        }
+        zip.finish().context("Failed to finish backup package")?;
+
+        let mut reader = BufReader::new(std::fs::File::open(&zip_path)?);
+        let mut writer = BufWriter::new(std::fs::File::create(dest)?);
+        encrypt_stream(&mut reader, &mut writer, passphrase)
+    })();
+    let _ = std::fs::remove_file(&zip_path);
+    result
+}
+
+/// 解密 `src` 并解包为 `root/<id>`（替换同 id 的本地副本）。
+fn decrypt_and_unpack(src: &Path, root: &Path, id: &str, passphrase: &str) -> anyhow::Result<()> {
+    let zip_path = src.with_extension("zip");
+    let tmp = root.join(format!("{id}.tmp"));
+    let result = (|| -> anyhow::Result<()> {
+        {
+            let mut reader = BufReader::new(std::fs::File::open(src)?);
+            let mut writer = BufWriter::new(std::fs::File::create(&zip_path)?);
+            decrypt_stream(&mut reader, &mut writer, passphrase)?;
+        }
+        let mut zip = ZipArchive::new(std::fs::File::open(&zip_path)?)
+            .context("Backup package is not a valid zip file")?;
+        if tmp.exists() {
+            std::fs::remove_dir_all(&tmp)?;
+        }
+        std::fs::create_dir_all(&tmp)?;
+        for i in 0..zip.len() {
+            let entry = zip.by_index(i)?;
+            let name = entry.name().to_string();
+            if !is_package_entry(&name) {
+                anyhow::bail!("Unexpected entry in backup package: {name}");
+            }
+            let mut out = std::fs::File::create(tmp.join(&name))?;
+            let copied = std::io::copy(&mut entry.take(MAX_ENTRY_BYTES + 1), &mut out)?;
+            if copied > MAX_ENTRY_BYTES {
+                anyhow::bail!("Backup package entry too large: {name}");
+            }
+        }
+        let manifest = read_manifest(&tmp)?;
+        if manifest.id != id {
+            anyhow::bail!("Backup package id mismatch: {}", manifest.id);
+        }
+        let dir = root.join(id);
+        if dir.exists() {
+            std::fs::remove_dir_all(&dir)?;
+        }
+        std::fs::rename(&tmp, &dir)?;
+        Ok(())
+    })();
+    let _ = std::fs::remove_file(&zip_path);
+    if result.is_err() {
+        let _ = std::fs::remove_dir_all(&tmp);
+    }
+    result
+}
+
+#[tauri::command]
+/// 读取远程备份目标设置（不返回密码 / secret key）。
+pub async fn get_backup_remote() -> CommandResult<Option<BackupRemoteStatus>> {
+    let Some(config) = config_store::get_backup_remote().await else {
+        return Ok(None);
+    };
+    let has_secret = tauri::async_runtime::spawn_blocking(read_secret)
+        .await
+        .ok()
+        .and_then(Result::ok)
+        .flatten()
+        .is_some();
+    Ok(Some(BackupRemoteStatus { config, has_secret }))
+}
+
+#[tauri::command]
+/// 设置远程备份目标。
+///
+/// # 参数
+/// - `config`：目标设置；为空表示停用并删除已保存的凭据。
+/// - `secret`：WebDAV 密码或 S3 secret key；为空表示保留已保存的值。
+///
+/// # 返回值
+/// - `Ok(())`：已保存。
+/// - `Err(CommandError)`：设置无效或凭据存储不可用。
+pub async fn set_backup_remote(
+    config: Option<SettingsBackupRemoteV1>,
+    secret: Option<String>,
+) -> CommandResult<()> {
+    let map_err = |e: anyhow::Error| {
+        to_command_error(
+            "DB_BACKUP_REMOTE_SAVE_FAILED",
+            "error.db_backup_remote_save_failed",
+            e,
+        )
+    };
+    let Some(config) = config else {
+        config_store::update_backup_remote(None)
+            .await
+            .map_err(map_err)?;
+        tauri::async_runtime::spawn_blocking(delete_secret)
+            .await
+            .map_err(|e| map_err(e.into()))?
+            .map_err(map_err)?;
+        return Ok(());
+    };
+    let config = normalize_remote(&config).map_err(|e| {
+        to_command_error(
+            "DB_BACKUP_REMOTE_INVALID",
+            "error.db_backup_remote_invalid",
+            e,
+        )
+    })?;
+    if let Some(secret) = secret.filter(|s| !s.is_empty()) {
+        tauri::async_runtime::spawn_blocking(move || write_secret(&secret))
+            .await
+            .map_err(|e| map_err(e.into()))?
+            .map_err(map_err)?;
+    }
+    config_store::update_backup_remote(Some(config))
+        .await
+        .map_err(map_err)
+}
+
+#[tauri::command]
+/// 加密并上传本地备份。
+///
+/// # 参数
+/// - `id`：本地备份 id（见 `list_backups`）。
+/// - `passphrase`：加密口令（至少 8 个字符，不会保存；恢复时需要同一口令）。
+///
+/// # 返回值
+/// - `Ok(RemoteBackupEntry)`：远程文件信息。
+/// - `Err(CommandError)`：备份不存在、目标未配置或上传失败。
+pub async fn upload_backup(
+    app: AppHandle,
+    id: String,
+    passphrase: String,
+) -> CommandResult<RemoteBackupEntry> {
+    let id = id.trim().to_string();
+    check_passphrase(&passphrase)?;
+    let map_err = |e: anyhow::Error| {
+        to_command_error(
+            "DB_BACKUP_UPLOAD_FAILED",
+            "error.db_backup_upload_failed",
+            e,
+        )
+    };
+    let root = backup_root().map_err(map_err)?;
+    let dir = root.join(&id);
+    if !is_valid_backup_id(&id) || !dir.is_dir() {
+        return Err(command_error(
+            "DB_BACKUP_NOT_FOUND",
+            "error.db_backup_not_found",
+        ));
+    }
+    let target = load_target().await?;
+    let name = remote_name(&id);
+    let package = root.join(format!(".upload-{name}"));
+    {
+        // 打包期间持有备份锁，避免轮换删除正在读取的目录。
+        let _guard = backup_lock().lock().await;
+        let package = package.clone();
+        tauri::async_runtime::spawn_blocking(move || pack_and_encrypt(&dir, &package, &passphrase))
+            .await
+            .map_err(|e| map_err(e.into()))?
+            .map_err(map_err)?;
+    }
+    let bytes = tokio::fs::metadata(&package)
+        .await
+        .map(|m| m.len())
+        .unwrap_or_default();
+    let progress =
+        Progress::new(Arc::new(app), &id, "upload", Some(bytes)).with_taskbar("backup_upload");
+    let result = target.upload(&name, &package, &progress).await;
+    let _ = tokio::fs::remove_file(&package).await;
+    result.map_err(map_err)?;
+    progress.finish();
+    tracing::info!(action = "db_backup_uploaded", id = %id, bytes);
+    Ok(RemoteBackupEntry {
+        name,
+        id,
+        bytes: Some(bytes),
+    })
+}
+
+#[tauri::command]
+/// 列出远程备份（最新在前）。
+pub async fn list_remote_backups() -> CommandResult<Vec<RemoteBackupEntry>> {
+    let target = load_target().await?;
+    let files = target.list().await.map_err(|e| {
+        to_command_error(
+            "DB_BACKUP_REMOTE_LIST_FAILED",
+            "error.db_backup_remote_list_failed",
+            e,
+        )
+    })?;
+    let mut entries: Vec<RemoteBackupEntry> = files
+        .into_iter()
+        .filter_map(|(name, bytes)| {
+            Some(RemoteBackupEntry {
+                id: backup_id_from_remote_name(&name)?,
+                name,
+                bytes,
+            })
+        })
+        .collect();
+    entries.sort_by(|a, b| b.id.cmp(&a.id));
+    Ok(entries)
+}
+
+#[tauri::command]
+/// 下载、解密并恢复远程备份（覆盖当前数据）。
+///
+/// # 参数
+/// - `name`：远程文件名（见 `list_remote_backups`）。
+/// - `passphrase`：上传时使用的口令。
+///
+/// # 返回值
+/// - `Ok(RestoreBackupReport)`：同 `restore_backup`。
+/// - `Err(CommandError)`：下载失败、口令错误（`DB_BACKUP_DECRYPT_FAILED`）或恢复失败。
+pub async fn restore_remote_backup(
+    app: AppHandle,
+    name: String,
+    passphrase: String,
+) -> CommandResult<RestoreBackupReport> {
+    let name = name.trim().to_string();
+    check_passphrase(&passphrase)?;
+    let Some(id) = backup_id_from_remote_name(&name) else {
+        return Err(command_error(
+            "DB_BACKUP_NOT_FOUND",
+            "error.db_backup_not_found",
+        ));
+    };
+    let map_err = |e: anyhow::Error| {
+        if e.downcast_ref::<BackupDecryptError>().is_some() {
+            to_command_error(
+                "DB_BACKUP_DECRYPT_FAILED",
+                "error.db_backup_decrypt_failed",
+                e,
+            )
+        } else {
+            to_command_error(
+                "DB_BACKUP_DOWNLOAD_FAILED",
+                "error.db_backup_download_failed",
+                e,
+            )
+        }
+    };
+    let target = load_target().await?;
+    let root = backup_root().map_err(map_err)?;
+    tokio::fs::create_dir_all(&root)
+        .await
+        .map_err(|e| map_err(e.into()))?;
+    let download = root.join(format!(".download-{name}"));
+    let progress =
+        Progress::new(Arc::new(app), &id, "download", None).with_taskbar("backup_download");
+    let result = match target.download(&name, &download, &progress).await {
+        Ok(()) => {
+            let _guard = backup_lock().lock().await;
+            let (download, root, id) = (download.clone(), root.clone(), id.clone());
+            tauri::async_runtime::spawn_blocking(move || {
+                decrypt_and_unpack(&download, &root, &id, &passphrase)
+            })
+            .await
+            .map_err(anyhow::Error::from)
+            .and_then(|r| r)
+        }
+        Err(e) => Err(e),
+    };
+    let _ = tokio::fs::remove_file(&download).await;
+    result.map_err(map_err)?;
+    progress.finish();
+    tracing::info!(action = "db_backup_downloaded", id = %id);
+    restore_backup(id).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn remote(kind: &str, url: &str) -> SettingsBackupRemoteV1 {
+        SettingsBackupRemoteV1 {
+            kind: kind.to_string(),
+            url: url.to_string(),
+            username: "AKID".to_string(),
+            region: None,
+        }
+    }
+
+    #[test]
+    fn s3_canonical_request_is_sorted_and_encoded() {
+        let url =
+            Url::parse("https://s3.example.com:9000/bucket?prefix=cp%2Fbackups%2F&list-type=2")
+                .unwrap();
+        assert_eq!(
+            canonical_request("GET", &url, "20260101T000000Z", "e3b0"),
+            "GET\n/bucket\nlist-type=2&prefix=cp%2Fbackups%2F\n\
+             host:s3.example.com:9000\nx-amz-content-sha256:e3b0\nx-amz-date:20260101T000000Z\n\n\
+             host;x-amz-content-sha256;x-amz-date\ne3b0"
+        );
+        assert_eq!(aws_uri_encode("a b/c~"), "a%20b%2Fc~");
+    }
+
+    #[test]
+    fn listings_are_parsed_for_both_targets() {
+        let dav = r#"<?xml version="1.0"?>
+<d:multistatus xmlns:d="DAV:">
+  <d:response><d:href>/dav/backups/</d:href><d:propstat><d:prop/></d:propstat></d:response>
+  <d:response><d:href>/dav/backups/20260101-030000.cpbk</d:href>
+    <d:propstat><d:prop><d:getcontentlength>1234</d:getcontentlength></d:prop></d:propstat>
+  </d:response>
+</d:multistatus>"#;
+        assert_eq!(
+            parse_propfind(dav),
+            vec![("20260101-030000.cpbk".to_string(), Some(1234))]
+        );
+
+        let s3 = "<ListBucketResult><IsTruncated>true</IsTruncated>\
+            <Contents><Key>cp/20260102-030000.cpbk</Key><Size>99</Size></Contents>\
+            <Contents><Key>cp/nested/x.cpbk</Key><Size>1</Size></Contents>\
+            <NextContinuationToken>abc&amp;def</NextContinuationToken></ListBucketResult>";
+        let (entries, token) = parse_list_objects(s3, "cp/");
+        assert_eq!(
+            entries,
+            vec![("20260102-030000.cpbk".to_string(), Some(99))]
+        );
+        assert_eq!(token.as_deref(), Some("abc&def"));
+    }
+
+    #[test]
+    fn remote_settings_are_validated() {
+        let webdav =
+            normalize_remote(&remote(" WebDAV ", "https://dav.example.com/backups")).unwrap();
+        assert_eq!(webdav.kind, "webdav");
+        assert_eq!(webdav.url, "https://dav.example.com/backups/");
+        assert!(normalize_remote(&remote("s3", "https://s3.example.com/")).is_err());
+        assert!(normalize_remote(&remote("s3", "https://s3.example.com/my%20bucket")).is_err());
+        assert!(normalize_remote(&remote("webdav", "https://u:p@dav.example.com/")).is_err());
+        assert!(normalize_remote(&remote("ftp", "https://dav.example.com/")).is_err());
+        assert_eq!(
+            backup_id_from_remote_name("20260101-030000.cpbk").as_deref(),
+            Some("20260101-030000")
+        );
+        assert!(backup_id_from_remote_name("../x.cpbk").is_none());
+        assert!(is_package_entry("server_ab12.db"));
+        assert!(!is_package_entry("../system.db"));
+    }
+
+    #[test]
+    fn progress_is_throttled_but_always_reports_completion() {
+        let sink = crate::shared::events::RecordingEventBus::new();
+        let progress = Progress::new(sink.clone(), "b1", "upload", Some(3 * PROGRESS_STEP));
+        progress.report(10);
+        progress.report(PROGRESS_STEP + 10);
+        progress.report(PROGRESS_STEP + 20);
+        progress.report(3 * PROGRESS_STEP);
+        let reported: Vec<u64> = sink
+            .payloads(PROGRESS_EVENT)
+            .iter()
+            .map(|p| p["transferred"].as_u64().unwrap())
+            .collect();
+        assert_eq!(reported, vec![PROGRESS_STEP + 10, 3 * PROGRESS_STEP]);
+    }
+}
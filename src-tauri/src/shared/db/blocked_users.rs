@@ -0,0 +1,230 @@
+//! shared｜数据库：屏蔽用户（server DB `blocked_users` 表）与过滤钩子。
+//!
+//! 说明：
+//! - 屏蔽列表按 server DB 存储（由 server 迁移 v4 创建）；读取消息的查询以
+//!   `user_id NOT IN (SELECT user_id FROM blocked_users)` 排除被屏蔽用户；
+//! - 通知与未读计数由前端按 `list_blocked_users` 的结果过滤；
+//! - 服务端同步是可选的：传入 `sync` 时经 API 层调用 `/api/users/{id}/block`，
+//!   同步失败不回滚本地屏蔽，仅在结果中标记 `synced = false`。
+//!
+//! 约定：注释中文，日志英文（tracing）。
+
+use std::collections::BTreeMap;
+
+use sea_orm::{ConnectionTrait, Value};
+use serde::{Deserialize, Serialize};
+
+use crate::features::network::data::http_client::ReqwestApiRequestAdapter;
+use crate::features::network::usecases::api_usecases::{self, ApiJsonRequest};
+use crate::shared::error::{CommandResult, command_error, to_command_error};
+
+use super::commands::{RawStatement, is_server_db_key};
+use super::get_db;
+
+/// 被屏蔽用户。
+#[derive(Debug, Clone, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct BlockedUser {
+    pub user_id: i64,
+    /// 本地缓存的用户名（未缓存时为空）。
+    pub name: Option<String>,
+    pub blocked_at: i64,
+    /// 是否已同步到服务端。
+    pub synced: bool,
+}
+
+/// 服务端同步参数（与 `api_request_json` 的连接参数一致）。
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BlockSyncOptions {
+    pub server_socket: String,
+    /// 请求头（通常包含鉴权 token）。
+    pub headers: Option<BTreeMap<String, String>>,
+    pub tls_policy: Option<String>,
+    pub tls_fingerprint: Option<String>,
+}
+
+fn now_ms() -> i64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_millis() as i64
+}
+
+fn validate_db_key(db_key: &str) -> CommandResult<()> {
+    if is_server_db_key(db_key) {
+        Ok(())
+    } else {
+        Err(command_error("DB_KEY_INVALID", "error.db_key_invalid"))
+    }
+}
+
+/// 调用服务端屏蔽/解除屏蔽接口；返回是否成功。
+async fn sync_to_server(sync: BlockSyncOptions, method: &str, user_id: i64) -> bool {
+    let api_request_port = ReqwestApiRequestAdapter::shared();
+    let result = api_usecases::api_request_json(
+        ApiJsonRequest {
+            server_socket: sync.server_socket,
+            method: method.to_string(),
+            path: format!("/api/users/{user_id}/block"),
+            headers: sync.headers,
+            body: None,
+            tls_policy: sync.tls_policy,
+            tls_fingerprint: sync.tls_fingerprint,
+        },
+        api_request_port.as_ref(),
+    )
+    .await;
+    match result {
+        Ok(response) if response.ok => true,
+        Ok(response) => {
+            tracing::warn!(
+                action = "db_blocked_users_sync_rejected",
+                method,
+                status = response.status
+            );
+            false
+        }
+        Err(e) => {
+            tracing::warn!(action = "db_blocked_users_sync_failed", method, error = %e);
+            false
+        }
+    }
+}
+
+async fn set_synced(db_key: &str, user_id: i64) -> anyhow::Result<()> {
+    let db = get_db(db_key).await?;
+    db.connection
+        .execute(&RawStatement::new(
+            "UPDATE blocked_users SET synced = 1 WHERE user_id = ?".to_string(),
+            vec![Value::from(user_id)],
+        ))
+        .await?;
+    Ok(())
+}
+
+#[tauri::command]
+/// 屏蔽用户：其消息不再出现在查询结果、通知与未读计数中。
+///
+/// # 参数
+/// - `db_key`：server DB key（`server_<sha256>`），必须已打开。
+/// - `user_id`：要屏蔽的用户 id。
+/// - `sync`：可选的服务端同步参数；为空时只在本地生效。
+///
+/// # 返回值
+/// - `Ok(BlockedUser)`：屏蔽记录（重复屏蔽保留最初的屏蔽时间）。
+/// - `Err(CommandError)`：key 非法或写入失败。
+pub async fn block_user(
+    db_key: String,
+    user_id: i64,
+    sync: Option<BlockSyncOptions>,
+) -> CommandResult<BlockedUser> {
+    validate_db_key(&db_key)?;
+    let map_err = |e: anyhow::Error| {
+        to_command_error("DB_BLOCK_USER_FAILED", "error.db_block_user_failed", e)
+    };
+    let db = get_db(&db_key).await.map_err(map_err)?;
+    db.connection
+        .execute(&RawStatement::new(
+            "INSERT OR IGNORE INTO blocked_users (user_id, blocked_at, synced) VALUES (?, ?, 0)"
+                .to_string(),
+            vec![Value::from(user_id), Value::from(now_ms())],
+        ))
+        .await
+        .map_err(|e| to_command_error("DB_BLOCK_USER_FAILED", "error.db_block_user_failed", e))?;
+    super::quick_switch::invalidate(&db_key);
+    tracing::info!(action = "db_user_blocked", user_id);
+
+    if let Some(sync) = sync
+        && sync_to_server(sync, "POST", user_id).await
+        && let Err(e) = set_synced(&db_key, user_id).await
+    {
+        tracing::warn!(action = "db_blocked_users_mark_synced_failed", error = %e);
+    }
+
+    load_blocked(&db_key)
+        .await
+        .map_err(map_err)?
+        .into_iter()
+        .find(|u| u.user_id == user_id)
+        .ok_or_else(|| command_error("DB_BLOCK_USER_FAILED", "error.db_block_user_failed"))
+}
+
+#[tauri::command]
+/// 解除屏蔽。
+///
+/// # 参数
+/// - `db_key`：server DB key。
+/// - `user_id`：用户 id。
+/// - `sync`：可选的服务端同步参数。
+///
+/// # 返回值
+/// - `Ok(bool)`：本地是否存在该屏蔽记录。
+pub async fn unblock_user(
+    db_key: String,
+    user_id: i64,
+    sync: Option<BlockSyncOptions>,
+) -> CommandResult<bool> {
+    validate_db_key(&db_key)?;
+    let db = get_db(&db_key).await.map_err(|e| {
+        to_command_error("DB_UNBLOCK_USER_FAILED", "error.db_unblock_user_failed", e)
+    })?;
+    let result = db
+        .connection
+        .execute(&RawStatement::new(
+            "DELETE FROM blocked_users WHERE user_id = ?".to_string(),
+            vec![Value::from(user_id)],
+        ))
+        .await
+        .map_err(|e| {
+            to_command_error("DB_UNBLOCK_USER_FAILED", "error.db_unblock_user_failed", e)
+        })?;
+    super::quick_switch::invalidate(&db_key);
+    tracing::info!(action = "db_user_unblocked", user_id);
+
+    if let Some(sync) = sync {
+        sync_to_server(sync, "DELETE", user_id).await;
+    }
+    Ok(result.rows_affected() > 0)
+}
+
+async fn load_blocked(db_key: &str) -> anyhow::Result<Vec<BlockedUser>> {
+    let db = get_db(db_key).await?;
+    let rows = db
+        .connection
+        .query_all(&RawStatement::new(
+            r#"
+            SELECT b.user_id AS user_id, u.name AS name, b.blocked_at AS blocked_at, b.synced AS synced
+            FROM blocked_users b
+            LEFT JOIN users u ON u.id = b.user_id
+            ORDER BY b.blocked_at DESC
+            "#
+            .to_string(),
+            Vec::new(),
+        ))
+        .await?;
+    Ok(rows
+        .iter()
+        .filter_map(|row| {
+            Some(BlockedUser {
+                user_id: row.try_get("", "user_id").ok()?,
+                name: row.try_get::<Option<String>>("", "name").ok().flatten(),
+                blocked_at: row.try_get("", "blocked_at").ok()?,
+                synced: row.try_get::<i64>("", "synced").ok()? != 0,
+            })
+        })
+        .collect())
+}
+
+#[tauri::command]
+/// 列出屏蔽用户（最近屏蔽在前）。
+pub async fn list_blocked_users(db_key: String) -> CommandResult<Vec<BlockedUser>> {
+    validate_db_key(&db_key)?;
+    load_blocked(&db_key).await.map_err(|e| {
+        to_command_error(
+            "DB_LIST_BLOCKED_USERS_FAILED",
+            "error.db_list_blocked_users_failed",
+            e,
+        )
+    })
+}
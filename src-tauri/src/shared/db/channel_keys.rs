@@ -0,0 +1,456 @@
+//! shared｜数据库：频道加密密钥库（system DB `channel_keys` 表）。
+//!
+//! 说明：
+//! - 服务端轮换频道密钥后，旧消息仍以旧密钥加密；本地按 `(server_socket, key_id)` 保存
+//!   历史上收到的全部密钥，解密时按消息携带的 `key_id` 查找；
+//! - 密钥以包装形式落盘：AES-256-GCM，AAD 为 `server_socket\nkey_id`，包装密钥（KEK）
+//!   由 PBKDF2-HMAC-SHA256 派生：设置了应用锁口令时派生自口令，否则派生自凭据存储中的
+//!   设备随机密钥；
+//! - 修改/清除应用锁口令时在同一事务内重新包装全部密钥（见 `set_app_lock_passphrase`）；
+//! - 启用应用锁的进程启动后，需以口令解锁一次才能派生 KEK（生物识别解锁不提供口令），
+//!   在此之前解密返回 `CHANNEL_KEY_STORE_LOCKED`；
+//! - 消息引用本地不存在的 `key_id` 时广播 `channel-key-missing`，前端据此提示重新同步密钥。
+//!
+//! 约定：注释中文，日志英文（tracing）。
+
+use std::sync::Mutex;
+
+use aes_gcm::aead::{Aead, KeyInit, Payload};
+use aes_gcm::{Aes256Gcm, Nonce};
+use base64::Engine;
+use base64::engine::general_purpose::STANDARD as BASE64;
+use sea_orm::{ConnectionTrait, TransactionTrait, Value};
+use serde::Serialize;
+use tauri::AppHandle;
+
+use crate::shared::error::{CommandResult, command_error, to_command_error};
+use crate::shared::events::{EventBus, EventBusExt};
+use crate::shared::secrets;
+
+use super::backup_crypto::pbkdf2_sha256;
+use super::commands::RawStatement;
+use super::get_db;
+
+/// 未知密钥事件名。
+pub const CHANNEL_KEY_MISSING_EVENT: &str = "channel-key-missing";
+
+/// 密钥库所在的 DB key（应用级 system DB）。
+const SYSTEM_DB_KEY: &str = "system";
+pub(crate) const DEVICE_SECRET_ACCOUNT: &str = "channel-key-device-secret";
+pub(crate) const SALT_ACCOUNT: &str = "channel-key-salt";
+
+/// KEK 的 PBKDF2 迭代次数（测试环境降低以免拖慢用例）。
+#[cfg(not(test))]
+const KEK_ITERATIONS: u32 = 600_000;
+#[cfg(test)]
+const KEK_ITERATIONS: u32 = 1_000;
+
+const KEY_LEN: usize = 32;
+const NONCE_LEN: usize = 12;
+
+/// 已派生的 KEK（进程内缓存）。
+static KEK: Mutex<Option<[u8; KEY_LEN]>> = Mutex::new(None);
+
+/// `channel-key-missing` 事件载荷。
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ChannelKeyMissingPayload {
+    pub server_socket: String,
+    pub channel_id: i64,
+    pub key_id: String,
+}
+
+/// 应用锁已启用但尚未以口令解锁，无法派生 KEK。
+#[derive(Debug)]
+pub struct KeyStoreLocked;
+
+impl std::fmt::Display for KeyStoreLocked {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "channel key store is locked until the app lock passphrase is entered"
+        )
+    }
+}
+
+impl std::error::Error for KeyStoreLocked {}
+
+fn now_ms() -> i64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_millis() as i64
+}
+
+fn random_bytes<const N: usize>() -> anyhow::Result<[u8; N]> {
+    let mut out = [0u8; N];
+    getrandom::fill(&mut out).map_err(|_| anyhow::anyhow!("Failed to generate random bytes"))?;
+    Ok(out)
+}
+
+/// 读取凭据存储中的机密（hex），不存在时生成并保存（阻塞调用）。
+fn load_or_create_secret(account: &str) -> anyhow::Result<Vec<u8>> {
+    if let Some(existing) = secrets::get(account)?
+        && let Ok(bytes) = hex::decode(existing.trim())
+        && !bytes.is_empty()
+    {
+        return Ok(bytes);
+    }
+    let bytes = random_bytes::<KEY_LEN>()?;
+    secrets::set(account, &hex::encode(bytes))?;
+    Ok(bytes.to_vec())
+}
+
+/// 由口令（`None` 表示使用设备密钥）派生 KEK（阻塞调用）。
+fn derive_kek(passphrase: Option<&str>) -> anyhow::Result<[u8; KEY_LEN]> {
+    let salt = load_or_create_secret(SALT_ACCOUNT)?;
+    let secret = match passphrase {
+        Some(passphrase) => passphrase.as_bytes().to_vec(),
+        None => load_or_create_secret(DEVICE_SECRET_ACCOUNT)?,
+    };
+    Ok(pbkdf2_sha256(&secret, &salt, KEK_ITERATIONS))
+}
+
+fn cache_kek(kek: [u8; KEY_LEN]) {
+    *KEK.lock().unwrap_or_else(|p| p.into_inner()) = Some(kek);
+}
+
+/// 当前 KEK（阻塞调用）：未缓存且未启用应用锁时由设备密钥派生。
+fn current_kek() -> anyhow::Result<[u8; KEY_LEN]> {
+    if let Some(kek) = *KEK.lock().unwrap_or_else(|p| p.into_inner()) {
+        return Ok(kek);
+    }
+    if crate::shared::app_lock::load_verifier()?.is_some() {
+        return Err(KeyStoreLocked.into());
+    }
+    let kek = derive_kek(None)?;
+    cache_kek(kek);
+    Ok(kek)
+}
+
+async fn blocking<T, F>(f: F) -> anyhow::Result<T>
+where
+    T: Send + 'static,
+    F: FnOnce() -> anyhow::Result<T> + Send + 'static,
+{
+    tauri::async_runtime::spawn_blocking(f).await?
+}
+
+fn aad(server_socket: &str, key_id: &str) -> Vec<u8> {
+    format!("{server_socket}\n{key_id}").into_bytes()
+}
+
+/// 以 KEK 包装频道密钥，返回 `nonce || 密文` 的 base64。
+fn wrap_key(kek: &[u8; KEY_LEN], aad: &[u8], key: &[u8]) -> anyhow::Result<String> {
+    let cipher = Aes256Gcm::new_from_slice(kek)?;
+    let nonce = random_bytes::<NONCE_LEN>()?;
+    let sealed = cipher
+        .encrypt(Nonce::from_slice(&nonce), Payload { msg: key, aad })
+        .map_err(|_| anyhow::anyhow!("Failed to wrap channel key"))?;
+    let mut out = nonce.to_vec();
+    out.extend_from_slice(&sealed);
+    Ok(BASE64.encode(out))
+}
+
+/// 解包频道密钥。
+fn unwrap_key(kek: &[u8; KEY_LEN], aad: &[u8], wrapped: &str) -> anyhow::Result<Vec<u8>> {
+    let raw = BASE64.decode(wrapped.trim())?;
+    if raw.len() <= NONCE_LEN {
+        return Err(anyhow::anyhow!("Wrapped channel key is truncated"));
+    }
+    let (nonce, sealed) = raw.split_at(NONCE_LEN);
+    Aes256Gcm::new_from_slice(kek)?
+        .decrypt(Nonce::from_slice(nonce), Payload { msg: sealed, aad })
+        .map_err(|_| anyhow::anyhow!("Failed to unwrap channel key"))
+}
+
+/// 以频道密钥解密消息（`nonce(12) || 密文+tag`）。
+fn decrypt_with_key(key: &[u8], payload: &[u8]) -> anyhow::Result<String> {
+    if key.len() != KEY_LEN || payload.len() <= NONCE_LEN {
+        return Err(anyhow::anyhow!("Invalid channel key or ciphertext length"));
+    }
+    let (nonce, sealed) = payload.split_at(NONCE_LEN);
+    let plain = Aes256Gcm::new_from_slice(key)?
+        .decrypt(Nonce::from_slice(nonce), sealed)
+        .map_err(|_| anyhow::anyhow!("Failed to decrypt channel message"))?;
+    Ok(String::from_utf8(plain)?)
+}
+
+/// 以口令派生并缓存 KEK（应用锁口令解锁成功后调用）。
+pub(crate) async fn unlock_with_passphrase(passphrase: String) -> anyhow::Result<()> {
+    let kek = blocking(move || derive_kek(Some(&passphrase))).await?;
+    cache_kek(kek);
+    Ok(())
+}
+
+/// 将全部密钥从旧口令派生的 KEK 重新包装到新口令（`None` 表示设备密钥）。
+///
+/// # 说明
+/// 在单个事务内完成；任一密钥解包失败则整体回滚，口令不应被更新。
+pub(crate) async fn rewrap(current: Option<String>, next: Option<String>) -> anyhow::Result<()> {
+    let db = get_db(SYSTEM_DB_KEY).await?;
+    let rows = db
+        .connection
+        .query_all(&RawStatement::new(
+            "SELECT server_socket, key_id, wrapped_key FROM channel_keys".to_string(),
+            Vec::new(),
+        ))
+        .await?;
+    if rows.is_empty() {
+        // 尚无密钥：清除口令时不必触碰凭据存储，待首次使用时再由设备密钥派生。
+        match next {
+            Some(next) => unlock_with_passphrase(next).await?,
+            None => *KEK.lock().unwrap_or_else(|p| p.into_inner()) = None,
+        }
+        return Ok(());
+    }
+    let (old_kek, new_kek) = blocking(move || {
+        Ok((
+            derive_kek(current.as_deref())?,
+            derive_kek(next.as_deref())?,
+        ))
+    })
+    .await?;
+    let txn = db.connection.begin().await?;
+    for row in &rows {
+        let server_socket: String = row.try_get("", "server_socket")?;
+        let key_id: String = row.try_get("", "key_id")?;
+        let wrapped: String = row.try_get("", "wrapped_key")?;
+        let binding = aad(&server_socket, &key_id);
+        let key = unwrap_key(&old_kek, &binding, &wrapped)?;
+        txn.execute(&RawStatement::new(
+            "UPDATE channel_keys SET wrapped_key = ? WHERE server_socket = ? AND key_id = ?"
+                .to_string(),
+            vec![
+                Value::from(wrap_key(&new_kek, &binding, &key)?),
+                Value::from(server_socket),
+                Value::from(key_id),
+            ],
+        ))
+        .await?;
+    }
+    txn.commit().await?;
+    cache_kek(new_kek);
+    tracing::info!(action = "db_channel_keys_rewrapped", keys = rows.len());
+    Ok(())
+}
+
+async fn load_wrapped(server_socket: &str, key_id: &str) -> anyhow::Result<Option<String>> {
+    let db = get_db(SYSTEM_DB_KEY).await?;
+    let row = db
+        .connection
+        .query_one(&RawStatement::new(
+            "SELECT wrapped_key FROM channel_keys WHERE server_socket = ? AND key_id = ?"
+                .to_string(),
+            vec![Value::from(server_socket), Value::from(key_id)],
+        ))
+        .await?;
+    Ok(match row {
+        Some(row) => Some(row.try_get("", "wrapped_key")?),
+        None => None,
+    })
+}
+
+fn map_store_error(
+    code: &'static str,
+    key: &'static str,
+    e: anyhow::Error,
+) -> crate::shared::error::CommandError {
+    if e.downcast_ref::<KeyStoreLocked>().is_some() {
+        return command_error("CHANNEL_KEY_STORE_LOCKED", "error.channel_key_store_locked");
+    }
+    to_command_error(code, key, e)
+}
+
+#[tauri::command]
+/// 保存服务端下发的频道密钥（轮换后的新密钥不会覆盖旧密钥）。
+///
+/// # 参数
+/// - `server_socket`：服务器 socket。
+/// - `channel_id`：频道 id。
+/// - `key_id`：密钥 id（消息中携带）。
+/// - `key`：密钥原文（base64，32 字节）。
+pub async fn store_channel_key(
+    server_socket: String,
+    channel_id: i64,
+    key_id: String,
+    key: String,
+) -> CommandResult<()> {
+    let server_socket = server_socket.trim().to_string();
+    let key_id = key_id.trim().to_string();
+    let raw = BASE64.decode(key.trim()).unwrap_or_default();
+    if server_socket.is_empty() || key_id.is_empty() || raw.len() != KEY_LEN {
+        return Err(command_error(
+            "CHANNEL_KEY_INVALID",
+            "error.channel_key_invalid",
+        ));
+    }
+    let map_err = |e: anyhow::Error| {
+        map_store_error(
+            "CHANNEL_KEY_SAVE_FAILED",
+            "error.channel_key_save_failed",
+            e,
+        )
+    };
+    let kek = blocking(current_kek).await.map_err(map_err)?;
+    let wrapped = wrap_key(&kek, &aad(&server_socket, &key_id), &raw).map_err(map_err)?;
+    let db = get_db(SYSTEM_DB_KEY).await.map_err(map_err)?;
+    db.connection
+        .execute(&RawStatement::new(
+            r#"
+            INSERT INTO channel_keys (server_socket, key_id, channel_id, wrapped_key, created_at)
+            VALUES (?1, ?2, ?3, ?4, ?5)
+            ON CONFLICT(server_socket, key_id) DO UPDATE SET
+                channel_id = excluded.channel_id,
+                wrapped_key = excluded.wrapped_key
+            "#
+            .to_string(),
+            vec![
+                Value::from(server_socket.as_str()),
+                Value::from(key_id.as_str()),
+                Value::from(channel_id),
+                Value::from(wrapped),
+                Value::from(now_ms()),
+            ],
+        ))
+        .await
+        .map_err(|e| map_err(e.into()))?;
+    tracing::info!(action = "db_channel_key_saved", channel_id, key_id = %key_id);
+    Ok(())
+}
+
+#[tauri::command]
+/// 列出频道本地已保存的密钥 id（按保存时间升序）。
+pub async fn list_channel_key_ids(
+    server_socket: String,
+    channel_id: i64,
+) -> CommandResult<Vec<String>> {
+    let map_err = |e: anyhow::Error| {
+        to_command_error(
+            "CHANNEL_KEY_LOAD_FAILED",
+            "error.channel_key_load_failed",
+            e,
+        )
+    };
+    let db = get_db(SYSTEM_DB_KEY).await.map_err(map_err)?;
+    let rows = db
+        .connection
+        .query_all(&RawStatement::new(
+            r#"
+            SELECT key_id FROM channel_keys
+            WHERE server_socket = ? AND channel_id = ?
+            ORDER BY created_at ASC, key_id ASC
+            "#
+            .to_string(),
+            vec![Value::from(server_socket.trim()), Value::from(channel_id)],
+        ))
+        .await
+        .map_err(|e| map_err(e.into()))?;
+    rows.iter()
+        .map(|row| row.try_get("", "key_id").map_err(|e| map_err(e.into())))
+        .collect()
+}
+
+#[tauri::command]
+/// 按消息携带的 `key_id` 查找频道密钥并解密。
+///
+/// # 参数
+/// - `ciphertext`：base64 编码的 `nonce(12) || 密文+tag`。
+///
+/// # 返回值
+/// - `Ok(String)`：明文。
+/// - `Err(CHANNEL_KEY_UNKNOWN)`：本地没有该密钥，同时广播 `channel-key-missing`。
+pub async fn decrypt_channel_message(
+    app: AppHandle,
+    server_socket: String,
+    channel_id: i64,
+    key_id: String,
+    ciphertext: String,
+) -> CommandResult<String> {
+    decrypt_channel_message_with(&server_socket, channel_id, &key_id, &ciphertext, &app).await
+}
+
+/// [`decrypt_channel_message`] 的核心逻辑（不依赖 Tauri，缺失事件经 `events` 投递）。
+pub async fn decrypt_channel_message_with(
+    server_socket: &str,
+    channel_id: i64,
+    key_id: &str,
+    ciphertext: &str,
+    events: &dyn EventBus,
+) -> CommandResult<String> {
+    let server_socket = server_socket.trim().to_string();
+    let key_id = key_id.trim().to_string();
+    let map_err = |e: anyhow::Error| {
+        map_store_error(
+            "CHANNEL_MESSAGE_DECRYPT_FAILED",
+            "error.channel_message_decrypt_failed",
+            e,
+        )
+    };
+    let Some(wrapped) = load_wrapped(&server_socket, &key_id)
+        .await
+        .map_err(map_err)?
+    else {
+        tracing::warn!(action = "db_channel_key_missing", channel_id, key_id = %key_id);
+        events.emit(
+            CHANNEL_KEY_MISSING_EVENT,
+            ChannelKeyMissingPayload {
+                server_socket,
+                channel_id,
+                key_id,
+            },
+        );
+        return Err(command_error(
+            "CHANNEL_KEY_UNKNOWN",
+            "error.channel_key_unknown",
+        ));
+    };
+    let kek = blocking(current_kek).await.map_err(map_err)?;
+    let key = unwrap_key(&kek, &aad(&server_socket, &key_id), &wrapped).map_err(map_err)?;
+    let payload = BASE64
+        .decode(ciphertext.trim())
+        .map_err(|e| map_err(e.into()))?;
+    decrypt_with_key(&key, &payload).map_err(map_err)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn wrapped_keys_are_bound_to_server_and_key_id() {
+        let kek = [7u8; KEY_LEN];
+        let key = [42u8; KEY_LEN];
+        let wrapped = wrap_key(&kek, &aad("tls://a:1", "k1"), &key).unwrap();
+        assert_eq!(
+            unwrap_key(&kek, &aad("tls://a:1", "k1"), &wrapped).unwrap(),
+            key.to_vec()
+        );
+        assert!(unwrap_key(&kek, &aad("tls://a:1", "k2"), &wrapped).is_err());
+        assert!(unwrap_key(&[8u8; KEY_LEN], &aad("tls://a:1", "k1"), &wrapped).is_err());
+    }
+
+    #[test]
+    fn decrypts_messages_with_the_referenced_key() {
+        let key = [3u8; KEY_LEN];
+        let nonce = [9u8; NONCE_LEN];
+        let sealed = Aes256Gcm::new_from_slice(&key)
+            .unwrap()
+            .encrypt(Nonce::from_slice(&nonce), &b"hello"[..])
+            .unwrap();
+        let mut payload = nonce.to_vec();
+        payload.extend_from_slice(&sealed);
+
+        assert_eq!(decrypt_with_key(&key, &payload).unwrap(), "hello");
+        assert!(decrypt_with_key(&[4u8; KEY_LEN], &payload).is_err());
+        assert!(decrypt_with_key(&key, &payload[..NONCE_LEN]).is_err());
+    }
+
+    #[test]
+    fn locked_store_maps_to_dedicated_error() {
+        let err = map_store_error("X", "error.x", KeyStoreLocked.into());
+        assert_eq!(err.code, "CHANNEL_KEY_STORE_LOCKED");
+        let err = map_store_error("X", "error.x", anyhow::anyhow!("boom"));
+        assert_eq!(err.code, "X");
+    }
+}
@@ -10,7 +10,9 @@ use sea_orm::{
 };
 
 use crate::shared::error::{CommandResult, command_error, to_command_error};
+use crate::shared::metrics;
 
+use super::sql_policy::SqlOrigin;
 use super::{close_db, connect_named, get_db, get_entry, remove_db};
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -119,14 +121,29 @@ pub struct DbQueryResult {
 }
 
 #[derive(Debug, Clone)]
-struct RawStatement {
+pub(crate) struct RawStatement {
     sql: String,
     values: Vec<Value>,
 }
 
 impl RawStatement {
-    fn new(sql: String, values: Vec<Value>) -> Self {
-        Self { sql, values }
+    pub(crate) fn new(sql: impl Into<String>, values: Vec<Value>) -> Self {
+        Self {
+            sql: sql.into(),
+            values,
+        }
+    }
+
+    /// 同参数的 `EXPLAIN QUERY PLAN` 语句。
+    pub(super) fn explain_query_plan(&self) -> Self {
+        Self {
+            sql: format!("EXPLAIN QUERY PLAN {}", self.sql),
+            values: self.values.clone(),
+        }
+    }
+
+    pub(super) fn sql(&self) -> &str {
+        &self.sql
     }
 }
 
@@ -153,7 +170,7 @@ impl ManagedDbKind {
     }
 }
 
-fn is_server_db_key(key: &str) -> bool {
+pub(super) fn is_server_db_key(key: &str) -> bool {
     let Some(hash) = key.strip_prefix("server_") else {
         return false;
     };
@@ -180,6 +197,30 @@ fn managed_db_path(key: &str) -> Result<PathBuf, crate::shared::app_data_dir::Ap
     Ok(managed_db_root()?.join(format!("{key}.db")))
 }
 
+/// 应用管理的 DB 文件路径（`system` 或 `server_<sha256>`；其他 key 返回 `None`）。
+pub(super) fn managed_db_file(key: &str) -> Option<PathBuf> {
+    if key != "system" && !is_server_db_key(key) {
+        return None;
+    }
+    managed_db_path(key).ok()
+}
+
+/// 按 key 重新打开应用管理的 DB 并补齐迁移（用于恢复备份后）。
+pub(super) async fn reopen_managed_db(key: &str) -> anyhow::Result<()> {
+    let kind = if key == "system" {
+        ManagedDbKind::System
+    } else {
+        ManagedDbKind::Server
+    };
+    let path = managed_db_file(key).context("Not a managed database key")?;
+    connect_named(key, path).await?;
+    run_migrations(key, kind).await?;
+    if kind == ManagedDbKind::Server {
+        crate::shared::mute_rules::schedule_evaluation(key);
+    }
+    Ok(())
+}
+
 fn is_managed_db_path(path: &Path) -> bool {
     let root = match managed_db_root() {
         Ok(r) => r,
@@ -200,7 +241,7 @@ async fn ensure_parent_dir(path: &Path) -> anyhow::Result<()> {
     Ok(())
 }
 
-fn map_values(params: Option<Vec<DbValue>>) -> Vec<Value> {
+pub(super) fn map_values(params: Option<Vec<DbValue>>) -> Vec<Value> {
     params
         .unwrap_or_default()
         .into_iter()
@@ -213,7 +254,7 @@ fn map_values(params: Option<Vec<DbValue>>) -> Vec<Value> {
         .collect()
 }
 
-fn row_get_value(row: &sea_orm::QueryResult, col: &str) -> DbValue {
+pub(super) fn row_get_value(row: &sea_orm::QueryResult, col: &str) -> DbValue {
     if let Ok(value) = row.try_get::<Option<bool>>("", col) {
         return value.map(DbValue::Bool).unwrap_or(DbValue::Null);
     }
@@ -260,7 +301,7 @@ fn strip_sql_comments(mut s: &str) -> &str {
     s
 }
 
-fn normalized_sql_head(sql: &str) -> &str {
+pub(super) fn normalized_sql_head(sql: &str) -> &str {
     strip_sql_comments(sql)
         .split(|ch: char| ch.is_whitespace() || ch == '(')
         .next()
@@ -287,7 +328,7 @@ fn validate_single_statement_sql(sql: &str) -> CommandResult<()> {
     Ok(())
 }
 
-fn validate_query_sql(sql: &str) -> CommandResult<()> {
+pub(super) fn validate_query_sql(sql: &str) -> CommandResult<()> {
     validate_single_statement_sql(sql)?;
     let head = normalized_sql_head(sql).to_ascii_lowercase();
     if head == "select" || head == "with" {
@@ -314,6 +355,16 @@ fn validate_execute_sql(sql: &str) -> CommandResult<()> {
     ))
 }
 
+/// server DB 中写入 `messages` 的语句（执行后需要按静音规则评估新消息）。
+fn writes_messages(key: &str, sql: &str) -> bool {
+    is_server_db_key(key)
+        && matches!(
+            normalized_sql_head(sql).to_ascii_lowercase().as_str(),
+            "insert" | "replace" | "update"
+        )
+        && sql.to_ascii_lowercase().contains("messages")
+}
+
 #[tauri::command]
 /// 初始化（或连接）一个命名数据库，并按需执行迁移。
 ///
@@ -352,20 +403,30 @@ pub async fn db_init(req: DbInitRequest) -> CommandResult<()> {
         .map_err(|e| to_command_error("DB_CONNECT_FAILED", "error.db_connect_failed", e))?;
     run_migrations(&req.key, kind)
         .await
-        .map_err(|e| to_command_error("DB_MIGRATE_FAILED", "error.db_migrate_failed", e))
+        .map_err(|e| to_command_error("DB_MIGRATE_FAILED", "error.db_migrate_failed", e))?;
+    if kind == ManagedDbKind::Server {
+        // 补评估上次关闭后写入或规则变化期间未评估的消息。
+        crate::shared::mute_rules::schedule_evaluation(&req.key);
+    }
+    Ok(())
 }
 
 #[tauri::command]
 /// 执行一条 SQL（非查询）。
 ///
 /// # 参数
+/// - `webview`：调用方 webview（据其 label 判定 SQL 来源，见 `sql_policy`）。
 /// - `req`：执行请求（key/sql/params）。
 ///
 /// # 返回值
 /// - `Ok(DbExecResult)`：执行结果（行数等）。
 /// - `Err(String)`：执行失败原因。
-pub async fn db_execute(req: DbExecuteRequest) -> CommandResult<DbExecResult> {
+pub async fn db_execute(
+    webview: tauri::Webview,
+    req: DbExecuteRequest,
+) -> CommandResult<DbExecResult> {
     validate_execute_sql(&req.sql)?;
+    super::sql_policy::enforce(SqlOrigin::for_webview(webview.label()), &req.sql).await?;
     let db = get_db(&req.key).await.map_err(|e| {
         to_command_error(
             "DB_GET_CONNECTION_FAILED",
@@ -374,11 +435,18 @@ pub async fn db_execute(req: DbExecuteRequest) -> CommandResult<DbExecResult> {
         )
     })?;
     let conn = &db.connection;
+    let evaluate_mute = writes_messages(&req.key, &req.sql);
     let stmt = RawStatement::new(req.sql, map_values(req.params));
+    let started = std::time::Instant::now();
     let result = conn
         .execute(&stmt)
         .await
         .map_err(|e| to_command_error("DB_EXECUTE_FAILED", "error.db_execute_failed", e))?;
+    metrics::observe_duration(metrics::DB_QUERY_LATENCY_MS, "execute", started.elapsed());
+    super::explain::report_if_slow(&req.key, &stmt, started.elapsed());
+    if evaluate_mute {
+        crate::shared::mute_rules::schedule_evaluation(&req.key);
+    }
     Ok(exec_result(&result))
 }
 
@@ -386,6 +454,7 @@ pub async fn db_execute(req: DbExecuteRequest) -> CommandResult<DbExecResult> {
 /// 执行一条查询 SQL，并按指定列名抽取结果。
 ///
 /// # 参数
+/// - `webview`：调用方 webview（据其 label 判定 SQL 来源，见 `sql_policy`）。
 /// - `req`：查询请求（key/sql/params/columns）。
 ///
 /// # 返回值
@@ -395,7 +464,10 @@ pub async fn db_execute(req: DbExecuteRequest) -> CommandResult<DbExecResult> {
 /// # 说明
 /// - 为减少跨端类型推断复杂度，调用方必须显式提供 `columns`。
 /// - 若 `columns` 为空，直接返回错误。
-pub async fn db_query(req: DbQueryRequest) -> CommandResult<DbQueryResult> {
+pub async fn db_query(
+    webview: tauri::Webview,
+    req: DbQueryRequest,
+) -> CommandResult<DbQueryResult> {
     if req.columns.is_empty() {
         return Err(command_error(
             "DB_COLUMNS_REQUIRED",
@@ -403,6 +475,7 @@ pub async fn db_query(req: DbQueryRequest) -> CommandResult<DbQueryResult> {
         ));
     }
     validate_query_sql(&req.sql)?;
+    super::sql_policy::enforce(SqlOrigin::for_webview(webview.label()), &req.sql).await?;
 
     let db = get_db(&req.key).await.map_err(|e| {
         to_command_error(
@@ -413,10 +486,13 @@ pub async fn db_query(req: DbQueryRequest) -> CommandResult<DbQueryResult> {
     })?;
     let conn = &db.connection;
     let stmt = RawStatement::new(req.sql, map_values(req.params));
+    let started = std::time::Instant::now();
     let rows = conn
         .query_all(&stmt)
         .await
         .map_err(|e| to_command_error("DB_QUERY_FAILED", "error.db_query_failed", e))?;
+    metrics::observe_duration(metrics::DB_QUERY_LATENCY_MS, "query", started.elapsed());
+    super::explain::report_if_slow(&req.key, &stmt, started.elapsed());
     let mut result_rows = Vec::with_capacity(rows.len());
 
     for row in rows.iter() {
@@ -437,12 +513,20 @@ pub async fn db_query(req: DbQueryRequest) -> CommandResult<DbQueryResult> {
 /// 在同一事务内按序执行多条 SQL（非查询）。
 ///
 /// # 参数
+/// - `webview`：调用方 webview（据其 label 判定 SQL 来源，见 `sql_policy`）。
 /// - `req`：事务请求（key/statements）。
 ///
 /// # 返回值
 /// - `Ok(Vec<DbExecResult>)`：每条语句的执行结果列表（与输入 statements 顺序一致）。
 /// - `Err(String)`：执行失败原因。
-pub async fn db_transaction(req: DbTransactionRequest) -> CommandResult<Vec<DbExecResult>> {
+pub async fn db_transaction(
+    webview: tauri::Webview,
+    req: DbTransactionRequest,
+) -> CommandResult<Vec<DbExecResult>> {
+    let origin = SqlOrigin::for_webview(webview.label());
+    for statement in &req.statements {
+        super::sql_policy::enforce(origin, &statement.sql).await?;
+    }
     let db = get_db(&req.key).await.map_err(|e| {
         to_command_error(
             "DB_GET_CONNECTION_FAILED",
@@ -459,9 +543,11 @@ pub async fn db_transaction(req: DbTransactionRequest) -> CommandResult<Vec<DbEx
         )
     })?;
     let mut results = Vec::with_capacity(req.statements.len());
+    let mut evaluate_mute = false;
 
     for statement in req.statements {
         validate_execute_sql(&statement.sql)?;
+        evaluate_mute |= writes_messages(&req.key, &statement.sql);
         let stmt = RawStatement::new(statement.sql, map_values(statement.params));
         let res = txn.execute(&stmt).await.map_err(|e| {
             to_command_error(
@@ -480,6 +566,9 @@ pub async fn db_transaction(req: DbTransactionRequest) -> CommandResult<Vec<DbEx
             e,
         )
     })?;
+    if evaluate_mute {
+        crate::shared::mute_rules::schedule_evaluation(&req.key);
+    }
     Ok(results)
 }
 
@@ -622,18 +711,19 @@ fn now_ms() -> i64 {
 }
 
 fn system_migrations() -> Vec<Migration> {
-    vec![Migration {
-        version: 1,
-        name: "system_base",
-        statements: vec![
-            r#"
+    vec![
+        Migration {
+            version: 1,
+            name: "system_base",
+            statements: vec![
+                r#"
             CREATE TABLE IF NOT EXISTS app_config (
                 key TEXT PRIMARY KEY,
                 value TEXT NOT NULL,
                 updated_at INTEGER NOT NULL
             );
             "#,
-            r#"
+                r#"
             CREATE TABLE IF NOT EXISTS servers (
                 server_socket TEXT PRIMARY KEY,
                 server_name TEXT,
@@ -643,16 +733,222 @@ fn system_migrations() -> Vec<Migration> {
                 db_path TEXT
             );
             "#,
-        ],
-    }]
+            ],
+        },
+        Migration {
+            version: 2,
+            name: "system_emoji_usage",
+            statements: vec![
+                r#"
+            CREATE TABLE IF NOT EXISTS emoji_usage (
+                emoji TEXT PRIMARY KEY,
+                use_count INTEGER NOT NULL,
+                last_used_at INTEGER NOT NULL
+            );
+            "#,
+            ],
+        },
+        Migration {
+            version: 3,
+            name: "system_notifications",
+            statements: vec![
+                r#"
+            CREATE TABLE IF NOT EXISTS notifications (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                kind TEXT NOT NULL,
+                title TEXT NOT NULL,
+                body TEXT NOT NULL,
+                server_socket TEXT,
+                channel_id TEXT,
+                message_id TEXT,
+                created_at INTEGER NOT NULL,
+                read_at INTEGER
+            );
+            "#,
+                r#"
+            CREATE INDEX IF NOT EXISTS idx_notifications_created_at
+                ON notifications(created_at);
+            "#,
+            ],
+        },
+        Migration {
+            version: 4,
+            name: "system_mute_rules",
+            statements: vec![
+                r#"
+            CREATE TABLE IF NOT EXISTS mute_rules (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                pattern TEXT NOT NULL,
+                is_regex INTEGER NOT NULL DEFAULT 0,
+                case_sensitive INTEGER NOT NULL DEFAULT 0,
+                created_at INTEGER NOT NULL
+            );
+            "#,
+            ],
+        },
+        Migration {
+            version: 5,
+            name: "system_audit_log",
+            statements: vec![
+                r#"
+            CREATE TABLE IF NOT EXISTS audit_log (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                kind TEXT NOT NULL,
+                server_socket TEXT,
+                detail TEXT NOT NULL,
+                created_at INTEGER NOT NULL
+            );
+            "#,
+                r#"
+            CREATE INDEX IF NOT EXISTS idx_audit_log_kind_created_at
+                ON audit_log(kind, created_at);
+            "#,
+            ],
+        },
+        Migration {
+            version: 6,
+            name: "system_onboarding_steps",
+            statements: vec![
+                r#"
+            CREATE TABLE IF NOT EXISTS onboarding_steps (
+                step TEXT PRIMARY KEY,
+                completed_at INTEGER NOT NULL
+            );
+            "#,
+            ],
+        },
+        Migration {
+            version: 7,
+            name: "system_favorites",
+            statements: vec![
+                r#"
+            CREATE TABLE IF NOT EXISTS favorites (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                server_socket TEXT NOT NULL,
+                db_key TEXT NOT NULL,
+                channel_id INTEGER NOT NULL,
+                alias TEXT,
+                position INTEGER NOT NULL,
+                created_at INTEGER NOT NULL,
+                UNIQUE (server_socket, channel_id)
+            );
+            "#,
+            ],
+        },
+        Migration {
+            version: 8,
+            name: "system_channel_keys",
+            statements: vec![
+                // wrapped_key 为 KEK 包装后的密钥（见 `channel_keys`），不落盘明文。
+                r#"
+            CREATE TABLE IF NOT EXISTS channel_keys (
+                server_socket TEXT NOT NULL,
+                key_id TEXT NOT NULL,
+                channel_id INTEGER NOT NULL,
+                wrapped_key TEXT NOT NULL,
+                created_at INTEGER NOT NULL,
+                PRIMARY KEY (server_socket, key_id)
+            );
+            "#,
+                r#"
+            CREATE INDEX IF NOT EXISTS idx_channel_keys_channel
+                ON channel_keys(server_socket, channel_id);
+            "#,
+            ],
+        },
+        Migration {
+            version: 9,
+            name: "system_device_verifications",
+            statements: vec![
+                r#"
+            CREATE TABLE IF NOT EXISTS device_verifications (
+                device_id TEXT PRIMARY KEY,
+                public_key TEXT NOT NULL,
+                fingerprint TEXT NOT NULL,
+                label TEXT,
+                cross_signature TEXT NOT NULL,
+                verified_at INTEGER NOT NULL
+            );
+            "#,
+            ],
+        },
+        Migration {
+            version: 10,
+            name: "system_calls",
+            statements: vec![
+                r#"
+            CREATE TABLE IF NOT EXISTS calls (
+                server_socket TEXT NOT NULL,
+                call_id TEXT NOT NULL,
+                peer_id TEXT NOT NULL,
+                direction TEXT NOT NULL,
+                media TEXT NOT NULL,
+                state TEXT NOT NULL,
+                invited_at INTEGER NOT NULL,
+                answered_at INTEGER,
+                ended_at INTEGER,
+                end_reason TEXT,
+                PRIMARY KEY (server_socket, call_id)
+            );
+            "#,
+                r#"
+            CREATE INDEX IF NOT EXISTS idx_calls_invited_at ON calls(invited_at);
+            "#,
+            ],
+        },
+        Migration {
+            version: 11,
+            name: "system_server_capabilities",
+            statements: vec![
+                r#"
+            CREATE TABLE IF NOT EXISTS server_capabilities (
+                server_socket TEXT PRIMARY KEY,
+                protocol_version INTEGER NOT NULL,
+                routes TEXT NOT NULL,
+                advertised INTEGER NOT NULL,
+                negotiated_at INTEGER NOT NULL
+            );
+            "#,
+            ],
+        },
+        Migration {
+            version: 12,
+            name: "system_server_emojis",
+            statements: vec![
+                r#"
+            CREATE TABLE IF NOT EXISTS server_emojis (
+                server_socket TEXT NOT NULL,
+                shortcode TEXT NOT NULL,
+                source_url TEXT NOT NULL,
+                asset TEXT NOT NULL,
+                animated INTEGER NOT NULL DEFAULT 0,
+                category TEXT,
+                updated_at INTEGER NOT NULL,
+                PRIMARY KEY (server_socket, shortcode)
+            );
+            "#,
+                r#"
+            CREATE INDEX IF NOT EXISTS idx_server_emojis_asset ON server_emojis(asset);
+            "#,
+                r#"
+            CREATE TABLE IF NOT EXISTS server_emoji_catalogs (
+                server_socket TEXT PRIMARY KEY,
+                etag TEXT,
+                synced_at INTEGER NOT NULL
+            );
+            "#,
+            ],
+        },
+    ]
 }
 
 fn server_migrations() -> Vec<Migration> {
-    vec![Migration {
-        version: 1,
-        name: "server_base",
-        statements: vec![
-            r#"
+    vec![
+        Migration {
+            version: 1,
+            name: "server_base",
+            statements: vec![
+                r#"
             CREATE TABLE IF NOT EXISTS channels (
                 id INTEGER PRIMARY KEY,
                 name TEXT NOT NULL,
@@ -660,7 +956,7 @@ fn server_migrations() -> Vec<Migration> {
                 created_at INTEGER
             );
             "#,
-            r#"
+                r#"
             CREATE TABLE IF NOT EXISTS messages (
                 id TEXT PRIMARY KEY,
                 channel_id INTEGER NOT NULL,
@@ -670,19 +966,213 @@ fn server_migrations() -> Vec<Migration> {
                 updated_at INTEGER NOT NULL
             );
             "#,
-            r#"
+                r#"
             CREATE INDEX IF NOT EXISTS idx_messages_channel_time
             ON messages(channel_id, created_at);
             "#,
-            r#"
+                r#"
             CREATE TABLE IF NOT EXISTS kv (
                 key TEXT PRIMARY KEY,
                 value TEXT NOT NULL,
                 updated_at INTEGER NOT NULL
             );
             "#,
-        ],
-    }]
+            ],
+        },
+        Migration {
+            version: 2,
+            name: "server_translations",
+            statements: vec![
+                r#"
+            CREATE TABLE IF NOT EXISTS translations (
+                message_id TEXT NOT NULL,
+                target_lang TEXT NOT NULL,
+                provider TEXT NOT NULL,
+                text TEXT NOT NULL,
+                created_at INTEGER NOT NULL,
+                PRIMARY KEY (message_id, target_lang)
+            );
+            "#,
+            ],
+        },
+        Migration {
+            version: 3,
+            name: "server_users",
+            statements: vec![
+                r#"
+            CREATE TABLE IF NOT EXISTS users (
+                id INTEGER PRIMARY KEY,
+                name TEXT NOT NULL,
+                updated_at INTEGER NOT NULL
+            );
+            "#,
+                r#"
+            CREATE INDEX IF NOT EXISTS idx_messages_user_time
+            ON messages(user_id, created_at);
+            "#,
+            ],
+        },
+        Migration {
+            version: 4,
+            name: "server_blocked_users",
+            statements: vec![
+                r#"
+            CREATE TABLE IF NOT EXISTS blocked_users (
+                user_id INTEGER PRIMARY KEY,
+                blocked_at INTEGER NOT NULL,
+                synced INTEGER NOT NULL DEFAULT 0
+            );
+            "#,
+            ],
+        },
+        Migration {
+            version: 5,
+            name: "server_message_muted",
+            statements: vec![
+                // NULL 表示尚未按静音规则评估；0/1 为评估结果。
+                r#"
+            ALTER TABLE messages ADD COLUMN muted INTEGER;
+            "#,
+                r#"
+            CREATE TRIGGER IF NOT EXISTS trg_messages_content_mute_reset
+            AFTER UPDATE OF content ON messages
+            BEGIN
+                UPDATE messages SET muted = NULL WHERE id = NEW.id;
+            END;
+            "#,
+            ],
+        },
+        Migration {
+            version: 6,
+            name: "server_delta_sync",
+            statements: vec![
+                r#"
+            CREATE TABLE IF NOT EXISTS sync_cursors (
+                channel_id INTEGER PRIMARY KEY,
+                cursor TEXT,
+                synced_at INTEGER NOT NULL,
+                applied_changes INTEGER NOT NULL DEFAULT 0
+            );
+            "#,
+                r#"
+            CREATE TABLE IF NOT EXISTS message_tombstones (
+                message_id TEXT PRIMARY KEY,
+                channel_id INTEGER NOT NULL,
+                deleted_at INTEGER NOT NULL
+            );
+            "#,
+            ],
+        },
+        Migration {
+            version: 7,
+            name: "server_outbox",
+            statements: vec![
+                // 乐观发送：临时行以 client_id 关联发件箱，status 为 pending/sent/failed。
+                r#"
+            ALTER TABLE messages ADD COLUMN client_id TEXT;
+            "#,
+                r#"
+            ALTER TABLE messages ADD COLUMN status TEXT NOT NULL DEFAULT 'sent';
+            "#,
+                r#"
+            CREATE TABLE IF NOT EXISTS outbox (
+                client_id TEXT PRIMARY KEY,
+                channel_id INTEGER NOT NULL,
+                body TEXT NOT NULL,
+                attempts INTEGER NOT NULL DEFAULT 0,
+                last_error TEXT,
+                created_at INTEGER NOT NULL
+            );
+            "#,
+            ],
+        },
+        Migration {
+            version: 8,
+            name: "server_channel_retention",
+            statements: vec![
+                // 列为 NULL 表示沿用全局设置，0 表示不限制。
+                r#"
+            CREATE TABLE IF NOT EXISTS channel_retention (
+                channel_id INTEGER PRIMARY KEY,
+                keep_days INTEGER,
+                keep_count INTEGER,
+                updated_at INTEGER NOT NULL
+            );
+            "#,
+            ],
+        },
+        Migration {
+            version: 9,
+            name: "server_channel_read_state",
+            statements: vec![
+                // 已读位置以服务端 `last_read_time` 为准，本地只做缓存。
+                r#"
+            CREATE TABLE IF NOT EXISTS channel_read_state (
+                channel_id INTEGER PRIMARY KEY,
+                last_read_time INTEGER NOT NULL,
+                updated_at INTEGER NOT NULL
+            );
+            "#,
+            ],
+        },
+        Migration {
+            version: 10,
+            name: "server_message_cascade",
+            statements: vec![
+                // 依附于消息 id 的派生行随消息一并删除（保留清理、归档、增量同步、发件箱回滚共用）；
+                // 新增此类表时须同时加入触发器与 `retention::DEPENDENT_TABLES`。
+                r#"
+            CREATE TRIGGER IF NOT EXISTS trg_messages_delete_cascade
+            AFTER DELETE ON messages
+            BEGIN
+                DELETE FROM translations WHERE message_id = OLD.id;
+            END;
+            "#,
+                // 清理此前删除路径遗留的孤立行。
+                r#"
+            DELETE FROM translations WHERE message_id NOT IN (SELECT id FROM messages);
+            "#,
+            ],
+        },
+        Migration {
+            version: 11,
+            name: "server_translations_by_provider",
+            statements: vec![
+                // 缓存键加入 provider：切换 provider 后不再命中其他 provider 的译文。
+                // 重建表前先移除引用它的触发器，重建后恢复。
+                r#"
+            DROP TRIGGER IF EXISTS trg_messages_delete_cascade;
+            "#,
+                r#"
+            CREATE TABLE translations_v11 (
+                message_id TEXT NOT NULL,
+                target_lang TEXT NOT NULL,
+                provider TEXT NOT NULL,
+                text TEXT NOT NULL,
+                created_at INTEGER NOT NULL,
+                PRIMARY KEY (message_id, target_lang, provider)
+            );
+            "#,
+                r#"
+            INSERT INTO translations_v11 (message_id, target_lang, provider, text, created_at)
+            SELECT message_id, target_lang, provider, text, created_at FROM translations;
+            "#,
+                r#"
+            DROP TABLE translations;
+            "#,
+                r#"
+            ALTER TABLE translations_v11 RENAME TO translations;
+            "#,
+                r#"
+            CREATE TRIGGER IF NOT EXISTS trg_messages_delete_cascade
+            AFTER DELETE ON messages
+            BEGIN
+                DELETE FROM translations WHERE message_id = OLD.id;
+            END;
+            "#,
+            ],
+        },
+    ]
 }
 
 struct Migration {
@@ -848,7 +1338,7 @@ mod tests {
         })
         .await
         .expect_err("custom path must be rejected");
-        assert!(err.contains("DB_PATH_NOT_ALLOWED"));
+        assert_eq!(err.code, "DB_PATH_NOT_ALLOWED");
 
         let err = db_init(DbInitRequest {
             key: "system".to_string(),
@@ -857,7 +1347,7 @@ mod tests {
         })
         .await
         .expect_err("invalid kind must be rejected");
-        assert!(err.contains("DB_KIND_INVALID"));
+        assert_eq!(err.code, "DB_KIND_INVALID");
 
         let err = db_init(DbInitRequest {
             key: "server_bad".to_string(),
@@ -866,7 +1356,41 @@ mod tests {
         })
         .await
         .expect_err("invalid server key must be rejected");
-        assert!(err.contains("DB_KEY_INVALID"));
+        assert_eq!(err.code, "DB_KEY_INVALID");
+    }
+
+    #[tokio::test]
+    async fn deleting_a_message_cascades_to_its_translations() {
+        let conn = sea_orm::Database::connect("sqlite::memory:")
+            .await
+            .expect("connect");
+        for migration in server_migrations() {
+            for sql in migration.statements {
+                conn.execute(&RawStatement::new(sql.to_string(), Vec::new()))
+                    .await
+                    .expect("migrate");
+            }
+        }
+        for sql in [
+            "INSERT INTO messages (id, channel_id, user_id, content, created_at, updated_at) VALUES ('1', 1, 1, 'hi', 1, 1)",
+            "INSERT INTO translations (message_id, target_lang, provider, text, created_at) VALUES ('1', 'en', 'p', 'hi', 1)",
+            "INSERT INTO translations (message_id, target_lang, provider, text, created_at) VALUES ('1', 'en', 'q', 'hi', 1)",
+            "DELETE FROM messages WHERE id = '1'",
+        ] {
+            conn.execute(&RawStatement::new(sql.to_string(), Vec::new()))
+                .await
+                .expect("execute");
+        }
+        let left: i64 = conn
+            .query_one(&RawStatement::new(
+                "SELECT COUNT(*) AS n FROM translations".to_string(),
+                Vec::new(),
+            ))
+            .await
+            .expect("query")
+            .and_then(|row| row.try_get("", "n").ok())
+            .expect("count");
+        assert_eq!(left, 0);
     }
 
     #[tokio::test]
@@ -885,7 +1409,7 @@ mod tests {
         let err = db_remove(key.clone())
             .await
             .expect_err("outside-root db must be rejected");
-        assert!(err.contains("DB_PATH_OUTSIDE_ROOT"));
+        assert_eq!(err.code, "DB_PATH_OUTSIDE_ROOT");
         assert!(
             unsafe_path.exists(),
             "outside-root file must not be deleted"
@@ -0,0 +1,648 @@
+//! shared｜数据库：增量同步（server DB `sync_cursors` / `message_tombstones` 表）。
+//!
+//! 说明：
+//! - 每个频道保存服务端下发的不透明变更游标（由 server 迁移 v6 创建），
+//!   经 API 层调用 `GET /api/channels/{cid}/changes?cursor=...` 拉取新增、编辑、删除的消息
+//!   （协议见 `docs/api/11-http-endpoints-v1.md` 7.4 与 `docs/api/14-pagination-and-cursor-v1.md` 第 6 节）；
+//! - 一页变更与新游标在同一个事务内写入 `messages`，任一步失败整体回滚，游标不前进；
+//! - 冲突规则见 [`plan_changes`]：删除优先且留下墓碑，旧版本不覆盖新版本；
+//! - 服务端报告游标失效（`cursor_invalid`）时清空游标，由调用方按全量补拉处理。
+//!
+//! 约定：注释中文，日志英文（tracing）。
+
+use std::collections::{BTreeMap, HashMap, HashSet};
+
+use anyhow::Context;
+use sea_orm::{ConnectionTrait, TransactionTrait, Value};
+use serde::{Deserialize, Serialize};
+
+use crate::features::network::data::capabilities_store;
+use crate::features::network::data::http_client::ReqwestApiRequestAdapter;
+use crate::features::network::domain::capabilities::ProtocolFeature;
+use crate::features::network::usecases::api_usecases::{self, ApiJsonRequest};
+use crate::shared::error::{CommandResult, command_error, to_command_error};
+use crate::shared::feature_flags::{self, SYNC_ENGINE_V2};
+use crate::shared::taskbar_progress::TaskbarProgress;
+
+use super::commands::{RawStatement, is_server_db_key};
+use super::get_db;
+
+/// 单次调用最多拉取的页数（剩余部分由 `has_more` 提示调用方继续）。
+const MAX_PAGES_PER_SYNC: usize = 20;
+
+/// 每页请求的变更条数。
+const PAGE_LIMIT: u32 = 200;
+
+/// 服务端变更中的消息（与 `GET /api/channels/{cid}/messages` 的 item 一致，只取需要的字段）。
+#[derive(Debug, Clone, Deserialize, PartialEq, Eq)]
+pub struct ChangedMessage {
+    pub mid: String,
+    pub uid: String,
+    pub send_time: i64,
+    #[serde(default)]
+    pub edited_at: Option<i64>,
+    #[serde(default)]
+    pub preview: String,
+}
+
+impl ChangedMessage {
+    /// 消息版本：最后编辑时间，未编辑时为发送时间。
+    fn version(&self) -> i64 {
+        self.edited_at.unwrap_or(self.send_time)
+    }
+
+    /// 发送者 id；服务端 uid 为十进制字符串，无法解析时返回 `None`。
+    fn user_id(&self) -> Option<i64> {
+        self.uid.trim().parse().ok()
+    }
+}
+
+/// `GET /api/channels/{cid}/changes` 的响应。
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct ChannelChangesPage {
+    #[serde(default)]
+    pub created: Vec<ChangedMessage>,
+    #[serde(default)]
+    pub edited: Vec<ChangedMessage>,
+    #[serde(default)]
+    pub deleted: Vec<String>,
+    pub next_cursor: Option<String>,
+    #[serde(default)]
+    pub has_more: bool,
+}
+
+/// 本地已知的消息状态（用于冲突判定）。
+#[derive(Debug, Clone, Default)]
+pub struct LocalSnapshot {
+    /// mid -> `messages.updated_at`。
+    pub versions: HashMap<String, i64>,
+    /// 已删除的 mid。
+    pub tombstones: HashSet<String>,
+}
+
+/// 冲突判定后的写入操作。
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SyncOp {
+    Insert(ChangedMessage),
+    Update(ChangedMessage),
+    Delete(String),
+    /// 被冲突规则丢弃的变更（mid）。
+    Skip(String),
+}
+
+/// 按冲突规则把一页变更转换为写入操作。
+///
+/// # 规则
+/// - 发送者 uid 不是整数 id 的变更无法归属用户，直接跳过；
+/// - 删除优先：同一页内既有编辑又有删除时只执行删除；已有墓碑的 mid 不会被重新写入；
+/// - 版本（`edited_at`，缺省为 `send_time`）低于本地 `updated_at` 的变更视为过期并跳过，
+///   相同版本重复下发时按更新处理（幂等）；
+/// - `created` 中本地已存在的 mid 按编辑处理，`edited` 中本地缺失的 mid 按新增处理。
+pub fn plan_changes(local: &LocalSnapshot, page: &ChannelChangesPage) -> Vec<SyncOp> {
+    let deleted: HashSet<&str> = page.deleted.iter().map(String::as_str).collect();
+    let mut ops = Vec::new();
+    let mut seen: HashMap<&str, i64> = HashMap::new();
+    for message in page.created.iter().chain(page.edited.iter()) {
+        let mid = message.mid.as_str();
+        if message.user_id().is_none() {
+            tracing::warn!(action = "db_delta_sync_invalid_uid", mid, uid = %message.uid);
+            ops.push(SyncOp::Skip(message.mid.clone()));
+            continue;
+        }
+        if deleted.contains(mid) || local.tombstones.contains(mid) {
+            ops.push(SyncOp::Skip(message.mid.clone()));
+            continue;
+        }
+        let known = seen
+            .get(mid)
+            .copied()
+            .or_else(|| local.versions.get(mid).copied());
+        match known {
+            Some(version) if message.version() < version => {
+                ops.push(SyncOp::Skip(message.mid.clone()));
+            }
+            Some(_) => {
+                seen.insert(mid, message.version());
+                ops.push(SyncOp::Update(message.clone()));
+            }
+            None => {
+                seen.insert(mid, message.version());
+                ops.push(SyncOp::Insert(message.clone()));
+            }
+        }
+    }
+    for mid in &page.deleted {
+        ops.push(SyncOp::Delete(mid.clone()));
+    }
+    ops
+}
+
+/// 频道同步状态（调试用）。
+#[derive(Debug, Clone, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct SyncState {
+    pub channel_id: i64,
+    /// 当前变更游标（从未同步或游标失效后为空）。
+    pub cursor: Option<String>,
+    pub synced_at: Option<i64>,
+    /// 累计写入的变更条数（新增 + 编辑 + 删除）。
+    pub applied_changes: i64,
+    pub local_messages: i64,
+    pub tombstones: i64,
+}
+
+/// `sync_channel_delta` 的结果。
+#[derive(Debug, Clone, Default, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct DeltaSyncResult {
+    pub inserted: Vec<String>,
+    pub updated: Vec<String>,
+    pub deleted: Vec<String>,
+    /// 被冲突规则丢弃的 mid。
+    pub skipped: Vec<String>,
+    /// 服务端报告游标失效，本地游标已清空，调用方应全量补拉。
+    pub cursor_reset: bool,
+    /// 达到单次页数上限，仍有未拉取的变更。
+    pub has_more: bool,
+}
+
+/// 服务端请求参数（与 `api_request_json` 的连接参数一致）。
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DeltaSyncOptions {
+    pub server_socket: String,
+    /// 请求头（通常包含鉴权 token）。
+    pub headers: Option<BTreeMap<String, String>>,
+    pub tls_policy: Option<String>,
+    pub tls_fingerprint: Option<String>,
+}
+
+fn now_ms() -> i64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_millis() as i64
+}
+
+fn validate_db_key(db_key: &str) -> CommandResult<()> {
+    if is_server_db_key(db_key) {
+        Ok(())
+    } else {
+        Err(command_error("DB_KEY_INVALID", "error.db_key_invalid"))
+    }
+}
+
+/// 对游标做 query 参数编码（游标为不透明字符串，可能包含 `+`、`/` 等字符）。
+fn encode_query_value(raw: &str) -> String {
+    let mut out = String::with_capacity(raw.len());
+    for byte in raw.bytes() {
+        if byte.is_ascii_alphanumeric() || matches!(byte, b'-' | b'_' | b'.' | b'~') {
+            out.push(byte as char);
+        } else {
+            out.push_str(&format!("%{byte:02X}"));
+        }
+    }
+    out
+}
+
+fn changes_path(channel_id: i64, cursor: Option<&str>) -> String {
+    match cursor {
+        Some(cursor) => format!(
+            "/api/channels/{channel_id}/changes?cursor={}&limit={PAGE_LIMIT}",
+            encode_query_value(cursor)
+        ),
+        None => format!("/api/channels/{channel_id}/changes?limit={PAGE_LIMIT}"),
+    }
+}
+
+/// 错误响应是否表示游标失效（`error.reason == "cursor_invalid"`）。
+fn is_cursor_invalid(error: Option<&serde_json::Value>) -> bool {
+    error
+        .and_then(|body| body.pointer("/error/reason"))
+        .and_then(|reason| reason.as_str())
+        == Some("cursor_invalid")
+}
+
+enum PageFetch {
+    Page(ChannelChangesPage),
+    CursorInvalid,
+}
+
+async fn fetch_page(
+    options: &DeltaSyncOptions,
+    channel_id: i64,
+    cursor: Option<&str>,
+) -> anyhow::Result<PageFetch> {
+    let api_request_port = ReqwestApiRequestAdapter::shared();
+    let response = api_usecases::api_request_json(
+        ApiJsonRequest {
+            server_socket: options.server_socket.clone(),
+            method: "GET".to_string(),
+            path: changes_path(channel_id, cursor),
+            headers: options.headers.clone(),
+            body: None,
+            tls_policy: options.tls_policy.clone(),
+            tls_fingerprint: options.tls_fingerprint.clone(),
+        },
+        api_request_port.as_ref(),
+    )
+    .await?;
+    if !response.ok {
+        if cursor.is_some() && is_cursor_invalid(response.error.as_ref()) {
+            return Ok(PageFetch::CursorInvalid);
+        }
+        anyhow::bail!("Changes request rejected with status {}", response.status);
+    }
+    let page = serde_json::from_value(response.body.unwrap_or_default())
+        .context("Invalid changes response")?;
+    Ok(PageFetch::Page(page))
+}
+
+async fn load_cursor(
+    conn: &impl ConnectionTrait,
+    channel_id: i64,
+) -> anyhow::Result<Option<String>> {
+    let row = conn
+        .query_one(&RawStatement::new(
+            "SELECT cursor FROM sync_cursors WHERE channel_id = ?".to_string(),
+            vec![Value::from(channel_id)],
+        ))
+        .await?;
+    Ok(row.and_then(|row| row.try_get::<Option<String>>("", "cursor").ok().flatten()))
+}
+
+async fn load_snapshot(
+    conn: &impl ConnectionTrait,
+    page: &ChannelChangesPage,
+) -> anyhow::Result<LocalSnapshot> {
+    let mids: Vec<&String> = page
+        .created
+        .iter()
+        .chain(page.edited.iter())
+        .map(|m| &m.mid)
+        .collect();
+    let mut snapshot = LocalSnapshot::default();
+    if mids.is_empty() {
+        return Ok(snapshot);
+    }
+    let placeholders = vec!["?"; mids.len()].join(", ");
+    let values: Vec<Value> = mids.iter().map(|mid| Value::from(mid.as_str())).collect();
+    let rows = conn
+        .query_all(&RawStatement::new(
+            format!("SELECT id, updated_at FROM messages WHERE id IN ({placeholders})"),
+            values.clone(),
+        ))
+        .await?;
+    for row in rows {
+        if let (Ok(id), Ok(updated_at)) = (
+            row.try_get::<String>("", "id"),
+            row.try_get::<i64>("", "updated_at"),
+        ) {
+            snapshot.versions.insert(id, updated_at);
+        }
+    }
+    let rows = conn
+        .query_all(&RawStatement::new(
+            format!(
+                "SELECT message_id FROM message_tombstones WHERE message_id IN ({placeholders})"
+            ),
+            values,
+        ))
+        .await?;
+    for row in rows {
+        if let Ok(id) = row.try_get::<String>("", "message_id") {
+            snapshot.tombstones.insert(id);
+        }
+    }
+    Ok(snapshot)
+}
+
+/// 在一个事务内应用一页变更并推进游标。
+async fn apply_page(
+    db_key: &str,
+    channel_id: i64,
+    page: &ChannelChangesPage,
+    result: &mut DeltaSyncResult,
+) -> anyhow::Result<()> {
+    let db = get_db(db_key).await?;
+    let txn = db.connection.begin().await?;
+    let snapshot = load_snapshot(&txn, page).await?;
+    let now = now_ms();
+    let mut applied = 0i64;
+    for op in plan_changes(&snapshot, page) {
+        match op {
+            SyncOp::Insert(message) | SyncOp::Update(message) => {
+                let user_id = message
+                    .user_id()
+                    .with_context(|| format!("Invalid uid for message {}", message.mid))?;
+                let exists = snapshot.versions.contains_key(&message.mid);
+                txn.execute(&RawStatement::new(
+                    r#"
+                    INSERT INTO messages (id, channel_id, user_id, content, created_at, updated_at)
+                    VALUES (?, ?, ?, ?, ?, ?)
+                    ON CONFLICT(id) DO UPDATE SET
+                        content = excluded.content,
+                        updated_at = excluded.updated_at
+                    "#
+                    .to_string(),
+                    vec![
+                        Value::from(message.mid.as_str()),
+                        Value::from(channel_id),
+                        Value::from(user_id),
+                        Value::from(message.preview.as_str()),
+                        Value::from(message.send_time),
+                        Value::from(message.version()),
+                    ],
+                ))
+                .await?;
+                applied += 1;
+                if exists || result.inserted.contains(&message.mid) {
+                    result.updated.push(message.mid);
+                } else {
+                    result.inserted.push(message.mid);
+                }
+            }
+            SyncOp::Delete(mid) => {
+                txn.execute(&RawStatement::new(
+                    "DELETE FROM messages WHERE id = ?".to_string(),
+                    vec![Value::from(mid.as_str())],
+                ))
+                .await?;
+                txn.execute(&RawStatement::new(
+                    "INSERT OR REPLACE INTO message_tombstones (message_id, channel_id, deleted_at) VALUES (?, ?, ?)"
+                        .to_string(),
+                    vec![Value::from(mid.as_str()), Value::from(channel_id), Value::from(now)],
+                ))
+                .await?;
+                applied += 1;
+                result.inserted.retain(|m| m != &mid);
+                result.updated.retain(|m| m != &mid);
+                result.deleted.push(mid);
+            }
+            SyncOp::Skip(mid) => result.skipped.push(mid),
+        }
+    }
+    txn.execute(&RawStatement::new(
+        r#"
+        INSERT INTO sync_cursors (channel_id, cursor, synced_at, applied_changes)
+        VALUES (?, ?, ?, ?)
+        ON CONFLICT(channel_id) DO UPDATE SET
+            cursor = COALESCE(excluded.cursor, sync_cursors.cursor),
+            synced_at = excluded.synced_at,
+            applied_changes = sync_cursors.applied_changes + excluded.applied_changes
+        "#
+        .to_string(),
+        vec![
+            Value::from(channel_id),
+            Value::from(page.next_cursor.clone()),
+            Value::from(now),
+            Value::from(applied),
+        ],
+    ))
+    .await?;
+    txn.commit().await?;
+    Ok(())
+}
+
+async fn reset_cursor(db_key: &str, channel_id: i64) -> anyhow::Result<()> {
+    let db = get_db(db_key).await?;
+    db.connection
+        .execute(&RawStatement::new(
+            "UPDATE sync_cursors SET cursor = NULL WHERE channel_id = ?".to_string(),
+            vec![Value::from(channel_id)],
+        ))
+        .await?;
+    Ok(())
+}
+
+async fn sync_channel(
+    db_key: &str,
+    channel_id: i64,
+    options: &DeltaSyncOptions,
+) -> anyhow::Result<DeltaSyncResult> {
+    let db = get_db(db_key).await?;
+    let mut cursor = load_cursor(&db.connection, channel_id).await?;
+    let mut result = DeltaSyncResult::default();
+    // 只有一页的同步很快完成，不占用任务栏；需要继续翻页时才显示进度。
+    let mut taskbar: Option<TaskbarProgress> = None;
+    for page_index in 0..MAX_PAGES_PER_SYNC {
+        let page = match fetch_page(options, channel_id, cursor.as_deref()).await? {
+            PageFetch::Page(page) => page,
+            PageFetch::CursorInvalid => {
+                reset_cursor(db_key, channel_id).await?;
+                result.cursor_reset = true;
+                result.has_more = false;
+                break;
+            }
+        };
+        apply_page(db_key, channel_id, &page, &mut result).await?;
+        result.has_more = page.has_more;
+        if !page.has_more || page.next_cursor.is_none() || page.next_cursor == cursor {
+            break;
+        }
+        taskbar
+            .get_or_insert_with(|| TaskbarProgress::start("delta_sync"))
+            .set(page_index as u64 + 1, MAX_PAGES_PER_SYNC as u64);
+        cursor = page.next_cursor;
+    }
+    if let Some(taskbar) = &taskbar {
+        taskbar.finish();
+    }
+    Ok(result)
+}
+
+#[tauri::command]
+/// 按服务端变更游标增量同步一个频道的消息。
+///
+/// # 参数
+/// - `db_key`：server DB key（`server_<sha256>`），必须已打开。
+/// - `channel_id`：频道 id。
+/// - `options`：服务端请求参数。
+///
+/// # 返回值
+/// - `Ok(DeltaSyncResult)`：本次写入/跳过的 mid；`cursorReset` 为 true 时调用方应全量补拉。
+/// - `Err(CommandError)`：key 非法、服务端未声明增量同步能力（或 `sync_engine_v2` 未启用）、
+///   请求失败或写入失败（失败页不会推进游标）；不支持增量同步时调用方应改为全量补拉。
+pub async fn sync_channel_delta(
+    db_key: String,
+    channel_id: i64,
+    options: DeltaSyncOptions,
+) -> CommandResult<DeltaSyncResult> {
+    validate_db_key(&db_key)?;
+    if !feature_flags::is_feature_enabled(SYNC_ENGINE_V2).await {
+        tracing::info!(action = "db_delta_sync_disabled", channel_id);
+        return Err(command_error(
+            "DB_DELTA_SYNC_UNSUPPORTED",
+            "error.db_delta_sync_unsupported",
+        ));
+    }
+    if !capabilities_store::supports(&options.server_socket, ProtocolFeature::DeltaSync).await {
+        tracing::info!(
+            action = "db_delta_sync_unsupported",
+            server_socket = %options.server_socket,
+            channel_id
+        );
+        return Err(command_error(
+            "DB_DELTA_SYNC_UNSUPPORTED",
+            "error.db_delta_sync_unsupported",
+        ));
+    }
+    let result = sync_channel(&db_key, channel_id, &options)
+        .await
+        .map_err(|e| {
+            tracing::warn!(action = "db_delta_sync_failed", channel_id, error = %e);
+            to_command_error("DB_DELTA_SYNC_FAILED", "error.db_delta_sync_failed", e)
+        })?;
+    if !result.inserted.is_empty() || !result.updated.is_empty() || !result.deleted.is_empty() {
+        super::quick_switch::invalidate(&db_key);
+        crate::shared::mute_rules::schedule_evaluation(&db_key);
+    }
+    tracing::info!(
+        action = "db_delta_sync_completed",
+        channel_id,
+        inserted = result.inserted.len(),
+        updated = result.updated.len(),
+        deleted = result.deleted.len(),
+        skipped = result.skipped.len(),
+        cursor_reset = result.cursor_reset
+    );
+    Ok(result)
+}
+
+async fn load_state(db_key: &str, channel_id: i64) -> anyhow::Result<SyncState> {
+    let db = get_db(db_key).await?;
+    let row = db
+        .connection
+        .query_one(&RawStatement::new(
+            r#"
+            SELECT
+                (SELECT cursor FROM sync_cursors WHERE channel_id = ?1) AS cursor,
+                (SELECT synced_at FROM sync_cursors WHERE channel_id = ?1) AS synced_at,
+                (SELECT applied_changes FROM sync_cursors WHERE channel_id = ?1) AS applied_changes,
+                (SELECT COUNT(*) FROM messages WHERE channel_id = ?1) AS local_messages,
+                (SELECT COUNT(*) FROM message_tombstones WHERE channel_id = ?1) AS tombstones
+            "#
+            .to_string(),
+            vec![Value::from(channel_id)],
+        ))
+        .await?
+        .context("Sync state query returned no row")?;
+    Ok(SyncState {
+        channel_id,
+        cursor: row.try_get::<Option<String>>("", "cursor").ok().flatten(),
+        synced_at: row.try_get::<Option<i64>>("", "synced_at").ok().flatten(),
+        applied_changes: row
+            .try_get::<Option<i64>>("", "applied_changes")
+            .ok()
+            .flatten()
+            .unwrap_or(0),
+        local_messages: row.try_get("", "local_messages").unwrap_or(0),
+        tombstones: row.try_get("", "tombstones").unwrap_or(0),
+    })
+}
+
+#[tauri::command]
+/// 读取频道同步状态（调试用）。
+///
+/// # 参数
+/// - `db_key`：server DB key。
+/// - `channel_id`：频道 id。
+pub async fn get_sync_state(db_key: String, channel_id: i64) -> CommandResult<SyncState> {
+    validate_db_key(&db_key)?;
+    load_state(&db_key, channel_id).await.map_err(|e| {
+        to_command_error(
+            "DB_SYNC_STATE_LOAD_FAILED",
+            "error.db_sync_state_load_failed",
+            e,
+        )
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn message(mid: &str, send_time: i64, edited_at: Option<i64>) -> ChangedMessage {
+        ChangedMessage {
+            mid: mid.to_string(),
+            uid: "7".to_string(),
+            send_time,
+            edited_at,
+            preview: format!("text {mid}"),
+        }
+    }
+
+    #[test]
+    fn deletes_win_over_edits_and_tombstones_block_resurrection() {
+        let local = LocalSnapshot {
+            versions: HashMap::new(),
+            tombstones: HashSet::from(["gone".to_string()]),
+        };
+        let page = ChannelChangesPage {
+            created: vec![message("gone", 10, None), message("new", 10, None)],
+            edited: vec![message("both", 10, Some(20))],
+            deleted: vec!["both".to_string()],
+            next_cursor: Some("c1".to_string()),
+            has_more: false,
+        };
+        assert_eq!(
+            plan_changes(&local, &page),
+            vec![
+                SyncOp::Skip("gone".to_string()),
+                SyncOp::Insert(message("new", 10, None)),
+                SyncOp::Skip("both".to_string()),
+                SyncOp::Delete("both".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn stale_versions_are_skipped_and_known_creates_become_updates() {
+        let local = LocalSnapshot {
+            versions: HashMap::from([("a".to_string(), 50), ("b".to_string(), 10)]),
+            tombstones: HashSet::new(),
+        };
+        let page = ChannelChangesPage {
+            created: vec![message("b", 10, None)],
+            edited: vec![message("a", 10, Some(40)), message("b", 10, Some(30))],
+            ..Default::default()
+        };
+        assert_eq!(
+            plan_changes(&local, &page),
+            vec![
+                SyncOp::Update(message("b", 10, None)),
+                SyncOp::Skip("a".to_string()),
+                SyncOp::Update(message("b", 10, Some(30))),
+            ]
+        );
+    }
+
+    #[test]
+    fn unparsable_uids_are_skipped_instead_of_defaulted() {
+        let mut bad = message("bad", 10, None);
+        bad.uid = "bob".to_string();
+        let page = ChannelChangesPage {
+            created: vec![bad, message("ok", 10, None)],
+            ..Default::default()
+        };
+        assert_eq!(
+            plan_changes(&LocalSnapshot::default(), &page),
+            vec![
+                SyncOp::Skip("bad".to_string()),
+                SyncOp::Insert(message("ok", 10, None)),
+            ]
+        );
+    }
+
+    #[test]
+    fn changes_path_encodes_cursor_and_detects_invalid_cursor() {
+        assert_eq!(
+            changes_path(12, Some("a+b/c=")),
+            "/api/channels/12/changes?cursor=a%2Bb%2Fc%3D&limit=200"
+        );
+        assert_eq!(changes_path(12, None), "/api/channels/12/changes?limit=200");
+        let body = serde_json::json!({ "error": { "reason": "cursor_invalid" } });
+        assert!(is_cursor_invalid(Some(&body)));
+        assert!(!is_cursor_invalid(None));
+    }
+}
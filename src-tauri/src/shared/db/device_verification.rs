@@ -0,0 +1,439 @@
+//! shared｜数据库：设备身份与交叉验证（system DB `device_verifications` 表）。
+//!
+//! 说明：
+//! - 每台设备持有一把 Ed25519 身份密钥，种子保存在系统凭据存储中，首次使用时生成；
+//!   设备 id 取公钥 SHA-256 的前 8 字节（hex）；
+//! - 导出的验证载荷（可编码为二维码）为 `carrypigeon-device:v1:<base64url(JSON)>`，
+//!   JSON 中包含设备 id、公钥与对 `设备 id || 公钥` 的自签名，证明持有私钥；
+//! - 验证远端设备时校验自签名，并以本机身份密钥对远端公钥签名（交叉签名）后保存；
+//!   同一设备 id 的公钥发生变化时拒绝验证，需先撤销旧记录；
+//! - 验证状态变化时广播 `device-verification-changed`。
+//!
+//! 约定：注释中文，日志英文（tracing）。
+
+use base64::Engine;
+use base64::engine::general_purpose::{STANDARD as BASE64, URL_SAFE_NO_PAD};
+use ed25519_dalek::{Signature, Signer, SigningKey, Verifier, VerifyingKey};
+use sea_orm::{ConnectionTrait, Value};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use tauri::AppHandle;
+
+use crate::shared::error::{CommandResult, command_error, to_command_error};
+use crate::shared::events::{EventBus, EventBusExt};
+use crate::shared::secrets;
+
+use super::commands::RawStatement;
+use super::get_db;
+
+/// 验证状态变化事件名。
+pub const DEVICE_VERIFICATION_CHANGED_EVENT: &str = "device-verification-changed";
+
+/// 验证载荷前缀。
+const PAYLOAD_PREFIX: &str = "carrypigeon-device:v1:";
+/// 设备名最大长度（字符数）。
+pub const MAX_LABEL_CHARS: usize = 64;
+
+/// 密钥所在的 DB key（应用级 system DB）。
+const SYSTEM_DB_KEY: &str = "system";
+pub(crate) const IDENTITY_ACCOUNT: &str = "device-identity-key";
+
+/// 本机设备身份。
+#[derive(Debug, Clone, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct DeviceIdentity {
+    pub device_id: String,
+    /// 公钥（base64）。
+    pub public_key: String,
+    /// 供人工比对的指纹（10 组 5 位十进制数字）。
+    pub fingerprint: String,
+}
+
+/// 导出的验证载荷。
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DeviceVerificationExport {
+    /// 二维码内容（`carrypigeon-device:v1:...`）。
+    pub payload: String,
+    pub identity: DeviceIdentity,
+}
+
+/// 已验证的远端设备。
+#[derive(Debug, Clone, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct VerifiedDevice {
+    pub device_id: String,
+    pub public_key: String,
+    pub fingerprint: String,
+    pub label: Option<String>,
+    /// 本机身份密钥对远端公钥的签名（base64）。
+    pub cross_signature: String,
+    pub verified_at: i64,
+}
+
+/// `device-verification-changed` 事件载荷。
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DeviceVerificationChangedPayload {
+    pub device_id: String,
+    pub verified: bool,
+}
+
+/// 验证载荷 JSON。
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct PayloadBody {
+    device_id: String,
+    public_key: String,
+    signature: String,
+}
+
+fn now_ms() -> i64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_millis() as i64
+}
+
+fn device_id_for(key: &VerifyingKey) -> String {
+    hex::encode(&Sha256::digest(key.as_bytes())[..8])
+}
+
+/// 指纹：公钥 SHA-256 前 20 字节按 2 字节一组转为 5 位十进制，共 10 组。
+fn fingerprint_for(key: &VerifyingKey) -> String {
+    Sha256::digest(key.as_bytes())[..20]
+        .chunks(2)
+        .map(|pair| format!("{:05}", u16::from_be_bytes([pair[0], pair[1]])))
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+fn identity_for(key: &VerifyingKey) -> DeviceIdentity {
+    DeviceIdentity {
+        device_id: device_id_for(key),
+        public_key: BASE64.encode(key.as_bytes()),
+        fingerprint: fingerprint_for(key),
+    }
+}
+
+fn self_signed_message(device_id: &str, key: &VerifyingKey) -> Vec<u8> {
+    let mut message = device_id.as_bytes().to_vec();
+    message.extend_from_slice(key.as_bytes());
+    message
+}
+
+/// 读取本机身份密钥，不存在时生成并保存（阻塞调用）。
+fn load_or_create_signing_key() -> anyhow::Result<SigningKey> {
+    if let Some(existing) = secrets::get(IDENTITY_ACCOUNT)?
+        && let Ok(bytes) = hex::decode(existing.trim())
+        && let Ok(seed) = <[u8; 32]>::try_from(bytes.as_slice())
+    {
+        return Ok(SigningKey::from_bytes(&seed));
+    }
+    let mut seed = [0u8; 32];
+    getrandom::fill(&mut seed).map_err(|_| anyhow::anyhow!("Failed to generate device key"))?;
+    secrets::set(IDENTITY_ACCOUNT, &hex::encode(seed))?;
+    tracing::info!(action = "db_device_identity_created");
+    Ok(SigningKey::from_bytes(&seed))
+}
+
+async fn signing_key() -> anyhow::Result<SigningKey> {
+    tauri::async_runtime::spawn_blocking(load_or_create_signing_key).await?
+}
+
+/// 生成本机验证载荷。
+fn encode_payload(key: &SigningKey) -> String {
+    let verifying = key.verifying_key();
+    let device_id = device_id_for(&verifying);
+    let signature = key.sign(&self_signed_message(&device_id, &verifying));
+    let body = PayloadBody {
+        device_id,
+        public_key: BASE64.encode(verifying.as_bytes()),
+        signature: BASE64.encode(signature.to_bytes()),
+    };
+    let json = serde_json::to_vec(&body).unwrap_or_default();
+    format!("{PAYLOAD_PREFIX}{}", URL_SAFE_NO_PAD.encode(json))
+}
+
+/// 解析并校验远端验证载荷，返回其公钥。
+fn decode_payload(payload: &str) -> anyhow::Result<VerifyingKey> {
+    let encoded = payload
+        .trim()
+        .strip_prefix(PAYLOAD_PREFIX)
+        .ok_or_else(|| anyhow::anyhow!("Unsupported device verification payload"))?;
+    let body: PayloadBody = serde_json::from_slice(&URL_SAFE_NO_PAD.decode(encoded)?)?;
+    let key_bytes: [u8; 32] = BASE64
+        .decode(&body.public_key)?
+        .try_into()
+        .map_err(|_| anyhow::anyhow!("Invalid device public key length"))?;
+    let key = VerifyingKey::from_bytes(&key_bytes)?;
+    if device_id_for(&key) != body.device_id {
+        return Err(anyhow::anyhow!("Device id does not match public key"));
+    }
+    let signature_bytes: [u8; 64] = BASE64
+        .decode(&body.signature)?
+        .try_into()
+        .map_err(|_| anyhow::anyhow!("Invalid device signature length"))?;
+    key.verify(
+        &self_signed_message(&body.device_id, &key),
+        &Signature::from_bytes(&signature_bytes),
+    )?;
+    Ok(key)
+}
+
+fn emit_changed(events: &dyn EventBus, device_id: &str, verified: bool) {
+    events.emit(
+        DEVICE_VERIFICATION_CHANGED_EVENT,
+        DeviceVerificationChangedPayload {
+            device_id: device_id.to_string(),
+            verified,
+        },
+    );
+}
+
+async fn load_verified_devices() -> anyhow::Result<Vec<VerifiedDevice>> {
+    let db = get_db(SYSTEM_DB_KEY).await?;
+    let rows = db
+        .connection
+        .query_all(&RawStatement::new(
+            r#"
+            SELECT device_id, public_key, fingerprint, label, cross_signature, verified_at
+            FROM device_verifications ORDER BY verified_at ASC
+            "#
+            .to_string(),
+            Vec::new(),
+        ))
+        .await?;
+    let mut devices = Vec::with_capacity(rows.len());
+    for row in rows {
+        devices.push(VerifiedDevice {
+            device_id: row.try_get("", "device_id")?,
+            public_key: row.try_get("", "public_key")?,
+            fingerprint: row.try_get("", "fingerprint")?,
+            label: row.try_get("", "label")?,
+            cross_signature: row.try_get("", "cross_signature")?,
+            verified_at: row.try_get("", "verified_at")?,
+        });
+    }
+    Ok(devices)
+}
+
+#[tauri::command]
+/// 获取本机设备身份（首次调用时生成并保存身份密钥）。
+pub async fn get_device_identity() -> CommandResult<DeviceIdentity> {
+    let key = signing_key().await.map_err(|e| {
+        to_command_error("DEVICE_IDENTITY_FAILED", "error.device_identity_failed", e)
+    })?;
+    Ok(identity_for(&key.verifying_key()))
+}
+
+#[tauri::command]
+/// 导出本机验证载荷（供另一台设备扫码或粘贴）。
+pub async fn export_device_verification() -> CommandResult<DeviceVerificationExport> {
+    let key = signing_key().await.map_err(|e| {
+        to_command_error("DEVICE_IDENTITY_FAILED", "error.device_identity_failed", e)
+    })?;
+    Ok(DeviceVerificationExport {
+        payload: encode_payload(&key),
+        identity: identity_for(&key.verifying_key()),
+    })
+}
+
+#[tauri::command]
+/// 校验远端设备的验证载荷，交叉签名后保存为已验证。
+///
+/// # 参数
+/// - `payload`：远端导出的验证载荷。
+/// - `label`：可选设备名（只影响本地展示）。
+///
+/// # 返回值
+/// - `Ok(VerifiedDevice)`：保存后的验证记录。
+/// - `Err(DEVICE_VERIFICATION_INVALID)`：载荷格式错误或签名无效。
+/// - `Err(DEVICE_VERIFICATION_KEY_CHANGED)`：该设备 id 已以其他公钥验证过。
+pub async fn verify_device(
+    app: AppHandle,
+    payload: String,
+    label: Option<String>,
+) -> CommandResult<VerifiedDevice> {
+    verify_device_with(&payload, label, &app).await
+}
+
+/// [`verify_device`] 的核心逻辑（不依赖 Tauri，变化事件经 `events` 投递）。
+pub async fn verify_device_with(
+    payload: &str,
+    label: Option<String>,
+    events: &dyn EventBus,
+) -> CommandResult<VerifiedDevice> {
+    let remote = decode_payload(payload).map_err(|e| {
+        to_command_error(
+            "DEVICE_VERIFICATION_INVALID",
+            "error.device_verification_invalid",
+            e,
+        )
+    })?;
+    let label = label
+        .map(|l| l.trim().chars().take(MAX_LABEL_CHARS).collect::<String>())
+        .filter(|l| !l.is_empty());
+    let map_err = |e: anyhow::Error| {
+        to_command_error(
+            "DEVICE_VERIFICATION_SAVE_FAILED",
+            "error.device_verification_save_failed",
+            e,
+        )
+    };
+    let local = signing_key().await.map_err(map_err)?;
+    let identity = identity_for(&remote);
+    if identity.device_id == device_id_for(&local.verifying_key()) {
+        return Err(command_error(
+            "DEVICE_VERIFICATION_INVALID",
+            "error.device_verification_invalid",
+        ));
+    }
+    if let Some(existing) = load_verified_devices()
+        .await
+        .map_err(map_err)?
+        .into_iter()
+        .find(|d| d.device_id == identity.device_id)
+        && existing.public_key != identity.public_key
+    {
+        tracing::warn!(action = "db_device_verification_key_changed", device_id = %identity.device_id);
+        return Err(command_error(
+            "DEVICE_VERIFICATION_KEY_CHANGED",
+            "error.device_verification_key_changed",
+        ));
+    }
+
+    let device = VerifiedDevice {
+        cross_signature: BASE64.encode(local.sign(remote.as_bytes()).to_bytes()),
+        device_id: identity.device_id,
+        public_key: identity.public_key,
+        fingerprint: identity.fingerprint,
+        label,
+        verified_at: now_ms(),
+    };
+    let db = get_db(SYSTEM_DB_KEY).await.map_err(map_err)?;
+    db.connection
+        .execute(&RawStatement::new(
+            r#"
+            INSERT INTO device_verifications
+                (device_id, public_key, fingerprint, label, cross_signature, verified_at)
+            VALUES (?1, ?2, ?3, ?4, ?5, ?6)
+            ON CONFLICT(device_id) DO UPDATE SET
+                label = excluded.label,
+                cross_signature = excluded.cross_signature,
+                verified_at = excluded.verified_at
+            "#
+            .to_string(),
+            vec![
+                Value::from(device.device_id.as_str()),
+                Value::from(device.public_key.as_str()),
+                Value::from(device.fingerprint.as_str()),
+                Value::from(device.label.clone()),
+                Value::from(device.cross_signature.as_str()),
+                Value::from(device.verified_at),
+            ],
+        ))
+        .await
+        .map_err(|e| map_err(e.into()))?;
+    tracing::info!(action = "db_device_verified", device_id = %device.device_id);
+    emit_changed(events, &device.device_id, true);
+    Ok(device)
+}
+
+#[tauri::command]
+/// 列出已验证的远端设备（按验证时间升序）。
+pub async fn list_verified_devices() -> CommandResult<Vec<VerifiedDevice>> {
+    load_verified_devices().await.map_err(|e| {
+        to_command_error(
+            "DEVICE_VERIFICATION_LOAD_FAILED",
+            "error.device_verification_load_failed",
+            e,
+        )
+    })
+}
+
+#[tauri::command]
+/// 撤销设备验证。
+///
+/// # 返回值
+/// - `Ok(true)`：已撤销；`Ok(false)`：原本未验证。
+pub async fn revoke_device_verification(app: AppHandle, device_id: String) -> CommandResult<bool> {
+    revoke_device_verification_with(&device_id, &app).await
+}
+
+/// [`revoke_device_verification`] 的核心逻辑（不依赖 Tauri）。
+pub async fn revoke_device_verification_with(
+    device_id: &str,
+    events: &dyn EventBus,
+) -> CommandResult<bool> {
+    let map_err = |e: anyhow::Error| {
+        to_command_error(
+            "DEVICE_VERIFICATION_SAVE_FAILED",
+            "error.device_verification_save_failed",
+            e,
+        )
+    };
+    let db = get_db(SYSTEM_DB_KEY).await.map_err(map_err)?;
+    let removed = db
+        .connection
+        .execute(&RawStatement::new(
+            "DELETE FROM device_verifications WHERE device_id = ?".to_string(),
+            vec![Value::from(device_id.trim())],
+        ))
+        .await
+        .map_err(|e| map_err(e.into()))?
+        .rows_affected()
+        > 0;
+    tracing::info!(action = "db_device_verification_revoked", device_id = %device_id, removed);
+    if removed {
+        emit_changed(events, device_id.trim(), false);
+    }
+    Ok(removed)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn payload_round_trips_and_rejects_tampering() {
+        let key = SigningKey::from_bytes(&[5u8; 32]);
+        let payload = encode_payload(&key);
+        assert!(payload.starts_with(PAYLOAD_PREFIX));
+        assert_eq!(decode_payload(&payload).unwrap(), key.verifying_key());
+
+        // 替换为另一把公钥（设备 id 同步替换）后自签名不再成立。
+        let other = SigningKey::from_bytes(&[6u8; 32]).verifying_key();
+        let body = PayloadBody {
+            device_id: device_id_for(&other),
+            public_key: BASE64.encode(other.as_bytes()),
+            signature: serde_json::from_slice::<PayloadBody>(
+                &URL_SAFE_NO_PAD
+                    .decode(payload.strip_prefix(PAYLOAD_PREFIX).unwrap())
+                    .unwrap(),
+            )
+            .unwrap()
+            .signature,
+        };
+        let forged = format!(
+            "{PAYLOAD_PREFIX}{}",
+            URL_SAFE_NO_PAD.encode(serde_json::to_vec(&body).unwrap())
+        );
+        assert!(decode_payload(&forged).is_err());
+        assert!(decode_payload("not-a-payload").is_err());
+    }
+
+    #[test]
+    fn identity_is_stable_for_a_key() {
+        let key = SigningKey::from_bytes(&[9u8; 32]).verifying_key();
+        let identity = identity_for(&key);
+        assert_eq!(identity, identity_for(&key));
+        assert_eq!(identity.device_id.len(), 16);
+        assert_eq!(identity.fingerprint.split(' ').count(), 10);
+        assert!(
+            identity
+                .fingerprint
+                .split(' ')
+                .all(|group| group.len() == 5 && group.chars().all(|c| c.is_ascii_digit()))
+        );
+    }
+}
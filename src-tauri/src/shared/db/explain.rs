@@ -0,0 +1,236 @@
+//! shared｜数据库：查询计划检查与慢查询日志（开发辅助）。
+//!
+//! 说明：
+//! - `db_explain` 仅在 debug 构建可用：返回 `EXPLAIN QUERY PLAN` 结果，并将查询重复执行
+//!   N 次统计耗时（只接受只读查询，避免重复写入）；
+//! - `db_query` / `db_execute` 执行后调用 [`report_if_slow`]：超过阈值（设置
+//!   `db_slow_query_threshold_ms`）时在后台取得查询计划并以 warn 级别记录。
+//!
+//! 约定：注释中文，日志英文（tracing）。
+
+use std::time::{Duration, Instant};
+
+use sea_orm::ConnectionTrait;
+use serde::Serialize;
+
+use crate::shared::error::{CommandResult, command_error, to_command_error};
+
+use super::commands::{DbValue, RawStatement, map_values, validate_query_sql};
+use super::{get_db, get_read_db};
+
+/// 默认慢查询阈值。
+const DEFAULT_SLOW_QUERY_MS: u32 = 200;
+
+/// 低于该耗时的语句不读取配置，避免为每条快查询增加开销。
+const SLOW_QUERY_FLOOR: Duration = Duration::from_millis(20);
+
+/// 默认计时次数。
+const DEFAULT_RUNS: u32 = 5;
+
+/// 最大计时次数。
+const MAX_RUNS: u32 = 100;
+
+/// 日志中 SQL 文本的最大长度（字符数）。
+const MAX_LOGGED_SQL_CHARS: usize = 2000;
+
+/// 查询计划中的一步（`EXPLAIN QUERY PLAN` 的一行）。
+#[derive(Debug, Clone, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct QueryPlanStep {
+    pub id: i64,
+    pub parent: i64,
+    pub detail: String,
+}
+
+/// `db_explain` 的结果。
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DbExplainResult {
+    pub plan: Vec<QueryPlanStep>,
+    /// 按层级缩进后的计划文本（与 sqlite3 命令行输出一致）。
+    pub plan_text: String,
+    pub runs: u32,
+    /// 最后一次执行返回的行数。
+    pub rows: usize,
+    pub min_ms: f64,
+    pub avg_ms: f64,
+    pub max_ms: f64,
+}
+
+/// 将计划格式化为树形文本（按 `parent` 缩进）。
+fn format_plan(plan: &[QueryPlanStep]) -> String {
+    let mut lines = Vec::with_capacity(plan.len());
+    let mut depths: Vec<(i64, usize)> = Vec::new();
+    for step in plan {
+        let depth = depths
+            .iter()
+            .rev()
+            .find(|(id, _)| *id == step.parent)
+            .map(|(_, depth)| depth + 1)
+            .unwrap_or(0);
+        depths.push((step.id, depth));
+        lines.push(format!("{}{}", "  ".repeat(depth), step.detail));
+    }
+    lines.join("\n")
+}
+
+/// 统计耗时：`(min, avg, max)`，单位毫秒。
+fn summarize(samples: &[Duration]) -> (f64, f64, f64) {
+    if samples.is_empty() {
+        return (0.0, 0.0, 0.0);
+    }
+    let ms: Vec<f64> = samples.iter().map(|d| d.as_secs_f64() * 1000.0).collect();
+    let min = ms.iter().copied().fold(f64::INFINITY, f64::min);
+    let max = ms.iter().copied().fold(0.0, f64::max);
+    (min, ms.iter().sum::<f64>() / ms.len() as f64, max)
+}
+
+async fn query_plan(key: &str, stmt: &RawStatement) -> anyhow::Result<Vec<QueryPlanStep>> {
+    let db = get_db(key).await?;
+    let rows = db.connection.query_all(&stmt.explain_query_plan()).await?;
+    let mut plan = Vec::with_capacity(rows.len());
+    for row in rows {
+        plan.push(QueryPlanStep {
+            id: row.try_get("", "id")?,
+            parent: row.try_get("", "parent")?,
+            detail: row.try_get("", "detail")?,
+        });
+    }
+    Ok(plan)
+}
+
+/// 语句耗时超过阈值时在后台记录查询计划。
+pub(super) fn report_if_slow(key: &str, stmt: &RawStatement, elapsed: Duration) {
+    if elapsed < SLOW_QUERY_FLOOR {
+        return;
+    }
+    let key = key.to_string();
+    let stmt = stmt.clone();
+    tauri::async_runtime::spawn(async move {
+        let configured = crate::features::settings::get_config_value::<u32>(String::from(
+            "db_slow_query_threshold_ms",
+        ))
+        .await;
+        let threshold = if configured == 0 {
+            DEFAULT_SLOW_QUERY_MS
+        } else {
+            configured
+        };
+        if elapsed < Duration::from_millis(u64::from(threshold)) {
+            return;
+        }
+        let plan = match query_plan(&key, &stmt).await {
+            Ok(plan) => format_plan(&plan),
+            Err(e) => format!("<unavailable: {e}>"),
+        };
+        let sql: String = stmt.sql().chars().take(MAX_LOGGED_SQL_CHARS).collect();
+        tracing::warn!(
+            action = "db_slow_query",
+            key = %key,
+            elapsed_ms = elapsed.as_millis() as u64,
+            threshold_ms = threshold,
+            sql = %sql,
+            plan = %plan
+        );
+    });
+}
+
+#[tauri::command]
+/// 返回查询计划并统计多次执行的耗时（仅 debug 构建可用）。
+///
+/// # 参数
+/// - `key`：数据库连接 key（由 `db_init` 初始化）。
+/// - `sql`：只读查询（`SELECT` / `WITH`）。
+/// - `params`：SQL 参数（可选）。
+/// - `runs`：计时次数（默认 5，最大 100）。
+///
+/// # 返回值
+/// - `Ok(DbExplainResult)`：查询计划与耗时统计。
+/// - `Err(CommandError)`：release 构建、SQL 非只读或执行失败。
+pub async fn db_explain(
+    key: String,
+    sql: String,
+    params: Option<Vec<DbValue>>,
+    runs: Option<u32>,
+) -> CommandResult<DbExplainResult> {
+    if !cfg!(debug_assertions) {
+        return Err(command_error(
+            "DB_EXPLAIN_DEV_ONLY",
+            "error.db_explain_dev_only",
+        ));
+    }
+    validate_query_sql(&sql)?;
+    let map_err =
+        |e: anyhow::Error| to_command_error("DB_EXPLAIN_FAILED", "error.db_explain_failed", e);
+    let stmt = RawStatement::new(sql, map_values(params));
+    let plan = query_plan(&key, &stmt).await.map_err(map_err)?;
+
+    let db = get_read_db(&key).await.map_err(map_err)?;
+    let runs = runs.unwrap_or(DEFAULT_RUNS).clamp(1, MAX_RUNS);
+    let mut samples = Vec::with_capacity(runs as usize);
+    let mut rows = 0;
+    for _ in 0..runs {
+        let started = Instant::now();
+        rows = db
+            .connection
+            .query_all(&stmt)
+            .await
+            .map_err(|e| map_err(e.into()))?
+            .len();
+        samples.push(started.elapsed());
+    }
+    let (min_ms, avg_ms, max_ms) = summarize(&samples);
+    Ok(DbExplainResult {
+        plan_text: format_plan(&plan),
+        plan,
+        runs,
+        rows,
+        min_ms,
+        avg_ms,
+        max_ms,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn step(id: i64, parent: i64, detail: &str) -> QueryPlanStep {
+        QueryPlanStep {
+            id,
+            parent,
+            detail: detail.to_string(),
+        }
+    }
+
+    #[test]
+    fn plan_is_indented_by_parent() {
+        let plan = vec![
+            step(2, 0, "CO-ROUTINE m"),
+            step(5, 2, "SCAN messages"),
+            step(12, 0, "SCAN c"),
+            step(
+                14,
+                0,
+                "SEARCH m USING AUTOMATIC COVERING INDEX (channel_id=?)",
+            ),
+        ];
+        assert_eq!(
+            format_plan(&plan),
+            "CO-ROUTINE m\n  SCAN messages\nSCAN c\nSEARCH m USING AUTOMATIC COVERING INDEX (channel_id=?)"
+        );
+    }
+
+    #[test]
+    fn timings_are_summarized_in_millis() {
+        assert_eq!(summarize(&[]), (0.0, 0.0, 0.0));
+        let (min, avg, max) = summarize(&[
+            Duration::from_millis(2),
+            Duration::from_millis(4),
+            Duration::from_millis(9),
+        ]);
+        assert!((min - 2.0).abs() < 1e-9);
+        assert!((avg - 5.0).abs() < 1e-9);
+        assert!((max - 9.0).abs() < 1e-9);
+    }
+}
@@ -0,0 +1,350 @@
+//! shared｜数据库：跨 server 的频道收藏（system DB `favorites` 表）。
+//!
+//! 说明：
+//! - 收藏按 `(server_socket, channel_id)` 唯一，可设置别名（只影响本地展示）；
+//! - `list_favorites_with_state` 逐个到对应 server DB 读取频道名、未读数与最近活跃时间；
+//!   server DB 未打开时该项 `available = false`，其余字段为空；
+//! - 未读数基于 server DB `channel_read_state` 缓存的已读时间（由前端在上报已读后同步），
+//!   不计入被屏蔽用户、被静音规则命中的消息与发件箱中的临时消息；
+//! - 收藏增删后重新发布跳转列表 / Dock 菜单（见 `shared::jump_list`）。
+//!
+//! 约定：注释中文，日志英文（tracing）。
+
+use sea_orm::{ConnectionTrait, Value};
+use serde::Serialize;
+
+use crate::shared::error::{CommandResult, command_error, to_command_error};
+
+use super::commands::{RawStatement, is_server_db_key};
+use super::get_db;
+
+/// 收藏所在的 DB key（应用级 system DB）。
+const SYSTEM_DB_KEY: &str = "system";
+
+/// 别名最大长度（字符数）。
+pub const MAX_ALIAS_CHARS: usize = 64;
+
+const CHANNEL_STATE_SQL: &str = r#"
+    SELECT
+        (SELECT name FROM channels WHERE id = ?1) AS name,
+        (
+            SELECT COUNT(*) FROM messages
+            WHERE channel_id = ?1
+              AND status = 'sent'
+              AND COALESCE(muted, 0) = 0
+              AND user_id NOT IN (SELECT user_id FROM blocked_users)
+              AND created_at > COALESCE(
+                  (SELECT last_read_time FROM channel_read_state WHERE channel_id = ?1), 0)
+        ) AS unread_count,
+        (SELECT MAX(created_at) FROM messages WHERE channel_id = ?1 AND status = 'sent')
+            AS last_active_at
+"#;
+
+/// 收藏记录。
+#[derive(Debug, Clone, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct Favorite {
+    pub id: i64,
+    pub server_socket: String,
+    pub db_key: String,
+    pub channel_id: i64,
+    pub alias: Option<String>,
+    pub position: i64,
+    pub created_at: i64,
+}
+
+/// 带频道状态的收藏（用于统一收藏栏）。
+#[derive(Debug, Clone, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct FavoriteWithState {
+    #[serde(flatten)]
+    pub favorite: Favorite,
+    /// 对应 server DB 是否已打开。
+    pub available: bool,
+    /// 频道名（本地未缓存时为空）。
+    pub channel_name: Option<String>,
+    pub unread_count: i64,
+    pub last_active_at: Option<i64>,
+}
+
+fn now_ms() -> i64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_millis() as i64
+}
+
+/// 规范化别名：去除首尾空白，空串视为未设置。
+fn normalize_alias(alias: Option<String>) -> CommandResult<Option<String>> {
+    let Some(alias) = alias
+        .map(|a| a.trim().to_string())
+        .filter(|a| !a.is_empty())
+    else {
+        return Ok(None);
+    };
+    if alias.chars().count() > MAX_ALIAS_CHARS {
+        return Err(command_error(
+            "DB_FAVORITE_ALIAS_TOO_LONG",
+            "error.db_favorite_alias_too_long",
+        ));
+    }
+    Ok(Some(alias))
+}
+
+fn validate_target(server_socket: &str, db_key: Option<&str>) -> CommandResult<()> {
+    if server_socket.is_empty() {
+        return Err(command_error(
+            "DB_FAVORITE_SERVER_SOCKET_REQUIRED",
+            "error.db_favorite_server_socket_required",
+        ));
+    }
+    match db_key {
+        Some(key) if !is_server_db_key(key) => {
+            Err(command_error("DB_KEY_INVALID", "error.db_key_invalid"))
+        }
+        _ => Ok(()),
+    }
+}
+
+async fn load_favorites() -> anyhow::Result<Vec<Favorite>> {
+    let db = get_db(SYSTEM_DB_KEY).await?;
+    let rows = db
+        .connection
+        .query_all(&RawStatement::new(
+            r#"
+            SELECT id, server_socket, db_key, channel_id, alias, position, created_at
+            FROM favorites ORDER BY position ASC, id ASC
+            "#
+            .to_string(),
+            Vec::new(),
+        ))
+        .await?;
+    let mut favorites = Vec::with_capacity(rows.len());
+    for row in rows {
+        favorites.push(Favorite {
+            id: row.try_get("", "id")?,
+            server_socket: row.try_get("", "server_socket")?,
+            db_key: row.try_get("", "db_key")?,
+            channel_id: row.try_get("", "channel_id")?,
+            alias: row.try_get("", "alias")?,
+            position: row.try_get("", "position")?,
+            created_at: row.try_get("", "created_at")?,
+        });
+    }
+    Ok(favorites)
+}
+
+/// 读取单个收藏的频道状态；server DB 未打开时返回 `available = false`。
+async fn load_state(favorite: Favorite) -> FavoriteWithState {
+    let mut state = FavoriteWithState {
+        favorite,
+        available: false,
+        channel_name: None,
+        unread_count: 0,
+        last_active_at: None,
+    };
+    let Ok(db) = get_db(&state.favorite.db_key).await else {
+        return state;
+    };
+    let row = db
+        .connection
+        .query_one(&RawStatement::new(
+            CHANNEL_STATE_SQL.to_string(),
+            vec![Value::from(state.favorite.channel_id)],
+        ))
+        .await;
+    match row {
+        Ok(Some(row)) => {
+            state.available = true;
+            state.channel_name = row.try_get("", "name").ok().flatten();
+            state.unread_count = row.try_get("", "unread_count").unwrap_or(0);
+            state.last_active_at = row.try_get("", "last_active_at").ok().flatten();
+        }
+        Ok(None) => state.available = true,
+        Err(e) => {
+            tracing::warn!(
+                action = "db_favorite_state_load_failed",
+                channel_id = state.favorite.channel_id,
+                error = %e
+            );
+        }
+    }
+    state
+}
+
+#[tauri::command]
+/// 收藏频道；已收藏时更新别名（位置不变）。
+///
+/// # 参数
+/// - `server_socket`：服务器 socket。
+/// - `db_key`：对应的 server DB key（`server_<sha256>`）。
+/// - `channel_id`：频道 id。
+/// - `alias`：可选别名；为空表示使用频道名。
+///
+/// # 返回值
+/// - `Ok(Favorite)`：保存后的收藏记录。
+/// - `Err(CommandError)`：参数非法或写入失败。
+pub async fn add_favorite(
+    server_socket: String,
+    db_key: String,
+    channel_id: i64,
+    alias: Option<String>,
+) -> CommandResult<Favorite> {
+    let server_socket = server_socket.trim().to_string();
+    validate_target(&server_socket, Some(&db_key))?;
+    let alias = normalize_alias(alias)?;
+    let map_err = |e: anyhow::Error| {
+        to_command_error(
+            "DB_FAVORITE_SAVE_FAILED",
+            "error.db_favorite_save_failed",
+            e,
+        )
+    };
+    let db = get_db(SYSTEM_DB_KEY).await.map_err(map_err)?;
+    db.connection
+        .execute(&RawStatement::new(
+            r#"
+            INSERT INTO favorites (server_socket, db_key, channel_id, alias, position, created_at)
+            VALUES (?1, ?2, ?3, ?4, (SELECT COALESCE(MAX(position), -1) + 1 FROM favorites), ?5)
+            ON CONFLICT(server_socket, channel_id) DO UPDATE SET
+                db_key = excluded.db_key,
+                alias = excluded.alias
+            "#
+            .to_string(),
+            vec![
+                Value::from(server_socket.as_str()),
+                Value::from(db_key.as_str()),
+                Value::from(channel_id),
+                Value::from(alias),
+                Value::from(now_ms()),
+            ],
+        ))
+        .await
+        .map_err(|e| map_err(e.into()))?;
+    tracing::info!(action = "db_favorite_saved", channel_id);
+    crate::shared::jump_list::schedule_refresh();
+    load_favorites()
+        .await
+        .map_err(map_err)?
+        .into_iter()
+        .find(|f| f.server_socket == server_socket && f.channel_id == channel_id)
+        .ok_or_else(|| command_error("DB_FAVORITE_SAVE_FAILED", "error.db_favorite_save_failed"))
+}
+
+#[tauri::command]
+/// 取消收藏。
+///
+/// # 返回值
+/// - `Ok(true)`：已删除；`Ok(false)`：原本未收藏。
+pub async fn remove_favorite(server_socket: String, channel_id: i64) -> CommandResult<bool> {
+    let server_socket = server_socket.trim().to_string();
+    validate_target(&server_socket, None)?;
+    let map_err = |e: anyhow::Error| {
+        to_command_error(
+            "DB_FAVORITE_REMOVE_FAILED",
+            "error.db_favorite_remove_failed",
+            e,
+        )
+    };
+    let db = get_db(SYSTEM_DB_KEY).await.map_err(map_err)?;
+    let removed = db
+        .connection
+        .execute(&RawStatement::new(
+            "DELETE FROM favorites WHERE server_socket = ? AND channel_id = ?".to_string(),
+            vec![Value::from(server_socket.as_str()), Value::from(channel_id)],
+        ))
+        .await
+        .map_err(|e| map_err(e.into()))?
+        .rows_affected()
+        > 0;
+    tracing::info!(action = "db_favorite_removed", channel_id, removed);
+    if removed {
+        crate::shared::jump_list::schedule_refresh();
+    }
+    Ok(removed)
+}
+
+#[tauri::command]
+/// 列出所有收藏，并附带各 server DB 中的频道状态（按收藏顺序）。
+pub async fn list_favorites_with_state() -> CommandResult<Vec<FavoriteWithState>> {
+    let favorites = load_favorites().await.map_err(|e| {
+        to_command_error(
+            "DB_FAVORITES_LOAD_FAILED",
+            "error.db_favorites_load_failed",
+            e,
+        )
+    })?;
+    let mut items = Vec::with_capacity(favorites.len());
+    for favorite in favorites {
+        items.push(load_state(favorite).await);
+    }
+    Ok(items)
+}
+
+#[tauri::command]
+/// 缓存频道已读位置（前端上报服务端已读后调用），只会前移。
+///
+/// # 参数
+/// - `db_key`：server DB key（`server_<sha256>`），必须已打开。
+/// - `channel_id`：频道 id。
+/// - `last_read_time`：已读到的消息时间（毫秒时间戳）。
+pub async fn set_channel_read_state(
+    db_key: String,
+    channel_id: i64,
+    last_read_time: i64,
+) -> CommandResult<()> {
+    if !is_server_db_key(&db_key) {
+        return Err(command_error("DB_KEY_INVALID", "error.db_key_invalid"));
+    }
+    let map_err = |e: anyhow::Error| {
+        to_command_error(
+            "DB_READ_STATE_SAVE_FAILED",
+            "error.db_read_state_save_failed",
+            e,
+        )
+    };
+    let db = get_db(&db_key).await.map_err(map_err)?;
+    db.connection
+        .execute(&RawStatement::new(
+            r#"
+            INSERT INTO channel_read_state (channel_id, last_read_time, updated_at)
+            VALUES (?1, ?2, ?3)
+            ON CONFLICT(channel_id) DO UPDATE SET
+                last_read_time = excluded.last_read_time,
+                updated_at = excluded.updated_at
+            WHERE excluded.last_read_time > channel_read_state.last_read_time
+            "#
+            .to_string(),
+            vec![
+                Value::from(channel_id),
+                Value::from(last_read_time),
+                Value::from(now_ms()),
+            ],
+        ))
+        .await
+        .map_err(|e| map_err(e.into()))?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn blank_alias_is_cleared() {
+        assert_eq!(normalize_alias(None).unwrap(), None);
+        assert_eq!(normalize_alias(Some("   ".to_string())).unwrap(), None);
+        assert_eq!(
+            normalize_alias(Some("  general ".to_string())).unwrap(),
+            Some("general".to_string())
+        );
+    }
+
+    #[test]
+    fn alias_length_counts_chars() {
+        let ok = "频".repeat(MAX_ALIAS_CHARS);
+        assert_eq!(normalize_alias(Some(ok.clone())).unwrap(), Some(ok));
+        let err = normalize_alias(Some("a".repeat(MAX_ALIAS_CHARS + 1))).unwrap_err();
+        assert_eq!(err.code, "DB_FAVORITE_ALIAS_TOO_LONG");
+    }
+}
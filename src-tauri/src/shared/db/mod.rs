@@ -8,7 +8,8 @@ use sea_orm::{ConnectOptions, ConnectionTrait, Database, DatabaseConnection};
 use std::collections::HashMap;
 use std::path::{Path, PathBuf};
 use std::sync::{Arc, OnceLock};
-use tokio::sync::RwLock;
+use std::time::{Duration, Instant};
+use tokio::sync::{Mutex, RwLock};
 
 use crate::features::settings::get_config_value;
 
@@ -65,6 +66,44 @@ impl CPDatabase {
             connection: Database::connect(options).await?,
         })
     }
+
+    /// 创建只读连接（用于搜索/导出等长查询，不与写入方争用主连接池）。
+    ///
+    /// # 说明
+    /// - URL 使用 `mode=ro`，每个连接额外设置 `query_only` pragma；
+    /// - 连接池固定为较小规模，空闲连接很快回收。
+    pub async fn new_read_only(url: &str) -> anyhow::Result<Self> {
+        let mut options = ConnectOptions::new(url);
+        options
+            .max_connections(READ_ONLY_MAX_CONNECTIONS)
+            .min_connections(0)
+            .connect_timeout(std::time::Duration::from_secs(3))
+            .idle_timeout(std::time::Duration::from_secs(10))
+            .max_lifetime(std::time::Duration::from_secs(3600));
+        let options = options.map_sqlx_sqlite_opts(|opts| {
+            opts.read_only(true)
+                .pragma("query_only", "ON")
+                .pragma("busy_timeout", "5000")
+        });
+        Ok(Self {
+            connection: Database::connect(options).await?,
+        })
+    }
+}
+
+/// 只读连接池的最大连接数。
+const READ_ONLY_MAX_CONNECTIONS: u32 = 2;
+
+/// 只读连接空闲多久后关闭。
+const READ_ONLY_IDLE_TIMEOUT: Duration = Duration::from_secs(60);
+
+/// 只读连接空闲检查间隔。
+const READ_ONLY_SWEEP_INTERVAL: Duration = Duration::from_secs(15);
+
+/// 按需打开的只读连接。
+struct ReadOnlyDb {
+    db: Arc<CPDatabase>,
+    last_used: Instant,
 }
 
 /// 已注册数据库条目（包含连接与对应的文件路径）。
@@ -73,6 +112,28 @@ pub struct DbEntry {
     pub db: Arc<CPDatabase>,
     /// 数据库文件路径（用于展示/删除等）。
     pub path: PathBuf,
+    /// 按需打开的只读连接（见 `get_read_db`）。
+    read_only: Mutex<Option<ReadOnlyDb>>,
+}
+
+impl DbEntry {
+    fn new(db: CPDatabase, path: PathBuf) -> Self {
+        Self {
+            db: Arc::new(db),
+            path,
+            read_only: Mutex::new(None),
+        }
+    }
+
+    /// 关闭只读连接（若已打开）。
+    async fn close_read_only(&self) {
+        let read_only = self.read_only.lock().await.take();
+        if let Some(read_only) = read_only
+            && let Err(e) = read_only.db.connection.clone().close().await
+        {
+            tracing::warn!(action = "db_read_only_close_failed", error = %e);
+        }
+    }
 }
 
 #[derive(Default)]
@@ -151,11 +212,8 @@ pub async fn connect_named(key: &str, path: PathBuf) -> anyhow::Result<()> {
         tracing::warn!(action = "db_pragma_set_failed", error = %e);
     }
 
-    let entry = DbEntry {
-        db: Arc::new(db),
-        path,
-    };
-    lock.map.insert(key.to_string(), Arc::new(entry));
+    lock.map
+        .insert(key.to_string(), Arc::new(DbEntry::new(db, path)));
     Ok(())
 }
 
@@ -176,6 +234,93 @@ pub async fn get_db(key: &str) -> anyhow::Result<Arc<CPDatabase>> {
         .ok_or_else(|| anyhow!("Database not initialized for key: {}", key))
 }
 
+/// 获取指定 key 对应的只读连接（首次调用时打开）。
+///
+/// # 参数
+/// - `key`：数据库连接 key（主连接必须已由 `connect_named` 打开）。
+///
+/// # 返回值
+/// - `Ok(Arc<CPDatabase>)`：只读连接；打开失败时回退到主连接。
+/// - `Err(anyhow::Error)`：key 未初始化。
+///
+/// # 说明
+/// - 用于搜索/导出等长查询，避免与写入方争用主连接池；
+/// - 空闲超过 `READ_ONLY_IDLE_TIMEOUT` 且没有调用方持有时由后台任务关闭。
+pub async fn get_read_db(key: &str) -> anyhow::Result<Arc<CPDatabase>> {
+    let entry = get_entry(key).await?;
+    let mut read_only = entry.read_only.lock().await;
+    if let Some(existing) = read_only.as_mut() {
+        existing.last_used = Instant::now();
+        return Ok(existing.db.clone());
+    }
+    let url = sqlite_read_only_url_for_path(&entry.path);
+    match CPDatabase::new_read_only(&url).await {
+        Ok(db) => {
+            let db = Arc::new(db);
+            *read_only = Some(ReadOnlyDb {
+                db: db.clone(),
+                last_used: Instant::now(),
+            });
+            ensure_read_only_sweeper();
+            tracing::debug!(action = "db_read_only_opened", key = %key);
+            Ok(db)
+        }
+        Err(e) => {
+            tracing::warn!(action = "db_read_only_open_failed", key = %key, error = %e);
+            Ok(entry.db.clone())
+        }
+    }
+}
+
+/// 启动只读连接空闲回收任务（进程内只启动一次）。
+fn ensure_read_only_sweeper() {
+    static STARTED: OnceLock<()> = OnceLock::new();
+    STARTED.get_or_init(|| {
+        tauri::async_runtime::spawn(async {
+            let mut ticker = tokio::time::interval(READ_ONLY_SWEEP_INTERVAL);
+            loop {
+                ticker.tick().await;
+                sweep_idle_read_only().await;
+            }
+        });
+    });
+}
+
+/// 关闭空闲且无人持有的只读连接。
+async fn sweep_idle_read_only() {
+    let entries: Vec<(String, Arc<DbEntry>)> = {
+        let registry = init_db_registry();
+        let lock = registry.read().await;
+        lock.map
+            .iter()
+            .map(|(key, entry)| (key.clone(), entry.clone()))
+            .collect()
+    };
+    for (key, entry) in entries {
+        let idle = {
+            let Ok(mut read_only) = entry.read_only.try_lock() else {
+                continue;
+            };
+            match read_only.as_ref() {
+                Some(existing)
+                    if existing.last_used.elapsed() >= READ_ONLY_IDLE_TIMEOUT
+                        && Arc::strong_count(&existing.db) == 1 =>
+                {
+                    read_only.take()
+                }
+                _ => None,
+            }
+        };
+        if let Some(idle) = idle {
+            if let Err(e) = idle.db.connection.clone().close().await {
+                tracing::warn!(action = "db_read_only_close_failed", key = %key, error = %e);
+            } else {
+                tracing::debug!(action = "db_read_only_closed", key = %key);
+            }
+        }
+    }
+}
+
 /// 获取指定 key 对应的数据库条目（含路径信息）。
 ///
 /// # 参数
@@ -193,6 +338,15 @@ pub async fn get_entry(key: &str) -> anyhow::Result<Arc<DbEntry>> {
         .ok_or_else(|| anyhow!("Database not initialized for key: {}", key))
 }
 
+/// 列出当前已注册的数据库 key（按字典序）。
+pub async fn registered_keys() -> Vec<String> {
+    let registry = init_db_registry();
+    let lock = registry.read().await;
+    let mut keys: Vec<String> = lock.map.keys().cloned().collect();
+    keys.sort();
+    keys
+}
+
 /// 关闭并移除指定 key 的数据库连接。
 ///
 /// # 参数
@@ -208,6 +362,7 @@ pub async fn close_db(key: &str) -> anyhow::Result<()> {
         lock.map.remove(key)
     };
     if let Some(entry) = entry {
+        entry.close_read_only().await;
         entry.db.connection.clone().close().await?;
     }
     Ok(())
@@ -230,6 +385,7 @@ pub async fn remove_db(key: &str) -> anyhow::Result<Option<PathBuf>> {
     };
     if let Some(entry) = entry {
         let path = entry.path.clone();
+        entry.close_read_only().await;
 
         // WAL 模式下，先执行 checkpoint 确保 -wal / -shm 文件被回收，
         // 避免 Windows 上因文件仍被锁而导致删除失败。
@@ -272,5 +428,29 @@ pub(crate) fn sqlite_url_for_path(path: &Path) -> String {
     url
 }
 
+/// 只读连接使用的 URL（`mode=ro`，文件必须已存在）。
+fn sqlite_read_only_url_for_path(path: &Path) -> String {
+    let url = sqlite_url_for_path(path);
+    match url.strip_suffix("?mode=rwc") {
+        Some(base) => format!("{base}?mode=ro"),
+        None => url,
+    }
+}
+
+pub mod archive;
+pub mod backup;
+pub mod backup_crypto;
+pub mod backup_remote;
+pub mod blocked_users;
+pub mod channel_keys;
 pub mod commands;
+pub mod delta_sync;
+pub mod device_verification;
+pub mod explain;
+pub mod favorites;
+pub mod outbox;
+pub mod quick_switch;
+pub mod retention;
+pub mod sql_policy;
+pub mod user_profile;
 pub use commands::*;
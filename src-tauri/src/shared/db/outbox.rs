@@ -0,0 +1,560 @@
+//! shared｜数据库：乐观发送（本地回显 + server DB `outbox` 表）。
+//!
+//! 说明：
+//! - `send_message_optimistic` 先在同一事务内写入临时消息行（`id = pending:<client_id>`，
+//!   `status = pending`）与发件箱记录，随后在后台经 API 层发送；
+//! - 发送请求携带 `Idempotency-Key: <client_id>`，重试不会产生重复消息；
+//! - 服务端确认（响应中的 `mid`）到达后在事务内把临时行改写为正式 id；若该 mid 已由
+//!   WS 推送或增量同步先行写入，则直接删除临时行；
+//! - 状态变化通过 `message-status-changed` 事件通知前端（pending/sent/failed）；
+//!   发送失败的记录保留在发件箱中，可用 `retry_optimistic_message` 重发。
+//!
+//! 约定：注释中文，日志英文（tracing）。
+
+use std::collections::BTreeMap;
+use std::sync::Arc;
+
+use anyhow::Context;
+use sea_orm::{ConnectionTrait, TransactionTrait, Value};
+use serde::{Deserialize, Serialize};
+use tauri::AppHandle;
+
+use crate::features::network::data::http_client::ReqwestApiRequestAdapter;
+use crate::features::network::usecases::api_usecases::{self, ApiJsonRequest};
+use crate::shared::error::{CommandResult, command_error, to_command_error};
+use crate::shared::events::{EventBus, EventBusExt};
+
+use super::commands::{RawStatement, is_server_db_key};
+use super::get_db;
+
+/// 临时消息 id 前缀。
+const PROVISIONAL_ID_PREFIX: &str = "pending:";
+
+/// 乐观发送的消息状态。
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum MessageSendStatus {
+    Pending,
+    Sent,
+    Failed,
+}
+
+impl MessageSendStatus {
+    fn as_str(self) -> &'static str {
+        match self {
+            Self::Pending => "pending",
+            Self::Sent => "sent",
+            Self::Failed => "failed",
+        }
+    }
+}
+
+/// 待发送的消息（与 `POST /api/channels/{cid}/messages` 请求体一致）。
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OutgoingMessage {
+    pub domain: String,
+    pub domain_version: String,
+    pub data: serde_json::Value,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub reply_to_mid: Option<String>,
+}
+
+/// 服务端请求参数（与 `api_request_json` 的连接参数一致）。
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct OutboxSendOptions {
+    pub server_socket: String,
+    /// 请求头（通常包含鉴权 token；不会写入发件箱）。
+    pub headers: Option<BTreeMap<String, String>>,
+    pub tls_policy: Option<String>,
+    pub tls_fingerprint: Option<String>,
+}
+
+/// 已写入的临时消息。
+#[derive(Debug, Clone, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct ProvisionalMessage {
+    pub client_id: String,
+    /// 临时行的 `messages.id`（确认后被替换为服务端 mid）。
+    pub provisional_id: String,
+    pub created_at: i64,
+}
+
+/// `message-status-changed` 事件载荷。
+#[derive(Debug, Clone, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct MessageStatusChangedEvent {
+    pub db_key: String,
+    pub channel_id: i64,
+    pub client_id: String,
+    /// 服务端 mid（仅 `sent` 时存在）。
+    pub message_id: Option<String>,
+    pub status: MessageSendStatus,
+    pub error: Option<String>,
+}
+
+/// 服务端确认。
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SendAck {
+    pub mid: String,
+    pub send_time: Option<i64>,
+}
+
+/// 从发送响应中解析服务端确认（响应为完整 message，至少包含 `mid`）。
+pub fn parse_ack(body: Option<&serde_json::Value>) -> Option<SendAck> {
+    let body = body?;
+    let mid = body.get("mid")?.as_str()?.trim();
+    if mid.is_empty() {
+        return None;
+    }
+    Some(SendAck {
+        mid: mid.to_string(),
+        send_time: body.get("send_time").and_then(|v| v.as_i64()),
+    })
+}
+
+fn provisional_id(client_id: &str) -> String {
+    format!("{PROVISIONAL_ID_PREFIX}{client_id}")
+}
+
+fn now_ms() -> i64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_millis() as i64
+}
+
+fn validate_db_key(db_key: &str) -> CommandResult<()> {
+    if is_server_db_key(db_key) {
+        Ok(())
+    } else {
+        Err(command_error("DB_KEY_INVALID", "error.db_key_invalid"))
+    }
+}
+
+fn emit_status(events: &dyn EventBus, event: MessageStatusChangedEvent) {
+    events.emit("message-status-changed", event);
+}
+
+/// 发件箱中的一条记录。
+struct OutboxEntry {
+    channel_id: i64,
+    body: OutgoingMessage,
+}
+
+async fn load_entry(db_key: &str, client_id: &str) -> anyhow::Result<Option<OutboxEntry>> {
+    let db = get_db(db_key).await?;
+    let row = db
+        .connection
+        .query_one(&RawStatement::new(
+            "SELECT channel_id, body FROM outbox WHERE client_id = ?".to_string(),
+            vec![Value::from(client_id)],
+        ))
+        .await?;
+    let Some(row) = row else {
+        return Ok(None);
+    };
+    let body: String = row.try_get("", "body")?;
+    Ok(Some(OutboxEntry {
+        channel_id: row.try_get("", "channel_id")?,
+        body: serde_json::from_str(&body).context("Invalid outbox body")?,
+    }))
+}
+
+/// 用服务端确认替换临时行并移出发件箱（同一事务）。
+async fn reconcile(db_key: &str, client_id: &str, ack: &SendAck) -> anyhow::Result<()> {
+    let db = get_db(db_key).await?;
+    let txn = db.connection.begin().await?;
+    let pending_id = provisional_id(client_id);
+    let existing = txn
+        .query_one(&RawStatement::new(
+            "SELECT id FROM messages WHERE id = ?".to_string(),
+            vec![Value::from(ack.mid.as_str())],
+        ))
+        .await?;
+    if existing.is_some() {
+        // 正式消息已由推送或同步写入：只需丢弃临时行，并补上 client_id 便于前端对应。
+        txn.execute(&RawStatement::new(
+            "DELETE FROM messages WHERE id = ?".to_string(),
+            vec![Value::from(pending_id.as_str())],
+        ))
+        .await?;
+        txn.execute(&RawStatement::new(
+            "UPDATE messages SET client_id = ?, status = 'sent' WHERE id = ?".to_string(),
+            vec![Value::from(client_id), Value::from(ack.mid.as_str())],
+        ))
+        .await?;
+    } else {
+        txn.execute(&RawStatement::new(
+            r#"
+            UPDATE messages
+            SET id = ?, status = 'sent', created_at = COALESCE(?, created_at)
+            WHERE id = ?
+            "#
+            .to_string(),
+            vec![
+                Value::from(ack.mid.as_str()),
+                Value::from(ack.send_time),
+                Value::from(pending_id.as_str()),
+            ],
+        ))
+        .await?;
+    }
+    txn.execute(&RawStatement::new(
+        "DELETE FROM outbox WHERE client_id = ?".to_string(),
+        vec![Value::from(client_id)],
+    ))
+    .await?;
+    txn.commit().await?;
+    Ok(())
+}
+
+async fn mark_failed(db_key: &str, client_id: &str, error: &str) -> anyhow::Result<()> {
+    let db = get_db(db_key).await?;
+    let txn = db.connection.begin().await?;
+    txn.execute(&RawStatement::new(
+        "UPDATE messages SET status = 'failed' WHERE id = ?".to_string(),
+        vec![Value::from(provisional_id(client_id))],
+    ))
+    .await?;
+    txn.execute(&RawStatement::new(
+        "UPDATE outbox SET attempts = attempts + 1, last_error = ? WHERE client_id = ?".to_string(),
+        vec![Value::from(error), Value::from(client_id)],
+    ))
+    .await?;
+    txn.commit().await?;
+    Ok(())
+}
+
+async fn transmit(
+    entry: &OutboxEntry,
+    client_id: &str,
+    options: &OutboxSendOptions,
+) -> anyhow::Result<SendAck> {
+    let mut headers = options.headers.clone().unwrap_or_default();
+    headers.insert("Idempotency-Key".to_string(), client_id.to_string());
+    let api_request_port = ReqwestApiRequestAdapter::shared();
+    let response = api_usecases::api_request_json(
+        ApiJsonRequest {
+            server_socket: options.server_socket.clone(),
+            method: "POST".to_string(),
+            path: format!("/api/channels/{}/messages", entry.channel_id),
+            headers: Some(headers),
+            body: Some(serde_json::to_value(&entry.body)?),
+            tls_policy: options.tls_policy.clone(),
+            tls_fingerprint: options.tls_fingerprint.clone(),
+        },
+        api_request_port.as_ref(),
+    )
+    .await?;
+    if !response.ok {
+        anyhow::bail!("Send rejected with status {}", response.status);
+    }
+    parse_ack(response.body.as_ref()).context("Send response is missing mid")
+}
+
+/// 发送一条发件箱记录并根据结果更新临时行与状态事件。
+async fn deliver(
+    events: Arc<dyn EventBus>,
+    db_key: String,
+    client_id: String,
+    options: OutboxSendOptions,
+) {
+    let entry = match load_entry(&db_key, &client_id).await {
+        Ok(Some(entry)) => entry,
+        Ok(None) => return,
+        Err(e) => {
+            tracing::warn!(action = "db_outbox_load_failed", error = %e);
+            return;
+        }
+    };
+    let outcome = async {
+        let ack = transmit(&entry, &client_id, &options).await?;
+        reconcile(&db_key, &client_id, &ack).await?;
+        anyhow::Ok(ack)
+    }
+    .await;
+    let event = match outcome {
+        Ok(ack) => {
+            tracing::info!(
+                action = "db_outbox_message_sent",
+                channel_id = entry.channel_id
+            );
+            super::quick_switch::invalidate(&db_key);
+            MessageStatusChangedEvent {
+                db_key,
+                channel_id: entry.channel_id,
+                client_id,
+                message_id: Some(ack.mid),
+                status: MessageSendStatus::Sent,
+                error: None,
+            }
+        }
+        Err(e) => {
+            let error = format!("{e:#}");
+            tracing::warn!(
+                action = "db_outbox_message_send_failed",
+                channel_id = entry.channel_id,
+                error = %error
+            );
+            if let Err(e) = mark_failed(&db_key, &client_id, &error).await {
+                tracing::warn!(action = "db_outbox_mark_failed_failed", error = %e);
+            }
+            MessageStatusChangedEvent {
+                db_key,
+                channel_id: entry.channel_id,
+                client_id,
+                message_id: None,
+                status: MessageSendStatus::Failed,
+                error: Some(error),
+            }
+        }
+    };
+    emit_status(events.as_ref(), event);
+}
+
+async fn insert_provisional(
+    db_key: &str,
+    channel_id: i64,
+    user_id: i64,
+    preview: &str,
+    body: &OutgoingMessage,
+) -> anyhow::Result<ProvisionalMessage> {
+    let client_id = uuid::Uuid::new_v4().to_string();
+    let provisional = ProvisionalMessage {
+        provisional_id: provisional_id(&client_id),
+        client_id,
+        created_at: now_ms(),
+    };
+    let db = get_db(db_key).await?;
+    let txn = db.connection.begin().await?;
+    txn.execute(&RawStatement::new(
+        r#"
+        INSERT INTO messages (id, channel_id, user_id, content, created_at, updated_at, client_id, status)
+        VALUES (?, ?, ?, ?, ?, ?, ?, ?)
+        "#
+        .to_string(),
+        vec![
+            Value::from(provisional.provisional_id.as_str()),
+            Value::from(channel_id),
+            Value::from(user_id),
+            Value::from(preview),
+            Value::from(provisional.created_at),
+            Value::from(provisional.created_at),
+            Value::from(provisional.client_id.as_str()),
+            Value::from(MessageSendStatus::Pending.as_str()),
+        ],
+    ))
+    .await?;
+    txn.execute(&RawStatement::new(
+        "INSERT INTO outbox (client_id, channel_id, body, attempts, created_at) VALUES (?, ?, ?, 0, ?)"
+            .to_string(),
+        vec![
+            Value::from(provisional.client_id.as_str()),
+            Value::from(channel_id),
+            Value::from(serde_json::to_string(body)?),
+            Value::from(provisional.created_at),
+        ],
+    ))
+    .await?;
+    txn.commit().await?;
+    Ok(provisional)
+}
+
+#[tauri::command]
+/// 乐观发送：立即写入临时消息行，后台发送并在确认后替换为正式 id。
+///
+/// # 参数
+/// - `db_key`：server DB key（`server_<sha256>`），必须已打开。
+/// - `channel_id`：频道 id。
+/// - `user_id`：当前用户 id（临时行的发送者）。
+/// - `preview`：本地回显的文本预览。
+/// - `message`：发送请求体。
+/// - `options`：服务端请求参数。
+///
+/// # 返回值
+/// - `Ok(ProvisionalMessage)`：临时行信息；后续状态经 `message-status-changed` 事件通知。
+/// - `Err(CommandError)`：key 非法或写入失败（此时不会发送）。
+pub async fn send_message_optimistic(
+    app: AppHandle,
+    db_key: String,
+    channel_id: i64,
+    user_id: i64,
+    preview: String,
+    message: OutgoingMessage,
+    options: OutboxSendOptions,
+) -> CommandResult<ProvisionalMessage> {
+    send_message_optimistic_with(
+        Arc::new(app),
+        db_key,
+        channel_id,
+        user_id,
+        preview,
+        message,
+        options,
+    )
+    .await
+}
+
+/// [`send_message_optimistic`] 的核心逻辑（不依赖 Tauri，状态事件经 `events` 投递）。
+pub async fn send_message_optimistic_with(
+    events: Arc<dyn EventBus>,
+    db_key: String,
+    channel_id: i64,
+    user_id: i64,
+    preview: String,
+    message: OutgoingMessage,
+    options: OutboxSendOptions,
+) -> CommandResult<ProvisionalMessage> {
+    validate_db_key(&db_key)?;
+    let provisional = insert_provisional(&db_key, channel_id, user_id, &preview, &message)
+        .await
+        .map_err(|e| {
+            to_command_error(
+                "DB_OUTBOX_ENQUEUE_FAILED",
+                "error.db_outbox_enqueue_failed",
+                e,
+            )
+        })?;
+    emit_status(
+        events.as_ref(),
+        MessageStatusChangedEvent {
+            db_key: db_key.clone(),
+            channel_id,
+            client_id: provisional.client_id.clone(),
+            message_id: None,
+            status: MessageSendStatus::Pending,
+            error: None,
+        },
+    );
+    crate::shared::mute_rules::schedule_evaluation(&db_key);
+    tauri::async_runtime::spawn(deliver(
+        events,
+        db_key,
+        provisional.client_id.clone(),
+        options,
+    ));
+    Ok(provisional)
+}
+
+#[tauri::command]
+/// 重发一条发送失败的乐观消息（沿用原 client_id，服务端按幂等键去重）。
+///
+/// # 返回值
+/// - `Ok(true)`：已重新进入发送流程；
+/// - `Ok(false)`：发件箱中不存在该记录（已发送或已丢弃）。
+pub async fn retry_optimistic_message(
+    app: AppHandle,
+    db_key: String,
+    client_id: String,
+    options: OutboxSendOptions,
+) -> CommandResult<bool> {
+    retry_optimistic_message_with(Arc::new(app), db_key, client_id, options).await
+}
+
+/// [`retry_optimistic_message`] 的核心逻辑（不依赖 Tauri）。
+pub async fn retry_optimistic_message_with(
+    events: Arc<dyn EventBus>,
+    db_key: String,
+    client_id: String,
+    options: OutboxSendOptions,
+) -> CommandResult<bool> {
+    validate_db_key(&db_key)?;
+    let map_err = |e: anyhow::Error| {
+        to_command_error("DB_OUTBOX_RETRY_FAILED", "error.db_outbox_retry_failed", e)
+    };
+    let Some(entry) = load_entry(&db_key, &client_id).await.map_err(map_err)? else {
+        return Ok(false);
+    };
+    let db = get_db(&db_key).await.map_err(map_err)?;
+    db.connection
+        .execute(&RawStatement::new(
+            "UPDATE messages SET status = 'pending' WHERE id = ?".to_string(),
+            vec![Value::from(provisional_id(&client_id))],
+        ))
+        .await
+        .map_err(|e| map_err(e.into()))?;
+    emit_status(
+        events.as_ref(),
+        MessageStatusChangedEvent {
+            db_key: db_key.clone(),
+            channel_id: entry.channel_id,
+            client_id: client_id.clone(),
+            message_id: None,
+            status: MessageSendStatus::Pending,
+            error: None,
+        },
+    );
+    tauri::async_runtime::spawn(deliver(events, db_key, client_id, options));
+    Ok(true)
+}
+
+#[tauri::command]
+/// 放弃一条未发送成功的乐观消息：删除临时行与发件箱记录。
+///
+/// # 返回值
+/// 发件箱中是否存在该记录。
+pub async fn discard_optimistic_message(db_key: String, client_id: String) -> CommandResult<bool> {
+    validate_db_key(&db_key)?;
+    let discard = async {
+        let db = get_db(&db_key).await?;
+        let txn = db.connection.begin().await?;
+        txn.execute(&RawStatement::new(
+            "DELETE FROM messages WHERE id = ?".to_string(),
+            vec![Value::from(provisional_id(&client_id))],
+        ))
+        .await?;
+        let result = txn
+            .execute(&RawStatement::new(
+                "DELETE FROM outbox WHERE client_id = ?".to_string(),
+                vec![Value::from(client_id.as_str())],
+            ))
+            .await?;
+        txn.commit().await?;
+        anyhow::Ok(result.rows_affected() > 0)
+    };
+    discard.await.map_err(|e| {
+        to_command_error(
+            "DB_OUTBOX_DISCARD_FAILED",
+            "error.db_outbox_discard_failed",
+            e,
+        )
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn ack_requires_non_empty_mid() {
+        let body =
+            serde_json::json!({ "mid": "723155640365318144", "send_time": 1700000000000i64 });
+        assert_eq!(
+            parse_ack(Some(&body)),
+            Some(SendAck {
+                mid: "723155640365318144".to_string(),
+                send_time: Some(1_700_000_000_000),
+            })
+        );
+        assert_eq!(parse_ack(Some(&serde_json::json!({ "mid": " " }))), None);
+        assert_eq!(parse_ack(Some(&serde_json::json!({ "ok": true }))), None);
+        assert_eq!(parse_ack(None), None);
+    }
+
+    #[test]
+    fn status_event_uses_snake_case_status() {
+        let event = MessageStatusChangedEvent {
+            db_key: "server_x".to_string(),
+            channel_id: 3,
+            client_id: "c".to_string(),
+            message_id: None,
+            status: MessageSendStatus::Pending,
+            error: None,
+        };
+        let value = serde_json::to_value(event).unwrap();
+        assert_eq!(value["status"], "pending");
+        assert_eq!(value["clientId"], "c");
+        assert_eq!(provisional_id("c"), "pending:c");
+    }
+}
@@ -0,0 +1,356 @@
+//! shared｜数据库：频道/用户快速切换（search-as-you-type）。
+//!
+//! 说明：
+//! - 在所有已打开的 server DB 上对 `channels.name` / `users.name` 做前缀 + 模糊匹配；
+//! - 候选集按 DB key 短时缓存，避免每次按键都全表读取；
+//! - 最近活跃（`messages.created_at`）会为得分加权。
+//!
+//! 约定：注释中文，日志英文（tracing）。
+
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex, OnceLock};
+use std::time::{Duration, Instant};
+
+use sea_orm::ConnectionTrait;
+use serde::Serialize;
+
+use crate::shared::error::{CommandResult, to_command_error};
+
+use super::commands::{RawStatement, is_server_db_key};
+use super::{get_read_db, init_db_registry};
+
+/// 默认返回条数。
+const DEFAULT_LIMIT: usize = 20;
+/// 最大返回条数（防止前端一次性拉取过多）。
+const MAX_LIMIT: usize = 200;
+/// 候选集缓存有效期：覆盖一次连续输入即可。
+const CANDIDATE_TTL: Duration = Duration::from_secs(5);
+/// 最近活跃加权上限。
+const RECENCY_BOOST_MAX: f64 = 150.0;
+/// 最近活跃加权半衰期（毫秒，3 天）。
+const RECENCY_HALF_LIFE_MS: f64 = 3.0 * 24.0 * 3600.0 * 1000.0;
+
+// 被屏蔽用户不作为候选，其消息也不计入频道的最近活跃时间。
+const CHANNEL_CANDIDATES_SQL: &str = r#"
+    SELECT c.id AS id, c.name AS name, m.last_active_at AS last_active_at
+    FROM channels c
+    LEFT JOIN (
+        SELECT channel_id, MAX(created_at) AS last_active_at
+        FROM messages
+        WHERE user_id NOT IN (SELECT user_id FROM blocked_users)
+        GROUP BY channel_id
+    ) m ON m.channel_id = c.id
+"#;
+
+const USER_CANDIDATES_SQL: &str = r#"
+    SELECT u.id AS id, u.name AS name, m.last_active_at AS last_active_at
+    FROM users u
+    LEFT JOIN (
+        SELECT user_id, MAX(created_at) AS last_active_at
+        FROM messages GROUP BY user_id
+    ) m ON m.user_id = u.id
+    WHERE u.id NOT IN (SELECT user_id FROM blocked_users)
+"#;
+
+/// 快速切换结果类型。
+#[derive(Debug, Clone, Copy, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum QuickSwitchKind {
+    Channel,
+    User,
+}
+
+/// 快速切换的单条结果。
+#[derive(Debug, Clone, Serialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct QuickSwitchItem {
+    /// 结果类型（频道/用户）。
+    pub kind: QuickSwitchKind,
+    /// 来源 server DB key（`server_<sha256>`）。
+    pub db_key: String,
+    /// 频道 id 或用户 id。
+    pub id: i64,
+    /// 展示名称。
+    pub name: String,
+    /// 综合得分（越高越靠前）。
+    pub score: f64,
+    /// 最近活跃时间（毫秒时间戳；无消息时为空）。
+    pub last_active_at: Option<i64>,
+}
+
+#[derive(Debug, Clone)]
+struct Candidate {
+    kind: QuickSwitchKind,
+    id: i64,
+    name: String,
+    name_lower: String,
+    last_active_at: Option<i64>,
+}
+
+struct CachedCandidates {
+    loaded_at: Instant,
+    items: Arc<Vec<Candidate>>,
+}
+
+fn candidate_cache() -> &'static Mutex<HashMap<String, CachedCandidates>> {
+    static CACHE: OnceLock<Mutex<HashMap<String, CachedCandidates>>> = OnceLock::new();
+    CACHE.get_or_init(|| Mutex::new(HashMap::new()))
+}
+
+/// 计算名称与查询的文本匹配得分（不含活跃加权）。
+///
+/// # 说明
+/// 优先级：完全匹配 > 前缀 > 词首前缀 > 子串 > 子序列模糊匹配；不匹配返回 `None`。
+/// `query` 与 `name` 需已转小写。
+fn match_score(query: &str, name: &str) -> Option<f64> {
+    if query.is_empty() {
+        return Some(0.0);
+    }
+    let extra = name.chars().count().saturating_sub(query.chars().count()) as f64;
+    if name == query {
+        return Some(1000.0);
+    }
+    if name.starts_with(query) {
+        return Some(800.0 - extra.min(100.0));
+    }
+    let word_prefix = name
+        .match_indices([' ', '_', '-', '.', '#'])
+        .any(|(idx, sep)| name[idx + sep.len()..].starts_with(query));
+    if word_prefix {
+        return Some(600.0 - extra.min(100.0));
+    }
+    if let Some(pos) = name.find(query) {
+        return Some(400.0 - (pos as f64).min(100.0));
+    }
+    fuzzy_score(query, name)
+}
+
+/// 子序列模糊匹配：连续命中加分，间隔扣分。
+fn fuzzy_score(query: &str, name: &str) -> Option<f64> {
+    let mut score = 200.0;
+    let mut chars = name.chars().enumerate();
+    let mut last_hit: Option<usize> = None;
+    for q in query.chars() {
+        let (idx, _) = chars.by_ref().find(|(_, c)| *c == q)?;
+        match last_hit {
+            Some(prev) if idx == prev + 1 => score += 10.0,
+            Some(prev) => score -= (idx - prev - 1).min(20) as f64 * 2.0,
+            None => score -= idx.min(20) as f64 * 2.0,
+        }
+        last_hit = Some(idx);
+    }
+    Some(score.max(1.0))
+}
+
+/// 最近活跃加权：按半衰期指数衰减。
+fn recency_boost(last_active_at: Option<i64>, now_ms: i64) -> f64 {
+    let Some(ts) = last_active_at else {
+        return 0.0;
+    };
+    let age = (now_ms - ts).max(0) as f64;
+    RECENCY_BOOST_MAX * 0.5f64.powf(age / RECENCY_HALF_LIFE_MS)
+}
+
+/// 在候选集中打分并取 top-N。
+fn rank(
+    query: &str,
+    sources: &[(String, Arc<Vec<Candidate>>)],
+    limit: usize,
+    now_ms: i64,
+) -> Vec<QuickSwitchItem> {
+    let query = query.trim().to_lowercase();
+    let mut items: Vec<QuickSwitchItem> = sources
+        .iter()
+        .flat_map(|(db_key, candidates)| {
+            candidates.iter().filter_map(|c| {
+                let text = match_score(&query, &c.name_lower)?;
+                Some(QuickSwitchItem {
+                    kind: c.kind,
+                    db_key: db_key.clone(),
+                    id: c.id,
+                    name: c.name.clone(),
+                    score: text + recency_boost(c.last_active_at, now_ms),
+                    last_active_at: c.last_active_at,
+                })
+            })
+        })
+        .collect();
+    items.sort_by(|a, b| {
+        b.score
+            .total_cmp(&a.score)
+            .then_with(|| a.name.len().cmp(&b.name.len()))
+            .then_with(|| a.name.cmp(&b.name))
+    });
+    items.truncate(limit);
+    items
+}
+
+async fn load_candidates(conn: &sea_orm::DatabaseConnection) -> anyhow::Result<Vec<Candidate>> {
+    let mut out = Vec::new();
+    for (kind, sql) in [
+        (QuickSwitchKind::Channel, CHANNEL_CANDIDATES_SQL),
+        (QuickSwitchKind::User, USER_CANDIDATES_SQL),
+    ] {
+        let stmt = RawStatement::new(sql.to_string(), Vec::new());
+        for row in conn.query_all(&stmt).await? {
+            let (Ok(id), Ok(name)) = (
+                row.try_get::<i64>("", "id"),
+                row.try_get::<String>("", "name"),
+            ) else {
+                continue;
+            };
+            out.push(Candidate {
+                kind,
+                id,
+                name_lower: name.to_lowercase(),
+                name,
+                last_active_at: row
+                    .try_get::<Option<i64>>("", "last_active_at")
+                    .ok()
+                    .flatten(),
+            });
+        }
+    }
+    Ok(out)
+}
+
+/// 丢弃指定 DB 的候选集缓存（屏蔽列表变化后调用）。
+pub(super) fn invalidate(db_key: &str) {
+    if let Ok(mut cache) = candidate_cache().lock() {
+        cache.remove(db_key);
+    }
+}
+
+async fn cached_candidates(
+    key: &str,
+    conn: &sea_orm::DatabaseConnection,
+) -> anyhow::Result<Arc<Vec<Candidate>>> {
+    if let Ok(cache) = candidate_cache().lock()
+        && let Some(entry) = cache.get(key)
+        && entry.loaded_at.elapsed() < CANDIDATE_TTL
+    {
+        return Ok(entry.items.clone());
+    }
+    let items = Arc::new(load_candidates(conn).await?);
+    if let Ok(mut cache) = candidate_cache().lock() {
+        cache.insert(
+            key.to_string(),
+            CachedCandidates {
+                loaded_at: Instant::now(),
+                items: items.clone(),
+            },
+        );
+    }
+    Ok(items)
+}
+
+fn now_ms() -> i64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_millis() as i64
+}
+
+#[tauri::command]
+/// 在所有已打开的 server DB 中搜索频道与用户（前缀 + 模糊匹配，最近活跃加权）。
+///
+/// # 参数
+/// - `query`：输入文本（大小写不敏感；为空时按最近活跃排序）。
+/// - `limit`：返回条数（默认 20，最大 200）。
+///
+/// # 返回值
+/// - `Ok(Vec<QuickSwitchItem>)`：按得分降序排列的结果。
+/// - `Err(CommandError)`：读取候选集失败。
+pub async fn quick_switch(
+    query: String,
+    limit: Option<u32>,
+) -> CommandResult<Vec<QuickSwitchItem>> {
+    let limit = limit
+        .map(|v| v as usize)
+        .unwrap_or(DEFAULT_LIMIT)
+        .clamp(1, MAX_LIMIT);
+
+    let keys: Vec<String> = {
+        let registry = init_db_registry();
+        let lock = registry.read().await;
+        lock.map
+            .keys()
+            .filter(|key| is_server_db_key(key))
+            .cloned()
+            .collect()
+    };
+
+    let mut sources = Vec::with_capacity(keys.len());
+    for key in keys {
+        // 候选集读取走只读连接，不与消息写入争用主连接池。
+        let Ok(db) = get_read_db(&key).await else {
+            continue;
+        };
+        let candidates = cached_candidates(&key, &db.connection).await.map_err(|e| {
+            to_command_error("DB_QUICK_SWITCH_FAILED", "error.db_quick_switch_failed", e)
+        })?;
+        sources.push((key, candidates));
+    }
+
+    let now = now_ms();
+    tokio::task::spawn_blocking(move || rank(&query, &sources, limit, now))
+        .await
+        .map_err(|e| to_command_error("DB_QUICK_SWITCH_FAILED", "error.db_quick_switch_failed", e))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn candidate(kind: QuickSwitchKind, id: i64, name: &str, last: Option<i64>) -> Candidate {
+        Candidate {
+            kind,
+            id,
+            name: name.to_string(),
+            name_lower: name.to_lowercase(),
+            last_active_at: last,
+        }
+    }
+
+    #[test]
+    fn match_score_orders_match_kinds() {
+        let exact = match_score("general", "general").expect("exact");
+        let prefix = match_score("gen", "general").expect("prefix");
+        let word = match_score("chat", "dev-chat").expect("word prefix");
+        let substring = match_score("era", "general").expect("substring");
+        let fuzzy = match_score("gnl", "general").expect("fuzzy");
+        assert!(exact > prefix && prefix > word && word > substring && substring > fuzzy);
+        assert_eq!(match_score("xyz", "general"), None);
+    }
+
+    #[test]
+    fn recent_activity_breaks_ties() {
+        let now = 10 * RECENCY_HALF_LIFE_MS as i64;
+        let sources = vec![(
+            "server_a".to_string(),
+            Arc::new(vec![
+                candidate(QuickSwitchKind::Channel, 1, "design", Some(0)),
+                candidate(QuickSwitchKind::Channel, 2, "devops", Some(now)),
+                candidate(QuickSwitchKind::User, 3, "Dev Bot", None),
+            ]),
+        )];
+        let ranked = rank("De", &sources, 10, now);
+        assert_eq!(ranked.len(), 3);
+        assert_eq!(ranked[0].id, 2);
+        assert_eq!(rank("de", &sources, 1, now).len(), 1);
+    }
+
+    #[test]
+    fn empty_query_ranks_by_recency() {
+        let sources = vec![(
+            "server_a".to_string(),
+            Arc::new(vec![
+                candidate(QuickSwitchKind::Channel, 1, "old", Some(0)),
+                candidate(QuickSwitchKind::User, 2, "new", Some(1_000)),
+            ]),
+        )];
+        let ranked = rank("  ", &sources, 10, 1_000);
+        assert_eq!(ranked[0].id, 2);
+        assert_eq!(ranked[0].kind, QuickSwitchKind::User);
+    }
+}
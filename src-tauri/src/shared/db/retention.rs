@@ -0,0 +1,520 @@
+//! shared｜数据库：消息保留策略与自动清理（server DB `channel_retention` 表）。
+//!
+//! 说明：
+//! - 全局默认值来自设置 `message_retention_days` / `message_retention_max_per_channel`
+//!   （0 = 不限制），频道可单独覆盖（列为 NULL 表示沿用全局值）；
+//! - 后台任务定期遍历已打开的 server DB，按策略删除过期消息与过期的删除墓碑；依附于消息 id
+//!   的派生行（[`DEPENDENT_TABLES`]）由 server DB 的删除触发器随消息一并删除；
+//! - 只清理已确认（`status = 'sent'`）的消息，发件箱中的临时行不受影响；
+//! - `preview_prune` 按同一规则返回将被删除的内容，不做修改。
+//!
+//! 约定：注释中文，日志英文（tracing）。
+
+use std::time::Duration;
+
+use sea_orm::{ConnectionTrait, TransactionTrait, Value};
+use serde::{Deserialize, Serialize};
+
+use crate::features::settings::get_config_value;
+use crate::shared::error::{CommandResult, command_error, to_command_error};
+
+use super::commands::{RawStatement, is_server_db_key};
+use super::get_db;
+
+/// 以 `message_id` 关联消息的派生表（由 `trg_messages_delete_cascade` 级联删除，此处仅用于统计）。
+const DEPENDENT_TABLES: &[&str] = &["translations"];
+
+/// 启动后首次清理前的延迟（避开启动高峰）。
+const STARTUP_DELAY: Duration = Duration::from_secs(5 * 60);
+/// 清理周期。
+const PRUNE_INTERVAL: Duration = Duration::from_secs(6 * 60 * 60);
+
+/// 预览中最多返回的消息 id 数。
+const PREVIEW_ID_LIMIT: usize = 100;
+
+const DAY_MS: i64 = 24 * 60 * 60 * 1000;
+
+/// 频道级覆盖（字段为空表示沿用全局设置，0 表示不限制）。
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ChannelRetentionOverride {
+    pub keep_days: Option<u32>,
+    pub keep_count: Option<u32>,
+}
+
+/// 生效的保留策略（0 = 不限制）。
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RetentionPolicy {
+    pub keep_days: u32,
+    pub keep_count: u32,
+}
+
+impl RetentionPolicy {
+    /// 是否不做任何清理。
+    pub fn is_unlimited(&self) -> bool {
+        self.keep_days == 0 && self.keep_count == 0
+    }
+
+    /// 按天数保留时的截止时间（早于该时间的消息会被删除）。
+    pub fn cutoff_ms(&self, now_ms: i64) -> Option<i64> {
+        (self.keep_days > 0).then(|| now_ms - i64::from(self.keep_days) * DAY_MS)
+    }
+}
+
+/// 合并频道覆盖与全局设置。
+pub fn resolve_policy(
+    overrides: ChannelRetentionOverride,
+    global: RetentionPolicy,
+) -> RetentionPolicy {
+    RetentionPolicy {
+        keep_days: overrides.keep_days.unwrap_or(global.keep_days),
+        keep_count: overrides.keep_count.unwrap_or(global.keep_count),
+    }
+}
+
+/// 频道保留设置（覆盖值与生效值）。
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ChannelRetention {
+    pub channel_id: i64,
+    pub overrides: ChannelRetentionOverride,
+    pub effective: RetentionPolicy,
+}
+
+/// `preview_prune` 的结果。
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PrunePreview {
+    pub channel_id: i64,
+    pub policy: RetentionPolicy,
+    pub message_count: i64,
+    /// 将被删除的消息数。
+    pub prune_count: i64,
+    /// 将被删除的消息中最新一条的时间（无删除时为空）。
+    pub newest_pruned_at: Option<i64>,
+    /// 将被删除的消息 id（最旧在前，最多 100 条）。
+    pub message_ids: Vec<String>,
+    /// 删除后将一并清理的派生行数。
+    pub dependent_rows: i64,
+}
+
+/// 一次清理的统计。
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct PruneStats {
+    pub messages: u64,
+    pub dependent_rows: u64,
+    pub tombstones: u64,
+}
+
+fn now_ms() -> i64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_millis() as i64
+}
+
+fn validate_db_key(db_key: &str) -> CommandResult<()> {
+    if is_server_db_key(db_key) {
+        Ok(())
+    } else {
+        Err(command_error("DB_KEY_INVALID", "error.db_key_invalid"))
+    }
+}
+
+async fn global_policy() -> RetentionPolicy {
+    RetentionPolicy {
+        keep_days: get_config_value::<u32>(String::from("message_retention_days")).await,
+        keep_count: get_config_value::<u32>(String::from("message_retention_max_per_channel"))
+            .await,
+    }
+}
+
+/// 候选消息的筛选条件（参数：channel_id, cutoff, keep_count）。
+const CANDIDATE_FILTER: &str = r#"
+    channel_id = ?1 AND status = 'sent' AND (
+        (?2 IS NOT NULL AND created_at < ?2)
+        OR (?3 > 0 AND id IN (
+            SELECT id FROM messages
+            WHERE channel_id = ?1 AND status = 'sent'
+            ORDER BY created_at DESC, id DESC
+            LIMIT -1 OFFSET ?3
+        ))
+    )
+"#;
+
+fn candidate_values(channel_id: i64, policy: RetentionPolicy, now: i64) -> Vec<Value> {
+    vec![
+        Value::from(channel_id),
+        Value::from(policy.cutoff_ms(now)),
+        Value::from(i64::from(policy.keep_count)),
+    ]
+}
+
+async fn load_overrides(
+    conn: &impl ConnectionTrait,
+    channel_id: i64,
+) -> anyhow::Result<ChannelRetentionOverride> {
+    let row = conn
+        .query_one(&RawStatement::new(
+            "SELECT keep_days, keep_count FROM channel_retention WHERE channel_id = ?".to_string(),
+            vec![Value::from(channel_id)],
+        ))
+        .await?;
+    Ok(row
+        .map(|row| ChannelRetentionOverride {
+            keep_days: row
+                .try_get::<Option<i64>>("", "keep_days")
+                .ok()
+                .flatten()
+                .map(|v| v.clamp(0, i64::from(u32::MAX)) as u32),
+            keep_count: row
+                .try_get::<Option<i64>>("", "keep_count")
+                .ok()
+                .flatten()
+                .map(|v| v.clamp(0, i64::from(u32::MAX)) as u32),
+        })
+        .unwrap_or_default())
+}
+
+/// 将随候选消息一并删除的派生行数（参数同 [`CANDIDATE_FILTER`]）。
+async fn count_dependents(conn: &impl ConnectionTrait, values: Vec<Value>) -> anyhow::Result<i64> {
+    let mut total = 0;
+    for table in DEPENDENT_TABLES {
+        let count: i64 = conn
+            .query_one(&RawStatement::new(
+                format!(
+                    "SELECT COUNT(*) AS n FROM {table} WHERE message_id IN (SELECT id FROM messages WHERE {CANDIDATE_FILTER})"
+                ),
+                values.clone(),
+            ))
+            .await?
+            .and_then(|row| row.try_get("", "n").ok())
+            .unwrap_or(0);
+        total += count;
+    }
+    Ok(total)
+}
+
+/// 按策略清理单个频道（同一事务内删除消息与派生行）。
+async fn prune_channel(
+    db_key: &str,
+    channel_id: i64,
+    global: RetentionPolicy,
+) -> anyhow::Result<PruneStats> {
+    let db = get_db(db_key).await?;
+    let txn = db.connection.begin().await?;
+    let policy = resolve_policy(load_overrides(&txn, channel_id).await?, global);
+    let mut stats = PruneStats::default();
+    if policy.is_unlimited() {
+        txn.commit().await?;
+        return Ok(stats);
+    }
+    let now = now_ms();
+    // 触发器删除的行不计入 rows_affected，需在删除前统计。
+    stats.dependent_rows =
+        count_dependents(&txn, candidate_values(channel_id, policy, now)).await? as u64;
+    stats.messages = txn
+        .execute(&RawStatement::new(
+            format!("DELETE FROM messages WHERE {CANDIDATE_FILTER}"),
+            candidate_values(channel_id, policy, now),
+        ))
+        .await?
+        .rows_affected();
+    if let Some(cutoff) = policy.cutoff_ms(now) {
+        // 墓碑只用于阻止过期变更复活消息；超过保留期后即使被复活，也会在下次清理时删除。
+        stats.tombstones = txn
+            .execute(&RawStatement::new(
+                "DELETE FROM message_tombstones WHERE channel_id = ? AND deleted_at < ?"
+                    .to_string(),
+                vec![Value::from(channel_id), Value::from(cutoff)],
+            ))
+            .await?
+            .rows_affected();
+    }
+    txn.commit().await?;
+    Ok(stats)
+}
+
+/// 清理一个 server DB 中的全部频道。
+pub async fn prune_db(db_key: &str) -> anyhow::Result<PruneStats> {
+    let global = global_policy().await;
+    let db = get_db(db_key).await?;
+    let rows = db
+        .connection
+        .query_all(&RawStatement::new(
+            r#"
+            SELECT channel_id FROM messages
+            UNION SELECT channel_id FROM channel_retention
+            "#
+            .to_string(),
+            Vec::new(),
+        ))
+        .await?;
+    let mut total = PruneStats::default();
+    for row in rows {
+        let Ok(channel_id) = row.try_get::<i64>("", "channel_id") else {
+            continue;
+        };
+        let stats = prune_channel(db_key, channel_id, global).await?;
+        total.messages += stats.messages;
+        total.dependent_rows += stats.dependent_rows;
+        total.tombstones += stats.tombstones;
+    }
+    if total.messages > 0 {
+        super::quick_switch::invalidate(db_key);
+    }
+    Ok(total)
+}
+
+/// 启动后台清理任务。
+pub fn spawn_retention_job() {
+    tauri::async_runtime::spawn(async move {
+        tokio::time::sleep(STARTUP_DELAY).await;
+        let mut interval = tokio::time::interval(PRUNE_INTERVAL);
+        loop {
+            interval.tick().await;
+            run_once().await;
+        }
+    });
+}
+
+async fn run_once() {
+    if global_policy().await.is_unlimited() && !has_any_override().await {
+        tracing::debug!(action = "db_retention_prune_skipped");
+        return;
+    }
+    for key in super::registered_keys().await {
+        if !is_server_db_key(&key) {
+            continue;
+        }
+        match prune_db(&key).await {
+            Ok(stats) => tracing::info!(
+                action = "db_retention_prune_completed",
+                messages = stats.messages,
+                dependent_rows = stats.dependent_rows,
+                tombstones = stats.tombstones
+            ),
+            Err(e) => tracing::warn!(action = "db_retention_prune_failed", error = %e),
+        }
+    }
+}
+
+/// 是否有任意已打开的 server DB 配置了频道级覆盖。
+async fn has_any_override() -> bool {
+    for key in super::registered_keys().await {
+        if !is_server_db_key(&key) {
+            continue;
+        }
+        let Ok(db) = get_db(&key).await else {
+            continue;
+        };
+        let found = db
+            .connection
+            .query_one(&RawStatement::new(
+                "SELECT 1 AS found FROM channel_retention LIMIT 1".to_string(),
+                Vec::new(),
+            ))
+            .await;
+        if matches!(found, Ok(Some(_))) {
+            return true;
+        }
+    }
+    false
+}
+
+#[tauri::command]
+/// 设置频道级保留策略。
+///
+/// # 参数
+/// - `db_key`：server DB key（`server_<sha256>`），必须已打开。
+/// - `channel_id`：频道 id。
+/// - `overrides`：覆盖值；字段为空表示沿用全局设置，两者都为空时删除覆盖。
+pub async fn set_channel_retention(
+    db_key: String,
+    channel_id: i64,
+    overrides: ChannelRetentionOverride,
+) -> CommandResult<ChannelRetention> {
+    validate_db_key(&db_key)?;
+    let save = async {
+        let db = get_db(&db_key).await?;
+        if overrides == ChannelRetentionOverride::default() {
+            db.connection
+                .execute(&RawStatement::new(
+                    "DELETE FROM channel_retention WHERE channel_id = ?".to_string(),
+                    vec![Value::from(channel_id)],
+                ))
+                .await?;
+        } else {
+            db.connection
+                .execute(&RawStatement::new(
+                    "INSERT OR REPLACE INTO channel_retention (channel_id, keep_days, keep_count, updated_at) VALUES (?, ?, ?, ?)"
+                        .to_string(),
+                    vec![
+                        Value::from(channel_id),
+                        Value::from(overrides.keep_days.map(i64::from)),
+                        Value::from(overrides.keep_count.map(i64::from)),
+                        Value::from(now_ms()),
+                    ],
+                ))
+                .await?;
+        }
+        anyhow::Ok(())
+    };
+    save.await.map_err(|e| {
+        to_command_error(
+            "DB_RETENTION_SAVE_FAILED",
+            "error.db_retention_save_failed",
+            e,
+        )
+    })?;
+    tracing::info!(action = "db_channel_retention_updated", channel_id);
+    Ok(ChannelRetention {
+        channel_id,
+        overrides,
+        effective: resolve_policy(overrides, global_policy().await),
+    })
+}
+
+#[tauri::command]
+/// 读取频道保留策略（覆盖值与生效值）。
+pub async fn get_channel_retention(
+    db_key: String,
+    channel_id: i64,
+) -> CommandResult<ChannelRetention> {
+    validate_db_key(&db_key)?;
+    let overrides = async {
+        let db = get_db(&db_key).await?;
+        load_overrides(&db.connection, channel_id).await
+    }
+    .await
+    .map_err(|e| {
+        to_command_error(
+            "DB_RETENTION_LOAD_FAILED",
+            "error.db_retention_load_failed",
+            e,
+        )
+    })?;
+    Ok(ChannelRetention {
+        channel_id,
+        overrides,
+        effective: resolve_policy(overrides, global_policy().await),
+    })
+}
+
+async fn load_preview(db_key: &str, channel_id: i64) -> anyhow::Result<PrunePreview> {
+    let db = get_db(db_key).await?;
+    let conn = &db.connection;
+    let policy = resolve_policy(
+        load_overrides(conn, channel_id).await?,
+        global_policy().await,
+    );
+    let message_count: i64 = conn
+        .query_one(&RawStatement::new(
+            "SELECT COUNT(*) AS n FROM messages WHERE channel_id = ?".to_string(),
+            vec![Value::from(channel_id)],
+        ))
+        .await?
+        .and_then(|row| row.try_get("", "n").ok())
+        .unwrap_or(0);
+    let mut preview = PrunePreview {
+        channel_id,
+        policy,
+        message_count,
+        prune_count: 0,
+        newest_pruned_at: None,
+        message_ids: Vec::new(),
+        dependent_rows: 0,
+    };
+    if policy.is_unlimited() {
+        return Ok(preview);
+    }
+    let now = now_ms();
+    let rows = conn
+        .query_all(&RawStatement::new(
+            format!(
+                "SELECT id, created_at FROM messages WHERE {CANDIDATE_FILTER} ORDER BY created_at ASC, id ASC"
+            ),
+            candidate_values(channel_id, policy, now),
+        ))
+        .await?;
+    preview.prune_count = rows.len() as i64;
+    preview.newest_pruned_at = rows
+        .last()
+        .and_then(|row| row.try_get::<i64>("", "created_at").ok());
+    preview.message_ids = rows
+        .iter()
+        .take(PREVIEW_ID_LIMIT)
+        .filter_map(|row| row.try_get::<String>("", "id").ok())
+        .collect();
+    preview.dependent_rows =
+        count_dependents(conn, candidate_values(channel_id, policy, now)).await?;
+    Ok(preview)
+}
+
+#[tauri::command]
+/// 预览按当前保留策略会从频道中删除的内容（不做修改）。
+///
+/// # 参数
+/// - `db_key`：server DB key。
+/// - `channel_id`：频道 id。
+pub async fn preview_prune(db_key: String, channel_id: i64) -> CommandResult<PrunePreview> {
+    validate_db_key(&db_key)?;
+    load_preview(&db_key, channel_id).await.map_err(|e| {
+        to_command_error(
+            "DB_RETENTION_PREVIEW_FAILED",
+            "error.db_retention_preview_failed",
+            e,
+        )
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn channel_overrides_take_precedence_over_global_policy() {
+        let global = RetentionPolicy {
+            keep_days: 30,
+            keep_count: 0,
+        };
+        assert_eq!(
+            resolve_policy(ChannelRetentionOverride::default(), global),
+            global
+        );
+        let unlimited = resolve_policy(
+            ChannelRetentionOverride {
+                keep_days: Some(0),
+                keep_count: None,
+            },
+            global,
+        );
+        assert!(unlimited.is_unlimited());
+        let capped = resolve_policy(
+            ChannelRetentionOverride {
+                keep_days: None,
+                keep_count: Some(500),
+            },
+            global,
+        );
+        assert_eq!(capped.keep_days, 30);
+        assert_eq!(capped.keep_count, 500);
+    }
+
+    #[test]
+    fn cutoff_is_only_set_for_day_based_retention() {
+        let now = 100 * DAY_MS;
+        let by_days = RetentionPolicy {
+            keep_days: 7,
+            keep_count: 0,
+        };
+        assert_eq!(by_days.cutoff_ms(now), Some(93 * DAY_MS));
+        let by_count = RetentionPolicy {
+            keep_days: 0,
+            keep_count: 10,
+        };
+        assert_eq!(by_count.cutoff_ms(now), None);
+        assert!(!by_count.is_unlimited());
+    }
+}
@@ -0,0 +1,361 @@
+//! shared｜数据库：WebView SQL 策略与命名查询注册表。
+//!
+//! 说明：
+//! - `db_execute` / `db_query` / `db_transaction` 接受来自 WebView 的任意 SQL；来源由命令桥按
+//!   调用方 webview 的 label 判定（不接受调用方自报），插件来源按设置 `db_sql_policy` 处理：
+//!   - `allow_all`（默认）：与原行为一致；
+//!   - `restrict_plugins`：插件来源只允许 DML，拒绝 DDL（含虚拟表/触发器）、ATTACH、PRAGMA 等；
+//!   - `named_only`：插件来源不允许原始 SQL，只能通过 `db_run_named` 执行已审核的语句；
+//! - 应用窗口（[`APP_WEBVIEWS`]，与 `features::windows` 的窗口 label 清单一致）不受策略影响，
+//!   其它 webview（含加载外部页面的认证窗口）一律按插件来源处理；
+//! - 当前插件模块加载在主窗口内，与应用共享来源：策略约束的是独立 webview 中的插件页面。
+//!
+//! 约定：注释中文，日志英文（tracing）。
+
+use sea_orm::ConnectionTrait;
+use serde::Serialize;
+
+use crate::shared::error::{CommandResult, command_error, to_command_error};
+
+use super::commands::{
+    DbValue, RawStatement, is_server_db_key, map_values, normalized_sql_head, row_get_value,
+};
+use super::get_db;
+
+/// 设置项 key。
+const POLICY_SETTING_KEY: &str = "db_sql_policy";
+
+/// 应用自身的窗口（与 `features::windows::domain::window_labels::APP_WINDOW_LABELS` 保持一致）。
+pub const APP_WEBVIEWS: &[&str] = &[
+    "main",
+    "screenshot-overlay",
+    "mini-chat",
+    "app-lock",
+    "user-info-popover",
+    "channel-info-popover",
+    "popover",
+    "tray-notification-popover",
+    "channel-info",
+    "user-profile",
+    "info-window",
+];
+
+/// 插件来源在受限模式下禁止的语句类型。
+const RESTRICTED_HEADS: &[&str] = &[
+    "create", "alter", "drop", "attach", "detach", "pragma", "vacuum", "reindex", "analyze",
+];
+
+/// WebView SQL 策略。
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum SqlPolicyMode {
+    #[default]
+    AllowAll,
+    RestrictPlugins,
+    NamedOnly,
+}
+
+impl SqlPolicyMode {
+    /// 解析设置值；空串视为默认值，未知值返回 `None`。
+    pub fn parse(raw: &str) -> Option<Self> {
+        match raw.trim() {
+            "" | "allow_all" => Some(Self::AllowAll),
+            "restrict_plugins" => Some(Self::RestrictPlugins),
+            "named_only" => Some(Self::NamedOnly),
+            _ => None,
+        }
+    }
+}
+
+/// 请求来源。
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SqlOrigin {
+    App,
+    Plugin,
+}
+
+impl SqlOrigin {
+    /// 按调用方 webview 的 label 判定来源：应用窗口之外一律视为插件。
+    pub fn for_webview(label: &str) -> Self {
+        if APP_WEBVIEWS.contains(&label) {
+            Self::App
+        } else {
+            Self::Plugin
+        }
+    }
+}
+
+/// 命名查询类型。
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NamedQueryKind {
+    Query,
+    Execute,
+}
+
+/// 已审核的命名语句（只作用于 server DB）。
+#[derive(Debug, Clone, Copy)]
+pub struct NamedQuery {
+    pub name: &'static str,
+    pub kind: NamedQueryKind,
+    pub sql: &'static str,
+    /// 查询返回的列（执行类为空）。
+    pub columns: &'static [&'static str],
+}
+
+/// 内置命名语句。
+pub const NAMED_QUERIES: &[NamedQuery] = &[
+    NamedQuery {
+        name: "channels.list",
+        kind: NamedQueryKind::Query,
+        sql: "SELECT id, name, owner_id, created_at FROM channels ORDER BY name ASC",
+        columns: &["id", "name", "owner_id", "created_at"],
+    },
+    NamedQuery {
+        name: "messages.recent",
+        kind: NamedQueryKind::Query,
+        sql: "SELECT id, channel_id, user_id, content, created_at, updated_at FROM messages \
+              WHERE channel_id = ? AND status = 'sent' \
+              AND user_id NOT IN (SELECT user_id FROM blocked_users) \
+              ORDER BY created_at DESC LIMIT ?",
+        columns: &[
+            "id",
+            "channel_id",
+            "user_id",
+            "content",
+            "created_at",
+            "updated_at",
+        ],
+    },
+    NamedQuery {
+        name: "users.get",
+        kind: NamedQueryKind::Query,
+        sql: "SELECT id, name, updated_at FROM users WHERE id = ?",
+        columns: &["id", "name", "updated_at"],
+    },
+    NamedQuery {
+        name: "kv.get",
+        kind: NamedQueryKind::Query,
+        sql: "SELECT value, updated_at FROM kv WHERE key = ?",
+        columns: &["value", "updated_at"],
+    },
+    NamedQuery {
+        name: "kv.set",
+        kind: NamedQueryKind::Execute,
+        sql: "INSERT INTO kv (key, value, updated_at) VALUES (?, ?, ?) \
+              ON CONFLICT(key) DO UPDATE SET value = excluded.value, updated_at = excluded.updated_at",
+        columns: &[],
+    },
+];
+
+/// 按名称查找命名语句。
+pub fn named_query(name: &str) -> Option<&'static NamedQuery> {
+    NAMED_QUERIES.iter().find(|q| q.name == name)
+}
+
+/// 统计 `?` 占位符数量（忽略字符串字面量内的问号）。
+fn placeholder_count(sql: &str) -> usize {
+    let mut in_string = false;
+    let mut count = 0;
+    for ch in sql.chars() {
+        match ch {
+            '\'' => in_string = !in_string,
+            '?' if !in_string => count += 1,
+            _ => {}
+        }
+    }
+    count
+}
+
+/// 按策略检查一条 SQL（基础校验由调用方先完成）。
+fn check(mode: SqlPolicyMode, origin: SqlOrigin, sql: &str) -> CommandResult<()> {
+    if origin == SqlOrigin::App {
+        return Ok(());
+    }
+    match mode {
+        SqlPolicyMode::AllowAll => Ok(()),
+        SqlPolicyMode::NamedOnly => Err(command_error(
+            "DB_SQL_NAMED_ONLY",
+            "error.db_sql_named_only",
+        )),
+        SqlPolicyMode::RestrictPlugins => {
+            let head = normalized_sql_head(sql).to_ascii_lowercase();
+            if RESTRICTED_HEADS.contains(&head.as_str()) {
+                Err(command_error(
+                    "DB_SQL_POLICY_DENIED",
+                    "error.db_sql_policy_denied",
+                ))
+            } else {
+                Ok(())
+            }
+        }
+    }
+}
+
+/// 读取当前策略（非法值回退为默认值）。
+async fn current_mode() -> SqlPolicyMode {
+    let raw =
+        crate::features::settings::get_config_value::<String>(POLICY_SETTING_KEY.to_string()).await;
+    SqlPolicyMode::parse(&raw).unwrap_or_default()
+}
+
+/// 对 WebView 传入的原始 SQL 执行策略检查。
+///
+/// 应用来源直接放行，不读取设置。
+pub(super) async fn enforce(origin: SqlOrigin, sql: &str) -> CommandResult<()> {
+    if origin == SqlOrigin::App {
+        return Ok(());
+    }
+    let mode = current_mode().await;
+    check(mode, origin, sql).inspect_err(|_| {
+        tracing::warn!(action = "db_sql_policy_denied", mode = ?mode);
+    })
+}
+
+/// `db_run_named` 的结果：查询类填充 `columns`/`rows`，执行类填充 `rows_affected`。
+#[derive(Debug, Clone, Serialize)]
+pub struct DbNamedResult {
+    pub columns: Vec<String>,
+    pub rows: Vec<Vec<DbValue>>,
+    pub rows_affected: u64,
+}
+
+#[tauri::command]
+/// 执行已审核的命名语句（任何 SQL 策略下均可用）。
+///
+/// # 参数
+/// - `key`：server DB key（`server_<sha256>`）。
+/// - `name`：命名语句（见 `NAMED_QUERIES`）。
+/// - `params`：参数，数量必须与语句占位符一致。
+///
+/// # 返回值
+/// - `Ok(DbNamedResult)`：执行结果。
+/// - `Err(CommandError)`：名称未知、参数数量不符或执行失败。
+pub async fn db_run_named(
+    key: String,
+    name: String,
+    params: Option<Vec<DbValue>>,
+) -> CommandResult<DbNamedResult> {
+    if !is_server_db_key(&key) {
+        return Err(command_error("DB_KEY_INVALID", "error.db_key_invalid"));
+    }
+    let Some(query) = named_query(name.trim()) else {
+        return Err(command_error(
+            "DB_NAMED_QUERY_UNKNOWN",
+            "error.db_named_query_unknown",
+        ));
+    };
+    let params = params.unwrap_or_default();
+    if params.len() != placeholder_count(query.sql) {
+        return Err(command_error(
+            "DB_NAMED_QUERY_PARAMS_INVALID",
+            "error.db_named_query_params_invalid",
+        ));
+    }
+    let map_err = |e: anyhow::Error| {
+        to_command_error("DB_NAMED_QUERY_FAILED", "error.db_named_query_failed", e)
+    };
+    let db = get_db(&key).await.map_err(map_err)?;
+    let stmt = RawStatement::new(query.sql.to_string(), map_values(Some(params)));
+    match query.kind {
+        NamedQueryKind::Query => {
+            let rows = db
+                .connection
+                .query_all(&stmt)
+                .await
+                .map_err(|e| map_err(e.into()))?;
+            Ok(DbNamedResult {
+                columns: query.columns.iter().map(|c| c.to_string()).collect(),
+                rows: rows
+                    .iter()
+                    .map(|row| {
+                        query
+                            .columns
+                            .iter()
+                            .map(|c| row_get_value(row, c))
+                            .collect()
+                    })
+                    .collect(),
+                rows_affected: 0,
+            })
+        }
+        NamedQueryKind::Execute => {
+            let result = db
+                .connection
+                .execute(&stmt)
+                .await
+                .map_err(|e| map_err(e.into()))?;
+            Ok(DbNamedResult {
+                columns: Vec::new(),
+                rows: Vec::new(),
+                rows_affected: result.rows_affected(),
+            })
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn origin_follows_the_calling_webview() {
+        assert_eq!(SqlOrigin::for_webview("main"), SqlOrigin::App);
+        assert_eq!(SqlOrigin::for_webview("screenshot-overlay"), SqlOrigin::App);
+        assert_eq!(SqlOrigin::for_webview("mini-chat"), SqlOrigin::App);
+        assert_eq!(SqlOrigin::for_webview("plugin-renderer"), SqlOrigin::Plugin);
+        assert_eq!(SqlOrigin::for_webview("auth-0"), SqlOrigin::Plugin);
+    }
+
+    #[test]
+    fn app_webviews_match_the_window_labels() {
+        use crate::features::windows::domain::window_labels::APP_WINDOW_LABELS;
+
+        let mut policy = APP_WEBVIEWS.to_vec();
+        let mut windows = APP_WINDOW_LABELS.to_vec();
+        policy.sort_unstable();
+        windows.sort_unstable();
+        assert_eq!(policy, windows);
+    }
+
+    #[test]
+    fn plugin_ddl_is_denied_only_in_restricted_modes() {
+        let ddl = "CREATE VIRTUAL TABLE t USING fts5(x)";
+        let dml = "UPDATE kv SET value = ? WHERE key = ?";
+        assert!(check(SqlPolicyMode::AllowAll, SqlOrigin::Plugin, ddl).is_ok());
+        assert!(check(SqlPolicyMode::RestrictPlugins, SqlOrigin::App, ddl).is_ok());
+        let err = check(SqlPolicyMode::RestrictPlugins, SqlOrigin::Plugin, ddl).unwrap_err();
+        assert_eq!(err.code, "DB_SQL_POLICY_DENIED");
+        assert!(check(SqlPolicyMode::RestrictPlugins, SqlOrigin::Plugin, dml).is_ok());
+        let err = check(SqlPolicyMode::NamedOnly, SqlOrigin::Plugin, dml).unwrap_err();
+        assert_eq!(err.code, "DB_SQL_NAMED_ONLY");
+    }
+
+    #[test]
+    fn policy_mode_parses_setting_values() {
+        assert_eq!(SqlPolicyMode::parse(""), Some(SqlPolicyMode::AllowAll));
+        assert_eq!(
+            SqlPolicyMode::parse(" named_only "),
+            Some(SqlPolicyMode::NamedOnly)
+        );
+        assert_eq!(SqlPolicyMode::parse("deny_everything"), None);
+    }
+
+    #[test]
+    fn named_queries_are_unique_single_statements() {
+        for (i, query) in NAMED_QUERIES.iter().enumerate() {
+            assert!(
+                NAMED_QUERIES[i + 1..].iter().all(|q| q.name != query.name),
+                "duplicate named query: {}",
+                query.name
+            );
+            assert!(!query.sql.contains(';'), "{}", query.name);
+            assert_eq!(
+                query.columns.is_empty(),
+                query.kind == NamedQueryKind::Execute,
+                "{}",
+                query.name
+            );
+        }
+        assert_eq!(placeholder_count("SELECT '?' FROM kv WHERE key = ?"), 1);
+        assert_eq!(placeholder_count(named_query("kv.set").unwrap().sql), 3);
+    }
+}
@@ -0,0 +1,228 @@
+//! shared｜数据库：本地用户目录（`users` 表）与用户资料卡预取（缓存的用户信息 + 共同频道）。
+//!
+//! 说明：
+//! - `users` 表由前端在拉取 `/api/users/me`、`/api/users/{uid}`、`/api/users?ids=` 与频道成员列表后
+//!   经 `record_users` 写入，快速切换、资料卡、助手上下文与屏蔽列表都从这里读取用户名；
+//! - 预取只读本地 server DB，不发网络请求；缓存缺失时对应字段为空，前端再按原流程补拉；
+//! - “共同频道”以本地消息记录近似：该用户发过言的频道，按最近发言时间倒序。
+//!
+//! 约定：注释中文，日志英文（tracing）。
+
+use sea_orm::{ConnectionTrait, TransactionTrait, Value};
+use serde::{Deserialize, Serialize};
+
+use crate::shared::error::{CommandResult, command_error, to_command_error};
+
+use super::commands::{RawStatement, is_server_db_key};
+use super::get_db;
+
+/// 单次写入的最大用户数（成员列表一页的量级）。
+const MAX_RECORD_USERS: usize = 1000;
+
+const UPSERT_USER_SQL: &str = r#"
+    INSERT INTO users (id, name, updated_at) VALUES (?, ?, ?)
+    ON CONFLICT(id) DO UPDATE SET name = excluded.name, updated_at = excluded.updated_at
+"#;
+
+/// 共同频道最多返回条数。
+const MUTUAL_CHANNEL_LIMIT: i64 = 20;
+
+const USER_SQL: &str = "SELECT name, updated_at FROM users WHERE id = ?";
+
+const MUTUAL_CHANNELS_SQL: &str = r#"
+    SELECT c.id AS id, c.name AS name, MAX(m.created_at) AS last_active_at
+    FROM messages m
+    JOIN channels c ON c.id = m.channel_id
+    WHERE m.user_id = ?
+    GROUP BY c.id, c.name
+    ORDER BY last_active_at DESC
+    LIMIT ?
+"#;
+
+/// 待写入本地用户目录的用户（服务端 `uid` + 展示名）。
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UserRecord {
+    pub uid: String,
+    pub name: String,
+}
+
+/// 共同频道条目。
+#[derive(Debug, Clone, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct MutualChannel {
+    pub id: i64,
+    pub name: String,
+    /// 该用户在此频道最近一次发言时间（毫秒）。
+    pub last_active_at: Option<i64>,
+}
+
+/// 用户资料卡预取结果。
+#[derive(Debug, Clone, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct UserProfilePrefetch {
+    pub user_id: i64,
+    /// 本地缓存的用户名（未缓存时为空）。
+    pub name: Option<String>,
+    /// 缓存更新时间（毫秒），前端据此判断是否需要后台刷新。
+    pub updated_at: Option<i64>,
+    pub mutual_channels: Vec<MutualChannel>,
+}
+
+/// 从 server DB 读取用户资料卡所需的缓存数据。
+///
+/// # 参数
+/// - `db_key`：server DB key（`server_<sha256>`），必须已打开。
+/// - `user_id`：用户 id。
+pub async fn load_user_profile_prefetch(
+    db_key: &str,
+    user_id: i64,
+) -> anyhow::Result<UserProfilePrefetch> {
+    if !is_server_db_key(db_key) {
+        anyhow::bail!("Not a server db key: {}", db_key);
+    }
+    let db = get_db(db_key).await?;
+    let conn = &db.connection;
+
+    let user = conn
+        .query_one(&RawStatement::new(
+            USER_SQL.to_string(),
+            vec![Value::from(user_id)],
+        ))
+        .await?;
+    let (name, updated_at) = match user {
+        Some(row) => (
+            row.try_get::<String>("", "name").ok(),
+            row.try_get::<i64>("", "updated_at").ok(),
+        ),
+        None => (None, None),
+    };
+
+    let rows = conn
+        .query_all(&RawStatement::new(
+            MUTUAL_CHANNELS_SQL.to_string(),
+            vec![Value::from(user_id), Value::from(MUTUAL_CHANNEL_LIMIT)],
+        ))
+        .await?;
+    let mutual_channels = rows
+        .iter()
+        .filter_map(|row| {
+            Some(MutualChannel {
+                id: row.try_get::<i64>("", "id").ok()?,
+                name: row.try_get::<String>("", "name").ok()?,
+                last_active_at: row
+                    .try_get::<Option<i64>>("", "last_active_at")
+                    .ok()
+                    .flatten(),
+            })
+        })
+        .collect();
+
+    Ok(UserProfilePrefetch {
+        user_id,
+        name,
+        updated_at,
+        mutual_channels,
+    })
+}
+
+fn now_ms() -> i64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_millis() as i64
+}
+
+/// 过滤出可写入的用户：`uid` 必须是整数 id，展示名不能为空；同一 id 以最后一条为准。
+fn valid_users(users: &[UserRecord]) -> Vec<(i64, String)> {
+    let mut out: Vec<(i64, String)> = Vec::with_capacity(users.len());
+    for user in users {
+        let (Ok(id), name) = (user.uid.trim().parse::<i64>(), user.name.trim()) else {
+            continue;
+        };
+        if name.is_empty() {
+            continue;
+        }
+        match out.iter_mut().find(|(existing, _)| *existing == id) {
+            Some(entry) => entry.1 = name.to_string(),
+            None => out.push((id, name.to_string())),
+        }
+    }
+    out
+}
+
+/// 将用户写入 server DB 的 `users` 表（已存在则更新展示名）。
+///
+/// # 返回值
+/// 写入的用户数（跳过 id 非法或展示名为空的条目）。
+pub async fn upsert_users(db_key: &str, users: &[UserRecord]) -> anyhow::Result<usize> {
+    let users = valid_users(users);
+    if users.is_empty() {
+        return Ok(0);
+    }
+    let db = get_db(db_key).await?;
+    let txn = db.connection.begin().await?;
+    let now = now_ms();
+    for (id, name) in &users {
+        txn.execute(&RawStatement::new(
+            UPSERT_USER_SQL,
+            vec![
+                Value::from(*id),
+                Value::from(name.as_str()),
+                Value::from(now),
+            ],
+        ))
+        .await?;
+    }
+    txn.commit().await?;
+    super::quick_switch::invalidate(db_key);
+    Ok(users.len())
+}
+
+#[tauri::command]
+/// 记录从服务端拉取到的用户资料（本地用户目录）。
+///
+/// # 参数
+/// - `db_key`：server DB key（`server_<sha256>`），必须已打开。
+/// - `users`：用户列表（`uid` 非整数或展示名为空的条目会被跳过）。
+///
+/// # 返回值
+/// - `Ok(usize)`：写入的用户数。
+/// - `Err(CommandError)`：key 非法、条目过多或写入失败。
+pub async fn record_users(db_key: String, users: Vec<UserRecord>) -> CommandResult<usize> {
+    if !is_server_db_key(&db_key) {
+        return Err(command_error("DB_KEY_INVALID", "error.db_key_invalid"));
+    }
+    if users.len() > MAX_RECORD_USERS {
+        return Err(command_error(
+            "DB_RECORD_USERS_TOO_MANY",
+            "error.db_record_users_too_many",
+        ));
+    }
+    upsert_users(&db_key, &users)
+        .await
+        .map_err(|e| to_command_error("DB_RECORD_USERS_FAILED", "error.db_record_users_failed", e))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn user(uid: &str, name: &str) -> UserRecord {
+        UserRecord {
+            uid: uid.to_string(),
+            name: name.to_string(),
+        }
+    }
+
+    #[test]
+    fn only_numeric_ids_with_names_are_recorded() {
+        let users = [
+            user("12", "Alice"),
+            user("abc", "Mallory"),
+            user("13", "  "),
+            user(" 12 ", "Alicia"),
+        ];
+        assert_eq!(valid_users(&users), vec![(12, "Alicia".to_string())]);
+    }
+}
@@ -0,0 +1,81 @@
+//! dnd｜Tauri 命令
+
+use tauri::AppHandle;
+
+use crate::features::settings::data::config_store;
+use crate::features::settings::domain::settings_schema::{
+    SettingsDndOverrideV1, SettingsDndRuleV1,
+};
+use crate::shared::error::{CommandResult, command_error, to_command_error};
+
+use super::{DndMode, DndStatus, LocalClock, refresh, validate_rule};
+
+/// 设置免打扰模式。
+///
+/// # 参数
+/// - `mode`：`on` / `off` / `auto`（清除手动覆盖，跟随定时规则）。
+/// - `until`：到期时间（毫秒时间戳，可选）；仅 `on`/`off` 有效，必须晚于当前时间。
+///
+/// # 返回值
+/// 设置后的免打扰状态；状态变化时同时广播 `dnd-changed`。
+#[tauri::command]
+pub async fn set_dnd(
+    app: AppHandle,
+    mode: DndMode,
+    until: Option<i64>,
+) -> CommandResult<DndStatus> {
+    if let Some(until) = until
+        && until <= LocalClock::now().now_ms
+    {
+        return Err(command_error(
+            "DND_INVALID_UNTIL",
+            "error.dnd_invalid_until",
+        ));
+    }
+    let dnd_override = match mode {
+        DndMode::On => Some(SettingsDndOverrideV1 {
+            active: true,
+            until_ms: until,
+        }),
+        DndMode::Off => Some(SettingsDndOverrideV1 {
+            active: false,
+            until_ms: until,
+        }),
+        DndMode::Auto => None,
+    };
+    config_store::update_dnd_override(dnd_override)
+        .await
+        .map_err(|e| to_command_error("DND_UPDATE_FAILED", "error.dnd_update_failed", e))?;
+    Ok(refresh(&app).await)
+}
+
+/// 获取当前免打扰状态。
+#[tauri::command]
+pub async fn get_dnd_status(app: AppHandle) -> CommandResult<DndStatus> {
+    Ok(refresh(&app).await)
+}
+
+/// 获取免打扰每周定时规则。
+#[tauri::command]
+pub async fn get_dnd_schedule() -> CommandResult<Vec<SettingsDndRuleV1>> {
+    Ok(config_store::get_dnd_settings().await.1)
+}
+
+/// 替换免打扰每周定时规则。
+///
+/// # 返回值
+/// 应用新规则后的免打扰状态。
+#[tauri::command]
+pub async fn set_dnd_schedule(
+    app: AppHandle,
+    rules: Vec<SettingsDndRuleV1>,
+) -> CommandResult<DndStatus> {
+    for rule in &rules {
+        validate_rule(rule)
+            .map_err(|e| to_command_error("DND_INVALID_RULE", "error.dnd_invalid_rule", e))?;
+    }
+    config_store::update_dnd_schedule(rules)
+        .await
+        .map_err(|e| to_command_error("DND_UPDATE_FAILED", "error.dnd_update_failed", e))?;
+    Ok(refresh(&app).await)
+}
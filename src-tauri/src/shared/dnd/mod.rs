@@ -0,0 +1,302 @@
+//! 模块入口：dnd（免打扰）。
+//!
+//! 说明：免打扰状态由三部分按优先级决定：
+//! 手动覆盖（`dnd_override`，可带到期时间）> 全局开关（`global_dnd`）> 每周定时规则（`dnd_schedule`）。
+//!
+//! - 状态计算是纯函数（[`evaluate`]），便于测试；
+//! - 后台每 30 秒重新计算一次，覆盖到期会被自动清除；状态变化时广播 `dnd-changed`
+//!   并通知托盘（免打扰期间不闪烁）；
+//! - 前端的通知决策通过 `get_dnd_status` 读取同一结果，保证通知与托盘一致。
+//!
+//! 约定：注释中文，日志英文（tracing）。
+
+pub mod commands;
+
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Mutex, OnceLock};
+use std::time::Duration;
+
+use chrono::{Datelike, Timelike};
+use serde::{Deserialize, Serialize};
+use tauri::AppHandle;
+
+use crate::features::settings::data::config_store;
+use crate::features::settings::domain::settings_schema::{
+    SettingsDndOverrideV1, SettingsDndRuleV1,
+};
+use crate::shared::events::EventBusExt;
+
+/// 状态变化事件名。
+pub const DND_CHANGED_EVENT: &str = "dnd-changed";
+
+/// 一天的分钟数。
+pub const MINUTES_PER_DAY: u16 = 24 * 60;
+
+/// 后台重新计算间隔。
+const REFRESH_INTERVAL: Duration = Duration::from_secs(30);
+
+/// `set_dnd` 的模式。
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DndMode {
+    /// 强制开启（可带到期时间）。
+    On,
+    /// 强制关闭（可带到期时间，用于在定时时段内临时恢复通知）。
+    Off,
+    /// 清除手动覆盖，跟随定时规则。
+    Auto,
+}
+
+/// 当前免打扰状态的来源。
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DndSource {
+    None,
+    Manual,
+    Global,
+    Schedule,
+}
+
+/// 免打扰状态。
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DndStatus {
+    pub active: bool,
+    pub source: DndSource,
+    /// 当前状态预计结束时间（毫秒时间戳）；无期限时为空。
+    pub until_ms: Option<i64>,
+}
+
+/// 计算所需的本地时间。
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct LocalClock {
+    /// 星期（0 = 周一 … 6 = 周日）。
+    pub weekday: u8,
+    /// 当天第几分钟。
+    pub minute: u16,
+    /// 当前分钟内已过去的毫秒数。
+    pub millis_into_minute: i64,
+    /// 当前时间（毫秒时间戳）。
+    pub now_ms: i64,
+}
+
+impl LocalClock {
+    /// 读取系统本地时间。
+    pub fn now() -> Self {
+        let now = chrono::Local::now();
+        Self {
+            weekday: now.weekday().num_days_from_monday() as u8,
+            minute: (now.hour() * 60 + now.minute()) as u16,
+            millis_into_minute: i64::from(now.second()) * 1000
+                + i64::from(now.timestamp_subsec_millis()),
+            now_ms: now.timestamp_millis(),
+        }
+    }
+}
+
+/// 校验定时规则。
+pub fn validate_rule(rule: &SettingsDndRuleV1) -> anyhow::Result<()> {
+    if rule.days.is_empty() || rule.days.iter().any(|d| *d > 6) {
+        anyhow::bail!("DND rule days must be within 0..=6 and not empty");
+    }
+    if rule.start_minute >= MINUTES_PER_DAY || rule.end_minute >= MINUTES_PER_DAY {
+        anyhow::bail!("DND rule minutes must be within 0..1440");
+    }
+    Ok(())
+}
+
+/// 若当前处于某条规则的时段内，返回距离该时段结束的分钟数（多条命中取最晚结束）。
+fn schedule_remaining_minutes(rules: &[SettingsDndRuleV1], clock: &LocalClock) -> Option<u16> {
+    let yesterday = (clock.weekday + 6) % 7;
+    let m = clock.minute;
+    rules
+        .iter()
+        .filter(|rule| rule.enabled)
+        .filter_map(|rule| {
+            let (start, end) = (rule.start_minute, rule.end_minute);
+            let today = rule.days.contains(&clock.weekday);
+            if start < end {
+                return (today && m >= start && m < end).then(|| end - m);
+            }
+            // 跨午夜：开始当天的 start 之后，或次日的 end 之前。
+            if today && m >= start {
+                return Some(MINUTES_PER_DAY - m + end);
+            }
+            (rule.days.contains(&yesterday) && m < end).then(|| end - m)
+        })
+        .max()
+}
+
+/// 计算免打扰状态（纯函数）。
+///
+/// # 说明
+/// 已过期的手动覆盖按不存在处理；调用方负责把它从配置中清除。
+pub fn evaluate(
+    global_dnd: bool,
+    rules: &[SettingsDndRuleV1],
+    dnd_override: Option<&SettingsDndOverrideV1>,
+    clock: &LocalClock,
+) -> DndStatus {
+    if let Some(o) = dnd_override
+        && o.until_ms.is_none_or(|until| until > clock.now_ms)
+    {
+        return DndStatus {
+            active: o.active,
+            source: DndSource::Manual,
+            until_ms: o.until_ms,
+        };
+    }
+    if global_dnd {
+        return DndStatus {
+            active: true,
+            source: DndSource::Global,
+            until_ms: None,
+        };
+    }
+    match schedule_remaining_minutes(rules, clock) {
+        Some(remaining) => DndStatus {
+            active: true,
+            source: DndSource::Schedule,
+            until_ms: Some(clock.now_ms - clock.millis_into_minute + i64::from(remaining) * 60_000),
+        },
+        None => DndStatus {
+            active: false,
+            source: DndSource::None,
+            until_ms: None,
+        },
+    }
+}
+
+static ACTIVE: AtomicBool = AtomicBool::new(false);
+
+fn last_status() -> &'static Mutex<Option<DndStatus>> {
+    static LAST: OnceLock<Mutex<Option<DndStatus>>> = OnceLock::new();
+    LAST.get_or_init(|| Mutex::new(None))
+}
+
+/// 最近一次计算出的免打扰是否生效（同步读取，供托盘等热路径使用）。
+pub fn is_active() -> bool {
+    ACTIVE.load(Ordering::SeqCst)
+}
+
+/// 重新计算状态；到期的手动覆盖会被清除，状态变化时广播事件并刷新托盘。
+pub async fn refresh(app: &AppHandle) -> DndStatus {
+    let (global_dnd, rules, mut dnd_override) = config_store::get_dnd_settings().await;
+    let clock = LocalClock::now();
+    if let Some(o) = &dnd_override
+        && o.until_ms.is_some_and(|until| until <= clock.now_ms)
+    {
+        if let Err(e) = config_store::update_dnd_override(None).await {
+            tracing::warn!(action = "app_dnd_override_clear_failed", error = %e);
+        }
+        tracing::info!(action = "app_dnd_override_expired");
+        dnd_override = None;
+    }
+    let status = evaluate(global_dnd, &rules, dnd_override.as_ref(), &clock);
+
+    let changed = {
+        let mut last = last_status().lock().unwrap_or_else(|p| p.into_inner());
+        let changed = last.as_ref() != Some(&status);
+        *last = Some(status.clone());
+        changed
+    };
+    // 状态切换时刷新托盘闪烁（移动端没有托盘）。
+    #[cfg_attr(mobile, allow(unused_variables))]
+    let was_active = ACTIVE.swap(status.active, Ordering::SeqCst);
+    if changed {
+        tracing::info!(action = "app_dnd_status_changed", active = status.active, source = ?status.source);
+        app.emit(DND_CHANGED_EVENT, &status);
+    }
+    #[cfg(desktop)]
+    if was_active != status.active {
+        crate::features::tray::di::commands::refresh_unread_flashing(app);
+    }
+    status
+}
+
+/// 启动后台定时刷新（在 setup 阶段调用一次）。
+pub fn spawn_dnd_watcher(app: AppHandle) {
+    tauri::async_runtime::spawn(async move {
+        loop {
+            refresh(&app).await;
+            tokio::time::sleep(REFRESH_INTERVAL).await;
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn clock(weekday: u8, hour: u16, minute: u16) -> LocalClock {
+        LocalClock {
+            weekday,
+            minute: hour * 60 + minute,
+            millis_into_minute: 0,
+            now_ms: 1_000_000_000,
+        }
+    }
+
+    fn rule(days: &[u8], start: (u16, u16), end: (u16, u16)) -> SettingsDndRuleV1 {
+        SettingsDndRuleV1 {
+            days: days.to_vec(),
+            start_minute: start.0 * 60 + start.1,
+            end_minute: end.0 * 60 + end.1,
+            enabled: true,
+        }
+    }
+
+    #[test]
+    fn same_day_rule_covers_its_window_only() {
+        let rules = [rule(&[0, 1, 2, 3, 4], (12, 0), (13, 30))];
+        let status = evaluate(false, &rules, None, &clock(2, 12, 45));
+        assert!(status.active);
+        assert_eq!(status.source, DndSource::Schedule);
+        assert_eq!(status.until_ms, Some(1_000_000_000 + 45 * 60_000));
+        assert!(!evaluate(false, &rules, None, &clock(2, 13, 30)).active);
+        assert!(!evaluate(false, &rules, None, &clock(5, 12, 45)).active);
+    }
+
+    #[test]
+    fn overnight_rule_belongs_to_start_day() {
+        // 周五 22:00 → 周六 07:00。
+        let rules = [rule(&[4], (22, 0), (7, 0))];
+        assert!(evaluate(false, &rules, None, &clock(4, 23, 0)).active);
+        assert!(evaluate(false, &rules, None, &clock(5, 6, 59)).active);
+        assert!(!evaluate(false, &rules, None, &clock(4, 6, 0)).active);
+        assert!(!evaluate(false, &rules, None, &clock(6, 6, 0)).active);
+        // 周日 23:00 → 周一 07:00 的跨周情况。
+        let sunday = [rule(&[6], (23, 0), (7, 0))];
+        assert!(evaluate(false, &sunday, None, &clock(0, 1, 0)).active);
+    }
+
+    #[test]
+    fn manual_override_wins_until_it_expires() {
+        let rules = [rule(&[2], (0, 0), (23, 59))];
+        let now = clock(2, 10, 0);
+        let off = SettingsDndOverrideV1 {
+            active: false,
+            until_ms: Some(now.now_ms + 60_000),
+        };
+        let status = evaluate(true, &rules, Some(&off), &now);
+        assert!(!status.active);
+        assert_eq!(status.source, DndSource::Manual);
+
+        let expired = SettingsDndOverrideV1 {
+            active: false,
+            until_ms: Some(now.now_ms),
+        };
+        assert_eq!(
+            evaluate(true, &rules, Some(&expired), &now).source,
+            DndSource::Global
+        );
+    }
+
+    #[test]
+    fn rule_validation_rejects_out_of_range_values() {
+        assert!(validate_rule(&rule(&[0], (8, 0), (9, 0))).is_ok());
+        assert!(validate_rule(&rule(&[7], (8, 0), (9, 0))).is_err());
+        assert!(validate_rule(&rule(&[], (8, 0), (9, 0))).is_err());
+        assert!(validate_rule(&rule(&[0], (24, 0), (9, 0))).is_err());
+    }
+}
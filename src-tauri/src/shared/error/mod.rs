@@ -2,29 +2,153 @@
 //!
 //! 约定：
 //! - 对前端暴露的 Tauri command 返回统一使用 `CommandResult<T>`；
-//! - 错误消息统一采用 `[ERROR_CODE] message` 结构，便于前端解析与日志检索；
+//! - 错误以结构化 `CommandError { code, message, details, retryable, correlation_id, request_id }` 序列化给前端；
+//! - 兼容模式（配置 `legacy_string_errors`）下仍序列化为 `[ERROR_CODE] message` 字符串；
 //! - 记录错误日志时统一使用 `action = "tauri_command_failed"`。
 
 use std::fmt::Display;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use serde::ser::SerializeStruct;
+use serde::{Serialize, Serializer};
 
 /// Tauri command 统一返回类型。
-///
-/// # 说明
-/// - 目前保持与前端兼容：错误仍为字符串；
-/// - 字符串格式统一为 `[ERROR_CODE] message`。
-pub type CommandResult<T> = Result<T, String>;
+pub type CommandResult<T> = Result<T, CommandError>;
+
+/// 兼容开关：为 true 时 `CommandError` 序列化为旧版字符串格式。
+static LEGACY_STRING_ERRORS: AtomicBool = AtomicBool::new(false);
+
+/// 默认视为可重试的错误码后缀（瞬时性失败）。
+const RETRYABLE_CODE_SUFFIXES: &[&str] = &["_RATE_LIMITED", "_TIMEOUT"];
+
+/// 设置是否以旧版字符串格式序列化命令错误（由 settings 同步）。
+pub fn set_legacy_string_errors(enabled: bool) {
+    LEGACY_STRING_ERRORS.store(enabled, Ordering::Relaxed);
+}
+
+/// 当前是否启用旧版字符串错误格式。
+pub fn legacy_string_errors() -> bool {
+    LEGACY_STRING_ERRORS.load(Ordering::Relaxed)
+}
+
+/// Tauri command 结构化错误。
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CommandError {
+    /// 稳定错误码（大写下划线风格）。
+    pub code: String,
+    /// 面向用户的（已翻译）消息。
+    pub message: String,
+    /// 诊断细节（可选；release 构建下不携带原始错误）。
+    pub details: Option<String>,
+    /// 调用方是否可以直接重试。
+    pub retryable: bool,
+    /// 本次命令调用的关联 id（与日志中的 `tauri_command` span 对应）。
+    pub correlation_id: Option<String>,
+    /// 前端传入的 `requestId`（原样回显）。
+    pub request_id: Option<String>,
+}
+
+impl CommandError {
+    /// 以错误码与消息构建错误（retryable 按错误码后缀推断，关联 id 取自当前命令 span）。
+    pub fn new(code: impl Into<String>, message: impl Into<String>) -> Self {
+        let code = code.into();
+        let retryable = is_retryable_code(&code);
+        let correlation = crate::shared::correlation::current_correlation().unwrap_or_default();
+        Self {
+            code,
+            message: message.into(),
+            details: None,
+            retryable,
+            correlation_id: (!correlation.correlation_id.is_empty())
+                .then_some(correlation.correlation_id),
+            request_id: correlation.request_id,
+        }
+    }
+
+    /// 附加诊断细节。
+    pub fn with_details(mut self, details: impl Into<String>) -> Self {
+        self.details = Some(details.into());
+        self
+    }
+
+    /// 显式覆盖是否可重试。
+    pub fn with_retryable(mut self, retryable: bool) -> Self {
+        self.retryable = retryable;
+        self
+    }
+
+    /// 旧版字符串格式：`[ERROR_CODE] message`。
+    pub fn to_legacy_string(&self) -> String {
+        format!("[{}] {}", self.code, self.message)
+    }
+}
+
+impl Display for CommandError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "[{}] {}", self.code, self.message)
+    }
+}
 
-/// 构建统一格式的命令错误消息（i18n版本）。
+impl std::error::Error for CommandError {}
+
+impl Serialize for CommandError {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        if legacy_string_errors() {
+            return serializer.serialize_str(&self.to_legacy_string());
+        }
+        let mut state = serializer.serialize_struct("CommandError", 6)?;
+        state.serialize_field("code", &self.code)?;
+        state.serialize_field("message", &self.message)?;
+        state.serialize_field("details", &self.details)?;
+        state.serialize_field("retryable", &self.retryable)?;
+        state.serialize_field("correlation_id", &self.correlation_id)?;
+        state.serialize_field("request_id", &self.request_id)?;
+        state.end()
+    }
+}
+
+/// 兼容历史代码中以字符串构造的错误（解析 `[ERROR_CODE] message` 前缀）。
+impl From<String> for CommandError {
+    fn from(raw: String) -> Self {
+        let parsed = raw
+            .strip_prefix('[')
+            .and_then(|rest| rest.split_once(']'))
+            .filter(|(code, _)| {
+                !code.is_empty()
+                    && code
+                        .chars()
+                        .all(|c| c.is_ascii_uppercase() || c.is_ascii_digit() || c == '_')
+            });
+        match parsed {
+            Some((code, message)) => Self::new(code, message.trim_start()),
+            None => Self::new("COMMAND_FAILED", raw),
+        }
+    }
+}
+
+impl From<&str> for CommandError {
+    fn from(raw: &str) -> Self {
+        Self::from(raw.to_string())
+    }
+}
+
+fn is_retryable_code(code: &str) -> bool {
+    RETRYABLE_CODE_SUFFIXES
+        .iter()
+        .any(|suffix| code.ends_with(suffix))
+}
+
+/// 构建统一格式的命令错误（i18n版本）。
 ///
 /// # 参数
 /// - `code`：稳定错误码（建议大写下划线风格）。
 /// - `i18n_key`：翻译key，格式为 `error.{code_lowercase}`。
 ///
 /// # 返回值
-/// 统一格式字符串：`[ERROR_CODE] 翻译后的消息`。
-pub fn command_error(code: &'static str, i18n_key: &str) -> String {
+/// `CommandError`，`message` 为翻译后的消息。
+pub fn command_error(code: &'static str, i18n_key: &str) -> CommandError {
     let message = rust_i18n::t!(i18n_key);
-    format!("[{code}] {message}")
+    CommandError::new(code, message)
 }
 
 /// 将任意可显示错误转换为统一命令错误，并输出结构化日志（i18n版本）。
@@ -32,15 +156,66 @@ pub fn command_error(code: &'static str, i18n_key: &str) -> String {
 /// # 参数
 /// - `code`：稳定错误码。
 /// - `i18n_key`：翻译key，格式为 `error.{code_lowercase}`。
-/// - `error`：原始错误对象（仅用于日志记录）。
+/// - `error`：原始错误对象（记录日志；debug 构建下同时写入 `details`）。
 ///
 /// # 返回值
-/// 统一格式字符串：`[ERROR_CODE] 翻译后的消息`。
-pub fn to_command_error<E>(code: &'static str, i18n_key: &str, error: E) -> String
+/// `CommandError`，`message` 为翻译后的消息。
+pub fn to_command_error<E>(code: &'static str, i18n_key: &str, error: E) -> CommandError
 where
     E: Display,
 {
     let raw_message = error.to_string();
     tracing::error!(action = "tauri_command_failed", code, error = %raw_message);
-    command_error(code, i18n_key)
+    let command_error = command_error(code, i18n_key);
+    if cfg!(debug_assertions) {
+        command_error.with_details(raw_message)
+    } else {
+        command_error
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn from_legacy_string_extracts_code() {
+        let error = CommandError::from("[LOG_OPEN_FAILED] no such file".to_string());
+        assert_eq!(error.code, "LOG_OPEN_FAILED");
+        assert_eq!(error.message, "no such file");
+        assert_eq!(error.to_string(), "[LOG_OPEN_FAILED] no such file");
+    }
+
+    #[test]
+    fn from_plain_string_uses_generic_code() {
+        let error = CommandError::from("mutex poisoned");
+        assert_eq!(error.code, "COMMAND_FAILED");
+        assert_eq!(error.message, "mutex poisoned");
+        assert!(!error.retryable);
+    }
+
+    #[test]
+    fn retryable_is_inferred_from_code_suffix() {
+        assert!(CommandError::new("TRANSLATION_RATE_LIMITED", "slow down").retryable);
+        assert!(!CommandError::new("TRANSLATION_FAILED", "nope").retryable);
+        assert!(
+            CommandError::new("NETWORK_TCP_SEND_FAILED", "x")
+                .with_retryable(true)
+                .retryable
+        );
+    }
+
+    #[test]
+    fn serializes_struct_or_legacy_string() {
+        let error = CommandError::new("DATA_DIR_UNCHANGED", "same dir").with_details("d");
+        let value = serde_json::to_value(&error).expect("serialize");
+        assert_eq!(value["code"], "DATA_DIR_UNCHANGED");
+        assert_eq!(value["details"], "d");
+        assert_eq!(value["retryable"], false);
+
+        set_legacy_string_errors(true);
+        let legacy = serde_json::to_value(&error).expect("serialize");
+        set_legacy_string_errors(false);
+        assert_eq!(legacy, "[DATA_DIR_UNCHANGED] same dir");
+    }
 }
@@ -0,0 +1,325 @@
+//! shared｜事件总线端口（event bus）。
+//!
+//! 说明：
+//! - 用例层与 DI 层只经 [`EventBus`] 发出事件，不直接调用 `tauri::Emitter`，因此可在
+//!   `cargo test` 与命令行子命令中无界面执行；
+//! - 生产环境由 `AppHandle` 实现（经 Tauri 事件总线投递）；命令行使用 [`NoopEventBus`]；
+//!   测试使用 [`RecordingEventBus`] 断言发出的事件；
+//! - 载荷先序列化为 JSON 再投递，保证 trait 可作为 `dyn` 使用；泛型便捷方法见 [`EventBusExt`]；
+//! - 高频事件（下载/传输进度等）可经 [`EventChannel`] 按 key 合并：同一 key 在间隔内只投递
+//!   最新的一条，结束时调用 `flush` 补发尾部载荷。
+//!
+//! 约定：注释中文，日志英文（tracing）。
+
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use serde::Serialize;
+use tauri::{AppHandle, Emitter, EventTarget, Runtime};
+
+/// 事件总线端口。
+pub trait EventBus: Send + Sync {
+    /// 广播一条事件（载荷已序列化为 JSON）。
+    fn emit_value(&self, event: &str, payload: serde_json::Value);
+
+    /// 只投递给指定窗口标签的监听者。
+    fn emit_to_value(&self, label: &str, event: &str, payload: serde_json::Value);
+
+    /// 广播一条事件，但跳过 `excluded` 中的窗口（这些窗口已经由其他通道收到）。
+    fn emit_except_value(&self, excluded: &[String], event: &str, payload: serde_json::Value);
+}
+
+/// 序列化载荷；失败只记录日志。
+fn to_payload<S: Serialize>(event: &str, payload: S) -> Option<serde_json::Value> {
+    serde_json::to_value(payload)
+        .inspect_err(|e| {
+            tracing::warn!(action = "app_events_payload_serialize_failed", event, error = %e);
+        })
+        .ok()
+}
+
+/// [`EventBus`] 的泛型便捷方法（对具体类型与 `dyn EventBus` 均可用）。
+pub trait EventBusExt: EventBus {
+    /// 序列化载荷并广播。
+    fn emit<S: Serialize>(&self, event: &str, payload: S) {
+        if let Some(value) = to_payload(event, payload) {
+            self.emit_value(event, value);
+        }
+    }
+
+    /// 序列化载荷并投递给指定窗口。
+    fn emit_to<S: Serialize>(&self, label: &str, event: &str, payload: S) {
+        if let Some(value) = to_payload(event, payload) {
+            self.emit_to_value(label, event, value);
+        }
+    }
+
+    /// 序列化载荷并广播给 `excluded` 之外的窗口。
+    fn emit_except<S: Serialize>(&self, excluded: &[String], event: &str, payload: S) {
+        if let Some(value) = to_payload(event, payload) {
+            self.emit_except_value(excluded, event, value);
+        }
+    }
+}
+
+impl<T: EventBus + ?Sized> EventBusExt for T {}
+
+impl dyn EventBus {
+    /// 为高频事件创建合并通道（见 [`EventChannel`]）。
+    pub fn channel(self: Arc<Self>, event: &'static str, interval: Duration) -> EventChannel {
+        EventChannel::new(self, event, interval)
+    }
+}
+
+/// 生产实现：经 Tauri 事件总线投递，失败只记录日志。
+impl<R: Runtime> EventBus for AppHandle<R> {
+    fn emit_value(&self, event: &str, payload: serde_json::Value) {
+        if let Err(e) = Emitter::emit(self, event, payload) {
+            tracing::warn!(action = "app_events_emit_failed", event, error = ?e);
+        }
+    }
+
+    fn emit_to_value(&self, label: &str, event: &str, payload: serde_json::Value) {
+        if let Err(e) = Emitter::emit_to(self, label, event, payload) {
+            tracing::warn!(action = "app_events_emit_failed", event, label, error = ?e);
+        }
+    }
+
+    fn emit_except_value(&self, excluded: &[String], event: &str, payload: serde_json::Value) {
+        let result = self.emit_filter(event, payload, |target| match target {
+            EventTarget::AnyLabel { label }
+            | EventTarget::Window { label }
+            | EventTarget::Webview { label }
+            | EventTarget::WebviewWindow { label } => !excluded.contains(label),
+            _ => true,
+        });
+        if let Err(e) = result {
+            tracing::warn!(action = "app_events_emit_failed", event, error = ?e);
+        }
+    }
+}
+
+/// 丢弃所有事件（无界面执行时使用）。
+#[derive(Debug, Default, Clone, Copy)]
+pub struct NoopEventBus;
+
+impl NoopEventBus {
+    /// 构造共享实例。
+    pub fn shared() -> Arc<dyn EventBus> {
+        Arc::new(Self)
+    }
+}
+
+impl EventBus for NoopEventBus {
+    fn emit_value(&self, _event: &str, _payload: serde_json::Value) {}
+
+    fn emit_to_value(&self, _label: &str, _event: &str, _payload: serde_json::Value) {}
+
+    fn emit_except_value(&self, _excluded: &[String], _event: &str, _payload: serde_json::Value) {}
+}
+
+/// 已记录的一条事件。
+#[derive(Debug, Clone, PartialEq)]
+pub struct RecordedEvent {
+    pub event: String,
+    /// 目标窗口（`None` 表示广播）。
+    pub target: Option<String>,
+    /// 广播时跳过的窗口。
+    pub excluded: Vec<String>,
+    pub payload: serde_json::Value,
+}
+
+/// 按顺序记录所有事件（测试与无界面工具使用）。
+#[derive(Debug, Default)]
+pub struct RecordingEventBus {
+    events: Mutex<Vec<RecordedEvent>>,
+}
+
+impl RecordingEventBus {
+    /// 构造空记录器。
+    pub fn new() -> Arc<Self> {
+        Arc::new(Self::default())
+    }
+
+    fn record(&self, event: RecordedEvent) {
+        self.events
+            .lock()
+            .unwrap_or_else(|p| p.into_inner())
+            .push(event);
+    }
+
+    /// 取出并清空已记录的事件。
+    pub fn take(&self) -> Vec<RecordedEvent> {
+        std::mem::take(&mut *self.events.lock().unwrap_or_else(|p| p.into_inner()))
+    }
+
+    /// 已记录的某个事件的全部载荷。
+    pub fn payloads(&self, event: &str) -> Vec<serde_json::Value> {
+        self.events
+            .lock()
+            .unwrap_or_else(|p| p.into_inner())
+            .iter()
+            .filter(|recorded| recorded.event == event)
+            .map(|recorded| recorded.payload.clone())
+            .collect()
+    }
+}
+
+impl EventBus for RecordingEventBus {
+    fn emit_value(&self, event: &str, payload: serde_json::Value) {
+        self.record(RecordedEvent {
+            event: event.to_string(),
+            target: None,
+            excluded: Vec::new(),
+            payload,
+        });
+    }
+
+    fn emit_to_value(&self, label: &str, event: &str, payload: serde_json::Value) {
+        self.record(RecordedEvent {
+            event: event.to_string(),
+            target: Some(label.to_string()),
+            excluded: Vec::new(),
+            payload,
+        });
+    }
+
+    fn emit_except_value(&self, excluded: &[String], event: &str, payload: serde_json::Value) {
+        self.record(RecordedEvent {
+            event: event.to_string(),
+            target: None,
+            excluded: excluded.to_vec(),
+            payload,
+        });
+    }
+}
+
+/// 单个 key 的合并状态。
+#[derive(Default)]
+struct ChannelSlot {
+    last_emit: Option<Instant>,
+    pending: Option<serde_json::Value>,
+}
+
+/// 高频事件合并通道。
+///
+/// 说明：
+/// - 同一 key（例如下载任务 id）在 `interval` 内最多投递一次，期间只保留最新载荷；
+/// - `flush` 立即投递所有尚未发出的载荷并清空状态（流程结束时调用，保证最终状态送达）；
+/// - 不启动后台任务：尾部载荷由下一次 `send` 或 `flush` 送出。
+pub struct EventChannel {
+    bus: Arc<dyn EventBus>,
+    event: &'static str,
+    interval: Duration,
+    slots: Mutex<HashMap<String, ChannelSlot>>,
+}
+
+impl EventChannel {
+    /// 创建合并通道。
+    pub fn new(bus: Arc<dyn EventBus>, event: &'static str, interval: Duration) -> Self {
+        Self {
+            bus,
+            event,
+            interval,
+            slots: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// 提交一条载荷（可能被合并）。
+    pub fn send<S: Serialize>(&self, key: &str, payload: S) {
+        if let Some(value) = to_payload(self.event, payload) {
+            self.send_at(key, value, Instant::now());
+        }
+    }
+
+    fn send_at(&self, key: &str, value: serde_json::Value, now: Instant) {
+        let due = {
+            let mut slots = self.slots.lock().unwrap_or_else(|p| p.into_inner());
+            let slot = slots.entry(key.to_string()).or_default();
+            let due = slot
+                .last_emit
+                .is_none_or(|last| now.duration_since(last) >= self.interval);
+            if due {
+                slot.last_emit = Some(now);
+                slot.pending = None;
+                Some(value)
+            } else {
+                slot.pending = Some(value);
+                None
+            }
+        };
+        if let Some(value) = due {
+            self.bus.emit_value(self.event, value);
+        }
+    }
+
+    /// 立即投递所有被合并而尚未发出的载荷，并重置合并状态。
+    pub fn flush(&self) {
+        let pending: Vec<serde_json::Value> =
+            std::mem::take(&mut *self.slots.lock().unwrap_or_else(|p| p.into_inner()))
+                .into_values()
+                .filter_map(|slot| slot.pending)
+                .collect();
+        for value in pending {
+            self.bus.emit_value(self.event, value);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn recording_bus_keeps_order_targets_and_serializes_payloads() {
+        let bus = RecordingEventBus::new();
+        let events: &dyn EventBus = bus.as_ref();
+        events.emit("a", serde_json::json!({ "n": 1 }));
+        events.emit_to("main", "b", vec![1, 2]);
+        events.emit_except(&["mini".to_string()], "a", "two");
+        assert_eq!(
+            bus.payloads("a"),
+            vec![serde_json::json!({ "n": 1 }), serde_json::json!("two")]
+        );
+        let all = bus.take();
+        assert_eq!(all.len(), 3);
+        assert_eq!(all[1].target.as_deref(), Some("main"));
+        assert_eq!(all[2].excluded, vec!["mini".to_string()]);
+        assert!(bus.take().is_empty());
+    }
+
+    #[test]
+    fn channel_coalesces_per_key_and_flushes_latest() {
+        let bus = RecordingEventBus::new();
+        let channel =
+            (bus.clone() as Arc<dyn EventBus>).channel("progress", Duration::from_millis(100));
+        let start = Instant::now();
+        let send = |key: &str, value: i64, ms: u64| {
+            channel.send_at(
+                key,
+                serde_json::json!(value),
+                start + Duration::from_millis(ms),
+            )
+        };
+        send("t1", 1, 0);
+        send("t1", 2, 10);
+        send("t1", 3, 20);
+        // 其他 key 不受 t1 节流影响。
+        send("t2", 10, 20);
+        send("t1", 4, 150);
+        send("t1", 5, 160);
+        assert_eq!(
+            bus.payloads("progress"),
+            vec![
+                serde_json::json!(1),
+                serde_json::json!(10),
+                serde_json::json!(4)
+            ]
+        );
+        channel.flush();
+        channel.flush();
+        assert_eq!(bus.payloads("progress").last(), Some(&serde_json::json!(5)));
+        assert_eq!(bus.payloads("progress").len(), 4);
+    }
+}
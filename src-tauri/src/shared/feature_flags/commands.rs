@@ -0,0 +1,88 @@
+//! feature_flags｜Tauri 命令
+
+use tauri::AppHandle;
+
+use crate::features::network::data::http_client::ReqwestApiRequestAdapter;
+use crate::features::network::usecases::api_usecases::{self, ApiJsonRequest};
+use crate::shared::error::CommandResult;
+use crate::shared::error::to_command_error;
+use crate::shared::events::EventBusExt;
+
+use super::{
+    FEATURE_FLAGS_CHANGED_EVENT, FeatureFlags, current_feature_flags, set_server_overrides,
+};
+
+/// 获取当前生效的 feature flag 表。
+#[tauri::command]
+pub async fn get_feature_flags() -> CommandResult<FeatureFlags> {
+    Ok(current_feature_flags().await)
+}
+
+/// 从服务端 `/api/server` 拉取 `feature_flags` 覆盖表并应用。
+///
+/// # 参数
+/// - `server_socket`：服务端 socket。
+/// - `tls_policy`/`tls_fingerprint`：TLS 相关参数（可选）。
+///
+/// # 返回值
+/// 应用后的生效 flag 表；若发生变化会广播 `feature-flags-changed` 事件。
+///
+/// # 说明
+/// 服务端未下发 `feature_flags` 时视为清空服务端覆盖。
+#[tauri::command]
+pub async fn refresh_feature_flags(
+    app: AppHandle,
+    server_socket: String,
+    tls_policy: Option<String>,
+    tls_fingerprint: Option<String>,
+) -> CommandResult<FeatureFlags> {
+    let api_request_port = ReqwestApiRequestAdapter::shared();
+    let response = api_usecases::api_request_json(
+        ApiJsonRequest {
+            server_socket,
+            method: "GET".to_string(),
+            path: "/api/server".to_string(),
+            headers: None,
+            body: None,
+            tls_policy,
+            tls_fingerprint,
+        },
+        api_request_port.as_ref(),
+    )
+    .await
+    .map_err(|e| {
+        to_command_error(
+            "FEATURE_FLAGS_REFRESH_FAILED",
+            "error.feature_flags_refresh_failed",
+            e,
+        )
+    })?;
+    if !response.ok {
+        return Err(to_command_error(
+            "FEATURE_FLAGS_REFRESH_FAILED",
+            "error.feature_flags_refresh_failed",
+            format!("GET /api/server returned {}", response.status),
+        ));
+    }
+
+    let overrides: FeatureFlags = response
+        .body
+        .as_ref()
+        .and_then(|body| body.get("feature_flags"))
+        .and_then(|flags| flags.as_object())
+        .map(|flags| {
+            flags
+                .iter()
+                .filter_map(|(key, value)| value.as_bool().map(|b| (key.clone(), b)))
+                .collect()
+        })
+        .unwrap_or_default();
+
+    let changed = set_server_overrides(overrides);
+    let flags = current_feature_flags().await;
+    if changed {
+        tracing::info!(action = "app_feature_flags_server_overrides_applied");
+        app.emit(FEATURE_FLAGS_CHANGED_EVENT, &flags);
+    }
+    Ok(flags)
+}
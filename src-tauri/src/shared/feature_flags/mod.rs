@@ -0,0 +1,115 @@
+//! 模块入口：feature_flags。
+//!
+//! 说明：轻量 feature flag（编译期默认值 + 服务端下发 + 本地配置覆盖），用于高风险子系统灰度。
+//! 各 flag 在对应子系统的命令入口检查（见各常量的说明）。
+//!
+//! 约定：注释中文，日志英文（tracing）。
+//!
+//! 优先级：编译期默认值 < 服务端 `/api/server` 的 `feature_flags` < 本地 config 的 `featureFlags`。
+//! 只有出现在 `FEATURE_FLAG_DEFAULTS` 中的 key 才会生效，未知 key 一律忽略。
+
+pub mod commands;
+
+use std::collections::BTreeMap;
+use std::sync::{OnceLock, RwLock};
+
+/// 前端订阅的 flag 变更事件名。
+pub const FEATURE_FLAGS_CHANGED_EVENT: &str = "feature-flags-changed";
+
+/// 游标增量同步（`sync_channel_delta`）；关闭时按服务端不支持处理，调用方回退全量补拉。
+pub const SYNC_ENGINE_V2: &str = "sync_engine_v2";
+/// 消息翻译（`translate_message`）。
+pub const MESSAGE_TRANSLATION: &str = "message_translation";
+/// 设置同步（`sync_settings_now`）；关闭时视同用户未启用同步。
+pub const SETTINGS_SYNC: &str = "settings_sync";
+
+/// 编译期默认值。
+pub const FEATURE_FLAG_DEFAULTS: &[(&str, bool)] = &[
+    (SYNC_ENGINE_V2, false),
+    (MESSAGE_TRANSLATION, true),
+    (SETTINGS_SYNC, true),
+];
+
+pub type FeatureFlags = BTreeMap<String, bool>;
+
+static SERVER_OVERRIDES: OnceLock<RwLock<FeatureFlags>> = OnceLock::new();
+
+fn server_overrides() -> &'static RwLock<FeatureFlags> {
+    SERVER_OVERRIDES.get_or_init(|| RwLock::new(BTreeMap::new()))
+}
+
+/// 按优先级合并默认值与覆盖表。
+pub fn merge_feature_flags(server: &FeatureFlags, local: &FeatureFlags) -> FeatureFlags {
+    FEATURE_FLAG_DEFAULTS
+        .iter()
+        .map(|(key, default)| {
+            let value = local
+                .get(*key)
+                .or_else(|| server.get(*key))
+                .copied()
+                .unwrap_or(*default);
+            (key.to_string(), value)
+        })
+        .collect()
+}
+
+/// 替换服务端下发的覆盖表。
+///
+/// # 返回值
+/// 覆盖表是否发生变化（调用方据此决定是否广播事件）。
+pub fn set_server_overrides(overrides: FeatureFlags) -> bool {
+    let Ok(mut guard) = server_overrides().write() else {
+        tracing::warn!(action = "app_feature_flags_server_overrides_lock_failed");
+        return false;
+    };
+    if *guard == overrides {
+        return false;
+    }
+    *guard = overrides;
+    true
+}
+
+/// 当前生效的 flag 表。
+pub async fn current_feature_flags() -> FeatureFlags {
+    let local = crate::features::settings::data::config_store::get_feature_flag_overrides().await;
+    let server = server_overrides()
+        .read()
+        .map(|guard| guard.clone())
+        .unwrap_or_default();
+    merge_feature_flags(&server, &local)
+}
+
+/// 判断单个 flag 是否启用（未知 key 视为关闭）。
+pub async fn is_feature_enabled(key: &str) -> bool {
+    current_feature_flags()
+        .await
+        .get(key)
+        .copied()
+        .unwrap_or(false)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn flags(entries: &[(&str, bool)]) -> FeatureFlags {
+        entries.iter().map(|(k, v)| (k.to_string(), *v)).collect()
+    }
+
+    #[test]
+    fn merge_feature_flags_applies_priority_and_ignores_unknown_keys() {
+        let server = flags(&[
+            ("settings_sync", false),
+            ("sync_engine_v2", true),
+            ("unknown", true),
+        ]);
+        let local = flags(&[("sync_engine_v2", false)]);
+        let merged = merge_feature_flags(&server, &local);
+
+        assert_eq!(merged.get("settings_sync"), Some(&false));
+        assert_eq!(merged.get("sync_engine_v2"), Some(&false));
+        assert_eq!(merged.get("message_translation"), Some(&true));
+        assert!(!merged.contains_key("unknown"));
+        assert_eq!(merged.len(), FEATURE_FLAG_DEFAULTS.len());
+    }
+}
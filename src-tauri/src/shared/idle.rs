@@ -0,0 +1,56 @@
+//! shared｜用户空闲检测。
+//!
+//! 说明：
+//! - 前端在用户有键鼠操作时（节流后）调用 `report_user_activity`；
+//! - 后端以进程内单调时钟记录最近一次活动时间，供自动锁定等功能查询空闲时长；
+//! - 进程启动时视为一次活动，避免启动即被判定为长时间空闲。
+//!
+//! 约定：注释中文，日志英文（tracing）。
+
+use std::sync::OnceLock;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::{Duration, Instant};
+
+use crate::shared::error::CommandResult;
+
+/// 进程内时钟起点。
+static EPOCH: OnceLock<Instant> = OnceLock::new();
+/// 最近一次活动距 `EPOCH` 的毫秒数。
+static LAST_ACTIVITY_MS: AtomicU64 = AtomicU64::new(0);
+
+fn elapsed_ms() -> u64 {
+    EPOCH.get_or_init(Instant::now).elapsed().as_millis() as u64
+}
+
+/// 记录一次用户活动。
+pub fn record_activity() {
+    LAST_ACTIVITY_MS.fetch_max(elapsed_ms(), Ordering::Relaxed);
+}
+
+/// 距最近一次用户活动的时长。
+pub fn idle_for() -> Duration {
+    let now = elapsed_ms();
+    let last = LAST_ACTIVITY_MS.load(Ordering::Relaxed);
+    Duration::from_millis(now.saturating_sub(last))
+}
+
+/// 上报用户活动（前端节流调用）。
+#[tauri::command]
+pub fn report_user_activity() -> CommandResult<()> {
+    record_activity();
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn activity_resets_idle_duration() {
+        record_activity();
+        std::thread::sleep(Duration::from_millis(20));
+        assert!(idle_for() >= Duration::from_millis(20));
+        record_activity();
+        assert!(idle_for() < Duration::from_millis(20));
+    }
+}
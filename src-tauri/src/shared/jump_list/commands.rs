@@ -0,0 +1,39 @@
+//! jump_list｜Tauri 命令
+
+use crate::shared::error::{CommandResult, command_error};
+
+use super::{JumpListEntry, RecentChannel, refresh, report_recent};
+
+/// 上报最近打开的频道（前端切换频道时调用），并更新跳转列表 / Dock 菜单。
+///
+/// # 参数
+/// - `channel`：频道所在 server socket、频道 id 与展示名（为空时显示 `#<id>`）。
+#[tauri::command]
+pub async fn report_recent_channel(channel: RecentChannel) -> CommandResult<()> {
+    let server_socket = channel.server_socket.trim().to_string();
+    if server_socket.is_empty() {
+        return Err(command_error(
+            "JUMP_LIST_SERVER_SOCKET_REQUIRED",
+            "error.jump_list_server_socket_required",
+        ));
+    }
+    let title = match channel.title.trim() {
+        "" => format!("#{}", channel.channel_id),
+        title => title.to_string(),
+    };
+    report_recent(RecentChannel {
+        server_socket,
+        channel_id: channel.channel_id,
+        title,
+    });
+    Ok(())
+}
+
+/// 立即重新发布跳转列表 / Dock 菜单（前端打开 system DB 后调用一次）。
+///
+/// # 返回值
+/// 发布的条目（收藏在前、最近在后）。
+#[tauri::command]
+pub async fn refresh_jump_list() -> CommandResult<Vec<JumpListEntry>> {
+    Ok(refresh().await)
+}
@@ -0,0 +1,229 @@
+//! shared｜系统快捷入口：Windows 跳转列表 / macOS Dock 菜单中的常用频道。
+//!
+//! 说明：
+//! - 条目由两部分组成：收藏频道（“已固定”，按收藏顺序）与最近打开的频道（“最近”，
+//!   由前端切换频道时经 `report_recent_channel` 上报，只保存在内存中）；
+//! - 收藏增删、最近频道变化时重新发布；前端在 system DB 打开后调用 `refresh_jump_list`
+//!   完成首次发布；
+//! - 每个条目以频道深链接描述（见 `app::deep_link`）：Windows 跳转列表以深链接为参数
+//!   启动本程序，经单实例回调转交已运行的实例；macOS Dock 菜单直接在进程内打开；
+//! - 其他平台（Linux、移动端）没有对应入口，发布为空操作。
+//!
+//! 约定：注释中文，日志英文（tracing）。
+
+pub mod commands;
+mod shell;
+
+use std::sync::{Mutex, OnceLock};
+
+use serde::{Deserialize, Serialize};
+use tauri::AppHandle;
+
+use crate::app::deep_link::ChannelLink;
+use crate::shared::db::favorites::{FavoriteWithState, list_favorites_with_state};
+
+/// 最多展示的收藏频道数。
+pub const MAX_PINNED: usize = 10;
+/// 最多展示的最近频道数。
+pub const MAX_RECENT: usize = 5;
+/// 内存中保留的最近频道数（被收藏占用的条目不展示，多留一些用于补位）。
+const RECENT_CAPACITY: usize = MAX_PINNED + MAX_RECENT;
+
+/// 最近打开的频道（前端 -> Rust）。
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RecentChannel {
+    pub server_socket: String,
+    pub channel_id: i64,
+    /// 展示名（频道名或别名）。
+    pub title: String,
+}
+
+/// 条目分组。
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum JumpListGroup {
+    Pinned,
+    Recent,
+}
+
+/// 已发布的一个条目。
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct JumpListEntry {
+    pub group: JumpListGroup,
+    pub title: String,
+    pub link: ChannelLink,
+}
+
+static APP: OnceLock<AppHandle> = OnceLock::new();
+static RECENT: Mutex<Vec<RecentChannel>> = Mutex::new(Vec::new());
+/// 最近一次发布的条目（macOS Dock 菜单按下标回查）。
+static PUBLISHED: Mutex<Vec<JumpListEntry>> = Mutex::new(Vec::new());
+
+/// 记录 AppHandle（setup 阶段调用一次）。
+pub fn init(app: &AppHandle) {
+    let _ = APP.set(app.clone());
+}
+
+/// 把频道移到最近列表最前（去重并截断）。
+pub fn push_recent(recent: &mut Vec<RecentChannel>, channel: RecentChannel) {
+    recent.retain(|c| {
+        !(c.server_socket == channel.server_socket && c.channel_id == channel.channel_id)
+    });
+    recent.insert(0, channel);
+    recent.truncate(RECENT_CAPACITY);
+}
+
+/// 合并收藏与最近频道：收藏在前，最近频道中已收藏的不重复展示。
+pub fn build_entries(pinned: &[RecentChannel], recent: &[RecentChannel]) -> Vec<JumpListEntry> {
+    let entry = |group, channel: &RecentChannel| JumpListEntry {
+        group,
+        title: channel.title.clone(),
+        link: ChannelLink {
+            server_socket: channel.server_socket.clone(),
+            channel_id: channel.channel_id,
+        },
+    };
+    let pinned: Vec<JumpListEntry> = pinned
+        .iter()
+        .take(MAX_PINNED)
+        .map(|c| entry(JumpListGroup::Pinned, c))
+        .collect();
+    let recent = recent
+        .iter()
+        .filter(|c| {
+            !pinned.iter().any(|p| {
+                p.link.server_socket == c.server_socket && p.link.channel_id == c.channel_id
+            })
+        })
+        .take(MAX_RECENT)
+        .map(|c| entry(JumpListGroup::Recent, c))
+        .collect::<Vec<_>>();
+    pinned.into_iter().chain(recent).collect()
+}
+
+/// 收藏的展示名：别名 > 本地缓存的频道名 > `#<id>`。
+fn favorite_channel(state: FavoriteWithState) -> RecentChannel {
+    let favorite = state.favorite;
+    RecentChannel {
+        title: favorite
+            .alias
+            .or(state.channel_name)
+            .unwrap_or_else(|| format!("#{}", favorite.channel_id)),
+        server_socket: favorite.server_socket,
+        channel_id: favorite.channel_id,
+    }
+}
+
+/// 记录最近打开的频道并重新发布。
+pub fn report_recent(channel: RecentChannel) {
+    push_recent(
+        &mut RECENT.lock().unwrap_or_else(|p| p.into_inner()),
+        channel,
+    );
+    schedule_refresh();
+}
+
+/// 在后台重新发布（收藏变化后调用）。
+pub fn schedule_refresh() {
+    tauri::async_runtime::spawn(async {
+        refresh().await;
+    });
+}
+
+/// 读取收藏与最近频道并发布到系统入口。
+///
+/// # 说明
+/// system DB 尚未打开时只发布最近频道。
+pub async fn refresh() -> Vec<JumpListEntry> {
+    let pinned: Vec<RecentChannel> = match list_favorites_with_state().await {
+        Ok(favorites) => favorites.into_iter().map(favorite_channel).collect(),
+        Err(e) => {
+            tracing::debug!(action = "app_jump_list_favorites_unavailable", error = ?e);
+            Vec::new()
+        }
+    };
+    let recent = RECENT.lock().unwrap_or_else(|p| p.into_inner()).clone();
+    let entries = build_entries(&pinned, &recent);
+    *PUBLISHED.lock().unwrap_or_else(|p| p.into_inner()) = entries.clone();
+    if let Some(app) = APP.get() {
+        shell::publish(app, entries.clone());
+    }
+    entries
+}
+
+/// 打开已发布的第 `index` 个条目（macOS Dock 菜单回调）。
+#[cfg_attr(not(target_os = "macos"), allow(dead_code))]
+fn activate(index: usize) {
+    let entry = PUBLISHED
+        .lock()
+        .unwrap_or_else(|p| p.into_inner())
+        .get(index)
+        .cloned();
+    match (entry, APP.get()) {
+        (Some(entry), Some(app)) => crate::app::deep_link::open_channel(app, entry.link),
+        _ => tracing::warn!(action = "app_jump_list_entry_missing", index),
+    }
+}
+
+/// 分组标题（按当前界面语言）。
+#[cfg_attr(not(any(target_os = "windows", target_os = "macos")), allow(dead_code))]
+fn group_title(group: JumpListGroup) -> String {
+    match group {
+        JumpListGroup::Pinned => rust_i18n::t!("jump_list.pinned").to_string(),
+        JumpListGroup::Recent => rust_i18n::t!("jump_list.recent").to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn channel(socket: &str, id: i64) -> RecentChannel {
+        RecentChannel {
+            server_socket: socket.to_string(),
+            channel_id: id,
+            title: format!("{socket}#{id}"),
+        }
+    }
+
+    #[test]
+    fn recent_moves_to_front_without_duplicates() {
+        let mut recent = Vec::new();
+        push_recent(&mut recent, channel("a", 1));
+        push_recent(&mut recent, channel("a", 2));
+        push_recent(&mut recent, channel("a", 1));
+        assert_eq!(recent, vec![channel("a", 1), channel("a", 2)]);
+        for id in 0..100 {
+            push_recent(&mut recent, channel("b", id));
+        }
+        assert_eq!(recent.len(), RECENT_CAPACITY);
+        assert_eq!(recent[0], channel("b", 99));
+    }
+
+    #[test]
+    fn pinned_come_first_and_are_not_repeated_in_recent() {
+        let pinned = [channel("a", 1), channel("b", 1)];
+        let recent = [channel("b", 1), channel("a", 2), channel("c", 3)];
+        let entries = build_entries(&pinned, &recent);
+        let summary: Vec<(JumpListGroup, &str)> = entries
+            .iter()
+            .map(|e| (e.group, e.title.as_str()))
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                (JumpListGroup::Pinned, "a#1"),
+                (JumpListGroup::Pinned, "b#1"),
+                (JumpListGroup::Recent, "a#2"),
+                (JumpListGroup::Recent, "c#3"),
+            ]
+        );
+
+        let many: Vec<RecentChannel> = (0..20).map(|id| channel("r", id)).collect();
+        let entries = build_entries(&many, &many);
+        assert_eq!(entries.len(), MAX_PINNED + MAX_RECENT);
+        assert_eq!(entries[MAX_PINNED].link.channel_id, MAX_PINNED as i64);
+    }
+}
@@ -0,0 +1,307 @@
+//! jump_list｜系统入口发布（Windows 跳转列表 / macOS Dock 菜单）。
+//!
+//! 说明：
+//! - Windows 使用 `ICustomDestinationList`：每个条目是指向本程序的 Shell Link，参数为频道
+//!   深链接；用户手动移除过的条目不能再次加入（否则 `AppendCategory` 整体失败），发布前过滤；
+//!   COM 调用为阻塞调用，在 `spawn_blocking` 中执行；
+//! - macOS 的 Dock 菜单由应用代理的 `applicationDockMenu:` 提供，tao 的代理未实现该方法，
+//!   首次发布时在运行时补上；菜单与回调目标均为 AppKit 对象，只能在主线程创建与访问；
+//! - 其它平台为空操作。
+//!
+//! 约定：注释中文，日志英文（tracing）。
+
+use tauri::AppHandle;
+
+use super::JumpListEntry;
+
+/// 发布到当前平台的系统入口（失败只记录日志）。
+pub fn publish(app: &AppHandle, entries: Vec<JumpListEntry>) {
+    tracing::info!(action = "app_jump_list_publish", count = entries.len());
+    platform::publish(app, entries);
+}
+
+#[cfg(target_os = "windows")]
+mod platform {
+    use std::collections::HashSet;
+
+    use anyhow::Context;
+    use tauri::AppHandle;
+    use windows::Win32::Storage::EnhancedStorage::PKEY_Title;
+    use windows::Win32::System::Com::{
+        CLSCTX_INPROC_SERVER, COINIT_APARTMENTTHREADED, CoCreateInstance, CoInitializeEx,
+        CoUninitialize,
+    };
+    use windows::Win32::UI::Shell::Common::{IObjectArray, IObjectCollection};
+    use windows::Win32::UI::Shell::PropertiesSystem::IPropertyStore;
+    use windows::Win32::UI::Shell::{
+        DestinationList, EnumerableObjectCollection, ICustomDestinationList, IShellLinkW, ShellLink,
+    };
+    use windows::core::{HSTRING, Interface, PCWSTR, PROPVARIANT};
+
+    use super::super::{JumpListEntry, JumpListGroup, group_title};
+
+    /// 当前线程的 COM 初始化（作用域结束时反初始化）。
+    struct ComScope;
+
+    impl ComScope {
+        fn enter() -> anyhow::Result<Self> {
+            unsafe { CoInitializeEx(None, COINIT_APARTMENTTHREADED) }
+                .ok()
+                .context("CoInitializeEx failed")?;
+            Ok(Self)
+        }
+    }
+
+    impl Drop for ComScope {
+        fn drop(&mut self) {
+            unsafe { CoUninitialize() };
+        }
+    }
+
+    pub fn publish(_app: &AppHandle, entries: Vec<JumpListEntry>) {
+        tauri::async_runtime::spawn_blocking(move || {
+            if let Err(e) = replace_list(&entries) {
+                tracing::warn!(action = "app_jump_list_publish_failed", error = %e);
+            }
+        });
+    }
+
+    /// 读取 Shell Link 的启动参数。
+    fn link_arguments(link: &IShellLinkW) -> String {
+        let mut buf = [0u16; 1024];
+        if unsafe { link.GetArguments(&mut buf) }.is_err() {
+            return String::new();
+        }
+        let len = buf.iter().position(|&c| c == 0).unwrap_or(buf.len());
+        String::from_utf16_lossy(&buf[..len])
+    }
+
+    /// 用户从跳转列表中移除过的条目参数。
+    fn removed_arguments(removed: &IObjectArray) -> HashSet<String> {
+        let count = unsafe { removed.GetCount() }.unwrap_or(0);
+        (0..count)
+            .filter_map(|i| unsafe { removed.GetAt::<IShellLinkW>(i) }.ok())
+            .map(|link| link_arguments(&link))
+            .collect()
+    }
+
+    fn shell_link(
+        exe: &HSTRING,
+        entry: &JumpListEntry,
+        arguments: &str,
+    ) -> anyhow::Result<IShellLinkW> {
+        unsafe {
+            let link: IShellLinkW = CoCreateInstance(&ShellLink, None, CLSCTX_INPROC_SERVER)?;
+            link.SetPath(exe)?;
+            link.SetArguments(&HSTRING::from(arguments))?;
+            link.SetIconLocation(exe, 0)?;
+            // 跳转列表显示的是 PKEY_Title，而不是描述。
+            let store: IPropertyStore = link.cast()?;
+            store.SetValue(&PKEY_Title, &PROPVARIANT::from(entry.title.as_str()))?;
+            store.Commit()?;
+            Ok(link)
+        }
+    }
+
+    /// 以 `entries` 替换本程序的跳转列表自定义分组。
+    fn replace_list(entries: &[JumpListEntry]) -> anyhow::Result<()> {
+        let _com = ComScope::enter()?;
+        let exe = HSTRING::from(
+            std::env::current_exe()
+                .context("Failed to resolve current exe")?
+                .as_os_str(),
+        );
+        unsafe {
+            let list: ICustomDestinationList =
+                CoCreateInstance(&DestinationList, None, CLSCTX_INPROC_SERVER)?;
+            if entries.is_empty() {
+                list.DeleteList(PCWSTR::null())?;
+                return Ok(());
+            }
+            let mut max_slots = 0u32;
+            let removed: IObjectArray = list.BeginList(&mut max_slots)?;
+            let removed = removed_arguments(&removed);
+
+            let mut remaining = max_slots as usize;
+            for group in [JumpListGroup::Pinned, JumpListGroup::Recent] {
+                let collection: IObjectCollection =
+                    CoCreateInstance(&EnumerableObjectCollection, None, CLSCTX_INPROC_SERVER)?;
+                let mut added = 0usize;
+                for entry in entries.iter().filter(|e| e.group == group) {
+                    if added >= remaining {
+                        break;
+                    }
+                    let arguments = entry.link.to_url()?;
+                    if removed.contains(&arguments) {
+                        continue;
+                    }
+                    collection.AddObject(&shell_link(&exe, entry, &arguments)?)?;
+                    added += 1;
+                }
+                if added == 0 {
+                    continue;
+                }
+                remaining -= added;
+                let array: IObjectArray = collection.cast()?;
+                list.AppendCategory(&HSTRING::from(group_title(group)), &array)?;
+            }
+            list.CommitList()?;
+        }
+        Ok(())
+    }
+}
+
+// objc2 绑定在不同版本中对方法安全性的标注不同，统一包在 unsafe 中。
+#[cfg(target_os = "macos")]
+#[allow(unused_unsafe)]
+mod platform {
+    use std::cell::{Cell, RefCell};
+
+    use objc2::rc::Retained;
+    use objc2::runtime::{AnyClass, AnyObject, Imp, NSObject, Sel};
+    use objc2::{MainThreadMarker, MainThreadOnly, define_class, msg_send, sel};
+    use objc2_app_kit::{NSApplication, NSMenu, NSMenuItem};
+    use objc2_foundation::{NSString, ns_string};
+    use tauri::AppHandle;
+
+    use super::super::{JumpListEntry, activate, group_title};
+
+    define_class!(
+        // SAFETY: 直接继承 NSObject，不实现 Drop，只在主线程使用。
+        #[unsafe(super(NSObject))]
+        #[thread_kind = MainThreadOnly]
+        #[name = "CarryPigeonDockMenuTarget"]
+        struct DockMenuTarget;
+
+        impl DockMenuTarget {
+            /// Dock 菜单项点击回调：`tag` 为条目下标。
+            #[unsafe(method(openEntry:))]
+            fn open_entry(&self, sender: &NSMenuItem) {
+                activate(unsafe { sender.tag() }.max(0) as usize);
+            }
+        }
+    );
+
+    impl DockMenuTarget {
+        fn new(mtm: MainThreadMarker) -> Retained<Self> {
+            unsafe { msg_send![Self::alloc(mtm), init] }
+        }
+    }
+
+    thread_local! {
+        static DOCK_MENU: RefCell<Option<Retained<NSMenu>>> = const { RefCell::new(None) };
+        static TARGET: RefCell<Option<Retained<DockMenuTarget>>> = const { RefCell::new(None) };
+        static HOOK_INSTALLED: Cell<bool> = const { Cell::new(false) };
+    }
+
+    /// `-[NSApplicationDelegate applicationDockMenu:]`：返回当前菜单（不转移所有权）。
+    extern "C-unwind" fn application_dock_menu(
+        _this: &AnyObject,
+        _cmd: Sel,
+        _sender: &AnyObject,
+    ) -> *mut NSMenu {
+        DOCK_MENU.with_borrow(|menu| {
+            menu.as_ref().map_or(std::ptr::null_mut(), |menu| {
+                Retained::as_ptr(menu).cast_mut()
+            })
+        })
+    }
+
+    /// 为应用代理补上 `applicationDockMenu:`（只执行一次）。
+    fn install_hook(mtm: MainThreadMarker) {
+        if HOOK_INSTALLED.get() {
+            return;
+        }
+        let app = NSApplication::sharedApplication(mtm);
+        let delegate: Option<Retained<AnyObject>> = unsafe { msg_send![&app, delegate] };
+        let Some(delegate) = delegate else {
+            tracing::warn!(action = "app_jump_list_dock_delegate_missing");
+            return;
+        };
+        let class: &AnyClass = delegate.class();
+        let imp: Imp = unsafe {
+            std::mem::transmute::<
+                extern "C-unwind" fn(&AnyObject, Sel, &AnyObject) -> *mut NSMenu,
+                Imp,
+            >(application_dock_menu)
+        };
+        // 已实现该方法时 class_addMethod 返回 NO，保持原实现。
+        let added = unsafe {
+            objc2::ffi::class_addMethod(
+                (class as *const AnyClass).cast_mut(),
+                sel!(applicationDockMenu:),
+                imp,
+                c"@@:@".as_ptr(),
+            )
+        };
+        tracing::info!(
+            action = "app_jump_list_dock_hook_installed",
+            added = added.as_bool()
+        );
+        HOOK_INSTALLED.set(true);
+    }
+
+    fn build_menu(mtm: MainThreadMarker, entries: &[JumpListEntry]) -> Retained<NSMenu> {
+        let target = TARGET.with_borrow_mut(|target| {
+            target
+                .get_or_insert_with(|| DockMenuTarget::new(mtm))
+                .clone()
+        });
+        let target: &AnyObject = &target;
+        let menu = NSMenu::new(mtm);
+        unsafe { menu.setAutoenablesItems(false) };
+        let mut last_group = None;
+        for (index, entry) in entries.iter().enumerate() {
+            if last_group != Some(entry.group) {
+                if last_group.is_some() {
+                    menu.addItem(&NSMenuItem::separatorItem(mtm));
+                }
+                // 分组标题：不可点击的灰色项。
+                let header = NSMenuItem::new(mtm);
+                unsafe {
+                    header.setTitle(&NSString::from_str(&group_title(entry.group)));
+                    header.setEnabled(false);
+                }
+                menu.addItem(&header);
+                last_group = Some(entry.group);
+            }
+            let item = unsafe {
+                NSMenuItem::initWithTitle_action_keyEquivalent(
+                    NSMenuItem::alloc(mtm),
+                    &NSString::from_str(&entry.title),
+                    Some(sel!(openEntry:)),
+                    ns_string!(""),
+                )
+            };
+            unsafe {
+                item.setTarget(Some(target));
+                item.setTag(index as isize);
+            }
+            menu.addItem(&item);
+        }
+        menu
+    }
+
+    pub fn publish(app: &AppHandle, entries: Vec<JumpListEntry>) {
+        let result = app.run_on_main_thread(move || {
+            let Some(mtm) = MainThreadMarker::new() else {
+                return;
+            };
+            install_hook(mtm);
+            let menu = (!entries.is_empty()).then(|| build_menu(mtm, &entries));
+            DOCK_MENU.with_borrow_mut(|current| *current = menu);
+        });
+        if let Err(e) = result {
+            tracing::warn!(action = "app_jump_list_publish_failed", error = %e);
+        }
+    }
+}
+
+#[cfg(not(any(target_os = "windows", target_os = "macos")))]
+mod platform {
+    use tauri::AppHandle;
+
+    use super::super::JumpListEntry;
+
+    pub fn publish(_app: &AppHandle, _entries: Vec<JumpListEntry>) {}
+}
@@ -8,6 +8,8 @@
 //! - 注释统一使用中文，便于团队维护与交接。
 //! - 日志输出统一使用英文，便于跨端检索与与上游/第三方日志对齐。
 
+pub mod subscriber;
+
 use std::sync::OnceLock;
 
 use tracing::{debug, error, info, warn};
@@ -0,0 +1,154 @@
+//! shared｜全局 tracing subscriber 组装（stderr + 文件日志 + correlation/指标层）。
+//!
+//! 说明：
+//! - 全局 subscriber 只能安装一次，因此所有 layer 在进程入口（`main.rs`）一次性组装；
+//! - 日志目录要等 Tauri `setup` 解析出数据目录后才知道，文件 layer 先挂上
+//!   [`FileLogWriter`]，在 [`attach_file_log`] 之前写入的内容直接丢弃；
+//! - `CorrelationLayer` 同时负责记录命令耗时（`COMMAND_DURATION_MS`）。
+//!
+//! 约定：注释中文，日志英文（tracing）。
+
+use std::io::Write;
+use std::path::Path;
+use std::sync::OnceLock;
+
+use tracing::Subscriber;
+use tracing_appender::non_blocking::{NonBlocking, WorkerGuard};
+use tracing_subscriber::filter::EnvFilter;
+use tracing_subscriber::fmt::{self, MakeWriter};
+use tracing_subscriber::layer::SubscriberExt;
+
+use crate::shared::correlation::CorrelationLayer;
+
+static FILE_SINK: OnceLock<NonBlocking> = OnceLock::new();
+
+/// 文件日志 writer：[`attach_file_log`] 之前丢弃写入，之后转发到按天滚动的日志文件。
+#[derive(Debug, Clone, Copy, Default)]
+pub struct FileLogWriter;
+
+/// [`FileLogWriter`] 单次写入的句柄。
+pub enum FileLogHandle {
+    File(NonBlocking),
+    Discard,
+}
+
+impl Write for FileLogHandle {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        match self {
+            Self::File(w) => w.write(buf),
+            Self::Discard => Ok(buf.len()),
+        }
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        match self {
+            Self::File(w) => w.flush(),
+            Self::Discard => Ok(()),
+        }
+    }
+}
+
+impl<'a> MakeWriter<'a> for FileLogWriter {
+    type Writer = FileLogHandle;
+
+    fn make_writer(&'a self) -> Self::Writer {
+        FILE_SINK
+            .get()
+            .map_or(FileLogHandle::Discard, |w| FileLogHandle::File(w.clone()))
+    }
+}
+
+/// 开始把日志写入 `log_dir/app.log.<date>`。
+///
+/// # 返回值
+/// - `Some(WorkerGuard)`：需保存到进程结束，drop 时刷新缓冲；
+/// - `None`：已挂接过（重复调用不会切换目录）。
+pub fn attach_file_log(log_dir: &Path) -> Option<WorkerGuard> {
+    if FILE_SINK.get().is_some() {
+        return None;
+    }
+    if let Err(e) = std::fs::create_dir_all(log_dir) {
+        tracing::warn!(action = "app_file_logger_dir_failed", error = %e);
+    }
+    let appender = tracing_appender::rolling::daily(log_dir, "app.log");
+    let (writer, guard) = tracing_appender::non_blocking(appender);
+    FILE_SINK.set(writer).ok()?;
+    Some(guard)
+}
+
+/// 组装全局 subscriber：`env_filter` + correlation/指标层 + stderr + 文件日志。
+pub fn build_subscriber<W>(env_filter: EnvFilter, file_writer: W) -> impl Subscriber + Send + Sync
+where
+    W: for<'w> MakeWriter<'w> + Send + Sync + 'static,
+{
+    tracing_subscriber::registry()
+        .with(env_filter)
+        .with(CorrelationLayer)
+        .with(fmt::layer().pretty().with_writer(std::io::stderr))
+        .with(fmt::layer().with_writer(file_writer).with_ansi(false))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::{Arc, Mutex};
+
+    #[derive(Clone, Default)]
+    struct Capture(Arc<Mutex<Vec<u8>>>);
+
+    impl Write for Capture {
+        fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+            self.0.lock().expect("capture").extend_from_slice(buf);
+            Ok(buf.len())
+        }
+
+        fn flush(&mut self) -> std::io::Result<()> {
+            Ok(())
+        }
+    }
+
+    impl<'a> MakeWriter<'a> for Capture {
+        type Writer = Capture;
+
+        fn make_writer(&'a self) -> Self::Writer {
+            self.clone()
+        }
+    }
+
+    #[test]
+    fn span_fields_reach_the_file_layer() {
+        let capture = Capture::default();
+        let subscriber = build_subscriber(EnvFilter::new("info"), capture.clone());
+        tracing::subscriber::with_default(subscriber, || {
+            let span = tracing::info_span!(
+                "tauri_command",
+                command = %"db_init",
+                correlation_id = %"cmd-7-abcdef12",
+            );
+            let _entered = span.enter();
+            // correlation 层与文件层挂在同一个 subscriber 上。
+            assert_eq!(
+                crate::shared::correlation::current_correlation()
+                    .map(|c| c.correlation_id)
+                    .as_deref(),
+                Some("cmd-7-abcdef12")
+            );
+            tracing::info!(action = "test_file_layer_written");
+        });
+        let written = String::from_utf8(capture.0.lock().expect("capture").clone()).expect("utf8");
+        assert!(written.contains("test_file_layer_written"), "{written}");
+        assert!(
+            written.contains("correlation_id=cmd-7-abcdef12"),
+            "{written}"
+        );
+    }
+
+    #[test]
+    fn writes_before_attach_are_discarded() {
+        let mut handle = FileLogWriter.make_writer();
+        if FILE_SINK.get().is_none() {
+            assert!(matches!(handle, FileLogHandle::Discard));
+        }
+        assert_eq!(handle.write(b"early").expect("write"), 5);
+    }
+}
@@ -0,0 +1,11 @@
+//! metrics｜Tauri 命令
+
+use crate::shared::error::CommandResult;
+
+use super::{MetricsSnapshot, snapshot};
+
+/// 获取当前进程内指标快照（counters + histograms）。
+#[tauri::command]
+pub fn get_metrics() -> CommandResult<MetricsSnapshot> {
+    Ok(snapshot())
+}
@@ -0,0 +1,235 @@
+//! shared｜进程内指标注册表（counters + histograms）。
+//!
+//! 说明：
+//! - 仅用于本地性能观测，不上报任何远端；
+//! - `get_metrics` 命令返回 JSON 快照；debug 构建可通过环境变量
+//!   `CARRYPIGEON_METRICS_PORT` 开启 Prometheus 文本格式的本地 HTTP 监听。
+//!
+//! 约定：注释中文，日志英文（tracing）。
+
+pub mod commands;
+#[cfg(debug_assertions)]
+pub mod prometheus;
+
+use std::collections::BTreeMap;
+use std::sync::{Mutex, OnceLock};
+use std::time::Duration;
+
+use serde::Serialize;
+
+pub use commands::*;
+
+/// 已拆包投递的 TCP 帧数。
+pub const FRAMES_RECEIVED: &str = "frames_received_total";
+/// 出站时经 zstd 压缩的 TCP 帧数。
+pub const FRAMES_COMPRESSED: &str = "frames_compressed_total";
+/// 入站时经 zstd 解压的 TCP 帧数。
+pub const FRAMES_DECOMPRESSED: &str = "frames_decompressed_total";
+/// 出站帧压缩累计节省的字节数。
+pub const FRAME_COMPRESSION_SAVED_BYTES: &str = "frame_compression_saved_bytes_total";
+/// DB 查询/执行耗时（毫秒）。
+pub const DB_QUERY_LATENCY_MS: &str = "db_query_latency_ms";
+/// Tauri 命令耗时（毫秒，按命令名分组）。
+pub const COMMAND_DURATION_MS: &str = "command_duration_ms";
+
+/// 直方图桶上界（毫秒）；最后隐含 `+Inf`。
+const HISTOGRAM_BUCKETS_MS: &[f64] = &[
+    1.0, 5.0, 10.0, 25.0, 50.0, 100.0, 250.0, 500.0, 1000.0, 2500.0, 5000.0,
+];
+
+#[derive(Debug, Clone, Default)]
+struct Histogram {
+    bucket_counts: Vec<u64>,
+    sum: f64,
+    count: u64,
+}
+
+impl Histogram {
+    fn observe(&mut self, value: f64) {
+        if self.bucket_counts.is_empty() {
+            self.bucket_counts = vec![0; HISTOGRAM_BUCKETS_MS.len()];
+        }
+        for (slot, le) in self.bucket_counts.iter_mut().zip(HISTOGRAM_BUCKETS_MS) {
+            if value <= *le {
+                *slot += 1;
+            }
+        }
+        self.sum += value;
+        self.count += 1;
+    }
+}
+
+#[derive(Default)]
+struct Registry {
+    counters: BTreeMap<&'static str, u64>,
+    histograms: BTreeMap<(&'static str, String), Histogram>,
+}
+
+fn registry() -> &'static Mutex<Registry> {
+    static REGISTRY: OnceLock<Mutex<Registry>> = OnceLock::new();
+    REGISTRY.get_or_init(|| Mutex::new(Registry::default()))
+}
+
+/// 计数器累加。
+pub fn incr_counter(name: &'static str, by: u64) {
+    if let Ok(mut guard) = registry().lock() {
+        *guard.counters.entry(name).or_default() += by;
+    }
+}
+
+/// 记录一次耗时观测。
+///
+/// # 参数
+/// - `name`：直方图名称。
+/// - `label`：分组标签（例如命令名；无分组时传空串）。
+/// - `elapsed`：耗时。
+pub fn observe_duration(name: &'static str, label: &str, elapsed: Duration) {
+    if let Ok(mut guard) = registry().lock() {
+        guard
+            .histograms
+            .entry((name, label.to_string()))
+            .or_default()
+            .observe(elapsed.as_secs_f64() * 1000.0);
+    }
+}
+
+/// 直方图快照（`buckets` 为累计计数，与 Prometheus `le` 语义一致）。
+#[derive(Debug, Clone, Serialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct HistogramSnapshot {
+    pub name: String,
+    pub label: String,
+    pub buckets: Vec<(f64, u64)>,
+    pub sum: f64,
+    pub count: u64,
+}
+
+/// 指标快照。
+#[derive(Debug, Clone, Serialize, Default, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct MetricsSnapshot {
+    pub counters: BTreeMap<String, u64>,
+    pub histograms: Vec<HistogramSnapshot>,
+}
+
+/// 读取当前指标快照。
+pub fn snapshot() -> MetricsSnapshot {
+    let Ok(guard) = registry().lock() else {
+        return MetricsSnapshot::default();
+    };
+    MetricsSnapshot {
+        counters: guard
+            .counters
+            .iter()
+            .map(|(k, v)| (k.to_string(), *v))
+            .collect(),
+        histograms: guard
+            .histograms
+            .iter()
+            .map(|((name, label), h)| HistogramSnapshot {
+                name: name.to_string(),
+                label: label.clone(),
+                buckets: HISTOGRAM_BUCKETS_MS
+                    .iter()
+                    .copied()
+                    .zip(h.bucket_counts.iter().copied())
+                    .collect(),
+                sum: h.sum,
+                count: h.count,
+            })
+            .collect(),
+    }
+}
+
+/// 将快照渲染为 Prometheus 文本格式。
+pub fn render_prometheus(snapshot: &MetricsSnapshot) -> String {
+    let mut out = String::new();
+    for (name, value) in &snapshot.counters {
+        out.push_str(&format!(
+            "# TYPE carrypigeon_{name} counter\ncarrypigeon_{name} {value}\n"
+        ));
+    }
+    let mut typed = std::collections::BTreeSet::new();
+    for h in &snapshot.histograms {
+        let name = format!("carrypigeon_{}", h.name);
+        if typed.insert(name.clone()) {
+            out.push_str(&format!("# TYPE {name} histogram\n"));
+        }
+        let label = if h.label.is_empty() {
+            String::new()
+        } else {
+            format!(
+                "label=\"{}\",",
+                h.label.replace('\\', "\\\\").replace('"', "\\\"")
+            )
+        };
+        for (le, count) in &h.buckets {
+            out.push_str(&format!("{name}_bucket{{{label}le=\"{le}\"}} {count}\n"));
+        }
+        out.push_str(&format!(
+            "{name}_bucket{{{label}le=\"+Inf\"}} {}\n",
+            h.count
+        ));
+        let label = label.trim_end_matches(',');
+        out.push_str(&format!("{name}_sum{{{label}}} {}\n", h.sum));
+        out.push_str(&format!("{name}_count{{{label}}} {}\n", h.count));
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn histogram_buckets_are_cumulative() {
+        let mut h = Histogram::default();
+        h.observe(3.0);
+        h.observe(30.0);
+        h.observe(9000.0);
+        assert_eq!(h.count, 3);
+        assert_eq!(h.bucket_counts[0], 0);
+        assert_eq!(h.bucket_counts[1], 1);
+        assert_eq!(h.bucket_counts[4], 2);
+        assert_eq!(*h.bucket_counts.last().expect("bucket"), 2);
+    }
+
+    #[test]
+    fn render_prometheus_emits_counters_and_histograms() {
+        let snapshot = MetricsSnapshot {
+            counters: BTreeMap::from([(FRAMES_RECEIVED.to_string(), 7)]),
+            histograms: vec![HistogramSnapshot {
+                name: COMMAND_DURATION_MS.to_string(),
+                label: "db_query".to_string(),
+                buckets: vec![(1.0, 0), (5.0, 2)],
+                sum: 6.5,
+                count: 2,
+            }],
+        };
+        let text = render_prometheus(&snapshot);
+        assert!(text.contains("carrypigeon_frames_received_total 7"));
+        assert!(
+            text.contains("carrypigeon_command_duration_ms_bucket{label=\"db_query\",le=\"5\"} 2")
+        );
+        assert!(
+            text.contains(
+                "carrypigeon_command_duration_ms_bucket{label=\"db_query\",le=\"+Inf\"} 2"
+            )
+        );
+        assert!(text.contains("carrypigeon_command_duration_ms_count{label=\"db_query\"} 2"));
+    }
+
+    #[test]
+    fn registry_records_counters_and_durations() {
+        incr_counter("test_counter_total", 2);
+        observe_duration("test_latency_ms", "", Duration::from_millis(12));
+        let snapshot = snapshot();
+        assert!(snapshot.counters["test_counter_total"] >= 2);
+        assert!(
+            snapshot
+                .histograms
+                .iter()
+                .any(|h| h.name == "test_latency_ms" && h.count >= 1)
+        );
+    }
+}
@@ -0,0 +1,53 @@
+//! metrics｜Prometheus 文本格式本地监听（仅 debug 构建）。
+//!
+//! 约定：注释中文，日志英文（tracing）。
+
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::TcpListener;
+
+use super::{render_prometheus, snapshot};
+
+/// 开启监听的环境变量（端口号）。
+const METRICS_PORT_ENV: &str = "CARRYPIGEON_METRICS_PORT";
+
+/// 若设置了 `CARRYPIGEON_METRICS_PORT`，在 `127.0.0.1:<port>` 上暴露 `/metrics`。
+///
+/// # 说明
+/// 仅绑定回环地址；任何路径都返回同一份指标文本。
+pub fn spawn_from_env() {
+    let Some(port) = std::env::var(METRICS_PORT_ENV)
+        .ok()
+        .and_then(|raw| raw.trim().parse::<u16>().ok())
+    else {
+        return;
+    };
+
+    tauri::async_runtime::spawn(async move {
+        let listener = match TcpListener::bind(("127.0.0.1", port)).await {
+            Ok(listener) => listener,
+            Err(e) => {
+                tracing::warn!(action = "app_metrics_listener_bind_failed", port, error = %e);
+                return;
+            }
+        };
+        tracing::info!(action = "app_metrics_listener_started", port);
+        loop {
+            let Ok((mut stream, _)) = listener.accept().await else {
+                continue;
+            };
+            tokio::spawn(async move {
+                // 读取并丢弃请求头即可，指标输出与路径无关。
+                let mut buf = [0u8; 1024];
+                let _ = stream.read(&mut buf).await;
+                let body = render_prometheus(&snapshot());
+                let response = format!(
+                    "HTTP/1.1 200 OK\r\nContent-Type: text/plain; version=0.0.4\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                    body.len(),
+                    body
+                );
+                let _ = stream.write_all(response.as_bytes()).await;
+                let _ = stream.shutdown().await;
+            });
+        }
+    });
+}
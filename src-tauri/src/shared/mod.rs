@@ -4,11 +4,186 @@
 //!
 //! 约定：注释中文，日志英文（tracing）。
 pub mod app_data_dir;
+pub mod app_lock;
+pub mod audit_log;
+pub mod badges;
 pub mod chat_cache;
 pub mod close_to_tray_state;
+pub mod correlation;
 pub mod db;
+pub mod dnd;
 pub mod error;
+pub mod events;
+pub mod feature_flags;
+pub mod idle;
+pub mod jump_list;
 pub mod log;
+pub mod metrics;
+pub mod mute_rules;
 pub mod net;
+pub mod notifications;
+pub mod paths;
+pub mod power;
+pub mod privacy;
+pub mod secrets;
+pub mod secure_wipe;
+pub mod taskbar_progress;
 pub mod temp_file;
+#[cfg(desktop)]
 pub mod window_bounds;
+
+use crate::app::registry::{CommandRegistration, CommandRegistry, command_set};
+
+/// 共享基础设施命令注册（临时文件/数据目录/DB/跳转列表/聊天缓存/免打扰/空闲检测/应用锁/安全擦除/通知历史/特性开关/日志/指标/任务栏进度）。
+pub struct SharedCommands;
+
+impl CommandRegistration for SharedCommands {
+    fn register(registry: &mut CommandRegistry) {
+        registry.add(command_set!(crate::shared::temp_file::commands => [
+            cleanup_temp_files,
+            remove_temp_file,
+            save_temp_file,
+            open_temp_file,
+            read_attachment_range,
+        ]));
+        registry.add(command_set!(crate::shared::paths::commands => [
+            get_data_directory,
+            set_data_directory,
+        ]));
+        registry.add(command_set!(crate::shared::db::commands => [
+            db_init,
+            db_execute,
+            db_query,
+            db_transaction,
+            db_path,
+            db_close,
+            db_remove,
+        ]));
+        registry.add(command_set!(crate::shared::db::explain => [
+            db_explain,
+        ]));
+        registry.add(command_set!(crate::shared::db::sql_policy => [
+            db_run_named,
+        ]));
+        registry.add(command_set!(crate::shared::db::quick_switch => [
+            quick_switch,
+        ]));
+        registry.add(command_set!(crate::shared::db::user_profile => [
+            record_users,
+        ]));
+        registry.add(command_set!(crate::shared::db::blocked_users => [
+            block_user,
+            unblock_user,
+            list_blocked_users,
+        ]));
+        registry.add(command_set!(crate::shared::db::delta_sync => [
+            sync_channel_delta,
+            get_sync_state,
+        ]));
+        registry.add(command_set!(crate::shared::db::outbox => [
+            send_message_optimistic,
+            retry_optimistic_message,
+            discard_optimistic_message,
+        ]));
+        registry.add(command_set!(crate::shared::db::retention => [
+            set_channel_retention,
+            get_channel_retention,
+            preview_prune,
+        ]));
+        registry.add(command_set!(crate::shared::db::archive => [
+            archive_channel,
+            restore_archive,
+        ]));
+        registry.add(command_set!(crate::shared::db::channel_keys => [
+            store_channel_key,
+            list_channel_key_ids,
+            decrypt_channel_message,
+        ]));
+        registry.add(command_set!(crate::shared::db::device_verification => [
+            get_device_identity,
+            export_device_verification,
+            verify_device,
+            list_verified_devices,
+            revoke_device_verification,
+        ]));
+        registry.add(command_set!(crate::shared::db::favorites => [
+            add_favorite,
+            remove_favorite,
+            list_favorites_with_state,
+            set_channel_read_state,
+        ]));
+        registry.add(command_set!(crate::shared::jump_list::commands => [
+            report_recent_channel,
+            refresh_jump_list,
+        ]));
+        registry.add(command_set!(crate::shared::db::backup => [
+            backup_now,
+            list_backups,
+            restore_backup,
+        ]));
+        registry.add(command_set!(crate::shared::db::backup_remote => [
+            get_backup_remote,
+            set_backup_remote,
+            upload_backup,
+            list_remote_backups,
+            restore_remote_backup,
+        ]));
+        registry.add(command_set!(crate::shared::chat_cache::commands => [
+            chat_cache_get,
+            chat_cache_load_all,
+            chat_cache_clear_all,
+            chat_cache_put,
+            chat_cache_remove,
+            chat_cache_remove_many,
+        ]));
+        registry.add(command_set!(crate::shared::dnd::commands => [
+            set_dnd,
+            get_dnd_status,
+            get_dnd_schedule,
+            set_dnd_schedule,
+        ]));
+        registry.add(command_set!(crate::shared::idle => [
+            report_user_activity,
+        ]));
+        registry.add(command_set!(crate::shared::app_lock::commands => [
+            get_app_lock_status,
+            set_app_lock_passphrase,
+            lock_app,
+            unlock_app,
+        ]));
+        registry.add(command_set!(crate::shared::secure_wipe => [
+            request_secure_wipe_token,
+            secure_wipe,
+        ]));
+        registry.add(command_set!(crate::shared::notifications::commands => [
+            record_notification,
+            get_notification_history,
+            mark_notification_read,
+        ]));
+        registry.add(command_set!(crate::shared::audit_log::commands => [
+            get_audit_log,
+        ]));
+        registry.add(command_set!(crate::shared::mute_rules::commands => [
+            add_mute_rule,
+            remove_mute_rule,
+            list_mute_rules,
+            test_mute_rule,
+        ]));
+        registry.add(command_set!(crate::shared::feature_flags::commands => [
+            get_feature_flags,
+            refresh_feature_flags,
+        ]));
+        registry.add(command_set!(crate::shared::log => [
+            log_info,
+            log_error,
+            log_warning,
+            log_debug,
+        ]));
+        registry.add(command_set!(crate::shared::metrics => [
+            get_metrics,
+        ]));
+        registry.add(command_set!(crate::shared::taskbar_progress => [
+            set_taskbar_progress,
+        ]));
+    }
+}
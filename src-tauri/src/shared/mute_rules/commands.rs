@@ -0,0 +1,88 @@
+//! mute_rules｜Tauri 命令
+
+use serde::Serialize;
+
+use crate::shared::error::{CommandResult, to_command_error};
+
+use super::{MuteRule, NewMuteRule};
+
+/// `test_mute_rule` 的结果。
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MuteTestResult {
+    /// 样例文本是否会被静音。
+    pub muted: bool,
+    /// 命中的已保存规则 id（测试草稿规则时为空）。
+    pub rule_ids: Vec<i64>,
+}
+
+/// 新增一条静音规则，并在后台重新评估已打开 server DB 中的消息。
+///
+/// # 返回值
+/// 保存后的规则；模式为空、过长或正则非法时返回 `MUTE_RULE_INVALID`。
+#[tauri::command]
+pub async fn add_mute_rule(rule: NewMuteRule) -> CommandResult<MuteRule> {
+    super::CompiledRule::compile(0, &rule)
+        .map_err(|e| to_command_error("MUTE_RULE_INVALID", "error.mute_rule_invalid", e))?;
+    let saved = super::add(&rule)
+        .await
+        .map_err(|e| to_command_error("MUTE_RULE_SAVE_FAILED", "error.mute_rule_save_failed", e))?;
+    tracing::info!(action = "app_mute_rule_added", rule_id = saved.id);
+    super::schedule_full_reevaluation();
+    Ok(saved)
+}
+
+/// 删除一条静音规则。
+///
+/// # 返回值
+/// 规则是否存在。
+#[tauri::command]
+pub async fn remove_mute_rule(id: i64) -> CommandResult<bool> {
+    let removed = super::remove(id).await.map_err(|e| {
+        to_command_error(
+            "MUTE_RULE_REMOVE_FAILED",
+            "error.mute_rule_remove_failed",
+            e,
+        )
+    })?;
+    if removed {
+        tracing::info!(action = "app_mute_rule_removed", rule_id = id);
+        super::schedule_full_reevaluation();
+    }
+    Ok(removed)
+}
+
+/// 列出全部静音规则（按创建顺序）。
+#[tauri::command]
+pub async fn list_mute_rules() -> CommandResult<Vec<MuteRule>> {
+    super::list()
+        .await
+        .map_err(|e| to_command_error("MUTE_RULES_LOAD_FAILED", "error.mute_rules_load_failed", e))
+}
+
+/// 用静音规则评估一段样例文本。
+///
+/// # 参数
+/// - `sample`：待评估文本（通知/未读路径传入消息内容）。
+/// - `rule`：可选的草稿规则；提供时只评估该规则，不读取已保存规则。
+#[tauri::command]
+pub async fn test_mute_rule(
+    sample: String,
+    rule: Option<NewMuteRule>,
+) -> CommandResult<MuteTestResult> {
+    if let Some(rule) = rule {
+        let muted = super::evaluate_draft(&rule, &sample)
+            .map_err(|e| to_command_error("MUTE_RULE_INVALID", "error.mute_rule_invalid", e))?;
+        return Ok(MuteTestResult {
+            muted,
+            rule_ids: Vec::new(),
+        });
+    }
+    let rule_ids = super::evaluate(&sample).await.map_err(|e| {
+        to_command_error("MUTE_RULES_LOAD_FAILED", "error.mute_rules_load_failed", e)
+    })?;
+    Ok(MuteTestResult {
+        muted: !rule_ids.is_empty(),
+        rule_ids,
+    })
+}
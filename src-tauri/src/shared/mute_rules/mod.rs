@@ -0,0 +1,389 @@
+//! 模块入口：mute_rules（关键词 / 正则静音规则）。
+//!
+//! 说明：
+//! - 规则存放在 system DB `mute_rules` 表，编译结果在内存中缓存，规则变化后失效；
+//! - 消息仍正常写入 server DB，只是 `messages.muted` 被标记为 1：server DB 中写入
+//!   `messages` 的语句执行后（以及 DB 打开时）在后台评估 `muted IS NULL` 的消息；
+//!   内容被修改时由触发器把 `muted` 重置为 NULL 以便重新评估；
+//! - 规则增删后所有已打开的 server DB 会整体重新评估；
+//! - 通知与未读角标通过 `test_mute_rule` 复用同一套匹配逻辑。
+//!
+//! 约定：注释中文，日志英文（tracing）。
+
+pub mod commands;
+
+use std::sync::{Arc, Mutex, OnceLock};
+
+use anyhow::{Context, Result};
+use regex::{Regex, RegexBuilder};
+use sea_orm::{ConnectionTrait, TransactionTrait};
+use serde::{Deserialize, Serialize};
+
+use crate::shared::db::commands::RawStatement;
+
+/// 规则所在的 DB key（应用级 system DB）。
+const SYSTEM_DB_KEY: &str = "system";
+
+/// 单条规则的最大长度。
+pub const MAX_PATTERN_LEN: usize = 256;
+
+/// 正则编译大小上限，避免病态规则拖慢评估。
+const REGEX_SIZE_LIMIT: usize = 1 << 20;
+
+/// 每批评估的消息条数。
+const EVALUATE_BATCH: i64 = 500;
+
+/// 待新增的静音规则。
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NewMuteRule {
+    pub pattern: String,
+    /// 是否按正则匹配（否则按子串匹配）。
+    #[serde(default)]
+    pub is_regex: bool,
+    /// 是否区分大小写（默认不区分）。
+    #[serde(default)]
+    pub case_sensitive: bool,
+}
+
+/// 已保存的静音规则。
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MuteRule {
+    pub id: i64,
+    pub pattern: String,
+    pub is_regex: bool,
+    pub case_sensitive: bool,
+    pub created_at: i64,
+}
+
+/// 编译后的规则。
+#[derive(Debug)]
+pub(crate) struct CompiledRule {
+    id: i64,
+    matcher: Matcher,
+}
+
+#[derive(Debug)]
+enum Matcher {
+    /// 子串匹配；不区分大小写时 `needle` 已转小写。
+    Text {
+        needle: String,
+        case_sensitive: bool,
+    },
+    Regex(Regex),
+}
+
+impl CompiledRule {
+    pub(crate) fn compile(id: i64, rule: &NewMuteRule) -> Result<Self> {
+        let pattern = rule.pattern.trim();
+        if pattern.is_empty() {
+            anyhow::bail!("Mute rule pattern is empty");
+        }
+        if pattern.chars().count() > MAX_PATTERN_LEN {
+            anyhow::bail!("Mute rule pattern is too long");
+        }
+        let matcher = if rule.is_regex {
+            Matcher::Regex(
+                RegexBuilder::new(pattern)
+                    .case_insensitive(!rule.case_sensitive)
+                    .size_limit(REGEX_SIZE_LIMIT)
+                    .build()
+                    .context("Invalid mute rule regex")?,
+            )
+        } else {
+            Matcher::Text {
+                needle: if rule.case_sensitive {
+                    pattern.to_string()
+                } else {
+                    pattern.to_lowercase()
+                },
+                case_sensitive: rule.case_sensitive,
+            }
+        };
+        Ok(Self { id, matcher })
+    }
+
+    /// `lowered` 为 `text` 的小写形式（多条规则共用，避免重复转换）。
+    fn is_match(&self, text: &str, lowered: &str) -> bool {
+        match &self.matcher {
+            Matcher::Text {
+                needle,
+                case_sensitive: true,
+            } => text.contains(needle.as_str()),
+            Matcher::Text { needle, .. } => lowered.contains(needle.as_str()),
+            Matcher::Regex(re) => re.is_match(text),
+        }
+    }
+}
+
+/// 返回命中的规则 id（按规则顺序）。
+fn matching_ids(rules: &[CompiledRule], text: &str) -> Vec<i64> {
+    let lowered = text.to_lowercase();
+    rules
+        .iter()
+        .filter(|rule| rule.is_match(text, &lowered))
+        .map(|rule| rule.id)
+        .collect()
+}
+
+fn compiled_cache() -> &'static Mutex<Option<Arc<Vec<CompiledRule>>>> {
+    static CACHE: OnceLock<Mutex<Option<Arc<Vec<CompiledRule>>>>> = OnceLock::new();
+    CACHE.get_or_init(|| Mutex::new(None))
+}
+
+fn invalidate() {
+    *compiled_cache().lock().unwrap_or_else(|p| p.into_inner()) = None;
+}
+
+fn now_ms() -> i64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_millis() as i64
+}
+
+/// 读取全部规则（按创建顺序）。
+pub async fn list() -> Result<Vec<MuteRule>> {
+    let db = crate::shared::db::get_db(SYSTEM_DB_KEY).await?;
+    let rows = db
+        .connection
+        .query_all(&RawStatement::new(
+            "SELECT id, pattern, is_regex, case_sensitive, created_at FROM mute_rules ORDER BY id ASC",
+            Vec::new(),
+        ))
+        .await
+        .context("MUTE_RULES_LOAD_FAILED")?;
+    Ok(rows
+        .iter()
+        .filter_map(|row| {
+            Some(MuteRule {
+                id: row.try_get("", "id").ok()?,
+                pattern: row.try_get("", "pattern").ok()?,
+                is_regex: row.try_get::<i64>("", "is_regex").ok()? != 0,
+                case_sensitive: row.try_get::<i64>("", "case_sensitive").ok()? != 0,
+                created_at: row.try_get("", "created_at").ok()?,
+            })
+        })
+        .collect())
+}
+
+async fn compiled_rules() -> Result<Arc<Vec<CompiledRule>>> {
+    if let Some(rules) = compiled_cache()
+        .lock()
+        .unwrap_or_else(|p| p.into_inner())
+        .as_ref()
+    {
+        return Ok(rules.clone());
+    }
+    let rules: Vec<CompiledRule> = list()
+        .await?
+        .into_iter()
+        .filter_map(|rule| {
+            let id = rule.id;
+            CompiledRule::compile(
+                id,
+                &NewMuteRule {
+                    pattern: rule.pattern,
+                    is_regex: rule.is_regex,
+                    case_sensitive: rule.case_sensitive,
+                },
+            )
+            .map_err(|e| {
+                tracing::warn!(action = "app_mute_rule_compile_failed", rule_id = id, error = %e);
+            })
+            .ok()
+        })
+        .collect();
+    let rules = Arc::new(rules);
+    *compiled_cache().lock().unwrap_or_else(|p| p.into_inner()) = Some(rules.clone());
+    Ok(rules)
+}
+
+/// 校验并保存一条规则；返回保存后的记录。
+pub async fn add(rule: &NewMuteRule) -> Result<MuteRule> {
+    CompiledRule::compile(0, rule)?;
+    let created_at = now_ms();
+    let pattern = rule.pattern.trim().to_string();
+    let db = crate::shared::db::get_db(SYSTEM_DB_KEY).await?;
+    let result = db
+        .connection
+        .execute(&RawStatement::new(
+            "INSERT INTO mute_rules (pattern, is_regex, case_sensitive, created_at) VALUES (?, ?, ?, ?)",
+            vec![
+                pattern.clone().into(),
+                i64::from(rule.is_regex).into(),
+                i64::from(rule.case_sensitive).into(),
+                created_at.into(),
+            ],
+        ))
+        .await
+        .context("MUTE_RULE_SAVE_FAILED")?;
+    invalidate();
+    Ok(MuteRule {
+        id: result.last_insert_id() as i64,
+        pattern,
+        is_regex: rule.is_regex,
+        case_sensitive: rule.case_sensitive,
+        created_at,
+    })
+}
+
+/// 删除规则；返回是否存在。
+pub async fn remove(id: i64) -> Result<bool> {
+    let db = crate::shared::db::get_db(SYSTEM_DB_KEY).await?;
+    let result = db
+        .connection
+        .execute(&RawStatement::new(
+            "DELETE FROM mute_rules WHERE id = ?",
+            vec![id.into()],
+        ))
+        .await
+        .context("MUTE_RULE_REMOVE_FAILED")?;
+    invalidate();
+    Ok(result.rows_affected() > 0)
+}
+
+/// 用当前规则评估一段文本，返回命中的规则 id。
+pub async fn evaluate(text: &str) -> Result<Vec<i64>> {
+    Ok(matching_ids(&compiled_rules().await?, text))
+}
+
+/// 用一条未保存的规则评估文本（供设置界面预览）。
+pub fn evaluate_draft(rule: &NewMuteRule, text: &str) -> Result<bool> {
+    let compiled = CompiledRule::compile(0, rule)?;
+    Ok(!matching_ids(std::slice::from_ref(&compiled), text).is_empty())
+}
+
+fn evaluation_lock() -> &'static tokio::sync::Mutex<()> {
+    static LOCK: OnceLock<tokio::sync::Mutex<()>> = OnceLock::new();
+    LOCK.get_or_init(|| tokio::sync::Mutex::new(()))
+}
+
+/// 评估指定 server DB 中尚未评估（`muted IS NULL`）的消息。
+async fn evaluate_pending(db_key: &str) -> Result<u64> {
+    let _guard = evaluation_lock().lock().await;
+    let rules = compiled_rules().await?;
+    let db = crate::shared::db::get_db(db_key).await?;
+    let conn = &db.connection;
+    if rules.is_empty() {
+        conn.execute(&RawStatement::new(
+            "UPDATE messages SET muted = 0 WHERE muted IS NULL",
+            Vec::new(),
+        ))
+        .await?;
+        return Ok(0);
+    }
+
+    let mut muted = 0;
+    loop {
+        let rows = conn
+            .query_all(&RawStatement::new(
+                "SELECT id, content FROM messages WHERE muted IS NULL LIMIT ?",
+                vec![EVALUATE_BATCH.into()],
+            ))
+            .await?;
+        if rows.is_empty() {
+            break;
+        }
+        let txn = conn.begin().await?;
+        for row in &rows {
+            let Ok(id) = row.try_get::<String>("", "id") else {
+                continue;
+            };
+            let content: String = row.try_get("", "content").unwrap_or_default();
+            let hit = !matching_ids(&rules, &content).is_empty();
+            muted += u64::from(hit);
+            txn.execute(&RawStatement::new(
+                "UPDATE messages SET muted = ? WHERE id = ?",
+                vec![i64::from(hit).into(), id.into()],
+            ))
+            .await?;
+        }
+        txn.commit().await?;
+        if (rows.len() as i64) < EVALUATE_BATCH {
+            break;
+        }
+    }
+    Ok(muted)
+}
+
+/// 在后台评估指定 server DB 的新消息（不阻塞调用方）。
+pub fn schedule_evaluation(db_key: &str) {
+    let db_key = db_key.to_string();
+    tauri::async_runtime::spawn(async move {
+        match evaluate_pending(&db_key).await {
+            Ok(muted) if muted > 0 => {
+                tracing::debug!(action = "app_mute_rules_messages_flagged", muted);
+            }
+            Ok(_) => {}
+            Err(e) => {
+                tracing::warn!(action = "app_mute_rules_evaluate_failed", error = %e);
+            }
+        }
+    });
+}
+
+/// 规则变化后重置并重新评估所有已打开的 server DB。
+pub fn schedule_full_reevaluation() {
+    tauri::async_runtime::spawn(async {
+        let keys: Vec<String> = {
+            let registry = crate::shared::db::init_db_registry();
+            let lock = registry.read().await;
+            lock.map
+                .keys()
+                .filter(|key| key.starts_with("server_"))
+                .cloned()
+                .collect()
+        };
+        for key in keys {
+            let reset = async {
+                let db = crate::shared::db::get_db(&key).await?;
+                db.connection
+                    .execute(&RawStatement::new(
+                        "UPDATE messages SET muted = NULL",
+                        Vec::new(),
+                    ))
+                    .await?;
+                anyhow::Ok(())
+            };
+            if let Err(e) = reset.await {
+                tracing::warn!(action = "app_mute_rules_reset_failed", error = %e);
+                continue;
+            }
+            schedule_evaluation(&key);
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rule(pattern: &str, is_regex: bool, case_sensitive: bool) -> NewMuteRule {
+        NewMuteRule {
+            pattern: pattern.to_string(),
+            is_regex,
+            case_sensitive,
+        }
+    }
+
+    #[test]
+    fn text_rules_match_substrings_case_insensitively_by_default() {
+        let rules = vec![
+            CompiledRule::compile(1, &rule("Spoiler", false, false)).unwrap(),
+            CompiledRule::compile(2, &rule("CI", false, true)).unwrap(),
+        ];
+        assert_eq!(matching_ids(&rules, "no SPOILERS please"), vec![1]);
+        assert_eq!(matching_ids(&rules, "ci failed"), Vec::<i64>::new());
+        assert_eq!(matching_ids(&rules, "CI failed"), vec![2]);
+    }
+
+    #[test]
+    fn regex_rules_are_validated_and_matched() {
+        assert!(CompiledRule::compile(1, &rule("(unclosed", true, false)).is_err());
+        assert!(CompiledRule::compile(1, &rule("   ", false, false)).is_err());
+        assert!(evaluate_draft(&rule(r"^\[bot\]", true, false), "[BOT] deploy done").unwrap());
+        assert!(!evaluate_draft(&rule(r"^\[bot\]", true, true), "[BOT] deploy done").unwrap());
+    }
+}
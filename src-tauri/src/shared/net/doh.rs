@@ -0,0 +1,297 @@
+//! shared｜DNS-over-HTTPS 解析（设置 `dns_over_https` / `dns_over_https_provider`）。
+//!
+//! 说明：
+//! - 面向 DNS 被污染/劫持的网络环境：开启后 TCP 连接（[`super::happy_eyeballs`]）与
+//!   访问服务端的 reqwest client（`header_policy::client_builder`）都先经 DoH 解析主机名；
+//! - 使用 JSON 格式的 DoH 接口（`application/dns-json`，Cloudflare / Google 等均支持），
+//!   并发查询 A 与 AAAA；
+//! - 结果按应答中最小 TTL 缓存（限制在 [`MIN_TTL`]..=[`MAX_TTL`]），切换开关或提供方时清空缓存；
+//! - DoH 查询失败或无结果时回退到系统 DNS，不阻断连接；
+//! - DoH 请求本身不经过此解析器（提供方主机名走系统 DNS，也可直接填写 IP 地址的 URL）。
+//!
+//! 约定：注释中文，日志英文（tracing）。
+
+use std::collections::HashMap;
+use std::net::{IpAddr, SocketAddr};
+use std::sync::{Mutex, OnceLock, RwLock};
+use std::time::{Duration, Instant};
+
+/// 默认 DoH 提供方。
+pub const DEFAULT_PROVIDER: &str = "https://cloudflare-dns.com/dns-query";
+/// 缓存 TTL 下限。
+pub const MIN_TTL: Duration = Duration::from_secs(30);
+/// 缓存 TTL 上限。
+pub const MAX_TTL: Duration = Duration::from_secs(3600);
+/// 单次 DoH 查询超时。
+const QUERY_TIMEOUT: Duration = Duration::from_secs(5);
+
+const RECORD_A: u16 = 1;
+const RECORD_AAAA: u16 = 28;
+
+/// 当前生效的提供方；`None` 表示未开启。
+static PROVIDER: RwLock<Option<String>> = RwLock::new(None);
+static CACHE: OnceLock<Mutex<DnsCache>> = OnceLock::new();
+static CLIENT: OnceLock<reqwest::Client> = OnceLock::new();
+
+fn cache() -> &'static Mutex<DnsCache> {
+    CACHE.get_or_init(|| Mutex::new(DnsCache::default()))
+}
+
+/// 解析结果缓存（键为小写主机名）。
+#[derive(Default)]
+struct DnsCache {
+    entries: HashMap<String, (Vec<IpAddr>, Instant)>,
+}
+
+impl DnsCache {
+    fn get(&self, host: &str, now: Instant) -> Option<Vec<IpAddr>> {
+        self.entries
+            .get(host)
+            .filter(|(_, expires_at)| *expires_at > now)
+            .map(|(ips, _)| ips.clone())
+    }
+
+    fn insert(&mut self, host: &str, ips: Vec<IpAddr>, ttl: Duration, now: Instant) {
+        self.entries.retain(|_, (_, expires_at)| *expires_at > now);
+        self.entries
+            .insert(host.to_string(), (ips, now + ttl.clamp(MIN_TTL, MAX_TTL)));
+    }
+}
+
+/// 更新 DoH 开关与提供方（设置写入时调用）；变化时清空缓存。
+pub fn configure(enabled: bool, provider: Option<&str>) {
+    let next = enabled.then(|| {
+        provider
+            .map(str::trim)
+            .filter(|p| !p.is_empty())
+            .unwrap_or(DEFAULT_PROVIDER)
+            .to_string()
+    });
+    let mut current = PROVIDER.write().unwrap_or_else(|p| p.into_inner());
+    if *current == next {
+        return;
+    }
+    tracing::info!(
+        action = "settings_dns_over_https_changed",
+        enabled,
+        provider = next.as_deref().unwrap_or("")
+    );
+    *current = next;
+    cache()
+        .lock()
+        .unwrap_or_else(|p| p.into_inner())
+        .entries
+        .clear();
+}
+
+/// 当前生效的 DoH 提供方（未开启时为 `None`）。
+pub fn provider() -> Option<String> {
+    PROVIDER.read().unwrap_or_else(|p| p.into_inner()).clone()
+}
+
+/// 提供方 URL 是否可用（必须为 https）。
+pub fn is_valid_provider(raw: &str) -> bool {
+    reqwest::Url::parse(raw.trim()).is_ok_and(|u| u.scheme() == "https" && u.host_str().is_some())
+}
+
+fn client() -> &'static reqwest::Client {
+    CLIENT.get_or_init(|| {
+        reqwest::Client::builder()
+            .timeout(QUERY_TIMEOUT)
+            .build()
+            .unwrap_or_default()
+    })
+}
+
+/// 从 DoH JSON 应答中取出 A/AAAA 地址与最小 TTL。
+fn parse_answers(body: &serde_json::Value) -> anyhow::Result<(Vec<IpAddr>, Duration)> {
+    let status = body.get("Status").and_then(|v| v.as_u64()).unwrap_or(0);
+    if status != 0 {
+        return Err(anyhow::anyhow!("DoH query failed with rcode {}", status));
+    }
+    let mut ips = Vec::new();
+    let mut ttl = MAX_TTL;
+    for answer in body
+        .get("Answer")
+        .and_then(|v| v.as_array())
+        .into_iter()
+        .flatten()
+    {
+        let kind = answer.get("type").and_then(|v| v.as_u64()).unwrap_or(0);
+        if kind != RECORD_A as u64 && kind != RECORD_AAAA as u64 {
+            continue;
+        }
+        let Some(ip) = answer
+            .get("data")
+            .and_then(|v| v.as_str())
+            .and_then(|s| s.parse::<IpAddr>().ok())
+        else {
+            continue;
+        };
+        let answer_ttl = answer.get("TTL").and_then(|v| v.as_u64()).unwrap_or(0);
+        ttl = ttl.min(Duration::from_secs(answer_ttl));
+        ips.push(ip);
+    }
+    Ok((ips, ttl))
+}
+
+async fn query(provider: &str, host: &str, record: u16) -> anyhow::Result<(Vec<IpAddr>, Duration)> {
+    let record = record.to_string();
+    let body: serde_json::Value = client()
+        .get(provider)
+        .query(&[("name", host), ("type", record.as_str())])
+        .header(reqwest::header::ACCEPT, "application/dns-json")
+        .send()
+        .await?
+        .error_for_status()?
+        .json()
+        .await?;
+    parse_answers(&body)
+}
+
+/// 经 DoH 解析主机名（带缓存）。
+async fn resolve_with_provider(provider: &str, host: &str) -> anyhow::Result<Vec<IpAddr>> {
+    let key = host.trim_end_matches('.').to_ascii_lowercase();
+    if let Some(ips) = cache()
+        .lock()
+        .unwrap_or_else(|p| p.into_inner())
+        .get(&key, Instant::now())
+    {
+        return Ok(ips);
+    }
+
+    let (v6, v4) = tokio::join!(
+        query(provider, &key, RECORD_AAAA),
+        query(provider, &key, RECORD_A)
+    );
+    if let (Err(err), Err(_)) = (&v6, &v4) {
+        return Err(anyhow::anyhow!("{}", err));
+    }
+    let mut ips = Vec::new();
+    let mut ttl = MAX_TTL;
+    for (found, found_ttl) in [v6, v4].into_iter().flatten() {
+        if !found.is_empty() {
+            ttl = ttl.min(found_ttl);
+        }
+        ips.extend(found);
+    }
+    if ips.is_empty() {
+        return Err(anyhow::anyhow!("DoH returned no addresses for {}", key));
+    }
+    cache().lock().unwrap_or_else(|p| p.into_inner()).insert(
+        &key,
+        ips.clone(),
+        ttl,
+        Instant::now(),
+    );
+    Ok(ips)
+}
+
+async fn system_lookup(host: &str, port: u16) -> anyhow::Result<Vec<SocketAddr>> {
+    Ok(tokio::net::lookup_host((host, port)).await?.collect())
+}
+
+/// 解析 `host` 为 socket 地址。
+///
+/// # 说明
+/// - IP 字面量直接返回；
+/// - 开启 DoH 时优先经 DoH 解析，失败时回退系统 DNS。
+pub async fn lookup(host: &str, port: u16) -> anyhow::Result<Vec<SocketAddr>> {
+    let host = host.trim_start_matches('[').trim_end_matches(']');
+    if let Ok(ip) = host.parse::<IpAddr>() {
+        return Ok(vec![SocketAddr::new(ip, port)]);
+    }
+    let Some(provider) = provider() else {
+        return system_lookup(host, port).await;
+    };
+    match resolve_with_provider(&provider, host).await {
+        Ok(ips) => Ok(ips
+            .into_iter()
+            .map(|ip| SocketAddr::new(ip, port))
+            .collect()),
+        Err(err) => {
+            tracing::warn!(
+                action = "network_dns_over_https_fallback",
+                host = %host,
+                error = %err
+            );
+            system_lookup(host, port).await
+        }
+    }
+}
+
+/// 拆分 `host:port`（IPv6 需带方括号）。
+pub fn split_host_port(addr: &str) -> Option<(&str, u16)> {
+    let (host, port) = addr.trim().rsplit_once(':')?;
+    let port = port.parse().ok()?;
+    let host = match host.strip_prefix('[') {
+        Some(rest) => rest.strip_suffix(']')?,
+        None if host.contains(':') => return None,
+        None => host,
+    };
+    (!host.is_empty()).then_some((host, port))
+}
+
+/// reqwest 解析器：开启 DoH 时经 DoH 解析，否则走系统 DNS。
+pub struct DohResolver;
+
+impl reqwest::dns::Resolve for DohResolver {
+    fn resolve(&self, name: reqwest::dns::Name) -> reqwest::dns::Resolving {
+        let host = name.as_str().to_string();
+        Box::pin(async move {
+            let addrs = lookup(&host, 0)
+                .await
+                .map_err(|e| -> Box<dyn std::error::Error + Send + Sync> { e.into() })?;
+            let addrs: reqwest::dns::Addrs = Box::new(addrs.into_iter());
+            Ok(addrs)
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_address_answers_with_min_ttl() {
+        let body = serde_json::json!({
+            "Status": 0,
+            "Answer": [
+                { "name": "example.com", "type": 5, "TTL": 10, "data": "alias.example.com." },
+                { "name": "alias.example.com", "type": 1, "TTL": 300, "data": "93.184.216.34" },
+                { "name": "alias.example.com", "type": 28, "TTL": 120, "data": "2606:2800:220:1::1" }
+            ]
+        });
+        let (ips, ttl) = parse_answers(&body).unwrap();
+        assert_eq!(ips.len(), 2);
+        assert_eq!(ips[0], "93.184.216.34".parse::<IpAddr>().unwrap());
+        assert_eq!(ttl, Duration::from_secs(120));
+        assert!(parse_answers(&serde_json::json!({ "Status": 3 })).is_err());
+    }
+
+    #[test]
+    fn cache_entries_expire_after_clamped_ttl() {
+        let mut cache = DnsCache::default();
+        let now = Instant::now();
+        let ip: IpAddr = "10.0.0.1".parse().unwrap();
+        cache.insert("example.com", vec![ip], Duration::from_secs(1), now);
+        assert_eq!(
+            cache.get("example.com", now + Duration::from_secs(10)),
+            Some(vec![ip])
+        );
+        assert_eq!(cache.get("example.com", now + MIN_TTL), None);
+    }
+
+    #[test]
+    fn splits_host_and_port() {
+        assert_eq!(
+            split_host_port("example.com:443"),
+            Some(("example.com", 443))
+        );
+        assert_eq!(split_host_port("[::1]:8080"), Some(("::1", 8080)));
+        assert_eq!(split_host_port("::1:8080"), None);
+        assert_eq!(split_host_port("example.com"), None);
+        assert!(is_valid_provider("https://dns.google/resolve"));
+        assert!(!is_valid_provider("http://1.1.1.1/dns-query"));
+    }
+}
@@ -0,0 +1,204 @@
+//! shared｜双栈连接（Happy Eyeballs，参考 RFC 8305）。
+//!
+//! 说明：
+//! - `TcpStream::connect` 按系统解析顺序逐个尝试地址，IPv6 路由不通时会长时间卡住；
+//! - 此处先解析全部地址，按 IPv6/IPv4 交替排序（有 IPv6 时 IPv6 优先），
+//!   每隔 [`CONNECTION_ATTEMPT_DELAY`] 发起下一次尝试，先连上者胜出，其余尝试立即取消；
+//! - 每个地址族有独立的单次连接超时；
+//! - 连接成功时记录胜出的地址族与耗时。
+//!
+//! 约定：注释中文，日志英文（tracing）。
+
+use std::net::SocketAddr;
+use std::time::{Duration, Instant};
+
+use tokio::net::TcpStream;
+use tokio::task::JoinSet;
+
+use crate::shared::net::doh;
+
+/// 两次连接尝试之间的间隔。
+pub const CONNECTION_ATTEMPT_DELAY: Duration = Duration::from_millis(250);
+/// IPv6 单次连接超时（IPv6 不通时尽快让位给 IPv4）。
+pub const IPV6_CONNECT_TIMEOUT: Duration = Duration::from_secs(5);
+/// IPv4 单次连接超时。
+pub const IPV4_CONNECT_TIMEOUT: Duration = Duration::from_secs(10);
+
+fn family(addr: &SocketAddr) -> &'static str {
+    if addr.is_ipv6() { "ipv6" } else { "ipv4" }
+}
+
+fn attempt_timeout(addr: &SocketAddr) -> Duration {
+    if addr.is_ipv6() {
+        IPV6_CONNECT_TIMEOUT
+    } else {
+        IPV4_CONNECT_TIMEOUT
+    }
+}
+
+/// 按地址族交替排序；IPv6 优先，同族内保持解析顺序。
+fn interleave(addrs: Vec<SocketAddr>) -> Vec<SocketAddr> {
+    let (preferred, other): (Vec<_>, Vec<_>) = addrs.into_iter().partition(|a| a.is_ipv6());
+    let mut out = Vec::with_capacity(preferred.len() + other.len());
+    let mut preferred = preferred.into_iter();
+    let mut other = other.into_iter();
+    loop {
+        match (preferred.next(), other.next()) {
+            (None, None) => break,
+            (a, b) => out.extend(a.into_iter().chain(b)),
+        }
+    }
+    out
+}
+
+/// 解析 `host:port`（IPv6 需带方括号；开启 DoH 时经 [`doh::lookup`]）并以双栈竞速方式建立 TCP 连接。
+pub async fn connect(addr: &str) -> anyhow::Result<TcpStream> {
+    let resolved = match doh::split_host_port(addr) {
+        Some((host, port)) => doh::lookup(host, port).await,
+        None => tokio::net::lookup_host(addr)
+            .await
+            .map(|addrs| addrs.collect())
+            .map_err(anyhow::Error::from),
+    }
+    .map_err(|e| anyhow::anyhow!("Failed to resolve {}: {}", addr, e))?;
+    connect_addrs(interleave(resolved), CONNECTION_ATTEMPT_DELAY)
+        .await
+        .map_err(|e| anyhow::anyhow!("Failed to connect to {}: {}", addr, e))
+}
+
+type Attempt = (SocketAddr, std::io::Result<TcpStream>);
+
+/// 发起单次连接尝试（带地址族超时）。
+fn spawn_attempt(attempts: &mut JoinSet<Attempt>, addr: SocketAddr) {
+    attempts.spawn(async move {
+        let result = tokio::time::timeout(attempt_timeout(&addr), TcpStream::connect(addr))
+            .await
+            .unwrap_or_else(|_| {
+                Err(std::io::Error::new(
+                    std::io::ErrorKind::TimedOut,
+                    "connect timed out",
+                ))
+            });
+        (addr, result)
+    });
+}
+
+/// 依次（间隔 `attempt_delay`）对已排序地址发起连接，返回最先成功的连接。
+///
+/// 某次尝试失败时立即发起下一次，不再等待间隔。
+async fn connect_addrs(
+    addrs: Vec<SocketAddr>,
+    attempt_delay: Duration,
+) -> anyhow::Result<TcpStream> {
+    let started = Instant::now();
+    let mut pending = addrs.into_iter();
+    let mut attempts: JoinSet<Attempt> = JoinSet::new();
+    let mut last_error: Option<anyhow::Error> = None;
+
+    let Some(first) = pending.next() else {
+        return Err(anyhow::anyhow!("No addresses resolved"));
+    };
+    spawn_attempt(&mut attempts, first);
+
+    loop {
+        tokio::select! {
+            joined = attempts.join_next() => {
+                let Some(joined) = joined else {
+                    return Err(last_error.unwrap_or_else(|| anyhow::anyhow!("No addresses resolved")));
+                };
+                let (addr, result) =
+                    joined.map_err(|e| anyhow::anyhow!("Connect task failed: {}", e))?;
+                match result {
+                    Ok(stream) => {
+                        tracing::info!(
+                            action = "network_happy_eyeballs_connected",
+                            family = family(&addr),
+                            addr = %addr,
+                            elapsed_ms = started.elapsed().as_millis() as u64
+                        );
+                        return Ok(stream);
+                    }
+                    Err(err) => {
+                        tracing::debug!(
+                            action = "network_happy_eyeballs_attempt_failed",
+                            family = family(&addr),
+                            addr = %addr,
+                            error = %err
+                        );
+                        last_error = Some(anyhow::anyhow!("{} ({}): {}", addr, family(&addr), err));
+                        if let Some(next) = pending.next() {
+                            spawn_attempt(&mut attempts, next);
+                        }
+                    }
+                }
+            }
+            _ = tokio::time::sleep(attempt_delay), if !pending.as_slice().is_empty() => {
+                if let Some(next) = pending.next() {
+                    spawn_attempt(&mut attempts, next);
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sa(raw: &str) -> SocketAddr {
+        raw.parse().unwrap()
+    }
+
+    #[test]
+    fn interleave_alternates_families_preferring_ipv6() {
+        let ordered = interleave(vec![
+            sa("127.0.0.1:1"),
+            sa("[::1]:1"),
+            sa("[::2]:1"),
+            sa("127.0.0.2:1"),
+            sa("[::3]:1"),
+        ]);
+        assert_eq!(
+            ordered,
+            vec![
+                sa("[::1]:1"),
+                sa("127.0.0.1:1"),
+                sa("[::2]:1"),
+                sa("127.0.0.2:1"),
+                sa("[::3]:1"),
+            ]
+        );
+        assert!(interleave(Vec::new()).is_empty());
+    }
+
+    #[tokio::test]
+    async fn falls_back_to_next_address_after_refusal() {
+        let closed = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let refused = closed.local_addr().unwrap();
+        drop(closed);
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let open = listener.local_addr().unwrap();
+
+        let stream = connect_addrs(vec![refused, open], Duration::from_secs(30))
+            .await
+            .unwrap();
+        assert_eq!(stream.peer_addr().unwrap(), open);
+    }
+
+    #[tokio::test]
+    async fn reports_error_when_every_attempt_fails() {
+        let closed = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let refused = closed.local_addr().unwrap();
+        drop(closed);
+        assert!(
+            connect_addrs(vec![refused], CONNECTION_ATTEMPT_DELAY)
+                .await
+                .is_err()
+        );
+        assert!(
+            connect_addrs(Vec::new(), CONNECTION_ATTEMPT_DELAY)
+                .await
+                .is_err()
+        );
+    }
+}
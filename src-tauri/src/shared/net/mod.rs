@@ -2,6 +2,9 @@
 //!
 //! 约定：注释中文，日志英文（tracing）。
 
+pub mod doh;
+pub mod happy_eyeballs;
 pub mod headers;
 pub mod origin;
+pub mod tls_certificate;
 pub mod tls_fingerprint;
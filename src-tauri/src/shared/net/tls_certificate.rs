@@ -0,0 +1,99 @@
+//! shared｜TLS 证书详情解析（供信任对话框展示）。
+//!
+//! 约定：注释中文，日志英文（tracing）。
+
+use std::net::IpAddr;
+
+use anyhow::Context;
+use serde::Serialize;
+use x509_parser::extensions::GeneralName;
+use x509_parser::prelude::{FromDer, X509Certificate};
+
+use super::tls_fingerprint::sha256_hex;
+
+/// 服务器证书详情（Rust -> 前端）。
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CertificateInfo {
+    /// 主体 DN（如 `CN=chat.example.com, O=Example`）。
+    pub subject: String,
+    /// 签发者 DN。
+    pub issuer: String,
+    /// 序列号（冒号分隔 hex）。
+    pub serial_number: String,
+    /// 生效时间（毫秒时间戳）。
+    pub not_before: i64,
+    /// 过期时间（毫秒时间戳）。
+    pub not_after: i64,
+    /// 当前时间是否在有效期内。
+    pub valid_now: bool,
+    /// 主体与签发者相同（自签名）。
+    pub self_signed: bool,
+    /// Subject Alternative Names（DNS / IP / URI / email）。
+    pub subject_alt_names: Vec<String>,
+    /// 证书 DER 的 SHA-256 指纹（小写 hex，与固定证书使用的格式一致）。
+    pub sha256_fingerprint: String,
+}
+
+fn format_general_name(name: &GeneralName<'_>) -> Option<String> {
+    match name {
+        GeneralName::DNSName(dns) => Some(format!("DNS:{dns}")),
+        GeneralName::IPAddress(bytes) => {
+            let ip = match bytes.len() {
+                4 => IpAddr::from(<[u8; 4]>::try_from(*bytes).ok()?),
+                16 => IpAddr::from(<[u8; 16]>::try_from(*bytes).ok()?),
+                _ => return None,
+            };
+            Some(format!("IP:{ip}"))
+        }
+        GeneralName::URI(uri) => Some(format!("URI:{uri}")),
+        GeneralName::RFC822Name(email) => Some(format!("email:{email}")),
+        _ => None,
+    }
+}
+
+/// 解析证书 DER。
+///
+/// # 返回值
+/// - `Ok(CertificateInfo)`：证书详情。
+/// - `Err(anyhow::Error)`：DER 不是合法的 X.509 证书。
+pub fn describe_certificate_der(der: &[u8]) -> anyhow::Result<CertificateInfo> {
+    let (_, cert) = X509Certificate::from_der(der).context("Invalid X.509 certificate")?;
+    let validity = cert.validity();
+    let subject_alt_names = cert
+        .subject_alternative_name()
+        .context("Invalid subjectAltName extension")?
+        .map(|ext| {
+            ext.value
+                .general_names
+                .iter()
+                .filter_map(format_general_name)
+                .collect()
+        })
+        .unwrap_or_default();
+    let subject = cert.subject().to_string();
+    let issuer = cert.issuer().to_string();
+
+    Ok(CertificateInfo {
+        self_signed: subject == issuer,
+        subject,
+        issuer,
+        serial_number: cert.raw_serial_as_string(),
+        not_before: validity.not_before.timestamp().saturating_mul(1000),
+        not_after: validity.not_after.timestamp().saturating_mul(1000),
+        valid_now: validity.is_valid(),
+        subject_alt_names,
+        sha256_fingerprint: sha256_hex(der),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn garbage_der_is_rejected() {
+        let err = describe_certificate_der(b"not a certificate").expect_err("should fail");
+        assert!(err.to_string().contains("Invalid X.509 certificate"));
+    }
+}
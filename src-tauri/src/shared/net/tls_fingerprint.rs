@@ -13,7 +13,8 @@ pub fn normalize_sha256_fingerprint(raw: &str) -> String {
         .collect()
 }
 
-fn sha256_hex(bytes: &[u8]) -> String {
+/// 计算 DER 的 SHA-256 指纹（小写 hex）。
+pub fn sha256_hex(bytes: &[u8]) -> String {
     let mut hasher = sha2::Sha256::new();
     hasher.update(bytes);
     hex::encode(hasher.finalize())
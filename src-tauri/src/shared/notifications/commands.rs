@@ -0,0 +1,69 @@
+//! notifications｜Tauri 命令
+
+use crate::shared::error::{CommandResult, to_command_error};
+
+use super::{NewNotification, NotificationHistoryPage};
+
+/// 默认单页条数。
+const DEFAULT_PAGE_SIZE: u32 = 30;
+
+fn now_ms() -> i64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_millis() as i64
+}
+
+/// 记录一条已弹出的通知。
+///
+/// # 返回值
+/// 新记录的 id。
+#[tauri::command]
+pub async fn record_notification(notification: NewNotification) -> CommandResult<i64> {
+    super::record(&notification, now_ms()).await.map_err(|e| {
+        to_command_error(
+            "NOTIFICATION_RECORD_FAILED",
+            "error.notification_record_failed",
+            e,
+        )
+    })
+}
+
+/// 分页读取通知历史（最新在前）。
+///
+/// # 参数
+/// - `cursor`：上一页返回的 `nextCursor`；为空时读取第一页。
+/// - `limit`：单页条数（默认 30，最大 100）。
+#[tauri::command]
+pub async fn get_notification_history(
+    cursor: Option<i64>,
+    limit: Option<u32>,
+) -> CommandResult<NotificationHistoryPage> {
+    super::load_page(cursor, limit.unwrap_or(DEFAULT_PAGE_SIZE))
+        .await
+        .map_err(|e| {
+            to_command_error(
+                "NOTIFICATION_HISTORY_LOAD_FAILED",
+                "error.notification_history_load_failed",
+                e,
+            )
+        })
+}
+
+/// 标记通知已读。
+///
+/// # 参数
+/// - `id`：通知 id；为空时全部标记为已读。
+///
+/// # 返回值
+/// 实际由未读变为已读的条数。
+#[tauri::command]
+pub async fn mark_notification_read(id: Option<i64>) -> CommandResult<u64> {
+    super::mark_read(id, now_ms()).await.map_err(|e| {
+        to_command_error(
+            "NOTIFICATION_MARK_READ_FAILED",
+            "error.notification_mark_read_failed",
+            e,
+        )
+    })
+}
@@ -0,0 +1,295 @@
+//! 模块入口：notifications（通知中心历史）。
+//!
+//! 说明：
+//! - 每条弹出过的桌面通知都写入 system DB `notifications` 表，关闭通知后仍可在通知中心回看；
+//! - 历史按 id 倒序分页，游标为上一页最后一条的 id；
+//! - 每次写入后按设置裁剪：超过保留天数的记录与超出保留条数的最旧记录会被删除。
+//!
+//! 约定：注释中文，日志英文（tracing）。
+
+pub mod commands;
+
+use anyhow::{Context, Result};
+use sea_orm::ConnectionTrait;
+use serde::{Deserialize, Serialize};
+
+use crate::shared::db::commands::RawStatement;
+
+/// 通知历史所在的 DB key（应用级 system DB）。
+const SYSTEM_DB_KEY: &str = "system";
+
+/// 未配置 `notification_history_max_age_days` 时的默认保留天数。
+pub const DEFAULT_MAX_AGE_DAYS: u32 = 30;
+
+/// 未配置 `notification_history_max_count` 时的默认保留条数。
+pub const DEFAULT_MAX_COUNT: u32 = 1000;
+
+/// 单页最大条数。
+pub const MAX_PAGE_SIZE: u32 = 100;
+
+const DAY_MS: i64 = 24 * 60 * 60 * 1000;
+
+/// 待记录的通知（由前端在通知弹出后上报）。
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NewNotification {
+    /// 通知类型（如 `message` / `mention`），用于前端分组与图标。
+    #[serde(default = "default_kind")]
+    pub kind: String,
+    pub title: String,
+    pub body: String,
+    pub server_socket: Option<String>,
+    pub channel_id: Option<String>,
+    pub message_id: Option<String>,
+}
+
+fn default_kind() -> String {
+    "message".to_string()
+}
+
+/// 通知历史记录。
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NotificationRecord {
+    pub id: i64,
+    pub kind: String,
+    pub title: String,
+    pub body: String,
+    pub server_socket: Option<String>,
+    pub channel_id: Option<String>,
+    pub message_id: Option<String>,
+    pub created_at: i64,
+    /// 已读时间（毫秒）；未读为空。
+    pub read_at: Option<i64>,
+}
+
+/// 通知历史分页结果。
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NotificationHistoryPage {
+    pub items: Vec<NotificationRecord>,
+    /// 下一页游标；没有更多记录时为空。
+    pub next_cursor: Option<i64>,
+    pub unread_count: i64,
+}
+
+/// 根据设置值计算裁剪条件：返回 `(最早保留时间, 最多保留条数)`；0 表示使用默认值。
+fn retention(max_age_days: u32, max_count: u32, now_ms: i64) -> (i64, i64) {
+    let days = if max_age_days == 0 {
+        DEFAULT_MAX_AGE_DAYS
+    } else {
+        max_age_days
+    };
+    let count = if max_count == 0 {
+        DEFAULT_MAX_COUNT
+    } else {
+        max_count
+    };
+    (now_ms - i64::from(days) * DAY_MS, i64::from(count))
+}
+
+/// 将多取一条的查询结果切成一页，并计算下一页游标。
+fn split_page(
+    mut rows: Vec<NotificationRecord>,
+    limit: usize,
+) -> (Vec<NotificationRecord>, Option<i64>) {
+    if rows.len() <= limit {
+        return (rows, None);
+    }
+    rows.truncate(limit);
+    let next = rows.last().map(|r| r.id);
+    (rows, next)
+}
+
+/// 写入一条通知并按设置裁剪历史。
+///
+/// # 返回值
+/// 新记录的 id。
+pub async fn record(notification: &NewNotification, now_ms: i64) -> Result<i64> {
+    let db = crate::shared::db::get_db(SYSTEM_DB_KEY).await?;
+    let stmt = RawStatement::new(
+        "INSERT INTO notifications
+           (kind, title, body, server_socket, channel_id, message_id, created_at)
+         VALUES (?, ?, ?, ?, ?, ?, ?)",
+        vec![
+            notification.kind.clone().into(),
+            notification.title.clone().into(),
+            notification.body.clone().into(),
+            notification.server_socket.clone().into(),
+            notification.channel_id.clone().into(),
+            notification.message_id.clone().into(),
+            now_ms.into(),
+        ],
+    );
+    let result = db
+        .connection
+        .execute(&stmt)
+        .await
+        .context("NOTIFICATION_RECORD_FAILED")?;
+    let id = result.last_insert_id() as i64;
+
+    if let Err(e) = prune(now_ms).await {
+        tracing::warn!(action = "app_notification_history_prune_failed", error = %e);
+    }
+    Ok(id)
+}
+
+/// 按保留天数与条数裁剪历史。
+pub async fn prune(now_ms: i64) -> Result<()> {
+    let max_age_days = crate::features::settings::get_config_value::<u32>(String::from(
+        "notification_history_max_age_days",
+    ))
+    .await;
+    let max_count = crate::features::settings::get_config_value::<u32>(String::from(
+        "notification_history_max_count",
+    ))
+    .await;
+    let (cutoff, keep) = retention(max_age_days, max_count, now_ms);
+
+    let db = crate::shared::db::get_db(SYSTEM_DB_KEY).await?;
+    let by_age = db
+        .connection
+        .execute(&RawStatement::new(
+            "DELETE FROM notifications WHERE created_at < ?",
+            vec![cutoff.into()],
+        ))
+        .await
+        .context("NOTIFICATION_PRUNE_FAILED")?;
+    let by_count = db
+        .connection
+        .execute(&RawStatement::new(
+            "DELETE FROM notifications WHERE id NOT IN
+               (SELECT id FROM notifications ORDER BY id DESC LIMIT ?)",
+            vec![keep.into()],
+        ))
+        .await
+        .context("NOTIFICATION_PRUNE_FAILED")?;
+    let removed = by_age.rows_affected() + by_count.rows_affected();
+    if removed > 0 {
+        tracing::debug!(action = "app_notification_history_pruned", removed);
+    }
+    Ok(())
+}
+
+/// 读取一页通知历史（id 倒序）。
+///
+/// # 参数
+/// - `cursor`：上一页返回的 `next_cursor`；为空时从最新一条开始。
+/// - `limit`：单页条数（1..=[`MAX_PAGE_SIZE`]）。
+pub async fn load_page(cursor: Option<i64>, limit: u32) -> Result<NotificationHistoryPage> {
+    let limit = limit.clamp(1, MAX_PAGE_SIZE);
+    let db = crate::shared::db::get_db(SYSTEM_DB_KEY).await?;
+    let stmt = RawStatement::new(
+        "SELECT id, kind, title, body, server_socket, channel_id, message_id, created_at, read_at
+         FROM notifications WHERE id < ? ORDER BY id DESC LIMIT ?",
+        vec![
+            cursor.unwrap_or(i64::MAX).into(),
+            (i64::from(limit) + 1).into(),
+        ],
+    );
+    let rows = db
+        .connection
+        .query_all(&stmt)
+        .await
+        .context("NOTIFICATION_HISTORY_LOAD_FAILED")?;
+    let records = rows
+        .iter()
+        .filter_map(|row| {
+            Some(NotificationRecord {
+                id: row.try_get("", "id").ok()?,
+                kind: row.try_get("", "kind").ok()?,
+                title: row.try_get("", "title").ok()?,
+                body: row.try_get("", "body").ok()?,
+                server_socket: row.try_get("", "server_socket").ok()?,
+                channel_id: row.try_get("", "channel_id").ok()?,
+                message_id: row.try_get("", "message_id").ok()?,
+                created_at: row.try_get("", "created_at").ok()?,
+                read_at: row.try_get("", "read_at").ok()?,
+            })
+        })
+        .collect();
+    let (items, next_cursor) = split_page(records, limit as usize);
+
+    let unread = db
+        .connection
+        .query_one(&RawStatement::new(
+            "SELECT COUNT(*) AS n FROM notifications WHERE read_at IS NULL",
+            Vec::new(),
+        ))
+        .await
+        .context("NOTIFICATION_HISTORY_LOAD_FAILED")?;
+    let unread_count = unread
+        .and_then(|row| row.try_get::<i64>("", "n").ok())
+        .unwrap_or(0);
+
+    Ok(NotificationHistoryPage {
+        items,
+        next_cursor,
+        unread_count,
+    })
+}
+
+/// 标记已读；`id` 为空时标记全部。
+///
+/// # 返回值
+/// 实际由未读变为已读的条数。
+pub async fn mark_read(id: Option<i64>, now_ms: i64) -> Result<u64> {
+    let db = crate::shared::db::get_db(SYSTEM_DB_KEY).await?;
+    let stmt = match id {
+        Some(id) => RawStatement::new(
+            "UPDATE notifications SET read_at = ? WHERE id = ? AND read_at IS NULL",
+            vec![now_ms.into(), id.into()],
+        ),
+        None => RawStatement::new(
+            "UPDATE notifications SET read_at = ? WHERE read_at IS NULL",
+            vec![now_ms.into()],
+        ),
+    };
+    let result = db
+        .connection
+        .execute(&stmt)
+        .await
+        .context("NOTIFICATION_MARK_READ_FAILED")?;
+    Ok(result.rows_affected())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn record_with_id(id: i64) -> NotificationRecord {
+        NotificationRecord {
+            id,
+            kind: "message".to_string(),
+            title: "t".to_string(),
+            body: "b".to_string(),
+            server_socket: None,
+            channel_id: None,
+            message_id: None,
+            created_at: 0,
+            read_at: None,
+        }
+    }
+
+    #[test]
+    fn retention_falls_back_to_defaults() {
+        let now = 100 * DAY_MS;
+        assert_eq!(
+            retention(0, 0, now),
+            (now - i64::from(DEFAULT_MAX_AGE_DAYS) * DAY_MS, 1000)
+        );
+        assert_eq!(retention(7, 50, now), (93 * DAY_MS, 50));
+    }
+
+    #[test]
+    fn page_split_reports_next_cursor_only_when_more_rows_exist() {
+        let rows: Vec<_> = (1..=3).rev().map(record_with_id).collect();
+        let (items, next) = split_page(rows.clone(), 2);
+        assert_eq!(items.len(), 2);
+        assert_eq!(next, Some(2));
+
+        let (items, next) = split_page(rows, 3);
+        assert_eq!(items.len(), 3);
+        assert_eq!(next, None);
+    }
+}
@@ -0,0 +1,152 @@
+//! paths｜Tauri 命令
+
+use std::path::{Path, PathBuf};
+
+use anyhow::Context;
+use sea_orm::ConnectionTrait;
+
+use serde::Serialize;
+use tauri::{AppHandle, Manager};
+
+use crate::shared::error::{CommandResult, command_error, to_command_error};
+
+/// 数据目录变更结果（Rust -> 前端）。
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DataDirectoryChange {
+    /// 新数据目录。
+    pub path: String,
+    /// 迁移复制的文件数。
+    pub migrated_files: u64,
+    /// 是否需要重启生效（打开中的数据库/日志句柄仍指向旧目录）。
+    pub restart_required: bool,
+}
+
+/// 以 `VACUUM INTO` 将 `from` 下的全部 SQLite 数据库写入 `to` 的相同相对位置。
+///
+/// 另开连接读取一致性快照：应用自身仍持有的连接与未 checkpoint 的 WAL 都不影响结果。
+///
+/// # 返回值
+/// 已写入快照的数据库（相对路径）。
+async fn snapshot_databases(from: &Path, to: &Path) -> anyhow::Result<Vec<PathBuf>> {
+    let databases = tokio::task::spawn_blocking({
+        let from = from.to_path_buf();
+        move || super::find_sqlite_files(&from)
+    })
+    .await??;
+    for rel in &databases {
+        let source = from.join(rel);
+        let dest = to.join(rel);
+        if let Some(parent) = dest.parent() {
+            tokio::fs::create_dir_all(parent)
+                .await
+                .with_context(|| format!("Failed to create dir: {}", parent.display()))?;
+        }
+        let conn = sea_orm::Database::connect(crate::shared::db::sqlite_url_for_path(&source))
+            .await
+            .with_context(|| format!("Failed to open database: {}", source.display()))?;
+        let dest_sql = dest.to_string_lossy().replace('\'', "''");
+        let result = conn
+            .execute_unprepared(&format!("VACUUM INTO '{dest_sql}'"))
+            .await
+            .with_context(|| format!("Failed to snapshot database: {}", source.display()));
+        let _ = conn.close().await;
+        result?;
+    }
+    Ok(databases)
+}
+
+/// 获取当前数据目录。
+#[tauri::command]
+pub fn get_data_directory() -> CommandResult<String> {
+    crate::shared::app_data_dir::get_app_data_dir()
+        .map(|dir| dir.to_string_lossy().to_string())
+        .map_err(|e| to_command_error("APP_DATA_DIR", "error.app_data_dir", e))
+}
+
+/// 设置自定义数据目录，并把现有数据复制过去。
+///
+/// # 参数
+/// - `path`：新数据目录（绝对路径，需为空目录或不存在）；传空字符串表示恢复平台默认目录。
+///
+/// # 返回值
+/// - `Ok(DataDirectoryChange)`：迁移完成，重启后生效。
+/// - `Err(CommandError)`：路径非法或迁移失败。
+///
+/// # 说明
+/// - SQLite 数据库以 `VACUUM INTO` 快照迁移，不直接复制打开中的文件；
+/// - 旧目录中的数据不会被删除，避免迁移失败时丢数据；
+/// - 便携模式下数据目录固定在可执行文件旁，不允许修改；
+/// - 移动端只能使用应用沙箱目录，不允许修改。
+#[tauri::command]
+pub async fn set_data_directory(
+    app: AppHandle,
+    path: String,
+) -> CommandResult<DataDirectoryChange> {
+    if cfg!(mobile) {
+        return Err(command_error(
+            "DATA_DIR_SANDBOXED",
+            "error.data_dir_sandboxed",
+        ));
+    }
+    if super::is_portable_mode() {
+        return Err(command_error(
+            "DATA_DIR_PORTABLE_LOCKED",
+            "error.data_dir_portable_locked",
+        ));
+    }
+    let default_dir = app
+        .path()
+        .app_data_dir()
+        .map_err(|e| to_command_error("APP_DATA_DIR", "error.app_data_dir", e))?;
+    let current = crate::shared::app_data_dir::get_app_data_dir()
+        .map_err(|e| to_command_error("APP_DATA_DIR", "error.app_data_dir", e))?;
+
+    let trimmed = path.trim();
+    let target = if trimmed.is_empty() {
+        default_dir.clone()
+    } else {
+        PathBuf::from(trimmed)
+    };
+    if target == current {
+        return Err(command_error(
+            "DATA_DIR_UNCHANGED",
+            "error.data_dir_unchanged",
+        ));
+    }
+
+    let migrate = async {
+        super::validate_target_dir(&current, &target, target == default_dir)?;
+        let snapshots = snapshot_databases(&current, &target).await?;
+        let copied = tokio::task::spawn_blocking({
+            let current = current.clone();
+            let target = target.clone();
+            move || -> anyhow::Result<u64> {
+                let copied = super::migrate_data_dir(&current, &target, &snapshots)?;
+                super::write_data_dir_pointer(&default_dir, &target)?;
+                Ok(copied + snapshots.len() as u64)
+            }
+        })
+        .await??;
+        anyhow::Ok(copied)
+    };
+    let migrated_files = migrate.await.map_err(|e| {
+        to_command_error(
+            "DATA_DIR_MIGRATE_FAILED",
+            "error.data_dir_migrate_failed",
+            e,
+        )
+    })?;
+
+    tracing::info!(
+        action = "app_data_dir_migrated",
+        from = %current.display(),
+        to = %target.display(),
+        files = migrated_files
+    );
+    Ok(DataDirectoryChange {
+        path: target.to_string_lossy().to_string(),
+        migrated_files,
+        restart_required: true,
+    })
+}
@@ -0,0 +1,345 @@
+//! 模块入口：paths。
+//!
+//! 说明：统一解析应用数据目录（平台默认目录 + 用户自定义目录），并提供目录迁移工具。
+//!
+//! 约定：注释中文，日志英文（tracing）。
+//!
+//! 目录选择记录在平台默认 app_data_dir 下的 `data-dir.json` 中：
+//! 该文件必须位于“固定位置”，否则改目录后下次启动无法找回。
+//!
+//! 移动端（Android / iOS）只能写应用沙箱：不支持便携模式与自定义目录，固定使用平台默认目录。
+
+pub mod commands;
+
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+
+use anyhow::Context;
+use serde::{Deserialize, Serialize};
+
+/// 自定义数据目录指针文件名（位于平台默认 app_data_dir）。
+const DATA_DIR_POINTER_FILE: &str = "data-dir.json";
+
+/// 便携模式标记文件名（与可执行文件同目录）。
+const PORTABLE_MARKER_FILE: &str = "carrypigeon.portable";
+
+/// 便携模式下数据目录名（与可执行文件同目录）。
+const PORTABLE_DATA_DIR: &str = "data";
+
+/// SQLite 数据库文件头。
+const SQLITE_HEADER: &[u8; 16] = b"SQLite format 3\0";
+
+/// SQLite 附属文件后缀（随快照一并跳过）。
+const SQLITE_SIDECAR_SUFFIXES: &[&str] = &["-wal", "-shm", "-journal"];
+
+static PORTABLE_ROOT: OnceLock<Option<PathBuf>> = OnceLock::new();
+
+/// 检测可执行文件目录下是否存在便携模式标记，存在则返回该目录。
+fn detect_portable_root(exe_dir: &Path) -> Option<PathBuf> {
+    exe_dir
+        .join(PORTABLE_MARKER_FILE)
+        .is_file()
+        .then(|| exe_dir.to_path_buf())
+}
+
+/// 便携模式根目录（可执行文件所在目录）；非便携模式返回 `None`。
+///
+/// 进程内只检测一次：运行期间删除/新增标记文件不会改变当前进程的模式。
+fn portable_root() -> Option<&'static Path> {
+    if cfg!(mobile) {
+        return None;
+    }
+    PORTABLE_ROOT
+        .get_or_init(|| {
+            let exe = std::env::current_exe().ok()?;
+            detect_portable_root(exe.parent()?)
+        })
+        .as_deref()
+}
+
+/// 是否运行在便携模式（U 盘等场景）。
+///
+/// 便携模式下所有数据固定写在可执行文件旁，且开机自启等写系统注册表/启动项的功能被禁用。
+pub fn is_portable_mode() -> bool {
+    portable_root().is_some()
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct DataDirPointer {
+    data_dir: PathBuf,
+}
+
+/// 根据平台默认目录解析实际使用的数据目录。
+///
+/// # 参数
+/// - `default_dir`：Tauri path API 给出的平台默认 app_data_dir。
+///
+/// # 返回值
+/// 便携模式下返回可执行文件旁的 `data/`；否则若存在合法的自定义目录指针则返回该目录，
+/// 其余情况返回 `default_dir`（移动端总是返回 `default_dir`）。
+pub fn resolve_data_dir(default_dir: &Path) -> PathBuf {
+    if cfg!(mobile) {
+        return default_dir.to_path_buf();
+    }
+    if let Some(root) = portable_root() {
+        return root.join(PORTABLE_DATA_DIR);
+    }
+    let pointer_path = default_dir.join(DATA_DIR_POINTER_FILE);
+    let Ok(raw) = std::fs::read_to_string(&pointer_path) else {
+        return default_dir.to_path_buf();
+    };
+    match serde_json::from_str::<DataDirPointer>(&raw) {
+        Ok(pointer) if pointer.data_dir.is_absolute() => pointer.data_dir,
+        Ok(pointer) => {
+            tracing::warn!(
+                action = "app_data_dir_pointer_not_absolute",
+                path = %pointer.data_dir.display()
+            );
+            default_dir.to_path_buf()
+        }
+        Err(error) => {
+            tracing::warn!(
+                action = "app_data_dir_pointer_parse_failed",
+                path = %pointer_path.display(),
+                error = %error
+            );
+            default_dir.to_path_buf()
+        }
+    }
+}
+
+/// 写入（或清除）自定义数据目录指针。
+///
+/// # 参数
+/// - `default_dir`：平台默认 app_data_dir。
+/// - `target`：新数据目录；与 `default_dir` 相同时删除指针文件。
+pub fn write_data_dir_pointer(default_dir: &Path, target: &Path) -> anyhow::Result<()> {
+    let pointer_path = default_dir.join(DATA_DIR_POINTER_FILE);
+    if target == default_dir {
+        return match std::fs::remove_file(&pointer_path) {
+            Ok(()) => Ok(()),
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => Ok(()),
+            Err(err) => Err(err.into()),
+        };
+    }
+    std::fs::create_dir_all(default_dir)
+        .with_context(|| format!("Failed to create dir: {}", default_dir.display()))?;
+    let json = serde_json::to_string_pretty(&DataDirPointer {
+        data_dir: target.to_path_buf(),
+    })?;
+    std::fs::write(&pointer_path, json).with_context(|| {
+        format!(
+            "Failed to write data dir pointer: {}",
+            pointer_path.display()
+        )
+    })
+}
+
+/// 校验目标数据目录：必须是绝对路径、不能与当前目录互相嵌套，且存在时必须为空。
+///
+/// `allow_non_empty` 仅用于“恢复默认目录”：默认目录里可能残留旧数据与指针文件。
+pub fn validate_target_dir(
+    current: &Path,
+    target: &Path,
+    allow_non_empty: bool,
+) -> anyhow::Result<()> {
+    if !target.is_absolute() {
+        return Err(anyhow::anyhow!("Data directory must be an absolute path"));
+    }
+    if target.starts_with(current) || current.starts_with(target) {
+        return Err(anyhow::anyhow!(
+            "Data directory must not be nested with the current one"
+        ));
+    }
+    if !allow_non_empty
+        && let Ok(mut entries) = std::fs::read_dir(target)
+        && entries.next().is_some()
+    {
+        return Err(anyhow::anyhow!("Target data directory is not empty"));
+    }
+    Ok(())
+}
+
+/// 递归列出 `root` 下的 SQLite 数据库文件（按文件头识别，返回相对路径）。
+pub fn find_sqlite_files(root: &Path) -> anyhow::Result<Vec<PathBuf>> {
+    let mut found = Vec::new();
+    if !root.exists() {
+        return Ok(found);
+    }
+    let mut stack = vec![root.to_path_buf()];
+    while let Some(dir) = stack.pop() {
+        for entry in std::fs::read_dir(&dir)
+            .with_context(|| format!("Failed to read dir: {}", dir.display()))?
+        {
+            let entry = entry?;
+            let path = entry.path();
+            let ty = entry.file_type()?;
+            if ty.is_dir() {
+                stack.push(path);
+            } else if ty.is_file() && has_sqlite_header(&path) {
+                found.push(path.strip_prefix(root)?.to_path_buf());
+            }
+        }
+    }
+    found.sort();
+    Ok(found)
+}
+
+fn has_sqlite_header(path: &Path) -> bool {
+    use std::io::Read;
+    let mut header = [0u8; 16];
+    std::fs::File::open(path)
+        .and_then(|mut file| file.read_exact(&mut header))
+        .is_ok()
+        && &header == SQLITE_HEADER
+}
+
+/// `rel` 是否为 `snapshots` 中某个数据库本身或其附属文件。
+fn is_snapshotted(rel: &Path, snapshots: &[PathBuf]) -> bool {
+    snapshots.iter().any(|db| {
+        rel == db
+            || SQLITE_SIDECAR_SUFFIXES.iter().any(|suffix| {
+                let mut sidecar = db.as_os_str().to_os_string();
+                sidecar.push(suffix);
+                rel == Path::new(&sidecar)
+            })
+    })
+}
+
+/// 将 `from` 下的全部内容递归复制到 `to`（不删除源目录）。
+///
+/// # 参数
+/// - `snapshots`：已通过 `VACUUM INTO` 写入目标目录的数据库（相对路径），连同附属文件一并跳过。
+///
+/// # 返回值
+/// 复制的文件数量。
+///
+/// # 说明
+/// - 会跳过 `data-dir.json` 指针文件（它只在默认目录中有意义）；
+/// - 打开中的 SQLite 文件不能直接复制（可能读到写了一半的页或遗漏 WAL），须先生成快照；
+/// - 源目录保留，待用户确认新目录可用后再手动清理，避免迁移中途失败导致数据丢失。
+pub fn migrate_data_dir(from: &Path, to: &Path, snapshots: &[PathBuf]) -> anyhow::Result<u64> {
+    std::fs::create_dir_all(to)
+        .with_context(|| format!("Failed to create dir: {}", to.display()))?;
+    if !from.exists() {
+        return Ok(0);
+    }
+    let mut copied = 0u64;
+    let mut stack = vec![from.to_path_buf()];
+    while let Some(dir) = stack.pop() {
+        for entry in std::fs::read_dir(&dir)
+            .with_context(|| format!("Failed to read dir: {}", dir.display()))?
+        {
+            let entry = entry?;
+            let src = entry.path();
+            let rel = src.strip_prefix(from)?;
+            if rel == Path::new(DATA_DIR_POINTER_FILE) || is_snapshotted(rel, snapshots) {
+                continue;
+            }
+            let dst = to.join(rel);
+            let ty = entry.file_type()?;
+            if ty.is_dir() {
+                std::fs::create_dir_all(&dst)
+                    .with_context(|| format!("Failed to create dir: {}", dst.display()))?;
+                stack.push(src);
+            } else if ty.is_file() {
+                std::fs::copy(&src, &dst)
+                    .with_context(|| format!("Failed to copy {}", src.display()))?;
+                copied += 1;
+            }
+        }
+    }
+    Ok(copied)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::{SystemTime, UNIX_EPOCH};
+
+    fn unique_temp_dir(prefix: &str) -> PathBuf {
+        let nanos = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .expect("time")
+            .as_nanos();
+        std::env::temp_dir().join(format!("carrypigeon-{prefix}-{nanos}"))
+    }
+
+    #[test]
+    fn resolve_data_dir_follows_pointer_and_falls_back() {
+        let default_dir = unique_temp_dir("paths-default");
+        std::fs::create_dir_all(&default_dir).expect("default dir");
+        assert_eq!(resolve_data_dir(&default_dir), default_dir);
+
+        let custom = unique_temp_dir("paths-custom");
+        write_data_dir_pointer(&default_dir, &custom).expect("write pointer");
+        assert_eq!(resolve_data_dir(&default_dir), custom);
+
+        write_data_dir_pointer(&default_dir, &default_dir).expect("clear pointer");
+        assert_eq!(resolve_data_dir(&default_dir), default_dir);
+
+        let _ = std::fs::remove_dir_all(&default_dir);
+    }
+
+    #[test]
+    fn detect_portable_root_requires_marker_file() {
+        let exe_dir = unique_temp_dir("paths-portable");
+        std::fs::create_dir_all(&exe_dir).expect("exe dir");
+        assert_eq!(detect_portable_root(&exe_dir), None);
+
+        std::fs::write(exe_dir.join(PORTABLE_MARKER_FILE), "").expect("marker");
+        assert_eq!(detect_portable_root(&exe_dir), Some(exe_dir.clone()));
+
+        let _ = std::fs::remove_dir_all(&exe_dir);
+    }
+
+    #[test]
+    fn migrate_data_dir_copies_nested_files() {
+        let from = unique_temp_dir("paths-from");
+        let to = unique_temp_dir("paths-to");
+        std::fs::create_dir_all(from.join("db")).expect("db dir");
+        std::fs::write(from.join("config.json"), "{}").expect("config");
+        std::fs::write(from.join("db").join("system.db"), "x").expect("db");
+        std::fs::write(from.join(DATA_DIR_POINTER_FILE), "{}").expect("pointer");
+
+        validate_target_dir(&from, &to, false).expect("target valid");
+        let copied = migrate_data_dir(&from, &to, &[]).expect("migrate");
+        assert_eq!(copied, 2);
+        assert!(to.join("db").join("system.db").exists());
+        assert!(!to.join(DATA_DIR_POINTER_FILE).exists());
+        assert!(from.join("config.json").exists(), "source must be kept");
+
+        let err = validate_target_dir(&from, &to, false).expect_err("non-empty target");
+        assert!(err.to_string().contains("not empty"));
+        let err =
+            validate_target_dir(&from, &from.join("nested"), false).expect_err("nested target");
+        assert!(err.to_string().contains("nested"));
+
+        let _ = std::fs::remove_dir_all(&from);
+        let _ = std::fs::remove_dir_all(&to);
+    }
+
+    #[test]
+    fn migrate_data_dir_skips_snapshotted_databases_and_sidecars() {
+        let from = unique_temp_dir("paths-sqlite-from");
+        let to = unique_temp_dir("paths-sqlite-to");
+        std::fs::create_dir_all(from.join("db")).expect("db dir");
+        let mut db = SQLITE_HEADER.to_vec();
+        db.extend_from_slice(b"pages");
+        std::fs::write(from.join("db").join("system.db"), &db).expect("db");
+        std::fs::write(from.join("db").join("system.db-wal"), "wal").expect("wal");
+        std::fs::write(from.join("db").join("notes.txt"), "x").expect("notes");
+
+        let snapshots = find_sqlite_files(&from).expect("find");
+        assert_eq!(snapshots, vec![PathBuf::from("db").join("system.db")]);
+        let copied = migrate_data_dir(&from, &to, &snapshots).expect("migrate");
+        assert_eq!(copied, 1);
+        assert!(to.join("db").join("notes.txt").exists());
+        assert!(!to.join("db").join("system.db").exists());
+        assert!(!to.join("db").join("system.db-wal").exists());
+
+        let _ = std::fs::remove_dir_all(&from);
+        let _ = std::fs::remove_dir_all(&to);
+    }
+}
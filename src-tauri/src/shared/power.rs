@@ -0,0 +1,118 @@
+//! shared｜电源状态检测（是否使用电池供电）。
+//!
+//! 说明：
+//! - Linux 读取 `/sys/class/power_supply`；macOS 解析 `pmset -g batt`；Windows 查询
+//!   `Win32_Battery.BatteryStatus`（1 = 放电中）；
+//! - 无法判断时返回 `None`，调用方应按“接通电源”处理，避免后台任务永远不执行。
+//!
+//! 约定：注释中文，日志英文（tracing）。
+
+/// 当前是否使用电池供电（阻塞调用，需在 `spawn_blocking` 中使用）。
+pub fn on_battery() -> Option<bool> {
+    #[cfg(target_os = "linux")]
+    {
+        linux_on_battery(std::path::Path::new("/sys/class/power_supply"))
+    }
+    #[cfg(target_os = "macos")]
+    {
+        let output = std::process::Command::new("pmset")
+            .args(["-g", "batt"])
+            .output()
+            .ok()?;
+        parse_pmset(&String::from_utf8_lossy(&output.stdout))
+    }
+    #[cfg(target_os = "windows")]
+    {
+        use std::os::windows::process::CommandExt;
+        // CREATE_NO_WINDOW：避免弹出控制台窗口。
+        let output = std::process::Command::new("powershell")
+            .args([
+                "-NoProfile",
+                "-NonInteractive",
+                "-Command",
+                "(Get-CimInstance Win32_Battery).BatteryStatus",
+            ])
+            .creation_flags(0x0800_0000)
+            .output()
+            .ok()?;
+        let status = String::from_utf8_lossy(&output.stdout);
+        let status = status.trim();
+        if status.is_empty() {
+            // 台式机没有电池。
+            return Some(false);
+        }
+        Some(status.lines().any(|line| line.trim() == "1"))
+    }
+    #[cfg(not(any(target_os = "linux", target_os = "macos", target_os = "windows")))]
+    {
+        None
+    }
+}
+
+#[cfg(target_os = "linux")]
+fn linux_on_battery(root: &std::path::Path) -> Option<bool> {
+    let read = |path: std::path::PathBuf| std::fs::read_to_string(path).ok();
+    let mut saw_supply = false;
+    let mut discharging = false;
+    for entry in std::fs::read_dir(root).ok()?.flatten() {
+        let dir = entry.path();
+        match read(dir.join("type")).as_deref().map(str::trim) {
+            Some("Mains") => {
+                saw_supply = true;
+                if read(dir.join("online")).as_deref().map(str::trim) == Some("1") {
+                    return Some(false);
+                }
+            }
+            Some("Battery") => {
+                saw_supply = true;
+                discharging |=
+                    read(dir.join("status")).as_deref().map(str::trim) == Some("Discharging");
+            }
+            _ => {}
+        }
+    }
+    saw_supply.then_some(discharging)
+}
+
+#[cfg(any(target_os = "macos", test))]
+fn parse_pmset(output: &str) -> Option<bool> {
+    let first = output.lines().next()?;
+    if first.contains("'Battery Power'") {
+        Some(true)
+    } else if first.contains("'AC Power'") {
+        Some(false)
+    } else {
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn pmset_source_line_is_parsed() {
+        assert_eq!(
+            parse_pmset("Now drawing from 'Battery Power'\n -InternalBattery-0 80%"),
+            Some(true)
+        );
+        assert_eq!(parse_pmset("Now drawing from 'AC Power'\n"), Some(false));
+        assert_eq!(parse_pmset(""), None);
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn linux_mains_online_wins_over_battery() {
+        let dir = tempfile::tempdir().unwrap();
+        let write = |name: &str, kind: &str, key: &str, value: &str| {
+            let supply = dir.path().join(name);
+            std::fs::create_dir_all(&supply).unwrap();
+            std::fs::write(supply.join("type"), kind).unwrap();
+            std::fs::write(supply.join(key), value).unwrap();
+        };
+        write("BAT0", "Battery\n", "status", "Discharging\n");
+        assert_eq!(linux_on_battery(dir.path()), Some(true));
+        write("AC", "Mains\n", "online", "1\n");
+        assert_eq!(linux_on_battery(dir.path()), Some(false));
+    }
+}
@@ -0,0 +1,59 @@
+//! shared｜隐私模式（设置 `privacy_mode`）。
+//!
+//! 说明：
+//! - 开启后前端通知只显示“#频道 有新消息”，托盘悬停预览不显示消息内容；
+//! - 后端为所有窗口开启内容保护（屏幕截图/录屏/共享中显示为空白），
+//!   仅 Windows 与 macOS 支持，其它平台（含移动端）为空操作；
+//! - 开关随设置写入即时生效（见 `config_store::apply_runtime_switches`），
+//!   新建窗口通过 [`is_enabled`] 在构建时带上同样的保护。
+//!
+//! 约定：注释中文，日志英文（tracing）。
+
+use std::sync::OnceLock;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use tauri::AppHandle;
+#[cfg(desktop)]
+use tauri::Manager;
+
+static ENABLED: AtomicBool = AtomicBool::new(false);
+static APP_HANDLE: OnceLock<AppHandle> = OnceLock::new();
+
+/// 当前是否开启隐私模式。
+pub fn is_enabled() -> bool {
+    ENABLED.load(Ordering::Relaxed)
+}
+
+/// 注册 AppHandle（setup 阶段调用一次），并按当前开关同步已有窗口。
+pub fn init(app: &AppHandle) {
+    let _ = APP_HANDLE.set(app.clone());
+    apply_to_windows(is_enabled());
+}
+
+/// 更新隐私模式开关；状态变化时同步所有已打开窗口的内容保护。
+pub fn set_enabled(enabled: bool) {
+    if ENABLED.swap(enabled, Ordering::Relaxed) == enabled {
+        return;
+    }
+    tracing::info!(action = "settings_privacy_mode_changed", enabled);
+    apply_to_windows(enabled);
+}
+
+#[cfg(mobile)]
+fn apply_to_windows(_enabled: bool) {}
+
+#[cfg(desktop)]
+fn apply_to_windows(enabled: bool) {
+    let Some(app) = APP_HANDLE.get() else {
+        return;
+    };
+    for (label, window) in app.webview_windows() {
+        if let Err(err) = window.set_content_protected(enabled) {
+            tracing::warn!(
+                action = "windows_content_protection_failed",
+                label = %label,
+                error = %err
+            );
+        }
+    }
+}
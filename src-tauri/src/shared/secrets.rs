@@ -0,0 +1,202 @@
+//! shared｜系统凭据存储（keyring）读写。
+//!
+//! 说明：
+//! - 统一封装 `carrypigeon-desktop` 服务下按账户名存取的机密字符串；
+//! - 系统未提供凭据存储（如无 Secret Service 的 Linux 环境）时，读取/删除视为空操作，
+//!   写入返回错误，由调用方决定是否降级；
+//! - 均为阻塞调用，异步上下文中需在 `spawn_blocking` 中使用；
+//! - 应用使用的全部账户登记在 [`REGISTRY`]，安全擦除按此清单删除；按前缀派生的账户
+//!   （如每个服务器一份的会话令牌）经本模块写入时记入索引账户，擦除时据此枚举。
+//!
+//! 约定：注释中文，日志英文（tracing）。
+
+use std::collections::BTreeSet;
+use std::sync::Mutex;
+
+use keyring_core::Entry;
+
+use crate::shared::chat_cache::commands::is_missing_secure_storage_error_message;
+
+const SERVICE: &str = "carrypigeon-desktop";
+/// 前缀账户索引（JSON 字符串数组）。
+const INDEX_ACCOUNT: &str = "secret-account-index";
+
+/// 凭据存储中的账户。
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SecretAccount {
+    /// 固定账户名。
+    Fixed(&'static str),
+    /// 按前缀派生的一组账户；须经本模块的 [`set`] 写入才会记入索引。
+    Prefixed(&'static str),
+}
+
+/// 应用使用的全部凭据账户；新增账户时须在此登记，否则安全擦除不会删除。
+pub const REGISTRY: &[SecretAccount] = &[
+    SecretAccount::Fixed(crate::shared::app_lock::VERIFIER_ACCOUNT),
+    SecretAccount::Fixed(crate::shared::chat_cache::commands::ACCOUNT),
+    SecretAccount::Fixed(crate::shared::db::backup_remote::SECRET_ACCOUNT),
+    SecretAccount::Fixed(crate::shared::db::channel_keys::DEVICE_SECRET_ACCOUNT),
+    SecretAccount::Fixed(crate::shared::db::channel_keys::SALT_ACCOUNT),
+    SecretAccount::Fixed(crate::shared::db::device_verification::IDENTITY_ACCOUNT),
+    SecretAccount::Prefixed(crate::features::network::data::session_token_store::ACCOUNT_PREFIX),
+];
+
+/// 串行化索引的读改写。
+static INDEX_LOCK: Mutex<()> = Mutex::new(());
+
+fn is_indexed(account: &str) -> bool {
+    REGISTRY
+        .iter()
+        .any(|a| matches!(a, SecretAccount::Prefixed(prefix) if account.starts_with(prefix)))
+}
+
+fn entry(account: &str) -> anyhow::Result<Option<Entry>> {
+    match Entry::new(SERVICE, account) {
+        Ok(entry) => Ok(Some(entry)),
+        Err(e) if is_missing_secure_storage_error_message(&e.to_string()) => Ok(None),
+        Err(e) => Err(e.into()),
+    }
+}
+
+fn read_raw(account: &str) -> anyhow::Result<Option<String>> {
+    let Some(entry) = entry(account)? else {
+        return Ok(None);
+    };
+    match entry.get_password() {
+        Ok(secret) => Ok(Some(secret)),
+        Err(e) if is_missing_secure_storage_error_message(&e.to_string()) => Ok(None),
+        Err(e) => Err(e.into()),
+    }
+}
+
+fn delete_raw(account: &str) -> anyhow::Result<()> {
+    let Some(entry) = entry(account)? else {
+        return Ok(());
+    };
+    match entry.delete_credential() {
+        Ok(()) => Ok(()),
+        Err(e) if is_missing_secure_storage_error_message(&e.to_string()) => Ok(()),
+        Err(e) => Err(e.into()),
+    }
+}
+
+fn read_index() -> anyhow::Result<BTreeSet<String>> {
+    Ok(read_raw(INDEX_ACCOUNT)?
+        .and_then(|raw| serde_json::from_str(&raw).ok())
+        .unwrap_or_default())
+}
+
+/// 在索引中加入或移除前缀账户。
+fn update_index(account: &str, present: bool) -> anyhow::Result<()> {
+    let _guard = INDEX_LOCK.lock().unwrap_or_else(|p| p.into_inner());
+    let mut index = read_index()?;
+    let changed = if present {
+        index.insert(account.to_string())
+    } else {
+        index.remove(account)
+    };
+    if !changed {
+        return Ok(());
+    }
+    if index.is_empty() {
+        return delete_raw(INDEX_ACCOUNT);
+    }
+    let entry = entry(INDEX_ACCOUNT)?
+        .ok_or_else(|| anyhow::anyhow!("secure storage is unavailable, cannot update index"))?;
+    entry.set_password(&serde_json::to_string(&index)?)?;
+    Ok(())
+}
+
+/// 需要擦除的账户：固定账户 + 索引中匹配已登记前缀的账户。
+fn accounts_to_wipe(index: &BTreeSet<String>) -> Vec<String> {
+    let mut out = Vec::new();
+    for account in REGISTRY {
+        match account {
+            SecretAccount::Fixed(name) => out.push(name.to_string()),
+            SecretAccount::Prefixed(prefix) => out.extend(
+                index
+                    .iter()
+                    .filter(|name| name.starts_with(prefix))
+                    .cloned(),
+            ),
+        }
+    }
+    out
+}
+
+/// 删除 [`REGISTRY`] 登记的全部账户及索引（安全擦除使用）。
+///
+/// # 返回值
+/// 删除失败的账户数（读取索引失败计为一次）。
+pub fn delete_registered() -> usize {
+    let _guard = INDEX_LOCK.lock().unwrap_or_else(|p| p.into_inner());
+    let (index, mut failures) = match read_index() {
+        Ok(index) => (index, 0),
+        Err(e) => {
+            tracing::warn!(action = "app_secret_index_read_failed", error = %e);
+            (BTreeSet::new(), 1)
+        }
+    };
+    for account in accounts_to_wipe(&index)
+        .iter()
+        .map(String::as_str)
+        .chain([INDEX_ACCOUNT])
+    {
+        if let Err(e) = delete_raw(account) {
+            tracing::warn!(action = "app_secret_delete_failed", account = %account, error = %e);
+            failures += 1;
+        }
+    }
+    failures
+}
+
+/// 读取机密。
+///
+/// # 返回值
+/// - `Ok(None)`：未保存或凭据存储不可用。
+pub fn get(account: &str) -> anyhow::Result<Option<String>> {
+    read_raw(account)
+}
+
+/// 保存机密（覆盖已有值）；前缀账户同时记入索引。
+pub fn set(account: &str, secret: &str) -> anyhow::Result<()> {
+    let entry = entry(account)?.ok_or_else(|| {
+        anyhow::anyhow!("secure storage is unavailable, cannot persist secret '{account}'")
+    })?;
+    entry.set_password(secret)?;
+    if is_indexed(account) {
+        update_index(account, true)?;
+    }
+    Ok(())
+}
+
+/// 删除机密（不存在时视为成功）；前缀账户同时移出索引。
+pub fn delete(account: &str) -> anyhow::Result<()> {
+    delete_raw(account)?;
+    if is_indexed(account) {
+        update_index(account, false)?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn wipe_covers_fixed_accounts_and_indexed_prefixes() {
+        let index: BTreeSet<String> = [
+            "session-tokens:tcp://a:7000".to_string(),
+            "unregistered:x".to_string(),
+        ]
+        .into_iter()
+        .collect();
+        let accounts = accounts_to_wipe(&index);
+        assert!(accounts.contains(&"app-lock-verifier".to_string()));
+        assert!(accounts.contains(&"session-tokens:tcp://a:7000".to_string()));
+        assert!(accounts.contains(&"channel-key-salt".to_string()));
+        assert!(!accounts.contains(&"unregistered:x".to_string()));
+        assert!(is_indexed("session-tokens:tcp://a:7000"));
+        assert!(!is_indexed("app-lock-verifier"));
+    }
+}
@@ -0,0 +1,221 @@
+//! shared｜安全擦除（一键清除本机全部数据后退出）。
+//!
+//! 说明：
+//! - 面向共用或可能被查扣的电脑：关闭所有数据库，删除凭据存储中登记的全部密钥
+//!   （`secrets::REGISTRY`），清空数据目录（DB、插件、日志、配置、缓存文件等）后退出应用；
+//! - 两步确认：先调用 `request_secure_wipe_token` 获取一次性令牌（60 秒有效），
+//!   再以该令牌调用 `secure_wipe`，避免误触或被单条调用触发；
+//! - SQLite 文件（含 -wal/-shm/-journal）删除前先以零覆盖；SSD / 写时复制文件系统上
+//!   覆盖不保证抹除物理数据，属尽力而为；
+//! - 使用自定义数据目录时，平台默认目录（含目录指针与迁移前的旧数据）一并清除；
+//!   WebView 本地数据目录同样清除（运行中被占用的文件可能无法删除）。
+//!
+//! 约定：注释中文，日志英文（tracing）。
+
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use tauri::{AppHandle, Manager};
+
+use crate::shared::error::{CommandResult, command_error, to_command_error};
+
+/// 确认令牌有效期。
+const TOKEN_TTL: Duration = Duration::from_secs(60);
+/// 覆盖写入的块大小。
+const OVERWRITE_CHUNK: usize = 1024 * 1024;
+
+static PENDING_TOKEN: Mutex<Option<(String, Instant)>> = Mutex::new(None);
+
+/// 校验并消费令牌（无论是否匹配，令牌都只能使用一次）。
+fn take_token(candidate: &str, now: Instant) -> bool {
+    let Some((token, issued_at)) = PENDING_TOKEN
+        .lock()
+        .unwrap_or_else(|p| p.into_inner())
+        .take()
+    else {
+        return false;
+    };
+    let fresh = now.saturating_duration_since(issued_at) <= TOKEN_TTL;
+    let matches = token.len() == candidate.len()
+        && token
+            .bytes()
+            .zip(candidate.bytes())
+            .fold(0u8, |acc, (a, b)| acc | (a ^ b))
+            == 0;
+    fresh && matches
+}
+
+fn is_sqlite_file(path: &Path) -> bool {
+    let Some(name) = path.file_name().and_then(|n| n.to_str()) else {
+        return false;
+    };
+    [".db", ".db-wal", ".db-shm", ".db-journal", ".sqlite"]
+        .iter()
+        .any(|suffix| name.ends_with(suffix))
+}
+
+/// 以零覆盖文件内容并落盘。
+fn overwrite_file(path: &Path) -> std::io::Result<()> {
+    let len = std::fs::metadata(path)?.len();
+    let mut file = std::fs::OpenOptions::new().write(true).open(path)?;
+    let zeros = vec![0u8; OVERWRITE_CHUNK];
+    let mut remaining = len;
+    while remaining > 0 {
+        let n = remaining.min(OVERWRITE_CHUNK as u64) as usize;
+        file.write_all(&zeros[..n])?;
+        remaining -= n as u64;
+    }
+    file.sync_all()
+}
+
+/// 覆盖 `dir` 下所有 SQLite 文件（递归）；返回失败数。
+fn overwrite_sqlite_files(dir: &Path) -> usize {
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return 0;
+    };
+    let mut failures = 0;
+    for entry in entries.flatten() {
+        let path = entry.path();
+        match entry.file_type() {
+            Ok(kind) if kind.is_dir() => failures += overwrite_sqlite_files(&path),
+            Ok(kind) if kind.is_file() && is_sqlite_file(&path) => {
+                if overwrite_file(&path).is_err() {
+                    failures += 1;
+                }
+            }
+            _ => {}
+        }
+    }
+    failures
+}
+
+/// 覆盖 SQLite 文件后删除 `root` 下的全部内容（保留 `root` 本身）；返回失败数。
+fn wipe_dir(root: &Path) -> usize {
+    let mut failures = overwrite_sqlite_files(root);
+    let Ok(entries) = std::fs::read_dir(root) else {
+        return failures;
+    };
+    for entry in entries.flatten() {
+        let path = entry.path();
+        let removed = match entry.file_type() {
+            Ok(kind) if kind.is_dir() => std::fs::remove_dir_all(&path),
+            _ => std::fs::remove_file(&path),
+        };
+        if removed.is_err() {
+            failures += 1;
+        }
+    }
+    failures
+}
+
+/// 获取安全擦除确认令牌（60 秒内有效，仅可使用一次）。
+#[tauri::command]
+pub fn request_secure_wipe_token() -> CommandResult<String> {
+    let mut bytes = [0u8; 16];
+    getrandom::fill(&mut bytes)
+        .map_err(|_| command_error("SECURE_WIPE_TOKEN_FAILED", "error.secure_wipe_token_failed"))?;
+    let token = hex::encode(bytes);
+    *PENDING_TOKEN.lock().unwrap_or_else(|p| p.into_inner()) =
+        Some((token.clone(), Instant::now()));
+    Ok(token)
+}
+
+/// 清除本机全部数据并退出应用。
+///
+/// # 参数
+/// - `confirm_token`：`request_secure_wipe_token` 返回的令牌。
+///
+/// # 说明
+/// 令牌校验通过后各步骤失败只计数、不中断，擦除完成后退出应用。
+#[tauri::command]
+pub async fn secure_wipe(app: AppHandle, confirm_token: String) -> CommandResult<()> {
+    if !take_token(&confirm_token, Instant::now()) {
+        return Err(command_error(
+            "SECURE_WIPE_INVALID_TOKEN",
+            "error.secure_wipe_invalid_token",
+        ));
+    }
+    let data_dir = crate::shared::app_data_dir::get_app_data_dir()
+        .map_err(|e| to_command_error("APP_DATA_DIR", "error.app_data_dir", e))?;
+    tracing::warn!(action = "app_secure_wipe_started");
+
+    let mut failures = 0usize;
+    for key in crate::shared::db::registered_keys().await {
+        if crate::shared::db::remove_db(&key).await.is_err() {
+            failures += 1;
+        }
+    }
+    if crate::shared::chat_cache::commands::shutdown_and_forget_key()
+        .await
+        .is_err()
+    {
+        failures += 1;
+    }
+    let secrets = tauri::async_runtime::spawn_blocking(crate::shared::secrets::delete_registered)
+        .await
+        .unwrap_or(1);
+    failures += secrets;
+    crate::features::settings::data::config_store::discard_cache().await;
+
+    let mut roots: Vec<PathBuf> = vec![data_dir];
+    // 平台默认数据目录与 WebView 本地数据（localStorage 等）目录。
+    for dir in [app.path().app_data_dir(), app.path().app_local_data_dir()]
+        .into_iter()
+        .flatten()
+    {
+        if !roots.contains(&dir) {
+            roots.push(dir);
+        }
+    }
+    failures += tauri::async_runtime::spawn_blocking(move || {
+        roots.iter().map(|root| wipe_dir(root)).sum::<usize>()
+    })
+    .await
+    .unwrap_or(1);
+
+    // 日志目录已被清除，这里只输出到仍打开的句柄（随进程退出释放）。
+    tracing::warn!(action = "app_secure_wipe_finished", failures);
+    app.exit(0);
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn token_is_single_use_and_expires() {
+        let token = request_secure_wipe_token().unwrap();
+        assert!(!take_token("wrong", Instant::now()));
+        // 错误尝试同样消费了令牌。
+        assert!(!take_token(&token, Instant::now()));
+
+        let token = request_secure_wipe_token().unwrap();
+        assert!(take_token(&token, Instant::now()));
+
+        let token = request_secure_wipe_token().unwrap();
+        let later = Instant::now() + TOKEN_TTL + Duration::from_secs(1);
+        assert!(!take_token(&token, later));
+    }
+
+    #[test]
+    fn wipe_overwrites_sqlite_files_and_clears_directory() {
+        let dir = tempfile::tempdir().unwrap();
+        let db_dir = dir.path().join("db");
+        std::fs::create_dir_all(&db_dir).unwrap();
+        let db = db_dir.join("system.db");
+        std::fs::write(&db, b"secret rows").unwrap();
+        std::fs::write(dir.path().join("config.json"), b"{}").unwrap();
+
+        overwrite_file(&db).unwrap();
+        assert_eq!(std::fs::read(&db).unwrap(), vec![0u8; 11]);
+
+        assert_eq!(wipe_dir(dir.path()), 0);
+        assert!(dir.path().exists());
+        assert_eq!(std::fs::read_dir(dir.path()).unwrap().count(), 0);
+        assert!(is_sqlite_file(Path::new("server.db-wal")));
+        assert!(!is_sqlite_file(Path::new("config.json")));
+    }
+}
@@ -0,0 +1,302 @@
+//! shared｜任务栏进度（Windows 任务栏按钮 / Unity 启动器 / macOS Dock 图标）。
+//!
+//! 说明：
+//! - 长耗时操作（增量同步、归档导出、备份与远程备份传输、插件安装）经 [`TaskbarProgress`]
+//!   登记进度；多个操作同时进行时合并显示：有失败显示错误、有暂停显示暂停，否则取各
+//!   确定进度的平均值，全部不确定时显示“忙碌”；
+//! - [`TaskbarProgress`] 在 drop 时自动清除：调用过 `finish` 视为成功，直接移除；否则
+//!   （`?` 提前返回、panic）视为失败，短暂显示错误状态后移除；
+//! - 前端驱动的操作经 `set_taskbar_progress(state, value)` 命令占用一个固定槽位；
+//! - 只作用于主窗口；移动端没有任务栏，合并结果不会应用。
+//!
+//! 约定：注释中文，日志英文（tracing）。
+
+use std::collections::BTreeMap;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::{Mutex, OnceLock};
+use std::time::Duration;
+
+use serde::Deserialize;
+use tauri::AppHandle;
+
+use crate::shared::error::CommandResult;
+
+/// 失败状态的展示时长。
+const ERROR_LINGER: Duration = Duration::from_secs(3);
+/// 前端驱动的操作使用的槽位 id（后端操作从 1 开始分配）。
+const FRONTEND_SLOT: u64 = 0;
+
+/// 任务栏进度状态（与系统任务栏的状态一一对应）。
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TaskbarState {
+    /// 无进度（清除）。
+    None,
+    /// 确定进度。
+    Normal,
+    /// 不确定进度（忙碌动画）。
+    Indeterminate,
+    Paused,
+    Error,
+}
+
+/// 单个操作的进度。
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct OperationProgress {
+    pub state: TaskbarState,
+    /// 0.0..=1.0；不确定进度时为 `None`。
+    pub value: Option<f64>,
+}
+
+/// 合并后的显示结果：状态与百分比（0..=100）。
+pub type CombinedProgress = (TaskbarState, Option<u64>);
+
+static APP: OnceLock<AppHandle> = OnceLock::new();
+static NEXT_ID: AtomicU64 = AtomicU64::new(FRONTEND_SLOT + 1);
+static OPERATIONS: Mutex<BTreeMap<u64, OperationProgress>> = Mutex::new(BTreeMap::new());
+/// 上次应用到窗口的结果（相同结果不重复设置）。
+static LAST_APPLIED: Mutex<Option<CombinedProgress>> = Mutex::new(None);
+
+/// 记录 AppHandle（setup 阶段调用一次）。
+pub fn init(app: &AppHandle) {
+    let _ = APP.set(app.clone());
+}
+
+/// 合并多个操作的进度。
+pub fn combine<'a>(
+    operations: impl IntoIterator<Item = &'a OperationProgress>,
+) -> CombinedProgress {
+    let mut any = false;
+    let mut error = false;
+    let mut paused = false;
+    let mut values = Vec::new();
+    for op in operations {
+        match op.state {
+            TaskbarState::None => continue,
+            TaskbarState::Error => error = true,
+            TaskbarState::Paused => paused = true,
+            TaskbarState::Normal | TaskbarState::Indeterminate => {}
+        }
+        any = true;
+        if op.state != TaskbarState::Indeterminate
+            && let Some(value) = op.value
+        {
+            values.push(value.clamp(0.0, 1.0));
+        }
+    }
+    if !any {
+        return (TaskbarState::None, None);
+    }
+    let percent = (!values.is_empty())
+        .then(|| (values.iter().sum::<f64>() / values.len() as f64 * 100.0).round() as u64);
+    let state = if error {
+        TaskbarState::Error
+    } else if paused {
+        TaskbarState::Paused
+    } else if percent.is_some() {
+        TaskbarState::Normal
+    } else {
+        TaskbarState::Indeterminate
+    };
+    (state, percent)
+}
+
+/// 更新（或以 `TaskbarState::None` 清除）某个槽位的进度，并刷新任务栏。
+fn set_slot(id: u64, progress: OperationProgress) {
+    let combined = {
+        let mut operations = OPERATIONS.lock().unwrap_or_else(|p| p.into_inner());
+        if progress.state == TaskbarState::None {
+            operations.remove(&id);
+        } else {
+            operations.insert(id, progress);
+        }
+        combine(operations.values())
+    };
+    apply(combined);
+}
+
+/// 把合并结果应用到主窗口（与上次相同时跳过）。
+fn apply(combined: CombinedProgress) {
+    {
+        let mut last = LAST_APPLIED.lock().unwrap_or_else(|p| p.into_inner());
+        if *last == Some(combined) {
+            return;
+        }
+        *last = Some(combined);
+    }
+    #[cfg(desktop)]
+    {
+        use tauri::Manager;
+        use tauri::window::{ProgressBarState, ProgressBarStatus};
+
+        let Some(window) = APP.get().and_then(|app| app.get_webview_window("main")) else {
+            return;
+        };
+        let (state, percent) = combined;
+        let status = match state {
+            TaskbarState::None => ProgressBarStatus::None,
+            TaskbarState::Normal => ProgressBarStatus::Normal,
+            TaskbarState::Indeterminate => ProgressBarStatus::Indeterminate,
+            TaskbarState::Paused => ProgressBarStatus::Paused,
+            TaskbarState::Error => ProgressBarStatus::Error,
+        };
+        if let Err(e) = window.set_progress_bar(ProgressBarState {
+            status: Some(status),
+            progress: percent,
+        }) {
+            tracing::debug!(action = "windows_taskbar_progress_apply_failed", error = %e);
+        }
+    }
+}
+
+/// 一个长耗时操作在任务栏上的进度（drop 时自动清除）。
+pub struct TaskbarProgress {
+    id: u64,
+    operation: &'static str,
+    finished: AtomicBool,
+}
+
+impl TaskbarProgress {
+    /// 开始一个操作（初始为不确定进度）。
+    pub fn start(operation: &'static str) -> Self {
+        let id = NEXT_ID.fetch_add(1, Ordering::Relaxed);
+        tracing::debug!(action = "windows_taskbar_progress_started", operation, id);
+        set_slot(
+            id,
+            OperationProgress {
+                state: TaskbarState::Indeterminate,
+                value: None,
+            },
+        );
+        Self {
+            id,
+            operation,
+            finished: AtomicBool::new(false),
+        }
+    }
+
+    /// 更新确定进度（`done / total`；`total` 为 0 时视为不确定）。
+    pub fn set(&self, done: u64, total: u64) {
+        let progress = if total == 0 {
+            OperationProgress {
+                state: TaskbarState::Indeterminate,
+                value: None,
+            }
+        } else {
+            OperationProgress {
+                state: TaskbarState::Normal,
+                value: Some(done as f64 / total as f64),
+            }
+        };
+        set_slot(self.id, progress);
+    }
+
+    /// 标记操作成功完成（drop 时直接清除，不显示错误）。
+    pub fn finish(&self) {
+        self.finished.store(true, Ordering::Relaxed);
+    }
+}
+
+impl Drop for TaskbarProgress {
+    fn drop(&mut self) {
+        let id = self.id;
+        let clear = OperationProgress {
+            state: TaskbarState::None,
+            value: None,
+        };
+        if self.finished.load(Ordering::Relaxed) {
+            set_slot(id, clear);
+            return;
+        }
+        tracing::debug!(
+            action = "windows_taskbar_progress_failed",
+            operation = self.operation,
+            id
+        );
+        set_slot(
+            id,
+            OperationProgress {
+                state: TaskbarState::Error,
+                value: None,
+            },
+        );
+        tauri::async_runtime::spawn(async move {
+            tokio::time::sleep(ERROR_LINGER).await;
+            set_slot(id, clear);
+        });
+    }
+}
+
+/// 设置前端驱动操作的任务栏进度。
+///
+/// # 参数
+/// - `state`：`none`（清除）/ `normal` / `indeterminate` / `paused` / `error`。
+/// - `value`：进度 0.0..=1.0（`normal` / `paused` / `error` 时有效）。
+///
+/// # 说明
+/// 与后端操作的进度合并显示；前端操作结束或失败时应以 `none` 清除。
+#[tauri::command]
+pub async fn set_taskbar_progress(state: TaskbarState, value: Option<f64>) -> CommandResult<()> {
+    set_slot(
+        FRONTEND_SLOT,
+        OperationProgress {
+            state,
+            value: value.filter(|v| v.is_finite()),
+        },
+    );
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn op(state: TaskbarState, value: Option<f64>) -> OperationProgress {
+        OperationProgress { state, value }
+    }
+
+    #[test]
+    fn combine_averages_determinate_operations() {
+        assert_eq!(combine(&[]), (TaskbarState::None, None));
+        assert_eq!(
+            combine(&[op(TaskbarState::None, Some(0.5))]),
+            (TaskbarState::None, None)
+        );
+        assert_eq!(
+            combine(&[op(TaskbarState::Indeterminate, None)]),
+            (TaskbarState::Indeterminate, None)
+        );
+        assert_eq!(
+            combine(&[
+                op(TaskbarState::Normal, Some(0.2)),
+                op(TaskbarState::Normal, Some(0.6)),
+                op(TaskbarState::Indeterminate, Some(0.9)),
+            ]),
+            (TaskbarState::Normal, Some(40))
+        );
+        // 越界值被截断。
+        assert_eq!(
+            combine(&[op(TaskbarState::Normal, Some(7.0))]),
+            (TaskbarState::Normal, Some(100))
+        );
+    }
+
+    #[test]
+    fn error_and_paused_take_priority() {
+        assert_eq!(
+            combine(&[
+                op(TaskbarState::Normal, Some(0.5)),
+                op(TaskbarState::Paused, Some(0.5)),
+            ]),
+            (TaskbarState::Paused, Some(50))
+        );
+        assert_eq!(
+            combine(&[
+                op(TaskbarState::Paused, None),
+                op(TaskbarState::Error, None),
+                op(TaskbarState::Indeterminate, None),
+            ]),
+            (TaskbarState::Error, None)
+        );
+    }
+}
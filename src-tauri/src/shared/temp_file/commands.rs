@@ -1,5 +1,6 @@
 //! temp_file｜Tauri 命令
 
+use tauri::ipc::Response;
 use tauri::{AppHandle, State};
 use tauri_plugin_opener::OpenerExt;
 
@@ -80,3 +81,25 @@ pub async fn open_temp_file(
         .map_err(|e| to_command_error("TEMP_FILE_OPEN_FAILED", "error.temp_file_open_failed", e))?;
     Ok(())
 }
+
+/// 按区间读取附件（已完成的临时文件）字节，供前端分段流式读取大文件。
+///
+/// 以原始二进制响应返回（前端收到 `ArrayBuffer`），不把 `Vec<u8>` 序列化为 JSON 数组；
+/// 单次最多返回 [`super::manager::MAX_READ_RANGE_BYTES`] 字节，返回长度小于 `len` 表示已到末尾。
+#[tauri::command]
+pub async fn read_attachment_range(
+    temp_files: State<'_, TempFileManager>,
+    file_id: String,
+    offset: u64,
+    len: u64,
+) -> CommandResult<Response> {
+    temp_files
+        .get_metadata(&file_id)
+        .await
+        .map_err(|e| to_command_error("TEMP_FILE_NOT_FOUND", "error.temp_file_not_found", e))?;
+    let bytes = temp_files
+        .read_range(&file_id, offset, len)
+        .await
+        .map_err(|e| to_command_error("TEMP_FILE_READ_FAILED", "error.temp_file_read_failed", e))?;
+    Ok(Response::new(bytes))
+}
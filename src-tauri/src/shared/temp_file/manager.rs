@@ -5,7 +5,7 @@ use std::time::{SystemTime, UNIX_EPOCH};
 
 use anyhow::Context;
 use sea_orm::{ConnectionTrait, DatabaseBackend, QueryResult, Statement, StatementBuilder, Value};
-use tokio::io::AsyncSeekExt;
+use tokio::io::{AsyncReadExt, AsyncSeekExt};
 use tracing::warn;
 
 use crate::shared::db::{CPDatabase, sqlite_url_for_path};
@@ -39,6 +39,9 @@ impl StatementBuilder for RawStmt {
     }
 }
 
+/// 单次区间读取的字节上限（`read_range` 超出部分截断）。
+pub const MAX_READ_RANGE_BYTES: u64 = 4 * 1024 * 1024;
+
 /// 临时文件管理器：管理临时文件的下载写入、元数据追踪与清理。
 pub struct TempFileManager {
     base_dir: PathBuf,
@@ -342,6 +345,28 @@ impl TempFileManager {
         Ok(dest.to_string_lossy().to_string())
     }
 
+    /// 读取已完成文件的字节区间。
+    ///
+    /// `len` 超过 [`MAX_READ_RANGE_BYTES`] 时截断；返回长度小于请求长度表示已到文件末尾。
+    pub async fn read_range(&self, id: &str, offset: u64, len: u64) -> anyhow::Result<Vec<u8>> {
+        let meta = self.get_metadata(id).await?;
+        if meta.state != "complete" {
+            anyhow::bail!(
+                "Temp file '{}' is not in 'complete' state (state={})",
+                id,
+                meta.state
+            );
+        }
+        let mut file = tokio::fs::File::open(&meta.file_path)
+            .await
+            .with_context(|| format!("Failed to open temp file: {}", meta.file_path))?;
+        file.seek(std::io::SeekFrom::Start(offset)).await?;
+        let len = len.min(MAX_READ_RANGE_BYTES);
+        let mut buf = Vec::with_capacity(len as usize);
+        file.take(len).read_to_end(&mut buf).await?;
+        Ok(buf)
+    }
+
     pub fn base_dir(&self) -> &Path {
         &self.base_dir
     }
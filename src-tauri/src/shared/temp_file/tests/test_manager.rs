@@ -215,3 +215,33 @@ async fn test_prune_incomplete_downloads_removes_part_files() {
     assert!(manager.get_metadata("prune-1").await.is_err());
     assert!(manager.get_metadata("prune-2").await.is_err());
 }
+
+#[tokio::test]
+async fn test_read_range_of_completed_file() {
+    let (manager, _dir) = create_test_manager().await;
+
+    let (mut file, _existing) = manager
+        .create_download("test-range", "https://example.com/range.bin", None, 10)
+        .await
+        .unwrap();
+    file.write_all(b"0123456789").await.unwrap();
+    drop(file);
+    assert!(manager.read_range("test-range", 0, 4).await.is_err());
+
+    manager.mark_complete("test-range", "bin").await.unwrap();
+    assert_eq!(
+        manager.read_range("test-range", 2, 3).await.unwrap(),
+        b"234"
+    );
+    assert_eq!(
+        manager.read_range("test-range", 8, 100).await.unwrap(),
+        b"89"
+    );
+    assert!(
+        manager
+            .read_range("test-range", 20, 4)
+            .await
+            .unwrap()
+            .is_empty()
+    );
+}
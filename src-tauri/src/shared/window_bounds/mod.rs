@@ -1,7 +1,8 @@
-//! 主窗口位置/尺寸记忆模块。
+//! 窗口位置/尺寸记忆模块。
 //!
 //! 在 Tauri `setup()` 阶段调用 `load()` 恢复上次的窗口 bounds，
 //! 在 `on_window_event` 监听 `Resized`/`Moved` 时调用 `save_async()` 持久化。
+//! 主窗口以外的窗口（如迷你聊天窗）使用 `*_named` 版本，各自独立的文件互不覆盖。
 //!
 //! 约定：注释中文，日志英文（tracing）。
 
@@ -24,18 +25,26 @@ pub struct WindowBounds {
     pub y: i32,
 }
 
-/// 解析后的窗口 bounds 文件路径（位于 `app_data_dir/window-bounds.json`）。
-fn bounds_file_path() -> Option<PathBuf> {
+/// 主窗口 bounds 文件名。
+const MAIN_BOUNDS_FILE: &str = "window-bounds.json";
+
+/// 解析后的窗口 bounds 文件路径（位于 `app_data_dir/{file_name}`）。
+fn bounds_file_path(file_name: &str) -> Option<PathBuf> {
     app_data_dir::get_app_data_dir()
         .ok()
-        .map(|dir| dir.join("window-bounds.json"))
+        .map(|dir| dir.join(file_name))
 }
 
-/// 从磁盘读取上次的窗口 bounds。
+/// 从磁盘读取上次的主窗口 bounds。
 ///
 /// 文件不存在或解析失败时返回 `None`。
 pub fn load() -> Option<WindowBounds> {
-    let path = bounds_file_path()?;
+    load_named(MAIN_BOUNDS_FILE)
+}
+
+/// 从 `app_data_dir/{file_name}` 读取窗口 bounds。
+pub fn load_named(file_name: &str) -> Option<WindowBounds> {
+    let path = bounds_file_path(file_name)?;
     let raw = std::fs::read_to_string(&path).ok()?;
     match serde_json::from_str::<WindowBounds>(&raw) {
         Ok(b) => {
@@ -60,9 +69,9 @@ pub fn load() -> Option<WindowBounds> {
     }
 }
 
-/// 同步写窗口 bounds 到磁盘（直接写，失败仅记录日志）。
+/// 同步写主窗口 bounds 到磁盘（直接写，失败仅记录日志）。
 pub fn save(bounds: WindowBounds) {
-    let Some(path) = bounds_file_path() else {
+    let Some(path) = bounds_file_path(MAIN_BOUNDS_FILE) else {
         tracing::warn!(action = "windows_bounds_save_no_data_dir");
         return;
     };
@@ -97,9 +106,14 @@ pub fn save(bounds: WindowBounds) {
     }
 }
 
-/// 异步写窗口 bounds（推荐在 on_window_event 中调用，避免阻塞主循环）。
+/// 异步写主窗口 bounds（推荐在 on_window_event 中调用，避免阻塞主循环）。
 pub fn save_async(bounds: WindowBounds) {
-    let Some(path) = bounds_file_path() else {
+    save_named_async(MAIN_BOUNDS_FILE, bounds);
+}
+
+/// 异步写窗口 bounds 到 `app_data_dir/{file_name}`。
+pub fn save_named_async(file_name: &str, bounds: WindowBounds) {
+    let Some(path) = bounds_file_path(file_name) else {
         return;
     };
     if let Some(parent) = path.parent() {
@@ -128,6 +142,56 @@ pub fn save_async(bounds: WindowBounds) {
     });
 }
 
+/// 读取窗口当前外框 bounds（逻辑像素）；窗口不可见时返回 `None`（避免记录隐藏/最小化位置）。
+pub fn from_window<R: tauri::Runtime>(window: &tauri::Window<R>) -> Option<WindowBounds> {
+    if !window.is_visible().unwrap_or(false) {
+        return None;
+    }
+    let size = window.outer_size().ok()?;
+    let pos = window.outer_position().ok()?;
+    let scale = window.scale_factor().unwrap_or(1.0);
+    let width = (f64::from(size.width) / scale).round().max(1.0) as u32;
+    let height = (f64::from(size.height) / scale).round().max(1.0) as u32;
+    let x = (f64::from(pos.x) / scale).round() as i32;
+    let y = (f64::from(pos.y) / scale).round() as i32;
+    Some(WindowBounds {
+        width,
+        height,
+        x,
+        y,
+    })
+}
+
+/// 校验 bounds 是否落在任意可用显示器的工作区内（防止越界）。
+///
+/// 允许较小偏差（窗口标题栏等可能略出屏）。
+pub fn is_within_monitors<R: tauri::Runtime>(app: &tauri::AppHandle<R>, b: &WindowBounds) -> bool {
+    let Ok(monitors) = app.available_monitors() else {
+        // 拿不到显示器列表时不做校验，默认通过。
+        return true;
+    };
+    if monitors.is_empty() {
+        return true;
+    }
+    for m in monitors {
+        let mpos = m.position();
+        let msize = m.size();
+        let mx0 = mpos.x;
+        let my0 = mpos.y;
+        let mx1 = mpos.x + msize.width as i32;
+        let my1 = mpos.y + msize.height as i32;
+        let wx1 = b.x + b.width as i32;
+        let wy1 = b.y + b.height as i32;
+        // 至少 64x64 像素落在某显示器工作区内。
+        let overlap_w = (wx1.min(mx1) - b.x.max(mx0)).max(0);
+        let overlap_h = (wy1.min(my1) - b.y.max(my0)).max(0);
+        if overlap_w >= 64 && overlap_h >= 64 {
+            return true;
+        }
+    }
+    false
+}
+
 /// 原子写：先写临时文件再 rename，避免半写入状态。
 fn write_atomic(path: &Path, bytes: &[u8]) -> std::io::Result<()> {
     use std::io::Write;
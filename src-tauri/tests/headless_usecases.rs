@@ -0,0 +1,152 @@
+//! 无界面用例集成测试：不创建 Tauri 应用，事件经 `RecordingEventBus` 断言。
+//!
+//! 约定：注释中文，日志英文（tracing）。
+
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use carrypigeon_desktop_lib::features::calls::domain::{
+    CallDirection, CallRegistry, parse_call_signal,
+};
+use carrypigeon_desktop_lib::features::network::di::prefetch::{
+    PREFETCH_READY_EVENT, PrefetchRuntime,
+};
+use carrypigeon_desktop_lib::features::network::di::presence::{
+    PRESENCE_BATCH_EVENT, PRESENCE_CHANGED_EVENT, PresenceRuntime,
+};
+use carrypigeon_desktop_lib::features::network::di::typing::{
+    TYPING_STATE_CHANGED_EVENT, TypingRuntime,
+};
+use carrypigeon_desktop_lib::features::network::domain::prefetch_scheduler::PrefetchPriority;
+use carrypigeon_desktop_lib::features::network::domain::presence::{
+    PresenceEntry, SUBSCRIBE_ROUTE,
+};
+use carrypigeon_desktop_lib::features::settings::data::config_store_port_adapter::ConfigStorePortAdapter;
+use carrypigeon_desktop_lib::features::voice_call::domain::model::CallState;
+use carrypigeon_desktop_lib::shared::events::RecordingEventBus;
+use serde_json::json;
+
+const SERVER: &str = "tls://chat.example:9000";
+
+#[test]
+fn typing_runtime_throttles_outbound_and_broadcasts_inbound_changes() {
+    let bus = RecordingEventBus::new();
+    let typing = TypingRuntime::new(bus.clone());
+
+    assert!(typing.should_send(SERVER, "c1"));
+    assert!(!typing.should_send(SERVER, "c1"));
+    typing.reset_outbound(SERVER, "c1");
+    assert!(typing.should_send(SERVER, "c1"));
+
+    typing.observe(SERVER, "c1", "u2", true);
+    typing.observe(SERVER, "c1", "u1", true);
+    // 重复推送只延长有效期，不再广播。
+    typing.observe(SERVER, "c1", "u1", true);
+    assert_eq!(typing.users(SERVER, "c1"), vec!["u1", "u2"]);
+
+    typing.clear_server(SERVER);
+    assert!(typing.users(SERVER, "c1").is_empty());
+    assert_eq!(
+        bus.payloads(TYPING_STATE_CHANGED_EVENT),
+        vec![
+            json!({ "serverSocket": SERVER, "channelId": "c1", "userIds": ["u2"] }),
+            json!({ "serverSocket": SERVER, "channelId": "c1", "userIds": ["u1", "u2"] }),
+            json!({ "serverSocket": SERVER, "channelId": "c1", "userIds": [] }),
+        ]
+    );
+}
+
+#[test]
+fn presence_runtime_batches_subscriptions_to_main_window_and_reports_changes() {
+    let bus = RecordingEventBus::new();
+    let presence = PresenceRuntime::new(bus.clone());
+
+    presence.set_watch(SERVER, "members:c1", &["u1".to_string(), "u2".to_string()]);
+    presence.set_watch(SERVER, "contacts", &["u2".to_string()]);
+    assert!(bus.take().is_empty());
+
+    presence.tick();
+    let batches = bus.take();
+    assert_eq!(batches.len(), 1);
+    assert_eq!(batches[0].event, PRESENCE_BATCH_EVENT);
+    assert_eq!(batches[0].target.as_deref(), Some("main"));
+    assert_eq!(
+        batches[0].payload,
+        json!({ "serverSocket": SERVER, "route": SUBSCRIBE_ROUTE, "userIds": ["u1", "u2"] })
+    );
+
+    let online = PresenceEntry {
+        user_id: "u1".to_string(),
+        status: "online".to_string(),
+        last_seen: None,
+    };
+    presence.observe(SERVER, vec![online.clone()]);
+    // 状态未变化时不广播。
+    presence.observe(SERVER, vec![online.clone()]);
+    assert_eq!(bus.payloads(PRESENCE_CHANGED_EVENT).len(), 1);
+    assert_eq!(
+        presence.get(SERVER, &["u1".to_string(), "u3".to_string()]),
+        vec![online]
+    );
+}
+
+#[test]
+fn prefetch_runtime_issues_focused_ticket_first_and_refills_on_complete() {
+    let bus = RecordingEventBus::new();
+    let prefetch = PrefetchRuntime::new(bus.clone());
+
+    prefetch.set_active(SERVER, Some("focused"));
+    let issued = bus.payloads(PREFETCH_READY_EVENT);
+    assert_eq!(issued.len(), 1);
+    assert_eq!(issued[0]["channel_id"], "focused");
+
+    prefetch.enqueue(SERVER, &["warm".to_string()], PrefetchPriority::Warm);
+    let issued = bus.payloads(PREFETCH_READY_EVENT);
+    assert_eq!(issued.len(), 2);
+    assert_eq!(issued[1]["channel_id"], "warm");
+
+    let first = issued[0]["ticket"].as_u64().unwrap();
+    assert!(prefetch.complete(first));
+    assert!(!prefetch.complete(first));
+
+    assert_eq!(prefetch.resync(SERVER).as_deref(), Some("focused"));
+    assert_eq!(prefetch.resync("tls://other:1"), None);
+}
+
+#[test]
+fn call_signals_drive_registry_without_tauri() {
+    let registry = CallRegistry::default();
+    let invite = parse_call_signal(
+        "call.invite",
+        &json!({ "callId": "call-1", "peerId": "42", "media": "video" }),
+    )
+    .unwrap();
+    let t = registry
+        .apply(SERVER, CallDirection::Inbound, &invite, 100)
+        .unwrap();
+    assert_eq!(t.record.state, CallState::Ringing);
+    assert_eq!(registry.active().len(), 1);
+
+    let reject = parse_call_signal(
+        "call.reject",
+        &json!({ "call_id": "call-1", "reason": "busy" }),
+    )
+    .unwrap();
+    let t = registry
+        .apply(SERVER, CallDirection::Inbound, &reject, 200)
+        .unwrap();
+    assert_eq!(t.record.state, CallState::Rejected);
+    assert_eq!(t.record.end_reason.as_deref(), Some("busy"));
+    assert!(registry.active().is_empty());
+}
+
+#[test]
+fn settings_adapter_syncs_close_to_tray_cache_without_app_handle() {
+    let cache = Arc::new(AtomicBool::new(true));
+    ConfigStorePortAdapter::init_close_to_tray_cache(cache.clone());
+
+    ConfigStorePortAdapter::notify_close_to_tray_changed(false);
+    assert!(!cache.load(Ordering::SeqCst));
+    ConfigStorePortAdapter::notify_close_to_tray_changed(true);
+    assert!(cache.load(Ordering::SeqCst));
+}
@@ -0,0 +1,39 @@
+/**
+ * @fileoverview activityReporter.ts
+ * @description 应用启动编排：向 Rust 上报用户活动（供空闲自动锁定使用）。
+ *
+ * 说明：键鼠事件按 `REPORT_INTERVAL_MS` 节流上报，避免频繁 IPC。
+ */
+
+import { invokeTauri, TAURI_COMMANDS } from "@/shared/tauri";
+
+const REPORT_INTERVAL_MS = 30_000;
+const ACTIVITY_EVENTS = ["pointerdown", "pointermove", "keydown", "wheel"] as const;
+
+let lastReportAt = 0;
+
+function onActivity(): void {
+  const now = Date.now();
+  if (now - lastReportAt < REPORT_INTERVAL_MS) return;
+  lastReportAt = now;
+  void invokeTauri<void>(TAURI_COMMANDS.reportUserActivity).catch(() => {
+    // 上报失败不影响使用；下次活动会再次尝试。
+    lastReportAt = 0;
+  });
+}
+
+/**
+ * 开始监听当前窗口的用户活动。
+ *
+ * @returns 停止监听函数。
+ */
+export function startActivityReporter(): () => void {
+  for (const name of ACTIVITY_EVENTS) {
+    window.addEventListener(name, onActivity, { passive: true, capture: true });
+  }
+  return () => {
+    for (const name of ACTIVITY_EVENTS) {
+      window.removeEventListener(name, onActivity, { capture: true });
+    }
+  };
+}
@@ -0,0 +1,30 @@
+/**
+ * @fileoverview sessionTokenBridge.ts
+ * @description 应用启动编排：同步 Rust 侧的会话令牌变化。
+ *
+ * 说明：
+ * - `api_request_json` 遇到 401 时由 Rust 自动刷新令牌，刷新后通过 `session-refreshed` 回写本地 session；
+ * - refresh token 被拒绝时 Rust 广播 `session-expired`，这里清空本地 session 并通知订阅者。
+ */
+
+import { applySessionFromRust } from "@/shared/net/auth/api";
+import { listenSessionExpired, listenSessionRefreshed } from "@/shared/tauri";
+
+/**
+ * 注册会话令牌同步监听。
+ *
+ * @returns 取消监听函数。
+ */
+export function registerSessionTokenBridge(): () => void {
+  const unlisteners = [
+    listenSessionRefreshed((event) => {
+      applySessionFromRust(event.payload.serverSocket, event.payload.tokens);
+    }),
+    listenSessionExpired((event) => {
+      applySessionFromRust(event.payload.serverSocket, null);
+    }),
+  ];
+  return () => {
+    for (const p of unlisteners) void p.then((unlisten) => unlisten());
+  };
+}
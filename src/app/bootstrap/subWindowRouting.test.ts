@@ -0,0 +1,34 @@
+/**
+ * @fileoverview subWindowRouting 单元测试
+ * @description 验证辅助窗口按 `?window=` 路由，且未知类型回落为主窗口流程。
+ */
+
+import { describe, expect, it, vi } from "vitest";
+import type { Router } from "vue-router";
+import { routeIfSubWindow } from "./subWindowRouting";
+
+function createRouter(): { router: Router; replace: ReturnType<typeof vi.fn> } {
+  const replace = vi.fn().mockResolvedValue(undefined);
+  return { router: { replace } as unknown as Router, replace };
+}
+
+describe("routeIfSubWindow", () => {
+  it("routes the mini chat window to its own view with the target conversation", () => {
+    const { router, replace } = createRouter();
+    const params = new URLSearchParams("window=mini-chat&server_socket=tls%3A%2F%2Fchat.example.com%3A8443&channel_id=42");
+
+    expect(routeIfSubWindow(router, params)).toBe(true);
+    expect(replace).toHaveBeenCalledWith({
+      path: "/mini-chat",
+      query: { serverSocket: "tls://chat.example.com:8443", channelId: "42" },
+    });
+  });
+
+  it("falls back to the main window flow without a window type or for unknown ones", () => {
+    const { router, replace } = createRouter();
+
+    expect(routeIfSubWindow(router, new URLSearchParams(""))).toBe(false);
+    expect(routeIfSubWindow(router, new URLSearchParams("window=unknown"))).toBe(false);
+    expect(replace).not.toHaveBeenCalled();
+  });
+});
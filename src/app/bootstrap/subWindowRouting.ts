@@ -68,6 +68,13 @@ export function routeIfSubWindow(router: Router, searchParams: URLSearchParams):
       });
     case "screenshot-overlay":
       return replaceSubWindowRoute(router, "/screenshot-overlay", {});
+    case "app-lock":
+      return replaceSubWindowRoute(router, "/app-lock", {});
+    case "mini-chat":
+      return replaceSubWindowRoute(router, "/mini-chat", {
+        serverSocket: searchParams.get("server_socket") ?? "",
+        channelId: searchParams.get("channel_id") ?? "",
+      });
     default:
       // 未知 window 类型应回落为主窗口流程，避免误判为子窗口导致 bootstrap 缺失。
       return false;
@@ -23,27 +23,39 @@ import { decideNotification, sendDesktopNotification, getChatCapabilities } from
 import { getNotificationCapabilities } from "@/features/notifications/api";
 import { playNotificationSound } from "@/shared/utils/notificationSound";
 import type { ChatMessage } from "@/features/chat/public/api-types";
+import { i18n } from "@/app/i18n";
 
 const logger = createLogger("trayIntegration");
 
+/**
+ * 读取隐私模式开关（设置 `privacy_mode`）；读取失败时按关闭处理。
+ */
+async function isPrivacyModeEnabled(): Promise<boolean> {
+  try {
+    return await invokeTauri<boolean>(TAURI_COMMANDS.settingsGetConfigBool, { key: "privacy_mode" });
+  } catch {
+    return false;
+  }
+}
+
 // ============ 托盘未读闪烁 ============
 
 /**
- * 注册主窗口托盘未读状态同步。
+ * 注册主窗口托盘未读状态同步（同时更新 Dock / 任务栏未读角标）。
  */
 export function registerTrayUnreadBridge(): WatchStopHandle | null {
   if (!isTauriRuntimeAvailable() || IS_MOCK_ENABLED) return null;
 
-  let lastState: boolean | null = null;
+  let lastCount: number | null = null;
   let innerStop: WatchStopHandle | null = null;
 
   const chat = getChatCapabilities();
   innerStop = watch(
-    () => chat.session.directory.totalUnreadCount.value > 0,
-    (hasUnread) => {
-      if (hasUnread === lastState) return;
-      lastState = hasUnread;
-      void setTrayUnreadFlashing(hasUnread);
+    () => chat.session.directory.totalUnreadCount.value,
+    (unreadCount) => {
+      if (unreadCount === lastCount) return;
+      lastCount = unreadCount;
+      void setTrayUnreadFlashing(unreadCount > 0, unreadCount);
     },
     { immediate: true },
   );
@@ -61,9 +73,9 @@ export function clearTrayUnreadFlashing(): void {
   void setTrayUnreadFlashing(false);
 }
 
-async function setTrayUnreadFlashing(hasUnread: boolean): Promise<void> {
+async function setTrayUnreadFlashing(hasUnread: boolean, unreadCount?: number): Promise<void> {
   try {
-    await invokeTauri<void>(TAURI_COMMANDS.setTrayUnreadFlashing, { hasUnread });
+    await invokeTauri<void>(TAURI_COMMANDS.setTrayUnreadFlashing, { hasUnread, unreadCount });
   } catch (error) {
     logger.warn("Action: chat_tray_unread_state_sync_failed", { error: String(error), hasUnread });
   }
@@ -85,8 +97,13 @@ export function registerTrayHoverBridge(): (() => void) | null {
     const chat = getChatCapabilities();
 
     const unlistenHover = await safeListen<{ x: number; y: number }>("tray-hover-settled", async (event) => {
-      const previews = chat.getUnreadMessagePreviews(4);
+      let previews = chat.getUnreadMessagePreviews(4);
       if (previews.length === 0) return;
+      // 隐私模式：托盘预览只保留频道，不显示内容。
+      if (await isPrivacyModeEnabled()) {
+        const text = i18n.global.t("privacy_tray_preview");
+        previews = previews.map((p) => ({ ...p, senderName: p.channelName, textPreview: text }));
+      }
 
       const data = encodeURIComponent(JSON.stringify(previews));
       const pos = event.payload;
@@ -205,8 +222,12 @@ export function createNotificationOnNewMessageHandler(deps: {
       const channelName = deps.getChannelName(channelId) || channelId;
       const senderName = message.from?.name ?? "Unknown";
       const previewText = message.kind === "core_text" ? message.text : ("preview" in message ? message.preview : "");
-      const title = `${senderName} · #${channelName}`;
-      const body = previewText.length > 100 ? previewText.slice(0, 100) + "..." : previewText;
+      // 隐私模式：通知不携带发送者与消息内容。
+      const privacy = await isPrivacyModeEnabled();
+      const title = privacy
+        ? i18n.global.t("privacy_notification_title", { channel: channelName })
+        : `${senderName} · #${channelName}`;
+      const body = privacy ? "" : previewText.length > 100 ? previewText.slice(0, 100) + "..." : previewText;
 
       await sendDesktopNotification({ title, body, channelId, messageId: message.id });
 
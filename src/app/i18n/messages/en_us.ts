@@ -249,6 +249,8 @@ export const en_us = {
   resend_in: "Resend ({n})",
   sign_in_register: "Sign In / Register",
   signing_in: "Signing in…",
+  sign_in_with_browser: "Sign in with browser",
+  oauth_waiting_browser: "Complete sign-in in your browser…",
   stage_handshake: "Handshake",
   stage_auth: "Auth",
   rack_overview: "Rack Overview",
@@ -272,6 +274,14 @@ export const en_us = {
   jump_to_bottom: "Jump to bottom",
   more_actions: "More actions",
   reply: "Reply",
+  server_emojis: "Server",
+  server_emojis_empty: "This server has no custom emojis",
+  presence_online: "Online",
+  presence_idle: "Idle",
+  presence_offline: "Offline",
+  typing_one: "{name} is typing…",
+  typing_two: "{first} and {second} are typing…",
+  typing_many: "{count} people are typing…",
   composer_type: "Type",
   composer_message: "Message",
   plugin_composer_placeholder: "This message type uses a plugin composer",
@@ -815,4 +825,14 @@ export const en_us = {
   pin_members_rail: "Pin Members",
   unpin_members_rail: "Unpin Members",
   server_info_no_brief: "No server brief",
+  app_lock_title: "Locked",
+  app_lock_passphrase_placeholder: "Enter passphrase",
+  app_lock_unlock: "Unlock",
+  app_lock_use_biometric: "Use biometrics",
+  mini_chat_empty: "No messages yet",
+  mini_chat_placeholder: "Send a message",
+  mini_chat_load_failed: "Failed to load messages",
+  mini_chat_send_failed: "Failed to send",
+  privacy_notification_title: "New message in #{channel}",
+  privacy_tray_preview: "New messages",
 };
@@ -251,6 +251,8 @@ export const zh_cn = {
   resend_in: "重新发送 ({n})",
   sign_in_register: "登录 / 注册",
   signing_in: "登录中…",
+  sign_in_with_browser: "通过浏览器登录",
+  oauth_waiting_browser: "请在浏览器中完成登录…",
   stage_handshake: "握手",
   stage_auth: "认证",
   rack_overview: "机架概览",
@@ -274,6 +276,14 @@ export const zh_cn = {
   jump_to_bottom: "跳到底部",
   more_actions: "更多操作",
   reply: "回复",
+  server_emojis: "服务器",
+  server_emojis_empty: "该服务器没有自定义表情",
+  presence_online: "在线",
+  presence_idle: "离开",
+  presence_offline: "离线",
+  typing_one: "{name} 正在输入…",
+  typing_two: "{first} 和 {second} 正在输入…",
+  typing_many: "{count} 人正在输入…",
   composer_type: "类型",
   composer_message: "消息",
   plugin_composer_placeholder: "此消息类型使用插件编辑器",
@@ -817,4 +827,14 @@ export const zh_cn = {
   pin_members_rail: "固定成员栏",
   unpin_members_rail: "收起成员栏",
   server_info_no_brief: "暂无服务器简介",
+  app_lock_title: "已锁定",
+  app_lock_passphrase_placeholder: "输入口令",
+  app_lock_unlock: "解锁",
+  app_lock_use_biometric: "使用生物识别",
+  mini_chat_empty: "暂无消息",
+  mini_chat_placeholder: "发送消息",
+  mini_chat_load_failed: "加载消息失败",
+  mini_chat_send_failed: "发送失败",
+  privacy_notification_title: "#{channel} 有新消息",
+  privacy_tray_preview: "有新消息",
 };
@@ -1,6 +1,6 @@
 import { createRouter, createWebHistory } from 'vue-router';
 import { LoginPage, RegisterPage, RequiredSetupPage, UserInfoPage } from '@/features/account/routes';
-import { ChatMainPage, ChannelInfoPage, ChannelMembersPage, JoinApplicationsPage, ChannelBansPage } from '@/features/chat/public/routes';
+import { ChatMainPage, ChannelInfoPage, ChannelMembersPage, JoinApplicationsPage, ChannelBansPage, MiniChatPage } from '@/features/chat/public/routes';
 import { PluginCenterPage, DomainCatalogPage, PluginDetailPage } from '@/features/plugins/routes';
 import { SettingsPage, EmojiManagePage } from '@/features/settings/routes';
 import { ServerManagerPage } from '@/features/server-connection/routes';
@@ -17,6 +17,9 @@ const TrayNotificationPopover = () => import('@/features/tray-notification/prese
 // 截图遮罩窗口
 import ScreenshotOverlayRoutes from '@/features/screenshot/routes';
 
+// 应用锁锁屏窗口
+import AppLockRoutes from '@/features/app-lock/routes';
+
 const router = createRouter({
   history: createWebHistory(),
   routes: [
@@ -45,10 +48,13 @@ const router = createRouter({
     { path: '/channel-info-popover', component: ChannelInfoPopoverView, name: 'channel-info-popover' },
     { path: '/user-info-popover', component: UserPopoverPage, name: 'user-info-popover' },
     { path: '/tray-notification-popover', component: TrayNotificationPopover, name: 'tray-notification-popover' },
+    { path: '/mini-chat', component: MiniChatPage, name: 'mini-chat' },
     // 关于页面
     ...aboutRoutes,
     // 截图遮罩窗口
     ...ScreenshotOverlayRoutes,
+    // 应用锁锁屏窗口
+    ...AppLockRoutes,
     // catch-all 兜底
     { path: '/:pathMatch(.*)*', redirect: '/chat' },
   ],
@@ -24,7 +24,7 @@ import {
   type SendVerificationCodeOutcome,
   toAuthFlowErrorInfo,
 } from "./application/authFlowOutcome";
-import { signInWithEmailCode } from "./application/loginSessionCoordinator";
+import { signInWithEmailCode, signInWithOAuth } from "./application/loginSessionCoordinator";
 import {
   getCheckRequiredGateUsecase,
   getRevokeTokenUsecase,
@@ -44,6 +44,7 @@ export type {
 export type AuthFlowServerCapabilities = {
   sendVerificationCode(email: string): Promise<SendVerificationCodeOutcome>;
   signInWithEmailCode(email: string, code: string): Promise<AuthSignInOutcome>;
+  signInWithOAuth(): Promise<AuthSignInOutcome>;
   revokeToken(refreshToken: string): Promise<RevokeTokenOutcome>;
   checkRequiredSetup(): Promise<AuthRequiredSetupOutcome>;
 };
@@ -94,6 +95,9 @@ export function createAuthFlowCapabilities(): AuthFlowCapabilities {
         signInWithEmailCode(email: string, code: string): Promise<AuthSignInOutcome> {
           return signInWithEmailCode(serverSocket, email, code);
         },
+        signInWithOAuth(): Promise<AuthSignInOutcome> {
+          return signInWithOAuth(serverSocket);
+        },
         async revokeToken(refreshToken: string): Promise<RevokeTokenOutcome> {
           try {
             await getRevokeTokenUsecase(serverSocket).execute(refreshToken);
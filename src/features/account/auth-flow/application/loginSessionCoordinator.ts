@@ -1,13 +1,15 @@
 /**
  * @fileoverview account/auth-flow login session coordinator。
  * @description
- * 收敛邮箱验证码登录后的会话持久化与当前用户快照同步，避免页面层自行编排登录事务。
+ * 收敛邮箱验证码 / OAuth 登录后的会话持久化与当前用户快照同步，避免页面层自行编排登录事务。
  */
 
 import { applyAuthenticatedUserSnapshot, syncCurrentUserSnapshot } from "@/features/account/application/currentUserSnapshot";
+import { loginWithOAuth } from "@/shared/net/auth/api";
 import { writeAuthSession } from "@/shared/utils/localState";
 import { getLoginWithEmailCodeUsecase } from "../di/auth.di";
 import { AuthRequiredPluginMissingError } from "../domain/errors/AuthErrors";
+import type { AuthLoginResult } from "../domain/types/AuthTypes";
 import type { AuthSignInOutcome } from "./authFlowOutcome";
 import { toAuthFlowErrorInfo } from "./authFlowOutcome";
 
@@ -73,3 +75,58 @@ export async function signInWithEmailCode(
     };
   }
 }
+
+/**
+ * 在系统浏览器中完成 OAuth 登录，并完成本地会话与当前用户快照同步。
+ *
+ * 说明：
+ * - 令牌由 Rust 换取（PKCE + 本地回环回调）并写入系统凭据存储；
+ * - 服务端未返回 uid 时以当前用户接口的结果为准，因此先同步当前用户再写入 session。
+ *
+ * @param serverSocket - 当前目标服务器 socket。
+ * @returns 显式登录结果。
+ */
+export async function signInWithOAuth(serverSocket: string): Promise<AuthSignInOutcome> {
+  try {
+    const result = await loginWithOAuth(serverSocket);
+    const { accessToken, refreshToken } = result.tokens;
+    const expiresAtMs = typeof result.tokens.expiresAtMs === "number" ? result.tokens.expiresAtMs : undefined;
+
+    let currentUser;
+    try {
+      currentUser = await syncCurrentUserSnapshot(serverSocket, accessToken);
+    } catch {
+      currentUser = applyAuthenticatedUserSnapshot({ uid: result.uid ?? undefined });
+    }
+    const uid = result.uid ?? currentUser.id;
+
+    writeAuthSession(serverSocket, {
+      accessToken,
+      refreshToken,
+      uid: uid || undefined,
+      expiresAtMs,
+    });
+
+    const login: AuthLoginResult = {
+      accessToken,
+      refreshToken,
+      expiresInSec: expiresAtMs ? Math.max(0, Math.trunc((expiresAtMs - Date.now()) / 1000)) : 0,
+      uid,
+      isNewUser: result.isNewUser,
+    };
+
+    return {
+      ok: true,
+      kind: "signed_in",
+      login,
+      currentUser,
+      redirectTo: "/chat",
+    };
+  } catch (error) {
+    return {
+      ok: false,
+      kind: "sign_in_rejected",
+      error: toAuthFlowErrorInfo(error),
+    };
+  }
+}
@@ -1,6 +1,6 @@
 /**
  * @fileoverview useLoginEmailAuth.ts
- * @description account/auth-flow｜页面编排：登录认证动作（发码/登录/倒计时）。
+ * @description account/auth-flow｜页面编排：登录认证动作（发码/登录/浏览器 OAuth 登录/倒计时）。
  */
 
 import { onBeforeUnmount, ref, type Ref } from "vue";
@@ -23,11 +23,13 @@ export type LoginEmailAuthModel = {
   code: Ref<string>;
   sending: Ref<boolean>;
   loggingIn: Ref<boolean>;
+  oauthLoggingIn: Ref<boolean>;
   banner: Ref<string>;
   countdown: Ref<number>;
   clearBanner(): void;
   handleSendCode(): Promise<void>;
   handleLogin(): Promise<void>;
+  handleOAuthLogin(): Promise<void>;
 };
 
 /**
@@ -43,6 +45,7 @@ export function useLoginEmailAuth(deps: UseLoginEmailAuthDeps): LoginEmailAuthMo
   const code = ref("");
   const sending = ref(false);
   const loggingIn = ref(false);
+  const oauthLoggingIn = ref(false);
   const banner = ref("");
   const countdown = ref(0);
 
@@ -127,28 +130,48 @@ export function useLoginEmailAuth(deps: UseLoginEmailAuthDeps): LoginEmailAuthMo
 
     loggingIn.value = true;
     try {
-      const outcome = await authServer.signInWithEmailCode(credential.email, credential.code);
-      if (outcome.ok && outcome.kind === "signed_in") {
-        if (deps.mode === "register" && !outcome.login.isNewUser) {
-          banner.value = "Account already exists. Please sign in instead.";
-          return;
-        }
-        void router.replace({ path: outcome.redirectTo, query: outcome.login.isNewUser ? { welcome: "new" } : undefined });
+      applySignInOutcome(await authServer.signInWithEmailCode(credential.email, credential.code));
+    } finally {
+      loggingIn.value = false;
+    }
+  }
+
+  /**
+   * 在系统浏览器中完成 OAuth 登录（仅当服务器在 `/api/server` 下发 `oauth` 时展示入口）。
+   */
+  async function handleOAuthLogin(): Promise<void> {
+    clearBanner();
+    const socket = readRequiredSocket();
+    if (!socket) return;
+    const authServer = authFlowCapabilities.forServer(socket);
+
+    oauthLoggingIn.value = true;
+    try {
+      applySignInOutcome(await authServer.signInWithOAuth());
+    } finally {
+      oauthLoggingIn.value = false;
+    }
+  }
+
+  function applySignInOutcome(outcome: AuthSignInOutcome): void {
+    if (outcome.ok && outcome.kind === "signed_in") {
+      if (deps.mode === "register" && !outcome.login.isNewUser) {
+        banner.value = "Account already exists. Please sign in instead.";
         return;
       }
-      if (outcome.ok && outcome.kind === "required_setup") {
-        authFlowCapabilities.updateMissingRequiredPlugins([...outcome.missingPluginIds]);
-        if (deps.onRequiredSetup) {
-          deps.onRequiredSetup(outcome);
-        } else {
-          void router.replace("/required-setup");
-        }
-        return;
+      void router.replace({ path: outcome.redirectTo, query: outcome.login.isNewUser ? { welcome: "new" } : undefined });
+      return;
+    }
+    if (outcome.ok && outcome.kind === "required_setup") {
+      authFlowCapabilities.updateMissingRequiredPlugins([...outcome.missingPluginIds]);
+      if (deps.onRequiredSetup) {
+        deps.onRequiredSetup(outcome);
+      } else {
+        void router.replace("/required-setup");
       }
-      banner.value = outcome.error.message;
-    } finally {
-      loggingIn.value = false;
+      return;
     }
+    banner.value = outcome.error.message;
   }
 
   onBeforeUnmount(() => {
@@ -160,10 +183,12 @@ export function useLoginEmailAuth(deps: UseLoginEmailAuthDeps): LoginEmailAuthMo
     code,
     sending,
     loggingIn,
+    oauthLoggingIn,
     banner,
     countdown,
     clearBanner,
     handleSendCode,
     handleLogin,
+    handleOAuthLogin,
   };
 }
@@ -8,6 +8,7 @@
 
 import { createAuthedHttpJsonClient } from "@/shared/net/http/authedHttpJsonClient";
 import { isApiRequestError } from "@/shared/net/http/apiErrors";
+import { recordServerUsers } from "@/shared/db/userDirectory";
 import { ProfileError } from "../domain/errors/ProfileErrors";
 
 /**
@@ -94,7 +95,9 @@ function rethrowProfileError(code: ProfileError["code"], fallback: string, error
 export async function httpGetCurrentUser(serverSocket: string, accessToken: string): Promise<ApiUserMe> {
   const client = createAuthedHttpJsonClient(serverSocket, accessToken);
   try {
-    return await client.requestJson<ApiUserMe>("GET", "/users/me");
+    const me = await client.requestJson<ApiUserMe>("GET", "/users/me");
+    recordServerUsers(serverSocket, [{ uid: me.uid, name: me.nickname ?? "" }]);
+    return me;
   } catch (e) {
     rethrowProfileError("get_me_failed", "Get current user failed", e);
   }
@@ -113,7 +116,9 @@ export async function httpGetUser(serverSocket: string, accessToken: string, uid
   const userId = String(uid).trim();
   if (!userId) throw new ProfileError({ code: "missing_uid", message: "Missing uid." });
   try {
-    return await client.requestJson<ApiUserPublic>("GET", `/users/${encodeURIComponent(userId)}`);
+    const user = await client.requestJson<ApiUserPublic>("GET", `/users/${encodeURIComponent(userId)}`);
+    recordServerUsers(serverSocket, [{ uid: user.uid, name: user.nickname }]);
+    return user;
   } catch (e) {
     rethrowProfileError("get_user_failed", "Get user failed", e);
   }
@@ -142,7 +147,9 @@ export async function httpListUsers(serverSocket: string, accessToken: string, i
   const csv = Array.from(unique).join(",");
   try {
     const res = await client.requestJson<ApiUsersBatchResponse>("GET", `/users?ids=${encodeURIComponent(csv)}`);
-    return Array.isArray(res?.items) ? res.items : [];
+    const items = Array.isArray(res?.items) ? res.items : [];
+    recordServerUsers(serverSocket, items.map((u) => ({ uid: u.uid, name: u.nickname })));
+    return items;
   } catch (e) {
     rethrowProfileError("list_users_failed", "List users failed", e);
   }
@@ -0,0 +1,48 @@
+/**
+ * @fileoverview 应用锁（frontend → Rust `get_app_lock_status` / `set_app_lock_passphrase` / `lock_app` / `unlock_app` 命令）。
+ *
+ * 说明：
+ * - 设置口令即启用应用锁，清除口令即关闭；
+ * - `unlockApp()` 不传口令时使用系统生物识别（需开启设置 `app_lock_biometric`）；
+ * - 空闲自动锁定分钟数由设置 `app_lock_idle_minutes` 控制（0 = 关闭）。
+ */
+import { invokeTauri } from "@/shared/tauri";
+import { TAURI_COMMANDS } from "@/shared/tauri/commands";
+
+/**
+ * 应用锁状态。
+ */
+export type AppLockStatus = {
+  configured: boolean;
+  locked: boolean;
+  biometricAvailable: boolean;
+  biometricEnabled: boolean;
+  idleMinutes: number;
+};
+
+export function getAppLockStatus(): Promise<AppLockStatus> {
+  return invokeTauri<AppLockStatus>(TAURI_COMMANDS.getAppLockStatus);
+}
+
+/**
+ * 设置、修改或清除应用锁口令。
+ *
+ * @param current - 当前口令（已设置口令时必填）。
+ * @param passphrase - 新口令；为 `null` 表示清除口令。
+ */
+export function setAppLockPassphrase(current: string | null, passphrase: string | null): Promise<void> {
+  return invokeTauri<void>(TAURI_COMMANDS.setAppLockPassphrase, { current, passphrase });
+}
+
+export function lockApp(): Promise<void> {
+  return invokeTauri<void>(TAURI_COMMANDS.lockApp);
+}
+
+/**
+ * 解锁应用。
+ *
+ * @param secret - 应用锁口令；省略时使用系统生物识别。
+ */
+export function unlockApp(secret?: string): Promise<void> {
+  return invokeTauri<void>(TAURI_COMMANDS.unlockApp, { secret: secret ?? null });
+}
@@ -0,0 +1,14 @@
+/**
+ * @fileoverview app-lock feature 路由出口。
+ * @description 提供应用锁锁屏窗口页面的懒加载入口。
+ */
+
+const AppLockPage = () => import("./presentation/pages/AppLockPage.vue");
+
+export default [
+  {
+    path: "/app-lock",
+    name: "app-lock",
+    component: AppLockPage,
+  },
+];
@@ -0,0 +1,59 @@
+/**
+ * @fileoverview 呼叫信令事件路由器（call.invite / call.accept / call.reject / call.ice_candidate / call.hangup）。
+ * @description
+ * chat 根运行时中的呼叫信令集成路由。
+ *
+ * 职责：
+ * - 把解密后的呼叫信令推送原样转交 Rust `handle_call_signal`（按 call id 推进状态机、写通话记录）；
+ * - 状态变化由 Rust 侧广播 `call-event`，UI / WebRTC 层通过 `listenCallEvent` 消费，这里不持有呼叫状态。
+ */
+
+import { invokeTauri } from "@/shared/tauri/invokeClient";
+import { TAURI_COMMANDS } from "@/shared/tauri/commands";
+
+type LoggerLike = {
+  debug(message: string, payload?: Record<string, unknown>): void;
+};
+
+/**
+ * 呼叫信令路由名。
+ */
+export const CALL_SIGNAL_EVENT_TYPES: ReadonlySet<string> = new Set([
+  "call.invite",
+  "call.accept",
+  "call.reject",
+  "call.ice_candidate",
+  "call.hangup",
+]);
+
+/**
+ * 呼叫信令事件路由器依赖。
+ */
+export type CallSignalEventRouterDeps = {
+  logger: LoggerLike;
+  getServerSocket: () => string;
+};
+
+/**
+ * 创建呼叫信令事件路由器。
+ *
+ * @param deps - 依赖注入。
+ * @returns 事件处理函数；已处理返回 true。
+ */
+export function createCallSignalEventRouter(deps: CallSignalEventRouterDeps) {
+  return function routeCallSignalEvent(eventType: string, payload: Record<string, unknown> | null): boolean {
+    if (!CALL_SIGNAL_EVENT_TYPES.has(eventType)) return false;
+    const serverSocket = deps.getServerSocket();
+    if (!serverSocket || !payload) return true;
+
+    invokeTauri(TAURI_COMMANDS.handleCallSignal, {
+      serverSocket,
+      route: eventType,
+      payload,
+      direction: "inbound",
+    }).catch((error: unknown) => {
+      deps.logger.debug("Action: chat_call_signal_rejected", { eventType, error: String(error) });
+    });
+    return true;
+  };
+}
@@ -21,9 +21,13 @@ import type { RoomSessionStatePort } from "@/features/chat/room-session/domain/p
 import { createMessageEventRouter } from "@/features/chat/message-flow/internal";
 import { createReadStateEventRouter } from "@/features/chat/room-session/internal";
 import { createChatGovernanceEventRouter } from "./createChatGovernanceEventRouter";
+import { createCallSignalEventRouter } from "./createCallSignalEventRouter";
+import { createTypingEventRouter } from "./createTypingEventRouter";
+import { createPresenceEventRouter } from "./createPresenceEventRouter";
 import { createNotificationOnNewMessageHandler } from "@/app/bootstrap/trayIntegration";
 import { invokeTauri } from "@/shared/tauri/invokeClient";
 import { TAURI_COMMANDS } from "@/shared/tauri/commands";
+import { peekBlockedUserIds } from "@/shared/db/blockedUsers";
 
 type LoggerLike = {
   debug(message: string, payload?: Record<string, unknown>): void;
@@ -69,9 +73,25 @@ export function createChatEventRouter(deps: ChatWsEventRouterDeps) {
     emitChannelProjectionChanged: deps.emitChannelProjectionChanged,
   });
 
+  const routeCallSignalEvent = createCallSignalEventRouter({
+    logger: deps.logger,
+    getServerSocket: deps.getServerSocket,
+  });
+
+  const routeTypingEvent = createTypingEventRouter({
+    logger: deps.logger,
+    getServerSocket: deps.getServerSocket,
+    getCurrentUserId: deps.getCurrentUserId,
+  });
+
+  const routePresenceEvent = createPresenceEventRouter({
+    logger: deps.logger,
+    getServerSocket: deps.getServerSocket,
+  });
+
   const handleNewMessage = createNotificationOnNewMessageHandler({
-    getGlobalDndEnabled: () =>
-      invokeTauri<boolean>(TAURI_COMMANDS.settingsGetConfigBool, { key: "global_dnd" }),
+    getGlobalDndEnabled: async () =>
+      (await invokeTauri<{ active: boolean }>(TAURI_COMMANDS.getDndStatus)).active,
     getDesktopNotificationsEnabled: () =>
       invokeTauri<boolean>(TAURI_COMMANDS.settingsGetConfigBool, { key: "desktop_notifications" }),
     getCurrentChannelId: deps.timelineState.readCurrentChannelId,
@@ -89,6 +109,16 @@ export function createChatEventRouter(deps: ChatWsEventRouterDeps) {
     mapWireMessage: deps.mapWireMessage,
     compareMessages: deps.compareMessages,
     onNewMessage: handleNewMessage,
+    isBlockedSender: (socket, message) => {
+      const senderId = String(message.from?.id ?? "").trim();
+      return senderId !== "" && peekBlockedUserIds(socket).has(senderId);
+    },
+    isMutedMessage: async (message) => {
+      const sample = message.kind === "core_text" ? message.text : "preview" in message ? message.preview : "";
+      if (!sample) return false;
+      const result = await invokeTauri<{ muted: boolean }>(TAURI_COMMANDS.testMuteRule, { sample });
+      return result.muted;
+    },
   });
 
   const routeReadStateEvent = createReadStateEventRouter({
@@ -103,6 +133,9 @@ export function createChatEventRouter(deps: ChatWsEventRouterDeps) {
     if (routeGovernanceEvent(eventType, payload)) return;
     if (routeMessageEvent(eventType, payload)) return;
     if (routeReadStateEvent(eventType, payload)) return;
+    if (routeCallSignalEvent(eventType, payload)) return;
+    if (routeTypingEvent(eventType, payload)) return;
+    if (routePresenceEvent(eventType, payload)) return;
 
     deps.logger.debug("Action: chat_ws_event_ignored", { eventType });
   };
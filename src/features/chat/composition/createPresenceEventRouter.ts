@@ -0,0 +1,64 @@
+/**
+ * @fileoverview 在线状态事件路由器（user.presence）。
+ * @description
+ * chat 根运行时中的在线状态集成路由。
+ *
+ * 职责：
+ * - 把解密后的在线状态推送转交 Rust `handle_presence_update`（写入缓存并合并广播变化）；
+ * - 推送可为单条（`userId` / `status` / `lastSeen`）或多条（`presences` 数组）；
+ * - 订阅与缓存由 Rust 侧管理，UI 通过 `get_presence` 与 `listenPresenceChanged` 消费。
+ */
+
+import { invokeTauri } from "@/shared/tauri/invokeClient";
+import { TAURI_COMMANDS } from "@/shared/tauri/commands";
+import type { PresenceEntry } from "@/shared/tauri/events";
+
+type LoggerLike = {
+  debug(message: string, payload?: Record<string, unknown>): void;
+};
+
+/**
+ * 在线状态推送的路由名。
+ */
+export const PRESENCE_EVENT_TYPE = "user.presence";
+
+/**
+ * 在线状态事件路由器依赖。
+ */
+export type PresenceEventRouterDeps = {
+  logger: LoggerLike;
+  getServerSocket: () => string;
+};
+
+function toPresenceEntry(raw: unknown): PresenceEntry | null {
+  if (!raw || typeof raw !== "object") return null;
+  const record = raw as Record<string, unknown>;
+  const userId = String(record.userId ?? "").trim();
+  const status = String(record.status ?? "").trim();
+  if (!userId || !status) return null;
+  const lastSeen = typeof record.lastSeen === "number" ? record.lastSeen : null;
+  return { userId, status, lastSeen };
+}
+
+/**
+ * 创建在线状态事件路由器。
+ *
+ * @param deps - 依赖注入。
+ * @returns 事件处理函数；已处理返回 true。
+ */
+export function createPresenceEventRouter(deps: PresenceEventRouterDeps) {
+  return function routePresenceEvent(eventType: string, payload: Record<string, unknown> | null): boolean {
+    if (eventType !== PRESENCE_EVENT_TYPE) return false;
+    const serverSocket = deps.getServerSocket();
+    if (!serverSocket || !payload) return true;
+
+    const raw = Array.isArray(payload.presences) ? payload.presences : [payload];
+    const presences = raw.map(toPresenceEntry).filter((entry): entry is PresenceEntry => entry !== null);
+    if (presences.length === 0) return true;
+
+    invokeTauri(TAURI_COMMANDS.handlePresenceUpdate, { serverSocket, presences }).catch((error: unknown) => {
+      deps.logger.debug("Action: chat_presence_update_rejected", { error: String(error) });
+    });
+    return true;
+  };
+}
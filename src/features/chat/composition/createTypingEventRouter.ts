@@ -0,0 +1,57 @@
+/**
+ * @fileoverview 正在输入事件路由器（channel.typing）。
+ * @description
+ * chat 根运行时中的正在输入集成路由。
+ *
+ * 职责：
+ * - 把解密后的 typing 推送转交 Rust `handle_typing_event`（按频道合并输入者、到期自动移除）；
+ * - 输入者变化由 Rust 侧广播 `typing-state-changed`，UI 通过 `listenTypingStateChanged` 消费，
+ *   这里不持有输入状态；本端自己的 typing 回显直接忽略。
+ */
+
+import { invokeTauri } from "@/shared/tauri/invokeClient";
+import { TAURI_COMMANDS } from "@/shared/tauri/commands";
+
+type LoggerLike = {
+  debug(message: string, payload?: Record<string, unknown>): void;
+};
+
+/**
+ * 正在输入推送的路由名。
+ */
+export const TYPING_EVENT_TYPE = "channel.typing";
+
+/**
+ * 正在输入事件路由器依赖。
+ */
+export type TypingEventRouterDeps = {
+  logger: LoggerLike;
+  getServerSocket: () => string;
+  getCurrentUserId: () => string;
+};
+
+/**
+ * 创建正在输入事件路由器。
+ *
+ * @param deps - 依赖注入。
+ * @returns 事件处理函数；已处理返回 true。
+ */
+export function createTypingEventRouter(deps: TypingEventRouterDeps) {
+  return function routeTypingEvent(eventType: string, payload: Record<string, unknown> | null): boolean {
+    if (eventType !== TYPING_EVENT_TYPE) return false;
+    const serverSocket = deps.getServerSocket();
+    const channelId = String(payload?.channelId ?? "").trim();
+    const userId = String(payload?.userId ?? "").trim();
+    if (!serverSocket || !channelId || !userId || userId === deps.getCurrentUserId()) return true;
+
+    invokeTauri(TAURI_COMMANDS.handleTypingEvent, {
+      serverSocket,
+      channelId,
+      userId,
+      typing: payload?.typing !== false,
+    }).catch((error: unknown) => {
+      deps.logger.debug("Action: chat_typing_event_rejected", { channelId, error: String(error) });
+    });
+    return true;
+  };
+}
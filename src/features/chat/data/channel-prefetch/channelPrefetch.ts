@@ -0,0 +1,56 @@
+/**
+ * @fileoverview 频道历史预取调度（data 层）。
+ * @description
+ * - Rust 侧按 server 限制并发，聚焦频道优先，冷频道延后到空闲时；
+ * - 前端收到 `channel-prefetch-ready` 票据后拉取历史，完成后调用 `completeChannelPrefetch` 归还名额。
+ */
+import { invokeTauri, TAURI_COMMANDS } from "@/shared/tauri";
+
+/**
+ * 预取优先级（`focused` 只由 `setActiveChannel` 决定）。
+ */
+export type ChannelPrefetchPriority = "warm" | "cold";
+
+/**
+ * 上报当前聚焦的频道。
+ *
+ * @param serverSocket - 服务器 socket。
+ * @param channelId - 频道 id；`null` 表示离开频道视图。
+ */
+export function setActiveChannel(serverSocket: string, channelId: string | null): Promise<void> {
+  return invokeTauri<void>(TAURI_COMMANDS.setActiveChannel, { serverSocket, channelId });
+}
+
+/**
+ * 将频道加入预取队列。
+ *
+ * @param serverSocket - 服务器 socket。
+ * @param channelIds - 频道 id 列表。
+ * @param priority - 优先级（默认 `cold`）。
+ */
+export function enqueueChannelPrefetch(
+  serverSocket: string,
+  channelIds: string[],
+  priority: ChannelPrefetchPriority = "cold",
+): Promise<void> {
+  return invokeTauri<void>(TAURI_COMMANDS.enqueueChannelPrefetch, { serverSocket, channelIds, priority });
+}
+
+/**
+ * 归还预取票据。
+ *
+ * @param ticket - `channel-prefetch-ready` 事件中的票据号。
+ * @returns 票据是否仍有效（超时后返回 `false`）。
+ */
+export function completeChannelPrefetch(ticket: number): Promise<boolean> {
+  return invokeTauri<boolean>(TAURI_COMMANDS.completeChannelPrefetch, { ticket });
+}
+
+/**
+ * 清空某个 server 的预取队列。
+ *
+ * @param serverSocket - 服务器 socket。
+ */
+export function clearChannelPrefetch(serverSocket: string): Promise<void> {
+  return invokeTauri<void>(TAURI_COMMANDS.clearChannelPrefetch, { serverSocket });
+}
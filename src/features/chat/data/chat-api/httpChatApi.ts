@@ -7,6 +7,7 @@
  */
 
 import { createAuthedHttpJsonClient } from "@/shared/net/http/authedHttpJsonClient";
+import { recordServerUsers } from "@/shared/db/userDirectory";
 import type {
   ChatChannelApplicationWire,
   ChatChannelBanWire,
@@ -183,7 +184,9 @@ export async function httpListChannelMembers(
   const channelId = String(cid).trim();
   if (!channelId) throw new Error("Missing cid");
   const res = await client.requestJson<ApiListMembersResponse>("GET", `/channels/${encodeURIComponent(channelId)}/members`);
-  return Array.isArray(res?.items) ? res.items : [];
+  const items = Array.isArray(res?.items) ? res.items : [];
+  recordServerUsers(serverSocket, items.map((m) => ({ uid: m.uid, name: m.nickname })));
+  return items;
 }
 
 export async function httpKickChannelMember(
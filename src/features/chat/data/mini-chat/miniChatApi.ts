@@ -0,0 +1,94 @@
+/**
+ * @fileoverview 迷你聊天窗数据访问（data 层）。
+ * @description
+ * 迷你窗不启动主窗口的连接运行时，也不持有 access token：
+ * - 历史与发送经 Rust `api_request_json`，由 Rust 侧附加系统凭据存储中的会话令牌（401 时透明刷新）；
+ * - 已读位置经 `mini_chat_mark_read` 上报，由 Rust 广播 `mini-chat-read-state` 同步给主窗口。
+ */
+import { invokeTauri, TAURI_COMMANDS } from "@/shared/tauri";
+import { CARRY_PIGEON_ACCEPT_V1 } from "@/shared/net/http/apiHeaders";
+import { getServerTlsConfig } from "@/shared/net/tls/serverTlsConfigProvider";
+import type { ChatMessagePageWire, ChatMessageWire, ChatSendMessageWire } from "../protocol/chatWireModels";
+
+type TauriApiResponse = {
+  ok: boolean;
+  status: number;
+  body?: unknown;
+  error?: unknown;
+};
+
+async function requestJson<T>(
+  serverSocket: string,
+  method: string,
+  path: string,
+  body?: unknown,
+  extraHeaders?: Record<string, string>,
+): Promise<T> {
+  const tls = getServerTlsConfig(serverSocket);
+  const headers: Record<string, string> = { Accept: CARRY_PIGEON_ACCEPT_V1, ...extraHeaders };
+  if (body !== undefined) headers["Content-Type"] = "application/json; charset=utf-8";
+  const res = await invokeTauri<TauriApiResponse>(TAURI_COMMANDS.apiRequestJson, {
+    serverSocket,
+    method,
+    path: `/api${path}`,
+    headers,
+    body,
+    tlsPolicy: tls.tlsPolicy,
+    tlsFingerprint: tls.tlsFingerprint,
+  });
+  if (!res.ok) throw new Error(`HTTP ${res.status} ${method} ${path}`);
+  return res.body as T;
+}
+
+/**
+ * 拉取频道最近的消息（按发送时间升序）。
+ *
+ * @param serverSocket - 服务器 socket。
+ * @param channelId - 频道 id。
+ * @param limit - 条数上限。
+ */
+export async function listMiniChatMessages(
+  serverSocket: string,
+  channelId: string,
+  limit: number = 30,
+): Promise<ChatMessageWire[]> {
+  const path = `/channels/${encodeURIComponent(channelId)}/messages?limit=${encodeURIComponent(String(limit))}`;
+  const page = await requestJson<ChatMessagePageWire | null>(serverSocket, "GET", path);
+  const items = Array.isArray(page?.items) ? page.items : [];
+  return [...items].sort((a, b) => a.send_time - b.send_time);
+}
+
+/**
+ * 发送一条文本消息。
+ *
+ * @param serverSocket - 服务器 socket。
+ * @param channelId - 频道 id。
+ * @param text - 消息文本。
+ */
+export function sendMiniChatText(serverSocket: string, channelId: string, text: string): Promise<ChatMessageWire> {
+  const clientMessageId = globalThis.crypto.randomUUID();
+  const req: ChatSendMessageWire = {
+    domain: "Core:Text",
+    domain_version: "1",
+    data: { text },
+    client_message_id: clientMessageId,
+  };
+  return requestJson<ChatMessageWire>(
+    serverSocket,
+    "POST",
+    `/channels/${encodeURIComponent(channelId)}/messages`,
+    req,
+    { "Idempotency-Key": clientMessageId },
+  );
+}
+
+/**
+ * 上报已读位置（同步到主窗口与其它迷你窗）。
+ *
+ * @param serverSocket - 服务器 socket。
+ * @param channelId - 频道 id。
+ * @param lastReadMessageId - 已读到的最后一条消息 id。
+ */
+export function markMiniChatRead(serverSocket: string, channelId: string, lastReadMessageId: string): Promise<void> {
+  return invokeTauri<void>(TAURI_COMMANDS.miniChatMarkRead, { serverSocket, channelId, lastReadMessageId });
+}
@@ -28,6 +28,10 @@ export type MessageEventRouterDeps = {
   compareMessages: (a: ChatMessage, b: ChatMessage) => number;
   /** 新消息到来时的回调（用于桌面通知等）。 */
   onNewMessage?: (channelId: string, message: ChatMessage) => void;
+  /** 发送者是否被屏蔽；被屏蔽用户的消息不进入时间线、不计入未读、不触发通知。 */
+  isBlockedSender?: (serverSocket: string, message: ChatMessage) => boolean;
+  /** 消息是否命中静音规则；命中的消息照常进入时间线，但不计入未读、不触发通知。 */
+  isMutedMessage?: (message: ChatMessage) => Promise<boolean>;
 };
 
 /**
@@ -49,10 +53,17 @@ export function createMessageEventRouter(deps: MessageEventRouterDeps) {
       const msg = (payload?.message ?? null) as ChatMessageRecord | null;
       if (!cid || !msg) return true;
 
-      const mapped = deps.mapWireMessage(deps.scope.getActiveServerSocket(), msg);
+      const socket = deps.scope.getActiveServerSocket();
+      const mapped = deps.mapWireMessage(socket, msg);
+      if (deps.isBlockedSender?.(socket, mapped)) return true;
       const inserted = deps.timelineState.appendMessageIfMissing(cid, mapped, deps.compareMessages);
-      if (inserted && deps.timelineState.readCurrentChannelId() !== cid) deps.unreadProjection.incrementChannelUnread(cid);
-      deps.onNewMessage?.(cid, mapped);
+      const project = (muted: boolean): void => {
+        if (muted) return;
+        if (inserted && deps.timelineState.readCurrentChannelId() !== cid) deps.unreadProjection.incrementChannelUnread(cid);
+        deps.onNewMessage?.(cid, mapped);
+      };
+      if (deps.isMutedMessage) void deps.isMutedMessage(mapped).then(project, () => project(false));
+      else project(false);
       return true;
     }
 
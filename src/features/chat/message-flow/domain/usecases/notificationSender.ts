@@ -8,6 +8,7 @@
 import { isPermissionGranted, requestPermission, sendNotification } from "@tauri-apps/plugin-notification";
 import { createLogger } from "@/shared/utils/logger";
 import { IS_MOCK_ENABLED } from "@/shared/config/runtime";
+import { invokeTauri, TAURI_COMMANDS } from "@/shared/tauri";
 
 const logger = createLogger("notification");
 
@@ -59,6 +60,16 @@ export async function sendDesktopNotification(params: {
 
     sendNotification({ title: params.title, body: params.body });
     logger.debug("Action: chat_notification_sent", { channelId: params.channelId });
+    // 写入通知中心历史；失败不影响通知本身。
+    void invokeTauri<number>(TAURI_COMMANDS.recordNotification, {
+      notification: {
+        kind: "message",
+        title: params.title,
+        body: params.body,
+        channelId: params.channelId,
+        messageId: params.messageId,
+      },
+    }).catch((e) => logger.warn("Action: chat_notification_history_record_failed", { error: String(e) }));
   } catch (e) {
     logger.error("Action: chat_notification_send_failed", { error: String(e) });
   }
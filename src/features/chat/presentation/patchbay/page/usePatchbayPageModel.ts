@@ -7,7 +7,7 @@
 import { computed, onBeforeUnmount, onMounted, proxyRefs, ref, type ComputedRef, type Ref, type ShallowUnwrapRef } from "vue";
 import { useRoute, useRouter } from "vue-router";
 import { useI18n } from "vue-i18n";
-import { invoke } from "@tauri-apps/api/core";
+import { invokeTauri } from "@/shared/tauri/invokeClient";
 import { TAURI_COMMANDS } from "@/shared/tauri/commands";
 import { createLogger } from "@/shared/utils/logger";
 import { toast } from "@/shared/utils/toast";
@@ -230,7 +230,7 @@ export function usePatchbayPageModel(): PatchbayPageModel {
 
   async function doFetchLinkPreview(url: string): Promise<void> {
     try {
-      const result = await invoke<ChatLinkPreview>(TAURI_COMMANDS.fetchLinkPreview, { url });
+      const result = await invokeTauri<ChatLinkPreview>(TAURI_COMMANDS.fetchLinkPreview, { url });
       linkPreview.value = result;
     } catch {
       linkPreview.value = null;
@@ -480,6 +480,8 @@ export function usePatchbayPageModel(): PatchbayPageModel {
 
   const membersRail = useMembersRailModel({
     members: roomGovernance.currentChannel.members,
+    serverSocket: computed(() => currentServerSocket.value ?? ""),
+    channelId: computed(() => currentSessionSnapshot.value.currentChannelId),
   });
 
   function handleReplyShortcut(messageId: string): void {
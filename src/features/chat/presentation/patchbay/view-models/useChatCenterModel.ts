@@ -38,6 +38,7 @@ import {
 import type { ChatLinkPreview } from "@/features/chat/domain/types/chatApiModels";
 import type { ChatApiPort } from "@/features/chat/domain/ports/chatApiPort";
 import type { PinSummary } from "@/features/chat/presentation/patchbay/components/layout/PinListBar.vue";
+import { useTypingIndicator } from "./useTypingIndicator";
 
 type RefLike<T> = Ref<T> | ComputedRef<T>;
 type ChatConnectionPillStateView = "connected" | "reconnecting" | "offline";
@@ -87,6 +88,8 @@ type ChatCenterRawModel = {
   domainRegistryStore: ComputedRef<DomainRegistryStoreLike>;
   mentionCandidates: Ref<MentionCandidate[]>;
   mentionMenuOpen: Ref<boolean>;
+  /** 当前频道“正在输入”提示文案；无人输入时为空串。 */
+  typingText: ComputedRef<string>;
   currentUserRole: ComputedRef<string>;
   searchPanelOpen: Ref<boolean>;
   searchState: ComputedRef<MessageSearchState>;
@@ -300,6 +303,36 @@ export function useChatCenterModel(deps: UseChatCenterModelDeps): ChatCenterMode
     return Boolean(message.mentions?.some((mention) => mention.userId === currentUserId.value));
   }
 
+  /**
+   * 正在输入提示：输入者 id 按频道成员解析为显示名，解析不到时回退为 id。
+   */
+  const typingUserIds = useTypingIndicator({
+    serverSocket: computed(() => deps.serverSocket?.value ?? ""),
+    channelId: computed(() => currentSessionSnapshot.value.currentChannelId),
+    currentUserId,
+  });
+  const typingNames = ref<Map<string, string>>(new Map());
+
+  watch(typingUserIds, async (userIds) => {
+    if (userIds.every((id) => typingNames.value.has(id))) return;
+    try {
+      const candidates = await deps.messageComposer.listMentionCandidates(currentSessionSnapshot.value.currentChannelId);
+      const next = new Map(typingNames.value);
+      for (const candidate of candidates) next.set(candidate.userId, candidate.displayName);
+      typingNames.value = next;
+    } catch (e) {
+      logger.debug("Action: chat_typing_names_resolve_failed", { error: String(e) });
+    }
+  });
+
+  const typingText = computed(() => {
+    const names = typingUserIds.value.map((id) => typingNames.value.get(id) || id);
+    if (names.length === 0) return "";
+    if (names.length === 1) return t("typing_one", { name: names[0] });
+    if (names.length === 2) return t("typing_two", { first: names[0], second: names[1] });
+    return t("typing_many", { count: names.length });
+  });
+
   /**
    * 搜索面板状态。
    */
@@ -696,6 +729,7 @@ export function useChatCenterModel(deps: UseChatCenterModelDeps): ChatCenterMode
     domainRegistryStore,
     mentionCandidates,
     mentionMenuOpen,
+    typingText,
     currentUserRole,
     searchPanelOpen,
     searchState,
@@ -1,16 +1,19 @@
 /**
  * @fileoverview members rail model
  * @description
- * 收敛 MembersRail 所需的成员列表读取，避免布局组件直接依赖 governance store。
+ * 收敛 MembersRail 所需的成员列表读取与在线状态，避免布局组件直接依赖 governance store。
  */
 
 import { computed, proxyRefs } from "vue";
-import type { ComputedRef, ShallowUnwrapRef } from "vue";
+import type { ComputedRef, Ref, ShallowUnwrapRef } from "vue";
 import type { RoomGovernanceMembersCapabilities, RoomGovernanceMembersSnapshot } from "@/features/chat/room-governance/api-types";
 import { useObservedCapabilitySnapshot } from "@/shared/utils/useObservedCapabilitySnapshot";
+import { usePresenceWatch } from "@/features/chat/presentation/shared/usePresenceWatch";
 
 type MembersRailRawModel = {
   members: ComputedRef<RoomGovernanceMembersSnapshot>;
+  /** 成员在线状态（`userId -> status`）；未知成员不在其中。 */
+  presence: Ref<Map<string, string>>;
 };
 /**
  * MembersRail 组件消费的页面模型。
@@ -22,6 +25,10 @@ export type MembersRailModel = ShallowUnwrapRef<MembersRailRawModel>;
  */
 export type UseMembersRailModelDeps = {
   members: RoomGovernanceMembersCapabilities;
+  /** 当前 server socket（在线状态订阅用）。 */
+  serverSocket: Readonly<Ref<string>>;
+  /** 当前频道 id（作为在线状态订阅的视图标识）。 */
+  channelId: Readonly<Ref<string>>;
 };
 
 /**
@@ -29,8 +36,14 @@ export type UseMembersRailModelDeps = {
  */
 export function useMembersRailModel(deps: UseMembersRailModelDeps): MembersRailModel {
   const membersSnapshot = useObservedCapabilitySnapshot(deps.members);
+  const presence = usePresenceWatch({
+    serverSocket: deps.serverSocket,
+    watcherId: computed(() => (deps.channelId.value ? `members:${deps.channelId.value}` : "")),
+    userIds: computed(() => membersSnapshot.value.map((m) => m.id)),
+  });
   const rawModel: MembersRailRawModel = {
     members: computed(() => membersSnapshot.value),
+    presence,
   };
   return proxyRefs(rawModel);
 }
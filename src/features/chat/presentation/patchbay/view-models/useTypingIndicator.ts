@@ -0,0 +1,85 @@
+/**
+ * @fileoverview useTypingIndicator.ts
+ * @description chat｜当前频道“正在输入”提示：消费 Rust 广播的 `typing-state-changed`。
+ *
+ * 说明：
+ * - 输入者集合由 Rust 侧合并与过期（见 `network/di/typing.rs`），这里只按 server/频道过滤；
+ * - 切换频道时用 `list_typing_users` 读取一次当前集合，避免错过切换前的广播；
+ * - 本端用户不计入输入者。
+ */
+
+import { onBeforeUnmount, ref, watch, type Ref } from "vue";
+import { createLogger } from "@/shared/utils/logger";
+import { invokeTauri } from "@/shared/tauri/invokeClient";
+import { TAURI_COMMANDS } from "@/shared/tauri/commands";
+import { listenTypingStateChanged } from "@/shared/tauri/events";
+
+const logger = createLogger("typingIndicator");
+
+type RefLike<T> = Readonly<Ref<T>>;
+
+/**
+ * 正在输入提示依赖。
+ */
+export type UseTypingIndicatorDeps = {
+  serverSocket: RefLike<string>;
+  channelId: RefLike<string>;
+  currentUserId: RefLike<string>;
+};
+
+/**
+ * 订阅当前频道的输入者列表。
+ *
+ * @returns 当前频道正在输入的用户 id（不含本端用户）。
+ */
+export function useTypingIndicator(deps: UseTypingIndicatorDeps): Ref<string[]> {
+  const typingUserIds = ref<string[]>([]);
+  let disposed = false;
+  let unlisten: (() => void) | null = null;
+  let seedSeq = 0;
+
+  function apply(userIds: string[]): void {
+    const self = deps.currentUserId.value;
+    typingUserIds.value = userIds.filter((id) => id && id !== self);
+  }
+
+  async function seed(serverSocket: string, channelId: string): Promise<void> {
+    const seq = ++seedSeq;
+    typingUserIds.value = [];
+    if (!serverSocket || !channelId) return;
+    try {
+      const userIds = await invokeTauri<string[]>(TAURI_COMMANDS.listTypingUsers, { serverSocket, channelId });
+      if (seq === seedSeq) apply(userIds);
+    } catch (e) {
+      logger.debug("Action: chat_typing_list_failed", { serverSocket, channelId, error: String(e) });
+    }
+  }
+
+  void listenTypingStateChanged((event) => {
+    const { serverSocket, channelId, userIds } = event.payload;
+    if (serverSocket !== deps.serverSocket.value.trim() || channelId !== deps.channelId.value.trim()) return;
+    // 广播晚于种子请求时以广播为准。
+    seedSeq += 1;
+    apply(userIds);
+  })
+    .then((fn) => {
+      if (disposed) fn();
+      else unlisten = fn;
+    })
+    .catch((e) => {
+      logger.warn("Action: chat_typing_listen_failed", { error: String(e) });
+    });
+
+  watch(
+    () => [deps.serverSocket.value.trim(), deps.channelId.value.trim()] as const,
+    ([serverSocket, channelId]) => void seed(serverSocket, channelId),
+    { immediate: true },
+  );
+
+  onBeforeUnmount(() => {
+    disposed = true;
+    unlisten?.();
+  });
+
+  return typingUserIds;
+}
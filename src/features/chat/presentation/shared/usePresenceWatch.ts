@@ -0,0 +1,115 @@
+/**
+ * @fileoverview 在线状态订阅（成员栏、联系人列表等通用）。
+ * @description chat｜presentation composable：把视图当前可见的用户上报给 Rust 在线状态管理器并消费状态变化。
+ *
+ * 说明：
+ * - 订阅的去重、合批与退订由 Rust `set_presence_watch` 负责（见 `network/domain/presence.rs`），
+ *   这里只按 `watcherId` 上报“当前可见的用户”，卸载时上报空列表；
+ * - 状态来自 `get_presence` 缓存与 `presence-changed` 广播；未知用户不出现在结果中。
+ */
+
+import { onBeforeUnmount, ref, watch, type ComputedRef, type Ref } from "vue";
+import { createLogger } from "@/shared/utils/logger";
+import { invokeTauri } from "@/shared/tauri/invokeClient";
+import { TAURI_COMMANDS } from "@/shared/tauri/commands";
+import { listenPresenceChanged, type PresenceEntry } from "@/shared/tauri/events";
+
+const logger = createLogger("presenceWatch");
+
+type ReadonlyRefLike<T> = Readonly<Ref<T>> | ComputedRef<T>;
+
+/**
+ * 在线状态订阅参数。
+ */
+export type UsePresenceWatchArgs = {
+  /**
+   * 当前 server socket。
+   */
+  serverSocket: ReadonlyRefLike<string>;
+  /**
+   * 视图标识（如 `members:<cid>`）；同一视图重复上报时覆盖上一次的集合。
+   */
+  watcherId: ReadonlyRefLike<string>;
+  /**
+   * 视图当前可见的用户 id。
+   */
+  userIds: ReadonlyRefLike<readonly string[]>;
+};
+
+/**
+ * 为一个视图订阅在线状态。
+ *
+ * @param args - 订阅参数。
+ * @returns 已知用户的在线状态（`userId -> status`）。
+ */
+export function usePresenceWatch(args: UsePresenceWatchArgs): Ref<Map<string, string>> {
+  const { serverSocket, watcherId, userIds } = args;
+  const statuses = ref<Map<string, string>>(new Map());
+  let disposed = false;
+  let unlisten: (() => void) | null = null;
+  let reported: { serverSocket: string; watcherId: string } | null = null;
+
+  function setWatch(socket: string, id: string, ids: readonly string[]): void {
+    void invokeTauri(TAURI_COMMANDS.setPresenceWatch, { serverSocket: socket, watcherId: id, userIds: ids }).catch((e) => {
+      logger.debug("Action: chat_presence_watch_failed", { serverSocket: socket, watcherId: id, error: String(e) });
+    });
+  }
+
+  function merge(entries: readonly PresenceEntry[]): void {
+    if (entries.length === 0) return;
+    const next = new Map(statuses.value);
+    for (const entry of entries) next.set(entry.userId, entry.status);
+    statuses.value = next;
+  }
+
+  async function seed(socket: string, ids: readonly string[]): Promise<void> {
+    try {
+      merge(await invokeTauri<PresenceEntry[]>(TAURI_COMMANDS.getPresence, { serverSocket: socket, userIds: ids }));
+    } catch (e) {
+      logger.debug("Action: chat_presence_get_failed", { serverSocket: socket, error: String(e) });
+    }
+  }
+
+  function release(): void {
+    if (!reported) return;
+    setWatch(reported.serverSocket, reported.watcherId, []);
+    reported = null;
+  }
+
+  void listenPresenceChanged((event) => {
+    if (event.payload.serverSocket !== serverSocket.value.trim()) return;
+    merge(event.payload.presences);
+  })
+    .then((fn) => {
+      if (disposed) fn();
+      else unlisten = fn;
+    })
+    .catch((e) => {
+      logger.warn("Action: chat_presence_listen_failed", { error: String(e) });
+    });
+
+  // 以拼接后的键去重：列表引用变化但内容不变时不重复上报。
+  watch(
+    () => JSON.stringify([serverSocket.value.trim(), watcherId.value.trim(), userIds.value.map((id) => String(id).trim()).filter(Boolean)]),
+    (key) => {
+      const [socket, id, ids] = JSON.parse(key) as [string, string, string[]];
+      if (reported && (reported.serverSocket !== socket || reported.watcherId !== id)) {
+        release();
+        statuses.value = new Map();
+      }
+      if (!socket || !id) return;
+      reported = { serverSocket: socket, watcherId: id };
+      setWatch(socket, id, ids);
+      if (ids.length > 0) void seed(socket, ids);
+    },
+    { immediate: true },
+  );
+
+  onBeforeUnmount(() => {
+    disposed = true;
+    unlisten?.();
+    release();
+  });
+
+  return statuses;
+}
@@ -16,6 +16,10 @@ export const ChannelInfoPage = () => import("../presentation/channel-info/Channe
  * 频道信息浮层视图路由组件。
  */
 export const ChannelInfoPopoverView = () => import("../presentation/channel-info/ChannelInfoPopoverView.vue");
+/**
+ * 画中画迷你聊天窗路由组件。
+ */
+export const MiniChatPage = () => import("../presentation/mini-chat/MiniChatPage.vue");
 /**
  * 频道成员治理页面路由组件。
  */
@@ -4,7 +4,8 @@
  *
  * 背景：
  * - 在 WS resume 失败（例如断线重连失败、事件流错位等）后，服务端会发送 `resume.failed`；
- * - 客户端需要走 HTTP 补拉，尽力恢复频道列表/未读计数/当前上下文的消息页。
+ * - 客户端需要走 HTTP 补拉，尽力恢复频道列表/未读计数/当前上下文的消息页；
+ * - 服务端支持频道变更游标时，先按游标把编辑/删除同步进本地 server DB（最新页补拉只覆盖新增）。
  *
  * 约束：
  * - 补拉应当“尽力而为”，避免 fan-out 过大导致请求风暴；
@@ -49,6 +50,10 @@ export type ResumeFailedCatchUpDeps = {
    * 刷新指定频道的最新页消息（不影响已加载的历史分页）。
    */
   refreshChannelLatestPage: (cid: string) => Promise<void>;
+  /**
+   * 按变更游标增量同步指定频道（服务端不支持时应静默返回）。
+   */
+  syncChannelDelta: (cid: string) => Promise<void>;
   /**
    * 刷新成员侧栏（尽力而为）。
    */
@@ -71,8 +76,8 @@ export function createResumeFailedCatchUp(deps: ResumeFailedCatchUpDeps) {
    *
    * 策略：
    * - `refreshChannels()`：刷新频道列表与未读计数；
-   * - 当前频道：刷新最新页；
-   * - 少量未读频道：刷新最新页（恢复上下文；限制上限避免风暴）。
+   * - 当前频道：按变更游标增量同步，再刷新最新页；
+   * - 少量未读频道：同上（恢复上下文；限制上限避免风暴）。
    *
    * @param socketKey - resume 失败对应的 server socket key。
    * @param reason - 服务端返回的失败原因。
@@ -112,6 +117,12 @@ export function createResumeFailedCatchUp(deps: ResumeFailedCatchUpDeps) {
     }
 
     for (const cid of ordered) {
+      if (isStale()) return;
+      try {
+        await deps.syncChannelDelta(cid);
+      } catch (e) {
+        deps.logger.warn("Action: chat_catch_up_delta_sync_failed", { cid, error: String(e) });
+      }
       if (isStale()) return;
       try {
         await deps.refreshChannelLatestPage(cid);
@@ -31,6 +31,10 @@ export type ReadStateReporterDeps = {
   scope: SessionScopePort;
   state: SessionReadMarkerStatePort;
   messageCache: Pick<SessionMessageCachePort, "listMessages">;
+  /**
+   * 服务端确认已读后，把已读时间缓存到本地 server DB（收藏栏等本地未读数依赖它）。
+   */
+  cacheReadState: (serverSocket: string, cid: string, lastReadTimeMs: number) => Promise<void>;
 };
 
 /**
@@ -116,6 +120,9 @@ export function createReadStateReporter(deps: ReadStateReporterDeps): ReadStateR
       if (deps.scope.getActiveScopeVersion() !== requestScopeVersion) return false;
       syncLocalReadMarker(channelId, res.lastReadMid, res.lastReadTime);
       deps.state.writeLastReportAtMs(channelId, now);
+      deps.cacheReadState(requestSocket, channelId, res.lastReadTime).catch((error) => {
+        logger.warn("Action: chat_read_state_cache_failed", { channelId, error: String(error) });
+      });
       return true;
     } catch (error) {
       logger.warn("Action: chat_read_state_report_failed", { channelId, error: String(error) });
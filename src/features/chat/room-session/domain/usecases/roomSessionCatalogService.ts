@@ -30,6 +30,8 @@ export type RoomSessionCatalogApplicationServiceDeps = {
   directoryState: SessionDirectoryStatePort;
   readMarkerState: SessionReadMarkerStatePort;
   messageCache: SessionMessageCachePort;
+  /** 读取被屏蔽用户 id（可选；缺省时不过滤）。 */
+  getBlockedUserIds?: (socket: string) => Promise<ReadonlySet<string>>;
 };
 
 /**
@@ -57,6 +59,7 @@ export class RoomSessionCatalogApplicationService {
       logger.warn("Action: chat_room_session_unreads_fetch_failed", { error: String(error) });
       return [] as ChatUnreadState[];
     });
+    const blockedUserIds = await this.deps.getBlockedUserIds?.(socket);
     if (this.isScopeStale(requestSocket, requestScopeVersion)) return;
 
     const unreadByCid: Record<string, { unread: number; lastReadTime: number }> = {};
@@ -83,6 +86,7 @@ export class RoomSessionCatalogApplicationService {
         messages: localTimeline,
         lastReadTimeMs: this.deps.readMarkerState.readLastReadTimeMs(cid),
         lastReadMessageId: this.deps.readMarkerState.readLastReadMessageId(cid),
+        blockedUserIds,
       });
       const displayUnread = recomputed == null ? serverUnread : recomputed;
       next.push({
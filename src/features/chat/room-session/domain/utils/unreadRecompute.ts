@@ -26,6 +26,8 @@ export type RecomputeChannelUnreadInput = {
   lastReadTimeMs: number;
   /** 本地已读标记消息 id。 */
   lastReadMessageId: string;
+  /** 被屏蔽用户 id（其消息不计入未读）。 */
+  blockedUserIds?: ReadonlySet<string>;
 };
 
 /**
@@ -34,7 +36,8 @@ export type RecomputeChannelUnreadInput = {
  * 规则：
  * - 统计「位于已读标记之后」且「未被撤回」的消息条数；
  * - 已删除消息已从时间线移除，天然不计入；
- * - 已撤回消息（`recalledAt` 有值）显式排除，与服务端缺陷对齐。
+ * - 已撤回消息（`recalledAt` 有值）显式排除，与服务端缺陷对齐；
+ * - 被屏蔽用户的消息排除。
  *
  * @returns 重算后的未读数；若本地时间线为空（未加载）返回 null，调用方应回退服务端值。
  */
@@ -45,6 +48,7 @@ export function recomputeChannelUnread(input: RecomputeChannelUnreadInput): numb
   let count = 0;
   for (const message of list) {
     if (message.recalledAt != null && message.recalledAt > 0) continue;
+    if (input.blockedUserIds?.has(String(message.from?.id ?? ""))) continue;
     if (isMessageAfterReadMarker(message.timeMs ?? 0, message.id ?? "", input.lastReadTimeMs, input.lastReadMessageId)) {
       count += 1;
     }
@@ -6,6 +6,8 @@
  * - WS 连接与事件路由；
  * - polling fallback；
  * - resume failed catch-up；
+ * - 连接 Channel 缺帧后的当前频道补拉；
+ * - 连接就绪后同步服务器自定义表情目录；
  * - 自动刷新与 session 监听的绑定/释放。
  *
  * 读取方式：
@@ -14,7 +16,12 @@
  */
 
 import { onAuthSessionChanged, startAuthSessionAutoRefresh } from "@/shared/net/auth/api";
+import { listenConnectionFrameGap, type ConnectionFrameGapEvent } from "@/shared/tauri/events";
 import { toHttpOrigin } from "@/shared/net/http/serverOrigin";
+import { buildTauriTlsArgs } from "@/shared/net/tls/tauriTlsArgs";
+import { syncChannelDeltaIfSupported } from "@/shared/db/deltaSync";
+import { invokeTauri } from "@/shared/tauri/invokeClient";
+import { TAURI_COMMANDS } from "@/shared/tauri/commands";
 import type { ChatEventEnvelope } from "@/features/chat/domain/types/chatEventModels";
 import {
   createPollingFallback,
@@ -83,12 +90,95 @@ export function createChatSessionConnectionRuntime(
     refreshChannelLatestPage: messageFlow.refreshChannelLatestPage,
   });
 
+  let stopFrameGapListener: (() => void) | null = null;
+
+  /**
+   * 本窗口缺帧后补拉当前频道（缺失的帧不会补投）。
+   *
+   * Rust 侧已为聚焦频道发放补齐票据（`resyncChannelId` 非空）时由预取链路处理，这里不重复拉取。
+   */
+  function handleFrameGap(gap: ConnectionFrameGapEvent): void {
+    const socket = scope.getActiveServerSocket();
+    if (!socket || gap.serverSocket.trim() !== socket || gap.resyncChannelId) return;
+    const cid = currentChannelId.value.trim();
+    logger.info("Action: chat_frame_gap_resync", { socket, cid, missing: gap.receivedSeq - gap.expectedSeq });
+    void governance.refreshChannels().catch((e) => {
+      logger.warn("Action: chat_frame_gap_refresh_channels_failed", { socket, error: String(e) });
+    });
+    if (!cid) return;
+    void messageFlow.refreshChannelLatestPage(cid).catch((e) => {
+      logger.warn("Action: chat_frame_gap_refresh_latest_failed", { cid, error: String(e) });
+    });
+  }
+
+  function ensureFrameGapListener(): void {
+    if (stopFrameGapListener) return;
+    let disposed = false;
+    let unlisten: (() => void) | null = null;
+    stopFrameGapListener = () => {
+      disposed = true;
+      unlisten?.();
+      stopFrameGapListener = null;
+    };
+    void listenConnectionFrameGap((event) => handleFrameGap(event.payload))
+      .then((fn) => {
+        if (disposed) fn();
+        else unlisten = fn;
+      })
+      .catch((e) => {
+        logger.warn("Action: chat_frame_gap_listen_failed", { error: String(e) });
+      });
+  }
+
+  let emojiSyncedSocket = "";
+
+  /**
+   * 同步服务器自定义表情目录（每个 server 在一次会话内只同步一次；Rust 侧按 ETag 条件请求）。
+   *
+   * 目录变化时 Rust 广播 `server-emojis-changed`，表情面板据此刷新；失败只记录日志。
+   */
+  async function syncServerEmojis(): Promise<void> {
+    const [socket, token] = await scope.getSocketAndValidToken();
+    if (!socket || !token || socket === emojiSyncedSocket) return;
+    emojiSyncedSocket = socket;
+    try {
+      await invokeTauri(TAURI_COMMANDS.syncServerEmojis, {
+        options: {
+          serverSocket: socket,
+          headers: { Authorization: `Bearer ${token}` },
+          ...buildTauriTlsArgs(socket),
+        },
+      });
+    } catch (e) {
+      emojiSyncedSocket = "";
+      logger.warn("Action: chat_server_emoji_sync_failed", { socket, error: String(e) });
+    }
+  }
+
   function teardownConnectionLifecycle(): void {
+    emojiSyncedSocket = "";
+    stopFrameGapListener?.();
     wsManager.close();
     polling.stop();
     connectionApplicationService.teardownSessionHooks();
   }
 
+  /**
+   * 按变更游标把频道的新增/编辑/删除同步进本地 server DB（服务端不支持时跳过）。
+   */
+  async function syncChannelDelta(cid: string): Promise<void> {
+    const channelId = Number(cid);
+    if (!Number.isSafeInteger(channelId)) return;
+    const [socket, token] = await scope.getSocketAndValidToken();
+    if (!socket || !token) return;
+    const result = await syncChannelDeltaIfSupported(channelId, {
+      serverSocket: socket,
+      headers: { Authorization: `Bearer ${token}` },
+      ...buildTauriTlsArgs(socket),
+    });
+    if (result?.cursorReset) logger.info("Action: chat_catch_up_delta_cursor_reset", { cid });
+  }
+
   const catchUpAfterResumeFailed = createResumeFailedCatchUp({
     logger,
     getActiveServerSocket: scope.getActiveServerSocket,
@@ -97,6 +187,7 @@ export function createChatSessionConnectionRuntime(
     listChannels: () => channelsRef.value,
     refreshChannels: governance.refreshChannels,
     refreshChannelLatestPage: messageFlow.refreshChannelLatestPage,
+    syncChannelDelta,
     refreshMembersRail: governance.refreshMembersRail,
     prefetchLimit: CATCH_UP_PREFETCH_LIMIT,
   });
@@ -135,7 +226,11 @@ export function createChatSessionConnectionRuntime(
   });
 
   return {
-    ensureChatReady: () => connectionApplicationService.ensureChatReady(),
+    ensureChatReady: async () => {
+      ensureFrameGapListener();
+      await connectionApplicationService.ensureChatReady();
+      void syncServerEmojis();
+    },
     teardownConnectionLifecycle,
   };
 }
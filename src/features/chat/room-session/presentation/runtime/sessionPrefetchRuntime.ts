@@ -0,0 +1,141 @@
+/**
+ * @fileoverview chat 会话频道预取运行时。
+ * @description
+ * 把频道导航与消息加载接入 Rust 侧的预取调度（`set_active_channel` 等命令）：
+ * - 当前频道变化时上报聚焦频道（缺帧定向补齐依赖它）；
+ * - 频道列表刷新后把有未读的频道以 warm 优先级入队；
+ * - 收到 `channel-prefetch-ready` 票据后拉取该频道最新页，完成或失败后归还票据。
+ *
+ * 说明：
+ * - 预取是尽力而为：任何失败只记录日志，不影响频道切换与消息加载；
+ * - 聚焦票据对应的频道已由频道切换加载过时直接归还，不重复拉取。
+ */
+
+import { watch, type WatchStopHandle } from "vue";
+import { invokeTauri } from "@/shared/tauri/invokeClient";
+import { TAURI_COMMANDS } from "@/shared/tauri/commands";
+import { listenChannelPrefetchReady, type ChannelPrefetchReadyEvent } from "@/shared/tauri/events";
+import type { ChatRuntimeScopePort } from "@/features/chat/composition/contracts/chatScopePort";
+import type { ChatMessageFlowRuntimePort } from "@/features/chat/message-flow/presentation/runtime/messageFlowRuntimePorts";
+import type { ChatSessionStateSlice } from "./sessionRuntimePorts";
+
+type LoggerLike = {
+  warn(message: string, payload?: Record<string, unknown>): void;
+};
+
+/**
+ * 会话预取 runtime 装配依赖。
+ */
+export type ChatSessionPrefetchRuntimeDeps = {
+  logger: LoggerLike;
+  channelsRef: ChatSessionStateSlice["channelsRef"];
+  currentChannelId: ChatSessionStateSlice["currentChannelId"];
+  messagesByChannel: ChatSessionStateSlice["messagesByChannel"];
+  messageFlow: ChatMessageFlowRuntimePort;
+  scope: ChatRuntimeScopePort;
+};
+
+/**
+ * 会话预取 runtime 端口。
+ */
+export type ChatSessionPrefetchRuntimePort = {
+  start(): void;
+  stop(): void;
+};
+
+/**
+ * 创建会话频道预取 runtime。
+ */
+export function createChatSessionPrefetchRuntime(deps: ChatSessionPrefetchRuntimeDeps): ChatSessionPrefetchRuntimePort {
+  const { logger, channelsRef, currentChannelId, messagesByChannel, messageFlow, scope } = deps;
+
+  let stopWatchers: WatchStopHandle[] = [];
+  let disposeListener: (() => void) | null = null;
+  let started = false;
+  let reportedSocket = "";
+
+  function reportActiveChannel(channelId: string): void {
+    const socket = scope.getActiveServerSocket();
+    if (!socket) return;
+    reportedSocket = socket;
+    const args = { serverSocket: socket, channelId: channelId || null };
+    void invokeTauri(TAURI_COMMANDS.setActiveChannel, args).catch((e) => {
+      logger.warn("Action: chat_prefetch_set_active_failed", { ...args, error: String(e) });
+    });
+  }
+
+  function enqueueUnreadChannels(): void {
+    const socket = scope.getActiveServerSocket();
+    if (!socket) return;
+    const current = currentChannelId.value.trim();
+    const channelIds = channelsRef.value
+      .filter((c) => (c.unread ?? 0) > 0 && String(c.id).trim() !== current)
+      .map((c) => String(c.id).trim())
+      .filter(Boolean);
+    if (channelIds.length === 0) return;
+    const args = { serverSocket: socket, channelIds, priority: "warm" };
+    void invokeTauri(TAURI_COMMANDS.enqueueChannelPrefetch, args).catch((e) => {
+      logger.warn("Action: chat_prefetch_enqueue_failed", { ...args, error: String(e) });
+    });
+  }
+
+  async function handleTicket(ticket: ChannelPrefetchReadyEvent): Promise<void> {
+    const socket = scope.getActiveServerSocket();
+    // 票据按 server 广播到所有窗口；非本窗口当前 server 的票据交给对应窗口处理。
+    if (!socket || ticket.server_socket.trim() !== socket) return;
+    const channelId = ticket.channel_id.trim();
+    const alreadyLoaded = ticket.priority === "focused" && (messagesByChannel[channelId]?.length ?? 0) > 0;
+    try {
+      if (!alreadyLoaded) await messageFlow.refreshChannelLatestPage(channelId);
+    } catch (e) {
+      logger.warn("Action: chat_prefetch_load_failed", { socket, channelId, priority: ticket.priority, error: String(e) });
+    } finally {
+      void invokeTauri(TAURI_COMMANDS.completeChannelPrefetch, { ticket: ticket.ticket }).catch((e) => {
+        logger.warn("Action: chat_prefetch_complete_failed", { ticket: ticket.ticket, error: String(e) });
+      });
+    }
+  }
+
+  function start(): void {
+    if (started) return;
+    started = true;
+    let disposed = false;
+    let unlisten: (() => void) | null = null;
+    disposeListener = () => {
+      disposed = true;
+      unlisten?.();
+    };
+    void listenChannelPrefetchReady((event) => {
+      void handleTicket(event.payload);
+    })
+      .then((fn) => {
+        if (disposed) fn();
+        else unlisten = fn;
+      })
+      .catch((e) => {
+        logger.warn("Action: chat_prefetch_listen_failed", { error: String(e) });
+      });
+    stopWatchers = [
+      watch(currentChannelId, (channelId) => reportActiveChannel(channelId.trim()), { immediate: true }),
+      watch(channelsRef, enqueueUnreadChannels, { immediate: true }),
+    ];
+  }
+
+  function stop(): void {
+    if (!started) return;
+    started = false;
+    for (const stopWatcher of stopWatchers) stopWatcher();
+    stopWatchers = [];
+    disposeListener?.();
+    disposeListener = null;
+    if (reportedSocket) {
+      const serverSocket = reportedSocket;
+      void invokeTauri(TAURI_COMMANDS.clearChannelPrefetch, { serverSocket }).catch((e) => {
+        logger.warn("Action: chat_prefetch_clear_failed", { serverSocket, error: String(e) });
+      });
+      reportedSocket = "";
+    }
+  }
+
+  return { start, stop };
+}
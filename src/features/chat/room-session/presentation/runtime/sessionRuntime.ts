@@ -3,6 +3,8 @@
  * @description
  * 聚合 room-session 对外公开的会话能力：
  * - 连接生命周期运行时；
+ * - 频道预取调度；
+ * - 正在输入上报；
  * - 频道视图动作；
  * - 切服后的状态重置。
  *
@@ -22,6 +24,8 @@ import type { ChatRuntimeScopePort } from "@/features/chat/composition/contracts
 import type { ChatMessageFlowRuntimePort } from "@/features/chat/message-flow/presentation/runtime/messageFlowRuntimePorts";
 import type { ChatGovernanceRuntimePort } from "@/features/chat/room-governance/presentation/runtime/governanceRuntimePorts";
 import { createChatSessionConnectionRuntime } from "./sessionConnectionRuntime";
+import { createChatSessionPrefetchRuntime } from "./sessionPrefetchRuntime";
+import { createChatSessionTypingRuntime } from "./sessionTypingRuntime";
 import type { ChatSessionRuntimePort, ChatSessionStateSlice } from "./sessionRuntimePorts";
 import { createRoomSessionStatePort } from "./sessionStatePorts";
 
@@ -127,6 +131,20 @@ export function createChatSessionRuntime(deps: ChatSessionRuntimeDeps): ChatSess
     scope,
     onWsEvent,
   });
+  const prefetchRuntime = createChatSessionPrefetchRuntime({
+    logger,
+    channelsRef,
+    currentChannelId,
+    messagesByChannel,
+    messageFlow,
+    scope,
+  });
+  const typingRuntime = createChatSessionTypingRuntime({
+    logger,
+    currentChannelId,
+    composerDraft,
+    scope,
+  });
 
   // 频道切换、读状态推进属于“当前频道视图动作”，和底层连接生命周期拆开装配。
   /**
@@ -148,6 +166,8 @@ export function createChatSessionRuntime(deps: ChatSessionRuntimeDeps): ChatSess
    * - 因此它天然属于 session 这个更高层的协调点。
    */
   function resetForServerChange(): void {
+    prefetchRuntime.stop();
+    typingRuntime.stop();
     resetRoomSessionState({
       teardownConnectionLifecycle: connectionRuntime.teardownConnectionLifecycle,
       state: sessionState,
@@ -155,7 +175,11 @@ export function createChatSessionRuntime(deps: ChatSessionRuntimeDeps): ChatSess
   }
 
   return {
-    ensureChatReady: connectionRuntime.ensureChatReady,
+    ensureChatReady: async () => {
+      await connectionRuntime.ensureChatReady();
+      prefetchRuntime.start();
+      typingRuntime.start();
+    },
     resetForServerChange,
     getMessageById: (channelId, messageId) => viewApplicationService.getMessageById(channelId, messageId),
     selectChannel: (channelId) => viewApplicationService.selectChannel(channelId),
@@ -14,6 +14,8 @@
 import type { ChatReadStateReporterPort } from "@/features/chat/domain/ports/runtimePorts";
 import { readAuthToken } from "@/shared/utils/localState";
 import { ensureValidAccessToken } from "@/shared/net/auth/api";
+import { getBlockedUserIds } from "@/shared/db/blockedUsers";
+import { setChannelReadState } from "@/shared/db/favorites";
 import { getActiveChatServerSocket } from "@/features/chat/composition/serverWorkspaceAdapter";
 import { createReadStateReporter, RoomSessionCatalogApplicationService } from "@/features/chat/room-session/internal";
 import type { ChatApiGateway } from "@/features/chat/composition/contracts/chatGateway";
@@ -120,6 +122,11 @@ export function createChatSessionSharedContext(
     scope,
     state: readMarkerState,
     messageCache,
+    async cacheReadState(serverSocket: string, cid: string, lastReadTimeMs: number): Promise<void> {
+      const channelId = Number(cid);
+      if (!Number.isSafeInteger(channelId)) return;
+      await setChannelReadState(serverSocket, channelId, Math.trunc(lastReadTimeMs));
+    },
   });
 
   /**
@@ -132,6 +139,7 @@ export function createChatSessionSharedContext(
     directoryState,
     readMarkerState,
     messageCache,
+    getBlockedUserIds,
   });
 
   return {
@@ -0,0 +1,94 @@
+/**
+ * @fileoverview chat 会话正在输入上报运行时。
+ * @description
+ * 把 composer 草稿变化接入 Rust 侧的 typing 节流（`notify_typing` / `reset_typing`）：
+ * - 同一频道内草稿变为非空内容时询问 Rust 是否放行，放行则发送一帧 `/channels/typing`；
+ * - 同一频道内草稿被清空（发送完成或手动清空）时重置节流，下次输入立即放行；
+ * - 切换频道导致的草稿恢复不视为输入，不上报。
+ *
+ * 说明：
+ * - typing 是尽力而为的提示：任何失败只记录日志，不影响输入与发送；
+ * - 帧格式见 `docs/api/15-tcp-frame-protocol-v1.md` §6。
+ */
+
+import { watch, type WatchStopHandle } from "vue";
+import { invokeTauri } from "@/shared/tauri/invokeClient";
+import { TAURI_COMMANDS } from "@/shared/tauri/commands";
+import { getTcpService } from "@/shared/net/tcp/tcpServiceProvider";
+import type { ChatRuntimeScopePort } from "@/features/chat/composition/contracts/chatScopePort";
+import type { ChatSessionStateSlice } from "./sessionRuntimePorts";
+
+type LoggerLike = {
+  debug(message: string, payload?: Record<string, unknown>): void;
+};
+
+/**
+ * 出站 typing 帧路由（与握手能力 `channels/typing` 对应）。
+ */
+export const TYPING_ROUTE = "/channels/typing";
+
+/**
+ * 会话 typing runtime 装配依赖。
+ */
+export type ChatSessionTypingRuntimeDeps = {
+  logger: LoggerLike;
+  currentChannelId: ChatSessionStateSlice["currentChannelId"];
+  composerDraft: ChatSessionStateSlice["composerDraft"];
+  scope: ChatRuntimeScopePort;
+};
+
+/**
+ * 会话 typing runtime 端口。
+ */
+export type ChatSessionTypingRuntimePort = {
+  start(): void;
+  stop(): void;
+};
+
+/**
+ * 创建会话正在输入上报 runtime。
+ */
+export function createChatSessionTypingRuntime(deps: ChatSessionTypingRuntimeDeps): ChatSessionTypingRuntimePort {
+  const { logger, currentChannelId, composerDraft, scope } = deps;
+
+  let stopWatcher: WatchStopHandle | null = null;
+
+  async function notifyTyping(serverSocket: string, channelId: string): Promise<void> {
+    try {
+      const shouldSend = await invokeTauri<boolean>(TAURI_COMMANDS.notifyTyping, { serverSocket, channelId });
+      if (!shouldSend) return;
+      const service = getTcpService(serverSocket);
+      if (!service) return;
+      await service.send(serverSocket, JSON.stringify({ route: TYPING_ROUTE, data: { cid: channelId, typing: true } }));
+    } catch (e) {
+      logger.debug("Action: chat_typing_notify_failed", { serverSocket, channelId, error: String(e) });
+    }
+  }
+
+  function resetTyping(serverSocket: string, channelId: string): void {
+    void invokeTauri(TAURI_COMMANDS.resetTyping, { serverSocket, channelId }).catch((e) => {
+      logger.debug("Action: chat_typing_reset_failed", { serverSocket, channelId, error: String(e) });
+    });
+  }
+
+  function handleDraftChange([draft, channelId]: [string, string], [prevDraft, prevChannelId]: [string, string]): void {
+    const cid = channelId.trim();
+    if (!cid || cid !== prevChannelId.trim() || draft === prevDraft) return;
+    const serverSocket = scope.getActiveServerSocket();
+    if (!serverSocket) return;
+    if (draft.trim()) void notifyTyping(serverSocket, cid);
+    else if (prevDraft.trim()) resetTyping(serverSocket, cid);
+  }
+
+  function start(): void {
+    if (stopWatcher) return;
+    stopWatcher = watch(() => [composerDraft.value, currentChannelId.value] as [string, string], handleDraftChange);
+  }
+
+  function stop(): void {
+    stopWatcher?.();
+    stopWatcher = null;
+  }
+
+  return { start, stop };
+}
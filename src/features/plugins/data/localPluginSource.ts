@@ -26,6 +26,7 @@ export function getLocalVoiceCallRuntimeEntry(serverId: string): PluginRuntimeEn
     permissions: ["invoke", "events", "ui", "storage"],
     providesDomains: [{ domain: "call_record", domainVersion: "1" }],
     minHostVersion: "0.0.0",
+    locales: [],
   };
 }
 
@@ -19,6 +19,7 @@ export type RawPluginRuntimeEntry = {
   permissions: string[];
   providesDomains: RawRuntimeProvidesDomain[];
   minHostVersion: string;
+  locales?: string[];
 };
 
 function toTrimmedString(value: unknown): string {
@@ -41,5 +42,6 @@ export function mapPluginRuntimeEntry(input: RawPluginRuntimeEntry): PluginRunti
     permissions: Array.isArray(input.permissions) ? input.permissions.map((item) => toTrimmedString(item)).filter(Boolean) : [],
     providesDomains: Array.isArray(input.providesDomains) ? input.providesDomains.map(mapRuntimeProvidesDomain) : [],
     minHostVersion: toTrimmedString(input.minHostVersion),
+    locales: Array.isArray(input.locales) ? input.locales.map((item) => toTrimmedString(item)).filter(Boolean) : [],
   };
 }
@@ -23,7 +23,13 @@ import { buildTauriTlsArgs } from "@/shared/net/tls/tauriTlsArgs";
 import type { PluginInstallQueryPort } from "@/features/plugins/domain/ports/PluginInstallQueryPort";
 import type { PluginLifecycleCommandPort } from "@/features/plugins/domain/ports/PluginLifecycleCommandPort";
 import { createPluginOperationError } from "@/features/plugins/domain/errors/PluginOperationError";
-import type { InstalledPluginState, PluginProgress, PluginProgressHandler } from "@/features/plugins/domain/types/pluginTypes";
+import type {
+  InstalledPluginState,
+  PluginInstallOptions,
+  PluginProgress,
+  PluginProgressHandler,
+  PluginTrustLevel,
+} from "@/features/plugins/domain/types/pluginTypes";
 
 type RustInstalledPluginState = {
   pluginId: string;
@@ -32,6 +38,7 @@ type RustInstalledPluginState = {
   enabled: boolean;
   status: "ok" | "failed" | string;
   lastError: string;
+  trustLevel?: PluginTrustLevel;
 };
 
 const logger = createLogger("tauriPluginManager");
@@ -50,6 +57,7 @@ function mapInstalledState(raw: RustInstalledPluginState): InstalledPluginState
     enabled: Boolean(raw.enabled),
     status: raw.status === "failed" ? "failed" : "ok",
     lastError: String(raw.lastError ?? ""),
+    trustLevel: raw.trustLevel ?? "unverified",
   };
 }
 
@@ -105,6 +113,7 @@ async function install(
   pluginId: string,
   version: string,
   onProgress?: PluginProgressHandler,
+  options?: PluginInstallOptions,
 ): Promise<InstalledPluginState> {
   const socket = serverSocket.trim();
   const id = pluginId.trim();
@@ -119,6 +128,7 @@ async function install(
       serverSocket: socket,
       pluginId: id,
       version: v || undefined,
+      confirmUntrusted: Boolean(options?.confirmUntrusted),
       ...buildTauriTlsArgs(socket),
     });
     emitProgress(id, "installed", 100, "Installed", onProgress);
@@ -137,6 +147,7 @@ async function installFromUrl(
   url: string,
   sha256: string,
   onProgress?: PluginProgressHandler,
+  options?: PluginInstallOptions,
 ): Promise<InstalledPluginState> {
   const socket = serverSocket.trim();
   const id = pluginId.trim();
@@ -160,6 +171,7 @@ async function installFromUrl(
       version: v,
       url: u,
       sha256: sum,
+      confirmUntrusted: Boolean(options?.confirmUntrusted),
       ...buildTauriTlsArgs(socket),
     });
     emitProgress(id, "installed", 100, "Installed", onProgress);
@@ -239,6 +251,14 @@ async function clearError(serverSocket: string, pluginId: string): Promise<Insta
   return mapInstalledState(raw);
 }
 
+async function markLoaded(serverSocket: string, pluginId: string, version: string): Promise<void> {
+  const socket = serverSocket.trim();
+  const id = pluginId.trim();
+  const v = version.trim();
+  if (!socket || !id || !v) return;
+  await invokeTauri<void>(TAURI_COMMANDS.pluginsMarkLoaded, { serverSocket: socket, pluginId: id, version: v, ...buildTauriTlsArgs(socket) });
+}
+
 async function uninstall(serverSocket: string, pluginId: string): Promise<void> {
   const socket = serverSocket.trim();
   const id = pluginId.trim();
@@ -265,5 +285,6 @@ export const tauriPluginLifecycleCommandAdapter: PluginLifecycleCommandPort = {
   disable,
   setFailed,
   clearError,
+  markLoaded,
   uninstall,
 };
@@ -3,7 +3,7 @@
  * @description plugins｜领域端口：插件生命周期命令（Command）。
  */
 
-import type { InstalledPluginState, PluginProgressHandler } from "../types/pluginTypes";
+import type { InstalledPluginState, PluginInstallOptions, PluginProgressHandler } from "../types/pluginTypes";
 
 /**
  * 插件生命周期命令端口（写能力）。
@@ -17,6 +17,7 @@ export interface PluginLifecycleCommandPort {
     pluginId: string,
     version: string,
     onProgress?: PluginProgressHandler,
+    options?: PluginInstallOptions,
   ): Promise<InstalledPluginState>;
 
   /**
@@ -29,6 +30,7 @@ export interface PluginLifecycleCommandPort {
     url: string,
     sha256: string,
     onProgress?: PluginProgressHandler,
+    options?: PluginInstallOptions,
   ): Promise<InstalledPluginState>;
 
   /**
@@ -61,6 +63,11 @@ export interface PluginLifecycleCommandPort {
    */
   clearError(serverSocket: string, pluginId: string): Promise<InstalledPluginState>;
 
+  /**
+   * 上报运行时加载成功，把该版本记为 known-good（清零连续失败次数）。
+   */
+  markLoaded(serverSocket: string, pluginId: string, version: string): Promise<void>;
+
   /**
    * 卸载插件。
    */
@@ -98,6 +98,11 @@ export type PluginContext = {
     invoke?: <T = unknown>(command: string, args?: Record<string, unknown>) => Promise<T>;
     /** 订阅宿主 Tauri 事件（权限 + 事件白名单），返回取消函数 */
     onEvent?: <T = unknown>(event: string, handler: (payload: T) => void) => () => void;
+    /** 插件间消息总线（权限 "bus"；只能发布到自身 domain 下的 topic） */
+    bus?: {
+      publish(topic: string, payload?: unknown): Promise<string[]>;
+      subscribe<T = unknown>(topic: string, handler: (payload: T, meta: { topic: string; from: string }) => void): () => void;
+    };
     /** 挂载全局浮层组件，返回卸载函数与组件实例句柄 */
     mountOverlay?: (component: Component, opts?: { zIndex?: number; props?: Record<string, unknown> }) => PluginOverlayMountHandle;
     /** 注册聊天头部/工具栏入口，返回注销函数 */
@@ -211,6 +211,10 @@ export type PluginRuntimeEntry = {
   permissions: string[];
   providesDomains: RuntimeProvidesDomain[];
   minHostVersion: string;
+  /**
+   * 插件包携带的语言（`locales/*.json`，小写 `-` 分隔，如 `zh-cn`）。
+   */
+  locales: string[];
 };
 
 /**
@@ -223,6 +227,33 @@ export type InstalledPluginState = {
   enabled: boolean;
   status: "ok" | "failed";
   lastError: string;
+  /**
+   * 是否为开发态链接插件（本地目录，版本固定为 `dev`）。
+   */
+  dev?: boolean;
+  /**
+   * 开发态插件的本地源目录。
+   */
+  devPath?: string | null;
+  /**
+   * 当前版本安装时确定的信任级别（由 Rust 侧按目录签名判定）。
+   */
+  trustLevel?: PluginTrustLevel;
+};
+
+/**
+ * 插件来源信任级别：官方 / 已审核 / 未验证（自定义来源总是未验证）。
+ */
+export type PluginTrustLevel = "first_party" | "verified" | "unverified";
+
+/**
+ * 安装选项。
+ */
+export type PluginInstallOptions = {
+  /**
+   * 用户已确认安装未经验证的插件（Rust 侧对未验证来源要求该标记）。
+   */
+  confirmUntrusted?: boolean;
 };
 
 /**
@@ -8,6 +8,7 @@ import {
   resolvePluginCatalogVersionEntry,
   type InstalledPluginState,
   type PluginCatalogEntryLike,
+  type PluginInstallOptions,
   type PluginProgress,
 } from "../types/pluginTypes";
 import type { PluginLifecycleCommandPort } from "../ports/PluginLifecycleCommandPort";
@@ -44,6 +45,7 @@ export type UpdateToLatestInput = {
   latestVersion: string;
   before: InstalledPluginState | null;
   onProgress?: (p: PluginProgress) => void;
+  options?: PluginInstallOptions;
 };
 
 /**
@@ -138,8 +140,9 @@ export class ApplyPluginRuntimeOps {
             String(resolved?.downloadUrl ?? input.plugin.downloadUrl ?? ""),
             String(resolved?.sha256 ?? input.plugin.sha256 ?? ""),
             input.onProgress,
+            input.options,
           )
-        : await this.commandPort.install(input.serverSocket, id, v, input.onProgress);
+        : await this.commandPort.install(input.serverSocket, id, v, input.onProgress, input.options);
 
     if (this.runtime.supported) {
       await this.runtime.validateVersion(id, v);
@@ -195,6 +195,10 @@ async function clearError(serverSocket: string, pluginId: string): Promise<Insta
   return stateToInstalled(pluginId, current[pluginId]);
 }
 
+async function markLoaded(): Promise<void> {
+  // mock 不维护 known-good 历史。
+}
+
 async function uninstall(serverSocket: string, pluginId: string): Promise<void> {
   await sleep(Math.min(220, MOCK_LATENCY_MS));
   const current = getMockPluginsState(serverSocket);
@@ -221,5 +225,6 @@ export const mockPluginLifecycleCommandAdapter: PluginLifecycleCommandPort = {
   disable,
   setFailed,
   clearError,
+  markLoaded,
   uninstall,
 };
@@ -9,6 +9,7 @@ import { invokeTauri } from "@/shared/tauri";
 import { TAURI_COMMANDS } from "@/shared/tauri/commands";
 import { createPluginInvokeApi } from "./pluginInvokeApi";
 import { createPluginEventApi } from "./pluginEventApi";
+import { createPluginBusApi } from "./pluginBusApi";
 import { createPluginUiApi, type PluginUiBridge } from "./pluginUiApi";
 
 export type TauriFetchResponse = {
@@ -63,6 +64,7 @@ export function createPluginNetworkApi(serverSocket: string): NonNullable<Plugin
  * - `invoke` / `onEvent` 分别由 "invoke" / "events" 权限门控，且命令/事件均以
  *   白名单前缀（目前固定为 "voice_call:"）约束，杜绝越权调用；
  * - `mountOverlay` / `registerToolbarAction` 由 "ui" 权限 + 宿主 UI 桥共同门控。
+ * - `bus` 由 "bus" 权限门控；发布的 topic 前缀与收件人由 Rust 侧校验/计算。
  *
  * 注：`sendMessage` 依赖宿主运行时桥（非纯数据），无法仅凭 serverSocket/pluginId 构造，
  * 故由调用方（domainRegistryContext）传入。
@@ -93,6 +95,9 @@ export function createHostApi(
   if (permissions.includes("events")) {
     host.onEvent = createPluginEventApi("voice_call:") as never;
   }
+  if (permissions.includes("bus")) {
+    host.bus = createPluginBusApi(serverSocket, pluginId);
+  }
   if (permissions.includes("ui") && uiBridge) {
     const ui = createPluginUiApi(uiBridge);
     host.mountOverlay = ui.mountOverlay as never;
@@ -0,0 +1,43 @@
+import { describe, it, expect, vi } from "vitest";
+
+vi.mock("@/shared/tauri", () => ({
+  invokeTauri: vi.fn(async (command: string) => (command === "plugins_bus_open" ? "token-1" : [])),
+}));
+vi.mock("@/shared/tauri/events", () => ({
+  listenPluginBusMessage: vi.fn(async () => () => {}),
+}));
+
+import { invokeTauri } from "@/shared/tauri";
+import { busTopicMatches, createPluginBusApi } from "./pluginBusApi";
+
+describe("pluginBusApi", () => {
+  it("按段匹配订阅模式", () => {
+    expect(busTopicMatches("emoji/packs", "emoji/packs")).toBe(true);
+    expect(busTopicMatches("emoji/*", "emoji/packs/added")).toBe(true);
+    expect(busTopicMatches("emoji/*", "emoji")).toBe(false);
+    expect(busTopicMatches("emoji/*", "emojis/packs")).toBe(false);
+  });
+  it("订阅返回取消函数", () => {
+    const bus = createPluginBusApi("tcp://127.0.0.1:7000", "composer");
+    const off = bus.subscribe("emoji/*", () => {});
+    expect(typeof off).toBe("function");
+    off();
+  });
+  it("发布只携带桥接签发的会话令牌", async () => {
+    const bus = createPluginBusApi("tcp://127.0.0.1:7000", "renderer");
+    await bus.publish("renderer/ready", { ok: true });
+    await bus.publish("renderer/ready");
+    const calls = vi.mocked(invokeTauri).mock.calls;
+    const opens = calls.filter(
+      ([command, args]) => command === "plugins_bus_open" && (args as { pluginId?: string })?.pluginId === "renderer",
+    );
+    expect(opens).toHaveLength(1);
+    const publishes = calls.filter(([command]) => command === "plugins_bus_publish");
+    expect(publishes.at(-1)?.[1]).toEqual({
+      serverSocket: "tcp://127.0.0.1:7000",
+      busToken: "token-1",
+      topic: "renderer/ready",
+      payload: null,
+    });
+  });
+});
@@ -0,0 +1,147 @@
+/**
+ * @fileoverview 插件间消息总线能力工厂。
+ * @description plugins｜runtime：`bus` 权限门控的 publish/subscribe（Rust 侧校验 topic 前缀并计算收件人）。
+ *
+ * 身份由宿主桥接确定：首次使用时以 `plugins_bus_open` 换取会话令牌，之后只携带令牌，
+ * 插件代码无法冒充其它插件发布或订阅。
+ */
+
+import type { UnlistenFn } from "@tauri-apps/api/event";
+import { buildTauriTlsArgs } from "@/shared/net/tls/tauriTlsArgs";
+import { invokeTauri } from "@/shared/tauri";
+import { TAURI_COMMANDS } from "@/shared/tauri/commands";
+import { listenPluginBusMessage } from "@/shared/tauri/events";
+import { createLogger } from "@/shared/utils/logger";
+
+export type PluginBusApi = {
+  /** 发布到插件自身 domain 下的 topic，返回收到消息的插件 id。 */
+  publish(topic: string, payload?: unknown): Promise<string[]>;
+  /** 订阅 topic（`<topic>` 精确匹配，`<prefix>/*` 匹配子 topic），返回取消函数。 */
+  subscribe<T = unknown>(topic: string, handler: (payload: T, meta: { topic: string; from: string }) => void): () => void;
+};
+
+const logger = createLogger("pluginBusApi");
+
+/** 每个插件的本地监听（key: `${serverSocket}\n${pluginId}`），用于插件卸载时统一清理。 */
+const listenersByPlugin = new Map<string, Set<() => void>>();
+/** 每个插件的总线会话令牌（key 同上）；同一 webview 内每个插件只有一个会话。 */
+const sessionsByPlugin = new Map<string, Promise<string>>();
+
+function pluginKey(serverSocket: string, pluginId: string): string {
+  return `${serverSocket}\n${pluginId}`;
+}
+
+/**
+ * 取得（必要时打开）插件的总线会话令牌；打开失败时不缓存，下次调用重试。
+ */
+function busSession(serverSocket: string, pluginId: string): Promise<string> {
+  const key = pluginKey(serverSocket, pluginId);
+  const existing = sessionsByPlugin.get(key);
+  if (existing) return existing;
+  const session = invokeTauri<string>(TAURI_COMMANDS.pluginsBusOpen, {
+    serverSocket,
+    pluginId,
+    ...buildTauriTlsArgs(serverSocket),
+  });
+  sessionsByPlugin.set(key, session);
+  session.catch(() => {
+    if (sessionsByPlugin.get(key) === session) sessionsByPlugin.delete(key);
+  });
+  return session;
+}
+
+/**
+ * 与 Rust 侧一致的订阅匹配规则。
+ */
+export function busTopicMatches(pattern: string, topic: string): boolean {
+  if (!pattern.endsWith("/*")) return pattern === topic;
+  const prefix = pattern.slice(0, -2);
+  return topic.startsWith(`${prefix}/`);
+}
+
+/**
+ * 创建插件总线能力。
+ *
+ * 说明：
+ * - 发布/订阅均携带会话令牌，Rust 侧据此确定插件身份；
+ * - 订阅先在 Rust 侧登记（决定收件人），再监听 `plugins-bus-message` 并按 `recipients` 过滤；
+ * - 底层 `listen` 异步返回 unlisten，用 `cancelled` 处理「先取消、后拿到 unlisten」的竞态。
+ *
+ * @param serverSocket 当前 server socket。
+ * @param pluginId 插件标识。
+ */
+export function createPluginBusApi(serverSocket: string, pluginId: string): PluginBusApi {
+  const key = pluginKey(serverSocket, pluginId);
+  return {
+    publish(topic: string, payload?: unknown): Promise<string[]> {
+      return busSession(serverSocket, pluginId).then((busToken) =>
+        invokeTauri<string[]>(TAURI_COMMANDS.pluginsBusPublish, {
+          serverSocket,
+          busToken,
+          topic: String(topic ?? "").trim(),
+          payload: payload ?? null,
+        }),
+      );
+    },
+    subscribe<T = unknown>(topic: string, handler: (payload: T, meta: { topic: string; from: string }) => void): () => void {
+      const pattern = String(topic ?? "").trim();
+      let unlisten: UnlistenFn | null = null;
+      let cancelled = false;
+      const listeners = listenersByPlugin.get(key) ?? new Set<() => void>();
+      listenersByPlugin.set(key, listeners);
+
+      const dispose = (): void => {
+        if (cancelled) return;
+        cancelled = true;
+        unlisten?.();
+        listeners.delete(dispose);
+        if (listeners.size === 0) listenersByPlugin.delete(key);
+        const session = sessionsByPlugin.get(key);
+        if (!session) return;
+        void session
+          .then((busToken) => invokeTauri<void>(TAURI_COMMANDS.pluginsBusUnsubscribe, { busToken, topic: pattern }))
+          .catch(() => {});
+      };
+      listeners.add(dispose);
+
+      void busSession(serverSocket, pluginId)
+        .then((busToken) => invokeTauri<void>(TAURI_COMMANDS.pluginsBusSubscribe, { busToken, topic: pattern }))
+        .then(() =>
+          listenPluginBusMessage((e) => {
+            const msg = e.payload;
+            if (msg.serverSocket !== serverSocket || !msg.recipients.includes(pluginId)) return;
+            if (!busTopicMatches(pattern, msg.topic)) return;
+            handler(msg.payload as T, { topic: msg.topic, from: msg.from });
+          }),
+        )
+        .then((fn) => {
+          unlisten = fn;
+          if (cancelled) unlisten();
+        })
+        .catch((e) => {
+          listeners.delete(dispose);
+          logger.warn("Action: plugins_bus_subscribe_failed", { pluginId, topic: pattern, error: String(e) });
+        });
+      return dispose;
+    },
+  };
+}
+
+/**
+ * 插件卸载时释放其全部总线订阅（本地监听 + Rust 侧登记）并关闭会话。
+ *
+ * @param serverSocket 当前 server socket。
+ * @param pluginId 插件标识。
+ */
+export async function releasePluginBus(serverSocket: string, pluginId: string): Promise<void> {
+  const key = pluginKey(serverSocket, pluginId);
+  const listeners = listenersByPlugin.get(key);
+  listenersByPlugin.delete(key);
+  const session = sessionsByPlugin.get(key);
+  sessionsByPlugin.delete(key);
+  for (const dispose of listeners ?? []) dispose();
+  if (!session) return;
+  const busToken = await session.catch(() => null);
+  if (!busToken) return;
+  await invokeTauri<void>(TAURI_COMMANDS.pluginsBusUnsubscribe, { busToken, topic: null });
+}
@@ -15,7 +15,7 @@ import {
 import { createPluginRuntimeError } from "./pluginRuntimeError";
 
 export { createPluginNetworkApi, createPluginStorageApi, type TauriFetchResponse } from "./hostApiFactory";
-export { getRuntimeEntry, getRuntimeEntryForVersion, toAppPluginEntryUrl } from "./runtimeGateway";
+export { getRuntimeEntry, getRuntimeEntryForVersion, reportPluginCrash, toAppPluginEntryUrl } from "./runtimeGateway";
 export type { PluginComposerPayload, PluginContext, PluginRuntimeContract } from "@/features/plugins/domain/types/pluginRuntimeTypes";
 
 /**
@@ -30,6 +30,7 @@ function getMockRuntimeEntry(serverSocket: string, pluginId: string, version?: s
       domainVersion: domain.version,
     })),
     minHostVersion: "0.0.0",
+    locales: [],
   };
 }
 
@@ -65,6 +66,130 @@ export function getRuntimeEntryForVersion(serverSocket: string, pluginId: string
   }).then(mapPluginRuntimeEntry);
 }
 
+/**
+ * 插件语言资源（按回退链合并后的消息）。
+ */
+export type PluginLocaleCatalog = {
+  pluginId: string;
+  version: string;
+  /** 请求的语言（已规范化）。 */
+  requested: string;
+  /** 实际命中的最具体语言；插件未携带可用语言时为 `null`。 */
+  lang: string | null;
+  available: string[];
+  messages: Record<string, unknown>;
+};
+
+/**
+ * 获取插件当前版本在指定语言下的语言资源（与宿主语言保持一致，缺失的 key 回退到通用语言）。
+ */
+export function getPluginLocale(serverSocket: string, pluginId: string, lang: string): Promise<PluginLocaleCatalog> {
+  if (IS_STORE_MOCK || USE_MOCK_TRANSPORT) {
+    return Promise.resolve({ pluginId, version: "", requested: lang, lang: null, available: [], messages: {} });
+  }
+  return invokeTauri<PluginLocaleCatalog>(TAURI_COMMANDS.pluginsGetLocale, {
+    serverSocket,
+    pluginId,
+    lang,
+    ...buildTauriTlsArgs(serverSocket),
+  });
+}
+
+/**
+ * 插件设置项声明（取自插件当前版本 `plugin.json` 的 `settings`）。
+ */
+export type PluginSettingField = {
+  key: string;
+  type: "boolean" | "string" | "number" | "integer" | "enum";
+  label: string | null;
+  description: string | null;
+  default: unknown;
+  min: number | null;
+  max: number | null;
+  maxLength: number | null;
+  options: string[];
+};
+
+export type PluginSettingsSchema = {
+  pluginId: string;
+  version: string;
+  fields: PluginSettingField[];
+};
+
+/**
+ * 插件设置生效值（默认值 + 用户修改，只含 schema 声明的 key）。
+ */
+export type PluginSettingsValues = {
+  pluginId: string;
+  version: string;
+  values: Record<string, unknown>;
+};
+
+/**
+ * 获取插件设置 schema（插件未声明设置时 `fields` 为空）。
+ */
+export function getPluginSettingsSchema(serverSocket: string, pluginId: string): Promise<PluginSettingsSchema> {
+  if (IS_STORE_MOCK || USE_MOCK_TRANSPORT) {
+    return Promise.resolve({ pluginId, version: "", fields: [] });
+  }
+  return invokeTauri<PluginSettingsSchema>(TAURI_COMMANDS.pluginsGetSettingsSchema, {
+    serverSocket,
+    pluginId,
+    ...buildTauriTlsArgs(serverSocket),
+  });
+}
+
+/**
+ * 获取插件设置生效值。
+ */
+export function getPluginSettings(serverSocket: string, pluginId: string): Promise<PluginSettingsValues> {
+  if (IS_STORE_MOCK || USE_MOCK_TRANSPORT) {
+    return Promise.resolve({ pluginId, version: "", values: {} });
+  }
+  return invokeTauri<PluginSettingsValues>(TAURI_COMMANDS.pluginsGetSettings, {
+    serverSocket,
+    pluginId,
+    ...buildTauriTlsArgs(serverSocket),
+  });
+}
+
+/**
+ * 按 schema 校验并更新插件设置（`null` 表示恢复默认；任一项不合法时整批拒绝）。
+ */
+export function updatePluginSettings(
+  serverSocket: string,
+  pluginId: string,
+  patch: Record<string, unknown>,
+): Promise<PluginSettingsValues> {
+  if (IS_STORE_MOCK || USE_MOCK_TRANSPORT) {
+    return Promise.resolve({ pluginId, version: "", values: {} });
+  }
+  return invokeTauri<PluginSettingsValues>(TAURI_COMMANDS.pluginsUpdateSettings, {
+    serverSocket,
+    pluginId,
+    patch,
+    ...buildTauriTlsArgs(serverSocket),
+  });
+}
+
+/**
+ * 上报插件运行时加载崩溃（import/activate 失败）；短时间内反复崩溃时 Rust 侧自动停用插件并发出 `plugin-auto-disabled`。
+ *
+ * 说明：只有本窗口经 `getRuntimeEntry` 取过该插件入口后才能上报，每次取入口限一次。
+ *
+ * @returns 是否已被自动停用。
+ */
+export async function reportPluginCrash(serverSocket: string, pluginId: string, message: string): Promise<boolean> {
+  if (IS_STORE_MOCK || USE_MOCK_TRANSPORT) return false;
+  const disabled = await invokeTauri<unknown>(TAURI_COMMANDS.pluginsReportCrash, {
+    serverSocket,
+    pluginId,
+    message,
+    ...buildTauriTlsArgs(serverSocket),
+  });
+  return disabled != null;
+}
+
 /**
  * 构造 `app://plugins/...` 的动态 import 入口 URL。
  */
@@ -23,7 +23,8 @@ export type DomainRegistryReconcilerDeps = {
   listInstalled: () => Promise<InstalledPluginState[]>;
   enablePluginRuntime: (pluginId: string) => Promise<void>;
   disablePluginRuntime: (pluginId: string) => Promise<void>;
-  markFailed: (pluginId: string, message: string) => Promise<void>;
+  /** 上报加载崩溃；返回插件是否因反复崩溃被自动停用。 */
+  reportCrash: (pluginId: string, message: string) => Promise<boolean>;
   notifyRuntimeStateChanged: () => void;
 };
 
@@ -43,16 +44,16 @@ export function createDomainRegistryReconciler(deps: DomainRegistryReconcilerDep
           await deps.enablePluginRuntime(state.pluginId);
         } catch (error) {
           const message = String(error) || "Runtime load failed";
-          deps.logger.error("Action: plugins_runtime_load_failed_mark_failed", {
+          deps.logger.error("Action: plugins_runtime_load_failed_report_crash", {
             key: deps.key,
             pluginId: state.pluginId,
             error: message,
           });
           try {
-            await deps.markFailed(state.pluginId, message);
-            deps.notifyRuntimeStateChanged();
+            // 单次失败只计数；统计窗口内反复失败时由 Rust 侧自动停用。
+            if (await deps.reportCrash(state.pluginId, message)) deps.notifyRuntimeStateChanged();
           } catch (syncError) {
-            deps.logger.error("Action: plugins_mark_failed_state_failed", {
+            deps.logger.error("Action: plugins_report_crash_failed", {
               key: deps.key,
               pluginId: state.pluginId,
               error: String(syncError),
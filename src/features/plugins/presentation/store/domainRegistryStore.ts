@@ -20,10 +20,13 @@ import type { PluginContext } from "@/features/plugins/domain/types/pluginRuntim
 import {
   getRuntimeEntry,
   getRuntimeEntryForVersion,
+  reportPluginCrash,
   type LoadedPluginModule,
 } from "@/features/plugins/presentation/runtime/pluginRuntime";
+import { releasePluginBus } from "@/features/plugins/presentation/runtime/pluginBusApi";
 import { assertPluginRuntimeHostCompatible } from "@/features/plugins/domain/policies/pluginHostCompatibility";
 import { registerServerScopeCleanupHandler } from "@/shared/utils/serverScopeLifecycle";
+import { listenPluginAutoDisabled, listenPluginRuntimeReady } from "@/shared/tauri/events";
 import {
   clearPluginRuntimeStateSyncListeners,
   notifyPluginRuntimeStateChanged,
@@ -56,6 +59,72 @@ const logger = createLogger("domainRegistryStore");
 const stores = new Map<string, DomainRegistryStore>();
 let runtimeStarted = false;
 let stopRuntimeCleanup: (() => void) | null = null;
+let stopAutoDisabledListener: (() => void) | null = null;
+let stopRuntimeReadyListener: (() => void) | null = null;
+
+/**
+ * 卸载 Rust 侧已停用插件的运行时，并通知对应 server 的状态变化。
+ *
+ * @returns 被卸载运行时的 store key 列表。
+ */
+function unloadDisabledPlugin(serverId: string, pluginId: string): string[] {
+  const keys: string[] = [];
+  for (const [key, store] of stores.entries()) {
+    if (store.runtimeById[pluginId]?.serverId !== serverId) continue;
+    keys.push(key);
+    void store.disablePluginRuntime(pluginId).finally(() => notifyPluginRuntimeStateChanged(key));
+  }
+  return keys;
+}
+
+/**
+ * 插件因反复崩溃被 Rust 侧自动停用后，卸载对应运行时并通知状态变化。
+ */
+function watchPluginAutoDisabled(): void {
+  let cancelled = false;
+  stopAutoDisabledListener = () => {
+    cancelled = true;
+  };
+  void listenPluginAutoDisabled((e) => {
+    const { serverId, pluginId, lastError } = e.payload;
+    const keys = unloadDisabledPlugin(serverId, pluginId);
+    if (keys.length > 0) logger.warn("Action: plugins_auto_disabled", { keys, pluginId, error: lastError });
+  }).then((unlisten) => {
+    if (cancelled) {
+      unlisten();
+      return;
+    }
+    stopAutoDisabledListener = unlisten;
+  });
+}
+
+/**
+ * 启动预加载校验完成后，卸载因安装损坏被停用的插件运行时。
+ *
+ * 说明：预加载早于连接服务端，通常此时尚无已加载的运行时；这里兜底处理已加载的情况。
+ */
+function watchPluginRuntimeReady(): void {
+  let cancelled = false;
+  stopRuntimeReadyListener = () => {
+    cancelled = true;
+  };
+  void listenPluginRuntimeReady((e) => {
+    const { serverId, ready, failed } = e.payload;
+    logger.info("Action: plugins_preload_ready", { serverId, ready: ready.length, failed: failed.length });
+    for (const failure of failed) {
+      if (!failure.disabled) continue;
+      const keys = unloadDisabledPlugin(serverId, failure.pluginId);
+      if (keys.length === 0) continue;
+      logger.warn("Action: plugins_preload_disabled", { keys, pluginId: failure.pluginId, error: failure.error });
+    }
+  }).then((unlisten) => {
+    if (cancelled) {
+      unlisten();
+      return;
+    }
+    stopRuntimeReadyListener = unlisten;
+  });
+}
 
 async function disposeDomainRegistryStore(store: DomainRegistryStore): Promise<void> {
   for (const pluginId of Object.keys(store.loadedById)) {
@@ -68,12 +137,14 @@ async function disposeDomainRegistryStore(store: DomainRegistryStore): Promise<v
  * 启动 domain-registry 运行时（幂等）。
  *
  * 说明：
- * - 显式注册 server-scope 清理回调；
+ * - 显式注册 server-scope 清理回调、插件自动停用与启动预加载结果监听；
  * - 避免模块加载时产生副作用。
  */
 export function startDomainRegistryRuntime(): void {
   if (runtimeStarted) return;
   runtimeStarted = true;
+  watchPluginAutoDisabled();
+  watchPluginRuntimeReady();
   stopRuntimeCleanup = registerServerScopeCleanupHandler(async (event) => {
     if (event.type === "all") {
       clearPluginRuntimeStateSyncListeners();
@@ -104,6 +175,10 @@ export async function stopDomainRegistryRuntime(): Promise<void> {
   runtimeStarted = false;
   stopRuntimeCleanup?.();
   stopRuntimeCleanup = null;
+  stopAutoDisabledListener?.();
+  stopAutoDisabledListener = null;
+  stopRuntimeReadyListener?.();
+  stopRuntimeReadyListener = null;
   clearPluginRuntimeStateSyncListeners();
   const tasks: Promise<void>[] = [];
   for (const [key, store] of stores.entries()) {
@@ -215,6 +290,13 @@ export function useDomainRegistryStore(serverSocket: string): DomainRegistryStor
       delete runtimeById[id];
       throw e;
     }
+
+    // 只有加载与 activate 都成功后才记为 known-good（启用本身不清零失败计数）。
+    try {
+      await commandPort.markLoaded(key, id, runtime.version);
+    } catch (e) {
+      logger.warn("Action: plugins_mark_loaded_failed", { key, pluginId: id, error: String(e) });
+    }
   }
 
   /**
@@ -230,6 +312,11 @@ export function useDomainRegistryStore(serverSocket: string): DomainRegistryStor
     unregisterPluginDomains(bindingByDomain, id);
     delete loadedById[id];
     delete runtimeById[id];
+    if (loaded?.permissions.includes("bus")) {
+      void releasePluginBus(key, id).catch((e) => {
+        logger.warn("Action: plugins_bus_release_failed", { key, pluginId: id, error: String(e) });
+      });
+    }
     if (!loaded?.deactivate) return;
     try {
       await Promise.resolve(loaded.deactivate());
@@ -288,9 +375,7 @@ export function useDomainRegistryStore(serverSocket: string): DomainRegistryStor
     listInstalled: () => queryPort.listInstalled(key),
     enablePluginRuntime,
     disablePluginRuntime,
-    async markFailed(pluginId, message): Promise<void> {
-      await commandPort.setFailed(key, pluginId, message);
-    },
+    reportCrash: (pluginId, message) => reportPluginCrash(key, pluginId, message),
     notifyRuntimeStateChanged: () => notifyPluginRuntimeStateChanged(key),
   });
 
@@ -16,6 +16,7 @@ import type {
 import type {
   InstalledPluginState,
   PluginCatalogEntryLike,
+  PluginInstallOptions,
   PluginProgress,
 } from "@/features/plugins/domain/types/pluginTypes";
 import type { Logger } from "@/shared/utils/logger";
@@ -44,8 +45,8 @@ export type PluginInstallActionsDeps = {
 };
 
 export type PluginInstallActions = {
-  install(plugin: PluginCatalogEntryLike, version: string): Promise<void>;
-  updateToLatest(plugin: PluginCatalogEntryLike, latestVersion: string): Promise<void>;
+  install(plugin: PluginCatalogEntryLike, version: string, options?: PluginInstallOptions): Promise<void>;
+  updateToLatest(plugin: PluginCatalogEntryLike, latestVersion: string, options?: PluginInstallOptions): Promise<void>;
   switchVersion(pluginId: string, version: string): Promise<void>;
   rollback(pluginId: string): Promise<void>;
   enable(pluginId: string): Promise<void>;
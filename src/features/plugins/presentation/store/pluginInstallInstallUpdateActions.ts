@@ -7,6 +7,7 @@ import {
   resolveLatestPluginCatalogVersionEntry,
   resolvePluginCatalogVersionEntry,
   type PluginCatalogEntryLike,
+  type PluginInstallOptions,
 } from "@/features/plugins/domain/types/pluginTypes";
 import type {
   PluginInstallActions,
@@ -22,7 +23,7 @@ export function createPluginInstallInstallUpdateActions(
 ): PluginInstallInstallUpdateActions {
   const operationRunner = createPluginInstallOperationRunner(deps);
 
-  async function install(plugin: PluginCatalogEntryLike, version: string): Promise<void> {
+  async function install(plugin: PluginCatalogEntryLike, version: string, options?: PluginInstallOptions): Promise<void> {
     const id = String(plugin?.pluginId ?? "").trim();
     const resolvedVersion =
       resolvePluginCatalogVersionEntry(plugin, version) ?? resolveLatestPluginCatalogVersionEntry(plugin);
@@ -43,14 +44,19 @@ export function createPluginInstallInstallUpdateActions(
                 String(resolvedVersion?.downloadUrl ?? plugin.downloadUrl ?? ""),
                 String(resolvedVersion?.sha256 ?? plugin.sha256 ?? ""),
                 onProgress,
+                options,
               )
-            : await deps.commandPort.install(deps.key, id, targetVersion, onProgress);
+            : await deps.commandPort.install(deps.key, id, targetVersion, onProgress, options);
         deps.installedById[id] = next;
       },
     });
   }
 
-  async function updateToLatest(plugin: PluginCatalogEntryLike, latestVersion: string): Promise<void> {
+  async function updateToLatest(
+    plugin: PluginCatalogEntryLike,
+    latestVersion: string,
+    options?: PluginInstallOptions,
+  ): Promise<void> {
     const id = String(plugin?.pluginId ?? "").trim();
     const resolved =
       resolvePluginCatalogVersionEntry(plugin, latestVersion) ??
@@ -77,6 +83,7 @@ export function createPluginInstallInstallUpdateActions(
           latestVersion: targetVersion,
           before,
           onProgress,
+          options,
         });
         if (next) deps.installedById[id] = next;
       },
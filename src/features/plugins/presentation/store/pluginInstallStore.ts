@@ -21,6 +21,7 @@ import {
 import {
   type PluginCatalogEntryLike,
   type InstalledPluginState,
+  type PluginInstallOptions,
   type PluginProgress,
 } from "@/features/plugins/domain/types/pluginTypes";
 import type {
@@ -42,7 +43,12 @@ import { registerServerScopeCleanupHandler } from "@/shared/utils/serverScopeLif
 import { createPluginOperationHelpers } from "./pluginInstallOperationHelpers";
 import { createPluginInstallActions } from "./pluginInstallActions";
 import { createPluginInstallSelectors } from "./pluginInstallSelectors";
-import { buildSensitivePermissionMessage, collectSensitivePermissionLabelsForVersion } from "./pluginPermissionGuard";
+import {
+  buildSensitivePermissionMessage,
+  buildUntrustedSourceMessage,
+  collectSensitivePermissionLabelsForVersion,
+  isUntrustedSourceError,
+} from "./pluginPermissionGuard";
 import { usePluginCatalogStore } from "./pluginCatalogStore";
 import { usePluginRuntimeAccess } from "./pluginRuntimeAccess";
 import { registerPluginRuntimeStateSyncListener } from "./pluginRuntimeStateSync";
@@ -319,6 +325,27 @@ export function usePluginInstallStore(serverSocket: string): InstallStore {
     });
   }
 
+  /**
+   * 执行安装类任务；未经验证的来源被 Rust 侧拒绝时，经用户确认后带 `confirmUntrusted` 重试一次。
+   */
+  async function runWithUntrustedConfirmation(
+    pluginId: string,
+    targetVersion: string,
+    operationLabel: "Install" | "Update",
+    task: (options?: PluginInstallOptions) => Promise<void>,
+  ): Promise<void> {
+    try {
+      await task();
+    } catch (error) {
+      if (!isUntrustedSourceError(error)) throw error;
+      const message = buildUntrustedSourceMessage({ operationLabel, pluginId, targetVersion });
+      // 无法弹窗确认时按拒绝处理。
+      const confirmed = typeof window !== "undefined" && typeof window.confirm === "function" && window.confirm(message);
+      if (!confirmed) throw error;
+      await task({ confirmUntrusted: true });
+    }
+  }
+
   async function runInstall(plugin: PluginCatalogEntryLike, version: string): Promise<InstallPluginOutcome> {
     const pluginId = String(plugin?.pluginId ?? "").trim();
     const targetVersion = String(version ?? "").trim();
@@ -332,7 +359,9 @@ export function usePluginInstallStore(serverSocket: string): InstallStore {
     const permissionConfirmationError = confirmSensitivePermissionChange(plugin, pluginId, targetVersion, "Install");
     if (permissionConfirmationError) return rejectInstall(permissionConfirmationError);
     try {
-      await install(plugin, targetVersion);
+      await runWithUntrustedConfirmation(pluginId, targetVersion, "Install", (options) =>
+        install(plugin, targetVersion, options),
+      );
       return {
         ok: true,
         kind: "plugin_installed",
@@ -360,7 +389,9 @@ export function usePluginInstallStore(serverSocket: string): InstallStore {
     const permissionConfirmationError = confirmSensitivePermissionChange(plugin, pluginId, targetVersion, "Update");
     if (permissionConfirmationError) return rejectUpdate(permissionConfirmationError);
     try {
-      await updateToLatest(plugin, targetVersion);
+      await runWithUntrustedConfirmation(pluginId, targetVersion, "Update", (options) =>
+        updateToLatest(plugin, targetVersion, options),
+      );
       return {
         ok: true,
         kind: "plugin_updated_to_latest",
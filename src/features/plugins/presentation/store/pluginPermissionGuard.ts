@@ -1,5 +1,5 @@
 /**
- * @fileoverview plugins｜presentation helper：sensitive permission confirmation text.
+ * @fileoverview plugins｜presentation helper：sensitive permission / untrusted source confirmation text.
  */
 
 import type { PluginCatalogEntryLike, PluginPermission } from "@/features/plugins/domain/types/pluginTypes";
@@ -37,3 +37,23 @@ export function buildSensitivePermissionMessage(input: SensitivePermissionMessag
     `This version requests sensitive permissions: ${input.sensitivePermissions.join(", ")}.`,
   ].join("\n");
 }
+
+export type UntrustedSourceMessageInput = {
+  operationLabel: "Install" | "Update";
+  pluginId: string;
+  targetVersion: string;
+};
+
+/**
+ * Rust 侧拒绝安装未经验证来源的插件（需用户确认后带 `confirmUntrusted` 重试）。
+ */
+export function isUntrustedSourceError(error: unknown): boolean {
+  return Boolean(error) && typeof error === "object" && (error as { code?: unknown }).code === "PLUGINS_UNTRUSTED_SOURCE";
+}
+
+export function buildUntrustedSourceMessage(input: UntrustedSourceMessageInput): string {
+  return [
+    `${input.operationLabel} ${input.pluginId} ${input.targetVersion}?`,
+    "This plugin is not signed by a trusted publisher. Only continue if you trust its source.",
+  ].join("\n");
+}
@@ -5,7 +5,9 @@
 import {
   buildSensitivePermissionMessage,
   collectSensitivePermissionLabels,
+  buildUntrustedSourceMessage,
   collectSensitivePermissionLabelsForVersion,
+  isUntrustedSourceError,
 } from "../presentation/store/pluginPermissionGuard";
 import type { PluginCatalogEntryLike } from "@/features/plugins/domain/types/pluginTypes";
 
@@ -50,3 +52,13 @@ export const pluginPermissionGuardMessageContractCheck: string = buildSensitiveP
   targetVersion: "1.2.3",
   sensitivePermissions: sensitiveLabels,
 });
+
+export const pluginPermissionGuardUntrustedMessageContractCheck: string = buildUntrustedSourceMessage({
+  operationLabel: "Update",
+  pluginId: "demo.plugin",
+  targetVersion: "1.2.3",
+});
+
+export const pluginPermissionGuardUntrustedErrorContractCheck: boolean = isUntrustedSourceError({
+  code: "PLUGINS_UNTRUSTED_SOURCE",
+});
@@ -1,19 +1,19 @@
-import { invoke } from "@tauri-apps/api/core";
+import { invokeTauri } from "@/shared/tauri/invokeClient";
 import { TAURI_COMMANDS } from "@/shared/tauri/commands";
 import type { ScreenCapture } from "../domain/contracts";
 
 export function startScreenshot(hideWindow?: boolean): Promise<void> {
-  return invoke(TAURI_COMMANDS.startScreenshot, { hideWindow });
+  return invokeTauri(TAURI_COMMANDS.startScreenshot, { hideWindow });
 }
 
 export function getScreenshotData(): Promise<ScreenCapture[]> {
-  return invoke(TAURI_COMMANDS.getScreenshotData);
+  return invokeTauri(TAURI_COMMANDS.getScreenshotData);
 }
 
 export function finishScreenshot(data: number[]): Promise<string> {
-  return invoke(TAURI_COMMANDS.finishScreenshot, { data });
+  return invokeTauri(TAURI_COMMANDS.finishScreenshot, { data });
 }
 
 export function cancelScreenshot(): Promise<void> {
-  return invoke(TAURI_COMMANDS.cancelScreenshot);
+  return invokeTauri(TAURI_COMMANDS.cancelScreenshot);
 }
@@ -245,8 +245,38 @@ function parseAad(aad: Uint8Array): { sequence: number; sessionId: bigint; times
 
 type HandshakeEnvelope = {
     sessionIdRaw: unknown;
+    codecRaw: unknown;
+    compressionRaw: unknown;
 };
 
+/**
+ * 业务 payload 编码（与 Rust 侧 `PayloadCodec` 对齐）。
+ */
+export type PayloadCodec = "json" | "msgpack";
+
+/**
+ * 客户端支持的编码（按偏好排序，随换钥包声明给服务端）。
+ */
+const SUPPORTED_PAYLOAD_CODECS: readonly PayloadCodec[] = ["msgpack", "json"];
+
+/**
+ * 本端可接收的单帧上限（u16 长度前缀，随换钥包声明）；服务端在 `/handshake` 中返回协商值，
+ * 超过上限的数据走 HTTP 旁路（见 `largePayload.ts`）。
+ */
+const MAX_FRAME_BYTES = 0xffff;
+
+/**
+ * 服务端是否在 `/handshake` 中选定 zstd（未返回时视为未启用）。
+ */
+function parseZstdSelected(value: unknown): boolean {
+    return typeof value === "string" && value.trim().toLowerCase() === "zstd";
+}
+
+function parsePayloadCodec(value: unknown): PayloadCodec {
+    const raw = typeof value === "string" ? value.trim().toLowerCase() : "";
+    return raw === "msgpack" ? "msgpack" : "json";
+}
+
 function isJsonObject(value: unknown): value is JsonObject {
     return Boolean(value) && typeof value === "object" && !Array.isArray(value);
 }
@@ -272,7 +302,7 @@ function parseHandshakeEnvelope(value: unknown): HandshakeEnvelope | null {
 
     const inner = data["data"];
     if (!isJsonObject(inner)) return null;
-    return { sessionIdRaw: inner["session_id"] };
+    return { sessionIdRaw: inner["session_id"], codecRaw: inner["codec"], compressionRaw: inner["compression"] };
 }
 
 function extractSessionIdDigitsFromText(text: string): string | null {
@@ -359,6 +389,18 @@ export class Encryption {
      * 是否已完成握手（收到 `/handshake` 后置为 true）。
      */
     private handshakeComplete: boolean = false;
+    /**
+     * 握手协商的业务 payload 编码（旧服务端未返回时为 json）。
+     */
+    private payloadCodec: PayloadCodec = "json";
+    /**
+     * 换钥时是否声明了 zstd 压缩能力（设置项 `frame_compression`）。
+     */
+    private compressionRequested: boolean = false;
+    /**
+     * 握手协商后是否启用明文压缩（启用时明文带 flags 头，由 Rust 侧压缩/解压）。
+     */
+    private payloadCompression: boolean = false;
 
     constructor(serverSocket: string, opts?: { transportSocket?: string; serverEccPublicKeyBase64?: string; frameConfig?: FrameConfig }) {
         this.serverSocket = serverSocket;
@@ -420,7 +462,7 @@ export class Encryption {
             const sessionId = BigInt(digits);
             if (sessionId <= 0n) throw new Error(`Invalid session_id: ${digits}`);
 
-            this.setHandshakeSession(sessionId);
+            this.setHandshakeSession(sessionId, envelope);
             return true;
         } catch (e) {
             tauriLog.error("Action: network_handshake_response_parse_failed", { error: String(e) });
@@ -440,7 +482,7 @@ export class Encryption {
         try {
             const sessionId = parseSessionIdFromUnknown(envelope.sessionIdRaw);
             if (sessionId == null) return false;
-            this.setHandshakeSession(sessionId);
+            this.setHandshakeSession(sessionId, envelope);
             return true;
         } catch (e) {
             tauriLog.error("Action: network_handshake_response_parse_failed", { error: String(e) });
@@ -448,12 +490,18 @@ export class Encryption {
         }
     }
 
-    private setHandshakeSession(sessionId: bigint): void {
+    private setHandshakeSession(sessionId: bigint, envelope: HandshakeEnvelope): void {
         this.sessionId = sessionId;
+        this.payloadCodec = parsePayloadCodec(envelope.codecRaw);
+        this.payloadCompression = this.compressionRequested && parseZstdSelected(envelope.compressionRaw);
         this.sendSequence = 0;
         this.receiveSequence = -1;
         this.handshakeComplete = true;
-        tauriLog.debug("Action: network_handshake_completed", { sessionId: this.sessionId.toString() });
+        tauriLog.debug("Action: network_handshake_completed", {
+            sessionId: this.sessionId.toString(),
+            codec: this.payloadCodec,
+            compression: this.payloadCompression ? "zstd" : "none",
+        });
     }
 
     /**
@@ -520,10 +568,18 @@ export class Encryption {
                   )
                 : aesKeyBase64;
 
+            // 压缩在加密前完成（见 `encodePayload`），这里按设置声明能力。
+            const frameCompression = await invokeTauri<boolean>(TAURI_COMMANDS.settingsGetConfigBool, {
+                key: "frame_compression",
+            }).catch(() => false);
+            this.compressionRequested = frameCompression;
             const keyPack = {
                 id: requestId,
                 session_id: 0,
                 key: keyPayload,
+                codecs: SUPPORTED_PAYLOAD_CODECS,
+                max_frame_bytes: MAX_FRAME_BYTES,
+                ...(frameCompression ? { compression: ["zstd"] } : {}),
             };
 
             const json = JSON.stringify(keyPack);
@@ -558,6 +614,43 @@ export class Encryption {
         }
     }
 
+    /**
+     * 当前协商的业务 payload 编码。
+     * @returns 编码名
+     */
+    public getPayloadCodec(): PayloadCodec {
+        return this.payloadCodec;
+    }
+
+    /**
+     * 按协商编码将业务 JSON 文本转为待加密 bytes（非 JSON 编码与 zstd 压缩由 Rust 侧完成）。
+     *
+     * 已协商压缩时，明文首字节为 flags 头（`0x01`=zstd），位于密文内并由 GCM 认证。
+     * @param plaintextJson - 业务 JSON 文本
+     * @returns 待加密 bytes
+     */
+    private async encodePayload(plaintextJson: string): Promise<Uint8Array> {
+        if (this.payloadCodec === "json" && !this.payloadCompression) return encoder.encode(plaintextJson);
+        const bytes = await invokeTauri<number[]>(TAURI_COMMANDS.tcpEncodePayload, {
+            serverSocket: this.serverSocket,
+            json: plaintextJson,
+        });
+        return Uint8Array.from(bytes);
+    }
+
+    /**
+     * 按协商编码将解密后的 bytes 还原为业务 JSON 文本（已协商压缩时先去 flags 头并解压）。
+     * @param plaintext - 解密后的 bytes
+     * @returns 业务 JSON 文本
+     */
+    private async decodePayload(plaintext: Uint8Array): Promise<string> {
+        if (this.payloadCodec === "json" && !this.payloadCompression) return decoder.decode(plaintext);
+        return invokeTauri<string>(TAURI_COMMANDS.tcpDecodePayload, {
+            serverSocket: this.serverSocket,
+            payload: Array.from(plaintext),
+        });
+    }
+
     /**
      * 加密并封帧业务 JSON 文本（AES-GCM + Netty length-prefix）。
      * @param plaintextJson - 业务 JSON 文本
@@ -569,7 +662,7 @@ export class Encryption {
 
         const nonce = window.crypto.getRandomValues(new Uint8Array(12));
         const aad = buildAad(this.sendSequence, this.sessionId, BigInt(Date.now()));
-        const plaintext = encoder.encode(plaintextJson);
+        const plaintext = await this.encodePayload(plaintextJson);
 
         try {
             const encrypted = await window.crypto.subtle.encrypt(
@@ -638,7 +731,7 @@ export class Encryption {
                 toArrayBuffer(cipherText)
             );
             this.receiveSequence = sequence;
-            return decoder.decode(decrypted);
+            return await this.decodePayload(new Uint8Array(decrypted));
         } catch (e) {
             tauriLog.error("Action: network_crypto_aes_gcm_decrypt_failed", { error: String(e) });
             throw e;
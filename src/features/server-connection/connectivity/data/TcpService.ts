@@ -7,6 +7,7 @@ import { publishIncomingMessage } from "@/shared/net/incomingMessageSink";
 import { Encryption } from "./Encryption";
 import { frameNettyPayload, type FrameConfig } from "./frameCodec";
 import { HandshakeWaitState } from "./HandshakeWaitState";
+import { resolveLargePayload } from "./largePayload";
 import { TcpRequestCallbackRegistry } from "./TcpRequestCallbackRegistry";
 import { createTcpRequestResponseSender } from "./TcpRequestResponseSender";
 
@@ -29,7 +30,7 @@ function isRecord(value: unknown): value is Record<string, unknown> {
  * - 提供 request/response 风格回调映射（request id）。
  *
  * 说明：
- * - Rust 侧已完成 length-prefix 拆包：前端监听的是 `tcp-frame`（payload only）；
+ * - Rust 侧已完成 length-prefix 拆包：前端经连接 Channel（回退为 `tcp-frame` 事件）接收 payload；
  * - 本类的日志统一通过 `tauriLog` 输出英文，便于跨端检索。
  */
 export class TcpService {
@@ -185,6 +186,7 @@ export class TcpService {
    * 处理一条解密后的 JSON 文本（来自拆包逻辑）。
    *
    * - 优先尝试识别握手响应（文本/JSON 两种形态），并在成功后 resolve 握手 Promise。
+   * - 携带 `large_payload` 描述的消息先经 HTTP 旁路拉取完整 `data`；
    * - 其他消息按 `id` 做请求-响应回调匹配；无法匹配时走通知/广播分发。
    *
    * @param data - 明文 JSON 文本（单帧 payload）。
@@ -197,7 +199,9 @@ export class TcpService {
       return;
     }
 
-    const value = this.parseIncomingJson(data);
+    const parsed = this.parseIncomingJson(data);
+    if (parsed === null) return;
+    const value = await resolveLargePayload(parsed, serverSocket);
     if (value === null) return;
 
     tauriLog.debug("Action: network_tcp_message_received", { value });
@@ -0,0 +1,25 @@
+/**
+ * @fileoverview largePayload 单元测试
+ * @description 验证大 payload 描述的识别与路径校验
+ */
+
+import { describe, expect, it } from "vitest";
+import { readLargePayloadDescriptor, resolveLargePayload } from "./largePayload";
+
+describe("largePayload", () => {
+  it("reads descriptor from the data slot", () => {
+    const message = { id: 3, code: 0, data: { large_payload: { path: "/messages/history/9", size: 1048576 } } };
+    expect(readLargePayloadDescriptor(message)).toEqual({ path: "/messages/history/9", size: 1048576 });
+  });
+
+  it("rejects absolute or protocol-relative paths", () => {
+    expect(readLargePayloadDescriptor({ data: { large_payload: { path: "https://evil/x", size: 1 } } })).toBeNull();
+    expect(readLargePayloadDescriptor({ data: { large_payload: { path: "//evil/x", size: 1 } } })).toBeNull();
+    expect(readLargePayloadDescriptor({ data: { large_payload: { path: "/x", size: -1 } } })).toBeNull();
+  });
+
+  it("passes through regular messages unchanged", async () => {
+    const message = { id: -1, code: 0, data: { route: "message", data: {} } };
+    await expect(resolveLargePayload(message, "tls://example:1")).resolves.toBe(message);
+  });
+});
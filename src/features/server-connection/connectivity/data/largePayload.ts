@@ -0,0 +1,93 @@
+/**
+ * @fileoverview largePayload.ts
+ * @description server-connection/connectivity｜数据层：超过帧上限的业务数据走 HTTP 旁路下载（分块传输约定）。
+ *
+ * 约定：
+ * - 单帧 payload 受握手协商的 `max_frame_bytes` 限制（Rust 侧超限即断开重连）；
+ * - 服务端需要下发更大的数据时，在原本的 `data` 位置放置描述：
+ *   `{ "large_payload": { "path": "/messages/...", "size": 12345 } }`；
+ * - 客户端按 `path` 通过 `/api` 旁路拉取 JSON，并替换回 `data` 后继续正常分发（`id` 回调匹配不受影响）。
+ *
+ * 日志：英文（`tauriLog`）。
+ */
+import { tauriLog } from "@/shared/tauri";
+import { ensureValidAccessToken } from "@/shared/net/auth/authSessionManager";
+import { createAuthedHttpJsonClient } from "@/shared/net/http/authedHttpJsonClient";
+
+/** 旁路下载允许的最大声明长度（防止异常描述触发超大下载）。 */
+export const MAX_LARGE_PAYLOAD_BYTES = 64 * 1024 * 1024;
+
+/**
+ * 大 payload 描述。
+ */
+export type LargePayloadDescriptor = {
+  /**
+   * `/api` 下的相对路径（以 `/` 开头）。
+   */
+  path: string;
+  /**
+   * 服务端声明的数据长度（字节）。
+   */
+  size: number;
+};
+
+function isRecord(value: unknown): value is Record<string, unknown> {
+  return Boolean(value) && typeof value === "object" && !Array.isArray(value);
+}
+
+/**
+ * 读取消息 `data` 位置上的大 payload 描述。
+ *
+ * @param message - 已解析的 JSON 消息。
+ * @returns 合法描述；不是描述或字段非法时返回 `null`。
+ */
+export function readLargePayloadDescriptor(message: unknown): LargePayloadDescriptor | null {
+  if (!isRecord(message) || !isRecord(message["data"])) return null;
+  const raw = message["data"]["large_payload"];
+  if (!isRecord(raw)) return null;
+  const path = typeof raw["path"] === "string" ? raw["path"].trim() : "";
+  const size = Number(raw["size"]);
+  // 仅允许相对 API 路径，避免服务端引导客户端携带 token 访问其他来源。
+  if (!path.startsWith("/") || path.startsWith("//")) return null;
+  if (!Number.isFinite(size) || size < 0) return null;
+  return { path, size: Math.trunc(size) };
+}
+
+/**
+ * 若消息携带大 payload 描述，则经 HTTP 旁路拉取并替换 `data`。
+ *
+ * @param message - 已解析的 JSON 消息。
+ * @param serverSocket - 消息所属 server socket。
+ * @returns 替换后的消息；无描述时原样返回；拉取失败返回 `null`（丢弃该消息）。
+ */
+export async function resolveLargePayload(message: unknown, serverSocket: string): Promise<unknown | null> {
+  const descriptor = readLargePayloadDescriptor(message);
+  if (!descriptor || !isRecord(message)) return message;
+  if (descriptor.size > MAX_LARGE_PAYLOAD_BYTES) {
+    tauriLog.warn("Action: network_large_payload_rejected", {
+      serverSocket,
+      path: descriptor.path,
+      size: descriptor.size,
+      max: MAX_LARGE_PAYLOAD_BYTES,
+    });
+    return null;
+  }
+  try {
+    const token = await ensureValidAccessToken(serverSocket);
+    const client = createAuthedHttpJsonClient(serverSocket, token);
+    const data = await client.requestJson<unknown>("GET", descriptor.path);
+    tauriLog.debug("Action: network_large_payload_fetched", {
+      serverSocket,
+      path: descriptor.path,
+      size: descriptor.size,
+    });
+    return { ...message, data };
+  } catch (error) {
+    tauriLog.error("Action: network_large_payload_fetch_failed", {
+      serverSocket,
+      path: descriptor.path,
+      error: String(error),
+    });
+    return null;
+  }
+}
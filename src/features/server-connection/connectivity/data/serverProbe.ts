@@ -0,0 +1,51 @@
+/**
+ * @fileoverview 服务器健康探测（data 层）。
+ * @description 调用 Rust `probe_server` 测量 TCP/TLS/HTTP 延迟与时钟偏差；最近一次结果由 Rust 侧按 server 缓存。
+ */
+import { invokeTauri, TAURI_COMMANDS } from "@/shared/tauri";
+import { buildTauriTlsArgs } from "@/shared/net/tls/tauriTlsArgs";
+
+/**
+ * 单个探测阶段（与 Rust `ProbeStage` 对齐）。
+ */
+export type ProbeStage = {
+  duration_ms: number | null;
+  error: string | null;
+};
+
+/**
+ * 服务器健康探测结果（与 Rust `ServerHealthReport` 对齐）。
+ */
+export type ServerHealthReport = {
+  server_socket: string;
+  probed_at: number;
+  tcp_connect: ProbeStage;
+  tls_handshake: ProbeStage;
+  http_round_trip: ProbeStage;
+  http_status: number | null;
+  /** 服务器时间减本地时间（毫秒，正数表示服务器偏快）。 */
+  clock_skew_ms: number | null;
+  healthy: boolean;
+};
+
+/**
+ * 探测服务器（使用机架中保存的 TLS 策略）。
+ *
+ * @param serverSocket - 服务器 socket。
+ * @returns 探测结果。
+ */
+export function probeServer(serverSocket: string): Promise<ServerHealthReport> {
+  return invokeTauri<ServerHealthReport>(TAURI_COMMANDS.probeServer, {
+    serverSocket,
+    ...buildTauriTlsArgs(serverSocket),
+  });
+}
+
+/**
+ * 读取所有服务器最近一次的探测结果。
+ *
+ * @returns 探测结果列表（按 server socket 排序）。
+ */
+export function getServerProbes(): Promise<ServerHealthReport[]> {
+  return invokeTauri<ServerHealthReport[]>(TAURI_COMMANDS.getServerProbes);
+}
@@ -3,7 +3,22 @@
  * @description 将 TcpService 的会话职责与运行时副作用解耦。
  */
 
-import { invokeTauri, listenTcpFrame, TAURI_COMMANDS, tauriLog, type TcpMessageEvent } from "@/shared/tauri";
+import {
+  invokeTauri,
+  listenConnectionFrameGap,
+  listenPresenceSubscriptionBatch,
+  listenTcpFrame,
+  listenTcpFrameBatch,
+  replayRecentFrames,
+  subscribeConnection,
+  TAURI_COMMANDS,
+  tauriLog,
+  type ConnectionChannelEvent,
+  type ConnectionFrameGapEvent,
+  type PresenceSubscriptionBatchEvent,
+  type TcpFrameBatchEvent,
+  type TcpMessageEvent,
+} from "@/shared/tauri";
 import { setTcpServiceProvider } from "@/shared/net/tcp/tcpServiceProvider";
 import { registerServerScopeCleanupHandler } from "@/shared/utils/serverScopeLifecycle";
 import type { Event, UnlistenFn } from "@tauri-apps/api/event";
@@ -16,31 +31,199 @@ import type { FrameConfig } from "./frameCodec";
  */
 const TCP_SERVICE = new Map<string, TcpService>();
 const TCP_SERVICE_INIT = new Map<string, { token: symbol; promise: Promise<TcpService> }>();
+/**
+ * @constant
+ * @description 本窗口的连接 Channel 订阅（key 为 server socket，value 为取消订阅函数）。
+ */
+const CONNECTION_SUBSCRIPTIONS = new Map<string, () => void>();
+/**
+ * @constant
+ * @description 本窗口已处理的最后一帧序号（key 为 server socket），用于重放与 Channel 投递去重。
+ */
+const LAST_FRAME_SEQ = new Map<string, number>();
+/**
+ * @constant
+ * @description 正在建立连接 Channel 的 server：期间的全局帧事件丢弃，由订阅后的重放按序补齐。
+ */
+const PENDING_REPLAY = new Set<string>();
+/**
+ * @constant
+ * @description 每个 server 的帧处理队列（保证重放帧先于订阅后的 Channel 帧按序解码）。
+ */
+const FRAME_QUEUE = new Map<string, Promise<void>>();
 const DEFAULT_FRAME_CONFIG: FrameConfig = { lengthBytes: 2, byteOrder: "be", lengthIncludesHeader: false };
 const KEY_EXCHANGE_TIMEOUT_MS = 15_000;
 
 let tcpFrameListenerSubscribed = false;
 let tcpFrameListenerStartingPromise: Promise<void> | null = null;
 let tcpFrameUnlisten: UnlistenFn | null = null;
+let tcpFrameBatchUnlisten: UnlistenFn | null = null;
+let connectionFrameGapUnlisten: UnlistenFn | null = null;
+let presenceBatchUnlisten: UnlistenFn | null = null;
 let tcpServiceProviderRegistered = false;
 let serverScopeCleanupHandlerRegistered = false;
 let unregisterServerScopeCleanupHandler: (() => void) | null = null;
 
-async function handleTcpFrameEvent(event: Event<TcpMessageEvent>): Promise<void> {
-  const service = TCP_SERVICE.get(event.payload.server_socket);
+async function handleTcpFramePayload(serverSocket: string, payload: number[]): Promise<void> {
+  const service = TCP_SERVICE.get(serverSocket);
   if (!service) return;
 
-  const framePayload = new Uint8Array(event.payload.payload);
+  const framePayload = new Uint8Array(payload);
   const plaintext = await service.decodeIncomingFrame(framePayload);
 
   if (!plaintext || plaintext.trim().length === 0) return;
   try {
-    await service.listen(plaintext, event.payload.server_socket);
+    await service.listen(plaintext, serverSocket);
   } catch (e) {
     tauriLog.error("Action: network_tcp_listen_failed", { error: String(e) });
   }
 }
 
+/**
+ * 按 server 串行执行帧处理任务（解密状态依赖帧顺序）。
+ */
+function enqueueFrameTask(serverSocket: string, task: () => Promise<void>): Promise<void> {
+  const previous = FRAME_QUEUE.get(serverSocket) ?? Promise.resolve();
+  const next = previous.then(task).catch((e) => {
+    tauriLog.error("Action: network_frame_task_failed", { serverSocket, error: String(e) });
+  });
+  FRAME_QUEUE.set(serverSocket, next);
+  void next.finally(() => {
+    if (FRAME_QUEUE.get(serverSocket) === next) FRAME_QUEUE.delete(serverSocket);
+  });
+  return next;
+}
+
+/**
+ * 推进本窗口的帧序号；已处理过的序号返回 false（重放与 Channel 投递可能重叠）。
+ */
+function advanceFrameSeq(serverSocket: string, seq: number): boolean {
+  if (seq <= (LAST_FRAME_SEQ.get(serverSocket) ?? 0)) return false;
+  LAST_FRAME_SEQ.set(serverSocket, seq);
+  return true;
+}
+
+/**
+ * 建立连接前记下当前最新帧序号，订阅后只重放此后的帧（不会重放旧连接的帧）。
+ */
+async function captureFrameSeqBaseline(serverSocket: string): Promise<void> {
+  if (LAST_FRAME_SEQ.has(serverSocket)) return;
+  try {
+    const replay = await replayRecentFrames(serverSocket, Number.MAX_SAFE_INTEGER);
+    LAST_FRAME_SEQ.set(serverSocket, replay.latestSeq);
+  } catch (e) {
+    tauriLog.debug("Action: network_frame_replay_baseline_failed", { serverSocket, error: String(e) });
+  }
+}
+
+/**
+ * 重放本窗口错过的帧（连接建立到 Channel 订阅之间，或订阅失败时由全局事件投递前到达的帧）。
+ */
+async function replayMissedFrames(serverSocket: string): Promise<void> {
+  PENDING_REPLAY.delete(serverSocket);
+  const sinceSeq = LAST_FRAME_SEQ.get(serverSocket) ?? 0;
+  try {
+    const replay = await replayRecentFrames(serverSocket, sinceSeq);
+    if (!replay.complete) {
+      tauriLog.warn("Action: network_frame_replay_incomplete", { serverSocket, sinceSeq, latestSeq: replay.latestSeq });
+    }
+    for (const frame of replay.frames) {
+      if (!advanceFrameSeq(serverSocket, frame.seq)) continue;
+      await handleTcpFramePayload(serverSocket, frame.payload);
+    }
+  } catch (e) {
+    tauriLog.warn("Action: network_frame_replay_failed", { serverSocket, sinceSeq, error: String(e) });
+  }
+}
+
+async function handleTcpFrameEvent(event: Event<TcpMessageEvent>): Promise<void> {
+  if (PENDING_REPLAY.has(event.payload.server_socket)) return;
+  await handleTcpFramePayload(event.payload.server_socket, event.payload.payload);
+}
+
+/**
+ * 处理洪泛期间的批量帧：按接收顺序逐条解码，保证解密状态与消息顺序一致。
+ */
+async function handleTcpFrameBatchEvent(event: Event<TcpFrameBatchEvent>): Promise<void> {
+  if (PENDING_REPLAY.has(event.payload.server_socket)) return;
+  for (const payload of event.payload.payloads) {
+    await handleTcpFramePayload(event.payload.server_socket, payload);
+  }
+}
+
+/**
+ * 处理连接 Channel 事件：与全局帧事件走同一解码路径，按序号跳过已重放的帧。
+ */
+async function handleConnectionChannelEvent(serverSocket: string, event: ConnectionChannelEvent): Promise<void> {
+  if (event.kind === "frame") {
+    if (!advanceFrameSeq(serverSocket, event.seq)) return;
+    await handleTcpFramePayload(serverSocket, event.payload);
+  } else if (event.kind === "frameBatch") {
+    for (const [index, payload] of event.payloads.entries()) {
+      if (!advanceFrameSeq(serverSocket, event.firstSeq + index)) continue;
+      await handleTcpFramePayload(serverSocket, payload);
+    }
+  }
+}
+
+/**
+ * 记录本窗口的缺帧便于排查；补齐由 Rust 侧的预取票据或 chat 会话的缺帧监听完成。
+ */
+function handleConnectionFrameGapEvent(event: Event<ConnectionFrameGapEvent>): void {
+  tauriLog.warn("Action: network_connection_frame_gap", { ...event.payload });
+}
+
+/**
+ * 发送 Rust 侧合批好的在线状态订阅/退订帧（仅主窗口收到该事件）。
+ */
+async function handlePresenceSubscriptionBatchEvent(event: Event<PresenceSubscriptionBatchEvent>): Promise<void> {
+  const { serverSocket, route, userIds } = event.payload;
+  const service = TCP_SERVICE.get(serverSocket);
+  if (!service) return;
+  try {
+    await service.send(serverSocket, JSON.stringify({ route, data: { uids: userIds } }));
+  } catch (e) {
+    tauriLog.warn("Action: network_presence_batch_send_failed", { serverSocket, route, error: String(e) });
+  }
+}
+
+/**
+ * 为本窗口订阅该连接的帧 Channel（best-effort：失败时继续使用全局帧事件）。
+ *
+ * 订阅完成后调用 `replay_recent_frames` 补齐订阅前错过的帧；重放任务排在所有 Channel 帧之前。
+ */
+async function subscribeConnectionChannel(serverSocket: string): Promise<void> {
+  if (CONNECTION_SUBSCRIPTIONS.has(serverSocket)) {
+    PENDING_REPLAY.delete(serverSocket);
+    return;
+  }
+  let subscribed: () => void = () => {};
+  const ready = new Promise<void>((resolve) => {
+    subscribed = resolve;
+  });
+  void enqueueFrameTask(serverSocket, async () => {
+    await ready;
+    await replayMissedFrames(serverSocket);
+  });
+  try {
+    const unsubscribe = await subscribeConnection(serverSocket, (event) => {
+      void enqueueFrameTask(serverSocket, () => handleConnectionChannelEvent(serverSocket, event));
+    });
+    CONNECTION_SUBSCRIPTIONS.set(serverSocket, unsubscribe);
+  } catch (e) {
+    tauriLog.warn("Action: network_connection_channel_subscribe_failed", { serverSocket, error: String(e) });
+  } finally {
+    subscribed();
+  }
+}
+
+function unsubscribeConnectionChannel(serverSocket: string): void {
+  const unsubscribe = CONNECTION_SUBSCRIPTIONS.get(serverSocket);
+  if (!unsubscribe) return;
+  CONNECTION_SUBSCRIPTIONS.delete(serverSocket);
+  unsubscribe();
+}
+
 async function ensureTcpFrameListener(): Promise<void> {
   if (tcpFrameListenerSubscribed) return;
   if (tcpFrameListenerStartingPromise) {
@@ -51,6 +234,9 @@ async function ensureTcpFrameListener(): Promise<void> {
   tcpFrameListenerStartingPromise = (async () => {
     try {
       tcpFrameUnlisten = await listenTcpFrame(handleTcpFrameEvent);
+      tcpFrameBatchUnlisten = await listenTcpFrameBatch(handleTcpFrameBatchEvent);
+      connectionFrameGapUnlisten = await listenConnectionFrameGap(handleConnectionFrameGapEvent);
+      presenceBatchUnlisten = await listenPresenceSubscriptionBatch(handlePresenceSubscriptionBatchEvent);
       tcpFrameListenerSubscribed = true;
     } catch (error) {
       tcpFrameListenerSubscribed = false;
@@ -78,6 +264,8 @@ function disposeTcpServiceByKey(key: string): void {
     TCP_SERVICE.delete(key);
   }
   TCP_SERVICE_INIT.delete(key);
+  PENDING_REPLAY.delete(key);
+  unsubscribeConnectionChannel(key);
   void invokeTauri(TAURI_COMMANDS.removeTcpService, { serverSocket: key }).catch((e) => {
     tauriLog.warn("Action: network_tcp_service_remove_failed", { key, error: String(e) });
   });
@@ -128,8 +316,13 @@ export async function createServerTcpService(
 
   const initToken = Symbol(serverSocketKey);
   const initPromise = (async (): Promise<TcpService> => {
+    // 新连接的帧在 Channel 订阅完成前暂不处理，订阅后从基线序号起按序重放。
+    await captureFrameSeqBaseline(serverSocketKey);
+    PENDING_REPLAY.add(serverSocketKey);
     const service = new TcpService(serverSocketKey, transportSocket, { frameConfig });
     TCP_SERVICE.set(serverSocketKey, service);
+    // 握手帧即经 Channel 投递到本窗口，不再经全局事件广播给其它窗口。
+    await subscribeConnectionChannel(serverSocketKey);
 
     tauriLog.debug("Action: network_tcp_service_initialization_started", { serverSocketKey, transportSocket, frameConfig });
 
@@ -141,6 +334,8 @@ export async function createServerTcpService(
       if (!isMockSocket) {
         await service.waitForKeyExchange(KEY_EXCHANGE_TIMEOUT_MS);
         tauriLog.debug("Action: network_handshake_completed", { serverSocketKey, transportSocket, frameConfig });
+        // 新连接上重新订阅各视图仍需要的在线状态（best-effort）。
+        void invokeTauri(TAURI_COMMANDS.resubscribePresence, { serverSocket: serverSocketKey }).catch(() => {});
       } else {
         tauriLog.debug("Action: network_handshake_mock_bypassed", { serverSocketKey, transportSocket, frameConfig });
       }
@@ -155,6 +350,7 @@ export async function createServerTcpService(
       });
       if (TCP_SERVICE.get(serverSocketKey) === service) {
         TCP_SERVICE.delete(serverSocketKey);
+        unsubscribeConnectionChannel(serverSocketKey);
       }
       throw e;
     } finally {
@@ -194,6 +390,18 @@ export async function stopTcpServiceRuntime(): Promise<void> {
     tcpFrameUnlisten();
     tcpFrameUnlisten = null;
   }
+  if (tcpFrameBatchUnlisten) {
+    tcpFrameBatchUnlisten();
+    tcpFrameBatchUnlisten = null;
+  }
+  if (connectionFrameGapUnlisten) {
+    connectionFrameGapUnlisten();
+    connectionFrameGapUnlisten = null;
+  }
+  if (presenceBatchUnlisten) {
+    presenceBatchUnlisten();
+    presenceBatchUnlisten = null;
+  }
   tcpFrameListenerSubscribed = false;
   tcpFrameListenerStartingPromise = null;
 
@@ -29,6 +29,7 @@ type ApiServerResponse = {
   ws_url?: string;
   required_plugins?: string[];
   capabilities?: Record<string, unknown>;
+  oauth?: { authorization_endpoint?: string; client_id?: string; scope?: string };
   server_time?: number;
 };
 
@@ -51,7 +52,20 @@ function mapServerInfo(raw: ApiServerResponse): ServerInfo {
     : undefined;
   const capabilities = raw.capabilities && typeof raw.capabilities === "object" ? (raw.capabilities as Record<string, unknown>) : undefined;
   const serverTimeMs = typeof raw.server_time === "number" ? raw.server_time : undefined;
-  return { serverId, name, brief, avatar, apiVersion, minSupportedApiVersion, wsUrl, requiredPlugins, capabilities, serverTimeMs };
+  const oauthEnabled = typeof raw.oauth?.authorization_endpoint === "string" && raw.oauth.authorization_endpoint.trim() !== "";
+  return {
+    serverId,
+    name,
+    brief,
+    avatar,
+    apiVersion,
+    minSupportedApiVersion,
+    wsUrl,
+    requiredPlugins,
+    capabilities,
+    oauthEnabled,
+    serverTimeMs,
+  };
 }
 
 /**
@@ -51,6 +51,10 @@ export type ServerInfo = {
    * 可选能力/特性标记（由服务端返回）。
    */
   capabilities?: Record<string, unknown>;
+  /**
+   * 服务端是否下发了 OAuth 登录配置（`oauth.authorization_endpoint`）。
+   */
+  oauthEnabled?: boolean;
   /**
    * 服务端时间戳（毫秒；可选，主要用于诊断/对时）。
    */
@@ -136,6 +136,59 @@ export async function importSettingsEnvelope(raw: string): Promise<void> {
   await invokeTauri<void>(TAURI_COMMANDS.settingsImportSettings, { raw });
 }
 
+/** 配置档案包含的分区（缺省为全部包含）。 */
+export type ProfileInclude = {
+  settings?: boolean;
+  servers?: boolean;
+  certificates?: boolean;
+  plugins?: boolean;
+  muteRules?: boolean;
+};
+
+export type PinnedCertificate = {
+  serverSocket: string;
+  tlsPolicy?: string | null;
+  tlsFingerprint: string;
+};
+
+/** 前端持有、随档案迁移的状态（服务器机架快照与固定证书）。 */
+export type ProfileClientState = {
+  serverRacks?: unknown;
+  pinnedCertificates?: PinnedCertificate[];
+};
+
+export type ProfileManifest = {
+  format: string;
+  version: number;
+  createdAt: number;
+  appVersion: string;
+  sections: string[];
+};
+
+export type ProfileImportReport = {
+  sections: string[];
+  settingsApplied: boolean;
+  serversApplied: number;
+  muteRulesAdded: number;
+  /** 需由调用方写回本地的服务器机架快照。 */
+  serverRacks: unknown | null;
+  pinnedCertificates: PinnedCertificate[];
+  /** 需重新安装的插件。 */
+  plugins: Array<{ serverId: string; pluginId: string; currentVersion: string | null; enabled: boolean }>;
+};
+
+export async function exportProfile(
+  destPath: string,
+  include?: ProfileInclude,
+  client?: ProfileClientState,
+): Promise<ProfileManifest> {
+  return invokeTauri<ProfileManifest>(TAURI_COMMANDS.settingsExportProfile, { destPath, include, client });
+}
+
+export async function importProfile(path: string): Promise<ProfileImportReport> {
+  return invokeTauri<ProfileImportReport>(TAURI_COMMANDS.settingsImportProfile, { path });
+}
+
 export async function resetSettingsEnvelope(): Promise<void> {
   await invokeTauri<void>(TAURI_COMMANDS.settingsResetSettings);
 }
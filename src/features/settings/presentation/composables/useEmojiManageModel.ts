@@ -4,7 +4,7 @@
  */
 
 import { ref } from "vue";
-import { invoke } from "@tauri-apps/api/core";
+import { invokeTauri } from "@/shared/tauri/invokeClient";
 import { TAURI_COMMANDS } from "@/shared/tauri/commands";
 import { createLogger } from "@/shared/utils/logger";
 
@@ -29,9 +29,9 @@ export function useEmojiManageModel() {
     if (!uid) return;
     loading.value = true;
     try {
-      emojis.value = await invoke<EmojiEntry[]>(TAURI_COMMANDS.listCustomEmojis, { uid });
+      emojis.value = await invokeTauri<EmojiEntry[]>(TAURI_COMMANDS.listCustomEmojis, { uid });
     } catch (e) {
-      error.value = String(e);
+      error.value = e instanceof Error ? e.message : String(e);
       logger.error("Action: chat_emoji_load_failed", { error: String(e) });
     } finally {
       loading.value = false;
@@ -40,7 +40,7 @@ export function useEmojiManageModel() {
 
   async function addEmoji(sourcePath: string, name: string, tags: string[] = [], uid: string): Promise<void> {
     try {
-      await invoke(TAURI_COMMANDS.saveEmoji, { sourcePath, name, tags, uid });
+      await invokeTauri(TAURI_COMMANDS.saveEmoji, { sourcePath, name, tags, uid });
       await loadEmojis(uid);
     } catch (e) {
       logger.error("Action: chat_emoji_save_failed", { error: String(e) });
@@ -50,7 +50,7 @@ export function useEmojiManageModel() {
 
   async function deleteEmoji(id: string, uid: string): Promise<void> {
     try {
-      await invoke(TAURI_COMMANDS.deleteEmoji, { id, uid });
+      await invokeTauri(TAURI_COMMANDS.deleteEmoji, { id, uid });
       await loadEmojis(uid);
     } catch (e) {
       logger.error("Action: chat_emoji_delete_failed", { error: String(e) });
@@ -59,7 +59,7 @@ export function useEmojiManageModel() {
   }
 
   async function getImagePath(id: string): Promise<string> {
-    return invoke<string>(TAURI_COMMANDS.getEmojiImagePath, { id });
+    return invokeTauri<string>(TAURI_COMMANDS.getEmojiImagePath, { id });
   }
 
   return { emojis, loading, error, loadEmojis, addEmoji, deleteEmoji, getImagePath };
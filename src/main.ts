@@ -19,7 +19,9 @@ import "tdesign-vue-next/es/style/index.css";
 import { getStoredAccent, getStoredTheme, setAccent, setTheme } from "@/shared/utils/theme";
 import "@/shared/serverIdentity";
 import { routeIfSubWindow } from "@/app/bootstrap/subWindowRouting";
+import { startActivityReporter } from "@/app/bootstrap/activityReporter";
 import { registerUserProfileBridge } from "@/app/bootstrap/userProfileBridge";
+import { registerSessionTokenBridge } from "@/app/bootstrap/sessionTokenBridge";
 import { ensureInitialServerSelection, restoreStartupSession } from "@/app/processes/session/api";
 import { ensureSecureChatCacheReady } from "@/shared/utils/chatSecureCache";
 import { getAccountCapabilities } from "@/features/account/api";
@@ -54,6 +56,7 @@ const serverConnectionCapabilities = getServerConnectionCapabilities();
 let pluginsRuntimeLease: Awaited<ReturnType<typeof pluginsCapabilities.runtime.acquireLease>> | null = null;
 let serverConnectionRuntimeLease: Awaited<ReturnType<typeof serverConnectionCapabilities.runtime.acquireLease>> | null = null;
 let trayUnreadBridgeStop: WatchStopHandle | null = null;
+let sessionTokenBridgeStop: (() => void) | null = null;
 let memoryMonitorStartTimer: number | null = null;
 
 authFlowCapabilities.configureInstalledPluginsQueryProvider((serverSocket: string) =>
@@ -102,6 +105,7 @@ async function startMainWindowRuntimes(): Promise<boolean> {
   }
 
   trayUnreadBridgeStop = registerTrayUnreadBridge();
+  sessionTokenBridgeStop = registerSessionTokenBridge();
   registerTrayHoverBridge();
   syncTrayLocaleOnStartup();
 
@@ -113,6 +117,10 @@ function releaseMainWindowRuntimes(): void {
     trayUnreadBridgeStop();
     trayUnreadBridgeStop = null;
   }
+  if (sessionTokenBridgeStop) {
+    sessionTokenBridgeStop();
+    sessionTokenBridgeStop = null;
+  }
   clearTrayUnreadFlashing();
 
   const releaseTasks: Promise<unknown>[] = [];
@@ -145,6 +153,8 @@ startLogPersistence();
 
 // Async startup after mount (fire-and-forget)
 if (hasTauriRuntime) {
+  // 空闲自动锁定依赖各窗口上报的用户活动。
+  startActivityReporter();
   void ensureSecureChatCacheReady().catch((e) => {
     logger.error("Action: api_main_ensure_cache_failed", { error: String(e) });
   });
@@ -0,0 +1,124 @@
+/**
+ * @fileoverview 数据库备份（Tauri DB：frontend → Rust `backup_now` / `list_backups` / `restore_backup` 命令）。
+ *
+ * 说明：
+ * - 备份包含 system DB 与当时已打开的 server DB；定时备份由设置 `scheduled_backup` 控制；
+ * - 恢复会覆盖当前数据，完成后应重新加载 `reopened` 中的 DB 相关状态；
+ * - 远程备份（WebDAV / S3 兼容）上传前用用户口令加密，口令不保存。
+ */
+import { invokeTauri } from "@/shared/tauri";
+import { TAURI_COMMANDS } from "@/shared/tauri/commands";
+
+/**
+ * 备份中的单个 DB 文件。
+ */
+export type BackupDatabase = {
+  key: string;
+  file: string;
+  bytes: number;
+  sha256: string;
+};
+
+/**
+ * 备份信息。
+ */
+export type BackupInfo = {
+  id: string;
+  createdAt: number;
+  scheduled: boolean;
+  databases: BackupDatabase[];
+  totalBytes: number;
+};
+
+/**
+ * 恢复结果。
+ */
+export type RestoreBackupReport = {
+  id: string;
+  restored: string[];
+  reopened: string[];
+};
+
+/**
+ * 立即备份。
+ */
+export function backupNow(): Promise<Omit<BackupInfo, "totalBytes">> {
+  return invokeTauri<Omit<BackupInfo, "totalBytes">>(TAURI_COMMANDS.backupNow);
+}
+
+/**
+ * 列出已有备份（最新在前）。
+ */
+export function listBackups(): Promise<BackupInfo[]> {
+  return invokeTauri<BackupInfo[]>(TAURI_COMMANDS.listBackups);
+}
+
+/**
+ * 从备份恢复（覆盖当前数据）。
+ */
+export function restoreBackup(id: string): Promise<RestoreBackupReport> {
+  return invokeTauri<RestoreBackupReport>(TAURI_COMMANDS.restoreBackup, { id });
+}
+
+/**
+ * 远程备份目标设置（密码 / secret key 不在此返回）。
+ */
+export type BackupRemoteConfig = {
+  kind: "webdav" | "s3";
+  /** WebDAV 目录 URL，或 S3 path-style URL（`https://host/bucket[/prefix]`）。 */
+  url: string;
+  /** WebDAV 用户名或 S3 access key id。 */
+  username: string;
+  region?: string | null;
+};
+
+/**
+ * 远程备份目标状态。
+ */
+export type BackupRemoteStatus = BackupRemoteConfig & {
+  hasSecret: boolean;
+};
+
+/**
+ * 远程备份条目。
+ */
+export type RemoteBackupEntry = {
+  name: string;
+  id: string;
+  bytes: number | null;
+};
+
+/**
+ * 读取远程备份目标设置。
+ */
+export function getBackupRemote(): Promise<BackupRemoteStatus | null> {
+  return invokeTauri<BackupRemoteStatus | null>(TAURI_COMMANDS.getBackupRemote);
+}
+
+/**
+ * 设置远程备份目标；`config` 为 null 表示停用，`secret` 为空表示保留已保存的凭据。
+ */
+export function setBackupRemote(config: BackupRemoteConfig | null, secret?: string): Promise<void> {
+  return invokeTauri<void>(TAURI_COMMANDS.setBackupRemote, { config, secret: secret ?? null });
+}
+
+/**
+ * 用口令加密并上传本地备份（进度见 `listenBackupTransferProgress`）。
+ */
+export function uploadBackup(id: string, passphrase: string): Promise<RemoteBackupEntry> {
+  return invokeTauri<RemoteBackupEntry>(TAURI_COMMANDS.uploadBackup, { id, passphrase });
+}
+
+/**
+ * 列出远程备份（最新在前）。
+ */
+export function listRemoteBackups(): Promise<RemoteBackupEntry[]> {
+  return invokeTauri<RemoteBackupEntry[]>(TAURI_COMMANDS.listRemoteBackups);
+}
+
+/**
+ * 下载、解密并恢复远程备份（覆盖当前数据）。
+ */
+export function restoreRemoteBackup(name: string, passphrase: string): Promise<RestoreBackupReport> {
+  return invokeTauri<RestoreBackupReport>(TAURI_COMMANDS.restoreRemoteBackup, { name, passphrase });
+}
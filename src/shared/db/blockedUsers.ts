@@ -0,0 +1,117 @@
+/**
+ * @fileoverview 屏蔽用户（Tauri DB：frontend → Rust `block_user` 等命令）。
+ *
+ * 说明：
+ * - 屏蔽列表存放在 per-server DB，Rust 侧的消息查询已排除被屏蔽用户；
+ * - 通知与未读计数在前端计算，这里按 server 缓存屏蔽用户 id 集合供其过滤；
+ *   实时事件路径需要同步判断，因此另保留一份最近一次读取的快照（`peekBlockedUserIds`）；
+ * - 增删屏蔽后立即更新快照并失效对应 server 的缓存。
+ */
+import { invokeTauri } from "@/shared/tauri";
+import { TAURI_COMMANDS } from "@/shared/tauri/commands";
+import { serverDbKey } from "./tauriDbClient";
+
+/**
+ * 被屏蔽用户（Rust -> 前端）。
+ */
+export type BlockedUser = {
+  userId: number;
+  name: string | null;
+  blockedAt: number;
+  /** 是否已同步到服务端。 */
+  synced: boolean;
+};
+
+/**
+ * 服务端同步参数；不传时只在本地屏蔽。
+ */
+export type BlockSyncOptions = {
+  serverSocket: string;
+  headers?: Record<string, string>;
+  tlsPolicy?: string;
+  tlsFingerprint?: string;
+};
+
+const blockedIdCache = new Map<string, Promise<ReadonlySet<string>>>();
+const blockedIdSnapshots = new Map<string, ReadonlySet<string>>();
+
+function updateSnapshot(key: string, userId: number, blocked: boolean): void {
+  const next = new Set(blockedIdSnapshots.get(key) ?? []);
+  if (blocked) next.add(String(userId));
+  else next.delete(String(userId));
+  blockedIdSnapshots.set(key, next);
+  blockedIdCache.delete(key);
+}
+
+/**
+ * 列出指定 server 的屏蔽用户（最近屏蔽在前）。
+ *
+ * @param serverSocket - 服务器 Socket 地址。
+ */
+export function listBlockedUsers(serverSocket: string): Promise<BlockedUser[]> {
+  return invokeTauri<BlockedUser[]>(TAURI_COMMANDS.listBlockedUsers, { dbKey: serverDbKey(serverSocket) });
+}
+
+/**
+ * 屏蔽用户。
+ *
+ * @param serverSocket - 服务器 Socket 地址。
+ * @param userId - 用户 id。
+ * @param sync - 可选的服务端同步参数。
+ */
+export async function blockUser(serverSocket: string, userId: number, sync?: BlockSyncOptions): Promise<BlockedUser> {
+  const key = serverDbKey(serverSocket);
+  const blocked = await invokeTauri<BlockedUser>(TAURI_COMMANDS.blockUser, { dbKey: key, userId, sync });
+  updateSnapshot(key, userId, true);
+  return blocked;
+}
+
+/**
+ * 解除屏蔽。
+ *
+ * @returns 本地是否存在该屏蔽记录。
+ */
+export async function unblockUser(serverSocket: string, userId: number, sync?: BlockSyncOptions): Promise<boolean> {
+  const key = serverDbKey(serverSocket);
+  const removed = await invokeTauri<boolean>(TAURI_COMMANDS.unblockUser, { dbKey: key, userId, sync });
+  updateSnapshot(key, userId, false);
+  return removed;
+}
+
+/**
+ * 读取屏蔽用户 id 集合（字符串形式，便于与消息的 `from.id` 比较；带缓存）。
+ *
+ * 读取失败时返回空集合，不影响消息展示。
+ *
+ * @param serverSocket - 服务器 Socket 地址。
+ */
+export function getBlockedUserIds(serverSocket: string): Promise<ReadonlySet<string>> {
+  const key = serverDbKey(serverSocket);
+  const cached = blockedIdCache.get(key);
+  if (cached) return cached;
+  const pending = listBlockedUsers(serverSocket)
+    .then((list) => {
+      const ids: ReadonlySet<string> = new Set(list.map((u) => String(u.userId)));
+      blockedIdSnapshots.set(key, ids);
+      return ids;
+    })
+    .catch(() => {
+      blockedIdCache.delete(key);
+      return new Set<string>() as ReadonlySet<string>;
+    });
+  blockedIdCache.set(key, pending);
+  return pending;
+}
+
+/**
+ * 同步读取最近一次加载的屏蔽用户 id 集合；尚未加载时返回空集合并在后台加载。
+ *
+ * @param serverSocket - 服务器 Socket 地址。
+ */
+export function peekBlockedUserIds(serverSocket: string): ReadonlySet<string> {
+  const key = serverDbKey(serverSocket);
+  const snapshot = blockedIdSnapshots.get(key);
+  if (snapshot) return snapshot;
+  void getBlockedUserIds(serverSocket);
+  return new Set<string>();
+}
@@ -0,0 +1,68 @@
+/**
+ * @fileoverview 频道归档（Tauri DB：frontend → Rust `archive_channel` / `restore_archive` 命令）。
+ *
+ * 说明：
+ * - 归档会把频道中已发送的消息及其翻译写入压缩文件，并从本地 server DB 删除；
+ * - 恢复时对应的 server DB 必须已打开，本地较新或已删除的消息不会被覆盖。
+ */
+import { invokeTauri } from "@/shared/tauri";
+import { TAURI_COMMANDS } from "@/shared/tauri/commands";
+import { serverDbKey } from "./tauriDbClient";
+
+/**
+ * 归档清单。
+ */
+export type ChannelArchiveManifest = {
+  format: string;
+  version: number;
+  serverSocket: string;
+  dbKey: string;
+  channelId: number;
+  createdAt: number;
+  messageCount: number;
+  translationCount: number;
+  oldestAt: number | null;
+  newestAt: number | null;
+  messagesSha256: string;
+  translationsSha256: string;
+};
+
+/**
+ * 归档结果。
+ */
+export type ChannelArchiveReport = {
+  path: string;
+  manifest: ChannelArchiveManifest;
+  fileBytes: number;
+};
+
+/**
+ * 恢复结果。
+ */
+export type ChannelArchiveRestoreReport = {
+  serverSocket: string;
+  dbKey: string;
+  channelId: number;
+  restored: number;
+  skipped: number;
+  translations: number;
+};
+
+/**
+ * 将频道归档到 `dest`，成功后从本地 DB 删除已归档的消息。
+ */
+export function archiveChannel(serverSocket: string, channelId: number, dest: string): Promise<ChannelArchiveReport> {
+  return invokeTauri<ChannelArchiveReport>(TAURI_COMMANDS.archiveChannel, {
+    dbKey: serverDbKey(serverSocket),
+    serverSocket,
+    channelId,
+    dest,
+  });
+}
+
+/**
+ * 将归档文件合并回对应的 server DB。
+ */
+export function restoreArchive(path: string): Promise<ChannelArchiveRestoreReport> {
+  return invokeTauri<ChannelArchiveRestoreReport>(TAURI_COMMANDS.restoreArchive, { path });
+}
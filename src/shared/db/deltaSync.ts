@@ -0,0 +1,91 @@
+/**
+ * @fileoverview 频道增量同步（Tauri DB：frontend → Rust `sync_channel_delta` 等命令）。
+ *
+ * 说明：
+ * - 变更游标按频道存放在 per-server DB，Rust 侧拉取 `/api/channels/{cid}/changes` 并在事务内写入消息；
+ * - `cursorReset` 为 true 表示服务端游标已失效，调用方应回退到全量补拉（最新页）；
+ * - `hasMore` 为 true 表示单次同步达到页数上限，可再次调用继续；
+ * - 服务端未声明 `channels/changes` 能力（或 `sync_engine_v2` feature flag 未启用）时命令返回
+ *   `DB_DELTA_SYNC_UNSUPPORTED`，
+ *   `syncChannelDeltaIfSupported` 将其视为“无需增量同步”。
+ */
+import { invokeTauri, TauriCommandError } from "@/shared/tauri";
+import { TAURI_COMMANDS } from "@/shared/tauri/commands";
+import { serverDbKey } from "./tauriDbClient";
+
+/**
+ * 服务端请求参数（与 `BlockSyncOptions` 一致）。
+ */
+export type DeltaSyncOptions = {
+  serverSocket: string;
+  headers?: Record<string, string>;
+  tlsPolicy?: string;
+  tlsFingerprint?: string;
+};
+
+/**
+ * 一次增量同步的结果（Rust -> 前端）。
+ */
+export type DeltaSyncResult = {
+  inserted: string[];
+  updated: string[];
+  deleted: string[];
+  /** 被冲突规则丢弃的 mid（过期版本或已删除）。 */
+  skipped: string[];
+  cursorReset: boolean;
+  hasMore: boolean;
+};
+
+/**
+ * 频道同步状态（调试用）。
+ */
+export type SyncState = {
+  channelId: number;
+  cursor: string | null;
+  syncedAt: number | null;
+  appliedChanges: number;
+  localMessages: number;
+  tombstones: number;
+};
+
+/**
+ * 按变更游标增量同步频道消息。
+ *
+ * @param channelId - 频道 id。
+ * @param options - 服务端请求参数。
+ */
+export function syncChannelDelta(channelId: number, options: DeltaSyncOptions): Promise<DeltaSyncResult> {
+  return invokeTauri<DeltaSyncResult>(TAURI_COMMANDS.syncChannelDelta, {
+    dbKey: serverDbKey(options.serverSocket),
+    channelId,
+    options,
+  });
+}
+
+/**
+ * 按变更游标增量同步频道消息；服务端不支持增量同步时返回 null。
+ *
+ * @param channelId - 频道 id。
+ * @param options - 服务端请求参数。
+ */
+export async function syncChannelDeltaIfSupported(
+  channelId: number,
+  options: DeltaSyncOptions,
+): Promise<DeltaSyncResult | null> {
+  try {
+    return await syncChannelDelta(channelId, options);
+  } catch (e) {
+    if (e instanceof TauriCommandError && e.code === "DB_DELTA_SYNC_UNSUPPORTED") return null;
+    throw e;
+  }
+}
+
+/**
+ * 读取频道同步状态。
+ *
+ * @param serverSocket - 服务器 Socket 地址。
+ * @param channelId - 频道 id。
+ */
+export function getSyncState(serverSocket: string, channelId: number): Promise<SyncState> {
+  return invokeTauri<SyncState>(TAURI_COMMANDS.getSyncState, { dbKey: serverDbKey(serverSocket), channelId });
+}
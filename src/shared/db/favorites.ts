@@ -0,0 +1,71 @@
+/**
+ * @fileoverview 跨 server 频道收藏（Tauri DB：frontend → Rust `add_favorite` 等命令）。
+ *
+ * 说明：
+ * - 收藏存放在 system DB，按 `(serverSocket, channelId)` 唯一；别名只影响本地展示；
+ * - 未读数依赖 `setChannelReadState` 缓存的已读时间，应在上报服务端已读后调用；
+ * - 对应 server DB 未打开时 `available` 为 `false`。
+ */
+import { invokeTauri } from "@/shared/tauri";
+import { TAURI_COMMANDS } from "@/shared/tauri/commands";
+import { serverDbKey } from "./tauriDbClient";
+
+/**
+ * 收藏记录。
+ */
+export type Favorite = {
+  id: number;
+  serverSocket: string;
+  dbKey: string;
+  channelId: number;
+  alias: string | null;
+  position: number;
+  createdAt: number;
+};
+
+/**
+ * 带频道状态的收藏。
+ */
+export type FavoriteWithState = Favorite & {
+  available: boolean;
+  channelName: string | null;
+  unreadCount: number;
+  lastActiveAt: number | null;
+};
+
+/**
+ * 收藏频道；已收藏时更新别名。
+ */
+export function addFavorite(serverSocket: string, channelId: number, alias?: string | null): Promise<Favorite> {
+  return invokeTauri<Favorite>(TAURI_COMMANDS.addFavorite, {
+    serverSocket,
+    dbKey: serverDbKey(serverSocket),
+    channelId,
+    alias: alias ?? null,
+  });
+}
+
+/**
+ * 取消收藏。
+ */
+export function removeFavorite(serverSocket: string, channelId: number): Promise<boolean> {
+  return invokeTauri<boolean>(TAURI_COMMANDS.removeFavorite, { serverSocket, channelId });
+}
+
+/**
+ * 列出所有收藏及其频道状态（统一收藏栏）。
+ */
+export function listFavoritesWithState(): Promise<FavoriteWithState[]> {
+  return invokeTauri<FavoriteWithState[]>(TAURI_COMMANDS.listFavoritesWithState);
+}
+
+/**
+ * 缓存频道已读时间（只会前移）。
+ */
+export function setChannelReadState(serverSocket: string, channelId: number, lastReadTime: number): Promise<void> {
+  return invokeTauri<void>(TAURI_COMMANDS.setChannelReadState, {
+    dbKey: serverDbKey(serverSocket),
+    channelId,
+    lastReadTime,
+  });
+}
@@ -3,3 +3,13 @@
  */
 export * from "./types";
 export * from "./tauriDbClient";
+export * from "./queryPlan";
+export * from "./namedQueries";
+export * from "./blockedUsers";
+export * from "./userDirectory";
+export * from "./deltaSync";
+export * from "./optimisticSend";
+export * from "./retention";
+export * from "./channelArchive";
+export * from "./favorites";
+export * from "./backups";
@@ -0,0 +1,36 @@
+/**
+ * @fileoverview 命名查询（Tauri DB：frontend → Rust `db_run_named` 命令）。
+ *
+ * 说明：
+ * - 语句由 Rust 侧注册并审核（见 `sql_policy.rs` 的 `NAMED_QUERIES`），前端只传名称与参数；
+ * - 在 `db_sql_policy = named_only` 时，插件来源只能通过这里访问 server DB。
+ */
+import { invokeTauri } from "@/shared/tauri";
+import { TAURI_COMMANDS } from "@/shared/tauri/commands";
+import { serverDbKey } from "./tauriDbClient";
+import type { DbValue } from "./types";
+
+/**
+ * 内置命名查询。
+ */
+export type NamedQueryName = "channels.list" | "messages.recent" | "users.get" | "kv.get" | "kv.set";
+
+/**
+ * 命名查询结果：查询类填充 `columns`/`rows`，执行类填充 `rows_affected`。
+ */
+export type DbNamedResult = {
+  columns: string[];
+  rows: DbValue[][];
+  rows_affected: number;
+};
+
+/**
+ * 在指定 server DB 上执行命名查询。
+ */
+export function runNamedQuery(serverSocket: string, name: NamedQueryName, params: DbValue[] = []): Promise<DbNamedResult> {
+  return invokeTauri<DbNamedResult>(TAURI_COMMANDS.dbRunNamed, {
+    key: serverDbKey(serverSocket),
+    name,
+    params,
+  });
+}
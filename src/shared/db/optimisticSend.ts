@@ -0,0 +1,89 @@
+/**
+ * @fileoverview 乐观发送（Tauri DB：frontend → Rust `send_message_optimistic` 等命令）。
+ *
+ * 说明：
+ * - Rust 侧立即写入临时消息行（`pending:<clientId>`）并在后台发送；
+ * - 确认后临时行被替换为服务端 mid，状态经 `message-status-changed` 事件通知（见 `listenMessageStatusChanged`）；
+ * - 失败的消息保留在发件箱中，可重发或丢弃。
+ */
+import { invokeTauri } from "@/shared/tauri";
+import { TAURI_COMMANDS } from "@/shared/tauri/commands";
+import { serverDbKey } from "./tauriDbClient";
+
+/**
+ * 服务端请求参数（与 `BlockSyncOptions` 一致；headers 不会持久化）。
+ */
+export type OutboxSendOptions = {
+  serverSocket: string;
+  headers?: Record<string, string>;
+  tlsPolicy?: string;
+  tlsFingerprint?: string;
+};
+
+/**
+ * 发送请求体（与 `POST /api/channels/{cid}/messages` 一致）。
+ */
+export type OutgoingMessage = {
+  domain: string;
+  domain_version: string;
+  data: unknown;
+  reply_to_mid?: string;
+};
+
+/**
+ * 已写入的临时消息。
+ */
+export type ProvisionalMessage = {
+  clientId: string;
+  provisionalId: string;
+  createdAt: number;
+};
+
+/**
+ * 乐观发送一条消息。
+ *
+ * @param channelId - 频道 id。
+ * @param userId - 当前用户 id。
+ * @param preview - 本地回显文本。
+ * @param message - 发送请求体。
+ * @param options - 服务端请求参数。
+ */
+export function sendMessageOptimistic(
+  channelId: number,
+  userId: number,
+  preview: string,
+  message: OutgoingMessage,
+  options: OutboxSendOptions,
+): Promise<ProvisionalMessage> {
+  return invokeTauri<ProvisionalMessage>(TAURI_COMMANDS.sendMessageOptimistic, {
+    dbKey: serverDbKey(options.serverSocket),
+    channelId,
+    userId,
+    preview,
+    message,
+    options,
+  });
+}
+
+/**
+ * 重发一条失败的乐观消息。
+ *
+ * @returns 发件箱中是否仍存在该记录。
+ */
+export function retryOptimisticMessage(clientId: string, options: OutboxSendOptions): Promise<boolean> {
+  return invokeTauri<boolean>(TAURI_COMMANDS.retryOptimisticMessage, {
+    dbKey: serverDbKey(options.serverSocket),
+    clientId,
+    options,
+  });
+}
+
+/**
+ * 丢弃一条未发送成功的乐观消息。
+ *
+ * @param serverSocket - 服务器 Socket 地址。
+ * @param clientId - 临时消息的 clientId。
+ */
+export function discardOptimisticMessage(serverSocket: string, clientId: string): Promise<boolean> {
+  return invokeTauri<boolean>(TAURI_COMMANDS.discardOptimisticMessage, { dbKey: serverDbKey(serverSocket), clientId });
+}
@@ -0,0 +1,44 @@
+/**
+ * @fileoverview 查询计划检查（开发辅助：frontend → Rust `db_explain` 命令）。
+ *
+ * 说明：
+ * - 仅 debug 构建可用，release 构建会返回 `DB_EXPLAIN_DEV_ONLY`；
+ * - 只接受只读查询，`runs` 次执行用于统计耗时。
+ */
+import { invokeTauri } from "@/shared/tauri";
+import { TAURI_COMMANDS } from "@/shared/tauri/commands";
+import type { DbValue } from "./types";
+
+/**
+ * `EXPLAIN QUERY PLAN` 的一行。
+ */
+export type QueryPlanStep = {
+  id: number;
+  parent: number;
+  detail: string;
+};
+
+/**
+ * 查询计划与耗时统计。
+ */
+export type DbExplainResult = {
+  plan: QueryPlanStep[];
+  planText: string;
+  runs: number;
+  rows: number;
+  minMs: number;
+  avgMs: number;
+  maxMs: number;
+};
+
+/**
+ * 检查查询计划并统计耗时。
+ */
+export function dbExplain(key: string, sql: string, params?: DbValue[], runs?: number): Promise<DbExplainResult> {
+  return invokeTauri<DbExplainResult>(TAURI_COMMANDS.dbExplain, {
+    key,
+    sql,
+    params: params ?? null,
+    runs: runs ?? null,
+  });
+}
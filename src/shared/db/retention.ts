@@ -0,0 +1,79 @@
+/**
+ * @fileoverview 消息保留策略（Tauri DB：frontend → Rust `set_channel_retention` 等命令）。
+ *
+ * 说明：
+ * - 全局默认值来自设置 `message_retention_days` / `message_retention_max_per_channel`；
+ * - 频道覆盖字段为 `null` 表示沿用全局值，`0` 表示不限制；
+ * - 自动清理由 Rust 后台任务执行，`previewPrune` 只用于展示将被删除的内容。
+ */
+import { invokeTauri } from "@/shared/tauri";
+import { TAURI_COMMANDS } from "@/shared/tauri/commands";
+import { serverDbKey } from "./tauriDbClient";
+
+/**
+ * 频道级覆盖。
+ */
+export type ChannelRetentionOverride = {
+  keepDays: number | null;
+  keepCount: number | null;
+};
+
+/**
+ * 生效的保留策略（0 = 不限制）。
+ */
+export type RetentionPolicy = {
+  keepDays: number;
+  keepCount: number;
+};
+
+/**
+ * 频道保留设置（覆盖值与生效值）。
+ */
+export type ChannelRetention = {
+  channelId: number;
+  overrides: ChannelRetentionOverride;
+  effective: RetentionPolicy;
+};
+
+/**
+ * 清理预览。
+ */
+export type PrunePreview = {
+  channelId: number;
+  policy: RetentionPolicy;
+  messageCount: number;
+  pruneCount: number;
+  newestPrunedAt: number | null;
+  /** 将被删除的消息 id（最旧在前，最多 100 条）。 */
+  messageIds: string[];
+  dependentRows: number;
+};
+
+/**
+ * 设置频道级保留策略（两个字段都为 `null` 时恢复为全局设置）。
+ */
+export function setChannelRetention(
+  serverSocket: string,
+  channelId: number,
+  overrides: ChannelRetentionOverride,
+): Promise<ChannelRetention> {
+  return invokeTauri<ChannelRetention>(TAURI_COMMANDS.setChannelRetention, {
+    dbKey: serverDbKey(serverSocket),
+    channelId,
+    overrides,
+  });
+}
+
+/**
+ * 读取频道保留策略。
+ */
+export function getChannelRetention(serverSocket: string, channelId: number): Promise<ChannelRetention> {
+  return invokeTauri<ChannelRetention>(TAURI_COMMANDS.getChannelRetention, { dbKey: serverDbKey(serverSocket), channelId });
+}
+
+/**
+ * 预览按当前策略将被删除的消息。
+ */
+export function previewPrune(serverSocket: string, channelId: number): Promise<PrunePreview> {
+  return invokeTauri<PrunePreview>(TAURI_COMMANDS.previewPrune, { dbKey: serverDbKey(serverSocket), channelId });
+}
@@ -0,0 +1,38 @@
+/**
+ * @fileoverview 本地用户目录（Tauri DB：frontend → Rust `record_users` 命令）。
+ *
+ * 说明：
+ * - 拉取用户资料与频道成员后把 `uid → 展示名` 写入 per-server DB 的 `users` 表；
+ *   快速切换、资料卡预取、助手上下文与屏蔽列表都依赖该表解析用户名；
+ * - 写入是旁路行为：失败只记录日志，不影响原请求的结果。
+ */
+import { invokeTauri } from "@/shared/tauri";
+import { TAURI_COMMANDS } from "@/shared/tauri/commands";
+import { createLogger } from "@/shared/utils/logger";
+import { serverDbKey } from "./tauriDbClient";
+
+const logger = createLogger("userDirectory");
+
+/**
+ * 待记录的用户（服务端 uid + 展示名）。
+ */
+export type UserDirectoryEntry = {
+  uid: string;
+  name: string;
+};
+
+/**
+ * 记录服务端返回的用户资料（不等待结果，失败只记日志）。
+ *
+ * @param serverSocket - 服务器 Socket 地址。
+ * @param users - 用户列表；展示名为空的条目会被忽略。
+ */
+export function recordServerUsers(serverSocket: string, users: UserDirectoryEntry[]): void {
+  const entries = users
+    .map((u) => ({ uid: String(u.uid ?? "").trim(), name: String(u.name ?? "").trim() }))
+    .filter((u) => u.uid && u.name);
+  if (!serverSocket.trim() || entries.length === 0) return;
+  invokeTauri<number>(TAURI_COMMANDS.recordUsers, { dbKey: serverDbKey(serverSocket), users: entries }).catch((e) => {
+    logger.warn("Action: chat_record_users_failed", { socket: serverSocket, count: entries.length, error: String(e) });
+  });
+}
@@ -127,6 +127,31 @@ export async function openTempFile(fileId: string): Promise<void> {
   return invokeTauri<void>(TAURI_COMMANDS.openTempFile, { fileId });
 }
 
+/**
+ * 按区间读取已下载附件的原始字节（二进制 IPC，不经 JSON 数组序列化）。
+ *
+ * 说明：单次最多返回 4 MiB；返回长度小于 `length` 表示已到文件末尾。
+ */
+export async function readAttachmentRange(fileId: string, offset: number, length: number): Promise<Uint8Array> {
+  const buffer = await invokeTauri<ArrayBuffer>(TAURI_COMMANDS.readAttachmentRange, { fileId, offset, len: length });
+  return new Uint8Array(buffer);
+}
+
+/**
+ * 分段流式读取整个附件。
+ *
+ * @param chunkSize - 每段字节数（默认 1 MiB）。
+ */
+export async function* streamAttachment(fileId: string, chunkSize = 1024 * 1024): AsyncGenerator<Uint8Array> {
+  let offset = 0;
+  for (;;) {
+    const chunk = await readAttachmentRange(fileId, offset, chunkSize);
+    if (chunk.byteLength > 0) yield chunk;
+    if (chunk.byteLength < chunkSize) return;
+    offset += chunk.byteLength;
+  }
+}
+
 export function getDownloadTasks(): Map<string, DownloadTask> {
   return downloadTasks;
 }
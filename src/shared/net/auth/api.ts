@@ -6,9 +6,13 @@
  */
 
 export {
+  applySessionFromRust,
   ensureValidAccessToken,
   ensureValidAuthSession,
   onAuthSessionChanged,
   revokeAndClearSession,
   startAuthSessionAutoRefresh,
 } from "./authSessionManager";
+
+export { openAuthWindow, type AuthWindowOutcome } from "./authWindow";
+export { loginWithOAuth, type OAuthLoginResult } from "./oauthLogin";
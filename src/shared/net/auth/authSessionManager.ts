@@ -16,6 +16,9 @@ import { createLogger } from "@/shared/utils/logger";
 import { getDeviceId } from "@/shared/utils/deviceId";
 import { readAuthSession, writeAuthSession, type AuthSession } from "@/shared/utils/localState";
 import { isApiRequestError } from "@/shared/net/http/apiErrors";
+import { getServerTlsConfig } from "@/shared/net/tls/serverTlsConfigProvider";
+import { invokeTauri, TAURI_COMMANDS, type SessionTokensPayload } from "@/shared/tauri";
+import { isTauriRuntimeAvailable } from "@/shared/tauri/runtime";
 
 const logger = createLogger("authSessionManager");
 
@@ -91,6 +94,42 @@ function shouldRefreshSoon(session: AuthSession, nowMs: number): boolean {
   return expiresAt <= nowMs + skewMs;
 }
 
+/**
+ * 通过 Rust 刷新会话（与 `api_request_json` 的 401 自动刷新共用同一把串行锁，
+ * 避免两侧同时使用同一个 refresh token 导致轮换冲突）。
+ *
+ * @param serverSocket - 服务端 socket。
+ * @param current - 当前 session。
+ * @returns 刷新成功返回新 session；失败返回 null。
+ */
+async function refreshViaRust(serverSocket: string, current: AuthSession): Promise<AuthSession | null> {
+  const tls = getServerTlsConfig(serverSocket);
+  const tokens: SessionTokensPayload = {
+    accessToken: current.accessToken,
+    refreshToken: current.refreshToken,
+    expiresAtMs: current.expiresAtMs ?? null,
+    deviceId: getDeviceId(),
+  };
+  try {
+    const next = await invokeTauri<SessionTokensPayload | null>(TAURI_COMMANDS.refreshSessionTokens, {
+      serverSocket,
+      tokens,
+      tlsPolicy: tls.tlsPolicy,
+      tlsFingerprint: tls.tlsFingerprint,
+    });
+    if (!next) return null;
+    return {
+      accessToken: next.accessToken,
+      refreshToken: next.refreshToken,
+      uid: current.uid,
+      expiresAtMs: typeof next.expiresAtMs === "number" ? next.expiresAtMs : undefined,
+    };
+  } catch (e) {
+    logger.warn("Action: auth_session_refresh_failed", { socket: serverSocket, error: String(e) });
+    return null;
+  }
+}
+
 /**
  * 使用已存储的 refresh token 执行 `POST /api/auth/refresh`。
  *
@@ -153,7 +192,9 @@ export async function ensureValidAuthSession(serverSocket: string): Promise<Auth
   if (inflight) return inflight;
 
   const p = (async () => {
-    const next = await refreshViaHttp(socket, current.refreshToken);
+    const next = isTauriRuntimeAvailable()
+      ? await refreshViaRust(socket, current)
+      : await refreshViaHttp(socket, current.refreshToken);
     if (!next) return current;
     writeAuthSession(socket, next);
     emitSession(socket, next);
@@ -233,6 +274,31 @@ export function startAuthSessionAutoRefresh(serverSocket: string): AutoRefreshHa
   };
 }
 
+/**
+ * 应用由 Rust 侧推送的 session 变化（自动刷新成功或会话过期）。
+ *
+ * @param serverSocket - 服务端 socket。
+ * @param tokens - 新令牌；为 null 表示会话已过期，清空本地 session。
+ */
+export function applySessionFromRust(serverSocket: string, tokens: SessionTokensPayload | null): void {
+  const socket = serverSocket.trim();
+  if (!socket) return;
+  if (!tokens) {
+    logger.warn("Action: auth_session_expired", { socket });
+    writeAuthSession(socket, null);
+    emitSession(socket, null);
+    return;
+  }
+  const next: AuthSession = {
+    accessToken: tokens.accessToken,
+    refreshToken: tokens.refreshToken,
+    uid: readAuthSession(socket)?.uid,
+    expiresAtMs: typeof tokens.expiresAtMs === "number" ? tokens.expiresAtMs : undefined,
+  };
+  writeAuthSession(socket, next);
+  emitSession(socket, next);
+}
+
 /**
  * Best-effort：吊销本地 session 对应的 refresh token，并清空本地存储。
  *
@@ -0,0 +1,34 @@
+/**
+ * @fileoverview authWindow.ts
+ * @description 网络基础设施：服务端网页认证步骤窗口（验证码、SSO 等）。
+ *
+ * 说明：
+ * - 认证页面在 Rust 打开的独立无痕窗口中加载，回调中的令牌由 Rust 解析并保存；
+ * - 前端只拿到结束状态：`sessionStored` 表示令牌已写入 Rust 会话存储（`api_request_json` 自动附加）；
+ * - 回调未带 access/refresh token（例如只带授权码）时以 `missing_credentials` 失败，
+ *   授权码登录请走 OAuth 流程（`loginWithOAuth`）。
+ */
+
+import { invokeTauri, TAURI_COMMANDS } from "@/shared/tauri";
+
+export type AuthWindowOutcome = {
+  status: "completed" | "cancelled" | "timed_out" | "failed";
+  sessionStored: boolean;
+  error: string | null;
+};
+
+/**
+ * 打开认证窗口并等待其结束。
+ *
+ * @param serverSocket - 需要认证的服务器。
+ * @param url - 认证页面地址（http/https）。
+ * @param redirectUrl - 回调地址（可选，缺省由 Rust 决定）。
+ * @returns 认证窗口结束状态（不含凭据）。
+ */
+export function openAuthWindow(serverSocket: string, url: string, redirectUrl?: string): Promise<AuthWindowOutcome> {
+  return invokeTauri<AuthWindowOutcome>(TAURI_COMMANDS.openAuthWindow, {
+    serverSocket,
+    url,
+    redirectUrl: redirectUrl ?? null,
+  });
+}
@@ -0,0 +1,34 @@
+/**
+ * @fileoverview oauthLogin.ts
+ * @description 网络基础设施：OAuth2/OIDC 浏览器登录（PKCE + 本地回环回调，由 Rust 完成）。
+ *
+ * 说明：
+ * - 授权端点由 `GET /api/server` 的 `oauth` 字段下发，协议见 `docs/api/11-http-endpoints-v1.md`；
+ * - 令牌由 Rust 换取并写入系统凭据存储，同时随结果返回，由登录编排写入本地 session。
+ */
+
+import { getDeviceId } from "@/shared/utils/deviceId";
+import { getServerTlsConfig } from "@/shared/net/tls/serverTlsConfigProvider";
+import { invokeTauri, TAURI_COMMANDS, type SessionTokensPayload } from "@/shared/tauri";
+
+export type OAuthLoginResult = {
+  uid: string | null;
+  isNewUser: boolean;
+  tokens: SessionTokensPayload;
+};
+
+/**
+ * 在系统浏览器中完成 OAuth 登录。
+ *
+ * @param serverSocket - 目标服务器。
+ * @returns 登录结果（含令牌）。
+ */
+export function loginWithOAuth(serverSocket: string): Promise<OAuthLoginResult> {
+  const tls = getServerTlsConfig(serverSocket);
+  return invokeTauri<OAuthLoginResult>(TAURI_COMMANDS.loginOauth, {
+    serverSocket,
+    deviceId: getDeviceId(),
+    tlsPolicy: tls.tlsPolicy,
+    tlsFingerprint: tls.tlsFingerprint,
+  });
+}
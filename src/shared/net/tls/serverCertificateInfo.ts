@@ -0,0 +1,37 @@
+/**
+ * @fileoverview 服务器证书详情（信任对话框使用）。
+ * @description
+ * - 由 Rust 侧完成 TLS 握手并解析叶子证书，WebView 无法直接读取自签证书；
+ * - 探测总是接受任意证书，结果仅用于展示，是否信任由用户决定。
+ */
+
+import { invokeTauri, TAURI_COMMANDS } from "@/shared/tauri";
+
+/**
+ * 服务器证书详情（与 Rust `CertificateInfo` 对齐）。
+ */
+export type ServerCertificateInfo = {
+  subject: string;
+  issuer: string;
+  serialNumber: string;
+  /** 生效时间（毫秒时间戳）。 */
+  notBefore: number;
+  /** 过期时间（毫秒时间戳）。 */
+  notAfter: number;
+  validNow: boolean;
+  selfSigned: boolean;
+  /** 形如 `DNS:chat.example.com`、`IP:10.0.0.1`。 */
+  subjectAltNames: string[];
+  /** SHA-256 指纹（小写 hex），可直接作为 `tlsFingerprint` 固定。 */
+  sha256Fingerprint: string;
+};
+
+/**
+ * 探测服务器证书。
+ *
+ * @param serverSocket - TLS 地址（`tls://`、`tls-insecure://` 或 `tls-fp://`）。
+ * @returns 证书详情。
+ */
+export function getServerCertificateInfo(serverSocket: string): Promise<ServerCertificateInfo> {
+  return invokeTauri<ServerCertificateInfo>(TAURI_COMMANDS.getServerCertificateInfo, { serverSocket });
+}
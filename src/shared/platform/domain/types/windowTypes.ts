@@ -31,14 +31,14 @@ export type OpenPopoverWindowArgs = {
  *
  * 信息窗口是一种独立的顶层窗口，用于展示较长内容（如关于页面、帮助文档）。
  *
- * @param label - 窗口标签（用于窗口管理器标识）
+ * @param label - 窗口标签：`channel-info` / `user-profile`，空串使用默认的 `info-window`；其它值会被拒绝
  * @param title - 窗口标题（显示在标题栏）
  * @param query - 窗口内容查询标识符（用于路由定位内容）
  * @param width - 窗口宽度
  * @param height - 窗口高度
  */
 export type OpenInfoWindowArgs = {
-  label: string;
+  label: "channel-info" | "user-profile" | "info-window" | "";
   title: string;
   query: string;
   width: number;
@@ -9,8 +9,37 @@
 export const TAURI_COMMANDS = {
   addTcpService: "add_tcp_service",
   removeTcpService: "remove_tcp_service",
+  subscribeConnection: "subscribe_connection",
+  unsubscribeConnection: "unsubscribe_connection",
+  connectionSubscriptionStatus: "connection_subscription_status",
+  replayRecentFrames: "replay_recent_frames",
+  getServerCertificateInfo: "get_server_certificate_info",
+  probeServer: "probe_server",
+  getServerProbes: "get_server_probes",
+  getServerCapabilities: "get_server_capabilities",
+  setActiveChannel: "set_active_channel",
+  enqueueChannelPrefetch: "enqueue_channel_prefetch",
+  completeChannelPrefetch: "complete_channel_prefetch",
+  clearChannelPrefetch: "clear_channel_prefetch",
+  setPresenceWatch: "set_presence_watch",
+  resubscribePresence: "resubscribe_presence",
+  handlePresenceUpdate: "handle_presence_update",
+  getPresence: "get_presence",
+  notifyTyping: "notify_typing",
+  resetTyping: "reset_typing",
+  handleTypingEvent: "handle_typing_event",
+  listTypingUsers: "list_typing_users",
   sendTcpService: "send_tcp_service",
+  tcpConnectionStatus: "tcp_connection_status",
+  tcpEncodePayload: "tcp_encode_payload",
+  tcpDecodePayload: "tcp_decode_payload",
+  startFrameCapture: "start_frame_capture",
+  stopFrameCapture: "stop_frame_capture",
+  exportFrameCapture: "export_frame_capture",
   apiRequestJson: "api_request_json",
+  setSessionTokens: "set_session_tokens",
+  refreshSessionTokens: "refresh_session_tokens",
+  loginOauth: "login_oauth",
   dbInit: "db_init",
   dbExecute: "db_execute",
   dbQuery: "db_query",
@@ -18,6 +47,87 @@ export const TAURI_COMMANDS = {
   dbClose: "db_close",
   dbRemove: "db_remove",
   dbPath: "db_path",
+  dbExplain: "db_explain",
+  dbRunNamed: "db_run_named",
+  dbQuickSwitch: "quick_switch",
+
+  // blocked users
+  blockUser: "block_user",
+  unblockUser: "unblock_user",
+  listBlockedUsers: "list_blocked_users",
+  recordUsers: "record_users",
+  syncChannelDelta: "sync_channel_delta",
+  getSyncState: "get_sync_state",
+  sendMessageOptimistic: "send_message_optimistic",
+  retryOptimisticMessage: "retry_optimistic_message",
+  discardOptimisticMessage: "discard_optimistic_message",
+  setChannelRetention: "set_channel_retention",
+  getChannelRetention: "get_channel_retention",
+  previewPrune: "preview_prune",
+  archiveChannel: "archive_channel",
+  restoreArchive: "restore_archive",
+  addFavorite: "add_favorite",
+  removeFavorite: "remove_favorite",
+  listFavoritesWithState: "list_favorites_with_state",
+  setChannelReadState: "set_channel_read_state",
+  reportRecentChannel: "report_recent_channel",
+  refreshJumpList: "refresh_jump_list",
+  backupNow: "backup_now",
+  listBackups: "list_backups",
+  restoreBackup: "restore_backup",
+  getBackupRemote: "get_backup_remote",
+  setBackupRemote: "set_backup_remote",
+  uploadBackup: "upload_backup",
+  listRemoteBackups: "list_remote_backups",
+  restoreRemoteBackup: "restore_remote_backup",
+
+  // do-not-disturb
+  setDnd: "set_dnd",
+  getDndStatus: "get_dnd_status",
+  getDndSchedule: "get_dnd_schedule",
+  setDndSchedule: "set_dnd_schedule",
+
+  // app lock / idle
+  reportUserActivity: "report_user_activity",
+  getAppLockStatus: "get_app_lock_status",
+  setAppLockPassphrase: "set_app_lock_passphrase",
+  lockApp: "lock_app",
+  unlockApp: "unlock_app",
+
+  // channel key store
+  storeChannelKey: "store_channel_key",
+  listChannelKeyIds: "list_channel_key_ids",
+  decryptChannelMessage: "decrypt_channel_message",
+
+  // device verification
+  getDeviceIdentity: "get_device_identity",
+  exportDeviceVerification: "export_device_verification",
+  verifyDevice: "verify_device",
+  listVerifiedDevices: "list_verified_devices",
+  revokeDeviceVerification: "revoke_device_verification",
+
+  // call signaling
+  handleCallSignal: "handle_call_signal",
+  listActiveCalls: "list_active_calls",
+  listCallHistory: "list_call_history",
+
+  // secure wipe
+  requestSecureWipeToken: "request_secure_wipe_token",
+  secureWipe: "secure_wipe",
+
+  // notification history
+  recordNotification: "record_notification",
+  getNotificationHistory: "get_notification_history",
+  markNotificationRead: "mark_notification_read",
+
+  // keyword mute rules
+  addMuteRule: "add_mute_rule",
+  removeMuteRule: "remove_mute_rule",
+  listMuteRules: "list_mute_rules",
+  testMuteRule: "test_mute_rule",
+
+  // audit log
+  getAuditLog: "get_audit_log",
 
   chatCacheLoadAll: "chat_cache_load_all",
   chatCacheGet: "chat_cache_get",
@@ -27,22 +137,47 @@ export const TAURI_COMMANDS = {
   chatCacheClearAll: "chat_cache_clear_all",
 
   toChatWindowSize: "to_chat_window_size",
+  getWindowCapabilities: "get_window_capabilities",
+  computePopoverRect: "compute_popover_rect",
+  openContextMenu: "open_context_menu",
   openPopoverWindow: "open_popover_window",
+  openUserPopoverWithData: "open_user_popover_with_data",
   openInfoWindow: "open_info_window",
+  openAuthWindow: "open_auth_window",
+  windowMinimize: "window_minimize",
+  windowToggleMaximize: "window_toggle_maximize",
+  windowCloseOrTray: "window_close_or_tray",
+  windowStartDrag: "window_start_drag",
+  openMiniChat: "open_mini_chat",
+  miniChatMarkRead: "mini_chat_mark_read",
 
   logInfo: "log_info",
   logError: "log_error",
   logWarning: "log_warning",
   logDebug: "log_debug",
 
-  loadPlugin: "load_plugin",
-  listPlugins: "list_plugins",
+  // 任务栏进度（前端驱动的长耗时操作）
+  setTaskbarProgress: "set_taskbar_progress",
 
   // 插件：zip 包产物 + 本地生命周期管理
   pluginsListInstalled: "plugins_list_installed",
   pluginsGetInstalledState: "plugins_get_installed_state",
   pluginsGetRuntimeEntry: "plugins_get_runtime_entry",
   pluginsGetRuntimeEntryForVersion: "plugins_get_runtime_entry_for_version",
+  pluginsBrowseCatalog: "plugins_browse_catalog",
+  pluginsGetAssets: "plugins_get_assets",
+  pluginsGetLocale: "plugins_get_locale",
+  pluginsGetSettingsSchema: "plugins_get_settings_schema",
+  pluginsGetSettings: "plugins_get_settings",
+  pluginsUpdateSettings: "plugins_update_settings",
+  pluginsBusOpen: "plugins_bus_open",
+  pluginsBusPublish: "plugins_bus_publish",
+  pluginsBusSubscribe: "plugins_bus_subscribe",
+  pluginsBusUnsubscribe: "plugins_bus_unsubscribe",
+  pluginsCheckCompatibility: "plugins_check_compatibility",
+  pluginsCheckRequirements: "plugins_check_requirements",
+  pluginsInstallRequirements: "plugins_install_requirements",
+  pluginsResolveDomain: "plugins_resolve_domain",
   pluginsInstallFromServerCatalog: "plugins_install_from_server_catalog",
   pluginsInstallFromUrl: "plugins_install_from_url",
   pluginsEnable: "plugins_enable",
@@ -50,7 +185,16 @@ export const TAURI_COMMANDS = {
   pluginsSwitchVersion: "plugins_switch_version",
   pluginsUninstall: "plugins_uninstall",
   pluginsSetFailed: "plugins_set_failed",
+  pluginsReportCrash: "plugins_report_crash",
   pluginsClearError: "plugins_clear_error",
+  pluginsMarkLoaded: "plugins_mark_loaded",
+  pluginsRollback: "plugins_rollback",
+  pluginsPruneVersions: "plugins_prune_versions",
+  pluginsDevLink: "plugins_dev_link",
+  pluginsDevUnlink: "plugins_dev_unlink",
+  pluginsValidatePackage: "plugins_validate_package",
+  pluginsHttpServerStart: "plugins_http_server_start",
+  pluginsHttpServerStop: "plugins_http_server_stop",
 
   // 插件宿主 API（按权限 gated）
   pluginsStorageGet: "plugins_storage_get",
@@ -67,6 +211,9 @@ export const TAURI_COMMANDS = {
   settingsExportSettings: "export_settings",
   settingsImportSettings: "import_settings",
   settingsResetSettings: "reset_settings",
+  settingsExportProfile: "export_profile",
+  settingsImportProfile: "import_profile",
+  settingsReloadConfig: "reload_config",
 
   setTrayUnreadFlashing: "set_tray_unread_flashing",
   setTrayLocale: "set_tray_locale",
@@ -93,12 +240,30 @@ export const TAURI_COMMANDS = {
   copyEmoji: "copy_emoji",
   writeTempEmojiFile: "write_temp_emoji_file",
   getEmojiImagePath: "get_emoji_image_path",
+  searchEmoji: "search_emoji",
+  recordEmojiUsage: "record_emoji_usage",
+  getFrequentlyUsedEmoji: "get_frequently_used_emoji",
+  syncServerEmojis: "sync_server_emojis",
+  listServerEmojis: "list_server_emojis",
+
+  // message rendering
+  renderMessageContent: "render_message_content",
+  renderMessageContents: "render_message_contents",
+
+  // assistant（需以 `assistant` feature 编译）
+  getAssistantStatus: "get_assistant_status",
+  assistantSummarizeChannel: "assistant_summarize_channel",
+  assistantDraftReply: "assistant_draft_reply",
 
   // screenshot
   startScreenshot: "start_screenshot",
   getScreenshotData: "get_screenshot_data",
   finishScreenshot: "finish_screenshot",
   cancelScreenshot: "cancel_screenshot",
+  listCaptureSources: "list_capture_sources",
+  startCapture: "start_capture",
+  respondCapturePermission: "respond_capture_permission",
+  stopCapture: "stop_capture",
 
   // voice call
   connectSignaling: "connect_signaling",
@@ -122,10 +287,24 @@ export const TAURI_COMMANDS = {
   writeAppLog: "write_app_log",
   readAppLogLines: "read_app_log_lines",
 
+  // diagnostics
+  getStartupTrace: "get_startup_trace",
+  wasLaunchedHidden: "was_launched_hidden",
+  takePendingDeepLink: "take_pending_deep_link",
+
+  // onboarding
+  getOnboardingState: "get_onboarding_state",
+  completeOnboardingStep: "complete_onboarding_step",
+
+  // self check
+  runSelfCheck: "run_self_check",
+  repairSelfCheckIssues: "repair",
+
   // temp_file
   cleanupTempFiles: "cleanup_temp_files",
   removeTempFile: "remove_temp_file",
   saveTempFile: "save_temp_file",
+  readAttachmentRange: "read_attachment_range",
   openTempFile: "open_temp_file",
 } as const;
 
@@ -1,7 +1,11 @@
 /**
  * @fileoverview Tauri 事件名与监听工具。
  */
+import { Channel } from "@tauri-apps/api/core";
 import { emit, listen, type Event, type UnlistenFn } from "@tauri-apps/api/event";
+import { getCurrentWebviewWindow } from "@tauri-apps/api/webviewWindow";
+import { TAURI_COMMANDS } from "./commands";
+import { invokeTauri } from "./invokeClient";
 import { isTauriRuntimeAvailable } from "./runtime";
 
 /**
@@ -11,9 +15,38 @@ import { isTauriRuntimeAvailable } from "./runtime";
 export const TAURI_EVENTS = {
   tcpMessage: "tcp-message",
   tcpFrame: "tcp-frame",
+  tcpFrameBatch: "tcp-frame-batch",
+  floodDetected: "flood-detected",
+  connectionFrameGap: "connection-frame-gap",
+  channelPrefetchReady: "channel-prefetch-ready",
+  typingStateChanged: "typing-state-changed",
+  presenceSubscriptionBatch: "presence-subscription-batch",
+  presenceChanged: "presence-changed",
+  serverEmojisChanged: "server-emojis-changed",
+  messageStatusChanged: "message-status-changed",
   tcpState: "tcp-state",
+  pluginsDomainProviderChanged: "plugins-domain-provider-changed",
+  pluginsSettingsChanged: "plugins-settings-changed",
+  pluginsBusMessage: "plugins-bus-message",
+  pluginAutoDisabled: "plugin-auto-disabled",
+  pluginRuntimeReady: "plugin-runtime-ready",
+  pluginsRequirementsProgress: "plugins-requirements-progress",
+  pluginDevReload: "plugin-dev-reload",
+  windowMaximizedChanged: "window-maximized-changed",
+  miniChatTargetChanged: "mini-chat-target-changed",
+  miniChatReadState: "mini-chat-read-state",
+  capturePermissionRequested: "capture-permission-requested",
+  dndChanged: "dnd-changed",
+  appLockChanged: "app-lock-changed",
+  channelKeyMissing: "channel-key-missing",
+  deviceVerificationChanged: "device-verification-changed",
+  callEvent: "call-event",
+  assistantStream: "assistant-stream",
   userProfileRequest: "user-profile-request",
   userProfileResponse: "user-profile-response",
+  backupTransferProgress: "backup-transfer-progress",
+  sessionRefreshed: "session-refreshed",
+  sessionExpired: "session-expired",
 } as const;
 
 /**
@@ -25,6 +58,144 @@ export const TAURI_EVENTS = {
  */
 export type TcpMessageEvent = { server_socket: string; payload: number[] };
 
+/**
+ * 洪泛期间批量投递的帧（Rust -> 前端）。
+ *
+ * 说明：`payloads` 按接收顺序排列，应逐条按 `tcp-frame` 的方式处理。
+ */
+export type TcpFrameBatchEvent = { server_socket: string; payloads: number[][] };
+
+/**
+ * 按连接订阅的 Channel 事件（Rust -> 订阅窗口）。
+ *
+ * 说明：
+ * - 与 `tcp-frame` / `tcp-frame-batch` / `tcp-message` 一一对应；订阅后本窗口不再收到该连接的这些全局事件；
+ * - 帧事件带 server 内自增序号（批量帧从 `firstSeq` 起连续），可作为 `replay_recent_frames` 的 `sinceSeq`。
+ */
+export type ConnectionChannelEvent =
+  | { kind: "frame"; seq: number; payload: number[] }
+  | { kind: "frameBatch"; firstSeq: number; payloads: number[][] }
+  | { kind: "message"; payload: number[] };
+
+/**
+ * `replay_recent_frames` 的结果（Rust -> 前端）。
+ *
+ * 说明：`complete` 为 false 表示缓冲已淘汰部分请求范围内的帧，调用方应回退到 HTTP 补拉。
+ */
+export type FrameReplay = {
+  frames: { seq: number; payload: number[] }[];
+  latestSeq: number;
+  complete: boolean;
+};
+
+/**
+ * 订阅窗口缺帧事件（Rust -> 受影响窗口）。
+ *
+ * 说明：
+ * - `expectedSeq` 至 `receivedSeq`（不含）之间的帧未投递到本窗口；
+ * - `resyncChannelId` 为已安排定向补齐的聚焦频道（以 `channel-prefetch-ready` 票据下发）；
+ *   为 null 时 Rust 侧未安排补齐，由窗口自行补拉当前频道。
+ */
+export type ConnectionFrameGapEvent = {
+  serverSocket: string;
+  subscriptionId: number;
+  expectedSeq: number;
+  receivedSeq: number;
+  resyncChannelId: string | null;
+};
+
+/**
+ * 入站洪泛事件载荷（Rust -> 前端）。
+ *
+ * 说明：
+ * - 每次洪泛在开始（`active=true`）与结束（`active=false`）时各发送一次；
+ * - `frames` / `duration_ms` 仅在结束事件中有意义。
+ */
+export type FloodDetectedEvent = {
+  server_socket: string;
+  active: boolean;
+  limit: number;
+  peak_rate: number;
+  frames: number;
+  duration_ms: number;
+};
+
+/**
+ * 频道预取票据（Rust -> 前端）。
+ *
+ * 说明：
+ * - 收到后拉取该频道历史，完成或失败后必须调用 `complete_channel_prefetch(ticket)` 归还名额；
+ * - `priority`：focused（当前聚焦）/ warm（有未读）/ cold（空闲时预取）。
+ */
+export type ChannelPrefetchReadyEvent = {
+  ticket: number;
+  server_socket: string;
+  channel_id: string;
+  priority: "focused" | "warm" | "cold";
+};
+
+/**
+ * 某频道正在输入的用户（Rust -> 前端，集合变化时广播）。
+ *
+ * 说明：`userIds` 为空表示该频道已无人输入。
+ */
+export type TypingStateChangedEvent = {
+  serverSocket: string;
+  channelId: string;
+  userIds: string[];
+};
+
+/**
+ * 一个用户的在线状态。
+ */
+export type PresenceEntry = {
+  userId: string;
+  status: string;
+  lastSeen?: number | null;
+};
+
+/**
+ * 待发送的在线状态订阅/退订帧（Rust -> 主窗口）。
+ *
+ * 说明：前端按 `{ route, data: { uids: userIds } }` 加密发送，不做额外处理。
+ */
+export type PresenceSubscriptionBatchEvent = {
+  serverSocket: string;
+  route: string;
+  userIds: string[];
+};
+
+/**
+ * 在线状态变化（Rust -> 前端，同一次推送中的变化合并为一个事件）。
+ */
+export type PresenceChangedEvent = {
+  serverSocket: string;
+  presences: PresenceEntry[];
+};
+
+/**
+ * 服务器自定义表情同步后发生变化（Rust -> 前端，收到后重新调用 `list_server_emojis`）。
+ */
+export type ServerEmojisChangedEvent = {
+  serverSocket: string;
+};
+
+/**
+ * 乐观发送状态变化（Rust -> 前端）。
+ *
+ * 说明：
+ * - `clientId` 对应 `send_message_optimistic` 返回的临时消息；
+ * - `messageId` 仅在 `sent` 时存在，为服务端分配的 mid。
+ */
+export type MessageStatusChangedEvent = {
+  dbKey: string;
+  channelId: number;
+  clientId: string;
+  messageId: string | null;
+  status: "pending" | "sent" | "failed";
+  error: string | null;
+};
+
 /**
  * TCP 连接生命周期事件载荷（Rust -> 前端）。
  *
@@ -43,6 +214,254 @@ export type TcpStateEvent = {
   error?: string;
 };
 
+/**
+ * domain 提供方（插件）信息。
+ */
+export type PluginDomainProvider = {
+  pluginId: string;
+  pluginVersion: string;
+  domainVersion: string;
+};
+
+/**
+ * domain 提供方变化事件载荷（Rust -> 前端）。
+ *
+ * 说明：
+ * - 插件启用/禁用/切换版本/卸载后由 Rust 侧重新计算并逐 domain 发出；
+ * - `conflict` 表示当前有多个启用插件同时提供该 domain。
+ */
+export type PluginDomainProviderChangedEvent = {
+  serverSocket: string;
+  domain: string;
+  previous: PluginDomainProvider | null;
+  current: PluginDomainProvider | null;
+  conflict: boolean;
+};
+
+/**
+ * 插件设置变化事件载荷（Rust -> 前端）。
+ *
+ * 说明：`plugins_update_settings` 写入成功且有生效值变化时发出；`values` 为变化后的全部生效值。
+ */
+export type PluginSettingsChangedEvent = {
+  serverSocket: string;
+  pluginId: string;
+  changed: string[];
+  values: Record<string, unknown>;
+};
+
+/**
+ * 插件总线消息事件载荷（Rust -> 前端）。
+ *
+ * 说明：Rust 侧已校验发布权限并计算收件人；运行时只把消息交给 `recipients` 中的插件。
+ */
+export type PluginBusMessageEvent = {
+  serverSocket: string;
+  topic: string;
+  from: string;
+  recipients: string[];
+  payload: unknown;
+};
+
+/**
+ * 插件自动停用事件载荷（Rust -> 前端）。
+ *
+ * 说明：插件在统计窗口内反复崩溃后被置为 disabled + failed；`lastError` 与写入 `state.json` 的一致。
+ */
+export type PluginAutoDisabledEvent = {
+  serverId: string;
+  pluginId: string;
+  version: string;
+  crashes: number;
+  lastError: string;
+};
+
+/**
+ * 启动预加载完成事件载荷（Rust -> 前端，每个有启用插件的服务端一条）。
+ *
+ * 说明：
+ * - `ready` 为校验通过的运行时入口（字段与 `plugins_get_runtime_entry` 一致）；
+ * - `failed` 中 `disabled = true` 表示插件已因校验失败被停用。
+ */
+export type PluginRuntimeReadyEvent = {
+  serverId: string;
+  ready: Array<{
+    serverId: string;
+    pluginId: string;
+    version: string;
+    entry: string;
+    minHostVersion: string;
+    permissions: string[];
+    providesDomains: Array<{ domain: string; domainVersion: string }>;
+    locales: string[];
+  }>;
+  failed: Array<{ pluginId: string; version: string; error: string; disabled: boolean }>;
+};
+
+/**
+ * 必装插件安装进度事件载荷（Rust -> 前端，`plugins_install_requirements` 期间逐个插件发出）。
+ *
+ * 说明：`index` 从 1 开始；每个插件以 `done` 或 `failed` 结束，`failed` 时携带 `error`。
+ */
+export type PluginRequirementsProgressEvent = {
+  serverSocket: string;
+  pluginId: string;
+  index: number;
+  total: number;
+  stage: "installing" | "enabling" | "done" | "failed";
+  error: string | null;
+};
+
+/**
+ * 开发态插件热重载事件载荷（Rust -> 前端）。
+ *
+ * 说明：源目录变化经 300ms 防抖合并后发出；`paths` 为相对源目录的文件路径。
+ */
+export type PluginDevReloadEvent = {
+  serverId: string;
+  pluginId: string;
+  paths: string[];
+};
+
+/**
+ * 窗口最大化状态变化事件载荷（Rust -> 前端，自绘标题栏切换按钮图标）。
+ */
+export type WindowMaximizedChangedEvent = {
+  label: string;
+  maximized: boolean;
+};
+
+/**
+ * 迷你聊天窗切换会话事件载荷（Rust -> 迷你窗）。
+ */
+export type MiniChatTargetChangedEvent = {
+  serverSocket: string;
+  channelId: string;
+};
+
+/**
+ * 已读状态同步事件载荷（Rust -> 所有窗口）。
+ *
+ * 说明：`source` 为上报窗口的 label，接收方应忽略自己发出的同步。
+ */
+export type MiniChatReadStateEvent = {
+  serverSocket: string;
+  channelId: string;
+  lastReadMessageId: string;
+  source: string;
+};
+
+/**
+ * 屏幕采集授权请求事件载荷（Rust -> 前端）。
+ *
+ * 说明：前端弹出确认框后以 `requestId` 调用 `respond_capture_permission`；60 秒未回复视为拒绝。
+ */
+export type CapturePermissionRequestedEvent = {
+  requestId: string;
+  sourceId: string;
+  sourceName: string;
+};
+
+/**
+ * 免打扰状态变化事件载荷（Rust -> 前端）。
+ *
+ * 说明：`source` 为生效来源（手动覆盖 / 全局开关 / 定时规则）；`untilMs` 为当前状态预计结束时间。
+ */
+export type DndChangedEvent = {
+  active: boolean;
+  source: "none" | "manual" | "global" | "schedule";
+  untilMs: number | null;
+};
+
+/**
+ * 应用锁状态变化事件载荷（Rust -> 前端）。
+ */
+export type AppLockChangedEvent = { locked: boolean };
+
+/**
+ * 消息引用的频道密钥本地不存在（Rust -> 前端），UI 应提示重新同步该频道密钥。
+ */
+export type ChannelKeyMissingEvent = { serverSocket: string; channelId: number; keyId: string };
+
+/**
+ * 设备验证状态变化事件载荷（Rust -> 前端）。
+ */
+export type DeviceVerificationChangedEvent = { deviceId: string; verified: boolean };
+
+/**
+ * 呼叫记录（Rust 侧 `CallRecord`）。
+ */
+export type CallRecord = {
+  serverSocket: string;
+  callId: string;
+  peerId: string;
+  direction: "inbound" | "outbound";
+  media: "audio" | "video";
+  state: "ringing" | "active" | "rejected" | "missed" | "ended";
+  invitedAt: number;
+  answeredAt: number | null;
+  endedAt: number | null;
+  endReason: string | null;
+};
+
+/**
+ * 呼叫信令事件载荷（Rust -> 前端 / WebRTC 层）。
+ */
+export type CallEvent = {
+  kind: "invite" | "accept" | "reject" | "ice_candidate" | "hangup";
+  call: CallRecord;
+  /**
+   * 仅 `ice_candidate` 携带：ICE 候选（`RTCIceCandidateInit` 形状）。
+   */
+  candidate: { candidate: string; sdpMid: string | null; sdpMLineIndex: number | null } | null;
+  at: number;
+};
+
+/**
+ * Rust 侧会话令牌（与 `set_session_tokens` / `refresh_session_tokens` 的参数一致）。
+ */
+export type SessionTokensPayload = {
+  accessToken: string;
+  refreshToken: string;
+  expiresAtMs?: number | null;
+  deviceId?: string | null;
+};
+
+/**
+ * 会话令牌已由 Rust 刷新事件载荷（`api_request_json` 遇到 401 后自动刷新）。
+ */
+export type SessionRefreshedEvent = { serverSocket: string; tokens: SessionTokensPayload };
+
+/**
+ * 会话过期事件载荷（refresh token 被服务端拒绝，需要重新登录）。
+ */
+export type SessionExpiredEvent = { serverSocket: string };
+
+/**
+ * 助手流式输出事件载荷（Rust -> 前端）。
+ *
+ * 说明：按 `requestId` 匹配 `assistant_summarize_channel` / `assistant_draft_reply` 的返回值；
+ * `done=true` 为最后一个事件，失败时附带 `error`。
+ */
+export type AssistantStreamEvent = {
+  requestId: string;
+  delta: string;
+  done: boolean;
+  error: string | null;
+};
+
+/**
+ * 远程备份传输进度事件载荷（Rust -> 前端）。
+ *
+ * 说明：按 `id`（备份 id）匹配 `upload_backup` / `restore_remote_backup`；服务器未返回大小时 `total` 为空。
+ */
+export type BackupTransferProgressEvent = {
+  id: string;
+  direction: "upload" | "download";
+  transferred: number;
+  total: number | null;
+};
+
 /**
  * user-profile 请求事件载荷（frontend -> frontend，经由 Tauri event bus）。
  */
@@ -79,7 +498,7 @@ export type UserProfileResponse = { id: string; ok: boolean; message?: string };
 export function listenTcpMessage(
   handler: (event: Event<TcpMessageEvent>) => void,
 ): Promise<UnlistenFn> {
-  return safeListen<TcpMessageEvent>(TAURI_EVENTS.tcpMessage, handler);
+  return safeListenCurrentWindow<TcpMessageEvent>(TAURI_EVENTS.tcpMessage, handler);
 }
 
 /**
@@ -91,7 +510,160 @@ export function listenTcpMessage(
 export function listenTcpFrame(
   handler: (event: Event<TcpMessageEvent>) => void,
 ): Promise<UnlistenFn> {
-  return safeListen<TcpMessageEvent>(TAURI_EVENTS.tcpFrame, handler);
+  return safeListenCurrentWindow<TcpMessageEvent>(TAURI_EVENTS.tcpFrame, handler);
+}
+
+/**
+ * 监听洪泛期间批量投递的帧事件。
+ *
+ * @param handler - 事件处理函数。
+ * @returns 取消监听函数（UnlistenFn）。
+ */
+export function listenTcpFrameBatch(
+  handler: (event: Event<TcpFrameBatchEvent>) => void,
+): Promise<UnlistenFn> {
+  return safeListenCurrentWindow<TcpFrameBatchEvent>(TAURI_EVENTS.tcpFrameBatch, handler);
+}
+
+/**
+ * 订阅某连接的帧 Channel（订阅后本窗口不再收到该连接的全局帧事件）。
+ *
+ * @param serverSocket - 服务端 socket。
+ * @param handler - Channel 事件处理函数。
+ * @returns 取消订阅函数（本窗口恢复接收全局帧事件）。
+ */
+export async function subscribeConnection(
+  serverSocket: string,
+  handler: (event: ConnectionChannelEvent) => void,
+): Promise<() => void> {
+  if (!isTauriRuntimeAvailable()) {
+    return () => {};
+  }
+  const onEvent = new Channel<ConnectionChannelEvent>();
+  onEvent.onmessage = handler;
+  const subscriptionId = await invokeTauri<number>(TAURI_COMMANDS.subscribeConnection, { serverSocket, onEvent });
+  return () => {
+    void invokeTauri<boolean>(TAURI_COMMANDS.unsubscribeConnection, { subscriptionId }).catch(() => {});
+  };
+}
+
+/**
+ * 取回某连接序号大于 `sinceSeq` 的最近入站帧（用于补齐订阅前错过的帧）。
+ *
+ * @param serverSocket - 服务端 socket。
+ * @param sinceSeq - 已处理的最后一帧序号；传入极大值时只返回 `latestSeq`。
+ * @returns 重放结果。
+ */
+export function replayRecentFrames(serverSocket: string, sinceSeq: number): Promise<FrameReplay> {
+  return invokeTauri<FrameReplay>(TAURI_COMMANDS.replayRecentFrames, { serverSocket, sinceSeq });
+}
+
+/**
+ * 监听本窗口的连接缺帧事件。
+ *
+ * @param handler - 事件处理函数。
+ * @returns 取消监听函数（UnlistenFn）。
+ */
+export function listenConnectionFrameGap(
+  handler: (event: Event<ConnectionFrameGapEvent>) => void,
+): Promise<UnlistenFn> {
+  return safeListenCurrentWindow<ConnectionFrameGapEvent>(TAURI_EVENTS.connectionFrameGap, handler);
+}
+
+/**
+ * 监听入站洪泛开始/结束事件。
+ *
+ * @param handler - 事件处理函数。
+ * @returns 取消监听函数（UnlistenFn）。
+ */
+export function listenFloodDetected(
+  handler: (event: Event<FloodDetectedEvent>) => void,
+): Promise<UnlistenFn> {
+  return safeListen<FloodDetectedEvent>(TAURI_EVENTS.floodDetected, handler);
+}
+
+/**
+ * 监听频道预取票据事件。
+ *
+ * @param handler - 事件处理函数。
+ * @returns 取消监听函数（UnlistenFn）。
+ */
+export function listenChannelPrefetchReady(
+  handler: (event: Event<ChannelPrefetchReadyEvent>) => void,
+): Promise<UnlistenFn> {
+  return safeListen<ChannelPrefetchReadyEvent>(TAURI_EVENTS.channelPrefetchReady, handler);
+}
+
+/**
+ * 监听频道输入者变化事件。
+ *
+ * @param handler - 事件处理函数。
+ * @returns 取消监听函数（UnlistenFn）。
+ */
+export function listenTypingStateChanged(
+  handler: (event: Event<TypingStateChangedEvent>) => void,
+): Promise<UnlistenFn> {
+  return safeListen<TypingStateChangedEvent>(TAURI_EVENTS.typingStateChanged, handler);
+}
+
+/**
+ * 监听待发送的在线状态订阅批次（仅主窗口收到）。
+ *
+ * @param handler - 事件处理函数。
+ * @returns 取消监听函数（UnlistenFn）。
+ */
+export function listenPresenceSubscriptionBatch(
+  handler: (event: Event<PresenceSubscriptionBatchEvent>) => void,
+): Promise<UnlistenFn> {
+  return safeListenCurrentWindow<PresenceSubscriptionBatchEvent>(TAURI_EVENTS.presenceSubscriptionBatch, handler);
+}
+
+/**
+ * 监听在线状态变化事件。
+ *
+ * @param handler - 事件处理函数。
+ * @returns 取消监听函数（UnlistenFn）。
+ */
+export function listenPresenceChanged(
+  handler: (event: Event<PresenceChangedEvent>) => void,
+): Promise<UnlistenFn> {
+  return safeListen<PresenceChangedEvent>(TAURI_EVENTS.presenceChanged, handler);
+}
+
+/**
+ * 监听服务器自定义表情变化事件。
+ *
+ * @param handler - 事件处理函数。
+ * @returns 取消监听函数（UnlistenFn）。
+ */
+export function listenServerEmojisChanged(
+  handler: (event: Event<ServerEmojisChangedEvent>) => void,
+): Promise<UnlistenFn> {
+  return safeListen<ServerEmojisChangedEvent>(TAURI_EVENTS.serverEmojisChanged, handler);
+}
+
+/**
+ * 监听乐观发送状态变化事件。
+ *
+ * @param handler - 事件处理函数。
+ * @returns 取消监听函数（UnlistenFn）。
+ */
+export function listenMessageStatusChanged(
+  handler: (event: Event<MessageStatusChangedEvent>) => void,
+): Promise<UnlistenFn> {
+  return safeListen<MessageStatusChangedEvent>(TAURI_EVENTS.messageStatusChanged, handler);
+}
+
+/**
+ * 监听远程备份传输进度事件。
+ *
+ * @param handler - 事件处理函数。
+ * @returns 取消监听函数（UnlistenFn）。
+ */
+export function listenBackupTransferProgress(
+  handler: (event: Event<BackupTransferProgressEvent>) => void,
+): Promise<UnlistenFn> {
+  return safeListen<BackupTransferProgressEvent>(TAURI_EVENTS.backupTransferProgress, handler);
 }
 
 /**
@@ -106,6 +678,241 @@ export function listenTcpState(
   return safeListen<TcpStateEvent>(TAURI_EVENTS.tcpState, handler);
 }
 
+/**
+ * 监听插件 domain 提供方变化事件。
+ *
+ * @param handler - 事件处理函数。
+ * @returns 取消监听函数（UnlistenFn）。
+ */
+export function listenPluginDomainProviderChanged(
+  handler: (event: Event<PluginDomainProviderChangedEvent>) => void,
+): Promise<UnlistenFn> {
+  return safeListen<PluginDomainProviderChangedEvent>(
+    TAURI_EVENTS.pluginsDomainProviderChanged,
+    handler,
+  );
+}
+
+/**
+ * 监听插件设置变化事件。
+ *
+ * @param handler - 事件处理函数。
+ * @returns 取消监听函数（UnlistenFn）。
+ */
+export function listenPluginSettingsChanged(
+  handler: (event: Event<PluginSettingsChangedEvent>) => void,
+): Promise<UnlistenFn> {
+  return safeListen<PluginSettingsChangedEvent>(
+    TAURI_EVENTS.pluginsSettingsChanged,
+    handler,
+  );
+}
+
+/**
+ * 监听插件总线消息事件。
+ *
+ * @param handler - 事件处理函数。
+ * @returns 取消监听函数（UnlistenFn）。
+ */
+export function listenPluginBusMessage(
+  handler: (event: Event<PluginBusMessageEvent>) => void,
+): Promise<UnlistenFn> {
+  return safeListen<PluginBusMessageEvent>(TAURI_EVENTS.pluginsBusMessage, handler);
+}
+
+/**
+ * 监听插件自动停用事件。
+ *
+ * @param handler - 事件处理函数。
+ * @returns 取消监听函数（UnlistenFn）。
+ */
+export function listenPluginAutoDisabled(
+  handler: (event: Event<PluginAutoDisabledEvent>) => void,
+): Promise<UnlistenFn> {
+  return safeListen<PluginAutoDisabledEvent>(TAURI_EVENTS.pluginAutoDisabled, handler);
+}
+
+/**
+ * 监听启动预加载完成事件。
+ *
+ * @param handler - 事件处理函数。
+ * @returns 取消监听函数（UnlistenFn）。
+ */
+export function listenPluginRuntimeReady(
+  handler: (event: Event<PluginRuntimeReadyEvent>) => void,
+): Promise<UnlistenFn> {
+  return safeListen<PluginRuntimeReadyEvent>(TAURI_EVENTS.pluginRuntimeReady, handler);
+}
+
+/**
+ * 监听必装插件安装进度事件。
+ *
+ * @param handler - 事件处理函数。
+ * @returns 取消监听函数（UnlistenFn）。
+ */
+export function listenPluginRequirementsProgress(
+  handler: (event: Event<PluginRequirementsProgressEvent>) => void,
+): Promise<UnlistenFn> {
+  return safeListen<PluginRequirementsProgressEvent>(TAURI_EVENTS.pluginsRequirementsProgress, handler);
+}
+
+/**
+ * 监听开发态插件热重载事件。
+ *
+ * @param handler - 事件处理函数。
+ * @returns 取消监听函数（UnlistenFn）。
+ */
+export function listenPluginDevReload(
+  handler: (event: Event<PluginDevReloadEvent>) => void,
+): Promise<UnlistenFn> {
+  return safeListen<PluginDevReloadEvent>(TAURI_EVENTS.pluginDevReload, handler);
+}
+
+/**
+ * 监听窗口最大化状态变化事件。
+ *
+ * @param handler - 事件处理函数。
+ * @returns 取消监听函数（UnlistenFn）。
+ */
+export function listenWindowMaximizedChanged(
+  handler: (event: Event<WindowMaximizedChangedEvent>) => void,
+): Promise<UnlistenFn> {
+  return safeListen<WindowMaximizedChangedEvent>(TAURI_EVENTS.windowMaximizedChanged, handler);
+}
+
+/**
+ * 监听迷你聊天窗切换会话事件。
+ *
+ * @param handler - 事件处理函数。
+ * @returns 取消监听函数（UnlistenFn）。
+ */
+export function listenMiniChatTargetChanged(
+  handler: (event: Event<MiniChatTargetChangedEvent>) => void,
+): Promise<UnlistenFn> {
+  return safeListen<MiniChatTargetChangedEvent>(TAURI_EVENTS.miniChatTargetChanged, handler);
+}
+
+/**
+ * 监听已读状态同步事件（主窗口与迷你窗之间）。
+ *
+ * @param handler - 事件处理函数。
+ * @returns 取消监听函数（UnlistenFn）。
+ */
+export function listenMiniChatReadState(
+  handler: (event: Event<MiniChatReadStateEvent>) => void,
+): Promise<UnlistenFn> {
+  return safeListen<MiniChatReadStateEvent>(TAURI_EVENTS.miniChatReadState, handler);
+}
+
+/**
+ * 监听屏幕采集授权请求事件。
+ *
+ * @param handler - 事件处理函数。
+ * @returns 取消监听函数（UnlistenFn）。
+ */
+export function listenCapturePermissionRequested(
+  handler: (event: Event<CapturePermissionRequestedEvent>) => void,
+): Promise<UnlistenFn> {
+  return safeListen<CapturePermissionRequestedEvent>(
+    TAURI_EVENTS.capturePermissionRequested,
+    handler,
+  );
+}
+
+/**
+ * 监听免打扰状态变化事件。
+ *
+ * @param handler - 事件处理函数。
+ * @returns 取消监听函数（UnlistenFn）。
+ */
+export function listenDndChanged(
+  handler: (event: Event<DndChangedEvent>) => void,
+): Promise<UnlistenFn> {
+  return safeListen<DndChangedEvent>(TAURI_EVENTS.dndChanged, handler);
+}
+
+/**
+ * 监听应用锁状态变化事件。
+ *
+ * @param handler - 事件处理函数。
+ * @returns 取消监听函数（UnlistenFn）。
+ */
+export function listenAppLockChanged(
+  handler: (event: Event<AppLockChangedEvent>) => void,
+): Promise<UnlistenFn> {
+  return safeListen<AppLockChangedEvent>(TAURI_EVENTS.appLockChanged, handler);
+}
+
+/**
+ * 监听频道密钥缺失事件。
+ *
+ * @param handler - 事件处理函数。
+ * @returns 取消监听函数（UnlistenFn）。
+ */
+export function listenChannelKeyMissing(
+  handler: (event: Event<ChannelKeyMissingEvent>) => void,
+): Promise<UnlistenFn> {
+  return safeListen<ChannelKeyMissingEvent>(TAURI_EVENTS.channelKeyMissing, handler);
+}
+
+/**
+ * 监听设备验证状态变化事件。
+ *
+ * @param handler - 事件处理函数。
+ * @returns 取消监听函数（UnlistenFn）。
+ */
+export function listenDeviceVerificationChanged(
+  handler: (event: Event<DeviceVerificationChangedEvent>) => void,
+): Promise<UnlistenFn> {
+  return safeListen<DeviceVerificationChangedEvent>(TAURI_EVENTS.deviceVerificationChanged, handler);
+}
+
+/**
+ * 监听呼叫信令事件。
+ *
+ * @param handler - 事件处理函数。
+ * @returns 取消监听函数（UnlistenFn）。
+ */
+export function listenCallEvent(handler: (event: Event<CallEvent>) => void): Promise<UnlistenFn> {
+  return safeListen<CallEvent>(TAURI_EVENTS.callEvent, handler);
+}
+
+/**
+ * 监听 Rust 侧会话令牌刷新事件。
+ *
+ * @param handler - 事件处理函数。
+ * @returns 取消监听函数（UnlistenFn）。
+ */
+export function listenSessionRefreshed(
+  handler: (event: Event<SessionRefreshedEvent>) => void,
+): Promise<UnlistenFn> {
+  return safeListen<SessionRefreshedEvent>(TAURI_EVENTS.sessionRefreshed, handler);
+}
+
+/**
+ * 监听会话过期事件。
+ *
+ * @param handler - 事件处理函数。
+ * @returns 取消监听函数（UnlistenFn）。
+ */
+export function listenSessionExpired(
+  handler: (event: Event<SessionExpiredEvent>) => void,
+): Promise<UnlistenFn> {
+  return safeListen<SessionExpiredEvent>(TAURI_EVENTS.sessionExpired, handler);
+}
+
+/**
+ * 监听助手流式输出事件。
+ *
+ * @param handler - 事件处理函数。
+ * @returns 取消监听函数（UnlistenFn）。
+ */
+export function listenAssistantStream(
+  handler: (event: Event<AssistantStreamEvent>) => void,
+): Promise<UnlistenFn> {
+  return safeListen<AssistantStreamEvent>(TAURI_EVENTS.assistantStream, handler);
+}
+
 /**
  * 通用 Tauri 事件监听（带浏览器环境静默回退）。
  *
@@ -126,6 +933,26 @@ export function safeListen<T>(
   return listen<T>(event, handler);
 }
 
+/**
+ * 在当前窗口上注册 Tauri 事件监听（带浏览器环境静默回退）。
+ *
+ * 说明：Rust 侧按窗口 label 过滤已订阅连接 Channel 的窗口，只有注册在窗口上的监听会被过滤，
+ * 因此按连接投递的帧事件必须通过此函数监听，避免同一帧经 Channel 与全局事件重复到达。
+ *
+ * @param event - 事件名。
+ * @param handler - 事件处理函数。
+ * @returns 取消监听函数（UnlistenFn）的 Promise。
+ */
+export function safeListenCurrentWindow<T>(
+  event: string,
+  handler: (event: Event<T>) => void,
+): Promise<UnlistenFn> {
+  if (!isTauriRuntimeAvailable()) {
+    return Promise.resolve(() => {});
+  }
+  return getCurrentWebviewWindow().listen<T>(event, handler);
+}
+
 /**
  * 发出 user-profile 请求事件（frontend → frontend，通过 Tauri event bus）。
  *
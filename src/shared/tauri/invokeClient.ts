@@ -14,6 +14,45 @@ export type TauriInvokeArgs = Record<string, unknown> | undefined;
  */
 const TAURI_UNAVAILABLE_MESSAGE = "Tauri runtime unavailable";
 
+/**
+ * Rust 侧结构化命令错误（`CommandError` 的序列化形态）。
+ */
+export type TauriCommandErrorPayload = {
+  code: string;
+  message: string;
+  details: string | null;
+  retryable: boolean;
+  correlation_id?: string | null;
+  request_id?: string | null;
+};
+
+/**
+ * 命令错误（保留 `[CODE] message` 形式的 message，兼容按字符串处理错误的调用方）。
+ */
+export class TauriCommandError extends Error {
+  readonly code: string;
+  readonly details: string | null;
+  readonly retryable: boolean;
+  readonly correlationId: string | null;
+  readonly requestId: string | null;
+
+  constructor(payload: TauriCommandErrorPayload) {
+    super(`[${payload.code}] ${payload.message}`);
+    this.name = "TauriCommandError";
+    this.code = payload.code;
+    this.details = payload.details;
+    this.retryable = payload.retryable;
+    this.correlationId = payload.correlation_id ?? null;
+    this.requestId = payload.request_id ?? null;
+  }
+}
+
+function isCommandErrorPayload(value: unknown): value is TauriCommandErrorPayload {
+  if (!value || typeof value !== "object") return false;
+  const v = value as Record<string, unknown>;
+  return typeof v.code === "string" && typeof v.message === "string";
+}
+
 /**
  * 调用 Rust 侧命令（Tauri invoke）。
  *
@@ -31,7 +70,13 @@ export async function invokeTauri<T = unknown>(
   if (!isTauriRuntimeAvailable()) {
     throw new Error(TAURI_UNAVAILABLE_MESSAGE);
   }
-  return tauriInvoke<T>(command, args);
+  try {
+    return await tauriInvoke<T>(command, args);
+  } catch (e) {
+    // 结构化错误转为 Error；旧版字符串错误（兼容模式）原样抛出。
+    if (isCommandErrorPayload(e)) throw new TauriCommandError(e);
+    throw e;
+  }
 }
 
 /**
@@ -18,6 +18,10 @@ import {
   KEY_LATEST_MESSAGE_TIME_MS,
 } from "@/shared/utils/storageKeys";
 import { readString, writeString } from "@/shared/utils/localStore";
+import { getDeviceId } from "@/shared/utils/deviceId";
+import { invokeTauri } from "@/shared/tauri/invokeClient";
+import { TAURI_COMMANDS } from "@/shared/tauri/commands";
+import { isTauriRuntimeAvailable } from "@/shared/tauri/runtime";
 
 /**
  * 前端侧保存的认证会话结构（按 server scope 隔离）。
@@ -162,12 +166,37 @@ export function readAuthSession(serverSocket: string): AuthSession | null {
   }
 }
 
+/**
+ * 将会话令牌同步到 Rust（非 Tauri 环境为空操作）。
+ *
+ * @param serverSocket - 服务器 Socket 地址。
+ * @param session - 会话令牌；当为 `null` 时清除 Rust 侧令牌。
+ */
+function mirrorSessionTokensToRust(
+  serverSocket: string,
+  session: Pick<AuthSession, "accessToken" | "refreshToken" | "expiresAtMs"> | null,
+): void {
+  if (!isTauriRuntimeAvailable()) return;
+  const tokens = session
+    ? {
+        accessToken: session.accessToken,
+        refreshToken: session.refreshToken,
+        expiresAtMs: session.expiresAtMs ?? null,
+        deviceId: getDeviceId(),
+      }
+    : null;
+  void invokeTauri<void>(TAURI_COMMANDS.setSessionTokens, { serverSocket, tokens }).catch(() => {
+    // Best-effort：Rust 侧缺少令牌时仅无法自动刷新，下次写入会再次同步。
+  });
+}
+
 /**
  * 持久化指定 server 的会话信息。
  *
  * 实现说明：
  * - 刻意将数据存入 `localStorage`（桌面端 webview scope）。
  * - 同时镜像写入旧版 `authToken` key，以保持旧调用点可读（只读兼容）。
+ * - 同时同步到 Rust（系统凭据存储），供 `api_request_json` 遇到 401 时自动刷新。
  *
  * @param serverSocket - 服务器 Socket 地址（用于推导 scope key，并作为 key 后缀）。
  * @param session - 会话载荷；当为 `null` 时清空已存储会话。
@@ -181,6 +210,7 @@ export function writeAuthSession(serverSocket: string, session: AuthSession | nu
   if (!session) {
     removeSecureChatCacheValueSync(key);
     writeAuthToken(socket, "");
+    mirrorSessionTokensToRust(socket, null);
     return;
   }
 
@@ -190,6 +220,7 @@ export function writeAuthSession(serverSocket: string, session: AuthSession | nu
   const expiresAtMs = typeof session.expiresAtMs === "number" ? session.expiresAtMs : undefined;
   setSecureChatCacheValueSync(key, JSON.stringify({ accessToken, refreshToken, uid, expiresAtMs }));
   writeAuthToken(socket, accessToken);
+  if (refreshToken) mirrorSessionTokensToRust(socket, { accessToken, refreshToken, expiresAtMs });
 }
 
 /**
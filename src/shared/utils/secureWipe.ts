@@ -0,0 +1,24 @@
+/**
+ * @fileoverview 安全擦除（frontend → Rust `request_secure_wipe_token` / `secure_wipe` 命令）。
+ *
+ * 说明：清除本机全部数据（数据库、插件、日志、配置、凭据存储中的密钥）后应用直接退出，
+ * 调用前应由用户明确确认。
+ */
+
+import { invokeTauri } from "@/shared/tauri/invokeClient";
+import { TAURI_COMMANDS } from "@/shared/tauri/commands";
+
+/**
+ * 执行安全擦除；成功时应用退出，返回的 Promise 通常不会 resolve。
+ */
+export async function secureWipe(): Promise<void> {
+  const confirmToken = await invokeTauri<string>(TAURI_COMMANDS.requestSecureWipeToken);
+  // WebView 存储由前端先行清空，避免退出前被重新写回。
+  try {
+    localStorage.clear();
+    sessionStorage.clear();
+  } catch {
+    // 存储不可用时忽略，数据目录仍会被后端清除。
+  }
+  await invokeTauri<void>(TAURI_COMMANDS.secureWipe, { confirmToken });
+}